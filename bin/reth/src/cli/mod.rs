@@ -2,7 +2,7 @@
 
 use crate::{
     args::LogArgs,
-    commands::debug_cmd,
+    commands::{debug_cmd, export},
     version::{LONG_VERSION, SHORT_VERSION},
 };
 use clap::{value_parser, Parser, Subcommand};
@@ -10,12 +10,13 @@ use reth_chainspec::ChainSpec;
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
     config_cmd, db, dump_genesis, import, init_cmd, init_state,
-    node::{self, NoArgs},
-    p2p, prune, recover, stage,
+    node::{self, AdditionalChainLauncher, NoArgs},
+    p2p, prune, recover, stage, trie,
 };
-use reth_cli_runner::CliRunner;
+use reth_cli_runner::{CliContext, CliRunner};
 use reth_db::DatabaseEnv;
 use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+use reth_ethereum_primitives::Receipt;
 use reth_network::EthNetworkPrimitives;
 use reth_node_builder::{NodeBuilder, WithLaunchContext};
 use reth_node_ethereum::{consensus::EthBeaconConsensus, EthExecutorProvider, EthereumNode};
@@ -144,6 +145,53 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>, Ext: clap::Args + fmt::Debug> Cl
         self.with_runner(CliRunner::try_default_runtime()?, launcher)
     }
 
+    /// Execute the configured cli command, and run the chains of `--chain.additional` next to the
+    /// node.
+    ///
+    /// The node is launched with the `launcher` closure, like with [`Cli::run`]. Every additional
+    /// chain is launched by the first of the `additional` launchers that supports it, so the
+    /// additional chains may have other chain spec and node types than the node.
+    ///
+    /// This command will be run on the [default tokio runtime](reth_cli_runner::tokio_runtime).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use reth::cli::Cli;
+    /// use reth_cli_commands::node::AdditionalChainLauncher;
+    /// use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+    /// use reth_node_ethereum::EthereumNode;
+    ///
+    /// let additional =
+    ///     AdditionalChainLauncher::new::<EthereumChainSpecParser, _, _>(|builder| async move {
+    ///         let handle = builder.launch_node(EthereumNode::default()).await?;
+    ///         handle.wait_for_node_exit().await
+    ///     });
+    ///
+    /// Cli::parse_args()
+    ///     .run_multi(
+    ///         async move |builder, _| {
+    ///             let handle = builder.launch_node(EthereumNode::default()).await?;
+    ///             handle.wait_for_node_exit().await
+    ///         },
+    ///         vec![additional],
+    ///     )
+    ///     .unwrap();
+    /// ```
+    pub fn run_multi<L, Fut>(
+        self,
+        launcher: L,
+        additional: Vec<AdditionalChainLauncher>,
+    ) -> eyre::Result<()>
+    where
+        L: FnOnce(WithLaunchContext<NodeBuilder<Arc<DatabaseEnv>, C::ChainSpec>>, Ext) -> Fut,
+        Fut: Future<Output = eyre::Result<()>>,
+    {
+        self.execute_with_runner(CliRunner::try_default_runtime()?, |command, ctx| {
+            command.execute_multi(ctx, launcher, additional)
+        })
+    }
+
     /// Execute the configured cli command with the provided [`CliRunner`].
     ///
     ///
@@ -169,10 +217,20 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>, Ext: clap::Args + fmt::Debug> Cl
     ///     })
     ///     .unwrap();
     /// ```
-    pub fn with_runner<L, Fut>(mut self, runner: CliRunner, launcher: L) -> eyre::Result<()>
+    pub fn with_runner<L, Fut>(self, runner: CliRunner, launcher: L) -> eyre::Result<()>
     where
         L: FnOnce(WithLaunchContext<NodeBuilder<Arc<DatabaseEnv>, C::ChainSpec>>, Ext) -> Fut,
         Fut: Future<Output = eyre::Result<()>>,
+    {
+        self.execute_with_runner(runner, |command, ctx| command.execute(ctx, launcher))
+    }
+
+    /// Execute the configured cli command with the provided [`CliRunner`], and execute the
+    /// [`NodeCommand`](node::NodeCommand) with the given closure.
+    fn execute_with_runner<F, Fut>(mut self, runner: CliRunner, execute_node: F) -> eyre::Result<()>
+    where
+        F: FnOnce(node::NodeCommand<C, Ext>, CliContext) -> Fut,
+        Fut: Future<Output = eyre::Result<()>>,
     {
         // add network name to logs dir
         self.logs.log_file_directory =
@@ -188,8 +246,11 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>, Ext: clap::Args + fmt::Debug> Cl
             (EthExecutorProvider::ethereum(spec.clone()), EthBeaconConsensus::new(spec))
         };
         match self.command {
+            Commands::Node(command) if command.is_rollback() => {
+                runner.run_blocking_until_ctrl_c(command.rollback::<EthereumNode>())
+            }
             Commands::Node(command) => {
-                runner.run_command_until_exit(|ctx| command.execute(ctx, launcher))
+                runner.run_command_until_exit(|ctx| execute_node(*command, ctx))
             }
             Commands::Init(command) => {
                 runner.run_blocking_until_ctrl_c(command.execute::<EthereumNode>())
@@ -205,7 +266,11 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>, Ext: clap::Args + fmt::Debug> Cl
                 runner.run_blocking_until_ctrl_c(command.execute::<EthereumNode>())
             }
             Commands::Stage(command) => runner.run_command_until_exit(|ctx| {
-                command.execute::<EthereumNode, _, _, EthNetworkPrimitives>(ctx, components)
+                command.execute::<EthereumNode, _, _, EthNetworkPrimitives, _>(
+                    ctx,
+                    components,
+                    |_, _, receipts| Receipt::calculate_receipt_root_no_memo(receipts),
+                )
             }),
             Commands::P2P(command) => {
                 runner.run_until_ctrl_c(command.execute::<EthNetworkPrimitives>())
@@ -220,6 +285,12 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>, Ext: clap::Args + fmt::Debug> Cl
                 runner.run_command_until_exit(|ctx| command.execute::<EthereumNode>(ctx))
             }
             Commands::Prune(command) => runner.run_until_ctrl_c(command.execute::<EthereumNode>()),
+            Commands::Trie(command) => {
+                runner.run_blocking_until_ctrl_c(command.execute::<EthereumNode>())
+            }
+            Commands::Export(command) => {
+                runner.run_blocking_until_ctrl_c(command.execute::<EthereumNode>())
+            }
         }
     }
 
@@ -276,6 +347,12 @@ pub enum Commands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
     /// Prune according to the configuration without any limits
     #[command(name = "prune")]
     Prune(prune::PruneCommand<C>),
+    /// Trie debugging utilities
+    #[command(name = "trie")]
+    Trie(trie::Command<C>),
+    /// Export stored chain data
+    #[command(name = "export")]
+    Export(export::Command<C>),
 }
 
 #[cfg(test)]
@@ -0,0 +1,123 @@
+//! Command for re-executing blocks on top of their historical state.
+
+use clap::Parser;
+use eyre::WrapErr;
+use reth_chainspec::ChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_commands::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use reth_cli_runner::CliContext;
+use reth_consensus::FullConsensus;
+use reth_ethereum_primitives::EthPrimitives;
+use reth_evm::{
+    execute::{BlockExecutorProvider, Executor},
+    parallel::ParallelExecutionStats,
+};
+use reth_node_ethereum::{consensus::EthBeaconConsensus, EthExecutorProvider};
+use reth_primitives_traits::BlockBody;
+use reth_provider::{
+    BlockNumReader, BlockReader, ChainSpecProvider, StateProviderFactory, TransactionVariant,
+};
+use reth_revm::database::StateProviderDatabase;
+use std::{sync::Arc, time::Instant};
+use tracing::*;
+
+/// `reth debug re-execute` command
+///
+/// Re-executes the blocks in the given range on top of the historical state before every block
+/// and validates the execution output against the stored blocks. This is intended for
+/// benchmarking the executors, e.g. the experimental parallel executor.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The first block to re-execute
+    #[arg(long, default_value = "1")]
+    from: u64,
+
+    /// The last block to re-execute, defaults to the last synced block
+    #[arg(long)]
+    to: Option<u64>,
+
+    /// Execute the transactions of every block optimistically in parallel
+    #[arg(long)]
+    parallel: bool,
+
+    /// Number of threads executing transactions in parallel, defaults to the number of CPUs
+    #[arg(long, requires = "parallel")]
+    threads: Option<usize>,
+}
+
+impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
+    /// Execute `debug re-execute` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec, Primitives = EthPrimitives>>(
+        self,
+        _ctx: CliContext,
+    ) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+
+        if self.from == 0 {
+            eyre::bail!("the genesis block can't be re-executed")
+        }
+
+        let provider = provider_factory.provider()?;
+        let to = match self.to {
+            Some(to) => to,
+            None => provider.best_block_number()?,
+        };
+
+        let chain_spec = provider_factory.chain_spec();
+        let consensus = EthBeaconConsensus::new(chain_spec.clone());
+        let sequential = EthExecutorProvider::ethereum(chain_spec.clone());
+        let parallel = if self.parallel {
+            let mut pool =
+                rayon::ThreadPoolBuilder::new().thread_name(|idx| format!("re-execute-{idx}"));
+            if let Some(threads) = self.threads {
+                pool = pool.num_threads(threads);
+            }
+            Some(EthExecutorProvider::parallel(chain_spec).with_pool(Arc::new(pool.build()?)))
+        } else {
+            None
+        };
+
+        let start = Instant::now();
+        let mut stats = ParallelExecutionStats::default();
+        for number in self.from..=to {
+            let block = provider
+                .recovered_block(number.into(), TransactionVariant::NoHash)?
+                .ok_or_else(|| eyre::eyre!("block {number} not found"))?;
+            let state = provider_factory.history_by_block_number(number - 1)?;
+            let db = StateProviderDatabase::new(&state);
+            stats.transactions += block.body().transactions().len() as u64;
+
+            let result = match &parallel {
+                Some(parallel) => {
+                    // every worker reads the state before the block from its own provider
+                    let mut executor = parallel.executor(db).with_snapshot(|| {
+                        let state = provider_factory.history_by_block_number(number - 1).ok()?;
+                        Some(StateProviderDatabase::new(state))
+                    });
+                    let result = executor.execute_one(&block)?;
+                    stats.reexecuted += executor.stats().reexecuted;
+                    result
+                }
+                None => sequential.executor(db).execute_one(&block)?,
+            };
+
+            consensus
+                .validate_block_post_execution(&block, &result)
+                .wrap_err_with(|| format!("invalid execution output of block {number}"))?;
+        }
+
+        info!(
+            target: "reth::cli",
+            blocks = to.saturating_sub(self.from) + 1,
+            transactions = stats.transactions,
+            reexecuted = stats.reexecuted,
+            elapsed = ?start.elapsed(),
+            "Re-executed blocks"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+//! `reth export` command. Exports stored chain data in formats for other tools.
+
+use clap::{Parser, Subcommand};
+use reth_chainspec::ChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_commands::common::CliNodeTypes;
+use reth_ethereum_primitives::EthPrimitives;
+
+mod prover_input;
+mod rpc_replay;
+
+/// `reth export` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(subcommand)]
+    command: Subcommands<C>,
+}
+
+/// `reth export` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands<C: ChainSpecParser> {
+    /// Export canonical blocks as engine API calls that can be replayed against any execution
+    /// client.
+    RpcReplay(rpc_replay::Command<C>),
+    /// Export the pre-state, ancestor headers and trie witness of a block as the input of a zkEVM
+    /// prover.
+    ProverInput(prover_input::Command<C>),
+}
+
+impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
+    /// Execute `export` command
+    pub async fn execute<N: CliNodeTypes<Primitives = EthPrimitives, ChainSpec = C::ChainSpec>>(
+        self,
+    ) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::RpcReplay(command) => command.execute::<N>().await,
+            Subcommands::ProverInput(command) => command.execute::<N>().await,
+        }
+    }
+}
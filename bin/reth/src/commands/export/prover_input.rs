@@ -0,0 +1,261 @@
+//! Command that exports the input of a zkEVM prover for a block.
+
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{keccak256, Address, BlockNumber, Bytes, B256, U256};
+use clap::Parser;
+use reth_chainspec::{ChainSpec, EthChainSpec};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_commands::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use reth_ethereum_primitives::EthPrimitives;
+use reth_evm::execute::{BlockExecutorProvider, Executor};
+use reth_node_ethereum::EthExecutorProvider;
+use reth_primitives_traits::Account;
+use reth_provider::{
+    AccountReader, BlockReader, ChainSpecProvider, HeaderProvider, ProviderError,
+    StateProofProvider, StateProvider, StateProviderFactory, TransactionVariant,
+};
+use reth_revm::{database::StateProviderDatabase, db::State, witness::ExecutionWitnessRecord};
+use serde_json::{json, Map, Value};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
+use tracing::info;
+
+/// The version of the prover input format, incremented on every incompatible change.
+const PROVER_INPUT_VERSION: u64 = 1;
+
+/// The state of an account before the block with its accessed storage slots, or `None` if the
+/// account didn't exist.
+type PreStateAccount = Option<(Account, BTreeMap<B256, U256>)>;
+
+/// `reth export prover-input` command
+///
+/// Executes the block on top of the state of its parent and writes everything a stateless
+/// zkEVM prover needs to re-execute it as a JSON object:
+///
+/// - `version`: the version of the format, currently `1`.
+/// - `chainId`: the chain id.
+/// - `block`: the RLP encoded block.
+/// - `ancestorHeaders`: the RLP encoded headers of the ancestors, in ascending order and ending
+///   with the parent. They start at the oldest block whose hash was read during execution.
+/// - `ancestorHashes`: the `number` and `hash` of the ancestors that were read during execution, in
+///   ascending order.
+/// - `preState`: the state of the accessed accounts before the block, by address. Accounts that
+///   didn't exist are `null`, and the storage only contains the accessed slots.
+/// - `codes`: the accessed and created bytecodes, by code hash.
+/// - `witness`: the trie nodes that prove the pre-state against the parent's state root and are
+///   required to compute the state root of the block, as returned by `debug_executionWitness`.
+///
+/// Maps are keyed by fixed-width hex strings in ascending order, and lists of trie nodes are
+/// ordered by value, so the output is stable for a block.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The number of the block to export.
+    #[arg(long, value_name = "NUMBER")]
+    block: BlockNumber,
+
+    /// The path of the prover input file.
+    #[arg(long, short, value_name = "PATH")]
+    output: PathBuf,
+}
+
+impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
+    /// Execute `export prover-input` command
+    pub async fn execute<N: CliNodeTypes<Primitives = EthPrimitives, ChainSpec = C::ChainSpec>>(
+        self,
+    ) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+        let chain_spec = provider_factory.chain_spec();
+        let number = self.block;
+        if number == 0 {
+            eyre::bail!("The genesis block isn't executed and has no prover input")
+        }
+
+        let block = provider_factory
+            .recovered_block(number.into(), TransactionVariant::WithHash)?
+            .ok_or(ProviderError::HeaderNotFound(number.into()))?;
+
+        // execute the block, recording the accessed state
+        let mut record = ExecutionWitnessRecord::default();
+        let mut accessed = BTreeMap::<Address, BTreeSet<U256>>::new();
+        let mut ancestor_hashes = BTreeMap::new();
+        let parent_state = provider_factory.history_by_block_number(number - 1)?;
+        EthExecutorProvider::ethereum(chain_spec.clone())
+            .executor(StateProviderDatabase::new(parent_state))
+            .execute_with_state_closure(&block, |statedb: &State<_>| {
+                record.record_executed_state(statedb);
+                for (address, account) in &statedb.cache.accounts {
+                    let slots = accessed.entry(*address).or_default();
+                    if let Some(account) = &account.account {
+                        slots.extend(account.storage.keys().copied());
+                    }
+                }
+                ancestor_hashes = statedb.block_hashes.clone();
+            })?;
+
+        // read the pre-state of the accessed accounts and slots
+        let state = provider_factory.history_by_block_number(number - 1)?;
+        let mut pre_state = BTreeMap::<Address, PreStateAccount>::new();
+        for (address, slots) in accessed {
+            let account = match state.basic_account(&address)? {
+                Some(account) => {
+                    let mut storage = BTreeMap::new();
+                    for slot in slots {
+                        let slot = B256::from(slot);
+                        storage.insert(slot, state.storage(address, slot)?.unwrap_or_default());
+                    }
+                    Some((account, storage))
+                }
+                None => None,
+            };
+            pre_state.insert(address, account);
+        }
+
+        let ExecutionWitnessRecord { hashed_state, codes, .. } = record;
+        let codes =
+            codes.into_iter().map(|code| (keccak256(&code), code)).collect::<BTreeMap<_, _>>();
+        let mut witness = state.witness(Default::default(), hashed_state)?;
+        witness.sort_unstable();
+        witness.dedup();
+
+        let first_ancestor = ancestor_hashes.keys().next().copied().unwrap_or(number - 1);
+        let ancestor_headers = provider_factory
+            .headers_range(first_ancestor..number)?
+            .iter()
+            .map(|header| Bytes::from(alloy_rlp::encode(header)))
+            .collect::<Vec<_>>();
+
+        let input = prover_input(
+            chain_spec.chain_id(),
+            Bytes::from(alloy_rlp::encode(block.clone_block())),
+            ancestor_headers,
+            ancestor_hashes,
+            pre_state,
+            codes,
+            witness,
+        );
+
+        let mut file = BufWriter::new(reth_fs_util::create_file(&self.output)?);
+        serde_json::to_writer(&mut file, &input)?;
+        file.flush()?;
+
+        info!(
+            target: "reth::cli",
+            number,
+            hash = %block.hash(),
+            gas_used = block.gas_used(),
+            path = %self.output.display(),
+            "Exported prover input"
+        );
+
+        Ok(())
+    }
+}
+
+/// Returns the prover input in the format documented on [`Command`].
+fn prover_input(
+    chain_id: u64,
+    block: Bytes,
+    ancestor_headers: Vec<Bytes>,
+    ancestor_hashes: BTreeMap<BlockNumber, B256>,
+    pre_state: BTreeMap<Address, PreStateAccount>,
+    codes: BTreeMap<B256, Bytes>,
+    witness: Vec<Bytes>,
+) -> Value {
+    let ancestor_hashes = ancestor_hashes
+        .into_iter()
+        .map(|(number, hash)| json!({ "number": number, "hash": hash }))
+        .collect::<Vec<_>>();
+    let pre_state = pre_state
+        .into_iter()
+        .map(|(address, account)| {
+            let account = account.map_or(Value::Null, |(account, storage)| {
+                json!({
+                    "nonce": format!("{:#x}", account.nonce),
+                    "balance": account.balance,
+                    "codeHash": account.get_bytecode_hash(),
+                    "storage": storage
+                        .into_iter()
+                        .map(|(slot, value)| (slot.to_string(), json!(value)))
+                        .collect::<Map<_, _>>(),
+                })
+            });
+            (address.to_string(), account)
+        })
+        .collect::<Map<_, _>>();
+    let codes = codes
+        .into_iter()
+        .map(|(hash, code)| (hash.to_string(), json!(code)))
+        .collect::<Map<_, _>>();
+
+    json!({
+        "version": PROVER_INPUT_VERSION,
+        "chainId": chain_id,
+        "block": block,
+        "ancestorHeaders": ancestor_headers,
+        "ancestorHashes": ancestor_hashes,
+        "preState": pre_state,
+        "codes": codes,
+        "witness": witness,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, bytes};
+
+    #[test]
+    fn prover_input_format() {
+        let code = bytes!("0x6000");
+        let code_hash = keccak256(&code);
+        let account = Account { nonce: 10, balance: U256::from(1), bytecode_hash: Some(code_hash) };
+        let contract = address!("0x1000000000000000000000000000000000000000");
+        let missing = address!("0x0000000000000000000000000000000000000001");
+        let slot = B256::with_last_byte(2);
+
+        let input = prover_input(
+            1,
+            bytes!("0x01"),
+            vec![bytes!("0x02"), bytes!("0x03")],
+            // numbers are ordered numerically, not by their decimal strings
+            BTreeMap::from([(10, B256::with_last_byte(10)), (9, B256::with_last_byte(9))]),
+            BTreeMap::from([
+                (contract, Some((account, BTreeMap::from([(slot, U256::from(3))])))),
+                (missing, None),
+            ]),
+            BTreeMap::from([(code_hash, code.clone())]),
+            vec![bytes!("0x04")],
+        );
+
+        assert_eq!(
+            input,
+            json!({
+                "version": 1,
+                "chainId": 1,
+                "block": "0x01",
+                "ancestorHeaders": ["0x02", "0x03"],
+                "ancestorHashes": [
+                    { "number": 9, "hash": B256::with_last_byte(9) },
+                    { "number": 10, "hash": B256::with_last_byte(10) },
+                ],
+                "preState": {
+                    "0x0000000000000000000000000000000000000001": null,
+                    "0x1000000000000000000000000000000000000000": {
+                        "nonce": "0xa",
+                        "balance": "0x1",
+                        "codeHash": code_hash,
+                        "storage": { slot.to_string(): "0x3" },
+                    },
+                },
+                "codes": { code_hash.to_string(): code },
+                "witness": ["0x04"],
+            })
+        );
+    }
+}
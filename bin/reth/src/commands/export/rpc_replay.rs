@@ -0,0 +1,215 @@
+//! Command that exports canonical blocks as a file of engine API calls.
+
+use alloy_consensus::{BlockHeader, Transaction};
+use alloy_eips::eip7685::Requests;
+use alloy_primitives::{BlockNumber, B256};
+use alloy_rpc_types::engine::{ExecutionPayload, ForkchoiceState};
+use clap::Parser;
+use reth_chainspec::{ChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_commands::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use reth_cli_util::parsers::parse_block_range;
+use reth_ethereum_primitives::{Block, EthPrimitives};
+use reth_evm::execute::{BlockExecutorProvider, Executor};
+use reth_node_ethereum::EthExecutorProvider;
+use reth_provider::{
+    BlockReader, ChainSpecProvider, ProviderError, StateProviderFactory, TransactionVariant,
+};
+use reth_revm::database::StateProviderDatabase;
+use serde_json::{json, Value};
+use std::{
+    io::{BufWriter, Write},
+    ops::RangeInclusive,
+    path::PathBuf,
+};
+use tracing::info;
+
+/// `reth export rpc-replay` command
+///
+/// Writes an `engine_newPayload` and an `engine_forkchoiceUpdated` JSON-RPC request per block,
+/// one request per line, using the engine API versions of the block's hardfork. Sending the
+/// requests in order to the authenticated RPC of an execution client synced to the parent of the
+/// first block replays the chain, e.g. for differential testing of clients.
+///
+/// The execution requests of Prague blocks aren't stored, so the blocks are re-executed, which
+/// requires the state of their parent blocks.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The inclusive range of blocks to export, e.g. `100..=200`. The genesis block can't be
+    /// exported.
+    #[arg(long, value_name = "FROM..=TO", value_parser = parse_block_range)]
+    range: RangeInclusive<BlockNumber>,
+
+    /// The path of the replay file.
+    #[arg(long, short, value_name = "PATH")]
+    output: PathBuf,
+}
+
+impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
+    /// Execute `export rpc-replay` command
+    pub async fn execute<N: CliNodeTypes<Primitives = EthPrimitives, ChainSpec = C::ChainSpec>>(
+        self,
+    ) -> eyre::Result<()> {
+        if *self.range.start() == 0 {
+            eyre::bail!("the genesis block can't be replayed")
+        }
+
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+        let chain_spec = provider_factory.chain_spec();
+        let executor = EthExecutorProvider::ethereum(chain_spec.clone());
+
+        let mut file = BufWriter::new(reth_fs_util::create_file(&self.output)?);
+        let mut request_id = 0;
+        for number in self.range.clone() {
+            let block = provider_factory
+                .recovered_block(number.into(), TransactionVariant::WithHash)?
+                .ok_or(ProviderError::HeaderNotFound(number.into()))?;
+            if !block.difficulty().is_zero() {
+                eyre::bail!("Block {number} was mined before the merge and can't be replayed")
+            }
+
+            let requests = if chain_spec.is_prague_active_at_timestamp(block.timestamp()) {
+                let state = provider_factory.history_by_block_number(number - 1)?;
+                let output =
+                    executor.executor(StateProviderDatabase::new(state)).execute(&block)?;
+                Some(output.result.requests)
+            } else {
+                None
+            };
+
+            let hash = block.hash();
+            for (method, params) in engine_calls(hash, block.into_block(), requests) {
+                request_id += 1;
+                let request = json!({
+                    "jsonrpc": "2.0",
+                    "id": request_id,
+                    "method": method,
+                    "params": params,
+                });
+                serde_json::to_writer(&mut file, &request)?;
+                file.write_all(b"\n")?;
+            }
+
+            if number % 10_000 == 0 {
+                info!(target: "reth::cli", number, "Exported blocks");
+            }
+        }
+        file.flush()?;
+
+        info!(
+            target: "reth::cli",
+            range = ?self.range,
+            path = %self.output.display(),
+            "Exported engine API calls"
+        );
+
+        Ok(())
+    }
+}
+
+/// Returns the `engine_newPayload` and `engine_forkchoiceUpdated` calls that import the block and
+/// make it canonical, using the engine API versions of the block's hardfork.
+///
+/// The execution requests are required for Prague blocks.
+fn engine_calls(
+    hash: B256,
+    block: Block,
+    requests: Option<Requests>,
+) -> [(&'static str, Value); 2] {
+    let versioned_hashes = block
+        .body
+        .transactions
+        .iter()
+        .filter_map(|tx| tx.blob_versioned_hashes())
+        .flatten()
+        .copied()
+        .collect::<Vec<_>>();
+    let parent_beacon_block_root = block.header.parent_beacon_block_root;
+
+    let (payload, _) = ExecutionPayload::from_block_unchecked(hash, &block);
+    let (new_payload, forkchoice_updated) = match payload {
+        ExecutionPayload::V1(payload) => {
+            (("engine_newPayloadV1", json!([payload])), "engine_forkchoiceUpdatedV1")
+        }
+        ExecutionPayload::V2(payload) => {
+            (("engine_newPayloadV2", json!([payload])), "engine_forkchoiceUpdatedV2")
+        }
+        ExecutionPayload::V3(payload) => {
+            let new_payload = match requests {
+                Some(requests) => {
+                    // requests without data are omitted, see EIP-7685
+                    let requests = requests
+                        .take()
+                        .into_iter()
+                        .filter(|request| request.len() > 1)
+                        .collect::<Vec<_>>();
+                    (
+                        "engine_newPayloadV4",
+                        json!([payload, versioned_hashes, parent_beacon_block_root, requests]),
+                    )
+                }
+                None => (
+                    "engine_newPayloadV3",
+                    json!([payload, versioned_hashes, parent_beacon_block_root]),
+                ),
+            };
+            (new_payload, "engine_forkchoiceUpdatedV3")
+        }
+    };
+
+    let state = ForkchoiceState {
+        head_block_hash: hash,
+        safe_block_hash: hash,
+        finalized_block_hash: hash,
+    };
+    [new_payload, (forkchoice_updated, json!([state, Value::Null]))]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{BlockBody, Header};
+    use alloy_eips::eip4895::Withdrawals;
+
+    #[test]
+    fn engine_call_versions() {
+        let block = |header: Header, withdrawals: Option<Withdrawals>| Block {
+            header,
+            body: BlockBody { withdrawals, ..Default::default() },
+        };
+        let methods = |block: Block, requests: Option<Requests>| {
+            engine_calls(B256::ZERO, block, requests).map(|(method, _)| method)
+        };
+
+        assert_eq!(
+            methods(block(Header::default(), None), None),
+            ["engine_newPayloadV1", "engine_forkchoiceUpdatedV1"]
+        );
+        assert_eq!(
+            methods(block(Header::default(), Some(Withdrawals::default())), None),
+            ["engine_newPayloadV2", "engine_forkchoiceUpdatedV2"]
+        );
+
+        let header = Header {
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(B256::ZERO),
+            ..Default::default()
+        };
+        assert_eq!(
+            methods(block(header.clone(), Some(Withdrawals::default())), None),
+            ["engine_newPayloadV3", "engine_forkchoiceUpdatedV3"]
+        );
+
+        let [(method, params), _] = engine_calls(
+            B256::ZERO,
+            block(header, Some(Withdrawals::default())),
+            Some(Requests::default()),
+        );
+        assert_eq!(method, "engine_newPayloadV4");
+        assert_eq!(params[3], json!([]));
+    }
+}
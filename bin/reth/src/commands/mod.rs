@@ -1,3 +1,4 @@
 //! This contains all of the `reth` commands
 
 pub mod debug_cmd;
+pub mod export;
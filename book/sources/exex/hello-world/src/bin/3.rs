@@ -18,6 +18,12 @@ async fn my_exex<Node: FullNodeComponents<Types: NodeTypes<Primitives = EthPrimi
             ExExNotification::ChainReverted { old } => {
                 info!(reverted_chain = ?old.range(), "Received revert");
             }
+            ExExNotification::NotificationsSkipped { count } => {
+                info!(%count, "Notifications skipped");
+            }
+            ExExNotification::Tick { tick } => {
+                info!(?tick, "Received tick");
+            }
         };
 
         if let Some(committed_chain) = notification.committed_chain() {
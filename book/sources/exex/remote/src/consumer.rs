@@ -25,6 +25,12 @@ async fn main() -> eyre::Result<()> {
             ExExNotification::ChainReverted { old } => {
                 info!(reverted_chain = ?old.range(), "Received revert");
             }
+            ExExNotification::NotificationsSkipped { count } => {
+                info!(%count, "Notifications skipped");
+            }
+            ExExNotification::Tick { tick } => {
+                info!(?tick, "Received tick");
+            }
         };
     }
 
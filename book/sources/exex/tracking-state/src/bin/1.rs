@@ -33,6 +33,12 @@ impl<Node: FullNodeComponents<Types: NodeTypes<Primitives = EthPrimitives>>> Fut
                 ExExNotification::ChainReverted { old } => {
                     info!(reverted_chain = ?old.range(), "Received revert");
                 }
+                ExExNotification::NotificationsSkipped { count } => {
+                    info!(%count, "Notifications skipped");
+                }
+                ExExNotification::Tick { tick } => {
+                    info!(?tick, "Received tick");
+                }
             };
 
             if let Some(committed_chain) = notification.committed_chain() {
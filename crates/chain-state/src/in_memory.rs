@@ -48,13 +48,16 @@ pub(crate) struct InMemoryStateMetrics {
 /// numbers first read the numbers map and then the blocks map.
 /// By acquiring the numbers lock first, we ensure that read-only lookups don't deadlock updates.
 /// This holds, because only lookup by number functions need to acquire the numbers lock first to
-/// get the block hash.
+/// get the block hash. The transactions lock is always acquired last.
 #[derive(Debug, Default)]
 pub(crate) struct InMemoryState<N: NodePrimitives = EthPrimitives> {
     /// All canonical blocks that are not on disk yet.
     blocks: RwLock<HashMap<B256, Arc<BlockState<N>>>>,
     /// Mapping of block numbers to block hashes.
     numbers: RwLock<BTreeMap<u64, B256>>,
+    /// Mapping of the hashes of the transactions in the canonical blocks to the hash of their
+    /// block and their index in the block.
+    transactions: RwLock<HashMap<TxHash, (B256, usize)>>,
     /// The pending block that has not yet been made canonical.
     pending: watch::Sender<Option<BlockState<N>>>,
     /// Metrics for the in-memory state.
@@ -68,9 +71,14 @@ impl<N: NodePrimitives> InMemoryState<N> {
         pending: Option<BlockState<N>>,
     ) -> Self {
         let (pending, _) = watch::channel(pending);
+        let mut transactions = HashMap::default();
+        for block in blocks.values() {
+            index_transactions(&mut transactions, block);
+        }
         let this = Self {
             blocks: RwLock::new(blocks),
             numbers: RwLock::new(numbers),
+            transactions: RwLock::new(transactions),
             pending,
             metrics: Default::default(),
         };
@@ -110,6 +118,12 @@ impl<N: NodePrimitives> InMemoryState<N> {
         self.numbers.read().get(&number).copied()
     }
 
+    /// Returns the hash of the block that contains the transaction and the index of the
+    /// transaction in the block.
+    pub(crate) fn transaction_location(&self, hash: TxHash) -> Option<(B256, usize)> {
+        self.transactions.read().get(&hash).copied()
+    }
+
     /// Returns the current chain head state.
     pub(crate) fn head_state(&self) -> Option<Arc<BlockState<N>>> {
         let hash = *self.numbers.read().last_key_value()?.1;
@@ -128,6 +142,30 @@ impl<N: NodePrimitives> InMemoryState<N> {
     }
 }
 
+/// Adds the transactions of the block to the transaction index.
+fn index_transactions<N: NodePrimitives>(
+    transactions: &mut HashMap<TxHash, (B256, usize)>,
+    block: &BlockState<N>,
+) {
+    let hash = block.hash();
+    for (index, tx) in block.block_ref().recovered_block().body().transactions_iter().enumerate() {
+        transactions.insert(*tx.tx_hash(), (hash, index));
+    }
+}
+
+/// Removes the transactions of the block from the transaction index.
+fn unindex_transactions<N: NodePrimitives>(
+    transactions: &mut HashMap<TxHash, (B256, usize)>,
+    block: &RecoveredBlock<N::Block>,
+) {
+    let hash = block.hash();
+    for tx in block.body().transactions_iter() {
+        if transactions.get(tx.tx_hash()).is_some_and(|(block_hash, _)| *block_hash == hash) {
+            transactions.remove(tx.tx_hash());
+        }
+    }
+}
+
 /// Inner type to provide in memory state. It includes a chain tracker to be
 /// advanced internally by the tree.
 #[derive(Debug)]
@@ -148,8 +186,10 @@ impl<N: NodePrimitives> CanonicalInMemoryStateInner<N> {
             // acquire locks, starting with the numbers lock
             let mut numbers = self.in_memory_state.numbers.write();
             let mut blocks = self.in_memory_state.blocks.write();
+            let mut transactions = self.in_memory_state.transactions.write();
             numbers.clear();
             blocks.clear();
+            transactions.clear();
             self.in_memory_state.pending.send_modify(|p| {
                 p.take();
             });
@@ -263,6 +303,7 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
             // acquire locks, starting with the numbers lock
             let mut numbers = self.inner.in_memory_state.numbers.write();
             let mut blocks = self.inner.in_memory_state.blocks.write();
+            let mut transactions = self.inner.in_memory_state.transactions.write();
 
             // we first remove the blocks from the reorged chain
             for block in reorged {
@@ -270,6 +311,7 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
                 let number = block.recovered_block().number();
                 blocks.remove(&hash);
                 numbers.remove(&number);
+                unindex_transactions::<N>(&mut transactions, block.recovered_block());
             }
 
             // insert the new blocks
@@ -278,6 +320,7 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
                 let block_state = BlockState::with_parent(block, parent);
                 let hash = block_state.hash();
                 let number = block_state.number();
+                index_transactions(&mut transactions, &block_state);
 
                 // append new blocks
                 blocks.insert(hash, Arc::new(block_state));
@@ -324,6 +367,7 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
             // acquire locks, starting with the numbers lock
             let mut numbers = self.inner.in_memory_state.numbers.write();
             let mut blocks = self.inner.in_memory_state.blocks.write();
+            let mut transactions = self.inner.in_memory_state.transactions.write();
 
             let BlockNumHash { number: persisted_height, hash: _ } = persisted_num_hash;
 
@@ -353,6 +397,9 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
                 numbers.insert(number, hash);
             }
 
+            // drop the transactions of the persisted blocks
+            transactions.retain(|_, (block_hash, _)| blocks.contains_key(block_hash));
+
             // also shift the pending state if it exists
             self.inner.in_memory_state.pending.send_modify(|p| {
                 if let Some(p) = p.as_mut() {
@@ -544,16 +591,25 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
         self.inner.in_memory_state.head_state().into_iter().flat_map(|head| head.iter())
     }
 
+    /// Returns the hash of the canonical block that contains the transaction with the given hash
+    /// and the index of the transaction in the block, if the block is in memory.
+    ///
+    /// This is a lookup in an index of the transactions of all in-memory blocks, so it doesn't
+    /// scan the blocks.
+    pub fn transaction_location(&self, hash: TxHash) -> Option<(B256, usize)> {
+        self.inner.in_memory_state.transaction_location(hash)
+    }
+
     /// Returns [`SignedTransaction`] type for the given `TxHash` if found.
     pub fn transaction_by_hash(&self, hash: TxHash) -> Option<N::SignedTx> {
-        for block_state in self.canonical_chain() {
-            if let Some(tx) =
-                block_state.block_ref().recovered_block().body().transaction_by_hash(&hash)
-            {
-                return Some(tx.clone())
-            }
-        }
-        None
+        let (block_hash, index) = self.transaction_location(hash)?;
+        self.state_by_hash(block_hash)?
+            .block_ref()
+            .recovered_block()
+            .body()
+            .transactions()
+            .get(index)
+            .cloned()
     }
 
     /// Returns a tuple with [`SignedTransaction`] type and [`TransactionMeta`] for the
@@ -562,28 +618,8 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
         &self,
         tx_hash: TxHash,
     ) -> Option<(N::SignedTx, TransactionMeta)> {
-        for block_state in self.canonical_chain() {
-            if let Some((index, tx)) = block_state
-                .block_ref()
-                .recovered_block()
-                .body()
-                .transactions_iter()
-                .enumerate()
-                .find(|(_, tx)| tx.trie_hash() == tx_hash)
-            {
-                let meta = TransactionMeta {
-                    tx_hash,
-                    index: index as u64,
-                    block_hash: block_state.hash(),
-                    block_number: block_state.block_ref().recovered_block().number(),
-                    base_fee: block_state.block_ref().recovered_block().base_fee_per_gas(),
-                    timestamp: block_state.block_ref().recovered_block().timestamp(),
-                    excess_blob_gas: block_state.block_ref().recovered_block().excess_blob_gas(),
-                };
-                return Some((tx.clone(), meta))
-            }
-        }
-        None
+        let (block_hash, index) = self.transaction_location(tx_hash)?;
+        self.state_by_hash(block_hash)?.transaction_with_meta(index)
     }
 }
 
@@ -736,30 +772,31 @@ impl<N: NodePrimitives> BlockState<N> {
         tx_hash: TxHash,
     ) -> Option<(N::SignedTx, TransactionMeta)> {
         self.chain().find_map(|block_state| {
-            block_state
+            let index = block_state
                 .block_ref()
                 .recovered_block()
                 .body()
                 .transactions_iter()
-                .enumerate()
-                .find(|(_, tx)| tx.trie_hash() == tx_hash)
-                .map(|(index, tx)| {
-                    let meta = TransactionMeta {
-                        tx_hash,
-                        index: index as u64,
-                        block_hash: block_state.hash(),
-                        block_number: block_state.block_ref().recovered_block().number(),
-                        base_fee: block_state.block_ref().recovered_block().base_fee_per_gas(),
-                        timestamp: block_state.block_ref().recovered_block().timestamp(),
-                        excess_blob_gas: block_state
-                            .block_ref()
-                            .recovered_block()
-                            .excess_blob_gas(),
-                    };
-                    (tx.clone(), meta)
-                })
+                .position(|tx| tx.trie_hash() == tx_hash)?;
+            block_state.transaction_with_meta(index)
         })
     }
+
+    /// Returns the transaction at the given index in this block with its [`TransactionMeta`].
+    pub fn transaction_with_meta(&self, index: usize) -> Option<(N::SignedTx, TransactionMeta)> {
+        let block = self.block_ref().recovered_block();
+        let tx = block.body().transactions().get(index)?;
+        let meta = TransactionMeta {
+            tx_hash: *tx.tx_hash(),
+            index: index as u64,
+            block_hash: self.hash(),
+            block_number: block.number(),
+            base_fee: block.base_fee_per_gas(),
+            timestamp: block.timestamp(),
+            excess_blob_gas: block.excess_blob_gas(),
+        };
+        Some((tx.clone(), meta))
+    }
 }
 
 /// Represents an executed block stored in-memory.
@@ -956,8 +993,8 @@ mod tests {
         StateRootProvider, StorageRootProvider,
     };
     use reth_trie::{
-        AccountProof, HashedStorage, MultiProof, MultiProofTargets, StorageMultiProof,
-        StorageProof, TrieInput,
+        AccountProof, HashedStorage, HashedStorageRange, MultiProof, MultiProofTargets,
+        StorageMultiProof, StorageProof, TrieInput,
     };
 
     fn create_mock_state(
@@ -1083,6 +1120,16 @@ mod tests {
         ) -> ProviderResult<StorageMultiProof> {
             Ok(StorageMultiProof::empty())
         }
+
+        fn storage_range(
+            &self,
+            _address: Address,
+            _hashed_storage: HashedStorage,
+            _start: B256,
+            _limit: usize,
+        ) -> ProviderResult<HashedStorageRange> {
+            Ok(HashedStorageRange::default())
+        }
     }
 
     impl StateProofProvider for MockStateProvider {
@@ -1290,6 +1337,40 @@ mod tests {
         assert_eq!(state.inner.in_memory_state.block_count(), 1);
     }
 
+    #[test]
+    fn test_in_memory_state_transaction_index() {
+        let state: CanonicalInMemoryState = CanonicalInMemoryState::empty();
+        let mut test_block_builder: TestBlockBuilder = TestBlockBuilder::default();
+        let blocks = test_block_builder.get_executed_blocks(1..4).collect::<Vec<_>>();
+        state.update_chain(NewCanonicalChain::Commit { new: blocks.clone() });
+
+        let assert_indexed = |block: &ExecutedBlockWithTrieUpdates, indexed: bool| {
+            let block = block.recovered_block();
+            for (index, tx) in block.body().transactions_iter().enumerate() {
+                let location = indexed.then_some((block.hash(), index));
+                assert_eq!(state.transaction_location(*tx.tx_hash()), location);
+                assert_eq!(state.transaction_by_hash(*tx.tx_hash()).is_some(), indexed);
+            }
+        };
+        blocks.iter().for_each(|block| assert_indexed(block, true));
+
+        // the transactions of reorged blocks are removed
+        let new_block = test_block_builder
+            .get_executed_block_with_number(3, blocks[1].recovered_block().hash());
+        state.update_chain(NewCanonicalChain::Reorg {
+            new: vec![new_block.clone()],
+            old: vec![blocks[2].block.clone()],
+        });
+        assert_indexed(&blocks[2], false);
+        assert_indexed(&new_block, true);
+
+        // the transactions of persisted blocks are removed
+        state.remove_persisted_blocks(blocks[0].recovered_block().num_hash());
+        assert_indexed(&blocks[0], false);
+        assert_indexed(&blocks[1], true);
+        assert_indexed(&new_block, true);
+    }
+
     #[test]
     fn test_in_memory_state_set_pending_block() {
         let state: CanonicalInMemoryState = CanonicalInMemoryState::empty();
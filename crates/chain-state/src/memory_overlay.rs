@@ -8,8 +8,8 @@ use reth_storage_api::{
     StateRootProvider, StorageRootProvider,
 };
 use reth_trie::{
-    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
-    MultiProofTargets, StorageMultiProof, TrieInput,
+    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, HashedStorageRange,
+    MultiProof, MultiProofTargets, StorageMultiProof, TrieInput,
 };
 use revm_database::BundleState;
 use std::sync::OnceLock;
@@ -176,6 +176,20 @@ impl<N: NodePrimitives> StorageRootProvider for MemoryOverlayStateProviderRef<'_
         hashed_storage.extend(&storage);
         self.historical.storage_multiproof(address, slots, hashed_storage)
     }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStorageRange> {
+        let state = &self.trie_state().state;
+        let mut hashed_storage =
+            state.storages.get(&keccak256(address)).cloned().unwrap_or_default();
+        hashed_storage.extend(&storage);
+        self.historical.storage_range(address, hashed_storage, start, limit)
+    }
 }
 
 impl<N: NodePrimitives> StateProofProvider for MemoryOverlayStateProviderRef<'_, N> {
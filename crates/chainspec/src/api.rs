@@ -1,4 +1,4 @@
-use crate::{ChainSpec, DepositContract};
+use crate::{BlockLimits, ChainSpec, DepositContract};
 use alloc::{boxed::Box, vec::Vec};
 use alloy_chains::Chain;
 use alloy_consensus::Header;
@@ -65,6 +65,16 @@ pub trait EthChainSpec: Send + Sync + Unpin + Debug {
 
     /// Returns the final total difficulty if the Paris hardfork is known.
     fn final_paris_total_difficulty(&self) -> Option<U256>;
+
+    /// Returns the block number of the Paris hardfork if it is known, none by default.
+    fn paris_block(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns the limits on the size of blocks, none by default.
+    fn block_limits(&self) -> BlockLimits {
+        BlockLimits::default()
+    }
 }
 
 impl EthChainSpec for ChainSpec {
@@ -127,4 +137,12 @@ impl EthChainSpec for ChainSpec {
     fn final_paris_total_difficulty(&self) -> Option<U256> {
         self.paris_block_and_final_difficulty.map(|(_, final_difficulty)| final_difficulty)
     }
+
+    fn paris_block(&self) -> Option<u64> {
+        self.paris_block()
+    }
+
+    fn block_limits(&self) -> BlockLimits {
+        self.block_limits
+    }
 }
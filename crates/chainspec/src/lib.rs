@@ -30,9 +30,9 @@ pub use info::ChainInfo;
 #[cfg(any(test, feature = "test-utils"))]
 pub use spec::test_fork_ids;
 pub use spec::{
-    make_genesis_header, BaseFeeParams, BaseFeeParamsKind, ChainSpec, ChainSpecBuilder,
-    ChainSpecProvider, DepositContract, ForkBaseFeeParams, HardforkBlobParams, DEV, HOLESKY, HOODI,
-    MAINNET, SEPOLIA,
+    make_genesis_header, BaseFeeParams, BaseFeeParamsKind, BlockLimits, ChainSpec,
+    ChainSpecBuilder, ChainSpecProvider, DepositContract, ForkBaseFeeParams, HardforkBlobParams,
+    BLOCK_RLP_LENGTH_RESERVE, DEV, HOLESKY, HOODI, MAINNET, SEPOLIA,
 };
 
 use reth_primitives_traits::sync::OnceLock;
@@ -18,8 +18,8 @@ use alloy_eips::{
     eip1559::INITIAL_BASE_FEE, eip6110::MAINNET_DEPOSIT_CONTRACT_ADDRESS,
     eip7685::EMPTY_REQUESTS_HASH, eip7840::BlobParams,
 };
-use alloy_genesis::Genesis;
-use alloy_primitives::{address, b256, Address, BlockNumber, B256, U256};
+use alloy_genesis::{Genesis, GenesisAccount};
+use alloy_primitives::{address, b256, Address, BlockNumber, Bytes, B256, U256};
 use alloy_trie::root::state_root_ref_unhashed;
 use derive_more::From;
 use reth_ethereum_forks::{
@@ -112,6 +112,7 @@ pub static MAINNET: LazyLock<Arc<ChainSpec>> = LazyLock::new(|| {
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: MAINNET_PRUNE_DELETE_LIMIT,
         blob_params: HardforkBlobParams::default(),
+        block_limits: BlockLimits::default(),
     };
     spec.genesis.config.dao_fork_support = true;
     spec.into()
@@ -141,6 +142,7 @@ pub static SEPOLIA: LazyLock<Arc<ChainSpec>> = LazyLock::new(|| {
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 10000,
         blob_params: HardforkBlobParams::default(),
+        block_limits: BlockLimits::default(),
     };
     spec.genesis.config.dao_fork_support = true;
     spec.into()
@@ -168,6 +170,7 @@ pub static HOLESKY: LazyLock<Arc<ChainSpec>> = LazyLock::new(|| {
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 10000,
         blob_params: HardforkBlobParams::default(),
+        block_limits: BlockLimits::default(),
     };
     spec.genesis.config.dao_fork_support = true;
     spec.into()
@@ -197,6 +200,7 @@ pub static HOODI: LazyLock<Arc<ChainSpec>> = LazyLock::new(|| {
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 10000,
         blob_params: HardforkBlobParams::default(),
+        block_limits: BlockLimits::default(),
     };
     spec.genesis.config.dao_fork_support = true;
     spec.into()
@@ -297,6 +301,62 @@ impl Default for HardforkBlobParams {
     }
 }
 
+/// The RLP length reserved for the header and the list headers of a block when transactions are
+/// packed into a block under [`BlockLimits::max_rlp_length`]. No header is longer than that.
+pub const BLOCK_RLP_LENGTH_RESERVE: usize = 1024;
+
+/// Limits on the size of blocks that are enforced before execution, on top of the gas limit.
+///
+/// The payload builders stop adding transactions once a limit is reached, and the transaction
+/// pool rejects transactions that can't fit into a block on their own.
+///
+/// Ethereum doesn't set these limits, but L2s and private chains may need them, e.g. to bound the
+/// size of the batches a block is posted in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlockLimits {
+    /// The maximum length of an RLP encoded block.
+    pub max_rlp_length: Option<usize>,
+    /// The maximum number of transactions in a block.
+    pub max_transactions: Option<usize>,
+}
+
+impl BlockLimits {
+    /// Reads the limits from the `maxBlockRlpLength` and `maxBlockTransactions` fields of the
+    /// genesis config. Limits that aren't set are disabled.
+    pub fn from_genesis(genesis: &Genesis) -> Self {
+        let limit = |key: &str| {
+            genesis
+                .config
+                .extra_fields
+                .get(key)
+                .and_then(|value| value.as_u64())
+                .map(|value| value as usize)
+        };
+
+        Self {
+            max_rlp_length: limit("maxBlockRlpLength"),
+            max_transactions: limit("maxBlockTransactions"),
+        }
+    }
+
+    /// Returns `true` if a transaction of the given RLP length can be added to a block that has
+    /// the given number of transactions, whose transactions and withdrawals take the given RLP
+    /// length, without exceeding the limits.
+    ///
+    /// [`BLOCK_RLP_LENGTH_RESERVE`] bytes are reserved for the header.
+    pub fn fits_transaction(
+        &self,
+        transactions: usize,
+        rlp_length: usize,
+        tx_rlp_length: usize,
+    ) -> bool {
+        self.max_transactions.is_none_or(|max_transactions| transactions < max_transactions) &&
+            self.max_rlp_length.is_none_or(|max_rlp_length| {
+                BLOCK_RLP_LENGTH_RESERVE + rlp_length + tx_rlp_length <= max_rlp_length
+            })
+    }
+}
+
 impl core::ops::Deref for ChainSpec {
     type Target = ChainHardforks;
 
@@ -341,6 +401,9 @@ pub struct ChainSpec {
 
     /// The settings passed for blob configurations for specific hardforks.
     pub blob_params: HardforkBlobParams,
+
+    /// The limits on the size of blocks.
+    pub block_limits: BlockLimits,
 }
 
 impl Default for ChainSpec {
@@ -355,6 +418,7 @@ impl Default for ChainSpec {
             base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
             prune_delete_limit: MAINNET_PRUNE_DELETE_LIMIT,
             blob_params: Default::default(),
+            block_limits: Default::default(),
         }
     }
 }
@@ -758,6 +822,8 @@ impl From<Genesis> for ChainSpec {
         // Extract blob parameters directly from blob_schedule
         let blob_params = HardforkBlobParams::from_schedule(&genesis.config.blob_schedule);
 
+        let block_limits = BlockLimits::from_genesis(&genesis);
+
         // NOTE: in full node, we prune all receipts except the deposit contract's. We do not
         // have the deployment block in the genesis file, so we use block zero. We use the same
         // deposit topic as the mainnet contract if we have the deposit contract address in the
@@ -776,6 +842,7 @@ impl From<Genesis> for ChainSpec {
             paris_block_and_final_difficulty,
             deposit_contract,
             blob_params,
+            block_limits,
             ..Default::default()
         }
     }
@@ -851,6 +918,41 @@ impl ChainSpecBuilder {
         self
     }
 
+    /// Add the given account to the genesis allocation, replacing any existing account at the
+    /// address.
+    ///
+    /// An empty genesis is created if none is set.
+    pub fn with_account(mut self, address: Address, account: GenesisAccount) -> Self {
+        self.genesis.get_or_insert_with(Default::default).alloc.insert(address, account);
+        self
+    }
+
+    /// Add an account with the given balance to the genesis allocation.
+    pub fn with_funded_account(self, address: Address, balance: U256) -> Self {
+        self.with_account(address, GenesisAccount { balance, ..Default::default() })
+    }
+
+    /// Add a contract with the given bytecode and storage to the genesis allocation.
+    ///
+    /// The nonce of the contract is set to 1, as for contracts deployed after EIP-161.
+    pub fn with_predeploy(
+        self,
+        address: Address,
+        code: Bytes,
+        storage: impl IntoIterator<Item = (B256, B256)>,
+    ) -> Self {
+        let storage = storage.into_iter().collect::<BTreeMap<_, _>>();
+        self.with_account(
+            address,
+            GenesisAccount {
+                nonce: Some(1),
+                code: Some(code),
+                storage: (!storage.is_empty()).then_some(storage),
+                ..Default::default()
+            },
+        )
+    }
+
     /// Add the given fork with the given activation condition to the spec.
     pub fn with_fork<H: Hardfork>(mut self, fork: H, condition: ForkCondition) -> Self {
         self.hardforks.insert(fork, condition);
@@ -869,6 +971,15 @@ impl ChainSpecBuilder {
         self
     }
 
+    /// Add the given fork, activated at the given number of seconds after the genesis timestamp.
+    ///
+    /// The offset is relative to the genesis set at the time of the call, so the genesis should
+    /// be set first.
+    pub fn with_fork_at_offset<H: Hardfork>(self, fork: H, offset: u64) -> Self {
+        let genesis_timestamp = self.genesis.as_ref().map_or(0, |genesis| genesis.timestamp);
+        self.with_fork(fork, ForkCondition::Timestamp(genesis_timestamp + offset))
+    }
+
     /// Enable the Paris hardfork at the given TTD.
     ///
     /// Does not set the merge netsplit block.
@@ -990,6 +1101,60 @@ impl ChainSpecBuilder {
         self
     }
 
+    /// Returns the genesis with its chain config set to the chain ID and the Ethereum fork
+    /// schedule of the builder.
+    ///
+    /// Loading the genesis, e.g. with `--chain <PATH>`, results in the same fork schedule. An
+    /// empty genesis is used if none is set.
+    pub fn to_genesis(&self) -> Genesis {
+        let mut genesis = self.genesis.clone().unwrap_or_default();
+        let block = |fork| match self.hardforks.get(fork) {
+            Some(ForkCondition::Block(block)) => Some(block),
+            _ => None,
+        };
+        let time = |fork| match self.hardforks.get(fork) {
+            Some(ForkCondition::Timestamp(time)) => Some(time),
+            _ => None,
+        };
+
+        let config = &mut genesis.config;
+        if let Some(chain) = self.chain {
+            config.chain_id = chain.id();
+        }
+        config.homestead_block = block(EthereumHardfork::Homestead);
+        config.dao_fork_block = block(EthereumHardfork::Dao);
+        config.dao_fork_support = config.dao_fork_block.is_some();
+        config.eip150_block = block(EthereumHardfork::Tangerine);
+        config.eip155_block = block(EthereumHardfork::SpuriousDragon);
+        config.eip158_block = config.eip155_block;
+        config.byzantium_block = block(EthereumHardfork::Byzantium);
+        config.constantinople_block = block(EthereumHardfork::Constantinople);
+        config.petersburg_block = block(EthereumHardfork::Petersburg);
+        config.istanbul_block = block(EthereumHardfork::Istanbul);
+        config.muir_glacier_block = block(EthereumHardfork::MuirGlacier);
+        config.berlin_block = block(EthereumHardfork::Berlin);
+        config.london_block = block(EthereumHardfork::London);
+        config.arrow_glacier_block = block(EthereumHardfork::ArrowGlacier);
+        config.gray_glacier_block = block(EthereumHardfork::GrayGlacier);
+        (config.terminal_total_difficulty, config.merge_netsplit_block) =
+            match self.hardforks.get(EthereumHardfork::Paris) {
+                Some(ForkCondition::TTD { total_difficulty, fork_block, .. }) => {
+                    (Some(total_difficulty), fork_block)
+                }
+                _ => (None, None),
+            };
+        config.shanghai_time = time(EthereumHardfork::Shanghai);
+        config.cancun_time = time(EthereumHardfork::Cancun);
+        config.prague_time = time(EthereumHardfork::Prague);
+        config.osaka_time = time(EthereumHardfork::Osaka);
+        genesis
+    }
+
+    /// Returns the genesis JSON of [`Self::to_genesis`].
+    pub fn genesis_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&self.to_genesis())
+    }
+
     /// Build the resulting [`ChainSpec`].
     ///
     /// # Panics
@@ -1075,7 +1240,7 @@ mod tests {
     use alloy_chains::Chain;
     use alloy_consensus::constants::ETH_TO_WEI;
     use alloy_evm::block::calc::{base_block_reward, block_reward};
-    use alloy_genesis::{ChainConfig, GenesisAccount};
+    use alloy_genesis::ChainConfig;
     use alloy_primitives::{b256, hex};
     use alloy_trie::{TrieAccount, EMPTY_ROOT_HASH};
     use core::ops::Deref;
@@ -2509,4 +2674,43 @@ Post-merge hard forks (timestamp based):
             assert_eq!(block_reward(base_reward, num_ommers), expected_reward);
         }
     }
+
+    #[test]
+    fn builder_genesis_json_roundtrip() {
+        let funded = Address::with_last_byte(1);
+        let predeploy = Address::with_last_byte(2);
+        let builder = ChainSpecBuilder::default()
+            .chain(Chain::from_id(1337))
+            .genesis(Genesis { timestamp: 100, ..Default::default() })
+            .cancun_activated()
+            .with_fork_at_offset(EthereumHardfork::Prague, 10)
+            .with_funded_account(funded, U256::from(ETH_TO_WEI))
+            .with_predeploy(predeploy, Bytes::from_static(&[0x00]), [(B256::ZERO, B256::ZERO)]);
+
+        let genesis: Genesis = serde_json::from_str(&builder.genesis_json().unwrap()).unwrap();
+        assert_eq!(genesis.config.chain_id, 1337);
+        assert_eq!(genesis.config.prague_time, Some(110));
+        assert_eq!(genesis.alloc[&funded].balance, U256::from(ETH_TO_WEI));
+        assert_eq!(genesis.alloc[&predeploy].nonce, Some(1));
+
+        let spec = ChainSpec::from(genesis);
+        let built = builder.build();
+        assert_eq!(spec.fork(EthereumHardfork::Prague), ForkCondition::Timestamp(110));
+        assert_eq!(spec.latest_fork_id(), built.latest_fork_id());
+        assert_eq!(spec.genesis_hash(), built.genesis_hash());
+    }
+
+    #[test]
+    fn block_limits_fit_transaction() {
+        assert!(BlockLimits::default().fits_transaction(usize::MAX - 1, usize::MAX / 2, 1));
+
+        let limits = BlockLimits {
+            max_rlp_length: Some(BLOCK_RLP_LENGTH_RESERVE + 1_000),
+            max_transactions: Some(2),
+        };
+        assert!(limits.fits_transaction(0, 0, 1_000));
+        assert!(limits.fits_transaction(1, 600, 400));
+        assert!(!limits.fits_transaction(1, 600, 401));
+        assert!(!limits.fits_transaction(2, 0, 1));
+    }
 }
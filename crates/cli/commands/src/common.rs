@@ -83,13 +83,13 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
         }
 
         info!(target: "reth::cli", ?db_path, ?sf_path, "Opening storage");
+        let db_args = self.db.database_args().with_static_files_path(Some(sf_path.clone()));
         let (db, sfp) = match access {
-            AccessRights::RW => (
-                Arc::new(init_db(db_path, self.db.database_args())?),
-                StaticFileProvider::read_write(sf_path)?,
-            ),
+            AccessRights::RW => {
+                (Arc::new(init_db(db_path, db_args)?), StaticFileProvider::read_write(sf_path)?)
+            }
             AccessRights::RO => (
-                Arc::new(open_db_read_only(&db_path, self.db.database_args())?),
+                Arc::new(open_db_read_only(&db_path, db_args)?),
                 StaticFileProvider::read_only(sf_path, false)?,
             ),
         };
@@ -0,0 +1,169 @@
+use super::{
+    confirm,
+    get::{maybe_json_value_parser, table_key, table_subkey},
+    put::seek_exact_dup,
+};
+use alloy_primitives::hex;
+use clap::Parser;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
+    database::Database,
+    table::{DupSort, Encode, Table},
+    transaction::{DbTx, DbTxMut},
+    DatabaseError, RawDupSort, RawKey, RawTable, TableViewer, Tables,
+};
+use reth_node_builder::NodeTypesWithDB;
+use reth_provider::ProviderFactory;
+use tracing::{error, info};
+
+/// The arguments for the `reth db delete` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The table name
+    table: Tables,
+
+    /// The key to delete the value of
+    #[arg(value_parser = maybe_json_value_parser)]
+    key: String,
+
+    /// The subkey to delete the value of. If not set, all values of the key are deleted from
+    /// dupsort tables.
+    #[arg(value_parser = maybe_json_value_parser)]
+    subkey: Option<String>,
+
+    /// Bypasses the interactive confirmation and deletes the value directly
+    #[arg(short, long)]
+    force: bool,
+}
+
+impl Command {
+    /// Asks the user to confirm the deletion, unless it's forced.
+    pub(crate) fn confirm(&self) -> bool {
+        self.force ||
+            confirm(&format!(
+                "Are you sure you want to delete from table {} at key {}? This cannot be undone.",
+                self.table, self.key
+            ))
+    }
+
+    /// Execute `db delete` command
+    pub fn execute<N: NodeTypesWithDB>(
+        self,
+        provider_factory: ProviderFactory<N>,
+    ) -> eyre::Result<()> {
+        self.table.view(&DeleteViewer { db: provider_factory.db_ref(), args: &self })
+    }
+}
+
+struct DeleteViewer<'a, DB: Database> {
+    db: &'a DB,
+    args: &'a Command,
+}
+
+impl<DB: Database> TableViewer<()> for DeleteViewer<'_, DB> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<(), Self::Error> {
+        eyre::ensure!(self.args.subkey.is_none(), "Table {} has no subkeys", T::NAME);
+        let key = table_key::<T>(&self.args.key)?;
+
+        let tx = self.db.tx_mut()?;
+        let deleted = tx.get::<RawTable<T>>(RawKey::new(key.clone()))?;
+        if deleted.is_some() {
+            tx.delete::<T>(key, None)?;
+        }
+        tx.commit()?;
+
+        log_deleted(T::NAME, deleted.iter().map(|value| value.raw_value()));
+        Ok(())
+    }
+
+    fn view_dupsort<T: DupSort>(&self) -> Result<(), Self::Error> {
+        let key = table_key::<T>(&self.args.key)?;
+        let subkey = self.args.subkey.as_deref().map(|subkey| table_subkey::<T>(Some(subkey)));
+
+        let tx = self.db.tx_mut()?;
+        let deleted = delete_dup::<T, _>(&tx, key, subkey.transpose()?)?;
+        tx.commit()?;
+
+        log_deleted(T::NAME, deleted.iter().map(Vec::as_slice));
+        Ok(())
+    }
+}
+
+/// Deletes the value of the given subkey from a dupsort table, or all values of the key if no
+/// subkey is given. Returns the deleted raw values.
+fn delete_dup<T: DupSort, TX: DbTxMut>(
+    tx: &TX,
+    key: T::Key,
+    subkey: Option<T::SubKey>,
+) -> Result<Vec<Vec<u8>>, DatabaseError> {
+    let mut cursor = tx.cursor_dup_write::<RawDupSort<T>>()?;
+    let key = RawKey::new(key);
+
+    if let Some(subkey) = subkey {
+        let deleted = seek_exact_dup(&mut cursor, key, subkey.encode().into())?;
+        if deleted.is_some() {
+            cursor.delete_current()?;
+        }
+        return Ok(deleted.into_iter().collect())
+    }
+
+    let mut deleted = Vec::new();
+    let mut entry = cursor.seek_exact(key.clone())?;
+    while let Some((_, value)) = entry {
+        deleted.push(value.into_value());
+        entry = cursor.next_dup()?;
+    }
+    if !deleted.is_empty() {
+        cursor.seek_exact(key)?;
+        cursor.delete_current_duplicates()?;
+    }
+    Ok(deleted)
+}
+
+/// Logs the deleted raw values, so they can be written back with `reth db put`.
+fn log_deleted<'a>(table: &str, deleted: impl IntoIterator<Item = &'a [u8]>) {
+    let mut deleted = deleted.into_iter().peekable();
+    if deleted.peek().is_none() {
+        error!(target: "reth::cli", "No content for the given table key.");
+    }
+    for value in deleted {
+        info!(target: "reth::cli", table, value = %hex::encode_prefixed(value), "Deleted value");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, B256, U256};
+    use reth_db::test_utils::create_test_rw_db;
+    use reth_db_api::{table::Compress, PlainStorageState};
+    use reth_primitives_traits::StorageEntry;
+
+    #[test]
+    fn delete_dup_values() {
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+        let address = address!("0x4448e1273fd5a8bfdb9ed111e96889c960eee145");
+        let entry =
+            |key: u8| StorageEntry { key: B256::with_last_byte(key), value: U256::from(key) };
+        for key in 1..=3 {
+            tx.put::<PlainStorageState>(address, entry(key)).unwrap();
+        }
+
+        // only the value of an existing subkey is deleted
+        let deleted =
+            delete_dup::<PlainStorageState, _>(&tx, address, Some(B256::with_last_byte(4)))
+                .unwrap();
+        assert!(deleted.is_empty());
+        let deleted =
+            delete_dup::<PlainStorageState, _>(&tx, address, Some(B256::with_last_byte(2)))
+                .unwrap();
+        assert_eq!(deleted, [Vec::from(entry(2).compress())]);
+
+        let deleted = delete_dup::<PlainStorageState, _>(&tx, address, None).unwrap();
+        assert_eq!(deleted, [Vec::from(entry(1).compress()), entry(3).compress().into()]);
+        assert_eq!(tx.get::<PlainStorageState>(address).unwrap(), None);
+    }
+}
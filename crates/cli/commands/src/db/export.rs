@@ -0,0 +1,72 @@
+use clap::{Parser, Subcommand};
+use reth_db_common::DbTool;
+use reth_provider::{
+    providers::ProviderNodeTypes, BytecodeProvider, BytecodeSizeFilter, DBProvider,
+};
+use serde_json::json;
+use std::{
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
+use tracing::info;
+
+/// The arguments for the `reth db export` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+#[derive(Subcommand, Debug)]
+enum Subcommands {
+    /// Exports the stored contract bytecode, as one JSON object with the code hash and the code
+    /// per line.
+    Bytecodes(BytecodesArgs),
+}
+
+/// The arguments for the `reth db export bytecodes` command
+#[derive(Parser, Debug)]
+struct BytecodesArgs {
+    /// Only export contracts with at least this many bytes of code.
+    #[arg(long, value_name = "BYTES")]
+    min_size: Option<usize>,
+    /// Only export contracts with at most this many bytes of code.
+    #[arg(long, value_name = "BYTES")]
+    max_size: Option<usize>,
+    /// The path of the export file.
+    #[arg(long, short, value_name = "PATH")]
+    output: PathBuf,
+}
+
+impl Command {
+    /// Execute `db export` command
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Bytecodes(args) => {
+                let provider =
+                    tool.provider_factory.provider()?.disable_long_read_transaction_safety();
+                let filter =
+                    BytecodeSizeFilter { min_size: args.min_size, max_size: args.max_size };
+
+                let mut file = BufWriter::new(reth_fs_util::create_file(&args.output)?);
+                let mut exported = 0u64;
+                for entry in provider.iter_bytecodes(filter)? {
+                    let (code_hash, bytecode) = entry?;
+                    let line = json!({ "codeHash": code_hash, "code": bytecode.original_bytes() });
+                    serde_json::to_writer(&mut file, &line)?;
+                    file.write_all(b"\n")?;
+
+                    exported += 1;
+                    if exported % 100_000 == 0 {
+                        info!(target: "reth::cli", exported, "Exporting bytecode");
+                    }
+                }
+                file.flush()?;
+
+                info!(target: "reth::cli", exported, path = ?args.output, "Exported bytecode");
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -8,12 +8,17 @@ use std::io::{self, Write};
 
 mod checksum;
 mod clear;
+mod delete;
 mod diff;
+mod export;
 mod get;
 mod list;
+mod put;
+mod scan;
 mod stats;
 /// DB List TUI
 mod tui;
+mod verify_static_files;
 
 /// `reth db` command
 #[derive(Debug, Parser)]
@@ -38,6 +43,16 @@ pub enum Subcommands {
     Diff(diff::Command),
     /// Gets the content of a table for the given key
     Get(get::Command),
+    /// Lists the decoded contents of a table in a key range
+    Scan(scan::Command),
+    /// Writes a value to a table
+    Put(put::Command),
+    /// Deletes the value of a key from a table
+    Delete(delete::Command),
+    /// Exports stored data for analysis with external tools
+    Export(export::Command),
+    /// Verifies the static files against the checksums in their manifest
+    VerifyStaticFiles(verify_static_files::Command),
     /// Deletes all database entries
     Drop {
         /// Bypasses the interactive confirmation and drops the database directly
@@ -107,20 +122,47 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool)?;
                 });
             }
+            Subcommands::Scan(command) => {
+                db_ro_exec!(self.env, tool, N, {
+                    command.execute(&tool)?;
+                });
+            }
+            Subcommands::Put(command) => {
+                if !command.confirm() {
+                    println!("Database write aborted!");
+                    return Ok(())
+                }
+
+                let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+                command.execute(provider_factory)?;
+            }
+            Subcommands::Delete(command) => {
+                if !command.confirm() {
+                    println!("Database delete aborted!");
+                    return Ok(())
+                }
+
+                let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+                command.execute(provider_factory)?;
+            }
+            Subcommands::Export(command) => {
+                db_ro_exec!(self.env, tool, N, {
+                    command.execute(&tool)?;
+                });
+            }
+            Subcommands::VerifyStaticFiles(command) => {
+                db_ro_exec!(self.env, tool, N, {
+                    command.execute(&tool)?;
+                });
+            }
             Subcommands::Drop { force } => {
-                if !force {
-                    // Ask for confirmation
-                    print!("Are you sure you want to drop the database at {data_dir}? This cannot be undone. (y/N): ");
-                    // Flush the buffer to ensure the message is printed immediately
-                    io::stdout().flush().unwrap();
-
-                    let mut input = String::new();
-                    io::stdin().read_line(&mut input).expect("Failed to read line");
-
-                    if !input.trim().eq_ignore_ascii_case("y") {
-                        println!("Database drop aborted!");
-                        return Ok(())
-                    }
+                if !force &&
+                    !confirm(&format!(
+                        "Are you sure you want to drop the database at {data_dir}? This cannot be undone."
+                    ))
+                {
+                    println!("Database drop aborted!");
+                    return Ok(())
                 }
 
                 let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
@@ -155,6 +197,17 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
     }
 }
 
+/// Asks for confirmation of the given question on stdin, and returns `true` if confirmed.
+fn confirm(question: &str) -> bool {
+    print!("{question} (y/N): ");
+    // Flush the buffer to ensure the message is printed immediately
+    io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).expect("Failed to read line");
+    input.trim().eq_ignore_ascii_case("y")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,253 @@
+use super::{
+    confirm,
+    get::{maybe_json_value_parser, table_key, table_subkey},
+};
+use alloy_primitives::{hex, Bytes};
+use clap::Parser;
+use reth_db_api::{
+    cursor::{DbCursorRW, DbDupCursorRO},
+    database::Database,
+    table::{Compress, Decompress, DupSort, Encode, Table},
+    tables_to_generic,
+    transaction::{DbTx, DbTxMut},
+    DatabaseError, RawDupSort, RawKey, RawTable, RawValue, TableViewer, Tables,
+};
+use reth_node_builder::NodeTypesWithDB;
+use reth_provider::ProviderFactory;
+use serde::de::DeserializeOwned;
+use tracing::info;
+
+/// The arguments for the `reth db put` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The table name
+    table: Tables,
+
+    /// The key to write the value for
+    #[arg(value_parser = maybe_json_value_parser)]
+    key: String,
+
+    /// The value to write, as JSON of the value type of the table, e.g. as returned by
+    /// `reth db get`.
+    #[arg(value_parser = maybe_json_value_parser)]
+    value: String,
+
+    /// The subkey of the value, required for dupsort tables.
+    ///
+    /// The existing value with the same subkey is replaced.
+    #[arg(long, value_parser = maybe_json_value_parser)]
+    subkey: Option<String>,
+
+    /// Read the value as hex encoded bytes in the codec of the table, e.g. as returned by
+    /// `reth db get --raw`.
+    ///
+    /// The value is decoded before it's written, so values that the node can't read are
+    /// rejected.
+    #[arg(long)]
+    raw: bool,
+
+    /// Bypasses the interactive confirmation and writes the value directly
+    #[arg(short, long)]
+    force: bool,
+}
+
+impl Command {
+    /// Asks the user to confirm the write, unless it's forced.
+    pub(crate) fn confirm(&self) -> bool {
+        self.force ||
+            confirm(&format!(
+                "Are you sure you want to write to table {} at key {}?",
+                self.table, self.key
+            ))
+    }
+
+    /// Execute `db put` command
+    pub fn execute<N: NodeTypesWithDB>(
+        self,
+        provider_factory: ProviderFactory<N>,
+    ) -> eyre::Result<()> {
+        let value = if self.raw {
+            serde_json::from_str::<Bytes>(&self.value)?.to_vec()
+        } else {
+            tables_to_generic!(self.table, |Table| compress_json_value::<Table>(&self.value))?
+        };
+        self.table.view(&PutViewer { db: provider_factory.db_ref(), args: &self, value })
+    }
+}
+
+/// Parses the JSON value of the table and encodes it with the codec of the table.
+fn compress_json_value<T: Table>(value: &str) -> eyre::Result<Vec<u8>>
+where
+    T::Value: DeserializeOwned,
+{
+    let value = serde_json::from_str::<T::Value>(value)
+        .map_err(|err| eyre::eyre!("Value can't be parsed for table {}: {err}", T::NAME))?;
+    Ok(value.compress().into())
+}
+
+struct PutViewer<'a, DB: Database> {
+    db: &'a DB,
+    args: &'a Command,
+    /// The raw value to write, in the codec of the table.
+    value: Vec<u8>,
+}
+
+impl<DB: Database> TableViewer<()> for PutViewer<'_, DB> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<(), Self::Error> {
+        eyre::ensure!(self.args.subkey.is_none(), "Table {} has no subkeys", T::NAME);
+
+        let key = table_key::<T>(&self.args.key)?;
+        let value = decode_value::<T>(&self.value)?;
+
+        let tx = self.db.tx_mut()?;
+        let previous = tx.get::<RawTable<T>>(RawKey::new(key.clone()))?;
+        tx.put::<RawTable<T>>(RawKey::new(key), RawValue::from_vec(self.value.clone()))?;
+        tx.commit()?;
+
+        log_put(T::NAME, &value, previous.as_ref().map(RawValue::raw_value))
+    }
+
+    fn view_dupsort<T: DupSort>(&self) -> Result<(), Self::Error> {
+        let Some(subkey) = self.args.subkey.as_deref() else {
+            eyre::bail!("Table {} is a dupsort table and requires a subkey", T::NAME)
+        };
+        let key = table_key::<T>(&self.args.key)?;
+        let subkey = table_subkey::<T>(Some(subkey))?;
+        let value = decode_value::<T>(&self.value)?;
+
+        let tx = self.db.tx_mut()?;
+        let previous = put_dup::<T, _>(&tx, key, subkey, self.value.clone())?;
+        tx.commit()?;
+
+        log_put(T::NAME, &value, previous.as_deref())
+    }
+}
+
+/// Decodes the raw value with the codec of the table.
+fn decode_value<T: Table>(value: &[u8]) -> eyre::Result<T::Value> {
+    T::Value::decompress(value)
+        .map_err(|err| eyre::eyre!("Value can't be decoded for table {}: {err}", T::NAME))
+}
+
+/// Writes the raw value of the given subkey to a dupsort table, replacing the existing value of
+/// the subkey.
+///
+/// The values of dupsort tables start with their encoded subkey, so the raw value is rejected if
+/// it doesn't. Returns the replaced raw value.
+fn put_dup<T: DupSort, TX: DbTxMut>(
+    tx: &TX,
+    key: T::Key,
+    subkey: T::SubKey,
+    value: Vec<u8>,
+) -> eyre::Result<Option<Vec<u8>>> {
+    let subkey: Vec<u8> = subkey.encode().into();
+    eyre::ensure!(
+        value.starts_with(&subkey),
+        "Value doesn't start with the encoded subkey {}",
+        hex::encode_prefixed(&subkey)
+    );
+
+    let mut cursor = tx.cursor_dup_write::<RawDupSort<T>>()?;
+    let key = RawKey::new(key);
+    let previous = seek_exact_dup(&mut cursor, key.clone(), subkey)?;
+    if previous.is_some() {
+        cursor.delete_current()?;
+    }
+    cursor.upsert(key, &RawValue::from_vec(value))?;
+
+    Ok(previous)
+}
+
+/// Positions the cursor at the value of the given encoded subkey and returns the raw value, or
+/// [`None`] if the key has no value with the subkey.
+pub(crate) fn seek_exact_dup<T: DupSort, C: DbDupCursorRO<RawDupSort<T>>>(
+    cursor: &mut C,
+    key: RawKey<T::Key>,
+    subkey: Vec<u8>,
+) -> Result<Option<Vec<u8>>, DatabaseError> {
+    Ok(cursor
+        .seek_by_key_subkey(key, RawKey::from_vec(subkey.clone()))?
+        .filter(|value| value.raw_value().starts_with(&subkey))
+        .map(RawValue::into_value))
+}
+
+fn log_put<V: serde::Serialize>(
+    table: &str,
+    value: &V,
+    previous: Option<&[u8]>,
+) -> eyre::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    match previous {
+        Some(previous) => info!(
+            target: "reth::cli",
+            table,
+            previous = %hex::encode_prefixed(previous),
+            "Replaced value"
+        ),
+        None => info!(target: "reth::cli", table, "Inserted value"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, B256, U256};
+    use reth_db::test_utils::create_test_rw_db;
+    use reth_db_api::{table::Compress, PlainAccountState, PlainStorageState};
+    use reth_primitives_traits::{Account, StorageEntry};
+
+    #[test]
+    fn compress_json_values() {
+        let account = Account { nonce: 1, balance: U256::from(2), bytecode_hash: None };
+        let json = serde_json::to_string(&account).unwrap();
+        assert_eq!(
+            compress_json_value::<PlainAccountState>(&json).unwrap(),
+            Vec::from(account.compress())
+        );
+
+        // values have to match the value type of the table
+        assert!(compress_json_value::<PlainAccountState>("\"0x01\"").is_err());
+    }
+
+    #[test]
+    fn put_dup_replaces_value_of_subkey() {
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+        let address = address!("0x4448e1273fd5a8bfdb9ed111e96889c960eee145");
+        let entry = |key: u8, value: u64| StorageEntry {
+            key: B256::with_last_byte(key),
+            value: U256::from(value),
+        };
+        tx.put::<PlainStorageState>(address, entry(1, 1)).unwrap();
+        tx.put::<PlainStorageState>(address, entry(2, 2)).unwrap();
+
+        let previous = put_dup::<PlainStorageState, _>(
+            &tx,
+            address,
+            B256::with_last_byte(1),
+            entry(1, 10).compress().into(),
+        )
+        .unwrap();
+        assert_eq!(previous, Some(entry(1, 1).compress().into()));
+
+        let mut cursor = tx.cursor_dup_read::<PlainStorageState>().unwrap();
+        let values = cursor
+            .walk_dup(Some(address), None)
+            .unwrap()
+            .map(|entry| entry.unwrap().1)
+            .collect::<Vec<_>>();
+        assert_eq!(values, [entry(1, 10), entry(2, 2)]);
+
+        // the value has to start with the subkey
+        assert!(put_dup::<PlainStorageState, _>(
+            &tx,
+            address,
+            B256::with_last_byte(3),
+            entry(1, 10).compress().into(),
+        )
+        .is_err());
+    }
+}
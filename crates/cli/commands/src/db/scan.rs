@@ -0,0 +1,123 @@
+use super::get::{maybe_json_value_parser, table_key};
+use alloy_primitives::hex;
+use clap::Parser;
+use reth_db_api::{
+    cursor::DbCursorRO, database::Database, table::Table, transaction::DbTx, RawKey, RawTable,
+    TableViewer, Tables,
+};
+use reth_db_common::DbTool;
+use reth_provider::providers::ProviderNodeTypes;
+use serde_json::{json, Value};
+use std::ops::Bound;
+
+/// The arguments for the `reth db scan` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The table name
+    table: Tables,
+
+    /// The first key to scan, inclusive. Scans from the start of the table if not set.
+    #[arg(long, value_parser = maybe_json_value_parser)]
+    from: Option<String>,
+
+    /// The key to stop the scan at, exclusive. Scans to the end of the table if not set.
+    #[arg(long, value_parser = maybe_json_value_parser)]
+    to: Option<String>,
+
+    /// The maximum number of entries to return
+    #[arg(long, short, default_value_t = 10)]
+    len: usize,
+
+    /// Output bytes instead of human-readable decoded values
+    #[arg(long)]
+    raw: bool,
+}
+
+impl Command {
+    /// Execute `db scan` command
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        self.table.view(&ScanViewer { tool, args: &self })
+    }
+}
+
+struct ScanViewer<'a, N: ProviderNodeTypes> {
+    tool: &'a DbTool<N>,
+    args: &'a Command,
+}
+
+impl<N: ProviderNodeTypes> TableViewer<()> for ScanViewer<'_, N> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<(), Self::Error> {
+        let entries =
+            self.tool.provider_factory.db_ref().view(|tx| scan_table::<T, _>(tx, self.args))??;
+
+        println!("{}", serde_json::to_string_pretty(&Value::Array(entries))?);
+        Ok(())
+    }
+}
+
+/// Returns the `[key, value]` pairs of the table in the key range of the arguments.
+fn scan_table<T: Table, TX: DbTx>(tx: &TX, args: &Command) -> eyre::Result<Vec<Value>> {
+    let from = args.from.as_deref().map(table_key::<T>).transpose()?;
+    let to = args.to.as_deref().map(table_key::<T>).transpose()?;
+    let range = (
+        from.map_or(Bound::Unbounded, |key| Bound::Included(RawKey::new(key))),
+        to.map_or(Bound::Unbounded, |key| Bound::Excluded(RawKey::new(key))),
+    );
+
+    let mut cursor = tx.cursor_read::<RawTable<T>>()?;
+    cursor
+        .walk_range(range)?
+        .take(args.len)
+        .map(|entry| {
+            let (key, value) = entry?;
+            let value = if args.raw {
+                json!(hex::encode_prefixed(value.raw_value()))
+            } else {
+                serde_json::to_value(value.value()?)?
+            };
+            Ok::<_, eyre::Report>(json!([key.key()?, value]))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use reth_db::test_utils::create_test_rw_db;
+    use reth_db_api::{table::Compress, transaction::DbTxMut, CanonicalHeaders};
+
+    #[test]
+    fn scan_key_range() {
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+        for number in 0..20 {
+            tx.put::<CanonicalHeaders>(number, B256::with_last_byte(number as u8)).unwrap();
+        }
+
+        let scan = |args: &[&str]| {
+            let args =
+                Command::try_parse_from(["scan", "CanonicalHeaders"].iter().chain(args)).unwrap();
+            scan_table::<CanonicalHeaders, _>(&tx, &args).unwrap()
+        };
+
+        // the range is half-open and ordered by the encoded key, not the JSON of the key
+        let entries = scan(&["--from", "9", "--to", "12"]);
+        assert_eq!(
+            entries,
+            [9, 10, 11].map(|number| json!([number, B256::with_last_byte(number as u8)])).to_vec()
+        );
+
+        // the number of entries is limited, and unbounded ranges start at the first key
+        let entries = scan(&["--len", "2"]);
+        assert_eq!(entries, vec![json!([0, B256::ZERO]), json!([1, B256::with_last_byte(1)])]);
+
+        let entries = scan(&["--from", "19", "--raw"]);
+        let raw = hex::encode_prefixed(B256::with_last_byte(19).compress());
+        assert_eq!(entries, vec![json!([19, raw])]);
+
+        assert!(scan(&["--from", "20"]).is_empty());
+    }
+}
@@ -5,7 +5,11 @@ use eyre::WrapErr;
 use human_bytes::human_bytes;
 use itertools::Itertools;
 use reth_chainspec::EthereumHardforks;
-use reth_db::{mdbx, static_file::iter_static_files, DatabaseEnv};
+use reth_db::{
+    mdbx::{self, growth::DatabaseGrowth},
+    static_file::iter_static_files,
+    DatabaseEnv,
+};
 use reth_db_api::{database::Database, TableViewer, Tables};
 use reth_db_common::DbTool;
 use reth_fs_util as fs;
@@ -34,6 +38,17 @@ pub struct Command {
     /// For individual table checksums, use the `reth db checksum` command.
     #[arg(long, default_value_t = false)]
     checksum: bool,
+
+    /// Continuously print the size of each table and the static files with their growth rates,
+    /// and the number of days until the disk is full at the current growth rate.
+    ///
+    /// The growth rates are computed from the sizes read since the command was started.
+    #[arg(long, default_value_t = false, conflicts_with = "checksum")]
+    watch: bool,
+
+    /// The interval between two reads of the table sizes with `--watch`.
+    #[arg(long, value_name = "SECONDS", default_value_t = 10, requires = "watch")]
+    interval: u64,
 }
 
 impl Command {
@@ -43,6 +58,16 @@ impl Command {
         data_dir: ChainPath<DataDirPath>,
         tool: &DbTool<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
     ) -> eyre::Result<()> {
+        if self.watch {
+            let db = tool.provider_factory.db_ref();
+            loop {
+                let growth = db.growth()?;
+                println!("{}", Self::db_growth_table(&growth));
+                println!("{}\n", Self::disk_forecast(&growth));
+                std::thread::sleep(Duration::from_secs(self.interval));
+            }
+        }
+
         if self.checksum {
             let checksum_report = self.checksum_report(tool)?;
             println!("{checksum_report}");
@@ -143,6 +168,68 @@ impl Command {
         Ok(table)
     }
 
+    fn db_growth_table(growth: &DatabaseGrowth) -> ComfyTable {
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header(["Table Name", "# Entries", "Total Size", "Growth / Day"]);
+
+        let format_rate = |bytes_per_day: Option<f64>| match bytes_per_day {
+            Some(rate) if rate < 0.0 => format!("-{}", human_bytes(-rate)),
+            Some(rate) => human_bytes(rate),
+            None => "N/A".to_string(),
+        };
+
+        for db_table in growth.tables.iter().sorted_by_key(|table| table.stats.name) {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(db_table.stats.name))
+                .add_cell(Cell::new(db_table.stats.entries))
+                .add_cell(Cell::new(human_bytes(db_table.stats.size() as f64)))
+                .add_cell(Cell::new(format_rate(db_table.bytes_per_day)));
+            table.add_row(row);
+        }
+
+        let max_widths = table.column_max_content_widths();
+        let mut separator = Row::new();
+        for width in max_widths {
+            separator.add_cell(Cell::new("-".repeat(width as usize)));
+        }
+        table.add_row(separator);
+
+        let mut row = Row::new();
+        row.add_cell(Cell::new("Tables"))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(human_bytes(growth.total_size() as f64)))
+            .add_cell(Cell::new(format_rate(growth.bytes_per_day)));
+        table.add_row(row);
+
+        if let Some(size) = growth.static_files_size {
+            let mut row = Row::new();
+            row.add_cell(Cell::new("Static Files"))
+                .add_cell(Cell::new(""))
+                .add_cell(Cell::new(human_bytes(size as f64)))
+                .add_cell(Cell::new(format_rate(growth.static_files_bytes_per_day)));
+            table.add_row(row);
+        }
+
+        table
+    }
+
+    fn disk_forecast(growth: &DatabaseGrowth) -> String {
+        let Some(disk) = growth.disk else { return "Disk space: unknown".to_string() };
+        let space = format!(
+            "Disk space: {} of {} available",
+            human_bytes(disk.available as f64),
+            human_bytes(disk.total as f64)
+        );
+        match (growth.total_bytes_per_day(), growth.days_until_full()) {
+            (None, _) => space,
+            (_, Some(days)) => {
+                format!("{space}, full in {days:.1} days at the current growth rate")
+            }
+            (Some(_), None) => format!("{space}, the database and static files aren't growing"),
+        }
+    }
+
     fn static_files_stats_table<N: NodePrimitives>(
         &self,
         data_dir: ChainPath<DataDirPath>,
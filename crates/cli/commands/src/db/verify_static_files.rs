@@ -0,0 +1,56 @@
+use clap::Parser;
+use human_bytes::human_bytes;
+use reth_db_common::DbTool;
+use reth_provider::{
+    providers::{ProviderNodeTypes, VerificationMode},
+    StaticFileProviderFactory,
+};
+use std::time::Instant;
+use tracing::info;
+
+/// The arguments for the `reth db verify-static-files` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Hash all chunks of the static files, instead of a random sample.
+    ///
+    /// The sizes of all static files are checked in both modes.
+    #[arg(long)]
+    full: bool,
+}
+
+impl Command {
+    /// Execute `db verify-static-files` command
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        let mode = if self.full { VerificationMode::Full } else { VerificationMode::Sample };
+        let manifest = tool.provider_factory.static_file_provider().manifest()?;
+        if manifest.is_empty() {
+            println!(
+                "No static files are recorded in the manifest. Static files are recorded once \
+                 they are finalized, on the next node startup."
+            );
+            return Ok(())
+        }
+
+        info!(
+            target: "reth::cli",
+            files = manifest.len(),
+            size = human_bytes(manifest.total_size() as f64),
+            ?mode,
+            "Verifying static files"
+        );
+        let start = Instant::now();
+        let mismatches = manifest.verify(mode)?;
+        for mismatch in &mismatches {
+            println!("{mismatch}");
+        }
+
+        eyre::ensure!(
+            mismatches.is_empty(),
+            "Found {} mismatches in {} static files",
+            mismatches.len(),
+            manifest.len()
+        );
+        println!("Verified {} static files in {:?}", manifest.len(), start.elapsed());
+        Ok(())
+    }
+}
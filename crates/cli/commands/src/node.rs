@@ -1,22 +1,37 @@
 //! Main node command for launching a node
 
+use crate::{
+    common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs},
+    stage::unwind::unwind,
+};
+use alloy_primitives::BlockNumber;
 use clap::{value_parser, Args, Parser};
+use futures::future::LocalBoxFuture;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_runner::CliContext;
 use reth_cli_util::parse_socket_address;
-use reth_db::{init_db, DatabaseEnv};
+use reth_db::{init_db, mdbx::compaction::CompactionScheduler, DatabaseEnv};
 use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
 use reth_node_builder::{NodeBuilder, WithLaunchContext};
 use reth_node_core::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, NetworkArgs, PayloadBuilderArgs,
-        PruningArgs, RpcServerArgs, TxPoolArgs,
+        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, ExExArgs, NetworkArgs,
+        PayloadBuilderArgs, PruningArgs, RpcServerArgs, SyncArgs, TxPoolArgs,
     },
     node_config::NodeConfig,
     version,
 };
+use reth_node_metrics::recorder::{chain_label_executor, sync_with_chain_label, with_chain_label};
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockBodyIndicesProvider, BlockNumReader, ProviderFactory,
+    StageCheckpointReader, StaticFileProviderFactory,
+};
+use reth_stages::StageId;
+use reth_static_file_types::StaticFileSegment;
+use reth_tasks::TaskExecutor;
 use std::{ffi::OsString, fmt, future::Future, net::SocketAddr, path::PathBuf, sync::Arc};
+use tracing::info;
 
 /// Start the node
 #[derive(Debug, Parser)]
@@ -42,6 +57,22 @@ pub struct NodeCommand<
     )]
     pub chain: Arc<C::ChainSpec>,
 
+    /// Additional chains to run in the same process, e.g. an L1 next to a rollup.
+    ///
+    /// Every additional chain runs as a separate node that shares the runtime of the process. The
+    /// ports of the n-th additional chain are configured as if it was started with `--instance`
+    /// increased by n, its data is stored in the directory of the chain inside of `--datadir`,
+    /// and its metrics are served on the `--metrics` endpoint with a `chain` label.
+    ///
+    /// The chain is launched by the first additional chain launcher of the binary that supports
+    /// it, see `Cli::run_multi`.
+    #[arg(
+        long = "chain.additional",
+        value_name = "CHAIN_OR_PATH",
+        conflicts_with = "with_unused_ports"
+    )]
+    pub additional_chains: Vec<String>,
+
     /// Enable Prometheus metrics.
     ///
     /// The metrics will be served at the given interface and port.
@@ -111,6 +142,18 @@ pub struct NodeCommand<
     #[command(flatten, next_help_heading = "Engine")]
     pub engine: EngineArgs,
 
+    /// Sync cli arguments
+    #[command(flatten, next_help_heading = "Sync")]
+    pub sync: SyncArgs,
+
+    /// ExEx cli arguments
+    #[command(flatten, next_help_heading = "ExEx")]
+    pub exex: ExExArgs,
+
+    /// Rollback cli arguments
+    #[command(flatten, next_help_heading = "Rollback")]
+    pub rollback: RollbackArgs,
+
     /// Additional cli arguments
     #[command(flatten, next_help_heading = "Extension")]
     pub ext: Ext,
@@ -148,10 +191,71 @@ impl<
     {
         tracing::info!(target: "reth::cli", version = ?version::SHORT_VERSION, "Starting reth");
 
+        let (node_config, additional_chains, ext) = self.into_node_config()?;
+        eyre::ensure!(
+            additional_chains.is_empty(),
+            "additional chains are not supported by this launcher, see `execute_multi`"
+        );
+        let builder = node_builder(node_config, ctx.task_executor)?;
+
+        launcher(builder, ext).await
+    }
+
+    /// Launches the node and a node for every additional chain, see `--chain.additional`.
+    ///
+    /// Every additional chain is launched by the first of the `additional` launchers that supports
+    /// it, so the additional chains may have other chain spec and node types than the node, e.g.
+    /// an L1 next to a rollup. The metrics of every node are labeled with its chain, and the nodes
+    /// run until all of them exit or one of them fails.
+    pub async fn execute_multi<L, Fut>(
+        self,
+        ctx: CliContext,
+        launcher: L,
+        additional: Vec<AdditionalChainLauncher>,
+    ) -> eyre::Result<()>
+    where
+        L: FnOnce(WithLaunchContext<NodeBuilder<Arc<DatabaseEnv>, C::ChainSpec>>, Ext) -> Fut,
+        Fut: Future<Output = eyre::Result<()>>,
+    {
+        tracing::info!(target: "reth::cli", version = ?version::SHORT_VERSION, "Starting reth");
+
+        let (node_config, additional_chains, ext) = self.into_node_config()?;
+        let base_config = node_config.clone().map_chainspec(|_| ());
+
+        let chain = node_config.chain.chain().to_string();
+        let executor = chain_label_executor(ctx.task_executor.clone(), chain.clone());
+        let builder = sync_with_chain_label(chain.clone(), || node_builder(node_config, executor))?;
+        let mut launches: Vec<LocalBoxFuture<'_, eyre::Result<()>>> =
+            vec![Box::pin(with_chain_label(chain, launcher(builder, ext)))];
+
+        for (index, chain) in (1..).zip(additional_chains) {
+            let launch = additional
+                .iter()
+                .find_map(|additional| (additional.launch)(&chain, &base_config, index, &ctx))
+                .ok_or_else(|| eyre::eyre!("no launcher supports additional chain {chain}"))??;
+            launches.push(launch);
+        }
+
+        futures::future::try_join_all(launches).await?;
+        Ok(())
+    }
+
+    /// Transforms the node command into the config of the node, the additional chains and the
+    /// extension arguments.
+    ///
+    /// Returns an error if `--rollback` is set, which must be executed with
+    /// [`NodeCommand::rollback`] instead of launching the node.
+    fn into_node_config(self) -> eyre::Result<(NodeConfig<C::ChainSpec>, Vec<String>, Ext)> {
+        eyre::ensure!(
+            !self.is_rollback(),
+            "--rollback must be executed with `NodeCommand::rollback` instead of launching the node"
+        );
+
         let Self {
             datadir,
             config,
             chain,
+            additional_chains,
             metrics,
             instance,
             with_unused_ports,
@@ -165,6 +269,9 @@ impl<
             pruning,
             ext,
             engine,
+            sync,
+            exex,
+            rollback: _,
         } = self;
 
         // set up node config
@@ -183,24 +290,185 @@ impl<
             dev,
             pruning,
             engine,
+            sync,
+            exex,
         };
 
-        let data_dir = node_config.datadir();
-        let db_path = data_dir.db();
-
-        tracing::info!(target: "reth::cli", path = ?db_path, "Opening database");
-        let database = Arc::new(init_db(db_path.clone(), self.db.database_args())?.with_metrics());
-
         if with_unused_ports {
             node_config = node_config.with_unused_ports();
         }
 
-        let builder = NodeBuilder::new(node_config)
-            .with_database(database)
-            .with_launch_context(ctx.task_executor);
+        Ok((node_config, additional_chains, ext))
+    }
 
-        launcher(builder, ext).await
+    /// Returns whether `--rollback` is set, in which case [`NodeCommand::rollback`] is executed
+    /// instead of launching the node.
+    pub const fn is_rollback(&self) -> bool {
+        self.rollback.target.is_some()
     }
+
+    /// Unwinds the storage of all stages to the block of `--rollback`, without launching the
+    /// node.
+    ///
+    /// With `--rollback.dry-run`, the storage is opened read-only and only the data that would be
+    /// removed is logged.
+    pub async fn rollback<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        let Some(target) = self.rollback.target else { return Ok(()) };
+        let env = EnvironmentArgs::<C> {
+            datadir: self.datadir,
+            config: self.config,
+            chain: self.chain,
+            db: self.db,
+        };
+        let access = if self.rollback.dry_run { AccessRights::RO } else { AccessRights::RW };
+        let Environment { provider_factory, config, .. } = env.init::<N>(access)?;
+
+        log_rollback(&provider_factory, target)?;
+        if self.rollback.dry_run {
+            info!(target: "reth::cli", %target, "Dry run, the storage was not modified");
+            return Ok(())
+        }
+
+        unwind(provider_factory, config, target, false)?;
+        info!(target: "reth::cli", %target, "Rolled back, the node can be started again");
+        Ok(())
+    }
+}
+
+/// Opens the database of the node and returns the builder to launch it with the executor.
+fn node_builder<ChainSpec: EthChainSpec>(
+    node_config: NodeConfig<ChainSpec>,
+    executor: TaskExecutor,
+) -> eyre::Result<WithLaunchContext<NodeBuilder<Arc<DatabaseEnv>, ChainSpec>>> {
+    let data_dir = node_config.datadir();
+    let db_path = data_dir.db();
+
+    let chain = node_config.chain.chain();
+    tracing::info!(target: "reth::cli", %chain, path = ?db_path, "Opening database");
+    let db_args =
+        node_config.db.database_args().with_static_files_path(Some(data_dir.static_files()));
+    let database = Arc::new(init_db(db_path.clone(), db_args)?.with_metrics());
+
+    if let Some(config) = node_config.db.compaction_config() {
+        tracing::info!(target: "reth::cli", ?config, "Starting database compaction scheduler");
+        CompactionScheduler::new(database.clone(), db_path.clone(), config).spawn();
+    }
+
+    Ok(NodeBuilder::new(node_config).with_database(database).with_launch_context(executor))
+}
+
+/// The launch of the node of an additional chain, returned by [`AdditionalChainLauncher`].
+type AdditionalChainLaunch = eyre::Result<LocalBoxFuture<'static, eyre::Result<()>>>;
+
+/// Launches the nodes of the additional chains that are supported by a chain spec parser, see
+/// `--chain.additional` and [`NodeCommand::execute_multi`].
+///
+/// The chain spec and node types of the additional chains may differ from the ones of the node
+/// they run next to.
+pub struct AdditionalChainLauncher {
+    /// Launches the given additional chain with the given index, based on the config of the node,
+    /// or returns [`None`] if the chain isn't supported.
+    #[allow(clippy::type_complexity)]
+    launch: Box<dyn Fn(&str, &NodeConfig<()>, u16, &CliContext) -> Option<AdditionalChainLaunch>>,
+}
+
+impl AdditionalChainLauncher {
+    /// Creates a launcher of the additional chains that are parsed by the chain spec parser `C`.
+    ///
+    /// The closure is called once for every additional chain it launches.
+    pub fn new<C, L, Fut>(launcher: L) -> Self
+    where
+        C: ChainSpecParser<ChainSpec: EthChainSpec>,
+        L: Fn(WithLaunchContext<NodeBuilder<Arc<DatabaseEnv>, C::ChainSpec>>) -> Fut + 'static,
+        Fut: Future<Output = eyre::Result<()>> + 'static,
+    {
+        let launch = move |chain: &str, base_config: &NodeConfig<()>, index, ctx: &CliContext| {
+            let chain_spec = C::parse(chain).ok()?;
+            let chain = chain_spec.chain().to_string();
+            let node_config = match base_config.for_additional_chain(chain_spec, index) {
+                Ok(node_config) => node_config,
+                Err(err) => return Some(Err(err)),
+            };
+            let executor = chain_label_executor(ctx.task_executor.clone(), chain.clone());
+            let builder =
+                sync_with_chain_label(chain.clone(), || node_builder(node_config, executor));
+            let builder = match builder {
+                Ok(builder) => builder,
+                Err(err) => return Some(Err(err)),
+            };
+            let launch: LocalBoxFuture<'static, _> =
+                Box::pin(with_chain_label(chain, launcher(builder)));
+            Some(Ok(launch))
+        };
+        Self { launch: Box::new(launch) }
+    }
+}
+
+impl fmt::Debug for AdditionalChainLauncher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdditionalChainLauncher").finish_non_exhaustive()
+    }
+}
+
+/// Parameters for rolling back the storage of the node, e.g. to recover from a bad upgrade without
+/// resyncing.
+#[derive(Debug, Clone, Copy, Default, Args, PartialEq, Eq)]
+pub struct RollbackArgs {
+    /// Unwinds the database and static files of all stages to the given block and exits without
+    /// starting the node.
+    ///
+    /// The given block stays in the database. The node must not be running.
+    #[arg(long = "rollback", value_name = "BLOCK", conflicts_with = "additional_chains")]
+    pub target: Option<BlockNumber>,
+
+    /// Logs the data that `--rollback` would remove, without modifying the storage.
+    #[arg(long = "rollback.dry-run", requires = "target")]
+    pub dry_run: bool,
+}
+
+/// Logs the data that is removed by rolling back to the given block.
+fn log_rollback<N: ProviderNodeTypes>(
+    provider_factory: &ProviderFactory<N>,
+    target: BlockNumber,
+) -> eyre::Result<()> {
+    let provider = provider_factory.provider()?;
+    let last = provider.last_block_number()?;
+    eyre::ensure!(target <= last, "rollback target {target} is above the latest block {last}");
+
+    let next_tx_num = |block| -> eyre::Result<u64> {
+        Ok(provider
+            .block_body_indices(block)?
+            .ok_or_else(|| eyre::eyre!("body indices of block {block} not found"))?
+            .next_tx_num())
+    };
+    info!(
+        target: "reth::cli",
+        %target,
+        last,
+        blocks = last - target,
+        transactions = next_tx_num(last)? - next_tx_num(target)?,
+        "Rolling back blocks"
+    );
+
+    for stage in StageId::ALL {
+        let checkpoint = provider.get_stage_checkpoint(stage)?.unwrap_or_default();
+        if checkpoint.block_number > target {
+            let checkpoint = checkpoint.block_number;
+            info!(target: "reth::cli", %stage, checkpoint, "Unwinding stage");
+        }
+    }
+
+    let static_file_provider = provider_factory.static_file_provider();
+    for segment in StaticFileSegment::iter() {
+        if let Some(highest) = static_file_provider
+            .get_highest_static_file_block(segment)
+            .filter(|highest| *highest > target)
+        {
+            info!(target: "reth::cli", %segment, highest, "Truncating static files");
+        }
+    }
+
+    Ok(())
 }
 
 /// No Additional arguments
@@ -211,6 +479,7 @@ pub struct NoArgs;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use reth_cli_runner::CliRunner;
     use reth_discv4::DEFAULT_DISCOVERY_PORT;
     use reth_ethereum_cli::chainspec::SUPPORTED_CHAINS;
     use std::{
@@ -233,6 +502,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_rollback() {
+        let cmd: NodeCommand =
+            NodeCommand::try_parse_args_from(["reth", "--rollback", "100", "--rollback.dry-run"])
+                .unwrap();
+        assert_eq!(cmd.rollback, RollbackArgs { target: Some(100), dry_run: true });
+        assert!(cmd.is_rollback());
+
+        let cmd: NodeCommand = NodeCommand::try_parse_args_from(["reth"]).unwrap();
+        assert!(!cmd.is_rollback());
+        assert!(NodeCommand::<EthereumChainSpecParser>::try_parse_args_from([
+            "reth",
+            "--rollback.dry-run"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn execute_rejects_rollback() {
+        let cmd: NodeCommand =
+            NodeCommand::try_parse_args_from(["reth", "--rollback", "100"]).unwrap();
+        let err = CliRunner::try_default_runtime()
+            .unwrap()
+            .run_command_until_exit(|ctx| {
+                cmd.execute(ctx, |_, _| async {
+                    Err::<(), _>(eyre::eyre!("the node must not be launched"))
+                })
+            })
+            .unwrap_err();
+        assert!(err.to_string().contains("--rollback"), "{err}");
+    }
+
     #[test]
     fn parse_discovery_addr() {
         let cmd: NodeCommand =
@@ -353,6 +654,33 @@ mod tests {
         assert_eq!(cmd.network.port, 30305);
     }
 
+    #[test]
+    fn parse_additional_chains() {
+        let cmd: NodeCommand = NodeCommand::parse_from([
+            "reth",
+            "--chain",
+            "mainnet",
+            "--datadir",
+            "my/custom/path",
+            "--chain.additional",
+            "sepolia",
+            "--chain.additional",
+            "holesky",
+        ]);
+        let (mainnet, additional_chains, _) = cmd.into_node_config();
+        assert_eq!(additional_chains, vec!["sepolia", "holesky"]);
+
+        let [sepolia, holesky] = [(1, "sepolia"), (2, "holesky")].map(|(index, chain)| {
+            let chain = EthereumChainSpecParser::parse(chain).unwrap();
+            mainnet.for_additional_chain(chain, index).unwrap()
+        });
+        assert_eq!(mainnet.datadir().db(), Path::new("my/custom/path/db"));
+        assert_eq!(sepolia.datadir().db(), Path::new("my/custom/path/sepolia/db"));
+        assert_eq!(holesky.datadir().db(), Path::new("my/custom/path/holesky/db"));
+        assert_eq!([mainnet.instance, sepolia.instance, holesky.instance], [1, 2, 3]);
+        assert!(sepolia.metrics.is_none());
+    }
+
     #[test]
     fn parse_with_unused_ports() {
         let cmd: NodeCommand = NodeCommand::parse_from(["reth", "--with-unused-ports"]);
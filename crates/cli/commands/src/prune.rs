@@ -1,10 +1,13 @@
 //! Command that runs pruning without any limits.
 use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
-use clap::Parser;
+use alloy_primitives::BlockNumber;
+use clap::{Parser, ValueEnum};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
-use reth_prune::PrunerBuilder;
+use reth_provider::BlockNumReader;
+use reth_prune::{PruneMode, PruneModes, PruneSegment, PrunerBuilder};
 use reth_static_file::StaticFileProducer;
+use std::collections::BTreeMap;
 use tracing::info;
 
 /// Prunes according to the configuration without any limits
@@ -12,13 +15,54 @@ use tracing::info;
 pub struct PruneCommand<C: ChainSpecParser> {
     #[command(flatten)]
     env: EnvironmentArgs<C>,
+
+    /// Prune only the given segments instead of the ones from the prune configuration.
+    #[arg(long, value_delimiter = ',', requires = "before")]
+    segments: Vec<PruneSegmentArg>,
+
+    /// Prune the data of the given segments before this block. The block itself is not pruned.
+    #[arg(long, value_name = "BLOCK_NUMBER", requires = "segments")]
+    before: Option<BlockNumber>,
+
+    /// Maximum number of entries deleted per batch. Progress is reported after every batch.
+    #[arg(long, value_name = "ENTRIES", default_value_t = 1_000_000)]
+    batch_size: usize,
+}
+
+/// Segment of the data that can be pruned on demand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PruneSegmentArg {
+    /// The transaction senders.
+    SenderRecovery,
+    /// The transaction hash to number lookup.
+    Txlookup,
+    /// The receipts.
+    Receipts,
+    /// The account history.
+    AccountHistory,
+    /// The storage history.
+    StorageHistory,
+}
+
+impl PruneSegmentArg {
+    /// Sets the prune mode of the segment in the given [`PruneModes`].
+    const fn apply(self, modes: &mut PruneModes, mode: PruneMode) {
+        let segment = match self {
+            Self::SenderRecovery => &mut modes.sender_recovery,
+            Self::Txlookup => &mut modes.transaction_lookup,
+            Self::Receipts => &mut modes.receipts,
+            Self::AccountHistory => &mut modes.account_history,
+            Self::StorageHistory => &mut modes.storage_history,
+        };
+        *segment = Some(mode);
+    }
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> PruneCommand<C> {
     /// Execute the `prune` command
     pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
         let Environment { config, provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
-        let prune_config = config.prune.unwrap_or_default();
+        let mut prune_config = config.prune.unwrap_or_default();
 
         // Copy data from database to static files
         info!(target: "reth::cli", "Copying data from database to static files...");
@@ -28,18 +72,69 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> PruneComma
             static_file_producer.lock().copy_to_static_files()?.min_block_num();
         info!(target: "reth::cli", ?lowest_static_file_height, "Copied data from database to static files");
 
+        // Prune the requested segments up to the given block, independent of the static files.
+        let prune_tip = if let Some(before) = self.before {
+            let mut segments = PruneModes::none();
+            for segment in &self.segments {
+                segment.apply(&mut segments, PruneMode::Before(before));
+            }
+            prune_config.segments = segments;
+
+            Some(provider_factory.best_block_number()?)
+        } else {
+            lowest_static_file_height
+        };
+
         // Delete data which has been copied to static files.
-        if let Some(prune_tip) = lowest_static_file_height {
+        if let Some(prune_tip) = prune_tip {
             info!(target: "reth::cli", ?prune_tip, ?prune_config, "Pruning data from database...");
-            // Run the pruner according to the configuration, and don't enforce any limits on it
+            // Run the pruner according to the configuration in batches, and don't enforce any
+            // other limits on it
             let mut pruner = PrunerBuilder::new(prune_config)
-                .delete_limit(usize::MAX)
+                .delete_limit(self.batch_size)
                 .build_with_provider_factory(provider_factory);
 
-            pruner.run(prune_tip)?;
+            let mut pruned = BTreeMap::<PruneSegment, usize>::new();
+            loop {
+                let output = pruner.run(prune_tip)?;
+                for (segment, segment_output) in &output.segments {
+                    *pruned.entry(*segment).or_default() += segment_output.pruned;
+                }
+                info!(target: "reth::cli", progress = %output.progress, ?pruned, "Pruned batch");
+
+                if output.progress.is_finished() {
+                    break
+                }
+            }
             info!(target: "reth::cli", "Pruned data from database");
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_segments() {
+        let cmd = PruneCommand::<EthereumChainSpecParser>::parse_from([
+            "reth",
+            "--segments",
+            "receipts,txlookup",
+            "--before",
+            "100",
+        ]);
+        assert_eq!(cmd.segments, vec![PruneSegmentArg::Receipts, PruneSegmentArg::Txlookup]);
+        assert_eq!(cmd.before, Some(100));
+
+        assert!(PruneCommand::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "--segments",
+            "receipts"
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,385 @@
+//! `reth stage backfill-receipts` command
+
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::{BlockHeader, ReceiptWithBloom};
+use alloy_primitives::{BlockNumber, TxNumber, B256};
+use clap::Parser;
+use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_util::get_secret_key;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_eth_wire::{GetReceipts, NetPrimitivesFor, Receipts};
+use reth_network::{NetworkHandle, PeerRequest, Peers};
+use reth_network_api::ReputationChangeKind;
+use reth_node_api::{HeaderTy, ReceiptTy};
+use reth_node_core::args::NetworkArgs;
+use reth_primitives_traits::{NodePrimitives, SealedHeader};
+use reth_provider::{
+    BlockBodyIndicesProvider, DBProvider, DatabaseProviderFactory, HeaderProvider,
+    StageCheckpointReader, StaticFileProviderFactory,
+};
+use reth_prune::PruneSegment;
+use reth_stages::StageId;
+use reth_static_file_types::StaticFileSegment;
+use std::{ops::RangeInclusive, time::Duration};
+use tokio::sync::oneshot;
+use tracing::*;
+
+/// The number of requests in a row that don't yield any receipts after which the backfill fails.
+const MAX_FAILED_REQUESTS: u32 = 32;
+
+/// The backoff after a request that didn't yield any receipts, multiplied by the number of such
+/// requests in a row.
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// The maximum backoff in between two requests.
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(10);
+
+/// `reth stage backfill-receipts` command
+///
+/// A node with receipts pruning stores the receipts in the database instead of static files, and
+/// deletes the pruned ones. This command fills the receipts static files from their highest block
+/// up to the last executed block: the receipts that are still in the database are copied, and the
+/// pruned ones are requested from peers and validated against the receipts root of their header.
+///
+/// It must run before the receipts pruning is removed from the configuration. Once all receipts
+/// are in static files, they're removed from the database together with the receipts prune
+/// checkpoints, and the node can be started without receipts pruning.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    #[command(flatten)]
+    network: NetworkArgs,
+
+    /// The last block to backfill, defaults to the last executed block.
+    ///
+    /// The backfill resumes from the highest block in the receipts static files, so a large range
+    /// can be backfilled over multiple runs.
+    #[arg(long, value_name = "BLOCK")]
+    to: Option<BlockNumber>,
+
+    /// The number of blocks whose receipts are requested from a peer at once.
+    #[arg(long, default_value_t = 64, value_parser = clap::value_parser!(u64).range(1..))]
+    batch_size: u64,
+}
+
+/// The receipts of a block that is backfilled.
+#[derive(Debug)]
+struct BlockReceipts<H, R> {
+    /// The header of the block.
+    header: SealedHeader<H>,
+    /// The number of the first transaction of the block.
+    first_tx_num: TxNumber,
+    /// The receipts of the block, or [`None`] if they were pruned from the database.
+    receipts: Option<Vec<R>>,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>> Command<C> {
+    /// Execute `stage backfill-receipts` command
+    ///
+    /// The receipts fetched from peers are validated with the given function, which calculates the
+    /// receipts root of a block of the chain.
+    pub async fn execute<N, P, R>(self, receipts_root: R) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        P: NetPrimitivesFor<N::Primitives>,
+        R: Fn(&C::ChainSpec, &HeaderTy<N>, &[ReceiptTy<N>]) -> B256,
+    {
+        let Environment { provider_factory, mut config, data_dir } =
+            self.env.init::<N>(AccessRights::RW)?;
+        let static_file_provider = provider_factory.static_file_provider();
+
+        let start = static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::Receipts)
+            .map_or(0, |block| block + 1);
+        let last_executed = provider_factory
+            .provider()?
+            .get_stage_checkpoint(StageId::Execution)?
+            .unwrap_or_default()
+            .block_number;
+        let end = self.to.unwrap_or(last_executed);
+        if end > last_executed {
+            eyre::bail!("block {end} is above the last executed block {last_executed}")
+        }
+        if start > end {
+            info!(target: "reth::cli", start, end, "Receipts are already in static files");
+            return Ok(())
+        }
+
+        config.peers.trusted_nodes_only = self.network.trusted_only;
+        config.peers.trusted_nodes.extend(self.network.trusted_peers.clone());
+
+        let network_secret_path =
+            self.network.p2p_secret_key.clone().unwrap_or_else(|| data_dir.p2p_secret());
+        let p2p_secret_key = get_secret_key(&network_secret_path)?;
+
+        let network = self
+            .network
+            .network_config::<P>(
+                &config,
+                provider_factory.chain_spec(),
+                p2p_secret_key,
+                data_dir.known_peers(),
+            )
+            .build(provider_factory.clone())
+            .start_network()
+            .await?;
+
+        info!(target: "reth::cli", start, end, "Backfilling receipts");
+
+        let chain_spec = provider_factory.chain_spec();
+        let receipts_root = |header: &HeaderTy<N>, receipts: &[ReceiptTy<N>]| {
+            receipts_root(&chain_spec, header, receipts)
+        };
+
+        let mut block = start;
+        while block <= end {
+            let range = block..=end.min(block + self.batch_size - 1);
+
+            let mut blocks = read_blocks::<N>(&provider_factory.provider()?, range.clone())?;
+            let pruned = blocks
+                .iter()
+                .filter(|block| block.receipts.is_none())
+                .map(|block| block.header.clone())
+                .collect::<Vec<_>>();
+            let mut fetched = fetch_receipts::<N::Primitives, P>(&network, &pruned, &receipts_root)
+                .await?
+                .into_iter();
+            for block in blocks.iter_mut().filter(|block| block.receipts.is_none()) {
+                block.receipts = fetched.next();
+            }
+
+            let mut writer = static_file_provider.latest_writer(StaticFileSegment::Receipts)?;
+            for block in blocks {
+                writer.increment_block(block.header.number())?;
+                for (idx, receipt) in block.receipts.unwrap_or_default().iter().enumerate() {
+                    writer.append_receipt(block.first_tx_num + idx as u64, receipt)?;
+                }
+            }
+            writer.commit()?;
+
+            info!(target: "reth::cli", block = range.end(), end, "Backfilled receipts");
+            block = range.end() + 1;
+        }
+
+        if end == last_executed {
+            let provider_rw = provider_factory.database_provider_rw()?;
+            provider_rw.tx_ref().clear::<tables::Receipts<ReceiptTy<N>>>()?;
+            for segment in [PruneSegment::Receipts, PruneSegment::ContractLogs] {
+                provider_rw.tx_ref().delete::<tables::PruneCheckpoints>(segment, None)?;
+            }
+            provider_rw.commit()?;
+
+            info!(
+                target: "reth::cli",
+                "All receipts are in static files, remove the receipts pruning from the \
+                 configuration before starting the node"
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads the headers of the blocks in the range, and the receipts that are still in the database.
+///
+/// The receipts of a block are only taken from the database if all of them are present, since
+/// the contract logs pruning keeps the receipts of some transactions only.
+fn read_blocks<N: CliNodeTypes>(
+    provider: &(impl DBProvider + HeaderProvider<Header = HeaderTy<N>> + BlockBodyIndicesProvider),
+    range: RangeInclusive<BlockNumber>,
+) -> eyre::Result<Vec<BlockReceipts<HeaderTy<N>, ReceiptTy<N>>>> {
+    let headers = provider.sealed_headers_range(range.clone())?;
+    let indices = provider.block_body_indices_range(range.clone())?;
+    if headers.len() != indices.len() || headers.len() as u64 != range.end() - range.start() + 1 {
+        eyre::bail!("missing headers or bodies of blocks {range:?}")
+    }
+
+    let mut cursor = provider.tx_ref().cursor_read::<tables::Receipts<ReceiptTy<N>>>()?;
+    headers
+        .into_iter()
+        .zip(indices)
+        .map(|(header, indices)| {
+            let receipts = cursor
+                .walk_range(indices.tx_num_range())?
+                .map(|entry| entry.map(|(_, receipt)| receipt))
+                .collect::<Result<Vec<_>, _>>()?;
+            let complete = receipts.len() as u64 == indices.tx_count();
+            Ok(BlockReceipts {
+                header,
+                first_tx_num: indices.first_tx_num(),
+                receipts: complete.then_some(receipts),
+            })
+        })
+        .collect()
+}
+
+/// Requests the receipts of the given blocks from the connected peers, until every block has
+/// receipts matching the receipts root of its header.
+///
+/// Peers that send receipts with a different root are penalized. An empty response for a block is
+/// expected from peers that pruned it, and the request is retried with another peer after a
+/// backoff. Fails if [`MAX_FAILED_REQUESTS`] requests in a row don't yield any receipts.
+async fn fetch_receipts<N: NodePrimitives, P: NetPrimitivesFor<N>>(
+    network: &NetworkHandle<P>,
+    headers: &[SealedHeader<N::BlockHeader>],
+    receipts_root: impl Fn(&N::BlockHeader, &[N::Receipt]) -> B256,
+) -> eyre::Result<Vec<Vec<N::Receipt>>> {
+    let mut receipts = Vec::with_capacity(headers.len());
+    let mut attempt = 0;
+    let mut failed_requests = 0;
+    while receipts.len() < headers.len() {
+        if failed_requests >= MAX_FAILED_REQUESTS {
+            eyre::bail!(
+                "no peer sent the receipts of block {} in {failed_requests} requests",
+                headers[receipts.len()].number()
+            )
+        }
+        if failed_requests > 0 {
+            tokio::time::sleep((RETRY_BACKOFF * failed_requests).min(MAX_RETRY_BACKOFF)).await;
+        }
+
+        let peers = network.get_all_peers().await?;
+        let Some(peer) = peers.get(attempt % peers.len().max(1)) else {
+            debug!(target: "reth::cli", "Waiting for peers");
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            continue
+        };
+        let peer_id = peer.remote_id;
+        attempt += 1;
+
+        let remaining = &headers[receipts.len()..];
+        let (response, rx) = oneshot::channel();
+        network.send_request(
+            peer_id,
+            PeerRequest::GetReceipts {
+                request: GetReceipts(remaining.iter().map(|header| header.hash()).collect()),
+                response,
+            },
+        );
+        let Receipts(response) = match rx.await {
+            Ok(Ok(response)) => response,
+            Ok(Err(err)) => {
+                debug!(target: "reth::cli", %peer_id, %err, "Failed to request receipts");
+                failed_requests += 1;
+                continue
+            }
+            Err(_) => {
+                failed_requests += 1;
+                continue
+            }
+        };
+
+        let (valid, invalid_block) = validate_receipts(remaining, response, &receipts_root);
+        if let Some(block) = invalid_block {
+            warn!(target: "reth::cli", %peer_id, block, "Peer sent receipts with an invalid root");
+            network.reputation_change(peer_id, ReputationChangeKind::BadMessage);
+        }
+
+        if valid.is_empty() {
+            failed_requests += 1;
+        } else {
+            failed_requests = 0;
+            receipts.extend(valid);
+        }
+    }
+
+    Ok(receipts)
+}
+
+/// Returns the receipts of the leading blocks of the response that match the receipts root of
+/// their header, and the number of the first block whose receipts have an invalid root, if any.
+///
+/// The response ends at the first block without receipts, which is sent by peers that pruned it.
+fn validate_receipts<H: BlockHeader, R>(
+    headers: &[SealedHeader<H>],
+    response: Vec<Vec<ReceiptWithBloom<R>>>,
+    receipts_root: impl Fn(&H, &[R]) -> B256,
+) -> (Vec<Vec<R>>, Option<BlockNumber>) {
+    let mut valid = Vec::new();
+    for (header, block_receipts) in headers.iter().zip(response) {
+        if block_receipts.is_empty() {
+            break
+        }
+        let block_receipts =
+            block_receipts.into_iter().map(|receipt| receipt.receipt).collect::<Vec<_>>();
+        if receipts_root(header.header(), &block_receipts) != header.receipts_root() {
+            return (valid, Some(header.number()))
+        }
+        valid.push(block_receipts);
+    }
+    (valid, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Header, TxReceipt};
+    use reth_ethereum_primitives::Receipt;
+
+    fn block(number: BlockNumber, receipts: &[Receipt]) -> SealedHeader {
+        SealedHeader::seal_slow(Header {
+            number,
+            receipts_root: Receipt::calculate_receipt_root_no_memo(receipts),
+            ..Default::default()
+        })
+    }
+
+    fn receipt(cumulative_gas_used: u64) -> Receipt {
+        Receipt { cumulative_gas_used, success: true, ..Default::default() }
+    }
+
+    fn response(blocks: &[&[Receipt]]) -> Vec<Vec<ReceiptWithBloom<Receipt>>> {
+        blocks
+            .iter()
+            .map(|receipts| {
+                receipts.iter().map(|receipt| receipt.clone().into_with_bloom()).collect()
+            })
+            .collect()
+    }
+
+    fn receipts_root(_: &Header, receipts: &[Receipt]) -> B256 {
+        Receipt::calculate_receipt_root_no_memo(receipts)
+    }
+
+    #[test]
+    fn validates_receipts_against_root() {
+        let first = [receipt(21_000)];
+        let second = [receipt(21_000), receipt(42_000)];
+        let headers = [block(1, &first), block(2, &second)];
+
+        let (valid, invalid) =
+            validate_receipts(&headers, response(&[&first, &second]), receipts_root);
+        assert_eq!(valid, vec![first.to_vec(), second.to_vec()]);
+        assert_eq!(invalid, None);
+    }
+
+    #[test]
+    fn stops_at_pruned_block() {
+        let first = [receipt(21_000)];
+        let second = [receipt(21_000)];
+        let headers = [block(1, &first), block(2, &second)];
+
+        let (valid, invalid) = validate_receipts(&headers, response(&[&first, &[]]), receipts_root);
+        assert_eq!(valid, vec![first.to_vec()]);
+        assert_eq!(invalid, None);
+    }
+
+    #[test]
+    fn rejects_invalid_root() {
+        let first = [receipt(21_000)];
+        let second = [receipt(21_000)];
+        let headers = [block(1, &first), block(2, &second)];
+
+        let (valid, invalid) =
+            validate_receipts(&headers, response(&[&first, &[receipt(1)]]), receipts_root);
+        assert_eq!(valid, vec![first.to_vec()]);
+        assert_eq!(invalid, Some(2));
+    }
+}
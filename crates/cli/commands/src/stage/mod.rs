@@ -3,12 +3,15 @@
 use std::sync::Arc;
 
 use crate::common::{CliNodeComponents, CliNodeTypes};
+use alloy_primitives::B256;
 use clap::{Parser, Subcommand};
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_runner::CliContext;
 use reth_eth_wire::NetPrimitivesFor;
+use reth_node_api::{HeaderTy, ReceiptTy};
 
+pub mod backfill_receipts;
 pub mod drop;
 pub mod dump;
 pub mod run;
@@ -37,22 +40,36 @@ pub enum Subcommands<C: ChainSpecParser> {
     Dump(dump::Command<C>),
     /// Unwinds a certain block range, deleting it from the database.
     Unwind(unwind::Command<C>),
+    /// Re-download the pruned receipts from peers and write all receipts to static files.
+    BackfillReceipts(backfill_receipts::Command<C>),
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>> Command<C> {
     /// Execute `stage` command
-    pub async fn execute<N, Comp, F, P>(self, ctx: CliContext, components: F) -> eyre::Result<()>
+    ///
+    /// The receipts root function calculates the receipts root of a block of the chain, see
+    /// [`backfill_receipts::Command::execute`].
+    pub async fn execute<N, Comp, F, P, R>(
+        self,
+        ctx: CliContext,
+        components: F,
+        receipts_root: R,
+    ) -> eyre::Result<()>
     where
         N: CliNodeTypes<ChainSpec = C::ChainSpec>,
         Comp: CliNodeComponents<N>,
         F: FnOnce(Arc<C::ChainSpec>) -> Comp,
         P: NetPrimitivesFor<N::Primitives>,
+        R: Fn(&C::ChainSpec, &HeaderTy<N>, &[ReceiptTy<N>]) -> B256,
     {
         match self.command {
             Subcommands::Run(command) => command.execute::<N, _, _, P>(ctx, components).await,
             Subcommands::Drop(command) => command.execute::<N>().await,
             Subcommands::Dump(command) => command.execute::<N, _, _>(components).await,
             Subcommands::Unwind(command) => command.execute::<N>().await,
+            Subcommands::BackfillReceipts(command) => {
+                command.execute::<N, P, _>(receipts_root).await
+            }
         }
     }
 }
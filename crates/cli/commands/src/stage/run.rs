@@ -263,18 +263,23 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                     None,
                 ),
                 StageEnum::Execution => (
-                    Box::new(ExecutionStage::new(
-                        components.executor().clone(),
-                        Arc::new(components.consensus().clone()),
-                        ExecutionStageThresholds {
-                            max_blocks: Some(batch_size),
-                            max_changes: None,
-                            max_cumulative_gas: None,
-                            max_duration: None,
-                        },
-                        config.stages.merkle.clean_threshold,
-                        ExExManagerHandle::empty(),
-                    )),
+                    Box::new(
+                        ExecutionStage::new(
+                            components.executor().clone(),
+                            Arc::new(components.consensus().clone()),
+                            ExecutionStageThresholds {
+                                max_blocks: Some(batch_size),
+                                max_changes: None,
+                                max_cumulative_gas: None,
+                                max_duration: None,
+                            },
+                            config.stages.merkle.clean_threshold,
+                            ExExManagerHandle::empty(),
+                        )
+                        .with_offloaded_receipts_verification(
+                            config.consensus.offload_receipts_verification,
+                        ),
+                    ),
                     None,
                 ),
                 StageEnum::TxLookup => (
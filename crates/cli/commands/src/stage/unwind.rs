@@ -2,7 +2,7 @@
 
 use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
 use alloy_eips::BlockHashOrNumber;
-use alloy_primitives::B256;
+use alloy_primitives::{BlockNumber, B256};
 use clap::{Parser, Subcommand};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
@@ -47,116 +47,126 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
         let Environment { provider_factory, config, .. } = self.env.init::<N>(AccessRights::RW)?;
 
         let target = self.command.unwind_target(provider_factory.clone())?;
+        unwind(provider_factory, config, target, self.offline)
+    }
+}
+
+/// Unwinds the database and static files to the given block, which stays in the database.
+///
+/// If `offline` is enabled, only the data of the offline stages is unwound.
+pub(crate) fn unwind<N>(
+    provider_factory: ProviderFactory<N>,
+    config: Config,
+    target: BlockNumber,
+    offline: bool,
+) -> eyre::Result<()>
+where
+    N: ProviderNodeTypes<ChainSpec: EthChainSpec + EthereumHardforks> + CliNodeTypes,
+{
+    let highest_static_file_block = provider_factory
+        .static_file_provider()
+        .get_highest_static_files()
+        .max_block_num()
+        .filter(|highest_static_file_block| *highest_static_file_block > target);
+
+    // Execute a pipeline unwind if the start of the range overlaps the existing static
+    // files. If that's the case, then copy all available data from MDBX to static files, and
+    // only then, proceed with the unwind.
+    //
+    // We also execute a pipeline unwind if `offline` is specified, because we need to only
+    // unwind the data associated with offline stages.
+    if highest_static_file_block.is_some() || offline {
+        if offline {
+            info!(target: "reth::cli", "Performing an unwind for offline-only data!");
+        }
 
-        let highest_static_file_block = provider_factory
-            .static_file_provider()
-            .get_highest_static_files()
-            .max_block_num()
-            .filter(|highest_static_file_block| *highest_static_file_block > target);
-
-        // Execute a pipeline unwind if the start of the range overlaps the existing static
-        // files. If that's the case, then copy all available data from MDBX to static files, and
-        // only then, proceed with the unwind.
-        //
-        // We also execute a pipeline unwind if `offline` is specified, because we need to only
-        // unwind the data associated with offline stages.
-        if highest_static_file_block.is_some() || self.offline {
-            if self.offline {
-                info!(target: "reth::cli", "Performing an unwind for offline-only data!");
-            }
-
-            if let Some(highest_static_file_block) = highest_static_file_block {
-                info!(target: "reth::cli", ?target, ?highest_static_file_block, "Executing a pipeline unwind.");
-            } else {
-                info!(target: "reth::cli", ?target, "Executing a pipeline unwind.");
-            }
-
-            // This will build an offline-only pipeline if the `offline` flag is enabled
-            let mut pipeline = self.build_pipeline(config, provider_factory)?;
-
-            // Move all applicable data from database to static files.
-            pipeline.move_to_static_files()?;
-
-            pipeline.unwind(target, None)?;
+        if let Some(highest_static_file_block) = highest_static_file_block {
+            info!(target: "reth::cli", ?target, ?highest_static_file_block, "Executing a pipeline unwind.");
         } else {
-            info!(target: "reth::cli", ?target, "Executing a database unwind.");
-            let provider = provider_factory.provider_rw()?;
+            info!(target: "reth::cli", ?target, "Executing a pipeline unwind.");
+        }
 
-            provider
-                .remove_block_and_execution_above(target, StorageLocation::Both)
-                .map_err(|err| eyre::eyre!("Transaction error on unwind: {err}"))?;
+        // This will build an offline-only pipeline if the `offline` flag is enabled
+        let mut pipeline = build_pipeline(config, provider_factory, offline)?;
 
-            // update finalized block if needed
-            let last_saved_finalized_block_number = provider.last_finalized_block_number()?;
-            if last_saved_finalized_block_number.is_none_or(|f| f > target) {
-                provider.save_finalized_block_number(target)?;
-            }
+        // Move all applicable data from database to static files.
+        pipeline.move_to_static_files()?;
 
-            provider.commit()?;
-        }
+        pipeline.unwind(target, None)?;
+    } else {
+        info!(target: "reth::cli", ?target, "Executing a database unwind.");
+        let provider = provider_factory.provider_rw()?;
+
+        provider
+            .remove_block_and_execution_above(target, StorageLocation::Both)
+            .map_err(|err| eyre::eyre!("Transaction error on unwind: {err}"))?;
 
-        info!(target: "reth::cli", ?target, "Unwound blocks");
+        // update finalized block if needed
+        let last_saved_finalized_block_number = provider.last_finalized_block_number()?;
+        if last_saved_finalized_block_number.is_none_or(|f| f > target) {
+            provider.save_finalized_block_number(target)?;
+        }
 
-        Ok(())
+        provider.commit()?;
     }
 
-    fn build_pipeline<N: ProviderNodeTypes<ChainSpec = C::ChainSpec> + CliNodeTypes>(
-        self,
-        config: Config,
-        provider_factory: ProviderFactory<N>,
-    ) -> Result<Pipeline<N>, eyre::Error> {
-        let stage_conf = &config.stages;
-        let prune_modes = config.prune.clone().map(|prune| prune.segments).unwrap_or_default();
-
-        let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
-
-        // Unwinding does not require a valid executor
-        let executor = NoopBlockExecutorProvider::<N::Primitives>::default();
-
-        let builder = if self.offline {
-            Pipeline::<N>::builder().add_stages(
-                OfflineStages::new(
-                    executor,
-                    NoopConsensus::arc(),
-                    config.stages,
-                    prune_modes.clone(),
-                )
+    info!(target: "reth::cli", ?target, "Unwound blocks");
+
+    Ok(())
+}
+
+fn build_pipeline<N>(
+    config: Config,
+    provider_factory: ProviderFactory<N>,
+    offline: bool,
+) -> Result<Pipeline<N>, eyre::Error>
+where
+    N: ProviderNodeTypes<ChainSpec: EthChainSpec + EthereumHardforks> + CliNodeTypes,
+{
+    let stage_conf = &config.stages;
+    let prune_modes = config.prune.clone().map(|prune| prune.segments).unwrap_or_default();
+
+    let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
+
+    // Unwinding does not require a valid executor
+    let executor = NoopBlockExecutorProvider::<N::Primitives>::default();
+
+    let builder = if offline {
+        Pipeline::<N>::builder().add_stages(
+            OfflineStages::new(executor, NoopConsensus::arc(), config.stages, prune_modes.clone())
                 .builder()
                 .disable(reth_stages::StageId::SenderRecovery),
+        )
+    } else {
+        Pipeline::<N>::builder().with_tip_sender(tip_tx).add_stages(
+            DefaultStages::new(
+                provider_factory.clone(),
+                tip_rx,
+                Arc::new(NoopConsensus::default()),
+                NoopHeaderDownloader::default(),
+                NoopBodiesDownloader::default(),
+                executor.clone(),
+                stage_conf.clone(),
+                prune_modes.clone(),
             )
-        } else {
-            Pipeline::<N>::builder().with_tip_sender(tip_tx).add_stages(
-                DefaultStages::new(
-                    provider_factory.clone(),
-                    tip_rx,
-                    Arc::new(NoopConsensus::default()),
-                    NoopHeaderDownloader::default(),
-                    NoopBodiesDownloader::default(),
-                    executor.clone(),
-                    stage_conf.clone(),
-                    prune_modes.clone(),
-                )
-                .set(ExecutionStage::new(
-                    executor,
-                    Arc::new(NoopConsensus::default()),
-                    ExecutionStageThresholds {
-                        max_blocks: None,
-                        max_changes: None,
-                        max_cumulative_gas: None,
-                        max_duration: None,
-                    },
-                    stage_conf.execution_external_clean_threshold(),
-                    ExExManagerHandle::empty(),
-                )),
-            )
-        };
-
-        let pipeline = builder.build(
-            provider_factory.clone(),
-            StaticFileProducer::new(provider_factory, prune_modes),
-        );
-        Ok(pipeline)
-    }
+            .set(ExecutionStage::new(
+                executor,
+                Arc::new(NoopConsensus::default()),
+                ExecutionStageThresholds {
+                    max_blocks: None,
+                    max_changes: None,
+                    max_cumulative_gas: None,
+                    max_duration: None,
+                },
+                stage_conf.execution_external_clean_threshold(),
+                ExExManagerHandle::empty(),
+            )),
+        )
+    };
+
+    let pipeline = builder
+        .build(provider_factory.clone(), StaticFileProducer::new(provider_factory, prune_modes));
+    Ok(pipeline)
 }
 
 /// `reth stage unwind` subcommand
@@ -0,0 +1,71 @@
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use clap::Parser;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_provider::{DBProvider, StageCheckpointReader};
+use reth_stages::StageId;
+use reth_trie::verify::TrieVerifier;
+use reth_trie_db::DatabaseTrieVerifier;
+use tracing::{error, info};
+
+/// `reth trie verify` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `trie verify` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+
+        let provider = provider_factory.provider()?;
+        let merkle = provider.get_stage_checkpoint(StageId::MerkleExecute)?.unwrap_or_default();
+        for stage_id in [StageId::AccountHashing, StageId::StorageHashing] {
+            let checkpoint = provider.get_stage_checkpoint(stage_id)?.unwrap_or_default();
+            if checkpoint.block_number != merkle.block_number {
+                eyre::bail!(
+                    "The trie is at block {}, but the {stage_id} stage is at block {}. Finish the \
+                     sync before verifying the trie.",
+                    merkle.block_number,
+                    checkpoint.block_number
+                );
+            }
+        }
+        drop(provider);
+
+        info!(target: "reth::cli", block = merkle.block_number, "Verifying trie");
+        let mut verifier = TrieVerifier::new();
+        let mut divergences = 0;
+        loop {
+            let step = verifier.verify_next_from_tx(provider_factory.provider()?.tx_ref())?;
+            for divergence in &step.divergences {
+                error!(target: "reth::cli", ?divergence, "{divergence}");
+            }
+            divergences += step.divergences.len();
+
+            if step.pass_finished {
+                break
+            }
+            if step.subtrie_finished && verifier.next_subtrie() % 256 == 0 {
+                info!(
+                    target: "reth::cli",
+                    progress = %format!(
+                        "{:.2}%",
+                        verifier.next_subtrie() as f64 / TrieVerifier::SUBTRIES as f64 * 100.0
+                    ),
+                    divergences,
+                    "Verified subtries"
+                );
+            }
+        }
+
+        if divergences > 0 {
+            eyre::bail!("Found {divergences} divergent trie nodes")
+        }
+        info!(target: "reth::cli", "Trie matches the hashed state");
+
+        Ok(())
+    }
+}
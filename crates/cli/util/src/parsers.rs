@@ -1,8 +1,9 @@
 use alloy_eips::BlockHashOrNumber;
-use alloy_primitives::B256;
+use alloy_primitives::{BlockNumber, B256};
 use reth_fs_util::FsPathError;
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs},
+    ops::RangeInclusive,
     path::Path,
     str::FromStr,
     time::Duration,
@@ -39,6 +40,18 @@ pub fn hash_or_num_value_parser(value: &str) -> eyre::Result<BlockHashOrNumber,
     }
 }
 
+/// Parse an inclusive range of block numbers in the format `<from>..=<to>`, e.g. `100..=200`.
+pub fn parse_block_range(value: &str) -> eyre::Result<RangeInclusive<BlockNumber>> {
+    let Some((from, to)) = value.split_once("..=") else {
+        eyre::bail!("block range must be in the format <from>..=<to>")
+    };
+    let range = from.trim().parse()?..=to.trim().parse()?;
+    if range.is_empty() {
+        eyre::bail!("block range {value} is empty")
+    }
+    Ok(range)
+}
+
 /// Error thrown while parsing a socket address.
 #[derive(thiserror::Error, Debug)]
 pub enum SocketAddressParsingError {
@@ -118,6 +131,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_block_ranges() {
+        assert_eq!(parse_block_range("100..=200").unwrap(), 100..=200);
+        assert_eq!(parse_block_range("5..=5").unwrap(), 5..=5);
+        assert!(parse_block_range("200..=100").is_err());
+        assert!(parse_block_range("100..200").is_err());
+    }
+
     #[test]
     fn parse_ms_or_seconds() {
         let ms = parse_duration_from_secs_or_ms("5ms").unwrap();
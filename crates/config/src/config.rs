@@ -28,6 +28,8 @@ pub struct Config {
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration for block validation.
+    pub consensus: ConsensusConfig,
 }
 
 impl Config {
@@ -122,6 +124,8 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Deposit Index stage configuration.
+    pub deposit_index: DepositIndexConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -248,6 +252,12 @@ pub struct ExecutionConfig {
         )
     )]
     pub max_duration: Option<Duration>,
+    /// Verify the state root every this many blocks, instead of only at the pipeline target.
+    ///
+    /// The execution stage finishes its run at every multiple of the interval, so the following
+    /// stages compute and verify the state root of that block before the next blocks are
+    /// executed.
+    pub state_root_interval: Option<u64>,
 }
 
 impl Default for ExecutionConfig {
@@ -259,6 +269,7 @@ impl Default for ExecutionConfig {
             max_cumulative_gas: Some(30_000_000 * 50_000),
             // 10 minutes
             max_duration: Some(Duration::from_secs(10 * 60)),
+            state_root_interval: None,
         }
     }
 }
@@ -388,6 +399,33 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Deposit Index stage configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct DepositIndexConfig {
+    /// Whether to index the deposits of the beacon chain deposit contract.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for DepositIndexConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
+/// Block validation configuration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ConsensusConfig {
+    /// Whether to verify the receipts root and logs bloom of executed blocks on a separate thread
+    /// pool, overlapping with the execution of the next blocks.
+    pub offload_receipts_verification: bool,
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -398,11 +436,15 @@ pub struct PruneConfig {
     /// Pruning configuration for every part of the data that can be pruned.
     #[cfg_attr(feature = "serde", serde(alias = "parts"))]
     pub segments: PruneModes,
+    /// Directory with the era1 archive files and their `checksums.txt` that must be verified
+    /// before block bodies are expired by the bodies history segment.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub era_dir: Option<PathBuf>,
 }
 
 impl Default for PruneConfig {
     fn default() -> Self {
-        Self { block_interval: DEFAULT_BLOCK_INTERVAL, segments: PruneModes::none() }
+        Self { block_interval: DEFAULT_BLOCK_INTERVAL, segments: PruneModes::none(), era_dir: None }
     }
 }
 
@@ -425,8 +467,10 @@ impl PruneConfig {
                     receipts,
                     account_history,
                     storage_history,
+                    bodies_history,
                     receipts_log_filter,
                 },
+            era_dir,
         } = other;
 
         // Merge block_interval, only update if it's the default interval
@@ -440,6 +484,8 @@ impl PruneConfig {
         self.segments.receipts = self.segments.receipts.or(receipts);
         self.segments.account_history = self.segments.account_history.or(account_history);
         self.segments.storage_history = self.segments.storage_history.or(storage_history);
+        self.segments.bodies_history = self.segments.bodies_history.or(bodies_history);
+        self.era_dir = self.era_dir.take().or(era_dir);
 
         if self.segments.receipts_log_filter.0.is_empty() && !receipts_log_filter.0.is_empty() {
             self.segments.receipts_log_filter = receipts_log_filter;
@@ -473,7 +519,12 @@ mod tests {
     use alloy_primitives::Address;
     use reth_network_peers::TrustedPeer;
     use reth_prune_types::{PruneMode, PruneModes, ReceiptsLogPruneConfig};
-    use std::{collections::BTreeMap, path::Path, str::FromStr, time::Duration};
+    use std::{
+        collections::BTreeMap,
+        path::{Path, PathBuf},
+        str::FromStr,
+        time::Duration,
+    };
 
     fn with_tempdir(filename: &str, proc: fn(&std::path::Path)) {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -960,11 +1011,13 @@ receipts = 'full'
                 receipts: Some(PruneMode::Distance(1000)),
                 account_history: None,
                 storage_history: Some(PruneMode::Before(5000)),
+                bodies_history: None,
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([(
                     Address::random(),
                     PruneMode::Full,
                 )])),
             },
+            era_dir: None,
         };
 
         let config2 = PruneConfig {
@@ -975,11 +1028,13 @@ receipts = 'full'
                 receipts: Some(PruneMode::Full),
                 account_history: Some(PruneMode::Distance(2000)),
                 storage_history: Some(PruneMode::Distance(3000)),
+                bodies_history: Some(PruneMode::Before(15537394)),
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([
                     (Address::random(), PruneMode::Distance(1000)),
                     (Address::random(), PruneMode::Before(2000)),
                 ])),
             },
+            era_dir: Some(PathBuf::from("era")),
         };
 
         let original_filter = config1.segments.receipts_log_filter.clone();
@@ -993,7 +1048,9 @@ receipts = 'full'
         assert_eq!(config1.segments.receipts, Some(PruneMode::Distance(1000)));
         assert_eq!(config1.segments.account_history, Some(PruneMode::Distance(2000)));
         assert_eq!(config1.segments.storage_history, Some(PruneMode::Before(5000)));
+        assert_eq!(config1.segments.bodies_history, Some(PruneMode::Before(15537394)));
         assert_eq!(config1.segments.receipts_log_filter, original_filter);
+        assert_eq!(config1.era_dir, Some(PathBuf::from("era")));
     }
 
     #[test]
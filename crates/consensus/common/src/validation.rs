@@ -76,6 +76,36 @@ pub fn validate_cancun_gas<B: Block>(block: &SealedBlock<B>) -> Result<(), Conse
     Ok(())
 }
 
+/// Validates that the block doesn't exceed the RLP length and transaction count limits of the
+/// chain, if any.
+#[inline]
+pub fn validate_block_limits<B, ChainSpec>(
+    block: &SealedBlock<B>,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError>
+where
+    B: Block,
+    ChainSpec: EthChainSpec,
+{
+    let limits = chain_spec.block_limits();
+
+    if let Some(max_count) = limits.max_transactions {
+        let count = block.body().transactions().len();
+        if count > max_count {
+            return Err(ConsensusError::TooManyTransactions { count, max_count })
+        }
+    }
+
+    if let Some(max_rlp_length) = limits.max_rlp_length {
+        let rlp_length = block.rlp_length();
+        if rlp_length > max_rlp_length {
+            return Err(ConsensusError::BlockTooLarge { rlp_length, max_rlp_length })
+        }
+    }
+
+    Ok(())
+}
+
 /// Ensures the block response data matches the header.
 ///
 /// This ensures the body response items match the header's hashes:
@@ -127,6 +157,7 @@ where
 /// - Compares the ommer hash in the block header to the block body
 /// - Compares the transactions root in the block header to the block body
 /// - Pre-execution transaction validation
+/// - Compares the block size to the limits of the chain
 /// - (Optionally) Compares the receipts root in the block header to the block body
 pub fn validate_block_pre_execution<B, ChainSpec>(
     block: &SealedBlock<B>,
@@ -134,8 +165,10 @@ pub fn validate_block_pre_execution<B, ChainSpec>(
 ) -> Result<(), ConsensusError>
 where
     B: Block,
-    ChainSpec: EthereumHardforks,
+    ChainSpec: EthChainSpec + EthereumHardforks,
 {
+    validate_block_limits(block, chain_spec)?;
+
     // Check ommers hash
     let ommers_hash = block.body().calculate_ommers_root();
     if Some(block.ommers_hash()) != ommers_hash {
@@ -398,4 +431,36 @@ mod tests {
             }))
         );
     }
+
+    #[test]
+    fn block_limits() {
+        let mut chain_spec = ChainSpecBuilder::mainnet().build();
+        let transactions = vec![mock_blob_tx(1, 1), mock_blob_tx(2, 1)];
+        let block = SealedBlock::seal_slow(alloy_consensus::Block {
+            header: Header::default(),
+            body: BlockBody { transactions, ommers: vec![], withdrawals: None },
+        });
+
+        // no limits by default
+        assert_eq!(validate_block_limits(&block, &chain_spec), Ok(()));
+
+        chain_spec.block_limits.max_transactions = Some(1);
+        assert_eq!(
+            validate_block_limits(&block, &chain_spec),
+            Err(ConsensusError::TooManyTransactions { count: 2, max_count: 1 })
+        );
+
+        chain_spec.block_limits.max_transactions = Some(2);
+        chain_spec.block_limits.max_rlp_length = Some(block.rlp_length() - 1);
+        assert_eq!(
+            validate_block_limits(&block, &chain_spec),
+            Err(ConsensusError::BlockTooLarge {
+                rlp_length: block.rlp_length(),
+                max_rlp_length: block.rlp_length() - 1
+            })
+        );
+
+        chain_spec.block_limits.max_rlp_length = Some(block.rlp_length());
+        assert_eq!(validate_block_limits(&block, &chain_spec), Ok(()));
+    }
 }
@@ -133,6 +133,38 @@ pub trait HeaderValidator<H = Header>: Debug + Send + Sync {
     ) -> Result<(), ConsensusError>;
 }
 
+/// Rules for the header fields that are specific to the consensus engine of a chain: the extra
+/// data, the nonce and the difficulty.
+///
+/// A [`HeaderValidator`] delegates the validation of these fields to its rules, so that chains
+/// with e.g. clique-like proof-of-authority rules, which store the signer in the extra data and
+/// votes in the nonce, can replace them while reusing the rest of the validation.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait HeaderRules<H = Header>: Debug + Send + Sync {
+    /// Validates the extra data of the header.
+    fn validate_extra_data(&self, header: &H) -> Result<(), ConsensusError>;
+
+    /// Validates the difficulty and the nonce of the header.
+    ///
+    /// `is_post_merge` is whether the merge is active at the block of the header.
+    fn validate_difficulty_and_nonce(
+        &self,
+        header: &H,
+        is_post_merge: bool,
+    ) -> Result<(), ConsensusError>;
+
+    /// Validates the difficulty of the header against its parent.
+    ///
+    /// **This should not be called for the genesis block**.
+    fn validate_difficulty_against_parent(
+        &self,
+        _header: &H,
+        _parent: &H,
+    ) -> Result<(), ConsensusError> {
+        Ok(())
+    }
+}
+
 /// Helper trait to cast `Arc<dyn Consensus>` to `Arc<dyn HeaderValidator>`
 pub trait AsHeaderValidator<H>: HeaderValidator<H> {
     /// Converts the [`Arc`] of self to [`Arc`] of [`HeaderValidator`]
@@ -276,6 +308,24 @@ pub enum ConsensusError {
         len: usize,
     },
 
+    /// Error when the RLP encoded block exceeds the maximum size of the chain.
+    #[error("block RLP length {rlp_length} exceeds max length {max_rlp_length}")]
+    BlockTooLarge {
+        /// The length of the RLP encoded block.
+        rlp_length: usize,
+        /// The maximum length of an RLP encoded block.
+        max_rlp_length: usize,
+    },
+
+    /// Error when the block has more transactions than the chain allows.
+    #[error("block has {count} transactions, more than the max of {max_count}")]
+    TooManyTransactions {
+        /// The number of transactions in the block.
+        count: usize,
+        /// The maximum number of transactions in a block.
+        max_count: usize,
+    },
+
     /// Error when the difficulty after a merge is not zero.
     #[error("difficulty after merge is not zero")]
     TheMergeDifficultyIsNotZero,
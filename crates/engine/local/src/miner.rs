@@ -8,22 +8,24 @@ use futures_util::{stream::Fuse, StreamExt};
 use reth_engine_primitives::{BeaconEngineMessage, EngineTypes};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{
-    BuiltPayload, EngineApiMessageVersion, PayloadAttributesBuilder, PayloadKind, PayloadTypes,
+    BuiltPayload, EngineApiMessageVersion, PayloadAttributesBuilder, PayloadBuilderAttributes,
+    PayloadKind, PayloadTypes,
 };
 use reth_provider::BlockReader;
 use reth_transaction_pool::TransactionPool;
 use std::{
     future::Future,
+    num::NonZeroU64,
     pin::Pin,
     task::{Context, Poll},
     time::{Duration, UNIX_EPOCH},
 };
 use tokio::{
     sync::{mpsc::UnboundedSender, oneshot},
-    time::Interval,
+    time::{Interval, Sleep},
 };
 use tokio_stream::wrappers::ReceiverStream;
-use tracing::error;
+use tracing::{error, info, Span};
 
 /// A mining mode for the local dev engine.
 #[derive(Debug)]
@@ -33,6 +35,15 @@ pub enum MiningMode {
     Instant(Fuse<ReceiverStream<TxHash>>),
     /// In this mode a block is built at a fixed interval.
     Interval(Interval),
+    /// In this mode a block is built at a fixed interval plus a random delay of up to the jitter.
+    JitteredInterval {
+        /// The fixed interval between blocks.
+        interval: Duration,
+        /// The maximum random delay added to the interval.
+        jitter: Duration,
+        /// Fires when the next block is due.
+        sleep: Pin<Box<Sleep>>,
+    },
 }
 
 impl MiningMode {
@@ -47,6 +58,24 @@ impl MiningMode {
         let start = tokio::time::Instant::now() + duration;
         Self::Interval(tokio::time::interval_at(start, duration))
     }
+
+    /// Constructor for a [`MiningMode::JitteredInterval`]
+    pub fn interval_with_jitter(interval: Duration, jitter: Duration) -> Self {
+        let sleep = Box::pin(tokio::time::sleep(jittered(interval, jitter)));
+        Self::JitteredInterval { interval, jitter, sleep }
+    }
+}
+
+/// Returns the interval plus a random delay of up to the jitter.
+fn jittered(interval: Duration, jitter: Duration) -> Duration {
+    let jitter_nanos = u64::try_from(jitter.as_nanos()).unwrap_or(u64::MAX);
+    interval + Duration::from_nanos(random_u64() % jitter_nanos.saturating_add(1))
+}
+
+/// Returns a random number.
+fn random_u64() -> u64 {
+    let random = B256::random();
+    u64::from_be_bytes(random[..8].try_into().expect("8 bytes"))
 }
 
 impl Future for MiningMode {
@@ -68,10 +97,30 @@ impl Future for MiningMode {
                 }
                 Poll::Pending
             }
+            Self::JitteredInterval { interval, jitter, sleep } => {
+                if sleep.as_mut().poll(cx).is_ready() {
+                    let next = tokio::time::Instant::now() + jittered(*interval, *jitter);
+                    sleep.as_mut().reset(next);
+                    return Poll::Ready(())
+                }
+                Poll::Pending
+            }
         }
     }
 }
 
+/// Reorgs periodically injected by the [`LocalMiner`].
+///
+/// Every `frequency` blocks, the miner replaces the last `depth` blocks with a single block built
+/// on top of their parent, and makes it the new head of the chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReorgInjection {
+    /// The number of mined blocks between reorgs.
+    pub frequency: NonZeroU64,
+    /// The number of blocks removed from the chain by a reorg.
+    pub depth: u64,
+}
+
 /// Local miner advancing the chain/
 #[derive(Debug)]
 pub struct LocalMiner<EngineT: EngineTypes, B> {
@@ -87,6 +136,10 @@ pub struct LocalMiner<EngineT: EngineTypes, B> {
     last_timestamp: u64,
     /// Stores latest mined blocks.
     last_block_hashes: Vec<B256>,
+    /// The reorgs to inject, if any.
+    reorg_injection: Option<ReorgInjection>,
+    /// Number of blocks mined since the last injected reorg.
+    blocks_since_reorg: u64,
 }
 
 impl<EngineT, B> LocalMiner<EngineT, B>
@@ -101,6 +154,7 @@ where
         to_engine: UnboundedSender<BeaconEngineMessage<EngineT>>,
         mode: MiningMode,
         payload_builder: PayloadBuilderHandle<EngineT>,
+        reorg_injection: Option<ReorgInjection>,
     ) {
         let latest_header =
             provider.sealed_header(provider.best_block_number().unwrap()).unwrap().unwrap();
@@ -112,6 +166,8 @@ where
             payload_builder,
             last_timestamp: latest_header.timestamp(),
             last_block_hashes: vec![latest_header.hash()],
+            reorg_injection,
+            blocks_since_reorg: 0,
         };
 
         // Spawn the miner
@@ -127,6 +183,8 @@ where
                 _ = &mut self.mode => {
                     if let Err(e) = self.advance().await {
                         error!(target: "engine::local", "Error advancing the chain: {:?}", e);
+                    } else if let Err(e) = self.maybe_reorg().await {
+                        error!(target: "engine::local", "Error injecting reorg: {:?}", e);
                     }
                 }
                 // send FCU once in a while
@@ -172,16 +230,21 @@ where
         Ok(())
     }
 
-    /// Generates payload attributes for a new block, passes them to FCU and inserts built payload
-    /// through newPayload.
-    async fn advance(&mut self) -> eyre::Result<()> {
-        let timestamp = std::cmp::max(
+    /// Returns the timestamp for the next block.
+    fn next_timestamp(&self) -> u64 {
+        std::cmp::max(
             self.last_timestamp + 1,
             std::time::SystemTime::now()
                 .duration_since(UNIX_EPOCH)
                 .expect("cannot be earlier than UNIX_EPOCH")
                 .as_secs(),
-        );
+        )
+    }
+
+    /// Generates payload attributes for a new block, passes them to FCU and inserts built payload
+    /// through newPayload.
+    async fn advance(&mut self) -> eyre::Result<()> {
+        let timestamp = self.next_timestamp();
 
         let (tx, rx) = oneshot::channel();
         self.to_engine.send(BeaconEngineMessage::ForkchoiceUpdated {
@@ -208,7 +271,8 @@ where
 
         let (tx, rx) = oneshot::channel();
         let payload = EngineT::block_to_payload(payload.block().clone());
-        self.to_engine.send(BeaconEngineMessage::NewPayload { payload, tx })?;
+        let span = Span::current();
+        self.to_engine.send(BeaconEngineMessage::NewPayload { payload, tx, span })?;
 
         let res = rx.await??;
 
@@ -226,4 +290,58 @@ where
 
         Ok(())
     }
+
+    /// Injects a reorg if enough blocks were mined since the last one.
+    async fn maybe_reorg(&mut self) -> eyre::Result<()> {
+        let Some(ReorgInjection { frequency, depth }) = self.reorg_injection else { return Ok(()) };
+
+        self.blocks_since_reorg += 1;
+        if self.blocks_since_reorg < frequency.get() {
+            return Ok(())
+        }
+        self.blocks_since_reorg = 0;
+
+        // the parent of the reorged blocks must be known
+        let depth = depth.min(self.last_block_hashes.len() as u64 - 1) as usize;
+        if depth == 0 {
+            return Ok(())
+        }
+        let fork_len = self.last_block_hashes.len() - depth;
+        let parent = self.last_block_hashes[fork_len - 1];
+
+        // the timestamp differs from the replaced block, so the new block is distinct even if it
+        // includes the same transactions
+        let timestamp = self.next_timestamp();
+        let attributes = EngineT::PayloadBuilderAttributes::try_new(
+            parent,
+            self.payload_attributes_builder.build(timestamp),
+            EngineApiMessageVersion::default() as u8,
+        )
+        .map_err(|err| eyre::eyre!("Invalid payload attributes: {err}"))?;
+
+        let payload_id = self.payload_builder.send_new_payload(attributes).await??;
+        let Some(Ok(payload)) =
+            self.payload_builder.resolve_kind(payload_id, PayloadKind::WaitForPending).await
+        else {
+            eyre::bail!("No payload")
+        };
+        let block = payload.block().clone();
+
+        let (tx, rx) = oneshot::channel();
+        self.to_engine.send(BeaconEngineMessage::NewPayload {
+            payload: EngineT::block_to_payload(block.clone()),
+            tx,
+            span: Span::current(),
+        })?;
+        if !rx.await??.is_valid() {
+            eyre::bail!("Invalid reorg payload")
+        }
+
+        info!(target: "engine::local", depth, number = block.number(), hash = %block.hash(), "Injecting reorg");
+
+        self.last_timestamp = timestamp;
+        self.last_block_hashes.truncate(fork_len);
+        self.last_block_hashes.push(block.hash());
+        self.update_forkchoice_state().await
+    }
 }
@@ -4,7 +4,8 @@
 //! which can be set to `Instant` or `Interval`. The `Instant` mode will
 //! constantly poll the payload builder and initiate block building
 //! with a single transaction. The `Interval` mode will initiate block
-//! building at a fixed interval.
+//! building at a fixed interval, optionally with a random jitter. Reorgs can additionally be
+//! injected periodically, see [`ReorgInjection`].
 
 use core::fmt;
 use std::{
@@ -14,7 +15,7 @@ use std::{
     task::{Context, Poll},
 };
 
-use crate::miner::{LocalMiner, MiningMode};
+use crate::miner::{LocalMiner, MiningMode, ReorgInjection};
 use futures_util::{Stream, StreamExt};
 use reth_chainspec::EthChainSpec;
 use reth_consensus::{ConsensusError, FullConsensus};
@@ -29,7 +30,7 @@ use reth_engine_tree::{
     persistence::PersistenceHandle,
     tree::{EngineApiTreeHandler, InvalidBlockHook, TreeConfig},
 };
-use reth_evm::{execute::BlockExecutorProvider, ConfigureEvm};
+use reth_evm::{execute::BlockExecutorProvider, profiler::StateAccessProfiler, ConfigureEvm};
 use reth_node_types::BlockTy;
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{PayloadAttributesBuilder, PayloadTypes};
@@ -75,10 +76,12 @@ where
         payload_validator: V,
         tree_config: TreeConfig,
         invalid_block_hook: Box<dyn InvalidBlockHook<N::Primitives>>,
+        state_access_profiler: Option<StateAccessProfiler>,
         sync_metrics_tx: MetricEventsSender,
         to_engine: UnboundedSender<BeaconEngineMessage<N::Engine>>,
         from_engine: EngineMessageStream<N::Engine>,
         mode: MiningMode,
+        reorg_injection: Option<ReorgInjection>,
         payload_attributes_builder: B,
         evm_config: C,
     ) -> Self
@@ -106,6 +109,7 @@ where
                 canonical_in_memory_state,
                 tree_config,
                 invalid_block_hook,
+                state_access_profiler,
                 engine_kind,
                 evm_config,
             );
@@ -118,6 +122,7 @@ where
             to_engine,
             mode,
             payload_builder,
+            reorg_injection,
         );
 
         Self { handler, incoming_requests: from_engine }
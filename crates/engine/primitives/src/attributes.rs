@@ -0,0 +1,142 @@
+//! Custom validation of payload attributes.
+
+use crate::{EngineTypes, EngineValidator, PayloadValidator};
+use alloc::sync::Arc;
+use core::fmt;
+use reth_errors::ConsensusError;
+use reth_payload_primitives::{
+    EngineApiMessageVersion, EngineObjectValidationError, InvalidPayloadAttributesError,
+    NewPayloadError, PayloadOrAttributes, PayloadTypes,
+};
+use reth_primitives_traits::{Block, RecoveredBlock};
+use reth_trie_common::HashedPostState;
+
+/// Validates the payload attributes of a forkchoice update before a payload job is started, in
+/// addition to the checks of the [`EngineValidator`].
+///
+/// This allows nodes with custom engine types to reject attributes, e.g. with too many
+/// withdrawals or invalid L2 fields. The returned error determines the error code of the engine
+/// API response:
+///  - [`EngineObjectValidationError::InvalidPayloadAttributes`]: `-38003` (invalid payload
+///    attributes)
+///  - [`EngineObjectValidationError::UnsupportedFork`]: `-38005` (unsupported fork)
+///  - [`EngineObjectValidationError::InvalidParams`]: `-32602` (invalid params)
+pub trait PayloadAttributesValidator<Attributes>: Send + Sync + 'static {
+    /// Validates the payload attributes sent with the given engine API version.
+    fn validate_payload_attributes(
+        &self,
+        version: EngineApiMessageVersion,
+        attributes: &Attributes,
+    ) -> Result<(), EngineObjectValidationError>;
+}
+
+impl<Attributes, F> PayloadAttributesValidator<Attributes> for F
+where
+    F: Fn(EngineApiMessageVersion, &Attributes) -> Result<(), EngineObjectValidationError>
+        + Send
+        + Sync
+        + 'static,
+{
+    fn validate_payload_attributes(
+        &self,
+        version: EngineApiMessageVersion,
+        attributes: &Attributes,
+    ) -> Result<(), EngineObjectValidationError> {
+        self(version, attributes)
+    }
+}
+
+/// An [`EngineValidator`] that runs a [`PayloadAttributesValidator`] once the payload attributes
+/// were validated by the inner validator.
+pub struct WithPayloadAttributesValidator<V, A> {
+    /// The validator of payloads and payload attributes.
+    inner: V,
+    /// The custom validation of the payload attributes.
+    attributes_validator: Arc<A>,
+}
+
+impl<V, A> WithPayloadAttributesValidator<V, A> {
+    /// Creates a new validator that runs the given validation after the inner validator.
+    pub fn new(inner: V, attributes_validator: A) -> Self {
+        Self { inner, attributes_validator: Arc::new(attributes_validator) }
+    }
+
+    /// Returns the inner validator.
+    pub const fn inner(&self) -> &V {
+        &self.inner
+    }
+}
+
+impl<V: Clone, A> Clone for WithPayloadAttributesValidator<V, A> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone(), attributes_validator: self.attributes_validator.clone() }
+    }
+}
+
+impl<V: fmt::Debug, A> fmt::Debug for WithPayloadAttributesValidator<V, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithPayloadAttributesValidator")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<V, A> PayloadValidator for WithPayloadAttributesValidator<V, A>
+where
+    V: PayloadValidator,
+    A: Send + Sync + 'static,
+{
+    type Block = V::Block;
+    type ExecutionData = V::ExecutionData;
+
+    fn ensure_well_formed_payload(
+        &self,
+        payload: Self::ExecutionData,
+    ) -> Result<RecoveredBlock<Self::Block>, NewPayloadError> {
+        self.inner.ensure_well_formed_payload(payload)
+    }
+
+    fn validate_block_post_execution_with_hashed_state(
+        &self,
+        state_updates: &HashedPostState,
+        block: &RecoveredBlock<Self::Block>,
+    ) -> Result<(), ConsensusError> {
+        self.inner.validate_block_post_execution_with_hashed_state(state_updates, block)
+    }
+}
+
+impl<Types, V, A> EngineValidator<Types> for WithPayloadAttributesValidator<V, A>
+where
+    Types: EngineTypes,
+    V: EngineValidator<Types>,
+    A: PayloadAttributesValidator<<Types as PayloadTypes>::PayloadAttributes>,
+{
+    fn validate_version_specific_fields(
+        &self,
+        version: EngineApiMessageVersion,
+        payload_or_attrs: PayloadOrAttributes<
+            '_,
+            Types::ExecutionData,
+            <Types as PayloadTypes>::PayloadAttributes,
+        >,
+    ) -> Result<(), EngineObjectValidationError> {
+        self.inner.validate_version_specific_fields(version, payload_or_attrs)
+    }
+
+    fn ensure_well_formed_attributes(
+        &self,
+        version: EngineApiMessageVersion,
+        attributes: &<Types as PayloadTypes>::PayloadAttributes,
+    ) -> Result<(), EngineObjectValidationError> {
+        self.inner.ensure_well_formed_attributes(version, attributes)?;
+        self.attributes_validator.validate_payload_attributes(version, attributes)
+    }
+
+    fn validate_payload_attributes_against_header(
+        &self,
+        attr: &<Types as PayloadTypes>::PayloadAttributes,
+        header: &<Self::Block as Block>::Header,
+    ) -> Result<(), InvalidPayloadAttributesError> {
+        self.inner.validate_payload_attributes_against_header(attr, header)
+    }
+}
@@ -0,0 +1,53 @@
+//! Engine API capabilities exchanged with the consensus client.
+
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+/// An engine API method that is required by an active or upcoming fork, but isn't supported by
+/// the consensus client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingCapability {
+    /// The method, e.g. `engine_newPayloadV4`.
+    pub method: String,
+    /// The name of the fork that requires the method.
+    pub fork: String,
+    /// The timestamp at which the fork activates.
+    pub activation_timestamp: u64,
+    /// Whether the fork is already active.
+    pub active: bool,
+}
+
+/// The capabilities sent by the consensus client with `engine_exchangeCapabilities`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapabilitiesHandshake {
+    /// The unix timestamp of the exchange in seconds.
+    pub timestamp: u64,
+    /// The capabilities of the consensus client.
+    pub capabilities: Vec<String>,
+    /// The methods required by the latest active fork and the upcoming forks that the consensus
+    /// client doesn't support.
+    pub missing: Vec<MissingCapability>,
+}
+
+/// Records the last engine API capabilities handshake with the consensus client.
+///
+/// A mismatch of the capabilities otherwise only surfaces once a fork activates and the consensus
+/// client calls a method that isn't supported. The record is cheap to clone and shared between the
+/// engine API and the RPC, e.g. the `admin_engineCapabilities` RPC.
+#[derive(Debug, Clone, Default)]
+pub struct ExchangedCapabilities {
+    inner: Arc<Mutex<Option<CapabilitiesHandshake>>>,
+}
+
+impl ExchangedCapabilities {
+    /// Records the given handshake, returning the previous one.
+    pub fn record(&self, handshake: CapabilitiesHandshake) -> Option<CapabilitiesHandshake> {
+        self.inner.lock().replace(handshake)
+    }
+
+    /// Returns the last handshake, or [`None`] if the consensus client didn't exchange its
+    /// capabilities yet.
+    pub fn last(&self) -> Option<CapabilitiesHandshake> {
+        self.inner.lock().clone()
+    }
+}
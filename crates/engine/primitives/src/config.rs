@@ -1,5 +1,9 @@
 //! Engine tree configuration.
 
+#[cfg(feature = "std")]
+use crate::ReorgGuard;
+use core::time::Duration;
+
 /// Triggers persistence when the number of canonical blocks in memory exceeds this threshold.
 pub const DEFAULT_PERSISTENCE_THRESHOLD: u64 = 2;
 
@@ -61,6 +65,13 @@ pub struct TreeConfig {
     cross_block_cache_size: u64,
     /// Whether the host has enough parallelism to run state root task.
     has_enough_parallelism: bool,
+    /// Whether to split the parallel state root calculation into tasks per account subtrie.
+    state_root_subtrie_split: bool,
+    /// Guard that refuses reorgs deeper than the maximum reorg depth, if configured.
+    #[cfg(feature = "std")]
+    reorg_guard: Option<ReorgGuard>,
+    /// New payloads taking at least this long to process are reported as slow, if configured.
+    slow_payload_threshold: Option<Duration>,
 }
 
 impl Default for TreeConfig {
@@ -76,6 +87,10 @@ impl Default for TreeConfig {
             use_caching_and_prewarming: false,
             cross_block_cache_size: DEFAULT_CROSS_BLOCK_CACHE_SIZE,
             has_enough_parallelism: has_enough_parallelism(),
+            state_root_subtrie_split: false,
+            #[cfg(feature = "std")]
+            reorg_guard: None,
+            slow_payload_threshold: None,
         }
     }
 }
@@ -106,6 +121,10 @@ impl TreeConfig {
             use_caching_and_prewarming,
             cross_block_cache_size,
             has_enough_parallelism,
+            state_root_subtrie_split: false,
+            #[cfg(feature = "std")]
+            reorg_guard: None,
+            slow_payload_threshold: None,
         }
     }
 
@@ -156,6 +175,22 @@ impl TreeConfig {
         self.cross_block_cache_size
     }
 
+    /// Returns whether to split the parallel state root calculation into tasks per account subtrie.
+    pub const fn state_root_subtrie_split(&self) -> bool {
+        self.state_root_subtrie_split
+    }
+
+    /// Returns the guard against deep reorgs, if a maximum reorg depth is configured.
+    #[cfg(feature = "std")]
+    pub const fn reorg_guard(&self) -> Option<&ReorgGuard> {
+        self.reorg_guard.as_ref()
+    }
+
+    /// Returns the duration after which a new payload is reported as slow, if configured.
+    pub const fn slow_payload_threshold(&self) -> Option<Duration> {
+        self.slow_payload_threshold
+    }
+
     /// Setter for persistence threshold.
     pub const fn with_persistence_threshold(mut self, persistence_threshold: u64) -> Self {
         self.persistence_threshold = persistence_threshold;
@@ -229,6 +264,29 @@ impl TreeConfig {
         self
     }
 
+    /// Setter for whether to split the parallel state root calculation into tasks per account
+    /// subtrie.
+    pub const fn with_state_root_subtrie_split(mut self, state_root_subtrie_split: bool) -> Self {
+        self.state_root_subtrie_split = state_root_subtrie_split;
+        self
+    }
+
+    /// Setter for the guard against deep reorgs.
+    #[cfg(feature = "std")]
+    pub fn with_reorg_guard(mut self, reorg_guard: Option<ReorgGuard>) -> Self {
+        self.reorg_guard = reorg_guard;
+        self
+    }
+
+    /// Setter for the duration after which a new payload is reported as slow.
+    pub const fn with_slow_payload_threshold(
+        mut self,
+        slow_payload_threshold: Option<Duration>,
+    ) -> Self {
+        self.slow_payload_threshold = slow_payload_threshold;
+        self
+    }
+
     /// Whether or not to use state root task
     pub fn use_state_root_task(&self) -> bool {
         self.has_enough_parallelism && !self.legacy_state_root
@@ -24,6 +24,9 @@ use serde::{de::DeserializeOwned, Serialize};
 // Re-export [`ExecutionPayload`] moved to `reth_payload_primitives`
 pub use reth_payload_primitives::ExecutionPayload;
 
+mod attributes;
+pub use attributes::{PayloadAttributesValidator, WithPayloadAttributesValidator};
+
 mod error;
 pub use error::*;
 
@@ -42,6 +45,16 @@ pub use invalid_block_hook::InvalidBlockHook;
 pub mod config;
 pub use config::*;
 
+#[cfg(feature = "std")]
+mod capabilities;
+#[cfg(feature = "std")]
+pub use capabilities::{CapabilitiesHandshake, ExchangedCapabilities, MissingCapability};
+
+#[cfg(feature = "std")]
+mod reorg;
+#[cfg(feature = "std")]
+pub use reorg::{RejectedReorg, ReorgGuard};
+
 /// This type defines the versioned types of the engine API.
 ///
 /// This includes the execution payload types and payload attributes that are used to trigger a
@@ -17,6 +17,7 @@ use reth_errors::RethResult;
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::PayloadTypes;
 use tokio::sync::{mpsc::UnboundedSender, oneshot};
+use tracing::Span;
 
 /// Represents the outcome of forkchoice update.
 ///
@@ -149,6 +150,8 @@ pub enum BeaconEngineMessage<Payload: PayloadTypes> {
         payload: Payload::ExecutionData,
         /// The sender for returning payload status result.
         tx: oneshot::Sender<Result<PayloadStatus, BeaconOnNewPayloadError>>,
+        /// The span of the request, which the processing of the payload is traced under.
+        span: Span,
     },
     /// Message with updated forkchoice state.
     ForkchoiceUpdated {
@@ -215,13 +218,16 @@ where
 
     /// Sends a new payload message to the beacon consensus engine and waits for a response.
     ///
+    /// The payload is processed under the current span, e.g. the span of the engine API request.
+    ///
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/shanghai.md#engine_newpayloadv2>
     pub async fn new_payload(
         &self,
         payload: Payload::ExecutionData,
     ) -> Result<PayloadStatus, BeaconOnNewPayloadError> {
         let (tx, rx) = oneshot::channel();
-        let _ = self.to_engine.send(BeaconEngineMessage::NewPayload { payload, tx });
+        let span = Span::current();
+        let _ = self.to_engine.send(BeaconEngineMessage::NewPayload { payload, tx, span });
         rx.await.map_err(|_| BeaconOnNewPayloadError::EngineUnavailable)?
     }
 
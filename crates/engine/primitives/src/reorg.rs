@@ -0,0 +1,114 @@
+//! Safeguard against deep reorgs.
+
+use alloy_primitives::B256;
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+/// A reorg that was refused because it exceeded the maximum reorg depth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RejectedReorg {
+    /// The head of the chain the engine refused to switch to.
+    pub head: B256,
+    /// The number of canonical blocks the reorg would have removed.
+    pub depth: u64,
+}
+
+/// Refuses reorgs that remove more canonical blocks than the configured maximum.
+///
+/// A refused reorg flags the node as requiring operator intervention: the engine keeps following
+/// its current canonical chain until the reorg is explicitly allowed with
+/// [`ReorgGuard::force_reorg`], e.g. via the `admin_forceReorg` RPC.
+///
+/// The guard is cheap to clone and shared between the engine and the RPC.
+#[derive(Debug, Clone)]
+pub struct ReorgGuard {
+    /// The maximum number of canonical blocks a reorg may remove.
+    max_depth: u64,
+    inner: Arc<Mutex<ReorgGuardState>>,
+}
+
+#[derive(Debug, Default)]
+struct ReorgGuardState {
+    /// The most recently refused reorg, if the node requires operator intervention.
+    rejected: Option<RejectedReorg>,
+    /// Block that a reorg exceeding the maximum depth is allowed to switch to.
+    forced: Option<B256>,
+}
+
+impl ReorgGuard {
+    /// Creates a new guard that refuses reorgs deeper than `max_depth` blocks.
+    pub fn new(max_depth: u64) -> Self {
+        Self { max_depth, inner: Default::default() }
+    }
+
+    /// Returns the maximum number of canonical blocks a reorg may remove.
+    pub const fn max_depth(&self) -> u64 {
+        self.max_depth
+    }
+
+    /// Returns whether a reorg that removes `depth` canonical blocks and makes the given chain
+    /// canonical is allowed.
+    ///
+    /// Reorgs deeper than the maximum depth are only allowed if the new chain contains the block
+    /// passed to [`ReorgGuard::force_reorg`], which also clears the flag. Otherwise the reorg is
+    /// recorded as rejected.
+    pub fn allows_reorg(&self, depth: u64, new_chain: &[B256]) -> bool {
+        if depth <= self.max_depth {
+            return true
+        }
+
+        let mut state = self.inner.lock();
+        if state.forced.is_some_and(|forced| new_chain.contains(&forced)) {
+            *state = ReorgGuardState::default();
+            return true
+        }
+
+        if let Some(&head) = new_chain.last() {
+            state.rejected = Some(RejectedReorg { head, depth });
+        }
+        false
+    }
+
+    /// Allows the next reorg to a chain that contains the given block, regardless of its depth.
+    ///
+    /// Returns the most recently rejected reorg, if any.
+    pub fn force_reorg(&self, block_hash: B256) -> Option<RejectedReorg> {
+        let mut state = self.inner.lock();
+        state.forced = Some(block_hash);
+        state.rejected
+    }
+
+    /// Returns the most recently rejected reorg, if the node requires operator intervention.
+    pub fn rejected_reorg(&self) -> Option<RejectedReorg> {
+        self.inner.lock().rejected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refuses_deep_reorg_until_forced() {
+        let guard = ReorgGuard::new(2);
+        let chain = [B256::with_last_byte(1), B256::with_last_byte(2), B256::with_last_byte(3)];
+
+        assert!(guard.allows_reorg(2, &chain));
+        assert_eq!(guard.rejected_reorg(), None);
+
+        assert!(!guard.allows_reorg(3, &chain));
+        let rejected = RejectedReorg { head: chain[2], depth: 3 };
+        assert_eq!(guard.rejected_reorg(), Some(rejected));
+
+        // forcing a block of another chain doesn't allow the reorg
+        assert_eq!(guard.force_reorg(B256::with_last_byte(4)), Some(rejected));
+        assert!(!guard.allows_reorg(3, &chain));
+
+        assert_eq!(guard.force_reorg(chain[1]), Some(rejected));
+        assert!(guard.allows_reorg(3, &chain));
+        assert_eq!(guard.rejected_reorg(), None);
+
+        // the override only applies once
+        assert!(!guard.allows_reorg(3, &chain));
+    }
+}
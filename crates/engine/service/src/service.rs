@@ -15,7 +15,7 @@ pub use reth_engine_tree::{
     engine::EngineApiEvent,
 };
 use reth_ethereum_primitives::EthPrimitives;
-use reth_evm::{execute::BlockExecutorProvider, ConfigureEvm};
+use reth_evm::{execute::BlockExecutorProvider, profiler::StateAccessProfiler, ConfigureEvm};
 use reth_network_p2p::BlockClient;
 use reth_node_types::{BlockTy, NodeTypes, NodeTypesWithEngine};
 use reth_payload_builder::PayloadBuilderHandle;
@@ -88,6 +88,7 @@ where
         payload_validator: V,
         tree_config: TreeConfig,
         invalid_block_hook: Box<dyn InvalidBlockHook<N::Primitives>>,
+        state_access_profiler: Option<StateAccessProfiler>,
         sync_metrics_tx: MetricEventsSender,
         evm_config: C,
     ) -> Self
@@ -116,6 +117,7 @@ where
                 canonical_in_memory_state,
                 tree_config,
                 invalid_block_hook,
+                state_access_profiler,
                 engine_kind,
                 evm_config,
             );
@@ -224,6 +226,7 @@ mod tests {
             engine_payload_validator,
             TreeConfig::default(),
             Box::new(NoopInvalidBlockHook::default()),
+            None,
             sync_metrics_tx,
             evm_config,
         );
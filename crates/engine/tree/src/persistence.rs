@@ -2,22 +2,36 @@ use crate::metrics::PersistenceMetrics;
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockNumHash;
 use reth_chain_state::ExecutedBlockWithTrieUpdates;
-use reth_errors::ProviderError;
+use reth_errors::{ProviderError, ProviderResult};
 use reth_ethereum_primitives::EthPrimitives;
 use reth_primitives_traits::NodePrimitives;
 use reth_provider::{
-    providers::ProviderNodeTypes, writer::UnifiedStorageWriter, BlockHashReader,
-    ChainStateBlockWriter, DatabaseProviderFactory, ProviderFactory, StaticFileProviderFactory,
+    providers::ProviderNodeTypes,
+    writer::{PreparedBlocks, UnifiedStorageWriter},
+    BlockHashReader, BlockProcessingHistoryWriter, BlockProcessingStats, ChainStateBlockWriter,
+    DatabaseProviderFactory, ProviderFactory, StaticFileProviderFactory,
+    TransactionLookupIndexReader,
 };
 use reth_prune::{PrunerError, PrunerOutput, PrunerWithFactory};
 use reth_stages_api::{MetricEvent, MetricEventsSender};
 use std::{
-    sync::mpsc::{Receiver, SendError, Sender},
-    time::Instant,
+    sync::mpsc::{Receiver, RecvTimeoutError, SendError, Sender},
+    thread::JoinHandle,
+    time::{Duration, Instant},
 };
 use thiserror::Error;
 use tokio::sync::oneshot;
-use tracing::{debug, error};
+use tracing::{debug, error, warn, Span};
+
+/// The maximum number of blocks that are prepared at once.
+///
+/// Larger ranges of blocks are prepared in chunks on a separate thread. The next chunk is prepared
+/// while the previous one is written, and all chunks are committed together.
+const SAVE_BLOCKS_CHUNK_SIZE: usize = 8;
+
+/// How often the service checks whether the blocks committed in the background are persisted, if
+/// no other action is received in the meantime.
+const PENDING_COMMIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 /// Writes parts of reth's in memory tree state to the database and static files.
 ///
@@ -41,6 +55,9 @@ where
     metrics: PersistenceMetrics,
     /// Sender for sync metrics - we only submit sync metrics for persisted blocks
     sync_metrics_tx: MetricEventsSender,
+    /// The commit of the last saved blocks, which runs in the background while the next blocks
+    /// are prepared.
+    pending_commit: Option<PendingCommit>,
 }
 
 impl<N> PersistenceService<N>
@@ -54,7 +71,14 @@ where
         pruner: PrunerWithFactory<ProviderFactory<N>>,
         sync_metrics_tx: MetricEventsSender,
     ) -> Self {
-        Self { provider, incoming, pruner, metrics: PersistenceMetrics::default(), sync_metrics_tx }
+        Self {
+            provider,
+            incoming,
+            pruner,
+            metrics: PersistenceMetrics::default(),
+            sync_metrics_tx,
+            pending_commit: None,
+        }
     }
 
     /// Prunes block data before the given block hash according to the configured prune
@@ -76,35 +100,42 @@ where
     /// This is the main loop, that will listen to database events and perform the requested
     /// database actions
     pub fn run(mut self) -> Result<(), PersistenceError> {
-        // If the receiver errors then senders have disconnected, so the loop should then end.
-        while let Ok(action) = self.incoming.recv() {
+        loop {
+            let action = if let Some(commit) = &self.pending_commit {
+                match self.incoming.recv_timeout(PENDING_COMMIT_POLL_INTERVAL) {
+                    Ok(action) => action,
+                    Err(RecvTimeoutError::Timeout) => {
+                        if commit.handle.is_finished() {
+                            self.finish_pending_commit()?;
+                        }
+                        continue
+                    }
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            } else {
+                // If the receiver errors then senders have disconnected, so the loop should then
+                // end.
+                let Ok(action) = self.incoming.recv() else { break };
+                action
+            };
+
+            // only the preparation of the next blocks to save overlaps with the commit
+            if !matches!(action, PersistenceAction::SaveBlocks(..)) {
+                self.finish_pending_commit()?;
+            }
+
             match action {
                 PersistenceAction::RemoveBlocksAbove(new_tip_num, sender) => {
                     let result = self.on_remove_blocks_above(new_tip_num)?;
                     // send new sync metrics based on removed blocks
                     let _ =
                         self.sync_metrics_tx.send(MetricEvent::SyncHeight { height: new_tip_num });
+                    self.send_transaction_lookup_status();
                     // we ignore the error because the caller may or may not care about the result
                     let _ = sender.send(result);
                 }
-                PersistenceAction::SaveBlocks(blocks, sender) => {
-                    let result = self.on_save_blocks(blocks)?;
-                    let result_number = result.map(|r| r.number);
-
-                    // we ignore the error because the caller may or may not care about the result
-                    let _ = sender.send(result);
-
-                    if let Some(block_number) = result_number {
-                        // send new sync metrics based on saved blocks
-                        let _ = self
-                            .sync_metrics_tx
-                            .send(MetricEvent::SyncHeight { height: block_number });
-
-                        if self.pruner.is_pruning_needed(block_number) {
-                            // We log `PrunerOutput` inside the `Pruner`
-                            let _ = self.prune_before(block_number)?;
-                        }
-                    }
+                PersistenceAction::SaveBlocks(blocks, span, sender) => {
+                    span.in_scope(|| self.on_save_blocks(blocks, sender))?;
                 }
                 PersistenceAction::SaveFinalizedBlock(finalized_block) => {
                     let provider = self.provider.database_provider_rw()?;
@@ -116,7 +147,52 @@ where
                     provider.save_safe_block_number(safe_block)?;
                     provider.commit()?;
                 }
+                PersistenceAction::SaveBlockProcessingStats(stats) => {
+                    // the history is informational, so failing to record it is not fatal
+                    if let Err(err) = self.provider.append_block_processing_stats(&stats) {
+                        warn!(target: "engine::persistence", %err, "Failed to save block processing stats");
+                    }
+                }
+            }
+        }
+
+        self.finish_pending_commit()
+    }
+
+    /// Sends the progress of the transaction hash index, which isn't reflected by the sync height.
+    fn send_transaction_lookup_status(&self) {
+        match self.provider.transaction_lookup_status() {
+            Ok(status) => {
+                let _ = self.sync_metrics_tx.send(MetricEvent::TransactionLookupStatus {
+                    indexed_block: status.indexed_block,
+                    stored_block: status.stored_block,
+                });
             }
+            Err(err) => {
+                debug!(target: "engine::persistence", %err, "Failed to read tx lookup status")
+            }
+        }
+    }
+
+    /// Waits for the blocks committed in the background to be persisted, then reports them and
+    /// runs the pruner if needed.
+    fn finish_pending_commit(&mut self) -> Result<(), PersistenceError> {
+        let Some(PendingCommit { handle, start_time }) = self.pending_commit.take() else {
+            return Ok(())
+        };
+        let block_number = match handle.join() {
+            Ok(result) => result?,
+            Err(panic) => std::panic::resume_unwind(panic),
+        };
+        self.metrics.save_blocks_duration_seconds.record(start_time.elapsed());
+
+        // send new sync metrics based on saved blocks
+        let _ = self.sync_metrics_tx.send(MetricEvent::SyncHeight { height: block_number });
+        self.send_transaction_lookup_status();
+
+        if self.pruner.is_pruning_needed(block_number) {
+            // We log `PrunerOutput` inside the `Pruner`
+            let _ = self.prune_before(block_number)?;
         }
         Ok(())
     }
@@ -133,35 +209,90 @@ where
         let new_tip_hash = provider_rw.block_hash(new_tip_num)?;
         UnifiedStorageWriter::from(&provider_rw, &sf_provider).remove_blocks_above(new_tip_num)?;
         UnifiedStorageWriter::commit_unwind(provider_rw)?;
+        if let Err(err) = self.provider.unwind_block_processing_history(new_tip_num) {
+            warn!(target: "engine::persistence", %err, "Failed to unwind block processing history");
+        }
 
         debug!(target: "engine::persistence", ?new_tip_num, ?new_tip_hash, "Removed blocks from disk");
         self.metrics.remove_blocks_above_duration_seconds.record(start_time.elapsed());
         Ok(new_tip_hash.map(|hash| BlockNumHash { hash, number: new_tip_num }))
     }
 
+    /// Saves the blocks and starts committing them in the background.
+    ///
+    /// The blocks are double-buffered: they are prepared while the previously saved blocks are
+    /// committed, and written once that commit is done. The result is sent to the caller once the
+    /// blocks are committed.
     fn on_save_blocks(
-        &self,
+        &mut self,
         blocks: Vec<ExecutedBlockWithTrieUpdates<N::Primitives>>,
-    ) -> Result<Option<BlockNumHash>, PersistenceError> {
+        sender: oneshot::Sender<Option<BlockNumHash>>,
+    ) -> Result<(), PersistenceError> {
         debug!(target: "engine::persistence", first=?blocks.first().map(|b| b.recovered_block.num_hash()), last=?blocks.last().map(|b| b.recovered_block.num_hash()), "Saving range of blocks");
         let start_time = Instant::now();
-        let last_block_hash_num = blocks.last().map(|block| BlockNumHash {
+        let Some(last_block_hash_num) = blocks.last().map(|block| BlockNumHash {
             hash: block.recovered_block().hash(),
             number: block.recovered_block().header().number(),
-        });
+        }) else {
+            // we ignore the error because the caller may or may not care about the result
+            let _ = sender.send(None);
+            return Ok(())
+        };
+
+        let provider_rw = std::thread::scope(|scope| {
+            // Holds at most one prepared chunk, so that the next chunk is prepared while the
+            // previous one is written.
+            let (prepared_tx, prepared_rx) = std::sync::mpsc::sync_channel(1);
+            std::thread::Builder::new()
+                .name("Persistence Prepare".to_string())
+                .spawn_scoped(scope, move || {
+                    let mut blocks = blocks.into_iter().peekable();
+                    while blocks.peek().is_some() {
+                        let chunk = blocks.by_ref().take(SAVE_BLOCKS_CHUNK_SIZE).collect();
+                        if prepared_tx.send(PreparedBlocks::new(chunk)).is_err() {
+                            // the writer failed, the remaining blocks are not needed
+                            break
+                        }
+                    }
+                })
+                .map_err(ProviderError::other)?;
+
+            // the previously saved blocks must be committed before these blocks are written
+            self.finish_pending_commit()?;
 
-        if last_block_hash_num.is_some() {
             let provider_rw = self.provider.database_provider_rw()?;
             let static_file_provider = self.provider.static_file_provider();
+            let writer = UnifiedStorageWriter::from(&provider_rw, &static_file_provider);
+            for blocks in prepared_rx {
+                writer.save_prepared_blocks(blocks)?;
+            }
+            Ok::<_, PersistenceError>(provider_rw)
+        })?;
+
+        let handle = std::thread::Builder::new()
+            .name("Persistence Commit".to_string())
+            .spawn(move || {
+                UnifiedStorageWriter::commit(provider_rw)?;
+                // we ignore the error because the caller may or may not care about the result
+                let _ = sender.send(Some(last_block_hash_num));
+                Ok(last_block_hash_num.number)
+            })
+            .map_err(ProviderError::other)?;
+        self.pending_commit = Some(PendingCommit { handle, start_time });
 
-            UnifiedStorageWriter::from(&provider_rw, &static_file_provider).save_blocks(blocks)?;
-            UnifiedStorageWriter::commit(provider_rw)?;
-        }
-        self.metrics.save_blocks_duration_seconds.record(start_time.elapsed());
-        Ok(last_block_hash_num)
+        Ok(())
     }
 }
 
+/// A commit of saved blocks that runs in the background.
+#[derive(Debug)]
+struct PendingCommit {
+    /// Handle of the thread committing the blocks, which returns the number of the highest block.
+    handle: JoinHandle<ProviderResult<u64>>,
+    /// When saving the blocks started.
+    start_time: Instant,
+}
+
 /// One of the errors that can happen when using the persistence service.
 #[derive(Debug, Error)]
 pub enum PersistenceError {
@@ -182,7 +313,9 @@ pub enum PersistenceAction<N: NodePrimitives = EthPrimitives> {
     ///
     /// First, header, transaction, and receipt-related data should be written to static files.
     /// Then the execution history-related data will be written to the database.
-    SaveBlocks(Vec<ExecutedBlockWithTrieUpdates<N>>, oneshot::Sender<Option<BlockNumHash>>),
+    ///
+    /// The blocks are saved under the given span.
+    SaveBlocks(Vec<ExecutedBlockWithTrieUpdates<N>>, Span, oneshot::Sender<Option<BlockNumHash>>),
 
     /// Removes block data above the given block number from the database.
     ///
@@ -195,6 +328,9 @@ pub enum PersistenceAction<N: NodePrimitives = EthPrimitives> {
 
     /// Update the persisted safe block on disk
     SaveSafeBlock(u64),
+
+    /// Append the processing timings of the given blocks to the block processing history.
+    SaveBlockProcessingStats(Vec<BlockProcessingStats>),
 }
 
 /// A handle to the persistence service
@@ -257,12 +393,14 @@ impl<T: NodePrimitives> PersistenceHandle<T> {
     /// of the sender argument.
     ///
     /// If there are no blocks to persist, then `None` is sent in the sender.
+    ///
+    /// The blocks are saved under the current span.
     pub fn save_blocks(
         &self,
         blocks: Vec<ExecutedBlockWithTrieUpdates<T>>,
         tx: oneshot::Sender<Option<BlockNumHash>>,
     ) -> Result<(), SendError<PersistenceAction<T>>> {
-        self.send_action(PersistenceAction::SaveBlocks(blocks, tx))
+        self.send_action(PersistenceAction::SaveBlocks(blocks, Span::current(), tx))
     }
 
     /// Persists the finalized block number on disk.
@@ -281,6 +419,15 @@ impl<T: NodePrimitives> PersistenceHandle<T> {
         self.send_action(PersistenceAction::SaveSafeBlock(safe_block))
     }
 
+    /// Appends the processing timings of the given blocks, ordered by block number, to the block
+    /// processing history on disk.
+    pub fn save_block_processing_stats(
+        &self,
+        stats: Vec<BlockProcessingStats>,
+    ) -> Result<(), SendError<PersistenceAction<T>>> {
+        self.send_action(PersistenceAction::SaveBlockProcessingStats(stats))
+    }
+
     /// Tells the persistence service to remove blocks above a certain block number. The removed
     /// blocks are returned by the service.
     ///
@@ -372,6 +519,23 @@ mod tests {
         assert_eq!(last_hash, actual_hash);
     }
 
+    #[tokio::test]
+    async fn test_save_blocks_multiple_chunks() {
+        reth_tracing::init_test_tracing();
+        let persistence_handle = default_persistence_handle();
+
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let blocks = test_block_builder
+            .get_executed_blocks(0..2 * SAVE_BLOCKS_CHUNK_SIZE as u64 + 1)
+            .collect::<Vec<_>>();
+        let last_hash = blocks.last().unwrap().recovered_block().hash();
+        let (tx, rx) = oneshot::channel();
+
+        persistence_handle.save_blocks(blocks, tx).unwrap();
+        let BlockNumHash { hash: actual_hash, number: _ } = rx.await.unwrap().unwrap();
+        assert_eq!(last_hash, actual_hash);
+    }
+
     #[tokio::test]
     async fn test_save_blocks_multiple_calls() {
         reth_tracing::init_test_tracing();
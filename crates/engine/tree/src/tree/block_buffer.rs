@@ -76,6 +76,7 @@ impl<B: Block> BlockBuffer<B> {
             if let Some(evicted_hash) = self.block_queue.pop_front() {
                 if let Some(evicted_block) = self.remove_block(&evicted_hash) {
                     self.remove_from_parent(evicted_block.parent_hash(), &evicted_hash);
+                    self.metrics.evicted_blocks.increment(1);
                 }
             }
         }
@@ -93,11 +94,12 @@ impl<B: Block> BlockBuffer<B> {
         &mut self,
         parent_hash: &BlockHash,
     ) -> Vec<RecoveredBlock<B>> {
-        let removed = self
+        let removed: Vec<_> = self
             .remove_block(parent_hash)
             .into_iter()
             .chain(self.remove_children(vec![*parent_hash]))
             .collect();
+        self.metrics.connected_blocks.increment(removed.len() as u64);
         self.metrics.blocks.set(self.blocks.len() as f64);
         removed
     }
@@ -116,12 +118,14 @@ impl<B: Block> BlockBuffer<B> {
         }
 
         // remove from other collections.
+        let mut discarded = 0;
         for block_hash in &block_hashes_to_remove {
             // It's fine to call
-            self.remove_block(block_hash);
+            discarded += u64::from(self.remove_block(block_hash).is_some());
         }
 
-        self.remove_children(block_hashes_to_remove);
+        discarded += self.remove_children(block_hashes_to_remove).len() as u64;
+        self.metrics.discarded_blocks.increment(discarded);
         self.metrics.blocks.set(self.blocks.len() as f64);
     }
 
@@ -11,8 +11,8 @@ use reth_provider::{
 };
 use reth_revm::db::BundleState;
 use reth_trie::{
-    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
-    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
+    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, HashedStorageRange,
+    MultiProof, MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
 };
 use revm_primitives::map::DefaultHashBuilder;
 use std::time::Duration;
@@ -237,6 +237,16 @@ impl<S: StorageRootProvider> StorageRootProvider for CachedStateProvider<S> {
     ) -> ProviderResult<StorageMultiProof> {
         self.state_provider.storage_multiproof(address, slots, hashed_storage)
     }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStorageRange> {
+        self.state_provider.storage_range(address, hashed_storage, start, limit)
+    }
 }
 
 impl<S: BlockHashReader> BlockHashReader for CachedStateProvider<S> {
@@ -4,6 +4,7 @@ use reth_metrics::{
     Metrics,
 };
 use reth_trie::updates::TrieUpdates;
+use std::time::Duration;
 
 /// Metrics for the `EngineApi`.
 #[derive(Debug, Default)]
@@ -28,6 +29,8 @@ pub(super) struct TreeMetrics {
     pub reorgs: Counter,
     /// The latest reorg depth
     pub latest_reorg_depth: Gauge,
+    /// The number of reorgs refused for exceeding the maximum reorg depth
+    pub rejected_reorgs: Counter,
 }
 
 /// Metrics for the `EngineApi`.
@@ -44,6 +47,10 @@ pub(crate) struct EngineMetrics {
     pub(crate) forkchoice_updated_messages: Counter,
     /// The total count of new payload messages received.
     pub(crate) new_payload_messages: Counter,
+    /// Histogram of new payload processing durations (in seconds)
+    pub(crate) new_payload_duration: Histogram,
+    /// The total count of new payloads that took longer than the slow payload threshold.
+    pub(crate) slow_new_payloads: Counter,
     /// Histogram of persistence operation durations (in seconds)
     pub(crate) persistence_duration: Histogram,
     /// Tracks the how often we failed to deliver a newPayload response.
@@ -81,10 +88,25 @@ impl BlockValidationMetrics {
     }
 }
 
+/// Durations of the phases of the latest inserted block, reported for slow new payloads.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct BlockPhaseDurations {
+    /// Duration of the block execution.
+    pub(crate) execution: Duration,
+    /// Duration of the state root computation.
+    pub(crate) state_root: Duration,
+}
+
 /// Metrics for the blockchain tree block buffer
 #[derive(Metrics)]
 #[metrics(scope = "blockchain_tree.block_buffer")]
 pub(crate) struct BlockBufferMetrics {
     /// Total blocks in the block buffer
     pub blocks: Gauge,
+    /// Total blocks evicted from the block buffer because it was full
+    pub evicted_blocks: Counter,
+    /// Total blocks discarded from the block buffer because they precede the finalized block
+    pub discarded_blocks: Counter,
+    /// Total blocks removed from the block buffer to be connected to their inserted parent
+    pub connected_blocks: Counter,
 }
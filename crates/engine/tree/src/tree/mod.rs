@@ -31,20 +31,25 @@ use reth_engine_primitives::{
 };
 use reth_errors::{ConsensusError, ProviderResult};
 use reth_ethereum_primitives::EthPrimitives;
-use reth_evm::{execute::BlockExecutorProvider, ConfigureEvm};
+use reth_evm::{
+    execute::BlockExecutorProvider,
+    profiler::{StateAccessProfiler, StateAccessReport},
+    ConfigureEvm, OnStateHook,
+};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{EngineApiMessageVersion, PayloadBuilderAttributes};
 use reth_primitives_traits::{
     Block, GotExpected, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader,
 };
 use reth_provider::{
-    providers::ConsistentDbView, BlockNumReader, BlockReader, DBProvider, DatabaseProviderFactory,
-    ExecutionOutcome, HashedPostStateProvider, ProviderError, StateCommitmentProvider,
-    StateProviderBox, StateProviderFactory, StateReader, StateRootProvider, TransactionVariant,
+    providers::ConsistentDbView, BlockNumReader, BlockProcessingStats, BlockReader, DBProvider,
+    DatabaseProviderFactory, ExecutionOutcome, HashedPostStateProvider, ProviderError,
+    StateCommitmentProvider, StateProviderBox, StateProviderFactory, StateReader,
+    StateRootProvider, TransactionVariant,
 };
 use reth_revm::database::StateProviderDatabase;
 use reth_stages_api::ControlFlow;
-use reth_trie::{updates::TrieUpdates, HashedPostState, TrieInput};
+use reth_trie::{prefix_set::TriePrefixSetsMut, updates::TrieUpdates, HashedPostState, TrieInput};
 use reth_trie_db::{DatabaseHashedPostState, StateCommitment};
 use reth_trie_parallel::root::{ParallelStateRoot, ParallelStateRootError};
 use std::{
@@ -55,7 +60,7 @@ use std::{
         mpsc::{Receiver, RecvError, RecvTimeoutError, Sender},
         Arc,
     },
-    time::Instant,
+    time::{Duration, Instant},
 };
 use tokio::sync::{
     mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
@@ -75,7 +80,7 @@ mod persistence_state;
 #[allow(unused)]
 mod trie_updates;
 
-use crate::tree::error::AdvancePersistenceError;
+use crate::tree::{error::AdvancePersistenceError, metrics::BlockPhaseDurations};
 pub use block_buffer::BlockBuffer;
 pub use invalid_block_hook::{InvalidBlockHooks, NoopInvalidBlockHook};
 pub use invalid_headers::InvalidHeaderCache;
@@ -600,8 +605,14 @@ where
     config: TreeConfig,
     /// Metrics for the engine api.
     metrics: EngineApiMetrics,
+    /// Phase durations of the latest inserted block, reported for slow new payloads.
+    block_phase_durations: BlockPhaseDurations,
+    /// Processing timings of the executed blocks that weren't persisted yet, by block hash.
+    block_processing_stats: HashMap<B256, BlockProcessingStats>,
     /// An invalid block hook.
     invalid_block_hook: Box<dyn InvalidBlockHook<N>>,
+    /// Profiler that records the state accessed by executed blocks, if enabled.
+    state_access_profiler: Option<StateAccessProfiler>,
     /// The engine API variant of this handler
     engine_kind: EngineApiKind,
     /// The type responsible for processing new payloads
@@ -691,8 +702,11 @@ where
             payload_builder,
             config,
             metrics: Default::default(),
+            block_phase_durations: Default::default(),
+            block_processing_stats: Default::default(),
             incoming_tx,
             invalid_block_hook: Box::new(NoopInvalidBlockHook),
+            state_access_profiler: None,
             engine_kind,
             payload_processor,
         }
@@ -703,6 +717,11 @@ where
         self.invalid_block_hook = invalid_block_hook;
     }
 
+    /// Sets the profiler of the state accessed by executed blocks.
+    fn set_state_access_profiler(&mut self, state_access_profiler: Option<StateAccessProfiler>) {
+        self.state_access_profiler = state_access_profiler;
+    }
+
     /// Creates a new [`EngineApiTreeHandler`] instance and spawns it in its
     /// own thread.
     ///
@@ -719,6 +738,7 @@ where
         canonical_in_memory_state: CanonicalInMemoryState<N>,
         config: TreeConfig,
         invalid_block_hook: Box<dyn InvalidBlockHook<N>>,
+        state_access_profiler: Option<StateAccessProfiler>,
         kind: EngineApiKind,
         evm_config: C,
     ) -> (Sender<FromEngine<EngineApiRequest<T, N>, N::Block>>, UnboundedReceiver<EngineApiEvent<N>>)
@@ -729,6 +749,8 @@ where
         let persistence_state = PersistenceState {
             last_persisted_block: BlockNumHash::new(best_block_number, header.hash()),
             rx: None,
+            span: None,
+            queued_save: None,
         };
 
         let (tx, outgoing) = unbounded_channel();
@@ -754,6 +776,7 @@ where
             evm_config,
         );
         task.set_invalid_block_hook(invalid_block_hook);
+        task.set_state_access_profiler(state_access_profiler);
         let incoming = task.incoming_tx.clone();
         std::thread::Builder::new().name("Tree Task".to_string()).spawn(|| task.run()).unwrap();
         (incoming, outgoing)
@@ -909,6 +932,7 @@ where
             return Ok(TreeOutcome::new(status))
         }
 
+        let mut download = None;
         let status = if self.backfill_sync_state.is_idle() {
             let mut latest_valid_hash = None;
             let num_hash = block.num_hash();
@@ -924,9 +948,16 @@ where
                             latest_valid_hash = Some(block_hash);
                             PayloadStatusEnum::Valid
                         }
-                        InsertPayloadOk::Inserted(BlockStatus::Disconnected { .. }) |
+                        InsertPayloadOk::Inserted(BlockStatus::Disconnected {
+                            head,
+                            missing_ancestor,
+                        }) => {
+                            // not known to be invalid, but we don't know anything else. the block
+                            // is buffered and connected once its missing ancestors arrive
+                            download = self.missing_payload_ancestors(head, missing_ancestor);
+                            PayloadStatusEnum::Syncing
+                        }
                         InsertPayloadOk::AlreadySeen(BlockStatus::Disconnected { .. }) => {
-                            // not known to be invalid, but we don't know anything else
                             PayloadStatusEnum::Syncing
                         }
                     };
@@ -942,6 +973,9 @@ where
         };
 
         let mut outcome = TreeOutcome::new(status);
+        if let Some(download) = download {
+            outcome = outcome.with_event(TreeEvent::Download(download));
+        }
         // if the block is valid and it is the current sync target head, make it canonical
         if outcome.outcome.is_valid() && self.is_sync_target_head(block_hash) {
             // but only if it isn't already the canonical head
@@ -955,6 +989,27 @@ where
         Ok(outcome)
     }
 
+    /// Records the processing duration of a new payload and reports it if it was slow.
+    fn on_new_payload_processed(&mut self, block: BlockNumHash, elapsed: Duration) {
+        self.metrics.engine.new_payload_duration.record(elapsed);
+        let phases = std::mem::take(&mut self.block_phase_durations);
+
+        if self.config.slow_payload_threshold().is_some_and(|threshold| elapsed >= threshold) {
+            // the trace id links the log to the exported trace of the payload
+            self.metrics.engine.slow_new_payloads.increment(1);
+            warn!(
+                target: "engine::tree",
+                ?block,
+                ?elapsed,
+                trace_id = reth_tracing::current_trace_id(),
+                execution = ?phases.execution,
+                state_root = ?phases.state_root,
+                persistence_in_progress = self.persistence_state.in_progress(),
+                "Slow new payload"
+            );
+        }
+    }
+
     /// Returns the new chain for the given head.
     ///
     /// This also handles reorgs.
@@ -1091,13 +1146,15 @@ where
             return PersistingKind::NotPersisting
         };
         // Check that the persistince action is saving blocks, not removing them.
-        let CurrentPersistenceAction::SavingBlocks { highest } = action else {
+        let CurrentPersistenceAction::SavingBlocks { .. } = action else {
             return PersistingKind::PersistingNotDescendant
         };
+        // the highest block being saved, including the blocks of a queued save
+        let highest = self.persistence_state.highest_saved_block();
 
         // The block being validated can only be a descendant if its number is higher than
         // the highest block persisting. Otherwise, it's likely a fork of a lower block.
-        if block.number() > highest.number && self.state.tree_state.is_descendant(*highest, block) {
+        if block.number() > highest.number && self.state.tree_state.is_descendant(highest, block) {
             return PersistingKind::PersistingDescendant
         }
 
@@ -1178,6 +1235,13 @@ where
 
         // 2. ensure we can apply a new chain update for the head block
         if let Some(chain_update) = self.on_new_head(state.head_block_hash)? {
+            if !self.is_reorg_allowed(&chain_update) {
+                // keep following the current canonical chain until the operator intervenes
+                return Ok(TreeOutcome::new(OnForkChoiceUpdated::valid(PayloadStatus::from_status(
+                    PayloadStatusEnum::Syncing,
+                ))))
+            }
+
             let tip = chain_update.tip().clone_sealed_header();
             self.on_canonical_chain_update(chain_update);
 
@@ -1302,10 +1366,25 @@ where
             .expect("Checked non-empty persisting blocks");
 
         debug!(target: "engine::tree", blocks = ?blocks_to_persist.iter().map(|block| block.recovered_block().num_hash()).collect::<Vec<_>>(), "Persisting blocks");
+        let processing_stats = blocks_to_persist
+            .iter()
+            .filter_map(|block| self.block_processing_stats.remove(&block.recovered_block().hash()))
+            .collect::<Vec<_>>();
+        // the remaining stats at or below the persisted blocks belong to forks
+        self.block_processing_stats.retain(|_, stats| stats.number > highest_num_hash.number);
+
+        let span = debug_span!(
+            target: "engine::persistence",
+            "persistence",
+            blocks = blocks_to_persist.len(),
+        );
         let (tx, rx) = oneshot::channel();
-        let _ = self.persistence.save_blocks(blocks_to_persist, tx);
+        let _ = span.in_scope(|| self.persistence.save_blocks(blocks_to_persist, tx));
+        if !processing_stats.is_empty() {
+            let _ = self.persistence.save_block_processing_stats(processing_stats);
+        }
 
-        self.persistence_state.start_save(highest_num_hash, rx);
+        self.persistence_state.start_save(highest_num_hash, span, rx);
     }
 
     /// Attempts to advance the persistence state.
@@ -1331,6 +1410,7 @@ where
                         // if this happened, then we persisted no blocks because we sent an
                         // empty vec of blocks
                         warn!(target: "engine::tree", "Persistence task completed but did not persist any blocks");
+                        self.persistence_state.start_queued_save();
                         return Ok(())
                     };
 
@@ -1353,6 +1433,10 @@ where
                 let blocks_to_persist = self.get_canonical_blocks_to_persist();
                 self.persist_blocks(blocks_to_persist);
             }
+        } else if self.persistence_state.can_queue_save() && self.should_persist() {
+            // the next blocks are prepared while the blocks in progress are committed
+            let blocks_to_persist = self.get_canonical_blocks_to_persist();
+            self.persist_blocks(blocks_to_persist);
         }
 
         Ok(())
@@ -1432,8 +1516,26 @@ where
                                     error!(target: "engine::tree", "Failed to send event: {err:?}");
                                 }
                             }
-                            BeaconEngineMessage::NewPayload { payload, tx } => {
-                                let mut output = self.on_new_payload(payload);
+                            BeaconEngineMessage::NewPayload { payload, tx, span } => {
+                                let block =
+                                    BlockNumHash::new(payload.block_number(), payload.block_hash());
+                                // process the payload as part of the trace of the request
+                                let span = debug_span!(
+                                    target: "engine::tree",
+                                    parent: &span,
+                                    "new_payload",
+                                    block_hash = %block.hash,
+                                    block_num = block.number,
+                                );
+                                if let Some(persistence_span) = self.persistence_state.span() {
+                                    span.follows_from(persistence_span);
+                                }
+
+                                let start = Instant::now();
+                                let mut output = span.in_scope(|| self.on_new_payload(payload));
+                                span.in_scope(|| {
+                                    self.on_new_payload_processed(block, start.elapsed())
+                                });
 
                                 let maybe_event =
                                     output.as_mut().ok().and_then(|out| out.event.take());
@@ -1599,12 +1701,39 @@ where
     /// This will update the tracked canonical in memory state and do the necessary housekeeping.
     fn make_canonical(&mut self, target: B256) -> ProviderResult<()> {
         if let Some(chain_update) = self.on_new_head(target)? {
-            self.on_canonical_chain_update(chain_update);
+            if self.is_reorg_allowed(&chain_update) {
+                self.on_canonical_chain_update(chain_update);
+            }
         }
 
         Ok(())
     }
 
+    /// Returns `false` if the chain update is a reorg that is deeper than the maximum reorg depth
+    /// of the configured [`ReorgGuard`](reth_engine_primitives::ReorgGuard).
+    fn is_reorg_allowed(&self, chain_update: &NewCanonicalChain<N>) -> bool {
+        let (Some(guard), NewCanonicalChain::Reorg { new, old }) =
+            (self.config.reorg_guard(), chain_update)
+        else {
+            return true
+        };
+
+        let new_chain = new.iter().map(|block| block.recovered_block().hash()).collect::<Vec<_>>();
+        if guard.allows_reorg(old.len() as u64, &new_chain) {
+            return true
+        }
+
+        self.metrics.tree.rejected_reorgs.increment(1);
+        error!(
+            target: "engine::tree",
+            depth = old.len(),
+            max_depth = guard.max_depth(),
+            head = ?new_chain.last(),
+            "Refusing reorg deeper than the maximum reorg depth, operator intervention required. Use `admin_forceReorg` to allow it"
+        );
+        false
+    }
+
     /// Convenience function to handle an optional tree event.
     fn on_maybe_tree_event(&mut self, event: Option<TreeEvent>) -> ProviderResult<()> {
         if let Some(event) = event {
@@ -1663,7 +1792,7 @@ where
         );
     }
 
-    /// Returns true if the canonical chain length minus the last persisted
+    /// Returns true if the canonical chain length minus the last persisted or saving
     /// block is greater than or equal to the persistence threshold and
     /// backfill is not running.
     pub const fn should_persist(&self) -> bool {
@@ -1672,7 +1801,7 @@ where
             return false
         }
 
-        let min_block = self.persistence_state.last_persisted_block.number;
+        let min_block = self.persistence_state.highest_saved_block().number;
         self.state.tree_state.canonical_block_number().saturating_sub(min_block) >
             self.config.persistence_threshold()
     }
@@ -1680,10 +1809,12 @@ where
     /// Returns a batch of consecutive canonical blocks to persist in the range
     /// `(last_persisted_number .. canonical_head - threshold]` . The expected
     /// order is oldest -> newest.
+    ///
+    /// If blocks are being saved, the batch starts after the highest of them.
     fn get_canonical_blocks_to_persist(&self) -> Vec<ExecutedBlockWithTrieUpdates<N>> {
         let mut blocks_to_persist = Vec::new();
         let mut current_hash = self.state.tree_state.canonical_block_hash();
-        let last_persisted_number = self.persistence_state.last_persisted_block.number;
+        let last_persisted_number = self.persistence_state.highest_saved_block().number;
 
         let canonical_head_number = self.state.tree_state.canonical_block_number();
 
@@ -2271,6 +2402,29 @@ where
         Some(TreeEvent::Download(request))
     }
 
+    /// Returns a request to download the missing ancestors of a buffered payload, if they extend
+    /// the canonical head and are too few to trigger a backfill.
+    ///
+    /// This connects payloads that arrive before their parent, e.g. during a brief desync with the
+    /// CL, without waiting for a forkchoice update.
+    fn missing_payload_ancestors(
+        &self,
+        head: BlockNumHash,
+        missing_ancestor: BlockNumHash,
+    ) -> Option<DownloadRequest> {
+        let distance = self.distance_from_local_tip(head.number, missing_ancestor.number)?;
+        if self.exceeds_backfill_run_threshold(head.number, missing_ancestor.number) {
+            return None
+        }
+
+        trace!(target: "engine::tree", %distance, missing=?missing_ancestor, "downloading missing payload ancestors");
+        Some(if distance == 1 {
+            DownloadRequest::single_block(missing_ancestor.hash)
+        } else {
+            DownloadRequest::BlockRange(missing_ancestor.hash, distance)
+        })
+    }
+
     /// Invoked with a block downloaded from the network
     ///
     /// Returns an event with the appropriate action to take, such as:
@@ -2428,7 +2582,13 @@ where
 
             self.payload_processor.spawn(header, txs, provider_builder, consistent_view, trie_input)
         } else {
-            self.payload_processor.spawn_cache_exclusive(header, txs, provider_builder)
+            // collect the prefix sets during execution for the parallel state root
+            self.payload_processor.spawn_cache_exclusive(
+                header,
+                txs,
+                provider_builder,
+                run_parallel_state_root,
+            )
         };
 
         // Use cached state provider before executing, used in execution after prewarming threads
@@ -2442,19 +2602,29 @@ where
         debug!(target: "engine::tree", block=?block_num_hash, "Executing block");
 
         let executor = self.executor_provider.executor(StateProviderDatabase::new(&state_provider));
+        let mut state_hook: Box<dyn OnStateHook> = Box::new(handle.state_hook());
+        if let Some(profiler) = &self.state_access_profiler {
+            state_hook = profiler.wrap_hook(state_hook);
+        }
         let execution_start = Instant::now();
-        let output = self.metrics.executor.execute_metered(
-            executor,
-            &block,
-            Box::new(handle.state_hook()),
-        )?;
+        let output = debug_span!(target: "engine::tree", "execution")
+            .in_scope(|| self.metrics.executor.execute_metered(executor, &block, state_hook))?;
         let execution_finish = Instant::now();
         let execution_time = execution_finish.duration_since(execution_start);
         debug!(target: "engine::tree", elapsed = ?execution_time, number=?block_num_hash.number, "Executed block");
+        self.block_phase_durations.execution = execution_time;
 
         // after executing the block we can stop executing transactions
         handle.stop_prewarming_execution();
 
+        if let Some(report) = self
+            .state_access_profiler
+            .as_ref()
+            .and_then(|profiler| profiler.on_block_executed(block_num_hash.number))
+        {
+            log_state_access_report(&report);
+        }
+
         if let Err(err) = self.consensus.validate_block_post_execution(&block, &output) {
             // call post-block hook
             self.on_invalid_block(&parent_block, &block, &output, None);
@@ -2474,6 +2644,7 @@ where
 
         debug!(target: "engine::tree", block=?block_num_hash, "Calculating block state root");
 
+        let state_root_span = debug_span!(target: "engine::tree", "state_root").entered();
         let root_time = Instant::now();
 
         let mut maybe_state_root = None;
@@ -2507,6 +2678,7 @@ where
                     persisting_kind,
                     block.header().parent_hash(),
                     &hashed_state,
+                    handle.prefix_sets(),
                 ) {
                     Ok(result) => {
                         info!(
@@ -2538,6 +2710,8 @@ where
 
         self.metrics.block_validation.record_state_root(&trie_output, root_elapsed.as_secs_f64());
         debug!(target: "engine::tree", ?root_elapsed, block=?block_num_hash, "Calculated state root");
+        self.block_phase_durations.state_root = root_elapsed;
+        drop(state_root_span);
 
         // ensure state root matches
         if state_root != block.header().state_root() {
@@ -2552,6 +2726,17 @@ where
         // terminate prewarming task with good state output
         handle.terminate_caching(Some(output.state.clone()));
 
+        self.block_processing_stats.insert(
+            block_num_hash.hash,
+            BlockProcessingStats {
+                number: block_num_hash.number,
+                hash: block_num_hash.hash,
+                gas_used: block.header().gas_used(),
+                execution_time,
+                state_root_time: root_elapsed,
+            },
+        );
+
         let executed: ExecutedBlockWithTrieUpdates<N> = ExecutedBlockWithTrieUpdates {
             block: ExecutedBlock {
                 recovered_block: Arc::new(block),
@@ -2586,6 +2771,9 @@ where
 
     /// Compute state root for the given hashed post state in parallel.
     ///
+    /// If the prefix sets of the hashed post state were collected during execution, they're used
+    /// instead of constructing them from the hashed post state.
+    ///
     /// # Returns
     ///
     /// Returns `Ok(_)` if computed successfully.
@@ -2597,15 +2785,22 @@ where
         persisting_kind: PersistingKind,
         parent_hash: B256,
         hashed_state: &HashedPostState,
+        prefix_sets: Option<TriePrefixSetsMut>,
     ) -> Result<(B256, TrieUpdates), ParallelStateRootError> {
         let consistent_view = ConsistentDbView::new_with_latest_tip(self.provider.clone())?;
 
         let mut input =
             self.compute_trie_input(persisting_kind, consistent_view.clone(), parent_hash)?;
         // Extend with block we are validating root for.
-        input.append_ref(hashed_state);
+        if let Some(prefix_sets) = prefix_sets {
+            input.append_ref_with_prefix_sets(hashed_state, prefix_sets);
+        } else {
+            input.append_ref(hashed_state);
+        }
 
-        ParallelStateRoot::new(consistent_view, input).incremental_root_with_updates()
+        ParallelStateRoot::new(consistent_view, input)
+            .with_subtrie_split(self.config.state_root_subtrie_split())
+            .incremental_root_with_updates()
     }
 
     /// Computes the trie input at the provided parent hash.
@@ -2949,6 +3144,11 @@ where
             None
         };
 
+        // buffered blocks at or below the finalized block can't become canonical anymore
+        if let Some(finalized) = num {
+            self.state.buffer.remove_old_blocks(finalized.number);
+        }
+
         self.state.tree_state.remove_until(
             upper_bound,
             self.persistence_state.last_persisted_block.hash,
@@ -3048,6 +3248,26 @@ impl PersistingKind {
     }
 }
 
+/// Logs a report of the state access profiler.
+fn log_state_access_report(report: &StateAccessReport) {
+    info!(
+        target: "engine::tree",
+        first_block = report.first_block,
+        last_block = report.last_block,
+        unique_accounts = report.unique_accounts,
+        unique_storage_slots = report.unique_storage_slots,
+        reused_accounts = report.reused_accounts,
+        reused_storage_slots = report.reused_storage_slots,
+        "State access report"
+    );
+    for (address, stats) in &report.accounts {
+        debug!(target: "engine::tree", %address, ?stats, "Hot account");
+    }
+    for (address, slot, stats) in &report.storage_slots {
+        debug!(target: "engine::tree", %address, %slot, ?stats, "Hot storage slot");
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3062,7 +3282,7 @@ mod tests {
     use assert_matches::assert_matches;
     use reth_chain_state::{test_utils::TestBlockBuilder, BlockState};
     use reth_chainspec::{ChainSpec, HOLESKY, MAINNET};
-    use reth_engine_primitives::ForkchoiceStatus;
+    use reth_engine_primitives::{ForkchoiceStatus, RejectedReorg, ReorgGuard};
     use reth_ethereum_consensus::EthBeaconConsensus;
     use reth_ethereum_engine_primitives::EthEngineTypes;
     use reth_ethereum_primitives::{Block, EthPrimitives};
@@ -3552,7 +3772,7 @@ mod tests {
 
         let received_action =
             test_harness.action_rx.recv().expect("Failed to receive save blocks action");
-        if let PersistenceAction::SaveBlocks(saved_blocks, _) = received_action {
+        if let PersistenceAction::SaveBlocks(saved_blocks, _, _) = received_action {
             // only blocks.len() - tree_config.memory_block_buffer_target() will be
             // persisted
             let expected_persist_len =
@@ -3645,6 +3865,13 @@ mod tests {
         // ensure block is buffered
         let buffered = test_harness.tree.state.buffer.block(&hash).unwrap();
         assert_eq!(buffered.clone_sealed_block(), sealed);
+
+        // the missing parent directly extends the canonical head, so it's downloaded
+        assert_matches!(
+            outcome.event,
+            Some(TreeEvent::Download(DownloadRequest::BlockSet(hashes)))
+                if hashes.len() == 1 && hashes.contains(&sealed.parent_hash)
+        );
     }
 
     #[test]
@@ -3688,6 +3915,7 @@ mod tests {
                         sidecar: ExecutionPayloadSidecar::none(),
                     },
                     tx,
+                    span: Span::none(),
                 }
                 .into(),
             ))
@@ -4001,7 +4229,7 @@ mod tests {
 
         // get rid of the prev action
         let received_action = test_harness.action_rx.recv().unwrap();
-        let PersistenceAction::SaveBlocks(saved_blocks, sender) = received_action else {
+        let PersistenceAction::SaveBlocks(saved_blocks, _, sender) = received_action else {
             panic!("received wrong action");
         };
         assert_eq!(saved_blocks, vec![blocks[0].clone(), blocks[1].clone()]);
@@ -4194,6 +4422,71 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_queue_blocks_to_persist_while_saving() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+        let mut test_block_builder = TestBlockBuilder::eth();
+
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(0..10).collect();
+        test_harness = test_harness.with_blocks(blocks[..6].to_vec());
+        test_harness.tree.persistence_state.last_persisted_block =
+            blocks[0].recovered_block().num_hash();
+        test_harness.tree.config =
+            TreeConfig::default().with_persistence_threshold(2).with_memory_block_buffer_target(2);
+
+        // the canonical head is block 5, so blocks 1 to 3 are saved
+        test_harness.tree.advance_persistence().unwrap();
+        let PersistenceAction::SaveBlocks(saved_blocks, _, first_sender) =
+            test_harness.action_rx.recv().unwrap()
+        else {
+            panic!("received wrong action");
+        };
+        assert_eq!(saved_blocks, blocks[1..4]);
+
+        for block in &blocks[6..] {
+            test_harness.tree.state.tree_state.insert_executed(block.clone());
+        }
+        test_harness
+            .tree
+            .state
+            .tree_state
+            .set_canonical_head(blocks[9].recovered_block().num_hash());
+
+        // the next blocks are saved while the first blocks are still in progress
+        test_harness.tree.advance_persistence().unwrap();
+        let PersistenceAction::SaveBlocks(saved_blocks, _, _second_sender) =
+            test_harness.action_rx.recv().unwrap()
+        else {
+            panic!("received wrong action");
+        };
+        assert_eq!(saved_blocks, blocks[4..8]);
+        assert_eq!(
+            test_harness.tree.persistence_state.current_action().cloned(),
+            Some(CurrentPersistenceAction::SavingBlocks {
+                highest: blocks[3].recovered_block().num_hash()
+            })
+        );
+
+        // at most one block save operation is queued
+        test_harness.tree.advance_persistence().unwrap();
+        assert!(test_harness.action_rx.try_recv().is_err());
+
+        // once the first blocks are persisted, the queued operation is in progress
+        first_sender.send(Some(blocks[3].recovered_block().num_hash())).unwrap();
+        test_harness.tree.advance_persistence().unwrap();
+        assert_eq!(
+            test_harness.tree.persistence_state.last_persisted_block,
+            blocks[3].recovered_block().num_hash()
+        );
+        assert_eq!(
+            test_harness.tree.persistence_state.current_action().cloned(),
+            Some(CurrentPersistenceAction::SavingBlocks {
+                highest: blocks[7].recovered_block().num_hash()
+            })
+        );
+    }
+
     #[tokio::test]
     async fn test_engine_tree_fcu_missing_head() {
         let chain_spec = MAINNET.clone();
@@ -4268,6 +4561,44 @@ mod tests {
         test_harness.check_canon_head(fork_chain_last_hash);
     }
 
+    #[tokio::test]
+    async fn test_engine_tree_fcu_reorg_exceeding_max_depth() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec.clone());
+        let guard = ReorgGuard::new(1);
+        let config = std::mem::take(&mut test_harness.tree.config);
+        test_harness.tree.config = config.with_reorg_guard(Some(guard.clone()));
+
+        let main_chain: Vec<_> = test_harness.block_builder.get_executed_blocks(0..5).collect();
+        let main_chain_last_hash = main_chain.last().unwrap().recovered_block().hash();
+        test_harness = test_harness.with_blocks(main_chain.clone());
+
+        // the reorg removes the last two canonical blocks
+        let fork_chain = test_harness.block_builder.create_fork(main_chain[2].recovered_block(), 3);
+        let fork_chain_last_hash = fork_chain.last().unwrap().hash();
+
+        for block in &fork_chain {
+            test_harness.insert_block(block.clone()).unwrap();
+        }
+
+        test_harness.send_fcu(fork_chain_last_hash, ForkchoiceStatus::Syncing).await;
+        test_harness.check_fork_chain_insertion(fork_chain.clone()).await;
+        test_harness.check_fcu(fork_chain_last_hash, ForkchoiceStatus::Syncing).await;
+
+        // the canonical chain is unchanged
+        test_harness.check_canon_head(main_chain_last_hash);
+        let rejected = RejectedReorg { head: fork_chain_last_hash, depth: 2 };
+        assert_eq!(guard.rejected_reorg(), Some(rejected));
+
+        // the reorg is performed once it is forced
+        assert_eq!(guard.force_reorg(fork_chain_last_hash), Some(rejected));
+        test_harness.send_fcu(fork_chain_last_hash, ForkchoiceStatus::Valid).await;
+        test_harness.check_canon_commit(fork_chain_last_hash).await;
+        test_harness.check_fcu(fork_chain_last_hash, ForkchoiceStatus::Valid).await;
+        test_harness.check_canon_head(fork_chain_last_hash);
+        assert_eq!(guard.rejected_reorg(), None);
+    }
+
     #[tokio::test]
     async fn test_engine_tree_live_sync_transition_required_blocks_requested() {
         reth_tracing::init_test_tracing();
@@ -3,6 +3,7 @@
 use crate::tree::{
     cached_state::{CachedStateMetrics, ProviderCacheBuilder, ProviderCaches, SavedCache},
     payload_processor::{
+        prefix_sets::{PrefixSetsCollector, TouchedAccount},
         prewarm::{PrewarmCacheTask, PrewarmContext, PrewarmTaskEvent},
         sparse_trie::StateRootComputeOutcome,
     },
@@ -23,7 +24,7 @@ use reth_provider::{
     StateProviderFactory, StateReader,
 };
 use reth_revm::{db::BundleState, state::EvmState};
-use reth_trie::TrieInput;
+use reth_trie::{prefix_set::TriePrefixSetsMut, TrieInput};
 use reth_trie_parallel::{
     proof_task::{ProofTaskCtx, ProofTaskManager},
     root::ParallelStateRootError,
@@ -39,6 +40,7 @@ use std::{
 
 pub mod executor;
 pub mod multiproof;
+mod prefix_sets;
 pub mod prewarm;
 pub mod sparse_trie;
 
@@ -191,17 +193,27 @@ where
             }
         });
 
-        PayloadHandle { to_multi_proof, prewarm_handle, state_root: Some(state_root_rx) }
+        PayloadHandle {
+            to_multi_proof,
+            prewarm_handle,
+            state_root: Some(state_root_rx),
+            to_prefix_sets: None,
+            prefix_sets: None,
+        }
     }
 
     /// Spawn cache prewarming exclusively.
     ///
+    /// If `collect_prefix_sets` is set, this also spawns a task that collects the trie prefix sets
+    /// from the state updates of the execution, see [`PayloadHandle::prefix_sets`].
+    ///
     /// Returns a [`PayloadHandle`] to communicate with the task.
     pub(super) fn spawn_cache_exclusive<P>(
         &self,
         header: SealedHeaderFor<N>,
         transactions: VecDeque<Recovered<N::SignedTx>>,
         provider_builder: StateProviderBuilder<N, P>,
+        collect_prefix_sets: bool,
     ) -> PayloadHandle
     where
         P: BlockReader
@@ -212,7 +224,26 @@ where
             + 'static,
     {
         let prewarm_handle = self.spawn_caching_with(header, transactions, provider_builder, None);
-        PayloadHandle { to_multi_proof: None, prewarm_handle, state_root: None }
+
+        let (to_prefix_sets, prefix_sets) = if collect_prefix_sets {
+            let (to_prefix_sets, touched_accounts_rx) = channel();
+            let (prefix_sets_tx, prefix_sets_rx) = channel();
+            self.executor.spawn_blocking(move || {
+                let _ =
+                    prefix_sets_tx.send(PrefixSetsCollector::default().run(touched_accounts_rx));
+            });
+            (Some(to_prefix_sets), Some(prefix_sets_rx))
+        } else {
+            (None, None)
+        };
+
+        PayloadHandle {
+            to_multi_proof: None,
+            prewarm_handle,
+            state_root: None,
+            to_prefix_sets,
+            prefix_sets,
+        }
     }
 
     /// Spawn prewarming optionally wired to the multiproof task for target updates.
@@ -285,6 +316,10 @@ pub struct PayloadHandle {
     prewarm_handle: CacheTaskHandle,
     /// Receiver for the state root
     state_root: Option<mpsc::Receiver<Result<StateRootComputeOutcome, ParallelStateRootError>>>,
+    /// Channel for the accounts touched by the evm state updates
+    to_prefix_sets: Option<Sender<Vec<TouchedAccount>>>,
+    /// Receiver for the prefix sets collected from the evm state updates
+    prefix_sets: Option<mpsc::Receiver<TriePrefixSetsMut>>,
 }
 
 impl PayloadHandle {
@@ -301,17 +336,34 @@ impl PayloadHandle {
             .map_err(|_| ParallelStateRootError::Other("sparse trie task dropped".to_string()))?
     }
 
+    /// Awaits the trie prefix sets collected from the state updates of the execution.
+    ///
+    /// This must be called after the execution finished and the hooks returned by
+    /// [`Self::state_hook`] were dropped.
+    ///
+    /// Returns `None` if payload processing was started without prefix sets collection.
+    pub(super) fn prefix_sets(&mut self) -> Option<TriePrefixSetsMut> {
+        // no more state updates will be sent
+        self.to_prefix_sets.take();
+        self.prefix_sets.take()?.recv().ok()
+    }
+
     /// Returns a state hook to be used to send state updates to this task.
     ///
-    /// If a multiproof task is spawned the hook will notify it about new states.
+    /// If a multiproof task is spawned the hook will notify it about new states. If prefix sets
+    /// are collected, the hook sends the touched accounts of the state updates to the collector.
     pub fn state_hook(&self) -> impl OnStateHook {
         // convert the channel into a `StateHookSender` that emits an event on drop
         let to_multi_proof = self.to_multi_proof.clone().map(StateHookSender::new);
+        let to_prefix_sets = self.to_prefix_sets.clone();
 
         move |source: StateChangeSource, state: &EvmState| {
             if let Some(sender) = &to_multi_proof {
                 let _ = sender.send(MultiProofMessage::StateUpdate(source, state.clone()));
             }
+            if let Some(sender) = &to_prefix_sets {
+                let _ = sender.send(TouchedAccount::from_state(state));
+            }
         }
     }
 
@@ -0,0 +1,162 @@
+//! Trie prefix sets collected from the state updates of the executed block.
+
+use alloy_primitives::{keccak256, Address, B256, U256};
+use reth_revm::state::EvmState;
+use reth_trie::{
+    prefix_set::{PrefixSetMut, TriePrefixSetsMut},
+    Nibbles,
+};
+use std::sync::mpsc::Receiver;
+
+/// An account touched by a state update, along with its changed storage slots.
+///
+/// This is sent by the state hook, the keys are only hashed by the [`PrefixSetsCollector`] to keep
+/// the overhead on the execution thread minimal.
+#[derive(Debug)]
+pub(super) struct TouchedAccount {
+    /// The address of the account.
+    address: Address,
+    /// Whether the account was selfdestructed.
+    destroyed: bool,
+    /// The changed storage slots of the account.
+    changed_slots: Vec<U256>,
+}
+
+impl TouchedAccount {
+    /// Returns the touched accounts of the state update.
+    pub(super) fn from_state(state: &EvmState) -> Vec<Self> {
+        state
+            .iter()
+            .filter(|(_, account)| account.is_touched())
+            .map(|(address, account)| Self {
+                address: *address,
+                destroyed: account.is_selfdestructed(),
+                changed_slots: account
+                    .storage
+                    .iter()
+                    .filter(|(_, value)| value.is_changed())
+                    .map(|(slot, _)| *slot)
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+/// Builds the trie prefix sets of a block from the accounts touched during its execution.
+///
+/// The resulting prefix sets are the same as the ones constructed from the
+/// [`HashedPostState`](reth_trie::HashedPostState) of the block, but are ready as soon as the
+/// execution finishes.
+#[derive(Debug, Default)]
+pub(super) struct PrefixSetsCollector {
+    prefix_sets: TriePrefixSetsMut,
+}
+
+impl PrefixSetsCollector {
+    /// Adds the accounts touched by a state update to the prefix sets.
+    pub(super) fn on_touched_accounts(&mut self, accounts: Vec<TouchedAccount>) {
+        for account in accounts {
+            let hashed_address = keccak256(account.address);
+            self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+
+            if account.destroyed {
+                self.prefix_sets.destroyed_accounts.insert(hashed_address);
+                self.prefix_sets.storage_prefix_sets.insert(hashed_address, PrefixSetMut::all());
+                continue
+            }
+
+            // the account is recreated if it was destroyed by a previous update
+            self.prefix_sets.destroyed_accounts.remove(&hashed_address);
+            if !account.changed_slots.is_empty() {
+                self.prefix_sets
+                    .storage_prefix_sets
+                    .entry(hashed_address)
+                    .or_default()
+                    .extend_keys(
+                        account
+                            .changed_slots
+                            .into_iter()
+                            .map(|slot| Nibbles::unpack(keccak256(B256::from(slot)))),
+                    );
+            }
+        }
+    }
+
+    /// Collects touched accounts until all senders are dropped and returns the prefix sets.
+    pub(super) fn run(mut self, updates: Receiver<Vec<TouchedAccount>>) -> TriePrefixSetsMut {
+        for accounts in updates {
+            self.on_touched_accounts(accounts);
+        }
+        self.prefix_sets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tree::payload_processor::evm_state_to_hashed_post_state;
+    use reth_trie::{prefix_set::PrefixSet, HashedPostState};
+    use revm_primitives::HashMap;
+    use revm_state::{Account, AccountInfo, AccountStatus, EvmStorageSlot};
+
+    fn account(status: AccountStatus, slots: &[(u64, u64)]) -> Account {
+        Account {
+            info: AccountInfo { balance: U256::from(1), ..Default::default() },
+            storage: slots
+                .iter()
+                .map(|(slot, value)| {
+                    (U256::from(*slot), EvmStorageSlot::new_changed(U256::ZERO, U256::from(*value)))
+                })
+                .collect(),
+            status,
+        }
+    }
+
+    /// Asserts that the prefix sets contain the same keys.
+    fn assert_prefix_sets_eq(got: TriePrefixSetsMut, expected: TriePrefixSetsMut) {
+        let (got, expected) = (got.freeze(), expected.freeze());
+        let keys = |set: &PrefixSet| set.iter().cloned().collect::<Vec<_>>();
+        assert_eq!(keys(&got.account_prefix_set), keys(&expected.account_prefix_set));
+        assert_eq!(got.destroyed_accounts, expected.destroyed_accounts);
+        assert_eq!(got.storage_prefix_sets.len(), expected.storage_prefix_sets.len());
+
+        // only the sets that consider all keys changed contain an unrelated key
+        let unrelated = Nibbles::unpack(B256::repeat_byte(0xff));
+        for (hashed_address, mut expected) in expected.storage_prefix_sets {
+            let mut got = got.storage_prefix_sets[&hashed_address].clone();
+            assert_eq!(keys(&got), keys(&expected));
+            assert_eq!(got.contains(&unrelated), expected.contains(&unrelated));
+        }
+    }
+
+    #[test]
+    fn prefix_sets_match_hashed_state() {
+        let destroyed = Address::with_last_byte(1);
+        let recreated = Address::with_last_byte(2);
+        let changed = Address::with_last_byte(3);
+
+        let updates: Vec<EvmState> = vec![
+            HashMap::from_iter([
+                (destroyed, account(AccountStatus::Touched, &[(1, 1)])),
+                (recreated, account(AccountStatus::Touched | AccountStatus::SelfDestructed, &[])),
+                (changed, account(AccountStatus::Touched, &[(1, 1), (2, 2)])),
+            ]),
+            HashMap::from_iter([
+                (destroyed, account(AccountStatus::Touched | AccountStatus::SelfDestructed, &[])),
+                (recreated, account(AccountStatus::Touched, &[(3, 3)])),
+                (changed, account(AccountStatus::Touched, &[(2, 3), (4, 4)])),
+                // not touched accounts are ignored
+                (Address::with_last_byte(4), account(AccountStatus::Loaded, &[(1, 1)])),
+            ]),
+        ];
+
+        let mut collector = PrefixSetsCollector::default();
+        let mut hashed_state = HashedPostState::default();
+        for update in updates {
+            collector.on_touched_accounts(TouchedAccount::from_state(&update));
+            hashed_state.extend(evm_state_to_hashed_post_state(update));
+        }
+
+        assert_prefix_sets_eq(collector.prefix_sets, hashed_state.construct_prefix_sets());
+    }
+}
@@ -2,7 +2,7 @@ use alloy_eips::BlockNumHash;
 use alloy_primitives::B256;
 use std::time::Instant;
 use tokio::sync::oneshot;
-use tracing::trace;
+use tracing::{trace, Span};
 
 /// The state of the persistence task.
 #[derive(Default, Debug)]
@@ -15,6 +15,12 @@ pub struct PersistenceState {
     /// sent when done. A None value means there's no persistence task in progress.
     pub(crate) rx:
         Option<(oneshot::Receiver<Option<BlockNumHash>>, Instant, CurrentPersistenceAction)>,
+    /// The span of the block save operation in progress, which new payloads processed
+    /// concurrently are linked to.
+    pub(crate) span: Option<Span>,
+    /// The block save operation started while the blocks of the operation in progress are
+    /// committed, so that its blocks are prepared in the meantime.
+    pub(crate) queued_save: Option<QueuedSave>,
 }
 
 impl PersistenceState {
@@ -32,15 +38,48 @@ impl PersistenceState {
     ) {
         self.rx =
             Some((rx, Instant::now(), CurrentPersistenceAction::RemovingBlocks { new_tip_num }));
+        self.span = None;
     }
 
     /// Sets the state for a block save operation.
+    ///
+    /// If blocks are already being saved, the operation is queued until they are persisted.
     pub(crate) fn start_save(
         &mut self,
         highest: BlockNumHash,
+        span: Span,
         rx: oneshot::Receiver<Option<BlockNumHash>>,
     ) {
+        if self.in_progress() {
+            debug_assert!(self.can_queue_save(), "only one block save operation can be queued");
+            self.queued_save = Some(QueuedSave { rx, started_at: Instant::now(), highest, span });
+            return
+        }
+
         self.rx = Some((rx, Instant::now(), CurrentPersistenceAction::SavingBlocks { highest }));
+        self.span = Some(span);
+    }
+
+    /// Returns `true` if blocks are being saved and no other block save operation is queued yet.
+    pub(crate) const fn can_queue_save(&self) -> bool {
+        matches!(self.rx, Some((_, _, CurrentPersistenceAction::SavingBlocks { .. }))) &&
+            self.queued_save.is_none()
+    }
+
+    /// Returns the highest block that is persisted or being saved.
+    pub(crate) const fn highest_saved_block(&self) -> BlockNumHash {
+        if let Some(queued) = &self.queued_save {
+            return queued.highest
+        }
+        if let Some((_, _, CurrentPersistenceAction::SavingBlocks { highest })) = &self.rx {
+            return *highest
+        }
+        self.last_persisted_block
+    }
+
+    /// Returns the span of the block save operation in progress, if any.
+    pub(crate) fn span(&self) -> Option<&Span> {
+        self.span.as_ref().filter(|_| self.in_progress())
     }
 
     /// Returns the current persistence action. If there is no persistence task in progress, then
@@ -50,6 +89,8 @@ impl PersistenceState {
     }
 
     /// Sets state for a finished persistence task.
+    ///
+    /// If a block save operation is queued, it becomes the operation in progress.
     pub(crate) fn finish(
         &mut self,
         last_persisted_block_hash: B256,
@@ -57,11 +98,34 @@ impl PersistenceState {
     ) {
         trace!(target: "engine::tree", block= %last_persisted_block_number, hash=%last_persisted_block_hash, "updating persistence state");
         self.rx = None;
+        self.span = None;
         self.last_persisted_block =
             BlockNumHash::new(last_persisted_block_number, last_persisted_block_hash);
+        self.start_queued_save();
+    }
+
+    /// Makes the queued block save operation, if any, the operation in progress.
+    pub(crate) fn start_queued_save(&mut self) {
+        if let Some(QueuedSave { rx, started_at, highest, span }) = self.queued_save.take() {
+            self.rx = Some((rx, started_at, CurrentPersistenceAction::SavingBlocks { highest }));
+            self.span = Some(span);
+        }
     }
 }
 
+/// A block save operation that was started while the previous one was still in progress.
+#[derive(Debug)]
+pub(crate) struct QueuedSave {
+    /// Receiver end of the channel the result of the operation is sent to.
+    rx: oneshot::Receiver<Option<BlockNumHash>>,
+    /// When the operation was started.
+    started_at: Instant,
+    /// The highest block being saved.
+    highest: BlockNumHash,
+    /// The span of the operation.
+    span: Span,
+}
+
 /// The currently running persistence action.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum CurrentPersistenceAction {
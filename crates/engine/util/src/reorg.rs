@@ -147,7 +147,7 @@ where
             let next = ready!(this.stream.poll_next_unpin(cx));
             let item = match (next, &this.last_forkchoice_state) {
                 (
-                    Some(BeaconEngineMessage::NewPayload { payload, tx }),
+                    Some(BeaconEngineMessage::NewPayload { payload, tx, span }),
                     Some(last_forkchoice_state),
                 ) if this.forkchoice_states_forwarded > this.frequency &&
                         // Only enter reorg state if new payload attaches to current head.
@@ -177,6 +177,7 @@ where
                             return Poll::Ready(Some(BeaconEngineMessage::NewPayload {
                                 payload,
                                 tx,
+                                span,
                             }))
                         }
                     };
@@ -195,11 +196,12 @@ where
 
                     let queue = VecDeque::from([
                         // Current payload
-                        BeaconEngineMessage::NewPayload { payload, tx },
+                        BeaconEngineMessage::NewPayload { payload, tx, span },
                         // Reorg payload
                         BeaconEngineMessage::NewPayload {
                             payload: Engine::block_to_payload(reorg_block),
                             tx: reorg_payload_tx,
+                            span: Span::none(),
                         },
                         // Reorg forkchoice state
                         BeaconEngineMessage::ForkchoiceUpdated {
@@ -40,7 +40,7 @@ where
         loop {
             let next = ready!(this.stream.poll_next_unpin(cx));
             let item = match next {
-                Some(BeaconEngineMessage::NewPayload { payload, tx }) => {
+                Some(BeaconEngineMessage::NewPayload { payload, tx, span }) => {
                     if this.skipped < this.threshold {
                         *this.skipped += 1;
                         tracing::warn!(
@@ -55,7 +55,7 @@ where
                         continue
                     }
                     *this.skipped = 0;
-                    Some(BeaconEngineMessage::NewPayload { payload, tx })
+                    Some(BeaconEngineMessage::NewPayload { payload, tx, span })
                 }
                 next => next,
             };
@@ -12,12 +12,12 @@ use alloy_consensus::EMPTY_OMMER_ROOT_HASH;
 use alloy_eips::merge::ALLOWED_FUTURE_BLOCK_TIME_SECONDS;
 use alloy_primitives::U256;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
-use reth_consensus::{Consensus, ConsensusError, FullConsensus, HeaderValidator};
+use reth_consensus::{Consensus, ConsensusError, FullConsensus, HeaderRules, HeaderValidator};
 use reth_consensus_common::validation::{
     validate_4844_header_standalone, validate_against_parent_4844,
     validate_against_parent_eip1559_base_fee, validate_against_parent_hash_number,
     validate_against_parent_timestamp, validate_block_pre_execution, validate_body_against_header,
-    validate_header_base_fee, validate_header_extra_data, validate_header_gas,
+    validate_header_base_fee, validate_header_gas,
 };
 use reth_execution_types::BlockExecutionResult;
 use reth_primitives_traits::{
@@ -26,22 +26,38 @@ use reth_primitives_traits::{
 };
 use std::{fmt::Debug, sync::Arc, time::SystemTime};
 
+mod rules;
+pub use rules::EthHeaderRules;
+
 mod validation;
 pub use validation::validate_block_post_execution;
 
 /// Ethereum beacon consensus
 ///
 /// This consensus engine does basic checks as outlined in the execution specs.
+///
+/// The extra data, nonce and difficulty are validated by the [`HeaderRules`], which are the
+/// [`EthHeaderRules`] by default.
 #[derive(Debug, Clone)]
-pub struct EthBeaconConsensus<ChainSpec> {
+pub struct EthBeaconConsensus<ChainSpec, Rules = EthHeaderRules> {
     /// Configuration
     chain_spec: Arc<ChainSpec>,
+    /// Rules for the extra data, nonce and difficulty.
+    rules: Rules,
 }
 
 impl<ChainSpec: EthChainSpec + EthereumHardforks> EthBeaconConsensus<ChainSpec> {
     /// Create a new instance of [`EthBeaconConsensus`]
     pub const fn new(chain_spec: Arc<ChainSpec>) -> Self {
-        Self { chain_spec }
+        Self { chain_spec, rules: EthHeaderRules::new() }
+    }
+}
+
+impl<ChainSpec: EthChainSpec + EthereumHardforks, Rules> EthBeaconConsensus<ChainSpec, Rules> {
+    /// Replaces the rules for the extra data, nonce and difficulty, e.g. with the rules of a
+    /// proof-of-authority chain.
+    pub fn with_header_rules<R>(self, rules: R) -> EthBeaconConsensus<ChainSpec, R> {
+        EthBeaconConsensus { chain_spec: self.chain_spec, rules }
     }
 
     /// Checks the gas limit for consistency between parent and self headers.
@@ -94,9 +110,10 @@ impl<ChainSpec: EthChainSpec + EthereumHardforks> EthBeaconConsensus<ChainSpec>
     }
 }
 
-impl<ChainSpec, N> FullConsensus<N> for EthBeaconConsensus<ChainSpec>
+impl<ChainSpec, Rules, N> FullConsensus<N> for EthBeaconConsensus<ChainSpec, Rules>
 where
     ChainSpec: Send + Sync + EthChainSpec + EthereumHardforks + Debug,
+    Rules: HeaderRules<N::BlockHeader>,
     N: NodePrimitives,
 {
     fn validate_block_post_execution(
@@ -108,10 +125,11 @@ where
     }
 }
 
-impl<B, ChainSpec: Send + Sync + EthChainSpec + EthereumHardforks + Debug> Consensus<B>
-    for EthBeaconConsensus<ChainSpec>
+impl<B, ChainSpec: Send + Sync + EthChainSpec + EthereumHardforks + Debug, Rules> Consensus<B>
+    for EthBeaconConsensus<ChainSpec, Rules>
 where
     B: Block,
+    Rules: HeaderRules<B::Header>,
 {
     type Error = ConsensusError;
 
@@ -128,10 +146,11 @@ where
     }
 }
 
-impl<H, ChainSpec: Send + Sync + EthChainSpec + EthereumHardforks + Debug> HeaderValidator<H>
-    for EthBeaconConsensus<ChainSpec>
+impl<H, ChainSpec: Send + Sync + EthChainSpec + EthereumHardforks + Debug, Rules> HeaderValidator<H>
+    for EthBeaconConsensus<ChainSpec, Rules>
 where
     H: BlockHeader,
+    Rules: HeaderRules<H>,
 {
     fn validate_header(&self, header: &SealedHeader<H>) -> Result<(), ConsensusError> {
         validate_header_gas(header.header())?;
@@ -179,8 +198,8 @@ where
 
         validate_against_parent_timestamp(header.header(), parent.header())?;
 
-        // TODO Check difficulty increment between parent and self
-        // Ace age did increment it by some formula that we need to follow.
+        self.rules.validate_difficulty_against_parent(header.header(), parent.header())?;
+
         self.validate_against_parent_gas_limit(header, parent)?;
 
         validate_against_parent_eip1559_base_fee(
@@ -204,15 +223,9 @@ where
     ) -> Result<(), ConsensusError> {
         let is_post_merge = self.chain_spec.is_paris_active_at_block(header.number());
 
-        if is_post_merge {
-            if !header.difficulty().is_zero() {
-                return Err(ConsensusError::TheMergeDifficultyIsNotZero)
-            }
-
-            if !header.nonce().is_some_and(|nonce| nonce.is_zero()) {
-                return Err(ConsensusError::TheMergeNonceIsNotZero)
-            }
+        self.rules.validate_difficulty_and_nonce(header, is_post_merge)?;
 
+        if is_post_merge {
             if header.ommers_hash() != EMPTY_OMMER_ROOT_HASH {
                 return Err(ConsensusError::TheMergeOmmerRootIsNotEmpty)
             }
@@ -225,15 +238,12 @@ where
             // Block validation with respect to the parent should ensure that the block timestamp
             // is greater than its parent timestamp.
 
-            // validate header extra data for all networks post merge
-            validate_header_extra_data(header)?;
-
             // mixHash is used instead of difficulty inside EVM
             // https://eips.ethereum.org/EIPS/eip-4399#using-mixhash-field-instead-of-difficulty
         } else {
-            // Note: This does not perform any pre merge checks for difficulty, mix_hash & nonce
-            // because those are deprecated and the expectation is that a reth node syncs in reverse
-            // order, making those checks obsolete.
+            // Note: This does not perform any pre merge checks for mix_hash, the difficulty and
+            // nonce are only validated by the header rules. The expectation is that a reth node
+            // syncs in reverse order, making the proof-of-work checks obsolete.
 
             // Check if timestamp is in the future. Clock can drift but this can be consensus issue.
             let present_timestamp =
@@ -245,11 +255,9 @@ where
                     present_timestamp,
                 })
             }
-
-            validate_header_extra_data(header)?;
         }
 
-        Ok(())
+        self.rules.validate_extra_data(header)
     }
 }
 
@@ -352,4 +360,55 @@ mod tests {
             Ok(())
         );
     }
+
+    #[test]
+    fn custom_header_rules() {
+        /// Clique-like rules, with a vanity and a signature in the extra data.
+        #[derive(Debug)]
+        struct PoaRules;
+
+        impl<H: BlockHeader> HeaderRules<H> for PoaRules {
+            fn validate_extra_data(&self, header: &H) -> Result<(), ConsensusError> {
+                if header.extra_data().len() < 32 + 65 {
+                    return Err(ConsensusError::Other("missing signature".to_string()))
+                }
+                Ok(())
+            }
+
+            fn validate_difficulty_and_nonce(
+                &self,
+                header: &H,
+                _is_post_merge: bool,
+            ) -> Result<(), ConsensusError> {
+                if header.difficulty() > U256::from(2) {
+                    return Err(ConsensusError::Other("invalid difficulty".to_string()))
+                }
+                Ok(())
+            }
+        }
+
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().paris_activated().build());
+        let header = reth_primitives_traits::Header {
+            difficulty: U256::from(2),
+            extra_data: vec![0; 32 + 65].into(),
+            ..Default::default()
+        };
+
+        // the ethereum rules reject the header
+        let consensus = EthBeaconConsensus::new(chain_spec);
+        assert_eq!(
+            consensus.validate_header_with_total_difficulty(&header, U256::ZERO),
+            Err(ConsensusError::TheMergeDifficultyIsNotZero)
+        );
+
+        let consensus = consensus.with_header_rules(PoaRules);
+        assert_eq!(consensus.validate_header_with_total_difficulty(&header, U256::ZERO), Ok(()));
+        assert_eq!(
+            consensus.validate_header_with_total_difficulty(
+                &reth_primitives_traits::Header { difficulty: U256::from(2), ..Default::default() },
+                U256::ZERO
+            ),
+            Err(ConsensusError::Other("missing signature".to_string()))
+        );
+    }
 }
@@ -0,0 +1,45 @@
+use reth_consensus::{ConsensusError, HeaderRules};
+use reth_consensus_common::validation::validate_header_extra_data;
+use reth_primitives_traits::BlockHeader;
+
+/// The Ethereum [`HeaderRules`].
+///
+/// The extra data is limited to 32 bytes. After the merge, the difficulty and the nonce must be
+/// zero. Before the merge, the difficulty and the nonce are not validated, since they are only
+/// relevant for proof-of-work and blocks are expected to be synced in reverse.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct EthHeaderRules;
+
+impl EthHeaderRules {
+    /// Creates the Ethereum rules.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl<H: BlockHeader> HeaderRules<H> for EthHeaderRules {
+    fn validate_extra_data(&self, header: &H) -> Result<(), ConsensusError> {
+        validate_header_extra_data(header)
+    }
+
+    fn validate_difficulty_and_nonce(
+        &self,
+        header: &H,
+        is_post_merge: bool,
+    ) -> Result<(), ConsensusError> {
+        if !is_post_merge {
+            return Ok(())
+        }
+
+        if !header.difficulty().is_zero() {
+            return Err(ConsensusError::TheMergeDifficultyIsNotZero)
+        }
+
+        if !header.nonce().is_some_and(|nonce| nonce.is_zero()) {
+            return Err(ConsensusError::TheMergeNonceIsNotZero)
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+//! Helpers for testing alternative EVM implementations against the Ethereum EVM.
+//!
+//! An alternative EVM, e.g. an AOT-compiled or a parallel EVM, is plugged in by its [`EvmFactory`]
+//! via [`EthEvmConfig::with_evm_factory`]. The helpers in this module execute blocks with both the
+//! alternative and the Ethereum EVM and assert that the results are identical.
+//!
+//! [`EvmFactory`]: reth_evm::EvmFactory
+
+use crate::EthEvmConfig;
+use alloc::{string::ToString, sync::Arc};
+use alloy_consensus::BlockHeader;
+use reth_chainspec::ChainSpec;
+use reth_ethereum_primitives::{Block, EthPrimitives, Receipt};
+use reth_evm::{
+    execute::{BasicBlockExecutorProvider, BlockExecutionError, BlockExecutorProvider, Executor},
+    ConfigureEvm, Database,
+};
+use reth_execution_types::BlockExecutionOutput;
+use reth_primitives_traits::RecoveredBlock;
+
+/// Executes the block on the given state with the EVM of the given factory and with the Ethereum
+/// EVM, and returns the output of the Ethereum EVM.
+///
+/// # Panics
+///
+/// If the receipts, requests, gas used or state changes of the two executions differ, or if only
+/// one of them fails.
+pub fn assert_block_conformance<F, DB>(
+    chain_spec: Arc<ChainSpec>,
+    evm_factory: F,
+    db: DB,
+    block: &RecoveredBlock<Block>,
+) -> Result<BlockExecutionOutput<Receipt>, BlockExecutionError>
+where
+    F: 'static,
+    EthEvmConfig<F>: ConfigureEvm<Primitives = EthPrimitives>,
+    DB: Database + Clone,
+{
+    let expected = BasicBlockExecutorProvider::new(EthEvmConfig::new(chain_spec.clone()))
+        .executor(db.clone())
+        .execute(block);
+    let actual = BasicBlockExecutorProvider::new(EthEvmConfig::new_with_evm_factory(
+        chain_spec,
+        evm_factory,
+    ))
+    .executor(db)
+    .execute(block);
+
+    match (&expected, &actual) {
+        (Ok(expected), Ok(actual)) => {
+            assert_eq!(expected, actual, "execution output of block {} differs", block.number())
+        }
+        (Err(expected), Err(actual)) => assert_eq!(
+            expected.to_string(),
+            actual.to_string(),
+            "execution error of block {} differs",
+            block.number()
+        ),
+        (expected, actual) => panic!(
+            "execution result of block {} differs, expected: {expected:?}, got: {actual:?}",
+            block.number()
+        ),
+    }
+
+    expected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::precompiles::{CustomPrecompiles, CustomPrecompilesEvmFactory};
+    use alloy_consensus::Header;
+    use alloy_primitives::{Address, U256};
+    use reth_chainspec::MAINNET;
+    use reth_ethereum_primitives::BlockBody;
+    use revm::database::{CacheDB, EmptyDB};
+
+    #[test]
+    fn block_conformance() {
+        let beneficiary = Address::with_last_byte(1);
+        let block = RecoveredBlock::new_unhashed(
+            Block {
+                header: Header { number: 1, beneficiary, ..Default::default() },
+                body: BlockBody::default(),
+            },
+            vec![],
+        );
+
+        let output = assert_block_conformance(
+            MAINNET.clone(),
+            CustomPrecompilesEvmFactory::new(CustomPrecompiles::default()),
+            CacheDB::<EmptyDB>::default(),
+            &block,
+        )
+        .unwrap();
+
+        // the block reward is paid to the beneficiary
+        assert!(
+            output.state.account(&beneficiary).unwrap().info.as_ref().unwrap().balance > U256::ZERO
+        );
+    }
+}
@@ -4,6 +4,11 @@ use crate::EthEvmConfig;
 use alloc::sync::Arc;
 use reth_chainspec::ChainSpec;
 use reth_evm::execute::BasicBlockExecutorProvider;
+#[cfg(feature = "std")]
+use reth_evm::{
+    parallel::{ParallelBlockExecutorProvider, SpeculativeEvmFactory},
+    EthEvmFactory,
+};
 
 /// Helper type with backwards compatible methods to obtain Ethereum executor
 /// providers.
@@ -20,6 +25,20 @@ impl EthExecutorProvider {
     pub fn mainnet() -> BasicBlockExecutorProvider<EthEvmConfig> {
         BasicBlockExecutorProvider::new(EthEvmConfig::mainnet())
     }
+
+    /// Creates a new experimental ethereum executor provider that executes the transactions of a
+    /// block optimistically in parallel.
+    ///
+    /// Blocks are only executed in parallel by executors with a state snapshot, on the pool set
+    /// with [`ParallelBlockExecutorProvider::with_pool`]. See [`reth_evm::parallel`] for details.
+    #[cfg(feature = "std")]
+    pub fn parallel(
+        chain_spec: Arc<ChainSpec>,
+    ) -> ParallelBlockExecutorProvider<EthEvmConfig<SpeculativeEvmFactory<EthEvmFactory>>> {
+        ParallelBlockExecutorProvider::new(
+            EthEvmConfig::new(chain_spec).with_evm_factory(SpeculativeEvmFactory::default()),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -880,4 +899,86 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn parallel_execution_matches_sequential() {
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+
+        let mut header = chain_spec.genesis_header().clone();
+        header.number = 1;
+        header.gas_limit = 1_000_000;
+        let gas_price = header.base_fee_per_gas.unwrap() as u128 * 2;
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        let secp = Secp256k1::new();
+        let senders = (0..3)
+            .map(|_| {
+                let key_pair = Keypair::new(&secp, &mut generators::rng());
+                let address = public_key_to_address(key_pair.public_key());
+                db.insert_account_info(
+                    address,
+                    AccountInfo { balance: U256::from(ETH_TO_WEI), ..Default::default() },
+                );
+                key_pair
+            })
+            .collect::<Vec<_>>();
+
+        // the beneficiary is credited with the priority fee of every transaction
+        header.beneficiary = public_key_to_address(senders[2].public_key());
+
+        // the third transaction depends on the first one, the last one is sent by the beneficiary
+        let transfers = [
+            (0, 0, address!("0x1000000000000000000000000000000000000001")),
+            (1, 0, address!("0x1000000000000000000000000000000000000002")),
+            (0, 1, address!("0x1000000000000000000000000000000000000001")),
+            (2, 0, address!("0x1000000000000000000000000000000000000003")),
+        ];
+        let transactions = transfers
+            .into_iter()
+            .map(|(sender, nonce, to)| {
+                sign_tx_with_key_pair(
+                    senders[sender],
+                    Transaction::Legacy(TxLegacy {
+                        chain_id: Some(chain_spec.chain.id()),
+                        nonce,
+                        gas_price,
+                        gas_limit: 21_000,
+                        to: TxKind::Call(to),
+                        value: U256::from(1_000),
+                        input: Bytes::new(),
+                    }),
+                )
+            })
+            .collect();
+        let block = Block { header, body: BlockBody { transactions, ..Default::default() } }
+            .try_into_recovered()
+            .unwrap();
+
+        let expected =
+            executor_provider(chain_spec.clone()).executor(db.clone()).execute(&block).unwrap();
+
+        for threads in [1, 4] {
+            let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+            let mut executor = EthExecutorProvider::parallel(chain_spec.clone())
+                .with_pool(Arc::new(pool))
+                .executor(db.clone())
+                .with_snapshot(|| Some(&db));
+            let result = executor.execute_one(&block).unwrap();
+
+            // the beneficiary observed its balance after it was credited by the preceding
+            // transactions, the dependency of the third transaction is only resolved from the
+            // multi-version map if the first one was speculated before
+            let stats = executor.stats();
+            assert_eq!(stats.transactions, 4);
+            if threads == 1 {
+                assert_eq!(stats.reexecuted, 1);
+            } else {
+                assert!((1..=2).contains(&stats.reexecuted), "{stats:?}");
+            }
+
+            let mut state = executor.into_state();
+            assert_eq!(result, expected.result);
+            assert_eq!(state.take_bundle(), expected.state);
+        }
+    }
 }
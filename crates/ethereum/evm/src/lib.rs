@@ -43,12 +43,18 @@ use reth_ethereum_forks::EthereumHardfork;
 
 pub mod execute;
 
+#[cfg(any(test, feature = "test-utils"))]
+pub mod conformance;
+
 mod build;
 pub use build::EthBlockAssembler;
 
 mod receipt;
 pub use receipt::RethReceiptBuilder;
 
+pub mod precompiles;
+use precompiles::{CustomPrecompiles, CustomPrecompilesEvmFactory};
+
 /// Ethereum-related EVM configuration.
 #[derive(Debug, Clone)]
 pub struct EthEvmConfig<EvmFactory = EthEvmFactory> {
@@ -73,6 +79,32 @@ impl EthEvmConfig {
     pub fn mainnet() -> Self {
         Self::ethereum(MAINNET.clone())
     }
+
+    /// Replaces the EVM with the one created by the given [`EvmFactory`], e.g. an alternative EVM
+    /// implementation.
+    ///
+    /// This keeps the block assembler configuration, e.g. the extra data. The `conformance` module,
+    /// enabled by the `test-utils` feature, has helpers to test the EVM against the Ethereum EVM.
+    pub fn with_evm_factory<F>(self, evm_factory: F) -> EthEvmConfig<F> {
+        EthEvmConfig {
+            executor_factory: EthBlockExecutorFactory::new(
+                RethReceiptBuilder::default(),
+                self.chain_spec().clone(),
+                evm_factory,
+            ),
+            block_assembler: self.block_assembler,
+        }
+    }
+
+    /// Registers the given [`CustomPrecompiles`] on top of the Ethereum precompiles.
+    ///
+    /// This keeps the block assembler configuration, e.g. the extra data.
+    pub fn with_precompiles(
+        self,
+        precompiles: CustomPrecompiles,
+    ) -> EthEvmConfig<CustomPrecompilesEvmFactory> {
+        self.with_evm_factory(CustomPrecompilesEvmFactory::new(precompiles))
+    }
 }
 
 impl<EvmFactory> EthEvmConfig<EvmFactory> {
@@ -0,0 +1,351 @@
+//! Support for injecting additional precompiles into the Ethereum EVM.
+//!
+//! This is intended for chains and devnets that want to expose extra precompile addresses backed
+//! by plain Rust functions without reimplementing [`ConfigureEvm`](reth_evm::ConfigureEvm).
+//!
+//! See [`EthEvmConfig::with_precompiles`](crate::EthEvmConfig::with_precompiles).
+
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use alloy_evm::{eth::EthEvmContext, EthEvm};
+use alloy_primitives::{Address, Bytes};
+use core::fmt;
+use reth_evm::{Database, EvmEnv, EvmFactory};
+use revm::{
+    context::{Cfg, Context, TxEnv},
+    context_interface::{
+        result::{EVMError, HaltReason},
+        ContextTr,
+    },
+    handler::{EthPrecompiles, PrecompileProvider},
+    inspector::{Inspector, NoOpInspector},
+    interpreter::{interpreter::EthInterpreter, Gas, InstructionResult, InterpreterResult},
+    precompile::{PrecompileError, PrecompileOutput, PrecompileResult},
+    primitives::hardfork::SpecId,
+    MainBuilder, MainContext,
+};
+
+/// Function computing the gas cost of a [`CustomPrecompile`] call for the given input.
+pub type PrecompileGasFn = Arc<dyn Fn(&Bytes) -> u64 + Send + Sync>;
+
+/// Function executing a [`CustomPrecompile`] call for the given input.
+pub type PrecompileRunFn = Arc<dyn Fn(&Bytes) -> Result<Bytes, PrecompileError> + Send + Sync>;
+
+/// A precompile backed by a Rust function that is registered at a fixed address.
+///
+/// Gas is metered before the function is invoked: the [`PrecompileGasFn`] hook computes the cost
+/// for the input and the call fails with [`PrecompileError::OutOfGas`] if it exceeds the gas
+/// limit of the call. By default the precompile is free and active for all specs.
+#[derive(Clone)]
+pub struct CustomPrecompile {
+    address: Address,
+    activation: SpecId,
+    gas: PrecompileGasFn,
+    run: PrecompileRunFn,
+}
+
+impl CustomPrecompile {
+    /// Creates a new precompile at the given address that executes the given function.
+    pub fn new<F>(address: Address, run: F) -> Self
+    where
+        F: Fn(&Bytes) -> Result<Bytes, PrecompileError> + Send + Sync + 'static,
+    {
+        Self { address, activation: SpecId::FRONTIER, gas: Arc::new(|_| 0), run: Arc::new(run) }
+    }
+
+    /// Sets the gas metering hook that computes the cost of a call for the given input.
+    pub fn with_gas<F>(mut self, gas: F) -> Self
+    where
+        F: Fn(&Bytes) -> u64 + Send + Sync + 'static,
+    {
+        self.gas = Arc::new(gas);
+        self
+    }
+
+    /// Charges the given fixed amount of gas for every call.
+    pub fn with_fixed_gas(self, gas: u64) -> Self {
+        self.with_gas(move |_| gas)
+    }
+
+    /// Only enables the precompile from the given spec onwards.
+    pub const fn active_from(mut self, spec: SpecId) -> Self {
+        self.activation = spec;
+        self
+    }
+
+    /// Returns the address of the precompile.
+    pub const fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Returns `true` if the precompile is enabled for the given spec.
+    pub fn is_active(&self, spec: SpecId) -> bool {
+        spec.is_enabled_in(self.activation)
+    }
+
+    /// Executes the precompile with the given input and gas limit.
+    pub fn call(&self, input: &Bytes, gas_limit: u64) -> PrecompileResult {
+        let gas_used = (self.gas)(input);
+        if gas_used > gas_limit {
+            return Err(PrecompileError::OutOfGas)
+        }
+        let output = (self.run)(input)?;
+        Ok(PrecompileOutput::new(gas_used, output))
+    }
+}
+
+impl fmt::Debug for CustomPrecompile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomPrecompile")
+            .field("address", &self.address)
+            .field("activation", &self.activation)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A set of [`CustomPrecompile`]s that are registered on top of the Ethereum precompiles.
+///
+/// Custom precompiles take precedence over the Ethereum precompiles, so registering a precompile
+/// at an existing address replaces it.
+#[derive(Debug, Clone, Default)]
+pub struct CustomPrecompiles {
+    precompiles: Arc<Vec<CustomPrecompile>>,
+}
+
+impl CustomPrecompiles {
+    /// Adds the given precompile, replacing any precompile previously registered at its address.
+    pub fn with_precompile(mut self, precompile: CustomPrecompile) -> Self {
+        let precompiles = Arc::make_mut(&mut self.precompiles);
+        precompiles.retain(|p| p.address != precompile.address);
+        precompiles.push(precompile);
+        self
+    }
+
+    /// Returns the number of registered precompiles.
+    pub fn len(&self) -> usize {
+        self.precompiles.len()
+    }
+
+    /// Returns `true` if no precompiles are registered.
+    pub fn is_empty(&self) -> bool {
+        self.precompiles.is_empty()
+    }
+
+    /// Returns the precompile at the given address if it is enabled for the given spec.
+    pub fn get(&self, address: &Address, spec: SpecId) -> Option<&CustomPrecompile> {
+        self.precompiles.iter().find(|p| p.address == *address && p.is_active(spec))
+    }
+
+    /// Returns an iterator over the addresses of all precompiles enabled for the given spec.
+    pub fn active_addresses(&self, spec: SpecId) -> impl Iterator<Item = Address> + '_ {
+        self.precompiles.iter().filter(move |p| p.is_active(spec)).map(|p| p.address)
+    }
+}
+
+impl FromIterator<CustomPrecompile> for CustomPrecompiles {
+    fn from_iter<T: IntoIterator<Item = CustomPrecompile>>(iter: T) -> Self {
+        iter.into_iter().fold(Self::default(), Self::with_precompile)
+    }
+}
+
+/// A [`PrecompileProvider`] that serves [`CustomPrecompiles`] on top of [`EthPrecompiles`].
+#[derive(Debug, Clone)]
+pub struct CustomPrecompilesProvider {
+    /// The Ethereum precompiles for the current spec.
+    inner: EthPrecompiles,
+    /// The additional precompiles.
+    custom: CustomPrecompiles,
+    /// The currently configured spec.
+    spec: SpecId,
+}
+
+impl CustomPrecompilesProvider {
+    /// Creates a new provider with the given custom precompiles for the latest spec.
+    pub fn new(custom: CustomPrecompiles) -> Self {
+        Self { inner: EthPrecompiles::default(), custom, spec: SpecId::LATEST }
+    }
+}
+
+impl<CTX: ContextTr> PrecompileProvider<CTX> for CustomPrecompilesProvider {
+    type Output = InterpreterResult;
+
+    fn set_spec(&mut self, spec: <CTX::Cfg as Cfg>::Spec) {
+        self.spec = spec.clone().into();
+        PrecompileProvider::<CTX>::set_spec(&mut self.inner, spec);
+    }
+
+    fn run(
+        &mut self,
+        context: &mut CTX,
+        address: &Address,
+        bytes: &Bytes,
+        gas_limit: u64,
+    ) -> Result<Option<Self::Output>, PrecompileError> {
+        let Some(precompile) = self.custom.get(address, self.spec) else {
+            return self.inner.run(context, address, bytes, gas_limit)
+        };
+
+        let mut result = InterpreterResult {
+            result: InstructionResult::Return,
+            gas: Gas::new(gas_limit),
+            output: Bytes::new(),
+        };
+
+        match precompile.call(bytes, gas_limit) {
+            Ok(output) => {
+                // gas was already checked against the limit in `CustomPrecompile::call`
+                let _ = result.gas.record_cost(output.gas_used);
+                result.output = output.bytes;
+            }
+            Err(PrecompileError::Fatal(err)) => return Err(PrecompileError::Fatal(err)),
+            Err(err) => {
+                result.result = if matches!(err, PrecompileError::OutOfGas) {
+                    InstructionResult::PrecompileOOG
+                } else {
+                    InstructionResult::PrecompileError
+                };
+            }
+        }
+
+        Ok(Some(result))
+    }
+
+    fn contains(&self, address: &Address) -> bool {
+        self.custom.get(address, self.spec).is_some() || self.inner.contains(address)
+    }
+
+    fn warm_addresses(&self) -> Box<impl Iterator<Item = Address>> {
+        let custom = self.custom.active_addresses(self.spec).collect::<Vec<_>>();
+        let overridden = custom.clone();
+        Box::new(
+            PrecompileProvider::<CTX>::warm_addresses(&self.inner)
+                .filter(move |address| !overridden.contains(address))
+                .chain(custom),
+        )
+    }
+}
+
+/// An Ethereum [`EvmFactory`] that registers [`CustomPrecompiles`] in every EVM it creates.
+#[derive(Debug, Clone, Default)]
+pub struct CustomPrecompilesEvmFactory {
+    precompiles: CustomPrecompiles,
+}
+
+impl CustomPrecompilesEvmFactory {
+    /// Creates a new factory with the given custom precompiles.
+    pub const fn new(precompiles: CustomPrecompiles) -> Self {
+        Self { precompiles }
+    }
+
+    /// Returns the custom precompiles of this factory.
+    pub const fn precompiles(&self) -> &CustomPrecompiles {
+        &self.precompiles
+    }
+}
+
+impl EvmFactory for CustomPrecompilesEvmFactory {
+    type Evm<DB: Database, I: Inspector<EthEvmContext<DB>, EthInterpreter>> =
+        EthEvm<DB, I, CustomPrecompilesProvider>;
+    type Context<DB: Database> = EthEvmContext<DB>;
+    type Tx = TxEnv;
+    type Error<DBError: core::error::Error + Send + Sync + 'static> = EVMError<DBError>;
+    type HaltReason = HaltReason;
+    type Spec = SpecId;
+
+    fn create_evm<DB: Database>(&self, db: DB, input: EvmEnv) -> Self::Evm<DB, NoOpInspector> {
+        let evm = Context::mainnet()
+            .with_db(db)
+            .with_cfg(input.cfg_env)
+            .with_block(input.block_env)
+            .build_mainnet_with_inspector(NoOpInspector {})
+            .with_precompiles(CustomPrecompilesProvider::new(self.precompiles.clone()));
+
+        EthEvm::new(evm, false)
+    }
+
+    fn create_evm_with_inspector<DB: Database, I: Inspector<Self::Context<DB>, EthInterpreter>>(
+        &self,
+        db: DB,
+        input: EvmEnv,
+        inspector: I,
+    ) -> Self::Evm<DB, I> {
+        EthEvm::new(self.create_evm(db, input).into_inner().with_inspector(inspector), true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthEvmConfig;
+    use alloy_primitives::{address, TxKind};
+    use reth_evm::{execute::ProviderError, ConfigureEvm, Evm};
+    use revm::{
+        context::CfgEnv, context_interface::result::ExecutionResult, database::CacheDB,
+        database_interface::EmptyDBTyped,
+    };
+
+    const ECHO: Address = address!("0x0000000000000000000000000000000000000999");
+
+    fn echo() -> CustomPrecompile {
+        CustomPrecompile::new(ECHO, |input| Ok(input.clone()))
+            .with_gas(|input| 100 + input.len() as u64)
+    }
+
+    #[test]
+    fn meters_gas_before_execution() {
+        let precompile = echo();
+        let input = Bytes::from_static(&[1, 2, 3]);
+
+        let output = precompile.call(&input, 103).unwrap();
+        assert_eq!(output.gas_used, 103);
+        assert_eq!(output.bytes, input);
+
+        assert_eq!(precompile.call(&input, 102), Err(PrecompileError::OutOfGas));
+    }
+
+    #[test]
+    fn respects_activation_spec() {
+        let precompiles: CustomPrecompiles =
+            [echo().active_from(SpecId::PRAGUE)].into_iter().collect();
+
+        assert!(precompiles.get(&ECHO, SpecId::CANCUN).is_none());
+        assert!(precompiles.get(&ECHO, SpecId::PRAGUE).is_some());
+        assert_eq!(precompiles.active_addresses(SpecId::CANCUN).count(), 0);
+    }
+
+    #[test]
+    fn replaces_precompile_at_same_address() {
+        let precompiles = CustomPrecompiles::default()
+            .with_precompile(echo())
+            .with_precompile(echo().with_fixed_gas(7));
+
+        assert_eq!(precompiles.len(), 1);
+        let output = precompiles.get(&ECHO, SpecId::LATEST).unwrap().call(&Bytes::new(), 7);
+        assert_eq!(output.unwrap().gas_used, 7);
+    }
+
+    #[test]
+    fn evm_calls_custom_precompile() {
+        let evm_config = EthEvmConfig::mainnet()
+            .with_precompiles([echo()].into_iter().collect::<CustomPrecompiles>());
+
+        let db = CacheDB::<EmptyDBTyped<ProviderError>>::default();
+        let evm_env =
+            EvmEnv { cfg_env: CfgEnv::default().with_spec(SpecId::PRAGUE), ..Default::default() };
+        let mut evm = evm_config.evm_with_env(db, evm_env);
+
+        let input = Bytes::from_static(b"hello");
+        let result = evm
+            .transact(TxEnv {
+                kind: TxKind::Call(ECHO),
+                data: input.clone(),
+                gas_limit: 100_000,
+                ..Default::default()
+            })
+            .unwrap();
+
+        let ExecutionResult::Success { output, .. } = result.result else {
+            panic!("expected successful execution, got {:?}", result.result)
+        };
+        assert_eq!(output.into_data(), input);
+    }
+}
@@ -79,3 +79,57 @@ where
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip4895::Withdrawal;
+    use reth_chainspec::MAINNET;
+    use reth_engine_primitives::WithPayloadAttributesValidator;
+    use reth_ethereum_engine_primitives::EthEngineTypes;
+
+    fn attributes(withdrawals: Option<usize>) -> EthPayloadAttributes {
+        EthPayloadAttributes {
+            // between shanghai and cancun
+            timestamp: 1_700_000_000,
+            prev_randao: Default::default(),
+            suggested_fee_recipient: Default::default(),
+            withdrawals: withdrawals.map(|count| vec![Withdrawal::default(); count]),
+            parent_beacon_block_root: None,
+        }
+    }
+
+    #[test]
+    fn test_payload_attributes_validator() {
+        let validator = WithPayloadAttributesValidator::new(
+            EthereumEngineValidator::new(MAINNET.clone()),
+            |_version: EngineApiMessageVersion, attributes: &EthPayloadAttributes| {
+                if attributes.withdrawals.as_ref().map_or(0, Vec::len) > 1 {
+                    return Err(EngineObjectValidationError::invalid_payload_attributes(
+                        std::io::Error::other("too many withdrawals"),
+                    ))
+                }
+                Ok(())
+            },
+        );
+        let validate = |attributes: &EthPayloadAttributes| {
+            EngineValidator::<EthEngineTypes>::ensure_well_formed_attributes(
+                &validator,
+                EngineApiMessageVersion::V2,
+                attributes,
+            )
+        };
+
+        assert!(validate(&attributes(Some(1))).is_ok());
+        assert!(matches!(
+            validate(&attributes(Some(2))),
+            Err(EngineObjectValidationError::InvalidPayloadAttributes(_))
+        ));
+
+        // the checks of the inner validator run first
+        assert!(matches!(
+            validate(&attributes(None)),
+            Err(EngineObjectValidationError::PayloadAttributes(_))
+        ));
+    }
+}
@@ -1,6 +1,11 @@
 //! Ethereum Node types config.
 
-pub use crate::{payload::EthereumPayloadBuilder, EthereumEngineValidator};
+pub use crate::{
+    payload::{
+        EthereumEvmFactoryPayloadBuilder, EthereumPayloadBuilder, EthereumPrecompilesPayloadBuilder,
+    },
+    EthereumEngineValidator,
+};
 use crate::{EthEngineTypes, EthEvmConfig};
 use reth_chainspec::ChainSpec;
 use reth_consensus::{ConsensusError, FullConsensus};
@@ -13,6 +18,7 @@ use reth_evm::{
     execute::BasicBlockExecutorProvider, ConfigureEvm, EvmFactory, EvmFactoryFor,
     NextBlockEnvAttributes,
 };
+use reth_evm_ethereum::precompiles::{CustomPrecompiles, CustomPrecompilesEvmFactory};
 use reth_network::{EthNetworkPrimitives, NetworkHandle, PeersInfo};
 use reth_node_api::{AddOnsContext, FullNodeComponents, NodeAddOns, TxTy};
 use reth_node_builder::{
@@ -30,14 +36,17 @@ use reth_node_builder::{
 };
 use reth_provider::{providers::ProviderFactoryBuilder, CanonStateSubscriptions, EthStorage};
 use reth_rpc::{eth::core::EthApiFor, ValidationApi};
-use reth_rpc_api::{eth::FullEthApiServer, servers::BlockSubmissionValidationApiServer};
+use reth_rpc_api::{
+    eth::FullEthApiServer,
+    servers::{BlockSubmissionValidationApiServer, PayloadValidationApiServer},
+};
 use reth_rpc_builder::config::RethRpcServerConfig;
 use reth_rpc_eth_types::{error::FromEvmError, EthApiError};
 use reth_rpc_server_types::RethRpcModule;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, EthTransactionPool, PoolTransaction, TransactionPool,
-    TransactionValidationTaskExecutor,
+    blobstore::DiskFileBlobStore, validate::TargetDenylist, EthTransactionPool, PoolTransaction,
+    TransactionPool, TransactionValidationTaskExecutor,
 };
 use reth_trie_db::MerklePatriciaTrie;
 use revm::context::TxEnv;
@@ -75,6 +84,73 @@ impl EthereumNode {
             .consensus(EthereumConsensusBuilder::default())
     }
 
+    /// Returns a [`ComponentsBuilder`] configured for a regular Ethereum node that registers the
+    /// given [`CustomPrecompiles`] on top of the Ethereum precompiles.
+    ///
+    /// The precompiles are used by both the block executor and the payload builder.
+    pub fn components_with_precompiles<Node>(
+        precompiles: CustomPrecompiles,
+    ) -> ComponentsBuilder<
+        Node,
+        EthereumPoolBuilder,
+        BasicPayloadServiceBuilder<EthereumPrecompilesPayloadBuilder>,
+        EthereumNetworkBuilder,
+        EthereumPrecompilesExecutorBuilder,
+        EthereumConsensusBuilder,
+    >
+    where
+        Node: FullNodeTypes<Types: NodeTypes<ChainSpec = ChainSpec, Primitives = EthPrimitives>>,
+        <Node::Types as NodeTypesWithEngine>::Engine: PayloadTypes<
+            BuiltPayload = EthBuiltPayload,
+            PayloadAttributes = EthPayloadAttributes,
+            PayloadBuilderAttributes = EthPayloadBuilderAttributes,
+        >,
+    {
+        ComponentsBuilder::default()
+            .node_types::<Node>()
+            .pool(EthereumPoolBuilder::default())
+            .payload(BasicPayloadServiceBuilder::new(
+                EthereumPayloadBuilder::default().with_precompiles(precompiles.clone()),
+            ))
+            .network(EthereumNetworkBuilder::default())
+            .executor(EthereumExecutorBuilder::default().with_precompiles(precompiles))
+            .consensus(EthereumConsensusBuilder::default())
+    }
+
+    /// Returns a [`ComponentsBuilder`] configured for a regular Ethereum node whose EVM is created
+    /// by the given [`EvmFactory`], e.g. an alternative EVM implementation.
+    ///
+    /// The EVM is used by both the block executor and the payload builder.
+    pub fn components_with_evm_factory<Node, F>(
+        evm_factory: F,
+    ) -> ComponentsBuilder<
+        Node,
+        EthereumPoolBuilder,
+        BasicPayloadServiceBuilder<EthereumEvmFactoryPayloadBuilder<F>>,
+        EthereumNetworkBuilder,
+        EthereumEvmFactoryExecutorBuilder<F>,
+        EthereumConsensusBuilder,
+    >
+    where
+        Node: FullNodeTypes<Types: NodeTypes<ChainSpec = ChainSpec, Primitives = EthPrimitives>>,
+        <Node::Types as NodeTypesWithEngine>::Engine: PayloadTypes<
+            BuiltPayload = EthBuiltPayload,
+            PayloadAttributes = EthPayloadAttributes,
+            PayloadBuilderAttributes = EthPayloadBuilderAttributes,
+        >,
+        F: Clone,
+    {
+        ComponentsBuilder::default()
+            .node_types::<Node>()
+            .pool(EthereumPoolBuilder::default())
+            .payload(BasicPayloadServiceBuilder::new(
+                EthereumPayloadBuilder::default().with_evm_factory(evm_factory.clone()),
+            ))
+            .network(EthereumNetworkBuilder::default())
+            .executor(EthereumExecutorBuilder::default().with_evm_factory(evm_factory))
+            .consensus(EthereumConsensusBuilder::default())
+    }
+
     /// Instantiates the [`ProviderFactoryBuilder`] for an ethereum node.
     ///
     /// # Open a Providerfactory in read-only mode from a datadir
@@ -144,9 +220,15 @@ where
         .task_spawner(ctx.components.task_executor().clone())
         .gas_cap(ctx.config.rpc_gas_cap.into())
         .max_simulate_blocks(ctx.config.rpc_max_simulate_blocks)
+        .access_list_delegations(ctx.config.access_list_delegations)
+        .pending_call_pool_txs(ctx.config.pending_call_pool_txs)
         .eth_proof_window(ctx.config.eth_proof_window)
+        .max_proofs_accounts(ctx.config.max_proofs_accounts)
         .fee_history_cache_config(ctx.config.fee_history_cache)
         .proof_permits(ctx.config.proof_permits)
+        .tracing_pool_threads(ctx.config.tracing_pool_threads)
+        .max_concurrent_calls(ctx.config.max_concurrent_calls)
+        .remote_state(ctx.remote_state)
         .build()
     }
 }
@@ -199,9 +281,13 @@ where
 
         self.inner
             .launch_add_ons_with(ctx, move |modules, _, _| {
+                modules.merge_if_module_configured(
+                    RethRpcModule::Admin,
+                    PayloadValidationApiServer::into_rpc(validation_api.clone()),
+                )?;
                 modules.merge_if_module_configured(
                     RethRpcModule::Flashbots,
-                    validation_api.into_rpc(),
+                    BlockSubmissionValidationApiServer::into_rpc(validation_api),
                 )?;
 
                 Ok(())
@@ -318,6 +404,82 @@ where
     }
 }
 
+impl EthereumExecutorBuilder {
+    /// Returns an executor builder that registers the given [`CustomPrecompiles`] in its EVM.
+    pub fn with_precompiles(
+        self,
+        precompiles: CustomPrecompiles,
+    ) -> EthereumPrecompilesExecutorBuilder {
+        EthereumPrecompilesExecutorBuilder { precompiles }
+    }
+
+    /// Returns an executor builder whose EVM is created by the given [`EvmFactory`], e.g. an
+    /// alternative EVM implementation.
+    pub fn with_evm_factory<F>(self, evm_factory: F) -> EthereumEvmFactoryExecutorBuilder<F> {
+        EthereumEvmFactoryExecutorBuilder { evm_factory }
+    }
+}
+
+/// An ethereum evm and executor builder whose EVM is created by an [`EvmFactory`], e.g. an
+/// alternative EVM implementation.
+///
+/// The `conformance` module of `reth_evm_ethereum`, enabled by its `test-utils` feature, has
+/// helpers to test the EVM against the Ethereum EVM.
+#[derive(Debug, Default, Clone)]
+pub struct EthereumEvmFactoryExecutorBuilder<F> {
+    evm_factory: F,
+}
+
+impl<Types, Node, F> ExecutorBuilder<Node> for EthereumEvmFactoryExecutorBuilder<F>
+where
+    Types: NodeTypesWithEngine<ChainSpec = ChainSpec, Primitives = EthPrimitives>,
+    Node: FullNodeTypes<Types = Types>,
+    F: Send + 'static,
+    EthEvmConfig<F>: ConfigureEvm<Primitives = EthPrimitives>,
+{
+    type EVM = EthEvmConfig<F>;
+    type Executor = BasicBlockExecutorProvider<Self::EVM>;
+
+    async fn build_evm(
+        self,
+        ctx: &BuilderContext<Node>,
+    ) -> eyre::Result<(Self::EVM, Self::Executor)> {
+        let evm_config = EthEvmConfig::new(ctx.chain_spec())
+            .with_extra_data(ctx.payload_builder_config().extra_data_bytes())
+            .with_evm_factory(self.evm_factory);
+        let executor = BasicBlockExecutorProvider::new(evm_config.clone());
+
+        Ok((evm_config, executor))
+    }
+}
+
+/// An ethereum evm and executor builder that registers additional [`CustomPrecompiles`].
+#[derive(Debug, Default, Clone)]
+pub struct EthereumPrecompilesExecutorBuilder {
+    precompiles: CustomPrecompiles,
+}
+
+impl<Types, Node> ExecutorBuilder<Node> for EthereumPrecompilesExecutorBuilder
+where
+    Types: NodeTypesWithEngine<ChainSpec = ChainSpec, Primitives = EthPrimitives>,
+    Node: FullNodeTypes<Types = Types>,
+{
+    type EVM = EthEvmConfig<CustomPrecompilesEvmFactory>;
+    type Executor = BasicBlockExecutorProvider<Self::EVM>;
+
+    async fn build_evm(
+        self,
+        ctx: &BuilderContext<Node>,
+    ) -> eyre::Result<(Self::EVM, Self::Executor)> {
+        let evm_config = EthEvmConfig::new(ctx.chain_spec())
+            .with_extra_data(ctx.payload_builder_config().extra_data_bytes())
+            .with_precompiles(self.precompiles);
+        let executor = BasicBlockExecutorProvider::new(evm_config.clone());
+
+        Ok((evm_config, executor))
+    }
+}
+
 /// A basic ethereum transaction pool.
 ///
 /// This contains various settings that can be configured and take precedence over the node's
@@ -339,12 +501,17 @@ where
         let data_dir = ctx.config().datadir();
         let pool_config = ctx.pool_config();
         let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
-        let validator = TransactionValidationTaskExecutor::eth_builder(ctx.provider().clone())
+        let mut validator = TransactionValidationTaskExecutor::eth_builder(ctx.provider().clone())
             .with_head_timestamp(ctx.head().timestamp)
             .kzg_settings(ctx.kzg_settings()?)
             .with_local_transactions_config(pool_config.local_transactions_config.clone())
-            .with_additional_tasks(ctx.config().txpool.additional_validation_tasks)
-            .build_with_tasks(ctx.task_executor().clone(), blob_store.clone());
+            .with_additional_tasks(ctx.config().txpool.additional_validation_tasks);
+        if let Some(path) = &ctx.config().txpool.target_denylist {
+            let denylist = TargetDenylist::from_file(path)?;
+            info!(target: "reth::cli", path = %path.display(), "Loaded transaction target denylist");
+            validator = validator.with_target_denylist(Arc::new(denylist));
+        }
+        let validator = validator.build_with_tasks(ctx.task_executor().clone(), blob_store.clone());
 
         let transaction_pool =
             reth_transaction_pool::Pool::eth_pool(validator, blob_store, pool_config);
@@ -379,7 +546,7 @@ where
                     chain_events,
                     ctx.task_executor().clone(),
                     reth_transaction_pool::maintain::MaintainPoolConfig {
-                        max_tx_lifetime: transaction_pool.config().max_queued_lifetime,
+                        max_tx_lifetime: transaction_pool.config().min_lifetime(),
                         ..Default::default()
                     },
                 ),
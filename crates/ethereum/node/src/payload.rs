@@ -6,8 +6,11 @@ use reth_ethereum_engine_primitives::{
 };
 use reth_ethereum_payload_builder::EthereumBuilderConfig;
 use reth_ethereum_primitives::EthPrimitives;
-use reth_evm::ConfigureEvm;
-use reth_evm_ethereum::EthEvmConfig;
+use reth_evm::{ConfigureEvm, NextBlockEnvAttributes};
+use reth_evm_ethereum::{
+    precompiles::{CustomPrecompiles, CustomPrecompilesEvmFactory},
+    EthEvmConfig,
+};
 use reth_node_api::{FullNodeTypes, NodeTypesWithEngine, PrimitivesTy, TxTy};
 use reth_node_builder::{
     components::PayloadBuilderBuilder, BuilderContext, PayloadBuilderConfig, PayloadTypes,
@@ -20,6 +23,20 @@ use reth_transaction_pool::{PoolTransaction, TransactionPool};
 pub struct EthereumPayloadBuilder;
 
 impl EthereumPayloadBuilder {
+    /// Returns a payload builder that registers the given [`CustomPrecompiles`] in its EVM.
+    pub fn with_precompiles(
+        self,
+        precompiles: CustomPrecompiles,
+    ) -> EthereumPrecompilesPayloadBuilder {
+        EthereumPrecompilesPayloadBuilder { inner: self, precompiles }
+    }
+
+    /// Returns a payload builder whose EVM is created by the given
+    /// [`EvmFactory`](reth_evm::EvmFactory), e.g. an alternative EVM implementation.
+    pub fn with_evm_factory<F>(self, evm_factory: F) -> EthereumEvmFactoryPayloadBuilder<F> {
+        EthereumEvmFactoryPayloadBuilder { inner: self, evm_factory }
+    }
+
     /// A helper method initializing [`reth_ethereum_payload_builder::EthereumPayloadBuilder`] with
     /// the given EVM config.
     pub fn build<Types, Node, Evm, Pool>(
@@ -48,7 +65,9 @@ impl EthereumPayloadBuilder {
             ctx.provider().clone(),
             pool,
             evm_config,
-            EthereumBuilderConfig::new().with_gas_limit(conf.gas_limit()),
+            EthereumBuilderConfig::new()
+                .with_gas_limit(conf.gas_limit())
+                .with_parallel_simulation(conf.parallel_simulation()),
         ))
     }
 }
@@ -77,3 +96,86 @@ where
         self.build(EthEvmConfig::new(ctx.chain_spec()), ctx, pool)
     }
 }
+
+/// An ethereum payload service that registers additional [`CustomPrecompiles`] in its EVM.
+///
+/// This must be paired with
+/// [`EthereumPrecompilesExecutorBuilder`](crate::node::EthereumPrecompilesExecutorBuilder)
+/// configured with the same precompiles so that built payloads can be executed.
+#[derive(Clone, Default, Debug)]
+pub struct EthereumPrecompilesPayloadBuilder {
+    inner: EthereumPayloadBuilder,
+    precompiles: CustomPrecompiles,
+}
+
+impl<Types, Node, Pool> PayloadBuilderBuilder<Node, Pool> for EthereumPrecompilesPayloadBuilder
+where
+    Types: NodeTypesWithEngine<ChainSpec = ChainSpec, Primitives = EthPrimitives>,
+    Node: FullNodeTypes<Types = Types>,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>>
+        + Unpin
+        + 'static,
+    Types::Engine: PayloadTypes<
+        BuiltPayload = EthBuiltPayload,
+        PayloadAttributes = EthPayloadAttributes,
+        PayloadBuilderAttributes = EthPayloadBuilderAttributes,
+    >,
+{
+    type PayloadBuilder = reth_ethereum_payload_builder::EthereumPayloadBuilder<
+        Pool,
+        Node::Provider,
+        EthEvmConfig<CustomPrecompilesEvmFactory>,
+    >;
+
+    async fn build_payload_builder(
+        self,
+        ctx: &BuilderContext<Node>,
+        pool: Pool,
+    ) -> eyre::Result<Self::PayloadBuilder> {
+        let evm_config = EthEvmConfig::new(ctx.chain_spec()).with_precompiles(self.precompiles);
+        self.inner.build(evm_config, ctx, pool)
+    }
+}
+
+/// An ethereum payload service whose EVM is created by an [`EvmFactory`](reth_evm::EvmFactory).
+///
+/// This must be paired with
+/// [`EthereumEvmFactoryExecutorBuilder`](crate::node::EthereumEvmFactoryExecutorBuilder)
+/// configured with the same EVM so that built payloads can be executed.
+#[derive(Clone, Default, Debug)]
+pub struct EthereumEvmFactoryPayloadBuilder<F> {
+    inner: EthereumPayloadBuilder,
+    evm_factory: F,
+}
+
+impl<Types, Node, Pool, F> PayloadBuilderBuilder<Node, Pool> for EthereumEvmFactoryPayloadBuilder<F>
+where
+    Types: NodeTypesWithEngine<ChainSpec = ChainSpec, Primitives = EthPrimitives>,
+    Node: FullNodeTypes<Types = Types>,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>>
+        + Unpin
+        + 'static,
+    Types::Engine: PayloadTypes<
+        BuiltPayload = EthBuiltPayload,
+        PayloadAttributes = EthPayloadAttributes,
+        PayloadBuilderAttributes = EthPayloadBuilderAttributes,
+    >,
+    F: Send + 'static,
+    EthEvmConfig<F>:
+        ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
+{
+    type PayloadBuilder = reth_ethereum_payload_builder::EthereumPayloadBuilder<
+        Pool,
+        Node::Provider,
+        EthEvmConfig<F>,
+    >;
+
+    async fn build_payload_builder(
+        self,
+        ctx: &BuilderContext<Node>,
+        pool: Pool,
+    ) -> eyre::Result<Self::PayloadBuilder> {
+        let evm_config = EthEvmConfig::new(ctx.chain_spec()).with_evm_factory(self.evm_factory);
+        self.inner.build(evm_config, ctx, pool)
+    }
+}
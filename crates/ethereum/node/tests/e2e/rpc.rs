@@ -13,6 +13,7 @@ use reth_chainspec::{ChainSpecBuilder, MAINNET};
 use reth_e2e_test_utils::setup_engine;
 use reth_node_ethereum::EthereumNode;
 use reth_payload_primitives::BuiltPayload;
+use reth_rpc_api::PayloadValidationResponse;
 use std::sync::Arc;
 
 alloy_sol_types::sol! {
@@ -266,3 +267,62 @@ async fn test_flashbots_validate_v4() -> eyre::Result<()> {
         .is_err());
     Ok(())
 }
+
+#[tokio::test]
+async fn test_admin_validate_payload() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .cancun_activated()
+            .build(),
+    );
+
+    let (mut nodes, _tasks, wallet) =
+        setup_engine::<EthereumNode>(1, chain_spec.clone(), false, eth_payload_attributes).await?;
+    let mut node = nodes.pop().unwrap();
+    let provider = ProviderBuilder::new()
+        .wallet(EthereumWallet::new(wallet.gen().swap_remove(0)))
+        .on_http(node.rpc_url());
+
+    let _ = provider.send_transaction(TransactionRequest::default().to(Address::ZERO)).await?;
+    let payload = node.new_payload().await?;
+
+    let block_hash = payload.block().hash();
+    let parent_beacon_block_root = payload.block().parent_beacon_block_root.unwrap();
+    let execution_payload =
+        ExecutionPayloadV3::from_block_unchecked(block_hash, &payload.block().clone().into_block());
+
+    let response = provider
+        .raw_request::<_, PayloadValidationResponse>(
+            "admin_validatePayload".into(),
+            (&execution_payload, Vec::<B256>::new(), parent_beacon_block_root),
+        )
+        .await?;
+    assert!(response.valid, "{:?}", response.error);
+    assert_eq!(response.block_hash, block_hash);
+    assert!(response.timings.state_root.is_some());
+
+    // the dry run must not insert the block
+    assert!(provider.get_block_by_hash(block_hash).await?.is_none());
+
+    // a block with a wrong state root, but a matching block hash, fails after the execution
+    let mut block = payload.block().clone().into_block();
+    block.header.state_root = B256::random();
+    let execution_payload =
+        ExecutionPayloadV3::from_block_unchecked(block.header.hash_slow(), &block);
+    let response = provider
+        .raw_request::<_, PayloadValidationResponse>(
+            "admin_validatePayload".into(),
+            (&execution_payload, Vec::<B256>::new(), parent_beacon_block_root),
+        )
+        .await?;
+    assert!(!response.valid);
+    assert!(response.error.unwrap().contains("state root"));
+    assert!(response.timings.execution.is_some());
+    assert!(response.timings.state_root.is_some());
+
+    Ok(())
+}
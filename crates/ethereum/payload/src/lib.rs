@@ -14,6 +14,7 @@ pub use validator::EthereumExecutionPayloadValidator;
 
 use alloy_consensus::{Transaction, Typed2718};
 use alloy_primitives::U256;
+use alloy_rlp::Encodable;
 use reth_basic_payload_builder::{
     is_better_payload, BuildArguments, BuildOutcome, MissingPayloadBehaviour, PayloadBuilder,
     PayloadConfig,
@@ -37,11 +38,12 @@ use reth_transaction_pool::{
     PoolTransaction, TransactionPool, ValidPoolTransaction,
 };
 use revm::context_interface::Block as _;
-use std::sync::Arc;
+use std::{collections::VecDeque, sync::Arc};
 use tracing::{debug, trace, warn};
 
 mod config;
 pub use config::*;
+mod simulation;
 use reth_primitives_traits::transaction::error::InvalidTransactionError;
 use reth_transaction_pool::error::Eip4844PoolTransactionError;
 
@@ -156,20 +158,28 @@ where
     let mut db =
         State::builder().with_database(cached_reads.as_db_mut(state)).with_bundle_update().build();
 
-    let mut builder = evm_config
-        .builder_for_next_block(
-            &mut db,
-            &parent_header,
-            NextBlockEnvAttributes {
-                timestamp: attributes.timestamp(),
-                suggested_fee_recipient: attributes.suggested_fee_recipient(),
-                prev_randao: attributes.prev_randao(),
-                gas_limit: builder_config.gas_limit(parent_header.gas_limit),
-                parent_beacon_block_root: attributes.parent_beacon_block_root(),
-                withdrawals: Some(attributes.withdrawals().clone()),
-            },
-        )
+    let next_block_attributes = NextBlockEnvAttributes {
+        timestamp: attributes.timestamp(),
+        suggested_fee_recipient: attributes.suggested_fee_recipient(),
+        prev_randao: attributes.prev_randao(),
+        gas_limit: builder_config.gas_limit(parent_header.gas_limit),
+        parent_beacon_block_root: attributes.parent_beacon_block_root(),
+        withdrawals: Some(attributes.withdrawals().clone()),
+    };
+    let evm_env = evm_config
+        .next_evm_env(&parent_header, &next_block_attributes)
         .map_err(PayloadBuilderError::other)?;
+    // the environment to simulate candidate transactions in, if enabled
+    let simulation_env = (builder_config.parallel_simulation > 1).then(|| evm_env.clone());
+
+    // interrupts the execution of a long running transaction once the deadline of the job is
+    // reached. Without a deadline the job is only cancelled in between transactions, so the
+    // instructions aren't inspected at all
+    let mut evm =
+        evm_config.evm_with_env_and_inspector(&mut db, evm_env, cancel.interrupt_inspector());
+    evm.set_inspector_enabled(cancel.deadline().is_some());
+    let ctx = evm_config.context_for_next_block(&parent_header, next_block_attributes);
+    let mut builder = evm_config.create_block_builder(evm, &parent_header, ctx);
 
     let chain_spec = client.chain_spec();
 
@@ -189,12 +199,36 @@ where
         PayloadBuilderError::Internal(err.into())
     })?;
 
+    let block_limits = chain_spec.block_limits();
+    let mut block_transactions = 0;
+    let mut block_rlp_length = attributes.withdrawals().length();
+
     let mut block_blob_count = 0;
     let blob_params = chain_spec.blob_params_at_timestamp(attributes.timestamp);
     let max_blob_count =
         blob_params.as_ref().map(|params| params.max_blob_count).unwrap_or_default();
 
-    while let Some(pool_tx) = best_txs.next() {
+    // candidate transactions that were already simulated
+    let mut candidates = VecDeque::new();
+
+    loop {
+        if let Some(evm_env) = simulation_env.as_ref().filter(|_| candidates.is_empty()) {
+            candidates.extend(best_txs.by_ref().take(builder_config.parallel_simulation));
+            let txs = candidates.iter().map(|tx| tx.to_consensus()).collect::<Vec<_>>();
+            simulation::simulate_transactions(
+                &evm_config,
+                evm_env,
+                builder.evm_mut().db_mut(),
+                &txs,
+                || {
+                    // every parallel worker reads the parent state in its own database transaction
+                    let state = client.state_by_block_hash(parent_header.hash()).ok()?;
+                    Some(StateProviderDatabase::new(state))
+                },
+            );
+        }
+        let Some(pool_tx) = candidates.pop_front().or_else(|| best_txs.next()) else { break };
+
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
             // we can't fit this transaction into the block, so we need to mark it as invalid
@@ -215,6 +249,24 @@ where
         // convert tx to a signed transaction
         let tx = pool_tx.to_consensus();
 
+        // the block is full if it reached the configured maximum number of transactions
+        if block_limits.max_transactions.is_some_and(|max| block_transactions >= max) {
+            break
+        }
+
+        // skip transactions that would exceed the configured maximum block size
+        let tx_rlp_length = tx.length();
+        if !block_limits.fits_transaction(block_transactions, block_rlp_length, tx_rlp_length) {
+            best_txs.mark_invalid(
+                &pool_tx,
+                InvalidPoolTransactionError::OversizedData(
+                    tx_rlp_length,
+                    block_limits.max_rlp_length.unwrap_or_default(),
+                ),
+            );
+            continue
+        }
+
         // There's only limited amount of blob space available per block, so we need to check if
         // the EIP-4844 can still fit in the block
         if let Some(blob_tx) = tx.as_eip4844() {
@@ -239,7 +291,16 @@ where
             }
         }
 
-        let gas_used = match builder.execute_transaction(tx.clone()) {
+        let result = builder.execute_transaction(tx.clone());
+
+        // the transaction may have been interrupted, in which case its result is invalid and the
+        // previous best payload is used instead
+        if cancel.is_cancelled() {
+            trace!(target: "payload_builder", tx=?tx.hash(), "payload job cancelled while executing transaction");
+            return Ok(BuildOutcome::Cancelled)
+        }
+
+        let gas_used = match result {
             Ok(gas_used) => gas_used,
             Err(BlockExecutionError::Validation(BlockValidationError::InvalidTx {
                 error, ..
@@ -279,6 +340,8 @@ where
             tx.effective_tip_per_gas(base_fee).expect("fee is always valid; execution succeeded");
         total_fees += U256::from(miner_fee) * U256::from(gas_used);
         cumulative_gas_used += gas_used;
+        block_transactions += 1;
+        block_rlp_length += tx_rlp_length;
     }
 
     // check if we have a better block
@@ -291,6 +354,11 @@ where
 
     let BlockBuilderOutcome { execution_result, block, .. } = builder.finish(&state_provider)?;
 
+    // the post execution changes may have been interrupted
+    if cancel.is_cancelled() {
+        return Ok(BuildOutcome::Cancelled)
+    }
+
     let requests = chain_spec
         .is_prague_active_at_timestamp(attributes.timestamp)
         .then_some(execution_result.requests);
@@ -323,3 +391,54 @@ where
 
     Ok(BuildOutcome::Better { payload, cached_reads })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{bytes, Address, TxKind};
+    use reth_evm::{execute::ProviderError, EvmEnv};
+    use reth_revm::cancelled::CancelOnDrop;
+    use revm::{
+        context::{
+            result::{ExecutionResult, HaltReason},
+            TxEnv,
+        },
+        database::CacheDB,
+        database_interface::EmptyDBTyped,
+        state::{AccountInfo, Bytecode},
+    };
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn interrupt_long_running_transaction_at_deadline() {
+        // an infinite loop: JUMPDEST PUSH1 0x00 JUMP
+        let contract = Address::with_last_byte(0xaa);
+        let mut db = CacheDB::<EmptyDBTyped<ProviderError>>::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo { code: Some(Bytecode::new_raw(bytes!("5b600056"))), ..Default::default() },
+        );
+
+        let cancel = CancelOnDrop::with_deadline(Instant::now() + Duration::from_millis(100));
+        let mut evm = EthEvmConfig::mainnet().evm_with_env_and_inspector(
+            db,
+            EvmEnv::default(),
+            cancel.interrupt_inspector(),
+        );
+
+        // enough gas to keep looping for minutes if the transaction isn't interrupted
+        let result = evm
+            .transact(TxEnv {
+                kind: TxKind::Call(contract),
+                gas_limit: 1_000_000_000_000,
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert!(cancel.is_cancelled());
+        assert!(matches!(
+            result.result,
+            ExecutionResult::Halt { reason: HaltReason::OutOfGas(_), .. }
+        ));
+    }
+}
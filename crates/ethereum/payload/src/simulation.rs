@@ -0,0 +1,320 @@
+//! Parallel simulation of candidate transactions.
+//!
+//! Executing a transaction is usually dominated by reading the state it accesses from the
+//! database. Before the candidates are executed sequentially, they are simulated in parallel
+//! against the current state of the block, and the state they read is added to the
+//! [`CachedReads`] of the payload builder, so the sequential execution reads it from memory.
+
+use alloy_consensus::transaction::Recovered;
+use alloy_primitives::{
+    map::{Entry, HashMap},
+    Address, B256, U256,
+};
+use rayon::prelude::*;
+use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
+use reth_evm::{ConfigureEvm, Evm, EvmEnvFor};
+use reth_primitives_traits::SignedTransaction;
+use reth_revm::{
+    cached::{CachedReads, CachedReadsDbMut},
+    db::{CacheState, State},
+    state::{AccountInfo, Bytecode},
+    Database, DatabaseRef,
+};
+use std::fmt;
+use tracing::trace;
+
+/// Simulates the given transactions in parallel against the current state of the block.
+///
+/// The transactions of a sender are simulated in order on top of each other's state changes, so
+/// a transaction reads the state left by the sender's previous transactions in the batch. The
+/// transactions of different senders are simulated independently of each other, so a transaction
+/// that depends on another sender's transaction in the batch reads the wrong state. This only
+/// makes the prefetched state less accurate, since the simulation results are discarded.
+///
+/// The simulations read the state that isn't cached from the databases returned by `snapshot_db`,
+/// which is called for every parallel worker, so the workers don't share a database transaction.
+/// A worker without a database doesn't simulate its transactions.
+pub(crate) fn simulate_transactions<EvmConfig, DB, SnapshotDB>(
+    evm_config: &EvmConfig,
+    evm_env: &EvmEnvFor<EvmConfig>,
+    state: &mut State<CachedReadsDbMut<'_, DB>>,
+    txs: &[Recovered<TransactionSigned>],
+    snapshot_db: impl Fn() -> Option<SnapshotDB> + Send + Sync,
+) where
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives>,
+    SnapshotDB: DatabaseRef<Error: core::error::Error + Send + Sync + 'static>,
+{
+    let mut evm_env = evm_env.clone();
+    evm_env.cfg_env.disable_nonce_check = true;
+
+    // group the transactions by sender, preserving their order
+    let mut senders = HashMap::<Address, usize>::default();
+    let mut groups = Vec::<Vec<&Recovered<TransactionSigned>>>::new();
+    for tx in txs {
+        let idx = *senders.entry(tx.signer()).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[idx].push(tx);
+    }
+
+    let cache = &state.cache;
+    let cached_reads = &*state.database.cached;
+
+    let reads = groups
+        .par_iter()
+        .map_init(snapshot_db, |db, group| {
+            let mut reads = CachedReads::default();
+            let Some(db) = db.as_ref() else { return reads };
+            let snapshot = BlockStateSnapshot { cache, cached_reads, db: reads.as_db_mut(db) };
+            // the state changes of the sender's transactions are kept in memory
+            let db = State::builder().with_database(snapshot).build();
+            let mut evm = evm_config.evm_with_env(db, evm_env.clone());
+            for tx in group {
+                if let Err(err) = evm.transact_commit(evm_config.tx_env(*tx)) {
+                    trace!(target: "payload_builder", %err, tx=?tx.tx_hash(), "failed to simulate transaction");
+                }
+            }
+            drop(evm);
+            reads
+        })
+        .collect::<Vec<_>>();
+
+    for reads in reads {
+        extend_cached_reads(state.database.cached, reads);
+    }
+}
+
+/// Adds the reads of a simulation to the cached reads of the payload builder, without replacing
+/// the storage that is already cached.
+fn extend_cached_reads(cached_reads: &mut CachedReads, reads: CachedReads) {
+    for (address, account) in reads.accounts {
+        match cached_reads.accounts.entry(address) {
+            Entry::Occupied(mut entry) => entry.get_mut().storage.extend(account.storage),
+            Entry::Vacant(entry) => {
+                entry.insert(account);
+            }
+        }
+    }
+    cached_reads.contracts.extend(reads.contracts);
+    cached_reads.block_hashes.extend(reads.block_hashes);
+}
+
+/// A read-only view of the state of the block that is being built.
+///
+/// The state is read from the changes of the already executed transactions first, then from the
+/// cached reads of the payload builder and finally from the database.
+struct BlockStateSnapshot<'a, DB> {
+    /// The state of the block that is being built.
+    cache: &'a CacheState,
+    /// The cached reads of the payload builder.
+    cached_reads: &'a CachedReads,
+    /// The database to read the remaining state from.
+    db: DB,
+}
+
+impl<DB: Database> Database for BlockStateSnapshot<'_, DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        if let Some(account) = self.cache.accounts.get(&address) {
+            return Ok(account.account_info());
+        }
+        if let Some(account) = self.cached_reads.accounts.get(&address) {
+            return Ok(account.info.clone());
+        }
+        self.db.basic(address)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        if let Some(code) = self
+            .cache
+            .contracts
+            .get(&code_hash)
+            .or_else(|| self.cached_reads.contracts.get(&code_hash))
+        {
+            return Ok(code.clone());
+        }
+        self.db.code_by_hash(code_hash)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        if let Some(account) = self.cache.accounts.get(&address) {
+            if let Some(value) =
+                account.account.as_ref().and_then(|account| account.storage.get(&index))
+            {
+                return Ok(*value);
+            }
+            // the storage of destroyed and newly created accounts is not in the database
+            if account.account.is_none() || account.status.is_storage_known() {
+                return Ok(U256::ZERO);
+            }
+        }
+        if let Some(value) =
+            self.cached_reads.accounts.get(&address).and_then(|account| account.storage.get(&index))
+        {
+            return Ok(*value);
+        }
+        self.db.storage(address, index)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        if let Some(hash) = self.cached_reads.block_hashes.get(&number) {
+            return Ok(*hash);
+        }
+        self.db.block_hash(number)
+    }
+}
+
+impl<DB> fmt::Debug for BlockStateSnapshot<'_, DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockStateSnapshot").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxLegacy;
+    use alloy_primitives::{Signature, TxKind};
+    use reth_ethereum_primitives::Transaction;
+    use reth_evm::EvmEnv;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::Account;
+    use reth_revm::{
+        database::StateProviderDatabase,
+        state::{Account as EvmAccount, AccountStatus},
+        test_utils::StateProviderTest,
+        DatabaseCommit,
+    };
+
+    fn transfer(sender: Address, nonce: u64, to: Address) -> Recovered<TransactionSigned> {
+        let tx = TxLegacy {
+            nonce,
+            gas_limit: 21_000,
+            to: TxKind::Call(to),
+            value: U256::from(1),
+            ..Default::default()
+        };
+        Recovered::new_unchecked(
+            TransactionSigned::new_unhashed(Transaction::Legacy(tx), Signature::test_signature()),
+            sender,
+        )
+    }
+
+    fn funded_state(accounts: &[Address]) -> StateProviderTest {
+        let mut state_provider = StateProviderTest::default();
+        for address in accounts {
+            let account = Account { balance: U256::from(1), ..Default::default() };
+            state_provider.insert_account(*address, account, None, Default::default());
+        }
+        state_provider
+    }
+
+    #[test]
+    fn test_simulations_prefetch_accessed_state() {
+        let (sender, other) = (Address::random(), Address::random());
+        let (first, second, third) = (Address::random(), Address::random(), Address::random());
+        let state_provider = funded_state(&[sender, other]);
+
+        let mut cached_reads = CachedReads::default();
+        let mut state = State::builder()
+            .with_database(cached_reads.as_db_mut(StateProviderDatabase::new(&state_provider)))
+            .build();
+        let txs =
+            [transfer(sender, 0, first), transfer(sender, 1, second), transfer(other, 0, third)];
+        simulate_transactions(
+            &EthEvmConfig::mainnet(),
+            &EvmEnv::default(),
+            &mut state,
+            &txs,
+            || Some(StateProviderDatabase::new(&state_provider)),
+        );
+        drop(state);
+
+        assert!(cached_reads.accounts.contains_key(&first));
+        assert!(cached_reads.accounts.contains_key(&third));
+        // the second transfer of the sender is simulated after its first transfer spent the
+        // sender's balance, so it fails before its recipient is read
+        assert!(!cached_reads.accounts.contains_key(&second));
+    }
+
+    #[test]
+    fn test_simulations_read_block_state() {
+        let (sender, recipient) = (Address::random(), Address::random());
+        let state_provider = StateProviderTest::default();
+
+        let mut cached_reads = CachedReads::default();
+        let mut state = State::builder()
+            .with_database(cached_reads.as_db_mut(StateProviderDatabase::new(&state_provider)))
+            .build();
+
+        // the sender is only funded by a transaction executed before in the block
+        state.load_cache_account(sender).unwrap();
+        state.commit(HashMap::from_iter([(
+            sender,
+            EvmAccount {
+                info: AccountInfo { balance: U256::from(1), ..Default::default() },
+                storage: Default::default(),
+                status: AccountStatus::Touched,
+            },
+        )]));
+
+        let txs = [transfer(sender, 0, recipient)];
+        simulate_transactions(
+            &EthEvmConfig::mainnet(),
+            &EvmEnv::default(),
+            &mut state,
+            &txs,
+            || Some(StateProviderDatabase::new(&state_provider)),
+        );
+        drop(state);
+
+        assert!(cached_reads.accounts.contains_key(&recipient));
+    }
+
+    #[test]
+    fn test_simulations_without_database() {
+        let (sender, recipient) = (Address::random(), Address::random());
+        let state_provider = funded_state(&[sender]);
+
+        let mut cached_reads = CachedReads::default();
+        let mut state = State::builder()
+            .with_database(cached_reads.as_db_mut(StateProviderDatabase::new(&state_provider)))
+            .build();
+        let txs = [transfer(sender, 0, recipient)];
+        simulate_transactions(
+            &EthEvmConfig::mainnet(),
+            &EvmEnv::default(),
+            &mut state,
+            &txs,
+            || None::<StateProviderDatabase<&StateProviderTest>>,
+        );
+        drop(state);
+
+        assert!(cached_reads.accounts.is_empty());
+    }
+
+    #[test]
+    fn test_extend_cached_reads_keeps_cached_storage() {
+        let address = Address::random();
+        let mut cached_reads = CachedReads::default();
+        cached_reads.insert_account(
+            address,
+            AccountInfo::default(),
+            HashMap::from_iter([(U256::from(1), U256::from(1))]),
+        );
+
+        let mut reads = CachedReads::default();
+        reads.insert_account(
+            address,
+            AccountInfo::default(),
+            HashMap::from_iter([(U256::from(1), U256::from(2)), (U256::from(2), U256::from(2))]),
+        );
+        extend_cached_reads(&mut cached_reads, reads);
+
+        let storage = &cached_reads.accounts[&address].storage;
+        assert_eq!(storage[&U256::from(1)], U256::from(1));
+        assert_eq!(storage[&U256::from(2)], U256::from(2));
+    }
+}
@@ -0,0 +1,402 @@
+//! Zero-copy archival format for blocks and receipts, based on [`rkyv`].
+//!
+//! Archived blocks keep the header RLP and the EIP-2718 encoding of every transaction next to the
+//! precomputed hashes, so that hashes and encoded transactions can be served straight from the
+//! archived bytes without decoding the block. Archived bytes are validated when accessed and must
+//! be aligned, e.g. stored in an [`AlignedVec`].
+
+use crate::{Block, BlockBody, Receipt, TransactionSigned, TxType};
+use alloc::vec::Vec;
+use alloy_consensus::Header;
+use alloy_eips::{
+    eip2718::{Decodable2718, Eip2718Error, Encodable2718},
+    eip4895::{Withdrawal, Withdrawals},
+};
+use alloy_primitives::{Address, Bytes, Log, LogData, B256};
+use alloy_rlp::Decodable;
+use reth_primitives_traits::{RecoveredBlock, SealedBlock, SignedTransaction};
+use rkyv::{rancor, Archive, Deserialize, Serialize};
+
+pub use rkyv::util::AlignedVec;
+
+/// Errors that can occur while archiving or reading archived blocks and receipts.
+#[derive(Debug, derive_more::Display, derive_more::From)]
+pub enum ArchiveError {
+    /// Failed to serialize or validate the archived bytes.
+    #[display("invalid archive: {_0}")]
+    Rkyv(rancor::Error),
+    /// Failed to decode an archived header.
+    #[display("invalid archived header: {_0}")]
+    Rlp(alloy_rlp::Error),
+    /// Failed to decode an archived transaction.
+    #[display("invalid archived transaction: {_0}")]
+    Eip2718(Eip2718Error),
+    /// Unknown transaction type of an archived receipt.
+    #[display("invalid archived receipt type: {_0}")]
+    #[from(skip)]
+    TxType(u8),
+}
+
+impl core::error::Error for ArchiveError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Rkyv(err) => Some(err),
+            Self::Rlp(err) => Some(err),
+            Self::Eip2718(err) => Some(err),
+            Self::TxType(_) => None,
+        }
+    }
+}
+
+/// Archival representation of a [`SealedBlock`] or a [`RecoveredBlock`].
+#[derive(Archive, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[rkyv(derive(Debug))]
+pub struct BlockArchive {
+    /// Hash of the block.
+    pub hash: [u8; 32],
+    /// RLP encoded header.
+    pub header: Vec<u8>,
+    /// Transactions of the block.
+    pub transactions: Vec<TransactionArchive>,
+    /// RLP encoded ommer headers.
+    pub ommers: Vec<Vec<u8>>,
+    /// Withdrawals of the block, if any.
+    pub withdrawals: Option<Vec<WithdrawalArchive>>,
+    /// Senders of the transactions, only set for recovered blocks.
+    pub senders: Option<Vec<[u8; 20]>>,
+}
+
+/// Archival representation of a [`TransactionSigned`].
+#[derive(Archive, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[rkyv(derive(Debug))]
+pub struct TransactionArchive {
+    /// Hash of the transaction.
+    pub hash: [u8; 32],
+    /// EIP-2718 encoded transaction.
+    pub encoded: Vec<u8>,
+}
+
+/// Archival representation of a [`Withdrawal`].
+#[derive(Archive, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[rkyv(derive(Debug))]
+pub struct WithdrawalArchive {
+    /// Monotonically increasing identifier issued by consensus layer.
+    pub index: u64,
+    /// Index of validator associated with withdrawal.
+    pub validator_index: u64,
+    /// Target address for withdrawn ether.
+    pub address: [u8; 20],
+    /// Value of the withdrawal in gwei.
+    pub amount: u64,
+}
+
+/// Archival representation of the receipts of a block.
+#[derive(Archive, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[rkyv(derive(Debug))]
+pub struct BlockReceiptsArchive {
+    /// Receipts of the block, in transaction order.
+    pub receipts: Vec<ReceiptArchive>,
+}
+
+/// Archival representation of a [`Receipt`].
+#[derive(Archive, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[rkyv(derive(Debug))]
+pub struct ReceiptArchive {
+    /// Receipt type.
+    pub tx_type: u8,
+    /// If transaction is executed successfully.
+    pub success: bool,
+    /// Gas used
+    pub cumulative_gas_used: u64,
+    /// Log send from contracts.
+    pub logs: Vec<LogArchive>,
+}
+
+/// Archival representation of a [`Log`].
+#[derive(Archive, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[rkyv(derive(Debug))]
+pub struct LogArchive {
+    /// Address of the contract that emitted the log.
+    pub address: [u8; 20],
+    /// Topics of the log.
+    pub topics: Vec<[u8; 32]>,
+    /// Data of the log.
+    pub data: Vec<u8>,
+}
+
+impl BlockArchive {
+    /// Creates the archival representation of a [`SealedBlock`].
+    pub fn from_sealed_block(block: &SealedBlock<Block>) -> Self {
+        let body = block.body();
+        Self {
+            hash: block.hash().0,
+            header: alloy_rlp::encode(block.header()),
+            transactions: body.transactions.iter().map(TransactionArchive::from).collect(),
+            ommers: body.ommers.iter().map(alloy_rlp::encode).collect(),
+            withdrawals: body
+                .withdrawals
+                .as_ref()
+                .map(|withdrawals| withdrawals.iter().map(WithdrawalArchive::from).collect()),
+            senders: None,
+        }
+    }
+
+    /// Creates the archival representation of a [`RecoveredBlock`], including its senders.
+    pub fn from_recovered_block(block: &RecoveredBlock<Block>) -> Self {
+        Self {
+            senders: Some(block.senders_iter().map(|sender| sender.0 .0).collect()),
+            ..Self::from_sealed_block(block.sealed_block())
+        }
+    }
+}
+
+impl From<&TransactionSigned> for TransactionArchive {
+    fn from(tx: &TransactionSigned) -> Self {
+        Self { hash: tx.tx_hash().0, encoded: tx.encoded_2718() }
+    }
+}
+
+impl From<&Withdrawal> for WithdrawalArchive {
+    fn from(withdrawal: &Withdrawal) -> Self {
+        Self {
+            index: withdrawal.index,
+            validator_index: withdrawal.validator_index,
+            address: withdrawal.address.0 .0,
+            amount: withdrawal.amount,
+        }
+    }
+}
+
+impl From<&Receipt> for ReceiptArchive {
+    fn from(receipt: &Receipt) -> Self {
+        Self {
+            tx_type: receipt.tx_type as u8,
+            success: receipt.success,
+            cumulative_gas_used: receipt.cumulative_gas_used,
+            logs: receipt
+                .logs
+                .iter()
+                .map(|log| LogArchive {
+                    address: log.address.0 .0,
+                    topics: log.topics().iter().map(|topic| topic.0).collect(),
+                    data: log.data.data.to_vec(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ArchivedBlockArchive {
+    /// Returns the hash of the block.
+    pub fn hash(&self) -> B256 {
+        B256::new(self.hash)
+    }
+
+    /// Decodes the header of the block.
+    pub fn decode_header(&self) -> Result<Header, ArchiveError> {
+        Ok(Header::decode(&mut self.header.as_slice())?)
+    }
+
+    /// Returns the hashes of the transactions, without decoding them.
+    pub fn transaction_hashes(&self) -> impl Iterator<Item = B256> + '_ {
+        self.transactions.iter().map(|tx| B256::new(tx.hash))
+    }
+
+    /// Returns the EIP-2718 encoded transactions, without decoding them.
+    pub fn encoded_transactions(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        self.transactions.iter().map(|tx| tx.encoded.as_slice())
+    }
+
+    /// Returns the senders of the transactions, if the block was archived with them.
+    pub fn senders(&self) -> Option<Vec<Address>> {
+        self.senders
+            .as_ref()
+            .map(|senders| senders.iter().map(|sender| Address::new(*sender)).collect())
+    }
+
+    /// Decodes the archived block into a [`SealedBlock`], using the archived hash.
+    pub fn to_sealed_block(&self) -> Result<SealedBlock<Block>, ArchiveError> {
+        let header = self.decode_header()?;
+        let transactions = self
+            .encoded_transactions()
+            .map(|mut encoded| TransactionSigned::decode_2718(&mut encoded))
+            .collect::<Result<_, _>>()?;
+        let ommers = self
+            .ommers
+            .iter()
+            .map(|ommer| Header::decode(&mut ommer.as_slice()))
+            .collect::<Result<_, _>>()?;
+        let withdrawals = self.withdrawals.as_ref().map(|withdrawals| {
+            Withdrawals::new(
+                withdrawals
+                    .iter()
+                    .map(|withdrawal| Withdrawal {
+                        index: withdrawal.index.to_native(),
+                        validator_index: withdrawal.validator_index.to_native(),
+                        address: Address::new(withdrawal.address),
+                        amount: withdrawal.amount.to_native(),
+                    })
+                    .collect(),
+            )
+        });
+
+        let block = Block::new(header, BlockBody { transactions, ommers, withdrawals });
+        Ok(SealedBlock::new_unchecked(block, self.hash()))
+    }
+
+    /// Decodes the archived block into a [`RecoveredBlock`].
+    ///
+    /// Returns `None` if the block was archived without senders.
+    pub fn to_recovered_block(&self) -> Result<Option<RecoveredBlock<Block>>, ArchiveError> {
+        let Some(senders) = self.senders() else { return Ok(None) };
+        Ok(Some(RecoveredBlock::new_sealed(self.to_sealed_block()?, senders)))
+    }
+}
+
+impl ArchivedReceiptArchive {
+    /// Decodes the archived receipt into a [`Receipt`].
+    pub fn to_receipt(&self) -> Result<Receipt, ArchiveError> {
+        let tx_type =
+            TxType::try_from(self.tx_type).map_err(|_| ArchiveError::TxType(self.tx_type))?;
+        let logs = self
+            .logs
+            .iter()
+            .map(|log| Log {
+                address: Address::new(log.address),
+                data: LogData::new_unchecked(
+                    log.topics.iter().map(|topic| B256::new(*topic)).collect(),
+                    Bytes::copy_from_slice(log.data.as_slice()),
+                ),
+            })
+            .collect();
+
+        Ok(Receipt {
+            tx_type,
+            success: self.success,
+            cumulative_gas_used: self.cumulative_gas_used.to_native(),
+            logs,
+        })
+    }
+}
+
+impl ArchivedBlockReceiptsArchive {
+    /// Decodes the archived receipts.
+    pub fn to_receipts(&self) -> Result<Vec<Receipt>, ArchiveError> {
+        self.receipts.iter().map(ArchivedReceiptArchive::to_receipt).collect()
+    }
+}
+
+/// Archives a [`SealedBlock`].
+pub fn archive_sealed_block(block: &SealedBlock<Block>) -> Result<AlignedVec, ArchiveError> {
+    Ok(rkyv::to_bytes::<rancor::Error>(&BlockArchive::from_sealed_block(block))?)
+}
+
+/// Archives a [`RecoveredBlock`], including its senders.
+pub fn archive_recovered_block(block: &RecoveredBlock<Block>) -> Result<AlignedVec, ArchiveError> {
+    Ok(rkyv::to_bytes::<rancor::Error>(&BlockArchive::from_recovered_block(block))?)
+}
+
+/// Archives the receipts of a block.
+pub fn archive_receipts<'a>(
+    receipts: impl IntoIterator<Item = &'a Receipt>,
+) -> Result<AlignedVec, ArchiveError> {
+    let archive =
+        BlockReceiptsArchive { receipts: receipts.into_iter().map(ReceiptArchive::from).collect() };
+    Ok(rkyv::to_bytes::<rancor::Error>(&archive)?)
+}
+
+/// Validates the given bytes and returns the archived block without copying it.
+pub fn access_block(bytes: &[u8]) -> Result<&ArchivedBlockArchive, ArchiveError> {
+    Ok(rkyv::access::<ArchivedBlockArchive, rancor::Error>(bytes)?)
+}
+
+/// Validates the given bytes and returns the archived receipts without copying them.
+pub fn access_receipts(bytes: &[u8]) -> Result<&ArchivedBlockReceiptsArchive, ArchiveError> {
+    Ok(rkyv::access::<ArchivedBlockReceiptsArchive, rancor::Error>(bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Transaction;
+    use alloy_consensus::TxEip1559;
+    use alloy_primitives::{bytes, PrimitiveSignature as Signature};
+
+    fn block() -> SealedBlock<Block> {
+        let transactions = vec![
+            TransactionSigned::default(),
+            TransactionSigned::new_unhashed(
+                Transaction::Eip1559(TxEip1559 { chain_id: 1, nonce: 1, ..Default::default() }),
+                Signature::test_signature(),
+            ),
+        ];
+        let withdrawals = Withdrawals::new(vec![Withdrawal {
+            index: 1,
+            validator_index: 2,
+            address: Address::with_last_byte(3),
+            amount: 4,
+        }]);
+        let body = BlockBody {
+            transactions,
+            ommers: vec![Header::default()],
+            withdrawals: Some(withdrawals),
+        };
+        SealedBlock::seal_slow(Block::new(Header { number: 1, ..Default::default() }, body))
+    }
+
+    #[test]
+    fn sealed_block_roundtrip() {
+        let block = block();
+        let bytes = archive_sealed_block(&block).unwrap();
+        let archived = access_block(&bytes).unwrap();
+
+        assert_eq!(archived.hash(), block.hash());
+        assert_eq!(
+            archived.transaction_hashes().collect::<Vec<_>>(),
+            block.body().transactions.iter().map(|tx| *tx.tx_hash()).collect::<Vec<_>>()
+        );
+        assert_eq!(archived.senders(), None);
+        assert_eq!(archived.to_recovered_block().unwrap(), None);
+        assert_eq!(archived.to_sealed_block().unwrap(), block);
+    }
+
+    #[test]
+    fn recovered_block_roundtrip() {
+        let block = block();
+        let senders = vec![Address::with_last_byte(1), Address::with_last_byte(2)];
+        let block = RecoveredBlock::new_sealed(block, senders);
+
+        let bytes = archive_recovered_block(&block).unwrap();
+        let archived = access_block(&bytes).unwrap();
+        assert_eq!(archived.to_recovered_block().unwrap(), Some(block));
+    }
+
+    #[test]
+    fn receipts_roundtrip() {
+        let receipts = vec![
+            Receipt {
+                tx_type: TxType::Eip1559,
+                success: true,
+                cumulative_gas_used: 21_000,
+                logs: vec![Log::new_unchecked(
+                    Address::with_last_byte(1),
+                    vec![B256::with_last_byte(2)],
+                    bytes!("0x03"),
+                )],
+            },
+            Receipt { tx_type: TxType::Legacy, cumulative_gas_used: 42_000, ..Default::default() },
+        ];
+
+        let bytes = archive_receipts(&receipts).unwrap();
+        let archived = access_receipts(&bytes).unwrap();
+        assert_eq!(archived.to_receipts().unwrap(), receipts);
+    }
+
+    #[test]
+    fn rejects_invalid_bytes() {
+        let bytes = archive_sealed_block(&block()).unwrap();
+        let mut truncated = AlignedVec::<16>::new();
+        truncated.extend_from_slice(&bytes[..bytes.len() / 2]);
+        assert!(matches!(access_block(&truncated), Err(ArchiveError::Rkyv(_))));
+    }
+}
@@ -19,6 +19,9 @@ pub use transaction::*;
 #[cfg(feature = "alloy-compat")]
 mod alloy_compat;
 
+#[cfg(feature = "rkyv")]
+pub mod archive;
+
 /// Bincode-compatible serde implementations.
 #[cfg(feature = "serde-bincode-compat")]
 pub mod serde_bincode_compat {
@@ -82,6 +82,52 @@ impl Receipt {
         })
     }
 
+    /// Returns length of RLP-encoded receipt fields without the bloom and without an RLP header.
+    ///
+    /// Unlike the consensus encoding, this includes the transaction type as the first field.
+    pub fn rlp_encoded_fields_length_without_bloom(&self) -> usize {
+        self.tx_type.length() +
+            self.success.length() +
+            self.cumulative_gas_used.length() +
+            self.logs.length()
+    }
+
+    /// RLP-encodes receipt fields without the bloom and without an RLP header.
+    ///
+    /// Unlike the consensus encoding, this includes the transaction type as the first field.
+    pub fn rlp_encode_fields_without_bloom(&self, out: &mut dyn BufMut) {
+        self.tx_type.encode(out);
+        self.success.encode(out);
+        self.cumulative_gas_used.encode(out);
+        self.logs.encode(out);
+    }
+
+    /// Returns RLP header for the encoding without the bloom.
+    pub fn rlp_header_inner_without_bloom(&self) -> Header {
+        Header { list: true, payload_length: self.rlp_encoded_fields_length_without_bloom() }
+    }
+
+    /// RLP-decodes the receipt without the bloom from the provided buffer.
+    pub fn rlp_decode_inner_without_bloom(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+
+        let remaining = buf.len();
+
+        let tx_type = Decodable::decode(buf)?;
+        let success = Decodable::decode(buf)?;
+        let cumulative_gas_used = Decodable::decode(buf)?;
+        let logs = Decodable::decode(buf)?;
+
+        if buf.len() + header.payload_length != remaining {
+            return Err(alloy_rlp::Error::UnexpectedLength);
+        }
+
+        Ok(Self { tx_type, success, cumulative_gas_used, logs })
+    }
+
     /// Calculates the receipt root for a header for the reference type of [Receipt].
     ///
     /// NOTE: Prefer `proofs::calculate_receipt_root` if you have log blooms memoized.
@@ -152,6 +198,25 @@ impl RlpDecodableReceipt for Receipt {
     }
 }
 
+/// The `eth/69` network encoding of a receipt, which omits the bloom:
+/// `[tx-type, status, cumulative-gas-used, logs]`.
+impl Encodable for Receipt {
+    fn encode(&self, out: &mut dyn BufMut) {
+        self.rlp_header_inner_without_bloom().encode(out);
+        self.rlp_encode_fields_without_bloom(out);
+    }
+
+    fn length(&self) -> usize {
+        self.rlp_header_inner_without_bloom().length_with_payload()
+    }
+}
+
+impl Decodable for Receipt {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Self::rlp_decode_inner_without_bloom(buf)
+    }
+}
+
 impl TxReceipt for Receipt {
     type Log = Log;
 
@@ -529,4 +594,19 @@ mod tests {
             b256!("0xfe70ae4a136d98944951b2123859698d59ad251a381abc9960fa81cae3d0d4a0")
         );
     }
+
+    #[test]
+    fn encode_decode_receipt_without_bloom() {
+        let receipt = Receipt {
+            tx_type: TxType::Eip1559,
+            success: true,
+            cumulative_gas_used: 21000,
+            logs: vec![],
+        };
+
+        let encoded = alloy_rlp::encode(&receipt);
+        assert_eq!(encoded, hex!("c60201825208c0"));
+        assert_eq!(encoded.len(), receipt.length());
+        assert_eq!(Receipt::decode(&mut encoded.as_slice()).unwrap(), receipt);
+    }
 }
@@ -0,0 +1,661 @@
+//! Experimental optimistic parallel execution of the transactions of a block.
+//!
+//! The [`ParallelBlockExecutor`] executes the transactions of a block speculatively on a
+//! persistent [`ThreadPool`], in the spirit of Block-STM:
+//!
+//! 1. The pre-execution changes of the block are applied, then every worker opens its own reader of
+//!    the [state snapshot](ParallelBlockExecutor::with_snapshot), takes the next transaction that
+//!    wasn't speculated yet and executes it. Workers read the writes of the preceding transactions
+//!    that were already speculated from a multi-version map, and the state before the block from
+//!    the cache of the executor and their snapshot reader. The observed values are recorded.
+//! 2. Once all transactions were speculated, the executor commits them in block order. A result is
+//!    only committed if every account and storage slot the transaction read still has the value
+//!    that was observed, i.e. the transaction didn't conflict with any preceding transaction.
+//!    Conflicting transactions are executed again on top of the current state.
+//!
+//! Since every transaction pays fees to the beneficiary of the block, a transaction that only
+//! credits the beneficiary doesn't conflict with a transaction that read it for its fee payment.
+//! The credit is applied to the current balance instead, unless the transaction observed the
+//! balance, e.g. with `BALANCE`, or the beneficiary is a contract or empty.
+//!
+//! Results are committed through the regular [`BlockExecutor`], so receipts, gas accounting and
+//! state hooks are the same as for sequential execution. This requires the [`ConfigureEvm`] to
+//! create its EVMs with a [`SpeculativeEvmFactory`], which can replay a precomputed result.
+//!
+//! This is intended for benchmarking on historical blocks, e.g. with `reth debug re-execute
+//! --parallel`, see [`ParallelExecutionStats`] for the number of transactions that had to be
+//! executed again.
+
+use crate::{
+    execute::{BlockExecutionError, BlockExecutor, BlockExecutorProvider, Executor},
+    ConfigureEvm, Database, Evm, EvmEnv, EvmEnvFor, EvmFactory, HaltReasonFor, OnStateHook,
+};
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_evm::block::BlockExecutorFactory;
+use alloy_primitives::{map::HashMap, Address, Bytes, B256, U256};
+use core::fmt;
+use parking_lot::{Mutex, RwLock};
+use rayon::ThreadPool;
+use reth_execution_types::BlockExecutionResult;
+use reth_primitives_traits::{NodePrimitives, ReceiptTy, Recovered, RecoveredBlock, TxTy};
+use revm::{
+    bytecode::{opcode, Bytecode},
+    context::{result::ResultAndState, BlockEnv},
+    database::{states::bundle_state::BundleRetention, CacheState, EmptyDB, State},
+    database_interface::{DBErrorMarker, DatabaseRef},
+    inspector::{Inspector, NoOpInspector},
+    interpreter::{interpreter::EthInterpreter, interpreter_types::Jumps, Interpreter},
+    state::{AccountInfo, EvmState},
+};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// An [`EvmFactory`] creating EVMs that can replay a precomputed result instead of executing the
+/// next transaction, see [`SpeculativeEvm::set_next_result`].
+#[derive(Debug, Clone, Default)]
+pub struct SpeculativeEvmFactory<F> {
+    inner: F,
+}
+
+impl<F> SpeculativeEvmFactory<F> {
+    /// Creates a new factory wrapping the given [`EvmFactory`].
+    pub const fn new(inner: F) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the wrapped [`EvmFactory`].
+    pub const fn inner(&self) -> &F {
+        &self.inner
+    }
+}
+
+impl<F: EvmFactory> EvmFactory for SpeculativeEvmFactory<F> {
+    type Evm<DB: Database, I: Inspector<Self::Context<DB>, EthInterpreter>> =
+        SpeculativeEvm<F::Evm<DB, I>>;
+    type Context<DB: Database> = F::Context<DB>;
+    type Tx = F::Tx;
+    type Error<DBError: core::error::Error + Send + Sync + 'static> = F::Error<DBError>;
+    type HaltReason = F::HaltReason;
+    type Spec = F::Spec;
+
+    fn create_evm<DB: Database>(
+        &self,
+        db: DB,
+        input: EvmEnv<Self::Spec>,
+    ) -> Self::Evm<DB, NoOpInspector> {
+        SpeculativeEvm::new(self.inner.create_evm(db, input))
+    }
+
+    fn create_evm_with_inspector<DB: Database, I: Inspector<Self::Context<DB>, EthInterpreter>>(
+        &self,
+        db: DB,
+        input: EvmEnv<Self::Spec>,
+        inspector: I,
+    ) -> Self::Evm<DB, I> {
+        SpeculativeEvm::new(self.inner.create_evm_with_inspector(db, input, inspector))
+    }
+}
+
+/// An [`Evm`] that returns a precomputed result for the next transaction instead of executing it.
+pub struct SpeculativeEvm<E: Evm> {
+    inner: E,
+    /// The result returned for the next transaction.
+    next_result: Option<ResultAndState<E::HaltReason>>,
+}
+
+impl<E: Evm> SpeculativeEvm<E> {
+    /// Creates a new EVM wrapping the given one.
+    pub const fn new(inner: E) -> Self {
+        Self { inner, next_result: None }
+    }
+
+    /// Sets the result that is returned for the next transaction.
+    ///
+    /// The result must be the outcome of executing that transaction on top of the current state,
+    /// since the transaction isn't checked against it.
+    pub fn set_next_result(&mut self, result: ResultAndState<E::HaltReason>) {
+        self.next_result = Some(result);
+    }
+}
+
+impl<E: Evm + fmt::Debug> fmt::Debug for SpeculativeEvm<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpeculativeEvm")
+            .field("inner", &self.inner)
+            .field("has_next_result", &self.next_result.is_some())
+            .finish()
+    }
+}
+
+impl<E: Evm> Evm for SpeculativeEvm<E> {
+    type DB = E::DB;
+    type Tx = E::Tx;
+    type Error = E::Error;
+    type HaltReason = E::HaltReason;
+    type Spec = E::Spec;
+
+    fn block(&self) -> &BlockEnv {
+        self.inner.block()
+    }
+
+    fn transact_raw(
+        &mut self,
+        tx: Self::Tx,
+    ) -> Result<ResultAndState<Self::HaltReason>, Self::Error> {
+        if let Some(result) = self.next_result.take() {
+            return Ok(result)
+        }
+        self.inner.transact_raw(tx)
+    }
+
+    fn transact_system_call(
+        &mut self,
+        caller: Address,
+        contract: Address,
+        data: Bytes,
+    ) -> Result<ResultAndState<Self::HaltReason>, Self::Error> {
+        self.inner.transact_system_call(caller, contract, data)
+    }
+
+    fn db_mut(&mut self) -> &mut Self::DB {
+        self.inner.db_mut()
+    }
+
+    fn finish(self) -> (Self::DB, EvmEnv<Self::Spec>) {
+        self.inner.finish()
+    }
+
+    fn set_inspector_enabled(&mut self, enabled: bool) {
+        self.inner.set_inspector_enabled(enabled)
+    }
+}
+
+/// Statistics of the transactions executed by a [`ParallelBlockExecutor`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParallelExecutionStats {
+    /// Number of executed transactions.
+    pub transactions: u64,
+    /// Number of transactions whose speculative result conflicted with a preceding transaction
+    /// and that were executed again.
+    pub reexecuted: u64,
+}
+
+/// A [`BlockExecutorProvider`] creating [`ParallelBlockExecutor`]s.
+#[derive(Debug, Clone)]
+pub struct ParallelBlockExecutorProvider<F> {
+    strategy_factory: F,
+    pool: Option<Arc<ThreadPool>>,
+}
+
+impl<F> ParallelBlockExecutorProvider<F> {
+    /// Creates a new provider with the given strategy factory.
+    ///
+    /// Blocks are executed sequentially until a pool is set with [`Self::with_pool`].
+    pub const fn new(strategy_factory: F) -> Self {
+        Self { strategy_factory, pool: None }
+    }
+
+    /// Sets the thread pool the transactions are executed on speculatively.
+    ///
+    /// The pool is shared by all executors created by this provider.
+    pub fn with_pool(mut self, pool: Arc<ThreadPool>) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+}
+
+impl<F, E> BlockExecutorProvider for ParallelBlockExecutorProvider<F>
+where
+    F: ConfigureEvm<
+            BlockExecutorFactory: BlockExecutorFactory<EvmFactory = SpeculativeEvmFactory<E>>,
+        > + 'static,
+    E: EvmFactory,
+{
+    type Primitives = F::Primitives;
+
+    type Executor<DB: Database> = ParallelBlockExecutor<F, DB>;
+
+    fn executor<DB>(&self, db: DB) -> Self::Executor<DB>
+    where
+        DB: Database,
+    {
+        let executor = ParallelBlockExecutor::new(self.strategy_factory.clone(), db);
+        match &self.pool {
+            Some(pool) => executor.with_pool(pool.clone()),
+            None => executor,
+        }
+    }
+}
+
+/// A snapshot without readers, with which blocks are executed sequentially.
+pub type NoSnapshot = fn() -> Option<EmptyDB>;
+
+/// A block executor that executes the transactions of a block optimistically in parallel.
+///
+/// Transactions are only speculated if both a [pool](Self::with_pool) and a
+/// [snapshot](Self::with_snapshot) are set, otherwise the block is executed sequentially.
+///
+/// See the [module documentation](self) for details.
+#[allow(missing_debug_implementations)]
+pub struct ParallelBlockExecutor<F, DB, S = NoSnapshot> {
+    /// Block execution strategy.
+    strategy_factory: F,
+    /// Database.
+    db: State<DB>,
+    /// Thread pool the transactions are speculated on.
+    pool: Option<Arc<ThreadPool>>,
+    /// Opens the readers of the state of the database for the workers.
+    snapshot: Option<S>,
+    /// Statistics of the executed transactions.
+    stats: ParallelExecutionStats,
+}
+
+impl<F, DB: Database> ParallelBlockExecutor<F, DB> {
+    /// Creates a new executor with the given strategy that executes blocks sequentially.
+    pub fn new(strategy_factory: F, db: DB) -> Self {
+        let db =
+            State::builder().with_database(db).with_bundle_update().without_state_clear().build();
+        Self {
+            strategy_factory,
+            db,
+            pool: None,
+            snapshot: None,
+            stats: ParallelExecutionStats::default(),
+        }
+    }
+}
+
+impl<F, DB, S> ParallelBlockExecutor<F, DB, S> {
+    /// Sets the thread pool the transactions are executed on speculatively.
+    pub fn with_pool(mut self, pool: Arc<ThreadPool>) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Sets the function opening a reader of the state of the database for every worker.
+    ///
+    /// The readers must see the same state as the database the executor was created with, e.g.
+    /// readers of the same historical state. Workers that can't open a reader don't speculate.
+    pub fn with_snapshot<S2, R>(self, snapshot: S2) -> ParallelBlockExecutor<F, DB, S2>
+    where
+        S2: Fn() -> Option<R> + Sync,
+        R: DatabaseRef,
+    {
+        let Self { strategy_factory, db, pool, snapshot: _, stats } = self;
+        ParallelBlockExecutor { strategy_factory, db, pool, snapshot: Some(snapshot), stats }
+    }
+
+    /// Returns the statistics of the transactions executed so far.
+    pub const fn stats(&self) -> ParallelExecutionStats {
+        self.stats
+    }
+}
+
+impl<F, E, DB, S, R> ParallelBlockExecutor<F, DB, S>
+where
+    F: ConfigureEvm<
+        BlockExecutorFactory: BlockExecutorFactory<EvmFactory = SpeculativeEvmFactory<E>>,
+    >,
+    E: EvmFactory,
+    DB: Database,
+    S: Fn() -> Option<R> + Sync,
+    R: DatabaseRef,
+{
+    /// Executes the block, speculating the transactions on the thread pool.
+    fn execute_block(
+        &mut self,
+        block: &RecoveredBlock<<F::Primitives as NodePrimitives>::Block>,
+        state_hook: Option<Box<dyn OnStateHook>>,
+    ) -> Result<BlockExecutionResult<ReceiptTy<F::Primitives>>, BlockExecutionError> {
+        let transactions = block.transactions_recovered().collect::<Vec<_>>();
+        let evm_config = &self.strategy_factory;
+        let evm_env = evm_config.evm_env(block.header());
+        let beneficiary = evm_env.block_env.beneficiary;
+        let mut strategy =
+            evm_config.executor_for_block(&mut self.db, block).with_state_hook(state_hook);
+
+        strategy.apply_pre_execution_changes()?;
+        self.stats.transactions += transactions.len() as u64;
+
+        let speculations = match (&self.pool, &self.snapshot) {
+            (Some(pool), Some(snapshot)) if transactions.len() > 1 => {
+                // the cache isn't modified until all transactions were speculated
+                let cache = &strategy.evm_mut().db_mut().cache;
+                speculate(pool, evm_config, &evm_env, &transactions, cache, snapshot)
+            }
+            _ => Vec::new(),
+        };
+        let speculated = !speculations.is_empty();
+        let mut speculations = speculations.into_iter();
+
+        // commit the transactions in block order
+        for tx in transactions {
+            if speculated {
+                let evm = strategy.evm_mut();
+                match speculations
+                    .next()
+                    .flatten()
+                    .and_then(|speculation| validate(evm.db_mut(), beneficiary, speculation))
+                {
+                    Some(result) => evm.set_next_result(result),
+                    None => self.stats.reexecuted += 1,
+                }
+            }
+            strategy.execute_transaction(tx)?;
+        }
+
+        let result = strategy.apply_post_execution_changes()?;
+
+        self.db.merge_transitions(BundleRetention::Reverts);
+
+        Ok(result)
+    }
+}
+
+impl<F, E, DB, S, R> Executor<DB> for ParallelBlockExecutor<F, DB, S>
+where
+    F: ConfigureEvm<
+        BlockExecutorFactory: BlockExecutorFactory<EvmFactory = SpeculativeEvmFactory<E>>,
+    >,
+    E: EvmFactory,
+    DB: Database,
+    S: Fn() -> Option<R> + Sync,
+    R: DatabaseRef,
+{
+    type Primitives = F::Primitives;
+    type Error = BlockExecutionError;
+
+    fn execute_one(
+        &mut self,
+        block: &RecoveredBlock<<Self::Primitives as NodePrimitives>::Block>,
+    ) -> Result<BlockExecutionResult<<Self::Primitives as NodePrimitives>::Receipt>, Self::Error>
+    {
+        self.execute_block(block, None)
+    }
+
+    fn execute_one_with_state_hook<H>(
+        &mut self,
+        block: &RecoveredBlock<<Self::Primitives as NodePrimitives>::Block>,
+        state_hook: H,
+    ) -> Result<BlockExecutionResult<<Self::Primitives as NodePrimitives>::Receipt>, Self::Error>
+    where
+        H: OnStateHook + 'static,
+    {
+        self.execute_block(block, Some(Box::new(state_hook)))
+    }
+
+    fn into_state(self) -> State<DB> {
+        self.db
+    }
+
+    fn size_hint(&self) -> usize {
+        self.db.bundle_state.size_hint()
+    }
+}
+
+/// The speculative execution of a transaction.
+#[derive(Debug)]
+struct Speculation<H> {
+    /// The result, or [`None`] if the execution failed.
+    result: Option<ResultAndState<H>>,
+    /// The accounts and storage slots that were read, with the observed values.
+    reads: Vec<StateRead>,
+    /// Whether the transaction observed the balance of the beneficiary.
+    beneficiary_observed: bool,
+}
+
+/// An account or storage slot read by a speculative execution.
+#[derive(Debug)]
+enum StateRead {
+    Account(Address, Option<AccountInfo>),
+    Storage(Address, U256, U256),
+}
+
+/// The accounts and storage slots written by the speculated transactions, by transaction index.
+///
+/// The beneficiary isn't recorded, since every transaction credits it. The credits are applied
+/// when the transactions are committed instead.
+#[derive(Debug, Default)]
+struct MultiVersionMap {
+    accounts: RwLock<HashMap<Address, BTreeMap<usize, Option<AccountInfo>>>>,
+    storage: RwLock<HashMap<(Address, U256), BTreeMap<usize, U256>>>,
+}
+
+impl MultiVersionMap {
+    /// Returns the account as written by the last transaction before the given index.
+    fn account(&self, address: Address, index: usize) -> Option<Option<AccountInfo>> {
+        let accounts = self.accounts.read();
+        accounts.get(&address)?.range(..index).next_back().map(|(_, info)| info.clone())
+    }
+
+    /// Returns the storage slot as written by the last transaction before the given index.
+    fn storage(&self, address: Address, slot: U256, index: usize) -> Option<U256> {
+        let storage = self.storage.read();
+        storage.get(&(address, slot))?.range(..index).next_back().map(|(_, value)| *value)
+    }
+
+    /// Records the state written by the transaction at the given index.
+    fn insert(&self, index: usize, state: &EvmState, beneficiary: Address) {
+        let written = state
+            .iter()
+            .filter(|(address, account)| **address != beneficiary && account.is_touched());
+        let mut accounts = self.accounts.write();
+        let mut storage = self.storage.write();
+        for (address, account) in written {
+            let info = (!account.is_selfdestructed()).then(|| account.info.clone());
+            accounts.entry(*address).or_default().insert(index, info);
+            for (slot, value) in account.changed_storage_slots() {
+                storage.entry((*address, *slot)).or_default().insert(index, value.present_value);
+            }
+        }
+    }
+}
+
+/// Speculatively executes the transactions on the pool, returning the speculation of every
+/// transaction in block order, or [`None`] for transactions that weren't speculated.
+fn speculate<F, S, R>(
+    pool: &ThreadPool,
+    evm_config: &F,
+    evm_env: &EvmEnvFor<F>,
+    transactions: &[Recovered<&TxTy<F::Primitives>>],
+    cache: &CacheState,
+    snapshot: &S,
+) -> Vec<Option<Speculation<HaltReasonFor<F>>>>
+where
+    F: ConfigureEvm,
+    S: Fn() -> Option<R> + Sync,
+    R: DatabaseRef,
+{
+    let beneficiary = evm_env.block_env.beneficiary;
+    let versions = MultiVersionMap::default();
+    let next = AtomicUsize::new(0);
+    let speculations = Mutex::new(transactions.iter().map(|_| None).collect::<Vec<_>>());
+    let workers = pool.current_num_threads().min(transactions.len());
+
+    pool.scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|_| {
+                let Some(reader) = snapshot() else { return };
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(tx) = transactions.get(index) else { return };
+
+                    let observed = Arc::new(AtomicBool::new(tx.signer() == beneficiary));
+                    let inspector =
+                        BeneficiaryInspector { beneficiary, observed: observed.clone() };
+                    let db = SnapshotDb {
+                        index,
+                        versions: &versions,
+                        cache,
+                        reader: &reader,
+                        reads: Vec::new(),
+                    };
+                    let mut evm =
+                        evm_config.evm_with_env_and_inspector(db, evm_env.clone(), inspector);
+                    let result = evm.transact(tx.clone()).ok();
+                    let (db, _) = evm.finish();
+
+                    if let Some(result) = &result {
+                        versions.insert(index, &result.state, beneficiary);
+                    }
+                    speculations.lock()[index] = Some(Speculation {
+                        result,
+                        reads: db.reads,
+                        beneficiary_observed: observed.load(Ordering::Relaxed),
+                    });
+                }
+            });
+        }
+    });
+
+    speculations.into_inner()
+}
+
+/// Returns the speculative result of a transaction if it's still valid on top of the given state.
+///
+/// If the transaction only credited the beneficiary, the credit is applied to its current balance.
+fn validate<DB: revm::Database, H>(
+    db: &mut DB,
+    beneficiary: Address,
+    speculation: Speculation<H>,
+) -> Option<ResultAndState<H>> {
+    let Speculation { result, reads, beneficiary_observed } = speculation;
+    let mut result = result?;
+
+    let mut beneficiary_balance = None;
+    for read in reads {
+        match read {
+            StateRead::Account(address, info) => {
+                let current = db.basic(address).ok()?;
+                if current == info {
+                    continue
+                }
+                if address != beneficiary || beneficiary_observed {
+                    return None
+                }
+                let (Some(info), Some(current)) = (info, current) else { return None };
+                // the credit must not depend on whether the beneficiary is empty
+                if info.nonce != current.nonce ||
+                    info.code_hash != KECCAK_EMPTY ||
+                    current.code_hash != KECCAK_EMPTY ||
+                    info.is_empty() ||
+                    current.is_empty()
+                {
+                    return None
+                }
+                beneficiary_balance = Some((info.balance, current.balance));
+            }
+            StateRead::Storage(address, slot, value) => {
+                if db.storage(address, slot).ok()? != value {
+                    return None
+                }
+            }
+        }
+    }
+
+    if let Some((observed, current)) = beneficiary_balance {
+        let account = result.state.get_mut(&beneficiary)?;
+        let credit = account.info.balance.checked_sub(observed)?;
+        account.info.balance = current.checked_add(credit)?;
+    }
+
+    Some(result)
+}
+
+/// Error of a read of a [`SnapshotDb`] that couldn't be served.
+#[derive(Debug, Clone, Copy, derive_more::Display)]
+#[display("speculative read failed")]
+struct SpeculativeReadError;
+
+impl core::error::Error for SpeculativeReadError {}
+
+impl DBErrorMarker for SpeculativeReadError {}
+
+/// Database of a worker that reads the state before a transaction and records the observed values.
+///
+/// Values are read from the multi-version map, then the cache of the executor, which contains the
+/// changes of the preceding blocks and the pre-execution changes, then the snapshot reader.
+struct SnapshotDb<'a, R> {
+    /// Index of the speculated transaction.
+    index: usize,
+    versions: &'a MultiVersionMap,
+    cache: &'a CacheState,
+    reader: &'a R,
+    reads: Vec<StateRead>,
+}
+
+impl<R> fmt::Debug for SnapshotDb<'_, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapshotDb").field("index", &self.index).finish_non_exhaustive()
+    }
+}
+
+impl<R: DatabaseRef> revm::Database for SnapshotDb<'_, R> {
+    type Error = SpeculativeReadError;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = match self.versions.account(address, self.index) {
+            Some(info) => info,
+            None => match self.cache.accounts.get(&address) {
+                Some(account) => account.account_info(),
+                None => self.reader.basic_ref(address).map_err(|_| SpeculativeReadError)?,
+            },
+        };
+        self.reads.push(StateRead::Account(address, info.clone()));
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        if let Some(code) = self.cache.contracts.get(&code_hash) {
+            return Ok(code.clone())
+        }
+        self.reader.code_by_hash_ref(code_hash).map_err(|_| SpeculativeReadError)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let cached = || {
+            let account = self.cache.accounts.get(&address)?;
+            // the storage of a cached account that doesn't exist is empty
+            let Some(plain) = &account.account else { return Some(U256::ZERO) };
+            plain
+                .storage
+                .get(&index)
+                .copied()
+                .or_else(|| account.status.is_storage_known().then_some(U256::ZERO))
+        };
+        let value = match self.versions.storage(address, index, self.index).or_else(cached) {
+            Some(value) => value,
+            None => self.reader.storage_ref(address, index).map_err(|_| SpeculativeReadError)?,
+        };
+        self.reads.push(StateRead::Storage(address, index, value));
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.reader.block_hash_ref(number).map_err(|_| SpeculativeReadError)
+    }
+}
+
+/// Inspector detecting whether a transaction observes the balance of the beneficiary, beyond
+/// crediting it.
+#[derive(Debug)]
+struct BeneficiaryInspector {
+    beneficiary: Address,
+    observed: Arc<AtomicBool>,
+}
+
+impl<CTX> Inspector<CTX, EthInterpreter> for BeneficiaryInspector {
+    fn step(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        // code executed as the beneficiary, e.g. with an EIP-7702 delegation
+        let observed = interp.input.target_address == self.beneficiary ||
+            matches!(
+                interp.bytecode.opcode(),
+                opcode::BALANCE | opcode::EXTCODESIZE | opcode::EXTCODECOPY | opcode::EXTCODEHASH
+            ) && interp.stack.data().last().is_some_and(|address| {
+                Address::from_word(B256::from(*address)) == self.beneficiary
+            });
+        if observed {
+            self.observed.store(true, Ordering::Relaxed);
+        }
+    }
+}
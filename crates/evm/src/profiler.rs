@@ -0,0 +1,347 @@
+//! State access profiler.
+//!
+//! The [`StateAccessProfiler`] records which accounts and storage slots are read and written by
+//! every executed block and aggregates them over a window of blocks into a
+//! [`StateAccessReport`] of the hottest keys. Keys that are accessed across many blocks are the
+//! ones that benefit the most from the cross-block cache and prewarming.
+use crate::OnStateHook;
+use alloy_evm::block::StateChangeSource;
+use alloy_primitives::{map::HashMap, Address, BlockNumber, B256};
+use core::hash::Hash;
+use parking_lot::Mutex;
+use revm::state::EvmState;
+use std::sync::Arc;
+
+/// The default number of blocks aggregated into a single [`StateAccessReport`].
+pub const DEFAULT_REPORT_INTERVAL: u64 = 100;
+
+/// The default number of hottest accounts and storage slots included in a
+/// [`StateAccessReport`].
+pub const DEFAULT_HOT_KEYS_LIMIT: usize = 20;
+
+/// Access statistics of a single account or storage slot.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccessStats {
+    /// Number of state changes (transactions and system calls) that loaded the key.
+    pub reads: u64,
+    /// Number of state changes that modified the key.
+    pub writes: u64,
+    /// Number of blocks the key was accessed in.
+    pub blocks: u64,
+}
+
+impl AccessStats {
+    /// Adds the accesses of a single block to the statistics.
+    const fn add_block(&mut self, block: Self) {
+        self.reads += block.reads;
+        self.writes += block.writes;
+        self.blocks += 1;
+    }
+}
+
+/// Aggregated state access statistics over a window of blocks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StateAccessReport {
+    /// The first block of the window.
+    pub first_block: BlockNumber,
+    /// The last block of the window.
+    pub last_block: BlockNumber,
+    /// Number of blocks executed in the window.
+    pub blocks: u64,
+    /// Number of distinct accounts accessed in the window.
+    pub unique_accounts: usize,
+    /// Number of distinct storage slots accessed in the window.
+    pub unique_storage_slots: usize,
+    /// Number of accounts accessed in more than one block of the window.
+    pub reused_accounts: usize,
+    /// Number of storage slots accessed in more than one block of the window.
+    pub reused_storage_slots: usize,
+    /// The hottest accounts, ordered by the number of blocks they were accessed in and then by
+    /// the number of reads.
+    pub accounts: Vec<(Address, AccessStats)>,
+    /// The hottest storage slots, ordered the same way as [`Self::accounts`].
+    pub storage_slots: Vec<(Address, B256, AccessStats)>,
+}
+
+/// Accounts and storage slots accessed by a set of blocks.
+#[derive(Debug, Default)]
+struct StateAccesses {
+    accounts: HashMap<Address, AccessStats>,
+    storage_slots: HashMap<(Address, B256), AccessStats>,
+}
+
+impl StateAccesses {
+    /// Records the accesses of a single state change.
+    fn record(&mut self, state: &EvmState) {
+        for (address, account) in state {
+            let stats = self.accounts.entry(*address).or_default();
+            stats.reads += 1;
+            if account.is_touched() {
+                stats.writes += 1;
+            }
+
+            for (slot, value) in &account.storage {
+                let stats = self.storage_slots.entry((*address, B256::from(*slot))).or_default();
+                stats.reads += 1;
+                if value.is_changed() {
+                    stats.writes += 1;
+                }
+            }
+        }
+    }
+}
+
+/// The blocks aggregated since the last report.
+#[derive(Debug, Default)]
+struct ProfilerWindow {
+    first_block: Option<BlockNumber>,
+    last_block: BlockNumber,
+    blocks: u64,
+    accesses: StateAccesses,
+}
+
+impl ProfilerWindow {
+    /// Merges the accesses of an executed block into the window.
+    fn add_block(&mut self, number: BlockNumber, block: StateAccesses) {
+        self.first_block.get_or_insert(number);
+        self.last_block = number;
+        self.blocks += 1;
+        merge_block(&mut self.accesses.accounts, block.accounts);
+        merge_block(&mut self.accesses.storage_slots, block.storage_slots);
+    }
+
+    /// Creates a report of the `limit` hottest keys of the window.
+    fn report(&self, limit: usize) -> StateAccessReport {
+        let StateAccesses { accounts, storage_slots } = &self.accesses;
+        StateAccessReport {
+            first_block: self.first_block.unwrap_or_default(),
+            last_block: self.last_block,
+            blocks: self.blocks,
+            unique_accounts: accounts.len(),
+            unique_storage_slots: storage_slots.len(),
+            reused_accounts: accounts.values().filter(|stats| stats.blocks > 1).count(),
+            reused_storage_slots: storage_slots.values().filter(|stats| stats.blocks > 1).count(),
+            accounts: hottest(accounts, limit),
+            storage_slots: hottest(storage_slots, limit)
+                .into_iter()
+                .map(|((address, slot), stats)| (address, slot, stats))
+                .collect(),
+        }
+    }
+}
+
+/// Merges the per-block statistics into the window statistics.
+fn merge_block<K: Hash + Eq>(window: &mut HashMap<K, AccessStats>, block: HashMap<K, AccessStats>) {
+    for (key, stats) in block {
+        window.entry(key).or_default().add_block(stats);
+    }
+}
+
+/// Returns the `limit` hottest keys.
+fn hottest<K: Copy + Ord>(stats: &HashMap<K, AccessStats>, limit: usize) -> Vec<(K, AccessStats)> {
+    let mut hottest = stats.iter().map(|(key, stats)| (*key, *stats)).collect::<Vec<_>>();
+    // ties are broken by the key to keep the reports deterministic
+    hottest.sort_unstable_by(|(a_key, a), (b_key, b)| {
+        (b.blocks, b.reads, b.writes).cmp(&(a.blocks, a.reads, a.writes)).then(a_key.cmp(b_key))
+    });
+    hottest.truncate(limit);
+    hottest
+}
+
+#[derive(Debug)]
+struct ProfilerInner {
+    /// Number of blocks aggregated into a single report.
+    report_interval: u64,
+    /// Number of hottest keys included in a report.
+    hot_keys_limit: usize,
+    /// Accesses of the block that is currently being executed.
+    pending: Mutex<StateAccesses>,
+    /// Blocks aggregated since the last report.
+    window: Mutex<ProfilerWindow>,
+    /// The most recent report.
+    latest_report: Mutex<Option<StateAccessReport>>,
+}
+
+/// Records the accounts and storage slots accessed by executed blocks.
+///
+/// Blocks are profiled by wrapping the state hook passed to the executor with
+/// [`StateAccessProfiler::wrap_hook`] and calling [`StateAccessProfiler::on_block_executed`]
+/// once the block was executed successfully. Blocks are expected to be executed one at a time.
+///
+/// The profiler is cheap to clone, all clones share the same statistics.
+#[derive(Debug, Clone)]
+pub struct StateAccessProfiler {
+    inner: Arc<ProfilerInner>,
+}
+
+impl StateAccessProfiler {
+    /// Creates a new profiler that produces a report every `report_interval` blocks, containing
+    /// the `hot_keys_limit` hottest accounts and storage slots.
+    pub fn new(report_interval: u64, hot_keys_limit: usize) -> Self {
+        Self {
+            inner: Arc::new(ProfilerInner {
+                report_interval: report_interval.max(1),
+                hot_keys_limit,
+                pending: Default::default(),
+                window: Default::default(),
+                latest_report: Default::default(),
+            }),
+        }
+    }
+
+    /// Returns the number of blocks aggregated into a single report.
+    pub fn report_interval(&self) -> u64 {
+        self.inner.report_interval
+    }
+
+    /// Wraps the given state hook, so that all state changes of the block being executed are
+    /// recorded before being forwarded to the inner hook.
+    ///
+    /// This discards the accesses recorded for a previous block that was never reported through
+    /// [`Self::on_block_executed`], e.g. because its execution failed.
+    pub fn wrap_hook(&self, inner: Box<dyn OnStateHook>) -> Box<dyn OnStateHook> {
+        *self.inner.pending.lock() = StateAccesses::default();
+        Box::new(ProfilingStateHook { profiler: self.clone(), inner })
+    }
+
+    /// Adds the accesses recorded for the block with the given number to the current window.
+    ///
+    /// Returns a new report if the window is complete.
+    pub fn on_block_executed(&self, number: BlockNumber) -> Option<StateAccessReport> {
+        let block = core::mem::take(&mut *self.inner.pending.lock());
+
+        let mut window = self.inner.window.lock();
+        window.add_block(number, block);
+        if window.blocks < self.inner.report_interval {
+            return None
+        }
+
+        let report = window.report(self.inner.hot_keys_limit);
+        *window = ProfilerWindow::default();
+        *self.inner.latest_report.lock() = Some(report.clone());
+        Some(report)
+    }
+
+    /// Returns the most recent report.
+    ///
+    /// If no window was completed yet, this returns a report of the blocks executed so far.
+    pub fn latest_report(&self) -> Option<StateAccessReport> {
+        if let Some(report) = self.inner.latest_report.lock().clone() {
+            return Some(report)
+        }
+
+        let window = self.inner.window.lock();
+        (window.blocks > 0).then(|| window.report(self.inner.hot_keys_limit))
+    }
+}
+
+impl Default for StateAccessProfiler {
+    fn default() -> Self {
+        Self::new(DEFAULT_REPORT_INTERVAL, DEFAULT_HOT_KEYS_LIMIT)
+    }
+}
+
+/// State hook that records the state changes into the [`StateAccessProfiler`].
+struct ProfilingStateHook {
+    profiler: StateAccessProfiler,
+    inner: Box<dyn OnStateHook>,
+}
+
+impl OnStateHook for ProfilingStateHook {
+    fn on_state(&mut self, source: StateChangeSource, state: &EvmState) {
+        self.profiler.inner.pending.lock().record(state);
+        self.inner.on_state(source, state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+    use revm::state::{Account, AccountStatus, EvmStorageSlot};
+
+    fn account(touched: bool, slots: impl IntoIterator<Item = (u64, bool)>) -> Account {
+        let storage = slots
+            .into_iter()
+            .map(|(slot, changed)| {
+                let mut value = EvmStorageSlot::new(U256::ZERO);
+                if changed {
+                    value.present_value = U256::from(1);
+                }
+                (U256::from(slot), value)
+            })
+            .collect();
+        let status = if touched { AccountStatus::Touched } else { AccountStatus::Loaded };
+        Account { info: Default::default(), storage, status }
+    }
+
+    struct NoopHook;
+
+    impl OnStateHook for NoopHook {
+        fn on_state(&mut self, _source: StateChangeSource, _state: &EvmState) {}
+    }
+
+    fn execute(profiler: &StateAccessProfiler, states: Vec<EvmState>) {
+        let mut hook = profiler.wrap_hook(Box::new(NoopHook));
+        for (idx, state) in states.iter().enumerate() {
+            hook.on_state(StateChangeSource::Transaction(idx), state);
+        }
+    }
+
+    #[test]
+    fn aggregates_hot_keys() {
+        let profiler = StateAccessProfiler::new(2, 1);
+        let hot = Address::with_last_byte(1);
+        let cold = Address::with_last_byte(2);
+
+        execute(
+            &profiler,
+            vec![
+                EvmState::from_iter([(hot, account(true, [(1, true)]))]),
+                EvmState::from_iter([
+                    (hot, account(false, [(1, false)])),
+                    (cold, account(false, [])),
+                ]),
+            ],
+        );
+        assert_eq!(profiler.on_block_executed(1), None);
+        assert_eq!(profiler.latest_report().map(|report| report.blocks), Some(1));
+
+        execute(&profiler, vec![EvmState::from_iter([(hot, account(false, [(2, false)]))])]);
+        let report = profiler.on_block_executed(2).unwrap();
+
+        assert_eq!(report.first_block, 1);
+        assert_eq!(report.last_block, 2);
+        assert_eq!(report.blocks, 2);
+        assert_eq!(report.unique_accounts, 2);
+        assert_eq!(report.unique_storage_slots, 2);
+        assert_eq!(report.reused_accounts, 1);
+        assert_eq!(report.reused_storage_slots, 0);
+        assert_eq!(report.accounts, vec![(hot, AccessStats { reads: 3, writes: 1, blocks: 2 })]);
+        assert_eq!(
+            report.storage_slots,
+            vec![(hot, B256::with_last_byte(1), AccessStats { reads: 2, writes: 1, blocks: 1 })]
+        );
+        assert_eq!(profiler.latest_report(), Some(report));
+    }
+
+    #[test]
+    fn discards_unfinished_block() {
+        let profiler = StateAccessProfiler::new(1, 10);
+
+        execute(
+            &profiler,
+            vec![EvmState::from_iter([(Address::with_last_byte(1), account(true, []))])],
+        );
+        execute(
+            &profiler,
+            vec![EvmState::from_iter([(Address::with_last_byte(2), account(false, []))])],
+        );
+
+        let report = profiler.on_block_executed(1).unwrap();
+        assert_eq!(
+            report.accounts,
+            vec![(Address::with_last_byte(2), AccessStats { reads: 1, writes: 0, blocks: 1 })]
+        );
+    }
+}
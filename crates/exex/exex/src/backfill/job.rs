@@ -1,4 +1,4 @@
-use crate::StreamBackfillJob;
+use crate::{ReceiptsBackfillJob, StreamBackfillJob};
 use std::{
     ops::RangeInclusive,
     time::{Duration, Instant},
@@ -69,6 +69,12 @@ where
         self.into()
     }
 
+    /// Converts the backfill job into a receipts backfill job, which reads the stored receipts
+    /// instead of executing the blocks where possible.
+    pub fn into_receipts(self) -> ReceiptsBackfillJob<E, P> {
+        self.into()
+    }
+
     fn execute_range(&mut self) -> BackfillJobResult<Chain<E::Primitives>> {
         debug!(
             target: "exex::backfill",
@@ -1,9 +1,11 @@
 mod factory;
 mod job;
+mod receipts;
 mod stream;
 #[cfg(test)]
 mod test_utils;
 
 pub use factory::BackfillJobFactory;
 pub use job::{BackfillJob, SingleBlockBackfillJob};
+pub use receipts::ReceiptsBackfillJob;
 pub use stream::StreamBackfillJob;
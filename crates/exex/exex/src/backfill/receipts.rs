@@ -0,0 +1,184 @@
+use super::job::BackfillJobResult;
+use crate::BackfillJob;
+use alloy_primitives::BlockNumber;
+use reth_evm::execute::{BlockExecutionError, BlockExecutorProvider, Executor};
+use reth_node_api::{BlockBody as _, NodePrimitives};
+use reth_primitives_traits::RecoveredBlock;
+use reth_provider::{
+    BlockReader, HeaderProvider, ProviderError, StateProviderFactory, TransactionVariant,
+};
+use reth_revm::database::StateProviderDatabase;
+use reth_tracing::tracing::trace;
+use std::ops::RangeInclusive;
+
+/// Receipts backfill job started for a specific range.
+///
+/// It implements [`Iterator`] which yields the block and its receipts each time the iterator is
+/// advanced. Unlike [`SingleBlockBackfillJob`](super::SingleBlockBackfillJob), the receipts are
+/// read from storage instead of re-executing the block, which is much faster for consumers that
+/// only need receipts and logs, e.g. log indexers. Blocks whose receipts were pruned are executed
+/// to recover them.
+#[derive(Debug, Clone)]
+pub struct ReceiptsBackfillJob<E, P> {
+    pub(crate) executor: E,
+    pub(crate) provider: P,
+    pub(crate) range: RangeInclusive<BlockNumber>,
+}
+
+impl<E, P> Iterator for ReceiptsBackfillJob<E, P>
+where
+    E: BlockExecutorProvider<Primitives: NodePrimitives<Block = P::Block, Receipt = P::Receipt>>,
+    P: HeaderProvider + BlockReader + StateProviderFactory,
+{
+    type Item = BackfillJobResult<(RecoveredBlock<P::Block>, Vec<P::Receipt>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|block_number| self.block_receipts(block_number))
+    }
+}
+
+impl<E, P> ReceiptsBackfillJob<E, P>
+where
+    E: BlockExecutorProvider<Primitives: NodePrimitives<Block = P::Block, Receipt = P::Receipt>>,
+    P: HeaderProvider + BlockReader + StateProviderFactory,
+{
+    pub(crate) fn block_receipts(
+        &self,
+        block_number: u64,
+    ) -> BackfillJobResult<(RecoveredBlock<P::Block>, Vec<P::Receipt>)> {
+        let block = self
+            .provider
+            .recovered_block(block_number.into(), TransactionVariant::WithHash)
+            .map_err(BlockExecutionError::other)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))
+            .map_err(BlockExecutionError::other)?;
+
+        // Receipts that were pruned are either missing entirely or, if only some of them were
+        // retained, don't match the number of transactions.
+        let receipts = self
+            .provider
+            .receipts_by_block(block_number.into())
+            .map_err(BlockExecutionError::other)?;
+        if let Some(receipts) =
+            receipts.filter(|receipts| receipts.len() == block.body().transaction_count())
+        {
+            return Ok((block, receipts))
+        }
+
+        // Configure the executor to use the previous block's state.
+        let executor = self.executor.executor(StateProviderDatabase::new(
+            self.provider
+                .history_by_block_number(block_number.saturating_sub(1))
+                .map_err(BlockExecutionError::other)?,
+        ));
+
+        trace!(target: "exex::backfill", number = block_number, txs = block.body().transaction_count(), "Receipts are pruned, executing block");
+
+        let output = executor.execute(&block)?;
+
+        Ok((block, output.result.receipts))
+    }
+}
+
+impl<E, P> From<BackfillJob<E, P>> for ReceiptsBackfillJob<E, P> {
+    fn from(job: BackfillJob<E, P>) -> Self {
+        Self { executor: job.executor, provider: job.provider, range: job.range }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backfill::test_utils::{blocks_and_execution_outputs, chain_spec},
+        BackfillJobFactory,
+    };
+    use reth_db_api::tables;
+    use reth_db_common::init::init_genesis;
+    use reth_ethereum_primitives::Receipt;
+    use reth_evm_ethereum::execute::EthExecutorProvider;
+    use reth_primitives_traits::crypto::secp256k1::public_key_to_address;
+    use reth_provider::{
+        providers::BlockchainProvider, test_utils::create_test_provider_factory_with_chain_spec,
+        BlockBodyIndicesProvider, DBProvider, ReceiptProvider,
+    };
+    use reth_testing_utils::generators;
+    use secp256k1::Keypair;
+
+    #[test]
+    fn test_receipts_backfill() -> eyre::Result<()> {
+        reth_tracing::init_test_tracing();
+
+        // Create a key pair for the sender
+        let key_pair = Keypair::new_global(&mut generators::rng());
+        let address = public_key_to_address(key_pair.public_key());
+
+        let chain_spec = chain_spec(address);
+
+        let executor = EthExecutorProvider::ethereum(chain_spec.clone());
+        let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&provider_factory)?;
+        let blockchain_db = BlockchainProvider::new(provider_factory.clone())?;
+
+        let blocks_and_execution_outputs =
+            blocks_and_execution_outputs(provider_factory, chain_spec, key_pair)?;
+
+        // Backfill the receipts of both blocks
+        let factory = BackfillJobFactory::new(executor, blockchain_db);
+        let job = factory.backfill(1..=2).into_receipts();
+        let blocks_and_receipts = job.collect::<Result<Vec<_>, _>>()?;
+
+        // Assert that the stored receipts match the ones produced by executing the blocks
+        assert_eq!(blocks_and_receipts.len(), blocks_and_execution_outputs.len());
+        for ((block, receipts), (expected_block, expected_output)) in
+            blocks_and_receipts.into_iter().zip(blocks_and_execution_outputs)
+        {
+            assert_eq!(block, expected_block);
+            assert_eq!(receipts, expected_output.result.receipts);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_receipts_backfill_pruned() -> eyre::Result<()> {
+        reth_tracing::init_test_tracing();
+
+        // Create a key pair for the sender
+        let key_pair = Keypair::new_global(&mut generators::rng());
+        let address = public_key_to_address(key_pair.public_key());
+
+        let chain_spec = chain_spec(address);
+
+        let executor = EthExecutorProvider::ethereum(chain_spec.clone());
+        let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&provider_factory)?;
+        let blockchain_db = BlockchainProvider::new(provider_factory.clone())?;
+
+        let blocks_and_execution_outputs =
+            blocks_and_execution_outputs(provider_factory.clone(), chain_spec, key_pair)?;
+
+        // Prune the receipts of the first block
+        let provider_rw = provider_factory.provider_rw()?;
+        let indices = provider_rw.block_body_indices(1)?.expect("block indices exist");
+        assert!(!indices.tx_num_range().is_empty());
+        provider_rw.remove::<tables::Receipts<Receipt>>(indices.tx_num_range())?;
+        provider_rw.commit()?;
+        let stored = blockchain_db.receipts_by_block(1.into())?.unwrap_or_default();
+        assert!(stored.is_empty());
+
+        // The receipts of the pruned block are recovered by executing it
+        let factory = BackfillJobFactory::new(executor, blockchain_db);
+        let job = factory.backfill(1..=2).into_receipts();
+        let blocks_and_receipts = job.collect::<Result<Vec<_>, _>>()?;
+
+        assert_eq!(blocks_and_receipts.len(), blocks_and_execution_outputs.len());
+        for ((block, receipts), (expected_block, expected_output)) in
+            blocks_and_receipts.into_iter().zip(blocks_and_execution_outputs)
+        {
+            assert_eq!(block, expected_block);
+            assert_eq!(receipts, expected_output.result.receipts);
+        }
+
+        Ok(())
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{ExExContextDyn, ExExEvent, ExExNotifications, ExExNotificationsStream};
+use crate::{
+    ExExContextDyn, ExExEvent, ExExNotifications, ExExNotificationsStream, ExExStore,
+    ExExStoreBatch, ExExStoreError, ExExStoreResult, ExExTickSchedule,
+};
 use alloy_eips::BlockNumHash;
 use reth_exex_types::ExExHead;
 use reth_node_api::{
@@ -138,6 +141,46 @@ where
     ) -> Result<(), SendError<BlockNumHash>> {
         self.events.send(ExExEvent::FinishedHeight(height)).map_err(|_| SendError(height))
     }
+
+    /// Sends an [`ExExEvent::ScheduleTicks`] to the ExEx task manager requesting periodic
+    /// [`ExExNotification::Tick`](crate::ExExNotification::Tick) notifications with the given
+    /// schedule.
+    ///
+    /// Returns an error if the channel was closed (ExEx task manager panicked).
+    pub fn schedule_ticks(
+        &self,
+        schedule: ExExTickSchedule,
+    ) -> Result<(), SendError<ExExTickSchedule>> {
+        self.events.send(ExExEvent::ScheduleTicks(schedule)).map_err(|_| SendError(schedule))
+    }
+
+    /// Opens the key-value store with the given name, in the ExEx directory of the datadir.
+    ///
+    /// If the store has committed batches, the notifications stream is set to start after the
+    /// block of the last one, so the ExEx resumes from what is stored on disk. Batches should be
+    /// committed with [`ExExContext::commit_store`].
+    pub fn open_store(&mut self, name: &str) -> ExExStoreResult<ExExStore> {
+        let store = ExExStore::open(self.config.datadir().exex_store(name))?;
+        if let Some(block) = store.block()? {
+            self.set_notifications_with_head(ExExHead { block });
+        }
+        Ok(store)
+    }
+
+    /// Commits the batch to the store for the given block, and then sends an
+    /// [`ExExEvent::FinishedHeight`] for it.
+    ///
+    /// The height is only sent once the batch is on disk, so the node keeps the notifications of
+    /// the blocks that aren't in the store yet, and delivers them again after a crash.
+    pub fn commit_store(
+        &self,
+        store: &ExExStore,
+        block: BlockNumHash,
+        batch: ExExStoreBatch,
+    ) -> ExExStoreResult<()> {
+        store.commit(block, batch)?;
+        self.send_finished_height(block).map_err(|_| ExExStoreError::FinishedHeight(block))
+    }
 }
 
 #[cfg(test)]
@@ -168,6 +211,8 @@ mod tests {
                 self.ctx.task_executor();
                 self.ctx.set_notifications_without_head();
                 self.ctx.set_notifications_with_head(ExExHead { block: Default::default() });
+                let store = self.ctx.open_store("test")?;
+                self.ctx.commit_store(&store, Default::default(), Default::default())?;
                 Ok(())
             }
         }
@@ -1,4 +1,5 @@
 use alloy_eips::BlockNumHash;
+use std::{num::NonZeroU64, time::Duration};
 
 /// Events emitted by an `ExEx`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -10,4 +11,21 @@ pub enum ExExEvent {
     ///
     /// On reorgs, it's possible for the height to go down.
     FinishedHeight(BlockNumHash),
+    /// Requests periodic [`ExExNotification::Tick`](crate::ExExNotification::Tick)
+    /// notifications, delivered through the notification channel of the `ExEx`.
+    ///
+    /// Replaces the previously requested schedule, if any.
+    ScheduleTicks(ExExTickSchedule),
+    /// Cancels the periodic tick notifications requested with [`ExExEvent::ScheduleTicks`].
+    CancelTicks,
+}
+
+/// Schedule of the periodic tick notifications requested by an `ExEx`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExExTickSchedule {
+    /// Tick every time the given interval elapses.
+    Interval(Duration),
+    /// Tick after every committed chain that contains a block whose number is a multiple of the
+    /// given number of blocks.
+    Blocks(NonZeroU64),
 }
@@ -106,6 +106,9 @@ pub use manager::*;
 mod notifications;
 pub use notifications::*;
 
+mod store;
+pub use store::*;
+
 mod wal;
 pub use wal::*;
 
@@ -1,5 +1,6 @@
 use crate::{
-    wal::Wal, ExExEvent, ExExNotification, ExExNotifications, FinishedExExHeight, WalHandle,
+    wal::Wal, ExExEvent, ExExNotification, ExExNotifications, ExExTick, ExExTickSchedule,
+    FinishedExExHeight, WalHandle,
 };
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockNumHash;
@@ -18,6 +19,7 @@ use std::{
     collections::VecDeque,
     fmt::Debug,
     future::{poll_fn, Future},
+    num::NonZeroU64,
     ops::Not,
     pin::Pin,
     sync::{
@@ -26,9 +28,12 @@ use std::{
     },
     task::{ready, Context, Poll},
 };
-use tokio::sync::{
-    mpsc::{self, error::SendError, UnboundedReceiver, UnboundedSender},
-    watch,
+use tokio::{
+    sync::{
+        mpsc::{self, error::SendError, UnboundedReceiver, UnboundedSender},
+        watch,
+    },
+    time::{interval_at, Instant, Interval, MissedTickBehavior},
 };
 use tokio_util::sync::{PollSendError, PollSender, ReusableBoxFuture};
 
@@ -56,12 +61,53 @@ pub enum ExExNotificationSource {
     BlockchainTree,
 }
 
+/// The priority of an `ExEx`, which determines how the [`ExExManager`] handles it falling behind.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExExPriority {
+    /// The `ExEx` receives every notification. If it falls behind, the manager stops accepting
+    /// new notifications, which applies back-pressure to the node.
+    #[default]
+    Critical,
+    /// The `ExEx` never applies back-pressure to the node. If it falls behind and the buffer of
+    /// the manager is full, the notifications it hasn't received yet are dropped, and it receives
+    /// an [`ExExNotification::NotificationsSkipped`] notification instead.
+    BestEffort,
+}
+
+/// The tick notifications requested by an `ExEx` with [`ExExEvent::ScheduleTicks`].
+#[derive(Debug)]
+enum ExExTicks {
+    /// Tick every time the interval elapses.
+    Interval(Interval),
+    /// Tick after every committed chain that contains a multiple of the number of blocks.
+    Blocks(NonZeroU64),
+}
+
+impl ExExTicks {
+    /// Returns the active ticks for the given schedule, or `None` if the schedule never ticks.
+    fn new(schedule: ExExTickSchedule) -> Option<Self> {
+        match schedule {
+            ExExTickSchedule::Interval(period) => {
+                if period.is_zero() {
+                    return None
+                }
+                let mut interval = interval_at(Instant::now() + period, period);
+                interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                Some(Self::Interval(interval))
+            }
+            ExExTickSchedule::Blocks(blocks) => Some(Self::Blocks(blocks)),
+        }
+    }
+}
+
 /// Metrics for an `ExEx`.
 #[derive(Metrics)]
 #[metrics(scope = "exex")]
 struct ExExMetrics {
     /// The total number of notifications sent to an `ExEx`.
     notifications_sent_total: Counter,
+    /// The total number of notifications dropped for a best-effort `ExEx`.
+    notifications_skipped_total: Counter,
     /// The total number of events an `ExEx` has sent to the manager.
     events_sent_total: Counter,
 }
@@ -81,8 +127,17 @@ pub struct ExExHandle<N: NodePrimitives = EthPrimitives> {
     sender: PollSender<ExExNotification<N>>,
     /// Channel to receive [`ExExEvent`]s from the `ExEx`.
     receiver: UnboundedReceiver<ExExEvent>,
+    /// The priority of the `ExEx`.
+    priority: ExExPriority,
     /// The ID of the next notification to send to this `ExEx`.
     next_notification_id: usize,
+    /// The number of notifications that were dropped for this `ExEx` and that it hasn't been
+    /// notified about yet.
+    skipped: usize,
+    /// The tick notifications requested by the `ExEx`, if any.
+    ticks: Option<ExExTicks>,
+    /// The tick that is due and that the `ExEx` hasn't received yet.
+    pending_tick: Option<ExExTick>,
     /// The finished block of the `ExEx`.
     ///
     /// If this is `None`, the `ExEx` has not emitted a `FinishedHeight` event.
@@ -112,7 +167,11 @@ impl<N: NodePrimitives> ExExHandle<N> {
                 metrics: ExExMetrics::new_with_labels(&[("exex", id)]),
                 sender: PollSender::new(notification_tx),
                 receiver: event_rx,
+                priority: ExExPriority::default(),
                 next_notification_id: 0,
+                skipped: 0,
+                ticks: None,
+                pending_tick: None,
                 finished_height: None,
             },
             event_tx,
@@ -120,6 +179,113 @@ impl<N: NodePrimitives> ExExHandle<N> {
         )
     }
 
+    /// Sets the priority of the `ExEx`.
+    ///
+    /// By default, the `ExEx` is [`ExExPriority::Critical`].
+    pub const fn with_priority(mut self, priority: ExExPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Returns the priority of the `ExEx`.
+    pub const fn priority(&self) -> ExExPriority {
+        self.priority
+    }
+
+    /// Drops the notifications up to the given notification ID, if the `ExEx` hasn't received
+    /// them yet.
+    ///
+    /// The `ExEx` is notified about the dropped notifications before it receives the next one.
+    fn skip_to(&mut self, notification_id: usize) {
+        let Some(skipped) = notification_id.checked_sub(self.next_notification_id) else { return };
+        if skipped == 0 {
+            return
+        }
+
+        warn!(
+            target: "exex::manager",
+            exex_id = %self.id,
+            from = %self.next_notification_id,
+            %skipped,
+            "ExEx is falling behind, skipping notifications"
+        );
+
+        self.next_notification_id = notification_id;
+        self.skipped += skipped;
+        self.metrics.notifications_skipped_total.increment(skipped as u64);
+    }
+
+    /// Replaces the tick notifications requested by the `ExEx`, dropping the tick that is due.
+    fn schedule_ticks(&mut self, schedule: Option<ExExTickSchedule>) {
+        debug!(target: "exex::manager", exex_id = %self.id, ?schedule, "Scheduling ticks");
+        self.ticks = schedule.and_then(ExExTicks::new);
+        self.pending_tick = None;
+        if schedule.is_some() && self.ticks.is_none() {
+            warn!(target: "exex::manager", exex_id = %self.id, "Ignoring ticks with a zero interval");
+        }
+    }
+
+    /// Polls the tick interval of the `ExEx`, if any, marking a tick as due when it elapses.
+    fn poll_interval_tick(&mut self, cx: &mut Context<'_>) {
+        if let Some(ExExTicks::Interval(interval)) = &mut self.ticks {
+            while interval.poll_tick(cx).is_ready() {
+                self.pending_tick.get_or_insert(ExExTick::Interval);
+            }
+        }
+    }
+
+    /// Marks a tick as due if the sent notification committed a chain containing a multiple of
+    /// the requested number of blocks.
+    fn on_notification_sent(&mut self, cx: &mut Context<'_>, notification: &ExExNotification<N>) {
+        let Some(ExExTicks::Blocks(blocks)) = self.ticks else { return };
+        let Some(chain) = notification.committed_chain() else { return };
+
+        let (first, tip) = (chain.first().number(), chain.tip().number());
+        if first.div_ceil(blocks.get()) <= tip / blocks.get() {
+            self.pending_tick = Some(ExExTick::Blocks { tip });
+            // Poll again to send the tick
+            cx.waker().wake_by_ref();
+        }
+    }
+
+    /// Reserves a slot in the `PollSender` channel and sends the due tick if the slot was
+    /// successfully reserved.
+    fn send_tick(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), PollSendError<ExExNotification<N>>>> {
+        if self.pending_tick.is_none() {
+            return Poll::Ready(Ok(()))
+        }
+        ready!(self.sender.poll_reserve(cx))?;
+
+        let Some(tick) = self.pending_tick.take() else { return Poll::Ready(Ok(())) };
+        debug!(target: "exex::manager", exex_id = %self.id, ?tick, "Sending tick");
+        self.sender.send_item(ExExNotification::Tick { tick })?;
+        self.metrics.notifications_sent_total.increment(1);
+        Poll::Ready(Ok(()))
+    }
+
+    /// Reserves a slot in the `PollSender` channel and notifies the `ExEx` about the skipped
+    /// notifications if the slot was successfully reserved.
+    fn send_skipped(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), PollSendError<ExExNotification<N>>>> {
+        ready!(self.sender.poll_reserve(cx))?;
+
+        debug!(
+            target: "exex::manager",
+            exex_id = %self.id,
+            skipped = %self.skipped,
+            "Sending skipped notifications"
+        );
+        let count = std::mem::take(&mut self.skipped);
+        self.sender.send_item(ExExNotification::NotificationsSkipped { count })?;
+        self.metrics.notifications_sent_total.increment(1);
+        Poll::Ready(Ok(()))
+    }
+
     /// Reserves a slot in the `PollSender` channel and sends the notification if the slot was
     /// successfully reserved.
     ///
@@ -153,7 +319,10 @@ impl<N: NodePrimitives> ExExHandle<N> {
                 // [ExExNotification::ChainReverted] cases and always send the
                 // notification, because the ExEx should be aware of the reorgs and reverts lower
                 // than its finished height
-                ExExNotification::ChainReorged { .. } | ExExNotification::ChainReverted { .. } => {}
+                ExExNotification::ChainReorged { .. } |
+                ExExNotification::ChainReverted { .. } |
+                ExExNotification::NotificationsSkipped { .. } |
+                ExExNotification::Tick { .. } => {}
             }
         }
 
@@ -178,6 +347,7 @@ impl<N: NodePrimitives> ExExHandle<N> {
             Ok(()) => {
                 self.next_notification_id = notification_id + 1;
                 self.metrics.notifications_sent_total.increment(1);
+                self.on_notification_sent(cx, notification);
                 Poll::Ready(Ok(()))
             }
             Err(err) => Poll::Ready(Err(err)),
@@ -264,7 +434,9 @@ where
     /// notification buffer in the manager.
     ///
     /// When the capacity is exceeded (which can happen if an `ExEx` is slow) no one can send
-    /// notifications over [`ExExManagerHandle`]s until there is capacity again.
+    /// notifications over [`ExExManagerHandle`]s until there is capacity again. Only
+    /// [`ExExPriority::Critical`] `ExEx`'s are waited for, the notifications that only
+    /// [`ExExPriority::BestEffort`] `ExEx`'s haven't received yet are dropped instead.
     pub fn new(
         provider: P,
         handles: Vec<ExExHandle<N>>,
@@ -433,10 +605,11 @@ where
     /// 2. Finalize the WAL with the finalized header, if necessary.
     /// 3. Drain [`ExExManagerHandle`] notifications, push them to the internal buffer and update
     ///    the internal buffer capacity.
-    /// 5. Send notifications from the internal buffer to those ExExes that are ready to receive new
-    ///    notifications.
-    /// 5. Remove notifications from the internal buffer that have been sent to **all** ExExes and
-    ///    update the internal buffer capacity.
+    /// 5. Send the due ticks and then notifications from the internal buffer to those ExExes that
+    ///    are ready to receive new notifications.
+    /// 5. Remove notifications from the internal buffer that have been sent to **all** ExExes. If
+    ///    the buffer is full, also remove the notifications that have been sent to all critical
+    ///    ExExes, skipping them for the best-effort ones. Update the internal buffer capacity.
     /// 6. Update the channel with the lowest [`FinishedExExHeight`] among all ExExes.
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
@@ -448,6 +621,8 @@ where
                 exex.metrics.events_sent_total.increment(1);
                 match event {
                     ExExEvent::FinishedHeight(height) => exex.finished_height = Some(height),
+                    ExExEvent::ScheduleTicks(schedule) => exex.schedule_ticks(Some(schedule)),
+                    ExExEvent::CancelTicks => exex.schedule_ticks(None),
                 }
             }
         }
@@ -492,25 +667,61 @@ where
 
         // Advance all poll senders
         let mut min_id = usize::MAX;
+        let mut min_critical_id = usize::MAX;
         for idx in (0..this.exex_handles.len()).rev() {
             let mut exex = this.exex_handles.swap_remove(idx);
 
+            // Notify the ExEx about the skipped notifications before sending the next one
+            if exex.skipped > 0 {
+                if let Poll::Ready(Err(err)) = exex.send_skipped(cx) {
+                    // The channel was closed, which is irrecoverable for the manager
+                    return Poll::Ready(Err(err.into()))
+                }
+            }
+
+            // Send the due tick, if any, before the next notification
+            exex.poll_interval_tick(cx);
+            if exex.skipped == 0 {
+                if let Poll::Ready(Err(err)) = exex.send_tick(cx) {
+                    // The channel was closed, which is irrecoverable for the manager
+                    return Poll::Ready(Err(err.into()))
+                }
+            }
+
             // It is a logic error for this to ever underflow since the manager manages the
             // notification IDs
             let notification_index = exex
                 .next_notification_id
                 .checked_sub(this.min_id)
                 .expect("exex expected notification ID outside the manager's range");
-            if let Some(notification) = this.buffer.get(notification_index) {
+            if let Some(notification) = this
+                .buffer
+                .get(notification_index)
+                .filter(|_| exex.skipped == 0 && exex.pending_tick.is_none())
+            {
                 if let Poll::Ready(Err(err)) = exex.send(cx, notification) {
                     // The channel was closed, which is irrecoverable for the manager
                     return Poll::Ready(Err(err.into()))
                 }
             }
             min_id = min_id.min(exex.next_notification_id);
+            if exex.priority == ExExPriority::Critical {
+                min_critical_id = min_critical_id.min(exex.next_notification_id);
+            }
             this.exex_handles.push(exex);
         }
 
+        // If the buffer is full, don't wait for the best-effort ExExes and drop the notifications
+        // that have already been sent to all critical ExExes
+        if this.buffer.len() >= this.max_capacity && min_critical_id > min_id {
+            min_id = min_critical_id.min(this.next_id);
+            for exex in &mut this.exex_handles {
+                exex.skip_to(min_id);
+            }
+            // Poll again to notify the ExExes about the skipped notifications
+            cx.waker().wake_by_ref();
+        }
+
         // Remove processed buffered notifications
         debug!(target: "exex::manager", %min_id, "Updating lowest notification id in buffer");
         this.buffer.retain(|&(id, _)| id >= min_id);
@@ -1095,6 +1306,142 @@ mod tests {
         assert_eq!(pinned_manager.buffer.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_best_effort_exex_skips_notifications() {
+        let provider_factory = create_test_provider_factory();
+        init_genesis(&provider_factory).unwrap();
+        let provider = BlockchainProvider::new(provider_factory.clone()).unwrap();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let (exex_handle, _, mut notifications) = ExExHandle::new(
+            "test_exex".to_string(),
+            Default::default(),
+            provider,
+            EthExecutorProvider::mainnet(),
+            wal.handle(),
+        );
+        let exex_handle = exex_handle.with_priority(ExExPriority::BestEffort);
+
+        let max_capacity = 2;
+        let mut exex_manager = std::pin::pin!(ExExManager::new(
+            provider_factory,
+            vec![exex_handle],
+            max_capacity,
+            wal,
+            empty_finalized_header_stream(),
+        ));
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        let notifications_to_send = (0..3u64)
+            .map(|number| {
+                let mut block: RecoveredBlock<reth_ethereum_primitives::Block> = Default::default();
+                block.set_block_number(number);
+                ExExNotification::ChainCommitted {
+                    new: Arc::new(Chain::new(vec![block], Default::default(), Default::default())),
+                }
+            })
+            .collect::<Vec<_>>();
+        for notification in &notifications_to_send {
+            exex_manager
+                .handle
+                .exex_tx
+                .send((ExExNotificationSource::Pipeline, notification.clone()))
+                .unwrap();
+        }
+
+        // The ExEx doesn't read its notifications, so the buffer is full after the first poll.
+        // Instead of waiting for the ExEx, the manager drops the notifications it hasn't received.
+        assert!(exex_manager.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(exex_manager.buffer.len(), 0);
+        assert!(exex_manager.handle.has_capacity());
+        assert_eq!(exex_manager.exex_handles[0].skipped, 1);
+
+        // The last notification is buffered until the ExEx catches up
+        assert!(exex_manager.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(exex_manager.buffer.len(), 1);
+
+        assert_eq!(notifications.next().await.unwrap().unwrap(), notifications_to_send[0]);
+
+        // The ExEx is notified about the skipped notification before receiving the next one
+        assert!(exex_manager.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(
+            notifications.next().await.unwrap().unwrap(),
+            ExExNotification::NotificationsSkipped { count: 1 }
+        );
+
+        assert!(exex_manager.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(notifications.next().await.unwrap().unwrap(), notifications_to_send[2]);
+        assert_eq!(exex_manager.exex_handles[0].skipped, 0);
+    }
+
+    #[tokio::test]
+    async fn test_sends_block_ticks() {
+        let provider_factory = create_test_provider_factory();
+        init_genesis(&provider_factory).unwrap();
+        let provider = BlockchainProvider::new(provider_factory.clone()).unwrap();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let (exex_handle, event_tx, mut notifications) = ExExHandle::new(
+            "test_exex".to_string(),
+            Default::default(),
+            provider,
+            EthExecutorProvider::mainnet(),
+            wal.handle(),
+        );
+
+        let mut exex_manager = std::pin::pin!(ExExManager::new(
+            provider_factory,
+            vec![exex_handle],
+            10,
+            wal,
+            empty_finalized_header_stream(),
+        ));
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        let schedule = ExExTickSchedule::Blocks(NonZeroU64::new(2).unwrap());
+        event_tx.send(ExExEvent::ScheduleTicks(schedule)).unwrap();
+
+        let notifications_to_send = (1..=3u64)
+            .map(|number| {
+                let mut block: RecoveredBlock<reth_ethereum_primitives::Block> = Default::default();
+                block.set_block_number(number);
+                ExExNotification::ChainCommitted {
+                    new: Arc::new(Chain::new(vec![block], Default::default(), Default::default())),
+                }
+            })
+            .collect::<Vec<_>>();
+        for notification in &notifications_to_send {
+            exex_manager
+                .handle
+                .exex_tx
+                .send((ExExNotificationSource::Pipeline, notification.clone()))
+                .unwrap();
+        }
+
+        assert!(exex_manager.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(notifications.next().await.unwrap().unwrap(), notifications_to_send[0]);
+
+        assert!(exex_manager.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(notifications.next().await.unwrap().unwrap(), notifications_to_send[1]);
+
+        // The tick for block 2 is sent before the next notification
+        assert!(exex_manager.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(
+            notifications.next().await.unwrap().unwrap(),
+            ExExNotification::Tick { tick: ExExTick::Blocks { tip: 2 } }
+        );
+
+        assert!(exex_manager.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(notifications.next().await.unwrap().unwrap(), notifications_to_send[2]);
+        assert_eq!(exex_manager.exex_handles[0].pending_tick, None);
+    }
+
     #[tokio::test]
     async fn exex_handle_new() {
         let provider_factory = create_test_provider_factory();
@@ -0,0 +1,442 @@
+//! Durable key-value store of an ExEx that follows the canonical chain.
+
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{BlockNumber, B256};
+use reth_exex_types::ExExNotification;
+use reth_libmdbx::{
+    ffi::MDBX_dbi, DatabaseFlags, Environment, Geometry, Transaction, TransactionKind, WriteFlags,
+    RW,
+};
+use reth_node_api::NodePrimitives;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::path::Path;
+
+/// The table with the entries of the store.
+const ENTRIES_TABLE: &str = "entries";
+/// The table with the data to revert the batches that aren't finalized, keyed by the big-endian
+/// block number.
+const HISTORY_TABLE: &str = "history";
+/// The table with the last committed and the finalized block.
+const META_TABLE: &str = "meta";
+
+/// The key of the block of the last committed batch in the meta table.
+const LAST_BLOCK_KEY: &[u8] = b"last_block";
+/// The key of the last finalized block in the meta table.
+const FINALIZED_BLOCK_KEY: &[u8] = b"finalized_block";
+
+/// The maximum size of the database of a store.
+const MAX_STORE_SIZE: usize = 1024 * 1024 * 1024 * 1024;
+/// The step by which the database of a store grows.
+const STORE_GROWTH_STEP: isize = 64 * 1024 * 1024;
+
+/// [`ExExStore`] result type.
+pub type ExExStoreResult<T> = Result<T, ExExStoreError>;
+
+/// [`ExExStore`] error types.
+#[derive(Debug, thiserror::Error)]
+pub enum ExExStoreError {
+    /// Filesystem error at the path
+    #[error(transparent)]
+    FsPathError(#[from] reth_fs_util::FsPathError),
+    /// Database error
+    #[error(transparent)]
+    Database(#[from] reth_libmdbx::Error),
+    /// Encode error
+    #[error("failed to encode: {0}")]
+    Encode(#[from] rmp_serde::encode::Error),
+    /// Decode error
+    #[error("failed to decode: {0}")]
+    Decode(#[from] rmp_serde::decode::Error),
+    /// A batch was committed for a block below the last committed block.
+    #[error("batch for block {block} is below the last committed block {last}")]
+    BlockBelowLast {
+        /// The block of the batch.
+        block: BlockNumber,
+        /// The last committed block.
+        last: BlockNumber,
+    },
+    /// A revert below the finalized block was requested.
+    #[error("can't revert to block {block} below the finalized block {finalized}")]
+    RevertFinalized {
+        /// The block to revert to.
+        block: BlockNumber,
+        /// The finalized block.
+        finalized: BlockNumber,
+    },
+    /// The batch was committed, but the ExEx manager is gone.
+    #[error("failed to send the finished height {0:?}")]
+    FinishedHeight(BlockNumHash),
+}
+
+/// A batch of writes to an [`ExExStore`].
+///
+/// The writes are applied in order, once the batch is committed with [`ExExStore::commit`].
+#[derive(Debug, Default)]
+pub struct ExExStoreBatch {
+    writes: Vec<(Vec<u8>, Option<Vec<u8>>)>,
+}
+
+impl ExExStoreBatch {
+    /// Sets the value of the key.
+    pub fn put<K: Serialize + ?Sized, V: Serialize + ?Sized>(
+        &mut self,
+        key: &K,
+        value: &V,
+    ) -> ExExStoreResult<()> {
+        self.writes.push((rmp_serde::to_vec(key)?, Some(rmp_serde::to_vec(value)?)));
+        Ok(())
+    }
+
+    /// Deletes the key.
+    pub fn delete<K: Serialize + ?Sized>(&mut self, key: &K) -> ExExStoreResult<()> {
+        self.writes.push((rmp_serde::to_vec(key)?, None));
+        Ok(())
+    }
+
+    /// Returns `true` if the batch has no writes.
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+}
+
+/// Durable key-value store of an ExEx that follows the canonical chain.
+///
+/// The store is an MDBX database in its own directory. Writes are grouped in batches, and every
+/// batch is committed for a block, usually the tip of the committed chain of a notification. A
+/// batch is committed in a single database transaction, so after a crash the store contains all
+/// batches up to some block, and the ExEx can resume the notifications from
+/// [`ExExStore::block`].
+///
+/// Batches of blocks that were reorged out are undone with [`ExExStore::revert_to`] or
+/// [`ExExStore::revert_notification`]. The data to undo a batch is kept until its block is
+/// finalized with [`ExExStore::finalize`], which only deletes the data of the finalized batches.
+///
+/// Keys and values are encoded with MessagePack, so any serializable type can be used. Encoded
+/// keys must fit in the maximum key size of MDBX, which is about a kilobyte.
+#[derive(Debug, Clone)]
+pub struct ExExStore {
+    env: Environment,
+}
+
+impl ExExStore {
+    /// Opens the store in the given directory, and creates it if it doesn't exist.
+    pub fn open(directory: impl AsRef<Path>) -> ExExStoreResult<Self> {
+        reth_fs_util::create_dir_all(&directory)?;
+        let env = Environment::builder()
+            .set_max_dbs(3)
+            .set_geometry(Geometry {
+                size: Some(0..MAX_STORE_SIZE),
+                growth_step: Some(STORE_GROWTH_STEP),
+                shrink_threshold: None,
+                page_size: None,
+            })
+            .open(directory.as_ref())?;
+
+        let tx = env.begin_rw_txn()?;
+        for table in [ENTRIES_TABLE, HISTORY_TABLE, META_TABLE] {
+            tx.create_db(Some(table), DatabaseFlags::empty())?;
+        }
+        tx.commit()?;
+
+        Ok(Self { env })
+    }
+
+    /// Returns the block of the last committed batch.
+    pub fn block(&self) -> ExExStoreResult<Option<BlockNumHash>> {
+        read_block(&self.env.begin_ro_txn()?, LAST_BLOCK_KEY)
+    }
+
+    /// Returns the value of the key.
+    pub fn get<K: Serialize + ?Sized, V: DeserializeOwned>(
+        &self,
+        key: &K,
+    ) -> ExExStoreResult<Option<V>> {
+        let key = rmp_serde::to_vec(key)?;
+        let tx = self.env.begin_ro_txn()?;
+        tx.get::<Vec<u8>>(open_table(&tx, ENTRIES_TABLE)?, &key)?
+            .map(|value| rmp_serde::from_slice(&value))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    /// Commits the batch for the given block.
+    ///
+    /// The block can't be below the block of the last committed batch. Batches committed for the
+    /// same block are reverted together.
+    pub fn commit(&self, block: BlockNumHash, batch: ExExStoreBatch) -> ExExStoreResult<()> {
+        let tx = self.env.begin_rw_txn()?;
+        if let Some(last) =
+            read_block(&tx, LAST_BLOCK_KEY)?.filter(|last| last.number > block.number)
+        {
+            return Err(ExExStoreError::BlockBelowLast { block: block.number, last: last.number })
+        }
+        let entries = open_table(&tx, ENTRIES_TABLE)?;
+        let history = open_table(&tx, HISTORY_TABLE)?;
+
+        let mut previous = Vec::with_capacity(batch.writes.len());
+        for (key, value) in batch.writes {
+            previous.push((key.clone(), tx.get::<Vec<u8>>(entries, &key)?));
+            write_entry(&tx, entries, &key, value.as_deref())?;
+        }
+        previous.reverse();
+
+        let history_key = block.number.to_be_bytes();
+        if let Some(existing) = tx.get::<Vec<u8>>(history, &history_key)? {
+            previous.extend(rmp_serde::from_slice::<CommittedBatch>(&existing)?.previous);
+        }
+        let committed = CommittedBatch { block: block.into(), previous };
+        tx.put(history, history_key, rmp_serde::to_vec(&committed)?, WriteFlags::empty())?;
+        write_block(&tx, LAST_BLOCK_KEY, Some(block))?;
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Reverts all batches committed for blocks above the given block.
+    pub fn revert_to(&self, block: BlockNumber) -> ExExStoreResult<()> {
+        let tx = self.env.begin_rw_txn()?;
+        let finalized = read_block(&tx, FINALIZED_BLOCK_KEY)?;
+        if let Some(finalized) = finalized.filter(|finalized| finalized.number > block) {
+            return Err(ExExStoreError::RevertFinalized { block, finalized: finalized.number })
+        }
+        let entries = open_table(&tx, ENTRIES_TABLE)?;
+        let mut cursor = tx.cursor_with_dbi(open_table(&tx, HISTORY_TABLE)?)?;
+
+        let mut reverted = false;
+        let mut last = None;
+        while let Some(((), value)) = cursor.last::<(), Vec<u8>>()? {
+            let batch = rmp_serde::from_slice::<CommittedBatch>(&value)?;
+            if batch.block.number <= block {
+                last = Some(batch.block.into());
+                break
+            }
+            for (key, value) in batch.previous {
+                write_entry(&tx, entries, &key, value.as_deref())?;
+            }
+            cursor.del(WriteFlags::empty())?;
+            reverted = true;
+        }
+        if !reverted {
+            return Ok(())
+        }
+
+        write_block(&tx, LAST_BLOCK_KEY, last.or(finalized))?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Reverts the batches of the blocks reverted by the notification, if any.
+    ///
+    /// This should be called for every notification, before the committed chain of the
+    /// notification is processed.
+    pub fn revert_notification<N: NodePrimitives>(
+        &self,
+        notification: &ExExNotification<N>,
+    ) -> ExExStoreResult<()> {
+        match notification.reverted_chain() {
+            Some(chain) => self.revert_to(chain.first().number().saturating_sub(1)),
+            None => Ok(()),
+        }
+    }
+
+    /// Drops the data to revert the batches committed for blocks at or below the given block.
+    ///
+    /// Only the data of the finalized batches is deleted, the rest of the store is not rewritten.
+    pub fn finalize(&self, block: BlockNumber) -> ExExStoreResult<()> {
+        let tx = self.env.begin_rw_txn()?;
+        let mut cursor = tx.cursor_with_dbi(open_table(&tx, HISTORY_TABLE)?)?;
+
+        // the last batch at or below the block becomes the finalized block
+        let after = block.saturating_add(1).to_be_bytes();
+        let last_finalized = match cursor.set_range::<(), ()>(&after)? {
+            Some(_) => cursor.prev::<(), Vec<u8>>()?,
+            None => cursor.last::<(), Vec<u8>>()?,
+        };
+        let Some(((), value)) = last_finalized else { return Ok(()) };
+        let finalized = rmp_serde::from_slice::<CommittedBatch>(&value)?.block;
+
+        while let Some((key, ())) = cursor.first::<[u8; 8], ()>()? {
+            if BlockNumber::from_be_bytes(key) > block {
+                break
+            }
+            cursor.del(WriteFlags::empty())?;
+        }
+
+        write_block(&tx, FINALIZED_BLOCK_KEY, Some(finalized.into()))?;
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+/// Returns the handle of the table.
+fn open_table<K: TransactionKind>(tx: &Transaction<K>, table: &str) -> ExExStoreResult<MDBX_dbi> {
+    Ok(tx.open_db(Some(table))?.dbi())
+}
+
+/// Reads the block at the key of the meta table.
+fn read_block<K: TransactionKind>(
+    tx: &Transaction<K>,
+    key: &[u8],
+) -> ExExStoreResult<Option<BlockNumHash>> {
+    tx.get::<Vec<u8>>(open_table(tx, META_TABLE)?, key)?
+        .map(|value| rmp_serde::from_slice::<StoredBlock>(&value).map(Into::into))
+        .transpose()
+        .map_err(Into::into)
+}
+
+/// Writes the block at the key of the meta table, or deletes the key for [`None`].
+fn write_block(
+    tx: &Transaction<RW>,
+    key: &[u8],
+    block: Option<BlockNumHash>,
+) -> ExExStoreResult<()> {
+    let meta = open_table(tx, META_TABLE)?;
+    match block {
+        Some(block) => {
+            let value = rmp_serde::to_vec(&StoredBlock::from(block))?;
+            tx.put(meta, key, value, WriteFlags::empty())?
+        }
+        None => {
+            tx.del(meta, key, None)?;
+        }
+    }
+    Ok(())
+}
+
+/// Sets the value of the entry, or deletes it for [`None`].
+fn write_entry(
+    tx: &Transaction<RW>,
+    entries: MDBX_dbi,
+    key: &[u8],
+    value: Option<&[u8]>,
+) -> ExExStoreResult<()> {
+    match value {
+        Some(value) => tx.put(entries, key, value, WriteFlags::empty())?,
+        None => {
+            tx.del(entries, key, None)?;
+        }
+    }
+    Ok(())
+}
+
+/// The data to revert the batches that were committed for a block.
+#[derive(Debug, Serialize, Deserialize)]
+struct CommittedBatch {
+    /// The block the batches were committed for.
+    block: StoredBlock,
+    /// The values of the keys before the batches, in reverse write order.
+    previous: Vec<(Vec<u8>, Option<Vec<u8>>)>,
+}
+
+/// The number and hash of a block, in a form that doesn't require the `serde` feature of the
+/// alloy types.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct StoredBlock {
+    number: BlockNumber,
+    hash: [u8; 32],
+}
+
+impl From<BlockNumHash> for StoredBlock {
+    fn from(block: BlockNumHash) -> Self {
+        Self { number: block.number, hash: block.hash.0 }
+    }
+}
+
+impl From<StoredBlock> for BlockNumHash {
+    fn from(block: StoredBlock) -> Self {
+        Self::new(block.number, B256::new(block.hash))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(number: BlockNumber) -> BlockNumHash {
+        BlockNumHash::new(number, B256::with_last_byte(number as u8))
+    }
+
+    fn batch(writes: &[(&str, Option<u64>)]) -> ExExStoreBatch {
+        let mut batch = ExExStoreBatch::default();
+        for (key, value) in writes {
+            match value {
+                Some(value) => batch.put(*key, value).unwrap(),
+                None => batch.delete(*key).unwrap(),
+            }
+        }
+        batch
+    }
+
+    #[test]
+    fn commit_revert_and_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ExExStore::open(dir.path()).unwrap();
+        assert_eq!(store.block().unwrap(), None);
+
+        store.commit(block(1), batch(&[("a", Some(1)), ("b", Some(1))])).unwrap();
+        store.commit(block(2), batch(&[("a", Some(2)), ("b", None), ("a", Some(3))])).unwrap();
+        store.commit(block(3), batch(&[("c", Some(3))])).unwrap();
+        store.commit(block(3), batch(&[("c", Some(4))])).unwrap();
+        assert_eq!(store.block().unwrap(), Some(block(3)));
+        assert_eq!(store.get::<_, u64>("a").unwrap(), Some(3));
+        assert_eq!(store.get::<_, u64>("b").unwrap(), None);
+        assert_eq!(store.get::<_, u64>("c").unwrap(), Some(4));
+        assert!(matches!(
+            store.commit(block(2), batch(&[])),
+            Err(ExExStoreError::BlockBelowLast { block: 2, last: 3 })
+        ));
+
+        store.revert_to(1).unwrap();
+        assert_eq!(store.block().unwrap(), Some(block(1)));
+        assert_eq!(store.get::<_, u64>("a").unwrap(), Some(1));
+        assert_eq!(store.get::<_, u64>("b").unwrap(), Some(1));
+        assert_eq!(store.get::<_, u64>("c").unwrap(), None);
+
+        store.commit(block(2), batch(&[("c", Some(2))])).unwrap();
+        drop(store);
+
+        // the committed batches and their revert data are read back from disk
+        let store = ExExStore::open(dir.path()).unwrap();
+        assert_eq!(store.block().unwrap(), Some(block(2)));
+        assert_eq!(store.get::<_, u64>("a").unwrap(), Some(1));
+        assert_eq!(store.get::<_, u64>("c").unwrap(), Some(2));
+
+        store.revert_to(0).unwrap();
+        assert_eq!(store.block().unwrap(), None);
+        assert_eq!(store.get::<_, u64>("a").unwrap(), None);
+    }
+
+    #[test]
+    fn finalize() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ExExStore::open(dir.path()).unwrap();
+        for number in 1..=3 {
+            store.commit(block(number), batch(&[("a", Some(number))])).unwrap();
+        }
+
+        store.finalize(2).unwrap();
+        assert!(matches!(
+            store.revert_to(1),
+            Err(ExExStoreError::RevertFinalized { block: 1, finalized: 2 })
+        ));
+        store.revert_to(2).unwrap();
+        assert_eq!(store.block().unwrap(), Some(block(2)));
+        assert_eq!(store.get::<_, u64>("a").unwrap(), Some(2));
+
+        // the finalized block is kept after reopening, and later batches can still be reverted
+        store.commit(block(3), batch(&[("a", Some(4))])).unwrap();
+        store.commit(block(4), batch(&[("a", Some(5))])).unwrap();
+        store.finalize(3).unwrap();
+        drop(store);
+        let store = ExExStore::open(dir.path()).unwrap();
+        assert_eq!(store.block().unwrap(), Some(block(4)));
+        assert!(matches!(
+            store.revert_to(2),
+            Err(ExExStoreError::RevertFinalized { block: 2, finalized: 3 })
+        ));
+        store.revert_to(3).unwrap();
+        assert_eq!(store.block().unwrap(), Some(block(3)));
+        assert_eq!(store.get::<_, u64>("a").unwrap(), Some(4));
+    }
+}
@@ -0,0 +1,166 @@
+//! Encryption of the WAL files at rest.
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Key, Nonce,
+};
+use alloy_primitives::{FixedBytes, B256};
+use std::{fmt, path::Path};
+
+/// Prefix of encrypted WAL files, followed by the nonce and the ciphertext.
+const ENCRYPTED_FILE_MAGIC: &[u8; 4] = b"RWE1";
+
+/// Length of the AES-GCM nonce in bytes.
+const NONCE_LENGTH: usize = 12;
+
+/// Errors of the WAL encryption.
+#[derive(Debug, thiserror::Error)]
+pub enum WalEncryptionError {
+    /// The key couldn't be loaded.
+    #[error("invalid WAL encryption key: {0}")]
+    InvalidKey(String),
+    /// The file couldn't be encrypted.
+    #[error("failed to encrypt WAL file")]
+    Encrypt,
+    /// The file couldn't be decrypted, because the key is wrong or the file was modified.
+    #[error("failed to decrypt WAL file, the key is wrong or the file is corrupted")]
+    Decrypt,
+    /// The file is encrypted, but no key is configured.
+    #[error("WAL file is encrypted, but no encryption key is configured")]
+    MissingKey,
+    /// The file isn't encrypted, but a key is configured.
+    #[error("WAL file isn't encrypted, but an encryption key is configured")]
+    Unencrypted,
+}
+
+/// A 256-bit key to encrypt the WAL files with, using AES-256-GCM.
+///
+/// Every file is encrypted with a random nonce, and its file ID is authenticated along with the
+/// content, so encrypted files can't be swapped.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct WalEncryptionKey([u8; 32]);
+
+impl WalEncryptionKey {
+    /// Creates a key from raw bytes.
+    pub const fn new(key: [u8; 32]) -> Self {
+        Self(key)
+    }
+
+    /// Parses a hex-encoded key, with or without the `0x` prefix.
+    pub fn from_hex(hex: &str) -> Result<Self, WalEncryptionError> {
+        hex.trim()
+            .parse::<B256>()
+            .map(|key| Self(key.0))
+            .map_err(|err| WalEncryptionError::InvalidKey(err.to_string()))
+    }
+
+    /// Loads the hex-encoded key from the given file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, WalEncryptionError> {
+        let hex = reth_fs_util::read_to_string(path)
+            .map_err(|err| WalEncryptionError::InvalidKey(err.to_string()))?;
+        Self::from_hex(&hex)
+    }
+
+    /// Loads the hex-encoded key from the given environment variable.
+    pub fn from_env(var: &str) -> Result<Self, WalEncryptionError> {
+        let hex = std::env::var(var)
+            .map_err(|err| WalEncryptionError::InvalidKey(format!("{var}: {err}")))?;
+        Self::from_hex(&hex)
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.0))
+    }
+
+    /// Encrypts the content of the file with the given ID.
+    pub(super) fn encrypt(
+        &self,
+        file_id: u32,
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>, WalEncryptionError> {
+        let nonce = FixedBytes::<NONCE_LENGTH>::random();
+        let aad = file_id.to_be_bytes();
+        let ciphertext = self
+            .cipher()
+            .encrypt(Nonce::from_slice(nonce.as_slice()), Payload { msg: plaintext, aad: &aad })
+            .map_err(|_| WalEncryptionError::Encrypt)?;
+
+        let mut encrypted =
+            Vec::with_capacity(ENCRYPTED_FILE_MAGIC.len() + NONCE_LENGTH + ciphertext.len());
+        encrypted.extend_from_slice(ENCRYPTED_FILE_MAGIC);
+        encrypted.extend_from_slice(nonce.as_slice());
+        encrypted.extend_from_slice(&ciphertext);
+        Ok(encrypted)
+    }
+}
+
+/// Returns the plaintext content of the file with the given ID.
+///
+/// Without a key, only files that aren't encrypted can be read. With a key, only encrypted files
+/// can be read, so a file written in plaintext can't be slipped into an encrypted WAL.
+pub(super) fn decrypt(
+    key: Option<&WalEncryptionKey>,
+    file_id: u32,
+    content: Vec<u8>,
+) -> Result<Vec<u8>, WalEncryptionError> {
+    let encrypted = content.strip_prefix(ENCRYPTED_FILE_MAGIC);
+    let (key, encrypted) = match (key, encrypted) {
+        (Some(key), Some(encrypted)) => (key, encrypted),
+        (Some(_), None) => return Err(WalEncryptionError::Unencrypted),
+        (None, Some(_)) => return Err(WalEncryptionError::MissingKey),
+        (None, None) => return Ok(content),
+    };
+    if encrypted.len() < NONCE_LENGTH {
+        return Err(WalEncryptionError::Decrypt)
+    }
+
+    let (nonce, ciphertext) = encrypted.split_at(NONCE_LENGTH);
+    let aad = file_id.to_be_bytes();
+    key.cipher()
+        .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: &aad })
+        .map_err(|_| WalEncryptionError::Decrypt)
+}
+
+impl fmt::Debug for WalEncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("WalEncryptionKey(..)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encryption_roundtrip() {
+        let key = WalEncryptionKey::new([1; 32]);
+        let encrypted = key.encrypt(7, b"notification").unwrap();
+        assert!(encrypted.starts_with(ENCRYPTED_FILE_MAGIC));
+
+        assert_eq!(decrypt(Some(&key), 7, encrypted.clone()).unwrap(), b"notification");
+        // the file ID is authenticated
+        assert!(matches!(
+            decrypt(Some(&key), 8, encrypted.clone()),
+            Err(WalEncryptionError::Decrypt)
+        ));
+        assert!(matches!(
+            decrypt(Some(&WalEncryptionKey::new([2; 32])), 7, encrypted.clone()),
+            Err(WalEncryptionError::Decrypt)
+        ));
+        assert!(matches!(decrypt(None, 7, encrypted), Err(WalEncryptionError::MissingKey)));
+
+        // unencrypted files are rejected if a key is configured
+        assert!(matches!(
+            decrypt(Some(&key), 7, b"plain".to_vec()),
+            Err(WalEncryptionError::Unencrypted)
+        ));
+        assert_eq!(decrypt(None, 7, b"plain".to_vec()).unwrap(), b"plain");
+    }
+
+    #[test]
+    fn parse_key() {
+        let key = WalEncryptionKey::from_hex(&format!("0x{}\n", "01".repeat(32))).unwrap();
+        assert_eq!(key, WalEncryptionKey::new([1; 32]));
+        assert!(WalEncryptionKey::from_hex("0x01").is_err());
+    }
+}
@@ -1,5 +1,6 @@
 //! Wal Errors
 
+use crate::wal::WalEncryptionError;
 use std::path::PathBuf;
 
 /// Wal Result type.
@@ -26,4 +27,7 @@ pub enum WalError {
     /// Decode error
     #[error("failed to decode notification {0} from {1}: {2}")]
     Decode(u32, PathBuf, rmp_serde::decode::Error),
+    /// Decryption error
+    #[error("failed to decrypt notification {0} from {1}: {2}")]
+    Decrypt(u32, PathBuf, WalEncryptionError),
 }
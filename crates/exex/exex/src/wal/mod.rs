@@ -10,6 +10,8 @@ mod metrics;
 use metrics::Metrics;
 mod error;
 pub use error::{WalError, WalResult};
+mod encryption;
+pub use encryption::{WalEncryptionError, WalEncryptionKey};
 
 use std::{
     path::Path,
@@ -46,7 +48,17 @@ where
 {
     /// Creates a new instance of [`Wal`].
     pub fn new(directory: impl AsRef<Path>) -> WalResult<Self> {
-        Ok(Self { inner: Arc::new(WalInner::new(directory)?) })
+        Self::new_with_encryption(directory, None)
+    }
+
+    /// Creates a new instance of [`Wal`] that encrypts its files with the given key, if any.
+    ///
+    /// Files that were written without encryption are rejected if a key is given.
+    pub fn new_with_encryption(
+        directory: impl AsRef<Path>,
+        encryption_key: Option<WalEncryptionKey>,
+    ) -> WalResult<Self> {
+        Ok(Self { inner: Arc::new(WalInner::new(directory, encryption_key)?) })
     }
 
     /// Returns a read-only handle to the WAL.
@@ -95,10 +107,13 @@ impl<N> WalInner<N>
 where
     N: NodePrimitives,
 {
-    fn new(directory: impl AsRef<Path>) -> WalResult<Self> {
+    fn new(
+        directory: impl AsRef<Path>,
+        encryption_key: Option<WalEncryptionKey>,
+    ) -> WalResult<Self> {
         let mut wal = Self {
             next_file_id: AtomicU32::new(0),
-            storage: Storage::new(directory)?,
+            storage: Storage::new(directory)?.with_encryption_key(encryption_key),
             block_cache: RwLock::new(BlockCache::default()),
             metrics: Metrics::default(),
         };
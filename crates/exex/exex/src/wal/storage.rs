@@ -1,10 +1,10 @@
 use std::{
-    fs::File,
+    io::Write,
     ops::RangeInclusive,
     path::{Path, PathBuf},
 };
 
-use crate::wal::{WalError, WalResult};
+use crate::wal::{encryption, WalEncryptionKey, WalError, WalResult};
 use reth_ethereum_primitives::EthPrimitives;
 use reth_exex_types::ExExNotification;
 use reth_node_api::NodePrimitives;
@@ -16,11 +16,13 @@ static FILE_EXTENSION: &str = "wal";
 /// The underlying WAL storage backed by a directory of files.
 ///
 /// Each notification is represented by a single file that contains a MessagePack-encoded
-/// notification.
+/// notification, optionally encrypted with a [`WalEncryptionKey`].
 #[derive(Debug, Clone)]
 pub struct Storage<N: NodePrimitives = EthPrimitives> {
     /// The path to the WAL file.
     path: PathBuf,
+    /// The key to encrypt the files with, if any.
+    encryption_key: Option<WalEncryptionKey>,
     _pd: std::marker::PhantomData<N>,
 }
 
@@ -33,7 +35,19 @@ where
     pub(super) fn new(path: impl AsRef<Path>) -> WalResult<Self> {
         reth_fs_util::create_dir_all(&path)?;
 
-        Ok(Self { path: path.as_ref().to_path_buf(), _pd: std::marker::PhantomData })
+        Ok(Self {
+            path: path.as_ref().to_path_buf(),
+            encryption_key: None,
+            _pd: std::marker::PhantomData,
+        })
+    }
+
+    /// Encrypts the written files with the given key.
+    ///
+    /// Files that were written without encryption remain readable.
+    pub(super) const fn with_encryption_key(mut self, key: Option<WalEncryptionKey>) -> Self {
+        self.encryption_key = key;
+        self
     }
 
     fn file_path(&self, id: u32) -> PathBuf {
@@ -134,16 +148,18 @@ where
         let file_path = self.file_path(file_id);
         debug!(target: "exex::wal::storage", ?file_path, "Reading notification from WAL");
 
-        let mut file = match File::open(&file_path) {
-            Ok(file) => file,
+        let content = match std::fs::read(&file_path) {
+            Ok(content) => content,
             Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
-            Err(err) => return Err(reth_fs_util::FsPathError::open(err, &file_path).into()),
+            Err(err) => return Err(reth_fs_util::FsPathError::read(err, &file_path).into()),
         };
-        let size = file.metadata().map_err(|err| WalError::FileMetadata(file_id, err))?.len();
+        let size = content.len() as u64;
+        let content = encryption::decrypt(self.encryption_key.as_ref(), file_id, content)
+            .map_err(|err| WalError::Decrypt(file_id, file_path.clone(), err))?;
 
         // Deserialize using the bincode- and msgpack-compatible serde wrapper
         let notification: reth_exex_types::serde_bincode_compat::ExExNotification<'_, N> =
-            rmp_serde::decode::from_read(&mut file)
+            rmp_serde::decode::from_slice(&content)
                 .map_err(|err| WalError::Decode(file_id, file_path, err))?;
 
         Ok(Some((notification.into(), size)))
@@ -167,9 +183,19 @@ where
         let notification =
             reth_exex_types::serde_bincode_compat::ExExNotification::<N>::from(notification);
 
-        reth_fs_util::atomic_write_file(&file_path, |file| {
-            rmp_serde::encode::write(file, &notification)
-        })?;
+        reth_fs_util::atomic_write_file(
+            &file_path,
+            |file| -> Result<(), Box<dyn core::error::Error + Send + Sync>> {
+                match &self.encryption_key {
+                    Some(key) => {
+                        let plaintext = rmp_serde::to_vec(&notification)?;
+                        file.write_all(&key.encrypt(file_id, &plaintext)?)?;
+                    }
+                    None => rmp_serde::encode::write(file, &notification)?,
+                }
+                Ok(())
+            },
+        )?;
 
         Ok(file_path.metadata().map_err(|err| WalError::FileMetadata(file_id, err))?.len())
     }
@@ -178,6 +204,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::Storage;
+    use crate::wal::WalEncryptionKey;
     use reth_exex_types::ExExNotification;
     use reth_provider::Chain;
     use reth_testing_utils::generators::{self, random_block};
@@ -229,6 +256,34 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_encrypted_roundtrip() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir()?;
+        let key = WalEncryptionKey::new([1; 32]);
+        let storage: Storage = Storage::new(&temp_dir)?.with_encryption_key(Some(key));
+
+        let block = random_block(&mut rng, 0, Default::default()).try_recover()?;
+        let notification = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(vec![block], Default::default(), None)),
+        };
+
+        let plain_storage: Storage = Storage::new(&temp_dir)?;
+        plain_storage.write_notification(0, &notification)?;
+        storage.write_notification(1, &notification)?;
+
+        let (read, _) = storage.read_notification(1)?.unwrap();
+        assert_eq!(read, notification);
+
+        // unencrypted files can't be read with the key
+        assert!(storage.read_notification(0).is_err());
+        // encrypted files can't be read without the key
+        assert!(plain_storage.read_notification(1).is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_files_range() -> eyre::Result<()> {
         let temp_dir = tempfile::tempdir()?;
@@ -14,7 +14,7 @@ mod notification;
 
 pub use finished_height::FinishedExExHeight;
 pub use head::ExExHead;
-pub use notification::ExExNotification;
+pub use notification::{ExExNotification, ExExTick};
 
 /// Bincode-compatible serde implementations for commonly used ExEx types.
 ///
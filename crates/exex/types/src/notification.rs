@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use alloy_primitives::BlockNumber;
 use reth_chain_state::CanonStateNotification;
 use reth_execution_types::Chain;
 use reth_primitives_traits::NodePrimitives;
@@ -25,6 +26,35 @@ pub enum ExExNotification<N: NodePrimitives = reth_chain_state::EthPrimitives> {
         /// The old chain before reversion.
         old: Arc<Chain<N>>,
     },
+    /// Notifications were dropped because the `ExEx` fell too far behind the node.
+    ///
+    /// This is only sent to best-effort `ExEx`'s, right before the next notification they receive.
+    /// The skipped blocks can be read from the node's database if the `ExEx` needs them.
+    NotificationsSkipped {
+        /// The number of notifications that were skipped.
+        count: usize,
+    },
+    /// A periodic tick requested by the `ExEx` with an `ExExEvent::ScheduleTicks` event.
+    ///
+    /// Ticks are delivered in order with the chain notifications, and ticks that are due while the
+    /// previous one wasn't received yet are coalesced.
+    Tick {
+        /// The reason of the tick.
+        tick: ExExTick,
+    },
+}
+
+/// The reason of an [`ExExNotification::Tick`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExExTick {
+    /// The requested interval elapsed.
+    Interval,
+    /// The committed chain reached a multiple of the requested number of blocks.
+    Blocks {
+        /// The tip of the committed chain that was sent right before the tick.
+        tip: BlockNumber,
+    },
 }
 
 impl<N: NodePrimitives> ExExNotification<N> {
@@ -33,7 +63,9 @@ impl<N: NodePrimitives> ExExNotification<N> {
     pub fn committed_chain(&self) -> Option<Arc<Chain<N>>> {
         match self {
             Self::ChainCommitted { new } | Self::ChainReorged { old: _, new } => Some(new.clone()),
-            Self::ChainReverted { .. } => None,
+            Self::ChainReverted { .. } | Self::NotificationsSkipped { .. } | Self::Tick { .. } => {
+                None
+            }
         }
     }
 
@@ -42,7 +74,9 @@ impl<N: NodePrimitives> ExExNotification<N> {
     pub fn reverted_chain(&self) -> Option<Arc<Chain<N>>> {
         match self {
             Self::ChainReorged { old, new: _ } | Self::ChainReverted { old } => Some(old.clone()),
-            Self::ChainCommitted { .. } => None,
+            Self::ChainCommitted { .. } | Self::NotificationsSkipped { .. } | Self::Tick { .. } => {
+                None
+            }
         }
     }
 
@@ -52,11 +86,14 @@ impl<N: NodePrimitives> ExExNotification<N> {
     /// - For [`Self::ChainReverted`], it's [`Self::ChainCommitted`].
     /// - For [`Self::ChainReorged`], it's [`Self::ChainReorged`] with the new chain as the old
     ///   chain and the old chain as the new chain.
+    /// - For [`Self::NotificationsSkipped`] and [`Self::Tick`], it's the same notification.
     pub fn into_inverted(self) -> Self {
         match self {
             Self::ChainCommitted { new } => Self::ChainReverted { old: new },
             Self::ChainReverted { old } => Self::ChainCommitted { new: old },
             Self::ChainReorged { old, new } => Self::ChainReorged { old: new, new: old },
+            Self::NotificationsSkipped { count } => Self::NotificationsSkipped { count },
+            Self::Tick { tick } => Self::Tick { tick },
         }
     }
 }
@@ -106,6 +143,8 @@ pub(super) mod serde_bincode_compat {
         ChainCommitted { new: Chain<'a, N> },
         ChainReorged { old: Chain<'a, N>, new: Chain<'a, N> },
         ChainReverted { old: Chain<'a, N> },
+        NotificationsSkipped { count: usize },
+        Tick { tick: super::ExExTick },
     }
 
     impl<'a, N> From<&'a super::ExExNotification<N>> for ExExNotification<'a, N>
@@ -126,6 +165,10 @@ pub(super) mod serde_bincode_compat {
                 super::ExExNotification::ChainReverted { old } => {
                     ExExNotification::ChainReverted { old: Chain::from(old.as_ref()) }
                 }
+                super::ExExNotification::NotificationsSkipped { count } => {
+                    ExExNotification::NotificationsSkipped { count: *count }
+                }
+                super::ExExNotification::Tick { tick } => ExExNotification::Tick { tick: *tick },
             }
         }
     }
@@ -145,6 +188,10 @@ pub(super) mod serde_bincode_compat {
                 ExExNotification::ChainReverted { old } => {
                     Self::ChainReverted { old: Arc::new(old.into()) }
                 }
+                ExExNotification::NotificationsSkipped { count } => {
+                    Self::NotificationsSkipped { count }
+                }
+                ExExNotification::Tick { tick } => Self::Tick { tick },
             }
         }
     }
@@ -84,9 +84,9 @@ pub use reth_network_peers::NodeRecord;
 pub mod test_utils;
 
 use crate::table::PongTable;
-use reth_net_nat::ResolveNatInterval;
 /// reexport to get public ip.
 pub use reth_net_nat::{external_ip, NatResolver};
+use reth_net_nat::{MappedPort, PortMappingProtocol, ResolveNatInterval};
 
 /// The default address for discv4 via UDP
 ///
@@ -375,6 +375,15 @@ impl Discv4 {
         self.send_to_service(cmd);
     }
 
+    /// Advertises the external ports of the given port mappings negotiated with a NAT gateway.
+    ///
+    /// This will update our [`NodeRecord`]'s udp and tcp ports, ports without a mapping are
+    /// unchanged.
+    pub fn set_mapped_ports(&self, mapped_ports: Vec<MappedPort>) {
+        let cmd = Discv4Command::SetMappedPorts(mapped_ports);
+        self.send_to_service(cmd);
+    }
+
     /// Sets the pair in the EIP-868 [`Enr`] of the node.
     ///
     /// If the key already exists, this will update it.
@@ -635,6 +644,34 @@ impl Discv4Service {
         }
     }
 
+    /// Updates the advertised UDP and TCP ports to the external ports of the given mappings.
+    ///
+    /// Ports without a mapping keep their current value.
+    fn set_mapped_ports(&mut self, mapped_ports: &[MappedPort]) {
+        let mut record = self.local_node_record;
+        for port in mapped_ports {
+            match port.protocol {
+                PortMappingProtocol::Udp => record.udp_port = port.external_port,
+                PortMappingProtocol::Tcp => record.tcp_port = port.external_port,
+            }
+        }
+        if record == self.local_node_record {
+            return
+        }
+
+        debug!(target: "discv4", udp_port = record.udp_port, tcp_port = record.tcp_port, "Updating external ports");
+        self.local_node_record = record;
+        if record.address.is_ipv4() {
+            let _ = self.local_eip_868_enr.set_udp4(record.udp_port, &self.secret_key);
+            let _ = self.local_eip_868_enr.set_tcp4(record.tcp_port, &self.secret_key);
+        } else {
+            let _ = self.local_eip_868_enr.set_udp6(record.udp_port, &self.secret_key);
+            let _ = self.local_eip_868_enr.set_tcp6(record.tcp_port, &self.secret_key);
+        }
+        *self.shared_node_record.lock() = record;
+        debug!(target: "discv4", enr=?self.local_eip_868_enr, "Updated local ENR");
+    }
+
     /// Returns the [`PeerId`] that identifies this node
     pub const fn local_peer_id(&self) -> &PeerId {
         &self.local_node_record.id
@@ -1752,6 +1789,9 @@ impl Discv4Service {
                             let _ = self.local_eip_868_enr.set_tcp6(port, &self.secret_key);
                         }
                     }
+                    Discv4Command::SetMappedPorts(mapped_ports) => {
+                        self.set_mapped_ports(&mapped_ports);
+                    }
 
                     Discv4Command::Terminated => {
                         // terminate the service
@@ -2033,6 +2073,7 @@ impl Default for ReceiveCache {
 enum Discv4Command {
     Add(NodeRecord),
     SetTcpPort(u16),
+    SetMappedPorts(Vec<MappedPort>),
     SetEIP868RLPPair { key: Vec<u8>, rlp: Bytes },
     Ban(PeerId, IpAddr),
     BanPeer(PeerId),
@@ -2563,6 +2604,37 @@ mod tests {
         };
     }
 
+    #[tokio::test]
+    async fn test_set_mapped_ports() {
+        reth_tracing::init_test_tracing();
+        let (discv4, mut service) = create_discv4().await;
+        let local = service.local_enr();
+
+        service.set_mapped_ports(&[
+            MappedPort {
+                protocol: PortMappingProtocol::Udp,
+                internal_port: local.udp_port,
+                external_port: 40404,
+                lease: Duration::from_secs(600),
+            },
+            MappedPort {
+                protocol: PortMappingProtocol::Tcp,
+                internal_port: local.tcp_port,
+                external_port: 40505,
+                lease: Duration::from_secs(600),
+            },
+        ]);
+
+        let record = service.local_enr();
+        assert_eq!(record.udp_port, 40404);
+        assert_eq!(record.tcp_port, 40505);
+        assert_eq!(discv4.node_record(), record);
+        assert_eq!(service.local_eip_868_enr.udp4(), Some(40404));
+        assert_eq!(service.local_eip_868_enr.tcp4(), Some(40505));
+        // the socket the service listens on is unchanged
+        assert_eq!(service.local_addr().port(), local.udp_port);
+    }
+
     #[tokio::test]
     async fn test_respect_ping_expiration() {
         reth_tracing::init_test_tracing();
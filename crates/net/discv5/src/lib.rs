@@ -122,6 +122,35 @@ impl Discv5 {
         self.set_eip868_in_local_enr(key, buf.into())
     }
 
+    /// Sets the `RLPx` port in the [`Enr`] of the node.
+    ///
+    /// This is used to advertise the external port a NAT gateway forwards to the `RLPx` listener.
+    pub fn set_tcp_port(&self, port: u16) {
+        let key = match self.rlpx_ip_mode {
+            IpMode::Ip6 => "tcp6",
+            _ => "tcp",
+        };
+        if let Err(err) = self.discv5.enr_insert(key, &port) {
+            error!(target: "net::discv5",
+                %err,
+                "failed to update tcp port in local enr"
+            );
+        }
+    }
+
+    /// Sets the discovery port in the [`Enr`] of the node.
+    ///
+    /// This is used to advertise the external port a NAT gateway forwards to the discv5 socket.
+    pub fn set_udp_port(&self, port: u16) {
+        let key = if self.discv5.local_enr().ip4().is_some() { "udp" } else { "udp6" };
+        if let Err(err) = self.discv5.enr_insert(key, &port) {
+            error!(target: "net::discv5",
+                %err,
+                "failed to update udp port in local enr"
+            );
+        }
+    }
+
     /// Adds the peer and id to the ban list.
     ///
     /// This will prevent any future inclusion in the table
@@ -104,6 +104,9 @@ pub struct ReverseHeadersDownloader<H: HeadersClient> {
     ///
     /// Note: headers are sorted from high to low
     queued_validated_headers: Vec<SealedHeader<H::Header>>,
+    /// The start of the gap that was requested again because its response blocked validating the
+    /// buffered responses, see [`Self::next_gap_request`].
+    requested_gap: Option<u64>,
     /// Header downloader metrics.
     metrics: HeaderDownloaderMetrics,
 }
@@ -190,6 +193,29 @@ where
         None
     }
 
+    /// Returns a request for the gap below the lowest validated header if the buffered responses
+    /// can't be validated because of it.
+    ///
+    /// Responses are buffered until all higher headers are validated. If the response for the
+    /// next expected headers is missing, e.g. because the request was sent to a slow peer, the
+    /// buffer fills up and no new requests are sent. The gap is requested again from another
+    /// peer, so that a single peer can't stall the download. Each gap is only requested again
+    /// once, whichever response arrives first is validated and the other one is discarded.
+    fn next_gap_request(&mut self) -> Option<HeadersRequest> {
+        if self.buffered_responses.len() < self.max_buffered_responses {
+            return None
+        }
+
+        let local_head = self.local_block_number()?;
+        let gap = self.next_chain_tip_block_number;
+        if gap <= local_head || self.requested_gap == Some(gap) {
+            return None
+        }
+
+        self.requested_gap = Some(gap);
+        Some(calc_next_request(local_head, gap, self.request_limit))
+    }
+
     /// Returns the next header to use for validation.
     ///
     /// Since this downloader downloads blocks with falling block number, this will return the
@@ -210,6 +236,7 @@ where
         debug!(target: "downloaders::headers", "Resetting headers downloader");
         self.next_request_block_number = 0;
         self.next_chain_tip_block_number = 0;
+        self.requested_gap = None;
         self.sync_target.take();
     }
 
@@ -628,6 +655,7 @@ where
         self.queued_validated_headers = Vec::new();
         self.buffered_responses = BinaryHeap::new();
         self.in_progress_queue.clear();
+        self.requested_gap = None;
 
         self.metrics.in_flight_requests.set(0.);
         self.metrics.buffered_responses.set(0.);
@@ -887,6 +915,17 @@ where
                 }
             }
 
+            // request the gap again if it blocks validating the full buffer
+            if let Some(request) = this.next_gap_request() {
+                trace!(
+                    target: "downloaders::headers",
+                    "Requesting headers gap {request:?}"
+                );
+                this.metrics.gap_requests.increment(1);
+                progress = true;
+                this.submit_request(request, Priority::High);
+            }
+
             // yield next batch
             if this.queued_validated_headers.len() >= this.stream_batch_size {
                 let next_batch = this.split_next_batch();
@@ -1230,6 +1269,7 @@ impl ReverseHeadersDownloaderBuilder {
             in_progress_queue: Default::default(),
             buffered_responses: Default::default(),
             queued_validated_headers: Default::default(),
+            requested_gap: None,
             metrics: Default::default(),
         }
     }
@@ -1392,6 +1432,38 @@ mod tests {
         assert_eq!(request.limit, 1);
     }
 
+    #[test]
+    fn test_next_gap_request() {
+        let client = Arc::new(TestHeadersClient::default());
+
+        let mut downloader = ReverseHeadersDownloaderBuilder::default()
+            .request_limit(10)
+            .max_buffered_responses(1)
+            .build(Arc::clone(&client), Arc::new(TestConsensus::default()));
+        downloader.update_local_head(SealedHeader::default());
+        downloader.next_chain_tip_block_number = 100;
+
+        // no gap is requested while the buffer isn't full
+        assert!(downloader.next_gap_request().is_none());
+
+        downloader.buffered_responses.push(OrderedHeadersResponse {
+            headers: Vec::new(),
+            request: HeadersRequest::falling(90.into(), 10),
+            peer_id: PeerId::default(),
+        });
+        let request = downloader.next_gap_request().unwrap();
+        assert_eq!(request.start, 100.into());
+        assert_eq!(request.limit, 10);
+
+        // the gap is only requested again once
+        assert!(downloader.next_gap_request().is_none());
+
+        // the next gap is requested once the first gap was validated
+        downloader.next_chain_tip_block_number = 80;
+        let request = downloader.next_gap_request().unwrap();
+        assert_eq!(request.start, 80.into());
+    }
+
     /// Tests that request calc works
     #[test]
     fn test_next_request() {
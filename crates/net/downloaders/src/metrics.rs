@@ -114,6 +114,9 @@ pub struct HeaderDownloaderMetrics {
     /// Out-of-order request happen when the headers downloader `SyncTarget::Tip`
     /// hash is different from the previous sync target hash.
     pub out_of_order_requests: Counter,
+    /// The number of requests for gaps that blocked validating the buffered responses, e.g.
+    /// because the gap was requested from a slow peer.
+    pub gap_requests: Counter,
     /// Number of timeout errors while requesting items
     pub timeout_errors: Counter,
     /// Number of validation errors while requesting items
@@ -64,6 +64,30 @@ impl From<NewBlockHashes> for Vec<BlockHashNumber> {
     }
 }
 
+/// Informs peers about the range of blocks the sender can serve.
+///
+/// This was added in `eth/69` and is sent whenever the range changes, see
+/// [EIP-7642](https://eips.ethereum.org/EIPS/eip-7642).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct BlockRangeUpdate {
+    /// The earliest block that can be served.
+    pub earliest: u64,
+    /// The latest block that can be served.
+    pub latest: u64,
+    /// The hash of the latest block.
+    pub latest_hash: B256,
+}
+
+impl BlockRangeUpdate {
+    /// Returns `true` if the earliest block is not greater than the latest block.
+    pub const fn is_valid(&self) -> bool {
+        self.earliest <= self.latest
+    }
+}
+
 /// A new block with the current total difficulty, which includes the difficulty of the returned
 /// block.
 #[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
@@ -150,7 +174,7 @@ impl NewPooledTransactionHashes {
                 matches!(version, EthVersion::Eth67 | EthVersion::Eth66)
             }
             Self::Eth68(_) => {
-                matches!(version, EthVersion::Eth68)
+                matches!(version, EthVersion::Eth68 | EthVersion::Eth69)
             }
         }
     }
@@ -31,7 +31,16 @@ pub trait NetworkPrimitives:
     type PooledTransaction: SignedTransaction + TryFrom<Self::BroadcastedTransaction> + 'static;
 
     /// The transaction type which peers return in `GetReceipts` messages.
-    type Receipt: TxReceipt + RlpEncodableReceipt + RlpDecodableReceipt + Unpin + 'static;
+    ///
+    /// The [`Encodable`] and [`Decodable`] implementations are used for the `eth/69` encoding,
+    /// which omits the bloom filter.
+    type Receipt: TxReceipt
+        + RlpEncodableReceipt
+        + RlpDecodableReceipt
+        + Encodable
+        + Decodable
+        + Unpin
+        + 'static;
 }
 
 /// This is a helper trait for use in bounds, where some of the [`NetworkPrimitives`] associated
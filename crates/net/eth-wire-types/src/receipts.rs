@@ -1,7 +1,7 @@
-//! Implements the `GetReceipts` and `Receipts` message types.
+//! Implements the `GetReceipts`, `Receipts` and `eth/69` `Receipts` message types.
 
 use alloc::vec::Vec;
-use alloy_consensus::{ReceiptWithBloom, RlpDecodableReceipt, RlpEncodableReceipt};
+use alloy_consensus::{ReceiptWithBloom, RlpDecodableReceipt, RlpEncodableReceipt, TxReceipt};
 use alloy_primitives::B256;
 use alloy_rlp::{RlpDecodableWrapper, RlpEncodableWrapper};
 use reth_codecs_derive::add_arbitrary_tests;
@@ -46,10 +46,68 @@ impl<T: RlpDecodableReceipt> alloy_rlp::Decodable for Receipts<T> {
     }
 }
 
+/// The `eth/69` response to [`GetReceipts`], containing receipt lists that correspond to each
+/// block requested.
+///
+/// Unlike [`Receipts`], the receipts don't include the bloom filter, which can be recomputed from
+/// the logs, see also [EIP-7642](https://eips.ethereum.org/EIPS/eip-7642).
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct Receipts69<T = Receipt>(
+    /// Each receipt hash should correspond to a block hash in the request.
+    pub Vec<Vec<T>>,
+);
+
+impl<T: alloy_rlp::Encodable> alloy_rlp::Encodable for Receipts69<T> {
+    #[inline]
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        self.0.encode(out)
+    }
+    #[inline]
+    fn length(&self) -> usize {
+        self.0.length()
+    }
+}
+
+impl<T: alloy_rlp::Decodable> alloy_rlp::Decodable for Receipts69<T> {
+    #[inline]
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        alloy_rlp::Decodable::decode(buf).map(Self)
+    }
+}
+
+impl<T: TxReceipt> Receipts69<T> {
+    /// Converts the receipts into [`Receipts`] by computing the bloom filter of every receipt.
+    pub fn into_with_bloom(self) -> Receipts<T> {
+        Receipts(
+            self.0
+                .into_iter()
+                .map(|receipts| {
+                    receipts.into_iter().map(|receipt| receipt.into_with_bloom()).collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<T> From<Receipts<T>> for Receipts69<T> {
+    fn from(receipts: Receipts<T>) -> Self {
+        Self(
+            receipts
+                .0
+                .into_iter()
+                .map(|receipts| receipts.into_iter().map(|receipt| receipt.receipt).collect())
+                .collect(),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{message::RequestPair, GetReceipts, Receipts};
+    use crate::{message::RequestPair, GetReceipts, Receipts, Receipts69};
     use alloy_consensus::TxType;
     use alloy_primitives::{hex, Log};
     use alloy_rlp::{Decodable, Encodable};
@@ -169,4 +227,47 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn encode_decode_receipts69() {
+        let data = hex!("f86d820457f868f866f864808001f85ff85d940000000000000000000000000000000000000011f842a0000000000000000000000000000000000000000000000000000000000000deada0000000000000000000000000000000000000000000000000000000000000beef830100ff");
+        let request = RequestPair {
+            request_id: 1111,
+            message: Receipts69(vec![vec![Receipt {
+                tx_type: TxType::Legacy,
+                cumulative_gas_used: 0x1u64,
+                logs: vec![Log::new_unchecked(
+                    hex!("0000000000000000000000000000000000000011").into(),
+                    vec![
+                        hex!("000000000000000000000000000000000000000000000000000000000000dead")
+                            .into(),
+                        hex!("000000000000000000000000000000000000000000000000000000000000beef")
+                            .into(),
+                    ],
+                    hex!("0100ff")[..].into(),
+                )],
+                success: false,
+            }]]),
+        };
+
+        let mut encoded = vec![];
+        request.encode(&mut encoded);
+        assert_eq!(encoded, data);
+
+        let decoded = RequestPair::<Receipts69>::decode(&mut &data[..]).unwrap();
+        assert_eq!(decoded, request);
+    }
+
+    #[test]
+    fn receipts69_bloom_roundtrip() {
+        let data = hex!("f90172820457f9016cf90169f901668001b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f85ff85d940000000000000000000000000000000000000011f842a0000000000000000000000000000000000000000000000000000000000000deada0000000000000000000000000000000000000000000000000000000000000beef830100ff");
+        let receipts = RequestPair::<Receipts>::decode(&mut &data[..]).unwrap().message;
+
+        let receipts69 = Receipts69::from(receipts.clone());
+        assert_eq!(receipts69.0[0][0], receipts.0[0][0].receipt);
+
+        // the bloom of the test vector doesn't match its logs, so compare against a recomputed one
+        let expected = receipts.0[0][0].receipt.clone().into_with_bloom();
+        assert_eq!(receipts69.into_with_bloom().0[0][0], expected);
+    }
 }
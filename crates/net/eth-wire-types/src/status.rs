@@ -1,7 +1,7 @@
-use crate::EthVersion;
+use crate::{BlockRangeUpdate, EthVersion};
 use alloy_chains::{Chain, NamedChain};
 use alloy_primitives::{hex, B256, U256};
-use alloy_rlp::{RlpDecodable, RlpEncodable};
+use alloy_rlp::{BufMut, Decodable, Encodable, RlpDecodable, RlpEncodable};
 use core::fmt::{Debug, Display};
 use reth_chainspec::{EthChainSpec, Hardforks, MAINNET};
 use reth_codecs_derive::add_arbitrary_tests;
@@ -213,8 +213,9 @@ impl StatusBuilder {
     }
 }
 
-/// Similar to [`Status`], but for `eth/69` version, which does not contain
-/// the `total_difficulty` field.
+/// Similar to [`Status`], but for `eth/69` version, which does not contain the
+/// `total_difficulty` field and advertises the range of blocks the peer can serve instead, see
+/// [EIP-7642](https://eips.ethereum.org/EIPS/eip-7642).
 #[derive(Copy, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
@@ -228,9 +229,6 @@ pub struct StatusEth69 {
     /// [EIP155](https://eips.ethereum.org/EIPS/eip-155#list-of-chain-ids).
     pub chain: Chain,
 
-    /// The highest difficulty block hash the peer has seen
-    pub blockhash: B256,
-
     /// The genesis hash of the peer's chain.
     pub genesis: B256,
 
@@ -240,6 +238,15 @@ pub struct StatusEth69 {
     /// [EIP-2124](https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2124.md).
     /// This was added in [`eth/64`](https://eips.ethereum.org/EIPS/eip-2364)
     pub forkid: ForkId,
+
+    /// The earliest block the peer can serve.
+    pub earliest: u64,
+
+    /// The latest block the peer can serve.
+    pub latest: u64,
+
+    /// The hash of the latest block.
+    pub blockhash: B256,
 }
 
 impl Display for StatusEth69 {
@@ -248,8 +255,14 @@ impl Display for StatusEth69 {
         let hexed_genesis = hex::encode(self.genesis);
         write!(
             f,
-            "Status {{ version: {}, chain: {}, blockhash: {}, genesis: {}, forkid: {:X?} }}",
-            self.version, self.chain, hexed_blockhash, hexed_genesis, self.forkid
+            "Status {{ version: {}, chain: {}, genesis: {}, forkid: {:X?}, earliest: {}, latest: {}, blockhash: {} }}",
+            self.version,
+            self.chain,
+            hexed_genesis,
+            self.forkid,
+            self.earliest,
+            self.latest,
+            hexed_blockhash,
         )
     }
 }
@@ -261,22 +274,26 @@ impl Debug for StatusEth69 {
         if f.alternate() {
             write!(
                 f,
-                "Status {{\n\tversion: {:?},\n\tchain: {:?},\n\tblockhash: {},\n\tgenesis: {},\n\tforkid: {:X?}\n}}",
+                "Status {{\n\tversion: {:?},\n\tchain: {:?},\n\tgenesis: {},\n\tforkid: {:X?},\n\tearliest: {},\n\tlatest: {},\n\tblockhash: {}\n}}",
                 self.version,
                 self.chain,
-                hexed_blockhash,
                 hexed_genesis,
-                self.forkid
+                self.forkid,
+                self.earliest,
+                self.latest,
+                hexed_blockhash,
             )
         } else {
             write!(
                 f,
-                "Status {{ version: {:?}, chain: {:?}, blockhash: {}, genesis: {}, forkid: {:X?} }}",
+                "Status {{ version: {:?}, chain: {:?}, genesis: {}, forkid: {:X?}, earliest: {}, latest: {}, blockhash: {} }}",
                 self.version,
                 self.chain,
-                hexed_blockhash,
                 hexed_genesis,
-                self.forkid
+                self.forkid,
+                self.earliest,
+                self.latest,
+                hexed_blockhash,
             )
         }
     }
@@ -285,25 +302,287 @@ impl Debug for StatusEth69 {
 // <https://etherscan.io/block/0>
 impl Default for StatusEth69 {
     fn default() -> Self {
-        Status::default().into()
-    }
-}
-
-impl From<Status> for StatusEth69 {
-    fn from(status: Status) -> Self {
+        let status = Status::default();
         Self {
             version: EthVersion::Eth69,
             chain: status.chain,
-            blockhash: status.blockhash,
             genesis: status.genesis,
             forkid: status.forkid,
+            earliest: 0,
+            latest: 0,
+            blockhash: status.blockhash,
         }
     }
 }
 
+/// The status message of the `eth` protocol, which depends on the negotiated version.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StatusMessage {
+    /// The status message of `eth/66`, `eth/67` and `eth/68`.
+    Legacy(Status),
+    /// The status message of `eth/69`.
+    Eth69(StatusEth69),
+}
+
+impl StatusMessage {
+    /// Decodes the status message of the given version.
+    pub fn decode_versioned(version: EthVersion, buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        if version.is_eth69() {
+            StatusEth69::decode(buf).map(Self::Eth69)
+        } else {
+            Status::decode(buf).map(Self::Legacy)
+        }
+    }
+
+    /// Returns the protocol version.
+    pub const fn version(&self) -> EthVersion {
+        match self {
+            Self::Legacy(status) => status.version,
+            Self::Eth69(status) => status.version,
+        }
+    }
+
+    /// Returns the chain id.
+    pub const fn chain(&self) -> &Chain {
+        match self {
+            Self::Legacy(status) => &status.chain,
+            Self::Eth69(status) => &status.chain,
+        }
+    }
+
+    /// Returns the genesis hash.
+    pub const fn genesis(&self) -> B256 {
+        match self {
+            Self::Legacy(status) => status.genesis,
+            Self::Eth69(status) => status.genesis,
+        }
+    }
+
+    /// Returns the fork id.
+    pub const fn forkid(&self) -> ForkId {
+        match self {
+            Self::Legacy(status) => status.forkid,
+            Self::Eth69(status) => status.forkid,
+        }
+    }
+
+    /// Returns the hash of the best block for `eth/68` and older, or of the latest block for
+    /// `eth/69`.
+    pub const fn blockhash(&self) -> B256 {
+        match self {
+            Self::Legacy(status) => status.blockhash,
+            Self::Eth69(status) => status.blockhash,
+        }
+    }
+
+    /// Returns the total difficulty, which is only part of the status before `eth/69`.
+    pub const fn total_difficulty(&self) -> Option<U256> {
+        match self {
+            Self::Legacy(status) => Some(status.total_difficulty),
+            Self::Eth69(_) => None,
+        }
+    }
+}
+
+impl Encodable for StatusMessage {
+    fn encode(&self, out: &mut dyn BufMut) {
+        match self {
+            Self::Legacy(status) => status.encode(out),
+            Self::Eth69(status) => status.encode(out),
+        }
+    }
+
+    fn length(&self) -> usize {
+        match self {
+            Self::Legacy(status) => status.length(),
+            Self::Eth69(status) => status.length(),
+        }
+    }
+}
+
+impl Display for StatusMessage {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Legacy(status) => Display::fmt(status, f),
+            Self::Eth69(status) => Display::fmt(status, f),
+        }
+    }
+}
+
+impl From<Status> for StatusMessage {
+    fn from(status: Status) -> Self {
+        Self::Legacy(status)
+    }
+}
+
+impl From<StatusEth69> for StatusMessage {
+    fn from(status: StatusEth69) -> Self {
+        Self::Eth69(status)
+    }
+}
+
+/// A status that holds the fields of all versions of the status message.
+///
+/// This is used to perform the handshake independent of the negotiated version: it is converted
+/// into the [`StatusMessage`] of the negotiated version before it is sent, see
+/// [`UnifiedStatus::into_message`]. Fields that are not part of the status message of a version are
+/// `None` if the status was received from a peer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnifiedStatus {
+    /// The current protocol version.
+    pub version: EthVersion,
+    /// The chain id.
+    pub chain: Chain,
+    /// The genesis hash of the chain.
+    pub genesis: B256,
+    /// The fork identifier.
+    pub forkid: ForkId,
+    /// The hash of the best block.
+    pub blockhash: B256,
+    /// Total difficulty of the best chain, only advertised before `eth/69`.
+    pub total_difficulty: Option<U256>,
+    /// The earliest block that can be served, only advertised since `eth/69`.
+    pub earliest_block: Option<u64>,
+    /// The number of the best block, only advertised since `eth/69`.
+    pub latest_block: Option<u64>,
+}
+
+impl UnifiedStatus {
+    /// Creates the status from the given [`EthChainSpec`], head block and the earliest block that
+    /// can be served.
+    pub fn spec_status<Spec>(spec: Spec, head: &Head, earliest_block: u64) -> Self
+    where
+        Spec: EthChainSpec + Hardforks,
+    {
+        Self::from(Status::spec_builder(spec, head).build())
+            .with_earliest_block(earliest_block)
+            .with_latest_block(head.number)
+    }
+
+    /// Sets the earliest block that can be served.
+    pub const fn with_earliest_block(mut self, earliest_block: u64) -> Self {
+        self.earliest_block = Some(earliest_block);
+        self
+    }
+
+    /// Sets the number of the best block.
+    pub const fn with_latest_block(mut self, latest_block: u64) -> Self {
+        self.latest_block = Some(latest_block);
+        self
+    }
+
+    /// Sets the [`EthVersion`] for the status.
+    pub fn set_eth_version(&mut self, version: EthVersion) {
+        self.version = version;
+    }
+
+    /// Sets the earliest block that can be served.
+    pub const fn set_earliest_block(&mut self, earliest_block: u64) {
+        self.earliest_block = Some(earliest_block);
+    }
+
+    /// Returns the range of blocks that can be served.
+    ///
+    /// Returns `None` if the range is unknown, i.e. the status was received before `eth/69`.
+    pub const fn block_range(&self) -> Option<BlockRangeUpdate> {
+        match (self.earliest_block, self.latest_block) {
+            (Some(earliest), Some(latest)) => {
+                Some(BlockRangeUpdate { earliest, latest, latest_hash: self.blockhash })
+            }
+            _ => None,
+        }
+    }
+
+    /// Sets the best block of the status from the given head.
+    pub fn set_head(&mut self, head: &Head) {
+        self.blockhash = head.hash;
+        self.total_difficulty = Some(head.total_difficulty);
+        self.latest_block = Some(head.number);
+    }
+
+    /// Creates the status from a received [`StatusMessage`].
+    pub const fn from_message(message: StatusMessage) -> Self {
+        match message {
+            StatusMessage::Legacy(status) => Self {
+                version: status.version,
+                chain: status.chain,
+                genesis: status.genesis,
+                forkid: status.forkid,
+                blockhash: status.blockhash,
+                total_difficulty: Some(status.total_difficulty),
+                earliest_block: None,
+                latest_block: None,
+            },
+            StatusMessage::Eth69(status) => Self {
+                version: status.version,
+                chain: status.chain,
+                genesis: status.genesis,
+                forkid: status.forkid,
+                blockhash: status.blockhash,
+                total_difficulty: None,
+                earliest_block: Some(status.earliest),
+                latest_block: Some(status.latest),
+            },
+        }
+    }
+
+    /// Converts the status into the [`StatusMessage`] of its version.
+    ///
+    /// Fields that are unknown default to zero.
+    pub fn into_message(self) -> StatusMessage {
+        if self.version.is_eth69() {
+            StatusMessage::Eth69(StatusEth69 {
+                version: self.version,
+                chain: self.chain,
+                genesis: self.genesis,
+                forkid: self.forkid,
+                earliest: self.earliest_block.unwrap_or_default(),
+                latest: self.latest_block.unwrap_or_default(),
+                blockhash: self.blockhash,
+            })
+        } else {
+            StatusMessage::Legacy(self.into_legacy())
+        }
+    }
+
+    /// Converts the status into a legacy [`Status`].
+    ///
+    /// The total difficulty defaults to zero if it is unknown.
+    pub fn into_legacy(self) -> Status {
+        Status {
+            version: self.version,
+            chain: self.chain,
+            total_difficulty: self.total_difficulty.unwrap_or_default(),
+            blockhash: self.blockhash,
+            genesis: self.genesis,
+            forkid: self.forkid,
+        }
+    }
+}
+
+impl Default for UnifiedStatus {
+    fn default() -> Self {
+        Status::default().into()
+    }
+}
+
+impl From<Status> for UnifiedStatus {
+    fn from(status: Status) -> Self {
+        Self::from_message(StatusMessage::Legacy(status))
+    }
+}
+
+impl Display for UnifiedStatus {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.into_message(), f)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{EthVersion, Status, StatusEth69};
+    use crate::{EthVersion, Status, StatusEth69, StatusMessage, UnifiedStatus};
     use alloy_consensus::constants::MAINNET_GENESIS_HASH;
     use alloy_genesis::Genesis;
     use alloy_primitives::{hex, B256, U256};
@@ -352,19 +631,21 @@ mod tests {
     }
 
     #[test]
-    fn test_status_to_statuseth69_conversion() {
+    fn test_unified_status_to_statuseth69_conversion() {
         let status = StatusEth69 {
             version: EthVersion::Eth69,
             chain: Chain::from_named(NamedChain::Mainnet),
+            genesis: MAINNET_GENESIS_HASH,
+            forkid: ForkId { hash: ForkHash([0xb7, 0x15, 0x07, 0x7d]), next: 0 },
+            earliest: 0,
+            latest: 18_000_000,
             blockhash: B256::from_str(
                 "feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13d",
             )
             .unwrap(),
-            genesis: MAINNET_GENESIS_HASH,
-            forkid: ForkId { hash: ForkHash([0xb7, 0x15, 0x07, 0x7d]), next: 0 },
         };
-        let status_converted: StatusEth69 = Status {
-            version: EthVersion::Eth69,
+        let mut unified = UnifiedStatus::from(Status {
+            version: EthVersion::Eth68,
             chain: Chain::from_named(NamedChain::Mainnet),
             total_difficulty: U256::from(36206751599115524359527u128),
             blockhash: B256::from_str(
@@ -373,59 +654,81 @@ mod tests {
             .unwrap(),
             genesis: MAINNET_GENESIS_HASH,
             forkid: ForkId { hash: ForkHash([0xb7, 0x15, 0x07, 0x7d]), next: 0 },
-        }
-        .into();
-        assert_eq!(status, status_converted);
+        })
+        .with_earliest_block(0)
+        .with_latest_block(18_000_000);
+        assert!(matches!(unified.into_message(), StatusMessage::Legacy(_)));
+
+        unified.set_eth_version(EthVersion::Eth69);
+        assert_eq!(unified.into_message(), StatusMessage::Eth69(status));
+
+        let received = UnifiedStatus::from_message(StatusMessage::Eth69(status));
+        assert_eq!(received.total_difficulty, None);
+        assert_eq!(received.latest_block, Some(18_000_000));
+        assert_eq!(received.into_message(), StatusMessage::Eth69(status));
     }
 
     #[test]
     fn encode_eth69_status_message() {
-        let expected = hex!("f84b4501a0feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13da0d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3c684b715077d80");
+        let expected = hex!("f8514501a0d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3c684b715077d8080840112a880a0feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13d");
         let status = StatusEth69 {
             version: EthVersion::Eth69,
             chain: Chain::from_named(NamedChain::Mainnet),
+            genesis: MAINNET_GENESIS_HASH,
+            forkid: ForkId { hash: ForkHash([0xb7, 0x15, 0x07, 0x7d]), next: 0 },
+            earliest: 0,
+            latest: 18_000_000,
             blockhash: B256::from_str(
                 "feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13d",
             )
             .unwrap(),
-            genesis: MAINNET_GENESIS_HASH,
-            forkid: ForkId { hash: ForkHash([0xb7, 0x15, 0x07, 0x7d]), next: 0 },
         };
 
         let mut rlp_status = vec![];
         status.encode(&mut rlp_status);
         assert_eq!(rlp_status, expected);
 
-        let status: StatusEth69 = Status::builder()
-            .chain(Chain::from_named(NamedChain::Mainnet))
-            .blockhash(
-                B256::from_str("feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13d")
+        let mut unified = UnifiedStatus::from(
+            Status::builder()
+                .chain(Chain::from_named(NamedChain::Mainnet))
+                .blockhash(
+                    B256::from_str(
+                        "feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13d",
+                    )
                     .unwrap(),
-            )
-            .genesis(MAINNET_GENESIS_HASH)
-            .forkid(ForkId { hash: ForkHash([0xb7, 0x15, 0x07, 0x7d]), next: 0 })
-            .build()
-            .into();
+                )
+                .genesis(MAINNET_GENESIS_HASH)
+                .forkid(ForkId { hash: ForkHash([0xb7, 0x15, 0x07, 0x7d]), next: 0 })
+                .build(),
+        )
+        .with_earliest_block(0)
+        .with_latest_block(18_000_000);
+        unified.set_eth_version(EthVersion::Eth69);
         let mut rlp_status = vec![];
-        status.encode(&mut rlp_status);
+        unified.into_message().encode(&mut rlp_status);
         assert_eq!(rlp_status, expected);
     }
 
     #[test]
     fn decode_eth69_status_message() {
-        let data = hex!("0xf84b4501a0feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13da0d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3c684b715077d80");
+        let data = hex!("0xf8514501a0d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3c684b715077d8080840112a880a0feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13d");
         let expected = StatusEth69 {
             version: EthVersion::Eth69,
             chain: Chain::from_named(NamedChain::Mainnet),
+            genesis: MAINNET_GENESIS_HASH,
+            forkid: ForkId { hash: ForkHash([0xb7, 0x15, 0x07, 0x7d]), next: 0 },
+            earliest: 0,
+            latest: 18_000_000,
             blockhash: B256::from_str(
                 "feb27336ca7923f8fab3bd617fcb6e75841538f71c1bcfc267d7838489d9e13d",
             )
             .unwrap(),
-            genesis: MAINNET_GENESIS_HASH,
-            forkid: ForkId { hash: ForkHash([0xb7, 0x15, 0x07, 0x7d]), next: 0 },
         };
         let status = StatusEth69::decode(&mut &data[..]).unwrap();
         assert_eq!(status, expected);
+
+        let status = StatusMessage::decode_versioned(EthVersion::Eth69, &mut &data[..]).unwrap();
+        assert_eq!(status, StatusMessage::Eth69(expected));
     }
 
     #[test]
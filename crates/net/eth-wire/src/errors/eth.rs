@@ -102,4 +102,12 @@ pub enum EthHandshakeError {
         /// The maximum allowed bit length for the total difficulty.
         maximum: usize,
     },
+    #[error("earliest block {earliest} is greater than latest block {latest}")]
+    /// The block range advertised in an `eth/69` status is invalid.
+    EarliestBlockGreaterThanLatestBlock {
+        /// The earliest block that can be served.
+        earliest: u64,
+        /// The latest block.
+        latest: u64,
+    },
 }
@@ -5,7 +5,7 @@ use crate::{
     message::{EthBroadcastMessage, ProtocolBroadcastMessage},
     p2pstream::HANDSHAKE_TIMEOUT,
     CanDisconnect, DisconnectReason, EthMessage, EthNetworkPrimitives, EthVersion, ProtocolMessage,
-    Status,
+    UnifiedStatus,
 };
 use alloy_primitives::bytes::{Bytes, BytesMut};
 use alloy_rlp::Encodable;
@@ -61,19 +61,19 @@ where
     /// remote peer.
     pub async fn handshake<N: NetworkPrimitives>(
         self,
-        status: Status,
+        status: UnifiedStatus,
         fork_filter: ForkFilter,
-    ) -> Result<(EthStream<S, N>, Status), EthStreamError> {
+    ) -> Result<(EthStream<S, N>, UnifiedStatus), EthStreamError> {
         self.handshake_with_timeout(status, fork_filter, HANDSHAKE_TIMEOUT).await
     }
 
     /// Wrapper around handshake which enforces a timeout.
     pub async fn handshake_with_timeout<N: NetworkPrimitives>(
         self,
-        status: Status,
+        status: UnifiedStatus,
         fork_filter: ForkFilter,
         timeout_limit: Duration,
-    ) -> Result<(EthStream<S, N>, Status), EthStreamError> {
+    ) -> Result<(EthStream<S, N>, UnifiedStatus), EthStreamError> {
         timeout(timeout_limit, Self::handshake_without_timeout(self, status, fork_filter))
             .await
             .map_err(|_| EthStreamError::StreamTimeout)?
@@ -82,20 +82,21 @@ where
     /// Handshake with no timeout
     pub async fn handshake_without_timeout<N: NetworkPrimitives>(
         mut self,
-        status: Status,
+        status: UnifiedStatus,
         fork_filter: ForkFilter,
-    ) -> Result<(EthStream<S, N>, Status), EthStreamError> {
+    ) -> Result<(EthStream<S, N>, UnifiedStatus), EthStreamError> {
         trace!(
             %status,
             "sending eth status to peer"
         );
-        EthereumEthHandshake(&mut self.inner).eth_handshake(status, fork_filter).await?;
+        let their_status =
+            EthereumEthHandshake(&mut self.inner).eth_handshake(status, fork_filter).await?;
 
         // now we can create the `EthStream` because the peer has successfully completed
         // the handshake
         let stream = EthStream::new(status.version, self.inner);
 
-        Ok((stream, status))
+        Ok((stream, their_status))
     }
 }
 
@@ -288,7 +289,7 @@ mod tests {
         hello::DEFAULT_TCP_PORT,
         p2pstream::UnauthedP2PStream,
         EthMessage, EthStream, EthVersion, HelloMessageWithProtocols, PassthroughCodec,
-        ProtocolVersion, Status,
+        ProtocolVersion, Status, UnifiedStatus,
     };
     use alloy_chains::NamedChain;
     use alloy_primitives::{bytes::Bytes, B256, U256};
@@ -308,7 +309,7 @@ mod tests {
         let genesis = B256::random();
         let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
 
-        let status = Status {
+        let status = UnifiedStatus::from(Status {
             version: EthVersion::Eth67,
             chain: NamedChain::Mainnet.into(),
             total_difficulty: U256::ZERO,
@@ -316,7 +317,7 @@ mod tests {
             genesis,
             // Pass the current fork id.
             forkid: fork_filter.current(),
-        };
+        });
 
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let local_addr = listener.local_addr().unwrap();
@@ -352,12 +353,119 @@ mod tests {
         handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn can_handshake_eth69() {
+        let genesis = B256::random();
+        let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
+
+        let status = UnifiedStatus {
+            version: EthVersion::Eth69,
+            chain: NamedChain::Mainnet.into(),
+            genesis,
+            // Pass the current fork id.
+            forkid: fork_filter.current(),
+            blockhash: B256::random(),
+            total_difficulty: None,
+            earliest_block: Some(0),
+            latest_block: Some(100),
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let status_clone = status;
+        let fork_filter_clone = fork_filter.clone();
+        let handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = PassthroughCodec::default().framed(incoming);
+            let (_, their_status) = UnauthedEthStream::new(stream)
+                .handshake::<EthNetworkPrimitives>(status_clone, fork_filter_clone)
+                .await
+                .unwrap();
+
+            assert_eq!(their_status, status_clone);
+        });
+
+        let outgoing = TcpStream::connect(local_addr).await.unwrap();
+        let sink = PassthroughCodec::default().framed(outgoing);
+
+        let (stream, their_status) = UnauthedEthStream::new(sink)
+            .handshake::<EthNetworkPrimitives>(status, fork_filter)
+            .await
+            .unwrap();
+
+        // the block range is exchanged instead of the total difficulty
+        assert_eq!(their_status, status);
+        assert_eq!(stream.version(), EthVersion::Eth69);
+
+        handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn fail_handshake_on_invalid_block_range() {
+        let genesis = B256::random();
+        let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
+
+        let status = UnifiedStatus {
+            version: EthVersion::Eth69,
+            chain: NamedChain::Mainnet.into(),
+            genesis,
+            // Pass the current fork id.
+            forkid: fork_filter.current(),
+            blockhash: B256::random(),
+            total_difficulty: None,
+            earliest_block: Some(100),
+            latest_block: Some(10),
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let fork_filter_clone = fork_filter.clone();
+        let handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = PassthroughCodec::default().framed(incoming);
+            let handshake_res = UnauthedEthStream::new(stream)
+                .handshake::<EthNetworkPrimitives>(status, fork_filter_clone)
+                .await;
+
+            assert!(matches!(
+                handshake_res,
+                Err(EthStreamError::EthHandshakeError(
+                    EthHandshakeError::EarliestBlockGreaterThanLatestBlock {
+                        earliest: 100,
+                        latest: 10
+                    }
+                ))
+            ));
+        });
+
+        let outgoing = TcpStream::connect(local_addr).await.unwrap();
+        let sink = PassthroughCodec::default().framed(outgoing);
+
+        let handshake_res = UnauthedEthStream::new(sink)
+            .handshake::<EthNetworkPrimitives>(status, fork_filter)
+            .await;
+
+        assert!(matches!(
+            handshake_res,
+            Err(EthStreamError::EthHandshakeError(
+                EthHandshakeError::EarliestBlockGreaterThanLatestBlock {
+                    earliest: 100,
+                    latest: 10
+                }
+            ))
+        ));
+
+        handle.await.unwrap();
+    }
+
     #[tokio::test]
     async fn pass_handshake_on_low_td_bitlen() {
         let genesis = B256::random();
         let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
 
-        let status = Status {
+        let status = UnifiedStatus::from(Status {
             version: EthVersion::Eth67,
             chain: NamedChain::Mainnet.into(),
             total_difficulty: U256::from(2).pow(U256::from(100)) - U256::from(1),
@@ -365,7 +473,7 @@ mod tests {
             genesis,
             // Pass the current fork id.
             forkid: fork_filter.current(),
-        };
+        });
 
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let local_addr = listener.local_addr().unwrap();
@@ -406,7 +514,7 @@ mod tests {
         let genesis = B256::random();
         let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
 
-        let status = Status {
+        let status = UnifiedStatus::from(Status {
             version: EthVersion::Eth67,
             chain: NamedChain::Mainnet.into(),
             total_difficulty: U256::from(2).pow(U256::from(164)),
@@ -414,7 +522,7 @@ mod tests {
             genesis,
             // Pass the current fork id.
             forkid: fork_filter.current(),
-        };
+        });
 
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let local_addr = listener.local_addr().unwrap();
@@ -550,7 +658,7 @@ mod tests {
         let genesis = B256::random();
         let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
 
-        let status = Status {
+        let status = UnifiedStatus::from(Status {
             version: EthVersion::Eth67,
             chain: NamedChain::Mainnet.into(),
             total_difficulty: U256::ZERO,
@@ -558,7 +666,7 @@ mod tests {
             genesis,
             // Pass the current fork id.
             forkid: fork_filter.current(),
-        };
+        });
 
         let status_copy = status;
         let fork_filter_clone = fork_filter.clone();
@@ -621,7 +729,7 @@ mod tests {
         let genesis = B256::random();
         let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
 
-        let status = Status {
+        let status = UnifiedStatus::from(Status {
             version: EthVersion::Eth67,
             chain: NamedChain::Mainnet.into(),
             total_difficulty: U256::ZERO,
@@ -629,7 +737,7 @@ mod tests {
             genesis,
             // Pass the current fork id.
             forkid: fork_filter.current(),
-        };
+        });
 
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let local_addr = listener.local_addr().unwrap();
@@ -7,7 +7,8 @@ use bytes::{Bytes, BytesMut};
 use derive_more::with_trait::Debug;
 use futures::{Sink, SinkExt, Stream};
 use reth_eth_wire_types::{
-    DisconnectReason, EthMessage, EthNetworkPrimitives, ProtocolMessage, Status,
+    DisconnectReason, EthMessage, EthNetworkPrimitives, ProtocolMessage, StatusMessage,
+    UnifiedStatus,
 };
 use reth_ethereum_forks::ForkFilter;
 use reth_primitives_traits::GotExpected;
@@ -22,10 +23,10 @@ pub trait EthRlpxHandshake: Debug + Send + Sync + 'static {
     fn handshake<'a>(
         &'a self,
         unauth: &'a mut dyn UnauthEth,
-        status: Status,
+        status: UnifiedStatus,
         fork_filter: ForkFilter,
         timeout_limit: Duration,
-    ) -> Pin<Box<dyn Future<Output = Result<Status, EthStreamError>> + 'a + Send>>;
+    ) -> Pin<Box<dyn Future<Output = Result<UnifiedStatus, EthStreamError>> + 'a + Send>>;
 }
 
 /// An unauthenticated stream that can send and receive messages.
@@ -58,10 +59,10 @@ impl EthRlpxHandshake for EthHandshake {
     fn handshake<'a>(
         &'a self,
         unauth: &'a mut dyn UnauthEth,
-        status: Status,
+        status: UnifiedStatus,
         fork_filter: ForkFilter,
         timeout_limit: Duration,
-    ) -> Pin<Box<dyn Future<Output = Result<Status, EthStreamError>> + 'a + Send>> {
+    ) -> Pin<Box<dyn Future<Output = Result<UnifiedStatus, EthStreamError>> + 'a + Send>> {
         Box::pin(async move {
             timeout(timeout_limit, EthereumEthHandshake(unauth).eth_handshake(status, fork_filter))
                 .await
@@ -80,18 +81,20 @@ where
     EthStreamError: From<E> + From<<S as Sink<Bytes>>::Error>,
 {
     /// Performs the `eth` rlpx protocol handshake using the given input stream.
+    ///
+    /// The status message that is sent depends on the version of the given status.
     pub async fn eth_handshake(
         self,
-        status: Status,
+        status: UnifiedStatus,
         fork_filter: ForkFilter,
-    ) -> Result<Status, EthStreamError> {
+    ) -> Result<UnifiedStatus, EthStreamError> {
         let unauth = self.0;
         // Send our status message
         let status_msg =
             alloy_rlp::encode(ProtocolMessage::<EthNetworkPrimitives>::from(EthMessage::<
                 EthNetworkPrimitives,
             >::Status(
-                status
+                status.into_message(),
             )))
             .into();
         unauth.send(status_msg).await.map_err(EthStreamError::from)?;
@@ -139,57 +142,76 @@ where
             EthMessage::Status(their_status) => {
                 trace!("Validating incoming ETH status from peer");
 
-                if status.genesis != their_status.genesis {
+                if status.genesis != their_status.genesis() {
                     unauth
                         .disconnect(DisconnectReason::ProtocolBreach)
                         .await
                         .map_err(EthStreamError::from)?;
                     return Err(EthHandshakeError::MismatchedGenesis(
-                        GotExpected { expected: status.genesis, got: their_status.genesis }.into(),
+                        GotExpected { expected: status.genesis, got: their_status.genesis() }
+                            .into(),
                     )
                     .into());
                 }
 
-                if status.version != their_status.version {
+                if status.version != their_status.version() {
                     unauth
                         .disconnect(DisconnectReason::ProtocolBreach)
                         .await
                         .map_err(EthStreamError::from)?;
                     return Err(EthHandshakeError::MismatchedProtocolVersion(GotExpected {
-                        got: their_status.version,
+                        got: their_status.version(),
                         expected: status.version,
                     })
                     .into());
                 }
 
-                if status.chain != their_status.chain {
+                if status.chain != *their_status.chain() {
                     unauth
                         .disconnect(DisconnectReason::ProtocolBreach)
                         .await
                         .map_err(EthStreamError::from)?;
                     return Err(EthHandshakeError::MismatchedChain(GotExpected {
-                        got: their_status.chain,
+                        got: *their_status.chain(),
                         expected: status.chain,
                     })
                     .into());
                 }
 
-                // Ensure total difficulty is reasonable
-                if status.total_difficulty.bit_len() > 160 {
-                    unauth
-                        .disconnect(DisconnectReason::ProtocolBreach)
-                        .await
-                        .map_err(EthStreamError::from)?;
-                    return Err(EthHandshakeError::TotalDifficultyBitLenTooLarge {
-                        got: status.total_difficulty.bit_len(),
-                        maximum: 160,
+                match their_status {
+                    StatusMessage::Legacy(their_status) => {
+                        // Ensure total difficulty is reasonable
+                        if their_status.total_difficulty.bit_len() > 160 {
+                            unauth
+                                .disconnect(DisconnectReason::ProtocolBreach)
+                                .await
+                                .map_err(EthStreamError::from)?;
+                            return Err(EthHandshakeError::TotalDifficultyBitLenTooLarge {
+                                got: their_status.total_difficulty.bit_len(),
+                                maximum: 160,
+                            }
+                            .into());
+                        }
+                    }
+                    StatusMessage::Eth69(their_status) => {
+                        // Ensure the advertised block range is valid
+                        if their_status.earliest > their_status.latest {
+                            unauth
+                                .disconnect(DisconnectReason::ProtocolBreach)
+                                .await
+                                .map_err(EthStreamError::from)?;
+                            return Err(EthHandshakeError::EarliestBlockGreaterThanLatestBlock {
+                                earliest: their_status.earliest,
+                                latest: their_status.latest,
+                            }
+                            .into());
+                        }
                     }
-                    .into());
                 }
 
                 // Fork validation
                 if let Err(err) = fork_filter
-                    .validate(their_status.forkid)
+                    .validate(their_status.forkid())
                     .map_err(EthHandshakeError::InvalidFork)
                 {
                     unauth
@@ -199,7 +221,7 @@ where
                     return Err(err.into());
                 }
 
-                Ok(their_status)
+                Ok(UnifiedStatus::from_message(their_status))
             }
             _ => {
                 unauth
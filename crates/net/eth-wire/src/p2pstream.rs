@@ -301,6 +301,14 @@ impl<S> P2PStream<S> {
         self.outgoing_messages.len() < self.outgoing_message_buffer_capacity
     }
 
+    /// Returns the round trip time of the last [`P2PMessage::Ping`] the peer responded to.
+    ///
+    /// The peer is pinged periodically, so this is a measure of the peer's latency even if no
+    /// requests are exchanged. Returns `None` if the peer didn't respond to a ping yet.
+    pub const fn ping_latency(&self) -> Option<Duration> {
+        self.pinger.rtt()
+    }
+
     /// Queues in a _snappy_ encoded [`P2PMessage::Pong`] message.
     fn send_pong(&mut self) {
         self.outgoing_messages.push_back(Bytes::from(alloy_rlp::encode(P2PMessage::Pong)));
@@ -19,6 +19,10 @@ pub(crate) struct Pinger {
     timeout: Duration,
     /// Keeps track of the state
     state: PingState,
+    /// When the last ping was sent.
+    ping_sent: Instant,
+    /// Round trip time of the last answered ping, if any.
+    rtt: Option<Duration>,
 }
 
 // === impl Pinger ===
@@ -34,6 +38,8 @@ impl Pinger {
             ping_interval: tokio::time::interval_at(now + ping_interval, ping_interval),
             timeout_timer: Box::pin(timeout_timer),
             timeout: timeout_duration,
+            ping_sent: now,
+            rtt: None,
         }
     }
 
@@ -44,6 +50,7 @@ impl Pinger {
             PingState::Ready => Err(PingerError::UnexpectedPong),
             PingState::WaitingForPong => {
                 self.state = PingState::Ready;
+                self.rtt = Some(self.ping_sent.elapsed());
                 self.ping_interval.reset();
                 Ok(())
             }
@@ -51,6 +58,7 @@ impl Pinger {
                 // if we receive a pong after timeout then we also reset the state, since the
                 // connection was kept alive after timeout
                 self.state = PingState::Ready;
+                self.rtt = Some(self.ping_sent.elapsed());
                 self.ping_interval.reset();
                 Ok(())
             }
@@ -62,6 +70,11 @@ impl Pinger {
         self.state
     }
 
+    /// Returns the round trip time of the last answered ping, if any.
+    pub(crate) const fn rtt(&self) -> Option<Duration> {
+        self.rtt
+    }
+
     /// Polls the state of the pinger and returns whether a new ping needs to be sent or if a
     /// previous ping timed out.
     pub(crate) fn poll_ping(
@@ -71,7 +84,8 @@ impl Pinger {
         match self.state() {
             PingState::Ready => {
                 if self.ping_interval.poll_tick(cx).is_ready() {
-                    self.timeout_timer.as_mut().reset(Instant::now() + self.timeout);
+                    self.ping_sent = Instant::now();
+                    self.timeout_timer.as_mut().reset(self.ping_sent + self.timeout);
                     self.state = PingState::WaitingForPong;
                     return Poll::Ready(Ok(PingerEvent::Ping))
                 }
@@ -135,7 +149,9 @@ mod tests {
         // we should wait for the interval to elapse and receive a pong before the timeout elapses
         let mut pinger = Pinger::new(interval, Duration::from_millis(20));
         assert_eq!(pinger.next().await.unwrap().unwrap(), PingerEvent::Ping);
+        assert!(pinger.rtt().is_none());
         pinger.on_pong().unwrap();
+        assert!(pinger.rtt().is_some());
         assert_eq!(pinger.next().await.unwrap().unwrap(), PingerEvent::Ping);
 
         tokio::time::sleep(interval).await;
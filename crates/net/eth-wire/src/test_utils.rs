@@ -4,7 +4,7 @@
 
 use crate::{
     hello::DEFAULT_TCP_PORT, EthVersion, HelloMessageWithProtocols, P2PStream, ProtocolVersion,
-    Status, UnauthedP2PStream,
+    Status, UnauthedP2PStream, UnifiedStatus,
 };
 use alloy_chains::Chain;
 use alloy_primitives::{B256, U256};
@@ -32,11 +32,11 @@ pub fn eth_hello() -> (HelloMessageWithProtocols, SecretKey) {
 }
 
 /// Returns testing eth handshake status and fork filter.
-pub fn eth_handshake() -> (Status, ForkFilter) {
+pub fn eth_handshake() -> (UnifiedStatus, ForkFilter) {
     let genesis = B256::random();
     let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
 
-    let status = Status {
+    let status = UnifiedStatus::from(Status {
         version: EthVersion::Eth67,
         chain: Chain::mainnet(),
         total_difficulty: U256::ZERO,
@@ -44,7 +44,7 @@ pub fn eth_handshake() -> (Status, ForkFilter) {
         genesis,
         // Pass the current fork id.
         forkid: fork_filter.current(),
-    };
+    });
     (status, fork_filter)
 }
 
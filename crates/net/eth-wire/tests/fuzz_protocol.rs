@@ -0,0 +1,351 @@
+//! Fuzz targets for decoding untrusted `eth-wire` input and for the `p2p` and `eth` handshakes.
+//!
+//! The targets are run with their seed inputs as regular tests, and can be fuzzed with
+//! `cargo test-fuzz`, e.g. `cargo test-fuzz -p reth-eth-wire --test fuzz_protocol
+//! fuzz_decode_eth_message`.
+
+use alloy_chains::Chain;
+use alloy_primitives::{b256, hex, B256, B512, U256};
+use alloy_rlp::{Decodable, Encodable};
+use arbitrary::Unstructured;
+use bytes::{Bytes, BytesMut};
+use futures::{Sink, Stream};
+use reth_eth_wire::{
+    errors::P2PStreamError, handshake::EthereumEthHandshake, BlockBodies, BlockHeaders,
+    CanDisconnect, DisconnectReason, EthMessage, EthNetworkPrimitives, EthVersion, GetBlockBodies,
+    GetBlockHeaders, GetPooledTransactions, GetReceipts, HelloMessageWithProtocols, NewBlockHashes,
+    P2PMessage, ProtocolMessage, ProtocolVersion, Receipts, RequestPair, Status, Transactions,
+    UnauthedP2PStream, UnifiedStatus,
+};
+use reth_ethereum_forks::{ForkFilter, ForkFilterKey, ForkHash, ForkId, Head};
+use std::{
+    collections::VecDeque,
+    fs,
+    future::Future,
+    io,
+    marker::PhantomData,
+    path::PathBuf,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use test_fuzz::test_fuzz;
+
+/// Mainnet genesis hash.
+const MAINNET_GENESIS: B256 =
+    b256!("0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3");
+
+/// The `eth/68` status message of [`mainnet_status`], encoded with its message id.
+const MAINNET_STATUS_RLP: &str = "00f8544401850400000000a0d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3a0d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3c984fc64ec0483118c30";
+
+/// Returns the fork filter of mainnet at genesis, only aware of the Homestead fork.
+fn mainnet_fork_filter() -> ForkFilter {
+    ForkFilter::new(Head::default(), MAINNET_GENESIS, 0, vec![ForkFilterKey::Block(1_150_000)])
+}
+
+/// Returns the `eth/68` status of mainnet at genesis.
+fn mainnet_status() -> UnifiedStatus {
+    UnifiedStatus::from(Status {
+        version: EthVersion::Eth68,
+        chain: Chain::mainnet(),
+        total_difficulty: U256::from(0x400000000u64),
+        blockhash: MAINNET_GENESIS,
+        genesis: MAINNET_GENESIS,
+        forkid: mainnet_fork_filter().current(),
+    })
+}
+
+/// Returns a `p2p` hello message with a fixed peer id.
+fn hello() -> HelloMessageWithProtocols {
+    HelloMessageWithProtocols {
+        protocol_version: ProtocolVersion::V5,
+        client_version: "reth/fuzz".to_string(),
+        protocols: vec![EthVersion::Eth68.into()],
+        port: 30303,
+        id: B512::with_last_byte(1),
+    }
+}
+
+/// Reads a hex encoded test vector from the `testdata` directory.
+fn test_vector(name: &str) -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("testdata").join(name);
+    let data = fs::read_to_string(path).expect("Unable to read file");
+    hex::decode(data.trim()).unwrap()
+}
+
+/// An in-memory stream that yields the given incoming messages and records the outgoing ones.
+///
+/// The stream ends after the incoming messages, like a peer that closed the connection.
+#[derive(Debug)]
+struct MockStream<E> {
+    incoming: VecDeque<BytesMut>,
+    outgoing: Vec<Bytes>,
+    disconnected: Option<DisconnectReason>,
+    _error: PhantomData<fn() -> E>,
+}
+
+impl<E> MockStream<E> {
+    fn new(incoming: impl IntoIterator<Item = Vec<u8>>) -> Self {
+        Self {
+            incoming: incoming.into_iter().map(|msg| BytesMut::from(&msg[..])).collect(),
+            outgoing: Vec::new(),
+            disconnected: None,
+            _error: PhantomData,
+        }
+    }
+}
+
+impl<E> Stream for MockStream<E> {
+    type Item = Result<BytesMut, E>;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.get_mut().incoming.pop_front().map(Ok))
+    }
+}
+
+impl<E> Sink<Bytes> for MockStream<E> {
+    type Error = E;
+
+    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Bytes) -> Result<(), Self::Error> {
+        self.get_mut().outgoing.push(item);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<E> CanDisconnect<Bytes> for MockStream<E> {
+    fn disconnect(
+        &mut self,
+        reason: DisconnectReason,
+    ) -> Pin<Box<dyn Future<Output = Result<(), E>> + Send + '_>> {
+        self.disconnected = Some(reason);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Runs the future to completion on a new runtime.
+fn block_on<F: Future>(fut: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap().block_on(fut)
+}
+
+/// Generates an arbitrary `eth/68` message from the given raw fuzzer input.
+fn arbitrary_eth_message(
+    u: &mut Unstructured<'_>,
+) -> arbitrary::Result<EthMessage<EthNetworkPrimitives>> {
+    Ok(match u.int_in_range(0..=9)? {
+        0 => EthMessage::Status(u.arbitrary::<Status>()?.into()),
+        1 => EthMessage::NewBlockHashes(u.arbitrary::<NewBlockHashes>()?),
+        2 => EthMessage::Transactions(u.arbitrary::<Transactions>()?),
+        3 => EthMessage::GetBlockHeaders(u.arbitrary::<RequestPair<GetBlockHeaders>>()?),
+        4 => EthMessage::BlockHeaders(u.arbitrary::<RequestPair<BlockHeaders>>()?),
+        5 => EthMessage::GetBlockBodies(u.arbitrary::<RequestPair<GetBlockBodies>>()?),
+        6 => EthMessage::BlockBodies(u.arbitrary::<RequestPair<BlockBodies>>()?),
+        7 => {
+            EthMessage::GetPooledTransactions(u.arbitrary::<RequestPair<GetPooledTransactions>>()?)
+        }
+        8 => EthMessage::GetReceipts(u.arbitrary::<RequestPair<GetReceipts>>()?),
+        _ => EthMessage::Receipts(u.arbitrary::<RequestPair<Receipts>>()?),
+    })
+}
+
+/// Decodes arbitrary bytes as an `eth` message of any version.
+///
+/// Decoding must never panic, and a decoded message must decode to itself once re-encoded.
+#[test_fuzz]
+fn fuzz_decode_eth_message(version: u8, data: Vec<u8>) {
+    let Ok(version) = EthVersion::try_from(version) else { return };
+    let Ok(msg) = ProtocolMessage::<EthNetworkPrimitives>::decode_message(version, &mut &data[..])
+    else {
+        return
+    };
+
+    let encoded = alloy_rlp::encode(&msg);
+    assert_eq!(encoded.len(), msg.length());
+    let decoded = ProtocolMessage::decode_message(version, &mut &encoded[..]).unwrap();
+    assert_eq!(msg, decoded);
+}
+
+/// Decodes arbitrary bytes as a `p2p` message.
+///
+/// Decoding must never panic, and a decoded message must decode to itself once re-encoded.
+#[test_fuzz]
+fn fuzz_decode_p2p_message(data: Vec<u8>) {
+    let Ok(msg) = P2PMessage::decode(&mut &data[..]) else { return };
+
+    let encoded = alloy_rlp::encode(&msg);
+    assert_eq!(P2PMessage::decode(&mut &encoded[..]).unwrap(), msg);
+}
+
+/// Generates a valid `eth/68` message, which must survive a round trip through the decoder, while
+/// every truncation of its encoding must be rejected.
+#[test_fuzz]
+fn fuzz_generated_eth_message(seed: Vec<u8>) {
+    let Ok(message) = arbitrary_eth_message(&mut Unstructured::new(&seed)) else { return };
+    let msg = ProtocolMessage::<EthNetworkPrimitives>::from(message);
+
+    let encoded = alloy_rlp::encode(&msg);
+    let decoded = ProtocolMessage::decode_message(EthVersion::Eth68, &mut &encoded[..]).unwrap();
+    assert_eq!(msg, decoded);
+
+    for len in 0..encoded.len() {
+        assert!(
+            ProtocolMessage::<EthNetworkPrimitives>::decode_message(
+                EthVersion::Eth68,
+                &mut &encoded[..len]
+            )
+            .is_err(),
+            "decoded truncated message: {msg:?}"
+        );
+    }
+}
+
+/// Runs the `p2p` hello handshake against a peer responding with the given messages.
+///
+/// The handshake must never panic, and must only succeed for a hello with a compatible protocol
+/// version and shared capabilities.
+#[test_fuzz]
+fn fuzz_p2p_handshake(responses: Vec<Vec<u8>>) {
+    let stream = MockStream::<io::Error>::new(responses.clone());
+    let hello = hello();
+
+    let Ok((p2p_stream, their_hello)) =
+        block_on(UnauthedP2PStream::new(stream).handshake(hello.clone()))
+    else {
+        return
+    };
+
+    let first = P2PMessage::decode(&mut &responses[0][..]).unwrap();
+    assert_eq!(first, P2PMessage::Hello(their_hello.clone()));
+    assert_eq!(their_hello.protocol_version, hello.protocol_version);
+    assert!(p2p_stream.shared_capabilities().eth_version().is_ok());
+    // our hello is the first message on the wire
+    assert_eq!(
+        p2p_stream.inner().outgoing[0],
+        alloy_rlp::encode(P2PMessage::Hello(hello.message()))
+    );
+}
+
+/// Runs the `eth` status handshake of mainnet at genesis against a peer responding with the given
+/// messages.
+///
+/// The handshake must never panic, must send the known-good encoding of our status, and must only
+/// succeed for a status of the same network.
+#[test_fuzz]
+fn fuzz_eth_handshake(responses: Vec<Vec<u8>>) {
+    let mut stream = MockStream::<P2PStreamError>::new(responses);
+    let status = mainnet_status();
+
+    let result =
+        block_on(EthereumEthHandshake(&mut stream).eth_handshake(status, mainnet_fork_filter()));
+
+    assert_eq!(stream.outgoing[0][..], hex::decode(MAINNET_STATUS_RLP).unwrap());
+    match result {
+        Ok(their_status) => {
+            assert!(stream.disconnected.is_none());
+            assert_eq!(their_status.version, status.version);
+            assert_eq!(their_status.chain, status.chain);
+            assert_eq!(their_status.genesis, status.genesis);
+        }
+        // the peer is disconnected on every failure
+        Err(_) => assert!(stream.disconnected.is_some()),
+    }
+}
+
+#[test]
+fn fork_id_matches_eip2124() {
+    // <https://eips.ethereum.org/EIPS/eip-2124#test-cases>
+    assert_eq!(
+        mainnet_fork_filter().current(),
+        ForkId { hash: ForkHash(hex!("fc64ec04")), next: 1_150_000 }
+    );
+}
+
+#[test]
+fn decode_eth_message_vectors() {
+    for vector in ["new_block_network_rlp", "new_pooled_transactions_network_rlp"] {
+        let data = test_vector(vector);
+        // the vectors are payloads without message id
+        let id = if vector.starts_with("new_block") { 0x07 } else { 0x08 };
+        let msg = [&[id][..], &data].concat();
+        for version in [EthVersion::Eth66, EthVersion::Eth67, EthVersion::Eth68] {
+            fuzz_decode_eth_message(version as u8, msg.clone());
+        }
+    }
+
+    // known-good vectors must be re-encoded byte for byte
+    let data = test_vector("request_pair_pooled_blob_transactions");
+    let msg =
+        ProtocolMessage::<EthNetworkPrimitives>::decode_message(EthVersion::Eth68, &mut &data[..])
+            .unwrap();
+    assert_eq!(alloy_rlp::encode(&msg), data);
+    fuzz_decode_eth_message(EthVersion::Eth68 as u8, data);
+
+    let status = hex::decode(MAINNET_STATUS_RLP).unwrap();
+    let msg = ProtocolMessage::<EthNetworkPrimitives>::decode_message(
+        EthVersion::Eth68,
+        &mut &status[..],
+    )
+    .unwrap();
+    assert_eq!(msg, ProtocolMessage::from(EthMessage::Status(mainnet_status().into_message())));
+    fuzz_decode_eth_message(EthVersion::Eth68 as u8, status);
+
+    fuzz_decode_eth_message(EthVersion::Eth68 as u8, Vec::new());
+    fuzz_decode_eth_message(EthVersion::Eth68 as u8, vec![0x00, 0xc0]);
+    fuzz_decode_eth_message(0xff, vec![0x00]);
+}
+
+#[test]
+fn decode_p2p_message_vectors() {
+    fuzz_decode_p2p_message(alloy_rlp::encode(P2PMessage::Hello(hello().message())));
+    fuzz_decode_p2p_message(alloy_rlp::encode(P2PMessage::Disconnect(
+        DisconnectReason::TooManyPeers,
+    )));
+    fuzz_decode_p2p_message(alloy_rlp::encode(P2PMessage::Ping));
+    fuzz_decode_p2p_message(vec![0x01, 0xc0]);
+    fuzz_decode_p2p_message(Vec::new());
+}
+
+#[test]
+fn generated_eth_messages() {
+    for seed in [vec![], vec![0; 64], (0..=255).collect(), vec![0xff; 512]] {
+        fuzz_generated_eth_message(seed);
+    }
+}
+
+#[test]
+fn p2p_handshake_vectors() {
+    let hello = alloy_rlp::encode(P2PMessage::Hello(hello().message()));
+    fuzz_p2p_handshake(vec![hello.clone()]);
+    fuzz_p2p_handshake(vec![hello[..hello.len() - 1].to_vec()]);
+    fuzz_p2p_handshake(vec![alloy_rlp::encode(P2PMessage::Disconnect(
+        DisconnectReason::UselessPeer,
+    ))]);
+    fuzz_p2p_handshake(vec![alloy_rlp::encode(P2PMessage::Ping)]);
+    fuzz_p2p_handshake(vec![]);
+}
+
+#[test]
+fn eth_handshake_vectors() {
+    let status = hex::decode(MAINNET_STATUS_RLP).unwrap();
+    fuzz_eth_handshake(vec![status.clone()]);
+    fuzz_eth_handshake(vec![status[..status.len() - 1].to_vec()]);
+    fuzz_eth_handshake(vec![[&[0x01][..], &status[1..]].concat()]);
+    fuzz_eth_handshake(vec![]);
+
+    // a status of another network
+    let mut other = mainnet_status();
+    other.chain = Chain::sepolia();
+    fuzz_eth_handshake(vec![alloy_rlp::encode(ProtocolMessage::<EthNetworkPrimitives>::from(
+        EthMessage::Status(other.into_message()),
+    ))]);
+}
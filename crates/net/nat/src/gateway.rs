@@ -0,0 +1,128 @@
+//! Default gateway lookup used by the port mapping protocols.
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+
+/// Path of the kernel routing table on Linux.
+#[cfg(target_os = "linux")]
+const PROC_NET_ROUTE: &str = "/proc/net/route";
+
+/// Returns the IPv4 address of the default gateway, if it can be determined.
+///
+/// This reads the kernel routing table on Linux and queries the default route with
+/// `route -n get default` on macOS and the BSDs.
+#[cfg(target_os = "linux")]
+pub fn default_gateway() -> Option<Ipv4Addr> {
+    std::fs::read_to_string(PROC_NET_ROUTE).ok().and_then(|table| parse_route_table(&table))
+}
+
+/// Returns the IPv4 address of the default gateway, if it can be determined.
+///
+/// This reads the kernel routing table on Linux and queries the default route with
+/// `route -n get default` on macOS and the BSDs.
+#[cfg(any(
+    target_os = "macos",
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd"
+))]
+pub fn default_gateway() -> Option<Ipv4Addr> {
+    let output =
+        std::process::Command::new("route").args(["-n", "get", "default"]).output().ok()?;
+    parse_route_get(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Returns the IPv4 address of the default gateway, if it can be determined.
+///
+/// The default route can't be looked up on this platform, so this always returns `None`.
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd"
+)))]
+pub const fn default_gateway() -> Option<Ipv4Addr> {
+    None
+}
+
+/// Returns the local IP address the OS would use to reach the given destination.
+///
+/// No packets are sent, connecting a UDP socket only selects the route.
+pub fn local_ip_towards(destination: IpAddr) -> Option<IpAddr> {
+    let bind_addr: SocketAddr = match destination {
+        IpAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+        IpAddr::V6(_) => (std::net::Ipv6Addr::UNSPECIFIED, 0).into(),
+    };
+    let socket = UdpSocket::bind(bind_addr).ok()?;
+    socket.connect((destination, 9)).ok()?;
+    socket.local_addr().ok().map(|addr| addr.ip()).filter(|ip| !ip.is_unspecified())
+}
+
+/// Parses the gateway of the default route from the contents of `/proc/net/route`.
+///
+/// Addresses in this table are hex encoded in host byte order.
+#[cfg(any(test, target_os = "linux"))]
+fn parse_route_table(table: &str) -> Option<Ipv4Addr> {
+    table.lines().skip(1).find_map(|line| {
+        let mut columns = line.split_whitespace();
+        let _iface = columns.next()?;
+        let destination = columns.next()?;
+        let gateway = columns.next()?;
+        if destination != "00000000" {
+            return None
+        }
+        let gateway = u32::from_str_radix(gateway, 16).ok()?;
+        (gateway != 0).then(|| Ipv4Addr::from(gateway.to_le_bytes()))
+    })
+}
+
+/// Parses the gateway from the output of `route -n get default`.
+#[cfg(any(
+    test,
+    target_os = "macos",
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd"
+))]
+fn parse_route_get(output: &str) -> Option<Ipv4Addr> {
+    output.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        (name.trim() == "gateway").then(|| value.trim().parse().ok()).flatten()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_default_route() {
+        let table = "\
+Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+eth0\t0000A8C0\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0
+eth0\t00000000\t0100A8C0\t0003\t0\t0\t100\t00000000\t0\t0\t0
+";
+        assert_eq!(parse_route_table(table), Some(Ipv4Addr::new(192, 168, 0, 1)));
+    }
+
+    #[test]
+    fn parse_route_table_without_default_route() {
+        let table = "\
+Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+eth0\t0000A8C0\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0
+";
+        assert_eq!(parse_route_table(table), None);
+    }
+
+    #[test]
+    fn parse_route_get_output() {
+        let output = "   route to: default
+destination: default
+       mask: default
+    gateway: 192.168.1.254
+  interface: en0
+      flags: <UP,GATEWAY,DONE,STATIC,PRCLONING>
+";
+        assert_eq!(parse_route_get(output), Some(Ipv4Addr::new(192, 168, 1, 254)));
+    }
+}
@@ -12,8 +12,13 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+pub mod gateway;
+pub mod mapping;
+pub mod natpmp;
 pub mod net_if;
+pub mod upnp;
 
+pub use mapping::{MappedPort, PortMappingError, PortMappingProtocol, PortMappingRequest};
 pub use net_if::{NetInterfaceError, DEFAULT_NET_IF_NAME};
 
 use std::{
@@ -25,7 +30,7 @@ use std::{
     task::{Context, Poll},
     time::Duration,
 };
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
 
 use crate::net_if::resolve_net_if_ip;
 #[cfg(feature = "serde")]
@@ -44,8 +49,10 @@ pub enum NatResolver {
     /// Resolve with any available resolver.
     #[default]
     Any,
-    /// Resolve external IP via `UPnP`.
+    /// Resolve external IP and forward ports via `UPnP`.
     Upnp,
+    /// Resolve external IP and forward ports via NAT-PMP.
+    Pmp,
     /// Resolve external IP via a network request.
     PublicIp,
     /// Use the given [`IpAddr`]
@@ -69,6 +76,72 @@ impl NatResolver {
             _ => None,
         }
     }
+
+    /// Returns `true` if this resolver negotiates port mappings with the gateway.
+    pub const fn is_port_mapping(&self) -> bool {
+        matches!(self, Self::Upnp | Self::Pmp)
+    }
+
+    /// Negotiates the given port mappings with the gateway and returns the external IP reported
+    /// by it.
+    ///
+    /// Mappings are requested with the given lease and must be renewed before it expires.
+    /// Returns [`PortMappingError::Unsupported`] if this is not a port mapping resolver.
+    pub async fn map_ports(
+        self,
+        mappings: &[PortMappingRequest],
+        lease: Duration,
+    ) -> Result<(IpAddr, Vec<MappedPort>), PortMappingError> {
+        match self {
+            Self::Upnp => {
+                let gateway = upnp::Gateway::search().await?;
+                let mut mapped = Vec::with_capacity(mappings.len());
+                for mapping in mappings {
+                    mapped.push(gateway.map_port(*mapping, lease).await?);
+                }
+                Ok((gateway.external_ip().await?, mapped))
+            }
+            Self::Pmp => {
+                let gateway = gateway::default_gateway().ok_or(PortMappingError::NoGateway)?;
+                let mut mapped = Vec::with_capacity(mappings.len());
+                for mapping in mappings {
+                    mapped.push(natpmp::map_port(gateway, *mapping, lease).await?);
+                }
+                Ok((natpmp::external_address(gateway).await?.into(), mapped))
+            }
+            _ => Err(PortMappingError::Unsupported),
+        }
+    }
+
+    /// Removes the given port mappings from the gateway.
+    ///
+    /// All mappings are attempted, the last error is returned if any of them failed.
+    /// Returns [`PortMappingError::Unsupported`] if this is not a port mapping resolver.
+    pub async fn unmap_ports(self, mapped_ports: &[MappedPort]) -> Result<(), PortMappingError> {
+        let mut result = Ok(());
+        match self {
+            Self::Upnp => {
+                let gateway = upnp::Gateway::search().await?;
+                for port in mapped_ports {
+                    if let Err(err) = gateway.unmap_port(port.protocol, port.external_port).await {
+                        result = Err(err);
+                    }
+                }
+            }
+            Self::Pmp => {
+                let gateway = gateway::default_gateway().ok_or(PortMappingError::NoGateway)?;
+                for port in mapped_ports {
+                    if let Err(err) =
+                        natpmp::unmap_port(gateway, port.protocol, port.internal_port).await
+                    {
+                        result = Err(err);
+                    }
+                }
+            }
+            _ => return Err(PortMappingError::Unsupported),
+        }
+        result
+    }
 }
 
 impl fmt::Display for NatResolver {
@@ -76,6 +149,7 @@ impl fmt::Display for NatResolver {
         match self {
             Self::Any => f.write_str("any"),
             Self::Upnp => f.write_str("upnp"),
+            Self::Pmp => f.write_str("pmp"),
             Self::PublicIp => f.write_str("publicip"),
             Self::ExternalIp(ip) => write!(f, "extip:{ip}"),
             Self::NetIf => f.write_str("netif"),
@@ -102,6 +176,7 @@ impl FromStr for NatResolver {
         let r = match s {
             "any" => Self::Any,
             "upnp" => Self::Upnp,
+            "pmp" | "natpmp" => Self::Pmp,
             "none" => Self::None,
             "publicip" | "public-ip" => Self::PublicIp,
             "netif" => Self::NetIf,
@@ -118,12 +193,20 @@ impl FromStr for NatResolver {
     }
 }
 
+/// Resolves the external IP and the port mappings established on the way, if any.
+type ResolveFuture = Pin<Box<dyn Future<Output = (Option<IpAddr>, Vec<MappedPort>)> + Send>>;
+
 /// With this type you can resolve the external public IP address on an interval basis.
+///
+/// If the resolver supports port mapping and mappings were configured via
+/// [`ResolveNatInterval::with_port_mappings`], every tick also renews the port mappings.
 #[must_use = "Does nothing unless polled"]
 pub struct ResolveNatInterval {
     resolver: NatResolver,
-    future: Option<Pin<Box<dyn Future<Output = Option<IpAddr>> + Send>>>,
+    future: Option<ResolveFuture>,
     interval: tokio::time::Interval,
+    port_mappings: Vec<PortMappingRequest>,
+    mapped_ports: Vec<MappedPort>,
 }
 
 impl fmt::Debug for ResolveNatInterval {
@@ -132,13 +215,74 @@ impl fmt::Debug for ResolveNatInterval {
             .field("resolver", &self.resolver)
             .field("future", &self.future.as_ref().map(drop))
             .field("interval", &self.interval)
+            .field("port_mappings", &self.port_mappings)
+            .field("mapped_ports", &self.mapped_ports)
             .finish()
     }
 }
 
 impl ResolveNatInterval {
     fn with_interval(resolver: NatResolver, interval: tokio::time::Interval) -> Self {
-        Self { resolver, future: None, interval }
+        Self {
+            resolver,
+            future: None,
+            interval,
+            port_mappings: Vec::new(),
+            mapped_ports: Vec::new(),
+        }
+    }
+
+    /// Configures the ports that should be forwarded by the gateway.
+    ///
+    /// Mappings are only negotiated if the resolver supports port mapping, see
+    /// [`NatResolver::is_port_mapping`]. Leases are requested for twice the interval period so
+    /// they are renewed well before they expire.
+    pub fn with_port_mappings(
+        mut self,
+        port_mappings: impl IntoIterator<Item = PortMappingRequest>,
+    ) -> Self {
+        self.set_port_mappings(port_mappings);
+        self
+    }
+
+    /// Replaces the ports that should be forwarded by the gateway, the new mappings are
+    /// negotiated on the next tick.
+    pub fn set_port_mappings(
+        &mut self,
+        port_mappings: impl IntoIterator<Item = PortMappingRequest>,
+    ) {
+        self.port_mappings = port_mappings.into_iter().collect();
+    }
+
+    /// Returns the port mappings established by the gateway on the last tick.
+    ///
+    /// Gateways may assign a different external port than requested, so these are the ports that
+    /// should be advertised to peers. This is empty if no mappings were negotiated.
+    pub fn mapped_ports(&self) -> &[MappedPort] {
+        &self.mapped_ports
+    }
+
+    /// Removes the port mappings established on the last tick from the gateway and stops
+    /// negotiating port mappings.
+    ///
+    /// This should be called on shutdown, otherwise the mappings stay active until their lease
+    /// expires.
+    pub async fn unmap_ports(&mut self) {
+        self.port_mappings.clear();
+        self.future = None;
+        let mapped_ports = std::mem::take(&mut self.mapped_ports);
+        if mapped_ports.is_empty() {
+            return
+        }
+
+        match self.resolver.unmap_ports(&mapped_ports).await {
+            Ok(()) => {
+                debug!(target: "net::nat", resolver = %self.resolver, ?mapped_ports, "Removed port mappings")
+            }
+            Err(err) => {
+                warn!(target: "net::nat", resolver = %self.resolver, %err, "Failed to remove port mappings")
+            }
+        }
     }
 
     /// Creates a new [`ResolveNatInterval`] that attempts to resolve the public IP with interval of
@@ -168,6 +312,9 @@ impl ResolveNatInterval {
 
     /// Polls for the next resolved [`IpAddr`] in the interval to be reached.
     ///
+    /// Once resolved, [`ResolveNatInterval::mapped_ports`] returns the port mappings renewed on
+    /// this tick.
+    ///
     /// This method can return the following values:
     ///
     ///  * `Poll::Pending` if the next [`IpAddr`] has not yet been resolved.
@@ -175,12 +322,26 @@ impl ResolveNatInterval {
     ///    `None` if the attempt was unsuccessful.
     pub fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<Option<IpAddr>> {
         if self.interval.poll_tick(cx).is_ready() {
-            self.future = Some(Box::pin(self.resolver.external_addr()));
+            if self.resolver.is_port_mapping() && !self.port_mappings.is_empty() {
+                let lease = self.interval.period() * 2;
+                self.future = Some(Box::pin(map_ports_with(
+                    self.resolver,
+                    self.port_mappings.clone(),
+                    lease,
+                )));
+            } else {
+                let resolver = self.resolver;
+                self.future =
+                    Some(Box::pin(async move { (resolver.external_addr().await, Vec::new()) }));
+            }
         }
 
         if let Some(mut fut) = self.future.take() {
             match fut.as_mut().poll(cx) {
-                Poll::Ready(ip) => return Poll::Ready(ip),
+                Poll::Ready((ip, mapped_ports)) => {
+                    self.mapped_ports = mapped_ports;
+                    return Poll::Ready(ip)
+                }
                 Poll::Pending => self.future = Some(fut),
             }
         }
@@ -197,7 +358,33 @@ pub async fn external_ip() -> Option<IpAddr> {
 /// Given a [`NatResolver`] attempts to produce an IP address (best effort).
 pub async fn external_addr_with(resolver: NatResolver) -> Option<IpAddr> {
     match resolver {
-        NatResolver::Any | NatResolver::Upnp | NatResolver::PublicIp => resolve_external_ip().await,
+        NatResolver::Any | NatResolver::PublicIp => resolve_external_ip().await,
+        NatResolver::Upnp => match upnp::Gateway::search().await {
+            Ok(gateway) => match gateway.external_ip().await {
+                Ok(ip) => Some(ip),
+                Err(err) => {
+                    debug!(target: "net::nat", %err, "Failed to query UPnP external IP");
+                    resolve_external_ip().await
+                }
+            },
+            Err(err) => {
+                debug!(target: "net::nat", %err, "Failed to find UPnP gateway");
+                resolve_external_ip().await
+            }
+        },
+        NatResolver::Pmp => {
+            let Some(gateway) = gateway::default_gateway() else {
+                debug!(target: "net::nat", "Failed to find NAT-PMP gateway");
+                return resolve_external_ip().await
+            };
+            match natpmp::external_address(gateway).await {
+                Ok(ip) => Some(ip.into()),
+                Err(err) => {
+                    debug!(target: "net::nat", %err, "Failed to query NAT-PMP external IP");
+                    resolve_external_ip().await
+                }
+            }
+        }
         NatResolver::ExternalIp(ip) => Some(ip),
         NatResolver::NetIf => resolve_net_if_ip(DEFAULT_NET_IF_NAME)
             .inspect_err(|err| {
@@ -211,6 +398,27 @@ pub async fn external_addr_with(resolver: NatResolver) -> Option<IpAddr> {
     }
 }
 
+/// Negotiates the port mappings and returns the external IP reported by the gateway together
+/// with the established mappings.
+///
+/// Falls back to resolving the external IP without port mapping if the negotiation fails.
+async fn map_ports_with(
+    resolver: NatResolver,
+    mappings: Vec<PortMappingRequest>,
+    lease: Duration,
+) -> (Option<IpAddr>, Vec<MappedPort>) {
+    match resolver.map_ports(&mappings, lease).await {
+        Ok((ip, mapped)) => {
+            debug!(target: "net::nat", %resolver, %ip, ?mapped, "Renewed port mappings");
+            (Some(ip), mapped)
+        }
+        Err(err) => {
+            warn!(target: "net::nat", %resolver, %err, "Failed to map ports");
+            (resolver.external_addr().await, Vec::new())
+        }
+    }
+}
+
 async fn resolve_external_ip() -> Option<IpAddr> {
     let futures = EXTERNAL_IP_APIS.iter().copied().map(resolve_external_ip_url_res).map(Box::pin);
     futures_util::future::select_ok(futures)
@@ -265,6 +473,10 @@ mod tests {
     fn test_from_str() {
         assert_eq!(NatResolver::Any, "any".parse().unwrap());
         assert_eq!(NatResolver::None, "none".parse().unwrap());
+        assert_eq!(NatResolver::Upnp, "upnp".parse().unwrap());
+        assert_eq!(NatResolver::Pmp, "pmp".parse().unwrap());
+        assert_eq!(NatResolver::Pmp, "natpmp".parse().unwrap());
+        assert_eq!(NatResolver::Pmp.to_string(), "pmp");
 
         let ip = NatResolver::ExternalIp(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
         let s = "extip:0.0.0.0";
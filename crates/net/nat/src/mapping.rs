@@ -0,0 +1,93 @@
+//! Port mapping types shared by the `UPnP` and NAT-PMP implementations.
+
+use std::{fmt, io, time::Duration};
+
+/// Transport protocol of a port mapping.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum PortMappingProtocol {
+    /// TCP, used by `RLPx`.
+    Tcp,
+    /// UDP, used by discovery.
+    Udp,
+}
+
+impl PortMappingProtocol {
+    /// Returns the protocol name as expected by `UPnP` gateways.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Tcp => "TCP",
+            Self::Udp => "UDP",
+        }
+    }
+}
+
+impl fmt::Display for PortMappingProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A port that should be forwarded by the gateway.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct PortMappingRequest {
+    /// Transport protocol of the mapping.
+    pub protocol: PortMappingProtocol,
+    /// The local port to forward to.
+    pub internal_port: u16,
+    /// The external port to request, gateways may assign a different one.
+    pub external_port: u16,
+}
+
+impl PortMappingRequest {
+    /// Requests a TCP mapping with the same internal and external port.
+    pub const fn tcp(port: u16) -> Self {
+        Self { protocol: PortMappingProtocol::Tcp, internal_port: port, external_port: port }
+    }
+
+    /// Requests a UDP mapping with the same internal and external port.
+    pub const fn udp(port: u16) -> Self {
+        Self { protocol: PortMappingProtocol::Udp, internal_port: port, external_port: port }
+    }
+}
+
+/// A port mapping that was established by the gateway.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct MappedPort {
+    /// Transport protocol of the mapping.
+    pub protocol: PortMappingProtocol,
+    /// The local port the mapping forwards to.
+    pub internal_port: u16,
+    /// The external port assigned by the gateway.
+    pub external_port: u16,
+    /// How long the gateway keeps the mapping alive.
+    pub lease: Duration,
+}
+
+/// Errors that can occur when negotiating port mappings with a gateway.
+#[derive(Debug, thiserror::Error)]
+pub enum PortMappingError {
+    /// No gateway could be found on the local network.
+    #[error("no gateway found")]
+    NoGateway,
+    /// The gateway did not respond in time.
+    #[error("gateway did not respond")]
+    Timeout,
+    /// The configured resolver does not support port mapping.
+    #[error("resolver does not support port mapping")]
+    Unsupported,
+    /// The gateway sent a response that could not be parsed.
+    #[error("malformed gateway response: {0}")]
+    MalformedResponse(&'static str),
+    /// The NAT-PMP gateway rejected the request with the given result code.
+    #[error("NAT-PMP request failed with result code {0}")]
+    NatPmp(u16),
+    /// The `UPnP` gateway rejected the request.
+    #[error("UPnP request failed: {0}")]
+    Upnp(String),
+    /// Socket error.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// HTTP error while talking to a `UPnP` gateway.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+}
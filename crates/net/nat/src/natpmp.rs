@@ -0,0 +1,199 @@
+//! NAT-PMP client, see [RFC 6886](https://datatracker.ietf.org/doc/html/rfc6886).
+
+use crate::mapping::{MappedPort, PortMappingError, PortMappingProtocol, PortMappingRequest};
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+use tokio::net::UdpSocket;
+use tracing::trace;
+
+/// The port NAT-PMP gateways listen on.
+pub const NATPMP_PORT: u16 = 5351;
+
+/// NAT-PMP protocol version.
+const VERSION: u8 = 0;
+
+/// Opcode requesting the external address of the gateway.
+const OP_EXTERNAL_ADDRESS: u8 = 0;
+
+/// Offset added to the request opcode in responses.
+const RESPONSE_OFFSET: u8 = 128;
+
+/// Initial retransmission timeout, doubled on every attempt as recommended by the RFC.
+const INITIAL_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// Number of attempts before giving up. The RFC allows up to 9, which would take more than a
+/// minute, we give up after a few seconds instead.
+const MAX_ATTEMPTS: u32 = 4;
+
+/// Requests the external IPv4 address from the NAT-PMP gateway.
+pub async fn external_address(gateway: Ipv4Addr) -> Result<Ipv4Addr, PortMappingError> {
+    let response = request(gateway, &[VERSION, OP_EXTERNAL_ADDRESS]).await?;
+    decode_external_address_response(&response)
+}
+
+/// Requests a port mapping from the NAT-PMP gateway.
+///
+/// Requesting an existing mapping again renews its lease.
+pub async fn map_port(
+    gateway: Ipv4Addr,
+    mapping: PortMappingRequest,
+    lease: Duration,
+) -> Result<MappedPort, PortMappingError> {
+    let response = request(gateway, &encode_mapping_request(mapping, lease)).await?;
+    decode_mapping_response(mapping.protocol, &response)
+}
+
+/// Removes the port mapping of the given internal port from the NAT-PMP gateway.
+///
+/// A mapping is removed by requesting it with an external port and lifetime of zero, see
+/// [RFC 6886 section 3.4](https://datatracker.ietf.org/doc/html/rfc6886#section-3.4).
+pub async fn unmap_port(
+    gateway: Ipv4Addr,
+    protocol: PortMappingProtocol,
+    internal_port: u16,
+) -> Result<(), PortMappingError> {
+    let mapping = PortMappingRequest { protocol, internal_port, external_port: 0 };
+    let response = request(gateway, &encode_mapping_request(mapping, Duration::ZERO)).await?;
+    decode_mapping_response(protocol, &response)?;
+    Ok(())
+}
+
+/// Sends the request to the gateway and waits for the response, retransmitting on timeout.
+async fn request(gateway: Ipv4Addr, payload: &[u8]) -> Result<Vec<u8>, PortMappingError> {
+    let socket = UdpSocket::bind(SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0))).await?;
+    socket.connect((gateway, NATPMP_PORT)).await?;
+
+    let mut timeout = INITIAL_TIMEOUT;
+    let mut buf = [0u8; 16];
+    for attempt in 1..=MAX_ATTEMPTS {
+        trace!(target: "net::nat", %gateway, attempt, "Sending NAT-PMP request");
+        socket.send(payload).await?;
+        if let Ok(res) = tokio::time::timeout(timeout, socket.recv(&mut buf)).await {
+            let len = res?;
+            return Ok(buf[..len].to_vec())
+        }
+        timeout *= 2;
+    }
+
+    Err(PortMappingError::Timeout)
+}
+
+const fn opcode(protocol: PortMappingProtocol) -> u8 {
+    match protocol {
+        PortMappingProtocol::Udp => 1,
+        PortMappingProtocol::Tcp => 2,
+    }
+}
+
+fn encode_mapping_request(mapping: PortMappingRequest, lease: Duration) -> [u8; 12] {
+    let lease = u32::try_from(lease.as_secs()).unwrap_or(u32::MAX);
+    let mut buf = [0u8; 12];
+    buf[0] = VERSION;
+    buf[1] = opcode(mapping.protocol);
+    // bytes 2..4 are reserved
+    buf[4..6].copy_from_slice(&mapping.internal_port.to_be_bytes());
+    buf[6..8].copy_from_slice(&mapping.external_port.to_be_bytes());
+    buf[8..12].copy_from_slice(&lease.to_be_bytes());
+    buf
+}
+
+/// Validates the common response header and returns the payload after the seconds since epoch
+/// field.
+fn decode_header(op: u8, response: &[u8]) -> Result<&[u8], PortMappingError> {
+    if response.len() < 8 {
+        return Err(PortMappingError::MalformedResponse("NAT-PMP response too short"))
+    }
+    if response[0] != VERSION || response[1] != op + RESPONSE_OFFSET {
+        return Err(PortMappingError::MalformedResponse("unexpected NAT-PMP opcode"))
+    }
+    let result_code = u16::from_be_bytes([response[2], response[3]]);
+    if result_code != 0 {
+        return Err(PortMappingError::NatPmp(result_code))
+    }
+    Ok(&response[8..])
+}
+
+fn decode_external_address_response(response: &[u8]) -> Result<Ipv4Addr, PortMappingError> {
+    let payload = decode_header(OP_EXTERNAL_ADDRESS, response)?;
+    let octets: [u8; 4] = payload
+        .get(..4)
+        .and_then(|octets| octets.try_into().ok())
+        .ok_or(PortMappingError::MalformedResponse("missing external address"))?;
+    Ok(Ipv4Addr::from(octets))
+}
+
+fn decode_mapping_response(
+    protocol: PortMappingProtocol,
+    response: &[u8],
+) -> Result<MappedPort, PortMappingError> {
+    let payload = decode_header(opcode(protocol), response)?;
+    if payload.len() < 8 {
+        return Err(PortMappingError::MalformedResponse("missing port mapping"))
+    }
+    Ok(MappedPort {
+        protocol,
+        internal_port: u16::from_be_bytes([payload[0], payload[1]]),
+        external_port: u16::from_be_bytes([payload[2], payload[3]]),
+        lease: Duration::from_secs(u32::from_be_bytes([
+            payload[4], payload[5], payload[6], payload[7],
+        ]) as u64),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_tcp_mapping() {
+        let request =
+            encode_mapping_request(PortMappingRequest::tcp(30303), Duration::from_secs(7200));
+        assert_eq!(request, [0, 2, 0, 0, 0x76, 0x5f, 0x76, 0x5f, 0, 0, 0x1c, 0x20]);
+    }
+
+    #[test]
+    fn encode_udp_unmapping() {
+        let mapping = PortMappingRequest {
+            protocol: PortMappingProtocol::Udp,
+            internal_port: 30303,
+            external_port: 0,
+        };
+        let request = encode_mapping_request(mapping, Duration::ZERO);
+        assert_eq!(request, [0, 1, 0, 0, 0x76, 0x5f, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn decode_mapping() {
+        let response = [0, 129, 0, 0, 0, 0, 0, 1, 0x76, 0x5f, 0x76, 0x60, 0, 0, 0x0e, 0x10];
+        let mapped = decode_mapping_response(PortMappingProtocol::Udp, &response).unwrap();
+        assert_eq!(
+            mapped,
+            MappedPort {
+                protocol: PortMappingProtocol::Udp,
+                internal_port: 30303,
+                external_port: 30304,
+                lease: Duration::from_secs(3600),
+            }
+        );
+    }
+
+    #[test]
+    fn decode_external_address() {
+        let response = [0, 128, 0, 0, 0, 0, 0, 1, 203, 0, 113, 7];
+        assert_eq!(
+            decode_external_address_response(&response).unwrap(),
+            Ipv4Addr::new(203, 0, 113, 7)
+        );
+    }
+
+    #[test]
+    fn decode_error_result_code() {
+        let response = [0, 130, 0, 2, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert!(matches!(
+            decode_mapping_response(PortMappingProtocol::Tcp, &response),
+            Err(PortMappingError::NatPmp(2))
+        ));
+    }
+}
@@ -0,0 +1,256 @@
+//! Minimal `UPnP` Internet Gateway Device client.
+//!
+//! Discovers the gateway via SSDP and talks to its `WANIPConnection` (or `WANPPPConnection`)
+//! service via SOAP to query the external address and manage port mappings.
+
+use crate::{
+    gateway::local_ip_towards,
+    mapping::{MappedPort, PortMappingError, PortMappingProtocol, PortMappingRequest},
+};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+use tokio::net::UdpSocket;
+use tracing::trace;
+
+/// SSDP multicast address.
+const SSDP_ADDR: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(239, 255, 255, 250)), 1900);
+
+/// How long to wait for gateways to answer the SSDP search.
+const SEARCH_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// SSDP search request for internet gateway devices.
+const SEARCH_REQUEST: &str = "M-SEARCH * HTTP/1.1\r\n\
+    HOST: 239.255.255.250:1900\r\n\
+    ST: urn:schemas-upnp-org:device:InternetGatewayDevice:1\r\n\
+    MAN: \"ssdp:discover\"\r\n\
+    MX: 2\r\n\r\n";
+
+/// Services that expose port mapping actions, in order of preference.
+const WAN_SERVICES: &[&str] = &[
+    "urn:schemas-upnp-org:service:WANIPConnection:2",
+    "urn:schemas-upnp-org:service:WANIPConnection:1",
+    "urn:schemas-upnp-org:service:WANPPPConnection:1",
+];
+
+/// Description attached to the mappings created by reth.
+const MAPPING_DESCRIPTION: &str = "reth";
+
+/// A discovered `UPnP` internet gateway.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gateway {
+    /// Address of the gateway.
+    pub addr: SocketAddr,
+    /// URL of the WAN connection control endpoint.
+    pub control_url: String,
+    /// The WAN connection service type.
+    pub service_type: String,
+}
+
+impl Gateway {
+    /// Discovers a gateway on the local network.
+    pub async fn search() -> Result<Self, PortMappingError> {
+        let socket = UdpSocket::bind(SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0))).await?;
+        socket.send_to(SEARCH_REQUEST.as_bytes(), SSDP_ADDR).await?;
+
+        let mut buf = [0u8; 2048];
+        let (len, addr) = tokio::time::timeout(SEARCH_TIMEOUT, socket.recv_from(&mut buf))
+            .await
+            .map_err(|_| PortMappingError::NoGateway)??;
+        let response = String::from_utf8_lossy(&buf[..len]);
+        let location = parse_location(&response)
+            .ok_or(PortMappingError::MalformedResponse("missing SSDP location"))?;
+        trace!(target: "net::nat", %addr, %location, "Found UPnP gateway");
+
+        let description = reqwest::get(location).await?.error_for_status()?.text().await?;
+        let (service_type, control_path) = parse_wan_service(&description)
+            .ok_or(PortMappingError::MalformedResponse("missing WAN connection service"))?;
+
+        Ok(Self {
+            addr,
+            control_url: resolve_url(location, control_path),
+            service_type: service_type.to_string(),
+        })
+    }
+
+    /// Queries the external IP address of the gateway.
+    pub async fn external_ip(&self) -> Result<IpAddr, PortMappingError> {
+        let response = self.soap_request("GetExternalIPAddress", "").await?;
+        extract_tag(&response, "NewExternalIPAddress")
+            .and_then(|ip| ip.trim().parse().ok())
+            .ok_or(PortMappingError::MalformedResponse("missing external IP address"))
+    }
+
+    /// Adds a port mapping, an existing mapping for the same external port is renewed.
+    pub async fn map_port(
+        &self,
+        mapping: PortMappingRequest,
+        lease: Duration,
+    ) -> Result<MappedPort, PortMappingError> {
+        let local_ip = local_ip_towards(self.addr.ip()).ok_or(PortMappingError::NoGateway)?;
+        let args = format!(
+            "<NewRemoteHost></NewRemoteHost>\
+             <NewExternalPort>{}</NewExternalPort>\
+             <NewProtocol>{}</NewProtocol>\
+             <NewInternalPort>{}</NewInternalPort>\
+             <NewInternalClient>{local_ip}</NewInternalClient>\
+             <NewEnabled>1</NewEnabled>\
+             <NewPortMappingDescription>{MAPPING_DESCRIPTION}</NewPortMappingDescription>\
+             <NewLeaseDuration>{}</NewLeaseDuration>",
+            mapping.external_port,
+            mapping.protocol,
+            mapping.internal_port,
+            lease.as_secs(),
+        );
+        self.soap_request("AddPortMapping", &args).await?;
+
+        Ok(MappedPort {
+            protocol: mapping.protocol,
+            internal_port: mapping.internal_port,
+            external_port: mapping.external_port,
+            lease,
+        })
+    }
+
+    /// Deletes the port mapping of the given external port.
+    pub async fn unmap_port(
+        &self,
+        protocol: PortMappingProtocol,
+        external_port: u16,
+    ) -> Result<(), PortMappingError> {
+        let args = format!(
+            "<NewRemoteHost></NewRemoteHost>\
+             <NewExternalPort>{external_port}</NewExternalPort>\
+             <NewProtocol>{protocol}</NewProtocol>"
+        );
+        self.soap_request("DeletePortMapping", &args).await?;
+        Ok(())
+    }
+
+    /// Invokes the given action on the WAN connection service and returns the response body.
+    async fn soap_request(&self, action: &str, args: &str) -> Result<String, PortMappingError> {
+        let body = format!(
+            "<?xml version=\"1.0\"?>\
+             <s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\" \
+             s:encodingStyle=\"http://schemas.xmlsoap.org/soap/encoding/\">\
+             <s:Body><u:{action} xmlns:u=\"{}\">{args}</u:{action}></s:Body>\
+             </s:Envelope>",
+            self.service_type
+        );
+
+        let response = reqwest::Client::new()
+            .post(&self.control_url)
+            .header("Content-Type", "text/xml; charset=\"utf-8\"")
+            .header("SOAPAction", format!("\"{}#{action}\"", self.service_type))
+            .body(body)
+            .send()
+            .await?;
+        let status = response.status();
+        let text = response.text().await?;
+        if !status.is_success() {
+            let reason = extract_tag(&text, "errorDescription").unwrap_or(status.as_str());
+            return Err(PortMappingError::Upnp(format!("{action}: {reason}")))
+        }
+        Ok(text)
+    }
+}
+
+/// Extracts the `LOCATION` header from an SSDP response.
+fn parse_location(response: &str) -> Option<&str> {
+    response.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim().eq_ignore_ascii_case("location").then(|| value.trim())
+    })
+}
+
+/// Finds the first supported WAN connection service in the device description and returns its
+/// service type and control URL.
+fn parse_wan_service(description: &str) -> Option<(&'static str, &str)> {
+    WAN_SERVICES.iter().find_map(|service_type| {
+        description.split("<service>").skip(1).find_map(|service| {
+            let service = service.split("</service>").next()?;
+            (extract_tag(service, "serviceType")? == *service_type)
+                .then(|| extract_tag(service, "controlURL"))
+                .flatten()
+                .map(|control_url| (*service_type, control_url))
+        })
+    })
+}
+
+/// Returns the text content of the first `<tag>` element.
+fn extract_tag<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    let start = xml.find(&format!("<{tag}>"))? + tag.len() + 2;
+    let end = start + xml[start..].find(&format!("</{tag}>"))?;
+    Some(xml[start..end].trim())
+}
+
+/// Resolves the control URL against the location of the device description.
+fn resolve_url(location: &str, path: &str) -> String {
+    if path.starts_with("http://") || path.starts_with("https://") {
+        return path.to_string()
+    }
+    // strip the path from the location, keeping `scheme://host:port`
+    let base_end = location
+        .find("://")
+        .and_then(|scheme_end| location[scheme_end + 3..].find('/').map(|i| scheme_end + 3 + i))
+        .unwrap_or(location.len());
+    let separator = if path.starts_with('/') { "" } else { "/" };
+    format!("{}{separator}{path}", &location[..base_end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DESCRIPTION: &str = r#"<?xml version="1.0"?>
+<root xmlns="urn:schemas-upnp-org:device-1-0">
+  <device>
+    <serviceList>
+      <service>
+        <serviceType>urn:schemas-upnp-org:service:Layer3Forwarding:1</serviceType>
+        <controlURL>/ctl/L3F</controlURL>
+      </service>
+      <service>
+        <serviceType>urn:schemas-upnp-org:service:WANIPConnection:1</serviceType>
+        <controlURL>/ctl/IPConn</controlURL>
+      </service>
+    </serviceList>
+  </device>
+</root>"#;
+
+    #[test]
+    fn parse_ssdp_location() {
+        let response = "HTTP/1.1 200 OK\r\nCACHE-CONTROL: max-age=120\r\n\
+            Location: http://192.168.0.1:5000/rootDesc.xml\r\n\
+            ST: urn:schemas-upnp-org:device:InternetGatewayDevice:1\r\n\r\n";
+        assert_eq!(parse_location(response), Some("http://192.168.0.1:5000/rootDesc.xml"));
+    }
+
+    #[test]
+    fn parse_device_description() {
+        assert_eq!(
+            parse_wan_service(DESCRIPTION),
+            Some(("urn:schemas-upnp-org:service:WANIPConnection:1", "/ctl/IPConn"))
+        );
+    }
+
+    #[test]
+    fn resolve_control_url() {
+        let location = "http://192.168.0.1:5000/rootDesc.xml";
+        assert_eq!(resolve_url(location, "/ctl/IPConn"), "http://192.168.0.1:5000/ctl/IPConn");
+        assert_eq!(resolve_url(location, "ctl/IPConn"), "http://192.168.0.1:5000/ctl/IPConn");
+        assert_eq!(
+            resolve_url(location, "http://192.168.0.2/ctl/IPConn"),
+            "http://192.168.0.2/ctl/IPConn"
+        );
+    }
+
+    #[test]
+    fn extract_external_ip() {
+        let response = "<s:Envelope><s:Body><u:GetExternalIPAddressResponse>\
+            <NewExternalIPAddress>203.0.113.7</NewExternalIPAddress>\
+            </u:GetExternalIPAddressResponse></s:Body></s:Envelope>";
+        assert_eq!(extract_tag(response, "NewExternalIPAddress"), Some("203.0.113.7"));
+    }
+}
@@ -26,7 +26,9 @@ use test_utils::PeersHandleProvider;
 pub use alloy_rpc_types_admin::EthProtocolInfo;
 use reth_network_p2p::sync::NetworkSyncUpdater;
 pub use reth_network_p2p::BlockClient;
-pub use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
+pub use reth_network_types::{
+    NetworkHealth, NetworkHealthStatus, PeerHealth, PeerKind, Reputation, ReputationChangeKind,
+};
 
 pub use downloaders::BlockDownloaderProvider;
 pub use error::NetworkError;
@@ -35,7 +37,12 @@ pub use events::{
     PeerRequestSender,
 };
 
-use std::{future::Future, net::SocketAddr, sync::Arc, time::Instant};
+use std::{
+    future::Future,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use reth_eth_wire_types::{capability::Capabilities, DisconnectReason, EthVersion, Status};
 use reth_network_peers::NodeRecord;
@@ -102,6 +109,10 @@ pub trait PeersInfo: Send + Sync {
 
     /// Returns the local ENR of the node.
     fn local_enr(&self) -> enr::Enr<enr::secp256k1::SecretKey>;
+
+    /// Returns the [`NetworkHealth`] of the peer connectivity, as tracked by the watchdog that
+    /// escalates discovery if the number of connected peers collapses.
+    fn network_health(&self) -> NetworkHealth;
 }
 
 /// Provides an API for managing the peers of the network.
@@ -170,6 +181,12 @@ pub trait Peers: PeersInfo {
         peer_id: PeerId,
     ) -> impl Future<Output = Result<Option<PeerInfo>, NetworkError>> + Send;
 
+    /// Returns the [`PeerHealthInfo`] for all trusted and static peers, including the ones that
+    /// are currently not connected.
+    fn get_peers_health(
+        &self,
+    ) -> impl Future<Output = Result<Vec<PeerHealthInfo>, NetworkError>> + Send;
+
     /// Returns the rpc [`PeerInfo`] for the given peers if they are connected.
     ///
     /// Note: This only returns peers that are connected, unconnected peers are ignored but keeping
@@ -238,8 +255,21 @@ pub struct PeerInfo {
     pub status: Arc<Status>,
     /// The timestamp when the session to that peer has been established.
     pub session_established: Instant,
+    /// Round trip time of the most recent ping answered by the peer, if any
+    pub latency: Option<Duration>,
+    /// The peer's connection kind
+    pub kind: PeerKind,
+}
+
+/// Health of the connection to a trusted or static peer.
+#[derive(Debug, Clone)]
+pub struct PeerHealthInfo {
+    /// The peer's node record
+    pub record: NodeRecord,
     /// The peer's connection kind
     pub kind: PeerKind,
+    /// The health of the connection to the peer
+    pub health: PeerHealth,
 }
 
 /// The direction of the connection.
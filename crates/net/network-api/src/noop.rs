@@ -9,9 +9,11 @@ use alloy_rpc_types_admin::EthProtocolInfo;
 use enr::{secp256k1::SecretKey, Enr};
 use reth_eth_wire_types::{DisconnectReason, ProtocolVersion};
 use reth_network_peers::NodeRecord;
-use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
+use reth_network_types::{NetworkHealth, PeerKind, Reputation, ReputationChangeKind};
 
-use crate::{NetworkError, NetworkInfo, NetworkStatus, PeerId, PeerInfo, Peers, PeersInfo};
+use crate::{
+    NetworkError, NetworkInfo, NetworkStatus, PeerHealthInfo, PeerId, PeerInfo, Peers, PeersInfo,
+};
 
 /// A type that implements all network trait that does nothing.
 ///
@@ -67,6 +69,10 @@ impl PeersInfo for NoopNetwork {
         let sk = SecretKey::from_slice(&[0xcd; 32]).unwrap();
         Enr::builder().build(&sk).unwrap()
     }
+
+    fn network_health(&self) -> NetworkHealth {
+        NetworkHealth::default()
+    }
 }
 
 impl Peers for NoopNetwork {
@@ -93,6 +99,10 @@ impl Peers for NoopNetwork {
         Ok(None)
     }
 
+    async fn get_peers_health(&self) -> Result<Vec<PeerHealthInfo>, NetworkError> {
+        Ok(vec![])
+    }
+
     async fn get_peers_by_id(&self, _peer_id: Vec<PeerId>) -> Result<Vec<PeerInfo>, NetworkError> {
         Ok(vec![])
     }
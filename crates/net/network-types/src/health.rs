@@ -0,0 +1,51 @@
+//! Health of the peer connectivity of the network.
+
+use std::time::Instant;
+
+/// Represents the health of the peer connectivity of the network.
+///
+/// See [`PeerCountWatchdogConfig`](crate::PeerCountWatchdogConfig) for when the network is
+/// degraded or stalled.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct NetworkHealth {
+    /// The current status of the network.
+    pub status: NetworkHealthStatus,
+    /// The number of connected peers.
+    pub connected_peers: usize,
+    /// Since when the network is degraded, if it is.
+    pub degraded_since: Option<Instant>,
+    /// The number of times discovery was escalated since the network is degraded.
+    pub escalations: u32,
+}
+
+/// Represents the status of the peer connectivity of the network.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum NetworkHealthStatus {
+    /// The network has more than the minimum number of peers.
+    #[default]
+    Healthy,
+    /// The network has at most the minimum number of peers.
+    Degraded,
+    /// The network has been degraded for longer than the stall timeout, and discovery is
+    /// escalated.
+    Stalled,
+}
+
+// === impl NetworkHealthStatus ===
+
+impl NetworkHealthStatus {
+    /// Returns the name of the status.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Healthy => "healthy",
+            Self::Degraded => "degraded",
+            Self::Stalled => "stalled",
+        }
+    }
+}
+
+impl core::fmt::Display for NetworkHealthStatus {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -18,15 +18,20 @@ pub mod session;
 
 /// [`BackoffKind`] definition.
 mod backoff;
+mod health;
 
 pub use peers::reputation::{Reputation, ReputationChangeKind, ReputationChangeWeights};
 
 pub use backoff::BackoffKind;
+pub use health::{NetworkHealth, NetworkHealthStatus};
 pub use peers::{
     addr::PeerAddr,
+    allowlist::PeerAllowlist,
+    health::{PeerHealth, PeerHealthStatus},
     kind::PeerKind,
+    quality::ResponseQuality,
     reputation::{is_banned_reputation, ReputationChangeOutcome, DEFAULT_REPUTATION},
     state::PeerConnectionState,
-    ConnectionsConfig, Peer, PeersConfig,
+    ConnectionsConfig, Peer, PeerCountWatchdogConfig, PeersConfig,
 };
 pub use session::{SessionLimits, SessionsConfig};
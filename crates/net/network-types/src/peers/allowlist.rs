@@ -0,0 +1,187 @@
+//! Allowlist of the peers a node may connect to, for private networks.
+
+use std::{
+    collections::HashSet,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::SystemTime,
+};
+
+use reth_network_peers::{AnyNode, NodeRecord, PeerId};
+
+/// The set of peers a node is allowed to dial and accept connections from.
+///
+/// This is meant for private networks, e.g. consortium chains, where every node is known in
+/// advance. Allowed peers with a known address are dialed even if discovery is disabled.
+///
+/// The allowlist can be backed by a file, which is reloaded by
+/// [`PeerAllowlist::reload_if_modified`] whenever it changed. Only the file is part of the
+/// serialized config.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PeerAllowlist {
+    /// The allowed peers.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    peers: HashSet<PeerId>,
+    /// The allowed peers with a known address.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    nodes: Vec<NodeRecord>,
+    /// The file the peers are loaded from, if any.
+    file: Option<PathBuf>,
+    /// The modification time of the file when the peers were loaded.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    modified: Option<SystemTime>,
+}
+
+impl PeerAllowlist {
+    /// Creates an allowlist of the given peers.
+    pub fn new(peers: impl IntoIterator<Item = PeerId>) -> Self {
+        Self { peers: peers.into_iter().collect(), ..Default::default() }
+    }
+
+    /// Creates an allowlist of the given peer IDs, enode URLs or ENRs.
+    ///
+    /// The peers with an address are dialed even if discovery is disabled.
+    pub fn from_nodes(nodes: impl IntoIterator<Item = AnyNode>) -> Self {
+        let mut allowlist = Self::default();
+        allowlist.set_nodes(nodes);
+        allowlist
+    }
+
+    /// Creates an allowlist backed by the given file, without loading it yet.
+    ///
+    /// The peers are loaded by the first call to [`PeerAllowlist::reload_if_modified`], until
+    /// then no peer is allowed.
+    pub fn with_file(path: impl Into<PathBuf>) -> Self {
+        Self { file: Some(path.into()), ..Default::default() }
+    }
+
+    /// Loads the allowlist from the given file, see [`PeerAllowlist::parse`] for the format.
+    pub fn from_file(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let mut allowlist = Self::with_file(path);
+        allowlist.reload_if_modified()?;
+        Ok(allowlist)
+    }
+
+    /// Parses the entries of an allowlist file.
+    ///
+    /// Every line holds a peer ID, an enode URL or an ENR. Empty lines and everything after a `#`
+    /// are ignored. The peers of enode URLs and ENRs with an address are dialed:
+    ///
+    /// ```text
+    /// # validator 1
+    /// enode://6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec012937307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0@10.3.58.6:30303
+    /// ```
+    pub fn parse(content: &str) -> io::Result<Vec<AnyNode>> {
+        let mut nodes = Vec::new();
+        for (idx, line) in content.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue
+            }
+
+            let node = PeerId::from_str(line)
+                .map(AnyNode::PeerId)
+                .or_else(|_| AnyNode::from_str(line))
+                .map_err(|_| {
+                    io::Error::new(
+                        ErrorKind::InvalidData,
+                        format!("invalid allowlist entry on line {}: {line}", idx + 1),
+                    )
+                })?;
+            nodes.push(node);
+        }
+        Ok(nodes)
+    }
+
+    /// Returns the file the allowlist is loaded from, if any.
+    pub fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    /// Returns the allowed peers.
+    pub const fn peers(&self) -> &HashSet<PeerId> {
+        &self.peers
+    }
+
+    /// Returns the allowed peers with a known address.
+    pub fn nodes(&self) -> &[NodeRecord] {
+        &self.nodes
+    }
+
+    /// Returns `true` if the given peer is allowed.
+    pub fn contains(&self, peer_id: &PeerId) -> bool {
+        self.peers.contains(peer_id)
+    }
+
+    /// Reloads the peers from the file if it was modified since it was last loaded.
+    ///
+    /// Returns `true` if the peers were reloaded. If the file can't be read or parsed, the current
+    /// peers are kept.
+    pub fn reload_if_modified(&mut self) -> io::Result<bool> {
+        let Some(file) = &self.file else { return Ok(false) };
+        let modified = std::fs::metadata(file)?.modified().ok();
+        if modified.is_some() && modified == self.modified {
+            return Ok(false)
+        }
+
+        let nodes = Self::parse(&std::fs::read_to_string(file)?)?;
+        self.set_nodes(nodes);
+        self.modified = modified;
+        Ok(true)
+    }
+
+    /// Replaces the allowed peers.
+    fn set_nodes(&mut self, nodes: impl IntoIterator<Item = AnyNode>) {
+        self.peers.clear();
+        self.nodes.clear();
+        for node in nodes {
+            self.peers.insert(node.peer_id());
+            self.nodes.extend(node.node_record());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_allowlist() {
+        let peer_id = PeerId::with_last_byte(2);
+        let content = format!(
+            "# bootnode\n{peer_id:x}\n\nenode://{:x}@10.3.58.6:30303 # validator\n",
+            PeerId::with_last_byte(1)
+        );
+        let allowlist = PeerAllowlist::from_nodes(PeerAllowlist::parse(&content).unwrap());
+        assert_eq!(allowlist.peers(), &HashSet::from([peer_id, PeerId::with_last_byte(1)]));
+
+        // the address of the enode URL is kept
+        let [node] = allowlist.nodes() else { panic!("expected a single node") };
+        assert_eq!(node.id, PeerId::with_last_byte(1));
+        assert_eq!(node.address, std::net::IpAddr::from([10, 3, 58, 6]));
+        assert_eq!(node.tcp_port, 30303);
+
+        assert!(PeerAllowlist::parse("10.3.58.6:30303").is_err());
+    }
+
+    #[test]
+    fn reload_modified_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("allowlist");
+        std::fs::write(&path, format!("{:x}", PeerId::with_last_byte(1))).unwrap();
+
+        let mut allowlist = PeerAllowlist::from_file(&path).unwrap();
+        assert!(allowlist.contains(&PeerId::with_last_byte(1)));
+        assert!(!allowlist.reload_if_modified().unwrap());
+
+        std::fs::write(&path, format!("{:x}", PeerId::with_last_byte(2))).unwrap();
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + std::time::Duration::from_secs(1)).unwrap();
+        assert!(allowlist.reload_if_modified().unwrap());
+        assert!(!allowlist.contains(&PeerId::with_last_byte(1)));
+        assert!(allowlist.contains(&PeerId::with_last_byte(2)));
+    }
+}
@@ -3,7 +3,7 @@
 use std::{
     collections::HashSet,
     io::{self, ErrorKind},
-    path::Path,
+    path::{Path, PathBuf},
     time::Duration,
 };
 
@@ -11,7 +11,7 @@ use reth_net_banlist::BanList;
 use reth_network_peers::{NodeRecord, TrustedPeer};
 use tracing::info;
 
-use crate::{BackoffKind, ReputationChangeWeights};
+use crate::{BackoffKind, PeerAllowlist, ReputationChangeWeights};
 
 /// Maximum number of available slots for outbound sessions.
 pub const DEFAULT_MAX_COUNT_PEERS_OUTBOUND: u32 = 100;
@@ -69,6 +69,16 @@ impl PeerBackoffDurations {
         now + backoff_time.min(self.max)
     }
 
+    /// Returns the duration to wait before redialing a trusted or static peer after the given
+    /// number of consecutive failed connection attempts.
+    ///
+    /// The duration starts at the low backoff duration and doubles with every failed attempt,
+    /// capped by the configured maximum backoff duration.
+    pub fn redial_backoff(&self, failed_dials: u32) -> Duration {
+        let exponent = failed_dials.saturating_sub(1).min(31);
+        self.low.saturating_mul(1 << exponent).min(self.max)
+    }
+
     /// Returns durations for testing.
     #[cfg(any(test, feature = "test-utils"))]
     pub const fn test() -> Self {
@@ -118,6 +128,35 @@ impl Default for ConnectionsConfig {
     }
 }
 
+/// Config of the watchdog that detects a collapsed number of connected peers.
+///
+/// The network is degraded while it has at most [`Self::min_peers`] connected peers. Once it stays
+/// degraded for [`Self::stall_timeout`], it's stalled and discovery is escalated every
+/// [`Self::escalation_interval`], until the number of peers recovers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PeerCountWatchdogConfig {
+    /// The maximum number of connected peers of a degraded network.
+    pub min_peers: usize,
+    /// How long the network can stay degraded before discovery is escalated.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub stall_timeout: Duration,
+    /// How often discovery is escalated while the network stays stalled.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub escalation_interval: Duration,
+}
+
+impl Default for PeerCountWatchdogConfig {
+    fn default() -> Self {
+        Self {
+            min_peers: 1,
+            stall_timeout: Duration::from_secs(60),
+            escalation_interval: Duration::from_secs(30),
+        }
+    }
+}
+
 /// Config type for initiating a `PeersManager` instance.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -131,6 +170,17 @@ pub struct PeersConfig {
     /// Connect to or accept from trusted nodes only?
     #[cfg_attr(feature = "serde", serde(alias = "connect_trusted_nodes_only"))]
     pub trusted_nodes_only: bool,
+    /// The file the trusted nodes added at runtime are persisted to, see
+    /// [`Self::with_trusted_nodes_from_file`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub trusted_nodes_file: Option<PathBuf>,
+    /// Trusted nodes added at runtime by a previous run, restored from the
+    /// [`Self::trusted_nodes_file`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub persisted_trusted_nodes: HashSet<NodeRecord>,
+    /// If set, only the peers in the allowlist are dialed or accepted, regardless of whether
+    /// they're trusted.
+    pub allowlist: Option<PeerAllowlist>,
     /// Maximum number of backoff attempts before we give up on a peer and dropping.
     ///
     /// The max time spent of a peer before it's removed from the set is determined by the
@@ -163,6 +213,8 @@ pub struct PeersConfig {
     /// This acts as an IP based rate limit.
     #[cfg_attr(feature = "serde", serde(default, with = "humantime_serde"))]
     pub incoming_ip_throttle_duration: Duration,
+    /// When to escalate discovery because the number of connected peers collapsed.
+    pub watchdog: PeerCountWatchdogConfig,
 }
 
 impl Default for PeersConfig {
@@ -177,9 +229,13 @@ impl Default for PeersConfig {
             backoff_durations: Default::default(),
             trusted_nodes: Default::default(),
             trusted_nodes_only: false,
+            trusted_nodes_file: None,
+            persisted_trusted_nodes: Default::default(),
+            allowlist: None,
             basic_nodes: Default::default(),
             max_backoff_count: 5,
             incoming_ip_throttle_duration: INBOUND_IP_THROTTLE_DURATION,
+            watchdog: Default::default(),
         }
     }
 }
@@ -249,6 +305,12 @@ impl PeersConfig {
         self
     }
 
+    /// Only dial and accept the peers in the given allowlist.
+    pub fn with_allowlist(mut self, allowlist: Option<PeerAllowlist>) -> Self {
+        self.allowlist = allowlist;
+        self
+    }
+
     /// Nodes available at launch.
     pub fn with_basic_nodes(mut self, nodes: HashSet<NodeRecord>) -> Self {
         self.basic_nodes = nodes;
@@ -276,6 +338,12 @@ impl PeersConfig {
         self
     }
 
+    /// Configures when to escalate discovery because the number of connected peers collapsed.
+    pub const fn with_watchdog(mut self, watchdog: PeerCountWatchdogConfig) -> Self {
+        self.watchdog = watchdog;
+        self
+    }
+
     /// Returns the maximum number of peers, inbound and outbound.
     pub const fn max_peers(&self) -> usize {
         self.connection_info.max_outbound + self.connection_info.max_inbound
@@ -297,6 +365,28 @@ impl PeersConfig {
         Ok(self.with_basic_nodes(nodes))
     }
 
+    /// Read from file the trusted nodes added at runtime by a previous run, in addition to the
+    /// configured trusted nodes. Ignored if None.
+    ///
+    /// The trusted nodes added or removed at runtime, e.g. via `admin_addTrustedPeer`, are
+    /// persisted to the same file. The configured [`Self::trusted_nodes`] are never written to it,
+    /// so they aren't restored once they're removed from the configuration.
+    pub fn with_trusted_nodes_from_file(
+        mut self,
+        optional_file: Option<impl AsRef<Path>>,
+    ) -> Result<Self, io::Error> {
+        let Some(file_path) = optional_file else { return Ok(self) };
+        self.trusted_nodes_file = Some(file_path.as_ref().to_path_buf());
+        let reader = match std::fs::File::open(file_path.as_ref()) {
+            Ok(file) => io::BufReader::new(file),
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(self),
+            Err(e) => Err(e)?,
+        };
+        info!(target: "net::peers", file = %file_path.as_ref().display(), "Loading saved trusted peers");
+        self.persisted_trusted_nodes = serde_json::from_reader(reader)?;
+        Ok(self)
+    }
+
     /// Returns settings for testing
     #[cfg(any(test, feature = "test-utils"))]
     pub fn test() -> Self {
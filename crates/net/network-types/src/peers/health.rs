@@ -0,0 +1,85 @@
+//! Health of the connection to a trusted or static peer.
+
+/// Represents the health of the connection to a peer.
+///
+/// This is tracked for trusted and static peers, which are redialed with an exponential backoff
+/// if the connection is lost.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct PeerHealth {
+    /// The current status of the peer.
+    pub status: PeerHealthStatus,
+    /// Number of consecutive failed connection attempts since the last established session.
+    pub failed_dials: u32,
+}
+
+// === impl PeerHealth ===
+
+impl PeerHealth {
+    /// Records an established session.
+    #[inline]
+    pub const fn on_connected(&mut self) {
+        self.status = PeerHealthStatus::Connected;
+        self.failed_dials = 0;
+    }
+
+    /// Records a closed session.
+    #[inline]
+    pub const fn on_disconnected(&mut self) {
+        self.status = PeerHealthStatus::Disconnected;
+    }
+
+    /// Records a failed connection attempt.
+    ///
+    /// A protocol mismatch means that the peer does not support our protocols or is on another
+    /// network.
+    #[inline]
+    pub const fn on_failed_dial(&mut self, protocol_mismatch: bool) {
+        self.status = if protocol_mismatch {
+            PeerHealthStatus::ProtocolMismatch
+        } else {
+            PeerHealthStatus::Unreachable
+        };
+        self.failed_dials = self.failed_dials.saturating_add(1);
+    }
+
+    /// Returns `true` if there is an active session to the peer.
+    #[inline]
+    pub const fn is_connected(&self) -> bool {
+        matches!(self.status, PeerHealthStatus::Connected)
+    }
+}
+
+/// Represents the status of the connection to a peer.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum PeerHealthStatus {
+    /// Not connected currently.
+    #[default]
+    Disconnected,
+    /// Connected via an active session.
+    Connected,
+    /// The last connection attempt failed, the peer is redialed after a backoff.
+    Unreachable,
+    /// The last connection attempt failed because the peer does not support our protocols or is
+    /// on another network, the peer is redialed after a backoff.
+    ProtocolMismatch,
+}
+
+// === impl PeerHealthStatus ===
+
+impl PeerHealthStatus {
+    /// Returns the name of the status.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Disconnected => "disconnected",
+            Self::Connected => "connected",
+            Self::Unreachable => "unreachable",
+            Self::ProtocolMismatch => "protocolMismatch",
+        }
+    }
+}
+
+impl core::fmt::Display for PeerHealthStatus {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
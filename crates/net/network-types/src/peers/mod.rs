@@ -1,18 +1,22 @@
 pub mod addr;
+pub mod allowlist;
 pub mod config;
+pub mod health;
 pub mod kind;
+pub mod quality;
 pub mod reputation;
 pub mod state;
 
-pub use config::{ConnectionsConfig, PeersConfig};
+pub use allowlist::PeerAllowlist;
+pub use config::{ConnectionsConfig, PeerCountWatchdogConfig, PeersConfig};
 pub use reputation::{Reputation, ReputationChange, ReputationChangeKind, ReputationChangeWeights};
 
 use alloy_eip2124::ForkId;
 use tracing::trace;
 
 use crate::{
-    is_banned_reputation, PeerAddr, PeerConnectionState, PeerKind, ReputationChangeOutcome,
-    DEFAULT_REPUTATION,
+    is_banned_reputation, PeerAddr, PeerConnectionState, PeerHealth, PeerKind,
+    ReputationChangeOutcome, DEFAULT_REPUTATION,
 };
 
 /// Tracks info about a single peer.
@@ -35,6 +39,8 @@ pub struct Peer {
     /// Counts number of times the peer was backed off due to a severe
     /// [`BackoffKind`](crate::BackoffKind).
     pub severe_backoff_counter: u8,
+    /// Health of the connection to the peer, tracked for trusted and static peers.
+    pub health: PeerHealth,
 }
 
 // === impl Peer ===
@@ -66,6 +72,7 @@ impl Peer {
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            health: Default::default(),
         }
     }
 
@@ -0,0 +1,122 @@
+//! Quality of the data served by a peer.
+
+/// The minimum share of valid responses a peer must serve, below which it's deprioritized.
+pub const MIN_RESPONSE_VALIDITY_RATE: f64 = 0.5;
+
+/// The number of responses that must be scored before the validity rate of a peer is considered.
+pub const MIN_SCORED_RESPONSES: u32 = 8;
+
+/// The number of scored responses after which the scores are halved, so recent responses weigh
+/// more than older ones.
+const RESPONSE_SCORE_WINDOW: u32 = 64;
+
+/// Tracks the validity of the responses served by a peer, e.g. bad block bodies or mismatched
+/// data.
+///
+/// Unlike the reputation, which bans peers that misbehave repeatedly, this is used to prefer peers
+/// that serve valid data when assigning requests.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResponseQuality {
+    /// Number of valid responses.
+    valid: u32,
+    /// Number of invalid responses.
+    invalid: u32,
+}
+
+// === impl ResponseQuality ===
+
+impl ResponseQuality {
+    /// Records a valid response.
+    pub const fn on_valid_response(&mut self) {
+        self.valid += 1;
+        self.decay();
+    }
+
+    /// Records an invalid response.
+    pub const fn on_invalid_response(&mut self) {
+        self.invalid += 1;
+        self.decay();
+    }
+
+    /// Rescores a response that was recorded as valid as invalid, e.g. because its data failed
+    /// validation after it was received.
+    pub const fn on_valid_response_invalidated(&mut self) {
+        if self.valid > 0 {
+            self.valid -= 1;
+            self.invalid += 1;
+        }
+    }
+
+    /// Returns the number of scored responses.
+    pub const fn scored_responses(&self) -> u32 {
+        self.valid + self.invalid
+    }
+
+    /// Returns the share of valid responses, `1.0` if no response was scored yet.
+    pub fn validity_rate(&self) -> f64 {
+        if self.scored_responses() == 0 {
+            return 1.0
+        }
+        self.valid as f64 / self.scored_responses() as f64
+    }
+
+    /// Returns `true` if enough responses were scored and the validity rate is below
+    /// [`MIN_RESPONSE_VALIDITY_RATE`].
+    pub fn is_poor(&self) -> bool {
+        self.scored_responses() >= MIN_SCORED_RESPONSES &&
+            self.validity_rate() < MIN_RESPONSE_VALIDITY_RATE
+    }
+
+    const fn decay(&mut self) {
+        if self.scored_responses() >= RESPONSE_SCORE_WINDOW {
+            self.valid /= 2;
+            self.invalid /= 2;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poor_quality_needs_enough_responses() {
+        let mut quality = ResponseQuality::default();
+        for _ in 0..MIN_SCORED_RESPONSES - 1 {
+            quality.on_invalid_response();
+        }
+        assert!(!quality.is_poor());
+
+        quality.on_invalid_response();
+        assert!(quality.is_poor());
+    }
+
+    #[test]
+    fn recent_responses_weigh_more() {
+        let mut quality = ResponseQuality::default();
+        for _ in 0..RESPONSE_SCORE_WINDOW - 1 {
+            quality.on_invalid_response();
+        }
+        assert!(quality.is_poor());
+
+        // the old invalid responses decay while the peer serves valid data
+        for _ in 0..RESPONSE_SCORE_WINDOW {
+            quality.on_valid_response();
+        }
+        assert!(!quality.is_poor());
+        assert!(quality.scored_responses() < RESPONSE_SCORE_WINDOW);
+    }
+
+    #[test]
+    fn invalidated_response_is_scored_once() {
+        let mut quality = ResponseQuality::default();
+        quality.on_valid_response();
+        quality.on_valid_response_invalidated();
+        assert_eq!(quality.scored_responses(), 1);
+        assert_eq!(quality.validity_rate(), 0.0);
+
+        // nothing to rescore
+        quality.on_valid_response_invalidated();
+        assert_eq!(quality.scored_responses(), 1);
+    }
+}
@@ -12,7 +12,8 @@ use reth_discv5::NetworkStackId;
 use reth_dns_discovery::DnsDiscoveryConfig;
 use reth_eth_wire::{
     handshake::{EthHandshake, EthRlpxHandshake},
-    EthNetworkPrimitives, HelloMessage, HelloMessageWithProtocols, NetworkPrimitives, Status,
+    EthNetworkPrimitives, HelloMessage, HelloMessageWithProtocols, NetworkPrimitives,
+    UnifiedStatus,
 };
 use reth_ethereum_forks::{ForkFilter, Head};
 use reth_network_peers::{mainnet_nodes, pk2id, sepolia_nodes, PeerId, TrustedPeer};
@@ -37,7 +38,7 @@ pub struct NetworkConfig<C, N: NetworkPrimitives = EthNetworkPrimitives> {
     /// The client type that can interact with the chain.
     ///
     /// This type is used to fetch the block number after we established a session and received the
-    /// [Status] block hash.
+    /// [`UnifiedStatus`] block hash.
     pub client: C,
     /// The node's secret key, from which the node's identity is derived.
     pub secret_key: SecretKey,
@@ -73,7 +74,7 @@ pub struct NetworkConfig<C, N: NetworkPrimitives = EthNetworkPrimitives> {
     /// The executor to use for spawning tasks.
     pub executor: Box<dyn TaskSpawner>,
     /// The `Status` message to send to peers at the beginning.
-    pub status: Status,
+    pub status: UnifiedStatus,
     /// Sets the hello message for the p2p handshake in `RLPx`
     pub hello_message: HelloMessageWithProtocols,
     /// Additional protocols to announce and handle in `RLPx`
@@ -89,6 +90,15 @@ pub struct NetworkConfig<C, N: NetworkPrimitives = EthNetworkPrimitives> {
     /// This can be overridden to support custom handshake logic via the
     /// [`NetworkConfigBuilder`].
     pub handshake: Arc<dyn EthRlpxHandshake>,
+    /// The relay to reserve a slot at and to dial peers through if dialing them directly fails,
+    /// see [`relay`](crate::relay).
+    ///
+    /// This is experimental.
+    pub relay: Option<SocketAddr>,
+    /// The address to run a [`RelayServer`](crate::relay::RelayServer) for other nodes on.
+    ///
+    /// This is experimental.
+    pub relay_server_addr: Option<SocketAddr>,
 }
 
 // === impl NetworkConfig ===
@@ -205,6 +215,8 @@ pub struct NetworkConfigBuilder<N: NetworkPrimitives = EthNetworkPrimitives> {
     extra_protocols: RlpxSubProtocols,
     /// Head used to start set for the fork filter and status.
     head: Option<Head>,
+    /// The earliest block that can be served, announced in the status.
+    earliest_block: Option<u64>,
     /// Whether tx gossip is disabled
     tx_gossip_disabled: bool,
     /// The block importer type
@@ -216,6 +228,10 @@ pub struct NetworkConfigBuilder<N: NetworkPrimitives = EthNetworkPrimitives> {
     /// The Ethereum P2P handshake, see also:
     /// <https://github.com/ethereum/devp2p/blob/master/rlpx.md#initial-handshake>.
     handshake: Arc<dyn EthRlpxHandshake>,
+    /// The relay for NAT'd connectivity.
+    relay: Option<SocketAddr>,
+    /// The address to run a relay for other nodes on.
+    relay_server_addr: Option<SocketAddr>,
 }
 
 impl NetworkConfigBuilder<EthNetworkPrimitives> {
@@ -251,11 +267,14 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             hello_message: None,
             extra_protocols: Default::default(),
             head: None,
+            earliest_block: None,
             tx_gossip_disabled: false,
             block_import: None,
             transactions_manager_config: Default::default(),
             nat: None,
             handshake: Arc::new(EthHandshake::default()),
+            relay: None,
+            relay_server_addr: None,
         }
     }
 
@@ -296,7 +315,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
 
     /// Sets the highest synced block.
     ///
-    /// This is used to construct the appropriate [`ForkFilter`] and [`Status`] message.
+    /// This is used to construct the appropriate [`ForkFilter`] and [`UnifiedStatus`] message.
     ///
     /// If not set, this defaults to the genesis specified by the current chain specification.
     pub const fn set_head(mut self, head: Head) -> Self {
@@ -304,6 +323,17 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         self
     }
 
+    /// Sets the earliest block that can be served, e.g. because the history below it has been
+    /// expired.
+    ///
+    /// This is announced in the eth/69 [`UnifiedStatus`] message.
+    ///
+    /// If not set, this defaults to the genesis block.
+    pub const fn set_earliest_block(mut self, earliest_block: u64) -> Self {
+        self.earliest_block = Some(earliest_block);
+        self
+    }
+
     /// Sets the `HelloMessage` to send when connecting to peers.
     ///
     /// ```
@@ -564,6 +594,23 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         self
     }
 
+    /// Sets the relay to reserve a slot at and to dial peers through if dialing them directly
+    /// fails, see [`relay`](crate::relay).
+    ///
+    /// This is experimental.
+    pub const fn relay(mut self, relay: Option<SocketAddr>) -> Self {
+        self.relay = relay;
+        self
+    }
+
+    /// Sets the address to run a [`RelayServer`](crate::relay::RelayServer) for other nodes on.
+    ///
+    /// This is experimental.
+    pub const fn relay_server_addr(mut self, addr: Option<SocketAddr>) -> Self {
+        self.relay_server_addr = addr;
+        self
+    }
+
     /// Consumes the type and creates the actual [`NetworkConfig`]
     /// for the given client type that can interact with the chain.
     ///
@@ -591,11 +638,14 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             hello_message,
             extra_protocols,
             head,
+            earliest_block,
             tx_gossip_disabled,
             block_import,
             transactions_manager_config,
             nat,
             handshake,
+            relay,
+            relay_server_addr,
         } = self;
 
         discovery_v5_builder = discovery_v5_builder.map(|mut builder| {
@@ -621,8 +671,9 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             total_difficulty: chain_spec.genesis().difficulty,
         });
 
-        // set the status
-        let status = Status::spec_builder(&chain_spec, &head).build();
+        // set the status, history below the earliest block may have been expired
+        let status =
+            UnifiedStatus::spec_status(&chain_spec, &head, earliest_block.unwrap_or_default());
 
         // set a fork filter based on the chain spec and head
         let fork_filter = chain_spec.fork_filter(head);
@@ -664,6 +715,8 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             transactions_manager_config,
             nat,
             handshake,
+            relay,
+            relay_server_addr,
         }
     }
 }
@@ -5,33 +5,44 @@ use crate::{
     error::{NetworkError, ServiceKind},
 };
 use enr::Enr;
-use futures::StreamExt;
-use reth_discv4::{DiscoveryUpdate, Discv4, Discv4Config};
+use futures::{FutureExt, StreamExt};
+use reth_discv4::{DiscoveryUpdate, Discv4, Discv4Config, Discv4Service};
 use reth_discv5::{DiscoveredPeer, Discv5};
 use reth_dns_discovery::{
     DnsDiscoveryConfig, DnsDiscoveryHandle, DnsDiscoveryService, DnsNodeRecordUpdate, DnsResolver,
 };
 use reth_ethereum_forks::{EnrForkIdEntry, ForkId};
+use reth_net_nat::{
+    MappedPort, NatResolver, PortMappingProtocol, PortMappingRequest, ResolveNatInterval,
+};
 use reth_network_api::{DiscoveredEvent, DiscoveryEvent};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::PeerAddr;
 use secp256k1::SecretKey;
 use std::{
     collections::VecDeque,
+    io,
     net::{IpAddr, SocketAddr},
     pin::Pin,
     sync::Arc,
     task::{ready, Context, Poll},
+    time::Duration,
 };
 use tokio::{sync::mpsc, task::JoinHandle};
 use tokio_stream::{wrappers::ReceiverStream, Stream};
-use tracing::trace;
+use tracing::{info, trace, warn};
 
 /// Default max capacity for cache of discovered peers.
 ///
 /// Default is 10 000 peers.
 pub const DEFAULT_MAX_CAPACITY_DISCOVERED_PEERS_CACHE: u32 = 10_000;
 
+/// Interval at which the port mappings are renewed with the NAT gateway.
+const PORT_MAPPING_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// How long to wait for the NAT gateway to remove the port mappings on shutdown.
+const UNMAP_PORTS_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// An abstraction over the configured discovery protocol.
 ///
 /// Listens for new discovered nodes and emits events for discovered nodes and their
@@ -50,10 +61,25 @@ pub struct Discovery {
     discv4_updates: Option<ReceiverStream<DiscoveryUpdate>>,
     /// The handle to the spawned discv4 service
     _discv4_service: Option<JoinHandle<()>>,
+    /// The address the discv4 service binds to, if it's started temporarily.
+    discovery_v4_addr: SocketAddr,
+    /// The secret key the discv4 service is started with, if it's started temporarily.
+    secret_key: SecretKey,
+    /// The task binding the temporary discv4 service, see [`Self::start_temporary_discv4`].
+    pending_discv4: Option<JoinHandle<io::Result<(Discv4, Discv4Service)>>>,
+    /// Whether the running discv4 service was started temporarily.
+    temporary_discv4: bool,
     /// Handler to interact with the Discovery v5 service
     discv5: Option<Discv5>,
     /// All KAD table updates from the discv5 service.
     discv5_updates: Option<ReceiverStream<discv5::Event>>,
+    /// The port of the discv5 socket, if discv5 is enabled.
+    discv5_port: Option<u16>,
+    /// Renews the mappings of the `RLPx` and discovery ports with the NAT gateway, if the
+    /// configured resolver supports port mapping.
+    port_mapping: Option<ResolveNatInterval>,
+    /// The port mappings currently advertised in the local records.
+    mapped_ports: Vec<MappedPort>,
     /// Handler to interact with the DNS discovery service
     _dns_discovery: Option<DnsDiscoveryHandle>,
     /// Updates from the DNS discovery service.
@@ -78,11 +104,26 @@ impl Discovery {
         discv4_config: Option<Discv4Config>,
         discv5_config: Option<reth_discv5::Config>, // contains discv5 listen address
         dns_discovery_config: Option<DnsDiscoveryConfig>,
+        nat: Option<NatResolver>,
     ) -> Result<Self, NetworkError> {
         // setup discv4 with the discovery address and tcp port
         let local_enr =
             NodeRecord::from_secret_key(discovery_v4_addr, &sk).with_tcp_port(tcp_addr.port());
 
+        // forward the `RLPx` port and the ports of all enabled discovery services
+        let discv5_port = discv5_config.as_ref().map(|config| config.discovery_socket().port());
+        let port_mapping = nat.filter(NatResolver::is_port_mapping).map(|resolver| {
+            let mut port_mappings = vec![PortMappingRequest::tcp(tcp_addr.port())];
+            if discv4_config.is_some() {
+                port_mappings.push(PortMappingRequest::udp(discovery_v4_addr.port()));
+            }
+            if let Some(port) = discv5_port.filter(|port| *port != discovery_v4_addr.port()) {
+                port_mappings.push(PortMappingRequest::udp(port));
+            }
+            ResolveNatInterval::interval(resolver, PORT_MAPPING_INTERVAL)
+                .with_port_mappings(port_mappings)
+        });
+
         let discv4_future = async {
             let Some(disc_config) = discv4_config else { return Ok((None, None, None)) };
             let (discv4, mut discv4_service) =
@@ -127,8 +168,15 @@ impl Discovery {
             discv4,
             discv4_updates,
             _discv4_service,
+            discovery_v4_addr,
+            secret_key: sk,
+            pending_discv4: None,
+            temporary_discv4: false,
             discv5,
             discv5_updates,
+            discv5_port,
+            port_mapping,
+            mapped_ports: Vec::new(),
             discovered_nodes: LruMap::new(DEFAULT_MAX_CAPACITY_DISCOVERED_PEERS_CACHE),
             queued_events: Default::default(),
             _dns_disc_service,
@@ -194,6 +242,121 @@ impl Discovery {
         }
     }
 
+    /// Starts a temporary discv4 service with the given boot nodes, if only discv5 is running.
+    ///
+    /// This is a fallback for nodes that only run discv5 and lost their peers, since discv4 has
+    /// far more nodes. The service is stopped with [`Self::stop_temporary_discv4`] once the peers
+    /// recovered.
+    pub(crate) fn start_temporary_discv4(&mut self, boot_nodes: Vec<NodeRecord>, fork_id: ForkId) {
+        if self.discv5.is_none() || self.discv4.is_some() || self.pending_discv4.is_some() {
+            return
+        }
+
+        let mut config = Discv4Config::builder();
+        config.add_boot_nodes(boot_nodes).add_eip868_pair("eth", fork_id);
+        let config = config.build();
+        let (addr, local_enr, sk) = (self.discovery_v4_addr, self.local_enr, self.secret_key);
+        info!(target: "net::discovery", %addr, "Starting temporary discv4");
+        self.pending_discv4 =
+            Some(tokio::spawn(async move { Discv4::bind(addr, local_enr, sk, config).await }));
+    }
+
+    /// Stops the discv4 service started by [`Self::start_temporary_discv4`], if any.
+    pub(crate) fn stop_temporary_discv4(&mut self) {
+        if let Some(pending) = self.pending_discv4.take() {
+            pending.abort();
+        }
+        if !self.temporary_discv4 {
+            return
+        }
+
+        info!(target: "net::discovery", "Stopping temporary discv4");
+        if let Some(discv4) = self.discv4.take() {
+            discv4.terminate();
+        }
+        self.discv4_updates = None;
+        self._discv4_service = None;
+        self.temporary_discv4 = false;
+    }
+
+    /// Installs the temporary discv4 service once it's bound.
+    fn poll_pending_discv4(&mut self, cx: &mut Context<'_>) {
+        let Some(Poll::Ready(result)) =
+            self.pending_discv4.as_mut().map(|task| task.poll_unpin(cx))
+        else {
+            return
+        };
+        self.pending_discv4 = None;
+
+        match result.map_err(io::Error::other).and_then(|result| result) {
+            Ok((discv4, mut service)) => {
+                self.discv4_updates = Some(service.update_stream());
+                self._discv4_service = Some(service.spawn());
+                if !self.mapped_ports.is_empty() {
+                    discv4.set_mapped_ports(self.discv4_mapped_ports());
+                }
+                self.discv4 = Some(discv4);
+                self.temporary_discv4 = true;
+            }
+            Err(err) => {
+                warn!(target: "net::discovery", %err, "Failed to start temporary discv4")
+            }
+        }
+    }
+
+    /// Returns the advertised port mappings of the `RLPx` port and the discv4 socket.
+    fn discv4_mapped_ports(&self) -> Vec<MappedPort> {
+        let udp_port = self.discovery_v4_addr.port();
+        self.mapped_ports
+            .iter()
+            .filter(|port| {
+                port.protocol == PortMappingProtocol::Tcp || port.internal_port == udp_port
+            })
+            .copied()
+            .collect()
+    }
+
+    /// Advertises the external ports the NAT gateway forwards in the local records of all
+    /// discovery services.
+    fn on_mapped_ports(&mut self, mapped_ports: Vec<MappedPort>) {
+        if mapped_ports.is_empty() || mapped_ports == self.mapped_ports {
+            return
+        }
+        self.mapped_ports = mapped_ports;
+
+        if let Some(discv4) = &self.discv4 {
+            discv4.set_mapped_ports(self.discv4_mapped_ports());
+        }
+        for port in &self.mapped_ports {
+            match port.protocol {
+                PortMappingProtocol::Tcp => {
+                    self.local_enr.tcp_port = port.external_port;
+                    if let Some(discv5) = &self.discv5 {
+                        discv5.set_tcp_port(port.external_port);
+                    }
+                }
+                PortMappingProtocol::Udp if Some(port.internal_port) == self.discv5_port => {
+                    if let Some(discv5) = &self.discv5 {
+                        discv5.set_udp_port(port.external_port);
+                    }
+                }
+                PortMappingProtocol::Udp => {}
+            }
+        }
+    }
+
+    /// Removes the port mappings from the NAT gateway, if any.
+    ///
+    /// This should be called on shutdown, otherwise the gateway keeps forwarding the ports until
+    /// the leases expire.
+    pub(crate) async fn unmap_ports(&mut self) {
+        let Some(port_mapping) = self.port_mapping.as_mut() else { return };
+        if tokio::time::timeout(UNMAP_PORTS_TIMEOUT, port_mapping.unmap_ports()).await.is_err() {
+            warn!(target: "net::discovery", "Timed out removing port mappings");
+        }
+        self.port_mapping = None;
+    }
+
     /// Returns discv5 handle.
     pub fn discv5(&self) -> Option<Discv5> {
         self.discv5.clone()
@@ -255,6 +418,8 @@ impl Discovery {
                 return Poll::Ready(event)
             }
 
+            self.poll_pending_discv4(cx);
+
             // drain the discv4 update stream
             while let Some(Poll::Ready(Some(update))) =
                 self.discv4_updates.as_mut().map(|updates| updates.poll_next_unpin(cx))
@@ -262,6 +427,18 @@ impl Discovery {
                 self.on_discv4_update(update)
             }
 
+            // renew the port mappings with the NAT gateway
+            if let Some(Poll::Ready(_)) =
+                self.port_mapping.as_mut().map(|port_mapping| port_mapping.poll_tick(cx))
+            {
+                let mapped_ports = self
+                    .port_mapping
+                    .as_ref()
+                    .map(|port_mapping| port_mapping.mapped_ports().to_vec())
+                    .unwrap_or_default();
+                self.on_mapped_ports(mapped_ports);
+            }
+
             // drain the discv5 update stream
             while let Some(Poll::Ready(Some(update))) =
                 self.discv5_updates.as_mut().map(|updates| updates.poll_next_unpin(cx))
@@ -325,8 +502,15 @@ impl Discovery {
             discv4_updates: Default::default(),
             discv5: None,
             discv5_updates: None,
+            discv5_port: None,
+            port_mapping: None,
+            mapped_ports: Vec::new(),
             queued_events: Default::default(),
             _discv4_service: Default::default(),
+            discovery_v4_addr: SocketAddr::from((std::net::Ipv4Addr::UNSPECIFIED, 0)),
+            secret_key: SecretKey::new(&mut rand::thread_rng()),
+            pending_discv4: None,
+            temporary_discv4: false,
             _dns_discovery: None,
             dns_discovery_updates: None,
             _dns_disc_service: None,
@@ -354,6 +538,7 @@ mod tests {
             Default::default(),
             None,
             Default::default(),
+            None,
         )
         .await
         .unwrap();
@@ -384,6 +569,7 @@ mod tests {
             Some(discv4_config),
             Some(discv5_config),
             None,
+            None,
         )
         .await
         .expect("should build discv5 with discv4 downgrade")
@@ -17,6 +17,8 @@ pub enum ServiceKind {
     Listener(SocketAddr),
     /// Discovery service.
     Discovery(SocketAddr),
+    /// Relay service.
+    Relay(SocketAddr),
 }
 
 impl ServiceKind {
@@ -25,6 +27,7 @@ impl ServiceKind {
         match self {
             Self::Listener(_) => "--port",
             Self::Discovery(_) => "--discovery.port",
+            Self::Relay(_) => "--relay-server",
         }
     }
 }
@@ -34,6 +37,7 @@ impl fmt::Display for ServiceKind {
         match self {
             Self::Listener(addr) => write!(f, "{addr} (listener service)"),
             Self::Discovery(addr) => write!(f, "{addr} (discovery service)"),
+            Self::Relay(addr) => write!(f, "{addr} (relay service)"),
         }
     }
 }
@@ -165,6 +165,7 @@ where
 
         let mut total_bytes = 0;
 
+        // Unknown blocks and blocks with expired bodies (EIP-4444) end the response.
         for hash in request.0 {
             if let Some(block) = self.client.block_by_hash(hash).unwrap_or_default() {
                 let body = block.into_body();
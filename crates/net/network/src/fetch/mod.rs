@@ -15,7 +15,7 @@ use reth_network_p2p::{
     priority::Priority,
 };
 use reth_network_peers::PeerId;
-use reth_network_types::ReputationChangeKind;
+use reth_network_types::{ReputationChangeKind, ResponseQuality};
 use std::{
     collections::{HashMap, VecDeque},
     sync::{
@@ -54,6 +54,10 @@ pub struct StateFetcher<N: NetworkPrimitives = EthNetworkPrimitives> {
     download_requests_rx: UnboundedReceiverStream<DownloadRequest<N>>,
     /// Sender for download requests, used to detach a [`FetchClient`]
     download_requests_tx: UnboundedSender<DownloadRequest<N>>,
+    /// Receiver for peers that were reported for invalid responses by a [`FetchClient`]
+    bad_responses_rx: UnboundedReceiverStream<PeerId>,
+    /// Sender for peers with invalid responses, used to detach a [`FetchClient`]
+    bad_responses_tx: UnboundedSender<PeerId>,
 }
 
 // === impl StateSyncer ===
@@ -61,6 +65,7 @@ pub struct StateFetcher<N: NetworkPrimitives = EthNetworkPrimitives> {
 impl<N: NetworkPrimitives> StateFetcher<N> {
     pub(crate) fn new(peers_handle: PeersHandle, num_active_peers: Arc<AtomicUsize>) -> Self {
         let (download_requests_tx, download_requests_rx) = mpsc::unbounded_channel();
+        let (bad_responses_tx, bad_responses_rx) = mpsc::unbounded_channel();
         Self {
             inflight_headers_requests: Default::default(),
             inflight_bodies_requests: Default::default(),
@@ -70,6 +75,8 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
             queued_requests: Default::default(),
             download_requests_rx: UnboundedReceiverStream::new(download_requests_rx),
             download_requests_tx,
+            bad_responses_rx: UnboundedReceiverStream::new(bad_responses_rx),
+            bad_responses_tx,
         }
     }
 
@@ -89,6 +96,8 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
                 best_number,
                 timeout,
                 last_response_likely_bad: false,
+                quality: ResponseQuality::default(),
+                last_response_scored_valid: false,
             },
         );
     }
@@ -123,6 +132,19 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         false
     }
 
+    /// Records that the last response of the peer failed validation.
+    ///
+    /// The response was scored as valid when it was received, so the score is corrected instead
+    /// of scoring the response a second time. Responses that were already scored as invalid, or
+    /// not scored at all, are left as is.
+    fn on_bad_response(&mut self, peer_id: &PeerId) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            if std::mem::take(&mut peer.last_response_scored_valid) {
+                peer.quality.on_valid_response_invalidated();
+            }
+        }
+    }
+
     /// Invoked when an active session is about to be disconnected.
     pub(crate) fn on_pending_disconnect(&mut self, peer_id: &PeerId) {
         if let Some(peer) = self.peers.get_mut(peer_id) {
@@ -131,14 +153,24 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
     }
 
     /// Returns the _next_ idle peer that's ready to accept a request,
-    /// prioritizing those with the lowest timeout/latency and those that recently responded with
-    /// adequate data.
+    /// prioritizing those that mostly serve valid data, those with the lowest timeout/latency and
+    /// those that recently responded with adequate data.
     fn next_best_peer(&self) -> Option<PeerId> {
         let mut idle = self.peers.iter().filter(|(_, peer)| peer.state.is_idle());
 
         let mut best_peer = idle.next()?;
 
         for maybe_better in idle {
+            // peers that serve mostly invalid data are only used if there are no other peers
+            let (best_is_poor, maybe_better_is_poor) =
+                (best_peer.1.quality.is_poor(), maybe_better.1.quality.is_poor());
+            if best_is_poor != maybe_better_is_poor {
+                if best_is_poor {
+                    best_peer = maybe_better;
+                }
+                continue
+            }
+
             // replace best peer if our current best peer sent us a bad response last time
             if best_peer.1.last_response_likely_bad && !maybe_better.1.last_response_likely_bad {
                 best_peer = maybe_better;
@@ -175,6 +207,11 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
     pub(crate) fn poll(&mut self, cx: &mut Context<'_>) -> Poll<FetchAction> {
         // drain buffered actions first
         loop {
+            // score reported responses first, so they're considered for the next request
+            while let Poll::Ready(Some(peer_id)) = self.bad_responses_rx.poll_next_unpin(cx) {
+                self.on_bad_response(&peer_id);
+            }
+
             let no_peers_available = match self.poll_action() {
                 PollAction::Ready(action) => return Poll::Ready(action),
                 PollAction::NoRequests => false,
@@ -267,6 +304,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
 
         let is_likely_bad_response =
             resp.as_ref().is_some_and(|r| res.is_likely_bad_headers_response(&r.request));
+        let served_valid_data = served_valid_data(&res, is_likely_bad_response);
 
         if let Some(resp) = resp {
             // delegate the response
@@ -276,6 +314,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             // update the peer's response state
             peer.last_response_likely_bad = is_likely_bad_response;
+            peer.score_response(served_valid_data);
 
             // If the peer is still ready to accept new requests, we try to send a followup
             // request immediately.
@@ -297,6 +336,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         res: RequestResult<Vec<N::BlockBody>>,
     ) -> Option<BlockResponseOutcome> {
         let is_likely_bad_response = res.as_ref().map_or(true, |bodies| bodies.is_empty());
+        let served_valid_data = served_valid_data(&res, is_likely_bad_response);
 
         if let Some(resp) = self.inflight_bodies_requests.remove(&peer_id) {
             let _ = resp.response.send(res.map(|b| (peer_id, b).into()));
@@ -304,6 +344,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             // update the peer's response state
             peer.last_response_likely_bad = is_likely_bad_response;
+            peer.score_response(served_valid_data);
 
             if peer.state.on_request_finished() && !is_likely_bad_response {
                 return self.followup_request(peer_id)
@@ -316,6 +357,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
     pub(crate) fn client(&self) -> FetchClient<N> {
         FetchClient {
             request_tx: self.download_requests_tx.clone(),
+            bad_responses_tx: self.bad_responses_tx.clone(),
             peers_handle: self.peers_handle.clone(),
             num_active_peers: Arc::clone(&self.num_active_peers),
         }
@@ -347,12 +389,39 @@ struct Peer {
     /// downloaded), but we still want to avoid requesting from the same peer again if it has the
     /// lowest timeout.
     last_response_likely_bad: bool,
+    /// Tracks the validity of the responses served by the peer.
+    ///
+    /// Peers that mostly serve invalid data are de-ranked for new requests.
+    quality: ResponseQuality,
+    /// Whether the last response of the peer was scored as valid, so that it can be rescored if
+    /// its data is found to be invalid later.
+    last_response_scored_valid: bool,
 }
 
 impl Peer {
     fn timeout(&self) -> u64 {
         self.timeout.load(Ordering::Relaxed)
     }
+
+    /// Scores the data served with a response, if the response is about the data.
+    fn score_response(&mut self, served_valid_data: Option<bool>) {
+        match served_valid_data {
+            Some(true) => self.quality.on_valid_response(),
+            Some(false) => self.quality.on_invalid_response(),
+            None => {}
+        }
+        self.last_response_scored_valid = served_valid_data == Some(true);
+    }
+}
+
+/// Returns whether the response served valid data, or `None` if the request failed for reasons
+/// unrelated to the data, like timeouts or dropped connections.
+fn served_valid_data<T>(res: &RequestResult<T>, is_likely_bad_response: bool) -> Option<bool> {
+    match res {
+        Ok(_) => Some(!is_likely_bad_response),
+        Err(RequestError::BadResponse) => Some(false),
+        Err(_) => None,
+    }
 }
 
 /// Tracks the state of an individual peer
@@ -472,6 +541,8 @@ mod tests {
     use crate::{peers::PeersManager, PeersConfig};
     use alloy_consensus::Header;
     use alloy_primitives::B512;
+    use reth_network_p2p::download::DownloadClient;
+    use reth_network_types::peers::quality::MIN_SCORED_RESPONSES;
     use std::future::poll_fn;
 
     #[tokio::test(flavor = "multi_thread")]
@@ -545,6 +616,42 @@ mod tests {
         assert_eq!(fetcher.next_best_peer(), Some(peer2));
     }
 
+    #[tokio::test]
+    async fn test_poor_quality_peer_deprioritized() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher =
+            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let peer1 = B512::random();
+        let peer2 = B512::random();
+
+        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(10)));
+        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::new(AtomicU64::new(300)));
+        assert_eq!(fetcher.next_best_peer(), Some(peer1));
+
+        // peer1 keeps serving bodies that fail validation
+        let client = fetcher.client();
+        for _ in 0..MIN_SCORED_RESPONSES {
+            fetcher.on_block_bodies_response(peer1, Ok(vec![Default::default()]));
+            client.report_bad_message(peer1);
+            let _ = poll_fn(|cx| Poll::Ready(fetcher.poll(cx))).await;
+        }
+
+        // every response is scored once, as invalid
+        let quality = fetcher.peers[&peer1].quality;
+        assert_eq!(quality.scored_responses(), MIN_SCORED_RESPONSES);
+        assert_eq!(quality.validity_rate(), 0.0);
+        assert_eq!(fetcher.next_best_peer(), Some(peer2));
+
+        // reports without a new response don't score the peer again
+        client.report_bad_message(peer1);
+        let _ = poll_fn(|cx| Poll::Ready(fetcher.poll(cx))).await;
+        assert_eq!(fetcher.peers[&peer1].quality.scored_responses(), MIN_SCORED_RESPONSES);
+
+        // without other peers, peer1 is still used
+        fetcher.on_session_closed(&peer2);
+        assert_eq!(fetcher.next_best_peer(), Some(peer1));
+    }
+
     #[tokio::test]
     async fn test_on_block_headers_response() {
         let manager = PeersManager::new(PeersConfig::default());
@@ -7,7 +7,10 @@ use std::{
     pin::Pin,
     task::{Context, Poll},
 };
-use tokio::net::{TcpListener, TcpStream};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::mpsc,
+};
 
 /// A tcp connection listener.
 ///
@@ -21,6 +24,8 @@ pub struct ConnectionListener {
     /// The active tcp listener for incoming connections.
     #[pin]
     incoming: TcpListenerStream,
+    /// Incoming connections that were relayed to this node, see [`relay`](crate::relay).
+    relayed: Option<mpsc::Receiver<(TcpStream, SocketAddr)>>,
 }
 
 impl ConnectionListener {
@@ -33,12 +38,28 @@ impl ConnectionListener {
 
     /// Creates a new connection listener stream.
     pub(crate) const fn new(listener: TcpListener, local_address: SocketAddr) -> Self {
-        Self { local_address, incoming: TcpListenerStream { inner: listener } }
+        Self { local_address, incoming: TcpListenerStream { inner: listener }, relayed: None }
+    }
+
+    /// Also yields the incoming connections that are relayed to this node.
+    pub(crate) fn with_relayed_connections(
+        mut self,
+        relayed: mpsc::Receiver<(TcpStream, SocketAddr)>,
+    ) -> Self {
+        self.relayed = Some(relayed);
+        self
     }
 
     /// Polls the type to make progress.
     pub fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<ListenerEvent> {
         let this = self.project();
+
+        if let Some(relayed) = this.relayed {
+            if let Poll::Ready(Some((stream, remote_addr))) = relayed.poll_recv(cx) {
+                return Poll::Ready(ListenerEvent::Incoming { stream, remote_addr })
+            }
+        }
+
         match ready!(this.incoming.poll_next(cx)) {
             Some(Ok((stream, remote_addr))) => {
                 if let Err(err) = stream.set_nodelay(true) {
@@ -29,10 +29,12 @@ use crate::{
     peers::PeersManager,
     poll_nested_stream_with_budget,
     protocol::IntoRlpxSubProtocol,
+    relay::{run_relay_reservation, RelayServer, RELAYED_CONNECTIONS_BUFFER},
     session::SessionManager,
     state::NetworkState,
     swarm::{Swarm, SwarmEvent},
     transactions::NetworkTransactionEvent,
+    watchdog::PeerCountWatchdog,
     FetchClient, NetworkBuilder,
 };
 use futures::{Future, StreamExt};
@@ -46,7 +48,7 @@ use reth_network_api::{
     EthProtocolInfo, NetworkEvent, NetworkStatus, PeerInfo, PeerRequest,
 };
 use reth_network_peers::{NodeRecord, PeerId};
-use reth_network_types::ReputationChangeKind;
+use reth_network_types::{PeerAddr, PeerKind, ReputationChangeKind};
 use reth_storage_api::BlockNumReader;
 use reth_tasks::shutdown::GracefulShutdown;
 use reth_tokio_util::EventSender;
@@ -133,6 +135,11 @@ pub struct NetworkManager<N: NetworkPrimitives = EthNetworkPrimitives> {
     /// This is updated via internal events and shared via `Arc` with the [`NetworkHandle`]
     /// Updated by the `NetworkWorker` and loaded by the `NetworkService`.
     num_active_peers: Arc<AtomicUsize>,
+    /// Escalates discovery if the number of connected peers collapses.
+    peer_count_watchdog: PeerCountWatchdog,
+    /// The resolved boot nodes, which are dialed again if the number of connected peers
+    /// collapses.
+    boot_nodes: Vec<NodeRecord>,
     /// Metrics for the Network
     metrics: NetworkMetrics,
     /// Disconnect metrics for the Network
@@ -249,8 +256,11 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             transactions_manager_config: _,
             nat,
             handshake,
+            relay,
+            relay_server_addr,
         } = config;
 
+        let peer_count_watchdog = PeerCountWatchdog::new(peers_config.watchdog);
         let peers_manager = PeersManager::new(peers_config);
         let peers_handle = peers_manager.handle();
 
@@ -273,7 +283,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
 
         if let Some(discv5) = discovery_v5_config.as_mut() {
             // merge configured boot nodes
-            discv5.extend_unsigned_boot_nodes(resolved_boot_nodes)
+            discv5.extend_unsigned_boot_nodes(resolved_boot_nodes.clone())
         }
 
         let discovery = Discovery::new(
@@ -283,6 +293,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             discovery_v4_config,
             discovery_v5_config,
             dns_discovery_config,
+            nat,
         )
         .await?;
         // need to retrieve the addr here since provided port could be `0`
@@ -292,6 +303,26 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
 
         let num_active_peers = Arc::new(AtomicUsize::new(0));
 
+        if let Some(addr) = relay_server_addr {
+            let server = RelayServer::bind(addr)
+                .await
+                .map_err(|err| NetworkError::from_io_error(err, ServiceKind::Relay(addr)))?;
+            executor.spawn(Box::pin(async move {
+                if let Err(err) = server.run().await {
+                    error!(target: "net::relay", %err, "Relay server failed");
+                }
+            }));
+        }
+
+        // keep a reservation at the relay, so that peers can dial this node through it
+        let incoming = if let Some(relay) = relay {
+            let (relayed_tx, relayed_rx) = mpsc::channel(RELAYED_CONNECTIONS_BUFFER);
+            executor.spawn(Box::pin(run_relay_reservation(relay, secret_key, relayed_tx)));
+            incoming.with_relayed_connections(relayed_rx)
+        } else {
+            incoming
+        };
+
         let sessions = SessionManager::new(
             secret_key,
             sessions_config,
@@ -301,6 +332,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             fork_filter,
             extra_protocols,
             handshake,
+            relay,
         );
 
         let state = NetworkState::new(
@@ -330,6 +362,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             discv5,
             event_sender.clone(),
             nat,
+            peer_count_watchdog.subscribe(),
         );
 
         Ok(Self {
@@ -341,6 +374,8 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             to_transactions_manager: None,
             to_eth_request_handler: None,
             num_active_peers,
+            peer_count_watchdog,
+            boot_nodes: resolved_boot_nodes,
             metrics: Default::default(),
             disconnect_metrics: Default::default(),
         })
@@ -519,6 +554,29 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         }
     }
 
+    /// Invoked by the [`PeerCountWatchdog`] when the network is stalled.
+    ///
+    /// The boot nodes are dialed again and added back to the discv4 table, in case they were
+    /// dropped while the node was offline, and a new discv4 lookup is started. If only discv5 is
+    /// running, a temporary discv4 service is started until the peer count recovered.
+    fn on_peer_count_collapsed(&mut self) {
+        for node in &self.boot_nodes {
+            let addr = PeerAddr::new(node.tcp_addr(), Some(node.udp_addr()));
+            self.swarm.state_mut().add_and_connect(node.id, PeerKind::Basic, addr);
+            self.swarm.state_mut().discovery_mut().add_discv4_node(*node);
+        }
+
+        if let Some(discv4) = self.swarm.state_mut().discovery_mut().discv4() {
+            discv4.send_lookup_self();
+        }
+
+        let fork_id = self.swarm.sessions().status().forkid;
+        self.swarm
+            .state_mut()
+            .discovery_mut()
+            .start_temporary_discv4(self.boot_nodes.clone(), fork_id);
+    }
+
     /// Invoked after a `NewBlock` message from the peer was validated
     fn on_block_import_result(&mut self, event: BlockImportEvent<N::Block>) {
         match event {
@@ -647,6 +705,9 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             NetworkHandleMessage::AddTrustedPeerId(peer_id) => {
                 self.swarm.state_mut().add_trusted_peer_id(peer_id);
             }
+            NetworkHandleMessage::SetPeerAllowlist(allowlist) => {
+                self.swarm.state_mut().set_peer_allowlist(allowlist);
+            }
             NetworkHandleMessage::AddPeerAddress(peer, kind, addr) => {
                 // only add peer if we are not shutting down
                 if !self.swarm.is_shutting_down() {
@@ -704,6 +765,9 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                 let peer_ids = self.swarm.state().peers().peers_by_kind(kind);
                 let _ = tx.send(self.get_peer_infos_by_ids(peer_ids));
             }
+            NetworkHandleMessage::GetPeersHealth(tx) => {
+                let _ = tx.send(self.swarm.state().peers().peers_health());
+            }
             NetworkHandleMessage::AddRlpxSubProtocol(proto) => self.add_rlpx_sub_protocol(proto),
             NetworkHandleMessage::GetTransactionsHandle(tx) => {
                 if let Some(ref tx_inner) = self.to_transactions_manager {
@@ -1041,6 +1105,8 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         }
 
         self.perform_network_shutdown();
+        // release the ports forwarded by the NAT gateway, since the node stops listening on them
+        self.swarm.state_mut().discovery_mut().unmap_ports().await;
         let res = shutdown_hook(self);
         drop(graceful_guard);
         res
@@ -1126,6 +1192,14 @@ impl<N: NetworkPrimitives> Future for NetworkManager<N> {
             return Poll::Pending
         }
 
+        // escalate discovery if the number of connected peers collapsed
+        let connected_peers = this.num_active_peers.load(Ordering::Relaxed);
+        if this.peer_count_watchdog.poll(cx, connected_peers).is_ready() {
+            this.on_peer_count_collapsed();
+        } else if this.peer_count_watchdog.is_healthy() {
+            this.swarm.state_mut().discovery_mut().stop_temporary_discv4();
+        }
+
         this.update_poll_metrics(start, poll_durations);
 
         Poll::Pending
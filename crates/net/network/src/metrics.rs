@@ -119,6 +119,12 @@ pub struct TransactionsManagerMetrics {
     /// Total number of times a transaction is sent that is already in the local pool.
     pub(crate) occurrences_transactions_already_in_pool: Counter,
 
+    /* -- Freq duplicate announcements -- */
+    /// Share of the hashes announced by a peer over its session that were already known, i.e.
+    /// pending import, in the local pool, pending fetch or fetched recently. Recorded per peer
+    /// when its session closes.
+    pub(crate) announcement_duplicate_rate: Histogram,
+
     /* ================ POOL IMPORTS ================ */
     /// Number of transactions about to be imported into the pool.
     pub(crate) pending_pool_imports: Gauge,
@@ -212,6 +218,14 @@ pub struct TransactionFetcherMetrics {
     /// [`PooledTransactions`](reth_eth_wire::PooledTransactions) responses, that weren't
     /// requested.
     pub(crate) unsolicited_transactions: Counter,
+    /* ================ HASHES RECENTLY FETCHED ================ */
+    /// Total number of hashes in the cache of recently fetched hashes.
+    pub(crate) hashes_recently_fetched: Gauge,
+    /// Current capacity of the cache of recently fetched hashes, which grows in adaptive mode.
+    pub(crate) capacity_cache_recently_fetched: Gauge,
+    /// Total number of times a hash is announced that was fetched recently, and isn't requested
+    /// again.
+    pub(crate) occurrences_hashes_recently_fetched: Counter,
     /* ================ SEARCH DURATION ================ */
     /// Time spent searching for an idle peer in call to
     /// [`TransactionFetcher::find_any_idle_fallback_peer_for_any_pending_hash`](crate::transactions::TransactionFetcher::find_any_idle_fallback_peer_for_any_pending_hash).
@@ -17,12 +17,14 @@ use reth_network_api::{
     events::{NetworkPeersEvents, PeerEvent, PeerEventStream},
     test_utils::{PeersHandle, PeersHandleProvider},
     BlockDownloaderProvider, DiscoveryEvent, NetworkError, NetworkEvent,
-    NetworkEventListenerProvider, NetworkInfo, NetworkStatus, PeerInfo, PeerRequest, Peers,
-    PeersInfo,
+    NetworkEventListenerProvider, NetworkInfo, NetworkStatus, PeerHealthInfo, PeerInfo,
+    PeerRequest, Peers, PeersInfo,
 };
 use reth_network_p2p::sync::{NetworkSyncUpdater, SyncState, SyncStateProvider};
 use reth_network_peers::{NodeRecord, PeerId};
-use reth_network_types::{PeerAddr, PeerKind, Reputation, ReputationChangeKind};
+use reth_network_types::{
+    NetworkHealth, PeerAddr, PeerAllowlist, PeerKind, Reputation, ReputationChangeKind,
+};
 use reth_tokio_util::{EventSender, EventStream};
 use secp256k1::SecretKey;
 use std::{
@@ -34,7 +36,7 @@ use std::{
 };
 use tokio::sync::{
     mpsc::{self, UnboundedSender},
-    oneshot,
+    oneshot, watch,
 };
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
@@ -66,6 +68,7 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
         discv5: Option<Discv5>,
         event_sender: EventSender<NetworkEvent<PeerRequest<N>>>,
         nat: Option<NatResolver>,
+        health: watch::Receiver<NetworkHealth>,
     ) -> Self {
         let inner = NetworkInner {
             num_active_peers,
@@ -83,6 +86,7 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
             discv5,
             event_sender,
             nat,
+            health,
         };
         Self { inner: Arc::new(inner) }
     }
@@ -96,6 +100,11 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
         &self.inner.to_manager_tx
     }
 
+    /// Returns a receiver that's notified whenever the [`NetworkHealth`] changes.
+    pub fn subscribe_health(&self) -> watch::Receiver<NetworkHealth> {
+        self.inner.health.clone()
+    }
+
     /// Returns the mode of the network, either pow, or pos
     pub fn mode(&self) -> &NetworkMode {
         &self.inner.network_mode
@@ -176,6 +185,14 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
         self.set_network_conn(NetworkConnectionState::Hibernate);
     }
 
+    /// Replaces the allowlist of peers, see
+    /// [`PeersConfig::allowlist`](reth_network_types::PeersConfig::allowlist).
+    ///
+    /// Connected peers that aren't in the new allowlist are disconnected. `None` allows all peers.
+    pub fn set_peer_allowlist(&self, allowlist: Option<PeerAllowlist>) {
+        self.send_message(NetworkHandleMessage::SetPeerAllowlist(allowlist));
+    }
+
     /// Set network connection state.
     fn set_network_conn(&self, network_conn: NetworkConnectionState) {
         self.send_message(NetworkHandleMessage::SetNetworkState(network_conn));
@@ -268,6 +285,10 @@ impl<N: NetworkPrimitives> PeersInfo for NetworkHandle<N> {
         }
         builder.build(&self.inner.secret_key).expect("valid enr")
     }
+
+    fn network_health(&self) -> NetworkHealth {
+        *self.inner.health.borrow()
+    }
 }
 
 impl<N: NetworkPrimitives> Peers for NetworkHandle<N> {
@@ -306,6 +327,12 @@ impl<N: NetworkPrimitives> Peers for NetworkHandle<N> {
         Ok(rx.await?)
     }
 
+    async fn get_peers_health(&self) -> Result<Vec<PeerHealthInfo>, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.manager().send(NetworkHandleMessage::GetPeersHealth(tx));
+        Ok(rx.await?)
+    }
+
     async fn get_peers_by_id(&self, peer_ids: Vec<PeerId>) -> Result<Vec<PeerInfo>, NetworkError> {
         let (tx, rx) = oneshot::channel();
         let _ = self.manager().send(NetworkHandleMessage::GetPeerInfosByIds(peer_ids, tx));
@@ -459,6 +486,8 @@ struct NetworkInner<N: NetworkPrimitives = EthNetworkPrimitives> {
     event_sender: EventSender<NetworkEvent<PeerRequest<N>>>,
     /// The NAT resolver
     nat: Option<NatResolver>,
+    /// The health of the peer connectivity, updated by the peer count watchdog.
+    health: watch::Receiver<NetworkHealth>,
 }
 
 /// Provides access to modify the network's additional protocol handlers.
@@ -472,6 +501,8 @@ pub trait NetworkProtocols: Send + Sync {
 pub(crate) enum NetworkHandleMessage<N: NetworkPrimitives = EthNetworkPrimitives> {
     /// Marks a peer as trusted.
     AddTrustedPeerId(PeerId),
+    /// Replaces the allowlist of peers.
+    SetPeerAllowlist(Option<PeerAllowlist>),
     /// Adds an address for a peer, including its ID, kind, and socket address.
     AddPeerAddress(PeerId, PeerKind, PeerAddr),
     /// Removes a peer from the peerset corresponding to the given kind.
@@ -527,6 +558,8 @@ pub(crate) enum NetworkHandleMessage<N: NetworkPrimitives = EthNetworkPrimitives
     GetPeerInfoById(PeerId, oneshot::Sender<Option<PeerInfo>>),
     /// Gets `PeerInfo` for a specific peer kind via a oneshot sender.
     GetPeerInfosByPeerKind(PeerKind, oneshot::Sender<Vec<PeerInfo>>),
+    /// Gets the `PeerHealthInfo` of all trusted and static peers via a oneshot sender.
+    GetPeersHealth(oneshot::Sender<Vec<PeerHealthInfo>>),
     /// Gets the reputation for a specific peer via a oneshot sender.
     GetReputationById(PeerId, oneshot::Sender<Option<Reputation>>),
     /// Retrieves the `TransactionsHandle` via a oneshot sender.
@@ -9,14 +9,17 @@ use futures::StreamExt;
 use reth_eth_wire::{errors::EthStreamError, DisconnectReason};
 use reth_ethereum_forks::ForkId;
 use reth_net_banlist::BanList;
-use reth_network_api::test_utils::{PeerCommand, PeersHandle};
+use reth_network_api::{
+    test_utils::{PeerCommand, PeersHandle},
+    PeerHealthInfo,
+};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::{
     peers::{
         config::PeerBackoffDurations,
         reputation::{DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE},
     },
-    ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig,
+    ConnectionsConfig, Peer, PeerAddr, PeerAllowlist, PeerConnectionState, PeerKind, PeersConfig,
     ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
 };
 use std::{
@@ -24,6 +27,7 @@ use std::{
     fmt::Display,
     io::{self},
     net::{IpAddr, SocketAddr},
+    path::PathBuf,
     task::{Context, Poll},
     time::Duration,
 };
@@ -33,7 +37,11 @@ use tokio::{
     time::{Instant, Interval},
 };
 use tokio_stream::wrappers::UnboundedReceiverStream;
-use tracing::{trace, warn};
+use tracing::{debug, trace, warn};
+
+/// Maximum jitter added to the redial backoff of trusted and static peers, as a fraction of the
+/// backoff.
+const REDIAL_BACKOFF_JITTER: f64 = 0.25;
 
 /// Maintains the state of _all_ the peers known to the network.
 ///
@@ -50,6 +58,13 @@ pub struct PeersManager {
     /// This tracks peer ids that are considered trusted, but for which we don't necessarily have
     /// an address: [`Self::add_trusted_peer_id`]
     trusted_peer_ids: HashSet<PeerId>,
+    /// The trusted peers added at runtime, which are persisted to the
+    /// [`Self::trusted_peers_file`].
+    ///
+    /// Trusted peers configured at launch aren't tracked here.
+    persisted_trusted_peer_ids: HashSet<PeerId>,
+    /// The file the trusted peers added at runtime are persisted to.
+    trusted_peers_file: Option<PathBuf>,
     /// Copy of the sender half, so new [`PeersHandle`] can be created on demand.
     manager_tx: mpsc::UnboundedSender<PeerCommand>,
     /// Receiver half of the command channel.
@@ -76,6 +91,8 @@ pub struct PeersManager {
     /// If non-trusted peers should be connected to, or the connection from non-trusted
     /// incoming peers should be accepted.
     trusted_nodes_only: bool,
+    /// If set, only the peers in the allowlist are dialed or accepted.
+    allowlist: Option<PeerAllowlist>,
     /// Timestamp of the last time [`Self::tick`] was called.
     last_tick: Instant,
     /// Maximum number of backoff attempts before we give up on a peer and dropping.
@@ -98,9 +115,13 @@ impl PeersManager {
             backoff_durations,
             trusted_nodes,
             trusted_nodes_only,
+            trusted_nodes_file,
+            persisted_trusted_nodes,
+            allowlist,
             basic_nodes,
             max_backoff_count,
             incoming_ip_throttle_duration,
+            watchdog: _,
         } = config;
         let (manager_tx, handle_rx) = mpsc::unbounded_channel();
         let now = Instant::now();
@@ -125,15 +146,34 @@ impl PeersManager {
             }
         }
 
+        let mut persisted_trusted_peer_ids = HashSet::with_capacity(persisted_trusted_nodes.len());
+        for NodeRecord { address, tcp_port, udp_port, id } in persisted_trusted_nodes {
+            trusted_peer_ids.insert(id);
+            persisted_trusted_peer_ids.insert(id);
+            peers.entry(id).or_insert_with(|| {
+                Peer::trusted(PeerAddr::new_with_ports(address, tcp_port, Some(udp_port)))
+            });
+        }
+
+        // a deserialized config only holds the file of the allowlist
+        let allowlist = allowlist.map(|mut allowlist| {
+            if let Err(err) = allowlist.reload_if_modified() {
+                warn!(target: "net::peers", ?err, file = ?allowlist.file(), "Failed to load peer allowlist");
+            }
+            allowlist
+        });
+
         for NodeRecord { address, tcp_port, udp_port, id } in basic_nodes {
             peers.entry(id).or_insert_with(|| {
                 Peer::new(PeerAddr::new_with_ports(address, tcp_port, Some(udp_port)))
             });
         }
 
-        Self {
+        let mut manager = Self {
             peers,
             trusted_peer_ids,
+            persisted_trusted_peer_ids,
+            trusted_peers_file,
             manager_tx,
             handle_rx: UnboundedReceiverStream::new(handle_rx),
             queued_actions: Default::default(),
@@ -146,11 +186,14 @@ impl PeersManager {
             ban_duration,
             backoff_durations,
             trusted_nodes_only,
+            allowlist,
             last_tick: Instant::now(),
             max_backoff_count,
             net_connection_state: NetworkConnectionState::default(),
             incoming_ip_throttle_duration,
-        }
+        };
+        manager.add_allowlisted_peers();
+        manager
     }
 
     /// Returns a new [`PeersHandle`] that can send commands to this type.
@@ -176,6 +219,37 @@ impl PeersManager {
         })
     }
 
+    /// Returns an iterator over the trusted peers added at runtime, see
+    /// [`PeersConfig::with_trusted_nodes_from_file`].
+    pub(crate) fn iter_persisted_trusted_peers(&self) -> impl Iterator<Item = NodeRecord> + '_ {
+        self.persisted_trusted_peer_ids.iter().filter_map(|peer_id| {
+            let peer = self.peers.get(peer_id).filter(|peer| peer.is_trusted())?;
+            Some(NodeRecord::new_with_ports(
+                peer.addr.tcp().ip(),
+                peer.addr.tcp().port(),
+                peer.addr.udp().map(|addr| addr.port()),
+                *peer_id,
+            ))
+        })
+    }
+
+    /// Writes the trusted peers added at runtime to the configured file, if any.
+    ///
+    /// This is called whenever a trusted peer is added or removed at runtime, so the file is up to
+    /// date even if the node isn't shut down gracefully.
+    fn persist_trusted_peers(&self) {
+        let Some(file) = &self.trusted_peers_file else { return };
+        let trusted_peers = self.iter_persisted_trusted_peers().collect::<Vec<_>>();
+        let res = file
+            .parent()
+            .map(reth_fs_util::create_dir_all)
+            .transpose()
+            .and_then(|_| reth_fs_util::write_json_file(file, &trusted_peers));
+        if let Err(err) = res {
+            warn!(target: "net::peers", %err, "Failed to write trusted peers to file");
+        }
+    }
+
     /// Returns the `NodeRecord` and `PeerKind` for the given peer id
     pub(crate) fn peer_by_id(&self, peer_id: PeerId) -> Option<(NodeRecord, PeerKind)> {
         self.peers.get(&peer_id).map(|v| {
@@ -196,6 +270,24 @@ impl PeersManager {
         self.peers.iter().filter_map(move |(peer_id, peer)| (peer.kind == kind).then_some(*peer_id))
     }
 
+    /// Returns the [`PeerHealthInfo`] of all trusted and static peers.
+    pub(crate) fn peers_health(&self) -> Vec<PeerHealthInfo> {
+        self.peers
+            .iter()
+            .filter(|(_, peer)| peer.is_trusted() || peer.is_static())
+            .map(|(peer_id, peer)| PeerHealthInfo {
+                record: NodeRecord::new_with_ports(
+                    peer.addr.tcp().ip(),
+                    peer.addr.tcp().port(),
+                    peer.addr.udp().map(|addr| addr.port()),
+                    *peer_id,
+                ),
+                kind: peer.kind,
+                health: peer.health,
+            })
+            .collect()
+    }
+
     /// Returns the number of currently active inbound connections.
     #[inline]
     pub(crate) const fn num_inbound_connections(&self) -> usize {
@@ -225,6 +317,62 @@ impl PeersManager {
         self.peers.iter().filter(|(_, peer)| peer.kind.is_trusted() && peer.state.is_idle()).count()
     }
 
+    /// Returns `true` if the peer may be dialed or accepted, see [`PeersConfig::allowlist`].
+    fn is_allowed(&self, peer_id: &PeerId) -> bool {
+        self.allowlist.as_ref().is_none_or(|allowlist| allowlist.contains(peer_id))
+    }
+
+    /// Replaces the allowlist and disconnects the peers that are no longer allowed.
+    pub(crate) fn set_allowlist(&mut self, allowlist: Option<PeerAllowlist>) {
+        self.allowlist = allowlist;
+        self.disconnect_disallowed_peers();
+        self.add_allowlisted_peers();
+    }
+
+    /// Reloads the file of the allowlist if it changed, see
+    /// [`PeerAllowlist::reload_if_modified`].
+    fn reload_allowlist(&mut self) {
+        let Some(allowlist) = &mut self.allowlist else { return };
+        match allowlist.reload_if_modified() {
+            Ok(false) => {}
+            Ok(true) => {
+                debug!(target: "net::peers", peers = allowlist.peers().len(), "Reloaded peer allowlist");
+                self.disconnect_disallowed_peers();
+                self.add_allowlisted_peers();
+            }
+            Err(err) => {
+                warn!(target: "net::peers", ?err, file = ?allowlist.file(), "Failed to reload peer allowlist");
+            }
+        }
+    }
+
+    /// Adds the allowed peers with a known address as static peers, so they're dialed even if
+    /// discovery is disabled.
+    fn add_allowlisted_peers(&mut self) {
+        let Some(allowlist) = &self.allowlist else { return };
+        for NodeRecord { address, tcp_port, udp_port, id } in allowlist.nodes().to_vec() {
+            if !self.peers.contains_key(&id) {
+                let addr = PeerAddr::new_with_ports(address, tcp_port, Some(udp_port));
+                self.add_peer_kind(id, PeerKind::Static, addr, None);
+            }
+        }
+    }
+
+    /// Disconnects all connected peers that aren't in the allowlist.
+    fn disconnect_disallowed_peers(&mut self) {
+        let Some(allowlist) = &self.allowlist else { return };
+        for (peer_id, peer) in &mut self.peers {
+            if peer.state.is_connected() && !allowlist.contains(peer_id) {
+                trace!(target: "net::peers", ?peer_id, "disconnecting peer that isn't allowed");
+                peer.state.disconnect();
+                self.queued_actions.push_back(PeerAction::Disconnect {
+                    peer_id: *peer_id,
+                    reason: Some(DisconnectReason::DisconnectRequested),
+                });
+            }
+        }
+    }
+
     /// Invoked when a new _incoming_ tcp connection is accepted.
     ///
     /// returns an error if the inbound ip address is on the ban list
@@ -321,7 +469,7 @@ impl PeersManager {
 
         // check if the peer is trustable or not
         let mut is_trusted = self.trusted_peer_ids.contains(&peer_id);
-        if self.trusted_nodes_only && !is_trusted {
+        if (self.trusted_nodes_only && !is_trusted) || !self.is_allowed(&peer_id) {
             self.queued_actions.push_back(PeerAction::DisconnectUntrustedIncoming { peer_id });
             return
         }
@@ -343,6 +491,7 @@ impl PeersManager {
                 }
 
                 peer.state = PeerConnectionState::In;
+                peer.health.on_connected();
 
                 is_trusted = is_trusted || peer.is_trusted();
             }
@@ -496,6 +645,7 @@ impl PeersManager {
         if let Some(peer) = self.peers.get_mut(peer_id) {
             self.connection_info.decr_state(peer.state);
             peer.state = PeerConnectionState::Idle;
+            peer.health.on_disconnected();
         }
     }
 
@@ -526,6 +676,7 @@ impl PeersManager {
                     // session to that peer
                     entry.get_mut().severe_backoff_counter = 0;
                     entry.get_mut().state = PeerConnectionState::Idle;
+                    entry.get_mut().health.on_disconnected();
                     return
                 }
             }
@@ -541,6 +692,7 @@ impl PeersManager {
             self.connection_info.decr_state(peer.state);
             self.connection_info.inc_out();
             peer.state = PeerConnectionState::Out;
+            peer.health.on_connected();
         }
     }
 
@@ -587,15 +739,20 @@ impl PeersManager {
     ) {
         trace!(target: "net::peers", ?remote_addr, ?peer_id, %err, "handling failed connection");
 
+        // trusted and static peers are redialed with an exponential backoff
+        let mut redial_after_failed_dials = None;
+
         if err.is_fatal_protocol_error() {
             trace!(target: "net::peers", ?remote_addr, ?peer_id, %err, "fatal connection error");
             // remove the peer to which we can't establish a connection due to protocol related
             // issues.
             if let Entry::Occupied(mut entry) = self.peers.entry(*peer_id) {
                 self.connection_info.decr_state(entry.get().state);
-                // only remove if the peer is not trusted
-                if entry.get().is_trusted() {
-                    entry.get_mut().state = PeerConnectionState::Idle;
+                // only remove if the peer is not trusted or static
+                if entry.get().is_trusted() || entry.get().is_static() {
+                    let peer = entry.get_mut();
+                    redial_after_failed_dials = Self::on_trusted_or_static_peer_failure(peer, true);
+                    peer.state = PeerConnectionState::Idle;
                 } else {
                     entry.remove();
                     self.queued_actions.push_back(PeerAction::PeerRemoved(*peer_id));
@@ -635,12 +792,24 @@ impl PeersManager {
                     peer.reputation = peer.reputation.saturating_add(reputation_change.as_i32());
                 };
 
+                if peer.is_trusted() || peer.is_static() {
+                    redial_after_failed_dials =
+                        Self::on_trusted_or_static_peer_failure(peer, false);
+                    if redial_after_failed_dials.is_some() {
+                        // failed dials are backed off with the redial backoff instead
+                        backoff_until = None;
+                    }
+                }
+
                 self.connection_info.decr_state(peer.state);
                 peer.state = PeerConnectionState::Idle;
 
-                if peer.severe_backoff_counter > self.max_backoff_count && !peer.is_trusted() {
+                if peer.severe_backoff_counter > self.max_backoff_count &&
+                    !peer.is_trusted() &&
+                    !peer.is_static()
+                {
                     // mark peer for removal if it has been backoff too many times and is _not_
-                    // trusted
+                    // trusted or static
                     remove_peer = true;
                 }
             }
@@ -655,9 +824,40 @@ impl PeersManager {
             }
         }
 
+        if let Some(failed_dials) = redial_after_failed_dials {
+            trace!(target: "net::peers", ?peer_id, failed_dials, "backing off redial of trusted or static peer");
+            let redial_at = self.redial_backoff_until(failed_dials);
+            self.backoff_peer_until(*peer_id, redial_at);
+        }
+
         self.fill_outbound_slots();
     }
 
+    /// Updates the health of a trusted or static peer after its connection failed.
+    ///
+    /// Returns the number of consecutive failed dials if the failure was a connection attempt,
+    /// otherwise the established session was dropped and the peer can be redialed immediately.
+    fn on_trusted_or_static_peer_failure(peer: &mut Peer, protocol_mismatch: bool) -> Option<u32> {
+        if peer.state.is_pending_out() {
+            peer.health.on_failed_dial(protocol_mismatch);
+            Some(peer.health.failed_dials)
+        } else {
+            peer.health.on_disconnected();
+            None
+        }
+    }
+
+    /// Returns the timestamp until which a trusted or static peer should not be redialed after the
+    /// given number of consecutive failed dials.
+    ///
+    /// A random jitter is added, so that peers that became unreachable at the same time are not
+    /// redialed in lockstep.
+    fn redial_backoff_until(&self, failed_dials: u32) -> std::time::Instant {
+        let backoff = self.backoff_durations.redial_backoff(failed_dials);
+        let jitter = backoff.mul_f64(rand::random::<f64>() * REDIAL_BACKOFF_JITTER);
+        std::time::Instant::now() + backoff + jitter
+    }
+
     /// Invoked if a pending session was disconnected because there's already a connection to the
     /// peer.
     ///
@@ -697,6 +897,8 @@ impl PeersManager {
     /// Marks the given peer as trusted.
     pub(crate) fn add_trusted_peer_id(&mut self, peer_id: PeerId) {
         self.trusted_peer_ids.insert(peer_id);
+        self.persisted_trusted_peer_ids.insert(peer_id);
+        self.persist_trusted_peers();
     }
 
     /// Called for a newly discovered trusted peer.
@@ -717,7 +919,7 @@ impl PeersManager {
         addr: PeerAddr,
         fork_id: Option<ForkId>,
     ) {
-        if self.ban_list.is_banned(&peer_id, &addr.tcp().ip()) {
+        if self.ban_list.is_banned(&peer_id, &addr.tcp().ip()) || !self.is_allowed(&peer_id) {
             return
         }
 
@@ -746,6 +948,8 @@ impl PeersManager {
 
         if kind.is_trusted() {
             self.trusted_peer_ids.insert(peer_id);
+            self.persisted_trusted_peer_ids.insert(peer_id);
+            self.persist_trusted_peers();
         }
     }
 
@@ -798,7 +1002,7 @@ impl PeersManager {
         addr: PeerAddr,
         fork_id: Option<ForkId>,
     ) {
-        if self.ban_list.is_banned(&peer_id, &addr.tcp().ip()) {
+        if self.ban_list.is_banned(&peer_id, &addr.tcp().ip()) || !self.is_allowed(&peer_id) {
             return
         }
 
@@ -831,6 +1035,8 @@ impl PeersManager {
 
         if kind.is_trusted() {
             self.trusted_peer_ids.insert(peer_id);
+            self.persisted_trusted_peer_ids.insert(peer_id);
+            self.persist_trusted_peers();
         }
     }
 
@@ -845,6 +1051,9 @@ impl PeersManager {
         peer.kind = PeerKind::Basic;
 
         self.trusted_peer_ids.remove(&peer_id);
+        if self.persisted_trusted_peer_ids.remove(&peer_id) {
+            self.persist_trusted_peers();
+        }
     }
 
     /// Returns the idle peer with the highest reputation.
@@ -853,15 +1062,16 @@ impl PeersManager {
     /// not currently marked as banned or backed off.
     ///
     /// If `trusted_nodes_only` is enabled, see [`PeersConfig`], then this will only consider
-    /// `trusted` peers.
+    /// `trusted` peers. If an allowlist is configured, only the allowed peers are considered.
     ///
     /// Returns `None` if no peer is available.
     fn best_unconnected(&mut self) -> Option<(PeerId, &mut Peer)> {
-        let mut unconnected = self.peers.iter_mut().filter(|(_, peer)| {
+        let mut unconnected = self.peers.iter_mut().filter(|(peer_id, peer)| {
             !peer.is_backed_off() &&
                 !peer.is_banned() &&
                 peer.state.is_unconnected() &&
-                (!self.trusted_nodes_only || peer.is_trusted())
+                (!self.trusted_nodes_only || peer.is_trusted()) &&
+                self.allowlist.as_ref().is_none_or(|allowlist| allowlist.contains(peer_id))
         });
 
         // keep track of the best peer, if there's one
@@ -987,6 +1197,7 @@ impl PeersManager {
             }
 
             while self.refill_slots_interval.poll_tick(cx).is_ready() {
+                self.reload_allowlist();
                 self.fill_outbound_slots();
             }
 
@@ -1164,9 +1375,10 @@ mod tests {
     };
     use reth_net_banlist::BanList;
     use reth_network_api::Direction;
-    use reth_network_peers::{PeerId, TrustedPeer};
+    use reth_network_peers::{AnyNode, NodeRecord, PeerId, TrustedPeer};
     use reth_network_types::{
-        peers::reputation::DEFAULT_REPUTATION, BackoffKind, ReputationChangeKind,
+        peers::reputation::DEFAULT_REPUTATION, BackoffKind, PeerHealthStatus, PeerKind,
+        ReputationChangeKind,
     };
     use std::{
         future::{poll_fn, Future},
@@ -1182,8 +1394,8 @@ mod tests {
     use crate::{
         error::SessionError,
         peers::{
-            ConnectionInfo, InboundConnectionError, PeerAction, PeerAddr, PeerBackoffDurations,
-            PeerConnectionState,
+            ConnectionInfo, InboundConnectionError, PeerAction, PeerAddr, PeerAllowlist,
+            PeerBackoffDurations, PeerConnectionState,
         },
         session::PendingSessionHandshakeError,
         PeersConfig,
@@ -2111,12 +2323,22 @@ mod tests {
             err => unreachable!("{err:?}"),
         }
 
-        // ensure we still have trusted peer
-        assert!(peers.peers.contains_key(&peer));
+        // ensure we still have trusted peer, which is redialed after a backoff
+        let p = peers.peers.get(&peer).unwrap();
+        assert_eq!(p.health.status, PeerHealthStatus::ProtocolMismatch);
+        assert_eq!(p.health.failed_dials, 1);
+        assert!(p.is_backed_off());
 
-        // await for the ban to expire
+        // await for the ban and the backoff to expire
         tokio::time::sleep(peers.backoff_durations.medium).await;
 
+        match event!(peers) {
+            PeerAction::UnBanPeer { peer_id } => {
+                assert_eq!(peer_id, peer);
+            }
+            err => unreachable!("{err:?}"),
+        }
+
         match event!(peers) {
             PeerAction::Connect { peer_id, remote_addr } => {
                 assert_eq!(peer_id, peer);
@@ -2126,6 +2348,94 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_fatal_outgoing_connection_error_static() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut peers = PeersManager::new(PeersConfig::test());
+        peers.add_peer_kind(peer, PeerKind::Static, PeerAddr::from_tcp(socket_addr), None);
+
+        match event!(peers) {
+            PeerAction::Connect { peer_id, remote_addr } => {
+                assert_eq!(peer_id, peer);
+                assert_eq!(remote_addr, socket_addr);
+            }
+            _ => unreachable!(),
+        }
+
+        let err = PendingSessionHandshakeError::Eth(EthStreamError::EthHandshakeError(
+            EthHandshakeError::NonStatusMessageInHandshake,
+        ));
+        assert!(err.is_fatal_protocol_error());
+
+        peers.on_outgoing_pending_session_dropped(&socket_addr, &peer, &err);
+
+        match event!(peers) {
+            PeerAction::BanPeer { peer_id } => {
+                assert_eq!(peer_id, peer);
+            }
+            err => unreachable!("{err:?}"),
+        }
+
+        // ensure the static peer isn't removed and is redialed after a backoff
+        let p = peers.peers.get(&peer).unwrap();
+        assert_eq!(p.state, PeerConnectionState::Idle);
+        assert_eq!(p.health.status, PeerHealthStatus::ProtocolMismatch);
+        assert!(p.is_backed_off());
+        assert!(!peers
+            .queued_actions
+            .iter()
+            .any(|action| matches!(action, PeerAction::PeerRemoved(peer_id) if *peer_id == peer)));
+    }
+
+    #[tokio::test]
+    async fn test_redial_backoff_static_peer() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let backoff_durations = PeerBackoffDurations {
+            high: Duration::from_secs(60 * 15),
+            max: Duration::from_secs(60 * 60),
+            ..PeerBackoffDurations::test()
+        };
+        let config = PeersConfig { backoff_durations, ..PeersConfig::test() };
+        let mut peers = PeersManager::new(config);
+        peers.add_peer_kind(peer, PeerKind::Static, PeerAddr::from_tcp(socket_addr), None);
+
+        for failed_dials in 1..=3 {
+            loop {
+                if let PeerAction::Connect { peer_id, .. } = event!(peers) {
+                    assert_eq!(peer_id, peer);
+                    break
+                }
+            }
+
+            let now = std::time::Instant::now();
+            peers.on_outgoing_connection_failure(
+                &socket_addr,
+                &peer,
+                &io::Error::new(io::ErrorKind::ConnectionRefused, ""),
+            );
+
+            // the peer is not removed and redialed with an exponential backoff instead of the
+            // high backoff for refused connections
+            let p = peers.peers.get(&peer).unwrap();
+            assert_eq!(p.health.status, PeerHealthStatus::Unreachable);
+            assert_eq!(p.health.failed_dials, failed_dials);
+            let backoff = backoff_durations.redial_backoff(failed_dials);
+            let until = peers.backed_off_peers[&peer];
+            assert!(until >= now + backoff);
+            assert!(until <= std::time::Instant::now() + backoff.mul_f64(1.25));
+        }
+
+        let p = peers.peers.get_mut(&peer).unwrap();
+        p.state = PeerConnectionState::PendingOut;
+        peers.connection_info.inc_pending_out();
+        peers.on_active_outgoing_established(peer);
+        let p = peers.peers.get(&peer).unwrap();
+        assert_eq!(p.health.status, PeerHealthStatus::Connected);
+        assert_eq!(p.health.failed_dials, 0);
+    }
+
     #[tokio::test]
     async fn test_outgoing_connection_error() {
         let peer = PeerId::random();
@@ -2327,6 +2637,48 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_persist_trusted_peers() {
+        let trusted_peer = PeerId::random();
+        let config = PeersConfig::test().with_trusted_nodes(vec![TrustedPeer {
+            host: Host::Ipv4(Ipv4Addr::new(127, 0, 1, 2)),
+            tcp_port: 8008,
+            udp_port: 8008,
+            id: trusted_peer,
+        }]);
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("trusted-peers.json");
+        let config = config.with_trusted_nodes_from_file(Some(&file)).unwrap();
+        let mut peers = PeersManager::new(config);
+
+        let basic_peer = PeerId::random();
+        let basic_sock = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8009);
+        peers.add_peer(basic_peer, PeerAddr::from_tcp(basic_sock), None);
+        let added_trusted_peer = PeerId::random();
+        let added_trusted_sock = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8010);
+        peers.add_trusted_peer(added_trusted_peer, PeerAddr::from_tcp(added_trusted_sock));
+        let removed_trusted_peer = PeerId::random();
+        let removed_trusted_sock = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8011);
+        peers.add_trusted_peer(removed_trusted_peer, PeerAddr::from_tcp(removed_trusted_sock));
+        peers.remove_peer_from_trusted_set(removed_trusted_peer);
+        drop(peers);
+
+        // only the trusted peers added at runtime are persisted and restored, the configured
+        // trusted peer isn't
+        let config = PeersConfig::test().with_trusted_nodes_from_file(Some(&file)).unwrap();
+        let mut peers = PeersManager::new(config);
+        let restored =
+            peers.iter_persisted_trusted_peers().map(|record| record.id).collect::<Vec<_>>();
+        assert_eq!(restored, vec![added_trusted_peer]);
+        assert!(peers.trusted_peer_ids.contains(&added_trusted_peer));
+        assert!(!peers.peers.contains_key(&trusted_peer));
+
+        // removing a restored trusted peer removes it from the file
+        peers.remove_peer_from_trusted_set(added_trusted_peer);
+        let config = PeersConfig::test().with_trusted_nodes_from_file(Some(&file)).unwrap();
+        assert!(config.persisted_trusted_nodes.is_empty());
+    }
+
     #[tokio::test]
     async fn test_connect_trusted_nodes_only() {
         let trusted_peer = PeerId::random();
@@ -2425,6 +2777,67 @@ mod tests {
         assert!(peers.peers.contains_key(&basic_peer));
     }
 
+    #[tokio::test]
+    async fn test_allowlist() {
+        let allowed_peer = PeerId::random();
+        let config = PeersConfig::test().with_allowlist(Some(PeerAllowlist::new([allowed_peer])));
+        let mut peers = PeersManager::new(config);
+
+        // peers that aren't allowed are neither tracked nor accepted
+        let other_peer = PeerId::random();
+        let other_sock = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        peers.add_peer(other_peer, PeerAddr::from_tcp(other_sock), None);
+        assert!(!peers.peers.contains_key(&other_peer));
+
+        assert!(peers.on_incoming_pending_session(other_sock.ip()).is_ok());
+        peers.on_incoming_session_established(other_peer, other_sock);
+        let Some(PeerAction::DisconnectUntrustedIncoming { peer_id }) =
+            peers.queued_actions.pop_front()
+        else {
+            panic!()
+        };
+        assert_eq!(peer_id, other_peer);
+
+        let allowed_sock = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 3)), 8008);
+        assert!(peers.on_incoming_pending_session(allowed_sock.ip()).is_ok());
+        peers.on_incoming_session_established(allowed_peer, allowed_sock);
+        assert!(peers.peers[&allowed_peer].state.is_connected());
+
+        // the peer is disconnected once it's removed from the allowlist
+        peers.set_allowlist(Some(PeerAllowlist::new([other_peer])));
+        assert!(peers.queued_actions.iter().any(|action| matches!(
+            action,
+            PeerAction::Disconnect { peer_id, .. } if *peer_id == allowed_peer
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_allowlist_dials_enodes() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let allowlist = PeerAllowlist::from_nodes([
+            AnyNode::NodeRecord(NodeRecord::new(socket_addr, peer)),
+            AnyNode::PeerId(PeerId::random()),
+        ]);
+        let config = PeersConfig::test().with_allowlist(Some(allowlist));
+        let mut peers = PeersManager::new(config);
+
+        // the peer with an address is dialed without being discovered
+        assert_eq!(peers.peers.len(), 1);
+        assert_eq!(peers.peers[&peer].kind, PeerKind::Static);
+        match event!(peers) {
+            PeerAction::PeerAdded(peer_id) => assert_eq!(peer_id, peer),
+            _ => unreachable!(),
+        }
+        match event!(peers) {
+            PeerAction::Connect { peer_id, remote_addr } => {
+                assert_eq!(peer_id, peer);
+                assert_eq!(remote_addr, socket_addr);
+            }
+            _ => unreachable!(),
+        }
+    }
+
     #[tokio::test]
     async fn test_incoming_at_capacity() {
         let mut config = PeersConfig::test();
@@ -0,0 +1,416 @@
+//! Experimental relay-assisted dialing for nodes behind a NAT.
+//!
+//! A node that can't accept incoming connections keeps a reservation at a relay, and other nodes
+//! dial it through the relay if dialing it directly fails. The relay only forwards the bytes of the
+//! connection, the `RLPx` session is established end-to-end, so the relay can't read or forge any
+//! messages. A node has to prove that it owns the peer id it reserves, so that it can't hijack the
+//! reservation of another node.
+//!
+//! A node runs a [`RelayServer`] if [`NetworkConfig::relay_server_addr`] is set, and keeps a
+//! reservation at the relay set in [`NetworkConfig::relay`].
+//!
+//! [`NetworkConfig::relay_server_addr`]: crate::NetworkConfig::relay_server_addr
+//! [`NetworkConfig::relay`]: crate::NetworkConfig::relay
+//!
+//! The relay protocol consists of newline terminated ASCII lines, which are exchanged before the
+//! `RLPx` handshake:
+//!
+//! - `RESERVE <peer id>`: sent by a node to reserve a slot for its peer id, the relay answers with
+//!   `CHALLENGE <nonce>`, a random hex encoded 32 byte nonce.
+//! - `SIGNATURE <signature>`: sent by the node in response to the challenge, the hex encoded 65
+//!   byte recoverable signature of the [`reservation_digest`] of the nonce with the secret key of
+//!   the peer id. The relay answers with `OK` if it's valid. The connection is kept open and the
+//!   relay sends `CONNECT <token> <dialer address>` for every node that dials the reserved peer.
+//! - `ACCEPT <token>`: sent by the reserved node on a new connection in response to a `CONNECT`,
+//!   the connection is then forwarded to the dialer as an incoming connection.
+//! - `DIAL <peer id>`: sent by a node to dial a reserved peer. The relay answers with `OK` once the
+//!   reserved node accepted the connection, which is then forwarded to it, or `ERR <reason>`.
+
+use alloy_primitives::{hex, keccak256, B256};
+use parking_lot::Mutex;
+use reth_network_peers::{pk2id, PeerId};
+use secp256k1::{
+    ecdsa::{RecoverableSignature, RecoveryId},
+    Message, SecretKey, SECP256K1,
+};
+use std::{collections::HashMap, io, net::SocketAddr, sync::Arc, time::Duration};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::{mpsc, oneshot},
+    time::timeout,
+};
+use tracing::{debug, info, trace};
+
+/// The maximum length of a line of the relay protocol.
+const MAX_LINE_LENGTH: usize = 256;
+
+/// The timeout for establishing a relayed connection.
+const RELAY_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The timeout for dialing a peer directly before dialing it through the relay.
+pub(crate) const DIRECT_DIAL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The interval in which a failed reservation is retried.
+const RESERVATION_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The number of relayed connections that are buffered until the node accepts them.
+pub(crate) const RELAYED_CONNECTIONS_BUFFER: usize = 16;
+
+/// The number of dials that are buffered for a reservation.
+const RESERVATION_BUFFER: usize = 16;
+
+/// The domain of the reservation signatures, so that they can't be replayed in another protocol.
+const RESERVATION_DOMAIN: &[u8] = b"reth relay reservation";
+
+/// Returns the digest of the relay's challenge nonce that a node signs to prove that it owns the
+/// peer id it reserves.
+pub fn reservation_digest(nonce: B256) -> B256 {
+    keccak256([RESERVATION_DOMAIN, nonce.as_slice()].concat())
+}
+
+/// Dials the peer through the relay, and returns the connection to it.
+///
+/// This fails if the peer has no reservation at the relay or doesn't accept the connection.
+pub async fn dial_via_relay(relay: SocketAddr, peer_id: PeerId) -> io::Result<TcpStream> {
+    timeout(RELAY_HANDSHAKE_TIMEOUT, async {
+        let mut stream = TcpStream::connect(relay).await?;
+        write_line(&mut stream, &format!("DIAL {peer_id}")).await?;
+        expect_ok(&mut stream).await?;
+        Ok(stream)
+    })
+    .await
+    .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "relay dial timed out"))?
+}
+
+/// Keeps a reservation for the local node at the relay, and sends the connections of the nodes
+/// that dial it through the relay, along with their address.
+///
+/// The reservation is renewed if it's lost, until the receiver of the connections is dropped.
+pub(crate) async fn run_relay_reservation(
+    relay: SocketAddr,
+    secret_key: SecretKey,
+    connections: mpsc::Sender<(TcpStream, SocketAddr)>,
+) {
+    while !connections.is_closed() {
+        if let Err(err) = reserve(relay, &secret_key, &connections).await {
+            debug!(target: "net::relay", %err, %relay, "Relay reservation failed");
+        }
+        tokio::time::sleep(RESERVATION_RETRY_INTERVAL).await;
+    }
+}
+
+/// Reserves a slot at the relay and accepts the relayed connections, until the reservation is
+/// closed.
+async fn reserve(
+    relay: SocketAddr,
+    secret_key: &SecretKey,
+    connections: &mpsc::Sender<(TcpStream, SocketAddr)>,
+) -> io::Result<()> {
+    let local_peer_id = pk2id(&secret_key.public_key(SECP256K1));
+    let mut control = timeout(RELAY_HANDSHAKE_TIMEOUT, async {
+        let mut control = TcpStream::connect(relay).await?;
+        write_line(&mut control, &format!("RESERVE {local_peer_id}")).await?;
+
+        let line = read_line(&mut control).await?;
+        let nonce = line
+            .strip_prefix("CHALLENGE ")
+            .and_then(|nonce| nonce.parse::<B256>().ok())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::ConnectionRefused, line))?;
+        write_line(&mut control, &sign_reservation(nonce, secret_key)).await?;
+
+        expect_ok(&mut control).await?;
+        Ok::<_, io::Error>(control)
+    })
+    .await
+    .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "relay reservation timed out"))??;
+    info!(target: "net::relay", %relay, "Reserved relay slot");
+
+    loop {
+        let line = read_line(&mut control).await?;
+        let Some((token, remote_addr)) = line
+            .strip_prefix("CONNECT ")
+            .and_then(|args| args.split_once(' '))
+            .and_then(|(token, addr)| Some((token.parse::<u64>().ok()?, addr.parse().ok()?)))
+        else {
+            return Err(invalid_data(format!("unexpected relay message: {line}")))
+        };
+
+        trace!(target: "net::relay", %remote_addr, "Accepting relayed connection");
+        let accept = async {
+            let mut stream = TcpStream::connect(relay).await?;
+            write_line(&mut stream, &format!("ACCEPT {token}")).await?;
+            Ok::<_, io::Error>(stream)
+        };
+        match timeout(RELAY_HANDSHAKE_TIMEOUT, accept).await {
+            Ok(Ok(stream)) => {
+                if connections.send((stream, remote_addr)).await.is_err() {
+                    return Ok(())
+                }
+            }
+            Ok(Err(err)) => {
+                debug!(target: "net::relay", %err, %remote_addr, "Failed to accept relayed dial")
+            }
+            Err(_) => {
+                debug!(target: "net::relay", %remote_addr, "Accepting relayed dial timed out")
+            }
+        }
+    }
+}
+
+/// A relay that forwards the connections of dialing nodes to the nodes that reserved a slot at it.
+///
+/// See the [module docs](self) for the protocol.
+#[derive(Debug)]
+pub struct RelayServer {
+    /// The listener for connections to the relay.
+    listener: TcpListener,
+    /// The reservations and the pending dials.
+    state: Arc<Mutex<RelayState>>,
+}
+
+impl RelayServer {
+    /// Creates a new relay that listens on the given address.
+    pub async fn bind(addr: SocketAddr) -> io::Result<Self> {
+        Ok(Self { listener: TcpListener::bind(addr).await?, state: Default::default() })
+    }
+
+    /// Returns the address the relay listens on.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts and forwards connections until the listener fails.
+    pub async fn run(self) -> io::Result<()> {
+        loop {
+            let (stream, remote_addr) = self.listener.accept().await?;
+            let state = self.state.clone();
+            tokio::spawn(async move {
+                if let Err(err) = handle_relay_connection(state, stream, remote_addr).await {
+                    trace!(target: "net::relay", %err, %remote_addr, "Relay connection failed");
+                }
+            });
+        }
+    }
+}
+
+/// The reservations and the pending dials of a [`RelayServer`].
+#[derive(Debug, Default)]
+struct RelayState {
+    /// The channels to the reserved nodes, to send them the token and address of a dialer.
+    reservations: HashMap<PeerId, mpsc::Sender<(u64, SocketAddr)>>,
+    /// The dials waiting for the reserved node to accept, by token.
+    pending_dials: HashMap<u64, oneshot::Sender<TcpStream>>,
+}
+
+/// Handles a connection to the relay.
+async fn handle_relay_connection(
+    state: Arc<Mutex<RelayState>>,
+    mut stream: TcpStream,
+    remote_addr: SocketAddr,
+) -> io::Result<()> {
+    let line = read_request_line(&mut stream).await?;
+    let (request, arg) = line.split_once(' ').unwrap_or((&line, ""));
+
+    match request {
+        "RESERVE" => {
+            let peer_id = arg.parse::<PeerId>().map_err(invalid_data)?;
+            let nonce = B256::random();
+            write_line(&mut stream, &format!("CHALLENGE {nonce}")).await?;
+            let line = read_request_line(&mut stream).await?;
+            if recover_reservation_signer(nonce, &line) != Some(peer_id) {
+                return write_line(&mut stream, "ERR invalid signature").await
+            }
+
+            let (dials_tx, mut dials) = mpsc::channel(RESERVATION_BUFFER);
+            state.lock().reservations.insert(peer_id, dials_tx.clone());
+            write_line(&mut stream, "OK").await?;
+            debug!(target: "net::relay", %peer_id, %remote_addr, "Reserved relay slot");
+
+            let mut buf = [0; 1];
+            let res = loop {
+                tokio::select! {
+                    dial = dials.recv() => {
+                        let Some((token, dialer)) = dial else { break Ok(()) };
+                        if let Err(err) =
+                            write_line(&mut stream, &format!("CONNECT {token} {dialer}")).await
+                        {
+                            break Err(err)
+                        }
+                    }
+                    // the reserved node doesn't send anything, until it closes the connection
+                    _ = stream.read(&mut buf) => break Ok(()),
+                }
+            };
+
+            // the slot may have been reserved again by a new connection of the node
+            let mut state = state.lock();
+            if state.reservations.get(&peer_id).is_some_and(|tx| tx.same_channel(&dials_tx)) {
+                state.reservations.remove(&peer_id);
+            }
+            res
+        }
+        "DIAL" => {
+            let peer_id = arg.parse::<PeerId>().map_err(invalid_data)?;
+            let token = rand::random::<u64>();
+            let (accepted_tx, accepted) = oneshot::channel();
+            let reservation = {
+                let mut state = state.lock();
+                state.pending_dials.insert(token, accepted_tx);
+                state.reservations.get(&peer_id).cloned()
+            };
+
+            let target = match reservation {
+                Some(reservation) if reservation.try_send((token, remote_addr)).is_ok() => {
+                    timeout(RELAY_HANDSHAKE_TIMEOUT, accepted).await.ok().and_then(Result::ok)
+                }
+                _ => None,
+            };
+            let Some(mut target) = target else {
+                state.lock().pending_dials.remove(&token);
+                return write_line(&mut stream, "ERR peer unavailable").await
+            };
+
+            write_line(&mut stream, "OK").await?;
+            trace!(target: "net::relay", %peer_id, %remote_addr, "Relaying connection");
+            tokio::io::copy_bidirectional(&mut stream, &mut target).await.map(drop)
+        }
+        "ACCEPT" => {
+            let token = arg.parse::<u64>().map_err(invalid_data)?;
+            if let Some(dial) = state.lock().pending_dials.remove(&token) {
+                let _ = dial.send(stream);
+            }
+            Ok(())
+        }
+        _ => Err(invalid_data(format!("unknown relay request: {line}"))),
+    }
+}
+
+/// Returns the `SIGNATURE` line in response to the relay's challenge nonce.
+fn sign_reservation(nonce: B256, secret_key: &SecretKey) -> String {
+    let digest = Message::from_digest(reservation_digest(nonce).0);
+    let (recovery_id, signature) =
+        SECP256K1.sign_ecdsa_recoverable(&digest, secret_key).serialize_compact();
+    let mut signature = signature.to_vec();
+    signature.push(i32::from(recovery_id) as u8);
+    format!("SIGNATURE {}", hex::encode(signature))
+}
+
+/// Recovers the peer id that signed the challenge nonce from a `SIGNATURE` line.
+fn recover_reservation_signer(nonce: B256, line: &str) -> Option<PeerId> {
+    let signature = hex::decode(line.strip_prefix("SIGNATURE ")?).ok()?;
+    let (&recovery_id, signature) = signature.split_last()?;
+    let signature = RecoverableSignature::from_compact(
+        signature,
+        RecoveryId::try_from(recovery_id as i32).ok()?,
+    )
+    .ok()?;
+    let public_key = SECP256K1
+        .recover_ecdsa(&Message::from_digest(reservation_digest(nonce).0), &signature)
+        .ok()?;
+    Some(pk2id(&public_key))
+}
+
+/// Reads a line sent to the relay, which times out after [`RELAY_HANDSHAKE_TIMEOUT`].
+async fn read_request_line(stream: &mut TcpStream) -> io::Result<String> {
+    timeout(RELAY_HANDSHAKE_TIMEOUT, read_line(stream))
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "relay request timed out"))?
+}
+
+/// Reads a line and expects it to be `OK`.
+async fn expect_ok(stream: &mut TcpStream) -> io::Result<()> {
+    match read_line(stream).await? {
+        line if line == "OK" => Ok(()),
+        line => Err(io::Error::new(io::ErrorKind::ConnectionRefused, line)),
+    }
+}
+
+/// Reads a newline terminated line.
+///
+/// The line is read byte by byte, so that no bytes of the relayed connection that follow it are
+/// consumed.
+async fn read_line(stream: &mut TcpStream) -> io::Result<String> {
+    let mut line = Vec::new();
+    loop {
+        match stream.read_u8().await? {
+            b'\n' => break,
+            byte if line.len() < MAX_LINE_LENGTH => line.push(byte),
+            _ => return Err(invalid_data("relay line too long")),
+        }
+    }
+    String::from_utf8(line).map_err(invalid_data)
+}
+
+/// Writes a newline terminated line.
+async fn write_line(stream: &mut TcpStream, line: &str) -> io::Result<()> {
+    stream.write_all(format!("{line}\n").as_bytes()).await
+}
+
+fn invalid_data(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    async fn spawn_relay() -> SocketAddr {
+        let relay = RelayServer::bind((Ipv4Addr::LOCALHOST, 0).into()).await.unwrap();
+        let relay_addr = relay.local_addr().unwrap();
+        tokio::spawn(relay.run());
+        relay_addr
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn relay_connection() {
+        let relay_addr = spawn_relay().await;
+
+        // dialing a peer without a reservation fails
+        let secret_key = SecretKey::new(&mut rand::thread_rng());
+        let peer_id = pk2id(&secret_key.public_key(SECP256K1));
+        assert!(dial_via_relay(relay_addr, peer_id).await.is_err());
+
+        let (connections_tx, mut connections) = mpsc::channel(1);
+        tokio::spawn(run_relay_reservation(relay_addr, secret_key, connections_tx));
+
+        // wait for the reservation
+        let mut dialer = loop {
+            if let Ok(stream) = dial_via_relay(relay_addr, peer_id).await {
+                break stream
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        };
+        let (mut accepted, remote_addr) = connections.recv().await.unwrap();
+        assert_eq!(remote_addr.ip(), dialer.local_addr().unwrap().ip());
+
+        dialer.write_all(b"ping").await.unwrap();
+        let mut buf = [0; 4];
+        accepted.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"ping");
+
+        accepted.write_all(b"pong").await.unwrap();
+        dialer.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"pong");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn reservation_requires_peer_id_ownership() {
+        let relay_addr = spawn_relay().await;
+        let peer_id = PeerId::random();
+
+        // reserving the peer id with a signature of another key is rejected
+        let mut control = TcpStream::connect(relay_addr).await.unwrap();
+        write_line(&mut control, &format!("RESERVE {peer_id}")).await.unwrap();
+        let line = read_line(&mut control).await.unwrap();
+        let nonce = line.strip_prefix("CHALLENGE ").unwrap().parse::<B256>().unwrap();
+
+        let secret_key = SecretKey::new(&mut rand::thread_rng());
+        write_line(&mut control, &sign_reservation(nonce, &secret_key)).await.unwrap();
+        assert!(expect_ok(&mut control).await.is_err());
+
+        // so the peer can't be dialed through the relay
+        assert!(dial_via_relay(relay_addr, peer_id).await.is_err());
+    }
+}
@@ -15,7 +15,7 @@ use crate::{
     message::{NewBlockMessage, PeerMessage, PeerResponse, PeerResponseResult},
     session::{
         conn::EthRlpxConnection,
-        handle::{ActiveSessionMessage, SessionCommand},
+        handle::{ActiveSessionMessage, BlockRangeInfo, SessionCommand},
         SessionId,
     },
 };
@@ -25,8 +25,9 @@ use metrics::Gauge;
 use reth_eth_wire::{
     capability::RawCapabilityMessage,
     errors::{EthHandshakeError, EthStreamError},
-    message::{EthBroadcastMessage, RequestPair},
-    Capabilities, DisconnectP2P, DisconnectReason, EthMessage, NetworkPrimitives,
+    message::{EthBroadcastMessage, MessageError, RequestPair},
+    BlockRangeUpdate, Capabilities, DisconnectP2P, DisconnectReason, EthMessage, EthMessageID,
+    NetworkPrimitives,
 };
 use reth_metrics::common::mpsc::MeteredPollSender;
 use reth_network_api::PeerRequest;
@@ -67,6 +68,14 @@ const TIMEOUT_SCALING: u32 = 3;
 /// before reading any more messages from the remote peer, throttling the peer.
 const MAX_QUEUED_OUTGOING_RESPONSES: usize = 4;
 
+/// The number of blocks the latest block has to advance by before the local block range is
+/// announced again, one epoch.
+const BLOCK_RANGE_UPDATE_BLOCKS: u64 = 32;
+
+/// How often `eth/69` sessions check whether the local block range should be announced, the
+/// duration of one epoch.
+pub(crate) const BLOCK_RANGE_UPDATE_INTERVAL: Duration = Duration::from_secs(32 * 12);
+
 /// The type that advances an established session by listening for incoming messages (from local
 /// node or read from connection) and emitting events back to the
 /// [`SessionManager`](super::SessionManager).
@@ -106,6 +115,9 @@ pub(crate) struct ActiveSession<N: NetworkPrimitives> {
     pub(crate) queued_outgoing: QueuedOutgoingMessages<N>,
     /// The maximum time we wait for a response from a peer.
     pub(crate) internal_request_timeout: Arc<AtomicU64>,
+    /// Round trip time of the most recent ping answered by the peer in milliseconds, shared with
+    /// the [`ActiveSessionHandle`](super::ActiveSessionHandle).
+    pub(crate) latency: Arc<AtomicU64>,
     /// Interval when to check for timed out requests.
     pub(crate) internal_request_timeout_interval: Interval,
     /// If an [`ActiveSession`] does not receive a response at all within this duration then it is
@@ -114,6 +126,16 @@ pub(crate) struct ActiveSession<N: NetworkPrimitives> {
     /// Used to reserve a slot to guarantee that the termination message is delivered
     pub(crate) terminate_message:
         Option<(PollSender<ActiveSessionMessage<N>>, ActiveSessionMessage<N>)>,
+    /// The block range the peer can serve, shared with the
+    /// [`ActiveSessionHandle`](super::ActiveSessionHandle). Only tracked for `eth/69` sessions.
+    pub(crate) remote_block_range: Option<BlockRangeInfo>,
+    /// The block range the local node can serve, shared by all sessions.
+    pub(crate) local_block_range: BlockRangeInfo,
+    /// The local block range that was last announced to the peer.
+    pub(crate) last_announced_block_range: BlockRangeUpdate,
+    /// Interval when to announce changes of the local block range, only set for `eth/69`
+    /// sessions.
+    pub(crate) block_range_update_interval: Option<Interval>,
 }
 
 impl<N: NetworkPrimitives> ActiveSession<N> {
@@ -135,6 +157,21 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
         self.queued_outgoing.shrink_to_fit();
     }
 
+    /// Queues a [`BlockRangeUpdate`] if the local block range changed since it was last announced.
+    ///
+    /// The range is announced at most once per epoch: the latest block must have advanced by at
+    /// least [`BLOCK_RANGE_UPDATE_BLOCKS`] unless the earliest block changed.
+    fn maybe_announce_block_range(&mut self) {
+        let range = self.local_block_range.get();
+        let last = self.last_announced_block_range;
+        if range.earliest != last.earliest ||
+            range.latest >= last.latest.saturating_add(BLOCK_RANGE_UPDATE_BLOCKS)
+        {
+            self.queued_outgoing.push_back(EthMessage::BlockRangeUpdate(range).into());
+            self.last_announced_block_range = range;
+        }
+    }
+
     /// Returns how many responses we've currently queued up.
     fn queued_response_count(&self) -> usize {
         self.queued_outgoing.messages.iter().filter(|m| m.is_response()).count()
@@ -255,6 +292,29 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
             EthMessage::Receipts(resp) => {
                 on_response!(resp, GetReceipts)
             }
+            EthMessage::Receipts69(resp) => {
+                // restore the bloom filters, which are not part of the eth/69 receipts
+                let resp = RequestPair {
+                    request_id: resp.request_id,
+                    message: resp.message.into_with_bloom(),
+                };
+                on_response!(resp, GetReceipts)
+            }
+            EthMessage::BlockRangeUpdate(msg) => {
+                if !msg.is_valid() {
+                    return OnIncomingMessageOutcome::BadMessage {
+                        error: EthStreamError::InvalidMessage(MessageError::Invalid(
+                            self.conn.version(),
+                            EthMessageID::BlockRangeUpdate,
+                        )),
+                        message: EthMessage::BlockRangeUpdate(msg),
+                    }
+                }
+                if let Some(block_range) = &self.remote_block_range {
+                    block_range.set(msg);
+                }
+                OnIncomingMessageOutcome::Ok
+            }
         }
     }
 
@@ -275,10 +335,15 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
     fn on_internal_peer_message(&mut self, msg: PeerMessage<N>) {
         match msg {
             PeerMessage::NewBlockHashes(msg) => {
-                self.queued_outgoing.push_back(EthMessage::NewBlockHashes(msg).into());
+                // block announcements were removed in eth/69
+                if !self.conn.version().is_eth69() {
+                    self.queued_outgoing.push_back(EthMessage::NewBlockHashes(msg).into());
+                }
             }
             PeerMessage::NewBlock(msg) => {
-                self.queued_outgoing.push_back(EthBroadcastMessage::NewBlock(msg.block).into());
+                if !self.conn.version().is_eth69() {
+                    self.queued_outgoing.push_back(EthBroadcastMessage::NewBlock(msg.block).into());
+                }
             }
             PeerMessage::PooledTransactions(msg) => {
                 if msg.is_valid_for_version(self.conn.version()) {
@@ -313,6 +378,16 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
     fn handle_outgoing_response(&mut self, id: u64, resp: PeerResponseResult<N>) {
         match resp.try_into_message(id) {
             Ok(msg) => {
+                // eth/69 receipts are sent without the bloom filters
+                let msg = match msg {
+                    EthMessage::Receipts(resp) if self.conn.version().is_eth69() => {
+                        EthMessage::Receipts69(RequestPair {
+                            request_id: resp.request_id,
+                            message: resp.message.into(),
+                        })
+                    }
+                    msg => msg,
+                };
                 self.queued_outgoing.push_back(msg.into());
             }
             Err(err) => {
@@ -502,6 +577,15 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
             return this.poll_disconnect(cx)
         }
 
+        // announce the local block range before flushing the outgoing messages
+        while this
+            .block_range_update_interval
+            .as_mut()
+            .is_some_and(|interval| interval.poll_tick(cx).is_ready())
+        {
+            this.maybe_announce_block_range();
+        }
+
         // The receive loop can be CPU intensive since it involves message decoding which could take
         // up a lot of resources and increase latencies for other sessions if not yielded manually.
         // If the budget is exhausted we manually yield back control to the (coop) scheduler. This
@@ -689,6 +773,12 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
             }
         }
 
+        // the latency is measured with the periodic pings of the p2p stream, so it's also known
+        // for peers we don't exchange requests with
+        if let Some(latency) = this.conn.inner().ping_latency() {
+            this.latency.store((latency.as_millis() as u64).max(1), Ordering::Relaxed);
+        }
+
         this.shrink_to_fit();
 
         Poll::Pending
@@ -843,12 +933,13 @@ mod tests {
     use super::*;
     use crate::session::{handle::PendingSessionEvent, start_pending_incoming_session};
     use alloy_eips::eip2124::ForkFilter;
+    use alloy_primitives::B256;
     use reth_chainspec::MAINNET;
     use reth_ecies::stream::ECIESStream;
     use reth_eth_wire::{
-        handshake::EthHandshake, EthNetworkPrimitives, EthStream, GetBlockBodies,
-        HelloMessageWithProtocols, P2PStream, Status, StatusBuilder, UnauthedEthStream,
-        UnauthedP2PStream,
+        handshake::EthHandshake, EthNetworkPrimitives, EthStream, EthVersion, GetBlockBodies,
+        HelloMessageWithProtocols, P2PStream, StatusBuilder, UnauthedEthStream, UnauthedP2PStream,
+        UnifiedStatus,
     };
     use reth_ethereum_forks::EthereumHardfork;
     use reth_network_peers::pk2id;
@@ -861,7 +952,9 @@ mod tests {
 
     /// Returns a testing `HelloMessage` and new secretkey
     fn eth_hello(server_key: &SecretKey) -> HelloMessageWithProtocols {
-        HelloMessageWithProtocols::builder(pk2id(&server_key.public_key(SECP256K1))).build()
+        HelloMessageWithProtocols::builder(pk2id(&server_key.public_key(SECP256K1)))
+            .protocol(EthVersion::Eth68)
+            .build()
     }
 
     struct SessionBuilder<N: NetworkPrimitives = EthNetworkPrimitives> {
@@ -872,7 +965,7 @@ mod tests {
         secret_key: SecretKey,
         local_peer_id: PeerId,
         hello: HelloMessageWithProtocols,
-        status: Status,
+        status: UnifiedStatus,
         fork_filter: ForkFilter,
         next_id: usize,
     }
@@ -974,8 +1067,13 @@ mod tests {
                         internal_request_timeout: Arc::new(AtomicU64::new(
                             INITIAL_REQUEST_TIMEOUT.as_millis() as u64,
                         )),
+                        latency: Default::default(),
                         protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
                         terminate_message: None,
+                        remote_block_range: None,
+                        local_block_range: Default::default(),
+                        last_announced_block_range: Default::default(),
+                        block_range_update_interval: None,
                     }
                 }
                 ev => {
@@ -1001,7 +1099,7 @@ mod tests {
                 hello: eth_hello(&secret_key),
                 secret_key,
                 local_peer_id,
-                status: StatusBuilder::default().build(),
+                status: StatusBuilder::default().build().into(),
                 fork_filter: MAINNET
                     .hardfork_fork_filter(EthereumHardfork::Frontier)
                     .expect("The Frontier fork filter should exist on mainnet"),
@@ -1095,6 +1193,49 @@ mod tests {
         rx.await.unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_announce_block_range() {
+        reth_tracing::init_test_tracing();
+        let mut builder = SessionBuilder::default();
+        builder.hello = HelloMessageWithProtocols::builder(builder.local_peer_id)
+            .protocol(EthVersion::Eth69)
+            .build();
+        builder.status.set_eth_version(EthVersion::Eth69);
+        builder.status = builder.status.with_earliest_block(0).with_latest_block(0);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let range = BlockRangeUpdate {
+            earliest: 0,
+            latest: BLOCK_RANGE_UPDATE_BLOCKS,
+            latest_hash: B256::random(),
+        };
+
+        let fut = builder.with_client_stream(local_addr, move |mut client_stream| async move {
+            let msg = client_stream.next().await.unwrap().unwrap();
+            assert_eq!(msg, EthMessage::BlockRangeUpdate(range));
+        });
+
+        tokio::task::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let mut session = builder.connect_incoming(incoming).await;
+            assert!(session.conn.version().is_eth69());
+
+            // an advance of less than an epoch is not announced
+            session.local_block_range.set(BlockRangeUpdate { latest: 1, ..range });
+            session.maybe_announce_block_range();
+            assert!(session.queued_outgoing.messages.is_empty());
+
+            session.local_block_range.set(range);
+            session.block_range_update_interval =
+                Some(tokio::time::interval(BLOCK_RANGE_UPDATE_INTERVAL));
+            session.await
+        });
+
+        fut.await;
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_request_timeout() {
         reth_tracing::init_test_tracing();
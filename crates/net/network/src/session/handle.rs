@@ -7,12 +7,21 @@ use crate::{
 };
 use reth_ecies::ECIESError;
 use reth_eth_wire::{
-    errors::EthStreamError, Capabilities, DisconnectReason, EthVersion, NetworkPrimitives, Status,
+    errors::EthStreamError, BlockRangeUpdate, Capabilities, DisconnectReason, EthVersion,
+    NetworkPrimitives, Status,
 };
 use reth_network_api::PeerInfo;
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::PeerKind;
-use std::{io, net::SocketAddr, sync::Arc, time::Instant};
+use std::{
+    io,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 use tokio::sync::{
     mpsc::{self, error::SendError},
     oneshot,
@@ -74,6 +83,10 @@ pub struct ActiveSessionHandle<N: NetworkPrimitives> {
     pub(crate) local_addr: Option<SocketAddr>,
     /// The Status message the peer sent for the `eth` handshake
     pub(crate) status: Arc<Status>,
+    /// Round trip time of the most recent ping answered by the peer in milliseconds, 0 if unknown.
+    pub(crate) latency: Arc<AtomicU64>,
+    /// The block range the peer can serve, only known for `eth/69` sessions.
+    pub(crate) block_range: Option<BlockRangeInfo>,
 }
 
 // === impl ActiveSessionHandle ===
@@ -134,6 +147,22 @@ impl<N: NetworkPrimitives> ActiveSessionHandle<N> {
         self.remote_addr
     }
 
+    /// Returns the round trip time of the most recent ping answered by the peer.
+    ///
+    /// Returns `None` if the peer didn't respond to a ping yet.
+    pub fn latency(&self) -> Option<Duration> {
+        let latency = self.latency.load(Ordering::Relaxed);
+        (latency > 0).then(|| Duration::from_millis(latency))
+    }
+
+    /// Returns the block range the peer announced it can serve.
+    ///
+    /// Returns `None` if the negotiated version does not announce block ranges, i.e. before
+    /// `eth/69`.
+    pub fn block_range(&self) -> Option<BlockRangeUpdate> {
+        self.block_range.as_ref().map(BlockRangeInfo::get)
+    }
+
     /// Extracts the [`PeerInfo`] from the session handle.
     pub(crate) fn peer_info(&self, record: &NodeRecord, kind: PeerKind) -> PeerInfo {
         PeerInfo {
@@ -148,11 +177,36 @@ impl<N: NetworkPrimitives> ActiveSessionHandle<N> {
             eth_version: self.version,
             status: self.status.clone(),
             session_established: self.established,
+            latency: self.latency(),
             kind,
         }
     }
 }
 
+/// The range of blocks a node can serve, as announced in the `eth/69` status and
+/// [`BlockRangeUpdate`] messages.
+///
+/// Clones share the same range, so updates are visible to all of them.
+#[derive(Debug, Clone, Default)]
+pub struct BlockRangeInfo(Arc<RwLock<BlockRangeUpdate>>);
+
+impl BlockRangeInfo {
+    /// Creates a new instance with the given range.
+    pub fn new(range: BlockRangeUpdate) -> Self {
+        Self(Arc::new(RwLock::new(range)))
+    }
+
+    /// Returns the current range.
+    pub fn get(&self) -> BlockRangeUpdate {
+        *self.0.read()
+    }
+
+    /// Replaces the current range.
+    pub fn set(&self, range: BlockRangeUpdate) {
+        *self.0.write() = range;
+    }
+}
+
 /// Events a pending session can produce.
 ///
 /// This represents the state changes a session can undergo until it is ready to send capability messages <https://github.com/ethereum/devp2p/blob/6b0abc3d956a626c28dce1307ee9f546db17b6bd/rlpx.md>.
@@ -174,6 +228,8 @@ pub enum PendingSessionEvent<N: NetworkPrimitives> {
         capabilities: Arc<Capabilities>,
         /// The Status message the peer sent for the `eth` handshake
         status: Arc<Status>,
+        /// The block range the peer announced in its `eth/69` status message
+        block_range: Option<BlockRangeUpdate>,
         /// The actual connection stream which can be used to send and receive `eth` protocol
         /// messages
         conn: EthRlpxConnection<N>,
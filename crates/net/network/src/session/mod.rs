@@ -8,8 +8,8 @@ mod handle;
 use active::QueuedOutgoingMessages;
 pub use conn::EthRlpxConnection;
 pub use handle::{
-    ActiveSessionHandle, ActiveSessionMessage, PendingSessionEvent, PendingSessionHandle,
-    SessionCommand,
+    ActiveSessionHandle, ActiveSessionMessage, BlockRangeInfo, PendingSessionEvent,
+    PendingSessionHandle, SessionCommand,
 };
 
 pub use reth_network_api::{Direction, PeerInfo};
@@ -27,6 +27,7 @@ use crate::{
     message::PeerMessage,
     metrics::SessionManagerMetrics,
     protocol::{IntoRlpxSubProtocol, OnNotSupported, RlpxSubProtocolHandlers, RlpxSubProtocols},
+    relay::{dial_via_relay, DIRECT_DIAL_TIMEOUT},
     session::active::ActiveSession,
 };
 use counter::SessionCounter;
@@ -35,7 +36,7 @@ use reth_ecies::{stream::ECIESStream, ECIESError};
 use reth_eth_wire::{
     errors::EthStreamError, handshake::EthRlpxHandshake, multiplex::RlpxProtocolMultiplexer,
     Capabilities, DisconnectReason, EthStream, EthVersion, HelloMessageWithProtocols,
-    NetworkPrimitives, Status, UnauthedP2PStream, HANDSHAKE_TIMEOUT,
+    NetworkPrimitives, Status, UnauthedP2PStream, UnifiedStatus, HANDSHAKE_TIMEOUT,
 };
 use reth_ethereum_forks::{ForkFilter, ForkId, ForkTransition, Head};
 use reth_metrics::common::mpsc::MeteredPollSender;
@@ -77,7 +78,9 @@ pub struct SessionManager<N: NetworkPrimitives> {
     /// The secret key used for authenticating sessions.
     secret_key: SecretKey,
     /// The `Status` message to send to peers.
-    status: Status,
+    status: UnifiedStatus,
+    /// The block range the local node can serve, announced to `eth/69` peers.
+    local_block_range: BlockRangeInfo,
     /// The `HelloMessage` message to send to peers.
     hello_message: HelloMessageWithProtocols,
     /// The [`ForkFilter`] used to validate the peer's `Status` message.
@@ -115,6 +118,8 @@ pub struct SessionManager<N: NetworkPrimitives> {
     metrics: SessionManagerMetrics,
     /// The [`EthRlpxHandshake`] is used to perform the initial handshake with the peer.
     handshake: Arc<dyn EthRlpxHandshake>,
+    /// The relay to dial peers through if dialing them directly fails.
+    relay: Option<SocketAddr>,
 }
 
 // === impl SessionManager ===
@@ -126,11 +131,12 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         secret_key: SecretKey,
         config: SessionsConfig,
         executor: Box<dyn TaskSpawner>,
-        status: Status,
+        status: UnifiedStatus,
         hello_message: HelloMessageWithProtocols,
         fork_filter: ForkFilter,
         extra_protocols: RlpxSubProtocols,
         handshake: Arc<dyn EthRlpxHandshake>,
+        relay: Option<SocketAddr>,
     ) -> Self {
         let (pending_sessions_tx, pending_sessions_rx) = mpsc::channel(config.session_event_buffer);
         let (active_session_tx, active_session_rx) = mpsc::channel(config.session_event_buffer);
@@ -144,6 +150,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
             pending_session_timeout: config.pending_session_timeout,
             secret_key,
             status,
+            local_block_range: BlockRangeInfo::new(status.block_range().unwrap_or_default()),
             hello_message,
             fork_filter,
             session_command_buffer: config.session_command_buffer,
@@ -158,6 +165,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
             disconnections_counter: Default::default(),
             metrics: Default::default(),
             handshake,
+            relay,
         }
     }
 
@@ -175,7 +183,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
     }
 
     /// Returns the current status of the session.
-    pub const fn status(&self) -> Status {
+    pub const fn status(&self) -> UnifiedStatus {
         self.status
     }
 
@@ -219,8 +227,11 @@ impl<N: NetworkPrimitives> SessionManager<N> {
     /// If the updated activated another fork, this will return a [`ForkTransition`] and updates the
     /// active [`ForkId`]. See also [`ForkFilter::set_head`].
     pub(crate) fn on_status_update(&mut self, head: Head) -> Option<ForkTransition> {
-        self.status.blockhash = head.hash;
-        self.status.total_difficulty = head.total_difficulty;
+        self.status.set_head(&head);
+        if let Some(block_range) = self.status.block_range() {
+            // active `eth/69` sessions announce the new range to their peers
+            self.local_block_range.set(block_range);
+        }
         let transition = self.fork_filter.set_head(head);
         self.status.forkid = self.fork_filter.current();
         transition
@@ -308,6 +319,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     session_id,
                     remote_addr,
                     remote_peer_id,
+                    self.relay,
                     secret_key,
                     hello_message,
                     status,
@@ -475,6 +487,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                 capabilities,
                 conn,
                 status,
+                block_range,
                 direction,
                 client_id,
             } => {
@@ -515,9 +528,17 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     self.initial_internal_request_timeout.as_millis() as u64,
                 ));
 
+                let latency = Arc::new(AtomicU64::new(0));
+
                 // negotiated version
                 let version = conn.version();
 
+                // block ranges are only announced since eth/69
+                let remote_block_range = block_range.map(BlockRangeInfo::new);
+                let block_range_update_interval = version
+                    .is_eth69()
+                    .then(|| tokio::time::interval(active::BLOCK_RANGE_UPDATE_INTERVAL));
+
                 let session = ActiveSession {
                     next_id: 0,
                     remote_peer_id: peer_id,
@@ -538,8 +559,13 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                         self.initial_internal_request_timeout,
                     ),
                     internal_request_timeout: Arc::clone(&timeout),
+                    latency: Arc::clone(&latency),
                     protocol_breach_request_timeout: self.protocol_breach_request_timeout,
                     terminate_message: None,
+                    remote_block_range: remote_block_range.clone(),
+                    local_block_range: self.local_block_range.clone(),
+                    last_announced_block_range: self.status.block_range().unwrap_or_default(),
+                    block_range_update_interval,
                 };
 
                 self.spawn(session);
@@ -557,6 +583,8 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     client_version: Arc::clone(&client_version),
                     remote_addr,
                     local_addr,
+                    latency,
+                    block_range: remote_block_range,
                 };
 
                 self.active_sessions.insert(peer_id, handle);
@@ -828,7 +856,7 @@ pub(crate) async fn start_pending_incoming_session<N: NetworkPrimitives>(
     remote_addr: SocketAddr,
     secret_key: SecretKey,
     hello: HelloMessageWithProtocols,
-    status: Status,
+    status: UnifiedStatus,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
@@ -859,13 +887,14 @@ async fn start_pending_outbound_session<N: NetworkPrimitives>(
     session_id: SessionId,
     remote_addr: SocketAddr,
     remote_peer_id: PeerId,
+    relay: Option<SocketAddr>,
     secret_key: SecretKey,
     hello: HelloMessageWithProtocols,
-    status: Status,
+    status: UnifiedStatus,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
-    let stream = match TcpStream::connect(remote_addr).await {
+    let stream = match connect_outbound(remote_addr, remote_peer_id, relay).await {
         Ok(stream) => {
             if let Err(err) = stream.set_nodelay(true) {
                 tracing::warn!(target: "net::session", "set nodelay failed: {:?}", err);
@@ -901,6 +930,37 @@ async fn start_pending_outbound_session<N: NetworkPrimitives>(
     .await
 }
 
+/// Connects to the peer, falling back to dialing it through the relay, if configured.
+///
+/// If a relay is configured, the direct connection attempt is abandoned after
+/// [`DIRECT_DIAL_TIMEOUT`] instead of the OS connect timeout, since peers behind a NAT usually
+/// drop the connection attempt. Returns the error of the direct connection attempt if both fail.
+async fn connect_outbound(
+    remote_addr: SocketAddr,
+    remote_peer_id: PeerId,
+    relay: Option<SocketAddr>,
+) -> io::Result<TcpStream> {
+    let Some(relay) = relay else { return TcpStream::connect(remote_addr).await };
+
+    let err = match tokio::time::timeout(DIRECT_DIAL_TIMEOUT, TcpStream::connect(remote_addr)).await
+    {
+        Ok(Ok(stream)) => return Ok(stream),
+        Ok(Err(err)) => err,
+        Err(_) => io::Error::new(io::ErrorKind::TimedOut, "direct dial timed out"),
+    };
+
+    match dial_via_relay(relay, remote_peer_id).await {
+        Ok(stream) => {
+            trace!(target: "net::session", %relay, ?remote_peer_id, "dialed peer through relay");
+            Ok(stream)
+        }
+        Err(relay_err) => {
+            trace!(target: "net::session", %relay_err, ?remote_peer_id, "relay dial failed");
+            Err(err)
+        }
+    }
+}
+
 /// Authenticates a session
 #[allow(clippy::too_many_arguments)]
 async fn authenticate<N: NetworkPrimitives>(
@@ -913,7 +973,7 @@ async fn authenticate<N: NetworkPrimitives>(
     secret_key: SecretKey,
     direction: Direction,
     hello: HelloMessageWithProtocols,
-    status: Status,
+    status: UnifiedStatus,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
@@ -996,7 +1056,7 @@ async fn authenticate_stream<N: NetworkPrimitives>(
     local_addr: Option<SocketAddr>,
     direction: Direction,
     mut hello: HelloMessageWithProtocols,
-    mut status: Status,
+    mut status: UnifiedStatus,
     fork_filter: ForkFilter,
     mut extra_handlers: RlpxSubProtocolHandlers,
 ) -> PendingSessionEvent<N> {
@@ -1055,12 +1115,13 @@ async fn authenticate_stream<N: NetworkPrimitives>(
         }
     };
 
+    // Before trying status handshake, set up the version to negotiated shared version, this
+    // determines the status message that is sent
+    status.set_eth_version(eth_version);
+
     let (conn, their_status) = if p2p_stream.shared_capabilities().len() == 1 {
         // if the shared caps are 1, we know both support the eth version
         // if the hello handshake was successful we can try status handshake
-        //
-        // Before trying status handshake, set up the version to negotiated shared version
-        status.set_eth_version(eth_version);
 
         // perform the eth protocol handshake
         match handshake
@@ -1118,7 +1179,8 @@ async fn authenticate_stream<N: NetworkPrimitives>(
         local_addr,
         peer_id: their_hello.id,
         capabilities: Arc::new(Capabilities::from(their_hello.capabilities)),
-        status: Arc::new(their_status),
+        status: Arc::new(their_status.into_legacy()),
+        block_range: their_status.block_range(),
         conn,
         direction,
         client_id: their_hello.client_version,
@@ -0,0 +1,358 @@
+//! A harness for end-to-end sync tests between in-process nodes.
+
+use crate::{
+    test_utils::{NetworkEventStream, Testnet, TestnetHandle},
+    FetchClient,
+};
+use alloy_consensus::{BlockHeader, Header};
+use alloy_primitives::{BlockHash, BlockNumber, B256, U256};
+use futures::StreamExt;
+use reth_chainspec::{ChainSpecProvider, Hardforks};
+use reth_consensus::{AsHeaderValidator, Consensus, ConsensusError, HeaderValidator};
+use reth_downloaders::{
+    bodies::bodies::BodiesDownloaderBuilder,
+    headers::reverse_headers::ReverseHeadersDownloaderBuilder,
+};
+use reth_eth_wire::EthNetworkPrimitives;
+use reth_ethereum_primitives::{Block, BlockBody, Receipt};
+use reth_network_api::{BlockDownloaderProvider, Peers};
+use reth_network_p2p::{
+    bodies::{downloader::BodyDownloader, response::BlockResponse},
+    error::{DownloadError, DownloadResult, RequestError},
+    headers::{
+        downloader::{HeaderDownloader, SyncTarget},
+        error::HeadersDownloaderError,
+    },
+};
+use reth_primitives_traits::{GotExpected, SealedBlock, SealedHeader};
+use reth_storage_api::{
+    errors::provider::ProviderResult, noop::NoopProvider, BlockReader, HeaderProvider,
+};
+use reth_transaction_pool::test_utils::TestPool;
+use std::{
+    fmt,
+    ops::{RangeBounds, RangeInclusive},
+    sync::Arc,
+};
+
+/// A harness that syncs the chain of a populated node into a fresh node.
+///
+/// Both nodes run in-process and are connected over a local session, so a sync is deterministic
+/// and not slowed down by any latency. The fresh node downloads the chain with the headers and
+/// bodies downloaders of the pipeline, see [`ReverseHeadersDownloaderBuilder`] and
+/// [`BodiesDownloaderBuilder`].
+///
+/// The [`FetchClient`] of the fresh node is exposed via [`SyncHarness::client`], so other
+/// downloaders can be tested against the populated node as well.
+pub struct SyncHarness<C: ChainSpecProvider> {
+    /// The provider of the populated node.
+    provider: C,
+    /// Consensus used to validate the downloaded headers and bodies.
+    consensus: Arc<dyn Consensus<Block, Error = ConsensusError>>,
+    /// The fetch client of the fresh node.
+    client: FetchClient<EthNetworkPrimitives>,
+    /// The network of the populated node.
+    _populated: TestnetHandle<C, TestPool>,
+    /// The network of the fresh node.
+    _fresh: TestnetHandle<NoopProvider<C::ChainSpec>, TestPool>,
+}
+
+// === impl SyncHarness ===
+
+impl<C> SyncHarness<C>
+where
+    C: BlockReader<Block = Block, Receipt = Receipt, Header = Header>
+        + HeaderProvider
+        + ChainSpecProvider<ChainSpec: Hardforks>
+        + Clone
+        + Unpin
+        + 'static,
+{
+    /// Same as [`Self::try_new`] but panics on error.
+    pub async fn new(provider: C) -> Self {
+        Self::try_new(provider).await.unwrap()
+    }
+
+    /// Launches a node that serves the chain of the given provider and a fresh node without any
+    /// blocks, and connects them.
+    ///
+    /// Returns once the session between the nodes is established.
+    pub async fn try_new(provider: C) -> Result<Self, crate::error::NetworkError> {
+        let mut populated = Testnet::try_create_with(1, provider.clone()).await?;
+        populated.for_each_mut(|peer| peer.install_request_handler());
+        let populated = populated.spawn();
+
+        let fresh = Testnet::try_create_with(1, NoopProvider::new(provider.chain_spec())).await?;
+        let fresh = fresh.spawn();
+
+        let source = &populated.peers()[0];
+        let target = &fresh.peers()[0];
+        let mut events = NetworkEventStream::new(target.event_listener());
+        target.network().add_peer(*source.peer_id(), source.local_addr());
+        events.next_session_established().await;
+
+        let client = target.network().fetch_client().await.expect("network manager is running");
+
+        Ok(Self {
+            provider,
+            consensus: Arc::new(ConnectedHeaders),
+            client,
+            _populated: populated,
+            _fresh: fresh,
+        })
+    }
+
+    /// Sets the consensus used to validate the downloaded headers and bodies.
+    ///
+    /// By default, headers are only checked to be connected to each other.
+    pub fn with_consensus(
+        mut self,
+        consensus: Arc<dyn Consensus<Block, Error = ConsensusError>>,
+    ) -> Self {
+        self.consensus = consensus;
+        self
+    }
+
+    /// Returns the provider of the populated node.
+    pub const fn provider(&self) -> &C {
+        &self.provider
+    }
+
+    /// Returns the [`FetchClient`] of the fresh node, which is connected to the populated node.
+    pub const fn client(&self) -> &FetchClient<EthNetworkPrimitives> {
+        &self.client
+    }
+
+    /// Downloads and validates the chain ending at `tip` on top of the genesis block of the
+    /// populated node, which the fresh node is assumed to be initialized with.
+    ///
+    /// Returns the downloaded blocks in ascending order, excluding the genesis block.
+    pub async fn sync(&self, tip: B256) -> DownloadResult<Vec<SealedBlock<Block>>> {
+        let genesis = self
+            .provider
+            .sealed_header(0)?
+            .ok_or(DownloadError::MissingHeader { block_number: 0 })?;
+        self.sync_from(genesis, tip).await
+    }
+
+    /// Downloads and validates the chain ending at `tip` on top of the given local head of the
+    /// fresh node.
+    ///
+    /// Returns the downloaded blocks in ascending order, excluding the local head.
+    pub async fn sync_from(
+        &self,
+        local_head: SealedHeader,
+        tip: B256,
+    ) -> DownloadResult<Vec<SealedBlock<Block>>> {
+        if local_head.hash() == tip {
+            return Ok(Vec::new())
+        }
+
+        let headers = self.download_headers(local_head.clone(), tip).await?;
+        let range = local_head.number() + 1..=headers.last().expect("not empty").number();
+        let headers = DownloadedHeaders(std::iter::once(local_head).chain(headers).collect());
+        self.download_bodies(headers, range).await
+    }
+
+    /// Syncs the chain ending at `tip` and asserts that the downloaded blocks match the chain of
+    /// the populated node.
+    ///
+    /// Returns the downloaded blocks in ascending order.
+    pub async fn assert_full_sync(&self, tip: B256) -> Vec<SealedBlock<Block>> {
+        let blocks = self.sync(tip).await.expect("failed to sync chain");
+
+        let tip_number = blocks.last().map(|block| block.number());
+        assert_eq!(blocks.len() as u64, tip_number.unwrap_or_default());
+
+        for block in &blocks {
+            let expected = self
+                .provider
+                .block(block.hash().into())
+                .expect("failed to read block")
+                .unwrap_or_else(|| panic!("block {} is missing", block.hash()));
+            assert_eq!(block.header(), &expected.header, "header mismatch at {}", block.number());
+            assert_eq!(block.body(), &expected.body, "body mismatch at {}", block.number());
+        }
+
+        blocks
+    }
+
+    /// Downloads the headers from `tip` down to the child of the local head with the reverse
+    /// headers downloader.
+    ///
+    /// Returns the headers in ascending order.
+    async fn download_headers(
+        &self,
+        local_head: SealedHeader,
+        tip: B256,
+    ) -> DownloadResult<Vec<SealedHeader>> {
+        let mut downloader = ReverseHeadersDownloaderBuilder::default()
+            .build(self.client.clone(), self.consensus.clone().as_header_validator());
+        let first = local_head.number() + 1;
+        downloader.update_local_head(local_head);
+        downloader.update_sync_target(SyncTarget::Tip(tip));
+
+        let mut headers: Vec<SealedHeader> = Vec::new();
+        while headers.last().is_none_or(|header| header.number() > first) {
+            let batch = downloader.next().await.ok_or(RequestError::ChannelClosed)?.map_err(
+                |HeadersDownloaderError::DetachedHead { header, error, .. }| {
+                    DownloadError::HeaderValidation {
+                        hash: header.hash(),
+                        number: header.number(),
+                        error,
+                    }
+                },
+            )?;
+            headers.extend(batch);
+        }
+
+        headers.reverse();
+        Ok(headers)
+    }
+
+    /// Downloads the bodies of the given headers in the range with the bodies downloader.
+    async fn download_bodies(
+        &self,
+        headers: DownloadedHeaders,
+        range: RangeInclusive<BlockNumber>,
+    ) -> DownloadResult<Vec<SealedBlock<Block>>> {
+        let mut downloader = BodiesDownloaderBuilder::default().build(
+            self.client.clone(),
+            self.consensus.clone(),
+            headers,
+        );
+        downloader.set_download_range(range.clone())?;
+
+        let len = range.count();
+        let mut blocks = Vec::with_capacity(len);
+        while blocks.len() < len {
+            let batch = downloader.next().await.ok_or(RequestError::ChannelClosed)??;
+            blocks.extend(batch.into_iter().map(|response| match response {
+                BlockResponse::Full(block) => block,
+                BlockResponse::Empty(header) => {
+                    let body = BlockBody {
+                        withdrawals: header.withdrawals_root().map(|_| Default::default()),
+                        ..Default::default()
+                    };
+                    SealedBlock::from_sealed_parts(header, body)
+                }
+            }));
+        }
+
+        Ok(blocks)
+    }
+}
+
+impl<C: ChainSpecProvider> fmt::Debug for SyncHarness<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyncHarness").finish_non_exhaustive()
+    }
+}
+
+/// The default consensus of the [`SyncHarness`], which only checks that headers are connected to
+/// their parents.
+#[derive(Debug)]
+struct ConnectedHeaders;
+
+impl HeaderValidator for ConnectedHeaders {
+    fn validate_header(&self, _header: &SealedHeader) -> Result<(), ConsensusError> {
+        Ok(())
+    }
+
+    fn validate_header_against_parent(
+        &self,
+        header: &SealedHeader,
+        parent: &SealedHeader,
+    ) -> Result<(), ConsensusError> {
+        if header.parent_hash() != parent.hash() {
+            return Err(ConsensusError::ParentHashMismatch(
+                GotExpected { got: header.parent_hash(), expected: parent.hash() }.into(),
+            ))
+        }
+        if header.number() != parent.number() + 1 {
+            return Err(ConsensusError::ParentBlockNumberMismatch {
+                parent_block_number: parent.number(),
+                block_number: header.number(),
+            })
+        }
+        Ok(())
+    }
+
+    fn validate_header_with_total_difficulty(
+        &self,
+        _header: &Header,
+        _total_difficulty: U256,
+    ) -> Result<(), ConsensusError> {
+        Ok(())
+    }
+}
+
+impl Consensus<Block> for ConnectedHeaders {
+    type Error = ConsensusError;
+
+    fn validate_body_against_header(
+        &self,
+        _body: &BlockBody,
+        _header: &SealedHeader,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn validate_block_pre_execution(&self, _block: &SealedBlock<Block>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// The headers downloaded by the fresh node in ascending order, which the bodies downloader
+/// reads the headers of the requested bodies from.
+#[derive(Debug)]
+struct DownloadedHeaders(Vec<SealedHeader>);
+
+impl DownloadedHeaders {
+    /// Returns the header with the given number.
+    fn get(&self, number: BlockNumber) -> Option<&SealedHeader> {
+        let first = self.0.first()?.number();
+        self.0.get(number.checked_sub(first)? as usize)
+    }
+
+    /// Returns the headers in the given range.
+    fn range(&self, range: impl RangeBounds<BlockNumber>) -> impl Iterator<Item = &SealedHeader> {
+        self.0.iter().filter(move |header| range.contains(&header.number()))
+    }
+}
+
+impl HeaderProvider for DownloadedHeaders {
+    type Header = Header;
+
+    fn header(&self, block_hash: &BlockHash) -> ProviderResult<Option<Header>> {
+        Ok(self.0.iter().find(|header| header.hash() == *block_hash).map(|h| h.clone_header()))
+    }
+
+    fn header_by_number(&self, num: u64) -> ProviderResult<Option<Header>> {
+        Ok(self.get(num).map(|header| header.clone_header()))
+    }
+
+    fn header_td(&self, _hash: &BlockHash) -> ProviderResult<Option<U256>> {
+        Ok(None)
+    }
+
+    fn header_td_by_number(&self, _number: BlockNumber) -> ProviderResult<Option<U256>> {
+        Ok(None)
+    }
+
+    fn headers_range(&self, range: impl RangeBounds<BlockNumber>) -> ProviderResult<Vec<Header>> {
+        Ok(self.range(range).map(|header| header.clone_header()).collect())
+    }
+
+    fn sealed_header(&self, number: BlockNumber) -> ProviderResult<Option<SealedHeader>> {
+        Ok(self.get(number).cloned())
+    }
+
+    fn sealed_headers_while(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+        mut predicate: impl FnMut(&SealedHeader) -> bool,
+    ) -> ProviderResult<Vec<SealedHeader>> {
+        Ok(self.range(range).take_while(|header| predicate(header)).cloned().collect())
+    }
+}
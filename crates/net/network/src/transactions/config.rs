@@ -4,10 +4,12 @@ use super::{
     SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
 };
 use crate::transactions::constants::tx_fetcher::{
-    DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH, DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS,
-    DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
+    DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH, DEFAULT_MAX_CAPACITY_CACHE_RECENTLY_FETCHED,
+    DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS, DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
+    DEFAULT_TTL_RECENTLY_FETCHED,
 };
 use derive_more::Constructor;
+use std::time::Duration;
 
 /// Configuration for managing transactions within the network.
 #[derive(Debug, Clone)]
@@ -20,6 +22,13 @@ pub struct TransactionsManagerConfig {
     /// How new pending transactions are propagated.
     #[cfg_attr(feature = "serde", serde(default))]
     pub propagation_mode: TransactionPropagationMode,
+    /// Whether new transactions are only announced by their hashes and never sent in full.
+    ///
+    /// Peers request the announced transactions with `GetPooledTransactions`, which are still
+    /// served. This reduces the upstream bandwidth, since every peer only fetches a transaction
+    /// once, at the cost of an extra round trip.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub propagate_hashes_only: bool,
 }
 
 impl Default for TransactionsManagerConfig {
@@ -28,6 +37,7 @@ impl Default for TransactionsManagerConfig {
             transaction_fetcher_config: TransactionFetcherConfig::default(),
             max_transactions_seen_by_peer_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             propagation_mode: TransactionPropagationMode::default(),
+            propagate_hashes_only: false,
         }
     }
 }
@@ -79,6 +89,9 @@ pub struct TransactionFetcherConfig {
     /// [`GetPooledTransactions`](reth_eth_wire::GetPooledTransactions) yet, or it wasn't returned
     /// upon request to peers.
     pub max_capacity_cache_txns_pending_fetch: u32,
+    /// Configuration for deduplicating announcements of recently fetched transactions.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub announcement_dedup: AnnouncementDedupConfig,
 }
 
 impl Default for TransactionFetcherConfig {
@@ -91,6 +104,36 @@ impl Default for TransactionFetcherConfig {
             soft_limit_byte_size_pooled_transactions_response_on_pack_request:
                 DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
                 max_capacity_cache_txns_pending_fetch: DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH,
+            announcement_dedup: AnnouncementDedupConfig::default(),
+        }
+    }
+}
+
+/// Configuration for the cache of recently fetched transaction hashes.
+///
+/// Transactions that were fetched, but didn't make it into the pool, e.g. because they're
+/// underpriced, keep being announced by other peers. Hashes announced within the TTL of their
+/// fetch aren't requested again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct AnnouncementDedupConfig {
+    /// Max capacity of the cache of recently fetched transaction hashes.
+    pub max_capacity: u32,
+    /// Time for which a fetched transaction hash is kept track of.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub ttl: Duration,
+    /// Whether the cache grows beyond its capacity, while it's full and many announced hashes
+    /// were fetched recently.
+    pub adaptive: bool,
+}
+
+impl Default for AnnouncementDedupConfig {
+    fn default() -> Self {
+        Self {
+            max_capacity: DEFAULT_MAX_CAPACITY_CACHE_RECENTLY_FETCHED,
+            ttl: DEFAULT_TTL_RECENTLY_FETCHED,
+            adaptive: false,
         }
     }
 }
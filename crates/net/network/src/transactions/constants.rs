@@ -61,6 +61,7 @@ pub mod tx_fetcher {
     use reth_network_types::peers::config::{
         DEFAULT_MAX_COUNT_PEERS_INBOUND, DEFAULT_MAX_COUNT_PEERS_OUTBOUND,
     };
+    use std::time::Duration;
 
     use super::{
         SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
@@ -148,6 +149,38 @@ pub mod tx_fetcher {
     pub const DEFAULT_MAX_COUNT_PENDING_FETCH: usize =
         DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH as usize / 2;
 
+    /* ============== HASHES RECENTLY FETCHED ============== */
+
+    /// Default limit for number of recently fetched transaction hashes to keep track of, to
+    /// filter out announcements of transactions that were already fetched.
+    ///
+    /// Default is [`DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH`], which defaults to 25 600 hashes.
+    pub const DEFAULT_MAX_CAPACITY_CACHE_RECENTLY_FETCHED: u32 =
+        DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH;
+
+    /// Default time in seconds for which a fetched transaction hash is kept track of.
+    ///
+    /// Default is 60 seconds.
+    pub const DEFAULT_TTL_SECS_RECENTLY_FETCHED: u64 = 60;
+
+    /// Default time for which a fetched transaction hash is kept track of.
+    ///
+    /// Default is [`DEFAULT_TTL_SECS_RECENTLY_FETCHED`], which defaults to 60 seconds.
+    pub const DEFAULT_TTL_RECENTLY_FETCHED: Duration =
+        Duration::from_secs(DEFAULT_TTL_SECS_RECENTLY_FETCHED);
+
+    /// Default factor by which the cache of recently fetched hashes can grow beyond its configured
+    /// capacity, in adaptive mode.
+    ///
+    /// Default is 4.
+    pub const DEFAULT_MAX_GROWTH_FACTOR_CACHE_RECENTLY_FETCHED: u32 = 4;
+
+    /// Default share of announced hashes that were fetched recently, at which the full cache of
+    /// recently fetched hashes grows, in adaptive mode.
+    ///
+    /// Default is 10%.
+    pub const DEFAULT_DUPLICATE_RATE_GROW_CACHE_RECENTLY_FETCHED: f64 = 0.1;
+
     /* ====== LIMITED CAPACITY ON FETCH PENDING HASHES ====== */
 
     /// Default budget for finding an idle fallback peer for any hash pending fetch, when said
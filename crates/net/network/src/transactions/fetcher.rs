@@ -26,7 +26,7 @@
 //! enough to buffer many hashes during network failure, to allow for recovery.
 
 use super::{
-    config::TransactionFetcherConfig,
+    config::{AnnouncementDedupConfig, TransactionFetcherConfig},
     constants::{tx_fetcher::*, SOFT_LIMIT_COUNT_HASHES_IN_GET_POOLED_TRANSACTIONS_REQUEST},
     MessageFilter, PeerMetadata, PooledTransactions,
     SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
@@ -58,7 +58,7 @@ use std::{
     collections::HashMap,
     pin::Pin,
     task::{ready, Context, Poll},
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tokio::sync::{mpsc::error::TrySendError, oneshot, oneshot::error::RecvError};
 use tracing::trace;
@@ -87,6 +87,8 @@ pub struct TransactionFetcher<N: NetworkPrimitives = EthNetworkPrimitives> {
     pub hashes_pending_fetch: LruCache<TxHash>,
     /// Tracks all hashes in the transaction fetcher.
     pub(super) hashes_fetch_inflight_and_pending_fetch: LruMap<TxHash, TxFetchMetadata, ByLength>,
+    /// Hashes that were fetched recently, for which announcements are filtered out.
+    pub hashes_recently_fetched: AnnouncementDedupCache,
     /// Filter for valid announcement and response data.
     pub(super) filter_valid_message: MessageFilter,
     /// Info on capacity of the transaction fetcher.
@@ -113,6 +115,9 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
 
         metrics.hashes_pending_fetch.set(hashes_pending_fetch);
         metrics.hashes_inflight_transaction_requests.set(total_hashes - hashes_pending_fetch);
+
+        metrics.hashes_recently_fetched.set(self.hashes_recently_fetched.len() as f64);
+        metrics.capacity_cache_recently_fetched.set(self.hashes_recently_fetched.capacity() as f64);
     }
 
     #[inline]
@@ -131,6 +136,7 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
         let TransactionFetcherConfig {
             max_inflight_requests,
             max_capacity_cache_txns_pending_fetch,
+            announcement_dedup,
             ..
         } = *config;
 
@@ -145,6 +151,7 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
             hashes_fetch_inflight_and_pending_fetch: LruMap::new(
                 max_inflight_requests + max_capacity_cache_txns_pending_fetch,
             ),
+            hashes_recently_fetched: AnnouncementDedupCache::new(announcement_dedup),
             info,
             metrics,
             ..Default::default()
@@ -497,7 +504,7 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
     }
 
     /// Filters out hashes that have been seen before. For hashes that have already been seen, the
-    /// peer is added as fallback peer.
+    /// peer is added as fallback peer. Hashes that were fetched recently are filtered out too.
     pub fn filter_unseen_and_pending_hashes(
         &mut self,
         new_announced_hashes: &mut ValidAnnouncementData,
@@ -506,6 +513,7 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
         client_version: &str,
     ) {
         let mut previously_unseen_hashes_count = 0;
+        let mut recently_fetched_hashes_count = 0;
 
         let msg_version = new_announced_hashes.msg_version();
 
@@ -547,6 +555,11 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
                 return false
             }
 
+            if self.hashes_recently_fetched.on_announced_hash(hash) {
+                recently_fetched_hashes_count += 1;
+                return false
+            }
+
             previously_unseen_hashes_count += 1;
 
             if self.hashes_fetch_inflight_and_pending_fetch.get_or_insert(*hash, ||
@@ -566,9 +579,16 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
             true
         });
 
+        if recently_fetched_hashes_count > 0 {
+            self.metrics
+                .occurrences_hashes_recently_fetched
+                .increment(recently_fetched_hashes_count);
+        }
+
         trace!(target: "net::tx",
             peer_id=format!("{peer_id:#}"),
             previously_unseen_hashes_count=previously_unseen_hashes_count,
+            recently_fetched_hashes_count=recently_fetched_hashes_count,
             msg_version=?msg_version,
             client_version=%client_version,
             "received previously unseen hashes in announcement from peer"
@@ -940,6 +960,7 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
                     if valid_payload.contains_key(requested_hash) {
                         // hash is now known, stop tracking
                         fetched.push(*requested_hash);
+                        self.hashes_recently_fetched.insert(*requested_hash);
                         return false
                     }
                     true
@@ -1006,6 +1027,7 @@ impl<T: NetworkPrimitives> Default for TransactionFetcher<T> {
             hashes_fetch_inflight_and_pending_fetch: LruMap::new(
                 DEFAULT_MAX_CAPACITY_CACHE_INFLIGHT_AND_PENDING_FETCH,
             ),
+            hashes_recently_fetched: AnnouncementDedupCache::new(Default::default()),
             filter_valid_message: Default::default(),
             info: TransactionFetcherInfo::default(),
             metrics: Default::default(),
@@ -1042,6 +1064,124 @@ impl TxFetchMetadata {
     }
 }
 
+/// Cache of recently fetched transaction hashes, used to deduplicate announcements.
+///
+/// In adaptive mode, the capacity doubles, up to
+/// [`DEFAULT_MAX_GROWTH_FACTOR_CACHE_RECENTLY_FETCHED`] times the configured capacity, if the cache
+/// evicts hashes before their TTL expires while at least
+/// [`DEFAULT_DUPLICATE_RATE_GROW_CACHE_RECENTLY_FETCHED`] of the announced hashes were fetched
+/// recently. The duplicate rate is measured over windows of as many announced hashes as the
+/// capacity of the cache.
+#[derive(Debug)]
+pub struct AnnouncementDedupCache {
+    /// Recently fetched hashes with the time they were fetched.
+    hashes: LruMap<TxHash, Instant, ByLength>,
+    /// Current capacity of the cache.
+    capacity: u32,
+    config: AnnouncementDedupConfig,
+    /// Number of announced hashes checked against the cache in the current window.
+    announced: u32,
+    /// Number of announced hashes that were fetched recently in the current window.
+    duplicates: u32,
+}
+
+impl AnnouncementDedupCache {
+    /// Returns a new cache with the given configuration.
+    pub fn new(config: AnnouncementDedupConfig) -> Self {
+        Self {
+            hashes: LruMap::new(config.max_capacity),
+            capacity: config.max_capacity,
+            config,
+            announced: 0,
+            duplicates: 0,
+        }
+    }
+
+    /// Records that the transaction of the given hash was fetched.
+    pub fn insert(&mut self, hash: TxHash) {
+        self.hashes.insert(hash, Instant::now());
+    }
+
+    /// Returns `true` if the announced hash was fetched within the TTL.
+    pub fn on_announced_hash(&mut self, hash: &TxHash) -> bool {
+        let is_duplicate = match self.hashes.peek(hash) {
+            Some(fetched_at) if fetched_at.elapsed() < self.config.ttl => true,
+            Some(_) => {
+                self.hashes.remove(hash);
+                false
+            }
+            None => false,
+        };
+
+        self.announced += 1;
+        if is_duplicate {
+            self.duplicates += 1;
+        }
+        if self.announced >= self.capacity {
+            self.on_window_end();
+        }
+
+        is_duplicate
+    }
+
+    /// Returns the current capacity of the cache.
+    pub const fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Returns the number of cached hashes.
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Returns `true` if no hashes are cached.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    /// Grows the cache if it's under duplicate pressure, and starts a new window.
+    fn on_window_end(&mut self) {
+        let duplicate_rate = self.duplicates as f64 / self.announced as f64;
+        self.announced = 0;
+        self.duplicates = 0;
+
+        let max_capacity = self
+            .config
+            .max_capacity
+            .saturating_mul(DEFAULT_MAX_GROWTH_FACTOR_CACHE_RECENTLY_FETCHED);
+        if !self.config.adaptive ||
+            self.capacity >= max_capacity ||
+            duplicate_rate < DEFAULT_DUPLICATE_RATE_GROW_CACHE_RECENTLY_FETCHED
+        {
+            return
+        }
+
+        // the cache is too small if its least recently fetched hash hasn't expired yet
+        let evicts_unexpired = self.hashes.len() >= self.capacity as usize &&
+            self.hashes
+                .iter()
+                .last()
+                .is_some_and(|(_, fetched_at)| fetched_at.elapsed() < self.config.ttl);
+        if !evicts_unexpired {
+            return
+        }
+
+        self.capacity = self.capacity.saturating_mul(2).min(max_capacity);
+        let mut hashes = LruMap::new(self.capacity);
+        // insert the least recently fetched hashes first, to keep the order
+        for (hash, fetched_at) in self.hashes.iter().collect::<Vec<_>>().into_iter().rev() {
+            hashes.insert(*hash, *fetched_at);
+        }
+        self.hashes = hashes;
+
+        trace!(target: "net::tx",
+            capacity=self.capacity,
+            %duplicate_rate,
+            "grew cache of recently fetched hashes"
+        );
+    }
+}
+
 /// Represents possible events from fetching transactions.
 #[derive(Debug)]
 pub enum FetchEvent<T = PooledTransaction> {
@@ -1272,6 +1412,7 @@ impl From<TransactionFetcherConfig> for TransactionFetcherInfo {
             soft_limit_byte_size_pooled_transactions_response,
             soft_limit_byte_size_pooled_transactions_response_on_pack_request,
             max_capacity_cache_txns_pending_fetch,
+            ..
         } = config;
 
         Self::new(
@@ -1510,4 +1651,44 @@ mod test {
         assert_eq!(1, verified_payload.len());
         assert!(verified_payload.contains(&signed_tx_1));
     }
+
+    #[test]
+    fn dedup_recently_fetched_hashes() {
+        let hashes = [B256::with_last_byte(1), B256::with_last_byte(2), B256::with_last_byte(3)];
+        let config = AnnouncementDedupConfig {
+            max_capacity: 2,
+            ttl: Duration::from_secs(60),
+            adaptive: true,
+        };
+
+        let mut cache = AnnouncementDedupCache::new(config);
+        assert!(!cache.on_announced_hash(&hashes[0]));
+        cache.insert(hashes[0]);
+        cache.insert(hashes[1]);
+
+        // half of the hashes in the window were fetched recently, and the cache is full
+        assert!(cache.on_announced_hash(&hashes[0]));
+        assert_eq!(cache.capacity(), 4);
+
+        // the least recently fetched hash isn't evicted after growing
+        cache.insert(hashes[2]);
+        assert!(cache.on_announced_hash(&hashes[0]));
+        assert_eq!(cache.len(), 3);
+
+        // fixed capacity
+        let mut cache =
+            AnnouncementDedupCache::new(AnnouncementDedupConfig { adaptive: false, ..config });
+        cache.insert(hashes[0]);
+        cache.insert(hashes[1]);
+        assert!(cache.on_announced_hash(&hashes[0]));
+        assert!(cache.on_announced_hash(&hashes[1]));
+        assert_eq!(cache.capacity(), 2);
+
+        // expired hashes are requested again
+        let mut cache =
+            AnnouncementDedupCache::new(AnnouncementDedupConfig { ttl: Duration::ZERO, ..config });
+        cache.insert(hashes[0]);
+        assert!(!cache.on_announced_hash(&hashes[0]));
+        assert!(cache.is_empty());
+    }
 }
@@ -12,7 +12,10 @@ pub use self::constants::{
     tx_fetcher::DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
     SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
 };
-pub use config::{TransactionFetcherConfig, TransactionPropagationMode, TransactionsManagerConfig};
+pub use config::{
+    AnnouncementDedupConfig, TransactionFetcherConfig, TransactionPropagationMode,
+    TransactionsManagerConfig,
+};
 pub use validation::*;
 
 pub(crate) use fetcher::{FetchEvent, TransactionFetcher};
@@ -29,6 +32,7 @@ use crate::{
     metrics::{TransactionsManagerMetrics, NETWORK_POOL_TRANSACTIONS_SCOPE},
     NetworkHandle,
 };
+use alloy_eips::eip2718::Encodable2718;
 use alloy_primitives::{TxHash, B256};
 use constants::SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE;
 use futures::{stream::FuturesUnordered, Future, StreamExt};
@@ -490,6 +494,19 @@ where
         }
     }
 
+    /// Keeps track of the hashes in an announcement from a peer that are already known.
+    fn on_announcement_duplicates(
+        &mut self,
+        peer_id: &PeerId,
+        announced_hashes_count: usize,
+        known_hashes_count: usize,
+    ) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.announced_hashes += announced_hashes_count as u64;
+            peer.known_announced_hashes += known_hashes_count as u64;
+        }
+    }
+
     /// Request handler for an incoming `NewPooledTransactionHashes`
     fn on_new_pooled_transaction_hashes(
         &mut self,
@@ -515,6 +532,7 @@ where
             return
         };
         let client = peer.client_version.clone();
+        let announced_hashes_count = msg.len();
 
         // keep track of the transactions the peer knows
         let mut count_txns_already_seen_by_peer = 0;
@@ -552,6 +570,7 @@ where
         }
 
         // 2. filter out transactions pending import to pool
+        let hashes_count_pre_known_filter = partially_valid_msg.len();
         partially_valid_msg.retain_by_hash(|hash| !self.transactions_by_peers.contains_key(hash));
 
         // 3. filter out known hashes
@@ -570,9 +589,11 @@ where
                 .occurrences_hashes_already_in_pool
                 .increment(already_known_hashes_count as u64);
         }
+        let mut known_hashes_count = hashes_count_pre_known_filter - partially_valid_msg.len();
 
         if partially_valid_msg.is_empty() {
             // nothing to request
+            self.on_announcement_duplicates(&peer_id, announced_hashes_count, known_hashes_count);
             return
         }
 
@@ -602,6 +623,7 @@ where
 
         if valid_announcement_data.is_empty() {
             // no valid announcement data
+            self.on_announcement_duplicates(&peer_id, announced_hashes_count, known_hashes_count);
             return
         }
 
@@ -611,6 +633,7 @@ where
         //
         // for any seen hashes add the peer as fallback. unseen hashes are loaded into the tx
         // fetcher, hence they should be valid at this point.
+        let hashes_count_pre_fetcher_filter = valid_announcement_data.len();
         let bad_imports = &self.bad_imports;
         self.transaction_fetcher.filter_unseen_and_pending_hashes(
             &mut valid_announcement_data,
@@ -618,6 +641,8 @@ where
             &peer_id,
             &client,
         );
+        known_hashes_count += hashes_count_pre_fetcher_filter - valid_announcement_data.len();
+        self.on_announcement_duplicates(&peer_id, announced_hashes_count, known_hashes_count);
 
         if valid_announcement_data.is_empty() {
             // nothing to request
@@ -734,6 +759,9 @@ where
 
     /// Propagate the full transactions to a specific peer.
     ///
+    /// If [`TransactionsManagerConfig::propagate_hashes_only`] is set, the transactions are only
+    /// announced.
+    ///
     /// Returns the propagated transactions.
     fn propagate_full_transactions_to_peer(
         &mut self,
@@ -747,13 +775,19 @@ where
         let mut propagated = PropagatedTransactions::default();
 
         // filter all transactions unknown to the peer
-        let mut full_transactions = FullTransactionsBuilder::new(peer.version);
+        let mut full_transactions = if self.config.propagate_hashes_only {
+            PropagateTransactionsBuilder::pooled(peer.version)
+        } else {
+            PropagateTransactionsBuilder::full(peer.version)
+        };
 
         let to_propagate = self.pool.get_all(txs).into_iter().map(PropagateTransaction::pool_tx);
 
         if propagation_mode.is_forced() {
             // skip cache check if forced
-            full_transactions.extend(to_propagate);
+            for tx in to_propagate {
+                full_transactions.push(&tx);
+            }
         } else {
             // Iterate through the transactions to propagate and fill the hashes and full
             // transaction
@@ -886,13 +920,15 @@ where
             return propagated
         }
 
-        // send full transactions to a set of the connected peers based on the configured mode
+        // send full transactions to a set of the connected peers based on the configured mode,
+        // unless transactions are only announced
+        let hashes_only = self.config.propagate_hashes_only;
         let max_num_full = self.config.propagation_mode.full_peer_count(self.peers.len());
 
         // Note: Assuming ~random~ order due to random state of the peers map hasher
         for (peer_idx, (peer_id, peer)) in self.peers.iter_mut().enumerate() {
             // determine whether to send full tx objects or hashes.
-            let mut builder = if peer_idx > max_num_full {
+            let mut builder = if hashes_only || peer_idx > max_num_full {
                 PropagateTransactionsBuilder::pooled(peer.version)
             } else {
                 PropagateTransactionsBuilder::full(peer.version)
@@ -1107,7 +1143,11 @@ where
         match event_result {
             NetworkEvent::Peer(PeerEvent::SessionClosed { peer_id, .. }) => {
                 // remove the peer
-                self.peers.remove(&peer_id);
+                if let Some(rate) =
+                    self.peers.remove(&peer_id).and_then(|peer| peer.announcement_duplicate_rate())
+                {
+                    self.metrics.announcement_duplicate_rate.record(rate);
+                }
                 self.transaction_fetcher.remove_peer(&peer_id);
             }
             NetworkEvent::ActivePeerSession { info, messages } => {
@@ -1526,8 +1566,12 @@ struct PropagateTransaction<T = TransactionSigned> {
 
 impl<T: SignedTransaction> PropagateTransaction<T> {
     /// Create a new instance from a transaction.
+    ///
+    /// The size is the EIP-2718 encoded length, which is the size announced in
+    /// [`NewPooledTransactionHashes68`] and the size of the transaction in a `PooledTransactions`
+    /// response, same as the encoded length of the pooled transaction.
     pub fn new(transaction: T) -> Self {
-        let size = transaction.length();
+        let size = transaction.encode_2718_len();
         Self { size, transaction: Arc::new(transaction) }
     }
 
@@ -1648,13 +1692,6 @@ impl<T> FullTransactionsBuilder<T> {
 }
 
 impl<T: SignedTransaction> FullTransactionsBuilder<T> {
-    /// Appends all transactions.
-    fn extend(&mut self, txs: impl IntoIterator<Item = PropagateTransaction<T>>) {
-        for tx in txs {
-            self.push(&tx)
-        }
-    }
-
     /// Append a transaction to the list of full transaction if the total message bytes size doesn't
     /// exceed the soft maximum target byte size. The limit is soft, meaning if one single
     /// transaction goes over the limit, it will be broadcasted in its own [`Transactions`]
@@ -1790,6 +1827,10 @@ pub struct PeerMetadata<N: NetworkPrimitives = EthNetworkPrimitives> {
     version: EthVersion,
     /// The peer's client version.
     client_version: Arc<str>,
+    /// Total number of hashes the peer announced in the session.
+    announced_hashes: u64,
+    /// Total number of hashes the peer announced in the session that were already known.
+    known_announced_hashes: u64,
 }
 
 impl<N: NetworkPrimitives> PeerMetadata<N> {
@@ -1805,8 +1846,17 @@ impl<N: NetworkPrimitives> PeerMetadata<N> {
             request_tx,
             version,
             client_version,
+            announced_hashes: 0,
+            known_announced_hashes: 0,
         }
     }
+
+    /// Returns the share of the hashes announced by the peer that were already known, if it
+    /// announced any.
+    fn announcement_duplicate_rate(&self) -> Option<f64> {
+        (self.announced_hashes > 0)
+            .then(|| self.known_announced_hashes as f64 / self.announced_hashes as f64)
+    }
 }
 
 /// Commands to send to the [`TransactionsManager`]
@@ -2223,6 +2273,47 @@ mod tests {
         assert_eq!(pool.get_all(txs_hashes.clone()).len(), txs_hashes.len());
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_announcement_duplicate_rate_per_peer() {
+        reth_tracing::init_test_tracing();
+
+        let secret_key = SecretKey::new(&mut rand::thread_rng());
+        let config = NetworkConfigBuilder::new(secret_key)
+            .listener_port(0)
+            .disable_discovery()
+            .build(NoopProvider::default());
+        let transactions_manager_config = config.transactions_manager_config.clone();
+        let (_network_handle, _network, mut tx_manager, _) = NetworkManager::new(config)
+            .await
+            .unwrap()
+            .into_builder()
+            .transactions(testing_pool(), transactions_manager_config)
+            .split_with_handle();
+
+        let peer_id_1 = PeerId::new([1; 64]);
+        let peer_id_2 = PeerId::new([2; 64]);
+        let (peer_1, _to_mock_session_rx_1) = new_mock_session(peer_id_1, EthVersion::Eth66);
+        let (peer_2, _to_mock_session_rx_2) = new_mock_session(peer_id_2, EthVersion::Eth66);
+        tx_manager.peers.insert(peer_id_1, peer_1);
+        tx_manager.peers.insert(peer_id_2, peer_2);
+
+        let hashes = vec![B256::random(), B256::random()];
+        let announce = |hashes: Vec<B256>| {
+            NewPooledTransactionHashes::from(NewPooledTransactionHashes66::from(hashes))
+        };
+        tx_manager.on_new_pooled_transaction_hashes(peer_id_1, announce(hashes.clone()));
+        // the first hash is already pending fetch from the first peer
+        tx_manager
+            .on_new_pooled_transaction_hashes(peer_id_2, announce(vec![hashes[0], B256::random()]));
+
+        let peer_1 = &tx_manager.peers[&peer_id_1];
+        assert_eq!((peer_1.announced_hashes, peer_1.known_announced_hashes), (2, 0));
+        assert_eq!(peer_1.announcement_duplicate_rate(), Some(0.0));
+        let peer_2 = &tx_manager.peers[&peer_id_2];
+        assert_eq!((peer_2.announced_hashes, peer_2.known_announced_hashes), (2, 1));
+        assert_eq!(peer_2.announcement_duplicate_rate(), Some(0.5));
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_handle_incoming_transactions() {
         reth_tracing::init_test_tracing();
@@ -2742,4 +2833,40 @@ mod tests {
         let propagated = tx_manager.propagate_transactions(propagate, PropagationMode::Basic);
         assert!(propagated.0.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_propagate_hashes_only() {
+        reth_tracing::init_test_tracing();
+
+        let (mut tx_manager, network) = new_tx_manager().await;
+        tx_manager.config.propagate_hashes_only = true;
+        let peer_id = PeerId::random();
+
+        // ensure not syncing
+        network.handle().update_sync_state(SyncState::Idle);
+
+        // mock a peer
+        let (tx, _rx) = mpsc::channel::<PeerRequest>(1);
+
+        let session_info = SessionInfo {
+            peer_id,
+            remote_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+            client_version: Arc::from(""),
+            capabilities: Arc::new(vec![].into()),
+            status: Arc::new(Default::default()),
+            version: EthVersion::Eth68,
+            peer_kind: PeerKind::Basic,
+        };
+        let messages: PeerRequestSender<PeerRequest> = PeerRequestSender::new(peer_id, tx);
+        tx_manager
+            .on_network_event(NetworkEvent::ActivePeerSession { info: session_info, messages });
+        let mut factory = MockTransactionFactory::default();
+        let eip1559_tx = Arc::new(factory.create_eip1559());
+        let propagate = vec![PropagateTransaction::pool_tx(eip1559_tx.clone())];
+
+        let propagated = tx_manager.propagate_transactions(propagate, PropagationMode::Basic);
+        let prop_txs = propagated.0.get(eip1559_tx.transaction.hash()).unwrap();
+        assert_eq!(prop_txs.len(), 1);
+        assert!(prop_txs[0].is_hash());
+    }
 }
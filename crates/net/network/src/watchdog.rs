@@ -0,0 +1,164 @@
+//! Watchdog that escalates discovery if the number of connected peers collapses.
+
+use reth_network_types::{NetworkHealth, NetworkHealthStatus, PeerCountWatchdogConfig};
+use std::{
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::watch,
+    time::{Interval, MissedTickBehavior},
+};
+use tracing::{info, warn};
+
+/// How often the number of connected peers is checked.
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Tracks the number of connected peers and decides when discovery must be escalated.
+///
+/// The network is degraded as soon as it has at most
+/// [`PeerCountWatchdogConfig::min_peers`] connected peers. If it stays degraded for longer than
+/// the stall timeout, it's stalled and discovery is escalated every escalation interval until the
+/// peer count recovers. The [`NetworkHealth`] is published on every change.
+#[derive(Debug)]
+pub(crate) struct PeerCountWatchdog {
+    /// The thresholds of the watchdog.
+    config: PeerCountWatchdogConfig,
+    /// Interval at which the peer count is checked.
+    interval: Interval,
+    /// When discovery was last escalated, if the network is stalled.
+    last_escalation: Option<Instant>,
+    /// Publishes the health of the network.
+    health: watch::Sender<NetworkHealth>,
+}
+
+impl PeerCountWatchdog {
+    /// Creates a new watchdog with the given config.
+    pub(crate) fn new(config: PeerCountWatchdogConfig) -> Self {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let (health, _) = watch::channel(NetworkHealth::default());
+        Self { config, interval, last_escalation: None, health }
+    }
+
+    /// Returns a receiver of the [`NetworkHealth`] published by the watchdog.
+    pub(crate) fn subscribe(&self) -> watch::Receiver<NetworkHealth> {
+        self.health.subscribe()
+    }
+
+    /// Returns `true` if the network has more than the minimum number of connected peers.
+    pub(crate) fn is_healthy(&self) -> bool {
+        self.health.borrow().status == NetworkHealthStatus::Healthy
+    }
+
+    /// Checks the number of connected peers whenever the interval ticks.
+    ///
+    /// Returns [`Poll::Ready`] if discovery must be escalated.
+    pub(crate) fn poll(&mut self, cx: &mut Context<'_>, connected_peers: usize) -> Poll<()> {
+        while self.interval.poll_tick(cx).is_ready() {
+            if self.check(connected_peers, Instant::now()) {
+                return Poll::Ready(())
+            }
+        }
+        Poll::Pending
+    }
+
+    /// Updates the health with the number of connected peers, and returns `true` if discovery must
+    /// be escalated.
+    fn check(&mut self, connected_peers: usize, now: Instant) -> bool {
+        let Self { config, last_escalation, health: sender, .. } = self;
+        let mut escalate = false;
+
+        sender.send_if_modified(|health| {
+            let previous = *health;
+
+            if connected_peers > config.min_peers {
+                if previous.status != NetworkHealthStatus::Healthy {
+                    info!(target: "net", connected_peers, "Peer count recovered");
+                }
+                *last_escalation = None;
+                *health = NetworkHealth { connected_peers, ..Default::default() };
+                return *health != previous
+            }
+
+            health.connected_peers = connected_peers;
+            let degraded_for = now.duration_since(*health.degraded_since.get_or_insert(now));
+            if degraded_for < config.stall_timeout {
+                health.status = NetworkHealthStatus::Degraded;
+                return *health != previous
+            }
+
+            health.status = NetworkHealthStatus::Stalled;
+            if last_escalation
+                .is_none_or(|last| now.duration_since(last) >= config.escalation_interval)
+            {
+                *last_escalation = Some(now);
+                health.escalations += 1;
+                escalate = true;
+                warn!(
+                    target: "net",
+                    connected_peers,
+                    ?degraded_for,
+                    escalations = health.escalations,
+                    "Peer count collapsed, escalating discovery"
+                );
+            }
+            *health != previous
+        });
+
+        escalate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watchdog() -> PeerCountWatchdog {
+        PeerCountWatchdog::new(PeerCountWatchdogConfig {
+            min_peers: 1,
+            stall_timeout: Duration::from_secs(60),
+            escalation_interval: Duration::from_secs(30),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_escalates_when_stalled() {
+        let mut watchdog = watchdog();
+        let health = watchdog.subscribe();
+        let start = Instant::now();
+
+        assert!(!watchdog.check(0, start));
+        assert_eq!(health.borrow().status, NetworkHealthStatus::Degraded);
+        assert_eq!(health.borrow().degraded_since, Some(start));
+
+        assert!(!watchdog.check(1, start + Duration::from_secs(59)));
+        assert_eq!(health.borrow().status, NetworkHealthStatus::Degraded);
+
+        assert!(watchdog.check(0, start + Duration::from_secs(60)));
+        assert_eq!(health.borrow().status, NetworkHealthStatus::Stalled);
+        assert_eq!(health.borrow().escalations, 1);
+
+        assert!(!watchdog.check(0, start + Duration::from_secs(89)));
+        assert!(watchdog.check(0, start + Duration::from_secs(90)));
+        assert_eq!(health.borrow().escalations, 2);
+    }
+
+    #[tokio::test]
+    async fn test_recovers() {
+        let mut watchdog = watchdog();
+        let health = watchdog.subscribe();
+        let start = Instant::now();
+
+        assert!(!watchdog.check(0, start));
+        assert!(watchdog.check(0, start + Duration::from_secs(60)));
+
+        assert!(!watchdog.check(5, start + Duration::from_secs(61)));
+        assert_eq!(*health.borrow(), NetworkHealth { connected_peers: 5, ..Default::default() });
+
+        // the stall timeout starts over
+        assert!(!watchdog.check(0, start + Duration::from_secs(62)));
+        assert_eq!(health.borrow().status, NetworkHealthStatus::Degraded);
+        assert_eq!(health.borrow().degraded_since, Some(start + Duration::from_secs(62)));
+    }
+}
@@ -6,6 +6,7 @@ mod multiplex;
 mod requests;
 mod session;
 mod startup;
+mod sync;
 mod transaction_hash_fetching;
 mod txgossip;
 
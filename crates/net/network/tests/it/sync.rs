@@ -0,0 +1,93 @@
+//! Tests for syncing a chain between in-process nodes
+
+use alloy_consensus::Header;
+use alloy_primitives::{Bytes, B256};
+use rand::{rngs::StdRng, SeedableRng};
+use reth_ethereum_primitives::{Block, BlockBody};
+use reth_network::test_utils::SyncHarness;
+use reth_network_p2p::error::DownloadError;
+use reth_primitives_traits::{BlockBody as _, SealedHeader};
+use reth_provider::test_utils::MockEthProvider;
+use std::sync::Arc;
+
+use crate::requests::rng_transaction;
+
+/// Returns a chain of connected blocks from genesis to `tip`, along with their hashes.
+fn chain(tip: u64) -> Vec<(B256, Block)> {
+    let mut rng = StdRng::seed_from_u64(tip);
+    let mut parent_hash = B256::ZERO;
+    (0..=tip)
+        .map(|number| {
+            let body = BlockBody {
+                transactions: (0..number % 3).map(|_| rng_transaction(&mut rng)).collect(),
+                ..Default::default()
+            };
+            let header = Header {
+                number,
+                parent_hash,
+                transactions_root: body.calculate_tx_root(),
+                ..Default::default()
+            };
+            parent_hash = header.hash_slow();
+            (parent_hash, Block { header, body })
+        })
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_full_sync() {
+    reth_tracing::init_test_tracing();
+    let chain = chain(300);
+    let tip = chain.last().unwrap().0;
+
+    let mock_provider = Arc::new(MockEthProvider::default());
+    mock_provider.extend_blocks(chain.clone());
+
+    let harness = SyncHarness::new(mock_provider).await;
+    let blocks = harness.assert_full_sync(tip).await;
+
+    // the genesis block isn't downloaded
+    assert_eq!(blocks.len(), chain.len() - 1);
+    for (block, (hash, _)) in blocks.iter().zip(&chain[1..]) {
+        assert_eq!(block.hash(), *hash);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sync_from_local_head() {
+    reth_tracing::init_test_tracing();
+    let chain = chain(200);
+    let tip = chain.last().unwrap().0;
+
+    let mock_provider = Arc::new(MockEthProvider::default());
+    mock_provider.extend_blocks(chain.clone());
+
+    let harness = SyncHarness::new(mock_provider).await;
+    let (hash, block) = &chain[100];
+    let local_head = SealedHeader::new(block.header.clone(), *hash);
+    let blocks = harness.sync_from(local_head, tip).await.unwrap();
+
+    assert_eq!(blocks.len(), 100);
+    for (block, (hash, _)) in blocks.iter().zip(&chain[101..]) {
+        assert_eq!(block.hash(), *hash);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sync_detached_head() {
+    reth_tracing::init_test_tracing();
+    let chain = chain(200);
+    let tip = chain.last().unwrap().0;
+
+    let mock_provider = Arc::new(MockEthProvider::default());
+    mock_provider.extend_blocks(chain);
+
+    let harness = SyncHarness::new(mock_provider).await;
+    // the chain of the populated node doesn't start at the genesis block of the fresh node
+    let genesis = SealedHeader::seal_slow(Header {
+        extra_data: Bytes::from_static(b"other genesis"),
+        ..Default::default()
+    });
+    let err = harness.sync_from(genesis, tip).await.unwrap_err();
+    assert!(matches!(err, DownloadError::HeaderValidation { number: 1, .. }), "{err:?}");
+}
@@ -5,8 +5,10 @@ use alloy_rpc_types_engine::JwtSecret;
 use reth_basic_payload_builder::PayloadBuilder;
 use reth_consensus::{ConsensusError, FullConsensus};
 use reth_db_api::{database_metrics::DatabaseMetrics, Database};
-use reth_engine_primitives::{BeaconConsensusEngineEvent, BeaconConsensusEngineHandle};
-use reth_evm::{execute::BlockExecutorProvider, ConfigureEvm};
+use reth_engine_primitives::{
+    BeaconConsensusEngineEvent, BeaconConsensusEngineHandle, ExchangedCapabilities, ReorgGuard,
+};
+use reth_evm::{execute::BlockExecutorProvider, profiler::StateAccessProfiler, ConfigureEvm};
 use reth_network_api::FullNetwork;
 use reth_node_core::node_config::NodeConfig;
 use reth_node_types::{NodeTypes, NodeTypesWithDBAdapter, NodeTypesWithEngine, TxTy};
@@ -124,6 +126,12 @@ pub struct AddOnsContext<'a, N: FullNodeComponents> {
     pub engine_events: EventSender<BeaconConsensusEngineEvent<<N::Types as NodeTypes>::Primitives>>,
     /// JWT secret for the node.
     pub jwt_secret: JwtSecret,
+    /// Profiler of the state accessed by the engine, if enabled.
+    pub state_access_profiler: Option<StateAccessProfiler>,
+    /// Guard of the engine against deep reorgs, if a maximum reorg depth is configured.
+    pub reorg_guard: Option<ReorgGuard>,
+    /// Record of the engine API capabilities exchanged with the consensus client.
+    pub exchanged_capabilities: ExchangedCapabilities,
 }
 
 /// Customizable node add-on types.
@@ -1,5 +1,6 @@
 //! Node add-ons. Depend on core [`NodeComponents`](crate::NodeComponents).
 
+use reth_exex::ExExPriority;
 use reth_node_api::{FullNodeComponents, NodeAddOns};
 
 use crate::{exex::BoxedLaunchExEx, hooks::NodeHooks};
@@ -11,7 +12,7 @@ pub struct AddOns<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> {
     /// Additional `NodeHooks` that are called at specific points in the node's launch lifecycle.
     pub hooks: NodeHooks<Node, AddOns>,
     /// The `ExExs` (execution extensions) of the node.
-    pub exexs: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+    pub exexs: Vec<(String, ExExPriority, Box<dyn BoxedLaunchExEx<Node>>)>,
     /// Additional captured addons.
     pub add_ons: AddOns,
 }
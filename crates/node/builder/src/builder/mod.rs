@@ -14,7 +14,7 @@ use futures::Future;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli_util::get_secret_key;
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
-use reth_exex::ExExContext;
+use reth_exex::{ExExContext, ExExPriority};
 use reth_network::{
     transactions::TransactionsManagerConfig, NetworkBuilder, NetworkConfig, NetworkConfigBuilder,
     NetworkHandle, NetworkManager, NetworkPrimitives,
@@ -31,7 +31,7 @@ use reth_node_core::{
 };
 use reth_provider::{
     providers::{BlockchainProvider, NodeTypesForProvider},
-    ChainSpecProvider, FullProvider,
+    BlockNumReader, ChainSpecProvider, FullProvider,
 };
 use reth_tasks::TaskExecutor;
 use reth_transaction_pool::{PoolConfig, PoolTransaction, TransactionPool};
@@ -500,6 +500,31 @@ where
         }
     }
 
+    /// Installs an `ExEx` (Execution Extension) with the given [`ExExPriority`] in the node.
+    ///
+    /// [`ExExPriority::BestEffort`] `ExEx`'s never slow down the node, but they can miss
+    /// notifications if they fall behind.
+    ///
+    /// # Note
+    ///
+    /// The `ExEx` ID must be unique.
+    pub fn install_exex_with_priority<F, R, E>(
+        self,
+        exex_id: impl Into<String>,
+        priority: ExExPriority,
+        exex: F,
+    ) -> Self
+    where
+        F: FnOnce(ExExContext<NodeAdapter<T, CB::Components>>) -> R + Send + 'static,
+        R: Future<Output = eyre::Result<E>> + Send,
+        E: Future<Output = eyre::Result<()>> + Send,
+    {
+        Self {
+            builder: self.builder.install_exex_with_priority(exex_id, priority, exex),
+            task_executor: self.task_executor,
+        }
+    }
+
     /// Installs an `ExEx` (Execution Extension) in the node if the condition is true.
     ///
     /// # Note
@@ -711,7 +736,9 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.executor.spawn_critical("p2p eth request handler", eth);
 
         let default_peers_path = self.config().datadir().known_peers();
-        let known_peers_file = self.config().network.persistent_peers_file(default_peers_path);
+        let known_peers_file = self.config().network.persistent_peers_file(
+            self.config().network.peers_file.clone().unwrap_or(default_peers_path),
+        );
         self.executor.spawn_critical_with_graceful_shutdown_signal(
             "p2p network task",
             |shutdown| {
@@ -793,7 +820,8 @@ impl<Node: FullNodeTypes<Types: NodeTypes<ChainSpec: Hardforks>>> BuilderContext
                 default_peers_path,
             )
             .with_task_executor(Box::new(self.executor.clone()))
-            .set_head(self.head);
+            .set_head(self.head)
+            .set_earliest_block(self.provider.earliest_block_number()?);
 
         Ok(builder)
     }
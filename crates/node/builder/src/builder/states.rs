@@ -12,7 +12,7 @@ use crate::{
     rpc::{RethRpcAddOns, RethRpcServerHandles, RpcContext},
     AddOns, FullNode,
 };
-use reth_exex::ExExContext;
+use reth_exex::{ExExContext, ExExPriority};
 use reth_node_api::{FullNodeComponents, FullNodeTypes, NodeAddOns, NodeTypes};
 use reth_node_core::node_config::NodeConfig;
 use reth_tasks::TaskExecutor;
@@ -212,13 +212,32 @@ where
     /// # Note
     ///
     /// The `ExEx` ID must be unique.
-    pub fn install_exex<F, R, E>(mut self, exex_id: impl Into<String>, exex: F) -> Self
+    pub fn install_exex<F, R, E>(self, exex_id: impl Into<String>, exex: F) -> Self
     where
         F: FnOnce(ExExContext<NodeAdapter<T, CB::Components>>) -> R + Send + 'static,
         R: Future<Output = eyre::Result<E>> + Send,
         E: Future<Output = eyre::Result<()>> + Send,
     {
-        self.add_ons.exexs.push((exex_id.into(), Box::new(exex)));
+        self.install_exex_with_priority(exex_id, ExExPriority::Critical, exex)
+    }
+
+    /// Installs an `ExEx` (Execution Extension) with the given [`ExExPriority`] in the node.
+    ///
+    /// # Note
+    ///
+    /// The `ExEx` ID must be unique.
+    pub fn install_exex_with_priority<F, R, E>(
+        mut self,
+        exex_id: impl Into<String>,
+        priority: ExExPriority,
+        exex: F,
+    ) -> Self
+    where
+        F: FnOnce(ExExContext<NodeAdapter<T, CB::Components>>) -> R + Send + 'static,
+        R: Future<Output = eyre::Result<E>> + Send,
+        E: Future<Output = eyre::Result<()>> + Send,
+    {
+        self.add_ons.exexs.push((exex_id.into(), priority, Box::new(exex)));
         self
     }
 
@@ -277,7 +296,10 @@ where
         })
     }
 
-    /// Sets the hook that is run to configure the rpc modules.
+    /// Adds a hook that is run to configure the rpc modules.
+    ///
+    /// Hooks run in the order they were added and can share values via
+    /// [`RpcContext::extensions`].
     pub fn extend_rpc_modules<F>(self, hook: F) -> Self
     where
         F: FnOnce(RpcContext<'_, NodeAdapter<T, CB::Components>, AO::EthApi>) -> eyre::Result<()>
@@ -285,7 +307,7 @@ where
             + 'static,
     {
         self.map_add_ons(|mut add_ons| {
-            add_ons.hooks_mut().set_extend_rpc_modules(hook);
+            add_ons.hooks_mut().add_extend_rpc_modules(hook);
             add_ons
         })
     }
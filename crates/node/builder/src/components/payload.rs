@@ -89,7 +89,8 @@ where
         let payload_job_config = BasicPayloadJobGeneratorConfig::default()
             .interval(conf.interval)
             .deadline(conf.deadline)
-            .max_payload_tasks(conf.max_payload_tasks);
+            .max_payload_tasks(conf.max_payload_tasks)
+            .interrupt_margin(conf.interrupt_margin);
 
         let payload_generator = BasicPayloadJobGenerator::with_builder(
             ctx.provider().clone(),
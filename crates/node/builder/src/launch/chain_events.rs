@@ -0,0 +1,271 @@
+//! Stream of canonical chain events over a Unix socket.
+//!
+//! Every event is a single line of the form `<kind> <number> <hash>`:
+//!
+//! - `head`: the new canonical head.
+//! - `reorg`: the lowest block that was removed from the canonical chain by a reorg, all blocks
+//!   from this number on must be discarded. It is followed by the `head` of the new chain.
+//! - `finalized`: the new finalized block.
+//!
+//! Every connection starts with the current `head` and `finalized` block, if any. Connections that
+//! fall behind the stream are closed, so the consumer can reconnect and resync.
+
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumHash;
+use futures::StreamExt;
+use reth_chain_state::{CanonStateNotification, CanonStateSubscriptions, ForkChoiceSubscriptions};
+use reth_node_api::NodePrimitives;
+use reth_provider::BlockIdReader;
+use reth_tasks::TaskExecutor;
+use std::{
+    fmt, io,
+    os::unix::fs::FileTypeExt,
+    path::{Path, PathBuf},
+};
+use tokio::{
+    io::{AsyncWrite, AsyncWriteExt},
+    net::UnixListener,
+    sync::broadcast::{self, error::RecvError},
+};
+use tracing::{debug, error, info, warn};
+
+/// The number of events buffered for every connection.
+const EVENTS_CHANNEL_SIZE: usize = 256;
+
+/// An event of the canonical chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChainEvent {
+    /// The new canonical head.
+    Head(BlockNumHash),
+    /// The lowest block that was removed from the canonical chain by a reorg.
+    Reorg(BlockNumHash),
+    /// The new finalized block.
+    Finalized(BlockNumHash),
+}
+
+impl ChainEvent {
+    /// Returns the events of a canonical state notification.
+    fn from_notification<N: NodePrimitives>(
+        notification: &CanonStateNotification<N>,
+    ) -> impl Iterator<Item = Self> {
+        let reorg = notification.reverted().map(|old| Self::Reorg(old.first().num_hash()));
+        reorg.into_iter().chain(std::iter::once(Self::Head(notification.tip().num_hash())))
+    }
+}
+
+impl fmt::Display for ChainEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (kind, BlockNumHash { number, hash }) = match self {
+            Self::Head(block) => ("head", block),
+            Self::Reorg(block) => ("reorg", block),
+            Self::Finalized(block) => ("finalized", block),
+        };
+        write!(f, "{kind} {number} {hash}")
+    }
+}
+
+/// Streams the canonical chain events to every process connected to the Unix socket at the given
+/// path.
+pub(crate) async fn run_chain_events_ipc<P>(path: PathBuf, provider: P, executor: TaskExecutor)
+where
+    P: CanonStateSubscriptions + ForkChoiceSubscriptions<Header: BlockHeader> + BlockIdReader,
+{
+    let mut canon_state = provider.canonical_state_stream();
+    let mut finalized = provider.finalized_block_stream();
+
+    // the current head and finalized block, which every connection starts with
+    let mut head = provider.chain_info().ok().map(BlockNumHash::from);
+    let mut last_finalized = provider.finalized_block_num_hash().ok().flatten();
+
+    if let Err(err) = remove_stale_socket(&path) {
+        error!(target: "reth::chain_events", %err, path = %path.display(), "Failed to start chain events IPC endpoint");
+        return
+    }
+    let listener = match UnixListener::bind(&path) {
+        Ok(listener) => listener,
+        Err(err) => {
+            error!(target: "reth::chain_events", %err, path = %path.display(), "Failed to bind chain events socket");
+            return
+        }
+    };
+    info!(target: "reth::chain_events", path = %path.display(), "Chain events IPC endpoint started");
+
+    let (events_tx, _) = broadcast::channel(EVENTS_CHANNEL_SIZE);
+    loop {
+        tokio::select! {
+            conn = listener.accept() => match conn {
+                Ok((stream, _)) => {
+                    debug!(target: "reth::chain_events", "Accepted chain events connection");
+                    let initial = head
+                        .map(ChainEvent::Head)
+                        .into_iter()
+                        .chain(last_finalized.map(ChainEvent::Finalized))
+                        .collect();
+                    executor.spawn(serve_connection(stream, initial, events_tx.subscribe()));
+                }
+                Err(err) => {
+                    warn!(target: "reth::chain_events", %err, "Failed to accept chain events connection");
+                }
+            },
+            notification = canon_state.next() => {
+                let Some(notification) = notification else { break };
+                head = Some(notification.tip().num_hash());
+                for event in ChainEvent::from_notification(&notification) {
+                    let _ = events_tx.send(event);
+                }
+            }
+            header = finalized.next() => {
+                let Some(header) = header else { break };
+                last_finalized = Some(header.num_hash());
+                let _ = events_tx.send(ChainEvent::Finalized(header.num_hash()));
+            }
+        }
+    }
+
+    let _ = std::fs::remove_file(&path);
+}
+
+/// Removes the socket a previous run left behind at the given path.
+///
+/// Returns an error if the path exists, but isn't a socket, or if another process still listens
+/// on it.
+fn remove_stale_socket(path: &Path) -> io::Result<()> {
+    let metadata = match std::fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    if !metadata.file_type().is_socket() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, "path exists and isn't a socket"))
+    }
+    if std::os::unix::net::UnixStream::connect(path).is_ok() {
+        return Err(io::Error::new(io::ErrorKind::AddrInUse, "socket is in use"))
+    }
+    std::fs::remove_file(path)
+}
+
+/// Writes the initial events and then the streamed events to the connection until it's closed or
+/// falls behind.
+async fn serve_connection(
+    mut stream: impl AsyncWrite + Unpin,
+    initial: Vec<ChainEvent>,
+    mut events: broadcast::Receiver<ChainEvent>,
+) {
+    for event in initial {
+        if stream.write_all(format!("{event}\n").as_bytes()).await.is_err() {
+            return
+        }
+    }
+
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            Err(RecvError::Lagged(skipped)) => {
+                debug!(target: "reth::chain_events", skipped, "Closing lagging chain events connection");
+                return
+            }
+            Err(RecvError::Closed) => return,
+        };
+
+        if stream.write_all(format!("{event}\n").as_bytes()).await.is_err() {
+            return
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use tokio::{io::AsyncReadExt, net::UnixStream};
+
+    async fn served_lines(
+        initial: Vec<ChainEvent>,
+        events: broadcast::Receiver<ChainEvent>,
+    ) -> Vec<String> {
+        let (server, mut client) = UnixStream::pair().unwrap();
+        serve_connection(server, initial, events).await;
+
+        let mut output = String::new();
+        client.read_to_string(&mut output).await.unwrap();
+        output.lines().map(str::to_string).collect()
+    }
+
+    #[tokio::test]
+    async fn connection_starts_with_initial_events() {
+        let (finalized, head, next) = (
+            BlockNumHash::new(1, B256::with_last_byte(1)),
+            BlockNumHash::new(2, B256::with_last_byte(2)),
+            BlockNumHash::new(3, B256::with_last_byte(3)),
+        );
+        let (events_tx, events) = broadcast::channel(EVENTS_CHANNEL_SIZE);
+        events_tx.send(ChainEvent::Head(next)).unwrap();
+        drop(events_tx);
+
+        let lines =
+            served_lines(vec![ChainEvent::Head(head), ChainEvent::Finalized(finalized)], events)
+                .await;
+        assert_eq!(
+            lines,
+            vec![
+                ChainEvent::Head(head).to_string(),
+                ChainEvent::Finalized(finalized).to_string(),
+                ChainEvent::Head(next).to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn lagging_connection_is_closed() {
+        let head = BlockNumHash::new(1, B256::with_last_byte(1));
+        let (events_tx, events) = broadcast::channel(1);
+        events_tx.send(ChainEvent::Head(BlockNumHash::new(2, B256::ZERO))).unwrap();
+        events_tx.send(ChainEvent::Head(BlockNumHash::new(3, B256::ZERO))).unwrap();
+
+        // the connection is closed after the initial events, without waiting for new events
+        let lines = served_lines(vec![ChainEvent::Head(head)], events).await;
+        assert_eq!(lines, vec![ChainEvent::Head(head).to_string()]);
+    }
+
+    #[test]
+    fn only_stale_sockets_are_removed() {
+        let dir = tempfile::tempdir().unwrap();
+
+        // nothing to remove
+        let path = dir.path().join("chain-events.ipc");
+        remove_stale_socket(&path).unwrap();
+
+        // other files are kept
+        let file = dir.path().join("file");
+        std::fs::write(&file, b"data").unwrap();
+        assert!(remove_stale_socket(&file).is_err());
+        assert!(file.exists());
+
+        // a socket another process listens on is kept
+        let listener = std::os::unix::net::UnixListener::bind(&path).unwrap();
+        assert_eq!(remove_stale_socket(&path).unwrap_err().kind(), io::ErrorKind::AddrInUse);
+        assert!(path.exists());
+
+        // the socket of a previous run is removed
+        drop(listener);
+        remove_stale_socket(&path).unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn chain_event_lines() {
+        let block = BlockNumHash::new(100, B256::with_last_byte(1));
+        assert_eq!(
+            ChainEvent::Head(block).to_string(),
+            format!("head 100 {}", B256::with_last_byte(1))
+        );
+        assert_eq!(
+            ChainEvent::Reorg(block).to_string(),
+            format!("reorg 100 {}", B256::with_last_byte(1))
+        );
+        assert_eq!(
+            ChainEvent::Finalized(block).to_string(),
+            format!("finalized 100 {}", B256::with_last_byte(1))
+        );
+    }
+}
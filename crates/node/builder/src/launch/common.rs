@@ -15,7 +15,7 @@ use reth_consensus::noop::NoopConsensus;
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
 use reth_db_common::init::{init_genesis, InitStorageError};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
-use reth_engine_local::MiningMode;
+use reth_engine_local::{MiningMode, ReorgInjection};
 use reth_engine_tree::tree::{InvalidBlockHook, InvalidBlockHooks, NoopInvalidBlockHook};
 use reth_evm::noop::NoopBlockExecutorProvider;
 use reth_fs_util as fs;
@@ -40,7 +40,7 @@ use reth_node_metrics::{
     version::VersionInfo,
 };
 use reth_provider::{
-    providers::{NodeTypesForProvider, ProviderNodeTypes, StaticFileProvider},
+    providers::{NodeTypesForProvider, ProviderNodeTypes, StaticFileProvider, VerificationMode},
     BlockHashReader, BlockNumReader, ChainSpecProvider, ProviderError, ProviderFactory,
     ProviderResult, StageCheckpointReader, StateProviderFactory, StaticFileProviderFactory,
 };
@@ -112,6 +112,9 @@ impl LaunchContext {
 
         // Update the config with the command line arguments
         toml_config.peers.trusted_nodes_only = config.network.trusted_only;
+        if let Some(interval) = config.sync.state_root_interval {
+            toml_config.stages.execution.state_root_interval = Some(interval);
+        }
 
         Ok(toml_config)
     }
@@ -362,12 +365,20 @@ impl<R, ChainSpec: EthChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpe
 
     /// Returns the [`MiningMode`] intended for --dev mode.
     pub fn dev_mining_mode(&self, pool: impl TransactionPool) -> MiningMode {
-        if let Some(interval) = self.node_config().dev.block_time {
-            MiningMode::interval(interval)
-        } else {
-            MiningMode::instant(pool)
+        let dev = &self.node_config().dev;
+        match (dev.block_time, dev.block_time_jitter) {
+            (Some(interval), Some(jitter)) => MiningMode::interval_with_jitter(interval, jitter),
+            (Some(interval), None) => MiningMode::interval(interval),
+            _ => MiningMode::instant(pool),
         }
     }
+
+    /// Returns the [`ReorgInjection`] intended for --dev mode, if enabled.
+    pub fn dev_reorg_injection(&self) -> Option<ReorgInjection> {
+        let dev = &self.node_config().dev;
+        dev.reorg_frequency
+            .map(|frequency| ReorgInjection { frequency, depth: dev.reorg_depth.unwrap_or(1) })
+    }
 }
 
 impl<DB, ChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpec>, DB>>
@@ -388,6 +399,7 @@ where
             StaticFileProvider::read_write(self.data_dir().static_files())?,
         )
         .with_prune_modes(self.prune_modes())
+        .with_changeset_walk_parallelism(self.node_config().rpc.rpc_historical_parallelism)
         .with_static_files_metrics();
 
         let has_receipt_pruning =
@@ -438,6 +450,21 @@ where
             rx.await??;
         }
 
+        // Record the checksums of newly finalized static files, and verify the static files
+        // against them to detect corruption early. Hashing the static files can take a while, so
+        // it doesn't delay the startup.
+        let mode = if self.node_config().db.verify_static_files {
+            VerificationMode::Full
+        } else {
+            VerificationMode::Sample
+        };
+        let static_file_provider = factory.static_file_provider();
+        self.task_executor().spawn_blocking(async move {
+            if let Err(err) = static_file_provider.check_manifest(mode) {
+                warn!(target: "reth::cli", %err, "Failed to verify static files");
+            }
+        });
+
         Ok(factory)
     }
 
@@ -762,12 +789,17 @@ where
 
     /// Returns the initial backfill to sync to at launch.
     ///
-    /// This returns the configured `debug.tip` if set, otherwise it will check if backfill was
-    /// previously interrupted and returns the block hash of the last checkpoint, see also
+    /// This returns the configured `debug.tip` if set, otherwise the configured `sync.checkpoint`
+    /// if the node didn't sync past it yet. Otherwise it will check if backfill was previously
+    /// interrupted and returns the block hash of the last checkpoint, see also
     /// [`Self::check_pipeline_consistency`]
     pub fn initial_backfill_target(&self) -> ProviderResult<Option<B256>> {
         let mut initial_target = self.node_config().debug.tip;
 
+        if initial_target.is_none() {
+            initial_target = self.sync_checkpoint_target()?;
+        }
+
         if initial_target.is_none() {
             initial_target = self.check_pipeline_consistency()?;
         }
@@ -775,6 +807,26 @@ where
         Ok(initial_target)
     }
 
+    /// Returns the block hash of the configured `sync.checkpoint` if its header is not known yet.
+    fn sync_checkpoint_target(&self) -> ProviderResult<Option<B256>> {
+        let Some(checkpoint) = self.node_config().sync.checkpoint else { return Ok(None) };
+
+        if let Some(local_hash) = self.blockchain_db().block_hash(checkpoint.number)? {
+            if local_hash != checkpoint.hash {
+                warn!(
+                    target: "reth::cli",
+                    %checkpoint,
+                    %local_hash,
+                    "Sync checkpoint conflicts with the local chain, ignoring it"
+                );
+            }
+            return Ok(None)
+        }
+
+        info!(target: "reth::cli", %checkpoint, "Syncing to trusted checkpoint");
+        Ok(Some(checkpoint.hash))
+    }
+
     /// Returns true if the node should terminate after the initial backfill run.
     ///
     /// This is the case if any of these configs are set:
@@ -847,6 +899,25 @@ where
             }
         }
 
+        // The optional deposit index stage isn't part of all stages, it only needs to catch up if
+        // it's enabled.
+        if self.toml_config().stages.deposit_index.enabled {
+            let deposit_index_checkpoint = self
+                .blockchain_db()
+                .get_stage_checkpoint(StageId::DepositIndex)?
+                .unwrap_or_default()
+                .block_number;
+            if deposit_index_checkpoint < first_stage_checkpoint {
+                debug!(
+                    target: "consensus::engine",
+                    first_stage_checkpoint,
+                    deposit_index_checkpoint,
+                    "Deposit index is behind the pipeline sync progress"
+                );
+                return self.blockchain_db().block_hash(first_stage_checkpoint);
+            }
+        }
+
         self.ensure_chain_specific_db_checks()?;
 
         Ok(None)
@@ -1073,6 +1144,8 @@ mod tests {
                     storage_history_full: false,
                     storage_history_distance: None,
                     storage_history_before: None,
+                    bodies_pre_merge: false,
+                    bodies_era_dir: None,
                     receipts_log_filter: vec![],
                 },
                 ..NodeConfig::test()
@@ -24,7 +24,11 @@ use reth_node_core::{
     primitives::Head,
 };
 use reth_node_events::{cl::ConsensusLayerHealthEvents, node};
-use reth_provider::providers::{BlockchainProvider, NodeTypesForProvider};
+use reth_provider::{
+    providers::{BlockchainProvider, NodeTypesForProvider},
+    BlockTagFallbacks,
+};
+use reth_revm::bytecode_cache::BytecodeCache;
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, error, info};
@@ -35,6 +39,7 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 use crate::{
     common::{Attached, LaunchContextWith, WithConfigs},
     hooks::NodeHooks,
+    launch::trie_verifier::run_trie_verifier,
     rpc::{EngineValidatorAddOn, RethRpcAddOns, RpcHandle},
     setup::build_networked_pipeline,
     AddOns, AddOnsContext, ExExLauncher, FullNode, LaunchContext, LaunchNode, NodeAdapter,
@@ -94,6 +99,16 @@ where
         } = target;
         let NodeHooks { on_component_initialized, on_node_started, .. } = hooks;
 
+        if let Some(size) = config.engine.bytecode_cache_size {
+            info!(target: "reth::cli", size_mb = size, "Installing bytecode cache");
+            BytecodeCache::install_global((size * 1024 * 1024) as usize);
+        }
+
+        let block_tag_fallbacks = BlockTagFallbacks {
+            safe: config.rpc.rpc_safe_fallback_depth,
+            finalized: config.rpc.rpc_finalized_fallback_depth,
+        };
+
         // setup the launch context
         let ctx = ctx
             .with_configured_globals()
@@ -122,7 +137,8 @@ where
             // passing FullNodeTypes as type parameter here so that we can build
             // later the components.
             .with_blockchain_db::<T, _>(move |provider_factory| {
-                Ok(BlockchainProvider::new(provider_factory)?)
+                Ok(BlockchainProvider::new(provider_factory)?
+                    .with_block_tag_fallbacks(block_tag_fallbacks))
             })?
             .with_components(components_builder, on_component_initialized).await?;
 
@@ -155,6 +171,7 @@ where
             exex_manager_handle.clone().unwrap_or_else(ExExManagerHandle::empty);
         let pipeline = build_networked_pipeline(
             &ctx.toml_config().stages,
+            ctx.toml_config().consensus,
             network_client.clone(),
             consensus.clone(),
             ctx.provider_factory().clone(),
@@ -181,8 +198,25 @@ where
         let pruner_events = pruner.events();
         info!(target: "reth::cli", prune_config=?ctx.prune_config().unwrap_or_default(), "Pruner initialized");
 
+        if let Some(interval) = node_config.engine.trie_verifier_interval {
+            info!(target: "reth::cli", ?interval, "Spawning trie verifier");
+            ctx.task_executor()
+                .spawn_blocking(run_trie_verifier(ctx.provider_factory().clone(), interval));
+        }
+
+        #[cfg(unix)]
+        if let Some(path) = node_config.rpc.chain_events_ipcpath.clone() {
+            ctx.task_executor().spawn(crate::launch::chain_events::run_chain_events_ipc(
+                path,
+                ctx.blockchain_db().clone(),
+                ctx.task_executor().clone(),
+            ));
+        }
+
         let event_sender = EventSender::default();
 
+        let state_access_profiler = node_config.engine.state_access_profiler();
+
         let beacon_engine_handle = BeaconConsensusEngineHandle::new(consensus_engine_tx.clone());
 
         // extract the jwt secret from the args if possible
@@ -194,6 +228,9 @@ where
             beacon_engine_handle: beacon_engine_handle.clone(),
             jwt_secret,
             engine_events: event_sender.clone(),
+            state_access_profiler: state_access_profiler.clone(),
+            reorg_guard: engine_tree_config.reorg_guard().cloned(),
+            exchanged_capabilities: Default::default(),
         };
         let engine_payload_validator = add_ons.engine_validator(&add_ons_ctx).await?;
 
@@ -223,10 +260,12 @@ where
                 engine_payload_validator,
                 engine_tree_config,
                 ctx.invalid_block_hook()?,
+                state_access_profiler,
                 ctx.sync_metrics_tx(),
                 consensus_engine_tx.clone(),
                 Box::pin(consensus_engine_stream),
                 ctx.dev_mining_mode(ctx.components().pool()),
+                ctx.dev_reorg_injection(),
                 LocalPayloadAttributesBuilder::new(ctx.chain_spec()),
                 ctx.components().evm_config().clone(),
             );
@@ -248,6 +287,7 @@ where
                 engine_payload_validator,
                 engine_tree_config,
                 ctx.invalid_block_hook()?,
+                state_access_profiler,
                 ctx.sync_metrics_tx(),
                 ctx.components().evm_config().clone(),
             );
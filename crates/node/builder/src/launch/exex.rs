@@ -5,8 +5,8 @@ use futures::future;
 use reth_chain_state::ForkChoiceSubscriptions;
 use reth_chainspec::EthChainSpec;
 use reth_exex::{
-    ExExContext, ExExHandle, ExExManager, ExExManagerHandle, ExExNotificationSource, Wal,
-    DEFAULT_EXEX_MANAGER_CAPACITY,
+    ExExContext, ExExHandle, ExExManager, ExExManagerHandle, ExExNotificationSource, ExExPriority,
+    Wal, WalEncryptionKey, DEFAULT_EXEX_MANAGER_CAPACITY,
 };
 use reth_node_api::{FullNodeComponents, NodeTypes, PrimitivesTy};
 use reth_provider::CanonStateSubscriptions;
@@ -19,7 +19,7 @@ use crate::{common::WithConfigs, exex::BoxedLaunchExEx};
 /// Can launch execution extensions.
 pub struct ExExLauncher<Node: FullNodeComponents> {
     head: Head,
-    extensions: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+    extensions: Vec<(String, ExExPriority, Box<dyn BoxedLaunchExEx<Node>>)>,
     components: Node,
     config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
 }
@@ -29,7 +29,7 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
     pub const fn new(
         head: Head,
         components: Node,
-        extensions: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+        extensions: Vec<(String, ExExPriority, Box<dyn BoxedLaunchExEx<Node>>)>,
         config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
     ) -> Self {
         Self { head, extensions, components, config_container }
@@ -50,20 +50,36 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
             return Ok(None)
         }
 
-        info!(target: "reth::cli", "Loading ExEx Write-Ahead Log...");
-        let exex_wal = Wal::new(
+        let exex_args = &config_container.config.exex;
+        let encryption_key = if let Some(path) = &exex_args.wal_encryption_key_file {
+            Some(WalEncryptionKey::from_file(path)?)
+        } else {
+            exex_args
+                .wal_encryption_key_env
+                .as_deref()
+                .map(WalEncryptionKey::from_env)
+                .transpose()?
+        };
+
+        info!(
+            target: "reth::cli",
+            encrypted = encryption_key.is_some(),
+            "Loading ExEx Write-Ahead Log..."
+        );
+        let exex_wal = Wal::new_with_encryption(
             config_container
                 .config
                 .datadir
                 .clone()
                 .resolve_datadir(config_container.config.chain.chain())
                 .exex_wal(),
+            encryption_key,
         )?;
 
         let mut exex_handles = Vec::with_capacity(extensions.len());
         let mut exexes = Vec::with_capacity(extensions.len());
 
-        for (id, exex) in extensions {
+        for (id, priority, exex) in extensions {
             // create a new exex handle
             let (handle, events, notifications) = ExExHandle::new(
                 id.clone(),
@@ -72,7 +88,7 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
                 components.block_executor().clone(),
                 exex_wal.handle(),
             );
-            exex_handles.push(handle);
+            exex_handles.push(handle.with_priority(priority));
 
             // create the launch context for the exex
             let context = ExExContext {
@@ -3,8 +3,11 @@
 pub mod common;
 mod exex;
 
+#[cfg(unix)]
+mod chain_events;
 pub(crate) mod debug;
 pub(crate) mod engine;
+mod trie_verifier;
 
 pub use common::LaunchContext;
 pub use exex::ExExLauncher;
@@ -0,0 +1,74 @@
+//! Background verification of the stored trie.
+
+use reth_provider::{
+    providers::ProviderNodeTypes, DBProvider, ProviderFactory, StageCheckpointReader,
+};
+use reth_stages::StageId;
+use reth_trie::verify::{TrieVerifier, TrieVerifierStep};
+use reth_trie_db::DatabaseTrieVerifier;
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+/// Continuously verifies the stored trie against the hashed state, one verifier step per
+/// interval.
+///
+/// Every step runs in its own read-only transaction, so the verifier never keeps a transaction
+/// open for long.
+pub(crate) async fn run_trie_verifier<N: ProviderNodeTypes>(
+    provider_factory: ProviderFactory<N>,
+    interval: Duration,
+) {
+    let mut verifier = TrieVerifier::new();
+    let mut divergences = 0;
+    loop {
+        tokio::time::sleep(interval).await;
+
+        match verify_next(&provider_factory, &mut verifier) {
+            Ok(Some(step)) => {
+                for divergence in &step.divergences {
+                    error!(
+                        target: "reth::trie_verifier",
+                        %divergence,
+                        "Stored trie diverges from the hashed state"
+                    );
+                }
+                divergences += step.divergences.len();
+                if step.pass_finished {
+                    info!(
+                        target: "reth::trie_verifier",
+                        divergences,
+                        "Finished trie verification pass"
+                    );
+                    divergences = 0;
+                }
+            }
+            Ok(None) => {
+                debug!(
+                    target: "reth::trie_verifier",
+                    "Hashed state and trie are not in sync, skipping verification"
+                );
+            }
+            Err(err) => warn!(target: "reth::trie_verifier", %err, "Failed to verify trie"),
+        }
+    }
+}
+
+/// Verifies the next subtrie, unless the hashing and merkle stages are at different blocks, e.g.
+/// in the middle of a pipeline sync, where the trie legitimately lags behind the hashed state.
+fn verify_next<N: ProviderNodeTypes>(
+    provider_factory: &ProviderFactory<N>,
+    verifier: &mut TrieVerifier,
+) -> eyre::Result<Option<TrieVerifierStep>> {
+    let provider = provider_factory.provider()?;
+    let checkpoint = |stage_id| {
+        provider.get_stage_checkpoint(stage_id).map(|checkpoint| checkpoint.unwrap_or_default())
+    };
+    let merkle = checkpoint(StageId::MerkleExecute)?;
+    if merkle.block_number != checkpoint(StageId::AccountHashing)?.block_number ||
+        merkle.block_number != checkpoint(StageId::StorageHashing)?.block_number
+    {
+        return Ok(None)
+    }
+
+    Ok(Some(verifier.verify_next_from_tx(provider.tx_ref())?))
+}
@@ -4,23 +4,34 @@ use crate::{BeaconConsensusEngineEvent, BeaconConsensusEngineHandle};
 use alloy_rpc_types::engine::ClientVersionV1;
 use alloy_rpc_types_engine::ExecutionData;
 use futures::TryFutureExt;
+use jsonrpsee::{
+    http_client::{HttpClient, HttpClientBuilder},
+    Methods,
+};
 use reth_chain_state::CanonStateSubscriptions;
 use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
 use reth_node_api::{
     AddOnsContext, BlockTy, EngineTypes, EngineValidator, FullNodeComponents, FullNodeTypes,
-    NodeAddOns, NodeTypes, NodeTypesWithEngine, ReceiptTy,
+    NodeAddOns, NodeTypes, NodeTypesWithEngine, PayloadAttributesValidator, PayloadTypes,
+    ReceiptTy, WithPayloadAttributesValidator,
 };
 use reth_node_core::{
     node_config::NodeConfig,
     version::{CARGO_PKG_VERSION, CLIENT_CODE, NAME_CLIENT, VERGEN_GIT_SHA},
 };
 use reth_payload_builder::{PayloadBuilderHandle, PayloadStore};
-use reth_rpc::eth::{EthApiTypes, FullEthApiServer};
-use reth_rpc_api::{eth::helpers::AddDevSigners, IntoEngineApiRpcModule};
+use reth_rpc::{
+    eth::{EthApiTypes, FullEthApiServer},
+    AdminCapabilitiesApi, AdminReorgApi, DebugHotStateApi,
+};
+use reth_rpc_api::{
+    eth::helpers::AddDevSigners, AdminCapabilitiesApiServer, AdminReorgApiServer,
+    DebugHotStateApiServer, IntoEngineApiRpcModule,
+};
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
+    RethRpcModule, RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
 };
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
 use reth_rpc_eth_types::{cache::cache_new_blocks_task, EthConfig, EthStateCache};
@@ -28,6 +39,8 @@ use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, info};
 use std::{
+    any::{type_name, Any, TypeId},
+    collections::HashMap,
     fmt::{self, Debug},
     future::Future,
     ops::{Deref, DerefMut},
@@ -95,6 +108,17 @@ where
         self
     }
 
+    /// Adds a hook that is run to configure the rpc modules, after the previously added hooks.
+    pub(crate) fn add_extend_rpc_modules<F>(&mut self, hook: F) -> &mut Self
+    where
+        F: ExtendRpcModules<Node, EthApi> + 'static,
+    {
+        let first = std::mem::replace(&mut self.extend_rpc_modules, Box::new(()));
+        self.extend_rpc_modules =
+            Box::new(ChainedExtendRpcModules { first, second: Box::new(hook) });
+        self
+    }
+
     /// Sets the hook that is run to configure the rpc modules.
     #[allow(unused)]
     pub(crate) fn extend_rpc_modules<F>(mut self, hook: F) -> Self
@@ -185,6 +209,87 @@ where
     }
 }
 
+/// Runs two [`ExtendRpcModules`] hooks in order.
+struct ChainedExtendRpcModules<Node: FullNodeComponents, EthApi> {
+    first: Box<dyn ExtendRpcModules<Node, EthApi>>,
+    second: Box<dyn ExtendRpcModules<Node, EthApi>>,
+}
+
+impl<Node, EthApi> ExtendRpcModules<Node, EthApi> for ChainedExtendRpcModules<Node, EthApi>
+where
+    Node: FullNodeComponents,
+    EthApi: EthApiTypes,
+{
+    fn extend_rpc_modules(self: Box<Self>, ctx: RpcContext<'_, Node, EthApi>) -> eyre::Result<()> {
+        let RpcContext { node, config, registry, modules, auth_module, extensions } = ctx;
+        self.first.extend_rpc_modules(RpcContext {
+            node: node.clone(),
+            config,
+            registry: &mut *registry,
+            modules: &mut *modules,
+            auth_module: &mut *auth_module,
+            extensions: &mut *extensions,
+        })?;
+        self.second.extend_rpc_modules(RpcContext {
+            node,
+            config,
+            registry,
+            modules,
+            auth_module,
+            extensions,
+        })
+    }
+}
+
+/// Typed registry of values shared by the hooks that configure the rpc modules.
+///
+/// This allows a hook to provide a handle, e.g. to a service backing its rpc module, that the
+/// hooks added after it, and the [`OnRpcStarted`] hook, can depend on.
+#[derive(Debug, Default)]
+pub struct RpcExtensions(HashMap<TypeId, Box<dyn Any + Send + Sync>>);
+
+impl RpcExtensions {
+    /// Inserts a value, returning the previous value of the same type, if any.
+    pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) -> Option<T> {
+        self.0
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|previous| previous.downcast().ok())
+            .map(|previous| *previous)
+    }
+
+    /// Returns a reference to the value of the given type, if any.
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.0.get(&TypeId::of::<T>()).and_then(|value| value.downcast_ref())
+    }
+
+    /// Returns a mutable reference to the value of the given type, if any.
+    pub fn get_mut<T: Send + Sync + 'static>(&mut self) -> Option<&mut T> {
+        self.0.get_mut(&TypeId::of::<T>()).and_then(|value| value.downcast_mut())
+    }
+
+    /// Returns a reference to the value of the given type, or an error if it's missing.
+    ///
+    /// Used by hooks to access the values they depend on.
+    pub fn require<T: Send + Sync + 'static>(&self) -> eyre::Result<&T> {
+        self.get().ok_or_else(|| {
+            eyre::eyre!(
+                "missing rpc extension {}, it must be added by an earlier hook",
+                type_name::<T>()
+            )
+        })
+    }
+}
+
+/// Authentication requirement of a method of a custom rpc module.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RpcAuth {
+    /// The method is served on the configured http, ws and ipc transports.
+    #[default]
+    Public,
+    /// The method is only served on the authenticated server, which requires a JWT.
+    Authenticated,
+}
+
 /// Helper wrapper type to encapsulate the [`RpcRegistryInner`] over components trait.
 #[derive(Debug, Clone)]
 #[allow(clippy::type_complexity)]
@@ -258,6 +363,10 @@ pub struct RpcContext<'a, Node: FullNodeComponents, EthApi: EthApiTypes> {
     ///
     /// This can be used to merge additional modules into the configured authenticated methods
     pub auth_module: &'a mut AuthRpcModule,
+    /// Typed values shared by the hooks.
+    ///
+    /// See [`RpcExtensions`].
+    pub extensions: &'a mut RpcExtensions,
 }
 
 impl<Node, EthApi> RpcContext<'_, Node, EthApi>
@@ -296,6 +405,29 @@ where
     ) -> &PayloadBuilderHandle<<Node::Types as NodeTypesWithEngine>::Engine> {
         self.node.payload_builder_handle()
     }
+
+    /// Installs the methods of a custom rpc module, each served according to its [`RpcAuth`].
+    ///
+    /// Public methods are merged into all configured transports and authenticated methods into
+    /// the authenticated server. Fails if any of the methods is already registered.
+    pub fn install_rpc_module(
+        &mut self,
+        module: impl Into<Methods>,
+        auth: impl Fn(&str) -> RpcAuth,
+    ) -> eyre::Result<()> {
+        let mut public = module.into();
+        let mut authenticated = public.clone();
+        for method in public.method_names().collect::<Vec<_>>() {
+            match auth(method) {
+                RpcAuth::Public => authenticated.remove(method),
+                RpcAuth::Authenticated => public.remove(method),
+            };
+        }
+
+        self.modules.merge_configured(public)?;
+        self.auth_module.merge_auth_methods(authenticated)?;
+        Ok(())
+    }
 }
 
 /// Handle to the launched RPC servers.
@@ -410,16 +542,56 @@ where
         self
     }
 
-    /// Sets the hook that is run to configure the rpc modules.
+    /// Adds a hook that is run to configure the rpc modules.
+    ///
+    /// Hooks run in the order they were added and can share values via
+    /// [`RpcContext::extensions`].
     pub fn extend_rpc_modules<F>(mut self, hook: F) -> Self
     where
         F: FnOnce(RpcContext<'_, Node, EthB::EthApi>) -> eyre::Result<()> + Send + 'static,
     {
-        self.hooks.set_extend_rpc_modules(hook);
+        self.hooks.add_extend_rpc_modules(hook);
         self
     }
 }
 
+impl<Node, EthB, EV, EB> RpcAddOns<Node, EthB, EV, EB>
+where
+    Node: FullNodeComponents,
+    EthB: EthApiBuilder<Node>,
+    EV: Clone,
+    EB: WithEngineValidatorBuilder,
+{
+    /// Adds a custom validation of the payload attributes, which the engine API runs before a
+    /// payload job is started.
+    ///
+    /// The validation runs after the checks of the engine validator, see
+    /// [`PayloadAttributesValidator`] for how its errors map to engine API error codes.
+    pub fn with_payload_attributes_validator<A>(
+        self,
+        attributes_validator: A,
+    ) -> RpcAddOns<
+        Node,
+        EthB,
+        PayloadAttributesValidatorBuilder<EV, A>,
+        EB::Output<PayloadAttributesValidatorBuilder<EV, A>>,
+    >
+    where
+        A: Clone,
+    {
+        let Self { hooks, eth_api_builder, engine_validator_builder, engine_api_builder } = self;
+        let engine_validator_builder =
+            PayloadAttributesValidatorBuilder { engine_validator_builder, attributes_validator };
+        RpcAddOns {
+            hooks,
+            eth_api_builder,
+            engine_api_builder: engine_api_builder
+                .with_engine_validator_builder(engine_validator_builder.clone()),
+            engine_validator_builder,
+        }
+    }
+}
+
 impl<Node, EthB, EV, EB> Default for RpcAddOns<Node, EthB, EV, EB>
 where
     Node: FullNodeComponents,
@@ -457,7 +629,16 @@ where
         let Self { eth_api_builder, engine_api_builder, hooks, .. } = self;
 
         let engine_api = engine_api_builder.build_engine_api(&ctx).await?;
-        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret, engine_events } = ctx;
+        let AddOnsContext {
+            node,
+            config,
+            beacon_engine_handle,
+            jwt_secret,
+            engine_events,
+            state_access_profiler,
+            reorg_guard,
+            exchanged_capabilities,
+        } = ctx;
 
         info!(target: "reth::cli", "Engine API handler initialized");
 
@@ -476,7 +657,15 @@ where
             }),
         );
 
-        let ctx = EthApiCtx { components: &node, config: config.rpc.eth_config(), cache };
+        let remote_state = config
+            .rpc
+            .rpc_remote_state_url
+            .as_deref()
+            .map(|url| HttpClientBuilder::default().build(url))
+            .transpose()?;
+
+        let ctx =
+            EthApiCtx { components: &node, config: config.rpc.eth_config(), cache, remote_state };
         let eth_api = eth_api_builder.build_eth_api(ctx);
 
         let auth_config = config.rpc.auth_server_config(jwt_secret)?;
@@ -498,13 +687,34 @@ where
             registry.eth_api().with_dev_accounts();
         }
 
+        if let Some(profiler) = state_access_profiler {
+            modules.merge_if_module_configured(
+                RethRpcModule::Debug,
+                DebugHotStateApi::new(profiler).into_rpc(),
+            )?;
+        }
+
+        if let Some(guard) = reorg_guard {
+            modules.merge_if_module_configured(
+                RethRpcModule::Admin,
+                AdminReorgApi::new(guard).into_rpc(),
+            )?;
+        }
+
+        modules.merge_if_module_configured(
+            RethRpcModule::Admin,
+            AdminCapabilitiesApi::new(exchanged_capabilities).into_rpc(),
+        )?;
+
         let mut registry = RpcRegistry { registry };
+        let mut extensions = RpcExtensions::default();
         let ctx = RpcContext {
             node: node.clone(),
             config,
             registry: &mut registry,
             modules: &mut modules,
             auth_module: &mut auth_module,
+            extensions: &mut extensions,
         };
 
         let RpcHooks { on_rpc_started, extend_rpc_modules } = hooks;
@@ -548,6 +758,7 @@ where
             registry: &mut registry,
             modules: &mut modules,
             auth_module: &mut auth_module,
+            extensions: &mut extensions,
         };
 
         on_rpc_started.on_rpc_started(ctx, handles.clone())?;
@@ -610,6 +821,8 @@ pub struct EthApiCtx<'a, N: FullNodeTypes> {
     pub config: EthConfig,
     /// Cache for eth state
     pub cache: EthStateCache<BlockTy<N::Types>, ReceiptTy<N::Types>>,
+    /// Client of the node that the state of blocks that are pruned locally is fetched from.
+    pub remote_state: Option<HttpClient>,
 }
 
 /// A `EthApi` that knows how to build `eth` namespace API from [`FullNodeComponents`].
@@ -685,6 +898,43 @@ where
     }
 }
 
+/// Builds the engine validator of another builder, with a custom validation of the payload
+/// attributes.
+///
+/// See [`RpcAddOns::with_payload_attributes_validator`].
+#[derive(Clone)]
+pub struct PayloadAttributesValidatorBuilder<EV, A> {
+    /// The builder of the engine validator.
+    engine_validator_builder: EV,
+    /// The custom validation of the payload attributes.
+    attributes_validator: A,
+}
+
+impl<EV: Debug, A> Debug for PayloadAttributesValidatorBuilder<EV, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PayloadAttributesValidatorBuilder")
+            .field("engine_validator_builder", &self.engine_validator_builder)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Node, EV, A> EngineValidatorBuilder<Node> for PayloadAttributesValidatorBuilder<EV, A>
+where
+    Node: FullNodeComponents,
+    EV: EngineValidatorBuilder<Node>,
+    A: PayloadAttributesValidator<
+            <<Node::Types as NodeTypesWithEngine>::Engine as PayloadTypes>::PayloadAttributes,
+        > + Clone,
+{
+    type Validator = WithPayloadAttributesValidator<EV::Validator, A>;
+
+    async fn build(self, ctx: &AddOnsContext<'_, Node>) -> eyre::Result<Self::Validator> {
+        let Self { engine_validator_builder, attributes_validator } = self;
+        let validator = engine_validator_builder.build(ctx).await?;
+        Ok(WithPayloadAttributesValidator::new(validator, attributes_validator))
+    }
+}
+
 /// Builder for engine API RPC module.
 pub trait EngineApiBuilder<Node: FullNodeComponents>: Send + Sync {
     /// The engine API RPC module. Only required to be convertible to an [`jsonrpsee`] module.
@@ -697,12 +947,33 @@ pub trait EngineApiBuilder<Node: FullNodeComponents>: Send + Sync {
     ) -> impl Future<Output = eyre::Result<Self::EngineApi>> + Send;
 }
 
+/// An [`EngineApiBuilder`] that builds the engine API with the validator of an
+/// [`EngineValidatorBuilder`], which can be replaced.
+///
+/// This is used by [`RpcAddOns::with_payload_attributes_validator`] to build the engine API with
+/// the same validator as the engine.
+pub trait WithEngineValidatorBuilder {
+    /// The engine API builder with another engine validator builder.
+    type Output<EV>;
+
+    /// Replaces the builder of the engine validator.
+    fn with_engine_validator_builder<EV>(self, engine_validator_builder: EV) -> Self::Output<EV>;
+}
+
 /// Builder for basic [`EngineApi`] implementation.
 #[derive(Debug, Default)]
 pub struct BasicEngineApiBuilder<EV> {
     engine_validator_builder: EV,
 }
 
+impl<EV> WithEngineValidatorBuilder for BasicEngineApiBuilder<EV> {
+    type Output<T> = BasicEngineApiBuilder<T>;
+
+    fn with_engine_validator_builder<T>(self, engine_validator_builder: T) -> Self::Output<T> {
+        BasicEngineApiBuilder { engine_validator_builder }
+    }
+}
+
 impl<N, EV> EngineApiBuilder<N> for BasicEngineApiBuilder<EV>
 where
     N: FullNodeComponents<
@@ -739,9 +1010,29 @@ where
             ctx.node.pool().clone(),
             Box::new(ctx.node.task_executor().clone()),
             client,
-            EngineCapabilities::default(),
+            EngineCapabilities::default()
+                .with_exchanged_capabilities(ctx.exchanged_capabilities.clone()),
             engine_validator,
             ctx.config.engine.accept_execution_requests_hash,
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rpc_extensions() {
+        let mut extensions = RpcExtensions::default();
+        assert!(extensions.require::<u64>().is_err());
+
+        assert_eq!(extensions.insert(1u64), None);
+        assert_eq!(extensions.insert("handle"), None);
+        assert_eq!(extensions.insert(2u64), Some(1));
+
+        *extensions.get_mut::<u64>().unwrap() += 1;
+        assert_eq!(extensions.require::<u64>().unwrap(), &3);
+        assert_eq!(extensions.get::<&str>(), Some(&"handle"));
+    }
+}
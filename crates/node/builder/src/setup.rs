@@ -4,7 +4,10 @@ use std::sync::Arc;
 
 use crate::BlockTy;
 use alloy_primitives::{BlockNumber, B256};
-use reth_config::{config::StageConfig, PruneConfig};
+use reth_config::{
+    config::{ConsensusConfig, StageConfig},
+    PruneConfig,
+};
 use reth_consensus::{ConsensusError, FullConsensus};
 use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
@@ -27,6 +30,7 @@ use tokio::sync::watch;
 #[allow(clippy::too_many_arguments)]
 pub fn build_networked_pipeline<N, Client, Executor>(
     config: &StageConfig,
+    consensus_config: ConsensusConfig,
     client: Client,
     consensus: Arc<dyn FullConsensus<N::Primitives, Error = ConsensusError>>,
     provider_factory: ProviderFactory<N>,
@@ -55,6 +59,7 @@ where
     let pipeline = build_pipeline(
         provider_factory,
         config,
+        consensus_config,
         header_downloader,
         body_downloader,
         consensus,
@@ -74,6 +79,7 @@ where
 pub fn build_pipeline<N, H, B, Executor>(
     provider_factory: ProviderFactory<N>,
     stage_config: &StageConfig,
+    consensus_config: ConsensusConfig,
     header_downloader: H,
     body_downloader: B,
     consensus: Arc<dyn FullConsensus<N::Primitives, Error = ConsensusError>>,
@@ -115,13 +121,19 @@ where
                 stage_config.clone(),
                 prune_modes,
             )
-            .set(ExecutionStage::new(
-                executor,
-                consensus,
-                stage_config.execution.into(),
-                stage_config.execution_external_clean_threshold(),
-                exex_manager_handle,
-            )),
+            .set(
+                ExecutionStage::new(
+                    executor,
+                    consensus,
+                    stage_config.execution.into(),
+                    stage_config.execution_external_clean_threshold(),
+                    exex_manager_handle,
+                )
+                .with_offloaded_receipts_verification(
+                    consensus_config.offload_receipts_verification,
+                )
+                .with_state_root_interval(stage_config.execution.state_root_interval),
+            ),
         )
         .build(provider_factory, static_file_producer);
 
@@ -8,7 +8,13 @@ use clap::{
     error::ErrorKind,
     Arg, Args, Command, Error,
 };
-use reth_db::{mdbx::MaxReadTransactionDuration, ClientVersion};
+use reth_db::{
+    mdbx::{
+        compaction::{CompactionConfig, CompactionWindow},
+        MaxReadTransactionDuration,
+    },
+    ClientVersion,
+};
 use reth_storage_errors::db::LogLevel;
 
 /// Parameters for database configuration
@@ -31,6 +37,29 @@ pub struct DatabaseArgs {
     /// Read transaction timeout in seconds, 0 means no timeout.
     #[arg(long = "db.read-transaction-timeout")]
     pub read_transaction_timeout: Option<u64>,
+    /// Schedule compaction of the database on the next restart if its freelist grows too large.
+    ///
+    /// The freelist is checked while the node is running, but the database is not compacted
+    /// online: it's compacted on the next restart, before the node starts. The node is down until
+    /// the compacted copy is made, which takes hours for a mainnet database, and compaction
+    /// requires as much free disk space as the database without the freelist.
+    #[arg(long = "db.compaction")]
+    pub compaction: bool,
+    /// Minimum share of free pages in the database, in percent, to trigger compaction. Defaults to
+    /// 30.
+    #[arg(
+        long = "db.compaction.threshold",
+        requires = "compaction",
+        value_parser = clap::value_parser!(u8).range(1..=100)
+    )]
+    pub compaction_threshold: Option<u8>,
+    /// Hours of the day in UTC during which the freelist is checked, e.g. 22-4. Defaults to 2-5.
+    #[arg(long = "db.compaction.window", requires = "compaction")]
+    pub compaction_window: Option<CompactionWindow>,
+    /// Verify all finalized static files against their checksums on startup, instead of a random
+    /// sample.
+    #[arg(long = "db.verify-static-files")]
+    pub verify_static_files: bool,
 }
 
 impl DatabaseArgs {
@@ -58,6 +87,22 @@ impl DatabaseArgs {
             .with_geometry_max_size(self.max_size)
             .with_growth_step(self.growth_step)
     }
+
+    /// Returns the configuration of the background compaction, if it's enabled.
+    pub fn compaction_config(&self) -> Option<CompactionConfig> {
+        if !self.compaction {
+            return None
+        }
+
+        let mut config = CompactionConfig::default();
+        if let Some(threshold) = self.compaction_threshold {
+            config.min_free_pages_percent = threshold;
+        }
+        if let Some(window) = self.compaction_window {
+            config.window = window;
+        }
+        Some(config)
+    }
 }
 
 /// clap value parser for [`LogLevel`].
@@ -331,6 +376,32 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_command_parser_with_compaction() {
+        let cmd = CommandParser::<DatabaseArgs>::try_parse_from([
+            "reth",
+            "--db.compaction",
+            "--db.compaction.threshold",
+            "40",
+            "--db.compaction.window",
+            "22-4",
+        ])
+        .unwrap();
+        let config = cmd.args.compaction_config().unwrap();
+        assert_eq!(config.min_free_pages_percent, 40);
+        assert_eq!(config.window, CompactionWindow { start_hour: 22, end_hour: 4 });
+
+        let cmd = CommandParser::<DatabaseArgs>::try_parse_from(["reth"]).unwrap();
+        assert_eq!(cmd.args.compaction_config(), None);
+
+        let result = CommandParser::<DatabaseArgs>::try_parse_from([
+            "reth",
+            "--db.compaction.window",
+            "22-4",
+        ]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_command_parser_without_log_level() {
         let cmd = CommandParser::<DatabaseArgs>::try_parse_from(["reth"]).unwrap();
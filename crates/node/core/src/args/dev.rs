@@ -1,6 +1,6 @@
 //! clap [Args](clap::Args) for Dev testnet configuration
 
-use std::time::Duration;
+use std::{num::NonZeroU64, time::Duration};
 
 use clap::Args;
 use humantime::parse_duration;
@@ -39,6 +39,34 @@ pub struct DevArgs {
         verbatim_doc_comment
     )]
     pub block_time: Option<Duration>,
+
+    /// Maximum random delay added to the interval between blocks.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --dev.block-time-jitter 2s
+    #[arg(
+        long = "dev.block-time-jitter",
+        help_heading = "Dev testnet",
+        requires = "block_time",
+        value_parser = parse_duration,
+        verbatim_doc_comment
+    )]
+    pub block_time_jitter: Option<Duration>,
+
+    /// Inject a reorg every given number of mined blocks.
+    ///
+    /// The reorged blocks are replaced by a single block, which stays canonical.
+    #[arg(long = "dev.reorg-frequency", help_heading = "Dev testnet", value_name = "BLOCKS")]
+    pub reorg_frequency: Option<NonZeroU64>,
+
+    /// The number of blocks removed by an injected reorg, 1 by default.
+    #[arg(
+        long = "dev.reorg-depth",
+        help_heading = "Dev testnet",
+        requires = "reorg_frequency",
+        value_parser = clap::value_parser!(u64).range(1..32)
+    )]
+    pub reorg_depth: Option<u64>,
 }
 
 #[cfg(test)]
@@ -56,13 +84,13 @@ mod tests {
     #[test]
     fn test_parse_dev_args() {
         let args = CommandParser::<DevArgs>::parse_from(["reth"]).args;
-        assert_eq!(args, DevArgs { dev: false, block_max_transactions: None, block_time: None });
+        assert_eq!(args, DevArgs::default());
 
         let args = CommandParser::<DevArgs>::parse_from(["reth", "--dev"]).args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: None, block_time: None });
+        assert_eq!(args, DevArgs { dev: true, ..Default::default() });
 
         let args = CommandParser::<DevArgs>::parse_from(["reth", "--auto-mine"]).args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: None, block_time: None });
+        assert_eq!(args, DevArgs { dev: true, ..Default::default() });
 
         let args = CommandParser::<DevArgs>::parse_from([
             "reth",
@@ -71,7 +99,10 @@ mod tests {
             "2",
         ])
         .args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: Some(2), block_time: None });
+        assert_eq!(
+            args,
+            DevArgs { dev: true, block_max_transactions: Some(2), ..Default::default() }
+        );
 
         let args =
             CommandParser::<DevArgs>::parse_from(["reth", "--dev", "--dev.block-time", "1s"]).args;
@@ -79,8 +110,33 @@ mod tests {
             args,
             DevArgs {
                 dev: true,
-                block_max_transactions: None,
-                block_time: Some(std::time::Duration::from_secs(1))
+                block_time: Some(std::time::Duration::from_secs(1)),
+                ..Default::default()
+            }
+        );
+
+        let args = CommandParser::<DevArgs>::parse_from([
+            "reth",
+            "--dev",
+            "--dev.block-time",
+            "1s",
+            "--dev.block-time-jitter",
+            "500ms",
+            "--dev.reorg-frequency",
+            "10",
+            "--dev.reorg-depth",
+            "2",
+        ])
+        .args;
+        assert_eq!(
+            args,
+            DevArgs {
+                dev: true,
+                block_time: Some(std::time::Duration::from_secs(1)),
+                block_time_jitter: Some(std::time::Duration::from_millis(500)),
+                reorg_frequency: NonZeroU64::new(10),
+                reorg_depth: Some(2),
+                ..Default::default()
             }
         );
     }
@@ -96,6 +152,15 @@ mod tests {
             "1s",
         ]);
         assert!(args.is_err());
+
+        // jitter requires a block time
+        let args = CommandParser::<DevArgs>::try_parse_from([
+            "reth",
+            "--dev",
+            "--dev.block-time-jitter",
+            "1s",
+        ]);
+        assert!(args.is_err());
     }
 
     #[test]
@@ -1,7 +1,10 @@
 //! clap [Args](clap::Args) for engine purposes
 
 use clap::Args;
-use reth_engine_primitives::TreeConfig;
+use humantime::parse_duration;
+use reth_engine_primitives::{ReorgGuard, TreeConfig};
+use reth_evm::profiler::{StateAccessProfiler, DEFAULT_HOT_KEYS_LIMIT, DEFAULT_REPORT_INTERVAL};
+use std::time::Duration;
 
 use crate::node_config::{
     DEFAULT_CROSS_BLOCK_CACHE_SIZE_MB, DEFAULT_MEMORY_BLOCK_BUFFER_TARGET,
@@ -32,14 +35,66 @@ pub struct EngineArgs {
     #[arg(long = "engine.cross-block-cache-size", default_value_t = DEFAULT_CROSS_BLOCK_CACHE_SIZE_MB)]
     pub cross_block_cache_size: u64,
 
+    /// Configure the size of the bytecode cache in megabytes.
+    ///
+    /// The analyzed bytecode of contracts is cached across blocks and shared by the executor, the
+    /// payload builder and the RPC. Bytecode isn't cached if not set.
+    #[arg(long = "engine.bytecode-cache-size", value_name = "MB")]
+    pub bytecode_cache_size: Option<u64>,
+
     /// Enable comparing trie updates from the state root task to the trie updates from the regular
     /// state root calculation.
     #[arg(long = "engine.state-root-task-compare-updates")]
     pub state_root_task_compare_updates: bool,
 
+    /// Enable splitting the parallel state root calculation into a task per subtrie below the
+    /// root of the account trie.
+    #[arg(long = "engine.state-root-subtrie-split")]
+    pub state_root_subtrie_split: bool,
+
     /// Enables accepting requests hash instead of an array of requests in `engine_newPayloadV4`.
     #[arg(long = "engine.accept-execution-requests-hash")]
     pub accept_execution_requests_hash: bool,
+
+    /// Enable profiling of the accounts and storage slots accessed by executed blocks.
+    ///
+    /// The hottest keys are periodically logged and can be queried with `debug_hotState`.
+    #[arg(long = "engine.state-access-profiler")]
+    pub state_access_profiler: bool,
+
+    /// Configure the number of blocks aggregated into a single state access report.
+    #[arg(long = "engine.state-access-profiler-interval", default_value_t = DEFAULT_REPORT_INTERVAL)]
+    pub state_access_profiler_interval: u64,
+
+    /// Configure the maximum number of canonical blocks a reorg may remove.
+    ///
+    /// Deeper reorgs are refused and require operator intervention with `admin_forceReorg`.
+    #[arg(long = "engine.max-reorg-depth", value_name = "DEPTH")]
+    pub max_reorg_depth: Option<u64>,
+
+    /// Enable the background verification of the stored trie against the hashed state, verifying
+    /// one subtrie or a bounded number of storage slots per interval, e.g. `1s`.
+    ///
+    /// Divergent trie nodes are logged with their paths, so corruption of the trie is detected
+    /// before it causes a state root mismatch. Also see `reth trie verify`.
+    #[arg(
+        long = "engine.trie-verifier-interval",
+        value_name = "DURATION",
+        value_parser = parse_duration
+    )]
+    pub trie_verifier_interval: Option<Duration>,
+
+    /// Report new payloads taking at least this long to process as slow, e.g. `2s`.
+    ///
+    /// Slow payloads are logged with the duration of their execution and state root phases and
+    /// counted in the `slow_new_payloads` metric. If spans are exported with `--log.otlp`, the log
+    /// includes the id of the payload's trace.
+    #[arg(
+        long = "engine.slow-payload-threshold",
+        value_name = "DURATION",
+        value_parser = parse_duration
+    )]
+    pub slow_payload_threshold: Option<Duration>,
 }
 
 impl Default for EngineArgs {
@@ -49,9 +104,16 @@ impl Default for EngineArgs {
             memory_block_buffer_target: DEFAULT_MEMORY_BLOCK_BUFFER_TARGET,
             legacy_state_root_task_enabled: false,
             state_root_task_compare_updates: false,
+            state_root_subtrie_split: false,
             caching_and_prewarming_enabled: false,
             cross_block_cache_size: DEFAULT_CROSS_BLOCK_CACHE_SIZE_MB,
+            bytecode_cache_size: None,
             accept_execution_requests_hash: false,
+            state_access_profiler: false,
+            state_access_profiler_interval: DEFAULT_REPORT_INTERVAL,
+            max_reorg_depth: None,
+            trie_verifier_interval: None,
+            slow_payload_threshold: None,
         }
     }
 }
@@ -65,7 +127,17 @@ impl EngineArgs {
             .with_legacy_state_root(self.legacy_state_root_task_enabled)
             .with_caching_and_prewarming(self.caching_and_prewarming_enabled)
             .with_always_compare_trie_updates(self.state_root_task_compare_updates)
+            .with_state_root_subtrie_split(self.state_root_subtrie_split)
             .with_cross_block_cache_size(self.cross_block_cache_size * 1024 * 1024)
+            .with_reorg_guard(self.max_reorg_depth.map(ReorgGuard::new))
+            .with_slow_payload_threshold(self.slow_payload_threshold)
+    }
+
+    /// Creates the [`StateAccessProfiler`] of the engine, if enabled.
+    pub fn state_access_profiler(&self) -> Option<StateAccessProfiler> {
+        self.state_access_profiler.then(|| {
+            StateAccessProfiler::new(self.state_access_profiler_interval, DEFAULT_HOT_KEYS_LIMIT)
+        })
     }
 }
 
@@ -87,4 +159,46 @@ mod tests {
         let args = CommandParser::<EngineArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn test_parse_bytecode_cache_size() {
+        let args = CommandParser::<EngineArgs>::parse_from([
+            "reth",
+            "--engine.bytecode-cache-size",
+            "256",
+        ])
+        .args;
+        assert_eq!(args.bytecode_cache_size, Some(256));
+    }
+
+    #[test]
+    fn test_parse_max_reorg_depth() {
+        let args =
+            CommandParser::<EngineArgs>::parse_from(["reth", "--engine.max-reorg-depth", "64"])
+                .args;
+        assert_eq!(args.max_reorg_depth, Some(64));
+        assert_eq!(args.tree_config().reorg_guard().map(|guard| guard.max_depth()), Some(64));
+    }
+
+    #[test]
+    fn test_parse_trie_verifier_interval() {
+        let args = CommandParser::<EngineArgs>::parse_from([
+            "reth",
+            "--engine.trie-verifier-interval",
+            "500ms",
+        ])
+        .args;
+        assert_eq!(args.trie_verifier_interval, Some(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn test_parse_slow_payload_threshold() {
+        let args = CommandParser::<EngineArgs>::parse_from([
+            "reth",
+            "--engine.slow-payload-threshold",
+            "2s",
+        ])
+        .args;
+        assert_eq!(args.tree_config().slow_payload_threshold(), Some(Duration::from_secs(2)));
+    }
 }
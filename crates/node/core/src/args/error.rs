@@ -20,3 +20,17 @@ pub(crate) enum ReceiptsLogError {
     #[error("block number is invalid: {0}")]
     InvalidBlockNumber(ParseIntError),
 }
+
+/// Error while parsing a `SyncCheckpoint`
+#[derive(thiserror::Error, Debug)]
+pub enum SyncCheckpointError {
+    /// The format of the checkpoint is invalid.
+    #[error("invalid checkpoint format, expected <NUMBER>:<HASH>: {0}")]
+    InvalidFormat(String),
+    /// The block number supplied is invalid.
+    #[error("block number is invalid: {0}")]
+    InvalidBlockNumber(ParseIntError),
+    /// The block hash supplied is invalid.
+    #[error("block hash is invalid: {0}")]
+    InvalidBlockHash(alloy_primitives::hex::FromHexError),
+}
@@ -0,0 +1,58 @@
+//! clap [Args](clap::Args) for execution extensions
+
+use clap::Args;
+use std::path::PathBuf;
+
+/// Parameters for configuring execution extensions.
+#[derive(Debug, Clone, Default, Args, PartialEq, Eq)]
+#[command(next_help_heading = "ExEx")]
+pub struct ExExArgs {
+    /// Path to a file with the hex-encoded 32-byte key to encrypt the ExEx write-ahead log with,
+    /// using AES-256-GCM.
+    ///
+    /// Write-ahead log files that were written without encryption are rejected, so encryption can
+    /// only be enabled on an empty write-ahead log.
+    #[arg(
+        long = "exex.wal-encryption-key-file",
+        value_name = "PATH",
+        conflicts_with = "wal_encryption_key_env"
+    )]
+    pub wal_encryption_key_file: Option<PathBuf>,
+
+    /// Name of the environment variable holding the hex-encoded 32-byte key to encrypt the ExEx
+    /// write-ahead log with, see `--exex.wal-encryption-key-file`.
+    #[arg(long = "exex.wal-encryption-key-env", value_name = "VAR")]
+    pub wal_encryption_key_env: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn exex_args_default_sanity_test() {
+        let default_args = ExExArgs::default();
+        let args = CommandParser::<ExExArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+    }
+
+    #[test]
+    fn wal_encryption_key_sources_conflict() {
+        let res = CommandParser::<ExExArgs>::try_parse_from([
+            "reth",
+            "--exex.wal-encryption-key-file",
+            "key.hex",
+            "--exex.wal-encryption-key-env",
+            "RETH_EXEX_WAL_KEY",
+        ]);
+        assert!(res.is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use alloy_primitives::U256;
-use clap::Args;
-use reth_rpc_eth_types::GasPriceOracleConfig;
+use clap::{builder::PossibleValuesParser, Args, TypedValueParser};
+use reth_rpc_eth_types::{GasPriceOracleConfig, GasPriceOracleStrategy};
 use reth_rpc_server_types::constants::gas_oracle::{
     DEFAULT_GAS_PRICE_BLOCKS, DEFAULT_GAS_PRICE_PERCENTILE, DEFAULT_IGNORE_GAS_PRICE,
     DEFAULT_MAX_GAS_PRICE,
@@ -25,17 +25,32 @@ pub struct GasPriceOracleArgs {
     /// The percentile of gas prices to use for the estimate
     #[arg(long = "gpo.percentile", default_value_t = DEFAULT_GAS_PRICE_PERCENTILE)]
     pub percentile: u32,
+
+    /// The strategy to estimate gas prices.
+    ///
+    /// `min-inclusion-tip` suggests at least the tip required for inclusion in the next block
+    /// given the pending transactions, `base-fee-trend` projects the base fee from the gas usage
+    /// of recent blocks, `pool-pressure` blends the estimate with the tips of the pending
+    /// transactions, weighted by how much of the next block they fill.
+    #[arg(
+        long = "gpo.strategy",
+        default_value_t = GasPriceOracleStrategy::Percentile,
+        value_parser = PossibleValuesParser::new(GasPriceOracleStrategy::ALL.map(|s| s.as_str()))
+            .map(|s| s.parse::<GasPriceOracleStrategy>().expect("possible values are valid"))
+    )]
+    pub strategy: GasPriceOracleStrategy,
 }
 
 impl GasPriceOracleArgs {
     /// Returns a [`GasPriceOracleConfig`] from the arguments.
     pub fn gas_price_oracle_config(&self) -> GasPriceOracleConfig {
-        let Self { blocks, ignore_price, max_price, percentile } = self;
+        let Self { blocks, ignore_price, max_price, percentile, strategy } = self;
         GasPriceOracleConfig {
             max_price: Some(U256::from(*max_price)),
             ignore_price: Some(U256::from(*ignore_price)),
             percentile: *percentile,
             blocks: *blocks,
+            strategy: *strategy,
             ..Default::default()
         }
     }
@@ -48,6 +63,7 @@ impl Default for GasPriceOracleArgs {
             ignore_price: DEFAULT_IGNORE_GAS_PRICE.to(),
             max_price: DEFAULT_MAX_GAS_PRICE.to(),
             percentile: DEFAULT_GAS_PRICE_PERCENTILE,
+            strategy: GasPriceOracleStrategy::default(),
         }
     }
 }
@@ -73,8 +89,23 @@ mod tests {
                 ignore_price: DEFAULT_IGNORE_GAS_PRICE.to(),
                 max_price: DEFAULT_MAX_GAS_PRICE.to(),
                 percentile: DEFAULT_GAS_PRICE_PERCENTILE,
+                strategy: GasPriceOracleStrategy::Percentile,
             }
         );
+
+        let args = CommandParser::<GasPriceOracleArgs>::parse_from([
+            "reth",
+            "--gpo.strategy",
+            "min-inclusion-tip",
+        ])
+        .args;
+        assert_eq!(args.strategy, GasPriceOracleStrategy::MinInclusionTip);
+        assert!(CommandParser::<GasPriceOracleArgs>::try_parse_from([
+            "reth",
+            "--gpo.strategy",
+            "median"
+        ])
+        .is_err());
     }
 
     #[test]
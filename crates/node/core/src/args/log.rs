@@ -57,6 +57,20 @@ pub struct LogArgs {
     )]
     pub journald_filter: String,
 
+    /// Export spans to the OTLP/HTTP collector at the given endpoint, e.g.
+    /// `http://localhost:4318/v1/traces`.
+    #[arg(long = "log.otlp", value_name = "URL", global = true)]
+    pub otlp: Option<String>,
+
+    /// The filter to use for spans exported to the OTLP collector.
+    #[arg(
+        long = "log.otlp.filter",
+        value_name = "FILTER",
+        global = true,
+        default_value = "engine=debug"
+    )]
+    pub otlp_filter: String,
+
     /// Sets whether or not the formatter emits ANSI terminal escape codes for colors and other
     /// text formatting.
     #[arg(
@@ -104,6 +118,10 @@ impl LogArgs {
             tracer = tracer.with_journald(self.journald_filter.clone());
         }
 
+        if let Some(endpoint) = &self.otlp {
+            tracer = tracer.with_otlp(endpoint.clone(), self.otlp_filter.clone());
+        }
+
         if self.log_file_max_files > 0 {
             let info = self.file_info();
             let file = self.layer(self.log_file_format, self.log_file_filter.clone(), false);
@@ -6,7 +6,7 @@ pub use network::{DiscoveryArgs, NetworkArgs};
 
 /// RpcServerArg struct for configuring the RPC
 mod rpc_server;
-pub use rpc_server::RpcServerArgs;
+pub use rpc_server::{AdditionalJwtSecretArg, RpcServerArgs};
 
 /// `RpcStateCacheArgs` struct for configuring RPC state cache
 mod rpc_state_cache;
@@ -60,6 +60,14 @@ pub use benchmark_args::BenchmarkArgs;
 mod engine;
 pub use engine::EngineArgs;
 
+/// `SyncArgs` for configuring the initial sync
+mod sync;
+pub use sync::{SyncArgs, SyncCheckpoint};
+
+/// `ExExArgs` for configuring execution extensions
+mod exex;
+pub use exex::ExExArgs;
+
 /// `RessArgs` for configuring ress subprotocol.
 mod ress_args;
 pub use ress_args::RessArgs;
@@ -3,7 +3,8 @@
 use std::{
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
     ops::Not,
-    path::PathBuf,
+    path::{Path, PathBuf},
+    time::Duration,
 };
 
 use clap::Args;
@@ -19,22 +20,24 @@ use reth_network::{
     transactions::{
         constants::{
             tx_fetcher::{
-                DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH, DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS,
-                DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
+                DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH,
+                DEFAULT_MAX_CAPACITY_CACHE_RECENTLY_FETCHED, DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS,
+                DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER, DEFAULT_TTL_SECS_RECENTLY_FETCHED,
             },
             tx_manager::{
                 DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS, DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             },
         },
-        TransactionFetcherConfig, TransactionsManagerConfig,
+        AnnouncementDedupConfig, TransactionFetcherConfig, TransactionsManagerConfig,
         DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
         SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
     },
-    HelloMessageWithProtocols, NetworkConfigBuilder, NetworkPrimitives, SessionsConfig,
+    HelloMessageWithProtocols, NetworkConfigBuilder, NetworkPrimitives, PeerAllowlist,
+    SessionsConfig,
 };
 use reth_network_peers::{mainnet_nodes, TrustedPeer};
 use secp256k1::SecretKey;
-use tracing::error;
+use tracing::{error, warn};
 
 use crate::version::P2P_CLIENT_VERSION;
 
@@ -57,6 +60,15 @@ pub struct NetworkArgs {
     #[arg(long)]
     pub trusted_only: bool,
 
+    /// Path to a file of the only peers to dial and accept connections from, for private
+    /// networks.
+    ///
+    /// Every line holds a peer ID, an enode URL or an ENR. Discovery is disabled, so the peers
+    /// of enode URLs and ENRs with an address are dialed, while peers only given by their ID must
+    /// connect to the node. The file is reloaded when it changes.
+    #[arg(long, value_name = "FILE")]
+    pub peer_allowlist: Option<PathBuf>,
+
     /// Comma separated enode URLs for P2P discovery bootstrap.
     ///
     /// Will fall back to a network-specific default if not specified.
@@ -68,7 +80,9 @@ pub struct NetworkArgs {
     pub dns_retries: usize,
 
     /// The path to the known peers file. Connected peers are dumped to this file on nodes
-    /// shutdown, and read on startup. Cannot be used with `--no-persist-peers`.
+    /// shutdown, and read on startup. Trusted peers added at runtime are saved to
+    /// `trusted-peers.json` in the same directory whenever they change. Cannot be used with
+    /// `--no-persist-peers`.
     #[arg(long, value_name = "FILE", verbatim_doc_comment, conflicts_with = "no_persist_peers")]
     pub peers_file: Option<PathBuf>,
 
@@ -87,10 +101,20 @@ pub struct NetworkArgs {
     #[arg(long, verbatim_doc_comment)]
     pub no_persist_peers: bool,
 
-    /// NAT resolution method (any|none|upnp|publicip|extip:\<IP\>)
+    /// NAT resolution method (any|none|upnp|pmp|publicip|extip:\<IP\>)
     #[arg(long, default_value = "any")]
     pub nat: NatResolver,
 
+    /// Experimental: relay to reserve a slot at, and to dial peers through if dialing them
+    /// directly fails, for nodes behind a NAT
+    #[arg(long, value_name = "ADDR")]
+    pub relay: Option<SocketAddr>,
+
+    /// Experimental: run a relay on the given address, that other nodes behind a NAT can reserve
+    /// a slot at
+    #[arg(long = "relay-server", value_name = "ADDR")]
+    pub relay_server: Option<SocketAddr>,
+
     /// Network listening address
     #[arg(long = "addr", value_name = "ADDR", default_value_t = DEFAULT_DISCOVERY_ADDR)]
     pub addr: IpAddr,
@@ -149,6 +173,26 @@ pub struct NetworkArgs {
     #[arg(long = "max-tx-pending-fetch", value_name = "COUNT", default_value_t = DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH, verbatim_doc_comment)]
     pub max_capacity_cache_txns_pending_fetch: u32,
 
+    /// Max capacity of cache of hashes for recently fetched transactions. Announcements of these
+    /// hashes aren't requested again.
+    #[arg(long = "tx-dedup-cache-size", value_name = "COUNT", default_value_t = DEFAULT_MAX_CAPACITY_CACHE_RECENTLY_FETCHED, verbatim_doc_comment)]
+    pub max_capacity_cache_txns_recently_fetched: u32,
+
+    /// Time in seconds for which the hash of a fetched transaction is remembered.
+    #[arg(long = "tx-dedup-ttl", value_name = "SECONDS", default_value_t = DEFAULT_TTL_SECS_RECENTLY_FETCHED, verbatim_doc_comment)]
+    pub ttl_secs_txns_recently_fetched: u64,
+
+    /// Grow the cache of hashes for recently fetched transactions, up to 4 times its capacity,
+    /// while many announced hashes were fetched recently.
+    #[arg(long = "tx-dedup-adaptive", verbatim_doc_comment)]
+    pub adaptive_cache_txns_recently_fetched: bool,
+
+    /// Only announce the hashes of new transactions to peers, and never broadcast them in full.
+    ///
+    /// Announced transactions are still served on request, this reduces the upstream bandwidth.
+    #[arg(long = "tx-propagation.hashes-only", verbatim_doc_comment)]
+    pub tx_propagate_hashes_only: bool,
+
     /// Name of network interface used to communicate with peers.
     ///
     /// If flag is set, but no value is passed, the default interface for docker `eth0` is tried.
@@ -193,9 +237,15 @@ impl NetworkArgs {
                 self.soft_limit_byte_size_pooled_transactions_response,
                 self.soft_limit_byte_size_pooled_transactions_response_on_pack_request,
                 self.max_capacity_cache_txns_pending_fetch,
+                AnnouncementDedupConfig {
+                    max_capacity: self.max_capacity_cache_txns_recently_fetched,
+                    ttl: Duration::from_secs(self.ttl_secs_txns_recently_fetched),
+                    adaptive: self.adaptive_cache_txns_recently_fetched,
+                },
             ),
             max_transactions_seen_by_peer_history: self.max_seen_tx_history,
             propagation_mode: Default::default(),
+            propagate_hashes_only: self.tx_propagate_hashes_only,
         }
     }
 
@@ -221,21 +271,32 @@ impl NetworkArgs {
         let chain_bootnodes = self
             .resolved_bootnodes()
             .unwrap_or_else(|| chain_spec.bootnodes().unwrap_or_else(mainnet_nodes));
-        let peers_file = self.peers_file.clone().unwrap_or(default_peers_file);
+        let peers_file =
+            self.persistent_peers_file(self.peers_file.clone().unwrap_or(default_peers_file));
 
-        // Configure peer connections
-        let peers_config = config
-            .peers
-            .clone()
+        // Configure peer connections, including the peers persisted by a previous run
+        let mut peers_config = config
+            .peers_config_with_basic_nodes_from_file(peers_file.as_deref())
             .with_max_inbound_opt(self.max_inbound_peers)
             .with_max_outbound_opt(self.max_outbound_peers);
+        let trusted_peers_file = peers_file.as_deref().map(Self::trusted_peers_file);
+        peers_config = match peers_config.clone().with_trusted_nodes_from_file(trusted_peers_file) {
+            Ok(peers_config) => peers_config,
+            Err(err) => {
+                warn!(target: "reth::cli", %err, "Failed to load persisted trusted peers");
+                peers_config
+            }
+        };
+        if let Some(file) = &self.peer_allowlist {
+            peers_config = peers_config.with_allowlist(Some(PeerAllowlist::with_file(file)));
+        }
+        let allowlist_only = peers_config.allowlist.is_some();
 
         // Configure basic network stack
         NetworkConfigBuilder::<N>::new(secret_key)
-            .peer_config(config.peers_config_with_basic_nodes_from_file(
-                self.persistent_peers_file(peers_file).as_deref(),
-            ))
             .external_ip_resolver(self.nat)
+            .relay(self.relay)
+            .relay_server_addr(self.relay_server)
             .sessions_config(
                 SessionsConfig::default().with_upscaled_event_buffer(peers_config.max_peers()),
             )
@@ -256,6 +317,8 @@ impl NetworkArgs {
                 let rlpx_socket = (addr, self.port).into();
                 self.discovery.apply_to_builder(builder, rlpx_socket, chain_bootnodes)
             })
+            // peers of private networks are configured upfront
+            .disable_discovery_if(allowlist_only)
             .listener_addr(SocketAddr::new(
                 addr, // set discovery port based on instance number
                 self.port,
@@ -272,6 +335,12 @@ impl NetworkArgs {
         self.no_persist_peers.not().then_some(peers_file)
     }
 
+    /// Returns the path to the file the trusted peers are persisted to, which is
+    /// `trusted-peers.json` next to the given persistent peers file.
+    pub fn trusted_peers_file(peers_file: &Path) -> PathBuf {
+        peers_file.with_file_name("trusted-peers.json")
+    }
+
     /// Sets the p2p port to zero, to allow the OS to assign a random unused port when
     /// the network components bind to a socket.
     pub const fn with_unused_p2p_port(mut self) -> Self {
@@ -313,6 +382,7 @@ impl Default for NetworkArgs {
             discovery: DiscoveryArgs::default(),
             trusted_peers: vec![],
             trusted_only: false,
+            peer_allowlist: None,
             bootnodes: None,
             dns_retries: 0,
             peers_file: None,
@@ -320,6 +390,8 @@ impl Default for NetworkArgs {
             p2p_secret_key: None,
             no_persist_peers: false,
             nat: NatResolver::Any,
+            relay: None,
+            relay_server: None,
             addr: DEFAULT_DISCOVERY_ADDR,
             port: DEFAULT_DISCOVERY_PORT,
             max_outbound_peers: None,
@@ -332,6 +404,10 @@ impl Default for NetworkArgs {
             max_pending_pool_imports: DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS,
             max_seen_tx_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             max_capacity_cache_txns_pending_fetch: DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH,
+            max_capacity_cache_txns_recently_fetched: DEFAULT_MAX_CAPACITY_CACHE_RECENTLY_FETCHED,
+            ttl_secs_txns_recently_fetched: DEFAULT_TTL_SECS_RECENTLY_FETCHED,
+            adaptive_cache_txns_recently_fetched: false,
+            tx_propagate_hashes_only: false,
             net_if: None,
         }
     }
@@ -35,6 +35,21 @@ pub struct PayloadBuilderArgs {
     /// Maximum number of tasks to spawn for building a payload.
     #[arg(long = "builder.max-tasks", default_value = "3", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
     pub max_payload_tasks: usize,
+
+    /// Number of candidate transactions to simulate in parallel while building a payload.
+    ///
+    /// The simulation prefetches the state the transactions access before they are executed, so
+    /// more transactions can be included before the deadline. Values below 2 disable it.
+    #[arg(long = "builder.parallel-simulation", default_value = "0", value_name = "CANDIDATES")]
+    pub parallel_simulation: usize,
+
+    /// How long before the payload's timestamp a transaction that is still executing is
+    /// interrupted, so the best payload built so far can be delivered in time.
+    ///
+    /// Specified in seconds or in milliseconds if the value ends with `ms`. Transactions are never
+    /// interrupted if not set.
+    #[arg(long = "builder.interrupt-margin", value_parser = parse_duration_from_secs_or_ms, value_name = "DURATION")]
+    pub interrupt_margin: Option<Duration>,
 }
 
 impl Default for PayloadBuilderArgs {
@@ -45,6 +60,8 @@ impl Default for PayloadBuilderArgs {
             interval: Duration::from_secs(1),
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            parallel_simulation: 0,
+            interrupt_margin: None,
         }
     }
 }
@@ -69,6 +86,14 @@ impl PayloadBuilderConfig for PayloadBuilderArgs {
     fn max_payload_tasks(&self) -> usize {
         self.max_payload_tasks
     }
+
+    fn parallel_simulation(&self) -> usize {
+        self.parallel_simulation
+    }
+
+    fn interrupt_margin(&self) -> Option<Duration> {
+        self.interrupt_margin
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -118,6 +143,17 @@ mod tests {
         assert_eq!(args.max_payload_tasks, 1)
     }
 
+    #[test]
+    fn test_args_with_parallel_simulation() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.parallel-simulation",
+            "8",
+        ])
+        .args;
+        assert_eq!(args.parallel_simulation, 8)
+    }
+
     #[test]
     fn test_args_with_invalid_max_tasks() {
         assert!(CommandParser::<PayloadBuilderArgs>::try_parse_from([
@@ -173,4 +209,15 @@ mod tests {
                 .args;
         assert_eq!(args.interval, Duration::from_millis(50));
     }
+
+    #[test]
+    fn test_args_with_interrupt_margin() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.interrupt-margin",
+            "500ms",
+        ])
+        .args;
+        assert_eq!(args.interrupt_margin, Some(Duration::from_millis(500)));
+    }
 }
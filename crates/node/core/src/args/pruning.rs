@@ -6,7 +6,7 @@ use clap::{builder::RangedU64ValueParser, Args};
 use reth_chainspec::EthChainSpec;
 use reth_config::config::PruneConfig;
 use reth_prune_types::{PruneMode, PruneModes, ReceiptsLogPruneConfig, MINIMUM_PRUNING_DISTANCE};
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, path::PathBuf};
 
 /// Parameters for pruning and full node
 #[derive(Debug, Clone, Args, PartialEq, Eq, Default)]
@@ -82,6 +82,16 @@ pub struct PruningArgs {
     #[arg(long = "prune.storagehistory.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["storage_history_full", "storage_history_distance"])]
     pub storage_history_before: Option<BlockNumber>,
 
+    // Bodies History
+    /// Expire pre-merge block bodies and receipts from static files according to EIP-4444. Headers
+    /// are kept.
+    #[arg(long = "prune.bodies.pre-merge", requires = "bodies_era_dir")]
+    pub bodies_pre_merge: bool,
+    /// Directory with the era1 files covering the expired history and their `checksums.txt`. The
+    /// files are verified before any block bodies are removed.
+    #[arg(long = "prune.bodies.era-dir", value_name = "PATH")]
+    pub bodies_era_dir: Option<PathBuf>,
+
     // Receipts Log Filter
     /// Configure receipts log filter. Format:
     /// <`address`>:<`prune_mode`>[,<`address`>:<`prune_mode`>...] Where <`prune_mode`> can be
@@ -111,6 +121,7 @@ impl PruningArgs {
                         .or(Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE))),
                     account_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
                     storage_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                    bodies_history: None,
                     receipts_log_filter: ReceiptsLogPruneConfig(
                         chain_spec
                             .deposit_contract()
@@ -119,6 +130,7 @@ impl PruningArgs {
                             .collect(),
                     ),
                 },
+                era_dir: None,
             }
         }
 
@@ -141,9 +153,20 @@ impl PruningArgs {
         if let Some(mode) = self.storage_history_prune_mode() {
             config.segments.storage_history = Some(mode);
         }
+        if let Some(mode) = self.bodies_history_prune_mode(chain_spec) {
+            config.segments.bodies_history = Some(mode);
+        }
+        if let Some(era_dir) = &self.bodies_era_dir {
+            config.era_dir = Some(era_dir.clone());
+        }
 
         Some(config)
     }
+
+    fn bodies_history_prune_mode(&self, chain_spec: &impl EthChainSpec) -> Option<PruneMode> {
+        self.bodies_pre_merge.then(|| chain_spec.paris_block()).flatten().map(PruneMode::Before)
+    }
+
     const fn sender_recovery_prune_mode(&self) -> Option<PruneMode> {
         if self.sender_recovery_full {
             Some(PruneMode::Full)
@@ -5,6 +5,7 @@ use std::{
     ffi::OsStr,
     net::{IpAddr, Ipv4Addr},
     path::PathBuf,
+    str::FromStr,
 };
 
 use alloy_primitives::Address;
@@ -59,6 +60,24 @@ pub struct RpcServerArgs {
     #[arg(long = "http.corsdomain")]
     pub http_corsdomain: Option<String>,
 
+    /// Disable the compression of HTTP responses.
+    ///
+    /// By default, responses are compressed with gzip, brotli, deflate or zstd, as negotiated
+    /// with the client's `Accept-Encoding` header.
+    #[arg(long = "http.disable-compression")]
+    pub http_disable_compression: bool,
+
+    /// Minimum size of HTTP responses in bytes to compress them, 1024 by default.
+    #[arg(long = "http.compression-min-size", value_name = "BYTES")]
+    pub http_compression_min_size: Option<u16>,
+
+    /// Compression level of HTTP responses, specific to the negotiated encoding, e.g. 1-9 for
+    /// gzip and 0-11 for brotli.
+    ///
+    /// Out of range levels are clamped. By default, the default level of the encoding is used.
+    #[arg(long = "http.compression-level", value_name = "LEVEL")]
+    pub http_compression_level: Option<u8>,
+
     /// Enable the WS-RPC server
     #[arg(long)]
     pub ws: bool,
@@ -87,6 +106,15 @@ pub struct RpcServerArgs {
     #[arg(long, default_value_t = constants::DEFAULT_IPC_ENDPOINT.to_string())]
     pub ipcpath: String,
 
+    /// Path of a Unix socket that streams canonical chain events, e.g. to processes that don't
+    /// run as an ExEx.
+    ///
+    /// Every event is a line of the form `<kind> <number> <hash>`, where the kind is `head`,
+    /// `reorg` for the lowest block removed by a reorg, or `finalized`. Connections start with
+    /// the current head and finalized block.
+    #[arg(long = "ipc.chain-events", value_name = "PATH")]
+    pub chain_events_ipcpath: Option<PathBuf>,
+
     /// Auth server address to listen on
     #[arg(long = "authrpc.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
     pub auth_addr: IpAddr,
@@ -104,6 +132,15 @@ pub struct RpcServerArgs {
     #[arg(long = "authrpc.jwtsecret", value_name = "PATH", global = true, required = false)]
     pub auth_jwtsecret: Option<PathBuf>,
 
+    /// Path to an additional JWT secret accepted by the authenticated engine-API RPC server,
+    /// optionally followed by the window in which it is accepted, as unix timestamps.
+    ///
+    /// This allows rotating the secret without restarting the execution and consensus layer at
+    /// the same time, e.g. `old.hex@..1767225600` keeps accepting the old secret until the given
+    /// time. Can be specified multiple times.
+    #[arg(long = "authrpc.jwtsecret.additional", value_name = "PATH[@FROM..UNTIL]")]
+    pub auth_jwtsecret_additional: Vec<AdditionalJwtSecretArg>,
+
     /// Enable auth engine API over IPC
     #[arg(long)]
     pub auth_ipc: bool,
@@ -175,6 +212,26 @@ pub struct RpcServerArgs {
     )]
     pub rpc_max_simulate_blocks: u64,
 
+    /// Include the EIP-7702 delegation target of the called account in the access lists
+    /// generated by `eth_createAccessList`.
+    #[arg(long = "rpc.access-list-delegations", default_value_t = false)]
+    pub rpc_access_list_delegations: bool,
+
+    /// Execute calls against the pending block on top of the caller's pending pool
+    /// transactions, so that dependent transactions can be simulated before inclusion.
+    #[arg(long = "rpc.pending-call-pool-txs", default_value_t = false)]
+    pub rpc_pending_call_pool_txs: bool,
+
+    /// Resolve the `safe` block tag to the block this many blocks below the latest block, as long
+    /// as the consensus layer hasn't set a safe block, e.g. on dev chains.
+    #[arg(long = "rpc.safe-fallback-depth", value_name = "BLOCKS")]
+    pub rpc_safe_fallback_depth: Option<u64>,
+
+    /// Resolve the `finalized` block tag to the block this many blocks below the latest block, as
+    /// long as the consensus layer hasn't set a finalized block, e.g. on dev chains.
+    #[arg(long = "rpc.finalized-fallback-depth", value_name = "BLOCKS")]
+    pub rpc_finalized_fallback_depth: Option<u64>,
+
     /// The maximum proof window for historical proof generation.
     /// This value allows for generating historical proofs up to
     /// configured number of blocks from current tip (up to `tip - window`).
@@ -185,10 +242,52 @@ pub struct RpcServerArgs {
     )]
     pub rpc_eth_proof_window: u64,
 
-    /// Maximum number of concurrent getproof requests.
+    /// Maximum number of accounts of a single `reth_getProofs` call.
+    #[arg(long = "rpc.max-proofs-accounts", value_name = "COUNT", default_value_t = constants::DEFAULT_MAX_PROOFS_ACCOUNTS)]
+    pub rpc_max_proofs_accounts: usize,
+
+    /// Maximum number of concurrent state proof and witness requests. Further requests wait in a
+    /// bounded queue, single account proofs ahead of batched proofs and witnesses.
     #[arg(long = "rpc.proof-permits", alias = "rpc-proof-permits", value_name = "COUNT", default_value_t = constants::DEFAULT_PROOF_PERMITS)]
     pub rpc_proof_permits: usize,
 
+    /// Number of threads of the pool that `debug` and `trace` requests are executed on.
+    ///
+    /// Defaults to the number of CPUs.
+    #[arg(long = "rpc.tracing-pool-threads", value_name = "COUNT")]
+    pub rpc_tracing_pool_threads: Option<usize>,
+
+    /// Maximum number of concurrent `eth_call`, `eth_estimateGas` and other simulations of the
+    /// `eth` namespace.
+    ///
+    /// These are bounded separately from the `debug` and `trace` requests, so tracing bursts don't
+    /// delay them.
+    #[arg(long = "rpc.max-concurrent-calls", value_name = "COUNT", default_value_t = constants::DEFAULT_MAX_CONCURRENT_CALLS)]
+    pub rpc_max_concurrent_calls: usize,
+
+    /// Number of workers that historical state reads are split across. The workers share the
+    /// read-only database transaction of the request.
+    ///
+    /// This speeds up `eth_call` and state root, proof and witness computation at old blocks.
+    #[arg(long = "rpc.historical-parallelism", value_name = "COUNT", default_value_t = 1)]
+    pub rpc_historical_parallelism: usize,
+
+    /// Path to the file that installed log and block filters are persisted to, so that
+    /// `eth_getFilterChanges` keeps working across restarts.
+    ///
+    /// The file is written at most once per second, so after a crash clients can receive changes
+    /// again that they already polled. Filters are only kept in memory if not set.
+    #[arg(long = "rpc.filters-file", value_name = "PATH")]
+    pub rpc_filters_file: Option<PathBuf>,
+
+    /// HTTP URL of a trusted node that the state of blocks that are pruned locally is fetched
+    /// from, for `eth_call` and tracing.
+    ///
+    /// Fetched accounts and storage are verified with `eth_getProof` against the local state root
+    /// of the block.
+    #[arg(long = "rpc.remote-state-url", value_name = "URL")]
+    pub rpc_remote_state_url: Option<String>,
+
     /// Path to file containing disallowed addresses, json-encoded list of strings. Block
     /// validation API will reject blocks containing transactions from these addresses.
     #[arg(long = "builder.disallow", value_name = "PATH", value_parser = reth_cli_util::parsers::read_json_from_file::<HashSet<Address>>)]
@@ -309,6 +408,9 @@ impl Default for RpcServerArgs {
             http_port: constants::DEFAULT_HTTP_RPC_PORT,
             http_api: None,
             http_corsdomain: None,
+            http_disable_compression: false,
+            http_compression_min_size: None,
+            http_compression_level: None,
             ws: false,
             ws_addr: Ipv4Addr::LOCALHOST.into(),
             ws_port: constants::DEFAULT_WS_RPC_PORT,
@@ -316,9 +418,11 @@ impl Default for RpcServerArgs {
             ws_api: None,
             ipcdisable: false,
             ipcpath: constants::DEFAULT_IPC_ENDPOINT.to_string(),
+            chain_events_ipcpath: None,
             auth_addr: Ipv4Addr::LOCALHOST.into(),
             auth_port: constants::DEFAULT_AUTH_PORT,
             auth_jwtsecret: None,
+            auth_jwtsecret_additional: Vec::new(),
             auth_ipc: false,
             auth_ipc_path: constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string(),
             rpc_jwtsecret: None,
@@ -332,15 +436,62 @@ impl Default for RpcServerArgs {
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
             rpc_gas_cap: constants::gas_oracle::RPC_DEFAULT_GAS_CAP,
             rpc_max_simulate_blocks: constants::DEFAULT_MAX_SIMULATE_BLOCKS,
+            rpc_access_list_delegations: false,
+            rpc_pending_call_pool_txs: false,
+            rpc_safe_fallback_depth: None,
+            rpc_finalized_fallback_depth: None,
             rpc_eth_proof_window: constants::DEFAULT_ETH_PROOF_WINDOW,
+            rpc_max_proofs_accounts: constants::DEFAULT_MAX_PROOFS_ACCOUNTS,
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
+            rpc_tracing_pool_threads: None,
+            rpc_max_concurrent_calls: constants::DEFAULT_MAX_CONCURRENT_CALLS,
+            rpc_historical_parallelism: 1,
+            rpc_filters_file: None,
+            rpc_remote_state_url: None,
             builder_disallow: Default::default(),
         }
     }
 }
 
+/// An additional JWT secret file for the authenticated engine-API RPC server, see
+/// `--authrpc.jwtsecret.additional`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdditionalJwtSecretArg {
+    /// Path to the secret.
+    pub path: PathBuf,
+    /// Unix timestamp from which the secret is accepted.
+    pub not_before: Option<u64>,
+    /// Unix timestamp until which the secret is accepted.
+    pub not_after: Option<u64>,
+}
+
+impl FromStr for AdditionalJwtSecretArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((path, window)) = s.rsplit_once('@') else {
+            return Ok(Self { path: s.into(), not_before: None, not_after: None })
+        };
+        let (not_before, not_after) = window
+            .split_once("..")
+            .ok_or_else(|| format!("invalid validity window, expected FROM..UNTIL: {window}"))?;
+        let timestamp = |value: &str| {
+            (!value.is_empty())
+                .then(|| {
+                    value.parse::<u64>().map_err(|err| format!("invalid timestamp {value}: {err}"))
+                })
+                .transpose()
+        };
+        Ok(Self {
+            path: path.into(),
+            not_before: timestamp(not_before)?,
+            not_after: timestamp(not_after)?,
+        })
+    }
+}
+
 /// clap value parser for [`RpcModuleSelection`].
 #[derive(Clone, Debug, Default)]
 #[non_exhaustive]
@@ -417,6 +568,36 @@ mod tests {
         assert_eq!(apis, expected);
     }
 
+    #[test]
+    fn test_rpc_server_additional_jwt_secrets() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--authrpc.jwtsecret.additional",
+            "old.hex@..1767225600",
+            "--authrpc.jwtsecret.additional",
+            "new.hex",
+        ])
+        .args;
+        assert_eq!(
+            args.auth_jwtsecret_additional,
+            vec![
+                AdditionalJwtSecretArg {
+                    path: "old.hex".into(),
+                    not_before: None,
+                    not_after: Some(1767225600)
+                },
+                AdditionalJwtSecretArg {
+                    path: "new.hex".into(),
+                    not_before: None,
+                    not_after: None
+                },
+            ]
+        );
+
+        assert!("old.hex@1767225600".parse::<AdditionalJwtSecretArg>().is_err());
+        assert!("old.hex@x..".parse::<AdditionalJwtSecretArg>().is_err());
+    }
+
     #[test]
     fn rpc_server_args_default_sanity_test() {
         let default_args = RpcServerArgs::default();
@@ -0,0 +1,121 @@
+//! clap [Args](clap::Args) for sync configuration
+
+use crate::args::error::SyncCheckpointError;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{BlockNumber, B256};
+use clap::Args;
+use std::{fmt, str::FromStr};
+
+/// Parameters for configuring the initial sync.
+#[derive(Debug, Clone, Copy, Default, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Sync")]
+pub struct SyncArgs {
+    /// Sync to a trusted checkpoint, given as `<NUMBER>:<HASH>`.
+    ///
+    /// The headers are downloaded backwards from the checkpoint and the remaining stages start
+    /// right away, without waiting for the consensus layer to provide a sync target. The
+    /// checkpoint is ignored if the node already synced past it.
+    #[arg(long = "sync.checkpoint", value_name = "NUMBER:HASH")]
+    pub checkpoint: Option<SyncCheckpoint>,
+    /// Verify the state root every this many blocks during the initial sync, instead of only at
+    /// the block the node syncs to.
+    ///
+    /// Overrides `stages.execution.state_root_interval` of the config file. Invalid state
+    /// transitions are detected earlier, at the cost of computing more state roots.
+    #[arg(
+        long = "sync.state-root-interval",
+        value_name = "BLOCKS",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub state_root_interval: Option<u64>,
+}
+
+/// A trusted block the node syncs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncCheckpoint {
+    /// Number of the block.
+    pub number: BlockNumber,
+    /// Hash of the block.
+    pub hash: B256,
+}
+
+impl SyncCheckpoint {
+    /// Returns the number and hash of the block.
+    pub const fn num_hash(&self) -> BlockNumHash {
+        BlockNumHash::new(self.number, self.hash)
+    }
+}
+
+impl FromStr for SyncCheckpoint {
+    type Err = SyncCheckpointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (number, hash) =
+            s.split_once(':').ok_or_else(|| SyncCheckpointError::InvalidFormat(s.to_string()))?;
+        Ok(Self {
+            number: number.parse().map_err(SyncCheckpointError::InvalidBlockNumber)?,
+            hash: hash.parse().map_err(SyncCheckpointError::InvalidBlockHash)?,
+        })
+    }
+}
+
+impl fmt::Display for SyncCheckpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.number, self.hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_sync_checkpoint() {
+        let hash = B256::with_last_byte(1);
+        let args = CommandParser::<SyncArgs>::parse_from([
+            "reth",
+            "--sync.checkpoint",
+            &format!("100:{hash}"),
+        ])
+        .args;
+        assert_eq!(args.checkpoint, Some(SyncCheckpoint { number: 100, hash }));
+
+        assert!(CommandParser::<SyncArgs>::try_parse_from(["reth", "--sync.checkpoint", "100"])
+            .is_err());
+        assert!(CommandParser::<SyncArgs>::try_parse_from([
+            "reth",
+            "--sync.checkpoint",
+            &format!("latest:{hash}"),
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_state_root_interval() {
+        let args =
+            CommandParser::<SyncArgs>::parse_from(["reth", "--sync.state-root-interval", "1000"])
+                .args;
+        assert_eq!(args.state_root_interval, Some(1000));
+
+        assert!(CommandParser::<SyncArgs>::try_parse_from([
+            "reth",
+            "--sync.state-root-interval",
+            "0"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn sync_checkpoint_roundtrip() {
+        let checkpoint = SyncCheckpoint { number: 100, hash: B256::with_last_byte(1) };
+        assert_eq!(checkpoint.to_string().parse::<SyncCheckpoint>().unwrap(), checkpoint);
+    }
+}
@@ -15,7 +15,7 @@ use reth_transaction_pool::{
     REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
     TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 /// Parameters for debugging purposes
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
@@ -53,6 +53,13 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.max-account-slots", alias = "txpool.max_account_slots", default_value_t = TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER)]
     pub max_account_slots: usize,
 
+    /// Max number of pending transactions per account.
+    ///
+    /// Further executable transactions of the account are parked in the queued sub-pool until
+    /// the pending ones are mined. Local transactions are exempt.
+    #[arg(long = "txpool.max-pending-account-slots", value_name = "SLOTS")]
+    pub max_pending_account_slots: Option<usize>,
+
     /// Price bump (in %) for the transaction pool underpriced check.
     #[arg(long = "txpool.pricebump", default_value_t = DEFAULT_PRICE_BUMP)]
     pub price_bump: u128,
@@ -106,6 +113,19 @@ pub struct TxPoolArgs {
     /// Maximum amount of time non-executable transaction are queued.
     #[arg(long = "txpool.lifetime", value_parser = parse_duration_from_secs_or_ms, default_value = "10800", value_name = "DURATION")]
     pub max_queued_lifetime: Duration,
+
+    /// Maximum amount of time transactions are kept in the basefee sub-pool waiting for the base
+    /// fee to drop, disabled by default. Expired transactions are removed with their descendants.
+    #[arg(long = "txpool.basefee-lifetime", value_parser = parse_duration_from_secs_or_ms, value_name = "DURATION")]
+    pub max_basefee_lifetime: Option<Duration>,
+
+    /// Path to a file of contract addresses and code hashes that transactions must not target.
+    ///
+    /// Every line holds an address or a code hash, optionally followed by `reject` (default) or
+    /// `deprioritize` to accept matching transactions without propagating them. The file is
+    /// reloaded when it changes.
+    #[arg(long = "txpool.target-denylist", value_name = "FILE")]
+    pub target_denylist: Option<PathBuf>,
 }
 
 impl Default for TxPoolArgs {
@@ -120,6 +140,7 @@ impl Default for TxPoolArgs {
             blobpool_max_count: TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
             blobpool_max_size: TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+            max_pending_account_slots: None,
             price_bump: DEFAULT_PRICE_BUMP,
             minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE,
             enforced_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
@@ -134,6 +155,8 @@ impl Default for TxPoolArgs {
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
+            max_basefee_lifetime: None,
+            target_denylist: None,
         }
     }
 }
@@ -164,6 +187,7 @@ impl RethTransactionPoolConfig for TxPoolArgs {
                 max_size: self.blobpool_max_size.saturating_mul(1024 * 1024),
             },
             max_account_slots: self.max_account_slots,
+            max_pending_account_slots: self.max_pending_account_slots,
             price_bumps: PriceBumpConfig {
                 default_price_bump: self.price_bump,
                 replace_blob_tx_price_bump: self.blob_transaction_price_bump,
@@ -174,6 +198,7 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             new_tx_listener_buffer_size: self.new_tx_listener_buffer_size,
             max_new_pending_txs_notifications: self.max_new_pending_txs_notifications,
             max_queued_lifetime: self.max_queued_lifetime,
+            max_basefee_lifetime: self.max_basefee_lifetime,
         }
     }
 }
@@ -29,6 +29,12 @@ pub trait PayloadBuilderConfig {
 
     /// Maximum number of tasks to spawn for building a payload.
     fn max_payload_tasks(&self) -> usize;
+
+    /// Number of candidate transactions to simulate in parallel while building a payload.
+    fn parallel_simulation(&self) -> usize;
+
+    /// How long before the payload's timestamp a still executing transaction is interrupted.
+    fn interrupt_margin(&self) -> Option<Duration>;
 }
 
 /// A trait that represents the configured network and can be used to apply additional configuration
@@ -355,6 +355,13 @@ impl<D> ChainPath<D> {
     pub fn exex_wal(&self) -> PathBuf {
         self.data_dir().join("exex/wal")
     }
+
+    /// Returns the path to the directory of the ExEx store with the given name for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/exex/store/<NAME>`
+    pub fn exex_store(&self, name: &str) -> PathBuf {
+        self.data_dir().join("exex/store").join(name)
+    }
 }
 
 impl<D> AsRef<Path> for ChainPath<D> {
@@ -2,8 +2,8 @@
 
 use crate::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, NetworkArgs, PayloadBuilderArgs,
-        PruningArgs, RpcServerArgs, TxPoolArgs,
+        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, ExExArgs, NetworkArgs,
+        PayloadBuilderArgs, PruningArgs, RpcServerArgs, SyncArgs, TxPoolArgs,
     },
     dirs::{ChainPath, DataDirPath},
     utils::get_single_header,
@@ -144,6 +144,12 @@ pub struct NodeConfig<ChainSpec> {
 
     /// All engine related arguments
     pub engine: EngineArgs,
+
+    /// All sync related arguments
+    pub sync: SyncArgs,
+
+    /// All ExEx related arguments
+    pub exex: ExExArgs,
 }
 
 impl NodeConfig<ChainSpec> {
@@ -173,6 +179,8 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             pruning: PruningArgs::default(),
             datadir: DatadirArgs::default(),
             engine: EngineArgs::default(),
+            sync: SyncArgs::default(),
+            exex: ExExArgs::default(),
         }
     }
 
@@ -273,6 +281,18 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
         self
     }
 
+    /// Set the sync args for the node
+    pub const fn with_sync(mut self, sync: SyncArgs) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    /// Set the ExEx args for the node
+    pub fn with_exex(mut self, exex: ExExArgs) -> Self {
+        self.exex = exex;
+        self
+    }
+
     /// Returns pruning configuration.
     pub fn prune_config(&self) -> Option<PruneConfig>
     where
@@ -400,6 +420,56 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
         self.network.adjust_instance_ports(self.instance);
     }
 
+    /// Returns the config of a node running the given chain in the same process as this node.
+    ///
+    /// The `index` of the additional chain, starting at 1, is added to the instance of this node
+    /// to configure non-conflicting ports. If a datadir is set, the data of the chain is stored
+    /// in the directory of the chain inside of it. Metrics aren't served, since all nodes of the
+    /// process share the endpoint of this node.
+    ///
+    /// The chain spec type of the additional chain may differ from the one of this node, e.g. for
+    /// an L1 next to a rollup.
+    pub fn for_additional_chain<C: EthChainSpec>(
+        &self,
+        chain: Arc<C>,
+        index: u16,
+    ) -> eyre::Result<NodeConfig<C>> {
+        let instance =
+            self.instance.checked_add(index).filter(|instance| *instance <= 200).ok_or_else(
+                || eyre!("instance of additional chain {} exceeds 200", chain.chain()),
+            )?;
+
+        let mut datadir = self.datadir.clone();
+        if self.datadir.datadir.is_some() {
+            let path = self
+                .datadir
+                .datadir
+                .unwrap_or_default()
+                .with_chain(chain.chain(), self.datadir.clone());
+            datadir.datadir = PathBuf::from(path).into();
+        }
+        datadir.static_files_path = None;
+
+        Ok(NodeConfig {
+            chain,
+            datadir,
+            config: self.config.clone(),
+            metrics: None,
+            instance,
+            network: self.network.clone(),
+            rpc: self.rpc.clone(),
+            txpool: self.txpool.clone(),
+            builder: self.builder.clone(),
+            debug: self.debug.clone(),
+            db: self.db,
+            dev: self.dev,
+            pruning: self.pruning.clone(),
+            engine: self.engine.clone(),
+            sync: self.sync,
+            exex: self.exex.clone(),
+        })
+    }
+
     /// Sets networking and RPC ports to zero, causing the OS to choose random unused ports when
     /// sockets are bound.
     pub fn with_unused_ports(mut self) -> Self {
@@ -464,6 +534,8 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             dev: self.dev,
             pruning: self.pruning,
             engine: self.engine,
+            sync: self.sync,
+            exex: self.exex,
         }
     }
 }
@@ -491,6 +563,8 @@ impl<ChainSpec> Clone for NodeConfig<ChainSpec> {
             pruning: self.pruning.clone(),
             datadir: self.datadir.clone(),
             engine: self.engine.clone(),
+            sync: self.sync,
+            exex: self.exex.clone(),
         }
     }
 }
@@ -1,9 +1,48 @@
 //! Prometheus recorder
 
 use eyre::WrapErr;
+use metrics::{
+    Counter, Gauge, Histogram, Key, KeyName, Label, Metadata, Recorder, SharedString, Unit,
+};
 use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
-use metrics_util::layers::{PrefixLayer, Stack};
-use std::sync::{atomic::AtomicBool, LazyLock};
+use metrics_util::layers::{Layer, PrefixLayer, Stack};
+use reth_tasks::TaskExecutor;
+use std::{
+    future::Future,
+    sync::{atomic::AtomicBool, LazyLock},
+};
+
+tokio::task_local! {
+    /// The chain of the node whose metrics are registered, see [`with_chain_label`].
+    static CHAIN_LABEL: SharedString;
+}
+
+/// Runs the future with a `chain` label added to all metrics that are registered while it runs.
+///
+/// This distinguishes the metrics of nodes of different chains that run in the same process and
+/// share the global recorder. Metrics are labeled when they are registered, so the tasks spawned
+/// by the node must be spawned with an executor of [`chain_label_executor`] to be labeled too.
+pub async fn with_chain_label<F: Future>(chain: impl Into<SharedString>, f: F) -> F::Output {
+    CHAIN_LABEL.scope(chain.into(), f).await
+}
+
+/// Returns the executor with a `chain` label added to all metrics that are registered by the
+/// tasks it spawns, see [`with_chain_label`].
+///
+/// Metrics that are registered by threads that aren't spawned by the executor aren't labeled.
+pub fn chain_label_executor(
+    executor: TaskExecutor,
+    chain: impl Into<SharedString>,
+) -> TaskExecutor {
+    let chain = chain.into();
+    executor.with_task_hook(move |fut| Box::pin(CHAIN_LABEL.scope(chain.clone(), fut)))
+}
+
+/// Calls the closure with a `chain` label added to all metrics that are registered during the
+/// call, see [`with_chain_label`].
+pub fn sync_with_chain_label<R>(chain: impl Into<SharedString>, f: impl FnOnce() -> R) -> R {
+    CHAIN_LABEL.sync_scope(chain.into(), f)
+}
 
 /// Installs the Prometheus recorder as the global recorder.
 ///
@@ -80,6 +119,7 @@ impl PrometheusRecorder {
 
         // Build metrics stack
         Stack::new(recorder)
+            .push(ChainLabelLayer)
             .push(PrefixLayer::new("reth"))
             .install()
             .wrap_err("Couldn't set metrics recorder.")?;
@@ -88,6 +128,57 @@ impl PrometheusRecorder {
     }
 }
 
+/// A [`Layer`] that adds the `chain` label of [`with_chain_label`] to the registered metrics.
+#[derive(Debug, Clone, Copy, Default)]
+struct ChainLabelLayer;
+
+impl<R> Layer<R> for ChainLabelLayer {
+    type Output = ChainLabelRecorder<R>;
+
+    fn layer(&self, inner: R) -> Self::Output {
+        ChainLabelRecorder(inner)
+    }
+}
+
+/// A [`Recorder`] that adds the `chain` label of [`with_chain_label`] to the registered metrics.
+#[derive(Debug)]
+struct ChainLabelRecorder<R>(R);
+
+impl<R> ChainLabelRecorder<R> {
+    /// Returns the key with the `chain` label added, if it's set.
+    fn labeled(key: &Key) -> Key {
+        CHAIN_LABEL
+            .try_with(|chain| key.with_extra_labels(vec![Label::new("chain", chain.clone())]))
+            .unwrap_or_else(|_| key.clone())
+    }
+}
+
+impl<R: Recorder> Recorder for ChainLabelRecorder<R> {
+    fn describe_counter(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.0.describe_counter(key, unit, description)
+    }
+
+    fn describe_gauge(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.0.describe_gauge(key, unit, description)
+    }
+
+    fn describe_histogram(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.0.describe_histogram(key, unit, description)
+    }
+
+    fn register_counter(&self, key: &Key, metadata: &Metadata<'_>) -> Counter {
+        self.0.register_counter(&Self::labeled(key), metadata)
+    }
+
+    fn register_gauge(&self, key: &Key, metadata: &Metadata<'_>) -> Gauge {
+        self.0.register_gauge(&Self::labeled(key), metadata)
+    }
+
+    fn register_histogram(&self, key: &Key, metadata: &Metadata<'_>) -> Histogram {
+        self.0.register_histogram(&Self::labeled(key), metadata)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,4 +199,48 @@ mod tests {
         let metrics = PROMETHEUS_RECORDER_HANDLE.handle.render();
         assert!(metrics.contains("process_cpu_seconds_total"), "{metrics:?}");
     }
+
+    #[test]
+    fn chain_label() {
+        // initialize the lazy handle
+        let _ = &*PROMETHEUS_RECORDER_HANDLE;
+
+        metrics::counter!("chain_label_test").increment(1);
+        sync_with_chain_label("sepolia", || metrics::counter!("chain_label_test").increment(2));
+
+        let metrics = PROMETHEUS_RECORDER_HANDLE.handle.render();
+        assert!(metrics.contains("reth_chain_label_test 1"), "{metrics:?}");
+        assert!(metrics.contains("reth_chain_label_test{chain=\"sepolia\"} 2"), "{metrics:?}");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_label_of_spawned_tasks() {
+        // initialize the lazy handle
+        let _ = &*PROMETHEUS_RECORDER_HANDLE;
+
+        let manager = reth_tasks::TaskManager::current();
+        let executor = chain_label_executor(manager.executor(), "holesky");
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let (blocking_tx, blocking_rx) = tokio::sync::oneshot::channel();
+        executor.spawn(async move {
+            metrics::counter!("chain_label_spawned_test").increment(3);
+            let _ = tx.send(());
+        });
+        executor.spawn_blocking(async move {
+            metrics::counter!("chain_label_spawned_blocking_test").increment(4);
+            let _ = blocking_tx.send(());
+        });
+        rx.await.unwrap();
+        blocking_rx.await.unwrap();
+
+        let metrics = PROMETHEUS_RECORDER_HANDLE.handle.render();
+        assert!(
+            metrics.contains("reth_chain_label_spawned_test{chain=\"holesky\"} 3"),
+            "{metrics:?}"
+        );
+        assert!(
+            metrics.contains("reth_chain_label_spawned_blocking_test{chain=\"holesky\"} 4"),
+            "{metrics:?}"
+        );
+    }
 }
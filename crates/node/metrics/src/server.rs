@@ -131,6 +131,30 @@ fn describe_db_metrics() {
     describe_gauge!("db.table_size", Unit::Bytes, "The size of a database table (in bytes)");
     describe_gauge!("db.table_pages", "The number of database pages for a table");
     describe_gauge!("db.table_entries", "The number of entries for a table");
+    describe_gauge!(
+        "db.table_growth_rate",
+        "The growth of a database table over the last day (in bytes per day)"
+    );
+    describe_gauge!("db.growth_rate", "The growth of all database tables (in bytes per day)");
+    describe_gauge!(
+        "db.static_files_growth_rate",
+        "The growth of the static files over the last day (in bytes per day)"
+    );
+    describe_gauge!(
+        "db.disk_available_space",
+        Unit::Bytes,
+        "The available space of the disk the database is on (in bytes)"
+    );
+    describe_gauge!(
+        "db.disk_total_space",
+        Unit::Bytes,
+        "The total space of the disk the database is on (in bytes)"
+    );
+    describe_gauge!(
+        "db.days_until_disk_full",
+        "The number of days until the disk is full at the current growth rate of the database and \
+         static files"
+    );
     describe_gauge!("db.freelist", "The number of pages on the freelist");
     describe_gauge!("db.page_size", Unit::Bytes, "The size of a database page (in bytes)");
     describe_gauge!(
@@ -30,8 +30,8 @@ pub use dev::OP_DEV;
 pub use op::OP_MAINNET;
 pub use op_sepolia::OP_SEPOLIA;
 use reth_chainspec::{
-    BaseFeeParams, BaseFeeParamsKind, ChainSpec, ChainSpecBuilder, DepositContract, EthChainSpec,
-    EthereumHardforks, ForkFilter, ForkId, Hardforks, Head,
+    BaseFeeParams, BaseFeeParamsKind, BlockLimits, ChainSpec, ChainSpecBuilder, DepositContract,
+    EthChainSpec, EthereumHardforks, ForkFilter, ForkId, Hardforks, Head,
 };
 use reth_ethereum_forks::{ChainHardforks, EthereumHardfork, ForkCondition, Hardfork};
 use reth_network_peers::NodeRecord;
@@ -249,6 +249,14 @@ impl EthChainSpec for OpChainSpec {
     fn final_paris_total_difficulty(&self) -> Option<U256> {
         self.inner.final_paris_total_difficulty()
     }
+
+    fn paris_block(&self) -> Option<u64> {
+        self.inner.paris_block()
+    }
+
+    fn block_limits(&self) -> BlockLimits {
+        self.inner.block_limits()
+    }
 }
 
 impl Hardforks for OpChainSpec {
@@ -364,6 +372,7 @@ impl From<Genesis> for OpChainSpec {
 
         let hardforks = ChainHardforks::new(ordered_hardforks);
         let genesis_header = SealedHeader::seal_slow(make_op_genesis_header(&genesis, &hardforks));
+        let block_limits = BlockLimits::from_genesis(&genesis);
 
         Self {
             inner: ChainSpec {
@@ -375,6 +384,7 @@ impl From<Genesis> for OpChainSpec {
                 // zero
                 paris_block_and_final_difficulty: Some((0, U256::ZERO)),
                 base_fee_params: optimism_genesis_info.base_fee_params,
+                block_limits,
                 ..Default::default()
             },
         }
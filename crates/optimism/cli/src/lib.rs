@@ -41,15 +41,15 @@ use commands::Commands;
 use futures_util::Future;
 use reth_chainspec::EthChainSpec;
 use reth_cli::chainspec::ChainSpecParser;
-use reth_cli_commands::node::NoArgs;
-use reth_cli_runner::CliRunner;
+use reth_cli_commands::node::{AdditionalChainLauncher, NoArgs, NodeCommand};
+use reth_cli_runner::{CliContext, CliRunner};
 use reth_db::DatabaseEnv;
 use reth_node_builder::{NodeBuilder, WithLaunchContext};
 use reth_node_core::{
     args::LogArgs,
     version::{LONG_VERSION, SHORT_VERSION},
 };
-use reth_optimism_consensus::OpBeaconConsensus;
+use reth_optimism_consensus::{calculate_receipt_root_no_memo_optimism, OpBeaconConsensus};
 use reth_optimism_evm::OpExecutorProvider;
 use reth_optimism_node::{OpNetworkPrimitives, OpNode};
 use reth_tracing::FileWorkerGuard;
@@ -137,11 +137,40 @@ where
         self.with_runner(CliRunner::try_default_runtime()?, launcher)
     }
 
+    /// Execute the configured cli command, and run the chains of `--chain.additional` next to the
+    /// rollup node, e.g. its L1.
+    ///
+    /// The rollup node is launched with the `launcher` closure, like with [`Cli::run`]. Every
+    /// additional chain is launched by the first of the `additional` launchers that supports it.
+    pub fn run_multi<L, Fut>(
+        self,
+        launcher: L,
+        additional: Vec<AdditionalChainLauncher>,
+    ) -> eyre::Result<()>
+    where
+        L: FnOnce(WithLaunchContext<NodeBuilder<Arc<DatabaseEnv>, C::ChainSpec>>, Ext) -> Fut,
+        Fut: Future<Output = eyre::Result<()>>,
+    {
+        self.execute_with_runner(CliRunner::try_default_runtime()?, |command, ctx| {
+            command.execute_multi(ctx, launcher, additional)
+        })
+    }
+
     /// Execute the configured cli command with the provided [`CliRunner`].
-    pub fn with_runner<L, Fut>(mut self, runner: CliRunner, launcher: L) -> eyre::Result<()>
+    pub fn with_runner<L, Fut>(self, runner: CliRunner, launcher: L) -> eyre::Result<()>
     where
         L: FnOnce(WithLaunchContext<NodeBuilder<Arc<DatabaseEnv>, C::ChainSpec>>, Ext) -> Fut,
         Fut: Future<Output = eyre::Result<()>>,
+    {
+        self.execute_with_runner(runner, |command, ctx| command.execute(ctx, launcher))
+    }
+
+    /// Execute the configured cli command with the provided [`CliRunner`], and execute the
+    /// [`NodeCommand`](reth_cli_commands::node::NodeCommand) with the given closure.
+    fn execute_with_runner<F, Fut>(mut self, runner: CliRunner, execute_node: F) -> eyre::Result<()>
+    where
+        F: FnOnce(NodeCommand<C, Ext>, CliContext) -> Fut,
+        Fut: Future<Output = eyre::Result<()>>,
     {
         // add network name to logs dir
         self.logs.log_file_directory =
@@ -154,8 +183,11 @@ where
         let _ = install_prometheus_recorder();
 
         match self.command {
+            Commands::Node(command) if command.is_rollback() => {
+                runner.run_blocking_until_ctrl_c(command.rollback::<OpNode>())
+            }
             Commands::Node(command) => {
-                runner.run_command_until_exit(|ctx| command.execute(ctx, launcher))
+                runner.run_command_until_exit(|ctx| execute_node(*command, ctx))
             }
             Commands::Init(command) => {
                 runner.run_blocking_until_ctrl_c(command.execute::<OpNode>())
@@ -172,9 +204,15 @@ where
             Commands::DumpGenesis(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Db(command) => runner.run_blocking_until_ctrl_c(command.execute::<OpNode>()),
             Commands::Stage(command) => runner.run_command_until_exit(|ctx| {
-                command.execute::<OpNode, _, _, OpNetworkPrimitives>(ctx, |spec| {
-                    (OpExecutorProvider::optimism(spec.clone()), OpBeaconConsensus::new(spec))
-                })
+                command.execute::<OpNode, _, _, OpNetworkPrimitives, _>(
+                    ctx,
+                    |spec| {
+                        (OpExecutorProvider::optimism(spec.clone()), OpBeaconConsensus::new(spec))
+                    },
+                    |spec, header, receipts| {
+                        calculate_receipt_root_no_memo_optimism(receipts, spec, header.timestamp)
+                    },
+                )
             }),
             Commands::P2P(command) => {
                 runner.run_until_ctrl_c(command.execute::<OpNetworkPrimitives>())
@@ -184,6 +222,9 @@ where
                 runner.run_command_until_exit(|ctx| command.execute::<OpNode>(ctx))
             }
             Commands::Prune(command) => runner.run_until_ctrl_c(command.execute::<OpNode>()),
+            Commands::Trie(command) => {
+                runner.run_blocking_until_ctrl_c(command.execute::<OpNode>())
+            }
             #[cfg(feature = "dev")]
             Commands::TestVectors(command) => runner.run_until_ctrl_c(command.execute()),
         }
@@ -18,7 +18,7 @@ use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_consensus::{Consensus, ConsensusError, FullConsensus, HeaderValidator};
 use reth_consensus_common::validation::{
     validate_against_parent_4844, validate_against_parent_eip1559_base_fee,
-    validate_against_parent_hash_number, validate_against_parent_timestamp,
+    validate_against_parent_hash_number, validate_against_parent_timestamp, validate_block_limits,
     validate_body_against_header, validate_cancun_gas, validate_header_base_fee,
     validate_header_extra_data, validate_header_gas,
 };
@@ -84,6 +84,8 @@ impl<ChainSpec: EthChainSpec + OpHardforks, B: Block> Consensus<B>
     }
 
     fn validate_block_pre_execution(&self, block: &SealedBlock<B>) -> Result<(), ConsensusError> {
+        validate_block_limits(block, &self.chain_spec)?;
+
         // Check ommers hash
         let ommers_hash = block.body().calculate_ommers_root();
         if Some(block.ommers_hash()) != ommers_hash {
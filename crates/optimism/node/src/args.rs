@@ -2,6 +2,8 @@
 
 //! clap [Args](clap::Args) for optimism rollup configuration
 
+use reth_optimism_rpc::sequencer::DEFAULT_SEQUENCER_RETRIES;
+
 /// Parameters for rollup configuration
 #[derive(Debug, Clone, PartialEq, Eq, clap::Args)]
 #[command(next_help_heading = "Rollup")]
@@ -10,6 +12,21 @@ pub struct RollupArgs {
     #[arg(long = "rollup.sequencer-http", value_name = "HTTP_URL")]
     pub sequencer_http: Option<String>,
 
+    /// Fallback HTTP endpoints for the sequencer mempool, in order of priority.
+    ///
+    /// Transactions are forwarded to the fallbacks if the sequencer endpoint is unavailable.
+    #[arg(
+        long = "rollup.sequencer-http-fallback",
+        value_name = "HTTP_URL",
+        value_delimiter = ',',
+        requires = "sequencer_http"
+    )]
+    pub sequencer_http_fallbacks: Vec<String>,
+
+    /// How often forwarding a transaction to all sequencer endpoints is retried
+    #[arg(long = "rollup.sequencer-retries", default_value_t = DEFAULT_SEQUENCER_RETRIES)]
+    pub sequencer_retries: usize,
+
     /// Disable transaction pool gossip
     #[arg(long = "rollup.disable-tx-pool-gossip")]
     pub disable_txpool_gossip: bool,
@@ -44,6 +61,8 @@ impl Default for RollupArgs {
     fn default() -> Self {
         Self {
             sequencer_http: None,
+            sequencer_http_fallbacks: Vec::new(),
+            sequencer_retries: DEFAULT_SEQUENCER_RETRIES,
             disable_txpool_gossip: false,
             enable_genesis_walkback: false,
             compute_pending_block: false,
@@ -72,6 +91,23 @@ mod tests {
         assert_eq!(args, default_args);
     }
 
+    #[test]
+    fn test_parse_optimism_sequencer_fallback_args() {
+        let args = CommandParser::<RollupArgs>::parse_from([
+            "reth",
+            "--rollup.sequencer-http",
+            "http://sequencer-1:8545",
+            "--rollup.sequencer-http-fallback",
+            "http://sequencer-2:8545,http://sequencer-3:8545",
+        ])
+        .args;
+        assert_eq!(args.sequencer_http.as_deref(), Some("http://sequencer-1:8545"));
+        assert_eq!(
+            args.sequencer_http_fallbacks,
+            ["http://sequencer-2:8545", "http://sequencer-3:8545"]
+        );
+    }
+
     #[test]
     fn test_parse_optimism_walkback_args() {
         let expected_args = RollupArgs { enable_genesis_walkback: true, ..Default::default() };
@@ -20,8 +20,8 @@ use reth_node_builder::{
     },
     node::{FullNodeTypes, NodeTypes, NodeTypesWithEngine},
     rpc::{
-        EngineValidatorAddOn, EngineValidatorBuilder, EthApiBuilder, RethRpcAddOns, RpcAddOns,
-        RpcHandle,
+        EngineValidatorAddOn, EngineValidatorBuilder, EthApiBuilder,
+        PayloadAttributesValidatorBuilder, RethRpcAddOns, RpcAddOns, RpcHandle,
     },
     BuilderContext, DebugNode, Node, NodeAdapter, NodeComponentsBuilder,
 };
@@ -37,6 +37,7 @@ use reth_optimism_primitives::{DepositReceipt, OpPrimitives, OpReceipt, OpTransa
 use reth_optimism_rpc::{
     eth::{ext::OpEthExtApi, OpEthApiBuilder},
     miner::{MinerApiExtServer, OpMinerExtApi},
+    sequencer::DEFAULT_SEQUENCER_HEALTH_CHECK_INTERVAL,
     witness::{DebugExecutionWitnessApiServer, OpDebugWitnessApi},
     OpEthApi, OpEthApiError, SequencerClient,
 };
@@ -48,8 +49,8 @@ use reth_rpc_eth_types::error::FromEvmError;
 use reth_rpc_server_types::RethRpcModule;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, CoinbaseTipOrdering, EthPoolTransaction, PoolTransaction,
-    TransactionPool, TransactionValidationTaskExecutor,
+    blobstore::DiskFileBlobStore, validate::TargetDenylist, CoinbaseTipOrdering,
+    EthPoolTransaction, PoolTransaction, TransactionPool, TransactionValidationTaskExecutor,
 };
 use reth_trie_db::MerklePatriciaTrie;
 use revm::context::TxEnv;
@@ -189,6 +190,8 @@ where
     fn add_ons(&self) -> Self::AddOns {
         Self::AddOns::builder()
             .with_sequencer(self.args.sequencer_http.clone())
+            .with_sequencer_fallbacks(self.args.sequencer_http_fallbacks.clone())
+            .with_sequencer_retries(self.args.sequencer_retries)
             .with_da_config(self.da_config.clone())
             .with_enable_tx_conditional(self.args.enable_tx_conditional)
             .build()
@@ -226,19 +229,14 @@ impl NodeTypesWithEngine for OpNode {
 
 /// Add-ons w.r.t. optimism.
 #[derive(Debug)]
-pub struct OpAddOns<N>
+pub struct OpAddOns<N, EV = OpEngineValidatorBuilder>
 where
     N: FullNodeComponents,
     OpEthApiBuilder: EthApiBuilder<N>,
 {
     /// Rpc add-ons responsible for launching the RPC servers and instantiating the RPC handlers
     /// and eth-api.
-    pub rpc_add_ons: RpcAddOns<
-        N,
-        OpEthApiBuilder,
-        OpEngineValidatorBuilder,
-        OpEngineApiBuilder<OpEngineValidatorBuilder>,
-    >,
+    pub rpc_add_ons: RpcAddOns<N, OpEthApiBuilder, EV, OpEngineApiBuilder<EV>>,
     /// Data availability configuration for the OP builder.
     pub da_config: OpDAConfig,
     /// Sequencer client, configured to forward submitted transactions to sequencer of given OP
@@ -269,7 +267,34 @@ where
     }
 }
 
-impl<N> NodeAddOns<N> for OpAddOns<N>
+impl<N, EV> OpAddOns<N, EV>
+where
+    N: FullNodeComponents,
+    OpEthApiBuilder: EthApiBuilder<N>,
+    EV: Clone,
+{
+    /// Adds a custom validation of the payload attributes, e.g. of the L2 fields, which the engine
+    /// API runs before a payload job is started.
+    ///
+    /// See [`RpcAddOns::with_payload_attributes_validator`].
+    pub fn with_payload_attributes_validator<A>(
+        self,
+        attributes_validator: A,
+    ) -> OpAddOns<N, PayloadAttributesValidatorBuilder<EV, A>>
+    where
+        A: Clone,
+    {
+        let Self { rpc_add_ons, da_config, sequencer_client, enable_tx_conditional } = self;
+        OpAddOns {
+            rpc_add_ons: rpc_add_ons.with_payload_attributes_validator(attributes_validator),
+            da_config,
+            sequencer_client,
+            enable_tx_conditional,
+        }
+    }
+}
+
+impl<N, EV> NodeAddOns<N> for OpAddOns<N, EV>
 where
     N: FullNodeComponents<
         Types: NodeTypesWithEngine<
@@ -283,6 +308,7 @@ where
     OpEthApiError: FromEvmError<N::Evm>,
     <N::Pool as TransactionPool>::Transaction: OpPooledTx,
     EvmFactoryFor<N::Evm>: EvmFactory<Tx = op_revm::OpTransaction<TxEnv>>,
+    EV: EngineValidatorBuilder<N>,
 {
     type Handle = RpcHandle<N, OpEthApi<N>>;
 
@@ -305,6 +331,12 @@ where
         );
         let miner_ext = OpMinerExtApi::new(da_config);
 
+        if let Some(sequencer_client) = sequencer_client.clone() {
+            ctx.node
+                .task_executor()
+                .spawn(sequencer_client.run_health_checks(DEFAULT_SEQUENCER_HEALTH_CHECK_INTERVAL));
+        }
+
         let tx_conditional_ext: OpEthExtApi<N::Pool, N::Provider> = OpEthExtApi::new(
             sequencer_client,
             ctx.node.pool().clone(),
@@ -348,7 +380,7 @@ where
     }
 }
 
-impl<N> RethRpcAddOns<N> for OpAddOns<N>
+impl<N, EV> RethRpcAddOns<N> for OpAddOns<N, EV>
 where
     N: FullNodeComponents<
         Types: NodeTypesWithEngine<
@@ -362,6 +394,7 @@ where
     OpEthApiError: FromEvmError<N::Evm>,
     <<N as FullNodeComponents>::Pool as TransactionPool>::Transaction: OpPooledTx,
     EvmFactoryFor<N::Evm>: EvmFactory<Tx = op_revm::OpTransaction<TxEnv>>,
+    EV: EngineValidatorBuilder<N>,
 {
     type EthApi = OpEthApi<N>;
 
@@ -370,7 +403,7 @@ where
     }
 }
 
-impl<N> EngineValidatorAddOn<N> for OpAddOns<N>
+impl<N, EV> EngineValidatorAddOn<N> for OpAddOns<N, EV>
 where
     N: FullNodeComponents<
         Types: NodeTypesWithEngine<
@@ -380,11 +413,12 @@ where
         >,
     >,
     OpEthApiBuilder: EthApiBuilder<N>,
+    EV: EngineValidatorBuilder<N>,
 {
-    type Validator = OpEngineValidator<N::Provider>;
+    type Validator = EV::Validator;
 
     async fn engine_validator(&self, ctx: &AddOnsContext<'_, N>) -> eyre::Result<Self::Validator> {
-        OpEngineValidatorBuilder::default().build(ctx).await
+        self.rpc_add_ons.engine_validator(ctx).await
     }
 }
 
@@ -392,9 +426,13 @@ where
 #[derive(Debug, Default, Clone)]
 #[non_exhaustive]
 pub struct OpAddOnsBuilder {
-    /// Sequencer client, configured to forward submitted transactions to sequencer of given OP
+    /// Sequencer endpoint, configured to forward submitted transactions to sequencer of given OP
     /// network.
-    sequencer_client: Option<SequencerClient>,
+    sequencer_url: Option<String>,
+    /// Fallback sequencer endpoints, in order of priority.
+    sequencer_fallbacks: Vec<String>,
+    /// How often forwarding to all sequencer endpoints is retried.
+    sequencer_retries: Option<usize>,
     /// Data availability configuration for the OP builder.
     da_config: Option<OpDAConfig>,
     /// Enable transaction conditionals.
@@ -404,7 +442,19 @@ pub struct OpAddOnsBuilder {
 impl OpAddOnsBuilder {
     /// With a [`SequencerClient`].
     pub fn with_sequencer(mut self, sequencer_client: Option<String>) -> Self {
-        self.sequencer_client = sequencer_client.map(SequencerClient::new);
+        self.sequencer_url = sequencer_client;
+        self
+    }
+
+    /// With fallback endpoints of the [`SequencerClient`], in order of priority.
+    pub fn with_sequencer_fallbacks(mut self, sequencer_fallbacks: Vec<String>) -> Self {
+        self.sequencer_fallbacks = sequencer_fallbacks;
+        self
+    }
+
+    /// Configure how often the [`SequencerClient`] retries forwarding to all endpoints.
+    pub const fn with_sequencer_retries(mut self, sequencer_retries: usize) -> Self {
+        self.sequencer_retries = Some(sequencer_retries);
         self
     }
 
@@ -428,8 +478,23 @@ impl OpAddOnsBuilder {
         N: FullNodeComponents<Types: NodeTypes<Primitives = OpPrimitives>>,
         OpEthApiBuilder: EthApiBuilder<N>,
     {
-        let Self { sequencer_client, da_config, enable_tx_conditional } = self;
+        let Self {
+            sequencer_url,
+            sequencer_fallbacks,
+            sequencer_retries,
+            da_config,
+            enable_tx_conditional,
+        } = self;
 
+        let sequencer_client = sequencer_url.map(|url| {
+            let client = SequencerClient::new_with_endpoints(
+                std::iter::once(url).chain(sequencer_fallbacks),
+            );
+            match sequencer_retries {
+                Some(retries) => client.with_max_retries(retries),
+                None => client,
+            }
+        });
         let sequencer_client_clone = sequencer_client.clone();
         OpAddOns {
             rpc_add_ons: RpcAddOns::new(
@@ -520,7 +585,7 @@ where
         let data_dir = ctx.config().datadir();
         let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
 
-        let validator = TransactionValidationTaskExecutor::eth_builder(ctx.provider().clone())
+        let mut validator = TransactionValidationTaskExecutor::eth_builder(ctx.provider().clone())
             .no_eip4844()
             .with_head_timestamp(ctx.head().timestamp)
             .kzg_settings(ctx.kzg_settings()?)
@@ -528,7 +593,13 @@ where
                 pool_config_overrides
                     .additional_validation_tasks
                     .unwrap_or_else(|| ctx.config().txpool.additional_validation_tasks),
-            )
+            );
+        if let Some(path) = &ctx.config().txpool.target_denylist {
+            let denylist = TargetDenylist::from_file(path)?;
+            info!(target: "reth::cli", path = %path.display(), "Loaded transaction target denylist");
+            validator = validator.with_target_denylist(Arc::new(denylist));
+        }
+        let validator = validator
             .build_with_tasks(ctx.task_executor().clone(), blob_store.clone())
             .map(|validator| {
                 OpTransactionValidator::new(validator)
@@ -574,7 +645,7 @@ where
                     chain_events,
                     ctx.task_executor().clone(),
                     reth_transaction_pool::maintain::MaintainPoolConfig {
-                        max_tx_lifetime: pool.config().max_queued_lifetime,
+                        max_tx_lifetime: pool.config().min_lifetime(),
                         ..Default::default()
                     },
                 ),
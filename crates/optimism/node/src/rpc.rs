@@ -9,7 +9,9 @@ use reth_chainspec::EthereumHardforks;
 use reth_node_api::{
     AddOnsContext, EngineTypes, FullNodeComponents, NodeTypes, NodeTypesWithEngine,
 };
-use reth_node_builder::rpc::{EngineApiBuilder, EngineValidatorBuilder};
+use reth_node_builder::rpc::{
+    EngineApiBuilder, EngineValidatorBuilder, WithEngineValidatorBuilder,
+};
 use reth_node_core::version::{CARGO_PKG_VERSION, CLIENT_CODE, VERGEN_GIT_SHA};
 use reth_optimism_rpc::engine::OP_ENGINE_CAPABILITIES;
 use reth_payload_builder::PayloadStore;
@@ -21,6 +23,14 @@ pub struct OpEngineApiBuilder<EV> {
     engine_validator_builder: EV,
 }
 
+impl<EV> WithEngineValidatorBuilder for OpEngineApiBuilder<EV> {
+    type Output<T> = OpEngineApiBuilder<T>;
+
+    fn with_engine_validator_builder<T>(self, engine_validator_builder: T) -> Self::Output<T> {
+        OpEngineApiBuilder { engine_validator_builder }
+    }
+}
+
 impl<N, EV> EngineApiBuilder<N> for OpEngineApiBuilder<EV>
 where
     N: FullNodeComponents<
@@ -57,7 +67,8 @@ where
             ctx.node.pool().clone(),
             Box::new(ctx.node.task_executor().clone()),
             client,
-            EngineCapabilities::new(OP_ENGINE_CAPABILITIES.iter().copied()),
+            EngineCapabilities::new(OP_ENGINE_CAPABILITIES.iter().copied())
+                .with_exchanged_capabilities(ctx.exchanged_capabilities.clone()),
             engine_validator,
             ctx.config.engine.accept_execution_requests_hash,
         );
@@ -6,16 +6,18 @@ use crate::{
     payload::{OpBuiltPayload, OpPayloadBuilderAttributes},
     OpPayloadPrimitives,
 };
-use alloy_consensus::{Transaction, Typed2718};
+use alloy_consensus::{conditional::BlockConditionalAttributes, Transaction, Typed2718};
 use alloy_primitives::{Bytes, B256, U256};
 use alloy_rlp::Encodable;
 use alloy_rpc_types_debug::ExecutionWitness;
 use alloy_rpc_types_engine::PayloadId;
+use alloy_rpc_types_eth::erc4337::{AccountStorage, TransactionConditional};
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
 use reth_basic_payload_builder::*;
 use reth_chain_state::{ExecutedBlock, ExecutedBlockWithTrieUpdates};
 use reth_chainspec::{ChainSpecProvider, EthChainSpec};
 use reth_evm::{
+    block::BlockExecutorFor,
     execute::{
         BlockBuilder, BlockBuilderOutcome, BlockExecutionError, BlockExecutor, BlockValidationError,
     },
@@ -36,6 +38,7 @@ use reth_revm::{
 };
 use reth_storage_api::{errors::ProviderError, StateProvider, StateProviderFactory};
 use reth_transaction_pool::{BestTransactionsAttributes, PoolTransaction, TransactionPool};
+use reth_trie_common::HashedStorage;
 use revm::context::{Block, BlockEnv};
 use std::sync::Arc;
 use tracing::{debug, trace, warn};
@@ -141,7 +144,7 @@ where
         best: impl FnOnce(BestTransactionsAttributes) -> Txs + Send + Sync + 'a,
     ) -> Result<BuildOutcome<OpBuiltPayload<N>>, PayloadBuilderError>
     where
-        Txs: PayloadTransactions<Transaction: PoolTransaction<Consensus = N::SignedTx>>,
+        Txs: PayloadTransactions<Transaction: OpPooledTx<Consensus = N::SignedTx>>,
     {
         let BuildArguments { mut cached_reads, config, cancel, best_payload } = args;
 
@@ -282,7 +285,7 @@ impl<Txs> OpBuilder<'_, Txs> {
         EvmConfig: ConfigureEvm<Primitives = N, NextBlockEnvCtx = OpNextBlockEnvAttributes>,
         ChainSpec: EthChainSpec + OpHardforks,
         N: OpPayloadPrimitives,
-        Txs: PayloadTransactions<Transaction: PoolTransaction<Consensus = N::SignedTx>>,
+        Txs: PayloadTransactions<Transaction: OpPooledTx<Consensus = N::SignedTx>>,
     {
         let Self { best } = self;
         debug!(target: "payload_builder", id=%ctx.payload_id(), parent_header = ?ctx.parent().hash(), parent_number = ctx.parent().number, "building new payload");
@@ -303,7 +306,10 @@ impl<Txs> OpBuilder<'_, Txs> {
         // 3. if mem pool transactions are requested we execute them
         if !ctx.attributes().no_tx_pool {
             let best_txs = best(ctx.best_transaction_attributes(builder.evm_mut().block()));
-            if ctx.execute_best_transactions(&mut info, &mut builder, best_txs)?.is_some() {
+            if ctx
+                .execute_best_transactions(&mut info, &mut builder, &state_provider, best_txs)?
+                .is_some()
+            {
                 return Ok(BuildOutcomeKind::Cancelled)
             }
 
@@ -424,12 +430,22 @@ pub struct ExecutionInfo {
     pub cumulative_da_bytes_used: u64,
     /// Tracks fees from executed mempool transactions
     pub total_fees: U256,
+    /// Number of transactions executed so far
+    pub transactions: usize,
+    /// RLP length of the transactions executed so far
+    pub transactions_rlp_length: usize,
 }
 
 impl ExecutionInfo {
     /// Create a new instance with allocated slots.
     pub fn new() -> Self {
-        Self { cumulative_gas_used: 0, cumulative_da_bytes_used: 0, total_fees: U256::ZERO }
+        Self {
+            cumulative_gas_used: 0,
+            cumulative_da_bytes_used: 0,
+            total_fees: U256::ZERO,
+            transactions: 0,
+            transactions_rlp_length: 0,
+        }
     }
 
     /// Returns true if the transaction would exceed the block limits:
@@ -535,21 +551,28 @@ where
     pub fn block_builder<'a, DB: Database>(
         &'a self,
         db: &'a mut State<DB>,
-    ) -> Result<impl BlockBuilder<Primitives = Evm::Primitives> + 'a, PayloadBuilderError> {
-        self.evm_config
-            .builder_for_next_block(
-                db,
-                self.parent(),
-                OpNextBlockEnvAttributes {
-                    timestamp: self.attributes().timestamp(),
-                    suggested_fee_recipient: self.attributes().suggested_fee_recipient(),
-                    prev_randao: self.attributes().prev_randao(),
-                    gas_limit: self.attributes().gas_limit.unwrap_or(self.parent().gas_limit),
-                    parent_beacon_block_root: self.attributes().parent_beacon_block_root(),
-                    extra_data: self.extra_data()?,
-                },
-            )
-            .map_err(PayloadBuilderError::other)
+    ) -> Result<
+        impl BlockBuilder<
+                Primitives = Evm::Primitives,
+                Executor: BlockExecutorFor<'a, Evm::BlockExecutorFactory, DB>,
+            > + 'a,
+        PayloadBuilderError,
+    > {
+        let attributes = OpNextBlockEnvAttributes {
+            timestamp: self.attributes().timestamp(),
+            suggested_fee_recipient: self.attributes().suggested_fee_recipient(),
+            prev_randao: self.attributes().prev_randao(),
+            gas_limit: self.attributes().gas_limit.unwrap_or(self.parent().gas_limit),
+            parent_beacon_block_root: self.attributes().parent_beacon_block_root(),
+            extra_data: self.extra_data()?,
+        };
+        let evm_env = self
+            .evm_config
+            .next_evm_env(self.parent(), &attributes)
+            .map_err(PayloadBuilderError::other)?;
+        let evm = self.evm_config.evm_with_env(db, evm_env);
+        let ctx = self.evm_config.context_for_next_block(self.parent(), attributes);
+        Ok(self.evm_config.create_block_builder(evm, self.parent(), ctx))
     }
 }
 
@@ -598,6 +621,8 @@ where
 
             // add gas used by the transaction to cumulative gas used, before creating the receipt
             info.cumulative_gas_used += gas_used;
+            info.transactions += 1;
+            info.transactions_rlp_length += sequencer_tx.length();
         }
 
         Ok(info)
@@ -605,21 +630,61 @@ where
 
     /// Executes the given best transactions and updates the execution info.
     ///
+    /// The conditionals of the transactions are checked against the state the payload is built
+    /// on, which is provided by the `state_provider` and the changes in the builder's state.
+    ///
     /// Returns `Ok(Some(())` if the job was cancelled.
-    pub fn execute_best_transactions(
+    pub fn execute_best_transactions<'a, DB>(
         &self,
         info: &mut ExecutionInfo,
-        builder: &mut impl BlockBuilder<Primitives = Evm::Primitives>,
+        builder: &mut impl BlockBuilder<
+            Primitives = Evm::Primitives,
+            Executor: BlockExecutor<Evm: reth_evm::Evm<DB = &'a mut State<DB>>>,
+        >,
+        state_provider: &impl StateProvider,
         mut best_txs: impl PayloadTransactions<
-            Transaction: PoolTransaction<Consensus = TxTy<Evm::Primitives>>,
+            Transaction: OpPooledTx<Consensus = TxTy<Evm::Primitives>>,
         >,
-    ) -> Result<Option<()>, PayloadBuilderError> {
+    ) -> Result<Option<()>, PayloadBuilderError>
+    where
+        DB: Database<Error = ProviderError> + 'a,
+    {
         let block_gas_limit = builder.evm_mut().block().gas_limit;
         let block_da_limit = self.da_config.max_da_block_size();
         let tx_da_limit = self.da_config.max_da_tx_size();
+        let block_limits = self.chain_spec.block_limits();
         let base_fee = builder.evm_mut().block().basefee;
+        let block_attr = BlockConditionalAttributes {
+            number: self.parent().number + 1,
+            timestamp: self.attributes().timestamp(),
+        };
 
         while let Some(tx) = best_txs.next(()) {
+            // skip transactions whose conditional doesn't allow them in this block, e.g. because
+            // it expired since the transaction was added to the pool
+            if !tx.matches_block_attributes(&block_attr) {
+                trace!(target: "payload_builder", tx = %tx.hash(), "skipping unmet conditional");
+                best_txs.mark_invalid(tx.sender(), tx.nonce());
+                continue
+            }
+
+            // skip transactions whose known accounts changed since the transaction was added to
+            // the pool, e.g. by a transaction executed before in this payload
+            if let Some(conditional) = tx.conditional() {
+                let db = builder.evm_mut().db_mut();
+                if !known_accounts_match(conditional, db, state_provider)
+                    .map_err(PayloadBuilderError::other)?
+                {
+                    trace!(
+                        target: "payload_builder",
+                        tx = %tx.hash(),
+                        "skipping unmet known accounts"
+                    );
+                    best_txs.mark_invalid(tx.sender(), tx.nonce());
+                    continue
+                }
+            }
+
             let tx = tx.into_consensus();
             if info.is_tx_over_limits(tx.inner(), block_gas_limit, tx_da_limit, block_da_limit) {
                 // we can't fit this transaction into the block, so we need to mark it as
@@ -629,6 +694,21 @@ where
                 continue
             }
 
+            // the block is full if it reached the configured maximum number of transactions
+            if block_limits.max_transactions.is_some_and(|max| info.transactions >= max) {
+                break
+            }
+
+            // skip transactions that would exceed the configured maximum block size
+            if !block_limits.fits_transaction(
+                info.transactions,
+                info.transactions_rlp_length,
+                tx.length(),
+            ) {
+                best_txs.mark_invalid(tx.signer(), tx.nonce());
+                continue
+            }
+
             // A sequencer's block should never contain blob or deposit transactions from the pool.
             if tx.is_eip4844() || tx.is_deposit() {
                 best_txs.mark_invalid(tx.signer(), tx.nonce());
@@ -667,6 +747,8 @@ where
             // receipt
             info.cumulative_gas_used += gas_used;
             info.cumulative_da_bytes_used += tx.length() as u64;
+            info.transactions += 1;
+            info.transactions_rlp_length += tx.length();
 
             // update add to total fees
             let miner_fee = tx
@@ -678,3 +760,109 @@ where
         Ok(None)
     }
 }
+
+/// Returns `true` if the storage of the known accounts of the conditional matches the state the
+/// payload is built on, including the changes of the transactions executed so far.
+fn known_accounts_match<DB>(
+    conditional: &TransactionConditional,
+    db: &mut State<DB>,
+    state_provider: &impl StateProvider,
+) -> Result<bool, ProviderError>
+where
+    DB: Database<Error = ProviderError>,
+{
+    for (address, storage) in &conditional.known_accounts {
+        // the storage of an account can only be read once the account is loaded
+        let account = db.load_cache_account(*address)?;
+        match storage {
+            AccountStorage::Slots(slots) => {
+                for (slot, expected_value) in slots {
+                    let value = db.storage(*address, U256::from_be_bytes(slot.0))?;
+                    if value != U256::from_be_bytes(expected_value.0) {
+                        return Ok(false)
+                    }
+                }
+            }
+            AccountStorage::RootHash(expected_root) => {
+                let storage = account.account.as_ref().map(|account| &account.storage);
+                let changes = HashedStorage::from_plain_storage(
+                    account.status,
+                    storage.into_iter().flatten(),
+                );
+                if state_provider.storage_root(*address, changes)? != *expected_root {
+                    return Ok(false)
+                }
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{map::HashMap, Address};
+    use reth_primitives_traits::Account;
+    use reth_revm::test_utils::StateProviderTest;
+    use reth_trie_common::root::storage_root_unhashed;
+    use revm::{
+        state::{Account as EvmAccount, AccountInfo, AccountStatus, EvmStorageSlot},
+        DatabaseCommit,
+    };
+
+    #[test]
+    fn test_known_accounts_match_build_state() {
+        let address = Address::random();
+        let slot = B256::with_last_byte(1);
+        let mut state_provider = StateProviderTest::default();
+        state_provider.insert_account(
+            address,
+            Account::default(),
+            None,
+            HashMap::from_iter([(slot, U256::from(1))]),
+        );
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase::new(&state_provider))
+            .with_bundle_update()
+            .build();
+
+        let known_slot = |value: u64| TransactionConditional {
+            known_accounts: HashMap::from_iter([(
+                address,
+                AccountStorage::Slots(HashMap::from_iter([(slot, B256::from(U256::from(value)))])),
+            )]),
+            ..Default::default()
+        };
+        let known_root = |value: u64| TransactionConditional {
+            known_accounts: HashMap::from_iter([(
+                address,
+                AccountStorage::RootHash(storage_root_unhashed([(slot, U256::from(value))])),
+            )]),
+            ..Default::default()
+        };
+
+        assert!(known_accounts_match(&known_slot(1), &mut db, &state_provider).unwrap());
+        assert!(known_accounts_match(&known_root(1), &mut db, &state_provider).unwrap());
+        assert!(!known_accounts_match(&known_slot(2), &mut db, &state_provider).unwrap());
+        assert!(!known_accounts_match(&known_root(2), &mut db, &state_provider).unwrap());
+
+        // a transaction executed before in the payload changes the slot
+        db.commit(HashMap::from_iter([(
+            address,
+            EvmAccount {
+                info: AccountInfo::default(),
+                storage: HashMap::from_iter([(
+                    U256::from_be_bytes(slot.0),
+                    EvmStorageSlot::new_changed(U256::from(1), U256::from(2)),
+                )]),
+                status: AccountStatus::Touched,
+            },
+        )]));
+
+        assert!(!known_accounts_match(&known_slot(1), &mut db, &state_provider).unwrap());
+        assert!(!known_accounts_match(&known_root(1), &mut db, &state_provider).unwrap());
+        assert!(known_accounts_match(&known_slot(2), &mut db, &state_provider).unwrap());
+        assert!(known_accounts_match(&known_root(2), &mut db, &state_provider).unwrap());
+    }
+}
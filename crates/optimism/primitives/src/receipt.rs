@@ -3,7 +3,7 @@ use alloy_consensus::{
     RlpEncodableReceipt, TxReceipt, Typed2718,
 };
 use alloy_primitives::{Bloom, Log};
-use alloy_rlp::{BufMut, Decodable, Header};
+use alloy_rlp::{BufMut, Decodable, Encodable, Header};
 use op_alloy_consensus::{OpDepositReceipt, OpTxType};
 use reth_primitives_traits::InMemorySize;
 
@@ -122,6 +122,92 @@ impl OpReceipt {
     }
 }
 
+impl OpReceipt {
+    /// Returns length of RLP-encoded receipt fields without the bloom and without an RLP header.
+    ///
+    /// Unlike the consensus encoding, this includes the transaction type as the first field.
+    pub fn rlp_encoded_fields_length_without_bloom(&self) -> usize {
+        let receipt = self.as_receipt();
+        let mut len = (self.tx_type() as u8).length() +
+            receipt.status.length() +
+            receipt.cumulative_gas_used.length() +
+            receipt.logs.length();
+        if let Self::Deposit(receipt) = self {
+            len += receipt.deposit_nonce.map_or(0, |nonce| nonce.length());
+            len += receipt.deposit_receipt_version.map_or(0, |version| version.length());
+        }
+        len
+    }
+
+    /// RLP-encodes receipt fields without the bloom and without an RLP header.
+    ///
+    /// Unlike the consensus encoding, this includes the transaction type as the first field.
+    pub fn rlp_encode_fields_without_bloom(&self, out: &mut dyn BufMut) {
+        let receipt = self.as_receipt();
+        (self.tx_type() as u8).encode(out);
+        receipt.status.encode(out);
+        receipt.cumulative_gas_used.encode(out);
+        receipt.logs.encode(out);
+        if let Self::Deposit(receipt) = self {
+            if let Some(nonce) = receipt.deposit_nonce {
+                nonce.encode(out);
+            }
+            if let Some(version) = receipt.deposit_receipt_version {
+                version.encode(out);
+            }
+        }
+    }
+
+    /// Returns RLP header for the encoding without the bloom.
+    pub fn rlp_header_inner_without_bloom(&self) -> Header {
+        Header { list: true, payload_length: self.rlp_encoded_fields_length_without_bloom() }
+    }
+
+    /// RLP-decodes the receipt without the bloom from the provided buffer.
+    pub fn rlp_decode_inner_without_bloom(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+
+        let remaining = buf.len();
+
+        let tx_type = OpTxType::decode(buf)?;
+        let status = Decodable::decode(buf)?;
+        let cumulative_gas_used = Decodable::decode(buf)?;
+        let logs = Decodable::decode(buf)?;
+
+        let mut deposit_nonce = None;
+        let mut deposit_receipt_version = None;
+        if tx_type == OpTxType::Deposit {
+            // the deposit fields are optional and only present if set
+            if remaining - buf.len() < header.payload_length {
+                deposit_nonce = Some(Decodable::decode(buf)?);
+            }
+            if remaining - buf.len() < header.payload_length {
+                deposit_receipt_version = Some(Decodable::decode(buf)?);
+            }
+        }
+
+        if buf.len() + header.payload_length != remaining {
+            return Err(alloy_rlp::Error::UnexpectedLength);
+        }
+
+        let receipt = Receipt { status, cumulative_gas_used, logs };
+        Ok(match tx_type {
+            OpTxType::Legacy => Self::Legacy(receipt),
+            OpTxType::Eip2930 => Self::Eip2930(receipt),
+            OpTxType::Eip1559 => Self::Eip1559(receipt),
+            OpTxType::Eip7702 => Self::Eip7702(receipt),
+            OpTxType::Deposit => Self::Deposit(OpDepositReceipt {
+                inner: receipt,
+                deposit_nonce,
+                deposit_receipt_version,
+            }),
+        })
+    }
+}
+
 impl Eip2718EncodableReceipt for OpReceipt {
     fn eip2718_encoded_length_with_bloom(&self, bloom: &Bloom) -> usize {
         !self.tx_type().is_legacy() as usize + self.rlp_header_inner(bloom).length_with_payload()
@@ -184,6 +270,25 @@ impl RlpDecodableReceipt for OpReceipt {
     }
 }
 
+/// The `eth/69` network encoding of a receipt, which omits the bloom:
+/// `[tx-type, status, cumulative-gas-used, logs, deposit-nonce?, deposit-receipt-version?]`.
+impl Encodable for OpReceipt {
+    fn encode(&self, out: &mut dyn BufMut) {
+        self.rlp_header_inner_without_bloom().encode(out);
+        self.rlp_encode_fields_without_bloom(out);
+    }
+
+    fn length(&self) -> usize {
+        self.rlp_header_inner_without_bloom().length_with_payload()
+    }
+}
+
+impl Decodable for OpReceipt {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Self::rlp_decode_inner_without_bloom(buf)
+    }
+}
+
 impl TxReceipt for OpReceipt {
     type Log = Log;
 
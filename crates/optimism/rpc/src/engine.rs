@@ -333,7 +333,9 @@ where
         Ok(self.inner.get_client_version_v1(client)?)
     }
 
-    async fn exchange_capabilities(&self, _capabilities: Vec<String>) -> RpcResult<Vec<String>> {
+    async fn exchange_capabilities(&self, capabilities: Vec<String>) -> RpcResult<Vec<String>> {
+        trace!(target: "rpc::engine", "Serving engine_exchangeCapabilities");
+        self.inner.record_consensus_capabilities(capabilities);
         Ok(self.inner.capabilities().list())
     }
 }
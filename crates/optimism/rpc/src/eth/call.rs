@@ -56,6 +56,16 @@ where
         self.inner.eth_api.max_simulate_blocks()
     }
 
+    #[inline]
+    fn access_list_delegations(&self) -> bool {
+        self.inner.eth_api.access_list_delegations()
+    }
+
+    #[inline]
+    fn pending_call_pool_txs(&self) -> bool {
+        self.inner.eth_api.pending_call_pool_txs()
+    }
+
     fn create_txn_env(
         &self,
         evm_env: &EvmEnv<SpecFor<Self::Evm>>,
@@ -30,7 +30,7 @@ use reth_rpc_eth_api::{
     },
     EthApiTypes, FromEvmError, FullEthApiServer, RpcNodeCore, RpcNodeCoreExt,
 };
-use reth_rpc_eth_types::{EthStateCache, FeeHistoryCache, GasPriceOracle};
+use reth_rpc_eth_types::{EthStateCache, FeeHistoryCache, GasPriceOracle, ProofTaskPool};
 use reth_tasks::{
     pool::{BlockingTaskGuard, BlockingTaskPool},
     TaskSpawner,
@@ -192,10 +192,20 @@ where
         self.inner.eth_api.blocking_task_pool()
     }
 
+    #[inline]
+    fn call_task_guard(&self) -> &BlockingTaskGuard {
+        self.inner.eth_api.call_task_guard()
+    }
+
     #[inline]
     fn tracing_task_guard(&self) -> &BlockingTaskGuard {
         self.inner.eth_api.blocking_task_guard()
     }
+
+    #[inline]
+    fn proof_task_pool(&self) -> &ProofTaskPool {
+        self.inner.eth_api.proof_task_pool()
+    }
 }
 
 impl<N> LoadFee for OpEthApi<N>
@@ -235,6 +245,11 @@ where
     fn max_proof_window(&self) -> u64 {
         self.inner.eth_api.eth_proof_window()
     }
+
+    #[inline]
+    fn max_proofs_accounts(&self) -> usize {
+        self.inner.eth_api.max_proofs_accounts()
+    }
 }
 
 impl<N> EthFees for OpEthApi<N>
@@ -337,9 +352,14 @@ where
         .task_spawner(ctx.components.task_executor().clone())
         .gas_cap(ctx.config.rpc_gas_cap.into())
         .max_simulate_blocks(ctx.config.rpc_max_simulate_blocks)
+        .access_list_delegations(ctx.config.access_list_delegations)
+        .pending_call_pool_txs(ctx.config.pending_call_pool_txs)
         .eth_proof_window(ctx.config.eth_proof_window)
+        .max_proofs_accounts(ctx.config.max_proofs_accounts)
         .fee_history_cache_config(ctx.config.fee_history_cache)
         .proof_permits(ctx.config.proof_permits)
+        .tracing_pool_threads(ctx.config.tracing_pool_threads)
+        .max_concurrent_calls(ctx.config.max_concurrent_calls)
         .build_inner();
 
         OpEthApi { inner: Arc::new(OpEthApiInner { eth_api, sequencer_client }) }
@@ -1,44 +1,123 @@
 //! Helpers for optimism specific RPC implementations.
 
-use std::sync::{
-    atomic::{self, AtomicUsize},
-    Arc,
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{self, AtomicBool, AtomicUsize},
+        Arc,
+    },
+    time::Duration,
 };
 
-use alloy_primitives::hex;
+use alloy_primitives::{hex, Bytes};
 use alloy_rpc_types_eth::erc4337::TransactionConditional;
+use parking_lot::Mutex;
 use reqwest::Client;
 use serde_json::{json, Value};
-use tracing::warn;
+use tracing::{debug, warn};
 
 use crate::SequencerClientError;
 
+/// The default number of times forwarding to all sequencer endpoints is retried.
+pub const DEFAULT_SEQUENCER_RETRIES: usize = 2;
+
+/// The default interval in which the health of the sequencer endpoints is checked.
+pub const DEFAULT_SEQUENCER_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The timeout for connecting to a sequencer endpoint.
+pub const DEFAULT_SEQUENCER_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The timeout for a request to a sequencer endpoint, after which the next endpoint is tried.
+pub const DEFAULT_SEQUENCER_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The maximum number of transactions kept for forwarding once a sequencer is reachable again.
+const MAX_UNFORWARDED_TRANSACTIONS: usize = 1024;
+
+/// The backoff between retries, multiplied by the attempt.
+const RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Returns the HTTP client used by default, which times out requests to an unresponsive
+/// sequencer endpoint, so the next endpoint is tried.
+fn default_http_client() -> Client {
+    Client::builder()
+        .use_rustls_tls()
+        .connect_timeout(DEFAULT_SEQUENCER_CONNECT_TIMEOUT)
+        .timeout(DEFAULT_SEQUENCER_REQUEST_TIMEOUT)
+        .build()
+        .unwrap()
+}
+
 /// A client to interact with a Sequencer
+///
+/// The client forwards requests to a prioritized list of sequencer endpoints. Endpoints that fail
+/// are marked unhealthy and only tried after the healthy ones, until a request or a health check
+/// succeeds again. Raw transactions that couldn't be forwarded to any endpoint are kept and
+/// forwarded by [`SequencerClient::run_health_checks`] once a sequencer is reachable again.
 #[derive(Debug, Clone)]
 pub struct SequencerClient {
     inner: Arc<SequencerClientInner>,
+    /// How often forwarding to all endpoints is retried.
+    max_retries: usize,
 }
 
 impl SequencerClient {
     /// Creates a new [`SequencerClient`].
     pub fn new(sequencer_endpoint: impl Into<String>) -> Self {
-        let client = Client::builder().use_rustls_tls().build().unwrap();
-        Self::with_client(sequencer_endpoint, client)
+        Self::with_client(sequencer_endpoint, default_http_client())
+    }
+
+    /// Creates a new [`SequencerClient`] for the given endpoints, in order of priority.
+    ///
+    /// # Panics
+    ///
+    /// If no endpoint is given.
+    pub fn new_with_endpoints(
+        sequencer_endpoints: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self::with_endpoints(sequencer_endpoints, default_http_client())
     }
 
     /// Creates a new [`SequencerClient`].
     pub fn with_client(sequencer_endpoint: impl Into<String>, http_client: Client) -> Self {
+        Self::with_endpoints([sequencer_endpoint.into()], http_client)
+    }
+
+    /// Creates a new [`SequencerClient`] for the given endpoints, in order of priority.
+    ///
+    /// # Panics
+    ///
+    /// If no endpoint is given.
+    pub fn with_endpoints(
+        sequencer_endpoints: impl IntoIterator<Item = impl Into<String>>,
+        http_client: Client,
+    ) -> Self {
+        let endpoints: Vec<_> =
+            sequencer_endpoints.into_iter().map(|url| SequencerEndpoint::new(url.into())).collect();
+        assert!(!endpoints.is_empty(), "at least one sequencer endpoint is required");
+
         let inner = SequencerClientInner {
-            sequencer_endpoint: sequencer_endpoint.into(),
+            endpoints,
             http_client,
             id: AtomicUsize::new(0),
+            unforwarded: Default::default(),
         };
-        Self { inner: Arc::new(inner) }
+        Self { inner: Arc::new(inner), max_retries: DEFAULT_SEQUENCER_RETRIES }
     }
 
-    /// Returns the network of the client
+    /// Sets how often forwarding to all endpoints is retried.
+    pub const fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Returns the endpoint of the sequencer with the highest priority.
     pub fn endpoint(&self) -> &str {
-        &self.inner.sequencer_endpoint
+        &self.inner.endpoints[0].url
+    }
+
+    /// Returns all endpoints of the sequencer, in order of priority.
+    pub fn endpoints(&self) -> impl Iterator<Item = &str> {
+        self.inner.endpoints.iter().map(|endpoint| endpoint.url.as_str())
     }
 
     /// Returns the client
@@ -46,6 +125,11 @@ impl SequencerClient {
         &self.inner.http_client
     }
 
+    /// Returns the number of raw transactions waiting to be forwarded.
+    pub fn unforwarded_transactions(&self) -> usize {
+        self.inner.unforwarded.lock().len()
+    }
+
     /// Returns the next id for the request
     fn next_request_id(&self) -> usize {
         self.inner.id.fetch_add(1, atomic::Ordering::SeqCst)
@@ -63,18 +147,60 @@ impl SequencerClient {
         serde_json::to_string(&request)
     }
 
-    /// Sends a POST request to the sequencer endpoint.
-    async fn post_request(&self, body: String) -> Result<(), reqwest::Error> {
+    /// Returns the endpoints in the order they are tried: the healthy ones first, then the
+    /// unhealthy ones, each in order of priority.
+    fn endpoints_by_priority(&self) -> Vec<&SequencerEndpoint> {
+        let (healthy, unhealthy): (Vec<_>, Vec<_>) =
+            self.inner.endpoints.iter().partition(|endpoint| endpoint.is_healthy());
+        healthy.into_iter().chain(unhealthy).collect()
+    }
+
+    /// Sends a POST request to the given endpoint.
+    async fn post_to(
+        &self,
+        endpoint: &SequencerEndpoint,
+        body: String,
+    ) -> Result<(), reqwest::Error> {
         self.http_client()
-            .post(self.endpoint())
+            .post(&endpoint.url)
             .header(reqwest::header::CONTENT_TYPE, "application/json")
             .body(body)
             .send()
-            .await?;
+            .await?
+            .error_for_status()?;
         Ok(())
     }
 
+    /// Sends a POST request to the sequencer endpoints, failing over to the next endpoint and
+    /// retrying if none of them could be reached.
+    async fn post_request(&self, body: String) -> Result<(), reqwest::Error> {
+        let mut last_err = None;
+        for attempt in 0..=self.max_retries {
+            if attempt > 0 {
+                tokio::time::sleep(RETRY_BACKOFF * attempt as u32).await;
+            }
+
+            for endpoint in self.endpoints_by_priority() {
+                match self.post_to(endpoint, body.clone()).await {
+                    Ok(()) => {
+                        endpoint.set_healthy(true);
+                        return Ok(())
+                    }
+                    Err(err) => {
+                        debug!(target: "rpc::eth", %err, endpoint = %endpoint.url, attempt, "Failed to reach sequencer endpoint");
+                        endpoint.set_healthy(false);
+                        last_err = Some(err);
+                    }
+                }
+            }
+        }
+        Err(last_err.expect("at least one sequencer endpoint"))
+    }
+
     /// Forwards a transaction to the sequencer endpoint.
+    ///
+    /// If none of the endpoints could be reached, the transaction is kept and forwarded once a
+    /// sequencer is reachable again.
     pub async fn forward_raw_transaction(&self, tx: &[u8]) -> Result<(), SequencerClientError> {
         let body = self
             .request_body("eth_sendRawTransaction", json!([format!("0x{}", hex::encode(tx))]))
@@ -92,6 +218,7 @@ impl SequencerClient {
                 %err,
                 "Failed to forward transaction to sequencer",
             );
+            self.push_unforwarded(Bytes::copy_from_slice(tx));
         })?;
 
         Ok(())
@@ -122,16 +249,85 @@ impl SequencerClient {
         })?;
         Ok(())
     }
+
+    /// Keeps a raw transaction that couldn't be forwarded, evicting the oldest one if full.
+    fn push_unforwarded(&self, tx: Bytes) {
+        let mut unforwarded = self.inner.unforwarded.lock();
+        if unforwarded.len() >= MAX_UNFORWARDED_TRANSACTIONS {
+            unforwarded.pop_front();
+        }
+        unforwarded.push_back(tx);
+    }
+
+    /// Checks the health of all endpoints, returns `true` if any endpoint is healthy.
+    pub async fn check_health(&self) -> bool {
+        let mut any_healthy = false;
+        for endpoint in &self.inner.endpoints {
+            let Ok(body) = self.request_body("eth_chainId", json!([])) else { continue };
+            let healthy = self.post_to(endpoint, body).await.is_ok();
+            if healthy != endpoint.is_healthy() {
+                debug!(target: "rpc::eth", endpoint = %endpoint.url, healthy, "Sequencer endpoint health changed");
+            }
+            endpoint.set_healthy(healthy);
+            any_healthy |= healthy;
+        }
+        any_healthy
+    }
+
+    /// Periodically checks the health of all endpoints and forwards the transactions that
+    /// couldn't be forwarded once a sequencer is reachable again.
+    pub async fn run_health_checks(self, interval: Duration) {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+            if !self.check_health().await {
+                continue
+            }
+
+            let unforwarded = std::mem::take(&mut *self.inner.unforwarded.lock());
+            if !unforwarded.is_empty() {
+                debug!(target: "rpc::eth", count = unforwarded.len(), "Forwarding transactions to sequencer");
+            }
+            for tx in unforwarded {
+                let _ = self.forward_raw_transaction(&tx).await;
+            }
+        }
+    }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 struct SequencerClientInner {
-    /// The endpoint of the sequencer
-    sequencer_endpoint: String,
+    /// The endpoints of the sequencer, in order of priority
+    endpoints: Vec<SequencerEndpoint>,
     /// The HTTP client
     http_client: Client,
     /// Keeps track of unique request ids
     id: AtomicUsize,
+    /// Raw transactions that couldn't be forwarded to any endpoint
+    unforwarded: Mutex<VecDeque<Bytes>>,
+}
+
+/// An endpoint of the sequencer and its health.
+#[derive(Debug)]
+struct SequencerEndpoint {
+    /// The URL of the endpoint
+    url: String,
+    /// Whether the last request to the endpoint succeeded
+    healthy: AtomicBool,
+}
+
+impl SequencerEndpoint {
+    const fn new(url: String) -> Self {
+        Self { url, healthy: AtomicBool::new(true) }
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.healthy.load(atomic::Ordering::Relaxed)
+    }
+
+    fn set_healthy(&self, healthy: bool) {
+        self.healthy.store(healthy, atomic::Ordering::Relaxed);
+    }
 }
 
 #[cfg(test)]
@@ -161,4 +357,38 @@ mod tests {
             r#"{"id":1,"jsonrpc":"2.0","method":"eth_sendRawTransactionConditional","params":["0x61626364",{"knownAccounts":{}}]}"#
         );
     }
+
+    #[test]
+    fn test_endpoints_by_priority() {
+        let client = SequencerClient::with_endpoints(
+            ["http://sequencer-1:8545", "http://sequencer-2:8545", "http://sequencer-3:8545"],
+            Client::new(),
+        );
+        assert_eq!(client.endpoint(), "http://sequencer-1:8545");
+
+        client.inner.endpoints[0].set_healthy(false);
+        let endpoints: Vec<_> = client
+            .endpoints_by_priority()
+            .into_iter()
+            .map(|endpoint| endpoint.url.as_str())
+            .collect();
+        assert_eq!(
+            endpoints,
+            ["http://sequencer-2:8545", "http://sequencer-3:8545", "http://sequencer-1:8545"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_keep_unforwarded_transaction() {
+        // nothing listens on the discard port
+        let client = SequencerClient::new("http://127.0.0.1:9").with_max_retries(0);
+        assert!(client.forward_raw_transaction(&[0xab]).await.is_err());
+        assert_eq!(client.unforwarded_transactions(), 1);
+
+        for _ in 0..MAX_UNFORWARDED_TRANSACTIONS {
+            client.push_unforwarded(Bytes::from_static(&[0xcd]));
+        }
+        assert_eq!(client.unforwarded_transactions(), MAX_UNFORWARDED_TRANSACTIONS);
+        assert_eq!(client.inner.unforwarded.lock()[0], Bytes::from_static(&[0xcd]));
+    }
 }
@@ -16,6 +16,19 @@ pub trait MaybeConditionalTransaction {
         self.conditional().map(|tc| tc.has_exceeded_block_attributes(block_attr)).unwrap_or(false)
     }
 
+    /// Check if the transaction can be included in a block with the given attributes, i.e. the
+    /// block number and timestamp are within the ranges of the conditional, if any.
+    ///
+    /// Unlike [`Self::has_exceeded_block_attributes`], this also checks the lower bounds, since a
+    /// transaction must not be included before its range starts.
+    fn matches_block_attributes(&self, block_attr: &BlockConditionalAttributes) -> bool {
+        let Some(tc) = self.conditional() else { return true };
+        tc.block_number_min.is_none_or(|min| block_attr.number >= min) &&
+            tc.block_number_max.is_none_or(|max| block_attr.number <= max) &&
+            tc.timestamp_min.is_none_or(|min| block_attr.timestamp >= min) &&
+            tc.timestamp_max.is_none_or(|max| block_attr.timestamp <= max)
+    }
+
     /// Helper that sets the conditional and returns the instance again
     fn with_conditional(mut self, conditional: TransactionConditional) -> Self
     where
@@ -25,3 +38,42 @@ pub trait MaybeConditionalTransaction {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct ConditionalTx(Option<TransactionConditional>);
+
+    impl MaybeConditionalTransaction for ConditionalTx {
+        fn set_conditional(&mut self, conditional: TransactionConditional) {
+            self.0 = Some(conditional)
+        }
+
+        fn conditional(&self) -> Option<&TransactionConditional> {
+            self.0.as_ref()
+        }
+    }
+
+    #[test]
+    fn test_matches_block_attributes() {
+        let attr = |number, timestamp| BlockConditionalAttributes { number, timestamp };
+
+        assert!(ConditionalTx::default().matches_block_attributes(&attr(10, 100)));
+
+        let tx = ConditionalTx::default().with_conditional(TransactionConditional {
+            block_number_min: Some(10),
+            block_number_max: Some(20),
+            timestamp_min: Some(100),
+            timestamp_max: Some(200),
+            ..Default::default()
+        });
+        assert!(tx.matches_block_attributes(&attr(10, 100)));
+        assert!(tx.matches_block_attributes(&attr(20, 200)));
+        assert!(!tx.matches_block_attributes(&attr(9, 150)));
+        assert!(!tx.matches_block_attributes(&attr(21, 150)));
+        assert!(!tx.matches_block_attributes(&attr(15, 99)));
+        assert!(!tx.matches_block_attributes(&attr(15, 201)));
+    }
+}
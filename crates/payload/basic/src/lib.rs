@@ -28,7 +28,7 @@ use std::{
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tokio::{
     sync::{oneshot, Semaphore},
@@ -111,6 +111,17 @@ impl<Client, Tasks, Builder> BasicPayloadJobGenerator<Client, Tasks, Builder> {
         tokio::time::Instant::now() + self.max_job_duration(unix_timestamp)
     }
 
+    /// Returns the [Instant] at which the transactions of the job's payload builds are
+    /// interrupted, which is the configured margin before the timestamp of the payload, i.e. the
+    /// time the payload is expected to be requested.
+    ///
+    /// Returns `None` if no margin is configured or the timestamp is closer than the margin.
+    #[inline]
+    fn interrupt_deadline(&self, unix_timestamp: u64) -> Option<Instant> {
+        let margin = self.config.interrupt_margin?;
+        Some(Instant::now() + duration_until(unix_timestamp).checked_sub(margin)?)
+    }
+
     /// Returns a reference to the tasks type
     pub const fn tasks(&self) -> &Tasks {
         &self.executor
@@ -163,12 +174,15 @@ where
         let until = self.job_deadline(config.attributes.timestamp());
         let deadline = Box::pin(tokio::time::sleep_until(until));
 
+        let interrupt_deadline = self.interrupt_deadline(config.attributes.timestamp());
+
         let cached_reads = self.maybe_pre_cached(parent_header.hash());
 
         let mut job = BasicPayloadJob {
             config,
             executor: self.executor.clone(),
             deadline,
+            interrupt_deadline,
             // ticks immediately
             interval: tokio::time::interval(self.config.interval),
             best_payload: PayloadState::Missing,
@@ -248,6 +262,11 @@ pub struct BasicPayloadJobGeneratorConfig {
     deadline: Duration,
     /// Maximum number of tasks to spawn for building a payload.
     max_payload_tasks: usize,
+    /// How long before the timestamp of the payload a still executing transaction is interrupted,
+    /// so the best payload built so far can be resolved in time.
+    ///
+    /// By default transactions are never interrupted.
+    interrupt_margin: Option<Duration>,
 }
 
 // === impl BasicPayloadJobGeneratorConfig ===
@@ -275,6 +294,13 @@ impl BasicPayloadJobGeneratorConfig {
         self.max_payload_tasks = max_payload_tasks;
         self
     }
+
+    /// Sets how long before the timestamp of the payload a still executing transaction is
+    /// interrupted.
+    pub const fn interrupt_margin(mut self, interrupt_margin: Option<Duration>) -> Self {
+        self.interrupt_margin = interrupt_margin;
+        self
+    }
 }
 
 impl Default for BasicPayloadJobGeneratorConfig {
@@ -284,6 +310,7 @@ impl Default for BasicPayloadJobGeneratorConfig {
             // 12s slot time
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            interrupt_margin: None,
         }
     }
 }
@@ -309,6 +336,8 @@ where
     executor: Tasks,
     /// The deadline when this job should resolve.
     deadline: Pin<Box<Sleep>>,
+    /// The deadline at which the transactions of a payload build are interrupted, if any.
+    interrupt_deadline: Option<Instant>,
     /// The interval at which the job should build a new payload after the last.
     interval: Interval,
     /// The best payload so far and its state.
@@ -341,7 +370,12 @@ where
     fn spawn_build_job(&mut self) {
         trace!(target: "payload_builder", id = %self.config.payload_id(), "spawn new payload build task");
         let (tx, rx) = oneshot::channel();
-        let cancel = CancelOnDrop::default();
+        // builds that start after the interrupt deadline run to completion, they are the only
+        // chance to build a payload at all
+        let cancel = match self.interrupt_deadline.filter(|deadline| *deadline > Instant::now()) {
+            Some(deadline) => CancelOnDrop::with_deadline(deadline),
+            None => CancelOnDrop::default(),
+        };
         let _cancel = cancel.clone();
         let guard = self.payload_task_guard.clone();
         let payload_config = self.config.clone();
@@ -407,7 +441,8 @@ where
                         trace!(target: "payload_builder", worse_fees = %fees, "skipped payload build of worse block");
                     }
                     BuildOutcome::Cancelled => {
-                        unreachable!("the cancel signal never fired")
+                        // only builds interrupted at their deadline are still polled
+                        trace!(target: "payload_builder", "payload build interrupted at deadline");
                     }
                 },
                 Poll::Ready(Err(error)) => {
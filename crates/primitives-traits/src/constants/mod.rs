@@ -13,6 +13,12 @@ pub const MINIMUM_GAS_LIMIT: u64 = 5000;
 /// The bound divisor of the gas limit, used in update calculations.
 pub const GAS_LIMIT_BOUND_DIVISOR: u64 = 1024;
 
+/// Maximum bytecode to permit for a contract.
+pub const MAX_CODE_BYTE_SIZE: usize = 24576;
+
+/// Maximum initcode to permit in a creation transaction and create instructions.
+pub const MAX_INIT_CODE_BYTE_SIZE: usize = 2 * MAX_CODE_BYTE_SIZE;
+
 /// The number of blocks to unwind during a reorg that already became a part of canonical chain.
 ///
 /// In reality, the node can end up in this particular situation very rarely. It would happen only
@@ -53,6 +53,11 @@
 //! mainnet. Newer transactions must always be recovered with the regular `recover` functions, see
 //! also [`recover_signer`](crypto::secp256k1::recover_signer).
 //!
+//! The stateless validity checks of transactions, e.g. the chain ID and the intrinsic gas, are
+//! provided by [`transaction::validation`]. Like the signer recovery with the default `k256`
+//! backend, they are `no_std` compatible, so provers can validate transactions with the same rules
+//! as the node.
+//!
 //! ## Bincode serde compatibility
 //!
 //! The [bincode-crate](https://github.com/bincode-org/bincode) is often used by additional tools when sending data over the network.
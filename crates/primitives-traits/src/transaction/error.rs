@@ -61,6 +61,14 @@ pub enum InvalidTransactionError {
     /// Thrown if the sender of a transaction is a contract.
     #[error("transaction signer has bytecode set")]
     SignerAccountHasBytecode,
+    /// The init code of a contract creation exceeds the limit of EIP-3860.
+    #[error("init code size {size} exceeds limit {limit}")]
+    ExceedsMaxInitCodeSize {
+        /// The size of the init code.
+        size: usize,
+        /// The maximum size of the init code.
+        limit: usize,
+    },
 }
 
 /// Represents error variants that can happen when trying to convert a transaction to pooled
@@ -6,6 +6,7 @@ pub mod signed;
 
 pub mod error;
 pub mod recover;
+pub mod validation;
 
 pub use alloy_consensus::transaction::{TransactionInfo, TransactionMeta};
 
@@ -0,0 +1,120 @@
+//! Stateless validity checks of transactions.
+//!
+//! These checks only depend on the transaction and the active hardfork, not on the state, so they
+//! can be performed wherever the transaction is available, including `no_std` environments.
+
+use crate::{constants::MAX_INIT_CODE_BYTE_SIZE, transaction::error::InvalidTransactionError};
+use alloy_consensus::Transaction;
+use revm_primitives::hardfork::SpecId;
+
+/// Returns the gas a transaction requires before execution, i.e. the maximum of the intrinsic gas
+/// and the calldata floor gas of [EIP-7623](https://eips.ethereum.org/EIPS/eip-7623).
+pub fn intrinsic_gas<T: Transaction>(transaction: &T, spec_id: SpecId) -> u64 {
+    let gas = revm_interpreter::gas::calculate_initial_tx_gas(
+        spec_id,
+        transaction.input(),
+        transaction.is_create(),
+        transaction.access_list().map(|l| l.len()).unwrap_or_default() as u64,
+        transaction
+            .access_list()
+            .map(|l| l.iter().map(|i| i.storage_keys.len()).sum::<usize>())
+            .unwrap_or_default() as u64,
+        transaction.authorization_list().map(|l| l.len()).unwrap_or_default() as u64,
+    );
+    gas.initial_gas.max(gas.floor_gas)
+}
+
+/// Ensures that the gas limit of the transaction covers its [`intrinsic_gas`].
+pub fn ensure_intrinsic_gas<T: Transaction>(
+    transaction: &T,
+    spec_id: SpecId,
+) -> Result<(), InvalidTransactionError> {
+    if transaction.gas_limit() < intrinsic_gas(transaction, spec_id) {
+        return Err(InvalidTransactionError::GasTooLow)
+    }
+    Ok(())
+}
+
+/// Ensures that the transaction is signed for the given chain.
+///
+/// Legacy transactions without a chain ID, i.e. without replay protection, are accepted.
+pub fn ensure_chain_id<T: Transaction>(
+    transaction: &T,
+    chain_id: u64,
+) -> Result<(), InvalidTransactionError> {
+    if transaction.chain_id().is_some_and(|id| id != chain_id) {
+        return Err(InvalidTransactionError::ChainIdMismatch)
+    }
+    Ok(())
+}
+
+/// Ensures that the init code of a contract creation doesn't exceed [`MAX_INIT_CODE_BYTE_SIZE`],
+/// as required since Shanghai by [EIP-3860](https://eips.ethereum.org/EIPS/eip-3860).
+pub fn ensure_max_init_code_size<T: Transaction>(
+    transaction: &T,
+    spec_id: SpecId,
+) -> Result<(), InvalidTransactionError> {
+    let size = transaction.input().len();
+    if spec_id.is_enabled_in(SpecId::SHANGHAI) &&
+        transaction.is_create() &&
+        size > MAX_INIT_CODE_BYTE_SIZE
+    {
+        return Err(InvalidTransactionError::ExceedsMaxInitCodeSize {
+            size,
+            limit: MAX_INIT_CODE_BYTE_SIZE,
+        })
+    }
+    Ok(())
+}
+
+/// Performs all stateless validity checks of the transaction: the chain ID, the init code size and
+/// the intrinsic gas.
+pub fn ensure_stateless_validity<T: Transaction>(
+    transaction: &T,
+    chain_id: u64,
+    spec_id: SpecId,
+) -> Result<(), InvalidTransactionError> {
+    ensure_chain_id(transaction, chain_id)?;
+    ensure_max_init_code_size(transaction, spec_id)?;
+    ensure_intrinsic_gas(transaction, spec_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxLegacy;
+    use alloy_primitives::{Bytes, TxKind};
+
+    #[test]
+    fn stateless_validity() {
+        let tx = TxLegacy { chain_id: Some(1), gas_limit: 21_000, ..Default::default() };
+        assert_eq!(intrinsic_gas(&tx, SpecId::PRAGUE), 21_000);
+        assert_eq!(ensure_stateless_validity(&tx, 1, SpecId::PRAGUE), Ok(()));
+        assert_eq!(
+            ensure_stateless_validity(&tx, 10, SpecId::PRAGUE),
+            Err(InvalidTransactionError::ChainIdMismatch)
+        );
+
+        // every non-zero calldata byte costs 16 gas
+        let tx = TxLegacy { input: Bytes::from_static(&[1]), ..tx };
+        assert_eq!(
+            ensure_stateless_validity(&tx, 1, SpecId::PRAGUE),
+            Err(InvalidTransactionError::GasTooLow)
+        );
+
+        let tx = TxLegacy {
+            to: TxKind::Create,
+            input: vec![0; MAX_INIT_CODE_BYTE_SIZE + 1].into(),
+            gas_limit: u64::MAX,
+            ..tx
+        };
+        assert_eq!(ensure_stateless_validity(&tx, 1, SpecId::MERGE), Ok(()));
+        assert_eq!(
+            ensure_stateless_validity(&tx, 1, SpecId::SHANGHAI),
+            Err(InvalidTransactionError::ExceedsMaxInitCodeSize {
+                size: MAX_INIT_CODE_BYTE_SIZE + 1,
+                limit: MAX_INIT_CODE_BYTE_SIZE
+            })
+        );
+    }
+}
@@ -74,5 +74,9 @@ pub mod serde_bincode_compat {
     pub use reth_primitives_traits::serde_bincode_compat::*;
 }
 
+/// Zero-copy archival format for blocks and receipts.
+#[cfg(feature = "rkyv")]
+pub use reth_ethereum_primitives::archive;
+
 // Re-export of `EthPrimitives`
 pub use reth_ethereum_primitives::EthPrimitives;
@@ -10,7 +10,7 @@ use reth_provider::{
     NodePrimitivesProvider, PruneCheckpointWriter, StaticFileProviderFactory,
 };
 use reth_prune_types::PruneModes;
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 use tokio::sync::watch;
 
 /// Contains the information required to build a pruner
@@ -20,6 +20,8 @@ pub struct PrunerBuilder {
     block_interval: usize,
     /// Pruning configuration for every part of the data that can be pruned.
     segments: PruneModes,
+    /// Directory with the era1 files to verify before expiring block bodies.
+    era_dir: Option<PathBuf>,
     /// The delete limit for pruner, per run.
     delete_limit: usize,
     /// Time a pruner job can run before timing out.
@@ -37,6 +39,7 @@ impl PrunerBuilder {
         Self::default()
             .block_interval(pruner_config.block_interval)
             .segments(pruner_config.segments)
+            .era_dir(pruner_config.era_dir)
     }
 
     /// Sets the minimum pruning interval measured in blocks.
@@ -51,6 +54,12 @@ impl PrunerBuilder {
         self
     }
 
+    /// Sets the directory with the era1 files to verify before expiring block bodies.
+    pub fn era_dir(mut self, era_dir: Option<PathBuf>) -> Self {
+        self.era_dir = era_dir;
+        self
+    }
+
     /// Sets the delete limit for pruner, per run.
     pub const fn delete_limit(mut self, prune_delete_limit: usize) -> Self {
         self.delete_limit = prune_delete_limit;
@@ -88,8 +97,11 @@ impl PrunerBuilder {
                 Primitives = <PF::ProviderRW as NodePrimitivesProvider>::Primitives,
             >,
     {
-        let segments =
-            SegmentSet::from_components(provider_factory.static_file_provider(), self.segments);
+        let segments = SegmentSet::from_components(
+            provider_factory.static_file_provider(),
+            self.segments,
+            self.era_dir,
+        );
 
         Pruner::new_with_factory(
             provider_factory,
@@ -112,7 +124,11 @@ impl PrunerBuilder {
             + BlockReader<Transaction: Encodable2718>
             + PruneCheckpointWriter,
     {
-        let segments = SegmentSet::<Provider>::from_components(static_file_provider, self.segments);
+        let segments = SegmentSet::<Provider>::from_components(
+            static_file_provider,
+            self.segments,
+            self.era_dir,
+        );
 
         Pruner::new(
             segments.into_vec(),
@@ -129,6 +145,7 @@ impl Default for PrunerBuilder {
         Self {
             block_interval: 5,
             segments: PruneModes::none(),
+            era_dir: None,
             delete_limit: MAINNET_PRUNE_DELETE_LIMIT,
             timeout: None,
             finished_exex_height: watch::channel(FinishedExExHeight::NoExExs).1,
@@ -1,6 +1,6 @@
 use crate::segments::{
-    AccountHistory, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory, TransactionLookup,
-    UserReceipts,
+    AccountHistory, BodiesHistory, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory,
+    TransactionLookup, UserReceipts,
 };
 use alloy_eips::eip2718::Encodable2718;
 use reth_db_api::{table::Value, transaction::DbTxMut};
@@ -10,6 +10,7 @@ use reth_provider::{
     StaticFileProviderFactory,
 };
 use reth_prune_types::PruneModes;
+use std::path::PathBuf;
 
 use super::{StaticFileHeaders, StaticFileReceipts, StaticFileTransactions};
 
@@ -54,9 +55,12 @@ where
 {
     /// Creates a [`SegmentSet`] from an existing components, such as [`StaticFileProvider`] and
     /// [`PruneModes`].
+    ///
+    /// The era1 directory is used to verify the history before block bodies are expired.
     pub fn from_components(
         static_file_provider: StaticFileProvider<Provider::Primitives>,
         prune_modes: PruneModes,
+        era_dir: Option<PathBuf>,
     ) -> Self {
         let PruneModes {
             sender_recovery,
@@ -64,6 +68,7 @@ where
             receipts,
             account_history,
             storage_history,
+            bodies_history,
             receipts_log_filter,
         } = prune_modes;
 
@@ -89,6 +94,8 @@ where
             .segment_opt(transaction_lookup.map(TransactionLookup::new))
             // Sender recovery
             .segment_opt(sender_recovery.map(SenderRecovery::new))
+            // Bodies history
+            .segment_opt(bodies_history.map(|mode| BodiesHistory::new(mode, era_dir)))
     }
 }
 
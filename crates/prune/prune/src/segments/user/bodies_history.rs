@@ -0,0 +1,270 @@
+use crate::{
+    segments::{PruneInput, Segment},
+    PrunerError,
+};
+use alloy_primitives::{hex, BlockNumber, B256};
+use reth_provider::StaticFileProviderFactory;
+use reth_prune_types::{
+    PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+};
+use reth_static_file_types::StaticFileSegment;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+use thiserror::Error;
+use tracing::{info, instrument, trace, warn};
+
+/// Number of blocks in a single era1 file.
+const ERA1_BLOCKS_PER_FILE: u64 = 8192;
+
+/// Name of the file listing the checksums of the era1 files, one per epoch.
+const ERA1_CHECKSUMS_FILE: &str = "checksums.txt";
+
+/// Expires block bodies and receipts from static files, keeping the headers.
+///
+/// Before anything is deleted, the era1 files covering the expired range are verified against
+/// their checksums, so the history stays available outside of the node. The verification runs on
+/// a separate thread and the segment does nothing until it has finished.
+///
+/// Static files are deleted as a whole, so blocks of a static file that also contains blocks above
+/// the prune target are kept.
+#[derive(Debug)]
+pub struct BodiesHistory {
+    mode: PruneMode,
+    era_dir: Option<PathBuf>,
+    verification: Arc<Mutex<EraVerification>>,
+}
+
+impl BodiesHistory {
+    pub fn new(mode: PruneMode, era_dir: Option<PathBuf>) -> Self {
+        Self { mode, era_dir, verification: Default::default() }
+    }
+
+    /// Returns `true` if the era1 files up to `to_block` have been verified. Starts the
+    /// verification if it hasn't been started yet.
+    fn is_verified(&self, to_block: BlockNumber) -> bool {
+        let Some(era_dir) = &self.era_dir else {
+            warn!(target: "pruner", "Bodies history pruning requires an era1 directory, skipping");
+            return false
+        };
+
+        let mut verification = self.verification.lock().expect("not poisoned");
+        match *verification {
+            EraVerification::Verified(verified) if verified >= to_block => return true,
+            EraVerification::InProgress | EraVerification::Failed => return false,
+            EraVerification::Pending | EraVerification::Verified(_) => {}
+        }
+
+        let state = self.verification.clone();
+        let era_dir = era_dir.clone();
+        let spawned = std::thread::Builder::new()
+            .name("era1-verification".to_string())
+            .spawn(move || {
+                info!(target: "pruner", ?era_dir, %to_block, "Verifying era1 files");
+                let result = verify_era1_files(&era_dir, to_block);
+                let mut verification = state.lock().expect("not poisoned");
+                *verification = match result {
+                    Ok(()) => {
+                        info!(target: "pruner", %to_block, "Verified era1 files");
+                        EraVerification::Verified(to_block)
+                    }
+                    Err(err) => {
+                        warn!(target: "pruner", %err, "Failed to verify era1 files, block bodies will not be expired");
+                        EraVerification::Failed
+                    }
+                };
+            });
+
+        match spawned {
+            Ok(_) => *verification = EraVerification::InProgress,
+            Err(err) => warn!(target: "pruner", %err, "Failed to spawn era1 verification"),
+        }
+
+        false
+    }
+}
+
+impl<Provider> Segment<Provider> for BodiesHistory
+where
+    Provider: StaticFileProviderFactory,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::Bodies
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        if input
+            .previous_checkpoint
+            .and_then(|checkpoint| checkpoint.block_number)
+            .is_some_and(|block_number| block_number >= input.to_block)
+        {
+            trace!(target: "pruner", "No block bodies to expire");
+            return Ok(SegmentOutput::done())
+        }
+
+        if !self.is_verified(input.to_block) {
+            trace!(target: "pruner", "Waiting for era1 files to be verified");
+            return Ok(SegmentOutput::done())
+        }
+
+        let static_file_provider = provider.static_file_provider();
+        let mut pruned = 0;
+        for segment in [StaticFileSegment::Transactions, StaticFileSegment::Receipts] {
+            let deleted =
+                static_file_provider.delete_segment_below_block(segment, input.to_block + 1)?;
+            trace!(target: "pruner", ?segment, ?deleted, "Expired static files");
+            pruned += deleted.len();
+        }
+
+        Ok(SegmentOutput {
+            progress: input.limiter.progress(true),
+            pruned,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: Some(input.to_block),
+                tx_number: None,
+            }),
+        })
+    }
+}
+
+/// State of the era1 files verification.
+#[derive(Debug, Default)]
+enum EraVerification {
+    #[default]
+    Pending,
+    InProgress,
+    /// The era1 files are verified up to the block number, inclusive.
+    Verified(BlockNumber),
+    Failed,
+}
+
+/// Errors that can occur when verifying era1 files.
+#[derive(Error, Debug)]
+pub enum EraVerificationError {
+    #[error("invalid checksum for epoch {0} in {ERA1_CHECKSUMS_FILE}")]
+    InvalidChecksum(u64),
+    #[error("missing checksum for epoch {0}")]
+    MissingChecksum(u64),
+    #[error("missing era1 file for epoch {0}")]
+    MissingFile(u64),
+    #[error("checksum mismatch for era1 file {0:?}")]
+    ChecksumMismatch(PathBuf),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// Verifies that the era1 files in the directory cover all blocks up to `to_block`, inclusive, and
+/// match the checksums listed in its `checksums.txt`.
+///
+/// The checksums file contains one sha256 hash per line, ordered by epoch. Anything after the
+/// first whitespace of a line is ignored, so the output of `sha256sum` ordered by epoch is accepted
+/// as well.
+pub fn verify_era1_files(dir: &Path, to_block: BlockNumber) -> Result<(), EraVerificationError> {
+    let checksums = std::fs::read_to_string(dir.join(ERA1_CHECKSUMS_FILE))?
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .enumerate()
+        .map(|(epoch, checksum)| {
+            hex::decode(checksum)
+                .ok()
+                .and_then(|checksum| B256::try_from(checksum.as_slice()).ok())
+                .ok_or(EraVerificationError::InvalidChecksum(epoch as u64))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut files = HashMap::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if let Some(epoch) = path.file_name().and_then(|name| era1_epoch(&name.to_string_lossy())) {
+            files.insert(epoch, path);
+        }
+    }
+
+    for epoch in 0..=to_block / ERA1_BLOCKS_PER_FILE {
+        let expected =
+            checksums.get(epoch as usize).ok_or(EraVerificationError::MissingChecksum(epoch))?;
+        let path = files.get(&epoch).ok_or(EraVerificationError::MissingFile(epoch))?;
+
+        if sha256_file(path)? != *expected {
+            return Err(EraVerificationError::ChecksumMismatch(path.clone()))
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses the epoch from an era1 file name of the `<network>-<epoch>-<short-root>.era1` format.
+fn era1_epoch(file_name: &str) -> Option<u64> {
+    let mut parts = file_name.strip_suffix(".era1")?.rsplit('-');
+    let _short_root = parts.next()?;
+    parts.next()?.parse().ok()
+}
+
+/// Computes the sha256 hash of the file.
+fn sha256_file(path: &Path) -> std::io::Result<B256> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0; 1 << 20];
+    loop {
+        let read = file.read(&mut buf)?;
+        if read == 0 {
+            break
+        }
+        hasher.update(&buf[..read]);
+    }
+    Ok(B256::from_slice(&hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+
+    fn write_era1(dir: &Path, epoch: u64, data: &[u8]) -> String {
+        std::fs::write(dir.join(format!("mainnet-{epoch:05}-5ec1ffb8.era1")), data).unwrap();
+        hex::encode(Sha256::digest(data))
+    }
+
+    #[test]
+    fn parse_era1_epoch() {
+        assert_eq!(era1_epoch("mainnet-00000-5ec1ffb8.era1"), Some(0));
+        assert_eq!(era1_epoch("sepolia-01896-4e1e7a59.era1"), Some(1896));
+        assert_eq!(era1_epoch("mainnet-00000-5ec1ffb8.era"), None);
+        assert_eq!(era1_epoch("checksums.txt"), None);
+    }
+
+    #[test]
+    fn verify_era1() {
+        let dir = tempfile::tempdir().unwrap();
+        let checksums =
+            [write_era1(dir.path(), 0, b"epoch 0"), write_era1(dir.path(), 1, b"epoch 1")];
+        std::fs::write(dir.path().join(ERA1_CHECKSUMS_FILE), checksums.join("\n")).unwrap();
+
+        assert_matches!(verify_era1_files(dir.path(), ERA1_BLOCKS_PER_FILE * 2 - 1), Ok(()));
+        assert_matches!(
+            verify_era1_files(dir.path(), ERA1_BLOCKS_PER_FILE * 2),
+            Err(EraVerificationError::MissingChecksum(2))
+        );
+
+        write_era1(dir.path(), 1, b"corrupted");
+        assert_matches!(verify_era1_files(dir.path(), ERA1_BLOCKS_PER_FILE - 1), Ok(()));
+        assert_matches!(
+            verify_era1_files(dir.path(), ERA1_BLOCKS_PER_FILE),
+            Err(EraVerificationError::ChecksumMismatch(_))
+        );
+    }
+}
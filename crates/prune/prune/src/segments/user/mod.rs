@@ -1,4 +1,5 @@
 mod account_history;
+mod bodies_history;
 mod history;
 mod receipts;
 mod receipts_by_logs;
@@ -7,6 +8,7 @@ mod storage_history;
 mod transaction_lookup;
 
 pub use account_history::AccountHistory;
+pub use bodies_history::{verify_era1_files, BodiesHistory, EraVerificationError};
 pub use receipts::Receipts;
 pub use receipts_by_logs::ReceiptsByLogs;
 pub use sender_recovery::SenderRecovery;
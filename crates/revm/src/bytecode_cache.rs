@@ -0,0 +1,123 @@
+use alloy_primitives::{map::B256Map, B256};
+use revm::bytecode::Bytecode;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// The cache shared by all [`StateProviderDatabase`](crate::database::StateProviderDatabase)s,
+/// once installed with [`BytecodeCache::install_global`].
+static GLOBAL: OnceLock<BytecodeCache> = OnceLock::new();
+
+/// A concurrent cache of analyzed contract bytecode, keyed by code hash.
+///
+/// Loading bytecode from the database decodes the code and its jump table on every access, which
+/// adds up for large contracts that are called repeatedly. Since the code of a code hash never
+/// changes, the analyzed bytecode can be shared between all state providers, regardless of the
+/// block they're at.
+///
+/// Once the cache is full, arbitrary entries are evicted to make room for new ones.
+///
+/// The cache is opt-in: bytecode is only cached by the state providers of the node once a cache is
+/// installed with [`BytecodeCache::install_global`].
+#[derive(Debug, Clone)]
+pub struct BytecodeCache {
+    inner: Arc<RwLock<BytecodeCacheInner>>,
+}
+
+impl BytecodeCache {
+    /// Creates a new cache that holds up to `max_size` bytes of bytecode.
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(BytecodeCacheInner {
+                entries: B256Map::default(),
+                size: 0,
+                max_size,
+            })),
+        }
+    }
+
+    /// Installs the cache shared by all state providers, i.e. the executor, the payload builder
+    /// and the RPC, holding up to `max_size` bytes of bytecode.
+    ///
+    /// Returns the installed cache, which keeps its size if a cache was installed before.
+    pub fn install_global(max_size: usize) -> &'static Self {
+        GLOBAL.get_or_init(|| Self::new(max_size))
+    }
+
+    /// Returns the cache shared by all state providers, if one is installed.
+    pub fn global() -> Option<&'static Self> {
+        GLOBAL.get()
+    }
+
+    /// Returns the bytecode of the given code hash, if it's cached.
+    pub fn get(&self, code_hash: &B256) -> Option<Bytecode> {
+        self.inner.read().unwrap_or_else(|err| err.into_inner()).entries.get(code_hash).cloned()
+    }
+
+    /// Caches the bytecode of the given code hash.
+    ///
+    /// Bytecode larger than the cache is ignored.
+    pub fn insert(&self, code_hash: B256, bytecode: Bytecode) {
+        let len = bytecode.len();
+        let mut inner = self.inner.write().unwrap_or_else(|err| err.into_inner());
+        if len > inner.max_size || inner.entries.contains_key(&code_hash) {
+            return
+        }
+
+        while inner.size + len > inner.max_size {
+            let Some(evicted) = inner.entries.keys().next().copied() else { break };
+            if let Some(evicted) = inner.entries.remove(&evicted) {
+                inner.size -= evicted.len();
+            }
+        }
+
+        inner.size += len;
+        inner.entries.insert(code_hash, bytecode);
+    }
+
+    /// Returns the number of cached entries.
+    pub fn len(&self) -> usize {
+        self.inner.read().unwrap_or_else(|err| err.into_inner()).entries.len()
+    }
+
+    /// Returns `true` if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the size of the cached bytecode in bytes.
+    pub fn size(&self) -> usize {
+        self.inner.read().unwrap_or_else(|err| err.into_inner()).size
+    }
+}
+
+#[derive(Debug)]
+struct BytecodeCacheInner {
+    entries: B256Map<Bytecode>,
+    /// The size of the cached bytecode in bytes.
+    size: usize,
+    /// The maximum size of the cached bytecode in bytes.
+    max_size: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Bytes;
+
+    #[test]
+    fn evicts_when_full() {
+        let cache = BytecodeCache::new(10);
+        let code = |len| Bytecode::new_raw(Bytes::from(vec![0x5b; len]));
+
+        cache.insert(B256::with_last_byte(1), code(6));
+        assert_eq!(cache.get(&B256::with_last_byte(1)), Some(code(6)));
+
+        // too large to ever fit
+        cache.insert(B256::with_last_byte(2), code(11));
+        assert_eq!(cache.get(&B256::with_last_byte(2)), None);
+
+        cache.insert(B256::with_last_byte(3), code(6));
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.size(), code(6).len());
+        assert_eq!(cache.get(&B256::with_last_byte(3)), Some(code(6)));
+    }
+}
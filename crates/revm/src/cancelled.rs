@@ -1,26 +1,117 @@
 use alloc::sync::Arc;
 use core::sync::atomic::AtomicBool;
+#[cfg(feature = "std")]
+use revm::{
+    inspector::Inspector,
+    interpreter::{
+        interpreter::EthInterpreter, interpreter_types::LoopControl, InstructionResult, Interpreter,
+    },
+};
+#[cfg(feature = "std")]
+use std::time::Instant;
 
 /// A marker that can be used to cancel execution.
 ///
 /// If dropped, it will set the `cancelled` flag to true.
 ///
-/// This is most useful when a payload job needs to be cancelled.
+/// This is most useful when a payload job needs to be cancelled. A job can additionally have a
+/// deadline, after which it is considered cancelled as well.
 #[derive(Default, Clone, Debug)]
-pub struct CancelOnDrop(Arc<AtomicBool>);
+pub struct CancelOnDrop {
+    cancelled: Arc<AtomicBool>,
+    #[cfg(feature = "std")]
+    deadline: Option<Instant>,
+}
 
 // === impl CancelOnDrop ===
 
 impl CancelOnDrop {
-    /// Returns true if the job was cancelled.
+    /// Creates a marker that is also cancelled once the deadline is reached.
+    #[cfg(feature = "std")]
+    pub fn with_deadline(deadline: Instant) -> Self {
+        Self { cancelled: Default::default(), deadline: Some(deadline) }
+    }
+
+    /// Returns the deadline of the job, if any.
+    #[cfg(feature = "std")]
+    pub const fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Returns true if the job was cancelled or its deadline is reached.
     pub fn is_cancelled(&self) -> bool {
-        self.0.load(core::sync::atomic::Ordering::Relaxed)
+        #[cfg(feature = "std")]
+        if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return true
+        }
+        self.cancelled.load(core::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Returns an [`Inspector`] that interrupts the execution of a transaction once the job is
+    /// cancelled or its deadline is reached.
+    ///
+    /// Inspecting every instruction is only worth it if the job has a [`Self::deadline`], without
+    /// one the job is cancelled before the next transaction anyway.
+    #[cfg(feature = "std")]
+    pub fn interrupt_inspector(&self) -> CancelInterrupt {
+        CancelInterrupt {
+            cancelled: self.cancelled.clone(),
+            deadline: self.deadline,
+            steps: 0,
+            interrupted: false,
+        }
     }
 }
 
 impl Drop for CancelOnDrop {
     fn drop(&mut self) {
-        self.0.store(true, core::sync::atomic::Ordering::Relaxed);
+        self.cancelled.store(true, core::sync::atomic::Ordering::Relaxed);
+    }
+}
+
+/// An [`Inspector`] that halts the execution of the current transaction once the associated
+/// [`CancelOnDrop`] is cancelled or its deadline is reached.
+///
+/// The interrupt is only checked every [`CancelInterrupt::CHECK_INTERVAL`] instructions to keep the
+/// overhead per instruction low. Once it is noticed, every frame halts with
+/// [`InstructionResult::OutOfGas`] on its next step, so the result of an interrupted transaction
+/// must be discarded. This allows to abandon a single long running transaction instead of waiting
+/// for its execution to finish.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct CancelInterrupt {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+    steps: u32,
+    interrupted: bool,
+}
+
+#[cfg(feature = "std")]
+impl CancelInterrupt {
+    /// The number of instructions executed in between two checks of the interrupt.
+    pub const CHECK_INTERVAL: u32 = 1024;
+
+    /// Returns true if the execution was cancelled or the deadline is reached.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(core::sync::atomic::Ordering::Relaxed) ||
+            self.deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<CTX> Inspector<CTX, EthInterpreter> for CancelInterrupt {
+    fn step(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        if !self.interrupted {
+            self.steps = self.steps.wrapping_add(1);
+            if self.steps % Self::CHECK_INTERVAL != 0 {
+                return
+            }
+            self.interrupted = self.is_cancelled();
+        }
+
+        if self.interrupted {
+            interp.control.set_instruction_result(InstructionResult::OutOfGas);
+        }
     }
 }
 
@@ -58,6 +149,26 @@ mod tests {
         assert!(!c.is_cancelled());
     }
 
+    #[test]
+    fn test_interrupt_on_drop() {
+        let c = CancelOnDrop::default();
+        let interrupt = c.interrupt_inspector();
+        assert!(!interrupt.is_cancelled());
+        drop(c);
+        assert!(interrupt.is_cancelled());
+    }
+
+    #[test]
+    fn test_cancelled_at_deadline() {
+        let c = CancelOnDrop::with_deadline(Instant::now() + std::time::Duration::from_secs(60));
+        assert!(!c.is_cancelled());
+        assert!(!c.interrupt_inspector().is_cancelled());
+
+        let c = CancelOnDrop::with_deadline(Instant::now());
+        assert!(c.is_cancelled());
+        assert!(c.interrupt_inspector().is_cancelled());
+    }
+
     #[test]
     fn test_default_cancel_task() {
         let c = ManualCancel::default();
@@ -1,3 +1,5 @@
+#[cfg(feature = "std")]
+use crate::bytecode_cache::BytecodeCache;
 use crate::primitives::alloy_primitives::{BlockNumber, StorageKey, StorageValue};
 use alloy_primitives::{Address, B256, U256};
 use core::ops::{Deref, DerefMut};
@@ -61,6 +63,9 @@ impl<T: StateProvider> EvmStateProvider for T {
 
 /// A [Database] and [`DatabaseRef`] implementation that uses [`EvmStateProvider`] as the underlying
 /// data source.
+///
+/// Bytecode is additionally looked up in the shared [`BytecodeCache`], if one is installed with
+/// [`BytecodeCache::install_global`].
 #[derive(Debug, Clone)]
 pub struct StateProviderDatabase<DB>(pub DB);
 
@@ -145,6 +150,19 @@ impl<DB: EvmStateProvider> DatabaseRef for StateProviderDatabase<DB> {
     ///
     /// Returns `Ok` with the bytecode if found, or the default bytecode otherwise.
     fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        #[cfg(feature = "std")]
+        if let Some(cache) = BytecodeCache::global() {
+            if let Some(bytecode) = cache.get(&code_hash) {
+                return Ok(bytecode)
+            }
+
+            let Some(bytecode) = self.bytecode_by_hash(&code_hash)? else {
+                return Ok(Bytecode::default())
+            };
+            cache.insert(code_hash, bytecode.0.clone());
+            return Ok(bytecode.0)
+        }
+
         Ok(self.bytecode_by_hash(&code_hash)?.unwrap_or_default().0)
     }
 
@@ -18,6 +18,10 @@ pub mod cached;
 /// A marker that can be used to cancel execution.
 pub mod cancelled;
 
+/// A cache of analyzed contract bytecode shared across state providers.
+#[cfg(feature = "std")]
+pub mod bytecode_cache;
+
 /// Contains glue code for integrating reth database into revm's [Database].
 pub mod database;
 
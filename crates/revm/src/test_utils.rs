@@ -1,6 +1,8 @@
 use alloc::vec::Vec;
 use alloy_primitives::{
-    keccak256, map::HashMap, Address, BlockNumber, Bytes, StorageKey, B256, U256,
+    keccak256,
+    map::{B256Map, HashMap},
+    Address, BlockNumber, Bytes, StorageKey, B256, U256,
 };
 use reth_primitives_traits::{Account, Bytecode};
 use reth_storage_api::{
@@ -9,8 +11,9 @@ use reth_storage_api::{
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
-    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, KeccakKeyHasher,
-    MultiProof, MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
+    root::storage_root_unsorted, updates::TrieUpdates, AccountProof, HashedPostState,
+    HashedStorage, HashedStorageRange, KeccakKeyHasher, MultiProof, MultiProofTargets,
+    StorageMultiProof, StorageProof, TrieInput,
 };
 
 /// Mock state for testing
@@ -96,10 +99,17 @@ impl StateRootProvider for StateProviderTest {
 impl StorageRootProvider for StateProviderTest {
     fn storage_root(
         &self,
-        _address: Address,
-        _hashed_storage: HashedStorage,
+        address: Address,
+        hashed_storage: HashedStorage,
     ) -> ProviderResult<B256> {
-        unimplemented!("storage root is not supported")
+        let mut storage = B256Map::default();
+        if !hashed_storage.wiped {
+            if let Some((slots, _)) = self.accounts.get(&address) {
+                storage.extend(slots.iter().map(|(slot, value)| (keccak256(slot), *value)));
+            }
+        }
+        storage.extend(hashed_storage.storage);
+        Ok(storage_root_unsorted(storage.into_iter().filter(|(_, value)| !value.is_zero())))
     }
 
     fn storage_proof(
@@ -119,6 +129,16 @@ impl StorageRootProvider for StateProviderTest {
     ) -> ProviderResult<StorageMultiProof> {
         unimplemented!("proof generation is not supported")
     }
+
+    fn storage_range(
+        &self,
+        _address: Address,
+        _hashed_storage: HashedStorage,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<HashedStorageRange> {
+        unimplemented!("storage range is not supported")
+    }
 }
 
 impl StateProofProvider for StateProviderTest {
@@ -0,0 +1,119 @@
+use alloy_eips::BlockId;
+use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_rpc_types_eth::{state::StateOverride, TransactionRequest};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Account abstraction rpc interface.
+///
+/// Helpers for bundlers of ERC-4337 user operations that simulate against the local EVM.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "aa"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "aa"))]
+pub trait AaApi {
+    /// Executes the validation phase of a user operation and traces the opcodes and storage
+    /// accessed by each of its entities, as required by the ERC-7562 validation rules.
+    ///
+    /// The call of the request is expected to only run the validation phase, e.g.
+    /// `simulateValidation` of the entry point. The stake of every entity is read from the entry
+    /// point after the call.
+    #[method(name = "traceValidation")]
+    async fn trace_validation(
+        &self,
+        request: UserOperationValidationRequest,
+        block_id: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+    ) -> RpcResult<UserOperationValidationTrace>;
+}
+
+/// Request to trace the validation phase of a user operation, used by `aa_traceValidation`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationValidationRequest {
+    /// The entry point the user operation is submitted to.
+    pub entry_point: Address,
+    /// The account of the user operation.
+    pub sender: Address,
+    /// The factory deploying the account, if the account is not deployed yet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub factory: Option<Address>,
+    /// The paymaster of the user operation, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster: Option<Address>,
+    /// The call running the validation phase of the user operation.
+    pub call: TransactionRequest,
+}
+
+/// Trace of the validation phase of a user operation, returned by `aa_traceValidation`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationValidationTrace {
+    /// Whether the validation call reverted.
+    ///
+    /// Note: `simulateValidation` of the entry point v0.6 always reverts, its result is encoded in
+    /// the revert data.
+    pub reverted: bool,
+    /// The return or revert data of the validation call.
+    pub output: Bytes,
+    /// The gas used by the validation call.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// The entities of the user operation, in the order factory, sender, paymaster.
+    pub entities: Vec<EntityValidation>,
+}
+
+/// The kind of an entity of a user operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EntityKind {
+    /// The factory deploying the account.
+    Factory,
+    /// The account of the user operation.
+    Sender,
+    /// The paymaster paying for the user operation.
+    Paymaster,
+}
+
+/// Validation trace of a single entity of a user operation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityValidation {
+    /// The kind of the entity.
+    pub kind: EntityKind,
+    /// The address of the entity.
+    pub address: Address,
+    /// The stake of the entity in the entry point.
+    pub stake: EntityStake,
+    /// The banned opcodes executed by the entity.
+    pub violations: Vec<OpcodeViolation>,
+    /// The storage slots accessed by the entity, grouped by account.
+    ///
+    /// Slots of the entity itself and of the sender are omitted, since accessing them is always
+    /// allowed. Bundlers are expected to check the remaining slots against the storage rules.
+    pub accessed_storage: BTreeMap<Address, Vec<B256>>,
+}
+
+/// Stake of an entity in the entry point.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityStake {
+    /// Whether the entry point considers the entity staked.
+    pub staked: bool,
+    /// The deposit of the entity.
+    pub deposit: U256,
+    /// The stake of the entity.
+    pub stake: U256,
+    /// The delay before the stake can be withdrawn, in seconds.
+    #[serde(with = "alloy_serde::quantity")]
+    pub unstake_delay_sec: u32,
+}
+
+/// A banned opcode executed during the validation phase of an entity.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpcodeViolation {
+    /// The account whose code executed the opcode.
+    pub address: Address,
+    /// The name of the opcode.
+    pub opcode: String,
+}
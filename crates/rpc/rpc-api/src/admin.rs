@@ -1,12 +1,17 @@
+use alloy_primitives::{B256, U64};
 use alloy_rpc_types_admin::{NodeInfo, PeerInfo};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_network_peers::{AnyNode, NodeRecord};
+use serde::{Deserialize, Serialize};
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
 pub trait AdminApi {
     /// Adds the given node record to the peerset.
+    ///
+    /// The peer is persisted with the known peers of the node, unless persistence of peers is
+    /// disabled.
     #[method(name = "addPeer")]
     fn add_peer(&self, record: NodeRecord) -> RpcResult<bool>;
 
@@ -17,6 +22,9 @@ pub trait AdminApi {
     fn remove_peer(&self, record: AnyNode) -> RpcResult<bool>;
 
     /// Adds the given node record to the trusted peerset.
+    ///
+    /// Trusted peers with a known address are persisted and restored on restart, unless
+    /// persistence of peers is disabled.
     #[method(name = "addTrustedPeer")]
     fn add_trusted_peer(&self, record: AnyNode) -> RpcResult<bool>;
 
@@ -34,6 +42,13 @@ pub trait AdminApi {
     #[method(name = "peers")]
     async fn peers(&self) -> RpcResult<Vec<PeerInfo>>;
 
+    /// Returns the health of the connections to the trusted and static peers, including the ones
+    /// that are currently not connected.
+    ///
+    /// Unreachable peers are redialed with an exponential backoff.
+    #[method(name = "peersHealth")]
+    async fn peers_health(&self) -> RpcResult<Vec<PeerHealthResponse>>;
+
     /// Creates an RPC subscription which serves events received from the network.
     #[subscription(
         name = "peerEvents",
@@ -45,4 +60,117 @@ pub trait AdminApi {
     /// Returns the ENR of the node.
     #[method(name = "nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfo>;
+
+    /// Replaces the log filter directives of the node, e.g. `engine::tree=trace`.
+    ///
+    /// The directives are applied on top of the configured log verbosity of every log output,
+    /// the same way as the `--log.<output>.filter` arguments. An empty string resets the filters.
+    ///
+    /// Returns true if the filter was successfully updated.
+    #[method(name = "setLogFilter")]
+    fn set_log_filter(&self, directives: String) -> RpcResult<bool>;
+}
+
+/// Admin namespace rpc interface to override the reorg depth limit of the engine.
+///
+/// This is only available if the node is configured with a maximum reorg depth.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait AdminReorgApi {
+    /// Allows the engine to reorg to a chain that contains the given block, even if the reorg
+    /// exceeds the maximum reorg depth.
+    ///
+    /// The reorg is performed on the next forkchoice update to that chain. Returns the most
+    /// recently refused reorg, or `null` if no reorg was refused.
+    #[method(name = "forceReorg")]
+    fn force_reorg(&self, block_hash: B256) -> RpcResult<Option<RejectedReorg>>;
+}
+
+/// Admin namespace rpc interface to rebuild the index of transaction hashes.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait AdminTxLookupApi {
+    /// Indexes the transaction hashes of the blocks in the range, so transactions that the
+    /// catching up transaction lookup stage didn't index yet, or whose hashes were pruned, can be
+    /// looked up by hash.
+    ///
+    /// The range must be adjacent to the index, see `reth_getTxLookupStatus`, and is limited to
+    /// 10000 blocks. Re-indexed pruned blocks are pruned again once they're outside of the
+    /// configured prune distance. Returns the number of indexed transactions.
+    #[method(name = "rebuildTxLookup")]
+    async fn rebuild_tx_lookup(&self, from_block: U64, to_block: U64) -> RpcResult<U64>;
+}
+
+/// The health of the connection to a trusted or static peer, returned by `admin_peersHealth`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerHealthResponse {
+    /// The node record of the peer.
+    pub enode: NodeRecord,
+    /// Whether the peer is trusted.
+    pub trusted: bool,
+    /// Whether the peer is static.
+    pub static_node: bool,
+    /// The status of the connection: `connected`, `disconnected`, `unreachable` or
+    /// `protocolMismatch`.
+    pub status: String,
+    /// The number of consecutive failed connection attempts since the last established session.
+    pub failed_dials: u32,
+    /// The round trip time of the most recent ping answered by the peer in milliseconds, if the
+    /// peer is connected.
+    pub latency_ms: Option<u64>,
+}
+
+/// A reorg that was refused for exceeding the maximum reorg depth, returned by
+/// `admin_forceReorg`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RejectedReorg {
+    /// The head of the chain the engine refused to switch to.
+    pub head: B256,
+    /// The number of canonical blocks the reorg would have removed.
+    pub depth: u64,
+}
+
+/// Admin namespace rpc interface to inspect the engine API capabilities exchanged with the
+/// consensus client.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait AdminCapabilitiesApi {
+    /// Returns the capabilities the consensus client sent with its last
+    /// `engine_exchangeCapabilities` call, and the methods required by the latest active fork and
+    /// the upcoming forks that it doesn't support.
+    ///
+    /// Returns `null` if the consensus client didn't exchange its capabilities yet.
+    #[method(name = "engineCapabilities")]
+    fn engine_capabilities(&self) -> RpcResult<Option<EngineCapabilitiesHandshake>>;
+}
+
+/// The engine API capabilities exchanged with the consensus client, returned by
+/// `admin_engineCapabilities`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineCapabilitiesHandshake {
+    /// The unix timestamp of the exchange in seconds.
+    pub timestamp: u64,
+    /// The capabilities of the consensus client.
+    pub capabilities: Vec<String>,
+    /// The methods required by the latest active fork and the upcoming forks that the consensus
+    /// client doesn't support.
+    pub missing: Vec<MissingEngineCapability>,
+}
+
+/// An engine API method that is required by an active or upcoming fork, but isn't supported by
+/// the consensus client.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingEngineCapability {
+    /// The method, e.g. `engine_newPayloadV4`.
+    pub method: String,
+    /// The name of the fork that requires the method.
+    pub fork: String,
+    /// The timestamp at which the fork activates.
+    pub activation_timestamp: u64,
+    /// Whether the fork is already active.
+    pub active: bool,
 }
@@ -7,6 +7,8 @@ use alloy_rpc_types_trace::geth::{
     BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Debug rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
@@ -370,6 +372,8 @@ pub trait DebugApi {
     /// Returns the storage at the given block height and transaction index. The result can be
     /// paged by providing a `maxResult` to cap the number of storage slots returned as well as
     /// specifying the offset via `keyStart` (hash of storage key).
+    ///
+    /// The storage is returned as of before the transaction at the given index is executed.
     #[method(name = "storageRangeAt")]
     async fn debug_storage_range_at(
         &self,
@@ -378,7 +382,7 @@ pub trait DebugApi {
         contract_address: Address,
         key_start: B256,
         max_result: u64,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<StorageRangeResult>;
 
     /// Returns the structured logs created during the execution of EVM against a block pulled
     /// from the pool of bad ones and returns them as a JSON object. For the second parameter see
@@ -434,3 +438,97 @@ pub trait DebugExecutionWitnessApi<Attributes> {
         attributes: Attributes,
     ) -> RpcResult<ExecutionWitness>;
 }
+
+/// Debug rpc interface for the state access profiler.
+///
+/// This is separate from the regular `debug_` api, because it is only available if the node runs
+/// with the state access profiler enabled.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "debug"))]
+pub trait DebugHotStateApi {
+    /// Returns the hottest accounts and storage slots of the most recent state access report.
+    ///
+    /// If no report was produced yet, this returns the statistics of the blocks executed so far,
+    /// or `null` if no block was executed since the node started.
+    #[method(name = "hotState")]
+    async fn hot_state(&self) -> RpcResult<Option<HotStateReport>>;
+}
+
+/// State access statistics of the blocks executed in a window, returned by `debug_hotState`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotStateReport {
+    /// The first block of the window.
+    pub first_block: u64,
+    /// The last block of the window.
+    pub last_block: u64,
+    /// Number of blocks executed in the window.
+    pub blocks: u64,
+    /// Number of distinct accounts accessed in the window.
+    pub unique_accounts: u64,
+    /// Number of distinct storage slots accessed in the window.
+    pub unique_storage_slots: u64,
+    /// Number of accounts accessed in more than one block of the window.
+    pub reused_accounts: u64,
+    /// Number of storage slots accessed in more than one block of the window.
+    pub reused_storage_slots: u64,
+    /// The hottest accounts.
+    pub accounts: Vec<HotAccount>,
+    /// The hottest storage slots.
+    pub storage_slots: Vec<HotStorageSlot>,
+}
+
+/// Access statistics of an account.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotAccount {
+    /// The address of the account.
+    pub address: Address,
+    /// The access statistics.
+    #[serde(flatten)]
+    pub stats: HotKeyStats,
+}
+
+/// Access statistics of a storage slot.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotStorageSlot {
+    /// The address of the account.
+    pub address: Address,
+    /// The storage slot.
+    pub slot: B256,
+    /// The access statistics.
+    #[serde(flatten)]
+    pub stats: HotKeyStats,
+}
+
+/// Number of reads, writes and blocks a key was accessed in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotKeyStats {
+    /// Number of transactions and system calls that loaded the key.
+    pub reads: u64,
+    /// Number of transactions and system calls that modified the key.
+    pub writes: u64,
+    /// Number of blocks the key was accessed in.
+    pub blocks: u64,
+}
+
+/// A page of the storage of an account, returned by `debug_storageRangeAt`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageRangeResult {
+    /// The storage slots of the page, keyed by the hash of the storage key.
+    pub storage: BTreeMap<B256, StorageRangeEntry>,
+    /// The hash of the storage key following the page, `None` if this is the last page.
+    pub next_key: Option<B256>,
+}
+
+/// A storage slot returned by `debug_storageRangeAt`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageRangeEntry {
+    /// The storage key, `None` if its preimage is unknown.
+    pub key: Option<B256>,
+    /// The value of the storage slot.
+    pub value: B256,
+}
@@ -14,6 +14,7 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+mod aa;
 mod admin;
 mod anvil;
 mod debug;
@@ -31,29 +32,50 @@ mod txpool;
 mod validation;
 mod web3;
 
+pub use aa::{
+    EntityKind, EntityStake, EntityValidation, OpcodeViolation, UserOperationValidationRequest,
+    UserOperationValidationTrace,
+};
+pub use admin::{
+    EngineCapabilitiesHandshake, MissingEngineCapability, PeerHealthResponse, RejectedReorg,
+};
+pub use debug::{
+    HotAccount, HotKeyStats, HotStateReport, HotStorageSlot, StorageRangeEntry, StorageRangeResult,
+};
+pub use reth::{
+    BlockProcessingEntry, HealthReport, NetworkHealthReport, TxLookupSegment, TxLookupSegmentState,
+    TxLookupStatus,
+};
+pub use txpool::{TxpoolDropReason, TxpoolEvent, TxpoolSubPool};
+pub use validation::{PayloadValidationResponse, PayloadValidationTimings};
+
 /// re-export of all server traits
 pub use servers::*;
 
 /// Aggregates all server traits.
 pub mod servers {
     pub use crate::{
-        admin::AdminApiServer,
-        debug::{DebugApiServer, DebugExecutionWitnessApiServer},
+        aa::AaApiServer,
+        admin::{
+            AdminApiServer, AdminCapabilitiesApiServer, AdminReorgApiServer, AdminTxLookupApiServer,
+        },
+        debug::{DebugApiServer, DebugExecutionWitnessApiServer, DebugHotStateApiServer},
         engine::{EngineApiServer, EngineEthApiServer, IntoEngineApiRpcModule},
         mev::{MevFullApiServer, MevSimApiServer},
         miner::MinerApiServer,
         net::NetApiServer,
         otterscan::OtterscanServer,
-        reth::RethApiServer,
+        reth::{RethApiServer, RethHealthApiServer},
         rpc::RpcApiServer,
         trace::TraceApiServer,
         txpool::TxPoolApiServer,
-        validation::BlockSubmissionValidationApiServer,
+        validation::{BlockSubmissionValidationApiServer, PayloadValidationApiServer},
         web3::Web3ApiServer,
     };
     pub use reth_rpc_eth_api::{
         self as eth, EthApiServer, EthBundleApiServer, EthCallBundleApiServer, EthFilterApiServer,
-        EthPubSubApiServer, L2EthApiExtServer,
+        EthPubSubApiServer, L2EthApiExtServer, RethBlockApiServer, RethFilterApiServer,
+        RethStateApiServer,
     };
 }
 
@@ -65,9 +87,12 @@ pub use clients::*;
 #[cfg(feature = "client")]
 pub mod clients {
     pub use crate::{
-        admin::AdminApiClient,
+        aa::AaApiClient,
+        admin::{
+            AdminApiClient, AdminCapabilitiesApiClient, AdminReorgApiClient, AdminTxLookupApiClient,
+        },
         anvil::AnvilApiClient,
-        debug::{DebugApiClient, DebugExecutionWitnessApiClient},
+        debug::{DebugApiClient, DebugExecutionWitnessApiClient, DebugHotStateApiClient},
         engine::{EngineApiClient, EngineEthApiClient},
         ganache::GanacheApiClient,
         hardhat::HardhatApiClient,
@@ -75,15 +100,15 @@ pub mod clients {
         miner::MinerApiClient,
         net::NetApiClient,
         otterscan::OtterscanClient,
-        reth::RethApiClient,
+        reth::{RethApiClient, RethHealthApiClient},
         rpc::RpcApiServer,
         trace::TraceApiClient,
         txpool::TxPoolApiClient,
-        validation::BlockSubmissionValidationApiClient,
+        validation::{BlockSubmissionValidationApiClient, PayloadValidationApiClient},
         web3::Web3ApiClient,
     };
     pub use reth_rpc_eth_api::{
         EthApiClient, EthBundleApiClient, EthCallBundleApiClient, EthFilterApiClient,
-        L2EthApiExtServer,
+        L2EthApiExtServer, RethBlockApiClient, RethFilterApiClient, RethStateApiClient,
     };
 }
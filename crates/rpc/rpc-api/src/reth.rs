@@ -1,6 +1,7 @@
 use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{Address, B256, U256, U64};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Reth API namespace for reth-specific methods
@@ -13,4 +14,161 @@ pub trait RethApi {
         &self,
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
+
+    /// Returns the number of deposits of the beacon chain deposit contract until the end of the
+    /// block.
+    ///
+    /// Returns `null` if the deposit index isn't enabled or hasn't reached the block yet.
+    #[method(name = "getDepositCount")]
+    async fn reth_get_deposit_count(&self, block_id: BlockId) -> RpcResult<Option<U64>>;
+
+    /// Returns the root of the beacon chain deposit contract's merkle tree at the end of the block,
+    /// as returned by `get_deposit_root` of the contract.
+    ///
+    /// Returns `null` if the deposit index isn't enabled or hasn't reached the block yet.
+    #[method(name = "getDepositRoot")]
+    async fn reth_get_deposit_root(&self, block_id: BlockId) -> RpcResult<Option<B256>>;
+
+    /// Returns the parent beacon block root of the block with the given timestamp, as stored in
+    /// the EIP-4788 beacon roots contract at the latest block.
+    ///
+    /// Returns `null` if the root isn't stored in the contract's ring buffer, e.g. because it was
+    /// overwritten already or there is no block with the timestamp.
+    #[method(name = "getBeaconRoot")]
+    async fn reth_get_beacon_root(&self, timestamp: U64) -> RpcResult<Option<B256>>;
+
+    /// Returns the hash of the block with the given number, as stored in the EIP-2935 history
+    /// storage contract at the latest block.
+    ///
+    /// Returns `null` if the hash isn't stored in the contract's ring buffer, which serves the
+    /// hashes of the last 8191 blocks before the latest block once Prague is active.
+    #[method(name = "getHistoricalBlockHash")]
+    async fn reth_get_historical_block_hash(&self, number: U64) -> RpcResult<Option<B256>>;
+
+    /// Returns the progress of the index of transaction hashes, which is used to look up
+    /// transactions by hash, e.g. by `eth_getTransactionByHash`.
+    #[method(name = "getTxLookupStatus")]
+    async fn reth_get_tx_lookup_status(&self) -> RpcResult<TxLookupStatus>;
+
+    /// Returns the processing timings of the canonical blocks in the range, as recorded by the
+    /// engine when the blocks were validated.
+    ///
+    /// Blocks that weren't processed by the engine, e.g. because they were synced by the pipeline,
+    /// are missing. The range is limited to 10000 blocks.
+    #[method(name = "getBlockProcessingHistory")]
+    async fn reth_get_block_processing_history(
+        &self,
+        from_block: U64,
+        to_block: U64,
+    ) -> RpcResult<Vec<BlockProcessingEntry>>;
+}
+
+/// Reth API namespace for the health of the node
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethHealthApi {
+    /// Returns the health of the node.
+    ///
+    /// The network is `degraded` while the number of connected peers is at or below the minimum
+    /// of the peer count watchdog, and `stalled` once discovery is escalated because it stayed
+    /// degraded for too long.
+    #[method(name = "health")]
+    fn reth_health(&self) -> RpcResult<HealthReport>;
+}
+
+/// Progress of the transaction hash index, returned by `reth_getTxLookupStatus`.
+///
+/// The stored blocks are split into the blocks up to `prunedBlock`, whose transaction hashes were
+/// pruned, the blocks up to `indexedBlock`, whose transaction hashes are indexed, and the blocks up
+/// to `storedBlock`, which weren't indexed yet. These are also listed as `segments`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxLookupStatus {
+    /// The highest block whose transaction hashes were pruned from the index, if any.
+    #[serde(default, with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
+    pub pruned_block: Option<u64>,
+    /// The highest block whose transaction hashes are indexed.
+    #[serde(with = "alloy_serde::quantity")]
+    pub indexed_block: u64,
+    /// The highest block whose transactions are stored.
+    #[serde(with = "alloy_serde::quantity")]
+    pub stored_block: u64,
+    /// The number of stored blocks whose transaction hashes aren't indexed yet.
+    #[serde(with = "alloy_serde::quantity")]
+    pub lag: u64,
+    /// The consecutive ranges of stored blocks by the state of their transaction hashes, in
+    /// ascending order.
+    pub segments: Vec<TxLookupSegment>,
+}
+
+/// A range of stored blocks whose transaction hashes are in the same state, see
+/// [`TxLookupStatus`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxLookupSegment {
+    /// The first block of the range.
+    #[serde(with = "alloy_serde::quantity")]
+    pub from_block: u64,
+    /// The last block of the range.
+    #[serde(with = "alloy_serde::quantity")]
+    pub to_block: u64,
+    /// The state of the transaction hashes of the blocks.
+    pub state: TxLookupSegmentState,
+}
+
+/// The state of the transaction hashes of a [`TxLookupSegment`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxLookupSegmentState {
+    /// The transaction hashes were pruned from the index.
+    Pruned,
+    /// The transaction hashes are indexed.
+    Indexed,
+    /// The transaction hashes weren't indexed yet.
+    Unindexed,
+}
+
+/// Processing timings of a block, returned by `reth_getBlockProcessingHistory`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockProcessingEntry {
+    /// The number of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub number: u64,
+    /// The hash of the block.
+    pub hash: B256,
+    /// The gas used by the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// The time it took to execute the block, in microseconds.
+    #[serde(with = "alloy_serde::quantity")]
+    pub execution_micros: u64,
+    /// The time it took to compute the state root of the block, in microseconds.
+    #[serde(with = "alloy_serde::quantity")]
+    pub state_root_micros: u64,
+}
+
+/// Health of the node, returned by `reth_health`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthReport {
+    /// The health of the peer connectivity.
+    pub network: NetworkHealthReport,
+}
+
+/// Health of the peer connectivity of the node.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkHealthReport {
+    /// The status of the network, either `healthy`, `degraded` or `stalled`.
+    pub status: String,
+    /// The number of connected peers.
+    #[serde(with = "alloy_serde::quantity")]
+    pub connected_peers: u64,
+    /// For how many seconds the network has been degraded, if it is.
+    #[serde(default, with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
+    pub degraded_seconds: Option<u64>,
+    /// The number of times discovery was escalated since the network is degraded.
+    #[serde(with = "alloy_serde::quantity")]
+    pub escalations: u64,
 }
@@ -1,7 +1,8 @@
 use alloy_json_rpc::RpcObject;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash, B256};
 use alloy_rpc_types_txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
 
 /// Txpool rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "txpool"))]
@@ -34,4 +35,78 @@ pub trait TxPoolApi<T: RpcObject> {
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     #[method(name = "content")]
     async fn txpool_content(&self) -> RpcResult<TxpoolContent<T>>;
+
+    /// Creates a subscription that streams the lifecycle events of all transactions in the pool.
+    ///
+    /// This allows mirroring the state of the pool without polling `txpool_content`. Events are
+    /// delivered on a best-effort basis: if the subscriber can't keep up, events are skipped.
+    #[subscription(
+        name = "subscribeEvents" => "events",
+        unsubscribe = "unsubscribeEvents",
+        item = TxpoolEvent<T>
+    )]
+    async fn txpool_subscribe_events(&self) -> jsonrpsee::core::SubscriptionResult;
+}
+
+/// A lifecycle event of a transaction in the pool, emitted by `txpool_subscribeEvents`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum TxpoolEvent<T> {
+    /// Transaction was added to a sub-pool.
+    ///
+    /// This is also emitted when an existing transaction moves between sub-pools, e.g. when a
+    /// queued transaction is promoted to pending.
+    Added {
+        /// Hash of the transaction.
+        hash: TxHash,
+        /// The sub-pool the transaction is now in.
+        subpool: TxpoolSubPool,
+        /// The transaction, if it is still in the pool when the event is emitted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        transaction: Option<T>,
+    },
+    /// Transaction was replaced by another transaction with the same sender and nonce.
+    Replaced {
+        /// Hash of the replaced transaction.
+        hash: TxHash,
+        /// Hash of the transaction that replaced it.
+        replaced_by: TxHash,
+    },
+    /// Transaction was included in a canonical block.
+    Mined {
+        /// Hash of the transaction.
+        hash: TxHash,
+        /// Hash of the block that includes the transaction.
+        block_hash: B256,
+    },
+    /// Transaction was removed from the pool without being mined.
+    Dropped {
+        /// Hash of the transaction.
+        hash: TxHash,
+        /// Why the transaction was removed.
+        reason: TxpoolDropReason,
+    },
+}
+
+/// The sub-pool of a transaction reported by [`TxpoolEvent::Added`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxpoolSubPool {
+    /// The transaction is ready for inclusion in the next block.
+    Pending,
+    /// The transaction is waiting for a nonce gap to close or for its fee cap to be met.
+    Queued,
+}
+
+/// The reason a transaction was removed from the pool, reported by [`TxpoolEvent::Dropped`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxpoolDropReason {
+    /// The transaction was evicted, e.g. because the pool reached its size limits or the
+    /// transaction was explicitly removed.
+    Discarded,
+    /// The transaction became invalid, e.g. because its nonce is outdated.
+    Invalid,
+    /// The transaction was not promoted within the configured lifetime of its sub-pool.
+    Expired,
 }
@@ -1,10 +1,14 @@
 //! API for block submission validation.
 
+use alloy_eips::eip7685::RequestsOrHash;
+use alloy_primitives::{BlockHash, B256};
 use alloy_rpc_types_beacon::relay::{
     BuilderBlockValidationRequest, BuilderBlockValidationRequestV2,
     BuilderBlockValidationRequestV3, BuilderBlockValidationRequestV4,
 };
+use alloy_rpc_types_engine::ExecutionPayload;
 use jsonrpsee::proc_macros::rpc;
+use serde::{Deserialize, Serialize};
 
 /// Block validation rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "flashbots"))]
@@ -38,3 +42,64 @@ pub trait BlockSubmissionValidationApi {
         request: BuilderBlockValidationRequestV4,
     ) -> jsonrpsee::core::RpcResult<()>;
 }
+
+/// Payload validation dry-run interface.
+///
+/// Runs the full validation pipeline that a `newPayload` call would go through (consensus checks,
+/// execution and state root computation) without inserting the block or touching forkchoice.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait PayloadValidationApi {
+    /// Validates the given payload against the state of its parent block.
+    ///
+    /// The optional fields mirror the additional `engine_newPayloadV3` and `engine_newPayloadV4`
+    /// arguments and must be provided for payloads of the corresponding forks.
+    #[method(name = "validatePayload")]
+    async fn validate_payload(
+        &self,
+        payload: ExecutionPayload,
+        versioned_hashes: Option<Vec<B256>>,
+        parent_beacon_block_root: Option<B256>,
+        execution_requests: Option<RequestsOrHash>,
+    ) -> jsonrpsee::core::RpcResult<PayloadValidationResponse>;
+}
+
+/// Outcome of an `admin_validatePayload` call.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadValidationResponse {
+    /// Hash of the validated block.
+    pub block_hash: BlockHash,
+    /// Whether the payload passed all validation steps.
+    pub valid: bool,
+    /// The error of the first failed validation step, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Time spent in each validation step.
+    pub timings: PayloadValidationTimings,
+}
+
+/// Per-step timings of a payload validation run, in microseconds.
+///
+/// Steps that were not reached because an earlier step failed are reported as `None`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadValidationTimings {
+    /// Converting the payload into a block and checking that it is well formed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub well_formed: Option<u64>,
+    /// Stateless consensus checks and validation against the parent header.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_execution: Option<u64>,
+    /// Block execution.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution: Option<u64>,
+    /// Post-execution consensus checks (gas used, receipts root, logs bloom, requests).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_execution: Option<u64>,
+    /// State root computation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_root: Option<u64>,
+    /// Total time spent validating the payload.
+    pub total: u64,
+}
@@ -10,7 +10,7 @@ use reth_rpc_api::servers::*;
 use reth_rpc_eth_types::EthSubscriptionIdProvider;
 use reth_rpc_layer::{
     secret_to_bearer_header, AuthClientLayer, AuthClientService, AuthLayer, JwtAuthValidator,
-    JwtSecret,
+    JwtSecret, RotatedJwtSecret, TraceContextLayer,
 };
 use reth_rpc_server_types::constants;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
@@ -26,6 +26,8 @@ pub struct AuthServerConfig {
     pub(crate) socket_addr: SocketAddr,
     /// The secret for the auth layer of the server.
     pub(crate) secret: JwtSecret,
+    /// Additional secrets accepted by the auth layer while they're active.
+    pub(crate) additional_secrets: Vec<RotatedJwtSecret>,
     /// Configs for JSON-RPC Http.
     pub(crate) server_config: ServerBuilder<Identity, Identity>,
     /// Configs for IPC server
@@ -49,11 +51,20 @@ impl AuthServerConfig {
 
     /// Convenience function to start a server in one step.
     pub async fn start(self, module: AuthRpcModule) -> Result<AuthServerHandle, RpcError> {
-        let Self { socket_addr, secret, server_config, ipc_server_config, ipc_endpoint } = self;
-
-        // Create auth middleware.
-        let middleware =
-            tower::ServiceBuilder::new().layer(AuthLayer::new(JwtAuthValidator::new(secret)));
+        let Self {
+            socket_addr,
+            secret,
+            additional_secrets,
+            server_config,
+            ipc_server_config,
+            ipc_endpoint,
+        } = self;
+
+        // Create auth middleware, which continues the trace context of the caller.
+        let validator = JwtAuthValidator::new(secret).with_additional_secrets(additional_secrets);
+        let middleware = tower::ServiceBuilder::new()
+            .layer(TraceContextLayer::new())
+            .layer(AuthLayer::new(validator));
 
         // By default, both http and ws are enabled.
         let server = server_config
@@ -87,6 +98,7 @@ impl AuthServerConfig {
 pub struct AuthServerConfigBuilder {
     socket_addr: Option<SocketAddr>,
     secret: JwtSecret,
+    additional_secrets: Vec<RotatedJwtSecret>,
     server_config: Option<ServerBuilder<Identity, Identity>>,
     ipc_server_config: Option<IpcServerBuilder<Identity, Identity>>,
     ipc_endpoint: Option<String>,
@@ -100,6 +112,7 @@ impl AuthServerConfigBuilder {
         Self {
             socket_addr: None,
             secret,
+            additional_secrets: Vec::new(),
             server_config: None,
             ipc_server_config: None,
             ipc_endpoint: None,
@@ -124,6 +137,16 @@ impl AuthServerConfigBuilder {
         self
     }
 
+    /// Set additional secrets that are accepted while they're active, e.g. the previous secret
+    /// during a rotation.
+    pub fn additional_secrets(
+        mut self,
+        secrets: impl IntoIterator<Item = RotatedJwtSecret>,
+    ) -> Self {
+        self.additional_secrets = secrets.into_iter().collect();
+        self
+    }
+
     /// Configures the JSON-RPC server
     ///
     /// Note: this always configures an [`EthSubscriptionIdProvider`]
@@ -154,6 +177,7 @@ impl AuthServerConfigBuilder {
                 SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), constants::DEFAULT_AUTH_PORT)
             }),
             secret: self.secret,
+            additional_secrets: self.additional_secrets,
             server_config: self.server_config.unwrap_or_else(|| {
                 ServerBuilder::new()
                     // This needs to large enough to handle large eth_getLogs responses and maximum
@@ -1,10 +1,14 @@
-use std::{net::SocketAddr, path::PathBuf};
+use std::{
+    net::SocketAddr,
+    path::PathBuf,
+    time::{Duration, UNIX_EPOCH},
+};
 
 use jsonrpsee::server::ServerBuilder;
 use reth_node_core::{args::RpcServerArgs, utils::get_or_create_jwt_secret_from_path};
 use reth_rpc::ValidationApiConfig;
 use reth_rpc_eth_types::{EthConfig, EthStateCacheConfig, GasPriceOracleConfig};
-use reth_rpc_layer::{JwtError, JwtSecret};
+use reth_rpc_layer::{CompressionConfig, CompressionLevel, JwtError, JwtSecret, RotatedJwtSecret};
 use reth_rpc_server_types::RpcModuleSelection;
 use tower::layer::util::Identity;
 use tracing::{debug, warn};
@@ -55,6 +59,9 @@ pub trait RethRpcServerConfig {
     /// Returns the default ipc server builder
     fn ipc_server_builder(&self) -> IpcServerBuilder<Identity, Identity>;
 
+    /// Returns the compression configuration of http responses, `None` if disabled.
+    fn http_compression_config(&self) -> Option<CompressionConfig>;
+
     /// Creates the [`RpcServerConfig`] from cli args.
     fn rpc_server_config(&self) -> RpcServerConfig;
 
@@ -99,11 +106,16 @@ impl RethRpcServerConfig for RpcServerArgs {
             .max_blocks_per_filter(self.rpc_max_blocks_per_filter.unwrap_or_max())
             .max_logs_per_response(self.rpc_max_logs_per_response.unwrap_or_max() as usize)
             .eth_proof_window(self.rpc_eth_proof_window)
+            .max_proofs_accounts(self.rpc_max_proofs_accounts)
             .rpc_gas_cap(self.rpc_gas_cap)
             .rpc_max_simulate_blocks(self.rpc_max_simulate_blocks)
+            .access_list_delegations(self.rpc_access_list_delegations)
+            .pending_call_pool_txs(self.rpc_pending_call_pool_txs)
             .state_cache(self.state_cache_config())
             .gpo_config(self.gas_price_oracle_config())
             .proof_permits(self.rpc_proof_permits)
+            .tracing_pool_threads(self.rpc_tracing_pool_threads)
+            .max_concurrent_calls(self.rpc_max_concurrent_calls)
     }
 
     fn flashbots_config(&self) -> ValidationApiConfig {
@@ -135,8 +147,10 @@ impl RethRpcServerConfig for RpcServerArgs {
     }
 
     fn transport_rpc_module_config(&self) -> TransportRpcModuleConfig {
-        let mut config = TransportRpcModuleConfig::default()
-            .with_config(RpcModuleConfig::new(self.eth_config(), self.flashbots_config()));
+        let mut config = TransportRpcModuleConfig::default().with_config(
+            RpcModuleConfig::new(self.eth_config(), self.flashbots_config())
+                .with_eth_filters_path(self.rpc_filters_file.clone()),
+        );
 
         if self.http {
             config = config.with_http(
@@ -177,6 +191,20 @@ impl RethRpcServerConfig for RpcServerArgs {
             .max_connections(self.rpc_max_connections.get())
     }
 
+    fn http_compression_config(&self) -> Option<CompressionConfig> {
+        if self.http_disable_compression {
+            return None
+        }
+        let mut config = CompressionConfig::default();
+        if let Some(min_size) = self.http_compression_min_size {
+            config.min_size = min_size;
+        }
+        if let Some(level) = self.http_compression_level {
+            config.level = CompressionLevel::Precise(level.into());
+        }
+        Some(config)
+    }
+
     fn rpc_server_config(&self) -> RpcServerConfig {
         let mut config = RpcServerConfig::default().with_jwt_secret(self.rpc_secret_key());
 
@@ -193,6 +221,7 @@ impl RethRpcServerConfig for RpcServerArgs {
                 .with_http_address(socket_address)
                 .with_http(self.http_ws_server_builder())
                 .with_http_cors(self.http_corsdomain.clone())
+                .with_http_compression(self.http_compression_config())
                 .with_ws_cors(self.ws_allowed_origins.clone());
         }
 
@@ -212,7 +241,31 @@ impl RethRpcServerConfig for RpcServerArgs {
     fn auth_server_config(&self, jwt_secret: JwtSecret) -> Result<AuthServerConfig, RpcError> {
         let address = SocketAddr::new(self.auth_addr, self.auth_port);
 
-        let mut builder = AuthServerConfig::builder(jwt_secret).socket_addr(address);
+        let additional_secrets = self
+            .auth_jwtsecret_additional
+            .iter()
+            .map(|arg| {
+                debug!(target: "reth::cli", path=?arg.path, "Reading additional JWT secret file");
+                let secret = JwtSecret::from_file(&arg.path).map_err(|err| {
+                    RpcError::Custom(format!(
+                        "failed to read JWT secret {}: {err}",
+                        arg.path.display()
+                    ))
+                })?;
+                let mut secret = RotatedJwtSecret::new(secret);
+                if let Some(not_before) = arg.not_before {
+                    secret = secret.with_not_before(UNIX_EPOCH + Duration::from_secs(not_before));
+                }
+                if let Some(not_after) = arg.not_after {
+                    secret = secret.with_not_after(UNIX_EPOCH + Duration::from_secs(not_after));
+                }
+                Ok(secret)
+            })
+            .collect::<Result<Vec<_>, RpcError>>()?;
+
+        let mut builder = AuthServerConfig::builder(jwt_secret)
+            .socket_addr(address)
+            .additional_secrets(additional_secrets);
         if self.auth_ipc {
             builder = builder
                 .ipc_endpoint(self.auth_ipc_path.clone())
@@ -245,6 +298,7 @@ mod tests {
     use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 
     use crate::config::RethRpcServerConfig;
+    use reth_rpc_layer::{CompressionConfig, CompressionLevel};
 
     /// A helper type to parse Args more easily
     #[derive(Parser)]
@@ -354,6 +408,29 @@ mod tests {
         assert_eq!(config.ipc_endpoint().unwrap(), constants::DEFAULT_IPC_ENDPOINT);
     }
 
+    #[test]
+    fn test_http_compression_config() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.http_compression_config(), Some(CompressionConfig::default()));
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--http.compression-min-size",
+            "4096",
+            "--http.compression-level",
+            "5",
+        ])
+        .args;
+        assert_eq!(
+            args.http_compression_config(),
+            Some(CompressionConfig { min_size: 4096, level: CompressionLevel::Precise(5) })
+        );
+
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--http.disable-compression"]).args;
+        assert_eq!(args.http_compression_config(), None);
+    }
+
     #[test]
     fn test_zero_filter_limits() {
         let args = CommandParser::<RpcServerArgs>::parse_from([
@@ -1,6 +1,6 @@
 use reth_rpc::{EthFilter, EthPubSub};
 use reth_rpc_eth_api::EthApiTypes;
-use reth_rpc_eth_types::EthConfig;
+use reth_rpc_eth_types::{EthConfig, EthFilterConfig};
 use reth_tasks::TaskSpawner;
 
 /// Handlers for core, filter and pubsub `eth` namespace APIs.
@@ -26,8 +26,22 @@ where
     where
         Tasks: TaskSpawner + Clone + 'static,
     {
-        let filter =
-            EthFilter::new(eth_api.clone(), config.filter_config(), Box::new(executor.clone()));
+        Self::bootstrap_with_filter_config(config.filter_config(), executor, eth_api)
+    }
+
+    /// Returns a new instance with the additional handlers for the `eth` namespace, with the given
+    /// filter config, e.g. to persist the installed filters.
+    ///
+    /// This will spawn all necessary tasks for the additional handlers.
+    pub fn bootstrap_with_filter_config<Tasks>(
+        filter_config: EthFilterConfig,
+        executor: Tasks,
+        eth_api: EthApi,
+    ) -> Self
+    where
+        Tasks: TaskSpawner + Clone + 'static,
+    {
+        let filter = EthFilter::new(eth_api.clone(), filter_config, Box::new(executor.clone()));
 
         let pubsub = EthPubSub::with_spawner(eth_api.clone(), Box::new(executor));
 
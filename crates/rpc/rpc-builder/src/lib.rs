@@ -38,12 +38,14 @@ use reth_evm::{execute::BlockExecutorProvider, ConfigureEvm};
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
 use reth_primitives_traits::NodePrimitives;
 use reth_provider::{
-    AccountReader, BlockReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-    ChangeSetReader, FullRpcProvider, ProviderBlock, StateProviderFactory,
+    AccountReader, BlockProcessingHistoryReader, BlockReader, BlockReaderIdExt,
+    CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, DepositIndexReader,
+    FullRpcProvider, ProviderBlock, StateProviderFactory, TransactionLookupIndexReader,
+    TransactionLookupIndexWriter,
 };
 use reth_rpc::{
-    AdminApi, DebugApi, EngineEthApi, EthApi, EthApiBuilder, EthBundle, MinerApi, NetApi,
-    OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, ValidationApiConfig, Web3Api,
+    AaApi, AdminApi, DebugApi, EngineEthApi, EthApi, EthApiBuilder, EthBundle, HealthApi, MinerApi,
+    NetApi, OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, ValidationApiConfig, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
@@ -51,7 +53,9 @@ use reth_rpc_eth_api::{
     EthApiServer, EthApiTypes, FullEthApiServer, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
 };
 use reth_rpc_eth_types::{EthConfig, EthSubscriptionIdProvider};
-use reth_rpc_layer::{AuthLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret};
+use reth_rpc_layer::{
+    AuthLayer, Claims, CompressionConfig, CompressionLayer, JwtAuthValidator, JwtSecret,
+};
 use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{noop::NoopTransactionPool, PoolTransaction, TransactionPool};
 use serde::{Deserialize, Serialize};
@@ -59,6 +63,7 @@ use std::{
     collections::HashMap,
     fmt::Debug,
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    path::PathBuf,
     sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
@@ -118,7 +123,11 @@ where
     Provider: FullRpcProvider<Block = N::Block, Receipt = N::Receipt, Header = N::BlockHeader>
         + CanonStateSubscriptions<Primitives = N>
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + DepositIndexReader
+        + TransactionLookupIndexReader
+        + TransactionLookupIndexWriter
+        + BlockProcessingHistoryReader,
     Pool: TransactionPool + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
@@ -538,7 +547,11 @@ where
     Provider: FullRpcProvider<Block = N::Block, Receipt = N::Receipt, Header = N::BlockHeader>
         + CanonStateSubscriptions<Primitives = N>
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + DepositIndexReader
+        + TransactionLookupIndexReader
+        + TransactionLookupIndexWriter
+        + BlockProcessingHistoryReader,
     Pool: TransactionPool + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
@@ -673,6 +686,9 @@ pub struct RpcModuleConfig {
     eth: EthConfig,
     /// `flashbots` namespace settings
     flashbots: ValidationApiConfig,
+    /// The file that installed `eth` log and block filters are persisted to, so they survive
+    /// restarts. Filters are only kept in memory if `None`.
+    eth_filters_path: Option<PathBuf>,
 }
 
 // === impl RpcModuleConfig ===
@@ -685,7 +701,13 @@ impl RpcModuleConfig {
 
     /// Returns a new RPC module config given the eth namespace config
     pub const fn new(eth: EthConfig, flashbots: ValidationApiConfig) -> Self {
-        Self { eth, flashbots }
+        Self { eth, flashbots, eth_filters_path: None }
+    }
+
+    /// Configures the file that installed `eth` log and block filters are persisted to
+    pub fn with_eth_filters_path(mut self, path: Option<PathBuf>) -> Self {
+        self.eth_filters_path = path;
+        self
     }
 
     /// Get a reference to the eth namespace config
@@ -724,7 +746,7 @@ impl RpcModuleConfigBuilder {
     /// Consumes the type and creates the [`RpcModuleConfig`]
     pub fn build(self) -> RpcModuleConfig {
         let Self { eth, flashbots } = self;
-        RpcModuleConfig { eth: eth.unwrap_or_default(), flashbots: flashbots.unwrap_or_default() }
+        RpcModuleConfig::new(eth.unwrap_or_default(), flashbots.unwrap_or_default())
     }
 
     /// Get a reference to the eth namespace config, if any
@@ -807,7 +829,10 @@ where
     {
         let blocking_pool_guard = BlockingTaskGuard::new(config.eth.max_tracing_requests);
 
-        let eth = EthHandlers::bootstrap(config.eth, executor.clone(), eth_api);
+        let filter_config =
+            config.eth.filter_config().persistence_path(config.eth_filters_path.clone());
+        let eth =
+            EthHandlers::bootstrap_with_filter_config(filter_config, executor.clone(), eth_api);
 
         Self {
             provider,
@@ -919,7 +944,11 @@ where
             Receipt = N::Receipt,
             Transaction = N::SignedTx,
         > + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + DepositIndexReader
+        + TransactionLookupIndexReader
+        + TransactionLookupIndexWriter
+        + BlockProcessingHistoryReader,
     Network: NetworkInfo + Peers + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
     EthApi: EthApiServer<
@@ -1008,8 +1037,9 @@ where
     ///
     /// If called outside of the tokio runtime.
     pub fn register_reth(&mut self) -> &mut Self {
-        let rethapi = self.reth_api();
-        self.modules.insert(RethRpcModule::Reth, rethapi.into_rpc().into());
+        let mut rethapi = self.reth_api().into_rpc();
+        rethapi.merge(self.health_api().into_rpc()).expect("No conflicts");
+        self.modules.insert(RethRpcModule::Reth, rethapi.into());
         self
     }
 
@@ -1034,7 +1064,11 @@ where
             Transaction = N::SignedTx,
             Receipt = N::Receipt,
         > + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + DepositIndexReader
+        + TransactionLookupIndexReader
+        + TransactionLookupIndexWriter
+        + BlockProcessingHistoryReader,
     Network: NetworkInfo + Peers + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
     EthApi: EthApiTypes,
@@ -1099,6 +1133,11 @@ where
     pub fn reth_api(&self) -> RethApi<Provider> {
         RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
     }
+
+    /// Instantiates `HealthApi`
+    pub fn health_api(&self) -> HealthApi<Network> {
+        HealthApi::new(self.network.clone())
+    }
 }
 
 impl<N, Provider, Pool, Network, Tasks, EthApi, BlockExecutor, Consensus>
@@ -1108,7 +1147,11 @@ where
     Provider: FullRpcProvider<Block = N::Block>
         + CanonStateSubscriptions<Primitives = N>
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + DepositIndexReader
+        + TransactionLookupIndexReader
+        + TransactionLookupIndexWriter
+        + BlockProcessingHistoryReader,
     Pool: TransactionPool + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
@@ -1193,9 +1236,13 @@ where
                     .entry(namespace)
                     .or_insert_with(|| match namespace {
                         RethRpcModule::Admin => {
-                            AdminApi::new(self.network.clone(), self.provider.chain_spec())
-                                .into_rpc()
-                                .into()
+                            let mut module =
+                                AdminApi::new(self.network.clone(), self.provider.chain_spec())
+                                    .into_rpc();
+                            module
+                                .merge(AdminTxLookupApiServer::into_rpc(self.reth_api()))
+                                .expect("No conflicts");
+                            module.into()
                         }
                         RethRpcModule::Debug => DebugApi::new(
                             eth_api.clone(),
@@ -1207,7 +1254,9 @@ where
                         RethRpcModule::Eth => {
                             // merge all eth handlers
                             let mut module = eth_api.clone().into_rpc();
-                            module.merge(eth_filter.clone().into_rpc()).expect("No conflicts");
+                            module
+                                .merge(EthFilterApiServer::into_rpc(eth_filter.clone()))
+                                .expect("No conflicts");
                             module.merge(eth_pubsub.clone().into_rpc()).expect("No conflicts");
                             module
                                 .merge(
@@ -1232,9 +1281,10 @@ where
                         .into_rpc()
                         .into(),
                         RethRpcModule::Web3 => Web3Api::new(self.network.clone()).into_rpc().into(),
-                        RethRpcModule::Txpool => TxPoolApi::new(
+                        RethRpcModule::Txpool => TxPoolApi::with_spawner(
                             self.eth.api.pool().clone(),
                             self.eth.api.tx_resp_builder().clone(),
+                            Box::new(self.executor.clone()),
                         )
                         .into_rpc()
                         .into(),
@@ -1248,9 +1298,24 @@ where
                         .into(),
                         RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
                         RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
-                                .into_rpc()
-                                .into()
+                            let mut module = RethApi::new(
+                                self.provider.clone(),
+                                Box::new(self.executor.clone()),
+                            )
+                            .into_rpc();
+                            module
+                                .merge(HealthApi::new(self.network.clone()).into_rpc())
+                                .expect("No conflicts");
+                            module
+                                .merge(RethFilterApiServer::into_rpc(eth_filter.clone()))
+                                .expect("No conflicts");
+                            module
+                                .merge(RethBlockApiServer::into_rpc(eth_api.clone()))
+                                .expect("No conflicts");
+                            module
+                                .merge(RethStateApiServer::into_rpc(eth_api.clone()))
+                                .expect("No conflicts");
+                            module.into()
                         }
                         // only relevant for Ethereum and configured in `EthereumAddOns`
                         // implementation
@@ -1262,6 +1327,11 @@ where
                                 .into_rpc()
                                 .into()
                         }
+                        RethRpcModule::Aa => {
+                            AaApi::new(eth_api.clone(), self.blocking_pool_guard.clone())
+                                .into_rpc()
+                                .into()
+                        }
                     })
                     .clone()
             })
@@ -1286,6 +1356,8 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     http_server_config: Option<ServerBuilder<Identity, Identity>>,
     /// Allowed CORS Domains for http
     http_cors_domains: Option<String>,
+    /// Compression of http responses, if enabled.
+    http_compression: Option<CompressionConfig>,
     /// Address where to bind the http server to
     http_addr: Option<SocketAddr>,
     /// Configs for WS server
@@ -1312,6 +1384,7 @@ impl Default for RpcServerConfig<Identity> {
         Self {
             http_server_config: None,
             http_cors_domains: None,
+            http_compression: Some(CompressionConfig::default()),
             http_addr: None,
             ws_server_config: None,
             ws_cors_domains: None,
@@ -1375,6 +1448,7 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         RpcServerConfig {
             http_server_config: self.http_server_config,
             http_cors_domains: self.http_cors_domains,
+            http_compression: self.http_compression,
             http_addr: self.http_addr,
             ws_server_config: self.ws_server_config,
             ws_cors_domains: self.ws_cors_domains,
@@ -1403,6 +1477,14 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configures the compression of http responses, `None` disables it.
+    ///
+    /// By default, responses are compressed with the default [`CompressionConfig`].
+    pub const fn with_http_compression(mut self, compression: Option<CompressionConfig>) -> Self {
+        self.http_compression = compression;
+        self
+    }
+
     /// Configures the [`SocketAddr`] of the http server
     ///
     /// Default is [`Ipv4Addr::LOCALHOST`] and
@@ -1490,9 +1572,9 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
     }
 
     /// Returns a [`CompressionLayer`] that adds compression support (gzip, deflate, brotli, zstd)
-    /// based on the client's `Accept-Encoding` header
-    fn maybe_compression_layer() -> Option<CompressionLayer> {
-        Some(CompressionLayer::new())
+    /// based on the client's `Accept-Encoding` header, if enabled
+    fn maybe_compression_layer(config: Option<CompressionConfig>) -> Option<CompressionLayer> {
+        config.map(CompressionLayer::with_config)
     }
 
     /// Builds and starts the configured server(s): http, ws, ipc.
@@ -1560,7 +1642,7 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                         tower::ServiceBuilder::new()
                             .option_layer(Self::maybe_cors_layer(cors)?)
                             .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
-                            .option_layer(Self::maybe_compression_layer()),
+                            .option_layer(Self::maybe_compression_layer(self.http_compression)),
                     )
                     .set_rpc_middleware(
                         self.rpc_middleware.clone().layer(
@@ -1634,7 +1716,7 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
                         .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
-                        .option_layer(Self::maybe_compression_layer()),
+                        .option_layer(Self::maybe_compression_layer(self.http_compression)),
                 )
                 .set_rpc_middleware(
                     self.rpc_middleware.clone().layer(
@@ -23,9 +23,9 @@ use reth_network_peers::NodeRecord;
 use reth_rpc_api::{
     clients::{AdminApiClient, EthApiClient},
     DebugApiClient, EthCallBundleApiClient, EthFilterApiClient, NetApiClient, OtterscanClient,
-    TraceApiClient, Web3ApiClient,
+    RethStateApiClient, TraceApiClient, Web3ApiClient,
 };
-use reth_rpc_server_types::RethRpcModule;
+use reth_rpc_server_types::{constants::DEFAULT_MAX_PROOFS_ACCOUNTS, RethRpcModule};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashSet;
@@ -146,6 +146,7 @@ where
     AdminApiClient::add_trusted_peer(client, node.into()).await.unwrap();
     AdminApiClient::remove_trusted_peer(client, node.into()).await.unwrap();
     AdminApiClient::node_info(client).await.unwrap();
+    assert!(AdminApiClient::peers_health(client).await.unwrap().is_empty());
 }
 
 async fn test_basic_eth_calls<C>(client: &C)
@@ -418,6 +419,27 @@ where
     Web3ApiClient::sha3(client, Bytes::default()).await.unwrap();
 }
 
+async fn test_basic_reth_calls<C>(client: &C)
+where
+    C: ClientT + SubscriptionClientT + Sync,
+{
+    let accounts = [Address::with_last_byte(1), Address::with_last_byte(2)];
+
+    // the proofs are returned in the order of the requested accounts
+    let proofs = RethStateApiClient::reth_get_proofs(
+        client,
+        accounts.iter().map(|address| (*address, vec![])).collect(),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(proofs.iter().map(|proof| proof.address).collect::<Vec<_>>(), accounts);
+
+    // the number of accounts is limited
+    let too_many = vec![(Address::default(), vec![]); DEFAULT_MAX_PROOFS_ACCOUNTS + 1];
+    RethStateApiClient::reth_get_proofs(client, too_many, None).await.unwrap_err();
+}
+
 async fn test_basic_otterscan_calls<C>(client: &C)
 where
     C: ClientT + SubscriptionClientT + Sync,
@@ -671,6 +693,24 @@ async fn test_call_web3_functions_http_and_ws() {
     test_basic_web3_calls(&client).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_reth_functions_http() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_http(vec![RethRpcModule::Reth]).await;
+    let client = handle.http_client().unwrap();
+    test_basic_reth_calls(&client).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_reth_functions_ws() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_ws(vec![RethRpcModule::Reth]).await;
+    let client = handle.ws_client().await.unwrap();
+    test_basic_reth_calls(&client).await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_call_otterscan_functions_http() {
     reth_tracing::init_test_tracing();
@@ -1,3 +1,5 @@
+use reth_chainspec::{EthereumHardfork, EthereumHardforks, ForkCondition};
+use reth_engine_primitives::{ExchangedCapabilities, MissingCapability};
 use std::collections::HashSet;
 
 /// The list of all supported Engine capabilities available over the engine endpoint.
@@ -20,23 +22,52 @@ pub const CAPABILITIES: &[&str] = &[
     "engine_getBlobsV1",
 ];
 
+/// The engine API methods introduced by the forks that are activated by timestamp.
+const FORK_CAPABILITIES: &[(EthereumHardfork, &[&str])] = &[
+    (
+        EthereumHardfork::Shanghai,
+        &["engine_forkchoiceUpdatedV2", "engine_getPayloadV2", "engine_newPayloadV2"],
+    ),
+    (
+        EthereumHardfork::Cancun,
+        &["engine_forkchoiceUpdatedV3", "engine_getPayloadV3", "engine_newPayloadV3"],
+    ),
+    (EthereumHardfork::Prague, &["engine_getPayloadV4", "engine_newPayloadV4"]),
+];
+
 // The list of all supported Engine capabilities available over the engine endpoint.
 ///
 /// Latest spec: Prague
 #[derive(Debug, Clone)]
 pub struct EngineCapabilities {
     inner: HashSet<String>,
+    /// The capabilities exchanged with the consensus client.
+    exchanged: ExchangedCapabilities,
 }
 
 impl EngineCapabilities {
     /// Creates a new `EngineCapabilities` instance with the given capabilities.
     pub fn new(capabilities: impl IntoIterator<Item: Into<String>>) -> Self {
-        Self { inner: capabilities.into_iter().map(Into::into).collect() }
+        Self {
+            inner: capabilities.into_iter().map(Into::into).collect(),
+            exchanged: Default::default(),
+        }
     }
 
     /// Returns the list of all supported Engine capabilities for Prague spec.
     fn prague() -> Self {
-        Self { inner: CAPABILITIES.iter().copied().map(str::to_owned).collect() }
+        Self::new(CAPABILITIES.iter().copied())
+    }
+
+    /// Records the capabilities exchanged with the consensus client in the given record.
+    pub fn with_exchanged_capabilities(mut self, exchanged: ExchangedCapabilities) -> Self {
+        self.exchanged = exchanged;
+        self
+    }
+
+    /// Returns the record of the capabilities exchanged with the consensus client.
+    pub const fn exchanged_capabilities(&self) -> &ExchangedCapabilities {
+        &self.exchanged
     }
 
     /// Returns the list of all supported Engine capabilities.
@@ -53,6 +84,46 @@ impl EngineCapabilities {
     pub fn remove_capability(&mut self, capability: &str) -> Option<String> {
         self.inner.take(capability)
     }
+
+    /// Returns the supported methods that are required by the latest active fork or by an
+    /// upcoming fork at the given timestamp, but aren't in the capabilities of the consensus
+    /// client.
+    pub fn missing_capabilities<ChainSpec: EthereumHardforks>(
+        &self,
+        chain_spec: &ChainSpec,
+        consensus_capabilities: &[String],
+        timestamp: u64,
+    ) -> Vec<MissingCapability> {
+        let latest_active = FORK_CAPABILITIES.iter().rposition(|(fork, _)| {
+            chain_spec.ethereum_fork_activation(*fork).active_at_timestamp(timestamp)
+        });
+
+        let mut missing = Vec::new();
+        for (fork, methods) in FORK_CAPABILITIES.iter().skip(latest_active.unwrap_or_default()) {
+            let ForkCondition::Timestamp(activation_timestamp) =
+                chain_spec.ethereum_fork_activation(*fork)
+            else {
+                continue
+            };
+            missing.extend(
+                methods
+                    .iter()
+                    .filter(|method| {
+                        self.inner.contains(**method) &&
+                            !consensus_capabilities
+                                .iter()
+                                .any(|capability| capability == **method)
+                    })
+                    .map(|method| MissingCapability {
+                        method: method.to_string(),
+                        fork: fork.to_string(),
+                        activation_timestamp,
+                        active: activation_timestamp <= timestamp,
+                    }),
+            );
+        }
+        missing
+    }
 }
 
 impl Default for EngineCapabilities {
@@ -60,3 +131,46 @@ impl Default for EngineCapabilities {
         Self::prague()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::{ChainSpecBuilder, MAINNET};
+
+    #[test]
+    fn flags_missing_capabilities_of_upcoming_fork() {
+        let chain_spec = ChainSpecBuilder::from(&*MAINNET)
+            .cancun_activated()
+            .with_fork(EthereumHardfork::Prague, ForkCondition::Timestamp(1_000))
+            .build();
+        let capabilities = EngineCapabilities::default();
+
+        // the consensus client only supports the methods up to Shanghai
+        let consensus_capabilities = CAPABILITIES
+            .iter()
+            .filter(|method| !method.ends_with("V3") && !method.ends_with("V4"))
+            .map(|method| method.to_string())
+            .collect::<Vec<_>>();
+
+        let missing = capabilities.missing_capabilities(&chain_spec, &consensus_capabilities, 500);
+        let methods = missing.iter().map(|m| (m.method.as_str(), m.active)).collect::<Vec<_>>();
+        assert_eq!(
+            methods,
+            [
+                ("engine_forkchoiceUpdatedV3", true),
+                ("engine_getPayloadV3", true),
+                ("engine_newPayloadV3", true),
+                ("engine_getPayloadV4", false),
+                ("engine_newPayloadV4", false),
+            ]
+        );
+        assert_eq!(missing[3].fork, "Prague");
+        assert_eq!(missing[3].activation_timestamp, 1_000);
+
+        // only the methods of the latest active fork are required once all forks are active
+        let missing =
+            capabilities.missing_capabilities(&chain_spec, &consensus_capabilities, 1_000);
+        assert!(missing.iter().all(|m| m.fork == "Prague" && m.active));
+        assert_eq!(missing.len(), 2);
+    }
+}
@@ -16,7 +16,10 @@ use async_trait::async_trait;
 use jsonrpsee_core::{server::RpcModule, RpcResult};
 use parking_lot::Mutex;
 use reth_chainspec::{EthereumHardfork, EthereumHardforks};
-use reth_engine_primitives::{BeaconConsensusEngineHandle, EngineTypes, EngineValidator};
+use reth_engine_primitives::{
+    BeaconConsensusEngineHandle, CapabilitiesHandshake, EngineTypes, EngineValidator,
+    MissingCapability,
+};
 use reth_payload_builder::PayloadStore;
 use reth_payload_primitives::{
     validate_payload_timestamp, EngineApiMessageVersion, ExecutionPayload,
@@ -27,9 +30,12 @@ use reth_rpc_api::{EngineApiServer, IntoEngineApiRpcModule};
 use reth_storage_api::{BlockReader, HeaderProvider, StateProviderFactory};
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::TransactionPool;
-use std::{sync::Arc, time::Instant};
+use std::{
+    sync::Arc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
 use tokio::sync::oneshot;
-use tracing::{trace, warn};
+use tracing::{info, trace, warn};
 
 /// The Engine API response sender.
 pub type EngineApiSender<Ok> = oneshot::Sender<EngineApiResult<Ok>>;
@@ -827,6 +833,47 @@ where
         &self.inner.capabilities
     }
 
+    /// Records the capabilities of the consensus client and warns about the methods it doesn't
+    /// support, although they are required by the latest active fork or an upcoming fork.
+    ///
+    /// The handshake is only logged if it changed since the last exchange.
+    pub fn record_consensus_capabilities(&self, capabilities: Vec<String>) {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let missing = self.inner.capabilities.missing_capabilities(
+            &*self.inner.chain_spec,
+            &capabilities,
+            timestamp,
+        );
+
+        let exchanged = self.inner.capabilities.exchanged_capabilities();
+        if exchanged
+            .last()
+            .is_none_or(|last| last.capabilities != capabilities || last.missing != missing)
+        {
+            info!(target: "rpc::engine", ?capabilities, "Exchanged engine API capabilities");
+            for MissingCapability { method, fork, activation_timestamp, active } in &missing {
+                if *active {
+                    warn!(
+                        target: "rpc::engine",
+                        %method,
+                        %fork,
+                        "Consensus client doesn't support engine API method of the active fork"
+                    );
+                } else {
+                    warn!(
+                        target: "rpc::engine",
+                        %method,
+                        %fork,
+                        activation_timestamp,
+                        "Consensus client doesn't support engine API method of an upcoming fork"
+                    );
+                }
+            }
+        }
+
+        exchanged.record(CapabilitiesHandshake { timestamp, capabilities, missing });
+    }
+
     fn get_blobs_v1(
         &self,
         versioned_hashes: Vec<B256>,
@@ -1126,7 +1173,9 @@ where
 
     /// Handler for `engine_exchangeCapabilitiesV1`
     /// See also <https://github.com/ethereum/execution-apis/blob/6452a6b194d7db269bf1dbd087a267251d3cc7f8/src/engine/common.md#capabilities>
-    async fn exchange_capabilities(&self, _capabilities: Vec<String>) -> RpcResult<Vec<String>> {
+    async fn exchange_capabilities(&self, capabilities: Vec<String>) -> RpcResult<Vec<String>> {
+        trace!(target: "rpc::engine", "Serving engine_exchangeCapabilities");
+        self.record_consensus_capabilities(capabilities);
         Ok(self.capabilities().list())
     }
 
@@ -139,7 +139,8 @@ impl From<EngineApiError> for jsonrpsee_types::error::ErrorObject<'static> {
                 )
             }
             EngineApiError::EngineObjectValidationError(
-                EngineObjectValidationError::PayloadAttributes(_),
+                EngineObjectValidationError::PayloadAttributes(_) |
+                EngineObjectValidationError::InvalidPayloadAttributes(_),
             ) => {
                 // Note: the data field is not required by the spec, but is also included by other
                 // clients
@@ -246,6 +247,16 @@ mod tests {
             )),
         );
 
+        ensure_engine_rpc_error(
+            INVALID_PAYLOAD_ATTRIBUTES,
+            INVALID_PAYLOAD_ATTRIBUTES_MSG,
+            EngineApiError::EngineObjectValidationError(
+                EngineObjectValidationError::invalid_payload_attributes(std::io::Error::other(
+                    "too many withdrawals",
+                )),
+            ),
+        );
+
         ensure_engine_rpc_error(
             UNKNOWN_PAYLOAD_CODE,
             "Unknown payload",
@@ -13,6 +13,7 @@ use alloy_rpc_types_eth::{
 };
 use alloy_serde::JsonStorageKey;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_eth_types::BlockFields;
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 use tracing::trace;
 
@@ -809,3 +810,97 @@ where
         Ok(EthState::get_proof(self, address, keys, block_number)?.await?)
     }
 }
+
+/// Rpc interface for the reth specific variants of the `eth_` block methods.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethBlockApi<B: RpcObject> {
+    /// Returns information about a block by hash, like `eth_getBlockByHash`, without the optional
+    /// fields excluded by [`BlockFields`].
+    #[method(name = "getBlockByHash")]
+    async fn reth_get_block_by_hash(
+        &self,
+        hash: B256,
+        full: bool,
+        fields: Option<BlockFields>,
+    ) -> RpcResult<Option<B>>;
+
+    /// Returns information about a block by number, like `eth_getBlockByNumber`, without the
+    /// optional fields excluded by [`BlockFields`].
+    #[method(name = "getBlockByNumber")]
+    async fn reth_get_block_by_number(
+        &self,
+        number: BlockNumberOrTag,
+        full: bool,
+        fields: Option<BlockFields>,
+    ) -> RpcResult<Option<B>>;
+}
+
+#[async_trait::async_trait]
+impl<T> RethBlockApiServer<RpcBlock<T::NetworkTypes>> for T
+where
+    T: FullEthApi,
+{
+    /// Handler for: `reth_getBlockByHash`
+    async fn reth_get_block_by_hash(
+        &self,
+        hash: B256,
+        full: bool,
+        fields: Option<BlockFields>,
+    ) -> RpcResult<Option<RpcBlock<T::NetworkTypes>>> {
+        trace!(target: "rpc::eth", ?hash, ?full, ?fields, "Serving reth_getBlockByHash");
+        let block = EthBlocks::rpc_block(self, hash.into(), full).await.map_err(Into::into)?;
+        Ok(block.map(|mut block| {
+            fields.unwrap_or_default().apply(&mut block);
+            block
+        }))
+    }
+
+    /// Handler for: `reth_getBlockByNumber`
+    async fn reth_get_block_by_number(
+        &self,
+        number: BlockNumberOrTag,
+        full: bool,
+        fields: Option<BlockFields>,
+    ) -> RpcResult<Option<RpcBlock<T::NetworkTypes>>> {
+        trace!(target: "rpc::eth", ?number, ?full, ?fields, "Serving reth_getBlockByNumber");
+        let block = EthBlocks::rpc_block(self, number.into(), full).await.map_err(Into::into)?;
+        Ok(block.map(|mut block| {
+            fields.unwrap_or_default().apply(&mut block);
+            block
+        }))
+    }
+}
+
+/// Rpc interface for the reth specific variants of the `eth_` state methods.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethStateApi {
+    /// Returns the account and storage values of multiple accounts including the Merkle-proofs,
+    /// like `eth_getProof` for each of the accounts.
+    ///
+    /// All proofs are generated in a single pass over the state trie and returned in the order of
+    /// the requested accounts.
+    #[method(name = "getProofs")]
+    async fn reth_get_proofs(
+        &self,
+        accounts: Vec<(Address, Vec<JsonStorageKey>)>,
+        block_number: Option<BlockId>,
+    ) -> RpcResult<Vec<EIP1186AccountProofResponse>>;
+}
+
+#[async_trait::async_trait]
+impl<T> RethStateApiServer for T
+where
+    T: FullEthApi,
+{
+    /// Handler for: `reth_getProofs`
+    async fn reth_get_proofs(
+        &self,
+        accounts: Vec<(Address, Vec<JsonStorageKey>)>,
+        block_number: Option<BlockId>,
+    ) -> RpcResult<Vec<EIP1186AccountProofResponse>> {
+        trace!(target: "rpc::eth", ?accounts, ?block_number, "Serving reth_getProofs");
+        Ok(EthState::get_proofs(self, accounts, block_number)?.await?)
+    }
+}
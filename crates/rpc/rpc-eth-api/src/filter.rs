@@ -1,8 +1,10 @@
 //! `eth_` RPC API for filtering.
 
 use alloy_json_rpc::RpcObject;
+use alloy_primitives::Bytes;
 use alloy_rpc_types_eth::{Filter, FilterChanges, FilterId, Log, PendingTransactionFilterKind};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_eth_types::logs_utils::LogsPage;
 use std::future::Future;
 
 /// Rpc Interface for poll-based ethereum filter API.
@@ -41,6 +43,20 @@ pub trait EthFilterApi<T: RpcObject> {
     async fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>>;
 }
 
+/// Rpc Interface for the reth specific extensions of the ethereum filter API.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethFilterApi {
+    /// Returns a page of the logs matching given filter object, starting at the position of the
+    /// continuation token, if any.
+    ///
+    /// Instead of failing if the query exceeds the configured [`QueryLimits`], the page ends early
+    /// and contains the continuation token of the next page. The query fails if the block of the
+    /// continuation token was reorged, since the previous pages may contain reorged logs.
+    #[method(name = "getLogsPaged")]
+    async fn logs_paged(&self, filter: Filter, cursor: Option<Bytes>) -> RpcResult<LogsPage>;
+}
+
 /// Limits for logs queries
 #[derive(Default, Debug, Clone, Copy)]
 pub struct QueryLimits {
@@ -2,7 +2,7 @@
 //! are executed on the `tokio` runtime.
 
 use futures::Future;
-use reth_rpc_eth_types::EthApiError;
+use reth_rpc_eth_types::{EthApiError, ProofTaskPool, ProofTaskPriority};
 use reth_tasks::{
     pool::{BlockingTaskGuard, BlockingTaskPool},
     TaskSpawner,
@@ -18,14 +18,22 @@ pub trait SpawnBlocking: EthApiTypes + Clone + Send + Sync + 'static {
     /// Runtime access in default trait method implementations.
     fn io_task_spawner(&self) -> impl TaskSpawner;
 
-    /// Returns a handle for spawning CPU heavy blocking tasks.
+    /// Returns a handle for spawning CPU heavy blocking tasks of the `debug` and `trace`
+    /// namespaces.
     ///
     /// Thread pool access in default trait method implementations.
     fn tracing_task_pool(&self) -> &BlockingTaskPool;
 
+    /// Returns handle to semaphore bounding the calls and simulations of the `eth` namespace, such
+    /// as `eth_call` and `eth_estimateGas`.
+    fn call_task_guard(&self) -> &BlockingTaskGuard;
+
     /// Returns handle to semaphore for pool of CPU heavy blocking tasks.
     fn tracing_task_guard(&self) -> &BlockingTaskGuard;
 
+    /// Returns the pool dedicated to state proof generation.
+    fn proof_task_pool(&self) -> &ProofTaskPool;
+
     /// See also [`Semaphore::acquire_owned`](`tokio::sync::Semaphore::acquire_owned`).
     fn acquire_owned(
         &self,
@@ -74,4 +82,50 @@ pub trait SpawnBlocking: EthApiTypes + Clone + Send + Sync + 'static {
         let fut = self.tracing_task_pool().spawn(move || f(this));
         async move { fut.await.map_err(|_| EthApiError::InternalBlockingTaskError)? }
     }
+
+    /// Executes a call or simulation of the `eth` namespace on a new blocking task, once a permit
+    /// of the [`call_task_guard`](Self::call_task_guard) is available.
+    ///
+    /// Note: Calls are usually dominated by state reads, so these run on blocking IO tasks like
+    /// [`spawn_blocking_io`](Self::spawn_blocking_io), bounded separately from the tracing pool so
+    /// that bursts of tracing requests don't delay them.
+    fn spawn_call<F, R>(&self, f: F) -> impl Future<Output = Result<R, Self::Error>> + Send
+    where
+        F: FnOnce(Self) -> Result<R, Self::Error> + Send + 'static,
+        R: Send + 'static,
+    {
+        let this = self.clone();
+        async move {
+            let permit = this
+                .call_task_guard()
+                .clone()
+                .acquire_owned()
+                .await
+                .map_err(|_| EthApiError::InternalEthError)?;
+            this.spawn_blocking_io(move |this| {
+                let _permit = permit;
+                f(this)
+            })
+            .await
+        }
+    }
+
+    /// Executes a blocking task on the proof pool, once a worker is available for the given
+    /// priority.
+    ///
+    /// Note: This is expected for state proof and witness generation, which is kept separate from
+    /// the tracing pool so neither can starve the other.
+    fn spawn_proof<F, R>(
+        &self,
+        priority: ProofTaskPriority,
+        f: F,
+    ) -> impl Future<Output = Result<R, Self::Error>> + Send
+    where
+        F: FnOnce(Self) -> Result<R, Self::Error> + Send + 'static,
+        R: Send + 'static,
+    {
+        let this = self.clone();
+        let pool = self.proof_task_pool().clone();
+        async move { pool.spawn(priority, move || f(this)).await? }
+    }
 }
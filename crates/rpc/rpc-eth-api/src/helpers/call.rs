@@ -6,8 +6,8 @@ use crate::{
     helpers::estimate::EstimateCall, FromEvmError, FullEthApiTypes, RpcBlock, RpcNodeCore,
 };
 use alloy_consensus::BlockHeader;
-use alloy_eips::eip2930::AccessListResult;
-use alloy_primitives::{Bytes, B256, U256};
+use alloy_eips::eip2930::{AccessList, AccessListItem, AccessListResult};
+use alloy_primitives::{Address, Bytes, TxKind, B256, U256};
 use alloy_rpc_types_eth::{
     simulate::{SimBlock, SimulatePayload, SimulatedBlock},
     state::{EvmOverrides, StateOverride},
@@ -22,19 +22,23 @@ use reth_evm::{
 };
 use reth_node_api::{BlockBody, NodePrimitives};
 use reth_primitives_traits::{Recovered, SealedHeader, SignedTransaction};
-use reth_provider::{BlockIdReader, ProviderHeader, ProviderTx};
+use reth_provider::{BlockIdReader, ProviderHeader, ProviderTx, StateProvider};
 use reth_revm::{
     database::StateProviderDatabase,
-    db::{CacheDB, State},
+    db::{CacheDB, DbAccount, State},
+    state::AccountInfo,
     DatabaseRef,
 };
 use reth_rpc_eth_types::{
     cache::db::{StateCacheDbRefMutWrapper, StateProviderTraitObjWrapper},
     error::{api::FromEvmHalt, ensure_success, FromEthApiError},
-    revm_utils::{apply_block_overrides, apply_state_overrides, caller_gas_allowance},
+    revm_utils::{
+        apply_block_overrides, apply_state_overrides, caller_gas_allowance, delegation_target,
+    },
     simulate::{self, EthSimulateError},
     EthApiError, RevertError, RpcInvalidTransactionError, StateCacheDb,
 };
+use reth_transaction_pool::{PoolTransaction, TransactionPool};
 use revm::{
     context_interface::{
         result::{ExecutionResult, ResultAndState},
@@ -43,8 +47,13 @@ use revm::{
     Database, DatabaseCommit,
 };
 use revm_inspectors::{access_list::AccessListInspector, transfer::TransferInspector};
+use std::collections::{BTreeMap, BTreeSet};
 use tracing::trace;
 
+/// The maximum number of times `eth_createAccessList` executes the transaction to find an access
+/// list that is stable under its own gas costs.
+pub const MAX_ACCESS_LIST_ITERATIONS: usize = 8;
+
 /// Result type for `eth_simulateV1` RPC method.
 pub type SimulatedBlocksResult<N, E> = Result<Vec<SimulatedBlock<RpcBlock<N>>>, E>;
 
@@ -97,7 +106,7 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
             let mut parent = base_block.sealed_header().clone();
 
             let this = self.clone();
-            self.spawn_with_state_at_block(block, move |state| {
+            self.spawn_call_with_state_at_block(block, move |state| {
                 let mut db =
                     State::builder().with_database(StateProviderDatabase::new(state)).build();
                 let mut gas_used = 0;
@@ -283,7 +292,7 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
             }
 
             let this = self.clone();
-            self.spawn_with_state_at_block(at.into(), move |state| {
+            self.spawn_call_with_state_at_block(at.into(), move |state| {
                 let mut results = Vec::with_capacity(transactions.len());
                 let mut db = CacheDB::new(StateProviderDatabase::new(state));
 
@@ -349,8 +358,7 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
             let block_id = block_number.unwrap_or_default();
             let (evm_env, at) = self.evm_env_at(block_id).await?;
 
-            self.spawn_blocking_io(move |this| this.create_access_list_with(evm_env, at, request))
-                .await
+            self.spawn_call(move |this| this.create_access_list_with(evm_env, at, request)).await
         }
     }
 
@@ -386,29 +394,43 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
         }
 
         // can consume the list since we're not using the request anymore
-        let initial = request.access_list.take().unwrap_or_default();
+        let mut access_list = request.access_list.take().unwrap_or_default();
 
-        let mut inspector = AccessListInspector::new(initial);
-
-        let (result, (evm_env, mut tx_env)) =
-            self.inspect(&mut db, evm_env, tx_env, &mut inspector)?;
-        let access_list = inspector.into_access_list();
-        tx_env.set_access_list(access_list.clone());
-        match result.result {
-            ExecutionResult::Halt { reason, gas_used } => {
-                let error =
-                    Some(Self::Error::from_evm_halt(reason, tx_env.gas_limit()).to_string());
-                return Ok(AccessListResult { access_list, gas_used: U256::from(gas_used), error })
+        if self.access_list_delegations() {
+            let target = match tx_env.kind() {
+                TxKind::Call(to) => delegation_target(&mut db, to)?,
+                TxKind::Create => None,
+            };
+            if let Some(address) =
+                target.filter(|target| access_list.iter().all(|item| item.address != *target))
+            {
+                access_list.0.push(AccessListItem { address, storage_keys: Vec::new() });
             }
-            ExecutionResult::Revert { output, gas_used } => {
-                let error = Some(RevertError::new(output).to_string());
-                return Ok(AccessListResult { access_list, gas_used: U256::from(gas_used), error })
+        }
+
+        // The generated access list changes the gas costs of the transaction, which can change the
+        // executed branches. Re-run the transaction with the generated access list until it's
+        // stable. The list only grows, but a transaction that accesses storage depending on its
+        // remaining gas can keep growing it, so the number of runs is bounded.
+        let mut iterations = 0;
+        let result = loop {
+            iterations += 1;
+            tx_env.set_access_list(access_list.clone());
+            let mut inspector = AccessListInspector::new(access_list.clone());
+
+            let (result, (env, tx)) = self.inspect(&mut db, evm_env, tx_env, &mut inspector)?;
+            (evm_env, tx_env) = (env, tx);
+
+            let generated = inspector.into_access_list();
+            let stable = normalized_access_list(&generated) == normalized_access_list(&access_list);
+            access_list = generated;
+            if stable || iterations == MAX_ACCESS_LIST_ITERATIONS {
+                break result
             }
-            ExecutionResult::Success { .. } => {}
         };
 
-        // transact again to get the exact gas used
-        let (result, (_, tx_env)) = self.transact(&mut db, evm_env, tx_env)?;
+        // if the list is stable, the last run used it and its gas used is exact, otherwise the
+        // list contains the entries accessed by the last run on top of the ones it used
         let res = match result.result {
             ExecutionResult::Halt { reason, gas_used } => {
                 let error =
@@ -428,6 +450,59 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
     }
 }
 
+/// Returns the accessed storage keys per account of the access list, regardless of their order.
+fn normalized_access_list(access_list: &AccessList) -> BTreeMap<Address, BTreeSet<B256>> {
+    let mut normalized = BTreeMap::<_, BTreeSet<_>>::new();
+    for item in access_list.iter() {
+        normalized.entry(item.address).or_default().extend(item.storage_keys.iter().copied());
+    }
+    normalized
+}
+
+/// Loads the sender, the recipient and the accessed accounts and storage slots of a call at a
+/// historical block into the cache of `db` with a single [`StateProvider::accounts_with_storage`]
+/// lookup, which historical state providers split across parallel workers.
+fn prefetch_call_state(
+    state: &dyn StateProvider,
+    request: &TransactionRequest,
+    db: &mut StateCacheDb<'_>,
+) -> Result<(), ProviderError> {
+    let mut targets = request.access_list.as_ref().map(normalized_access_list).unwrap_or_default();
+    if let Some(from) = request.from {
+        targets.entry(from).or_default();
+    }
+    if let Some(TxKind::Call(to)) = request.to {
+        targets.entry(to).or_default();
+    }
+    if targets.len() < 2 {
+        // nothing to split across workers, the state is loaded lazily
+        return Ok(())
+    }
+
+    let targets = targets
+        .into_iter()
+        .map(|(address, slots)| (address, slots.into_iter().collect()))
+        .collect::<Vec<_>>();
+    let accounts = state.accounts_with_storage(&targets)?;
+    for ((address, slots), (account, values)) in targets.into_iter().zip(accounts) {
+        let Some(account) = account else {
+            db.cache.accounts.insert(address, DbAccount::new_not_existing());
+            continue
+        };
+
+        let mut db_account = DbAccount::from(AccountInfo::from(account));
+        db_account.storage.extend(
+            slots
+                .into_iter()
+                .zip(values)
+                .map(|(slot, value)| (U256::from_be_bytes(slot.0), value.unwrap_or_default())),
+        );
+        db.cache.accounts.insert(address, db_account);
+    }
+
+    Ok(())
+}
+
 /// Executes code on state.
 pub trait Call:
     LoadState<
@@ -448,6 +523,14 @@ pub trait Call:
     /// Returns the maximum number of blocks accepted for `eth_simulateV1`.
     fn max_simulate_blocks(&self) -> u64;
 
+    /// Returns whether `eth_createAccessList` includes the
+    /// [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702) delegation target of the called account.
+    fn access_list_delegations(&self) -> bool;
+
+    /// Returns whether calls against the pending block include the caller's pending pool
+    /// transactions.
+    fn pending_call_pool_txs(&self) -> bool;
+
     /// Executes the closure with the state that corresponds to the given [`BlockId`].
     fn with_state_at_block<F, R>(&self, at: BlockId, f: F) -> Result<R, Self::Error>
     where
@@ -517,10 +600,20 @@ pub trait Call:
     where
         Self: LoadPendingBlock,
     {
-        let this = self.clone();
-        self.spawn_with_call_at(request, at, overrides, move |db, evm_env, tx_env| {
-            this.transact(db, evm_env, tx_env)
-        })
+        async move {
+            let permit = self
+                .call_task_guard()
+                .clone()
+                .acquire_owned()
+                .await
+                .map_err(|_| EthApiError::InternalEthError)?;
+            let this = self.clone();
+            self.spawn_with_call_at(request, at, overrides, move |db, evm_env, tx_env| {
+                let _permit = permit;
+                this.transact(db, evm_env, tx_env)
+            })
+            .await
+        }
     }
 
     /// Executes the closure with the state that corresponds to the given [`BlockId`] on a new task
@@ -539,6 +632,23 @@ pub trait Call:
         })
     }
 
+    /// Executes the closure with the state that corresponds to the given [`BlockId`] on a new task,
+    /// bounded like the other calls of the `eth` namespace, see [`SpawnBlocking::spawn_call`].
+    fn spawn_call_with_state_at_block<F, R>(
+        &self,
+        at: BlockId,
+        f: F,
+    ) -> impl Future<Output = Result<R, Self::Error>> + Send
+    where
+        F: FnOnce(StateProviderTraitObjWrapper<'_>) -> Result<R, Self::Error> + Send + 'static,
+        R: Send + 'static,
+    {
+        self.spawn_call(move |this| {
+            let state = this.state_at_block_id(at)?;
+            f(StateProviderTraitObjWrapper(&state))
+        })
+    }
+
     /// Prepares the state and env for the given [`TransactionRequest`] at the given [`BlockId`] and
     /// executes the closure on a new task returning the result of the closure.
     ///
@@ -547,6 +657,10 @@ pub trait Call:
     ///
     /// This is primarily used by `eth_call`.
     ///
+    /// If [`Self::pending_call_pool_txs`] is enabled and the call targets the pending block, the
+    /// pending pool transactions of the caller are applied to the state first, see
+    /// [`Self::apply_pending_pool_transactions`].
+    ///
     /// # Blocking behaviour
     ///
     /// This assumes executing the call is relatively more expensive on IO than CPU because it
@@ -573,12 +687,30 @@ pub trait Call:
         R: Send + 'static,
     {
         async move {
+            let pending_sender =
+                request.from.filter(|_| at.is_pending() && self.pending_call_pool_txs());
             let (evm_env, at) = self.evm_env_at(at).await?;
             let this = self.clone();
             self.spawn_blocking_io(move |_| {
+                // Only historical state providers split the prefetch across workers, the latest
+                // and pending state are cheap enough to load on demand.
+                let historical = !at.is_pending() &&
+                    evm_env.block_env.number <
+                        this.provider()
+                            .best_block_number()
+                            .map_err(Self::Error::from_eth_err)?;
+
                 let state = this.state_at_block_id(at)?;
                 let mut db =
                     CacheDB::new(StateProviderDatabase::new(StateProviderTraitObjWrapper(&state)));
+                if historical {
+                    prefetch_call_state(&*state, &request, &mut db)
+                        .map_err(Self::Error::from_eth_err)?;
+                }
+
+                if let Some(sender) = pending_sender {
+                    this.apply_pending_pool_transactions(&mut db, evm_env.clone(), sender)?;
+                }
 
                 let (evm_env, tx_env) =
                     this.prepare_call_env(evm_env, request, &mut db, overrides)?;
@@ -678,6 +810,56 @@ pub trait Call:
         Ok(index)
     }
 
+    /// Applies the pending pool transactions of the given sender to the database, so that calls
+    /// can depend on transactions that aren't included yet.
+    ///
+    /// The transactions are executed in nonce order, starting at the sender's nonce in the given
+    /// database. The first nonce gap or transaction that fails to execute, e.g. because it became
+    /// invalid, ends the sequence.
+    ///
+    /// Returns the number of applied transactions.
+    fn apply_pending_pool_transactions<DB>(
+        &self,
+        db: &mut DB,
+        evm_env: EvmEnvFor<Self::Evm>,
+        sender: Address,
+    ) -> Result<usize, Self::Error>
+    where
+        Self: LoadPendingBlock,
+        DB: Database<Error = ProviderError> + DatabaseCommit,
+    {
+        let mut nonce = db
+            .basic(sender)
+            .map_err(Self::Error::from_eth_err)?
+            .map(|account| account.nonce)
+            .unwrap_or_default();
+
+        let mut transactions = self.pool().get_pending_transactions_by_sender(sender);
+        transactions.sort_unstable_by_key(|tx| tx.nonce());
+
+        let mut evm = self.evm_config().evm_with_env(db, evm_env);
+        let mut applied = 0;
+        for tx in transactions {
+            if tx.nonce() < nonce {
+                // already included in the state
+                continue
+            }
+            if tx.nonce() > nonce {
+                break
+            }
+
+            let tx_env = self.evm_config().tx_env(tx.transaction.clone_into_consensus());
+            if evm.transact_commit(tx_env).is_err() {
+                break
+            }
+            nonce += 1;
+            applied += 1;
+        }
+
+        trace!(target: "rpc::eth::call", ?sender, applied, "applied pending pool transactions");
+        Ok(applied)
+    }
+
     /// Configures a new `TxEnv`  for the [`TransactionRequest`]
     ///
     /// All `TxEnv` fields are derived from the given [`TransactionRequest`], if fields are
@@ -157,14 +157,17 @@ pub trait EstimateCall: Call {
                 // limit
                 return Err(Self::Error::from_evm_halt(reason, tx_env.gas_limit()))
             }
-            ExecutionResult::Revert { output, .. } => {
+            ExecutionResult::Revert { output, gas_used } => {
                 // if price or limit was included in the request then we can execute the request
                 // again with the block's gas limit to check if revert is gas related or not
                 return if tx_request_gas_limit.is_some() || tx_request_gas_price.is_some() {
                     Err(self.map_out_of_gas_err(block_env_gas_limit, evm_env, tx_env, &mut db))
                 } else {
                     // the transaction did revert
-                    Err(RpcInvalidTransactionError::Revert(RevertError::new(output)).into_eth_err())
+                    Err(RpcInvalidTransactionError::Revert(
+                        RevertError::new(output).with_gas_used(gas_used),
+                    )
+                    .into_eth_err())
                 }
             }
         };
@@ -272,7 +275,7 @@ pub trait EstimateCall: Call {
         async move {
             let (evm_env, at) = self.evm_env_at(at).await?;
 
-            self.spawn_blocking_io(move |this| {
+            self.spawn_call(move |this| {
                 let state = this.state_at_block_id(at)?;
                 EstimateCall::estimate_gas_with(&this, evm_env, request, state, state_override)
             })
@@ -306,9 +309,10 @@ pub trait EstimateCall: Call {
                 // highest, which means the caller lacks funds to pay for the tx
                 RpcInvalidTransactionError::BasicOutOfGas(req_gas_limit).into_eth_err()
             }
-            ExecutionResult::Revert { output, .. } => {
+            ExecutionResult::Revert { output, gas_used } => {
                 // reverted again after bumping the limit
-                RpcInvalidTransactionError::Revert(RevertError::new(output)).into_eth_err()
+                RpcInvalidTransactionError::Revert(RevertError::new(output).with_gas_used(gas_used))
+                    .into_eth_err()
             }
             ExecutionResult::Halt { reason, .. } => {
                 Self::Error::from_evm_halt(reason, req_gas_limit)
@@ -12,7 +12,7 @@ use reth_primitives_traits::BlockBody;
 use reth_provider::{BlockIdReader, ChainSpecProvider, HeaderProvider};
 use reth_rpc_eth_types::{
     fee_history::calculate_reward_percentiles_for_block, EthApiError, FeeHistoryCache,
-    FeeHistoryEntry, GasPriceOracle, RpcInvalidTransactionError,
+    FeeHistoryEntry, GasPriceOracle, GasPriceOracleStrategy, RpcInvalidTransactionError,
 };
 use tracing::debug;
 
@@ -339,7 +339,26 @@ pub trait LoadFee: LoadBlock {
         let suggested_tip = self.suggested_priority_fee();
         async move {
             let (header, suggested_tip) = futures::try_join!(header, suggested_tip)?;
-            let base_fee = header.and_then(|h| h.base_fee_per_gas()).unwrap_or_default();
+            let mut base_fee =
+                header.as_ref().and_then(|h| h.base_fee_per_gas()).unwrap_or_default();
+
+            // use the projected base fee if it's trending up
+            if self.gas_oracle().config().strategy == GasPriceOracleStrategy::BaseFeeTrend {
+                if let Some(header) = header {
+                    let base_fee_params = self
+                        .provider()
+                        .chain_spec()
+                        .base_fee_params_at_timestamp(header.timestamp());
+                    if let Some(projected) = self
+                        .gas_oracle()
+                        .projected_base_fee(base_fee_params)
+                        .map_err(Self::Error::from_eth_err)?
+                    {
+                        base_fee = base_fee.max(projected);
+                    }
+                }
+            }
+
             Ok(suggested_tip + U256::from(base_fee))
         }
     }
@@ -364,6 +383,11 @@ pub trait LoadFee: LoadBlock {
     where
         Self: 'static,
     {
-        async move { self.gas_oracle().suggest_tip_cap().await.map_err(Self::Error::from_eth_err) }
+        async move {
+            self.gas_oracle()
+                .suggest_tip_cap_with_pool(self.pool())
+                .await
+                .map_err(Self::Error::from_eth_err)
+        }
     }
 }
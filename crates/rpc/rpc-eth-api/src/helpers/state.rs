@@ -9,13 +9,14 @@ use alloy_rpc_types_eth::{Account, EIP1186AccountProofResponse};
 use alloy_serde::JsonStorageKey;
 use futures::Future;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
-use reth_errors::RethError;
 use reth_evm::{ConfigureEvm, EvmEnvFor};
 use reth_provider::{
     BlockIdReader, BlockNumReader, ChainSpecProvider, StateProvider, StateProviderBox,
     StateProviderFactory,
 };
-use reth_rpc_eth_types::{EthApiError, PendingBlockEnv, RpcInvalidTransactionError};
+use reth_rpc_eth_types::{
+    EthApiError, PendingBlockEnv, ProofTaskPriority, RpcInvalidTransactionError,
+};
 use reth_transaction_pool::TransactionPool;
 
 /// Helper methods for `eth_` methods relating to state (accounts).
@@ -23,6 +24,9 @@ pub trait EthState: LoadState + SpawnBlocking {
     /// Returns the maximum number of blocks into the past for generating state proofs.
     fn max_proof_window(&self) -> u64;
 
+    /// Returns the maximum number of accounts of a single `reth_getProofs` call.
+    fn max_proofs_accounts(&self) -> usize;
+
     /// Returns the number of transactions sent from an address at the given block identifier.
     ///
     /// If this is [`BlockNumberOrTag::Pending`](alloy_eips::BlockNumberOrTag) then this will
@@ -91,12 +95,6 @@ pub trait EthState: LoadState + SpawnBlocking {
         Self: EthApiSpec,
     {
         Ok(async move {
-            let _permit = self
-                .acquire_owned()
-                .await
-                .map_err(RethError::other)
-                .map_err(EthApiError::Internal)?;
-
             let chain_info = self.chain_info().map_err(Self::Error::from_eth_err)?;
             let block_id = block_id.unwrap_or_default();
 
@@ -111,7 +109,7 @@ pub trait EthState: LoadState + SpawnBlocking {
                 return Err(EthApiError::ExceedsMaxProofWindow.into())
             }
 
-            self.spawn_blocking_io(move |this| {
+            self.spawn_proof(ProofTaskPriority::High, move |this| {
                 let state = this.state_at_block_id(block_id)?;
                 let storage_keys = keys.iter().map(|key| key.as_b256()).collect::<Vec<_>>();
                 let proof = state
@@ -123,6 +121,65 @@ pub trait EthState: LoadState + SpawnBlocking {
         })
     }
 
+    /// Returns the values stored of multiple accounts, with Merkle-proofs, at given blocknumber.
+    ///
+    /// All proofs are generated in a single pass over the state trie and returned in the order of
+    /// the requested accounts, which are limited to [`Self::max_proofs_accounts`].
+    fn get_proofs(
+        &self,
+        accounts: Vec<(Address, Vec<JsonStorageKey>)>,
+        block_id: Option<BlockId>,
+    ) -> Result<
+        impl Future<Output = Result<Vec<EIP1186AccountProofResponse>, Self::Error>> + Send,
+        Self::Error,
+    >
+    where
+        Self: EthApiSpec,
+    {
+        let max_accounts = self.max_proofs_accounts();
+        if accounts.len() > max_accounts {
+            return Err(EthApiError::InvalidParams(format!(
+                "number of accounts exceeds the maximum of {max_accounts}"
+            ))
+            .into())
+        }
+
+        Ok(async move {
+            let chain_info = self.chain_info().map_err(Self::Error::from_eth_err)?;
+            let block_id = block_id.unwrap_or_default();
+
+            // Check whether the distance to the block exceeds the maximum configured window.
+            let block_number = self
+                .provider()
+                .block_number_for_id(block_id)
+                .map_err(Self::Error::from_eth_err)?
+                .ok_or(EthApiError::HeaderNotFound(block_id))?;
+            let max_window = self.max_proof_window();
+            if chain_info.best_number.saturating_sub(block_number) > max_window {
+                return Err(EthApiError::ExceedsMaxProofWindow.into())
+            }
+
+            self.spawn_proof(ProofTaskPriority::Low, move |this| {
+                let state = this.state_at_block_id(block_id)?;
+                let targets = accounts
+                    .iter()
+                    .map(|(address, keys)| {
+                        (*address, keys.iter().map(|key| key.as_b256()).collect())
+                    })
+                    .collect::<Vec<_>>();
+                let proofs = state
+                    .proofs(Default::default(), &targets)
+                    .map_err(Self::Error::from_eth_err)?;
+                Ok(proofs
+                    .into_iter()
+                    .zip(accounts)
+                    .map(|(proof, (_, keys))| proof.into_eip1186_response(keys))
+                    .collect())
+            })
+            .await
+        })
+    }
+
     /// Returns the account at the given address for the provided block identifier.
     fn get_account(
         &self,
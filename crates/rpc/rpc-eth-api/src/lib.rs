@@ -22,9 +22,9 @@ pub mod pubsub;
 pub mod types;
 
 pub use bundle::{EthBundleApiServer, EthCallBundleApiServer};
-pub use core::{EthApiServer, FullEthApiServer};
+pub use core::{EthApiServer, FullEthApiServer, RethBlockApiServer, RethStateApiServer};
 pub use ext::L2EthApiExtServer;
-pub use filter::{EngineEthFilter, EthFilterApiServer, QueryLimits};
+pub use filter::{EngineEthFilter, EthFilterApiServer, QueryLimits, RethFilterApiServer};
 pub use node::{RpcNodeCore, RpcNodeCoreExt};
 pub use pubsub::EthPubSubApiServer;
 pub use reth_rpc_eth_types::error::{
@@ -36,10 +36,10 @@ pub use types::{EthApiTypes, FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, R
 #[cfg(feature = "client")]
 pub use bundle::{EthBundleApiClient, EthCallBundleApiClient};
 #[cfg(feature = "client")]
-pub use core::EthApiClient;
+pub use core::{EthApiClient, RethBlockApiClient, RethStateApiClient};
 #[cfg(feature = "client")]
 pub use ext::L2EthApiExtClient;
 #[cfg(feature = "client")]
-pub use filter::EthFilterApiClient;
+pub use filter::{EthFilterApiClient, RethFilterApiClient};
 
 use reth_trie_common as _;
@@ -0,0 +1,64 @@
+//! Helpers for block responses.
+
+use alloy_rpc_types_eth::Block;
+use serde::{Deserialize, Serialize};
+
+/// Selects the optional fields of the blocks returned by `reth_getBlockByHash` and
+/// `reth_getBlockByNumber`.
+///
+/// All fields are included by default, so consumers that poll full blocks can opt out of the
+/// fields they don't use to reduce the size of the response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BlockFields {
+    /// Whether to include the withdrawals of the block.
+    pub include_withdrawals: bool,
+    /// Whether to include the hashes of the uncles of the block, otherwise they're replaced by an
+    /// empty list.
+    pub include_uncles: bool,
+}
+
+impl BlockFields {
+    /// Removes the excluded fields from the block.
+    pub fn apply<T, H>(&self, block: &mut Block<T, H>) {
+        if !self.include_withdrawals {
+            block.withdrawals = None;
+        }
+        if !self.include_uncles {
+            block.uncles.clear();
+        }
+    }
+}
+
+impl Default for BlockFields {
+    fn default() -> Self {
+        Self { include_withdrawals: true, include_uncles: true }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip4895::{Withdrawal, Withdrawals};
+    use alloy_primitives::B256;
+    use alloy_rpc_types_eth::{Header, Transaction};
+
+    #[test]
+    fn omits_excluded_fields() {
+        let fields: BlockFields = serde_json::from_str(r#"{"includeWithdrawals":false}"#).unwrap();
+        assert_eq!(fields, BlockFields { include_withdrawals: false, include_uncles: true });
+
+        let mut block = Block::<Transaction, Header> {
+            uncles: vec![B256::ZERO],
+            withdrawals: Some(Withdrawals::new(vec![Withdrawal::default()])),
+            ..Default::default()
+        };
+        BlockFields::default().apply(&mut block);
+        assert!(block.withdrawals.is_some());
+
+        fields.apply(&mut block);
+        assert!(block.withdrawals.is_none());
+        assert_eq!(block.uncles, vec![B256::ZERO]);
+        assert!(!serde_json::to_string(&block).unwrap().contains("withdrawals"));
+    }
+}
@@ -1,14 +1,14 @@
 //! Configuration for `eth` namespace APIs.
 
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 use crate::{
     EthStateCacheConfig, FeeHistoryCacheConfig, GasPriceOracleConfig, RPC_DEFAULT_GAS_CAP,
 };
 use reth_rpc_server_types::constants::{
     default_max_tracing_requests, DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_BLOCKS_PER_FILTER,
-    DEFAULT_MAX_LOGS_PER_RESPONSE, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_MAX_TRACE_FILTER_BLOCKS,
-    DEFAULT_PROOF_PERMITS,
+    DEFAULT_MAX_CONCURRENT_CALLS, DEFAULT_MAX_LOGS_PER_RESPONSE, DEFAULT_MAX_PROOFS_ACCOUNTS,
+    DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_MAX_TRACE_FILTER_BLOCKS, DEFAULT_PROOF_PERMITS,
 };
 use serde::{Deserialize, Serialize};
 
@@ -24,6 +24,8 @@ pub struct EthConfig {
     pub gas_oracle: GasPriceOracleConfig,
     /// The maximum number of blocks into the past for generating state proofs.
     pub eth_proof_window: u64,
+    /// The maximum number of accounts of a single `reth_getProofs` call.
+    pub max_proofs_accounts: usize,
     /// The maximum number of tracing calls that can be executed in concurrently.
     pub max_tracing_requests: usize,
     /// Maximum number of blocks for `trace_filter` requests.
@@ -38,6 +40,11 @@ pub struct EthConfig {
     pub rpc_gas_cap: u64,
     /// Max number of blocks for `eth_simulateV1`.
     pub rpc_max_simulate_blocks: u64,
+    /// Whether `eth_createAccessList` includes the EIP-7702 delegation target of the called
+    /// account.
+    pub access_list_delegations: bool,
+    /// Whether calls against the pending block include the caller's pending pool transactions.
+    pub pending_call_pool_txs: bool,
     ///
     /// Sets TTL for stale filters
     pub stale_filter_ttl: Duration,
@@ -45,6 +52,13 @@ pub struct EthConfig {
     pub fee_history_cache: FeeHistoryCacheConfig,
     /// The maximum number of getproof calls that can be executed concurrently.
     pub proof_permits: usize,
+    /// The number of threads of the blocking task pool of the `debug` and `trace` namespaces.
+    ///
+    /// Defaults to the number of CPUs.
+    pub tracing_pool_threads: Option<usize>,
+    /// The maximum number of `eth_call`s, `eth_estimateGas` and other simulations of the `eth`
+    /// namespace that can be executed concurrently.
+    pub max_concurrent_calls: usize,
 }
 
 impl EthConfig {
@@ -63,15 +77,20 @@ impl Default for EthConfig {
             cache: EthStateCacheConfig::default(),
             gas_oracle: GasPriceOracleConfig::default(),
             eth_proof_window: DEFAULT_ETH_PROOF_WINDOW,
+            max_proofs_accounts: DEFAULT_MAX_PROOFS_ACCOUNTS,
             max_tracing_requests: default_max_tracing_requests(),
             max_trace_filter_blocks: DEFAULT_MAX_TRACE_FILTER_BLOCKS,
             max_blocks_per_filter: DEFAULT_MAX_BLOCKS_PER_FILTER,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
             rpc_max_simulate_blocks: DEFAULT_MAX_SIMULATE_BLOCKS,
+            access_list_delegations: false,
+            pending_call_pool_txs: false,
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
             fee_history_cache: FeeHistoryCacheConfig::default(),
             proof_permits: DEFAULT_PROOF_PERMITS,
+            tracing_pool_threads: None,
+            max_concurrent_calls: DEFAULT_MAX_CONCURRENT_CALLS,
         }
     }
 }
@@ -125,17 +144,48 @@ impl EthConfig {
         self
     }
 
+    /// Configures whether `eth_createAccessList` includes EIP-7702 delegation targets
+    pub const fn access_list_delegations(mut self, enabled: bool) -> Self {
+        self.access_list_delegations = enabled;
+        self
+    }
+
+    /// Configures whether calls against the pending block include the caller's pending pool
+    /// transactions
+    pub const fn pending_call_pool_txs(mut self, enabled: bool) -> Self {
+        self.pending_call_pool_txs = enabled;
+        self
+    }
+
     /// Configures the maximum proof window for historical proof generation.
     pub const fn eth_proof_window(mut self, window: u64) -> Self {
         self.eth_proof_window = window;
         self
     }
 
+    /// Configures the maximum number of accounts of a single `reth_getProofs` call.
+    pub const fn max_proofs_accounts(mut self, max_accounts: usize) -> Self {
+        self.max_proofs_accounts = max_accounts;
+        self
+    }
+
     /// Configures the number of getproof requests
     pub const fn proof_permits(mut self, permits: usize) -> Self {
         self.proof_permits = permits;
         self
     }
+
+    /// Configures the number of threads of the tracing pool
+    pub const fn tracing_pool_threads(mut self, threads: Option<usize>) -> Self {
+        self.tracing_pool_threads = threads;
+        self
+    }
+
+    /// Configures the maximum number of concurrent calls and simulations
+    pub const fn max_concurrent_calls(mut self, max_concurrent_calls: usize) -> Self {
+        self.max_concurrent_calls = max_concurrent_calls;
+        self
+    }
 }
 
 /// Config for the filter
@@ -154,6 +204,11 @@ pub struct EthFilterConfig {
     /// A filter is considered stale if it has not been polled for longer than this duration and
     /// will be removed.
     pub stale_filter_ttl: Duration,
+    /// The file that installed log and block filters are persisted to.
+    ///
+    /// The file is updated at most once per second, so filters restored after a crash may return
+    /// changes again that were already polled. If `None` then filters are lost on restart.
+    pub persistence_path: Option<PathBuf>,
 }
 
 impl EthFilterConfig {
@@ -175,6 +230,12 @@ impl EthFilterConfig {
         self.stale_filter_ttl = duration;
         self
     }
+
+    /// Sets the file that installed log and block filters are persisted to.
+    pub fn persistence_path(mut self, path: Option<PathBuf>) -> Self {
+        self.persistence_path = path;
+        self
+    }
 }
 
 impl Default for EthFilterConfig {
@@ -184,6 +245,7 @@ impl Default for EthFilterConfig {
             max_logs_per_response: None,
             // 5min
             stale_filter_ttl: Duration::from_secs(5 * 60),
+            persistence_path: None,
         }
     }
 }
@@ -77,6 +77,16 @@ impl reth_storage_api::StorageRootProvider for StateProviderTraitObjWrapper<'_>
     ) -> ProviderResult<reth_trie::StorageMultiProof> {
         self.0.storage_multiproof(address, slots, hashed_storage)
     }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<reth_trie::HashedStorageRange> {
+        self.0.storage_range(address, hashed_storage, start, limit)
+    }
 }
 
 impl reth_storage_api::StateProofProvider for StateProviderTraitObjWrapper<'_> {
@@ -175,6 +185,15 @@ impl StateProvider for StateProviderTraitObjWrapper<'_> {
     fn account_nonce(&self, addr: &Address) -> reth_errors::ProviderResult<Option<u64>> {
         self.0.account_nonce(addr)
     }
+
+    fn accounts_with_storage(
+        &self,
+        targets: &[(Address, Vec<alloy_primitives::StorageKey>)],
+    ) -> reth_errors::ProviderResult<
+        Vec<(Option<reth_primitives_traits::Account>, Vec<Option<alloy_primitives::StorageValue>>)>,
+    > {
+        self.0.accounts_with_storage(targets)
+    }
 }
 
 /// Hack to get around 'higher-ranked lifetime error', see
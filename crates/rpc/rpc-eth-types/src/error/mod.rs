@@ -3,7 +3,7 @@
 pub mod api;
 use crate::error::api::FromEvmHalt;
 use alloy_eips::BlockId;
-use alloy_primitives::{Address, Bytes, U256};
+use alloy_primitives::{Address, Bytes, U256, U64};
 use alloy_rpc_types_eth::{error::EthRpcErrorCode, request::TransactionInputError, BlockError};
 use alloy_sol_types::{ContractError, RevertReason};
 pub use api::{AsEthApiError, FromEthApiError, FromEvmError, IntoEthApiError};
@@ -120,6 +120,9 @@ pub enum EthApiError {
     /// Error thrown when a spawned blocking task failed to deliver an anticipated response
     #[error("internal eth error")]
     InternalEthError,
+    /// Error thrown when too many state proof requests are waiting for the proof pool.
+    #[error("too many pending proof requests")]
+    ProofQueueFull,
     /// Error thrown when a (tracing) call exceeds the configured timeout
     #[error("execution aborted (timeout = {0:?})")]
     ExecutionTimedOut(Duration),
@@ -224,9 +227,9 @@ impl From<EthApiError> for jsonrpsee_types::error::ErrorObject<'static> {
                 jsonrpsee_types::error::CALL_EXECUTION_FAILED_CODE,
                 err.to_string(),
             ),
-            err @ (EthApiError::InternalBlockingTaskError | EthApiError::InternalEthError) => {
-                internal_rpc_err(err.to_string())
-            }
+            err @ (EthApiError::InternalBlockingTaskError |
+            EthApiError::InternalEthError |
+            EthApiError::ProofQueueFull) => internal_rpc_err(err.to_string()),
             err @ EthApiError::TransactionInputError(_) => invalid_params_rpc_err(err.to_string()),
             EthApiError::Other(err) => err.to_rpc_error(),
             EthApiError::MuxTracerError(msg) => internal_rpc_err(msg.to_string()),
@@ -274,6 +277,9 @@ impl From<reth_errors::ProviderError> for EthApiError {
             ProviderError::TotalDifficultyNotFound(num) => Self::HeaderNotFound(num.into()),
             ProviderError::FinalizedBlockNotFound => Self::HeaderNotFound(BlockId::finalized()),
             ProviderError::SafeBlockNotFound => Self::HeaderNotFound(BlockId::safe()),
+            err @ ProviderError::InvalidTransactionLookupRange { .. } => {
+                Self::InvalidParams(err.to_string())
+            }
             err => Self::Internal(err.into()),
         }
     }
@@ -516,14 +522,7 @@ impl RpcInvalidTransactionError {
 impl From<RpcInvalidTransactionError> for jsonrpsee_types::error::ErrorObject<'static> {
     fn from(err: RpcInvalidTransactionError) -> Self {
         match err {
-            RpcInvalidTransactionError::Revert(revert) => {
-                // include out data if some
-                rpc_err(
-                    revert.error_code(),
-                    revert.to_string(),
-                    revert.output.as_ref().map(|out| out.as_ref()),
-                )
-            }
+            RpcInvalidTransactionError::Revert(revert) => revert.into(),
             RpcInvalidTransactionError::Other(err) => err.to_rpc_error(),
             err => rpc_err(err.error_code(), err.to_string(), None),
         }
@@ -612,6 +611,7 @@ impl From<InvalidTransactionError> for RpcInvalidTransactionError {
             InvalidTransactionError::TipAboveFeeCap => Self::TipAboveFeeCap,
             InvalidTransactionError::FeeCapTooLow => Self::FeeCapTooLow,
             InvalidTransactionError::SignerAccountHasBytecode => Self::SenderNoEOA,
+            InvalidTransactionError::ExceedsMaxInitCodeSize { .. } => Self::MaxInitCodeSizeExceeded,
         }
     }
 }
@@ -625,6 +625,8 @@ pub struct RevertError {
     ///
     /// Note: this is `None` if output was empty
     output: Option<Bytes>,
+    /// The gas used by the reverted execution, if known.
+    gas_used: Option<u64>,
 }
 
 // === impl RevertError ==
@@ -635,34 +637,83 @@ impl RevertError {
     /// Note: this is intended to wrap an revm output
     pub fn new(output: Bytes) -> Self {
         if output.is_empty() {
-            Self { output: None }
+            Self { output: None, gas_used: None }
         } else {
-            Self { output: Some(output) }
+            Self { output: Some(output), gas_used: None }
         }
     }
 
+    /// Sets the gas used by the reverted execution.
+    pub const fn with_gas_used(mut self, gas_used: u64) -> Self {
+        self.gas_used = Some(gas_used);
+        self
+    }
+
     /// Returns error code to return for this error.
     pub const fn error_code(&self) -> i32 {
         EthRpcErrorCode::ExecutionError.code()
     }
+
+    /// Returns the output data of the reverted execution, if any.
+    pub const fn output(&self) -> Option<&Bytes> {
+        self.output.as_ref()
+    }
+
+    /// Returns the gas used by the reverted execution, if known.
+    pub const fn gas_used(&self) -> Option<u64> {
+        self.gas_used
+    }
+
+    /// Returns the decoded revert reason if the output is a string, a Solidity `Error(string)` or
+    /// a `Panic(uint256)`.
+    pub fn reason(&self) -> Option<String> {
+        let reason = RevertReason::decode(self.output.as_ref()?)?;
+        let error = reason.to_string();
+        if matches!(reason, RevertReason::ContractError(ContractError::Revert(_))) {
+            // we strip redundant `revert: ` prefix from the revert reason
+            return Some(error.trim_start_matches("revert: ").to_string())
+        }
+        Some(error)
+    }
+
+    /// Returns the decoded reason and the gas used of the reverted execution.
+    pub fn details(&self) -> RevertDetails {
+        RevertDetails { reason: self.reason(), gas_used: self.gas_used.map(U64::from) }
+    }
 }
 
 impl std::fmt::Display for RevertError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("execution reverted")?;
-        if let Some(reason) = self.output.as_ref().and_then(|out| RevertReason::decode(out)) {
-            let error = reason.to_string();
-            let mut error = error.as_str();
-            if matches!(reason, RevertReason::ContractError(ContractError::Revert(_))) {
-                // we strip redundant `revert: ` prefix from the revert reason
-                error = error.trim_start_matches("revert: ");
-            }
-            write!(f, ": {error}")?;
+        if let Some(reason) = self.reason() {
+            write!(f, ": {reason}")?;
         }
         Ok(())
     }
 }
 
+impl From<RevertError> for jsonrpsee_types::error::ErrorObject<'static> {
+    fn from(err: RevertError) -> Self {
+        // tooling expects the `data` of the error to be the hex encoded revert output
+        rpc_err(err.error_code(), err.to_string(), err.output.as_ref().map(|out| out.as_ref()))
+    }
+}
+
+/// The decoded reason and the gas used of a reverted execution.
+///
+/// These are kept separate from the `data` of the JSON-RPC error, which is the hex encoded revert
+/// output.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevertDetails {
+    /// The decoded revert reason, if the output is a known Solidity error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// The gas used by the reverted execution, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_used: Option<U64>,
+}
+
 /// A helper error type that's mainly used to mirror `geth` Txpool's error messages
 #[derive(Debug, thiserror::Error)]
 pub enum RpcPoolError {
@@ -800,9 +851,9 @@ pub fn ensure_success<Halt, Error: FromEvmHalt<Halt> + FromEthApiError>(
 ) -> Result<Bytes, Error> {
     match result {
         ExecutionResult::Success { output, .. } => Ok(output.into_data()),
-        ExecutionResult::Revert { output, .. } => {
-            Err(Error::from_eth_err(RpcInvalidTransactionError::Revert(RevertError::new(output))))
-        }
+        ExecutionResult::Revert { output, gas_used } => Err(Error::from_eth_err(
+            RpcInvalidTransactionError::Revert(RevertError::new(output).with_gas_used(gas_used)),
+        )),
         ExecutionResult::Halt { reason, gas_used } => Err(Error::from_evm_halt(reason, gas_used)),
     }
 }
@@ -854,4 +905,37 @@ mod tests {
         let msg = err.to_string();
         assert_eq!(msg, "execution reverted: test_revert_reason");
     }
+
+    #[test]
+    fn revert_err_data() {
+        let revert = Revert::from("test_revert_reason");
+        let err = RevertError::new(revert.abi_encode().into()).with_gas_used(21_512);
+        let err: jsonrpsee_types::error::ErrorObject<'static> =
+            RpcInvalidTransactionError::Revert(err).into();
+        assert_eq!(err.code(), EthRpcErrorCode::ExecutionError.code());
+
+        let data: serde_json::Value = serde_json::from_str(err.data().unwrap().get()).unwrap();
+        assert_eq!(data, format!("0x{}", alloy_primitives::hex::encode(revert.abi_encode())));
+
+        // reverts without output don't include data
+        let err: jsonrpsee_types::error::ErrorObject<'static> =
+            RevertError::new(Bytes::new()).with_gas_used(21_000).into();
+        assert_eq!(err.message(), "execution reverted");
+        assert!(err.data().is_none());
+    }
+
+    #[test]
+    fn revert_err_details() {
+        let revert = Revert::from("test_revert_reason");
+        let err = RevertError::new(revert.abi_encode().into()).with_gas_used(21_512);
+        let details = serde_json::to_value(err.details()).unwrap();
+        assert_eq!(
+            details,
+            serde_json::json!({ "reason": "test_revert_reason", "gasUsed": "0x5408" })
+        );
+
+        let err = RevertError::new(Bytes::new()).with_gas_used(21_000);
+        let details = serde_json::to_value(err.details()).unwrap();
+        assert_eq!(details, serde_json::json!({ "gasUsed": "0x5208" }));
+    }
 }
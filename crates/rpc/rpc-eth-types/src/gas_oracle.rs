@@ -3,7 +3,10 @@
 
 use super::{EthApiError, EthResult, EthStateCache, RpcInvalidTransactionError};
 use alloy_consensus::{constants::GWEI_TO_WEI, BlockHeader, Transaction};
-use alloy_eips::BlockNumberOrTag;
+use alloy_eips::{
+    eip1559::{calc_next_block_base_fee, BaseFeeParams},
+    BlockNumberOrTag,
+};
 use alloy_primitives::{B256, U256};
 use alloy_rpc_types_eth::BlockId;
 use derive_more::{Deref, DerefMut, From, Into};
@@ -17,9 +20,13 @@ use reth_rpc_server_types::{
     },
 };
 use reth_storage_api::{BlockReader, BlockReaderIdExt};
+use reth_transaction_pool::{BestTransactionsAttributes, TransactionPool};
 use schnellru::{ByLength, LruMap};
 use serde::{Deserialize, Serialize};
-use std::fmt::{self, Debug, Formatter};
+use std::{
+    fmt::{self, Debug, Formatter},
+    str::FromStr,
+};
 use tokio::sync::Mutex;
 use tracing::warn;
 
@@ -27,6 +34,62 @@ use tracing::warn;
 /// [`RPC_DEFAULT_GAS_CAP`](constants::gas_oracle::RPC_DEFAULT_GAS_CAP).
 pub const RPC_DEFAULT_GAS_CAP: GasCap = GasCap(constants::gas_oracle::RPC_DEFAULT_GAS_CAP);
 
+/// The number of blocks ahead the base fee is projected by
+/// [`GasPriceOracleStrategy::BaseFeeTrend`].
+const BASE_FEE_PROJECTION_BLOCKS: usize = 2;
+
+/// The strategy of the [`GasPriceOracle`] to estimate gas prices.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GasPriceOracleStrategy {
+    /// Suggests the configured percentile of the lowest tips in the last blocks.
+    #[default]
+    Percentile,
+    /// Suggests at least the lowest tip of the pending transactions that fill the next block,
+    /// falling back to [`GasPriceOracleStrategy::Percentile`].
+    MinInclusionTip,
+    /// Suggests a gas price with the base fee projected from the gas usage of the last blocks,
+    /// with the tip of [`GasPriceOracleStrategy::Percentile`].
+    BaseFeeTrend,
+    /// Blends the tip of [`GasPriceOracleStrategy::Percentile`] with the configured percentile of
+    /// the tips of the pending transactions that fit in the next block, weighted by how much of
+    /// the block they fill.
+    PoolPressure,
+}
+
+impl GasPriceOracleStrategy {
+    /// All strategies.
+    pub const ALL: [Self; 4] =
+        [Self::Percentile, Self::MinInclusionTip, Self::BaseFeeTrend, Self::PoolPressure];
+
+    /// Returns the name of the strategy.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Percentile => "percentile",
+            Self::MinInclusionTip => "min-inclusion-tip",
+            Self::BaseFeeTrend => "base-fee-trend",
+            Self::PoolPressure => "pool-pressure",
+        }
+    }
+}
+
+impl FromStr for GasPriceOracleStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|strategy| strategy.as_str() == s)
+            .ok_or_else(|| format!("unknown gas price oracle strategy: {s}"))
+    }
+}
+
+impl fmt::Display for GasPriceOracleStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Settings for the [`GasPriceOracle`]
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -57,6 +120,10 @@ pub struct GasPriceOracleConfig {
 
     /// The minimum gas price, under which the sample will be ignored
     pub ignore_price: Option<U256>,
+
+    /// The strategy to estimate gas prices
+    #[serde(default)]
+    pub strategy: GasPriceOracleStrategy,
 }
 
 impl Default for GasPriceOracleConfig {
@@ -70,6 +137,7 @@ impl Default for GasPriceOracleConfig {
             default: None,
             max_price: Some(DEFAULT_MAX_GAS_PRICE),
             ignore_price: Some(DEFAULT_IGNORE_GAS_PRICE),
+            strategy: GasPriceOracleStrategy::default(),
         }
     }
 }
@@ -211,6 +279,87 @@ where
         Ok(price)
     }
 
+    /// Suggests a gas price estimate using the configured [`GasPriceOracleStrategy`].
+    ///
+    /// With [`GasPriceOracleStrategy::MinInclusionTip`], the estimate is raised to the lowest tip
+    /// of the best pending transactions of the pool that fill the next block.
+    ///
+    /// With [`GasPriceOracleStrategy::PoolPressure`], the estimate is blended with the tips of the
+    /// best pending transactions of the pool that fit in the next block, weighted by the share of
+    /// the block they fill.
+    pub async fn suggest_tip_cap_with_pool<Pool>(&self, pool: &Pool) -> EthResult<U256>
+    where
+        Pool: TransactionPool,
+    {
+        let price = self.suggest_tip_cap().await?;
+        let strategy = self.oracle_config.strategy;
+        if !matches!(
+            strategy,
+            GasPriceOracleStrategy::MinInclusionTip | GasPriceOracleStrategy::PoolPressure
+        ) {
+            return Ok(price)
+        }
+
+        let header = self
+            .provider
+            .sealed_header_by_number_or_tag(BlockNumberOrTag::Latest)?
+            .ok_or(EthApiError::HeaderNotFound(BlockId::latest()))?;
+
+        let mut price = if strategy == GasPriceOracleStrategy::PoolPressure {
+            PoolPressure::new(pool, header.gas_limit(), self.ignore_price)
+                .blend(price, self.oracle_config.percentile)
+        } else if let Some(min_inclusion_tip) = min_inclusion_tip(pool, header.gas_limit()) {
+            price.max(U256::from(min_inclusion_tip))
+        } else {
+            return Ok(price)
+        };
+        if let Some(max_price) = self.oracle_config.max_price {
+            price = price.min(max_price);
+        }
+        Ok(price)
+    }
+
+    /// Projects the base fee [`BASE_FEE_PROJECTION_BLOCKS`] blocks after the next block, assuming
+    /// that the average gas usage of the last `blocks` blocks persists.
+    ///
+    /// The projection is never lower than the base fee of the next block. Returns `None` if the
+    /// latest block has no base fee.
+    pub fn projected_base_fee(&self, base_fee_params: BaseFeeParams) -> EthResult<Option<u64>> {
+        let header = self
+            .provider
+            .sealed_header_by_number_or_tag(BlockNumberOrTag::Latest)?
+            .ok_or(EthApiError::HeaderNotFound(BlockId::latest()))?;
+        let Some(next_base_fee) = header.next_block_base_fee(base_fee_params) else {
+            return Ok(None)
+        };
+
+        let start = header.number().saturating_sub(self.oracle_config.blocks.max(1) as u64 - 1);
+        let (gas_used, gas_limit) = self
+            .provider
+            .headers_range(start..=header.number())?
+            .iter()
+            .fold((0u128, 0u128), |(gas_used, gas_limit), header| {
+                (gas_used + header.gas_used() as u128, gas_limit + header.gas_limit() as u128)
+            });
+        if gas_limit == 0 {
+            return Ok(Some(next_base_fee))
+        }
+
+        let block_gas_limit = header.gas_limit();
+        let projected_gas_used = (block_gas_limit as u128 * gas_used / gas_limit) as u64;
+        let mut base_fee = next_base_fee;
+        for _ in 0..BASE_FEE_PROJECTION_BLOCKS {
+            base_fee = calc_next_block_base_fee(
+                projected_gas_used,
+                block_gas_limit,
+                base_fee,
+                base_fee_params,
+            );
+        }
+
+        Ok(Some(base_fee.max(next_base_fee)))
+    }
+
     /// Get the `limit` lowest effective tip values for the given block. If the oracle has a
     /// configured `ignore_price` threshold, then tip values under that threshold will be ignored
     /// before returning a result.
@@ -277,6 +426,74 @@ where
     }
 }
 
+/// Returns the lowest effective tip of the best pending transactions of the pool that fill a block
+/// with the given gas limit, or `None` if they don't fill the block.
+fn min_inclusion_tip<Pool: TransactionPool>(pool: &Pool, gas_limit: u64) -> Option<u128> {
+    let base_fee = pool.block_info().pending_basefee;
+    let mut gas_used = 0u64;
+    for tx in pool.best_transactions_with_attributes(BestTransactionsAttributes::base_fee(base_fee))
+    {
+        gas_used = gas_used.saturating_add(tx.gas_limit());
+        if gas_used >= gas_limit {
+            return tx.effective_tip_per_gas(base_fee)
+        }
+    }
+    None
+}
+
+/// The best pending transactions of the pool that fit in the next block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct PoolPressure {
+    /// The gas limit of the block.
+    gas_limit: u64,
+    /// The gas of the pending transactions that fit in the block.
+    pending_gas: u64,
+    /// The effective tips of the pending transactions that fit in the block, in ascending order.
+    tips: Vec<u128>,
+}
+
+impl PoolPressure {
+    /// Collects the best pending transactions of the pool that fit in a block with the given gas
+    /// limit. Tips under `ignore_price` are not sampled, but their gas is still counted.
+    fn new<Pool: TransactionPool>(pool: &Pool, gas_limit: u64, ignore_price: Option<u128>) -> Self {
+        let base_fee = pool.block_info().pending_basefee;
+        let mut pressure = Self { gas_limit, ..Default::default() };
+        for tx in
+            pool.best_transactions_with_attributes(BestTransactionsAttributes::base_fee(base_fee))
+        {
+            let Some(pending_gas) = pressure.pending_gas.checked_add(tx.gas_limit()) else { break };
+            if pending_gas > gas_limit {
+                break
+            }
+            pressure.pending_gas = pending_gas;
+
+            let Some(tip) = tx.effective_tip_per_gas(base_fee) else { continue };
+            if ignore_price.is_none_or(|ignore_price| tip >= ignore_price) {
+                pressure.tips.push(tip);
+            }
+        }
+        pressure.tips.sort_unstable();
+        pressure
+    }
+
+    /// Blends the given estimate with the `percentile` of the sampled tips, weighted by the share
+    /// of the block filled by the pending transactions.
+    ///
+    /// An empty pool keeps the estimate, and a pool that fills the block replaces it, so the
+    /// estimate follows a spike of demand before it's included in the blocks.
+    fn blend(&self, price: U256, percentile: u32) -> U256 {
+        if self.tips.is_empty() || self.gas_limit == 0 {
+            return price
+        }
+
+        let index = (self.tips.len() - 1) * (percentile.min(100) as usize) / 100;
+        let pool_tip = U256::from(self.tips[index]);
+        let gas_limit = U256::from(self.gas_limit);
+        let pending_gas = U256::from(self.pending_gas);
+        (price * (gas_limit - pending_gas) + pool_tip * pending_gas) / gas_limit
+    }
+}
+
 /// Container type for mutable inner state of the [`GasPriceOracle`]
 #[derive(Debug)]
 struct GasPriceOracleInner {
@@ -325,6 +542,34 @@ impl Default for GasCap {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_consensus::{Block, Header};
+    use alloy_primitives::Address;
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, MockTransaction},
+        TransactionOrigin,
+    };
+
+    const GAS_LIMIT: u64 = 30_000_000;
+    const BASE_FEE: u64 = GWEI_TO_WEI;
+
+    /// Returns an oracle on top of three blocks with the given gas usage and base fee.
+    fn oracle(gas_used: u64, base_fee: Option<u64>) -> GasPriceOracle<MockEthProvider> {
+        let provider = MockEthProvider::default();
+        provider.extend_blocks((1..=3).map(|number| {
+            let header = Header {
+                number,
+                gas_limit: GAS_LIMIT,
+                gas_used,
+                base_fee_per_gas: base_fee,
+                ..Default::default()
+            };
+            (B256::with_last_byte(number as u8), Block { header, body: Default::default() })
+        }));
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let config = GasPriceOracleConfig { blocks: 3, ..Default::default() };
+        GasPriceOracle::new(provider, config, cache)
+    }
 
     #[test]
     fn max_price_sanity() {
@@ -336,4 +581,82 @@ mod tests {
     fn ignore_price_sanity() {
         assert_eq!(DEFAULT_IGNORE_GAS_PRICE, U256::from(2u64));
     }
+
+    #[test]
+    fn strategy_roundtrip() {
+        for strategy in GasPriceOracleStrategy::ALL {
+            assert_eq!(strategy.as_str().parse::<GasPriceOracleStrategy>().unwrap(), strategy);
+            assert_eq!(serde_json::to_string(&strategy).unwrap(), format!("\"{strategy}\""));
+        }
+        assert!("median".parse::<GasPriceOracleStrategy>().is_err());
+
+        // configs without a strategy use the percentile strategy
+        let config: GasPriceOracleConfig = serde_json::from_str(
+            r#"{"blocks":20,"percentile":60,"maxHeaderHistory":1024,"maxBlockHistory":1024,"maxRewardPercentileCount":400,"default":null,"maxPrice":null,"ignorePrice":null}"#,
+        )
+        .unwrap();
+        assert_eq!(config.strategy, GasPriceOracleStrategy::Percentile);
+    }
+
+    #[test]
+    fn pool_pressure_blend() {
+        let price = U256::from(10);
+
+        // an empty pool keeps the estimate
+        let empty = PoolPressure { gas_limit: 1_000, ..Default::default() };
+        assert_eq!(empty.blend(price, 60), price);
+
+        // a half full block moves the estimate halfway to the pool tip
+        let half = PoolPressure { gas_limit: 1_000, pending_gas: 500, tips: vec![20, 30, 40] };
+        assert_eq!(half.blend(price, 50), U256::from(20));
+        assert_eq!(half.blend(price, 100), U256::from(25));
+
+        // a full block replaces the estimate, also if the pool tips are lower
+        let full = PoolPressure { gas_limit: 1_000, pending_gas: 1_000, tips: vec![2, 4] };
+        assert_eq!(full.blend(price, 0), U256::from(2));
+        assert_eq!(full.blend(price, 100), U256::from(4));
+    }
+
+    #[tokio::test]
+    async fn projected_base_fee_follows_gas_usage() {
+        let params = BaseFeeParams::ethereum();
+        let next_base_fee = calc_next_block_base_fee(GAS_LIMIT, GAS_LIMIT, BASE_FEE, params);
+
+        // full blocks raise the base fee in every projected block
+        let projected = oracle(GAS_LIMIT, Some(BASE_FEE)).projected_base_fee(params).unwrap();
+        let expected = (0..BASE_FEE_PROJECTION_BLOCKS).fold(next_base_fee, |base_fee, _| {
+            calc_next_block_base_fee(GAS_LIMIT, GAS_LIMIT, base_fee, params)
+        });
+        assert_eq!(projected, Some(expected));
+        assert!(expected > next_base_fee);
+
+        // empty blocks don't project the base fee below the base fee of the next block
+        let next_base_fee = calc_next_block_base_fee(0, GAS_LIMIT, BASE_FEE, params);
+        let projected = oracle(0, Some(BASE_FEE)).projected_base_fee(params).unwrap();
+        assert_eq!(projected, Some(next_base_fee));
+
+        // blocks without a base fee can't be projected
+        assert_eq!(oracle(GAS_LIMIT, None).projected_base_fee(params).unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn min_inclusion_tip_of_filled_block() {
+        let pool = testing_pool();
+        let sender = Address::random();
+        // the transactions are ordered by tip, the one with the lowest tip fills the block
+        for (nonce, tip) in [(0, 30), (1, 20), (2, 10)] {
+            let tx = MockTransaction::eip1559()
+                .with_sender(sender)
+                .with_nonce(nonce)
+                .with_max_fee(u64::MAX as u128)
+                .with_priority_fee(tip)
+                .with_gas_limit(1_000);
+            pool.add_transaction(TransactionOrigin::External, tx).await.unwrap();
+        }
+
+        assert_eq!(min_inclusion_tip(&pool, 1_000), Some(30));
+        assert_eq!(min_inclusion_tip(&pool, 2_500), Some(10));
+        // the pending transactions don't fill the block
+        assert_eq!(min_inclusion_tip(&pool, 3_001), None);
+    }
 }
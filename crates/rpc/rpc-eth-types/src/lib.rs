@@ -8,6 +8,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+pub mod block;
 pub mod builder;
 pub mod cache;
 pub mod error;
@@ -16,12 +17,15 @@ pub mod gas_oracle;
 pub mod id_provider;
 pub mod logs_utils;
 pub mod pending_block;
+pub mod proof_pool;
 pub mod receipt;
+pub mod remote_state;
 pub mod revm_utils;
 pub mod simulate;
 pub mod transaction;
 pub mod utils;
 
+pub use block::BlockFields;
 pub use builder::config::{EthConfig, EthFilterConfig};
 pub use cache::{
     config::EthStateCacheConfig, db::StateCacheDb, multi_consumer::MultiConsumerLruCache,
@@ -30,9 +34,12 @@ pub use cache::{
 pub use error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError, SignError};
 pub use fee_history::{FeeHistoryCache, FeeHistoryCacheConfig, FeeHistoryEntry};
 pub use gas_oracle::{
-    GasCap, GasPriceOracle, GasPriceOracleConfig, GasPriceOracleResult, RPC_DEFAULT_GAS_CAP,
+    GasCap, GasPriceOracle, GasPriceOracleConfig, GasPriceOracleResult, GasPriceOracleStrategy,
+    RPC_DEFAULT_GAS_CAP,
 };
 pub use id_provider::EthSubscriptionIdProvider;
 pub use pending_block::{PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin};
+pub use proof_pool::{ProofTaskPool, ProofTaskPriority};
 pub use receipt::EthReceiptBuilder;
+pub use remote_state::{RemoteState, RemoteStateError, RemoteStateFetcher, RemoteStateProvider};
 pub use transaction::TransactionSource;
@@ -4,12 +4,13 @@
 
 use alloy_consensus::TxReceipt;
 use alloy_eips::{eip2718::Encodable2718, BlockNumHash};
-use alloy_primitives::TxHash;
+use alloy_primitives::{Bytes, TxHash, B256};
 use alloy_rpc_types_eth::{FilteredParams, Log};
 use reth_chainspec::ChainInfo;
 use reth_errors::ProviderError;
 use reth_primitives_traits::{BlockBody, RecoveredBlock, SignedTransaction};
 use reth_storage_api::{BlockReader, ProviderBlock};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 /// Returns all matching of a block's receipts when the transaction hashes are known.
@@ -182,6 +183,136 @@ pub fn get_filter_block_range(
     (from_block_number, to_block_number)
 }
 
+/// The position of a log in the chain, used as continuation token of paginated logs queries.
+///
+/// Positions are ordered by block number, transaction index and log index. The cursor also
+/// contains the hash of its block, so a reorg of the blocks of the previous pages can be detected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LogCursor {
+    /// Number of the block containing the log.
+    pub block_number: u64,
+    /// Hash of the block containing the log.
+    pub block_hash: B256,
+    /// Index of the transaction in the block.
+    pub transaction_index: u64,
+    /// Index of the log in the block.
+    pub log_index: u64,
+}
+
+impl LogCursor {
+    /// Length of an encoded cursor.
+    const ENCODED_LEN: usize = 56;
+
+    /// Returns the position at the start of the given block.
+    pub const fn block_start(block: BlockNumHash) -> Self {
+        Self {
+            block_number: block.number,
+            block_hash: block.hash,
+            transaction_index: 0,
+            log_index: 0,
+        }
+    }
+
+    /// Returns the position of the given log.
+    pub fn from_log(log: &Log) -> Self {
+        Self {
+            block_number: log.block_number.unwrap_or_default(),
+            block_hash: log.block_hash.unwrap_or_default(),
+            transaction_index: log.transaction_index.unwrap_or_default(),
+            log_index: log.log_index.unwrap_or_default(),
+        }
+    }
+
+    /// Encodes the cursor into an opaque continuation token.
+    pub fn encode(&self) -> Bytes {
+        let mut token = Vec::with_capacity(Self::ENCODED_LEN);
+        token.extend_from_slice(&self.block_number.to_be_bytes());
+        token.extend_from_slice(self.block_hash.as_slice());
+        token.extend_from_slice(&self.transaction_index.to_be_bytes());
+        token.extend_from_slice(&self.log_index.to_be_bytes());
+        token.into()
+    }
+
+    /// Decodes a continuation token created with [`LogCursor::encode`].
+    ///
+    /// Returns `None` if the token is malformed.
+    pub fn decode(token: &[u8]) -> Option<Self> {
+        if token.len() != Self::ENCODED_LEN {
+            return None
+        }
+        let word =
+            |offset: usize| u64::from_be_bytes(token[offset..offset + 8].try_into().unwrap());
+        Some(Self {
+            block_number: word(0),
+            block_hash: B256::from_slice(&token[8..40]),
+            transaction_index: word(40),
+            log_index: word(48),
+        })
+    }
+}
+
+/// Returns the last block of a page of a paginated logs query that starts at the given block.
+///
+/// A page scans at most `max_blocks` blocks, but at least one block.
+pub fn logs_page_end_block(start_block: u64, to_block: u64, max_blocks: Option<u64>) -> u64 {
+    max_blocks.map_or(to_block, |max_blocks| {
+        to_block.min(start_block.saturating_add(max_blocks.max(1) - 1))
+    })
+}
+
+/// Collects the logs of a page of a paginated logs query.
+#[derive(Debug)]
+pub struct LogsPageCollector {
+    /// The position of the first log of the page, if the page continues a previous page.
+    cursor: Option<LogCursor>,
+    /// The maximum number of logs of the page.
+    max_logs: Option<usize>,
+    /// The collected logs.
+    logs: Vec<Log>,
+}
+
+impl LogsPageCollector {
+    /// Creates a collector for the page that starts at the given cursor.
+    ///
+    /// A page contains at least one log, so the query makes progress even if the maximum number
+    /// of logs is zero.
+    pub fn new(cursor: Option<LogCursor>, max_logs: Option<usize>) -> Self {
+        Self { cursor, max_logs: max_logs.map(|max_logs| max_logs.max(1)), logs: Vec::new() }
+    }
+
+    /// Adds the matching logs of the next block, skipping the logs before the cursor.
+    ///
+    /// Returns the cursor of the next page once the page is full.
+    pub fn extend(&mut self, block_logs: Vec<Log>) -> Option<LogCursor> {
+        for log in block_logs {
+            let position = LogCursor::from_log(&log);
+            if self.cursor.is_some_and(|cursor| position < cursor) {
+                continue
+            }
+            if self.max_logs.is_some_and(|max_logs| self.logs.len() >= max_logs) {
+                return Some(position)
+            }
+            self.logs.push(log);
+        }
+        None
+    }
+
+    /// Returns the page with the given cursor of the next page, `None` if this is the last page.
+    pub fn finish(self, next: Option<LogCursor>) -> LogsPage {
+        LogsPage { logs: self.logs, cursor: next.map(|cursor| cursor.encode()) }
+    }
+}
+
+/// A page of the logs matching a filter.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogsPage {
+    /// The matching logs of the page.
+    pub logs: Vec<Log>,
+    /// The continuation token to request the next page with, `None` if this is the last page.
+    pub cursor: Option<Bytes>,
+}
+
 #[cfg(test)]
 mod tests {
     use alloy_rpc_types_eth::Filter;
@@ -255,4 +386,66 @@ mod tests {
         assert_eq!(from_block_number, 16022082);
         assert_eq!(to_block_number, best_number);
     }
+
+    #[test]
+    fn log_cursor_roundtrip() {
+        let cursor = LogCursor {
+            block_number: 17,
+            block_hash: B256::random(),
+            transaction_index: 3,
+            log_index: 42,
+        };
+        assert_eq!(LogCursor::decode(&cursor.encode()), Some(cursor));
+        assert_eq!(LogCursor::decode(&cursor.encode()[1..]), None);
+        assert!(LogCursor::block_start(BlockNumHash::new(18, B256::random())) > cursor);
+    }
+
+    #[test]
+    fn logs_page_scans_max_blocks() {
+        assert_eq!(logs_page_end_block(10, 100, Some(5)), 14);
+        assert_eq!(logs_page_end_block(10, 12, Some(5)), 12);
+        assert_eq!(logs_page_end_block(10, 100, None), 100);
+        // a page scans at least one block
+        assert_eq!(logs_page_end_block(10, 100, Some(0)), 10);
+        assert_eq!(logs_page_end_block(10, 100, Some(1)), 10);
+    }
+
+    fn block_logs(number: u64, count: u64) -> Vec<Log> {
+        let hash = B256::with_last_byte(number as u8);
+        (0..count)
+            .map(|idx| Log {
+                block_number: Some(number),
+                block_hash: Some(hash),
+                transaction_index: Some(idx),
+                log_index: Some(idx),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    /// Collects all logs of the blocks page by page, returning the number of logs per page.
+    fn collect_pages(blocks: &[Vec<Log>], max_logs: Option<usize>) -> Vec<usize> {
+        let mut pages = Vec::new();
+        let mut cursor = None;
+        loop {
+            let start = cursor.map_or(0, |cursor: LogCursor| cursor.block_number as usize);
+            let mut page = LogsPageCollector::new(cursor, max_logs);
+            let next = blocks[start..].iter().find_map(|logs| page.extend(logs.clone()));
+            let page = page.finish(next);
+            pages.push(page.logs.len());
+            match page.cursor {
+                Some(token) => cursor = Some(LogCursor::decode(&token).unwrap()),
+                None => return pages,
+            }
+        }
+    }
+
+    #[test]
+    fn logs_pages_contain_every_log_once() {
+        let blocks = (0..4).map(|number| block_logs(number, number + 1)).collect::<Vec<_>>();
+        assert_eq!(collect_pages(&blocks, Some(3)), vec![3, 3, 3, 1]);
+        assert_eq!(collect_pages(&blocks, None), vec![10]);
+        // a limit of zero logs still makes progress
+        assert_eq!(collect_pages(&blocks, Some(0)), vec![1; 10]);
+    }
 }
@@ -0,0 +1,397 @@
+//! A dedicated pool for generating state proofs.
+
+use crate::EthApiError;
+use parking_lot::Mutex;
+use reth_metrics::{
+    metrics::{Counter, Gauge, Histogram},
+    Metrics,
+};
+use std::{
+    collections::VecDeque,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::oneshot;
+
+/// The number of tasks that may wait in the queue of a [`ProofTaskPool`] per worker, before new
+/// tasks are rejected.
+pub const DEFAULT_MAX_QUEUED_PROOF_TASKS_PER_WORKER: usize = 4;
+
+/// The priority of a proof task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofTaskPriority {
+    /// Single account proofs, e.g. `eth_getProof`.
+    High,
+    /// Batched proofs and execution witnesses.
+    Low,
+}
+
+impl ProofTaskPriority {
+    const fn as_str(&self) -> &'static str {
+        match self {
+            Self::High => "high",
+            Self::Low => "low",
+        }
+    }
+}
+
+/// A bounded pool dedicated to state proof generation.
+///
+/// Proofs are generated outside of the tracing pool, so heavy proof traffic can't starve the
+/// tracing endpoints and vice versa. Up to `max_tasks` proofs are generated concurrently, on
+/// blocking threads that are spawned on demand.
+///
+/// Tasks wait in a queue until a worker is available. Waiting [`ProofTaskPriority::High`] tasks
+/// are always served before waiting [`ProofTaskPriority::Low`] tasks, and low priority tasks may
+/// only occupy half of the workers, so that single account proofs are served even if the pool is
+/// busy generating witnesses. Once the queue of a priority is full, new tasks of that priority are
+/// rejected with [`EthApiError::ProofQueueFull`].
+#[derive(Debug, Clone)]
+pub struct ProofTaskPool {
+    scheduler: Arc<ProofTaskScheduler>,
+}
+
+impl ProofTaskPool {
+    /// Creates a new pool that runs up to `max_tasks` tasks concurrently and queues up to
+    /// [`DEFAULT_MAX_QUEUED_PROOF_TASKS_PER_WORKER`] tasks per worker.
+    pub fn new(max_tasks: usize) -> Self {
+        let max_tasks = max_tasks.max(1);
+        Self::with_max_queued_tasks(
+            max_tasks,
+            max_tasks * DEFAULT_MAX_QUEUED_PROOF_TASKS_PER_WORKER,
+        )
+    }
+
+    /// Creates a new pool that runs up to `max_tasks` tasks concurrently and queues up to
+    /// `max_queued_tasks` tasks of each priority.
+    pub fn with_max_queued_tasks(max_tasks: usize, max_queued_tasks: usize) -> Self {
+        let max_tasks = max_tasks.max(1);
+        Self {
+            scheduler: Arc::new(ProofTaskScheduler {
+                max_tasks,
+                max_low_priority_tasks: max_tasks.div_ceil(2),
+                max_queued_tasks,
+                state: Default::default(),
+                high_priority_metrics: ProofTaskPoolMetrics::new_with_labels(&[(
+                    "priority",
+                    ProofTaskPriority::High.as_str(),
+                )]),
+                low_priority_metrics: ProofTaskPoolMetrics::new_with_labels(&[(
+                    "priority",
+                    ProofTaskPriority::Low.as_str(),
+                )]),
+            }),
+        }
+    }
+
+    /// Runs the function on the pool once a worker is available for the given priority and returns
+    /// its result.
+    pub async fn spawn<F, R>(&self, priority: ProofTaskPriority, func: F) -> Result<R, EthApiError>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let permit = self.scheduler.acquire(priority).await?;
+        let metrics = self.scheduler.metrics(priority).clone();
+
+        tokio::task::spawn_blocking(move || {
+            // the permit is released once the task is done, even if the caller went away
+            let _permit = permit;
+            metrics.active_tasks.increment(1);
+            let started_at = Instant::now();
+            let res = func();
+            metrics.execution_duration.record(started_at.elapsed());
+            metrics.active_tasks.decrement(1);
+            res
+        })
+        .await
+        .map_err(|_| EthApiError::InternalBlockingTaskError)
+    }
+}
+
+/// Hands out the workers of a [`ProofTaskPool`] by priority.
+#[derive(Debug)]
+struct ProofTaskScheduler {
+    /// The maximum number of tasks running concurrently.
+    max_tasks: usize,
+    /// The maximum number of [`ProofTaskPriority::Low`] tasks running concurrently.
+    max_low_priority_tasks: usize,
+    /// The maximum number of tasks waiting per priority.
+    max_queued_tasks: usize,
+    /// The running and waiting tasks.
+    state: Mutex<SchedulerState>,
+    /// Metrics of the [`ProofTaskPriority::High`] tasks.
+    high_priority_metrics: ProofTaskPoolMetrics,
+    /// Metrics of the [`ProofTaskPriority::Low`] tasks.
+    low_priority_metrics: ProofTaskPoolMetrics,
+}
+
+/// The running and waiting tasks of a [`ProofTaskScheduler`].
+#[derive(Debug, Default)]
+struct SchedulerState {
+    /// The number of running tasks.
+    active: usize,
+    /// The number of running [`ProofTaskPriority::Low`] tasks.
+    active_low_priority: usize,
+    /// The waiting [`ProofTaskPriority::High`] tasks, in order of arrival.
+    high_priority_queue: VecDeque<oneshot::Sender<ProofTaskPermit>>,
+    /// The waiting [`ProofTaskPriority::Low`] tasks, in order of arrival.
+    low_priority_queue: VecDeque<oneshot::Sender<ProofTaskPermit>>,
+}
+
+impl SchedulerState {
+    const fn queue_mut(
+        &mut self,
+        priority: ProofTaskPriority,
+    ) -> &mut VecDeque<oneshot::Sender<ProofTaskPermit>> {
+        match priority {
+            ProofTaskPriority::High => &mut self.high_priority_queue,
+            ProofTaskPriority::Low => &mut self.low_priority_queue,
+        }
+    }
+}
+
+impl ProofTaskScheduler {
+    const fn metrics(&self, priority: ProofTaskPriority) -> &ProofTaskPoolMetrics {
+        match priority {
+            ProofTaskPriority::High => &self.high_priority_metrics,
+            ProofTaskPriority::Low => &self.low_priority_metrics,
+        }
+    }
+
+    /// Returns the priority of the next task that can be started, if any.
+    fn next_priority(&self, state: &SchedulerState) -> Option<ProofTaskPriority> {
+        if state.active >= self.max_tasks {
+            return None
+        }
+        if !state.high_priority_queue.is_empty() {
+            return Some(ProofTaskPriority::High)
+        }
+        (!state.low_priority_queue.is_empty() &&
+            state.active_low_priority < self.max_low_priority_tasks)
+            .then_some(ProofTaskPriority::Low)
+    }
+
+    /// Marks a task of the given priority as running.
+    fn start(
+        self: &Arc<Self>,
+        state: &mut SchedulerState,
+        priority: ProofTaskPriority,
+    ) -> ProofTaskPermit {
+        state.active += 1;
+        if priority == ProofTaskPriority::Low {
+            state.active_low_priority += 1;
+        }
+        ProofTaskPermit { scheduler: Some(self.clone()), priority }
+    }
+
+    /// Marks a task of the given priority as done.
+    const fn finish(state: &mut SchedulerState, priority: ProofTaskPriority) {
+        state.active -= 1;
+        if priority == ProofTaskPriority::Low {
+            state.active_low_priority -= 1;
+        }
+    }
+
+    /// Starts the waiting tasks as long as workers are available, high priority tasks first.
+    fn dispatch(self: &Arc<Self>, state: &mut SchedulerState) {
+        while let Some(priority) = self.next_priority(state) {
+            let Some(waiter) = state.queue_mut(priority).pop_front() else { break };
+            let permit = self.start(state, priority);
+            if let Err(mut permit) = waiter.send(permit) {
+                // the caller went away while waiting
+                permit.scheduler = None;
+                Self::finish(state, priority);
+            }
+        }
+    }
+
+    /// Waits until a worker is available for a task of the given priority.
+    async fn acquire(
+        self: &Arc<Self>,
+        priority: ProofTaskPriority,
+    ) -> Result<ProofTaskPermit, EthApiError> {
+        let metrics = self.metrics(priority);
+        let rx = {
+            let mut state = self.state.lock();
+            let can_start = state.active < self.max_tasks &&
+                match priority {
+                    ProofTaskPriority::High => state.high_priority_queue.is_empty(),
+                    ProofTaskPriority::Low => {
+                        state.high_priority_queue.is_empty() &&
+                            state.low_priority_queue.is_empty() &&
+                            state.active_low_priority < self.max_low_priority_tasks
+                    }
+                };
+            if can_start {
+                metrics.queue_duration.record(Duration::ZERO);
+                return Ok(self.start(&mut state, priority))
+            }
+
+            let max_queued_tasks = self.max_queued_tasks;
+            let queue = state.queue_mut(priority);
+            // callers that went away while waiting don't count towards the limit
+            queue.retain(|waiter| !waiter.is_closed());
+            if queue.len() >= max_queued_tasks {
+                metrics.rejected_tasks.increment(1);
+                return Err(EthApiError::ProofQueueFull)
+            }
+            let (tx, rx) = oneshot::channel();
+            queue.push_back(tx);
+            rx
+        };
+
+        let queued = QueuedTask::new(&metrics.queued_tasks);
+        let permit = rx.await.map_err(|_| EthApiError::InternalBlockingTaskError)?;
+        metrics.queue_duration.record(queued.started_at.elapsed());
+        Ok(permit)
+    }
+}
+
+/// A running task of a [`ProofTaskPool`], which frees its worker for the next waiting task once
+/// dropped.
+#[derive(Debug)]
+struct ProofTaskPermit {
+    scheduler: Option<Arc<ProofTaskScheduler>>,
+    priority: ProofTaskPriority,
+}
+
+impl Drop for ProofTaskPermit {
+    fn drop(&mut self) {
+        if let Some(scheduler) = self.scheduler.take() {
+            let mut state = scheduler.state.lock();
+            ProofTaskScheduler::finish(&mut state, self.priority);
+            scheduler.dispatch(&mut state);
+        }
+    }
+}
+
+/// Tracks a task in the queue of the pool, until it's dropped.
+struct QueuedTask<'a> {
+    gauge: &'a Gauge,
+    started_at: Instant,
+}
+
+impl<'a> QueuedTask<'a> {
+    fn new(gauge: &'a Gauge) -> Self {
+        gauge.increment(1);
+        Self { gauge, started_at: Instant::now() }
+    }
+}
+
+impl Drop for QueuedTask<'_> {
+    fn drop(&mut self) {
+        self.gauge.decrement(1);
+    }
+}
+
+/// Metrics of the [`ProofTaskPool`], labeled by the priority of the tasks.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "rpc.proof_pool")]
+struct ProofTaskPoolMetrics {
+    /// The number of tasks waiting for a worker.
+    queued_tasks: Gauge,
+    /// The number of tasks running on a worker.
+    active_tasks: Gauge,
+    /// The number of tasks rejected because the queue was full.
+    rejected_tasks: Counter,
+    /// The time tasks waited for a worker.
+    queue_duration: Histogram,
+    /// The time tasks ran on a worker.
+    execution_duration: Histogram,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    /// Occupies a worker with a task of the given priority until the returned sender is used.
+    async fn occupy(
+        pool: &ProofTaskPool,
+        priority: ProofTaskPriority,
+    ) -> (mpsc::Sender<()>, tokio::task::JoinHandle<Result<(), EthApiError>>) {
+        let active = pool.scheduler.state.lock().active;
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let task = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.spawn(priority, move || release_rx.recv().unwrap()).await }
+        });
+        while pool.scheduler.state.lock().active == active {
+            tokio::task::yield_now().await;
+        }
+        (release_tx, task)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn low_priority_tasks_leave_workers_for_high_priority() {
+        let pool = ProofTaskPool::new(2);
+
+        // occupy the only worker available for low priority tasks
+        let (release, blocking) = occupy(&pool, ProofTaskPriority::Low).await;
+
+        // another low priority task is queued
+        let queued = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.spawn(ProofTaskPriority::Low, || 2).await }
+        });
+
+        // high priority tasks are still served
+        assert_eq!(pool.spawn(ProofTaskPriority::High, || 1).await.unwrap(), 1);
+        assert!(!queued.is_finished());
+
+        release.send(()).unwrap();
+        blocking.await.unwrap().unwrap();
+        assert_eq!(queued.await.unwrap().unwrap(), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn waiting_high_priority_tasks_are_served_first() {
+        let pool = ProofTaskPool::new(1);
+        let (release, blocking) = occupy(&pool, ProofTaskPriority::High).await;
+
+        let low = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.spawn(ProofTaskPriority::Low, Instant::now).await }
+        });
+        while pool.scheduler.state.lock().low_priority_queue.is_empty() {
+            tokio::task::yield_now().await;
+        }
+        let high = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.spawn(ProofTaskPriority::High, Instant::now).await }
+        });
+        while pool.scheduler.state.lock().high_priority_queue.is_empty() {
+            tokio::task::yield_now().await;
+        }
+
+        // the high priority task arrived last, but starts first
+        release.send(()).unwrap();
+        blocking.await.unwrap().unwrap();
+        let high_started_at = high.await.unwrap().unwrap();
+        let low_started_at = low.await.unwrap().unwrap();
+        assert!(high_started_at <= low_started_at);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn rejects_tasks_once_the_queue_is_full() {
+        let pool = ProofTaskPool::with_max_queued_tasks(1, 1);
+        let (release, blocking) = occupy(&pool, ProofTaskPriority::High).await;
+
+        let queued = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.spawn(ProofTaskPriority::High, || 1).await }
+        });
+        while pool.scheduler.state.lock().high_priority_queue.is_empty() {
+            tokio::task::yield_now().await;
+        }
+
+        let err = pool.spawn(ProofTaskPriority::High, || 2).await.unwrap_err();
+        assert!(matches!(err, EthApiError::ProofQueueFull));
+
+        release.send(()).unwrap();
+        blocking.await.unwrap().unwrap();
+        assert_eq!(queued.await.unwrap().unwrap(), 1);
+        assert_eq!(pool.scheduler.state.lock().active, 0);
+    }
+}
@@ -0,0 +1,608 @@
+//! State of a block that is fetched from a remote node if it's pruned locally.
+
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_eips::BlockId;
+use alloy_primitives::{
+    keccak256, map::HashMap, Address, BlockNumber, Bytes, StorageKey, StorageValue, B256,
+};
+use alloy_rpc_types_eth::EIP1186AccountProofResponse;
+use jsonrpsee_core::{client::ClientT, rpc_params};
+use parking_lot::Mutex;
+use reth_errors::{ProviderError, ProviderResult};
+use reth_primitives_traits::{Account, Bytecode};
+use reth_storage_api::{
+    AccountReader, BlockHashReader, HashedPostStateProvider, StateProofProvider, StateProvider,
+    StateRootProvider, StorageRootProvider,
+};
+use reth_trie_common::{
+    proof::ProofVerificationError, updates::TrieUpdates, AccountProof, HashedPostState,
+    HashedStorage, HashedStorageRange, MultiProof, MultiProofTargets, StorageMultiProof,
+    StorageProof, TrieInput, EMPTY_ROOT_HASH,
+};
+use revm::database::BundleState;
+use std::future::Future;
+use tokio::runtime::Handle;
+
+/// Fetches the state of a block from a remote node.
+pub trait RemoteStateFetcher: Send + Sync {
+    /// The error of a failed request.
+    type Error: core::error::Error + Send + Sync + 'static;
+
+    /// Returns the proof of the account and the given storage slots at the block, e.g. with
+    /// `eth_getProof`.
+    fn proof(
+        &self,
+        address: Address,
+        slots: Vec<B256>,
+        block: BlockId,
+    ) -> impl Future<Output = Result<EIP1186AccountProofResponse, Self::Error>> + Send;
+
+    /// Returns the code of the account at the block, e.g. with `eth_getCode`.
+    fn code(
+        &self,
+        address: Address,
+        block: BlockId,
+    ) -> impl Future<Output = Result<Bytes, Self::Error>> + Send;
+}
+
+impl<C: ClientT + Send + Sync> RemoteStateFetcher for C {
+    type Error = jsonrpsee_core::client::Error;
+
+    fn proof(
+        &self,
+        address: Address,
+        slots: Vec<B256>,
+        block: BlockId,
+    ) -> impl Future<Output = Result<EIP1186AccountProofResponse, Self::Error>> + Send {
+        async move { self.request("eth_getProof", rpc_params![address, slots, block]).await }
+    }
+
+    fn code(
+        &self,
+        address: Address,
+        block: BlockId,
+    ) -> impl Future<Output = Result<Bytes, Self::Error>> + Send {
+        async move { self.request("eth_getCode", rpc_params![address, block]).await }
+    }
+}
+
+/// Errors of state that is fetched from a remote node.
+#[derive(Debug, thiserror::Error)]
+pub enum RemoteStateError {
+    /// The request to the remote node failed.
+    #[error("failed to fetch state from remote node: {0}")]
+    Fetch(#[source] Box<dyn core::error::Error + Send + Sync>),
+    /// The remote node returned a proof for another account or other storage slots.
+    #[error("remote node returned an unexpected proof for account {0}")]
+    UnexpectedProof(Address),
+    /// The proof doesn't match the trusted state root.
+    #[error("invalid proof for account {0}: {1}")]
+    InvalidProof(Address, #[source] ProofVerificationError),
+    /// The fetched code doesn't match the proven code hash.
+    #[error("code of account {address} doesn't match code hash {code_hash}")]
+    CodeHashMismatch {
+        /// The account.
+        address: Address,
+        /// The proven code hash.
+        code_hash: B256,
+    },
+}
+
+impl From<RemoteStateError> for ProviderError {
+    fn from(error: RemoteStateError) -> Self {
+        Self::other(error)
+    }
+}
+
+/// A remote node to fetch the state of blocks that are pruned locally from.
+#[derive(Debug, Clone)]
+pub struct RemoteState<F> {
+    /// Fetches the state from the remote node.
+    fetcher: F,
+    /// The runtime the requests are executed on.
+    handle: Handle,
+}
+
+impl<F> RemoteState<F> {
+    /// Creates a new remote state that executes the requests on the current runtime.
+    ///
+    /// # Panics
+    ///
+    /// If called outside of a tokio runtime.
+    pub fn new(fetcher: F) -> Self {
+        Self { fetcher, handle: Handle::current() }
+    }
+
+    /// Returns a [`RemoteStateProvider`] for the local state of the block with the given hash and
+    /// trusted state root.
+    pub fn state_provider<S>(
+        &self,
+        state: S,
+        block_hash: B256,
+        state_root: B256,
+    ) -> RemoteStateProvider<S, F>
+    where
+        F: Clone,
+    {
+        RemoteStateProvider::new(state, self.clone(), block_hash, state_root)
+    }
+}
+
+/// A [`StateProvider`] that fetches the state of a block from a remote node if it's pruned
+/// locally.
+///
+/// Reads are served by the local state provider. Accounts and storage slots that are pruned
+/// locally are fetched with `eth_getProof` and verified against the trusted state root of the
+/// block, i.e. the state root of the local header. Missing bytecode is fetched with
+/// `eth_getCode` and verified against the proven code hash. Fetched state is cached, so every
+/// account and slot is fetched at most once. State roots and proofs are computed by the local
+/// state provider.
+///
+/// This is used as the state of `eth_call` and tracing if a remote node is configured. The
+/// requests block the current thread, so this must not be used on a thread of a current-thread
+/// runtime.
+#[derive(Debug)]
+pub struct RemoteStateProvider<S, F> {
+    /// The local state of the block.
+    state: S,
+    /// The remote node.
+    remote: RemoteState<F>,
+    /// The hash of the block.
+    block_hash: B256,
+    /// The trusted state root of the block.
+    state_root: B256,
+    /// The fetched accounts, with their storage root.
+    accounts: Mutex<HashMap<Address, Option<(Account, B256)>>>,
+    /// The fetched storage slots.
+    storage: Mutex<HashMap<(Address, StorageKey), StorageValue>>,
+    /// The accounts of the fetched code hashes, to fetch missing bytecode.
+    code_owners: Mutex<HashMap<B256, Address>>,
+}
+
+impl<S, F> RemoteStateProvider<S, F> {
+    /// Creates a new provider for the state of the block with the given hash and trusted state
+    /// root.
+    pub fn new(state: S, remote: RemoteState<F>, block_hash: B256, state_root: B256) -> Self {
+        Self {
+            state,
+            remote,
+            block_hash,
+            state_root,
+            accounts: Default::default(),
+            storage: Default::default(),
+            code_owners: Default::default(),
+        }
+    }
+
+    /// Returns the local state provider.
+    pub const fn inner(&self) -> &S {
+        &self.state
+    }
+}
+
+impl<S, F: RemoteStateFetcher> RemoteStateProvider<S, F> {
+    /// Blocks on the request.
+    fn fetch<T>(
+        &self,
+        request: impl Future<Output = Result<T, F::Error>>,
+    ) -> Result<T, RemoteStateError> {
+        tokio::task::block_in_place(|| self.remote.handle.block_on(request))
+            .map_err(|err| RemoteStateError::Fetch(Box::new(err)))
+    }
+
+    /// Fetches the proof of the account and the given storage slots, and verifies it against the
+    /// state root.
+    fn fetch_proof(
+        &self,
+        address: Address,
+        slots: Vec<B256>,
+    ) -> Result<AccountProof, RemoteStateError> {
+        let response =
+            self.fetch(self.remote.fetcher.proof(address, slots.clone(), self.block_hash.into()))?;
+        let mut proof = AccountProof::from_eip1186_proof(response);
+        if proof.address != address ||
+            proof.storage_proofs.iter().map(|proof| proof.key).ne(slots.iter().copied())
+        {
+            return Err(RemoteStateError::UnexpectedProof(address))
+        }
+        proof
+            .verify(self.state_root)
+            .map_err(|err| RemoteStateError::InvalidProof(address, err))?;
+
+        // accounts without code are stored without code hash
+        if let Some(info) = &mut proof.info {
+            info.bytecode_hash = info.bytecode_hash.filter(|code_hash| *code_hash != KECCAK_EMPTY);
+        }
+
+        let account = proof.info.map(|info| (info, proof.storage_root));
+        if let Some(code_hash) = account.and_then(|(info, _)| info.bytecode_hash) {
+            self.code_owners.lock().insert(code_hash, address);
+        }
+        self.accounts.lock().insert(address, account);
+        Ok(proof)
+    }
+
+    /// Returns the account, fetching it from the remote node if it wasn't fetched before.
+    fn remote_account(&self, address: Address) -> Result<Option<Account>, RemoteStateError> {
+        if let Some(account) = self.accounts.lock().get(&address) {
+            return Ok(account.map(|(info, _)| info))
+        }
+        Ok(self.fetch_proof(address, Vec::new())?.info)
+    }
+
+    /// Returns the storage value, fetching it from the remote node if it wasn't fetched before.
+    fn remote_storage(
+        &self,
+        address: Address,
+        slot: StorageKey,
+    ) -> Result<StorageValue, RemoteStateError> {
+        if let Some(value) = self.storage.lock().get(&(address, slot)) {
+            return Ok(*value)
+        }
+        // no need to fetch the slots of accounts that don't exist or have no storage
+        if self.accounts.lock().get(&address).is_some_and(|account| {
+            account.is_none_or(|(_, storage_root)| storage_root == EMPTY_ROOT_HASH)
+        }) {
+            return Ok(StorageValue::ZERO)
+        }
+
+        let proof = self.fetch_proof(address, vec![slot])?;
+        let value = proof.storage_proofs[0].value;
+        self.storage.lock().insert((address, slot), value);
+        Ok(value)
+    }
+
+    /// Fetches the bytecode of an account that was fetched from the remote node.
+    fn remote_bytecode(&self, code_hash: B256) -> Result<Option<Bytecode>, RemoteStateError> {
+        let Some(address) = self.code_owners.lock().get(&code_hash).copied() else {
+            return Ok(None)
+        };
+        let code = self.fetch(self.remote.fetcher.code(address, self.block_hash.into()))?;
+        if keccak256(&code) != code_hash {
+            return Err(RemoteStateError::CodeHashMismatch { address, code_hash })
+        }
+        Ok(Some(Bytecode::new_raw(code)))
+    }
+}
+
+impl<S: StateProvider, F: RemoteStateFetcher> AccountReader for RemoteStateProvider<S, F> {
+    fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
+        match self.state.basic_account(address) {
+            Err(ProviderError::StateAtBlockPruned(_)) => Ok(self.remote_account(*address)?),
+            result => result,
+        }
+    }
+}
+
+impl<S: StateProvider, F: RemoteStateFetcher> StateProvider for RemoteStateProvider<S, F> {
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        match self.state.storage(account, storage_key) {
+            Err(ProviderError::StateAtBlockPruned(_)) => {
+                Ok(Some(self.remote_storage(account, storage_key)?))
+            }
+            result => result,
+        }
+    }
+
+    fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        match self.state.bytecode_by_hash(code_hash)? {
+            Some(bytecode) => Ok(Some(bytecode)),
+            None => Ok(self.remote_bytecode(*code_hash)?),
+        }
+    }
+}
+
+impl<S: StateProvider, F: RemoteStateFetcher> BlockHashReader for RemoteStateProvider<S, F> {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.state.block_hash(number)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.state.canonical_hashes_range(start, end)
+    }
+}
+
+impl<S: StateProvider, F: RemoteStateFetcher> HashedPostStateProvider
+    for RemoteStateProvider<S, F>
+{
+    fn hashed_post_state(&self, bundle_state: &BundleState) -> HashedPostState {
+        self.state.hashed_post_state(bundle_state)
+    }
+}
+
+impl<S: StateProvider, F: RemoteStateFetcher> StateRootProvider for RemoteStateProvider<S, F> {
+    fn state_root(&self, hashed_state: HashedPostState) -> ProviderResult<B256> {
+        self.state.state_root(hashed_state)
+    }
+
+    fn state_root_from_nodes(&self, input: TrieInput) -> ProviderResult<B256> {
+        self.state.state_root_from_nodes(input)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        hashed_state: HashedPostState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.state.state_root_with_updates(hashed_state)
+    }
+
+    fn state_root_from_nodes_with_updates(
+        &self,
+        input: TrieInput,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.state.state_root_from_nodes_with_updates(input)
+    }
+}
+
+impl<S: StateProvider, F: RemoteStateFetcher> StorageRootProvider for RemoteStateProvider<S, F> {
+    fn storage_root(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<B256> {
+        self.state.storage_root(address, hashed_storage)
+    }
+
+    fn storage_proof(
+        &self,
+        address: Address,
+        slot: B256,
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<StorageProof> {
+        self.state.storage_proof(address, slot, hashed_storage)
+    }
+
+    fn storage_multiproof(
+        &self,
+        address: Address,
+        slots: &[B256],
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<StorageMultiProof> {
+        self.state.storage_multiproof(address, slots, hashed_storage)
+    }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStorageRange> {
+        self.state.storage_range(address, hashed_storage, start, limit)
+    }
+}
+
+impl<S: StateProvider, F: RemoteStateFetcher> StateProofProvider for RemoteStateProvider<S, F> {
+    fn proof(
+        &self,
+        input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> ProviderResult<AccountProof> {
+        self.state.proof(input, address, slots)
+    }
+
+    fn multiproof(
+        &self,
+        input: TrieInput,
+        targets: MultiProofTargets,
+    ) -> ProviderResult<MultiProof> {
+        self.state.multiproof(input, targets)
+    }
+
+    fn witness(&self, input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>> {
+        self.state.witness(input, target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+    use alloy_rpc_types_eth::EIP1186StorageProof;
+    use reth_trie_common::{proof::ProofRetainer, HashBuilder, Nibbles};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Local state that is pruned.
+    struct PrunedState;
+
+    impl AccountReader for PrunedState {
+        fn basic_account(&self, _: &Address) -> ProviderResult<Option<Account>> {
+            Err(ProviderError::StateAtBlockPruned(1))
+        }
+    }
+
+    impl StateProvider for PrunedState {
+        fn storage(&self, _: Address, _: StorageKey) -> ProviderResult<Option<StorageValue>> {
+            Err(ProviderError::StateAtBlockPruned(1))
+        }
+
+        fn bytecode_by_hash(&self, _: &B256) -> ProviderResult<Option<Bytecode>> {
+            Ok(None)
+        }
+    }
+
+    impl BlockHashReader for PrunedState {
+        fn block_hash(&self, _: BlockNumber) -> ProviderResult<Option<B256>> {
+            Ok(None)
+        }
+
+        fn canonical_hashes_range(
+            &self,
+            _: BlockNumber,
+            _: BlockNumber,
+        ) -> ProviderResult<Vec<B256>> {
+            Ok(Vec::new())
+        }
+    }
+
+    impl HashedPostStateProvider for PrunedState {
+        fn hashed_post_state(&self, _: &BundleState) -> HashedPostState {
+            unimplemented!()
+        }
+    }
+
+    impl StateRootProvider for PrunedState {
+        fn state_root(&self, _: HashedPostState) -> ProviderResult<B256> {
+            unimplemented!()
+        }
+
+        fn state_root_from_nodes(&self, _: TrieInput) -> ProviderResult<B256> {
+            unimplemented!()
+        }
+
+        fn state_root_with_updates(
+            &self,
+            _: HashedPostState,
+        ) -> ProviderResult<(B256, TrieUpdates)> {
+            unimplemented!()
+        }
+
+        fn state_root_from_nodes_with_updates(
+            &self,
+            _: TrieInput,
+        ) -> ProviderResult<(B256, TrieUpdates)> {
+            unimplemented!()
+        }
+    }
+
+    impl StorageRootProvider for PrunedState {
+        fn storage_root(&self, _: Address, _: HashedStorage) -> ProviderResult<B256> {
+            unimplemented!()
+        }
+
+        fn storage_proof(
+            &self,
+            _: Address,
+            _: B256,
+            _: HashedStorage,
+        ) -> ProviderResult<StorageProof> {
+            unimplemented!()
+        }
+
+        fn storage_multiproof(
+            &self,
+            _: Address,
+            _: &[B256],
+            _: HashedStorage,
+        ) -> ProviderResult<StorageMultiProof> {
+            unimplemented!()
+        }
+
+        fn storage_range(
+            &self,
+            _: Address,
+            _: HashedStorage,
+            _: B256,
+            _: usize,
+        ) -> ProviderResult<HashedStorageRange> {
+            unimplemented!()
+        }
+    }
+
+    impl StateProofProvider for PrunedState {
+        fn proof(&self, _: TrieInput, _: Address, _: &[B256]) -> ProviderResult<AccountProof> {
+            unimplemented!()
+        }
+
+        fn multiproof(&self, _: TrieInput, _: MultiProofTargets) -> ProviderResult<MultiProof> {
+            unimplemented!()
+        }
+
+        fn witness(&self, _: TrieInput, _: HashedPostState) -> ProviderResult<Vec<Bytes>> {
+            unimplemented!()
+        }
+    }
+
+    /// Returns the proof of a single account.
+    struct MockFetcher {
+        response: EIP1186AccountProofResponse,
+        requests: AtomicUsize,
+    }
+
+    impl RemoteStateFetcher for MockFetcher {
+        type Error = std::io::Error;
+
+        async fn proof(
+            &self,
+            _: Address,
+            slots: Vec<B256>,
+            _: BlockId,
+        ) -> Result<EIP1186AccountProofResponse, Self::Error> {
+            self.requests.fetch_add(1, Ordering::Relaxed);
+            let mut response = self.response.clone();
+            response.storage_proof.retain(|proof| slots.contains(&proof.key.as_b256()));
+            Ok(response)
+        }
+
+        async fn code(&self, _: Address, _: BlockId) -> Result<Bytes, Self::Error> {
+            Err(std::io::ErrorKind::NotFound.into())
+        }
+    }
+
+    /// Builds a trie with a single leaf and returns its root and the proof of the leaf.
+    fn single_leaf_trie(hashed_key: B256, value: Vec<u8>) -> (B256, Vec<Bytes>) {
+        let path = Nibbles::unpack(hashed_key);
+        let mut builder =
+            HashBuilder::default().with_proof_retainer(ProofRetainer::from_iter([path.clone()]));
+        builder.add_leaf(path, &value);
+        let root = builder.root();
+        let proof = builder.take_proof_nodes().into_nodes_sorted();
+        (root, proof.into_iter().map(|(_, node)| node).collect())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn fetches_and_verifies_pruned_state() {
+        let address = Address::with_last_byte(1);
+        let account = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+        let slot = B256::with_last_byte(2);
+        let value = U256::from(3);
+
+        let (storage_root, storage_proof) =
+            single_leaf_trie(keccak256(slot), alloy_rlp::encode(value));
+        let (state_root, account_proof) = single_leaf_trie(
+            keccak256(address),
+            alloy_rlp::encode(account.into_trie_account(storage_root)),
+        );
+        let response = EIP1186AccountProofResponse {
+            address,
+            balance: account.balance,
+            code_hash: KECCAK_EMPTY,
+            nonce: account.nonce,
+            storage_hash: storage_root,
+            account_proof,
+            storage_proof: vec![EIP1186StorageProof {
+                key: slot.into(),
+                value,
+                proof: storage_proof,
+            }],
+        };
+
+        let remote = RemoteState::new(MockFetcher {
+            response: response.clone(),
+            requests: Default::default(),
+        });
+        let provider = RemoteStateProvider::new(PrunedState, remote, B256::ZERO, state_root);
+        assert_eq!(provider.basic_account(&address).unwrap(), Some(account));
+        assert_eq!(provider.storage(address, slot).unwrap(), Some(value));
+        assert_eq!(provider.storage(address, slot).unwrap(), Some(value));
+        assert_eq!(provider.basic_account(&address).unwrap(), Some(account));
+        assert_eq!(provider.remote.fetcher.requests.load(Ordering::Relaxed), 2);
+
+        // state that doesn't match the state root is rejected
+        let mut tampered = response;
+        tampered.balance = U256::from(11);
+        let remote =
+            RemoteState::new(MockFetcher { response: tampered, requests: Default::default() });
+        let provider = RemoteStateProvider::new(PrunedState, remote, B256::ZERO, state_root);
+        assert!(provider.basic_account(&address).is_err());
+    }
+}
@@ -53,6 +53,24 @@ where
         .saturating_to())
 }
 
+/// Returns the address the code of the given account is delegated to, if the account has an
+/// [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702) delegation.
+pub fn delegation_target<DB>(db: &mut DB, account: Address) -> EthResult<Option<Address>>
+where
+    DB: Database,
+    EthApiError: From<<DB as Database>::Error>,
+{
+    let Some(info) = db.basic(account)? else { return Ok(None) };
+    let code = match info.code {
+        Some(code) => code,
+        None => db.code_by_hash(info.code_hash)?,
+    };
+    match code {
+        Bytecode::Eip7702(code) => Ok(Some(code.delegated_address)),
+        _ => Ok(None),
+    }
+}
+
 /// Helper type for representing the fees of a `TransactionRequest`
 #[derive(Debug)]
 pub struct CallFees {
@@ -5,7 +5,31 @@ use std::{
     task::{Context, Poll},
 };
 use tower::{Layer, Service};
-use tower_http::compression::{Compression, CompressionLayer as TowerCompressionLayer};
+use tower_http::compression::{
+    predicate::SizeAbove, Compression, CompressionLayer as TowerCompressionLayer,
+};
+
+pub use tower_http::compression::CompressionLevel;
+
+/// The default minimum size of a response body in bytes for it to be compressed.
+///
+/// Smaller responses barely shrink, so compressing them isn't worth the CPU time.
+pub const DEFAULT_COMPRESSION_MIN_SIZE: u16 = 1024;
+
+/// Configuration of the response compression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressionConfig {
+    /// The minimum size of a response body in bytes for it to be compressed.
+    pub min_size: u16,
+    /// The compression level of all encodings.
+    pub level: CompressionLevel,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self { min_size: DEFAULT_COMPRESSION_MIN_SIZE, level: CompressionLevel::Default }
+    }
+}
 
 /// This layer is a wrapper around [`tower_http::compression::CompressionLayer`] that integrates
 /// with jsonrpsee's HTTP types. It automatically compresses responses based on the client's
@@ -13,14 +37,26 @@ use tower_http::compression::{Compression, CompressionLayer as TowerCompressionL
 #[allow(missing_debug_implementations)]
 #[derive(Clone)]
 pub struct CompressionLayer {
-    inner_layer: TowerCompressionLayer,
+    inner_layer: TowerCompressionLayer<SizeAbove>,
 }
 
 impl CompressionLayer {
     /// Creates a new compression layer with zstd, gzip, brotli and deflate enabled.
     pub fn new() -> Self {
+        Self::with_config(CompressionConfig::default())
+    }
+
+    /// Creates a new compression layer with zstd, gzip, brotli and deflate enabled, that compresses
+    /// responses according to the given [`CompressionConfig`].
+    pub fn with_config(config: CompressionConfig) -> Self {
         Self {
-            inner_layer: TowerCompressionLayer::new().gzip(true).br(true).deflate(true).zstd(true),
+            inner_layer: TowerCompressionLayer::new()
+                .gzip(true)
+                .br(true)
+                .deflate(true)
+                .zstd(true)
+                .quality(config.level)
+                .compress_when(SizeAbove::new(config.min_size)),
         }
     }
 }
@@ -47,7 +83,7 @@ impl<S> Layer<S> for CompressionLayer {
 #[allow(missing_debug_implementations)]
 #[derive(Clone)]
 pub struct CompressionService<S> {
-    compression: Compression<S>,
+    compression: Compression<S, SizeAbove>,
 }
 
 impl<S> Service<HttpRequest> for CompressionService<S>
@@ -166,4 +202,23 @@ mod tests {
             "Response size ({response_size}) should equal original size ({uncompressed_len})"
         );
     }
+
+    #[tokio::test]
+    async fn test_no_compression_below_min_size() {
+        let uncompressed_len = TEST_DATA.repeat(REPEAT_COUNT).len();
+        let config = CompressionConfig {
+            min_size: uncompressed_len as u16 + 1,
+            level: CompressionLevel::Fastest,
+        };
+        let mut service = CompressionLayer::with_config(config).layer(MockRequestService);
+        let request =
+            HttpRequest::builder().header(ACCEPT_ENCODING, "br").body(HttpBody::empty()).unwrap();
+
+        let response = service.call(request).await.unwrap();
+        assert!(
+            response.headers().get(CONTENT_ENCODING).is_none(),
+            "Response should not be compressed below the minimum size"
+        );
+        assert_eq!(get_response_size(response).await, uncompressed_len);
+    }
 }
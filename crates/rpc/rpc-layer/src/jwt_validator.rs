@@ -1,14 +1,58 @@
 use crate::{AuthValidator, JwtError, JwtSecret};
 use http::{header, HeaderMap, Response, StatusCode};
 use jsonrpsee_http_client::{HttpBody, HttpResponse};
+use std::time::SystemTime;
 use tracing::error;
 
+/// An additional JWT secret accepted by the [`JwtAuthValidator`] during a validity window.
+///
+/// This allows rotating the secret shared with the consensus layer without restarting both
+/// clients at the same time: the old and the new secret are both accepted until the window of
+/// the old one ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RotatedJwtSecret {
+    secret: JwtSecret,
+    not_before: Option<SystemTime>,
+    not_after: Option<SystemTime>,
+}
+
+impl RotatedJwtSecret {
+    /// Creates a new secret that is accepted at all times.
+    pub const fn new(secret: JwtSecret) -> Self {
+        Self { secret, not_before: None, not_after: None }
+    }
+
+    /// Only accept the secret from the given time on.
+    pub const fn with_not_before(mut self, not_before: SystemTime) -> Self {
+        self.not_before = Some(not_before);
+        self
+    }
+
+    /// Only accept the secret until the given time.
+    pub const fn with_not_after(mut self, not_after: SystemTime) -> Self {
+        self.not_after = Some(not_after);
+        self
+    }
+
+    /// Returns the secret.
+    pub const fn secret(&self) -> &JwtSecret {
+        &self.secret
+    }
+
+    /// Returns `true` if the secret is accepted at the given time.
+    pub fn is_active_at(&self, now: SystemTime) -> bool {
+        self.not_before.is_none_or(|not_before| now >= not_before) &&
+            self.not_after.is_none_or(|not_after| now <= not_after)
+    }
+}
+
 /// Implements JWT validation logics and integrates
 /// to an Http [`AuthLayer`][crate::AuthLayer]
 /// by implementing the [`AuthValidator`] trait.
 #[derive(Debug, Clone)]
 pub struct JwtAuthValidator {
     secret: JwtSecret,
+    additional_secrets: Vec<RotatedJwtSecret>,
 }
 
 impl JwtAuthValidator {
@@ -16,14 +60,44 @@ impl JwtAuthValidator {
     /// Validation logics are implemented by the `secret`
     /// argument (see [`JwtSecret`]).
     pub const fn new(secret: JwtSecret) -> Self {
-        Self { secret }
+        Self { secret, additional_secrets: Vec::new() }
+    }
+
+    /// Also accepts the given secrets while they're active, see [`RotatedJwtSecret`].
+    pub fn with_additional_secrets(
+        mut self,
+        secrets: impl IntoIterator<Item = RotatedJwtSecret>,
+    ) -> Self {
+        self.additional_secrets.extend(secrets);
+        self
+    }
+
+    /// Validates the token against the secret and the active additional secrets.
+    ///
+    /// Returns the error of the main secret if no secret accepts the token.
+    fn validate_token(&self, jwt: &str) -> Result<(), JwtError> {
+        let err = match self.secret.validate(jwt) {
+            Ok(_) => return Ok(()),
+            Err(err) => err,
+        };
+
+        let now = SystemTime::now();
+        if self
+            .additional_secrets
+            .iter()
+            .filter(|secret| secret.is_active_at(now))
+            .any(|secret| secret.secret.validate(jwt).is_ok())
+        {
+            return Ok(())
+        }
+        Err(err)
     }
 }
 
 impl AuthValidator for JwtAuthValidator {
     fn validate(&self, headers: &HeaderMap) -> Result<(), HttpResponse> {
         match get_bearer(headers) {
-            Some(jwt) => match self.secret.validate(&jwt) {
+            Some(jwt) => match self.validate_token(&jwt) {
                 Ok(_) => Ok(()),
                 Err(e) => {
                     error!(target: "engine::jwt-validator", "Invalid JWT: {e}");
@@ -64,8 +138,38 @@ fn err_response(err: JwtError) -> HttpResponse {
 
 #[cfg(test)]
 mod tests {
-    use crate::jwt_validator::get_bearer;
+    use crate::{
+        jwt_validator::{get_bearer, RotatedJwtSecret},
+        Claims, JwtAuthValidator, JwtSecret,
+    };
     use http::{header, HeaderMap};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    fn token(secret: &JwtSecret) -> String {
+        let iat = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        secret.encode(&Claims { iat, exp: None }).unwrap()
+    }
+
+    #[test]
+    fn accepts_active_additional_secrets() {
+        let secret = JwtSecret::random();
+        let old = JwtSecret::random();
+        let expired = JwtSecret::random();
+        let pending = JwtSecret::random();
+        let now = SystemTime::now();
+
+        let validator = JwtAuthValidator::new(secret).with_additional_secrets([
+            RotatedJwtSecret::new(old).with_not_after(now + Duration::from_secs(60)),
+            RotatedJwtSecret::new(expired).with_not_after(now - Duration::from_secs(60)),
+            RotatedJwtSecret::new(pending).with_not_before(now + Duration::from_secs(60)),
+        ]);
+
+        assert!(validator.validate_token(&token(&secret)).is_ok());
+        assert!(validator.validate_token(&token(&old)).is_ok());
+        assert!(validator.validate_token(&token(&expired)).is_err());
+        assert!(validator.validate_token(&token(&pending)).is_err());
+        assert!(validator.validate_token(&token(&JwtSecret::random())).is_err());
+    }
 
     #[test]
     fn auth_header_available() {
@@ -15,16 +15,20 @@ mod auth_client_layer;
 mod auth_layer;
 mod compression_layer;
 mod jwt_validator;
+mod trace_context_layer;
 
 pub use auth_layer::{AuthService, ResponseFuture};
-pub use compression_layer::CompressionLayer;
+pub use compression_layer::{
+    CompressionConfig, CompressionLayer, CompressionLevel, DEFAULT_COMPRESSION_MIN_SIZE,
+};
 
 // Export alloy JWT types
 pub use alloy_rpc_types_engine::{Claims, JwtError, JwtSecret};
 
 pub use auth_client_layer::{secret_to_bearer_header, AuthClientLayer, AuthClientService};
 pub use auth_layer::AuthLayer;
-pub use jwt_validator::JwtAuthValidator;
+pub use jwt_validator::{JwtAuthValidator, RotatedJwtSecret};
+pub use trace_context_layer::{TraceContextLayer, TraceContextService};
 
 /// General purpose trait to validate Http Authorization headers. It's supposed to be integrated as
 /// a validator trait into an [`AuthLayer`].
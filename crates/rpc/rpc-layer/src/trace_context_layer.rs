@@ -0,0 +1,73 @@
+use jsonrpsee_http_client::HttpRequest;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use tracing::{debug_span, instrument::Instrumented, Instrument, Span};
+
+/// The W3C trace context header of a request.
+const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// This is an Http middleware layer that continues the trace of the caller, e.g. a consensus client
+/// tracing its calls to the engine API.
+///
+/// Requests carrying a W3C `traceparent` header are handled inside an `engine_api_request` span
+/// whose parent is the propagated trace context, so the spans of the request handling are exported
+/// as part of the caller's trace.
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub struct TraceContextLayer;
+
+impl TraceContextLayer {
+    /// Creates an instance of [`TraceContextLayer`].
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for TraceContextLayer {
+    type Service = TraceContextService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TraceContextService { inner }
+    }
+}
+
+/// The service of [`TraceContextLayer`], which instruments the inner service's future with the
+/// span of the request.
+#[derive(Clone, Debug)]
+pub struct TraceContextService<S> {
+    inner: S,
+}
+
+impl<S> Service<HttpRequest> for TraceContextService<S>
+where
+    S: Service<HttpRequest>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Instrumented<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: HttpRequest) -> Self::Future {
+        let span = request_span(&req);
+        self.inner.call(req).instrument(span)
+    }
+}
+
+/// Returns the span to handle the request in, which continues the propagated trace context if
+/// the request has one.
+fn request_span(req: &HttpRequest) -> Span {
+    if !req.headers().contains_key(TRACEPARENT_HEADER) {
+        return Span::none()
+    }
+
+    let span = debug_span!(target: "engine::rpc", "engine_api_request");
+    let headers = req
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?)));
+    reth_tracing::set_remote_parent(&span, headers);
+    span
+}
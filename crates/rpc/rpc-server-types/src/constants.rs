@@ -32,6 +32,13 @@ pub fn default_max_tracing_requests() -> usize {
 /// The default number of getproof calls we are allowing to run concurrently.
 pub const DEFAULT_PROOF_PERMITS: usize = 25;
 
+/// The default number of `eth_call`s and other simulations of the `eth` namespace we are allowing
+/// to run concurrently.
+///
+/// These are executed on the blocking threads of the tokio runtime, which are limited to 512 by
+/// default, so this leaves room for the other blocking tasks.
+pub const DEFAULT_MAX_CONCURRENT_CALLS: usize = 256;
+
 /// The default IPC endpoint
 #[cfg(windows)]
 pub const DEFAULT_IPC_ENDPOINT: &str = r"\\.\pipe\reth.ipc";
@@ -54,6 +61,9 @@ pub const DEFAULT_MAX_SIMULATE_BLOCKS: u64 = 256;
 /// The default eth historical proof window.
 pub const DEFAULT_ETH_PROOF_WINDOW: u64 = 0;
 
+/// The default maximum number of accounts of a single `reth_getProofs` call.
+pub const DEFAULT_MAX_PROOFS_ACCOUNTS: usize = 1_000;
+
 /// Maximum eth historical proof window. Equivalent to roughly 6 months of data on a 12
 /// second block time, and a month on a 2 second block time.
 pub const MAX_ETH_PROOF_WINDOW: u64 = 28 * 24 * 60 * 60 / 2;
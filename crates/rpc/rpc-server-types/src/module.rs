@@ -273,6 +273,8 @@ pub enum RethRpcModule {
     Miner,
     /// `mev_` module
     Mev,
+    /// `aa_` module
+    Aa,
 }
 
 // === impl RethRpcModule ===
@@ -324,6 +326,7 @@ impl FromStr for RethRpcModule {
             "flashbots" => Self::Flashbots,
             "miner" => Self::Miner,
             "mev" => Self::Mev,
+            "aa" => Self::Aa,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
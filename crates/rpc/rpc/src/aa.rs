@@ -0,0 +1,492 @@
+//! `aa` namespace handler implementation.
+
+use alloy_eips::BlockId;
+use alloy_primitives::{Address, B256, U256};
+use alloy_rpc_types_eth::{
+    state::{EvmOverrides, StateOverride},
+    TransactionInput, TransactionRequest,
+};
+use alloy_sol_types::{sol, SolCall};
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_rpc_api::{
+    AaApiServer, EntityKind, EntityStake, EntityValidation, OpcodeViolation,
+    UserOperationValidationRequest, UserOperationValidationTrace,
+};
+use reth_rpc_eth_api::helpers::{Call, LoadPendingBlock};
+use reth_rpc_eth_types::cache::db::StateCacheDbRefMutWrapper;
+use reth_tasks::pool::BlockingTaskGuard;
+use revm::{
+    bytecode::opcode::{self, OpCode},
+    context_interface::ContextTr,
+    inspector::Inspector,
+    interpreter::{
+        interpreter::EthInterpreter, interpreter_types::Jumps, CallInputs, CallOutcome,
+        CreateInputs, CreateOutcome, Interpreter,
+    },
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+
+sol! {
+    /// Deposit and stake of an account in the entry point.
+    ///
+    /// The ABI encoding is the same for the entry point v0.6, which uses smaller integer types.
+    struct DepositInfo {
+        uint256 deposit;
+        bool staked;
+        uint112 stake;
+        uint32 unstakeDelaySec;
+        uint48 withdrawTime;
+    }
+
+    /// Returns the deposit and stake of the given account.
+    function getDepositInfo(address account) external view returns (DepositInfo memory info);
+}
+
+/// Opcodes that entities must not use during the validation phase, see ERC-7562.
+///
+/// `GAS` is handled separately, since it's allowed if immediately followed by a call.
+const BANNED_OPCODES: &[u8] = &[
+    opcode::GASPRICE,
+    opcode::GASLIMIT,
+    opcode::DIFFICULTY,
+    opcode::TIMESTAMP,
+    opcode::BASEFEE,
+    opcode::BLOCKHASH,
+    opcode::NUMBER,
+    opcode::SELFBALANCE,
+    opcode::BALANCE,
+    opcode::ORIGIN,
+    opcode::CREATE,
+    opcode::CREATE2,
+    opcode::COINBASE,
+    opcode::SELFDESTRUCT,
+    opcode::INVALID,
+    opcode::BLOBHASH,
+    opcode::BLOBBASEFEE,
+];
+
+/// `aa` API implementation.
+///
+/// Simulates the validation phase of ERC-4337 user operations against the local EVM, so that
+/// bundlers don't need a separate simulation client.
+pub struct AaApi<Eth> {
+    inner: Arc<AaApiInner<Eth>>,
+}
+
+impl<Eth> AaApi<Eth> {
+    /// Creates a new instance of the `aa` API.
+    pub fn new(eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
+        Self { inner: Arc::new(AaApiInner { eth_api, blocking_task_guard }) }
+    }
+
+    /// Access the underlying `Eth` API.
+    pub fn eth_api(&self) -> &Eth {
+        &self.inner.eth_api
+    }
+}
+
+impl<Eth> AaApi<Eth>
+where
+    Eth: Call + LoadPendingBlock + 'static,
+{
+    /// Executes the validation call of the user operation with the [`ValidationInspector`] and
+    /// reads the stake of its entities from the entry point.
+    pub async fn trace_validation(
+        &self,
+        request: UserOperationValidationRequest,
+        block_id: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+    ) -> Result<UserOperationValidationTrace, Eth::Error> {
+        let UserOperationValidationRequest { entry_point, sender, factory, paymaster, call } =
+            request;
+        let entities = [
+            (EntityKind::Factory, factory),
+            (EntityKind::Sender, Some(sender)),
+            (EntityKind::Paymaster, paymaster),
+        ]
+        .into_iter()
+        .filter_map(|(kind, address)| Some(EntityTrace::new(kind, address?)))
+        .collect();
+
+        let this = self.eth_api().clone();
+        self.eth_api()
+            .spawn_with_call_at(
+                call,
+                block_id.unwrap_or_default(),
+                EvmOverrides::new(state_overrides, None),
+                move |db, evm_env, tx_env| {
+                    let StateCacheDbRefMutWrapper(db) = db;
+
+                    let mut inspector = ValidationInspector::new(sender, entities);
+                    let (res, (evm_env, _)) = this.transact_with_inspector(
+                        StateCacheDbRefMutWrapper(&mut *db),
+                        evm_env,
+                        tx_env,
+                        &mut inspector,
+                    )?;
+
+                    let mut entities = Vec::with_capacity(inspector.entities.len());
+                    for entity in inspector.entities {
+                        let request = TransactionRequest::default().to(entry_point).input(
+                            TransactionInput::new(
+                                getDepositInfoCall { account: entity.address }.abi_encode().into(),
+                            ),
+                        );
+                        let tx_env = this.create_txn_env(
+                            &evm_env,
+                            request,
+                            StateCacheDbRefMutWrapper(&mut *db),
+                        )?;
+                        let (deposit_res, _) = this.transact(
+                            StateCacheDbRefMutWrapper(&mut *db),
+                            evm_env.clone(),
+                            tx_env,
+                        )?;
+
+                        // accounts that aren't entry points have no deposit info
+                        let stake = deposit_res
+                            .result
+                            .output()
+                            .filter(|_| deposit_res.result.is_success())
+                            .and_then(|output| {
+                                getDepositInfoCall::abi_decode_returns(output, true).ok()
+                            })
+                            .map(|ret| EntityStake {
+                                staked: ret.info.staked,
+                                deposit: ret.info.deposit,
+                                stake: U256::from(ret.info.stake),
+                                unstake_delay_sec: ret.info.unstakeDelaySec,
+                            })
+                            .unwrap_or_default();
+
+                        entities.push(entity.into_validation(stake));
+                    }
+
+                    let reverted = !res.result.is_success();
+                    let gas_used = res.result.gas_used();
+                    Ok(UserOperationValidationTrace {
+                        reverted,
+                        output: res.result.into_output().unwrap_or_default(),
+                        gas_used,
+                        entities,
+                    })
+                },
+            )
+            .await
+    }
+}
+
+#[async_trait]
+impl<Eth> AaApiServer for AaApi<Eth>
+where
+    Eth: Call + LoadPendingBlock + 'static,
+{
+    /// Handler for `aa_traceValidation`
+    async fn trace_validation(
+        &self,
+        request: UserOperationValidationRequest,
+        block_id: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+    ) -> RpcResult<UserOperationValidationTrace> {
+        let _permit = self.inner.blocking_task_guard.clone().acquire_owned().await;
+        Self::trace_validation(self, request, block_id, state_overrides).await.map_err(Into::into)
+    }
+}
+
+impl<Eth> std::fmt::Debug for AaApi<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AaApi").finish_non_exhaustive()
+    }
+}
+
+impl<Eth> Clone for AaApi<Eth> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct AaApiInner<Eth> {
+    /// Access to commonly used code of the `eth` namespace
+    eth_api: Eth,
+    // restrict the number of concurrent simulations.
+    blocking_task_guard: BlockingTaskGuard,
+}
+
+/// Opcodes and storage accessed by an entity during the validation phase.
+#[derive(Debug)]
+struct EntityTrace {
+    kind: EntityKind,
+    address: Address,
+    violations: Vec<OpcodeViolation>,
+    accessed_storage: BTreeMap<Address, BTreeSet<B256>>,
+}
+
+impl EntityTrace {
+    const fn new(kind: EntityKind, address: Address) -> Self {
+        Self { kind, address, violations: Vec::new(), accessed_storage: BTreeMap::new() }
+    }
+
+    fn into_validation(self, stake: EntityStake) -> EntityValidation {
+        EntityValidation {
+            kind: self.kind,
+            address: self.address,
+            stake,
+            violations: self.violations,
+            accessed_storage: self
+                .accessed_storage
+                .into_iter()
+                .map(|(address, slots)| (address, slots.into_iter().collect()))
+                .collect(),
+        }
+    }
+}
+
+/// A call frame of the validation call.
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    /// Index of the entity the frame belongs to, if any.
+    entity: Option<usize>,
+    /// Account whose code is executed, `None` for the init code of a created account.
+    code_address: Option<Address>,
+}
+
+/// Inspector tracing the validation phase of a user operation.
+///
+/// A call frame belongs to an entity if the entity is called, or if the frame is nested in a frame
+/// of the entity. Frames of the entry point itself don't belong to any entity.
+#[derive(Debug)]
+struct ValidationInspector {
+    /// The account of the user operation.
+    sender: Address,
+    /// The entities of the user operation.
+    entities: Vec<EntityTrace>,
+    /// The stack of active call frames.
+    frames: Vec<Frame>,
+    /// Entity and account that executed `GAS` in the previous step.
+    pending_gas: Option<(usize, Address)>,
+    /// Whether the factory already used its single allowed `CREATE2`.
+    factory_created: bool,
+}
+
+impl ValidationInspector {
+    const fn new(sender: Address, entities: Vec<EntityTrace>) -> Self {
+        Self { sender, entities, frames: Vec::new(), pending_gas: None, factory_created: false }
+    }
+
+    fn push_frame(&mut self, entity: Option<usize>, code_address: Option<Address>) {
+        let entity = entity.or_else(|| self.frames.last().and_then(|frame| frame.entity));
+        self.frames.push(Frame { entity, code_address });
+    }
+
+    fn record_violation(&mut self, entity: usize, address: Address, op: u8) {
+        let opcode = OpCode::new(op).map_or_else(|| format!("0x{op:02x}"), |op| op.to_string());
+        self.entities[entity].violations.push(OpcodeViolation { address, opcode });
+    }
+}
+
+impl<CTX> Inspector<CTX, EthInterpreter> for ValidationInspector
+where
+    CTX: ContextTr,
+{
+    fn step(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        let op = interp.bytecode.opcode();
+
+        if let Some((entity, address)) = self.pending_gas.take() {
+            if !matches!(
+                op,
+                opcode::CALL | opcode::CALLCODE | opcode::DELEGATECALL | opcode::STATICCALL
+            ) {
+                self.record_violation(entity, address, opcode::GAS);
+            }
+        }
+
+        let Some(&Frame { entity: Some(entity), code_address }) = self.frames.last() else {
+            return
+        };
+        let target = interp.input.target_address;
+        let address = code_address.unwrap_or(target);
+
+        match op {
+            opcode::GAS => self.pending_gas = Some((entity, address)),
+            opcode::SLOAD | opcode::SSTORE => {
+                if target == self.sender || target == self.entities[entity].address {
+                    return
+                }
+                if let Some(slot) = interp.stack.data().last() {
+                    self.entities[entity]
+                        .accessed_storage
+                        .entry(target)
+                        .or_default()
+                        .insert(B256::from(*slot));
+                }
+            }
+            // the factory may deploy the sender
+            opcode::CREATE2
+                if self.entities[entity].kind == EntityKind::Factory && !self.factory_created =>
+            {
+                self.factory_created = true
+            }
+            op if BANNED_OPCODES.contains(&op) => self.record_violation(entity, address, op),
+            _ => {}
+        }
+    }
+
+    fn call(&mut self, _context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        let entity =
+            self.entities.iter().position(|entity| entity.address == inputs.target_address);
+        self.push_frame(entity, Some(inputs.bytecode_address));
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, _inputs: &CallInputs, _outcome: &mut CallOutcome) {
+        self.frames.pop();
+    }
+
+    fn create(&mut self, _context: &mut CTX, _inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.push_frame(None, None);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        _outcome: &mut CreateOutcome,
+    ) {
+        self.frames.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Bytes, TxKind};
+    use reth_evm::{ConfigureEvm, Evm};
+    use reth_evm_ethereum::EthEvmConfig;
+    use revm::{
+        bytecode::Bytecode,
+        context::TxEnv,
+        database::{CacheDB, EmptyDB},
+        state::AccountInfo,
+    };
+
+    /// Returns the code calling the given account without arguments.
+    fn call_code(target: Address) -> Vec<u8> {
+        let mut code = vec![
+            opcode::PUSH1,
+            0,
+            opcode::PUSH1,
+            0,
+            opcode::PUSH1,
+            0,
+            opcode::PUSH1,
+            0,
+            opcode::PUSH1,
+            0,
+            opcode::PUSH20,
+        ];
+        code.extend_from_slice(target.as_slice());
+        code.extend_from_slice(&[opcode::GAS, opcode::CALL, opcode::POP]);
+        code
+    }
+
+    #[test]
+    fn frames_inherit_entity() {
+        let sender = Address::with_last_byte(1);
+        let paymaster = Address::with_last_byte(2);
+        let mut inspector = ValidationInspector::new(
+            sender,
+            vec![
+                EntityTrace::new(EntityKind::Sender, sender),
+                EntityTrace::new(EntityKind::Paymaster, paymaster),
+            ],
+        );
+
+        // entry point
+        inspector.push_frame(None, Some(Address::with_last_byte(3)));
+        assert_eq!(inspector.frames.last().unwrap().entity, None);
+
+        // sender calling a library
+        inspector.push_frame(Some(0), Some(sender));
+        inspector.push_frame(None, Some(Address::with_last_byte(4)));
+        assert_eq!(inspector.frames.last().unwrap().entity, Some(0));
+        inspector.frames.truncate(1);
+
+        // paymaster creating a contract
+        inspector.push_frame(Some(1), Some(paymaster));
+        inspector.push_frame(None, None);
+        assert_eq!(inspector.frames.last().unwrap().entity, Some(1));
+
+        inspector.record_violation(1, paymaster, opcode::TIMESTAMP);
+        assert_eq!(
+            inspector.entities[1].violations,
+            vec![OpcodeViolation { address: paymaster, opcode: "TIMESTAMP".to_string() }]
+        );
+    }
+
+    #[test]
+    fn traces_validation_bytecode() {
+        let entry_point = Address::with_last_byte(1);
+        let sender = Address::with_last_byte(2);
+        let library = Address::with_last_byte(3);
+
+        // the entry point calls the sender, its `GAS` doesn't belong to any entity
+        let entry_point_code = [call_code(sender), vec![opcode::STOP]].concat();
+        // the sender reads the timestamp and its own storage, uses `GAS` without a call and calls
+        // the library
+        let sender_code = [
+            vec![opcode::TIMESTAMP, opcode::POP],
+            vec![opcode::PUSH1, 1, opcode::SLOAD, opcode::POP],
+            vec![opcode::GAS, opcode::POP],
+            call_code(library),
+            vec![opcode::STOP],
+        ]
+        .concat();
+        // the library reads its own storage on behalf of the sender
+        let library_code = vec![opcode::PUSH1, 7, opcode::SLOAD, opcode::POP, opcode::STOP];
+
+        let mut db = CacheDB::<EmptyDB>::default();
+        for (address, code) in [
+            (entry_point, Bytes::from(entry_point_code)),
+            (sender, Bytes::from(sender_code)),
+            (library, Bytes::from(library_code)),
+        ] {
+            db.insert_account_info(address, AccountInfo::from_bytecode(Bytecode::new_raw(code)));
+        }
+
+        let mut inspector =
+            ValidationInspector::new(sender, vec![EntityTrace::new(EntityKind::Sender, sender)]);
+        let mut evm = EthEvmConfig::mainnet().evm_with_env_and_inspector(
+            db,
+            Default::default(),
+            &mut inspector,
+        );
+        let tx = TxEnv {
+            caller: Address::with_last_byte(4),
+            kind: TxKind::Call(entry_point),
+            gas_limit: 1_000_000,
+            ..Default::default()
+        };
+        let result = evm.transact(tx).unwrap();
+        assert!(result.result.is_success());
+        drop(evm);
+
+        let [entity] = inspector.entities.as_slice() else { panic!("expected a single entity") };
+        assert_eq!(
+            entity.violations,
+            vec![
+                OpcodeViolation { address: sender, opcode: "TIMESTAMP".to_string() },
+                OpcodeViolation { address: sender, opcode: "GAS".to_string() },
+            ]
+        );
+        assert_eq!(
+            entity.accessed_storage,
+            BTreeMap::from([(library, BTreeSet::from([B256::with_last_byte(7)]))])
+        );
+        assert!(inspector.frames.is_empty());
+    }
+}
@@ -1,6 +1,7 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use alloy_genesis::ChainConfig;
+use alloy_primitives::B256;
 use alloy_rpc_types_admin::{
     EthInfo, EthPeerInfo, EthProtocolInfo, NodeInfo, PeerInfo, PeerNetworkInfo, PeerProtocolInfo,
     Ports, ProtocolInfo,
@@ -8,11 +9,18 @@ use alloy_rpc_types_admin::{
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::{EthChainSpec, EthereumHardfork, EthereumHardforks, ForkCondition};
+use reth_engine_primitives::{ExchangedCapabilities, MissingCapability, ReorgGuard};
 use reth_network_api::{NetworkInfo, Peers};
 use reth_network_peers::{id2pk, AnyNode, NodeRecord};
 use reth_network_types::PeerKind;
-use reth_rpc_api::AdminApiServer;
-use reth_rpc_server_types::ToRpcResult;
+use reth_rpc_api::{
+    AdminApiServer, AdminCapabilitiesApiServer, AdminReorgApiServer, EngineCapabilitiesHandshake,
+    MissingEngineCapability, PeerHealthResponse, RejectedReorg,
+};
+use reth_rpc_server_types::{
+    result::{internal_rpc_err, invalid_params_rpc_err},
+    ToRpcResult,
+};
 
 /// `admin` API implementation.
 ///
@@ -103,6 +111,40 @@ where
         Ok(infos)
     }
 
+    /// Handler for `admin_peersHealth`
+    async fn peers_health(&self) -> RpcResult<Vec<PeerHealthResponse>> {
+        let health = self.network.get_peers_health().await.to_rpc_result()?;
+        let connected = health
+            .iter()
+            .filter(|info| info.health.is_connected())
+            .map(|info| info.record.id)
+            .collect();
+        let latencies = self
+            .network
+            .get_peers_by_id(connected)
+            .await
+            .to_rpc_result()?
+            .into_iter()
+            .map(|peer| (peer.remote_id, peer.latency))
+            .collect::<HashMap<_, _>>();
+
+        Ok(health
+            .into_iter()
+            .map(|info| PeerHealthResponse {
+                enode: info.record,
+                trusted: info.kind.is_trusted(),
+                static_node: info.kind.is_static(),
+                status: info.health.status.to_string(),
+                failed_dials: info.health.failed_dials,
+                latency_ms: latencies
+                    .get(&info.record.id)
+                    .copied()
+                    .flatten()
+                    .map(|latency| latency.as_millis() as u64),
+            })
+            .collect())
+    }
+
     /// Handler for `admin_nodeInfo`
     async fn node_info(&self) -> RpcResult<NodeInfo> {
         let enode = self.network.local_node_record();
@@ -182,6 +224,14 @@ where
         })
     }
 
+    /// Handler for `admin_setLogFilter`
+    fn set_log_filter(&self, directives: String) -> RpcResult<bool> {
+        let handle = reth_tracing::log_filter_handle()
+            .ok_or_else(|| internal_rpc_err("log filters can't be changed at runtime"))?;
+        handle.set_filter(&directives).map_err(|err| invalid_params_rpc_err(err.to_string()))?;
+        Ok(true)
+    }
+
     /// Handler for `admin_peerEvents`
     async fn subscribe_peer_events(
         &self,
@@ -196,3 +246,57 @@ impl<N, ChainSpec> std::fmt::Debug for AdminApi<N, ChainSpec> {
         f.debug_struct("AdminApi").finish_non_exhaustive()
     }
 }
+
+/// `admin_forceReorg` implementation, overriding the [`ReorgGuard`] of the engine.
+#[derive(Debug, Clone)]
+pub struct AdminReorgApi {
+    guard: ReorgGuard,
+}
+
+impl AdminReorgApi {
+    /// Creates a new instance of the `admin_forceReorg` API.
+    pub const fn new(guard: ReorgGuard) -> Self {
+        Self { guard }
+    }
+}
+
+impl AdminReorgApiServer for AdminReorgApi {
+    /// Handler for `admin_forceReorg`
+    fn force_reorg(&self, block_hash: B256) -> RpcResult<Option<RejectedReorg>> {
+        Ok(self
+            .guard
+            .force_reorg(block_hash)
+            .map(|rejected| RejectedReorg { head: rejected.head, depth: rejected.depth }))
+    }
+}
+
+/// `admin_engineCapabilities` implementation, reading the [`ExchangedCapabilities`] of the engine
+/// API.
+#[derive(Debug, Clone)]
+pub struct AdminCapabilitiesApi {
+    exchanged: ExchangedCapabilities,
+}
+
+impl AdminCapabilitiesApi {
+    /// Creates a new instance of the `admin_engineCapabilities` API.
+    pub const fn new(exchanged: ExchangedCapabilities) -> Self {
+        Self { exchanged }
+    }
+}
+
+impl AdminCapabilitiesApiServer for AdminCapabilitiesApi {
+    /// Handler for `admin_engineCapabilities`
+    fn engine_capabilities(&self) -> RpcResult<Option<EngineCapabilitiesHandshake>> {
+        Ok(self.exchanged.last().map(|handshake| EngineCapabilitiesHandshake {
+            timestamp: handshake.timestamp,
+            capabilities: handshake.capabilities,
+            missing: handshake
+                .missing
+                .into_iter()
+                .map(|MissingCapability { method, fork, activation_timestamp, active }| {
+                    MissingEngineCapability { method, fork, activation_timestamp, active }
+                })
+                .collect(),
+        }))
+    }
+}
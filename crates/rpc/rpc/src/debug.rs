@@ -1,7 +1,7 @@
 use alloy_consensus::BlockHeader;
 use alloy_eips::{eip2718::Encodable2718, BlockId, BlockNumberOrTag};
 use alloy_genesis::ChainConfig;
-use alloy_primitives::{Address, Bytes, B256};
+use alloy_primitives::{keccak256, map::HashMap, Address, Bytes, B256};
 use alloy_rlp::{Decodable, Encodable};
 use alloy_rpc_types_debug::ExecutionWitness;
 use alloy_rpc_types_eth::{
@@ -18,7 +18,8 @@ use jsonrpsee::core::RpcResult;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_evm::{
     execute::{BlockExecutorProvider, Executor},
-    ConfigureEvm, EvmEnvFor, TxEnvFor,
+    profiler::{AccessStats, StateAccessProfiler},
+    ConfigureEvm, Evm, EvmEnvFor, TxEnvFor,
 };
 use reth_primitives_traits::{
     Block as _, BlockBody, NodePrimitives, ReceiptWithBloom, RecoveredBlock, SignedTransaction,
@@ -26,28 +27,44 @@ use reth_primitives_traits::{
 use reth_provider::{
     BlockIdReader, BlockReaderIdExt, ChainSpecProvider, HeaderProvider, ProviderBlock,
     ReceiptProviderIdExt, StateProofProvider, StateProvider, StateProviderFactory,
-    TransactionVariant,
+    StorageRootProvider, TransactionVariant,
 };
 use reth_revm::{
     database::StateProviderDatabase,
-    db::{CacheDB, State},
+    db::{AccountState, CacheDB, State},
     witness::ExecutionWitnessRecord,
 };
-use reth_rpc_api::DebugApiServer;
+use reth_rpc_api::{
+    DebugApiServer, DebugHotStateApiServer, HotAccount, HotKeyStats, HotStateReport,
+    HotStorageSlot, StorageRangeEntry, StorageRangeResult,
+};
 use reth_rpc_eth_api::{
-    helpers::{EthTransactions, TraceExt},
-    EthApiTypes, FromEthApiError, RpcNodeCore,
+    helpers::{EthTransactions, LoadState, SpawnBlocking, TraceExt},
+    EthApiTypes, FromEthApiError, FromEvmError, RpcNodeCore,
+};
+use reth_rpc_eth_types::{
+    cache::db::StateProviderTraitObjWrapper, EthApiError, ProofTaskPool, ProofTaskPriority,
+    StateCacheDb,
 };
-use reth_rpc_eth_types::{EthApiError, StateCacheDb};
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 use reth_tasks::pool::BlockingTaskGuard;
+use reth_trie_common::HashedStorage;
 use revm::{context_interface::Transaction, state::EvmState, DatabaseCommit};
-use revm_inspectors::tracing::{
-    FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig, TransactionContext,
+use revm_inspectors::{
+    opcode::OpcodeGasInspector,
+    tracing::{
+        FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig,
+        TransactionContext,
+    },
 };
 use std::sync::Arc;
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
+/// Name of the native tracer that counts the executed opcodes.
+///
+/// This is not a geth built-in tracer, so it is requested like a JS tracer, via the `tracer` field.
+const OPCODE_COUNT_TRACER: &str = "opcodeCountTracer";
+
 /// `debug` API implementation.
 ///
 /// This type provides the functionality for handling `debug` related requests.
@@ -418,6 +435,17 @@ where
                         return Ok(frame.into());
                     }
                 },
+                GethDebugTracerType::JsTracer(code) if code == OPCODE_COUNT_TRACER => {
+                    let mut inspector = OpcodeGasInspector::default();
+                    let inspector = self
+                        .eth_api()
+                        .spawn_with_call_at(call, at, overrides, move |db, evm_env, tx_env| {
+                            this.eth_api().inspect(db, evm_env, tx_env, &mut inspector)?;
+                            Ok(inspector)
+                        })
+                        .await?;
+                    Ok(opcode_count_frame(&inspector))
+                }
                 #[cfg(not(feature = "js-tracer"))]
                 GethDebugTracerType::JsTracer(_) => {
                     Err(EthApiError::Unsupported("JS Tracer is not enabled").into())
@@ -627,13 +655,17 @@ where
     }
 
     /// Generates an execution witness, using the given recovered block.
+    ///
+    /// The witness is generated on the proof pool, see [`ProofTaskPool`].
     pub async fn debug_execution_witness_for_block(
         &self,
         block: Arc<RecoveredBlock<ProviderBlock<Eth::Provider>>>,
     ) -> Result<ExecutionWitness, Eth::Error> {
         let this = self.clone();
         self.eth_api()
-            .spawn_with_state_at_block(block.parent_hash().into(), move |state_provider| {
+            .spawn_proof(ProofTaskPriority::Low, move |eth_api| {
+                let state = eth_api.state_at_block_id(block.parent_hash().into())?;
+                let state_provider = StateProviderTraitObjWrapper(&state);
                 let db = StateProviderDatabase::new(&state_provider);
                 let block_executor = this.inner.block_executor.executor(db);
 
@@ -655,6 +687,83 @@ where
             .await
     }
 
+    /// Returns a page of the storage of the account, as of before the transaction at the given
+    /// index of the block is executed.
+    ///
+    /// The storage is read from the hashed storage, so the keys are only resolved if they were
+    /// accessed by the replayed transactions.
+    pub async fn debug_storage_range_at(
+        &self,
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> Result<StorageRangeResult, Eth::Error> {
+        let block = self
+            .eth_api()
+            .recovered_block(block_hash.into())
+            .await?
+            .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+        if tx_idx >= block.body().transaction_count() {
+            return Err(EthApiError::InvalidParams(format!(
+                "transaction index {tx_idx} out of range"
+            ))
+            .into())
+        }
+        let (evm_env, _) = self.eth_api().evm_env_at(block_hash.into()).await?;
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(&state));
+
+                this.eth_api().apply_pre_execution_changes(&block, &mut db, &evm_env)?;
+
+                // replay all transactions prior to the targeted transaction
+                let mut evm = this.eth_api().evm_config().evm_with_env(&mut db, evm_env);
+                for tx in block.transactions_recovered().take(tx_idx) {
+                    let tx_env = this.eth_api().evm_config().tx_env(tx);
+                    evm.transact_commit(tx_env).map_err(Eth::Error::from_evm_err)?;
+                }
+                drop(evm);
+
+                // overlay the storage changes of the replayed transactions, the accessed slots
+                // also serve as preimages of the hashed slots
+                let mut hashed_storage = HashedStorage::default();
+                let mut preimages = HashMap::default();
+                if let Some(account) = db.cache.accounts.get(&contract_address) {
+                    hashed_storage.wiped = matches!(
+                        account.account_state,
+                        AccountState::StorageCleared | AccountState::NotExisting
+                    );
+                    for (slot, value) in &account.storage {
+                        let slot = B256::from(*slot);
+                        let hashed_slot = keccak256(slot);
+                        preimages.insert(hashed_slot, slot);
+                        hashed_storage.storage.insert(hashed_slot, *value);
+                    }
+                }
+
+                let limit = usize::try_from(max_result).unwrap_or(usize::MAX);
+                let range = state
+                    .storage_range(contract_address, hashed_storage, key_start, limit)
+                    .map_err(EthApiError::from)?;
+                Ok(StorageRangeResult {
+                    storage: range
+                        .slots
+                        .into_iter()
+                        .map(|(hashed_slot, value)| {
+                            let key = preimages.get(&hashed_slot).copied();
+                            (hashed_slot, StorageRangeEntry { key, value: value.into() })
+                        })
+                        .collect(),
+                    next_key: range.next_key,
+                })
+            })
+            .await
+    }
+
     /// Returns the code associated with a given hash at the specified block ID. If no code is
     /// found, it returns None. If no block ID is provided, it defaults to the latest block.
     pub async fn debug_code_by_hash(
@@ -800,6 +909,11 @@ where
                         return Ok((frame.into(), res.state));
                     }
                 },
+                GethDebugTracerType::JsTracer(code) if code == OPCODE_COUNT_TRACER => {
+                    let mut inspector = OpcodeGasInspector::default();
+                    let (res, _) = self.eth_api().inspect(db, evm_env, tx_env, &mut inspector)?;
+                    Ok((opcode_count_frame(&inspector), res.state))
+                }
                 #[cfg(not(feature = "js-tracer"))]
                 GethDebugTracerType::JsTracer(_) => {
                     Err(EthApiError::Unsupported("JS Tracer is not enabled").into())
@@ -841,6 +955,16 @@ where
     }
 }
 
+/// Returns the result of the [`OPCODE_COUNT_TRACER`], a map of opcode names to the number of times
+/// they were executed.
+fn opcode_count_frame(inspector: &OpcodeGasInspector) -> GethTrace {
+    let counts = inspector
+        .opcode_gas_iter()
+        .map(|opcode| (opcode.opcode, opcode.count.into()))
+        .collect::<serde_json::Map<_, _>>();
+    GethTrace::JS(counts.into())
+}
+
 #[async_trait]
 impl<Eth, BlockExecutor> DebugApiServer for DebugApi<Eth, BlockExecutor>
 where
@@ -1006,7 +1130,6 @@ where
         &self,
         block: BlockNumberOrTag,
     ) -> RpcResult<ExecutionWitness> {
-        let _permit = self.acquire_trace_permit().await;
         Self::debug_execution_witness(self, block).await.map_err(Into::into)
     }
 
@@ -1015,7 +1138,6 @@ where
         &self,
         hash: B256,
     ) -> RpcResult<ExecutionWitness> {
-        let _permit = self.acquire_trace_permit().await;
         Self::debug_execution_witness_by_block_hash(self, hash).await.map_err(Into::into)
     }
 
@@ -1203,15 +1325,26 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_storageRangeAt`
     async fn debug_storage_range_at(
         &self,
-        _block_hash: B256,
-        _tx_idx: usize,
-        _contract_address: Address,
-        _key_start: B256,
-        _max_result: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> RpcResult<StorageRangeResult> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_storage_range_at(
+            self,
+            block_hash,
+            tx_idx,
+            contract_address,
+            key_start,
+            max_result,
+        )
+        .await
+        .map_err(Into::into)
     }
 
     async fn debug_trace_bad_block(
@@ -1263,3 +1396,51 @@ struct DebugApiInner<Eth, BlockExecutor> {
     /// block executor for debug & trace apis
     block_executor: BlockExecutor,
 }
+
+/// `debug_hotState` implementation, serving the reports of the [`StateAccessProfiler`].
+#[derive(Debug, Clone)]
+pub struct DebugHotStateApi {
+    profiler: StateAccessProfiler,
+}
+
+impl DebugHotStateApi {
+    /// Creates a new instance of the `debug_hotState` API.
+    pub const fn new(profiler: StateAccessProfiler) -> Self {
+        Self { profiler }
+    }
+}
+
+#[async_trait]
+impl DebugHotStateApiServer for DebugHotStateApi {
+    /// Handler for `debug_hotState`
+    async fn hot_state(&self) -> RpcResult<Option<HotStateReport>> {
+        Ok(self.profiler.latest_report().map(|report| HotStateReport {
+            first_block: report.first_block,
+            last_block: report.last_block,
+            blocks: report.blocks,
+            unique_accounts: report.unique_accounts as u64,
+            unique_storage_slots: report.unique_storage_slots as u64,
+            reused_accounts: report.reused_accounts as u64,
+            reused_storage_slots: report.reused_storage_slots as u64,
+            accounts: report
+                .accounts
+                .into_iter()
+                .map(|(address, stats)| HotAccount { address, stats: hot_key_stats(stats) })
+                .collect(),
+            storage_slots: report
+                .storage_slots
+                .into_iter()
+                .map(|(address, slot, stats)| HotStorageSlot {
+                    address,
+                    slot,
+                    stats: hot_key_stats(stats),
+                })
+                .collect(),
+        }))
+    }
+}
+
+/// Converts the [`AccessStats`] of the profiler into the rpc type.
+const fn hot_key_stats(stats: AccessStats) -> HotKeyStats {
+    HotKeyStats { reads: stats.reads, writes: stats.writes, blocks: stats.blocks }
+}
@@ -4,16 +4,18 @@ use crate::{
     eth::{core::EthApiInner, EthTxBuilder},
     EthApi,
 };
+use jsonrpsee::http_client::HttpClient;
 use reth_node_api::NodePrimitives;
 use reth_provider::{
     BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, StateProviderFactory,
 };
 use reth_rpc_eth_types::{
     fee_history::fee_history_cache_new_blocks_task, EthStateCache, FeeHistoryCache,
-    FeeHistoryCacheConfig, GasCap, GasPriceOracle,
+    FeeHistoryCacheConfig, GasCap, GasPriceOracle, RemoteState,
 };
 use reth_rpc_server_types::constants::{
-    DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
+    DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_CONCURRENT_CALLS, DEFAULT_MAX_PROOFS_ACCOUNTS,
+    DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
 };
 use reth_tasks::{pool::BlockingTaskPool, TaskSpawner, TokioTaskExecutor};
 use std::sync::Arc;
@@ -33,12 +35,18 @@ where
     evm_config: EvmConfig,
     gas_cap: GasCap,
     max_simulate_blocks: u64,
+    access_list_delegations: bool,
+    pending_call_pool_txs: bool,
     eth_proof_window: u64,
+    max_proofs_accounts: usize,
     fee_history_cache_config: FeeHistoryCacheConfig,
     proof_permits: usize,
     eth_cache: Option<EthStateCache<Provider::Block, Provider::Receipt>>,
     gas_oracle: Option<GasPriceOracle<Provider>>,
     blocking_task_pool: Option<BlockingTaskPool>,
+    tracing_pool_threads: Option<usize>,
+    max_concurrent_calls: usize,
+    remote_state: Option<HttpClient>,
     task_spawner: Box<dyn TaskSpawner + 'static>,
 }
 
@@ -60,8 +68,14 @@ where
             gas_oracle: None,
             gas_cap: GasCap::default(),
             max_simulate_blocks: DEFAULT_MAX_SIMULATE_BLOCKS,
+            access_list_delegations: false,
+            pending_call_pool_txs: false,
             eth_proof_window: DEFAULT_ETH_PROOF_WINDOW,
+            max_proofs_accounts: DEFAULT_MAX_PROOFS_ACCOUNTS,
             blocking_task_pool: None,
+            tracing_pool_threads: None,
+            max_concurrent_calls: DEFAULT_MAX_CONCURRENT_CALLS,
+            remote_state: None,
             fee_history_cache_config: FeeHistoryCacheConfig::default(),
             proof_permits: DEFAULT_PROOF_PERMITS,
             task_spawner: TokioTaskExecutor::default().boxed(),
@@ -101,18 +115,61 @@ where
         self
     }
 
+    /// Sets whether `eth_createAccessList` includes the EIP-7702 delegation target of the called
+    /// account.
+    pub const fn access_list_delegations(mut self, access_list_delegations: bool) -> Self {
+        self.access_list_delegations = access_list_delegations;
+        self
+    }
+
+    /// Sets whether calls against the pending block include the caller's pending pool
+    /// transactions.
+    pub const fn pending_call_pool_txs(mut self, pending_call_pool_txs: bool) -> Self {
+        self.pending_call_pool_txs = pending_call_pool_txs;
+        self
+    }
+
     /// Sets the maximum number of blocks into the past for generating state proofs.
     pub const fn eth_proof_window(mut self, eth_proof_window: u64) -> Self {
         self.eth_proof_window = eth_proof_window;
         self
     }
 
-    /// Sets the blocking task pool.
+    /// Sets the maximum number of accounts of a single `reth_getProofs` call.
+    pub const fn max_proofs_accounts(mut self, max_proofs_accounts: usize) -> Self {
+        self.max_proofs_accounts = max_proofs_accounts;
+        self
+    }
+
+    /// Sets the blocking task pool of the `debug` and `trace` namespaces.
     pub fn blocking_task_pool(mut self, blocking_task_pool: BlockingTaskPool) -> Self {
         self.blocking_task_pool = Some(blocking_task_pool);
         self
     }
 
+    /// Sets the number of threads of the blocking task pool of the `debug` and `trace`
+    /// namespaces, if it's not set with [`Self::blocking_task_pool`].
+    ///
+    /// Defaults to the number of CPUs.
+    pub const fn tracing_pool_threads(mut self, threads: Option<usize>) -> Self {
+        self.tracing_pool_threads = threads;
+        self
+    }
+
+    /// Sets the maximum number of `eth_call`s and other simulations of the `eth` namespace that
+    /// are executed concurrently.
+    pub const fn max_concurrent_calls(mut self, max_concurrent_calls: usize) -> Self {
+        self.max_concurrent_calls = max_concurrent_calls;
+        self
+    }
+
+    /// Sets the client of the node that the state of blocks that are pruned locally is fetched
+    /// from, for `eth_call` and tracing.
+    pub fn remote_state(mut self, client: Option<HttpClient>) -> Self {
+        self.remote_state = client;
+        self
+    }
+
     /// Sets the fee history cache.
     pub fn fee_history_cache_config(
         mut self,
@@ -156,8 +213,14 @@ where
             gas_oracle,
             gas_cap,
             max_simulate_blocks,
+            access_list_delegations,
+            pending_call_pool_txs,
             eth_proof_window,
+            max_proofs_accounts,
             blocking_task_pool,
+            tracing_pool_threads,
+            max_concurrent_calls,
+            remote_state,
             fee_history_cache_config,
             proof_permits,
             task_spawner,
@@ -189,13 +252,16 @@ where
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
-            blocking_task_pool.unwrap_or_else(|| {
-                BlockingTaskPool::build().expect("failed to build blocking task pool")
-            }),
+            blocking_task_pool.unwrap_or_else(|| build_tracing_task_pool(tracing_pool_threads)),
+            max_concurrent_calls,
             fee_history_cache,
             evm_config,
             task_spawner,
             proof_permits,
+            access_list_delegations,
+            pending_call_pool_txs,
+            max_proofs_accounts,
+            remote_state.map(RemoteState::new),
         )
     }
 
@@ -221,3 +287,17 @@ where
         EthApi { inner: Arc::new(self.build_inner()), tx_resp_builder: EthTxBuilder }
     }
 }
+
+/// Builds the tracing pool with the given number of threads, or one thread per CPU.
+///
+/// # Panics
+///
+/// If the pool cannot be built.
+fn build_tracing_task_pool(threads: Option<usize>) -> BlockingTaskPool {
+    let pool = BlockingTaskPool::builder()
+        .num_threads(threads.unwrap_or_default())
+        .thread_name(|idx| format!("rpc-tracing-{idx}"))
+        .build()
+        .expect("failed to build blocking task pool");
+    BlockingTaskPool::new(pool)
+}
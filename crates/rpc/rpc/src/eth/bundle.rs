@@ -134,7 +134,7 @@ where
         let eth_api = self.eth_api().clone();
 
         self.eth_api()
-            .spawn_with_state_at_block(at, move |state| {
+            .spawn_call_with_state_at_block(at, move |state| {
                 let coinbase = evm_env.block_env.beneficiary;
                 let basefee = evm_env.block_env.basefee;
                 let db = CacheDB::new(StateProviderDatabase::new(state));
@@ -9,6 +9,7 @@ use alloy_eips::BlockNumberOrTag;
 use alloy_network::Ethereum;
 use alloy_primitives::{Bytes, U256};
 use derive_more::Deref;
+use jsonrpsee::http_client::HttpClient;
 use reth_node_api::{FullNodeComponents, FullNodeTypes};
 use reth_provider::{
     BlockReader, BlockReaderIdExt, NodePrimitivesProvider, ProviderBlock, ProviderReceipt,
@@ -20,7 +21,9 @@ use reth_rpc_eth_api::{
 };
 use reth_rpc_eth_types::{
     EthApiError, EthStateCache, FeeHistoryCache, GasCap, GasPriceOracle, PendingBlock,
+    ProofTaskPool, RemoteState,
 };
+use reth_rpc_server_types::constants::{DEFAULT_MAX_CONCURRENT_CALLS, DEFAULT_MAX_PROOFS_ACCOUNTS};
 use reth_tasks::{
     pool::{BlockingTaskGuard, BlockingTaskPool},
     TaskSpawner, TokioTaskExecutor,
@@ -141,10 +144,15 @@ where
             max_simulate_blocks,
             eth_proof_window,
             blocking_task_pool,
+            DEFAULT_MAX_CONCURRENT_CALLS,
             fee_history_cache,
             evm_config,
             TokioTaskExecutor::default().boxed(),
             proof_permits,
+            false,
+            false,
+            DEFAULT_MAX_PROOFS_ACCOUNTS,
+            None,
         );
 
         Self { inner: Arc::new(inner), tx_resp_builder: EthTxBuilder }
@@ -240,10 +248,20 @@ where
         self.inner.blocking_task_pool()
     }
 
+    #[inline]
+    fn call_task_guard(&self) -> &BlockingTaskGuard {
+        self.inner.call_task_guard()
+    }
+
     #[inline]
     fn tracing_task_guard(&self) -> &BlockingTaskGuard {
         self.inner.blocking_task_guard()
     }
+
+    #[inline]
+    fn proof_task_pool(&self) -> &ProofTaskPool {
+        self.inner.proof_task_pool()
+    }
 }
 
 /// Container type `EthApi`
@@ -265,16 +283,25 @@ pub struct EthApiInner<Provider: BlockReader, Pool, Network, EvmConfig> {
     gas_cap: u64,
     /// Maximum number of blocks for `eth_simulateV1`.
     max_simulate_blocks: u64,
+    /// Whether `eth_createAccessList` includes the EIP-7702 delegation target of the called
+    /// account.
+    access_list_delegations: bool,
+    /// Whether calls against the pending block include the caller's pending pool transactions.
+    pending_call_pool_txs: bool,
     /// The maximum number of blocks into the past for generating state proofs.
     eth_proof_window: u64,
+    /// The maximum number of accounts of a single `reth_getProofs` call.
+    max_proofs_accounts: usize,
     /// The block number at which the node started
     starting_block: U256,
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
     /// Cached pending block if any
     pending_block: Mutex<Option<PendingBlock<Provider::Block, Provider::Receipt>>>,
-    /// A pool dedicated to CPU heavy blocking tasks.
+    /// A pool dedicated to CPU heavy blocking tasks of the `debug` and `trace` namespaces.
     blocking_task_pool: BlockingTaskPool,
+    /// Guard for the calls and simulations of the `eth` namespace.
+    call_task_guard: BlockingTaskGuard,
     /// Cache for block fees history
     fee_history_cache: FeeHistoryCache,
     /// The type that defines how to configure the EVM
@@ -283,8 +310,14 @@ pub struct EthApiInner<Provider: BlockReader, Pool, Network, EvmConfig> {
     /// Guard for getproof calls
     blocking_task_guard: BlockingTaskGuard,
 
+    /// A pool dedicated to state proof generation.
+    proof_task_pool: ProofTaskPool,
+
     /// Transaction broadcast channel
     raw_tx_sender: broadcast::Sender<Bytes>,
+
+    /// The node that the state of blocks that are pruned locally is fetched from.
+    remote_state: Option<RemoteState<HttpClient>>,
 }
 
 impl<Provider, Pool, Network, EvmConfig> EthApiInner<Provider, Pool, Network, EvmConfig>
@@ -303,10 +336,15 @@ where
         max_simulate_blocks: u64,
         eth_proof_window: u64,
         blocking_task_pool: BlockingTaskPool,
+        max_concurrent_calls: usize,
         fee_history_cache: FeeHistoryCache,
         evm_config: EvmConfig,
         task_spawner: Box<dyn TaskSpawner + 'static>,
         proof_permits: usize,
+        access_list_delegations: bool,
+        pending_call_pool_txs: bool,
+        max_proofs_accounts: usize,
+        remote_state: Option<RemoteState<HttpClient>>,
     ) -> Self {
         let signers = parking_lot::RwLock::new(Default::default());
         // get the block number of the latest block
@@ -330,15 +368,21 @@ where
             gas_oracle,
             gas_cap: gas_cap.into().into(),
             max_simulate_blocks,
+            access_list_delegations,
+            pending_call_pool_txs,
             eth_proof_window,
+            max_proofs_accounts,
             starting_block,
             task_spawner,
             pending_block: Default::default(),
             blocking_task_pool,
+            call_task_guard: BlockingTaskGuard::new(max_concurrent_calls),
             fee_history_cache,
             evm_config,
             blocking_task_guard: BlockingTaskGuard::new(proof_permits),
+            proof_task_pool: ProofTaskPool::new(proof_permits),
             raw_tx_sender,
+            remote_state,
         }
     }
 }
@@ -379,6 +423,12 @@ where
         &self.blocking_task_pool
     }
 
+    /// Returns the guard bounding the concurrent calls and simulations of the `eth` namespace.
+    #[inline]
+    pub const fn call_task_guard(&self) -> &BlockingTaskGuard {
+        &self.call_task_guard
+    }
+
     /// Returns a handle to the EVM config.
     #[inline]
     pub const fn evm_config(&self) -> &EvmConfig {
@@ -403,6 +453,19 @@ where
         self.max_simulate_blocks
     }
 
+    /// Returns whether `eth_createAccessList` includes EIP-7702 delegation targets.
+    #[inline]
+    pub const fn access_list_delegations(&self) -> bool {
+        self.access_list_delegations
+    }
+
+    /// Returns whether calls against the pending block include the caller's pending pool
+    /// transactions.
+    #[inline]
+    pub const fn pending_call_pool_txs(&self) -> bool {
+        self.pending_call_pool_txs
+    }
+
     /// Returns a handle to the gas oracle.
     #[inline]
     pub const fn gas_oracle(&self) -> &GasPriceOracle<Provider> {
@@ -441,12 +504,31 @@ where
         self.eth_proof_window
     }
 
+    /// The maximum number of accounts of a single `reth_getProofs` call.
+    #[inline]
+    pub const fn max_proofs_accounts(&self) -> usize {
+        self.max_proofs_accounts
+    }
+
     /// Returns reference to [`BlockingTaskGuard`].
     #[inline]
     pub const fn blocking_task_guard(&self) -> &BlockingTaskGuard {
         &self.blocking_task_guard
     }
 
+    /// Returns a handle to the proof thread pool.
+    #[inline]
+    pub const fn proof_task_pool(&self) -> &ProofTaskPool {
+        &self.proof_task_pool
+    }
+
+    /// Returns the node that the state of blocks that are pruned locally is fetched from, if
+    /// configured.
+    #[inline]
+    pub const fn remote_state(&self) -> Option<&RemoteState<HttpClient>> {
+        self.remote_state.as_ref()
+    }
+
     /// Returns [`broadcast::Receiver`] of new raw transactions
     #[inline]
     pub fn subscribe_to_raw_transactions(&self) -> broadcast::Receiver<Bytes> {
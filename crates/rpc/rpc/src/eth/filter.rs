@@ -1,10 +1,10 @@
 //! `eth_` `Filter` RPC handler implementation
 
 use alloy_consensus::BlockHeader;
-use alloy_primitives::TxHash;
+use alloy_primitives::{Bytes, TxHash};
 use alloy_rpc_types_eth::{
-    BlockNumHash, Filter, FilterBlockOption, FilterChanges, FilterId, FilteredParams, Log,
-    PendingTransactionFilterKind,
+    BlockNumHash, BlockNumberOrTag, Filter, FilterBlockOption, FilterChanges, FilterId,
+    FilteredParams, Log, PendingTransactionFilterKind,
 };
 use async_trait::async_trait;
 use futures::future::TryFutureExt;
@@ -16,30 +16,38 @@ use reth_provider::{
     ProviderError, ProviderReceipt,
 };
 use reth_rpc_eth_api::{
-    EngineEthFilter, EthApiTypes, EthFilterApiServer, FullEthApiTypes, QueryLimits, RpcNodeCoreExt,
-    RpcTransaction, TransactionCompat,
+    EngineEthFilter, EthApiTypes, EthFilterApiServer, FullEthApiTypes, QueryLimits,
+    RethFilterApiServer, RpcNodeCoreExt, RpcTransaction, TransactionCompat,
 };
 use reth_rpc_eth_types::{
-    logs_utils::{self, append_matching_block_logs, ProviderOrBlock},
+    logs_utils::{
+        self, append_matching_block_logs, logs_page_end_block, LogCursor, LogsPage,
+        LogsPageCollector, ProviderOrBlock,
+    },
     EthApiError, EthFilterConfig, EthStateCache, EthSubscriptionIdProvider,
 };
 use reth_rpc_server_types::{result::rpc_error_with_code, ToRpcResult};
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::{NewSubpoolTransactionStream, PoolTransaction, TransactionPool};
+use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
     fmt,
     future::Future,
     iter::StepBy,
     ops::RangeInclusive,
-    sync::Arc,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     time::{Duration, Instant},
 };
 use tokio::{
-    sync::{mpsc::Receiver, Mutex},
+    sync::{mpsc::Receiver, oneshot, Mutex},
     time::MissedTickBehavior,
 };
-use tracing::{error, trace};
+use tracing::{debug, error, trace, warn};
 
 impl<Eth> EngineEthFilter for EthFilter<Eth>
 where
@@ -59,6 +67,9 @@ where
 /// The maximum number of headers we read at once when handling a range filter.
 const MAX_HEADERS_RANGE: u64 = 1_000; // with ~530bytes per header this is ~500kb
 
+/// How often the installed filters are written to the persistence file if they changed.
+const FILTERS_PERSIST_INTERVAL: Duration = Duration::from_secs(1);
+
 /// `Eth` filter RPC implementation.
 ///
 /// This type handles `eth_` rpc requests related to filters (`eth_getLogs`).
@@ -89,6 +100,12 @@ where
     ///
     /// This also spawns a task that periodically clears stale filters.
     ///
+    /// If [`EthFilterConfig::persistence_path`] is set, the log and block filters persisted by a
+    /// previous instance are restored, and a task is spawned that writes the filters to the file
+    /// whenever they changed. The filters are written at most every
+    /// [`FILTERS_PERSIST_INTERVAL`], so if the node crashes, the restored filters can be behind and
+    /// `eth_getFilterChanges` returns changes again that were already returned before the crash.
+    ///
     /// # Create a new instance with [`EthApi`](crate::EthApi)
     ///
     /// ```no_run
@@ -108,11 +125,20 @@ where
     /// let filter = EthFilter::new(eth_api, Default::default(), TokioTaskExecutor::default().boxed());
     /// ```
     pub fn new(eth_api: Eth, config: EthFilterConfig, task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let EthFilterConfig { max_blocks_per_filter, max_logs_per_response, stale_filter_ttl } =
-            config;
+        let EthFilterConfig {
+            max_blocks_per_filter,
+            max_logs_per_response,
+            stale_filter_ttl,
+            persistence_path,
+        } = config;
+        let active_filters = match &persistence_path {
+            Some(path) => ActiveFilters::from_persisted(read_persisted_filters(path)),
+            None => ActiveFilters::new(),
+        };
         let inner = EthFilterInner {
             eth_api,
-            active_filters: ActiveFilters::new(),
+            active_filters,
+            filters_changed: AtomicBool::new(false),
             id_provider: Arc::new(EthSubscriptionIdProvider::default()),
             max_headers_range: MAX_HEADERS_RANGE,
             task_spawner,
@@ -130,6 +156,16 @@ where
             }),
         );
 
+        if let Some(path) = persistence_path {
+            let this = eth_filter.clone();
+            eth_filter.inner.task_spawner.spawn_critical(
+                "eth-filters_persist-filters",
+                Box::pin(async move {
+                    this.watch_and_persist_filters(path).await;
+                }),
+            );
+        }
+
         eth_filter
     }
 
@@ -161,11 +197,43 @@ where
 
             if !is_valid {
                 trace!(target: "rpc::eth", "evict filter with id: {:?}", id);
+                if filter.kind.is_persisted() {
+                    self.inner.mark_filters_changed();
+                }
             }
 
             is_valid
         })
     }
+
+    /// Endless future that writes the log and block filters to the given file whenever they
+    /// changed, at most every [`FILTERS_PERSIST_INTERVAL`].
+    ///
+    /// Polling a filter advances its cursor, so the file lags behind the cursors by up to one
+    /// interval. This is an at-least-once guarantee: changes are never skipped after a restart,
+    /// but may be delivered twice after a crash.
+    async fn watch_and_persist_filters(&self, path: PathBuf) {
+        let mut interval = tokio::time::interval(FILTERS_PERSIST_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if !self.inner.filters_changed.swap(false, Ordering::Relaxed) {
+                continue
+            }
+
+            let filters = self.active_filters().persisted().await;
+            let (tx, rx) = oneshot::channel();
+            let file = path.clone();
+            self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+                let _ = tx.send(write_persisted_filters(&file, &filters));
+            }));
+            if let Ok(Err(err)) = rx.await {
+                error!(target: "rpc::eth", %err, "Failed to persist filters");
+                // retry with the next tick
+                self.inner.mark_filters_changed();
+            }
+        }
+    }
 }
 
 impl<Eth> EthFilter<Eth>
@@ -207,6 +275,9 @@ where
             let mut block = best_number + 1;
             std::mem::swap(&mut filter.block, &mut block);
             filter.last_poll_timestamp = Instant::now();
+            if filter.kind.is_persisted() {
+                self.inner.mark_filters_changed();
+            }
 
             (block, filter.kind.clone())
         };
@@ -353,8 +424,11 @@ where
     async fn uninstall_filter(&self, id: FilterId) -> RpcResult<bool> {
         trace!(target: "rpc::eth", "Serving eth_uninstallFilter");
         let mut filters = self.inner.active_filters.inner.lock().await;
-        if filters.remove(&id).is_some() {
+        if let Some(filter) = filters.remove(&id) {
             trace!(target: "rpc::eth::filter", ?id, "uninstalled filter");
+            if filter.kind.is_persisted() {
+                self.inner.mark_filters_changed();
+            }
             Ok(true)
         } else {
             Ok(false)
@@ -370,6 +444,23 @@ where
     }
 }
 
+#[async_trait]
+impl<Eth> RethFilterApiServer for EthFilter<Eth>
+where
+    Eth: FullEthApiTypes + RpcNodeCoreExt<Provider: BlockIdReader> + 'static,
+{
+    /// Returns a page of the logs matching given filter object.
+    ///
+    /// Handler for `reth_getLogsPaged`
+    async fn logs_paged(&self, filter: Filter, cursor: Option<Bytes>) -> RpcResult<LogsPage> {
+        trace!(target: "rpc::eth", "Serving reth_getLogsPaged");
+        let cursor = cursor
+            .map(|token| LogCursor::decode(&token).ok_or(EthFilterError::InvalidCursor))
+            .transpose()?;
+        Ok(self.inner.logs_page_for_filter(filter, cursor, self.inner.query_limits).await?)
+    }
+}
+
 impl<Eth> std::fmt::Debug for EthFilter<Eth>
 where
     Eth: EthApiTypes,
@@ -386,6 +477,8 @@ struct EthFilterInner<Eth: EthApiTypes> {
     eth_api: Eth,
     /// All currently installed filters.
     active_filters: ActiveFilters<RpcTransaction<Eth::NetworkTypes>>,
+    /// Whether the log or block filters changed since they were last persisted.
+    filters_changed: AtomicBool,
     /// Provides ids to identify filters
     id_provider: Arc<dyn IdProvider>,
     /// limits for logs queries
@@ -398,6 +491,13 @@ struct EthFilterInner<Eth: EthApiTypes> {
     stale_filter_ttl: Duration,
 }
 
+impl<Eth: EthApiTypes> EthFilterInner<Eth> {
+    /// Marks the log and block filters as changed, so they are persisted with the next write.
+    fn mark_filters_changed(&self) {
+        self.filters_changed.store(true, Ordering::Relaxed);
+    }
+}
+
 impl<Eth> EthFilterInner<Eth>
 where
     Eth: RpcNodeCoreExt<Provider: BlockIdReader, Pool: TransactionPool> + EthApiTypes,
@@ -459,19 +559,8 @@ where
             FilterBlockOption::Range { from_block, to_block } => {
                 // compute the range
                 let info = self.provider().chain_info()?;
-
-                // we start at the most recent block if unset in filter
-                let start_block = info.best_number;
-                let from = from_block
-                    .map(|num| self.provider().convert_block_number(num))
-                    .transpose()?
-                    .flatten();
-                let to = to_block
-                    .map(|num| self.provider().convert_block_number(num))
-                    .transpose()?
-                    .flatten();
                 let (from_block_number, to_block_number) =
-                    logs_utils::get_filter_block_range(from, to, start_block, info);
+                    self.filter_block_range(from_block, to_block, info)?;
                 self.get_logs_in_block_range(
                     &filter,
                     from_block_number,
@@ -484,6 +573,131 @@ where
         }
     }
 
+    /// Returns a page of the logs matching given filter object, starting at the given cursor.
+    ///
+    /// Unlike [`Self::logs_for_filter`], exceeding the limits doesn't fail the query. Instead, the
+    /// page ends once it contains the maximum number of logs or the maximum number of blocks was
+    /// scanned, and the page contains the cursor to continue from. A page contains at least one
+    /// log and scans at least one block, so the query makes progress with any limits.
+    ///
+    /// Returns [`EthFilterError::ReorgedCursor`] if the block of the cursor was reorged, since the
+    /// previous pages may contain logs that are no longer canonical.
+    async fn logs_page_for_filter(
+        &self,
+        filter: Filter,
+        cursor: Option<LogCursor>,
+        limits: QueryLimits,
+    ) -> Result<LogsPage, EthFilterError> {
+        let info = self.provider().chain_info()?;
+        let (from_block, to_block) = match filter.block_option {
+            FilterBlockOption::AtBlockHash(block_hash) => {
+                let block_number = self
+                    .provider()
+                    .block_number(block_hash)?
+                    .ok_or_else(|| ProviderError::HeaderNotFound(block_hash.into()))?;
+                (block_number, block_number)
+            }
+            FilterBlockOption::Range { from_block, to_block } => {
+                self.filter_block_range(from_block, to_block, info)?
+            }
+        };
+
+        if to_block < from_block {
+            return Err(EthFilterError::InvalidBlockRangeParams)
+        }
+
+        let start_block = match cursor {
+            Some(cursor) if !(from_block..=to_block).contains(&cursor.block_number) => {
+                return Err(EthFilterError::InvalidCursor)
+            }
+            Some(cursor) => {
+                ensure_canonical_cursor(self.provider(), &cursor)?;
+                cursor.block_number
+            }
+            None => from_block,
+        };
+        let end_block = logs_page_end_block(start_block, to_block, limits.max_blocks_per_filter);
+        trace!(target: "rpc::eth::filter", from=start_block, to=end_block, ?cursor, ?filter, "finding page of logs in range");
+
+        let mut page = LogsPageCollector::new(cursor, limits.max_logs_per_response);
+        let filter_params = FilteredParams::new(Some(filter.clone()));
+        let address_filter = FilteredParams::address_filter(&filter.address);
+        let topics_filter = FilteredParams::topics_filter(&filter.topics);
+
+        for (from, to) in
+            BlockRangeInclusiveIter::new(start_block..=end_block, self.max_headers_range)
+        {
+            let headers = self.provider().headers_range(from..=to)?;
+
+            for (idx, header) in headers.iter().enumerate() {
+                if !FilteredParams::matches_address(header.logs_bloom(), &address_filter) ||
+                    !FilteredParams::matches_topics(header.logs_bloom(), &topics_filter)
+                {
+                    continue
+                }
+
+                let block_hash = match headers.get(idx + 1) {
+                    Some(parent) => parent.parent_hash(),
+                    None => self
+                        .provider()
+                        .block_hash(header.number())?
+                        .ok_or_else(|| ProviderError::HeaderNotFound(header.number().into()))?,
+                };
+                let num_hash = BlockNumHash::new(header.number(), block_hash);
+                let Some((receipts, maybe_block)) =
+                    self.receipts_and_maybe_block(&num_hash, info.best_number).await?
+                else {
+                    continue
+                };
+
+                let mut block_logs = Vec::new();
+                append_matching_block_logs(
+                    &mut block_logs,
+                    maybe_block
+                        .map(ProviderOrBlock::Block)
+                        .unwrap_or_else(|| ProviderOrBlock::Provider(self.provider())),
+                    &filter_params,
+                    num_hash,
+                    &receipts,
+                    false,
+                    header.timestamp(),
+                )?;
+
+                if let Some(next) = page.extend(block_logs) {
+                    return Ok(page.finish(Some(next)))
+                }
+            }
+        }
+
+        let next = if end_block < to_block {
+            let number = end_block + 1;
+            let hash = self
+                .provider()
+                .block_hash(number)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+            Some(LogCursor::block_start(BlockNumHash::new(number, hash)))
+        } else {
+            None
+        };
+        Ok(page.finish(next))
+    }
+
+    /// Converts the block range of a filter into the inclusive range of block numbers.
+    fn filter_block_range(
+        &self,
+        from_block: Option<BlockNumberOrTag>,
+        to_block: Option<BlockNumberOrTag>,
+        info: ChainInfo,
+    ) -> Result<(u64, u64), EthFilterError> {
+        // we start at the most recent block if unset in filter
+        let start_block = info.best_number;
+        let from =
+            from_block.map(|num| self.provider().convert_block_number(num)).transpose()?.flatten();
+        let to =
+            to_block.map(|num| self.provider().convert_block_number(num)).transpose()?.flatten();
+        Ok(logs_utils::get_filter_block_range(from, to, start_block, info))
+    }
+
     /// Installs a new filter and returns the new identifier.
     async fn install_filter(
         &self,
@@ -491,6 +705,9 @@ where
     ) -> RpcResult<FilterId> {
         let last_poll_block_number = self.provider().best_block_number().to_rpc_result()?;
         let id = FilterId::from(self.id_provider.next_id());
+        if kind.is_persisted() {
+            self.mark_filters_changed();
+        }
         let mut filters = self.active_filters.inner.lock().await;
         filters.insert(
             id.clone(),
@@ -630,6 +847,93 @@ impl<T> ActiveFilters<T> {
     pub fn new() -> Self {
         Self { inner: Arc::new(Mutex::new(HashMap::default())) }
     }
+
+    /// Returns an instance with the given persisted filters installed.
+    ///
+    /// The restored filters count as polled now, so clients have the stale filter ttl to poll
+    /// them again after a restart.
+    fn from_persisted(filters: Vec<PersistedFilter>) -> Self {
+        let now = Instant::now();
+        let filters = filters
+            .into_iter()
+            .map(|PersistedFilter { id, block, kind }| {
+                let kind = match kind {
+                    PersistedFilterKind::Log { filter } => FilterKind::Log(filter),
+                    PersistedFilterKind::Block => FilterKind::Block,
+                };
+                (id, ActiveFilter { block, last_poll_timestamp: now, kind })
+            })
+            .collect();
+        Self { inner: Arc::new(Mutex::new(filters)) }
+    }
+
+    /// Returns the installed log and block filters with the block they were polled at last.
+    ///
+    /// Pending transaction filters are skipped, since they can't be restored after a restart.
+    async fn persisted(&self) -> Vec<PersistedFilter> {
+        self.inner
+            .lock()
+            .await
+            .iter()
+            .filter_map(|(id, filter)| {
+                let kind = match &filter.kind {
+                    FilterKind::Log(filter) => PersistedFilterKind::Log { filter: filter.clone() },
+                    FilterKind::Block => PersistedFilterKind::Block,
+                    FilterKind::PendingTransaction(_) => return None,
+                };
+                Some(PersistedFilter { id: id.clone(), block: filter.block, kind })
+            })
+            .collect()
+    }
+}
+
+/// A log or block filter as it's written to the persistence file.
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedFilter {
+    /// The id of the filter.
+    id: FilterId,
+    /// At which block the filter was polled last.
+    block: u64,
+    /// What kind of filter it is.
+    kind: PersistedFilterKind,
+}
+
+/// The kind of a [`PersistedFilter`].
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum PersistedFilterKind {
+    /// A log filter with its criteria.
+    Log { filter: Box<Filter> },
+    /// A block filter.
+    Block,
+}
+
+/// Reads the filters from the persistence file.
+///
+/// Returns no filters if the file doesn't exist or can't be read, since a node must start without
+/// the filters of a previous instance.
+fn read_persisted_filters(path: &Path) -> Vec<PersistedFilter> {
+    if !path.exists() {
+        return Vec::new()
+    }
+    match reth_fs_util::read_json_file::<Vec<PersistedFilter>>(path) {
+        Ok(filters) => {
+            debug!(target: "rpc::eth", count = filters.len(), "Restored persisted filters");
+            filters
+        }
+        Err(err) => {
+            warn!(target: "rpc::eth", %err, "Failed to restore persisted filters");
+            Vec::new()
+        }
+    }
+}
+
+/// Atomically replaces the persistence file with the given filters.
+fn write_persisted_filters(
+    path: &Path,
+    filters: &[PersistedFilter],
+) -> Result<(), reth_fs_util::FsPathError> {
+    reth_fs_util::atomic_write_file(path, |file| serde_json::to_writer(file, filters))
 }
 
 /// An installed filter
@@ -750,6 +1054,25 @@ enum FilterKind<T> {
     PendingTransaction(PendingTransactionKind<T>),
 }
 
+impl<T> FilterKind<T> {
+    /// Returns true if the filter is persisted, which is the case for log and block filters.
+    const fn is_persisted(&self) -> bool {
+        !matches!(self, Self::PendingTransaction(_))
+    }
+}
+
+/// Returns [`EthFilterError::ReorgedCursor`] if the block of the cursor of a paginated logs query
+/// is no longer canonical, i.e. the blocks of the previous pages were reorged.
+fn ensure_canonical_cursor(
+    provider: &impl BlockHashReader,
+    cursor: &LogCursor,
+) -> Result<(), EthFilterError> {
+    if provider.block_hash(cursor.block_number)? != Some(cursor.block_hash) {
+        return Err(EthFilterError::ReorgedCursor)
+    }
+    Ok(())
+}
+
 /// An iterator that yields _inclusive_ block ranges of a given step size
 #[derive(Debug)]
 struct BlockRangeInclusiveIter {
@@ -802,6 +1125,12 @@ pub enum EthFilterError {
     /// Error serving request in `eth_` namespace.
     #[error(transparent)]
     EthAPIError(#[from] EthApiError),
+    /// The continuation token of a paginated query is malformed or outside of the block range.
+    #[error("invalid logs cursor")]
+    InvalidCursor,
+    /// The block of the continuation token of a paginated query was reorged.
+    #[error("logs cursor block was reorged")]
+    ReorgedCursor,
     /// Error thrown when a spawned task failed to deliver a response.
     #[error("internal filter error")]
     InternalError,
@@ -819,6 +1148,8 @@ impl From<EthFilterError> for jsonrpsee::types::error::ErrorObject<'static> {
             }
             EthFilterError::EthAPIError(err) => err.into(),
             err @ (EthFilterError::InvalidBlockRangeParams |
+            EthFilterError::InvalidCursor |
+            EthFilterError::ReorgedCursor |
             EthFilterError::QueryExceedsMaxBlocks(_) |
             EthFilterError::QueryExceedsMaxResults { .. }) => {
                 rpc_error_with_code(jsonrpsee::types::error::INVALID_PARAMS_CODE, err.to_string())
@@ -836,7 +1167,9 @@ impl From<ProviderError> for EthFilterError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_primitives::B256;
     use rand::Rng;
+    use reth_provider::test_utils::MockEthProvider;
     use reth_testing_utils::generators;
 
     #[test]
@@ -860,4 +1193,64 @@ mod tests {
 
         assert_eq!(end, *range.end());
     }
+
+    #[test]
+    fn test_reorged_cursor() {
+        let provider = MockEthProvider::default();
+        let hash = B256::random();
+        provider.add_block(
+            hash,
+            reth_ethereum_primitives::Block {
+                header: alloy_consensus::Header { number: 5, ..Default::default() },
+                body: Default::default(),
+            },
+        );
+
+        let cursor = |block_hash| LogCursor { block_number: 5, block_hash, ..Default::default() };
+        ensure_canonical_cursor(&provider, &cursor(hash)).unwrap();
+        assert!(matches!(
+            ensure_canonical_cursor(&provider, &cursor(B256::random())),
+            Err(EthFilterError::ReorgedCursor)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_persisted_filters_roundtrip() {
+        let filters = ActiveFilters::<()>::new();
+        {
+            let mut inner = filters.inner.lock().await;
+            let log_filter =
+                Filter::new().from_block(10u64).event_signature(B256::with_last_byte(1));
+            for (id, block, kind) in [
+                (FilterId::Num(1), 5, FilterKind::Log(Box::new(log_filter))),
+                (FilterId::Str("0x2".to_string()), 7, FilterKind::Block),
+                (
+                    FilterId::Num(3),
+                    9,
+                    FilterKind::PendingTransaction(PendingTransactionKind::Hashes(
+                        PendingTransactionsReceiver::new(tokio::sync::mpsc::channel(1).1),
+                    )),
+                ),
+            ] {
+                inner.insert(id, ActiveFilter { block, last_poll_timestamp: Instant::now(), kind });
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("filters.json");
+        assert!(read_persisted_filters(&path).is_empty());
+        write_persisted_filters(&path, &filters.persisted().await).unwrap();
+
+        let restored = ActiveFilters::<()>::from_persisted(read_persisted_filters(&path));
+        let restored = restored.inner.lock().await;
+        assert_eq!(restored.len(), 2);
+        let log = &restored[&FilterId::Num(1)];
+        assert_eq!(log.block, 5);
+        assert!(
+            matches!(&log.kind, FilterKind::Log(filter) if filter.get_from_block() == Some(10))
+        );
+        let block = &restored[&FilterId::Str("0x2".to_string())];
+        assert_eq!(block.block, 7);
+        assert!(matches!(block.kind, FilterKind::Block));
+    }
 }
@@ -46,6 +46,16 @@ where
         self.inner.max_simulate_blocks()
     }
 
+    #[inline]
+    fn access_list_delegations(&self) -> bool {
+        self.inner.access_list_delegations()
+    }
+
+    #[inline]
+    fn pending_call_pool_txs(&self) -> bool {
+        self.inner.pending_call_pool_txs()
+    }
+
     fn create_txn_env(
         &self,
         evm_env: &EvmEnv<SpecFor<Self::Evm>>,
@@ -152,3 +162,134 @@ where
     Provider: BlockReader,
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::{eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M, BlockId};
+    use alloy_primitives::{bytes, Address, Bytes, B256};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_provider::{
+        test_utils::{ExtendedAccount, MockEthProvider},
+        StateProviderFactory,
+    };
+    use reth_revm::database::StateProviderDatabase;
+    use reth_rpc_eth_api::helpers::call::MAX_ACCESS_LIST_ITERATIONS;
+    use reth_rpc_eth_types::{
+        EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
+    };
+    use reth_rpc_server_types::constants::{
+        DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
+    };
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, MockTransaction, TestPool},
+        TransactionPool,
+    };
+    use revm::database::CacheDB;
+
+    fn mock_eth_api(
+        contract: Address,
+        code: Bytes,
+    ) -> EthApi<MockEthProvider, TestPool, (), EthEvmConfig> {
+        let pool = testing_pool();
+        let mock_provider = MockEthProvider::default();
+
+        let evm_config = EthEvmConfig::new(mock_provider.chain_spec());
+        mock_provider
+            .add_account(contract, ExtendedAccount::new(0, U256::ZERO).with_bytecode(code));
+
+        let cache = EthStateCache::spawn(mock_provider.clone(), Default::default());
+        EthApi::new(
+            mock_provider.clone(),
+            pool,
+            (),
+            cache.clone(),
+            GasPriceOracle::new(mock_provider, Default::default(), cache),
+            ETHEREUM_BLOCK_GAS_LIMIT_30M,
+            DEFAULT_MAX_SIMULATE_BLOCKS,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(FeeHistoryCacheConfig::default()),
+            evm_config,
+            DEFAULT_PROOF_PERMITS,
+        )
+    }
+
+    fn call(contract: Address) -> TransactionRequest {
+        TransactionRequest {
+            from: Some(Address::random()),
+            to: Some(TxKind::Call(contract)),
+            gas: Some(100_000),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn create_access_list_reaches_fixed_point() {
+        // PUSH1 0x01 SLOAD STOP
+        let contract = Address::random();
+        let eth_api = mock_eth_api(contract, bytes!("60015400"));
+
+        let result = eth_api
+            .create_access_list_with(EvmEnv::default(), BlockId::latest(), call(contract))
+            .unwrap();
+        assert_eq!(result.error, None);
+        assert_eq!(result.access_list.0.len(), 1);
+        assert_eq!(result.access_list.0[0].address, contract);
+        assert_eq!(result.access_list.0[0].storage_keys, vec![B256::with_last_byte(1)]);
+    }
+
+    #[tokio::test]
+    async fn create_access_list_iterations_are_bounded() {
+        // GAS SLOAD STOP, reads a different slot whenever the access list changes the gas costs
+        let contract = Address::random();
+        let eth_api = mock_eth_api(contract, bytes!("5a5400"));
+
+        let result = eth_api
+            .create_access_list_with(EvmEnv::default(), BlockId::latest(), call(contract))
+            .unwrap();
+        assert_eq!(result.error, None);
+
+        // every run accesses one more slot
+        let item = result.access_list.0.iter().find(|item| item.address == contract).unwrap();
+        assert_eq!(item.storage_keys.len(), MAX_ACCESS_LIST_ITERATIONS);
+    }
+
+    /// Adds transfers of the sender with the given nonces and gas limits to the pool, and returns
+    /// the number of applied pending transactions and the sender's nonce afterwards.
+    async fn apply_pending_transfers(txs: &[(u64, u64)]) -> (usize, u64) {
+        let eth_api = mock_eth_api(Address::random(), Bytes::new());
+        let sender = Address::random();
+        eth_api.provider().add_account(sender, ExtendedAccount::new(0, U256::from(u64::MAX)));
+
+        for &(nonce, gas_limit) in txs {
+            let tx = MockTransaction::eip1559()
+                .with_sender(sender)
+                .with_nonce(nonce)
+                .with_gas_limit(gas_limit);
+            eth_api.pool().add_external_transaction(tx).await.unwrap();
+        }
+
+        let state = eth_api.provider().latest().unwrap();
+        let mut db = CacheDB::new(StateProviderDatabase::new(state));
+        let applied =
+            eth_api.apply_pending_pool_transactions(&mut db, EvmEnv::default(), sender).unwrap();
+        let nonce = db.basic(sender).unwrap().map(|account| account.nonce).unwrap_or_default();
+        (applied, nonce)
+    }
+
+    #[tokio::test]
+    async fn apply_pending_pool_transactions_stops_at_nonce_gap() {
+        assert_eq!(apply_pending_transfers(&[]).await, (0, 0));
+        assert_eq!(apply_pending_transfers(&[(0, 21_000), (1, 21_000)]).await, (2, 2));
+        assert_eq!(apply_pending_transfers(&[(0, 21_000), (1, 21_000), (3, 21_000)]).await, (2, 2));
+    }
+
+    #[tokio::test]
+    async fn apply_pending_pool_transactions_stops_at_invalid_transaction() {
+        // the second transaction's gas limit is below the intrinsic gas
+        let txs = [(0, 21_000), (1, 1_000), (2, 21_000)];
+        assert_eq!(apply_pending_transfers(&txs).await, (1, 1));
+    }
+}
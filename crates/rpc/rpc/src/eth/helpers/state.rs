@@ -1,13 +1,19 @@
 //! Contains RPC handler implementations specific to state.
 
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockId;
+use alloy_primitives::B256;
 use reth_chainspec::EthereumHardforks;
-use reth_provider::{BlockReader, ChainSpecProvider, StateProviderFactory};
+use reth_provider::{
+    BlockReader, BlockReaderIdExt, ChainSpecProvider, StateProviderBox, StateProviderFactory,
+};
 use reth_transaction_pool::TransactionPool;
 
 use reth_rpc_eth_api::{
     helpers::{EthState, LoadState, SpawnBlocking},
-    RpcNodeCoreExt,
+    FromEthApiError, RpcNodeCoreExt,
 };
+use reth_rpc_eth_types::EthApiError;
 
 use crate::EthApi;
 
@@ -19,18 +25,57 @@ where
     fn max_proof_window(&self) -> u64 {
         self.inner.eth_proof_window()
     }
+
+    fn max_proofs_accounts(&self) -> usize {
+        self.inner.max_proofs_accounts()
+    }
 }
 
 impl<Provider, Pool, Network, EvmConfig> LoadState for EthApi<Provider, Pool, Network, EvmConfig>
 where
     Self: RpcNodeCoreExt<
-        Provider: BlockReader
+        Provider: BlockReaderIdExt
                       + StateProviderFactory
                       + ChainSpecProvider<ChainSpec: EthereumHardforks>,
         Pool: TransactionPool,
     >,
-    Provider: BlockReader,
+    Provider: BlockReaderIdExt,
+{
+    fn state_at_hash(&self, block_hash: B256) -> Result<StateProviderBox, Self::Error> {
+        let state =
+            self.provider().history_by_block_hash(block_hash).map_err(Self::Error::from_eth_err)?;
+        self.with_remote_state(state, block_hash.into())
+    }
+
+    fn state_at_block_id(&self, at: BlockId) -> Result<StateProviderBox, Self::Error> {
+        let state = self.provider().state_by_block_id(at).map_err(Self::Error::from_eth_err)?;
+        if at.is_pending() {
+            return Ok(state)
+        }
+        self.with_remote_state(state, at)
+    }
+}
+
+impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConfig>
+where
+    Provider: BlockReaderIdExt,
 {
+    /// Wraps the state of the block in a
+    /// [`RemoteStateProvider`](reth_rpc_eth_types::RemoteStateProvider) that fetches the state
+    /// that is pruned locally, if a remote node is configured.
+    fn with_remote_state(
+        &self,
+        state: StateProviderBox,
+        at: BlockId,
+    ) -> Result<StateProviderBox, EthApiError> {
+        let Some(remote) = self.inner.remote_state() else { return Ok(state) };
+        let header = self
+            .inner
+            .provider()
+            .sealed_header_by_id(at)?
+            .ok_or(EthApiError::HeaderNotFound(at))?;
+        Ok(Box::new(remote.state_provider(state, header.hash(), header.state_root())))
+    }
 }
 
 #[cfg(test)]
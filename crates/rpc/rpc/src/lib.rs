@@ -32,6 +32,7 @@ use jsonwebtoken as _;
 use pin_project as _;
 use tower as _;
 
+mod aa;
 mod admin;
 mod debug;
 mod engine;
@@ -46,14 +47,15 @@ mod txpool;
 mod validation;
 mod web3;
 
-pub use admin::AdminApi;
-pub use debug::DebugApi;
+pub use aa::AaApi;
+pub use admin::{AdminApi, AdminCapabilitiesApi, AdminReorgApi};
+pub use debug::{DebugApi, DebugHotStateApi};
 pub use engine::{EngineApi, EngineEthApi};
 pub use eth::{EthApi, EthApiBuilder, EthBundle, EthFilter, EthPubSub};
 pub use miner::MinerApi;
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
-pub use reth::RethApi;
+pub use reth::{HealthApi, RethApi};
 pub use rpc::RPCApi;
 pub use trace::TraceApi;
 pub use txpool::TxPoolApi;
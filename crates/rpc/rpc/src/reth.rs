@@ -1,16 +1,37 @@
-use std::{collections::HashMap, future::Future, sync::Arc};
+use std::{collections::HashMap, future::Future, sync::Arc, time::Duration};
 
-use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_consensus::BlockHeader;
+use alloy_eips::{eip2935::HISTORY_STORAGE_ADDRESS, eip4788::BEACON_ROOTS_ADDRESS, BlockId};
+use alloy_primitives::{Address, B256, U256, U64};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_errors::RethResult;
-use reth_provider::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
-use reth_rpc_api::RethApiServer;
+use reth_network_api::PeersInfo;
+use reth_provider::{
+    BlockProcessingHistoryReader, BlockReaderIdExt, ChangeSetReader, DepositIndexReader,
+    ProviderResult, StateProvider, StateProviderFactory, TransactionLookupIndexReader,
+    TransactionLookupIndexWriter, TransactionLookupStatus,
+};
+use reth_rpc_api::{
+    AdminTxLookupApiServer, BlockProcessingEntry, HealthReport, NetworkHealthReport, RethApiServer,
+    RethHealthApiServer, TxLookupSegment, TxLookupSegmentState, TxLookupStatus,
+};
 use reth_rpc_eth_types::{EthApiError, EthResult};
 use reth_tasks::TaskSpawner;
 use tokio::sync::oneshot;
 
+/// The length of the ring buffer of the EIP-4788 beacon roots contract.
+const BEACON_ROOTS_BUFFER_LENGTH: u64 = 8191;
+
+/// The number of block hashes served by the EIP-2935 history storage contract.
+const HISTORY_SERVE_WINDOW: u64 = 8191;
+
+/// The maximum number of blocks indexed by a single `admin_rebuildTxLookup` call.
+const MAX_TX_LOOKUP_REBUILD_BLOCKS: u64 = 10_000;
+
+/// The maximum number of blocks returned by a single `reth_getBlockProcessingHistory` call.
+const MAX_BLOCK_PROCESSING_HISTORY_BLOCKS: u64 = 10_000;
+
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
@@ -33,9 +54,47 @@ impl<Provider> RethApi<Provider> {
     }
 }
 
+/// `reth` API implementation of the health of the node.
+///
+/// This is separate from [`RethApi`], so it doesn't depend on the network.
+#[derive(Debug, Clone)]
+pub struct HealthApi<Network> {
+    /// The network that can be queried for its health.
+    network: Network,
+}
+
+impl<Network> HealthApi<Network> {
+    /// Create a new instance of the [`HealthApi`]
+    pub const fn new(network: Network) -> Self {
+        Self { network }
+    }
+}
+
+impl<Network: PeersInfo> HealthApi<Network> {
+    /// Returns the health of the node.
+    pub fn health(&self) -> HealthReport {
+        let health = self.network.network_health();
+        HealthReport {
+            network: NetworkHealthReport {
+                status: health.status.to_string(),
+                connected_peers: health.connected_peers as u64,
+                degraded_seconds: health.degraded_since.map(|since| since.elapsed().as_secs()),
+                escalations: health.escalations.into(),
+            },
+        }
+    }
+}
+
 impl<Provider> RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + DepositIndexReader
+        + StateProviderFactory
+        + TransactionLookupIndexReader
+        + TransactionLookupIndexWriter
+        + BlockProcessingHistoryReader
+        + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -83,12 +142,131 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns the number of deposit contract deposits until the end of the given block.
+    pub async fn deposit_count(&self, block_id: BlockId) -> EthResult<Option<U64>> {
+        self.on_blocking_task(|this| async move {
+            let block_number = this.block_number(block_id)?;
+            Ok(this.provider().deposit_count(block_number)?.map(U64::from))
+        })
+        .await
+    }
+
+    /// Returns the root of the deposit contract's merkle tree at the end of the given block.
+    pub async fn deposit_root(&self, block_id: BlockId) -> EthResult<Option<B256>> {
+        self.on_blocking_task(|this| async move {
+            let block_number = this.block_number(block_id)?;
+            Ok(this.provider().deposit_root(block_number)?)
+        })
+        .await
+    }
+
+    /// Returns the parent beacon block root of the block with the given timestamp from the
+    /// EIP-4788 beacon roots contract.
+    pub async fn beacon_root(&self, timestamp: u64) -> EthResult<Option<B256>> {
+        self.on_blocking_task(|this| async move {
+            let state = this.provider().latest()?;
+            Ok(read_beacon_root(&*state, timestamp)?)
+        })
+        .await
+    }
+
+    /// Returns the hash of the block with the given number from the EIP-2935 history storage
+    /// contract.
+    pub async fn historical_block_hash(&self, number: u64) -> EthResult<Option<B256>> {
+        self.on_blocking_task(|this| async move {
+            let Some(latest) = this.provider().latest_header()? else { return Ok(None) };
+            // the state is pinned to the hash of the latest header so that it matches its number
+            let state = this.provider().state_by_block_id(latest.hash().into())?;
+            Ok(read_historical_block_hash(&*state, latest.number(), number)?)
+        })
+        .await
+    }
+
+    /// Returns the progress of the transaction hash index.
+    pub async fn tx_lookup_status(&self) -> EthResult<TxLookupStatus> {
+        self.on_blocking_task(|this| async move {
+            let status = this.provider().transaction_lookup_status()?;
+            Ok(TxLookupStatus {
+                pruned_block: status.pruned_block,
+                indexed_block: status.indexed_block,
+                stored_block: status.stored_block,
+                lag: status.lag(),
+                segments: tx_lookup_segments(&status),
+            })
+        })
+        .await
+    }
+
+    /// Indexes the transaction hashes of the given blocks and returns the number of indexed
+    /// transactions.
+    pub async fn rebuild_tx_lookup(&self, from_block: u64, to_block: u64) -> EthResult<u64> {
+        if to_block < from_block {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+        if to_block - from_block >= MAX_TX_LOOKUP_REBUILD_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range exceeds the maximum of {MAX_TX_LOOKUP_REBUILD_BLOCKS} blocks"
+            )))
+        }
+
+        self.on_blocking_task(|this| async move {
+            Ok(this.provider().index_transaction_hashes(from_block..=to_block)?)
+        })
+        .await
+    }
+
+    /// Returns the processing timings of the canonical blocks in the given range.
+    pub async fn block_processing_history(
+        &self,
+        from_block: u64,
+        to_block: u64,
+    ) -> EthResult<Vec<BlockProcessingEntry>> {
+        if to_block < from_block {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+        if to_block - from_block >= MAX_BLOCK_PROCESSING_HISTORY_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range exceeds the maximum of {MAX_BLOCK_PROCESSING_HISTORY_BLOCKS} blocks"
+            )))
+        }
+
+        self.on_blocking_task(|this| async move {
+            let mut entries = Vec::new();
+            for stats in this.provider().block_processing_history(from_block..=to_block)? {
+                // blocks that were reorged out by the pipeline may still be recorded
+                if this.provider().block_hash(stats.number)? != Some(stats.hash) {
+                    continue
+                }
+                entries.push(BlockProcessingEntry {
+                    number: stats.number,
+                    hash: stats.hash,
+                    gas_used: stats.gas_used,
+                    execution_micros: micros(stats.execution_time),
+                    state_root_micros: micros(stats.state_root_time),
+                });
+            }
+            Ok(entries)
+        })
+        .await
+    }
+
+    fn block_number(&self, block_id: BlockId) -> EthResult<u64> {
+        self.provider().block_number_for_id(block_id)?.ok_or(EthApiError::HeaderNotFound(block_id))
+    }
 }
 
 #[async_trait]
 impl<Provider> RethApiServer for RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + DepositIndexReader
+        + StateProviderFactory
+        + TransactionLookupIndexReader
+        + TransactionLookupIndexWriter
+        + BlockProcessingHistoryReader
+        + 'static,
 {
     /// Handler for `reth_getBalanceChangesInBlock`
     async fn reth_get_balance_changes_in_block(
@@ -97,6 +275,128 @@ where
     ) -> RpcResult<HashMap<Address, U256>> {
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
+
+    /// Handler for `reth_getDepositCount`
+    async fn reth_get_deposit_count(&self, block_id: BlockId) -> RpcResult<Option<U64>> {
+        Ok(Self::deposit_count(self, block_id).await?)
+    }
+
+    /// Handler for `reth_getDepositRoot`
+    async fn reth_get_deposit_root(&self, block_id: BlockId) -> RpcResult<Option<B256>> {
+        Ok(Self::deposit_root(self, block_id).await?)
+    }
+
+    /// Handler for `reth_getBeaconRoot`
+    async fn reth_get_beacon_root(&self, timestamp: U64) -> RpcResult<Option<B256>> {
+        Ok(Self::beacon_root(self, timestamp.to()).await?)
+    }
+
+    /// Handler for `reth_getHistoricalBlockHash`
+    async fn reth_get_historical_block_hash(&self, number: U64) -> RpcResult<Option<B256>> {
+        Ok(Self::historical_block_hash(self, number.to()).await?)
+    }
+
+    /// Handler for `reth_getTxLookupStatus`
+    async fn reth_get_tx_lookup_status(&self) -> RpcResult<TxLookupStatus> {
+        Ok(Self::tx_lookup_status(self).await?)
+    }
+
+    /// Handler for `reth_getBlockProcessingHistory`
+    async fn reth_get_block_processing_history(
+        &self,
+        from_block: U64,
+        to_block: U64,
+    ) -> RpcResult<Vec<BlockProcessingEntry>> {
+        Ok(Self::block_processing_history(self, from_block.to(), to_block.to()).await?)
+    }
+}
+
+impl<Network> RethHealthApiServer for HealthApi<Network>
+where
+    Network: PeersInfo + 'static,
+{
+    /// Handler for `reth_health`
+    fn reth_health(&self) -> RpcResult<HealthReport> {
+        Ok(Self::health(self))
+    }
+}
+
+#[async_trait]
+impl<Provider> AdminTxLookupApiServer for RethApi<Provider>
+where
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + DepositIndexReader
+        + StateProviderFactory
+        + TransactionLookupIndexReader
+        + TransactionLookupIndexWriter
+        + BlockProcessingHistoryReader
+        + 'static,
+{
+    /// Handler for `admin_rebuildTxLookup`
+    async fn rebuild_tx_lookup(&self, from_block: U64, to_block: U64) -> RpcResult<U64> {
+        Ok(Self::rebuild_tx_lookup(self, from_block.to(), to_block.to()).await.map(U64::from)?)
+    }
+}
+
+/// Splits the stored blocks into the ranges of pruned, indexed and unindexed transaction hashes.
+fn tx_lookup_segments(status: &TransactionLookupStatus) -> Vec<TxLookupSegment> {
+    let mut segments = Vec::new();
+    let mut push = |from_block, to_block, state| {
+        if from_block <= to_block {
+            segments.push(TxLookupSegment { from_block, to_block, state })
+        }
+    };
+
+    let first_indexed = status.pruned_block.map_or(0, |pruned_block| pruned_block + 1);
+    if let Some(pruned_block) = status.pruned_block {
+        push(0, pruned_block, TxLookupSegmentState::Pruned);
+    }
+    push(first_indexed, status.indexed_block, TxLookupSegmentState::Indexed);
+    if let Some(unindexed) = status.unindexed_blocks() {
+        push(*unindexed.start(), *unindexed.end(), TxLookupSegmentState::Unindexed);
+    }
+    segments
+}
+
+/// Reads the parent beacon block root of the block with the given timestamp from the EIP-4788
+/// beacon roots contract in the given state.
+fn read_beacon_root(state: &dyn StateProvider, timestamp: u64) -> ProviderResult<Option<B256>> {
+    let timestamp_index = timestamp % BEACON_ROOTS_BUFFER_LENGTH;
+
+    // the slot is shared with other timestamps, so it's only valid if it stores ours
+    let stored_timestamp =
+        state.storage(BEACON_ROOTS_ADDRESS, B256::from(U256::from(timestamp_index)))?;
+    if stored_timestamp != Some(U256::from(timestamp)) {
+        return Ok(None)
+    }
+
+    let root_index = timestamp_index + BEACON_ROOTS_BUFFER_LENGTH;
+    let root = state.storage(BEACON_ROOTS_ADDRESS, B256::from(U256::from(root_index)))?;
+    Ok(root.map(B256::from))
+}
+
+/// Reads the hash of the block with the given number from the EIP-2935 history storage contract
+/// in the state of the `latest` block.
+fn read_historical_block_hash(
+    state: &dyn StateProvider,
+    latest: u64,
+    number: u64,
+) -> ProviderResult<Option<B256>> {
+    // the contract serves the hashes of the blocks before the latest block, within the serve
+    // window
+    if number >= latest || latest - number > HISTORY_SERVE_WINDOW {
+        return Ok(None)
+    }
+
+    let slot = B256::from(U256::from(number % HISTORY_SERVE_WINDOW));
+    let hash = state.storage(HISTORY_STORAGE_ADDRESS, slot)?;
+    Ok(hash.filter(|hash| !hash.is_zero()).map(B256::from))
+}
+
+/// Returns the duration in microseconds, saturating at `u64::MAX`.
+fn micros(duration: Duration) -> u64 {
+    u64::try_from(duration.as_micros()).unwrap_or(u64::MAX)
 }
 
 impl<Provider> std::fmt::Debug for RethApi<Provider> {
@@ -117,3 +417,77 @@ struct RethApiInner<Provider> {
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+
+    fn slot(index: u64) -> B256 {
+        B256::from(U256::from(index))
+    }
+
+    #[test]
+    fn beacon_root_ring_buffer() {
+        let provider = MockEthProvider::default();
+        // wraps around to the same slot as timestamp 5
+        let timestamp = BEACON_ROOTS_BUFFER_LENGTH + 5;
+        let root = B256::random();
+        provider.add_account(
+            BEACON_ROOTS_ADDRESS,
+            ExtendedAccount::new(0, U256::ZERO).extend_storage([
+                (slot(5), U256::from(timestamp)),
+                (slot(5 + BEACON_ROOTS_BUFFER_LENGTH), U256::from_be_bytes(root.0)),
+            ]),
+        );
+
+        assert_eq!(read_beacon_root(&provider, timestamp).unwrap(), Some(root));
+        // the slot was overwritten by the newer timestamp
+        assert_eq!(read_beacon_root(&provider, 5).unwrap(), None);
+        // no root was stored for this timestamp
+        assert_eq!(read_beacon_root(&provider, timestamp + 1).unwrap(), None);
+    }
+
+    #[test]
+    fn historical_block_hash_serve_window() {
+        let provider = MockEthProvider::default();
+        let latest = HISTORY_SERVE_WINDOW + 10;
+        // wraps around to the same slot as block 5
+        let number = HISTORY_SERVE_WINDOW + 5;
+        let hash = B256::random();
+        provider.add_account(
+            HISTORY_STORAGE_ADDRESS,
+            ExtendedAccount::new(0, U256::ZERO)
+                .extend_storage([(slot(5), U256::from_be_bytes(hash.0))]),
+        );
+
+        assert_eq!(read_historical_block_hash(&provider, latest, number).unwrap(), Some(hash));
+        // out of the serve window, the slot stores the hash of a newer block
+        assert_eq!(read_historical_block_hash(&provider, latest, 5).unwrap(), None);
+        // the latest block and blocks after it are not served
+        assert_eq!(read_historical_block_hash(&provider, latest, latest).unwrap(), None);
+        assert_eq!(read_historical_block_hash(&provider, latest, latest + 1).unwrap(), None);
+        // the hash of a block in the window was not stored
+        assert_eq!(read_historical_block_hash(&provider, latest, number + 1).unwrap(), None);
+    }
+
+    #[test]
+    fn tx_lookup_segments_split_stored_blocks() {
+        let segment = |from_block, to_block, state| TxLookupSegment { from_block, to_block, state };
+        let status =
+            TransactionLookupStatus { pruned_block: Some(9), indexed_block: 20, stored_block: 30 };
+        assert_eq!(
+            tx_lookup_segments(&status),
+            vec![
+                segment(0, 9, TxLookupSegmentState::Pruned),
+                segment(10, 20, TxLookupSegmentState::Indexed),
+                segment(21, 30, TxLookupSegmentState::Unindexed),
+            ]
+        );
+
+        // everything up to the indexed block is pruned, and the index caught up
+        let status =
+            TransactionLookupStatus { pruned_block: Some(30), indexed_block: 30, stored_block: 30 };
+        assert_eq!(tx_lookup_segments(&status), vec![segment(0, 30, TxLookupSegmentState::Pruned)]);
+    }
+}
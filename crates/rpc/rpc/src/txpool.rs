@@ -2,18 +2,20 @@ use core::fmt;
 use std::collections::BTreeMap;
 
 use alloy_consensus::Transaction;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash};
 use alloy_rpc_types_txpool::{
     TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus,
 };
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
-use reth_rpc_api::TxPoolApiServer;
+use futures::StreamExt;
+use jsonrpsee::{core::RpcResult, server::SubscriptionMessage, PendingSubscriptionSink};
+use reth_rpc_api::{TxPoolApiServer, TxpoolDropReason, TxpoolEvent, TxpoolSubPool};
 use reth_rpc_types_compat::TransactionCompat;
+use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{
-    AllPoolTransactions, PoolConsensusTx, PoolTransaction, TransactionPool,
+    AllPoolTransactions, FullTransactionEvent, PoolConsensusTx, PoolTransaction, TransactionPool,
 };
-use tracing::trace;
+use tracing::{error, trace};
 
 /// `txpool` API implementation.
 ///
@@ -23,12 +25,26 @@ pub struct TxPoolApi<Pool, Eth> {
     /// An interface to interact with the pool
     pool: Pool,
     tx_resp_builder: Eth,
+    /// The type that's used to spawn subscription tasks, [`TokioTaskExecutor`] if not set.
+    subscription_task_spawner: Option<Box<dyn TaskSpawner>>,
 }
 
 impl<Pool, Eth> TxPoolApi<Pool, Eth> {
     /// Creates a new instance of `TxpoolApi`.
+    ///
+    /// Subscription tasks are spawned via [`tokio::task::spawn`]
     pub const fn new(pool: Pool, tx_resp_builder: Eth) -> Self {
-        Self { pool, tx_resp_builder }
+        Self { pool, tx_resp_builder, subscription_task_spawner: None }
+    }
+
+    /// Creates a new instance of `TxpoolApi` that spawns subscription tasks with the given
+    /// spawner.
+    pub const fn with_spawner(
+        pool: Pool,
+        tx_resp_builder: Eth,
+        subscription_task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        Self { pool, tx_resp_builder, subscription_task_spawner: Some(subscription_task_spawner) }
     }
 }
 
@@ -68,6 +84,45 @@ where
 
         Ok(content)
     }
+
+    /// Converts a pool event into the event served by `txpool_subscribeEvents`.
+    ///
+    /// Returns `None` for events that don't change the contents of the pool.
+    fn pool_event(
+        &self,
+        event: FullTransactionEvent<Pool::Transaction>,
+    ) -> Option<TxpoolEvent<Eth::Transaction>> {
+        let event = match event {
+            FullTransactionEvent::Pending(hash) => self.added_event(hash, TxpoolSubPool::Pending),
+            FullTransactionEvent::Queued(hash) => self.added_event(hash, TxpoolSubPool::Queued),
+            FullTransactionEvent::Mined { tx_hash, block_hash } => {
+                TxpoolEvent::Mined { hash: tx_hash, block_hash }
+            }
+            FullTransactionEvent::Replaced { transaction, replaced_by } => {
+                TxpoolEvent::Replaced { hash: *transaction.hash(), replaced_by }
+            }
+            FullTransactionEvent::Discarded(hash) => {
+                TxpoolEvent::Dropped { hash, reason: TxpoolDropReason::Discarded }
+            }
+            FullTransactionEvent::Expired(hash) => {
+                TxpoolEvent::Dropped { hash, reason: TxpoolDropReason::Expired }
+            }
+            FullTransactionEvent::Invalid(hash) => {
+                TxpoolEvent::Dropped { hash, reason: TxpoolDropReason::Invalid }
+            }
+            FullTransactionEvent::Propagated(_) => return None,
+        };
+        Some(event)
+    }
+
+    /// Creates an [`TxpoolEvent::Added`] event, including the transaction if it's still in the
+    /// pool.
+    fn added_event(&self, hash: TxHash, subpool: TxpoolSubPool) -> TxpoolEvent<Eth::Transaction> {
+        let transaction = self.pool.get(&hash).and_then(|tx| {
+            self.tx_resp_builder.fill_pending(tx.transaction.clone_into_consensus()).ok()
+        });
+        TxpoolEvent::Added { hash, subpool, transaction }
+    }
 }
 
 #[async_trait]
@@ -142,6 +197,42 @@ where
         trace!(target: "rpc::eth", "Serving txpool_content");
         Ok(self.content().map_err(Into::into)?)
     }
+
+    /// Handler for `txpool_subscribeEvents`
+    async fn txpool_subscribe_events(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        trace!(target: "rpc::eth", "Serving txpool_subscribeEvents");
+        let sink = pending.accept().await?;
+        let mut events = self.pool.all_transactions_event_listener();
+        let this = self.clone();
+        let default_spawner = TokioTaskExecutor::default();
+        let spawner = self.subscription_task_spawner.as_deref().unwrap_or(&default_spawner);
+        spawner.spawn(Box::pin(async move {
+            loop {
+                tokio::select! {
+                    _ = sink.closed() => break,
+                    maybe_event = events.next() => {
+                        let Some(event) = maybe_event else { break };
+                        let Some(event) = this.pool_event(event) else { continue };
+                        let msg = match SubscriptionMessage::from_json(&event) {
+                            Ok(msg) => msg,
+                            Err(err) => {
+                                error!(target: "rpc::eth", %err, "Failed to serialize txpool event");
+                                break
+                            }
+                        };
+                        if sink.send(msg).await.is_err() {
+                            break
+                        }
+                    }
+                }
+            }
+        }));
+
+        Ok(())
+    }
 }
 
 impl<Pool, Eth> fmt::Debug for TxPoolApi<Pool, Eth> {
@@ -149,3 +240,59 @@ impl<Pool, Eth> fmt::Debug for TxPoolApi<Pool, Eth> {
         f.debug_struct("TxpoolApi").finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::EthTxBuilder;
+    use alloy_primitives::B256;
+    use reth_transaction_pool::test_utils::{testing_pool, MockTransaction};
+
+    #[tokio::test]
+    async fn test_pool_events() {
+        let pool = testing_pool();
+        let api = TxPoolApi::new(pool.clone(), EthTxBuilder::default());
+
+        let hash = pool.add_external_transaction(MockTransaction::eip1559()).await.unwrap();
+        let event = api.pool_event(FullTransactionEvent::Pending(hash)).unwrap();
+        assert!(matches!(
+            event,
+            TxpoolEvent::Added { hash: added, subpool: TxpoolSubPool::Pending, transaction: Some(_) }
+                if added == hash
+        ));
+
+        // the transaction is omitted if it's no longer in the pool
+        let missing = TxHash::random();
+        let event = api.pool_event(FullTransactionEvent::Queued(missing)).unwrap();
+        assert_eq!(
+            event,
+            TxpoolEvent::Added { hash: missing, subpool: TxpoolSubPool::Queued, transaction: None }
+        );
+
+        let block_hash = B256::random();
+        let event = api.pool_event(FullTransactionEvent::Mined { tx_hash: hash, block_hash });
+        assert_eq!(event, Some(TxpoolEvent::Mined { hash, block_hash }));
+
+        let event = api.pool_event(FullTransactionEvent::Invalid(hash));
+        assert_eq!(event, Some(TxpoolEvent::Dropped { hash, reason: TxpoolDropReason::Invalid }));
+
+        assert_eq!(api.pool_event(FullTransactionEvent::Propagated(Default::default())), None);
+    }
+
+    #[test]
+    fn test_pool_event_serde() {
+        let hash = TxHash::random();
+        let replaced_by = TxHash::random();
+        let event = TxpoolEvent::<()>::Replaced { hash, replaced_by };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({ "type": "replaced", "hash": hash, "replacedBy": replaced_by })
+        );
+
+        let event = TxpoolEvent::<()>::Dropped { hash, reason: TxpoolDropReason::Discarded };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({ "type": "dropped", "hash": hash, "reason": "discarded" })
+        );
+    }
+}
@@ -18,7 +18,11 @@ use reth_consensus::{Consensus, FullConsensus};
 use reth_engine_primitives::PayloadValidator;
 use reth_errors::{BlockExecutionError, ConsensusError, ProviderError};
 use reth_evm::execute::{BlockExecutorProvider, Executor};
-use reth_metrics::{metrics, metrics::Gauge, Metrics};
+use reth_metrics::{
+    metrics,
+    metrics::{Gauge, Histogram},
+    Metrics,
+};
 use reth_node_api::NewPayloadError;
 use reth_primitives_traits::{
     constants::GAS_LIMIT_BOUND_DIVISOR, BlockBody, GotExpected, NodePrimitives, RecoveredBlock,
@@ -26,12 +30,15 @@ use reth_primitives_traits::{
 };
 use reth_provider::{BlockExecutionOutput, BlockReaderIdExt, StateProviderFactory};
 use reth_revm::{cached::CachedReads, database::StateProviderDatabase};
-use reth_rpc_api::BlockSubmissionValidationApiServer;
-use reth_rpc_server_types::result::internal_rpc_err;
+use reth_rpc_api::{
+    BlockSubmissionValidationApiServer, PayloadValidationApiServer, PayloadValidationResponse,
+    PayloadValidationTimings,
+};
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
 use reth_tasks::TaskSpawner;
 use revm_primitives::{Address, B256, U256};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashSet, sync::Arc};
+use std::{collections::HashSet, sync::Arc, time::Instant};
 use tokio::sync::{oneshot, RwLock};
 
 /// The type that implements the `validation` rpc namespace trait
@@ -203,6 +210,79 @@ where
         Ok(())
     }
 
+    /// Runs the full validation of the given payload against the state of its parent without
+    /// inserting the block, recording the time spent in each step.
+    ///
+    /// Validation stops at the first failing step, the error is reported in the response.
+    pub async fn validate_payload(&self, payload: ExecutionData) -> PayloadValidationResponse {
+        let started_at = Instant::now();
+        let mut response = PayloadValidationResponse {
+            block_hash: payload.payload.block_hash(),
+            ..Default::default()
+        };
+
+        if let Err(err) = self.validate_payload_steps(payload, &mut response.timings).await {
+            response.error = Some(err.to_string());
+        } else {
+            response.valid = true;
+        }
+
+        response.timings.total = started_at.elapsed().as_micros() as u64;
+        self.metrics.payload_validation_duration.record(started_at.elapsed());
+        response
+    }
+
+    /// Executes the individual payload validation steps, filling in the timings of every step
+    /// that completes.
+    async fn validate_payload_steps(
+        &self,
+        payload: ExecutionData,
+        timings: &mut PayloadValidationTimings,
+    ) -> Result<(), ValidationApiError> {
+        let mut step = Instant::now();
+        let block = self.payload_validator.ensure_well_formed_payload(payload)?;
+        timings.well_formed = Some(step.elapsed().as_micros() as u64);
+
+        step = Instant::now();
+        self.consensus.validate_header_with_total_difficulty(block.sealed_header(), U256::MAX)?;
+        self.consensus.validate_header(block.sealed_header())?;
+        self.consensus.validate_block_pre_execution(block.sealed_block())?;
+
+        let parent_header = self
+            .provider
+            .sealed_header_by_hash(block.parent_hash())?
+            .ok_or_else(|| ValidationApiError::MissingParentBlock)?;
+        self.consensus.validate_header_against_parent(block.sealed_header(), &parent_header)?;
+        timings.pre_execution = Some(step.elapsed().as_micros() as u64);
+
+        step = Instant::now();
+        let parent_header_hash = parent_header.hash();
+        let state_provider = self.provider.state_by_block_hash(parent_header_hash)?;
+        let mut request_cache = self.cached_reads(parent_header_hash).await;
+        let cached_db = request_cache.as_db_mut(StateProviderDatabase::new(&state_provider));
+        let output = self.executor_provider.executor(cached_db).execute(&block)?;
+        self.update_cached_reads(parent_header_hash, request_cache).await;
+        timings.execution = Some(step.elapsed().as_micros() as u64);
+
+        step = Instant::now();
+        self.consensus.validate_block_post_execution(&block, &output)?;
+        timings.post_execution = Some(step.elapsed().as_micros() as u64);
+
+        step = Instant::now();
+        let state_root =
+            state_provider.state_root(state_provider.hashed_post_state(&output.state))?;
+        timings.state_root = Some(step.elapsed().as_micros() as u64);
+
+        if state_root != block.header().state_root() {
+            return Err(ConsensusError::BodyStateRootDiff(
+                GotExpected { got: state_root, expected: block.header().state_root() }.into(),
+            )
+            .into())
+        }
+
+        Ok(())
+    }
+
     /// Ensures that fields of [`BidTrace`] match the fields of the [`SealedHeaderFor`].
     fn validate_message_against_header(
         &self,
@@ -463,6 +543,58 @@ where
     }
 }
 
+#[async_trait]
+impl<Provider, E> PayloadValidationApiServer for ValidationApi<Provider, E>
+where
+    Provider: BlockReaderIdExt<Header = <E::Primitives as NodePrimitives>::BlockHeader>
+        + ChainSpecProvider<ChainSpec: EthereumHardforks>
+        + StateProviderFactory
+        + Clone
+        + 'static,
+    E: BlockExecutorProvider,
+{
+    /// Handler for `admin_validatePayload`
+    async fn validate_payload(
+        &self,
+        payload: ExecutionPayload,
+        versioned_hashes: Option<Vec<B256>>,
+        parent_beacon_block_root: Option<B256>,
+        execution_requests: Option<RequestsOrHash>,
+    ) -> RpcResult<PayloadValidationResponse> {
+        let sidecar = match (versioned_hashes, parent_beacon_block_root, execution_requests) {
+            (Some(versioned_hashes), Some(parent_beacon_block_root), None) => {
+                ExecutionPayloadSidecar::v3(CancunPayloadFields {
+                    parent_beacon_block_root,
+                    versioned_hashes,
+                })
+            }
+            (Some(versioned_hashes), Some(parent_beacon_block_root), Some(requests)) => {
+                ExecutionPayloadSidecar::v4(
+                    CancunPayloadFields { parent_beacon_block_root, versioned_hashes },
+                    PraguePayloadFields { requests },
+                )
+            }
+            (None, None, None) => ExecutionPayloadSidecar::none(),
+            _ => {
+                return Err(invalid_params_rpc_err(
+                    "versioned hashes and parent beacon block root must be provided together \
+                     with execution requests",
+                ))
+            }
+        };
+
+        let this = self.clone();
+        let (tx, rx) = oneshot::channel();
+
+        self.task_spawner.spawn_blocking(Box::pin(async move {
+            let response = this.validate_payload(ExecutionData { payload, sidecar }).await;
+            let _ = tx.send(response);
+        }));
+
+        rx.await.map_err(|_| internal_rpc_err("Internal blocking task error"))
+    }
+}
+
 pub struct ValidationApiInner<Provider, E: BlockExecutorProvider> {
     /// The provider that can interact with the chain.
     provider: Provider,
@@ -562,4 +694,6 @@ pub enum ValidationApiError {
 pub(crate) struct ValidationMetrics {
     /// The number of entries configured in the builder validation disallow list.
     pub(crate) disallow_size: Gauge,
+    /// The time it took to run an `admin_validatePayload` request.
+    pub(crate) payload_validation_duration: Histogram,
 }
@@ -29,6 +29,16 @@ pub enum MetricEvent {
         /// If specified, `entities_total` metric is updated.
         max_block_number: Option<BlockNumber>,
     },
+    /// Progress of the transaction hash index, as read from the database.
+    ///
+    /// This is sent after the sync height changed outside of the pipeline, since the stage
+    /// checkpoints of a [`MetricEvent::SyncHeight`] don't reflect the index.
+    TransactionLookupStatus {
+        /// The highest block whose transaction hashes are indexed.
+        indexed_block: BlockNumber,
+        /// The highest block whose transactions are stored.
+        stored_block: BlockNumber,
+    },
 }
 
 /// Metrics routine that listens to new metric events on the `events_rx` receiver.
@@ -75,6 +85,11 @@ impl MetricsListener {
                 if let Some(total) = total {
                     stage_metrics.entities_total.set(total as f64);
                 }
+
+                self.sync_metrics.update_transaction_lookup_lag(stage_id, checkpoint.block_number);
+            }
+            MetricEvent::TransactionLookupStatus { indexed_block, stored_block } => {
+                self.sync_metrics.set_transaction_lookup_status(indexed_block, stored_block);
             }
         }
     }
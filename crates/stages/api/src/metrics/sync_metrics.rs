@@ -1,10 +1,16 @@
 use crate::StageId;
+use alloy_primitives::BlockNumber;
 use reth_metrics::{metrics::Gauge, Metrics};
 use std::collections::HashMap;
 
 #[derive(Debug, Default)]
 pub(crate) struct SyncMetrics {
     pub(crate) stages: HashMap<StageId, StageMetrics>,
+    pub(crate) transaction_lookup: TransactionLookupMetrics,
+    /// The last checkpoint of the bodies stage.
+    bodies_checkpoint: BlockNumber,
+    /// The last checkpoint of the transaction lookup stage.
+    transaction_lookup_checkpoint: BlockNumber,
 }
 
 impl SyncMetrics {
@@ -15,6 +21,33 @@ impl SyncMetrics {
             .entry(stage_id)
             .or_insert_with(|| StageMetrics::new_with_labels(&[("stage", stage_id.to_string())]))
     }
+
+    /// Records the checkpoint of the bodies or transaction lookup stage and updates the number of
+    /// blocks the transaction hash index lags behind the stored blocks.
+    pub(crate) fn update_transaction_lookup_lag(
+        &mut self,
+        stage_id: StageId,
+        block_number: BlockNumber,
+    ) {
+        match stage_id {
+            StageId::Bodies => self.bodies_checkpoint = block_number,
+            StageId::TransactionLookup => self.transaction_lookup_checkpoint = block_number,
+            _ => return,
+        }
+        self.transaction_lookup
+            .lag
+            .set(self.bodies_checkpoint.saturating_sub(self.transaction_lookup_checkpoint) as f64);
+    }
+
+    /// Records the progress of the transaction hash index as read from the database.
+    pub(crate) fn set_transaction_lookup_status(
+        &mut self,
+        indexed_block: BlockNumber,
+        stored_block: BlockNumber,
+    ) {
+        self.update_transaction_lookup_lag(StageId::TransactionLookup, indexed_block);
+        self.update_transaction_lookup_lag(StageId::Bodies, stored_block);
+    }
 }
 
 #[derive(Metrics)]
@@ -27,3 +60,10 @@ pub(crate) struct StageMetrics {
     /// The number of total entities of the last commit for a stage, if applicable.
     pub(crate) entities_total: Gauge,
 }
+
+#[derive(Metrics)]
+#[metrics(scope = "sync.transaction_lookup")]
+pub(crate) struct TransactionLookupMetrics {
+    /// The number of stored blocks whose transaction hashes aren't indexed yet.
+    pub(crate) lag: Gauge,
+}
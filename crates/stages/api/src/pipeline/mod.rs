@@ -163,6 +163,9 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
         let _ = self.register_metrics(); // ignore error
 
         loop {
+            // Only the progress of the last pass counts towards the maximum block, the stages may
+            // have finished before it in a previous pass
+            self.progress.minimum_block_number = None;
             let next_action = self.run_loop().await?;
 
             if next_action.is_unwind() && self.fail_on_unwind {
@@ -384,7 +387,12 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
         let stage = &mut self.stages[stage_index];
         let stage_id = stage.id();
         let mut made_progress = false;
-        let target = self.max_block.or(previous_stage);
+        // A stage can't run past the previous stage, which may have finished before the maximum
+        // block, e.g. the execution stage with a state root interval.
+        let target = match (self.max_block, previous_stage) {
+            (Some(max_block), Some(previous_stage)) => Some(max_block.min(previous_stage)),
+            (max_block, previous_stage) => max_block.or(previous_stage),
+        };
 
         loop {
             let prev_checkpoint = self.provider_factory.get_stage_checkpoint(stage_id)?;
@@ -947,6 +955,53 @@ mod tests {
         );
     }
 
+    /// Runs a pipeline where the first stage finishes before the maximum block, so the next stage
+    /// runs only up to it.
+    #[tokio::test]
+    async fn run_pipeline_with_early_finished_stage() {
+        let provider_factory = create_test_provider_factory();
+
+        let mut pipeline = Pipeline::<MockNodeTypesWithDB>::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(5), done: true }))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .add_stage(
+                TestStage::new(StageId::Other("B"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(5), done: true }))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .with_max_block(10)
+            .build(
+                provider_factory.clone(),
+                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
+            );
+        let events = pipeline.events();
+
+        // Run pipeline
+        tokio::spawn(async move {
+            pipeline.run().await.unwrap();
+        });
+
+        let targets = events
+            .filter_map(|event| match event {
+                PipelineEvent::Run { stage_id, target, .. } => Some((stage_id, target)),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(
+            targets,
+            vec![
+                (StageId::Other("A"), Some(10)),
+                (StageId::Other("B"), Some(5)),
+                (StageId::Other("A"), Some(10)),
+                (StageId::Other("B"), Some(10)),
+            ]
+        );
+    }
+
     /// Runs a pipeline that unwinds during sync.
     ///
     /// The flow is:
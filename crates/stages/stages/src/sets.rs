@@ -38,9 +38,10 @@
 //! ```
 use crate::{
     stages::{
-        AccountHashingStage, BodyStage, ExecutionStage, FinishStage, HeaderStage,
-        IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage, PruneSenderRecoveryStage,
-        PruneStage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
+        AccountHashingStage, BodyStage, DepositIndexStage, ExecutionStage, FinishStage,
+        HeaderStage, IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage,
+        PruneSenderRecoveryStage, PruneStage, SenderRecoveryStage, StorageHashingStage,
+        TransactionLookupStage,
     },
     StageSet, StageSetBuilder,
 };
@@ -291,6 +292,7 @@ where
 /// - [`PruneSenderRecoveryStage`]
 /// - [`HashingStages`]
 /// - [`HistoryIndexingStages`]
+/// - [`DepositIndexStage`], if enabled
 /// - [`PruneStage`]
 #[derive(Debug)]
 #[non_exhaustive]
@@ -324,6 +326,7 @@ where
     PruneSenderRecoveryStage: Stage<Provider>,
     HashingStages: StageSet<Provider>,
     HistoryIndexingStages: StageSet<Provider>,
+    DepositIndexStage: Stage<Provider>,
     PruneStage: Stage<Provider>,
 {
     fn builder(self) -> StageSetBuilder<Provider> {
@@ -338,6 +341,13 @@ where
                 stages_config: self.stages_config.clone(),
                 prune_modes: self.prune_modes.clone(),
             })
+            // If the deposit index is enabled, add the deposit index stage.
+            .add_stage_opt(
+                self.stages_config
+                    .deposit_index
+                    .enabled
+                    .then(|| DepositIndexStage::new(self.stages_config.deposit_index)),
+            )
             // If any prune modes are set, add the prune stage.
             .add_stage_opt(self.prune_modes.is_empty().not().then(|| {
                 // Prune stage should be added after all hashing stages, because otherwise it will
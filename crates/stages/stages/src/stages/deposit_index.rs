@@ -0,0 +1,112 @@
+use reth_chainspec::EthChainSpec;
+use reth_config::config::DepositIndexConfig;
+use reth_db_api::transaction::DbTxMut;
+use reth_provider::{ChainSpecProvider, DBProvider, DepositIndexWriter, ReceiptProvider};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use reth_storage_errors::provider::ProviderError;
+use tracing::info;
+
+/// The deposit index stage.
+///
+/// This stage indexes the deposits of the beacon chain deposit contract from the receipts of the
+/// [`ExecutionStage`][crate::stages::ExecutionStage]. Every deposit is verified to have the next
+/// contiguous index, and its deposit data root is stored as a leaf of the deposit contract's merkle
+/// tree, which allows to serve the deposit count and root at any indexed block.
+///
+/// Blocks before the deployment of the deposit contract are skipped. This stage is optional and
+/// requires the receipts of the deposit contract to be retained.
+#[derive(Debug, Clone)]
+pub struct DepositIndexStage {
+    /// The maximum number of blocks to process before committing progress to the database.
+    commit_threshold: u64,
+}
+
+impl Default for DepositIndexStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000 }
+    }
+}
+
+impl DepositIndexStage {
+    /// Create new instance of [`DepositIndexStage`].
+    pub const fn new(config: DepositIndexConfig) -> Self {
+        Self { commit_threshold: config.commit_threshold }
+    }
+}
+
+impl<Provider> Stage<Provider> for DepositIndexStage
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + ChainSpecProvider<ChainSpec: EthChainSpec>
+        + ReceiptProvider
+        + DepositIndexWriter,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::DepositIndex
+    }
+
+    /// Index the deposits of the next range of blocks.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+        let (start, end) = range.into_inner();
+
+        if let Some(contract) = provider.chain_spec().deposit_contract() {
+            let start = start.max(contract.block);
+            info!(target: "sync::stages::deposit_index", from = start, to = end, "Indexing deposits");
+
+            for block_number in start..=end {
+                let receipts = provider
+                    .receipts_by_block(block_number.into())?
+                    .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?;
+                provider.append_deposits(block_number, &receipts)?;
+            }
+        }
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(end), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        provider.unwind_deposit_index(input.unwind_to)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::{
+        test_utils::create_test_provider_factory, DepositIndexReader, StageCheckpointWriter,
+    };
+
+    #[test]
+    fn skips_blocks_before_deposit_contract() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let contract_block = provider.chain_spec().deposit_contract().unwrap().block;
+
+        let mut stage = DepositIndexStage { commit_threshold: u64::MAX };
+        let output = stage
+            .execute(&provider, ExecInput { target: Some(contract_block - 1), checkpoint: None })
+            .unwrap();
+        assert_eq!(
+            output,
+            ExecOutput { checkpoint: StageCheckpoint::new(contract_block - 1), done: true }
+        );
+
+        provider.save_stage_checkpoint(StageId::DepositIndex, output.checkpoint).unwrap();
+        assert_eq!(provider.deposit_count(contract_block - 1).unwrap(), Some(0));
+    }
+}
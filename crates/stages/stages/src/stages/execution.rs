@@ -1,6 +1,6 @@
 use crate::stages::MERKLE_STAGE_DEFAULT_CLEAN_THRESHOLD;
 use alloy_consensus::{BlockHeader, Header};
-use alloy_primitives::BlockNumber;
+use alloy_primitives::{Address, BlockNumber};
 use num_traits::Zero;
 use reth_config::config::ExecutionConfig;
 use reth_consensus::{ConsensusError, FullConsensus};
@@ -9,14 +9,17 @@ use reth_evm::{
     execute::{BlockExecutorProvider, Executor},
     metrics::ExecutorMetrics,
 };
-use reth_execution_types::Chain;
+use reth_execution_types::{BlockExecutionResult, Chain};
 use reth_exex::{ExExManagerHandle, ExExNotification, ExExNotificationSource};
-use reth_primitives_traits::{format_gas_throughput, Block, BlockBody, NodePrimitives};
+use reth_primitives_traits::{
+    block::error::BlockRecoveryError, format_gas_throughput, Block, BlockBody, NodePrimitives,
+    RecoveredBlock,
+};
 use reth_provider::{
     providers::{StaticFileProvider, StaticFileWriter},
     BlockHashReader, BlockReader, DBProvider, ExecutionOutcome, HeaderProvider,
     LatestStateProviderRef, OriginalValuesKnown, ProviderError, StateCommitmentProvider,
-    StateWriter, StaticFileProviderFactory, StatsReader, StorageLocation, TransactionVariant,
+    StateWriter, StaticFileProviderFactory, StatsReader, StorageLocation,
 };
 use reth_revm::database::StateProviderDatabase;
 use reth_stages_api::{
@@ -27,8 +30,9 @@ use reth_stages_api::{
 use reth_static_file_types::StaticFileSegment;
 use std::{
     cmp::Ordering,
+    collections::VecDeque,
     ops::RangeInclusive,
-    sync::Arc,
+    sync::{mpsc, Arc},
     task::{ready, Context, Poll},
     time::{Duration, Instant},
 };
@@ -64,6 +68,13 @@ use super::missing_static_data_error;
 /// - [`tables::AccountsHistory`] to remove change set and apply old values to
 /// - [`tables::PlainAccountState`] [`tables::StoragesHistory`] to remove change set and apply old
 ///   values to [`tables::PlainStorageState`]
+///
+/// Executed blocks are validated against their receipts, but the state root is not verified by
+/// this stage. It's verified by the [`MerkleStage`](crate::stages::MerkleStage) for the block the
+/// pipeline runs to, so no state commitment is computed for the blocks in between. With a
+/// [state root interval](ExecutionStage::with_state_root_interval), the stage finishes at every
+/// multiple of the interval instead, so that the state root of that block is verified by the
+/// following stages before the next blocks are executed.
 // false positive, we cannot derive it if !DB: Debug.
 #[derive(Debug)]
 pub struct ExecutionStage<E>
@@ -94,6 +105,13 @@ where
     exex_manager_handle: ExExManagerHandle<E::Primitives>,
     /// Executor metrics.
     metrics: ExecutorMetrics,
+    /// Whether the post-execution validation of blocks is offloaded to a separate thread pool.
+    offload_receipts_verification: bool,
+    /// The thread pool the post-execution validation is offloaded to, built on the first run of
+    /// the stage and reused by the following runs.
+    validation_pool: Option<Arc<rayon::ThreadPool>>,
+    /// The interval of blocks at which the stage finishes, so that their state root is verified.
+    state_root_interval: Option<u64>,
 }
 
 impl<E> ExecutionStage<E>
@@ -117,9 +135,37 @@ where
             post_unwind_commit_input: None,
             exex_manager_handle,
             metrics: ExecutorMetrics::default(),
+            offload_receipts_verification: false,
+            validation_pool: None,
+            state_root_interval: None,
         }
     }
 
+    /// Sets whether the post-execution validation of blocks, which includes the verification of
+    /// the receipts root and logs bloom, is offloaded to a separate thread pool.
+    ///
+    /// If enabled, the validation of a block overlaps with the execution of the next blocks.
+    pub const fn with_offloaded_receipts_verification(mut self, enabled: bool) -> Self {
+        self.offload_receipts_verification = enabled;
+        self
+    }
+
+    /// Sets the interval of blocks at which the state root is verified.
+    ///
+    /// The stage finishes at every multiple of the interval, even if the target is further ahead,
+    /// so that the following stages compute and verify the state root of that block. An interval
+    /// of zero is ignored.
+    pub fn with_state_root_interval(mut self, interval: Option<u64>) -> Self {
+        self.state_root_interval = interval.filter(|interval| *interval > 0);
+        self
+    }
+
+    /// Returns the last block to execute in a run of the stage from `start_block` to `target`.
+    fn run_end_block(&self, start_block: BlockNumber, target: BlockNumber) -> BlockNumber {
+        self.state_root_interval
+            .map_or(target, |interval| target.min(start_block.div_ceil(interval) * interval))
+    }
+
     /// Create an execution stage with the provided executor.
     ///
     /// The commit threshold will be set to [`MERKLE_STAGE_DEFAULT_CLEAN_THRESHOLD`].
@@ -150,6 +196,7 @@ where
             external_clean_threshold,
             ExExManagerHandle::empty(),
         )
+        .with_state_root_interval(config.state_root_interval)
     }
 
     /// Returns whether we can perform pruning of [`tables::AccountChangeSets`] and
@@ -288,7 +335,7 @@ where
         }
 
         let start_block = input.next_block();
-        let max_block = input.target();
+        let max_block = self.run_end_block(start_block, input.target());
         let static_file_provider = provider.static_file_provider();
 
         self.ensure_consistency(provider, input.checkpoint().block_number, None)?;
@@ -301,7 +348,7 @@ where
         let mut stage_checkpoint = execution_checkpoint(
             &static_file_provider,
             start_block,
-            max_block,
+            input.target(),
             input.checkpoint(),
         )?;
 
@@ -320,16 +367,48 @@ where
         let mut cumulative_gas = 0;
         let batch_start = Instant::now();
 
+        // Senders of the upcoming blocks are recovered in the background while the current block
+        // is executed.
+        let mut sender_recovery = SenderRecoveryPipeline::spawn()?;
+        let mut next_fetch_block = start_block;
+
+        // Executed blocks are validated against their execution results either right away, or in
+        // the background while the next blocks are executed.
+        if self.offload_receipts_verification && self.validation_pool.is_none() {
+            self.validation_pool = Some(Arc::new(
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(POST_EXECUTION_VALIDATION_THREADS)
+                    .thread_name(|idx| format!("post-exec-validation-{idx}"))
+                    .build()
+                    .map_err(|err| StageError::Fatal(err.into()))?,
+            ));
+        }
+        let mut validation =
+            PostExecutionValidation::new(self.consensus.clone(), self.validation_pool.clone());
+
         let mut blocks = Vec::new();
         let mut results = Vec::new();
         for block_number in start_block..=max_block {
             // Fetch the block
             let fetch_block_start = Instant::now();
 
-            // we need the block's transactions but we don't need the transaction hashes
-            let block = provider
-                .recovered_block(block_number.into(), TransactionVariant::NoHash)?
-                .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+            // Keep the sender recovery pipeline filled with the upcoming blocks
+            while next_fetch_block <= max_block && !sender_recovery.is_full() {
+                // we need the block's transactions but we don't need the transaction hashes
+                let block = provider
+                    .block(next_fetch_block.into())?
+                    .ok_or_else(|| ProviderError::HeaderNotFound(next_fetch_block.into()))?;
+                let senders = provider.senders_by_tx_range(
+                    provider
+                        .block_body_indices(next_fetch_block)?
+                        .ok_or(ProviderError::BlockBodyIndicesNotFound(next_fetch_block))?
+                        .tx_num_range(),
+                )?;
+                sender_recovery.submit(block, senders)?;
+                next_fetch_block += 1;
+            }
+
+            let block = sender_recovery.next()?;
 
             fetch_block_duration += fetch_block_start.elapsed();
 
@@ -341,20 +420,19 @@ where
             // Execute the block
             let execute_start = Instant::now();
 
-            let result = self.metrics.metered_one(&block, |input| {
+            let result = match self.metrics.metered_one(&block, |input| {
                 executor.execute_one(input).map_err(|error| StageError::Block {
                     block: Box::new(block.block_with_parent()),
                     error: BlockErrorKind::Execution(error),
                 })
-            })?;
-
-            if let Err(err) = self.consensus.validate_block_post_execution(&block, &result) {
-                return Err(StageError::Block {
-                    block: Box::new(block.block_with_parent()),
-                    error: BlockErrorKind::Validation(err),
-                })
-            }
-            results.push(result);
+            }) {
+                Ok(result) => result,
+                Err(err) => {
+                    // An invalid block that was executed before this one must be reported first
+                    validation.wait_all()?;
+                    return Err(err)
+                }
+            };
 
             execution_duration += execute_start.elapsed();
 
@@ -377,9 +455,14 @@ where
             stage_progress = block_number;
             stage_checkpoint.progress.processed += block.header().gas_used();
 
-            // If we have ExExes we need to save the block in memory for later
-            if self.exex_manager_handle.has_exexs() {
-                blocks.push(block);
+            validation.submit(block, result);
+            while let Some((block, result)) = validation.next(false)? {
+                results.push(result);
+
+                // If we have ExExes we need to save the block in memory for later
+                if self.exex_manager_handle.has_exexs() {
+                    blocks.push(block);
+                }
             }
 
             // Check if we should commit now
@@ -393,6 +476,14 @@ where
             }
         }
 
+        // Wait for the remaining blocks to be validated before anything is written
+        while let Some((block, result)) = validation.next(true)? {
+            results.push(result);
+            if self.exex_manager_handle.has_exexs() {
+                blocks.push(block);
+            }
+        }
+
         // prepare execution output for writing
         let time = Instant::now();
         let mut state = ExecutionOutcome::from_blocks(
@@ -564,6 +655,167 @@ where
     }
 }
 
+/// The maximum number of blocks that have their senders recovered ahead of the block that is being
+/// executed.
+const SENDER_RECOVERY_LOOKAHEAD: usize = 32;
+
+/// Recovers the senders of blocks on a separate thread, so that the recovery of the upcoming blocks
+/// overlaps with the execution of the current one.
+///
+/// Blocks are passed to the recovery thread and back through bounded channels, and are returned in
+/// the order they were submitted. Senders are only recovered if they are not provided, i.e. if
+/// they're missing from the database. The signatures of a block are recovered in parallel.
+#[derive(Debug)]
+struct SenderRecoveryPipeline<B: Block> {
+    /// Sends blocks with their known senders to the recovery thread.
+    to_recovery: mpsc::SyncSender<(B, Vec<Address>)>,
+    /// Receives recovered blocks from the recovery thread.
+    from_recovery: mpsc::Receiver<Result<RecoveredBlock<B>, BlockRecoveryError<B>>>,
+    /// The number of blocks that were submitted but not yet returned.
+    in_flight: usize,
+}
+
+impl<B: Block + 'static> SenderRecoveryPipeline<B> {
+    /// Spawns the recovery thread.
+    fn spawn() -> Result<Self, StageError> {
+        let (to_recovery, recovery_rx) =
+            mpsc::sync_channel::<(B, Vec<Address>)>(SENDER_RECOVERY_LOOKAHEAD);
+        let (recovery_tx, from_recovery) = mpsc::sync_channel(SENDER_RECOVERY_LOOKAHEAD);
+
+        std::thread::Builder::new()
+            .name("sender-recovery".to_string())
+            .spawn(move || {
+                for (block, senders) in recovery_rx {
+                    if recovery_tx.send(block.try_into_recovered_unchecked(senders)).is_err() {
+                        break
+                    }
+                }
+            })
+            .map_err(|err| StageError::Fatal(err.into()))?;
+
+        Ok(Self { to_recovery, from_recovery, in_flight: 0 })
+    }
+
+    /// Returns `true` if no more blocks can be submitted before the next one is returned.
+    const fn is_full(&self) -> bool {
+        self.in_flight >= SENDER_RECOVERY_LOOKAHEAD
+    }
+
+    /// Submits the block for sender recovery.
+    fn submit(&mut self, block: B, senders: Vec<Address>) -> Result<(), StageError> {
+        self.to_recovery
+            .send((block, senders))
+            .map_err(|_| StageError::Fatal("sender recovery thread exited".into()))?;
+        self.in_flight += 1;
+        Ok(())
+    }
+
+    /// Returns the next submitted block with its senders, blocking until it is recovered.
+    fn next(&mut self) -> Result<RecoveredBlock<B>, StageError> {
+        let recovered = self
+            .from_recovery
+            .recv()
+            .map_err(|_| StageError::Fatal("sender recovery thread exited".into()))?;
+        self.in_flight -= 1;
+        Ok(recovered.map_err(|_| ProviderError::SenderRecoveryError)?)
+    }
+}
+
+/// The maximum number of executed blocks that wait for their validation before the execution of the
+/// next block is blocked.
+const POST_EXECUTION_VALIDATION_LOOKAHEAD: usize = 32;
+
+/// The number of threads that validate executed blocks if the validation is offloaded.
+const POST_EXECUTION_VALIDATION_THREADS: usize = 2;
+
+/// An executed block with its execution result.
+type ExecutedBlock<N> = (
+    RecoveredBlock<<N as NodePrimitives>::Block>,
+    BlockExecutionResult<<N as NodePrimitives>::Receipt>,
+);
+
+/// Validates executed blocks against their execution results, e.g. the gas used, the receipts root
+/// and the logs bloom.
+///
+/// If offloading is enabled, blocks are validated on a separate thread pool, so that the
+/// validation overlaps with the execution of the next blocks. Otherwise, blocks are validated as
+/// soon as they are submitted. In both cases, blocks are returned in the order they were
+/// submitted, so the first invalid block is always the one that is reported.
+#[derive(Debug)]
+struct PostExecutionValidation<N: NodePrimitives> {
+    /// The consensus instance for validating blocks.
+    consensus: Arc<dyn FullConsensus<N, Error = ConsensusError>>,
+    /// The thread pool the validation is offloaded to, if enabled.
+    pool: Option<Arc<rayon::ThreadPool>>,
+    /// Receives the validation outcomes, in the order the blocks were submitted.
+    pending: VecDeque<mpsc::Receiver<Result<ExecutedBlock<N>, StageError>>>,
+}
+
+impl<N: NodePrimitives> PostExecutionValidation<N> {
+    /// Creates a new validation pipeline that offloads the validation to `pool`, if any.
+    fn new(
+        consensus: Arc<dyn FullConsensus<N, Error = ConsensusError>>,
+        pool: Option<Arc<rayon::ThreadPool>>,
+    ) -> Self {
+        Self { consensus, pool, pending: VecDeque::new() }
+    }
+
+    /// Submits the executed block for validation.
+    fn submit(
+        &mut self,
+        block: RecoveredBlock<N::Block>,
+        result: BlockExecutionResult<N::Receipt>,
+    ) {
+        let (tx, rx) = mpsc::sync_channel(1);
+        let consensus = self.consensus.clone();
+        let validate = move || {
+            let outcome = match consensus.validate_block_post_execution(&block, &result) {
+                Ok(()) => Ok((block, result)),
+                Err(err) => Err(StageError::Block {
+                    block: Box::new(block.block_with_parent()),
+                    error: BlockErrorKind::Validation(err),
+                }),
+            };
+            let _ = tx.send(outcome);
+        };
+
+        match &self.pool {
+            Some(pool) => pool.spawn(validate),
+            None => validate(),
+        }
+        self.pending.push_back(rx);
+    }
+
+    /// Returns the next submitted block if it's validated.
+    ///
+    /// Blocks until the next block is validated if `wait` is set, or if too many blocks are
+    /// pending. Returns `None` if there are no pending blocks.
+    fn next(&mut self, wait: bool) -> Result<Option<ExecutedBlock<N>>, StageError> {
+        let Some(rx) = self.pending.front() else { return Ok(None) };
+        let exited = || StageError::Fatal("post-execution validation task exited".into());
+
+        let outcome = if wait || self.pending.len() > POST_EXECUTION_VALIDATION_LOOKAHEAD {
+            rx.recv().map_err(|_| exited())?
+        } else {
+            match rx.try_recv() {
+                Ok(outcome) => outcome,
+                Err(mpsc::TryRecvError::Empty) => return Ok(None),
+                Err(mpsc::TryRecvError::Disconnected) => return Err(exited()),
+            }
+        };
+        self.pending.pop_front();
+
+        outcome.map(Some)
+    }
+
+    /// Waits for all pending blocks to be validated, returning the error of the first invalid
+    /// one.
+    fn wait_all(&mut self) -> Result<(), StageError> {
+        while self.next(true)?.is_some() {}
+        Ok(())
+    }
+}
+
 fn execution_checkpoint<N: NodePrimitives>(
     provider: &StaticFileProvider<N>,
     start_block: BlockNumber,
@@ -680,6 +932,7 @@ mod tests {
     use reth_prune::PruneModes;
     use reth_prune_types::{PruneMode, ReceiptsLogPruneConfig};
     use reth_stages_api::StageUnitCheckpoint;
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
     use std::collections::BTreeMap;
 
     fn stage() -> ExecutionStage<BasicBlockExecutorProvider<EthEvmConfig>> {
@@ -703,6 +956,78 @@ mod tests {
         )
     }
 
+    #[test]
+    fn state_root_interval_run_end_block() {
+        let stage = stage();
+        assert_eq!(stage.run_end_block(1, 250), 250);
+
+        let stage = stage.with_state_root_interval(Some(100));
+        assert_eq!(stage.run_end_block(1, 250), 100);
+        assert_eq!(stage.run_end_block(100, 250), 100);
+        assert_eq!(stage.run_end_block(101, 250), 200);
+        assert_eq!(stage.run_end_block(201, 250), 250);
+
+        // an interval of zero is ignored
+        let stage = stage.with_state_root_interval(Some(0));
+        assert_eq!(stage.run_end_block(1, 250), 250);
+    }
+
+    #[test]
+    fn sender_recovery_pipeline() {
+        let mut rng = generators::rng();
+        let blocks = random_block_range(
+            &mut rng,
+            0..=SENDER_RECOVERY_LOOKAHEAD as u64 * 2,
+            BlockRangeParams { tx_count: 1..3, ..Default::default() },
+        );
+
+        let mut pipeline = SenderRecoveryPipeline::spawn().unwrap();
+        let mut submitted = blocks.iter().enumerate();
+        for block in &blocks {
+            while !pipeline.is_full() {
+                let Some((i, block)) = submitted.next() else { break };
+                // Provide the senders of every other block, so only the rest needs recovery
+                let senders = if i % 2 == 0 { block.senders().unwrap() } else { Vec::new() };
+                pipeline.submit(block.clone().unseal(), senders).unwrap();
+            }
+
+            let recovered = pipeline.next().unwrap();
+            assert_eq!(recovered.hash(), block.hash());
+            assert_eq!(recovered.senders(), block.senders().unwrap());
+        }
+    }
+
+    #[test]
+    fn post_execution_validation_reports_first_invalid_block() {
+        let mut rng = generators::rng();
+        let blocks = random_block_range(&mut rng, 0..=3, BlockRangeParams::default());
+
+        let consensus = Arc::new(reth_consensus::test_utils::TestConsensus::default());
+        consensus.set_fail_validation(true);
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+        let mut validation =
+            PostExecutionValidation::<reth_ethereum_primitives::EthPrimitives>::new(
+                consensus,
+                Some(Arc::new(pool)),
+            );
+        for block in &blocks {
+            validation.submit(
+                RecoveredBlock::new_sealed(block.clone(), Vec::new()),
+                BlockExecutionResult {
+                    receipts: Vec::new(),
+                    requests: Default::default(),
+                    gas_used: 0,
+                },
+            );
+        }
+
+        // All blocks are invalid, the first one is reported
+        assert_matches!(
+            validation.wait_all(),
+            Err(StageError::Block { block, .. }) if block.block.number == 0
+        );
+    }
+
     #[test]
     fn execution_checkpoint_matches() {
         let factory = create_test_provider_factory();
@@ -912,7 +1237,8 @@ mod tests {
                 mode.receipts_log_filter = random_filter.clone();
             }
 
-            let mut execution_stage = stage();
+            // Also covers the offloaded post-execution validation
+            let mut execution_stage = stage().with_offloaded_receipts_verification(mode.is_some());
             provider.set_prune_modes(mode.clone().unwrap_or_default());
 
             let output = execution_stage.execute(&provider, input).unwrap();
@@ -1,5 +1,7 @@
 /// The bodies stage.
 mod bodies;
+/// The deposit contract index stage.
+mod deposit_index;
 /// The execution stage that generates state diff.
 mod execution;
 /// The finish stage
@@ -25,6 +27,7 @@ mod sender_recovery;
 mod tx_lookup;
 
 pub use bodies::*;
+pub use deposit_index::*;
 pub use execution::*;
 pub use finish::*;
 pub use hashing_account::*;
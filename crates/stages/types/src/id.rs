@@ -22,6 +22,11 @@ pub enum StageId {
     IndexAccountHistory,
     Prune,
     Finish,
+    /// Optional stage indexing the deposits of the beacon deposit contract.
+    ///
+    /// Not part of [`StageId::ALL`], since the stage is disabled by default and its checkpoint is
+    /// only advanced while the index is enabled.
+    DepositIndex,
     /// Other custom stage with a provided string identifier.
     Other(&'static str),
 }
@@ -77,6 +82,7 @@ impl StageId {
             Self::IndexStorageHistory => "IndexStorageHistory",
             Self::Prune => "Prune",
             Self::Finish => "Finish",
+            Self::DepositIndex => "DepositIndex",
             Self::Other(s) => s,
         }
     }
@@ -121,6 +127,7 @@ mod tests {
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
         assert_eq!(StageId::Finish.to_string(), "Finish");
+        assert_eq!(StageId::DepositIndex.to_string(), "DepositIndex");
 
         assert_eq!(StageId::Other("Foo").to_string(), "Foo");
     }
@@ -3,7 +3,7 @@
 use crate::{segments, segments::Segment, StaticFileProducerEvent};
 use alloy_primitives::BlockNumber;
 use parking_lot::Mutex;
-use rayon::prelude::*;
+use rayon::{prelude::*, ThreadPool, ThreadPoolBuilder};
 use reth_codecs::Compact;
 use reth_db_api::table::Value;
 use reth_primitives_traits::NodePrimitives;
@@ -23,6 +23,10 @@ use std::{
 };
 use tracing::{debug, trace};
 
+/// The number of segments produced by the [`StaticFileProducer`], which is the number of threads
+/// used to copy and finalize them concurrently.
+const PRODUCED_SEGMENTS: usize = 3;
+
 /// Result of [`StaticFileProducerInner::run`] execution.
 pub type StaticFileProducerResult = ProviderResult<StaticFileTargets>;
 
@@ -67,11 +71,18 @@ pub struct StaticFileProducerInner<Provider> {
     /// files. See [`StaticFileProducerInner::get_static_file_targets`].
     prune_modes: PruneModes,
     event_sender: EventSender<StaticFileProducerEvent>,
+    /// Thread pool to copy and finalize the segments on, with a thread per segment.
+    pool: ThreadPool,
 }
 
 impl<Provider> StaticFileProducerInner<Provider> {
     fn new(provider: Provider, prune_modes: PruneModes) -> Self {
-        Self { provider, prune_modes, event_sender: Default::default() }
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(PRODUCED_SEGMENTS)
+            .thread_name(|idx| format!("static-file-{idx}"))
+            .build()
+            .expect("failed to create static file producer thread pool");
+        Self { provider, prune_modes, event_sender: Default::default(), pool }
     }
 }
 
@@ -109,7 +120,8 @@ where
     /// For each [Some] target in [`StaticFileTargets`], initializes a corresponding [Segment] and
     /// runs it with the provided block range using [`reth_provider::providers::StaticFileProvider`]
     /// and a read-only database transaction from [`DatabaseProviderFactory`]. All segments are run
-    /// in parallel.
+    /// in parallel, and once all of them succeeded, they are committed and their indices are
+    /// updated in parallel as well.
     ///
     /// NOTE: it doesn't delete the data from database, and the actual deleting (aka pruning) logic
     /// lives in the `prune` crate.
@@ -141,27 +153,32 @@ where
             segments.push((Box::new(segments::Receipts), block_range));
         }
 
-        segments.par_iter().try_for_each(|(segment, block_range)| -> ProviderResult<()> {
-            debug!(target: "static_file", segment = %segment.segment(), ?block_range, "StaticFileProducer segment");
-            let start = Instant::now();
+        self.pool.install(|| {
+            segments.par_iter().try_for_each(|(segment, block_range)| -> ProviderResult<()> {
+                debug!(target: "static_file", segment = %segment.segment(), ?block_range, "StaticFileProducer segment");
+                let start = Instant::now();
 
-            // Create a new database transaction on every segment to prevent long-lived read-only
-            // transactions
-            let provider = self.provider.database_provider_ro()?.disable_long_read_transaction_safety();
-            segment.copy_to_static_files(provider,  block_range.clone())?;
+                // Create a new database transaction on every segment to prevent long-lived
+                // read-only transactions
+                let provider = self.provider.database_provider_ro()?.disable_long_read_transaction_safety();
+                segment.copy_to_static_files(provider,  block_range.clone())?;
 
-            let elapsed = start.elapsed(); // TODO(alexey): track in metrics
-            debug!(target: "static_file", segment = %segment.segment(), ?block_range, ?elapsed, "Finished StaticFileProducer segment");
+                let elapsed = start.elapsed(); // TODO(alexey): track in metrics
+                debug!(target: "static_file", segment = %segment.segment(), ?block_range, ?elapsed, "Finished StaticFileProducer segment");
 
-            Ok(())
+                Ok(())
+            })
         })?;
 
-        self.provider.static_file_provider().commit()?;
-        for (segment, block_range) in segments {
-            self.provider
-                .static_file_provider()
-                .update_index(segment.segment(), Some(*block_range.end()))?;
-        }
+        // Flush the segments concurrently, so that a segment which finalized its file in this run
+        // doesn't stall the others.
+        self.pool.install(|| {
+            segments.par_iter().try_for_each(|(segment, block_range)| {
+                let static_file_provider = self.provider.static_file_provider();
+                static_file_provider.latest_writer(segment.segment())?.commit()?;
+                static_file_provider.update_index(segment.segment(), Some(*block_range.end()))
+            })
+        })?;
 
         let elapsed = start.elapsed(); // TODO(alexey): track in metrics
         debug!(target: "static_file", ?targets, ?elapsed, "StaticFileProducer finished");
@@ -34,6 +34,36 @@ impl<H: Compact> Compact for StoredBlockOmmers<H> {
     }
 }
 
+/// The storage representation of the deposit contract's incremental merkle tree after a block.
+///
+/// Only the nodes below the highest set bit of the deposit count can be in use, so the branch is
+/// stored without the trailing ones.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct StoredDepositBranch {
+    /// The number of deposits made until the end of the block.
+    pub count: u64,
+    /// The left siblings of the path to the next leaf, from the bottom of the tree.
+    pub branch: Vec<B256>,
+}
+
+impl Compact for StoredDepositBranch {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_u64(self.count);
+        8 + self.branch.to_compact(buf)
+    }
+
+    fn from_compact(mut buf: &[u8], _len: usize) -> (Self, &[u8]) {
+        let count = bytes::Buf::get_u64(&mut buf);
+        let (branch, new_buf) = Vec::from_compact(buf, buf.len());
+        (Self { count, branch }, new_buf)
+    }
+}
+
 /// Hash of the block header.
 pub type HeaderHash = B256;
 
@@ -50,6 +80,12 @@ mod tests {
         assert_eq!(ommer.clone(), StoredBlockOmmers::decompress(&ommer.compress()).unwrap());
     }
 
+    #[test]
+    fn test_deposit_branch() {
+        let branch = StoredDepositBranch { count: 5, branch: vec![B256::repeat_byte(1); 3] };
+        assert_eq!(branch.clone(), StoredDepositBranch::decompress(&branch.compress()).unwrap());
+    }
+
     #[test]
     fn fuzz_stored_block_ommers() {
         fuzz_test_stored_block_ommers(StoredBlockOmmers::default())
@@ -224,6 +224,7 @@ impl_compression_for_compact!(
     StorageTrieEntry,
     StoredBlockBodyIndices,
     StoredBlockOmmers<H>,
+    StoredDepositBranch,
     StoredBlockWithdrawals,
     StaticFileBlockWithdrawals,
     Bytecode,
@@ -19,7 +19,7 @@ pub use raw::{RawDupSort, RawKey, RawTable, RawValue, TableRawRow};
 use crate::{
     models::{
         accounts::BlockNumberAddress,
-        blocks::{HeaderHash, StoredBlockOmmers},
+        blocks::{HeaderHash, StoredBlockOmmers, StoredDepositBranch},
         storage_sharded_key::StorageShardedKey,
         AccountBeforeTx, ClientVersion, CompactU256, IntegerList, ShardedKey,
         StoredBlockBodyIndices, StoredBlockWithdrawals,
@@ -525,6 +525,26 @@ tables! {
         type Key = ChainStateKey;
         type Value = BlockNumber;
     }
+
+    /// Stores the leaves of the deposit contract's merkle tree, the hash tree root of the deposit
+    /// data, by deposit index.
+    table DepositLeaves {
+        type Key = u64;
+        type Value = B256;
+    }
+
+    /// Stores the number of deposits made until the end of each block that contains deposits,
+    /// along with the branch of the deposit contract's merkle tree at that point.
+    table BlockDepositBranches {
+        type Key = BlockNumber;
+        type Value = StoredDepositBranch;
+    }
+
+    /// Stores the state of the started database schema migrations by schema version.
+    table SchemaMigrations {
+        type Key = u64;
+        type Value = Vec<u8>;
+    }
 }
 
 /// Keys for the `ChainState` table.
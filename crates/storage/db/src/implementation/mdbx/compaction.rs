@@ -0,0 +1,370 @@
+//! Compaction of the MDBX database.
+//!
+//! Deleted data doesn't shrink an MDBX database, the freed pages are put on the freelist and reused
+//! by later writes instead. Over time, the freelist can grow to a large share of the database,
+//! which wastes disk space and fragments the data.
+//!
+//! The [`CompactionScheduler`] monitors the freelist during a configured time window and requests
+//! compaction if it's too large. The database is compacted the next time it's opened with write
+//! access, i.e. on the next restart of the node, before any other transaction is started on it.
+//!
+//! There is no online compaction: a running node can't swap its open environment for a compacted
+//! copy, and writes made while an online copy is taken would be lost. Compacting offline means
+//! that the copy is exact and that no long-lived read transaction pins the freelist of a running
+//! node while the copy is made, but the node is down for as long as the copy takes. The copy
+//! reads and writes every used page, so it takes hours for a mainnet database.
+
+use super::{growth::DiskSpace, DatabaseArguments, DatabaseEnvKind};
+use crate::DatabaseEnv;
+use reth_fs_util as fs;
+use reth_tracing::tracing::{debug, error, info, warn};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The name of the MDBX data file in the database directory.
+const DATA_FILE_NAME: &str = "mdbx.dat";
+
+/// The directory in the database directory that the compacted copy is written to.
+const COMPACTING_DIR_NAME: &str = "compacting";
+
+/// The file in the database directory that marks that compaction was requested.
+const COMPACTION_REQUESTED_FILE_NAME: &str = "compaction-requested";
+
+/// Default interval between two checks of the freelist.
+pub const DEFAULT_COMPACTION_CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// The freelist statistics of the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FreelistStats {
+    /// The number of pages on the freelist.
+    pub free_pages: usize,
+    /// The number of pages allocated in the data file.
+    pub total_pages: usize,
+}
+
+impl FreelistStats {
+    /// Returns the share of free pages among all allocated pages, in percent.
+    pub fn free_pages_percent(&self) -> u8 {
+        if self.total_pages == 0 {
+            return 0
+        }
+        (self.free_pages.min(self.total_pages) * 100 / self.total_pages) as u8
+    }
+
+    /// Returns the number of pages that are in use, i.e. the number of pages of a compacted copy.
+    pub const fn used_pages(&self) -> usize {
+        self.total_pages.saturating_sub(self.free_pages)
+    }
+}
+
+impl DatabaseEnv {
+    /// Returns the freelist statistics of the database.
+    pub fn freelist_stats(&self) -> eyre::Result<FreelistStats> {
+        let free_pages = self.freelist()?;
+        // pgno is 0 based
+        let total_pages = self.info()?.last_pgno() + 1;
+        Ok(FreelistStats { free_pages, total_pages })
+    }
+}
+
+/// Requests compaction of the database located at `path`, which is done the next time the
+/// database is opened with write access.
+pub fn request_compaction(path: &Path) -> eyre::Result<()> {
+    fs::write(path.join(COMPACTION_REQUESTED_FILE_NAME), [])?;
+    Ok(())
+}
+
+/// Returns `true` if compaction of the database located at `path` was requested.
+pub fn compaction_requested(path: &Path) -> bool {
+    path.join(COMPACTION_REQUESTED_FILE_NAME).exists()
+}
+
+/// Compacts the database located at `path` if compaction was requested by
+/// [`request_compaction`].
+///
+/// The database must not be opened by any process while it's compacted. The compacted copy is
+/// made next to the data file and replaces it once it's complete. If there isn't enough free disk
+/// space for the copy, compaction is skipped and attempted again on the next open.
+///
+/// Returns `true` if the database was compacted.
+pub fn compact_if_requested(path: &Path, args: DatabaseArguments) -> eyre::Result<bool> {
+    // A copy that was interrupted, e.g. by a crash of the node, is incomplete
+    let compacting_dir = path.join(COMPACTING_DIR_NAME);
+    if compacting_dir.exists() {
+        fs::remove_dir_all(&compacting_dir)?;
+    }
+
+    if !compaction_requested(path) {
+        return Ok(false)
+    }
+
+    let env = DatabaseEnv::open(path, DatabaseEnvKind::RO, args)?;
+    let stats = env.freelist_stats()?;
+    let required_space = (stats.used_pages() * env.stat()?.page_size() as usize) as u64;
+    if let Some(disk) = DiskSpace::of(path).filter(|disk| disk.available < required_space) {
+        warn!(
+            target: "storage::db::mdbx",
+            ?stats,
+            required_space,
+            available_space = disk.available,
+            "Not enough free disk space to compact database"
+        );
+        return Ok(false)
+    }
+
+    info!(
+        target: "storage::db::mdbx",
+        ?stats,
+        required_space,
+        "Compacting database, the node starts once the compacted copy is made"
+    );
+    fs::create_dir_all(&compacting_dir)?;
+    let compacting_file = compacting_dir.join(DATA_FILE_NAME);
+    env.copy(&compacting_file, true)?;
+    drop(env);
+
+    fs::rename(&compacting_file, path.join(DATA_FILE_NAME))?;
+    fs::remove_dir_all(&compacting_dir)?;
+    fs::remove_file(path.join(COMPACTION_REQUESTED_FILE_NAME))?;
+    info!(target: "storage::db::mdbx", "Compacted database");
+
+    Ok(true)
+}
+
+/// A window of hours of the day, in UTC, e.g. `2-5` for 02:00 to 05:00.
+///
+/// The window wraps around midnight if the end hour is lower than the start hour, e.g. `22-4`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionWindow {
+    /// The hour the window starts at, inclusive.
+    pub start_hour: u8,
+    /// The hour the window ends at, exclusive.
+    pub end_hour: u8,
+}
+
+impl CompactionWindow {
+    /// Returns `true` if the given hour of the day is in the window.
+    pub const fn contains(&self, hour: u8) -> bool {
+        if self.start_hour <= self.end_hour {
+            self.start_hour <= hour && hour < self.end_hour
+        } else {
+            self.start_hour <= hour || hour < self.end_hour
+        }
+    }
+
+    /// Returns `true` if the current time is in the window.
+    pub fn is_open(&self) -> bool {
+        let secs = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        self.contains((secs / 3600 % 24) as u8)
+    }
+}
+
+impl Default for CompactionWindow {
+    fn default() -> Self {
+        Self { start_hour: 2, end_hour: 5 }
+    }
+}
+
+impl FromStr for CompactionWindow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| format!("Invalid window: {s}. Use '<start hour>-<end hour>'."))?;
+
+        let parse_hour = |hour: &str| {
+            hour.trim()
+                .parse::<u8>()
+                .ok()
+                .filter(|hour| *hour < 24)
+                .ok_or_else(|| format!("Invalid hour: {hour}. Use a number from 0 to 23."))
+        };
+
+        Ok(Self { start_hour: parse_hour(start)?, end_hour: parse_hour(end)? })
+    }
+}
+
+impl fmt::Display for CompactionWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start_hour, self.end_hour)
+    }
+}
+
+/// Configuration of the [`CompactionScheduler`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionConfig {
+    /// The minimum share of free pages, in percent, for the database to be compacted.
+    pub min_free_pages_percent: u8,
+    /// The hours of the day during which the database can be compacted.
+    pub window: CompactionWindow,
+    /// The interval between two checks of the freelist.
+    pub check_interval: Duration,
+}
+
+impl Default for CompactionConfig {
+    fn default() -> Self {
+        Self {
+            min_free_pages_percent: 30,
+            window: CompactionWindow::default(),
+            check_interval: DEFAULT_COMPACTION_CHECK_INTERVAL,
+        }
+    }
+}
+
+/// Periodically checks the freelist of the database during the configured window and requests
+/// compaction if the freelist is too large.
+///
+/// The database is compacted on the next restart of the node, see [`compact_if_requested`].
+pub struct CompactionScheduler {
+    /// The database to compact.
+    db: Arc<DatabaseEnv>,
+    /// The path of the database.
+    path: PathBuf,
+    /// The configuration of the scheduler.
+    config: CompactionConfig,
+}
+
+impl CompactionScheduler {
+    /// Creates a new [`CompactionScheduler`] for the database located at `path`.
+    pub const fn new(db: Arc<DatabaseEnv>, path: PathBuf, config: CompactionConfig) -> Self {
+        Self { db, path, config }
+    }
+
+    /// Spawns the scheduler on a dedicated thread.
+    pub fn spawn(self) -> thread::JoinHandle<()> {
+        thread::Builder::new()
+            .name("db-compaction".to_string())
+            .spawn(move || self.run())
+            .expect("failed to spawn db compaction thread")
+    }
+
+    /// Runs the scheduler.
+    pub fn run(self) {
+        loop {
+            thread::sleep(self.config.check_interval);
+
+            if let Err(err) = self.on_check() {
+                error!(target: "storage::db::mdbx", %err, "Failed to compact database");
+            }
+        }
+    }
+
+    /// Requests compaction if it's within the window, compaction wasn't requested yet and the
+    /// freelist exceeds the threshold.
+    fn on_check(&self) -> eyre::Result<()> {
+        if !self.config.window.is_open() {
+            return Ok(())
+        }
+
+        if compaction_requested(&self.path) {
+            debug!(target: "storage::db::mdbx", "Database compaction is waiting for restart");
+            return Ok(())
+        }
+
+        let stats = self.db.freelist_stats()?;
+        let free_pages_percent = stats.free_pages_percent();
+        debug!(target: "storage::db::mdbx", ?stats, %free_pages_percent, "Checked database freelist");
+        if free_pages_percent < self.config.min_free_pages_percent {
+            return Ok(())
+        }
+
+        request_compaction(&self.path)?;
+        info!(
+            target: "storage::db::mdbx",
+            ?stats,
+            %free_pages_percent,
+            "Requested database compaction, the database is compacted offline on the next restart"
+        );
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for CompactionScheduler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompactionScheduler")
+            .field("path", &self.path)
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mdbx::{DatabaseArguments, DatabaseEnvKind},
+        tables, Database,
+    };
+    use reth_db_api::{
+        models::ClientVersion,
+        transaction::{DbTx, DbTxMut},
+    };
+
+    #[test]
+    fn compaction_window() {
+        let window: CompactionWindow = "2-5".parse().unwrap();
+        assert_eq!(window, CompactionWindow { start_hour: 2, end_hour: 5 });
+        assert!(!window.contains(1));
+        assert!(window.contains(2));
+        assert!(window.contains(4));
+        assert!(!window.contains(5));
+
+        let window: CompactionWindow = "22-4".parse().unwrap();
+        assert!(window.contains(23));
+        assert!(window.contains(0));
+        assert!(!window.contains(4));
+        assert!(!window.contains(12));
+
+        assert!("2".parse::<CompactionWindow>().is_err());
+        assert!("2-24".parse::<CompactionWindow>().is_err());
+    }
+
+    #[test]
+    fn compact_on_open() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let args = DatabaseArguments::new(ClientVersion::default());
+
+        {
+            let db = DatabaseEnv::open(dir.path(), DatabaseEnvKind::RW, args.clone()).unwrap();
+            db.create_tables().unwrap();
+
+            let tx = db.tx_mut().unwrap();
+            for number in 0..4096 {
+                tx.put::<tables::CanonicalHeaders>(number, Default::default()).unwrap();
+            }
+            tx.commit().unwrap();
+            let tx = db.tx_mut().unwrap();
+            tx.clear::<tables::CanonicalHeaders>().unwrap();
+            tx.put::<tables::CanonicalHeaders>(0, Default::default()).unwrap();
+            tx.commit().unwrap();
+
+            let stats = db.freelist_stats().unwrap();
+            assert!(stats.free_pages > 0);
+
+            request_compaction(dir.path()).unwrap();
+            assert!(compaction_requested(dir.path()));
+
+            // Writes after the request are part of the compacted database
+            let tx = db.tx_mut().unwrap();
+            tx.put::<tables::CanonicalHeaders>(1, Default::default()).unwrap();
+            tx.commit().unwrap();
+        }
+
+        // The database is compacted when it's opened again
+        let db = DatabaseEnv::open(dir.path(), DatabaseEnvKind::RW, args).unwrap();
+        assert!(!compaction_requested(dir.path()));
+        assert!(!dir.path().join(COMPACTING_DIR_NAME).exists());
+        assert_eq!(db.freelist_stats().unwrap().free_pages, 0);
+
+        let tx = db.tx().unwrap();
+        assert_eq!(tx.entries::<tables::CanonicalHeaders>().unwrap(), 2);
+    }
+}
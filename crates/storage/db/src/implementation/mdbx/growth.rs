@@ -0,0 +1,299 @@
+//! Growth of the MDBX database tables and forecasting when the disk is full.
+//!
+//! Every time the table stats are read through [`DatabaseEnv::growth`](crate::DatabaseEnv::growth)
+//! or reported as metrics, the table sizes and the size of the static files are recorded. The
+//! growth rate of a table is computed over the recorded sizes of the last [`GROWTH_WINDOW`], and
+//! the growth rate of all tables and the static files is extrapolated to forecast when the disk
+//! the database is on runs out of space.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+use sysinfo::Disks;
+
+/// The window the growth rates are computed over.
+pub const GROWTH_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The maximum number of recorded size samples.
+const MAX_SAMPLES: usize = 1_024;
+
+/// The minimum time between two recorded size samples, so the samples cover the whole
+/// [`GROWTH_WINDOW`] no matter how often the sizes are read.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(GROWTH_WINDOW.as_secs() / MAX_SAMPLES as u64);
+
+/// Seconds per day.
+const SECONDS_PER_DAY: f64 = 24.0 * 60.0 * 60.0;
+
+/// The stats of a database table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableStats {
+    /// The name of the table.
+    pub name: &'static str,
+    /// The number of entries in the table.
+    pub entries: usize,
+    /// The size of a page in bytes.
+    pub page_size: usize,
+    /// The number of leaf pages.
+    pub leaf_pages: usize,
+    /// The number of branch pages.
+    pub branch_pages: usize,
+    /// The number of overflow pages.
+    pub overflow_pages: usize,
+}
+
+impl TableStats {
+    /// Returns the size of the table in bytes.
+    pub const fn size(&self) -> usize {
+        self.page_size * (self.leaf_pages + self.branch_pages + self.overflow_pages)
+    }
+}
+
+/// The stats and growth of a database table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TableGrowth {
+    /// The current stats of the table.
+    pub stats: TableStats,
+    /// The growth of the table in bytes per day, or [`None`] if the table size wasn't recorded
+    /// before.
+    pub bytes_per_day: Option<f64>,
+}
+
+/// The space of the disk the database is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskSpace {
+    /// The available space in bytes.
+    pub available: u64,
+    /// The total space in bytes.
+    pub total: u64,
+}
+
+impl DiskSpace {
+    /// Returns the space of the disk that the given path is on, i.e. the disk with the longest
+    /// mount point that the path starts with.
+    pub fn of(path: &Path) -> Option<Self> {
+        let path = path.canonicalize().ok()?;
+        let disks = Disks::new_with_refreshed_list();
+        disks
+            .list()
+            .iter()
+            .filter(|disk| path.starts_with(disk.mount_point()))
+            .max_by_key(|disk| disk.mount_point().as_os_str().len())
+            .map(|disk| Self { available: disk.available_space(), total: disk.total_space() })
+    }
+}
+
+/// The stats and growth of all database tables and the static files.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseGrowth {
+    /// The stats and growth of each table.
+    pub tables: Vec<TableGrowth>,
+    /// The growth of all tables in bytes per day, or [`None`] if the table sizes weren't recorded
+    /// before.
+    pub bytes_per_day: Option<f64>,
+    /// The size of the static files in bytes, if their directory is configured.
+    pub static_files_size: Option<u64>,
+    /// The growth of the static files in bytes per day, or [`None`] if their size wasn't recorded
+    /// before.
+    pub static_files_bytes_per_day: Option<f64>,
+    /// The space of the disk the database is on, if it's known.
+    pub disk: Option<DiskSpace>,
+}
+
+impl DatabaseGrowth {
+    /// Returns the size of all tables in bytes.
+    pub fn total_size(&self) -> usize {
+        self.tables.iter().map(|table| table.stats.size()).sum()
+    }
+
+    /// Returns the growth of all tables and the static files in bytes per day, or [`None`] if the
+    /// sizes weren't recorded before.
+    pub fn total_bytes_per_day(&self) -> Option<f64> {
+        Some(self.bytes_per_day? + self.static_files_bytes_per_day.unwrap_or_default())
+    }
+
+    /// Returns the number of days until the disk is full at the current growth rate of the tables
+    /// and the static files.
+    ///
+    /// Returns [`None`] if the disk space is unknown, or if the database doesn't grow.
+    pub fn days_until_full(&self) -> Option<f64> {
+        let bytes_per_day = self.total_bytes_per_day().filter(|rate| *rate > 0.0)?;
+        Some(self.disk?.available as f64 / bytes_per_day)
+    }
+}
+
+/// Returns the size of the files in the given directory and its subdirectories in bytes.
+///
+/// Files that can't be read are skipped.
+pub(crate) fn directory_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else { return 0 };
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            Some(if metadata.is_dir() { directory_size(&entry.path()) } else { metadata.len() })
+        })
+        .sum()
+}
+
+/// The sizes of the tables and the static files at a point in time.
+#[derive(Debug)]
+struct Sample {
+    /// When the sizes were recorded.
+    at: Instant,
+    /// The size of each table in bytes.
+    sizes: HashMap<&'static str, usize>,
+    /// The size of the static files in bytes, if their directory is configured.
+    static_files: Option<u64>,
+}
+
+/// Records the sizes of the tables to compute their growth.
+#[derive(Debug, Default)]
+pub(crate) struct GrowthTracker {
+    /// The recorded sizes, oldest first.
+    samples: VecDeque<Sample>,
+}
+
+impl GrowthTracker {
+    /// Records the sizes of the given tables and static files, and returns their growth since the
+    /// oldest sample in the [`GROWTH_WINDOW`].
+    ///
+    /// The sizes are only recorded if the last sample is at least [`SAMPLE_INTERVAL`] old.
+    pub(crate) fn record(
+        &mut self,
+        now: Instant,
+        tables: Vec<TableStats>,
+        static_files: Option<u64>,
+        disk: Option<DiskSpace>,
+    ) -> DatabaseGrowth {
+        while self.samples.front().is_some_and(|sample| now - sample.at > GROWTH_WINDOW) ||
+            self.samples.len() >= MAX_SAMPLES
+        {
+            self.samples.pop_front();
+        }
+
+        let oldest = self.samples.front().filter(|sample| now > sample.at);
+        let days = oldest.map(|sample| (now - sample.at).as_secs_f64() / SECONDS_PER_DAY);
+        let rate =
+            |size: usize, old_size: Option<usize>| Some((size as f64 - old_size? as f64) / days?);
+
+        let bytes_per_day = rate(
+            tables.iter().map(TableStats::size).sum(),
+            oldest.map(|sample| sample.sizes.values().sum()),
+        );
+        let static_files_bytes_per_day = static_files.and_then(|size| {
+            let old_size = oldest?.static_files?;
+            Some((size as f64 - old_size as f64) / days?)
+        });
+        let tables = tables
+            .into_iter()
+            .map(|stats| TableGrowth {
+                stats,
+                bytes_per_day: rate(
+                    stats.size(),
+                    oldest.and_then(|sample| sample.sizes.get(stats.name).copied()),
+                ),
+            })
+            .collect::<Vec<_>>();
+
+        if self.samples.back().is_none_or(|sample| now - sample.at >= SAMPLE_INTERVAL) {
+            self.samples.push_back(Sample {
+                at: now,
+                sizes: tables.iter().map(|table| (table.stats.name, table.stats.size())).collect(),
+                static_files,
+            });
+        }
+
+        DatabaseGrowth {
+            tables,
+            bytes_per_day,
+            static_files_size: static_files,
+            static_files_bytes_per_day,
+            disk,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(name: &'static str, leaf_pages: usize) -> TableStats {
+        TableStats {
+            name,
+            entries: leaf_pages,
+            page_size: 4096,
+            leaf_pages,
+            branch_pages: 0,
+            overflow_pages: 0,
+        }
+    }
+
+    #[test]
+    fn test_growth_forecast() {
+        let mut tracker = GrowthTracker::default();
+        let disk = Some(DiskSpace { available: 4096 * 100, total: 4096 * 1_000 });
+        let start = Instant::now();
+
+        let growth = tracker.record(start, vec![stats("A", 10), stats("B", 10)], None, disk);
+        assert_eq!(growth.total_size(), 4096 * 20);
+        assert_eq!(growth.bytes_per_day, None);
+        assert_eq!(growth.days_until_full(), None);
+
+        let half_day = Duration::from_secs(12 * 60 * 60);
+        let growth =
+            tracker.record(start + half_day, vec![stats("A", 15), stats("B", 10)], None, disk);
+        assert_eq!(growth.tables[0].bytes_per_day, Some(4096.0 * 10.0));
+        assert_eq!(growth.tables[1].bytes_per_day, Some(0.0));
+        assert_eq!(growth.bytes_per_day, Some(4096.0 * 10.0));
+        assert_eq!(growth.days_until_full(), Some(10.0));
+
+        // the first sample is outside of the window
+        let now = start + half_day + GROWTH_WINDOW;
+        let growth = tracker.record(now, vec![stats("A", 5), stats("B", 10)], None, disk);
+        assert_eq!(growth.bytes_per_day, Some(-4096.0 * 10.0));
+        assert_eq!(growth.days_until_full(), None);
+    }
+
+    #[test]
+    fn test_growth_forecast_includes_static_files() {
+        let mut tracker = GrowthTracker::default();
+        let disk = Some(DiskSpace { available: 4096 * 100, total: 4096 * 1_000 });
+        let start = Instant::now();
+
+        let growth = tracker.record(start, vec![stats("A", 10)], Some(4096), disk);
+        assert_eq!(growth.static_files_size, Some(4096));
+        assert_eq!(growth.static_files_bytes_per_day, None);
+
+        // only the static files grow
+        let half_day = Duration::from_secs(12 * 60 * 60);
+        let growth = tracker.record(start + half_day, vec![stats("A", 10)], Some(4096 * 6), disk);
+        assert_eq!(growth.bytes_per_day, Some(0.0));
+        assert_eq!(growth.static_files_bytes_per_day, Some(4096.0 * 10.0));
+        assert_eq!(growth.total_bytes_per_day(), Some(4096.0 * 10.0));
+        assert_eq!(growth.days_until_full(), Some(10.0));
+    }
+
+    #[test]
+    fn test_growth_window_with_frequent_samples() {
+        let mut tracker = GrowthTracker::default();
+        let start = Instant::now();
+        let step = Duration::from_secs(10);
+
+        // the table grows in the first hour only, and its size is read every 10 seconds for half
+        // a day
+        let steps = 12 * 60 * 6;
+        let mut growth = None;
+        for i in 0..=steps {
+            let pages = 10 + i.min(360);
+            growth =
+                Some(tracker.record(start + step * i as u32, vec![stats("A", pages)], None, None));
+        }
+
+        // the growth is still computed over the whole half day
+        assert!(tracker.samples.len() <= MAX_SAMPLES);
+        assert_eq!(growth.unwrap().bytes_per_day, Some(4096.0 * 360.0 * 2.0));
+    }
+}
@@ -24,15 +24,19 @@ use reth_storage_errors::db::LogLevel;
 use reth_tracing::tracing::error;
 use std::{
     ops::{Deref, Range},
-    path::Path,
-    sync::Arc,
-    time::{SystemTime, UNIX_EPOCH},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Instant, SystemTime, UNIX_EPOCH},
 };
 use tx::Tx;
 
+pub mod compaction;
 pub mod cursor;
+pub mod growth;
 pub mod tx;
 
+use growth::{DatabaseGrowth, DiskSpace, GrowthTracker, TableGrowth, TableStats};
+
 mod utils;
 
 /// 1 KB in bytes
@@ -99,6 +103,9 @@ pub struct DatabaseArguments {
     ///
     /// This flag affects only at environment opening but can't be changed after.
     exclusive: Option<bool>,
+    /// The directory of the static files of the node, which are included in the forecast of
+    /// when the disk is full.
+    static_files_path: Option<PathBuf>,
 }
 
 impl Default for DatabaseArguments {
@@ -121,6 +128,7 @@ impl DatabaseArguments {
             log_level: None,
             max_read_transaction_duration: None,
             exclusive: None,
+            static_files_path: None,
         }
     }
 
@@ -161,6 +169,13 @@ impl DatabaseArguments {
         self
     }
 
+    /// Set the directory of the static files, whose growth is included in the forecast of when
+    /// the disk is full.
+    pub fn with_static_files_path(mut self, static_files_path: Option<PathBuf>) -> Self {
+        self.static_files_path = static_files_path;
+        self
+    }
+
     /// Returns the client version if any.
     pub const fn client_version(&self) -> &ClientVersion {
         &self.client_version
@@ -176,6 +191,12 @@ pub struct DatabaseEnv {
     metrics: Option<Arc<DatabaseEnvMetrics>>,
     /// Write lock for when dealing with a read-write environment.
     _lock_file: Option<StorageLock>,
+    /// The path of the database directory.
+    path: PathBuf,
+    /// The directory of the static files, if their growth is tracked.
+    static_files_path: Option<PathBuf>,
+    /// Records the table sizes to compute their growth.
+    growth: Mutex<GrowthTracker>,
 }
 
 impl Database for DatabaseEnv {
@@ -209,54 +230,59 @@ impl DatabaseMetrics for DatabaseEnv {
     fn gauge_metrics(&self) -> Vec<(&'static str, f64, Vec<Label>)> {
         let mut metrics = Vec::new();
 
-        let _ = self
-            .view(|tx| {
-                for table in Tables::ALL.iter().map(Tables::name) {
-                    let table_db = tx.inner.open_db(Some(table)).wrap_err("Could not open db.")?;
-
-                    let stats = tx
-                        .inner
-                        .db_stat(&table_db)
-                        .wrap_err(format!("Could not find table: {table}"))?;
-
-                    let page_size = stats.page_size() as usize;
-                    let leaf_pages = stats.leaf_pages();
-                    let branch_pages = stats.branch_pages();
-                    let overflow_pages = stats.overflow_pages();
-                    let num_pages = leaf_pages + branch_pages + overflow_pages;
-                    let table_size = page_size * num_pages;
-                    let entries = stats.entries();
-
-                    metrics.push((
-                        "db.table_size",
-                        table_size as f64,
-                        vec![Label::new("table", table)],
-                    ));
-                    metrics.push((
-                        "db.table_pages",
-                        leaf_pages as f64,
-                        vec![Label::new("table", table), Label::new("type", "leaf")],
-                    ));
-                    metrics.push((
-                        "db.table_pages",
-                        branch_pages as f64,
-                        vec![Label::new("table", table), Label::new("type", "branch")],
-                    ));
-                    metrics.push((
-                        "db.table_pages",
-                        overflow_pages as f64,
-                        vec![Label::new("table", table), Label::new("type", "overflow")],
-                    ));
+        if let Ok(growth) =
+            self.growth().map_err(|error| error!(%error, "Failed to read db table stats"))
+        {
+            for TableGrowth { stats, bytes_per_day } in &growth.tables {
+                let table = stats.name;
+                metrics.push((
+                    "db.table_size",
+                    stats.size() as f64,
+                    vec![Label::new("table", table)],
+                ));
+                metrics.push((
+                    "db.table_pages",
+                    stats.leaf_pages as f64,
+                    vec![Label::new("table", table), Label::new("type", "leaf")],
+                ));
+                metrics.push((
+                    "db.table_pages",
+                    stats.branch_pages as f64,
+                    vec![Label::new("table", table), Label::new("type", "branch")],
+                ));
+                metrics.push((
+                    "db.table_pages",
+                    stats.overflow_pages as f64,
+                    vec![Label::new("table", table), Label::new("type", "overflow")],
+                ));
+                metrics.push((
+                    "db.table_entries",
+                    stats.entries as f64,
+                    vec![Label::new("table", table)],
+                ));
+                if let Some(bytes_per_day) = bytes_per_day {
                     metrics.push((
-                        "db.table_entries",
-                        entries as f64,
+                        "db.table_growth_rate",
+                        *bytes_per_day,
                         vec![Label::new("table", table)],
                     ));
                 }
+            }
 
-                Ok::<(), eyre::Report>(())
-            })
-            .map_err(|error| error!(%error, "Failed to read db table stats"));
+            if let Some(bytes_per_day) = growth.bytes_per_day {
+                metrics.push(("db.growth_rate", bytes_per_day, vec![]));
+            }
+            if let Some(bytes_per_day) = growth.static_files_bytes_per_day {
+                metrics.push(("db.static_files_growth_rate", bytes_per_day, vec![]));
+            }
+            if let Some(disk) = growth.disk {
+                metrics.push(("db.disk_available_space", disk.available as f64, vec![]));
+                metrics.push(("db.disk_total_space", disk.total as f64, vec![]));
+            }
+            if let Some(days) = growth.days_until_full() {
+                metrics.push(("db.days_until_disk_full", days, vec![]));
+            }
+        }
 
         if let Ok(freelist) =
             self.freelist().map_err(|error| error!(%error, "Failed to read db.freelist"))
@@ -282,15 +308,20 @@ impl DatabaseEnv {
     /// Opens the database at the specified path with the given `EnvKind`.
     ///
     /// It does not create the tables, for that call [`DatabaseEnv::create_tables`].
+    ///
+    /// If the database is opened with write access and compaction of it was requested by the
+    /// [`CompactionScheduler`](compaction::CompactionScheduler), it's compacted first.
     pub fn open(
         path: &Path,
         kind: DatabaseEnvKind,
         args: DatabaseArguments,
     ) -> Result<Self, DatabaseError> {
         let _lock_file = if kind.is_rw() {
-            StorageLock::try_acquire(path)
-                .map_err(|err| DatabaseError::Other(err.to_string()))?
-                .into()
+            let lock = StorageLock::try_acquire(path)
+                .map_err(|err| DatabaseError::Other(err.to_string()))?;
+            compaction::compact_if_requested(path, args.clone())
+                .map_err(|err| DatabaseError::Other(err.to_string()))?;
+            Some(lock)
         } else {
             None
         };
@@ -429,6 +460,9 @@ impl DatabaseEnv {
             inner: inner_env.open(path).map_err(|e| DatabaseError::Open(e.into()))?,
             metrics: None,
             _lock_file,
+            path: path.to_path_buf(),
+            static_files_path: args.static_files_path,
+            growth: Mutex::default(),
         };
 
         Ok(env)
@@ -440,6 +474,45 @@ impl DatabaseEnv {
         self
     }
 
+    /// Returns the stats of all tables defined in [`Tables`].
+    pub fn table_stats(&self) -> eyre::Result<Vec<TableStats>> {
+        self.view(|tx| {
+            Tables::ALL
+                .iter()
+                .map(|table| {
+                    let name = table.name();
+                    let table_db = tx.inner.open_db(Some(name)).wrap_err("Could not open db.")?;
+                    let stats = tx
+                        .inner
+                        .db_stat(&table_db)
+                        .wrap_err(format!("Could not find table: {name}"))?;
+
+                    Ok(TableStats {
+                        name,
+                        entries: stats.entries(),
+                        page_size: stats.page_size() as usize,
+                        leaf_pages: stats.leaf_pages(),
+                        branch_pages: stats.branch_pages(),
+                        overflow_pages: stats.overflow_pages(),
+                    })
+                })
+                .collect::<eyre::Result<Vec<_>>>()
+        })?
+    }
+
+    /// Returns the stats of all tables with their growth, the size and growth of the static
+    /// files if their directory is configured, and the space of the disk the database is on.
+    ///
+    /// Every call records the table sizes, the growth is computed over the sizes recorded within
+    /// the [`GROWTH_WINDOW`](growth::GROWTH_WINDOW).
+    pub fn growth(&self) -> eyre::Result<DatabaseGrowth> {
+        let tables = self.table_stats()?;
+        let static_files = self.static_files_path.as_deref().map(growth::directory_size);
+        let disk = DiskSpace::of(&self.path);
+        let mut tracker = self.growth.lock().unwrap_or_else(|err| err.into_inner());
+        Ok(tracker.record(Instant::now(), tables, static_files, disk))
+    }
+
     /// Creates all the tables defined in [`Tables`], if necessary.
     pub fn create_tables(&self) -> Result<(), DatabaseError> {
         self.create_tables_for::<Tables>()
@@ -1,16 +1,18 @@
 //! Helper functions for initializing and opening a database.
 
-use crate::{is_database_empty, TableSet, Tables};
+use crate::{is_database_empty, migration::migrations, TableSet, Tables};
 use eyre::Context;
 use std::path::Path;
 
 pub use crate::implementation::mdbx::*;
 pub use reth_libmdbx::*;
 
-/// Creates a new database at the specified path if it doesn't exist. Does NOT create tables. Check
-/// [`init_db`].
+/// Creates a new database at the specified path if it doesn't exist. Does NOT create tables or
+/// migrate databases of an older version. Check [`init_db`].
 pub fn create_db<P: AsRef<Path>>(path: P, args: DatabaseArguments) -> eyre::Result<DatabaseEnv> {
-    use crate::version::{check_db_version_file, create_db_version_file, DatabaseVersionError};
+    use crate::version::{
+        create_db_version_file, get_db_version, DatabaseVersionError, DB_VERSION,
+    };
 
     let rpath = path.as_ref();
     if is_database_empty(rpath) {
@@ -18,7 +20,13 @@ pub fn create_db<P: AsRef<Path>>(path: P, args: DatabaseArguments) -> eyre::Resu
             .wrap_err_with(|| format!("Could not create database directory {}", rpath.display()))?;
         create_db_version_file(rpath)?;
     } else {
-        match check_db_version_file(rpath) {
+        match get_db_version(rpath) {
+            Ok(version) if version > DB_VERSION => {
+                return Err(
+                    DatabaseVersionError::UnknownVersion { version, latest: DB_VERSION }.into()
+                )
+            }
+            // Older versions are upgraded by the migrations in `init_db`
             Ok(_) => (),
             Err(DatabaseVersionError::MissingFile) => create_db_version_file(rpath)?,
             Err(err) => return Err(err.into()),
@@ -35,14 +43,15 @@ pub fn init_db<P: AsRef<Path>>(path: P, args: DatabaseArguments) -> eyre::Result
 }
 
 /// Opens up an existing database or creates a new one at the specified path. Creates tables defined
-/// in the given [`TableSet`] if necessary. Read/Write mode.
+/// in the given [`TableSet`] if necessary and runs the pending [`migrations`]. Read/Write mode.
 pub fn init_db_for<P: AsRef<Path>, TS: TableSet>(
     path: P,
     args: DatabaseArguments,
 ) -> eyre::Result<DatabaseEnv> {
     let client_version = args.client_version().clone();
-    let db = create_db(path, args)?;
+    let db = create_db(&path, args)?;
     db.create_tables_for::<TS>()?;
+    migrations().run(&db, path.as_ref())?;
     db.record_client_version(client_version)?;
     Ok(db)
 }
@@ -0,0 +1,327 @@
+//! Versioned database schema migrations.
+//!
+//! Changes of the table layout or of the encoding of a table are shipped as a [`Migration`] to the
+//! next [`DB_VERSION`], instead of requiring users to drop the affected stages and resync. On
+//! startup, the registered migrations upgrade the database from the version recorded in the
+//! database version file, see [`migrations`].
+//!
+//! Migrations run in batches. Every batch is committed together with the checkpoint to resume
+//! from, which is stored in the [`SchemaMigrations`] table, so an interrupted migration continues
+//! where it left off on the next startup. Once a migration finished, its version is written to the
+//! database version file.
+
+use crate::version::{get_db_version, write_db_version_file, DatabaseVersionError, DB_VERSION};
+use reth_db_api::{
+    cursor::DbCursorRO,
+    database::Database,
+    tables::SchemaMigrations,
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
+use reth_tracing::tracing::info;
+use std::{fmt::Debug, io, path::Path};
+
+/// Error when running the database [`Migrations`].
+#[derive(thiserror::Error, Debug)]
+pub enum MigrationError {
+    /// Database error.
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+    /// Error reading the version of the database.
+    #[error(transparent)]
+    Version(#[from] DatabaseVersionError),
+    /// IO error occurred while writing the database version file.
+    #[error("IO error occurred while writing the database version file: {0}")]
+    IOWrite(#[from] io::Error),
+}
+
+/// A versioned step migrating the database schema.
+pub trait Migration<TX>: Debug + Send + Sync {
+    /// The database version once the migration finished.
+    ///
+    /// The migration upgrades databases of the previous version.
+    fn version(&self) -> u64;
+
+    /// A short description of the migration, used for logging.
+    fn description(&self) -> &'static str;
+
+    /// Migrates the next batch, starting at the given checkpoint, or at the beginning if it's
+    /// `None`.
+    ///
+    /// Returns the checkpoint to resume from, or `None` if the migration finished. The batch is
+    /// committed together with the returned checkpoint.
+    fn migrate_batch(
+        &self,
+        tx: &TX,
+        checkpoint: Option<Vec<u8>>,
+    ) -> Result<Option<Vec<u8>>, DatabaseError>;
+}
+
+/// The state of a started migration, as stored in the [`SchemaMigrations`] table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum MigrationState {
+    /// The migration is in progress and resumes from the checkpoint.
+    InProgress(Vec<u8>),
+    /// The migration finished.
+    Finished,
+}
+
+impl MigrationState {
+    const IN_PROGRESS: u8 = 0;
+    const FINISHED: u8 = 1;
+
+    fn encode(self) -> Vec<u8> {
+        match self {
+            Self::InProgress(mut checkpoint) => {
+                checkpoint.insert(0, Self::IN_PROGRESS);
+                checkpoint
+            }
+            Self::Finished => vec![Self::FINISHED],
+        }
+    }
+
+    fn decode(value: &[u8]) -> Result<Self, DatabaseError> {
+        match value.split_first() {
+            Some((&Self::IN_PROGRESS, checkpoint)) => Ok(Self::InProgress(checkpoint.to_vec())),
+            Some((&Self::FINISHED, [])) => Ok(Self::Finished),
+            _ => Err(DatabaseError::Decode),
+        }
+    }
+}
+
+/// An ordered set of [`Migration`]s.
+#[derive(Debug)]
+pub struct Migrations<TX> {
+    migrations: Vec<Box<dyn Migration<TX>>>,
+}
+
+impl<TX> Default for Migrations<TX> {
+    fn default() -> Self {
+        Self { migrations: Vec::new() }
+    }
+}
+
+impl<TX: DbTxMut + DbTx> Migrations<TX> {
+    /// Creates an empty set of migrations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a migration.
+    ///
+    /// # Panics
+    ///
+    /// If the version of the migration is zero or doesn't follow the version of the previously
+    /// added migration.
+    pub fn with_migration(mut self, migration: impl Migration<TX> + 'static) -> Self {
+        let version = migration.version();
+        match self.migrations.last().map(|migration| migration.version()) {
+            Some(previous) => assert!(
+                version == previous + 1,
+                "migration version {version} must follow {previous}"
+            ),
+            None => assert!(version > 0, "migration version must be greater than 0"),
+        }
+        self.migrations.push(Box::new(migration));
+        self
+    }
+
+    /// Returns the latest database version known to the migrations, at least [`DB_VERSION`].
+    pub fn latest_version(&self) -> u64 {
+        self.migrations.last().map_or(DB_VERSION, |migration| migration.version().max(DB_VERSION))
+    }
+
+    /// Upgrades the database at the given path from the version recorded in its database version
+    /// file, running the missing migrations in order and resuming an interrupted one from its last
+    /// committed checkpoint.
+    ///
+    /// Returns the version of the database.
+    ///
+    /// Fails if the database was written by a newer client, or if it's older than [`DB_VERSION`]
+    /// and there are no migrations to upgrade it.
+    pub fn run<DB: Database<TXMut = TX>>(
+        &self,
+        db: &DB,
+        db_path: &Path,
+    ) -> Result<u64, MigrationError> {
+        let latest = self.latest_version();
+        let mut version = get_db_version(db_path)?;
+        if version > latest {
+            return Err(DatabaseVersionError::UnknownVersion { version, latest }.into())
+        }
+
+        // Migrations started or finished by a newer client
+        if let Some((started, _)) = db.tx()?.cursor_read::<SchemaMigrations>()?.last()? {
+            if started > latest {
+                return Err(DatabaseVersionError::UnknownVersion { version: started, latest }.into())
+            }
+        }
+
+        let from = version;
+        for migration in self.migrations.iter().filter(|migration| migration.version() > from) {
+            if migration.version() != version + 1 {
+                return Err(DatabaseVersionError::VersionMismatch { version }.into())
+            }
+            version = migration.version();
+            Self::run_migration(db, migration.as_ref())?;
+
+            write_db_version_file(db_path, version)?;
+            let tx = db.tx_mut()?;
+            tx.delete::<SchemaMigrations>(version, None)?;
+            tx.commit()?;
+        }
+
+        if version < DB_VERSION {
+            return Err(DatabaseVersionError::VersionMismatch { version }.into())
+        }
+
+        Ok(version)
+    }
+
+    /// Runs the batches of the migration until it's finished.
+    fn run_migration<DB: Database<TXMut = TX>>(
+        db: &DB,
+        migration: &dyn Migration<TX>,
+    ) -> Result<(), DatabaseError> {
+        let version = migration.version();
+        loop {
+            let tx = db.tx_mut()?;
+            let checkpoint = match tx.get::<SchemaMigrations>(version)? {
+                Some(value) => match MigrationState::decode(&value)? {
+                    MigrationState::InProgress(checkpoint) => Some(checkpoint),
+                    MigrationState::Finished => return Ok(()),
+                },
+                None => {
+                    info!(
+                        target: "reth::db",
+                        version,
+                        description = migration.description(),
+                        "Running database migration"
+                    );
+                    None
+                }
+            };
+
+            let state = match migration.migrate_batch(&tx, checkpoint)? {
+                Some(checkpoint) => MigrationState::InProgress(checkpoint),
+                None => {
+                    info!(target: "reth::db", version, "Finished database migration");
+                    MigrationState::Finished
+                }
+            };
+            tx.put::<SchemaMigrations>(version, state.encode())?;
+            tx.commit()?;
+        }
+    }
+}
+
+/// Returns the migrations of the reth database schema.
+pub fn migrations<TX: DbTxMut + DbTx>() -> Migrations<TX> {
+    // Register new migrations here, in order of their versions, and bump `DB_VERSION` to the
+    // version of the last one.
+    Migrations::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::create_test_rw_db, version::get_db_version};
+    use alloy_primitives::B256;
+    use assert_matches::assert_matches;
+    use reth_db_api::tables::CanonicalHeaders;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Writes a canonical header hash per batch, failing after the given number of batches.
+    #[derive(Debug)]
+    struct FillCanonicalHeaders {
+        blocks: u64,
+        batches_left: AtomicUsize,
+    }
+
+    impl<TX: DbTxMut + DbTx> Migration<TX> for FillCanonicalHeaders {
+        fn version(&self) -> u64 {
+            DB_VERSION + 1
+        }
+
+        fn description(&self) -> &'static str {
+            "fill canonical headers"
+        }
+
+        fn migrate_batch(
+            &self,
+            tx: &TX,
+            checkpoint: Option<Vec<u8>>,
+        ) -> Result<Option<Vec<u8>>, DatabaseError> {
+            if self.batches_left.fetch_sub(1, Ordering::Relaxed) == 0 {
+                return Err(DatabaseError::Other("interrupted".to_string()))
+            }
+
+            let block = checkpoint
+                .map(|checkpoint| u64::from_be_bytes(checkpoint.try_into().unwrap()))
+                .unwrap_or_default();
+            tx.put::<CanonicalHeaders>(block, B256::with_last_byte(block as u8))?;
+
+            let next = block + 1;
+            Ok((next < self.blocks).then(|| next.to_be_bytes().to_vec()))
+        }
+    }
+
+    #[test]
+    fn resumes_interrupted_migration() {
+        let db = create_test_rw_db();
+        let migrations = |batches| {
+            Migrations::new().with_migration(FillCanonicalHeaders {
+                blocks: 4,
+                batches_left: AtomicUsize::new(batches),
+            })
+        };
+
+        assert!(migrations(2).run(db.db(), db.path()).is_err());
+        assert_eq!(db.tx().unwrap().entries::<CanonicalHeaders>().unwrap(), 2);
+        assert_eq!(get_db_version(db.path()).unwrap(), DB_VERSION);
+
+        // Only the remaining batches run
+        assert_eq!(migrations(2).run(db.db(), db.path()).unwrap(), DB_VERSION + 1);
+        assert_eq!(db.tx().unwrap().entries::<CanonicalHeaders>().unwrap(), 4);
+        assert_eq!(get_db_version(db.path()).unwrap(), DB_VERSION + 1);
+        assert_eq!(db.tx().unwrap().entries::<SchemaMigrations>().unwrap(), 0);
+
+        // Finished migrations don't run again
+        assert_eq!(migrations(0).run(db.db(), db.path()).unwrap(), DB_VERSION + 1);
+    }
+
+    #[test]
+    fn rejects_newer_database() {
+        let db = create_test_rw_db();
+        let migrations = Migrations::new()
+            .with_migration(FillCanonicalHeaders { blocks: 1, batches_left: AtomicUsize::new(0) });
+        let latest = DB_VERSION + 1;
+
+        // Migration finished by a newer client, but its version file wasn't written yet
+        let tx = db.tx_mut().unwrap();
+        tx.put::<SchemaMigrations>(latest + 1, MigrationState::Finished.encode()).unwrap();
+        tx.commit().unwrap();
+        assert_matches!(
+            migrations.run(db.db(), db.path()),
+            Err(MigrationError::Version(DatabaseVersionError::UnknownVersion { version, .. }))
+                if version == latest + 1
+        );
+
+        write_db_version_file(db.path(), latest + 2).unwrap();
+        assert_matches!(
+            migrations.run(db.db(), db.path()),
+            Err(MigrationError::Version(DatabaseVersionError::UnknownVersion { version, .. }))
+                if version == latest + 2
+        );
+        assert_eq!(db.tx().unwrap().entries::<CanonicalHeaders>().unwrap(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "must follow")]
+    fn rejects_unordered_migrations() {
+        let migration = || FillCanonicalHeaders { blocks: 1, batches_left: AtomicUsize::new(0) };
+        let _ = Migrations::<<crate::DatabaseEnv as Database>::TXMut>::new()
+            .with_migration(migration())
+            .with_migration(migration());
+    }
+}
@@ -31,6 +31,19 @@ pub enum DatabaseVersionError {
         /// The detected version in the database.
         version: u64,
     },
+    /// The database was written by a newer client.
+    ///
+    /// Your database version is newer than the latest database version known by this client.
+    #[error(
+        "unknown database version detected: your database version (v{version}) \
+         is newer than the latest database version (v{latest}) known by this client"
+    )]
+    UnknownVersion {
+        /// The detected version in the database.
+        version: u64,
+        /// The latest version known by this client.
+        latest: u64,
+    },
     /// IO error occurred while reading the database version file.
     #[error("IO error occurred while reading {path}: {err}")]
     IORead {
@@ -47,6 +60,9 @@ pub enum DatabaseVersionError {
 /// Otherwise, returns different [`DatabaseVersionError`] error variants.
 pub fn check_db_version_file<P: AsRef<Path>>(db_path: P) -> Result<(), DatabaseVersionError> {
     let version = get_db_version(db_path)?;
+    if version > DB_VERSION {
+        return Err(DatabaseVersionError::UnknownVersion { version, latest: DB_VERSION })
+    }
     if version != DB_VERSION {
         return Err(DatabaseVersionError::VersionMismatch { version })
     }
@@ -75,7 +91,14 @@ pub fn get_db_version<P: AsRef<Path>>(db_path: P) -> Result<u64, DatabaseVersion
 /// This function will create a file if it does not exist,
 /// and will entirely replace its contents if it does.
 pub fn create_db_version_file<P: AsRef<Path>>(db_path: P) -> io::Result<()> {
-    fs::write(db_version_file_path(db_path), DB_VERSION.to_string())
+    write_db_version_file(db_path, DB_VERSION)
+}
+
+/// Writes the given version to the database version file with [`DB_VERSION_FILE_NAME`] name.
+///
+/// Used by the [migrations](crate::migration) to record the version of the migrated database.
+pub fn write_db_version_file<P: AsRef<Path>>(db_path: P, version: u64) -> io::Result<()> {
+    fs::write(db_version_file_path(db_path), version.to_string())
 }
 
 /// Returns a database version file path.
@@ -85,7 +108,7 @@ pub fn db_version_file_path<P: AsRef<Path>>(db_path: P) -> PathBuf {
 
 #[cfg(test)]
 mod tests {
-    use super::{check_db_version_file, db_version_file_path, DatabaseVersionError};
+    use super::{check_db_version_file, db_version_file_path, DatabaseVersionError, DB_VERSION};
     use assert_matches::assert_matches;
     use std::fs;
     use tempfile::tempdir;
@@ -115,4 +138,17 @@ mod tests {
         let result = check_db_version_file(&dir);
         assert_matches!(result, Err(DatabaseVersionError::VersionMismatch { version: 0 }));
     }
+
+    #[test]
+    fn unknown_version() {
+        let dir = tempdir().unwrap();
+        fs::write(db_version_file_path(&dir), (DB_VERSION + 1).to_string()).unwrap();
+
+        let result = check_db_version_file(&dir);
+        assert_matches!(
+            result,
+            Err(DatabaseVersionError::UnknownVersion { version, latest: DB_VERSION })
+                if version == DB_VERSION + 1
+        );
+    }
 }
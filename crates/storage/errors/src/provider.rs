@@ -4,6 +4,7 @@ use crate::{
 use alloc::{boxed::Box, string::String};
 use alloy_eips::{BlockHashOrNumber, HashOrNumber};
 use alloy_primitives::{Address, BlockHash, BlockNumber, TxNumber, B256};
+use core::ops::RangeInclusive;
 use derive_more::Display;
 use reth_primitives_traits::{transaction::signed::RecoveryError, GotExpected};
 use reth_prune_types::PruneSegmentError;
@@ -103,6 +104,25 @@ pub enum ProviderError {
     /// State is not available for the given block number because it is pruned.
     #[error("state at block #{_0} is pruned")]
     StateAtBlockPruned(BlockNumber),
+    /// A deposit event of the deposit contract could not be decoded.
+    #[error("invalid deposit event in block #{_0}")]
+    InvalidDepositEvent(BlockNumber),
+    /// The index of a deposit event doesn't follow the previous deposit.
+    #[error("unexpected deposit index in block #{block_number}: {index}")]
+    DepositIndexMismatch {
+        /// The block containing the deposit.
+        block_number: BlockNumber,
+        /// The index of the deposit.
+        index: GotExpected<u64>,
+    },
+    /// The transaction hashes of the block range can't be indexed.
+    #[error("can't index the transaction hashes of blocks {range:?}: {reason}")]
+    InvalidTransactionLookupRange {
+        /// The block range to index.
+        range: RangeInclusive<BlockNumber>,
+        /// Why the range can't be indexed.
+        reason: &'static str,
+    },
     /// Provider does not support this particular request.
     #[error("this provider does not support this request")]
     UnsupportedProvider,
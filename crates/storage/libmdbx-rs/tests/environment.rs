@@ -167,3 +167,37 @@ fn test_freelist() {
     freelist = env.freelist().unwrap();
     assert!(freelist > 0);
 }
+
+#[test]
+fn test_copy_compact() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    // Write a few values and delete most of them, so the freelist is not empty.
+    let tx = env.begin_rw_txn().expect("begin_rw_txn");
+    let db = tx.open_db(None).unwrap();
+    for i in 0..1024u64 {
+        tx.put(db.dbi(), i.to_be_bytes(), [0u8; 128], WriteFlags::default()).expect("tx.put");
+    }
+    tx.commit().expect("tx.commit");
+    let tx = env.begin_rw_txn().expect("begin_rw_txn");
+    let db = tx.open_db(None).unwrap();
+    for i in 1..1024u64 {
+        tx.del(db.dbi(), i.to_be_bytes(), None).expect("tx.del");
+    }
+    tx.commit().expect("tx.commit");
+    assert!(env.freelist().unwrap() > 0);
+
+    let copy_dir = tempdir().unwrap();
+    env.copy(copy_dir.path().join("mdbx.dat"), true).unwrap();
+
+    // The copy doesn't contain the free pages, but contains the data.
+    let copy = Environment::builder().open(copy_dir.path()).unwrap();
+    assert_eq!(copy.freelist().unwrap(), 0);
+    assert!(copy.info().unwrap().last_pgno() < env.info().unwrap().last_pgno());
+
+    let tx = copy.begin_ro_txn().expect("begin_ro_txn");
+    let db = tx.open_db(None).unwrap();
+    assert_eq!(tx.get::<Vec<u8>>(db.dbi(), &0u64.to_be_bytes()).unwrap(), Some(vec![0u8; 128]));
+    assert_eq!(tx.get::<Vec<u8>>(db.dbi(), &1u64.to_be_bytes()).unwrap(), None);
+}
@@ -1,20 +1,24 @@
 #![allow(unused)]
 use crate::{
     providers::{ConsistentProvider, ProviderNodeTypes, StaticFileProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BlockSource, CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions,
-    ChainSpecProvider, ChainStateBlockReader, ChangeSetReader, DatabaseProvider,
-    DatabaseProviderFactory, FullProvider, HashedPostStateProvider, HeaderProvider, ProviderError,
+    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockProcessingHistoryReader,
+    BlockProcessingStats, BlockReader, BlockReaderIdExt, BlockSource, BlockTagFallbacks,
+    CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions, ChainSpecProvider,
+    ChainStateBlockReader, ChangeSetReader, DatabaseProvider, DatabaseProviderFactory,
+    DepositIndexReader, FullProvider, HashedPostStateProvider, HeaderProvider, ProviderError,
     ProviderFactory, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
     StageCheckpointReader, StateProviderBox, StateProviderFactory, StateReader,
-    StaticFileProviderFactory, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    StaticFileProviderFactory, TransactionLookupIndexReader, TransactionLookupIndexWriter,
+    TransactionLookupStatus, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use alloy_consensus::{transaction::TransactionMeta, Header};
 use alloy_eips::{
     eip4895::{Withdrawal, Withdrawals},
     BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag,
 };
-use alloy_primitives::{Address, BlockHash, BlockNumber, Sealable, TxHash, TxNumber, B256, U256};
+use alloy_primitives::{
+    Address, BlockHash, BlockNumber, Log, Sealable, TxHash, TxNumber, B256, U256,
+};
 use alloy_rpc_types_engine::ForkchoiceState;
 use reth_chain_state::{
     BlockState, CanonicalInMemoryState, ForkChoiceNotifications, ForkChoiceSubscriptions,
@@ -37,7 +41,7 @@ use reth_primitives_traits::BlockBody;
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DBProvider, NodePrimitivesProvider, OmmersProvider,
+    BlockBodyIndicesProvider, DBProvider, NodePrimitivesProvider, OmmersProvider, ReceiptLog,
     StateCommitmentProvider, StorageChangeSetReader,
 };
 use reth_storage_errors::provider::ProviderResult;
@@ -63,6 +67,8 @@ pub struct BlockchainProvider<N: NodeTypesWithDB> {
     /// Tracks the chain info wrt forkchoice updates and in memory canonical
     /// state.
     pub(crate) canonical_in_memory_state: CanonicalInMemoryState<N::Primitives>,
+    /// Fallbacks for the `safe` and `finalized` block tags.
+    pub(crate) block_tag_fallbacks: BlockTagFallbacks,
 }
 
 impl<N: NodeTypesWithDB> Clone for BlockchainProvider<N> {
@@ -70,6 +76,7 @@ impl<N: NodeTypesWithDB> Clone for BlockchainProvider<N> {
         Self {
             database: self.database.clone(),
             canonical_in_memory_state: self.canonical_in_memory_state.clone(),
+            block_tag_fallbacks: self.block_tag_fallbacks,
         }
     }
 }
@@ -121,6 +128,7 @@ impl<N: ProviderNodeTypes> BlockchainProvider<N> {
                 finalized_header,
                 safe_header,
             ),
+            block_tag_fallbacks: BlockTagFallbacks::default(),
         })
     }
 
@@ -135,6 +143,17 @@ impl<N: ProviderNodeTypes> BlockchainProvider<N> {
     #[track_caller]
     pub fn consistent_provider(&self) -> ProviderResult<ConsistentProvider<N>> {
         ConsistentProvider::new(self.database.clone(), self.canonical_in_memory_state())
+            .map(|provider| provider.with_block_tag_fallbacks(self.block_tag_fallbacks))
+    }
+
+    /// Sets the fallbacks for the `safe` and `finalized` block tags, which are used as long as the
+    /// consensus layer hasn't set these blocks.
+    pub const fn with_block_tag_fallbacks(
+        mut self,
+        block_tag_fallbacks: BlockTagFallbacks,
+    ) -> Self {
+        self.block_tag_fallbacks = block_tag_fallbacks;
+        self
     }
 
     /// This uses a given [`BlockState`] to initialize a state provider for that block.
@@ -262,6 +281,10 @@ impl<N: ProviderNodeTypes> BlockNumReader for BlockchainProvider<N> {
         self.database.last_block_number()
     }
 
+    fn earliest_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.database.earliest_block_number()
+    }
+
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
         self.consistent_provider()?.block_number(hash)
     }
@@ -279,6 +302,20 @@ impl<N: ProviderNodeTypes> BlockIdReader for BlockchainProvider<N> {
     fn finalized_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
         Ok(self.canonical_in_memory_state.get_finalized_num_hash())
     }
+
+    fn safe_tag_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
+        match self.canonical_in_memory_state.get_safe_num_hash() {
+            Some(num_hash) => Ok(Some(num_hash)),
+            None => self.block_tag_fallbacks.safe_num_hash(self),
+        }
+    }
+
+    fn finalized_tag_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
+        match self.canonical_in_memory_state.get_finalized_num_hash() {
+            Some(num_hash) => Ok(Some(num_hash)),
+            None => self.block_tag_fallbacks.finalized_num_hash(self),
+        }
+    }
 }
 
 impl<N: ProviderNodeTypes> BlockReader for BlockchainProvider<N> {
@@ -441,6 +478,14 @@ impl<N: ProviderNodeTypes> ReceiptProvider for BlockchainProvider<N> {
     ) -> ProviderResult<Vec<Self::Receipt>> {
         self.consistent_provider()?.receipts_by_tx_range(range)
     }
+
+    fn logs_by_block(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        filter: impl Fn(BlockNumber, &Log) -> bool,
+    ) -> ProviderResult<Vec<ReceiptLog>> {
+        self.consistent_provider()?.logs_by_block(range, filter)
+    }
 }
 
 impl<N: ProviderNodeTypes> ReceiptProviderIdExt for BlockchainProvider<N> {
@@ -604,14 +649,14 @@ impl<N: ProviderNodeTypes> StateProviderFactory for BlockchainProvider<N> {
             BlockNumberOrTag::Latest => self.latest(),
             BlockNumberOrTag::Finalized => {
                 // we can only get the finalized state by hash, not by num
-                let hash =
-                    self.finalized_block_hash()?.ok_or(ProviderError::FinalizedBlockNotFound)?;
-                self.state_by_block_hash(hash)
+                let num_hash =
+                    self.finalized_tag_num_hash()?.ok_or(ProviderError::FinalizedBlockNotFound)?;
+                self.state_by_block_hash(num_hash.hash)
             }
             BlockNumberOrTag::Safe => {
                 // we can only get the safe state by hash, not by num
-                let hash = self.safe_block_hash()?.ok_or(ProviderError::SafeBlockNotFound)?;
-                self.state_by_block_hash(hash)
+                let num_hash = self.safe_tag_num_hash()?.ok_or(ProviderError::SafeBlockNotFound)?;
+                self.state_by_block_hash(num_hash.hash)
             }
             BlockNumberOrTag::Earliest => self.history_by_block_number(0),
             BlockNumberOrTag::Pending => self.pending(),
@@ -742,6 +787,37 @@ impl<N: ProviderNodeTypes> ChangeSetReader for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> DepositIndexReader for BlockchainProvider<N> {
+    fn deposit_count(&self, block_number: BlockNumber) -> ProviderResult<Option<u64>> {
+        self.database.deposit_count(block_number)
+    }
+
+    fn deposit_root(&self, block_number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.database.deposit_root(block_number)
+    }
+}
+
+impl<N: ProviderNodeTypes> BlockProcessingHistoryReader for BlockchainProvider<N> {
+    fn block_processing_history(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockProcessingStats>> {
+        self.database.block_processing_history(range)
+    }
+}
+
+impl<N: ProviderNodeTypes> TransactionLookupIndexReader for BlockchainProvider<N> {
+    fn transaction_lookup_status(&self) -> ProviderResult<TransactionLookupStatus> {
+        self.database.transaction_lookup_status()
+    }
+}
+
+impl<N: ProviderNodeTypes> TransactionLookupIndexWriter for BlockchainProvider<N> {
+    fn index_transaction_hashes(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<u64> {
+        self.database.index_transaction_hashes(range)
+    }
+}
+
 impl<N: ProviderNodeTypes> AccountReader for BlockchainProvider<N> {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
@@ -804,9 +880,9 @@ mod tests {
     use reth_primitives_traits::{BlockBody, SignedTransaction};
     use reth_storage_api::{
         BlockBodyIndicesProvider, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader,
-        BlockReaderIdExt, BlockSource, ChangeSetReader, DatabaseProviderFactory, HeaderProvider,
-        OmmersProvider, ReceiptProvider, ReceiptProviderIdExt, StateProviderFactory,
-        TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+        BlockReaderIdExt, BlockSource, BlockTagFallbacks, ChangeSetReader, DatabaseProviderFactory,
+        HeaderProvider, OmmersProvider, ReceiptLog, ReceiptProvider, ReceiptProviderIdExt,
+        StateProviderFactory, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
     };
     use reth_testing_utils::generators::{
         self, random_block, random_block_range, random_changeset_range, random_eoa_accounts,
@@ -1748,6 +1824,39 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_receipt_provider_logs_by_block() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, database_blocks, in_memory_blocks, receipts) = provider_with_random_blocks(
+            &mut rng,
+            TEST_BLOCKS_COUNT,
+            TEST_BLOCKS_COUNT,
+            BlockRangeParams { tx_count: 1..3, ..Default::default() },
+        )?;
+
+        // the range spans both database and in-memory blocks
+        let range =
+            database_blocks.first().unwrap().number..=in_memory_blocks.last().unwrap().number;
+        let mut expected = Vec::new();
+        for block_number in range.clone() {
+            ReceiptLog::extend_matching(
+                &mut expected,
+                block_number,
+                receipts[block_number as usize].iter().map(|receipt| &receipt.logs),
+                |_, _| true,
+            );
+        }
+        assert!(!expected.is_empty());
+        assert_eq!(provider.logs_by_block(range.clone(), |_, _| true)?, expected);
+
+        // only the matching logs are returned, with their position in the block
+        let address = expected.last().unwrap().log.address;
+        expected.retain(|log| log.log.address == address);
+        assert_eq!(provider.logs_by_block(range, |_, log| log.address == address)?, expected);
+
+        Ok(())
+    }
+
     #[test]
     fn test_receipt_provider_id_ext_receipts_by_block_number_or_tag() -> eyre::Result<()> {
         let mut rng = generators::rng();
@@ -2163,6 +2272,54 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_block_tag_fallbacks() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, database_blocks, _, _) = provider_with_random_blocks(
+            &mut rng,
+            TEST_BLOCKS_COUNT,
+            0,
+            BlockRangeParams::default(),
+        )?;
+
+        // A provider without safe and finalized blocks
+        let latest = database_blocks.last().unwrap().clone_sealed_header();
+        let provider = BlockchainProvider::with_latest(provider.database.clone(), latest)?;
+        assert_eq!(provider.safe_block_num_hash()?, None);
+        assert!(provider.convert_block_number(BlockNumberOrTag::Finalized).is_err());
+
+        let provider = provider
+            .with_block_tag_fallbacks(BlockTagFallbacks { safe: Some(1), finalized: Some(100) });
+
+        // The safe tag resolves to one block below the latest block
+        let safe_block = &database_blocks[database_blocks.len() - 2];
+        let safe = BlockNumHash { number: safe_block.number, hash: safe_block.hash() };
+        assert_eq!(provider.safe_tag_num_hash()?, Some(safe));
+        assert_eq!(provider.consistent_provider()?.safe_tag_num_hash()?, Some(safe));
+        assert_eq!(provider.block_hash_for_id(BlockNumberOrTag::Safe.into())?, Some(safe.hash));
+        assert_eq!(
+            provider.sealed_header_by_number_or_tag(BlockNumberOrTag::Safe)?,
+            Some(safe_block.clone_sealed_header())
+        );
+
+        // The finalized tag is clamped to the first block
+        assert_eq!(provider.convert_block_number(BlockNumberOrTag::Finalized)?, Some(0));
+
+        // The safe and finalized blocks themselves stay unset
+        assert_eq!(provider.safe_block_num_hash()?, None);
+        assert_eq!(provider.finalized_block_number()?, None);
+        assert_eq!(provider.consistent_provider()?.finalized_block_number()?, None);
+
+        // Blocks set by the consensus layer take precedence
+        provider.canonical_in_memory_state.set_safe(database_blocks[0].clone_sealed_header());
+        assert_eq!(
+            provider.convert_block_number(BlockNumberOrTag::Safe)?,
+            Some(database_blocks[0].number)
+        );
+
+        Ok(())
+    }
+
     macro_rules! test_by_tx_range {
         ([$(($method:ident, $data_extractor:expr)),* $(,)?]) => {{
 
@@ -1,19 +1,19 @@
 use super::{DatabaseProviderRO, ProviderFactory, ProviderNodeTypes};
 use crate::{
     providers::StaticFileProvider, AccountReader, BlockHashReader, BlockIdReader, BlockNumReader,
-    BlockReader, BlockReaderIdExt, BlockSource, ChainSpecProvider, ChangeSetReader, HeaderProvider,
-    ProviderError, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
-    StageCheckpointReader, StateReader, StaticFileProviderFactory, TransactionVariant,
-    TransactionsProvider, WithdrawalsProvider,
+    BlockReader, BlockReaderIdExt, BlockSource, BlockTagFallbacks, ChainSpecProvider,
+    ChangeSetReader, HeaderProvider, ProviderError, PruneCheckpointReader, ReceiptProvider,
+    ReceiptProviderIdExt, StageCheckpointReader, StateReader, StaticFileProviderFactory,
+    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
-use alloy_consensus::{transaction::TransactionMeta, BlockHeader};
+use alloy_consensus::{transaction::TransactionMeta, BlockHeader, TxReceipt};
 use alloy_eips::{
     eip2718::Encodable2718, eip4895::Withdrawals, BlockHashOrNumber, BlockId, BlockNumHash,
     BlockNumberOrTag, HashOrNumber,
 };
 use alloy_primitives::{
     map::{hash_map, HashMap},
-    Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256,
+    Address, BlockHash, BlockNumber, Log, TxHash, TxNumber, B256, U256,
 };
 use reth_chain_state::{BlockState, CanonicalInMemoryState, MemoryOverlayStateProviderRef};
 use reth_chainspec::{ChainInfo, EthereumHardforks};
@@ -26,7 +26,7 @@ use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
     BlockBodyIndicesProvider, DatabaseProviderFactory, NodePrimitivesProvider, OmmersProvider,
-    StateProvider, StorageChangeSetReader,
+    ReceiptLog, StateProvider, StorageChangeSetReader,
 };
 use reth_storage_errors::provider::ProviderResult;
 use revm_database::states::PlainStorageRevert;
@@ -51,6 +51,8 @@ pub struct ConsistentProvider<N: ProviderNodeTypes> {
     head_block: Option<Arc<BlockState<N::Primitives>>>,
     /// In-memory canonical state. This is not a snapshot, and can change! Use with caution.
     canonical_in_memory_state: CanonicalInMemoryState<N::Primitives>,
+    /// Fallbacks for the `safe` and `finalized` block tags.
+    block_tag_fallbacks: BlockTagFallbacks,
 }
 
 impl<N: ProviderNodeTypes> ConsistentProvider<N> {
@@ -72,7 +74,21 @@ impl<N: ProviderNodeTypes> ConsistentProvider<N> {
         // entirely. Resulting in gaps on the range.
         let head_block = state.head_state();
         let storage_provider = storage_provider_factory.database_provider_ro()?;
-        Ok(Self { storage_provider, head_block, canonical_in_memory_state: state })
+        Ok(Self {
+            storage_provider,
+            head_block,
+            canonical_in_memory_state: state,
+            block_tag_fallbacks: BlockTagFallbacks::default(),
+        })
+    }
+
+    /// Sets the fallbacks for the `safe` and `finalized` block tags.
+    pub const fn with_block_tag_fallbacks(
+        mut self,
+        block_tag_fallbacks: BlockTagFallbacks,
+    ) -> Self {
+        self.block_tag_fallbacks = block_tag_fallbacks;
+        self
     }
 
     // Helper function to convert range bounds
@@ -590,6 +606,44 @@ impl<N: ProviderNodeTypes> ConsistentProvider<N> {
         }
         fetch_from_db(&self.storage_provider)
     }
+
+    /// Returns the in-memory block that contains the transaction with the given hash and the
+    /// index of the transaction in the block.
+    ///
+    /// The block is looked up in the transaction index of the [`CanonicalInMemoryState`] instead of
+    /// scanning the in-memory blocks. The index isn't a snapshot, so if the in-memory blocks
+    /// changed since the snapshot was taken, the in-memory blocks of the snapshot are scanned
+    /// instead.
+    fn in_memory_transaction(&self, hash: TxHash) -> Option<(&BlockState<N::Primitives>, usize)> {
+        let head_block = self.head_block.as_ref()?;
+        if let Some((block_hash, index)) = self.canonical_in_memory_state.transaction_location(hash)
+        {
+            if let Some(block_state) = head_block.block_on_chain(block_hash.into()) {
+                return Some((block_state, index))
+            }
+        }
+
+        // the index covers all blocks of the snapshot, unless blocks were persisted and removed
+        // from the index, or the in-memory state was reorged, after the snapshot was taken
+        let current_anchor = self.canonical_in_memory_state.head_state().map(|head| head.anchor());
+        if current_anchor.map(|anchor| anchor.number) == Some(head_block.anchor().number) &&
+            self.canonical_in_memory_state.state_by_hash(head_block.hash()).is_some()
+        {
+            return None
+        }
+
+        // the storage provider doesn't see blocks that were persisted after the snapshot was
+        // taken either, so the snapshot must be scanned
+        head_block.chain().find_map(|block_state| {
+            let index = block_state
+                .block_ref()
+                .recovered_block()
+                .body()
+                .transactions_iter()
+                .position(|tx| tx.trie_hash() == hash)?;
+            Some((block_state, index))
+        })
+    }
 }
 
 impl<N: ProviderNodeTypes> ConsistentProvider<N> {
@@ -764,6 +818,10 @@ impl<N: ProviderNodeTypes> BlockNumReader for ConsistentProvider<N> {
         self.storage_provider.last_block_number()
     }
 
+    fn earliest_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.storage_provider.earliest_block_number()
+    }
+
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
         self.get_in_memory_or_storage_by_block(
             hash.into(),
@@ -785,6 +843,20 @@ impl<N: ProviderNodeTypes> BlockIdReader for ConsistentProvider<N> {
     fn finalized_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
         Ok(self.canonical_in_memory_state.get_finalized_num_hash())
     }
+
+    fn safe_tag_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
+        match self.canonical_in_memory_state.get_safe_num_hash() {
+            Some(num_hash) => Ok(Some(num_hash)),
+            None => self.block_tag_fallbacks.safe_num_hash(self),
+        }
+    }
+
+    fn finalized_tag_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
+        match self.canonical_in_memory_state.get_finalized_num_hash() {
+            Some(num_hash) => Ok(Some(num_hash)),
+            None => self.block_tag_fallbacks.finalized_num_hash(self),
+        }
+    }
 }
 
 impl<N: ProviderNodeTypes> BlockReader for ConsistentProvider<N> {
@@ -949,8 +1021,14 @@ impl<N: ProviderNodeTypes> TransactionsProvider for ConsistentProvider<N> {
     }
 
     fn transaction_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Self::Transaction>> {
-        if let Some(tx) = self.head_block.as_ref().and_then(|b| b.transaction_on_chain(hash)) {
-            return Ok(Some(tx))
+        if let Some((block_state, index)) = self.in_memory_transaction(hash) {
+            return Ok(block_state
+                .block_ref()
+                .recovered_block()
+                .body()
+                .transactions()
+                .get(index)
+                .cloned());
         }
 
         self.storage_provider.transaction_by_hash(hash)
@@ -960,10 +1038,8 @@ impl<N: ProviderNodeTypes> TransactionsProvider for ConsistentProvider<N> {
         &self,
         tx_hash: TxHash,
     ) -> ProviderResult<Option<(Self::Transaction, TransactionMeta)>> {
-        if let Some((tx, meta)) =
-            self.head_block.as_ref().and_then(|b| b.transaction_meta_on_chain(tx_hash))
-        {
-            return Ok(Some((tx, meta)))
+        if let Some((block_state, index)) = self.in_memory_transaction(tx_hash) {
+            return Ok(block_state.transaction_with_meta(index))
         }
 
         self.storage_provider.transaction_by_hash_with_meta(tx_hash)
@@ -1056,24 +1132,18 @@ impl<N: ProviderNodeTypes> ReceiptProvider for ConsistentProvider<N> {
     }
 
     fn receipt_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Self::Receipt>> {
-        for block_state in self.head_block.iter().flat_map(|b| b.chain()) {
-            let executed_block = block_state.block_ref();
-            let block = executed_block.recovered_block();
-            let receipts = block_state.executed_block_receipts();
+        if let Some((block_state, tx_index)) = self.in_memory_transaction(hash) {
+            let receipts = block_state.receipts();
 
             // assuming 1:1 correspondence between transactions and receipts
             debug_assert_eq!(
-                block.body().transactions().len(),
-                receipts.len(),
+                block_state.block_ref().recovered_block().body().transactions().len(),
+                receipts.first().map_or(0, Vec::len),
                 "Mismatch between transaction and receipt count"
             );
 
-            if let Some(tx_index) =
-                block.body().transactions_iter().position(|tx| tx.trie_hash() == hash)
-            {
-                // safe to use tx_index for receipts due to 1:1 correspondence
-                return Ok(receipts.get(tx_index).cloned());
-            }
+            // safe to use tx_index for receipts due to 1:1 correspondence
+            return Ok(receipts.first().and_then(|receipts| receipts.get(tx_index)).cloned())
         }
 
         self.storage_provider.receipt_by_hash(hash)
@@ -1102,6 +1172,29 @@ impl<N: ProviderNodeTypes> ReceiptProvider for ConsistentProvider<N> {
             },
         )
     }
+
+    fn logs_by_block(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        filter: impl Fn(BlockNumber, &Log) -> bool,
+    ) -> ProviderResult<Vec<ReceiptLog>> {
+        let blocks = self.get_in_memory_or_storage_by_block_range_while(
+            range,
+            |db_provider, range, _| Ok(vec![db_provider.logs_by_block(range, &filter)?]),
+            |block_state, _| {
+                let mut logs = Vec::new();
+                ReceiptLog::extend_matching(
+                    &mut logs,
+                    block_state.number(),
+                    block_state.receipts().iter().flatten().map(TxReceipt::logs),
+                    &filter,
+                );
+                Some(logs)
+            },
+            |_| true,
+        )?;
+        Ok(blocks.into_iter().flatten().collect())
+    }
 }
 
 impl<N: ProviderNodeTypes> ReceiptProviderIdExt for ConsistentProvider<N> {
@@ -1276,11 +1369,8 @@ impl<N: ProviderNodeTypes> BlockReaderIdExt for ConsistentProvider<N> {
             BlockNumberOrTag::Latest => {
                 Some(self.canonical_in_memory_state.get_canonical_head().unseal())
             }
-            BlockNumberOrTag::Finalized => {
-                self.canonical_in_memory_state.get_finalized_header().map(|h| h.unseal())
-            }
-            BlockNumberOrTag::Safe => {
-                self.canonical_in_memory_state.get_safe_header().map(|h| h.unseal())
+            BlockNumberOrTag::Finalized | BlockNumberOrTag::Safe => {
+                self.sealed_header_by_number_or_tag(id)?.map(|h| h.unseal())
             }
             BlockNumberOrTag::Earliest => self.header_by_number(0)?,
             BlockNumberOrTag::Pending => self.canonical_in_memory_state.pending_header(),
@@ -1298,9 +1388,21 @@ impl<N: ProviderNodeTypes> BlockReaderIdExt for ConsistentProvider<N> {
                 Ok(Some(self.canonical_in_memory_state.get_canonical_head()))
             }
             BlockNumberOrTag::Finalized => {
-                Ok(self.canonical_in_memory_state.get_finalized_header())
+                match self.canonical_in_memory_state.get_finalized_header() {
+                    Some(header) => Ok(Some(header)),
+                    None => self
+                        .block_tag_fallbacks
+                        .finalized_num_hash(self)?
+                        .map_or(Ok(None), |num_hash| self.sealed_header(num_hash.number)),
+                }
             }
-            BlockNumberOrTag::Safe => Ok(self.canonical_in_memory_state.get_safe_header()),
+            BlockNumberOrTag::Safe => match self.canonical_in_memory_state.get_safe_header() {
+                Some(header) => Ok(Some(header)),
+                None => self
+                    .block_tag_fallbacks
+                    .safe_num_hash(self)?
+                    .map_or(Ok(None), |num_hash| self.sealed_header(num_hash.number)),
+            },
             BlockNumberOrTag::Earliest => self
                 .header_by_number(0)?
                 .map_or_else(|| Ok(None), |h| Ok(Some(SealedHeader::seal_slow(h)))),
@@ -1487,7 +1589,8 @@ mod tests {
     use reth_db_api::models::AccountBeforeTx;
     use reth_execution_types::ExecutionOutcome;
     use reth_primitives::{RecoveredBlock, SealedBlock};
-    use reth_storage_api::{BlockReader, BlockSource, ChangeSetReader};
+    use reth_primitives_traits::SignedTransaction;
+    use reth_storage_api::{BlockReader, BlockSource, ChangeSetReader, TransactionsProvider};
     use reth_testing_utils::generators::{
         self, random_block_range, random_changeset_range, random_eoa_accounts, BlockRangeParams,
     };
@@ -1848,4 +1951,70 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_transaction_persisted_after_snapshot() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (database_blocks, in_memory_blocks) =
+            random_blocks(&mut rng, TEST_BLOCKS_COUNT, 2, None, None, 1..3);
+
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw()?;
+        for block in database_blocks.clone() {
+            provider_rw.insert_historical_block(
+                block.try_recover().expect("failed to seal block with senders"),
+            )?;
+        }
+        provider_rw.commit()?;
+
+        let provider = BlockchainProvider::new(factory)?;
+        let chain = NewCanonicalChain::Commit {
+            new: in_memory_blocks
+                .iter()
+                .map(|block| {
+                    let senders = block.senders().expect("failed to recover senders");
+                    ExecutedBlockWithTrieUpdates::new(
+                        Arc::new(RecoveredBlock::new_sealed(block.clone(), senders)),
+                        Default::default(),
+                        Default::default(),
+                        Default::default(),
+                    )
+                })
+                .collect(),
+        };
+        provider.canonical_in_memory_state.update_chain(chain);
+
+        let consistent_provider = provider.consistent_provider()?;
+
+        // Transactions of blocks that were persisted before the snapshot are served by the
+        // storage provider, without scanning the unchanged in-memory blocks
+        let database_block = database_blocks.last().unwrap();
+        let tx = database_block.body().transactions.first().unwrap();
+        assert!(consistent_provider.in_memory_transaction(*tx.tx_hash()).is_none());
+        assert_eq!(consistent_provider.transaction_by_hash(*tx.tx_hash())?, Some(tx.clone()));
+
+        // Persist the first in-memory block after the snapshot was taken, which removes its
+        // transactions from the transaction index
+        let persisted_block = in_memory_blocks.first().unwrap();
+        provider.canonical_in_memory_state.remove_persisted_blocks(persisted_block.num_hash());
+        let tx = persisted_block.body().transactions.first().unwrap();
+        assert_eq!(provider.canonical_in_memory_state.transaction_location(*tx.tx_hash()), None);
+
+        // The snapshot still serves the transaction, which its storage provider doesn't see
+        assert_eq!(consistent_provider.transaction_by_hash(*tx.tx_hash())?, Some(tx.clone()));
+        assert_eq!(
+            consistent_provider
+                .transaction_by_hash_with_meta(*tx.tx_hash())?
+                .map(|(_, meta)| meta.block_hash),
+            Some(persisted_block.hash())
+        );
+
+        // Transactions of the blocks that are still in memory are served from the index
+        let in_memory_block = in_memory_blocks.last().unwrap();
+        let tx = in_memory_block.body().transactions.last().unwrap();
+        assert!(provider.canonical_in_memory_state.transaction_location(*tx.tx_hash()).is_some());
+        assert_eq!(consistent_provider.transaction_by_hash(*tx.tx_hash())?, Some(tx.clone()));
+
+        Ok(())
+    }
 }
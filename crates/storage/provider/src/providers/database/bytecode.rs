@@ -0,0 +1,69 @@
+//! Iteration over the stored contract bytecode.
+
+use crate::DatabaseProvider;
+use reth_db_api::{cursor::DbCursorRO, tables, transaction::DbTx};
+use reth_node_types::NodeTypes;
+use reth_storage_api::{BytecodeIter, BytecodeProvider, BytecodeSizeFilter};
+use reth_storage_errors::provider::ProviderResult;
+use std::iter;
+
+impl<TX: DbTx + 'static, N: NodeTypes> BytecodeProvider for DatabaseProvider<TX, N> {
+    fn iter_bytecodes(&self, filter: BytecodeSizeFilter) -> ProviderResult<BytecodeIter<'_>> {
+        let mut cursor = self.tx_ref().cursor_read::<tables::Bytecodes>()?;
+        let mut next = cursor.first().transpose();
+
+        Ok(Box::new(iter::from_fn(move || loop {
+            let entry = next.take()?;
+            // stop walking after an error
+            if entry.is_ok() {
+                next = cursor.next().transpose();
+            }
+
+            match entry {
+                Ok((code_hash, bytecode)) => {
+                    if filter.contains(bytecode.len()) {
+                        return Some(Ok((code_hash, bytecode)))
+                    }
+                }
+                Err(err) => return Some(Err(err.into())),
+            }
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_provider_factory;
+    use alloy_primitives::{Bytes, B256};
+    use reth_db_api::transaction::DbTxMut;
+    use reth_primitives_traits::Bytecode;
+
+    #[test]
+    fn iter_bytecodes() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let code = |len| Bytecode::new_raw(Bytes::from(vec![0x5b; len]));
+        for (code_hash, len) in [(1, 10), (2, 20), (3, 30)] {
+            provider
+                .tx_ref()
+                .put::<tables::Bytecodes>(B256::with_last_byte(code_hash), code(len))
+                .unwrap();
+        }
+
+        let code_hashes = |filter| {
+            provider
+                .iter_bytecodes(filter)
+                .unwrap()
+                .map(|entry| entry.unwrap().0)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(code_hashes(BytecodeSizeFilter::default()), [1, 2, 3].map(B256::with_last_byte));
+        assert_eq!(
+            code_hashes(BytecodeSizeFilter { min_size: Some(15), max_size: Some(20) }),
+            [B256::with_last_byte(2)]
+        );
+        assert!(code_hashes(BytecodeSizeFilter { min_size: Some(31), max_size: None }).is_empty());
+    }
+}
@@ -0,0 +1,357 @@
+//! Index of the beacon deposit contract's deposits.
+
+use crate::{
+    providers::NodeTypesForProvider, DatabaseProvider, StageCheckpointReader, StageCheckpointWriter,
+};
+use alloy_consensus::TxReceipt;
+use alloy_primitives::{BlockNumber, Log, B256};
+use alloy_sol_types::{sol, SolEvent};
+use reth_chainspec::EthChainSpec;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW},
+    models::StoredDepositBranch,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_node_types::NodeTypes;
+use reth_primitives_traits::GotExpected;
+use reth_stages_types::{StageCheckpoint, StageId};
+use reth_storage_api::{DepositIndexReader, DepositIndexWriter};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use sha2::{Digest, Sha256};
+
+/// Depth of the deposit contract's merkle tree.
+const DEPOSIT_CONTRACT_TREE_DEPTH: usize = 32;
+
+sol! {
+    /// Emitted by the deposit contract for every deposit.
+    event DepositEvent(
+        bytes pubkey,
+        bytes withdrawal_credentials,
+        bytes amount,
+        bytes signature,
+        bytes index
+    );
+}
+
+/// A decoded deposit of the deposit contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Deposit {
+    /// The index of the deposit.
+    index: u64,
+    /// The hash tree root of the deposit data, the leaf of the deposit contract's merkle tree.
+    data_root: B256,
+}
+
+impl Deposit {
+    /// Decodes the deposit from the data of a `DepositEvent` log.
+    ///
+    /// Returns `None` if the fields of the event don't have the expected lengths.
+    fn decode(log: &Log) -> Option<Self> {
+        let event = DepositEvent::decode_log_data(&log.data, true).ok()?;
+        let pubkey = <&[u8; 48]>::try_from(&event.pubkey[..]).ok()?;
+        let withdrawal_credentials = B256::try_from(&event.withdrawal_credentials[..]).ok()?;
+        let amount = <&[u8; 8]>::try_from(&event.amount[..]).ok()?;
+        let signature = <&[u8; 96]>::try_from(&event.signature[..]).ok()?;
+        let index = <[u8; 8]>::try_from(&event.index[..]).ok()?;
+
+        Some(Self {
+            index: u64::from_le_bytes(index),
+            data_root: deposit_data_root(pubkey, withdrawal_credentials, amount, signature),
+        })
+    }
+}
+
+/// Computes the hash tree root of the deposit data, as done by the deposit contract.
+fn deposit_data_root(
+    pubkey: &[u8; 48],
+    withdrawal_credentials: B256,
+    amount: &[u8; 8],
+    signature: &[u8; 96],
+) -> B256 {
+    let pubkey_root = sha256(&[pubkey.as_slice(), &[0; 16]]);
+    let signature_root = sha256(&[
+        sha256(&[&signature[..64]]).as_slice(),
+        sha256(&[&signature[64..], &[0; 32]]).as_slice(),
+    ]);
+    sha256(&[
+        sha256(&[pubkey_root.as_slice(), withdrawal_credentials.as_slice()]).as_slice(),
+        sha256(&[amount.as_slice(), &[0; 24], signature_root.as_slice()]).as_slice(),
+    ])
+}
+
+/// Returns the SHA-256 hash of the concatenated chunks.
+fn sha256(chunks: &[&[u8]]) -> B256 {
+    let mut hasher = Sha256::new();
+    for chunk in chunks {
+        hasher.update(chunk);
+    }
+    B256::from_slice(&hasher.finalize())
+}
+
+/// The incremental merkle tree of the deposit contract.
+#[derive(Debug, Default)]
+struct DepositTree {
+    /// The left siblings of the path to the next leaf.
+    branch: [B256; DEPOSIT_CONTRACT_TREE_DEPTH],
+    /// The number of leaves.
+    count: u64,
+}
+
+impl DepositTree {
+    /// Appends a leaf to the tree.
+    fn push(&mut self, leaf: B256) {
+        self.count += 1;
+        let mut node = leaf;
+        let mut size = self.count;
+        for height in 0..DEPOSIT_CONTRACT_TREE_DEPTH {
+            if size & 1 == 1 {
+                self.branch[height] = node;
+                return
+            }
+            node = sha256(&[self.branch[height].as_slice(), node.as_slice()]);
+            size /= 2;
+        }
+    }
+
+    /// Returns the root of the tree, mixed in with the number of leaves.
+    fn root(&self) -> B256 {
+        let mut node = B256::ZERO;
+        let mut zero_hash = B256::ZERO;
+        let mut size = self.count;
+        for height in 0..DEPOSIT_CONTRACT_TREE_DEPTH {
+            node = if size & 1 == 1 {
+                sha256(&[self.branch[height].as_slice(), node.as_slice()])
+            } else {
+                sha256(&[node.as_slice(), zero_hash.as_slice()])
+            };
+            zero_hash = sha256(&[zero_hash.as_slice(), zero_hash.as_slice()]);
+            size /= 2;
+        }
+        sha256(&[node.as_slice(), &self.count.to_le_bytes(), &[0; 24]])
+    }
+}
+
+impl From<StoredDepositBranch> for DepositTree {
+    fn from(stored: StoredDepositBranch) -> Self {
+        let mut branch = [B256::ZERO; DEPOSIT_CONTRACT_TREE_DEPTH];
+        for (node, stored) in branch.iter_mut().zip(stored.branch) {
+            *node = stored;
+        }
+        Self { branch, count: stored.count }
+    }
+}
+
+impl From<&DepositTree> for StoredDepositBranch {
+    fn from(tree: &DepositTree) -> Self {
+        // nodes at and above the highest set bit of the count are never read
+        let len = (u64::BITS - tree.count.leading_zeros()) as usize;
+        Self {
+            count: tree.count,
+            branch: tree.branch[..len.min(DEPOSIT_CONTRACT_TREE_DEPTH)].to_vec(),
+        }
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
+    /// Returns the deposit tree at the end of the given block, regardless of the index
+    /// checkpoint.
+    fn indexed_deposit_tree(&self, block_number: BlockNumber) -> ProviderResult<DepositTree> {
+        let mut cursor = self.tx_ref().cursor_read::<tables::BlockDepositBranches>()?;
+        // the last block with deposits at or before the given block
+        let entry = match cursor.seek(block_number + 1)? {
+            Some(_) => cursor.prev()?,
+            None => cursor.last()?,
+        };
+        Ok(entry.map(|(_, branch)| branch.into()).unwrap_or_default())
+    }
+
+    /// Returns the deposit tree at the end of the given block, or `None` if the block isn't
+    /// indexed yet.
+    fn deposit_tree(&self, block_number: BlockNumber) -> ProviderResult<Option<DepositTree>> {
+        let indexed = self
+            .get_stage_checkpoint(StageId::DepositIndex)?
+            .is_some_and(|checkpoint| checkpoint.block_number >= block_number);
+        if !indexed {
+            return Ok(None)
+        }
+
+        self.indexed_deposit_tree(block_number).map(Some)
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> DepositIndexReader for DatabaseProvider<TX, N> {
+    fn deposit_count(&self, block_number: BlockNumber) -> ProviderResult<Option<u64>> {
+        Ok(self.deposit_tree(block_number)?.map(|tree| tree.count))
+    }
+
+    fn deposit_root(&self, block_number: BlockNumber) -> ProviderResult<Option<B256>> {
+        Ok(self.deposit_tree(block_number)?.map(|tree| tree.root()))
+    }
+}
+
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> DepositIndexWriter
+    for DatabaseProvider<TX, N>
+{
+    fn append_deposits<'a, R>(
+        &self,
+        block_number: BlockNumber,
+        receipts: impl IntoIterator<Item = &'a R>,
+    ) -> ProviderResult<()>
+    where
+        R: TxReceipt<Log = Log> + 'a,
+    {
+        let Some(contract) = self.chain_spec().deposit_contract() else { return Ok(()) };
+
+        let mut branches_cursor = self.tx_ref().cursor_write::<tables::BlockDepositBranches>()?;
+        let mut leaves_cursor = self.tx_ref().cursor_write::<tables::DepositLeaves>()?;
+        let mut tree: DepositTree =
+            branches_cursor.last()?.map(|(_, branch)| branch.into()).unwrap_or_default();
+
+        let initial_count = tree.count;
+        for log in receipts.into_iter().flat_map(|receipt| receipt.logs()) {
+            if log.address != contract.address || log.topics().first() != Some(&contract.topic) {
+                continue
+            }
+
+            let deposit =
+                Deposit::decode(log).ok_or(ProviderError::InvalidDepositEvent(block_number))?;
+            if deposit.index != tree.count {
+                return Err(ProviderError::DepositIndexMismatch {
+                    block_number,
+                    index: GotExpected { got: deposit.index, expected: tree.count },
+                })
+            }
+
+            leaves_cursor.append(deposit.index, &deposit.data_root)?;
+            tree.push(deposit.data_root);
+        }
+
+        if tree.count != initial_count {
+            branches_cursor.append(block_number, &StoredDepositBranch::from(&tree))?;
+        }
+
+        Ok(())
+    }
+
+    fn unwind_deposit_index(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        let count = self.indexed_deposit_tree(block_number)?.count;
+
+        let mut leaves_cursor = self.tx_ref().cursor_write::<tables::DepositLeaves>()?;
+        let mut entry = leaves_cursor.seek(count)?;
+        while entry.is_some() {
+            leaves_cursor.delete_current()?;
+            entry = leaves_cursor.next()?;
+        }
+
+        let mut branches_cursor = self.tx_ref().cursor_write::<tables::BlockDepositBranches>()?;
+        let mut entry = branches_cursor.seek(block_number + 1)?;
+        while entry.is_some() {
+            branches_cursor.delete_current()?;
+            entry = branches_cursor.next()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
+    /// Unwinds the deposit index to the given block if it's ahead of it.
+    ///
+    /// Used when blocks are removed outside of the pipeline, which doesn't run the optional
+    /// deposit index stage.
+    pub(crate) fn unwind_deposit_index_above(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<()> {
+        let ahead = self
+            .get_stage_checkpoint(StageId::DepositIndex)?
+            .is_some_and(|checkpoint| checkpoint.block_number > block_number);
+        if ahead {
+            self.unwind_deposit_index(block_number)?;
+            self.save_stage_checkpoint(StageId::DepositIndex, StageCheckpoint::new(block_number))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_provider_factory;
+    use alloy_primitives::{b256, Address, Bytes};
+    use reth_ethereum_primitives::Receipt;
+
+    fn deposit_log(address: Address, index: u64) -> Log {
+        let event = DepositEvent {
+            pubkey: Bytes::from([1; 48]),
+            withdrawal_credentials: Bytes::from([2; 32]),
+            amount: Bytes::from(32_000_000_000u64.to_le_bytes()),
+            signature: Bytes::from([3; 96]),
+            index: Bytes::from(index.to_le_bytes()),
+        };
+        Log { address, data: event.encode_log_data() }
+    }
+
+    #[test]
+    fn empty_tree_root() {
+        // `get_deposit_root` of the mainnet deposit contract before the first deposit
+        assert_eq!(
+            DepositTree::default().root(),
+            b256!("0xd70a234731285c6804c2a4f56711ddb8c82c99740f207854891028af34e27e5e")
+        );
+    }
+
+    #[test]
+    fn stored_branch_roundtrip() {
+        let mut tree = DepositTree::default();
+        for i in 0..13u8 {
+            tree.push(B256::repeat_byte(i));
+            let stored = StoredDepositBranch::from(&tree);
+            assert_eq!(stored.branch.len(), (u64::BITS - tree.count.leading_zeros()) as usize);
+            assert_eq!(DepositTree::from(stored).root(), tree.root());
+        }
+    }
+
+    #[test]
+    fn append_and_unwind_deposits() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let contract = provider.chain_spec().deposit_contract().unwrap().address;
+
+        let receipt = |indices: &[u64]| Receipt {
+            logs: indices.iter().map(|index| deposit_log(contract, *index)).collect(),
+            ..Default::default()
+        };
+        provider.append_deposits(1, &[receipt(&[0, 1])]).unwrap();
+        provider.append_deposits(2, &[receipt(&[])]).unwrap();
+        provider.append_deposits(3, &[receipt(&[2])]).unwrap();
+        provider.save_stage_checkpoint(StageId::DepositIndex, StageCheckpoint::new(3)).unwrap();
+
+        assert_eq!(provider.deposit_count(0).unwrap(), Some(0));
+        assert_eq!(provider.deposit_count(2).unwrap(), Some(2));
+        assert_eq!(provider.deposit_count(3).unwrap(), Some(3));
+        assert_eq!(provider.deposit_count(4).unwrap(), None);
+
+        let mut tree = DepositTree::default();
+        let leaf = Deposit::decode(&deposit_log(contract, 0)).unwrap().data_root;
+        tree.push(leaf);
+        tree.push(leaf);
+        assert_eq!(provider.deposit_root(2).unwrap(), Some(tree.root()));
+        tree.push(leaf);
+        assert_eq!(provider.deposit_root(3).unwrap(), Some(tree.root()));
+
+        // deposits must be contiguous
+        assert!(matches!(
+            provider.append_deposits(4, &[receipt(&[5])]),
+            Err(ProviderError::DepositIndexMismatch { block_number: 4, .. })
+        ));
+
+        provider.unwind_deposit_index(1).unwrap();
+        let mut tree = DepositTree::default();
+        tree.push(leaf);
+        tree.push(leaf);
+        assert_eq!(provider.deposit_count(3).unwrap(), Some(2));
+        assert_eq!(provider.deposit_root(3).unwrap(), Some(tree.root()));
+    }
+}
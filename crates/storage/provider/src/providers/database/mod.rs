@@ -2,14 +2,17 @@ use crate::{
     providers::{state::latest::LatestStateProvider, StaticFileProvider},
     to_range,
     traits::{BlockSource, ReceiptProvider},
-    BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory,
-    HashedPostStateProvider, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, ProviderError,
-    PruneCheckpointReader, StageCheckpointReader, StateProviderBox, StaticFileProviderFactory,
-    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    BlockHashReader, BlockNumReader, BlockProcessingHistoryReader, BlockProcessingHistoryWriter,
+    BlockProcessingStats, BlockReader, ChainSpecProvider, DatabaseProviderFactory,
+    DepositIndexReader, HashedPostStateProvider, HeaderProvider, HeaderSyncGap,
+    HeaderSyncGapProvider, ProviderError, PruneCheckpointReader, StageCheckpointReader,
+    StateProviderBox, StaticFileProviderFactory, TransactionLookupIndexReader,
+    TransactionLookupIndexWriter, TransactionLookupStatus, TransactionVariant,
+    TransactionsProvider, WithdrawalsProvider,
 };
 use alloy_consensus::transaction::TransactionMeta;
 use alloy_eips::{eip4895::Withdrawals, BlockHashOrNumber};
-use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256};
+use alloy_primitives::{Address, BlockHash, BlockNumber, Log, TxHash, TxNumber, B256, U256};
 use core::fmt;
 use reth_chainspec::ChainInfo;
 use reth_db::{init_db, mdbx::DatabaseArguments, DatabaseEnv};
@@ -22,8 +25,8 @@ use reth_primitives::{RecoveredBlock, SealedBlock, SealedHeader, StaticFileSegme
 use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, NodePrimitivesProvider, OmmersProvider, StateCommitmentProvider,
-    TryIntoHistoricalStateProvider,
+    BlockBodyIndicesProvider, NodePrimitivesProvider, OmmersProvider, ReceiptLog,
+    StateCommitmentProvider, TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::HashedPostState;
@@ -50,6 +53,10 @@ mod metrics;
 mod chain;
 pub use chain::*;
 
+mod bytecode;
+mod deposits;
+mod tx_lookup;
+
 /// A common provider that fetches data from a database or static file.
 ///
 /// This provider implements most provider or provider factory traits.
@@ -64,6 +71,9 @@ pub struct ProviderFactory<N: NodeTypesWithDB> {
     prune_modes: PruneModes,
     /// The node storage handler.
     storage: Arc<N::Storage>,
+    /// Number of workers, each with its own read-only transaction, that historical state
+    /// providers split changeset walks and state lookups across.
+    changeset_walk_parallelism: usize,
 }
 
 impl<N: NodeTypes> ProviderFactory<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>> {
@@ -86,6 +96,7 @@ impl<N: NodeTypesWithDB> ProviderFactory<N> {
             static_file_provider,
             prune_modes: PruneModes::none(),
             storage: Default::default(),
+            changeset_walk_parallelism: 1,
         }
     }
 
@@ -101,6 +112,17 @@ impl<N: NodeTypesWithDB> ProviderFactory<N> {
         self
     }
 
+    /// Sets the number of workers that historical state providers of read-only providers split
+    /// changeset walks and state lookups across. The workers share the read-only transaction of
+    /// the provider, so they all read the same snapshot of the database.
+    ///
+    /// This speeds up state root, proof and witness computation and state lookups at old blocks.
+    /// A value of `1` (the default) reads the historical state sequentially.
+    pub const fn with_changeset_walk_parallelism(mut self, parallelism: usize) -> Self {
+        self.changeset_walk_parallelism = parallelism;
+        self
+    }
+
     /// Returns reference to the underlying database.
     pub const fn db_ref(&self) -> &N::DB {
         &self.db
@@ -128,6 +150,7 @@ impl<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>> ProviderFactory<N> {
             static_file_provider,
             prune_modes: PruneModes::none(),
             storage: Default::default(),
+            changeset_walk_parallelism: 1,
         })
     }
 }
@@ -147,7 +170,8 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
             self.static_file_provider.clone(),
             self.prune_modes.clone(),
             self.storage.clone(),
-        ))
+        )
+        .with_changeset_walk_parallelism(self.changeset_walk_parallelism))
     }
 
     /// Returns a provider with a created `DbTxMut` inside, which allows fetching and updating
@@ -345,6 +369,10 @@ impl<N: ProviderNodeTypes> BlockNumReader for ProviderFactory<N> {
         self.provider()?.last_block_number()
     }
 
+    fn earliest_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.static_file_provider.earliest_block_number()
+    }
+
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
         self.provider()?.block_number(hash)
     }
@@ -524,6 +552,14 @@ impl<N: ProviderNodeTypes> ReceiptProvider for ProviderFactory<N> {
             |_| true,
         )
     }
+
+    fn logs_by_block(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        filter: impl Fn(BlockNumber, &Log) -> bool,
+    ) -> ProviderResult<Vec<ReceiptLog>> {
+        self.provider()?.logs_by_block(range, filter)
+    }
 }
 
 impl<N: ProviderNodeTypes> WithdrawalsProvider for ProviderFactory<N> {
@@ -586,6 +622,50 @@ impl<N: ProviderNodeTypes> StageCheckpointReader for ProviderFactory<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> DepositIndexReader for ProviderFactory<N> {
+    fn deposit_count(&self, block_number: BlockNumber) -> ProviderResult<Option<u64>> {
+        self.provider()?.deposit_count(block_number)
+    }
+
+    fn deposit_root(&self, block_number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.provider()?.deposit_root(block_number)
+    }
+}
+
+impl<N: ProviderNodeTypes> TransactionLookupIndexReader for ProviderFactory<N> {
+    fn transaction_lookup_status(&self) -> ProviderResult<TransactionLookupStatus> {
+        self.provider()?.transaction_lookup_status()
+    }
+}
+
+impl<N: ProviderNodeTypes> TransactionLookupIndexWriter for ProviderFactory<N> {
+    fn index_transaction_hashes(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<u64> {
+        let provider_rw = self.provider_rw()?;
+        let indexed = provider_rw.index_transaction_hashes(range)?;
+        provider_rw.commit()?;
+        Ok(indexed)
+    }
+}
+
+impl<N: ProviderNodeTypes> BlockProcessingHistoryReader for ProviderFactory<N> {
+    fn block_processing_history(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockProcessingStats>> {
+        self.static_file_provider.block_processing_history().read(range)
+    }
+}
+
+impl<N: ProviderNodeTypes> BlockProcessingHistoryWriter for ProviderFactory<N> {
+    fn append_block_processing_stats(&self, stats: &[BlockProcessingStats]) -> ProviderResult<()> {
+        self.static_file_provider.block_processing_history().append(stats)
+    }
+
+    fn unwind_block_processing_history(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        self.static_file_provider.block_processing_history().unwind(block_number)
+    }
+}
+
 impl<N: NodeTypesWithDB> ChainSpecProvider for ProviderFactory<N> {
     type ChainSpec = N::ChainSpec;
 
@@ -620,13 +700,21 @@ where
     N: NodeTypesWithDB<DB: fmt::Debug, ChainSpec: fmt::Debug, Storage: fmt::Debug>,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Self { db, chain_spec, static_file_provider, prune_modes, storage } = self;
+        let Self {
+            db,
+            chain_spec,
+            static_file_provider,
+            prune_modes,
+            storage,
+            changeset_walk_parallelism,
+        } = self;
         f.debug_struct("ProviderFactory")
             .field("db", &db)
             .field("chain_spec", &chain_spec)
             .field("static_file_provider", &static_file_provider)
             .field("prune_modes", &prune_modes)
             .field("storage", &storage)
+            .field("changeset_walk_parallelism", &changeset_walk_parallelism)
             .finish()
     }
 }
@@ -639,6 +727,7 @@ impl<N: NodeTypesWithDB> Clone for ProviderFactory<N> {
             static_file_provider: self.static_file_provider.clone(),
             prune_modes: self.prune_modes.clone(),
             storage: self.storage.clone(),
+            changeset_walk_parallelism: self.changeset_walk_parallelism,
         }
     }
 }
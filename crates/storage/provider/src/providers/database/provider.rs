@@ -24,11 +24,12 @@ use alloy_eips::{eip2718::Encodable2718, eip4895::Withdrawals, BlockHashOrNumber
 use alloy_primitives::{
     keccak256,
     map::{hash_map, B256Map, HashMap, HashSet},
-    Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256,
+    Address, BlockHash, BlockNumber, Log, TxHash, TxNumber, B256, U256,
 };
 use itertools::Itertools;
 use rayon::slice::ParallelSliceMut;
 use reth_chainspec::{ChainInfo, ChainSpecProvider, EthChainSpec, EthereumHardforks};
+use reth_db::static_file::ReceiptMask;
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
     database::Database,
@@ -54,7 +55,7 @@ use reth_prune_types::{
 };
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BlockBodyReader, NodePrimitivesProvider, OmmersProvider,
+    BlockBodyIndicesProvider, BlockBodyReader, NodePrimitivesProvider, OmmersProvider, ReceiptLog,
     StateProvider, StorageChangeSetReader, TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::{ProviderResult, RootMismatch};
@@ -145,6 +146,9 @@ pub struct DatabaseProvider<TX, N: NodeTypes> {
     prune_modes: PruneModes,
     /// Node storage handler.
     storage: Arc<N::Storage>,
+    /// Number of workers that historical state providers split changeset walks and state lookups
+    /// across.
+    changeset_walk_parallelism: usize,
 }
 
 impl<TX, N: NodeTypes> DatabaseProvider<TX, N> {
@@ -152,6 +156,13 @@ impl<TX, N: NodeTypes> DatabaseProvider<TX, N> {
     pub const fn prune_modes_ref(&self) -> &PruneModes {
         &self.prune_modes
     }
+
+    /// Sets the number of workers that historical state providers split changeset walks and state
+    /// lookups across. The workers share the transaction of this provider.
+    pub const fn with_changeset_walk_parallelism(mut self, parallelism: usize) -> Self {
+        self.changeset_walk_parallelism = parallelism;
+        self
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
@@ -182,7 +193,8 @@ impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
         let storage_history_prune_checkpoint =
             self.get_prune_checkpoint(PruneSegment::StorageHistory)?;
 
-        let mut state_provider = HistoricalStateProviderRef::new(self, block_number);
+        let mut state_provider = HistoricalStateProviderRef::new(self, block_number)
+            .with_changeset_walk_parallelism(self.changeset_walk_parallelism);
 
         // If we pruned account or storage history, we can't return state on every historical block.
         // Instead, we should cap it at the latest prune checkpoint for corresponding prune segment.
@@ -241,7 +253,14 @@ impl<TX: DbTxMut, N: NodeTypes> DatabaseProvider<TX, N> {
         prune_modes: PruneModes,
         storage: Arc<N::Storage>,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider, prune_modes, storage }
+        Self {
+            tx,
+            chain_spec,
+            static_file_provider,
+            prune_modes,
+            storage,
+            changeset_walk_parallelism: 1,
+        }
     }
 }
 
@@ -387,7 +406,9 @@ impl<TX: DbTx + 'static, N: NodeTypes> TryIntoHistoricalStateProvider for Databa
         let storage_history_prune_checkpoint =
             self.get_prune_checkpoint(PruneSegment::StorageHistory)?;
 
-        let mut state_provider = HistoricalStateProvider::new(self, block_number);
+        let parallelism = self.changeset_walk_parallelism;
+        let mut state_provider = HistoricalStateProvider::new(self, block_number)
+            .with_changeset_walk_parallelism(parallelism);
 
         // If we pruned account or storage history, we can't return state on every historical block.
         // Instead, we should cap it at the latest prune checkpoint for corresponding prune segment.
@@ -508,7 +529,14 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
         prune_modes: PruneModes,
         storage: Arc<N::Storage>,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider, prune_modes, storage }
+        Self {
+            tx,
+            chain_spec,
+            static_file_provider,
+            prune_modes,
+            storage,
+            changeset_walk_parallelism: 1,
+        }
     }
 
     /// Consume `DbTx` or `DbTxMut`.
@@ -573,6 +601,12 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
         // `None`.
         let Some(body) = self.block_body_indices(block_number)? else { return Ok(None) };
 
+        // The body has been expired from the static files, so we can't assemble the block.
+        if self.static_file_provider.is_block_expired(StaticFileSegment::Transactions, block_number)
+        {
+            return Ok(None)
+        }
+
         let tx_range = body.tx_num_range();
 
         let (transactions, senders) = if tx_range.is_empty() {
@@ -1145,6 +1179,10 @@ impl<TX: DbTx + 'static, N: NodeTypes> BlockNumReader for DatabaseProvider<TX, N
             .unwrap_or_default())
     }
 
+    fn earliest_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.static_file_provider.earliest_block_number()
+    }
+
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
         Ok(self.tx.get::<tables::HeaderNumbers>(hash)?)
     }
@@ -1168,8 +1206,8 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> BlockReader for DatabaseProvid
     /// Returns the block with matching number from database.
     ///
     /// If the header for this block is not found, this returns `None`.
-    /// If the header is found, but the transactions either do not exist, or are not indexed, this
-    /// will return None.
+    /// If the header is found, but the transactions either do not exist, are not indexed or have
+    /// been expired, this will return None.
     fn block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Self::Block>> {
         if let Some(number) = self.convert_hash_or_number(id)? {
             if let Some(header) = self.header_by_number(number)? {
@@ -1458,6 +1496,13 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> TransactionsProvider for Datab
         let mut tx_cursor = self.tx.cursor_read::<tables::Transactions<Self::Transaction>>()?;
 
         if let Some(block_number) = self.convert_hash_or_number(id)? {
+            if self
+                .static_file_provider
+                .is_block_expired(StaticFileSegment::Transactions, block_number)
+            {
+                return Ok(None)
+            }
+
             if let Some(body) = self.block_body_indices(block_number)? {
                 let tx_range = body.tx_num_range();
                 return if tx_range.is_empty() {
@@ -1540,6 +1585,10 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
         block: BlockHashOrNumber,
     ) -> ProviderResult<Option<Vec<Self::Receipt>>> {
         if let Some(number) = self.convert_hash_or_number(block)? {
+            if self.static_file_provider.is_block_expired(StaticFileSegment::Receipts, number) {
+                return Ok(None)
+            }
+
             if let Some(body) = self.block_body_indices(number)? {
                 let tx_range = body.tx_num_range();
                 return if tx_range.is_empty() {
@@ -1564,6 +1613,50 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
             |_| true,
         )
     }
+
+    fn logs_by_block(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        filter: impl Fn(BlockNumber, &Log) -> bool,
+    ) -> ProviderResult<Vec<ReceiptLog>> {
+        let mut logs = Vec::new();
+        for (block_number, body) in range.clone().zip(self.block_body_indices_range(range)?) {
+            if body.tx_count == 0 ||
+                self.static_file_provider
+                    .is_block_expired(StaticFileSegment::Receipts, block_number)
+            {
+                continue
+            }
+
+            // every receipt is reduced to its logs as soon as it's decoded
+            let receipts = self.static_file_provider.get_range_with_static_file_or_database(
+                StaticFileSegment::Receipts,
+                body.tx_num_range(),
+                |static_file, range, _| {
+                    static_file.fetch_range_with_predicate(
+                        StaticFileSegment::Receipts,
+                        range,
+                        |cursor, number| {
+                            Ok(cursor
+                                .get_one::<ReceiptMask<Self::Receipt>>(number.into())?
+                                .map(TxReceipt::into_logs))
+                        },
+                        |_| true,
+                    )
+                },
+                |range, _| {
+                    self.tx
+                        .cursor_read::<tables::Receipts<Self::Receipt>>()?
+                        .walk_range(range)?
+                        .map(|entry| Ok(entry?.1.into_logs()))
+                        .collect()
+                },
+                |_| true,
+            )?;
+            ReceiptLog::extend_matching(&mut logs, block_number, receipts, &filter);
+        }
+        Ok(logs)
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypes<ChainSpec: EthereumHardforks>> WithdrawalsProvider
@@ -2767,6 +2860,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecu
 
         // Update pipeline progress
         self.update_pipeline_stages(block, true)?;
+        self.unwind_deposit_index_above(block)?;
 
         Ok(Chain::new(blocks, execution_state, None))
     }
@@ -2789,6 +2883,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecu
 
         // Update pipeline progress
         self.update_pipeline_stages(block, true)?;
+        self.unwind_deposit_index_above(block)?;
 
         Ok(())
     }
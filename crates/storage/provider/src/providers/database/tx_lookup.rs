@@ -0,0 +1,187 @@
+//! Index of transaction hashes to transaction numbers.
+
+use crate::{
+    providers::NodeTypesForProvider, BlockBodyIndicesProvider, DatabaseProvider,
+    PruneCheckpointReader, PruneCheckpointWriter, StageCheckpointReader, StageCheckpointWriter,
+    TransactionsProviderExt,
+};
+use alloy_primitives::BlockNumber;
+use reth_db_api::{
+    cursor::DbCursorRW,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_node_types::NodeTypes;
+use reth_prune_types::{PruneCheckpoint, PruneSegment};
+use reth_stages_types::{StageCheckpoint, StageId};
+use reth_storage_api::{
+    TransactionLookupIndexReader, TransactionLookupIndexWriter, TransactionLookupStatus,
+};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::ops::RangeInclusive;
+
+impl<TX: DbTx + 'static, N: NodeTypes> TransactionLookupIndexReader for DatabaseProvider<TX, N> {
+    fn transaction_lookup_status(&self) -> ProviderResult<TransactionLookupStatus> {
+        let checkpoint = |stage_id| -> ProviderResult<_> {
+            Ok(self.get_stage_checkpoint(stage_id)?.unwrap_or_default().block_number)
+        };
+
+        Ok(TransactionLookupStatus {
+            pruned_block: self
+                .get_prune_checkpoint(PruneSegment::TransactionLookup)?
+                .and_then(|checkpoint| checkpoint.block_number),
+            indexed_block: checkpoint(StageId::TransactionLookup)?,
+            stored_block: checkpoint(StageId::Bodies)?,
+        })
+    }
+}
+
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> TransactionLookupIndexWriter
+    for DatabaseProvider<TX, N>
+{
+    fn index_transaction_hashes(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<u64> {
+        let status = self.transaction_lookup_status()?;
+        let invalid_range =
+            |reason| ProviderError::InvalidTransactionLookupRange { range: range.clone(), reason };
+        if range.is_empty() {
+            return Err(invalid_range("the range is empty"))
+        }
+        // unwinds only remove the hashes of indexed blocks, and the pruner only the hashes of the
+        // blocks up to its checkpoint, so there must be no gaps
+        if *range.start() > status.indexed_block + 1 {
+            return Err(invalid_range("the range doesn't continue the index"))
+        }
+        if status.pruned_block.is_some_and(|pruned_block| *range.end() < pruned_block) {
+            return Err(invalid_range("the range doesn't reach the unpruned blocks"))
+        }
+        if *range.end() > status.stored_block {
+            return Err(invalid_range("the blocks aren't stored"))
+        }
+
+        let first_tx_num = self
+            .block_body_indices(*range.start())?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(*range.start()))?
+            .first_tx_num();
+        let next_tx_num = self
+            .block_body_indices(*range.end())?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(*range.end()))?
+            .next_tx_num();
+
+        let hashes = self.transaction_hashes_by_range(first_tx_num..next_tx_num)?;
+        let mut cursor = self.tx_ref().cursor_write::<tables::TransactionHashNumbers>()?;
+        for (hash, tx_number) in &hashes {
+            cursor.upsert(*hash, tx_number)?;
+        }
+
+        // the pruner doesn't revisit the blocks below its checkpoint, so it's rewound to prune the
+        // re-indexed blocks again once they're outside of the configured prune distance
+        if let Some(checkpoint) =
+            self.get_prune_checkpoint(PruneSegment::TransactionLookup)?.filter(|checkpoint| {
+                checkpoint.block_number.is_some_and(|pruned_block| *range.start() <= pruned_block)
+            })
+        {
+            self.save_prune_checkpoint(
+                PruneSegment::TransactionLookup,
+                PruneCheckpoint {
+                    block_number: range.start().checked_sub(1),
+                    tx_number: first_tx_num.checked_sub(1),
+                    ..checkpoint
+                },
+            )?;
+        }
+
+        if *range.end() > status.indexed_block {
+            let checkpoint =
+                self.get_stage_checkpoint(StageId::TransactionLookup)?.unwrap_or_default();
+            self.save_stage_checkpoint(
+                StageId::TransactionLookup,
+                StageCheckpoint { block_number: *range.end(), ..checkpoint },
+            )?;
+        }
+
+        Ok(hashes.len() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::create_test_provider_factory, BlockWriter, StorageLocation,
+        TransactionsProvider,
+    };
+    use reth_primitives_traits::SignedTransaction;
+    use reth_prune_types::{PruneMode, PruneModes};
+    use reth_testing_utils::generators::{self, random_block, BlockParams};
+
+    #[test]
+    fn index_transaction_hashes() {
+        // insert the blocks without indexing their transaction hashes
+        let prune_modes =
+            PruneModes { transaction_lookup: Some(PruneMode::Full), ..PruneModes::none() };
+        let factory = create_test_provider_factory().with_prune_modes(prune_modes);
+        let provider = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let blocks = (0..=2)
+            .map(|number| {
+                let tx_count = if number == 0 { 0 } else { 2 };
+                random_block(
+                    &mut rng,
+                    number,
+                    BlockParams { tx_count: Some(tx_count), ..Default::default() },
+                )
+            })
+            .collect::<Vec<_>>();
+        for block in &blocks {
+            provider
+                .insert_block(block.clone().try_recover().unwrap(), StorageLocation::Database)
+                .unwrap();
+        }
+        provider.save_stage_checkpoint(StageId::Bodies, StageCheckpoint::new(2)).unwrap();
+        assert_eq!(
+            provider.transaction_lookup_status().unwrap(),
+            TransactionLookupStatus { pruned_block: None, indexed_block: 0, stored_block: 2 }
+        );
+
+        let invalid_range = |range| {
+            matches!(
+                provider.index_transaction_hashes(range),
+                Err(ProviderError::InvalidTransactionLookupRange { .. })
+            )
+        };
+        assert!(invalid_range(2..=2));
+        assert!(invalid_range(1..=3));
+
+        assert_eq!(provider.index_transaction_hashes(1..=1).unwrap(), 2);
+        let tx_hash = *blocks[1].body().transactions[1].tx_hash();
+        assert_eq!(provider.transaction_id(tx_hash).unwrap(), Some(1));
+        assert_eq!(provider.transaction_lookup_status().unwrap().lag(), 1);
+
+        assert_eq!(provider.index_transaction_hashes(2..=2).unwrap(), 2);
+        assert_eq!(provider.transaction_lookup_status().unwrap().unindexed_blocks(), None);
+
+        // pruned blocks can be indexed again, which rewinds the pruner
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::TransactionLookup,
+                PruneCheckpoint {
+                    block_number: Some(2),
+                    tx_number: Some(3),
+                    prune_mode: PruneMode::Full,
+                },
+            )
+            .unwrap();
+        assert!(invalid_range(1..=1));
+        assert_eq!(provider.index_transaction_hashes(2..=2).unwrap(), 2);
+        assert_eq!(provider.transaction_lookup_status().unwrap().pruned_block, Some(1));
+        assert_eq!(
+            provider.get_prune_checkpoint(PruneSegment::TransactionLookup).unwrap(),
+            Some(PruneCheckpoint {
+                block_number: Some(1),
+                tx_number: Some(1),
+                prune_mode: PruneMode::Full,
+            })
+        );
+    }
+}
@@ -9,8 +9,9 @@ pub use database::*;
 
 mod static_file;
 pub use static_file::{
-    StaticFileAccess, StaticFileJarProvider, StaticFileProvider, StaticFileProviderRW,
-    StaticFileProviderRWRefMut, StaticFileWriter,
+    ManifestMismatch, StaticFileAccess, StaticFileJarProvider, StaticFileManifest,
+    StaticFileProvider, StaticFileProviderRW, StaticFileProviderRWRefMut, StaticFileWriter,
+    VerificationMode,
 };
 
 mod state;
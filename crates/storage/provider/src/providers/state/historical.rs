@@ -3,10 +3,16 @@ use crate::{
     HashedPostStateProvider, ProviderError, StateProvider, StateRootProvider,
 };
 use alloy_eips::merge::EPOCH_SLOTS;
-use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
+use alloy_primitives::{
+    map::{AddressMap, B256Map},
+    Address, BlockNumber, Bytes, StorageKey, StorageValue, B256, U256,
+};
+use rayon::prelude::*;
 use reth_db_api::{
     cursor::{DbCursorRO, DbDupCursorRO},
-    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    models::{
+        storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress, ShardedKey,
+    },
     table::Table,
     tables,
     transaction::DbTx,
@@ -21,14 +27,14 @@ use reth_trie::{
     proof::{Proof, StorageProof},
     updates::TrieUpdates,
     witness::TrieWitness,
-    AccountProof, HashedPostState, HashedStorage, MultiProof, MultiProofTargets, StateRoot,
-    StorageMultiProof, StorageRoot, TrieInput,
+    AccountProof, HashedPostState, HashedStorage, HashedStorageRange, KeyHasher, MultiProof,
+    MultiProofTargets, StateRoot, StorageMultiProof, StorageRoot, TrieInput,
 };
 use reth_trie_db::{
     DatabaseHashedPostState, DatabaseHashedStorage, DatabaseProof, DatabaseStateRoot,
     DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieWitness, StateCommitment,
 };
-use std::fmt::Debug;
+use std::{fmt::Debug, ops::RangeInclusive};
 
 /// State provider for a given block number which takes a tx reference.
 ///
@@ -49,6 +55,8 @@ pub struct HistoricalStateProviderRef<'b, Provider> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// Number of workers that changeset walks and state lookups are split across.
+    parallelism: usize,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -64,7 +72,7 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
 {
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: &'b Provider, block_number: BlockNumber) -> Self {
-        Self { provider, block_number, lowest_available_blocks: Default::default() }
+        Self { provider, block_number, lowest_available_blocks: Default::default(), parallelism: 1 }
     }
 
     /// Create new `StateProvider` for historical block number and lowest block numbers at which
@@ -74,7 +82,21 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
         block_number: BlockNumber,
         lowest_available_blocks: LowestAvailableBlocks,
     ) -> Self {
-        Self { provider, block_number, lowest_available_blocks }
+        Self { provider, block_number, lowest_available_blocks, parallelism: 1 }
+    }
+
+    /// Set the number of workers that changeset walks and state lookups are split across.
+    ///
+    /// All workers read through the transaction of the provider, so they see the same snapshot of
+    /// the database. With `0` or `1` workers the state is read sequentially.
+    pub const fn with_changeset_walk_parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Returns the number of workers if the lookups should be split across them.
+    fn parallelism(&self) -> Option<usize> {
+        (self.parallelism > 1).then_some(self.parallelism)
     }
 
     /// Lookup an account in the `AccountsHistory` table
@@ -134,11 +156,105 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
             );
         }
 
+        if let Some(parallelism) = self.parallelism() {
+            return self
+                .revert_state_parallel::<<Provider::StateCommitment as StateCommitment>::KeyHasher>(
+                    parallelism,
+                )
+        }
+
         Ok(HashedPostState::from_reverts::<
             <Provider::StateCommitment as StateCommitment>::KeyHasher,
         >(self.tx(), self.block_number)?)
     }
 
+    /// Retrieve revert hashed state by splitting the changesets from this block up to the last
+    /// written changeset into ranges that are walked concurrently, each with its own cursors on
+    /// the transaction of this provider.
+    ///
+    /// Every range only keeps the first value it observes for each account and storage slot, so
+    /// merging the ranges in ascending order yields the same result as a sequential walk.
+    fn revert_state_parallel<KH: KeyHasher>(
+        &self,
+        parallelism: usize,
+    ) -> ProviderResult<HashedPostState> {
+        let tx = self.tx();
+        let last_account_changeset =
+            tx.cursor_read::<tables::AccountChangeSets>()?.last()?.map(|(block, _)| block);
+        let last_storage_changeset = tx
+            .cursor_read::<tables::StorageChangeSets>()?
+            .last()?
+            .map(|(key, _)| key.block_number());
+        let Some(last_changeset) = last_account_changeset.max(last_storage_changeset) else {
+            return Ok(HashedPostState::default())
+        };
+        let ranges = changeset_walk_ranges(self.block_number, last_changeset, parallelism);
+
+        let reverts = ranges
+            .into_par_iter()
+            .map(|range| -> ProviderResult<_> {
+                let mut accounts = AddressMap::default();
+                let mut accounts_cursor = tx.cursor_read::<tables::AccountChangeSets>()?;
+                for entry in accounts_cursor.walk_range(range.clone())? {
+                    let (_, AccountBeforeTx { address, info }) = entry?;
+                    accounts.entry(address).or_insert(info);
+                }
+
+                let mut storages = AddressMap::<B256Map<U256>>::default();
+                let mut storages_cursor = tx.cursor_read::<tables::StorageChangeSets>()?;
+                for entry in storages_cursor.walk_range(BlockNumberAddress::range(range))? {
+                    let (BlockNumberAddress((_, address)), storage) = entry?;
+                    let account_storage = storages.entry(address).or_default();
+                    account_storage.entry(storage.key).or_insert(storage.value);
+                }
+
+                Ok((accounts, storages))
+            })
+            .collect::<ProviderResult<Vec<_>>>()?;
+
+        // Merge in ascending block order, values from earlier ranges take precedence.
+        let mut accounts = AddressMap::default();
+        let mut storages = AddressMap::<B256Map<U256>>::default();
+        for (range_accounts, range_storages) in reverts {
+            for (address, info) in range_accounts {
+                accounts.entry(address).or_insert(info);
+            }
+            for (address, range_storage) in range_storages {
+                let storage = storages.entry(address).or_default();
+                for (slot, value) in range_storage {
+                    storage.entry(slot).or_insert(value);
+                }
+            }
+        }
+
+        let hashed_accounts = accounts
+            .into_iter()
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|(address, info)| (KH::hash_key(address), info))
+            .collect::<Vec<_>>();
+        let hashed_storages = storages
+            .into_iter()
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|(address, storage)| {
+                (
+                    KH::hash_key(address),
+                    // Storage reverts are never wiped, see `HashedPostState::from_reverts`.
+                    HashedStorage::from_iter(
+                        false,
+                        storage.into_iter().map(|(slot, value)| (KH::hash_key(slot), value)),
+                    ),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Ok(HashedPostState {
+            accounts: hashed_accounts.into_iter().collect(),
+            storages: hashed_storages.into_iter().collect(),
+        })
+    }
+
     /// Retrieve revert hashed storage for this history provider and target address.
     fn revert_storage(&self, address: Address) -> ProviderResult<HashedStorage> {
         if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
@@ -358,6 +474,20 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StorageRoo
         StorageProof::overlay_storage_multiproof(self.tx(), address, slots, revert_storage)
             .map_err(ProviderError::from)
     }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStorageRange> {
+        let mut revert_storage = self.revert_storage(address)?;
+        revert_storage.extend(&hashed_storage);
+        revert_storage
+            .overlay_range(self.tx(), address, start, limit)
+            .map_err(|err| ProviderError::Database(err.into()))
+    }
 }
 
 impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProofProvider
@@ -438,6 +568,53 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentPr
     fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
         self.tx().get_by_encoded_key::<tables::Bytecodes>(code_hash).map_err(Into::into)
     }
+
+    /// Get the accounts and storage slots of the targets, split across the workers if there are
+    /// any.
+    fn accounts_with_storage(
+        &self,
+        targets: &[(Address, Vec<StorageKey>)],
+    ) -> ProviderResult<Vec<(Option<Account>, Vec<Option<StorageValue>>)>> {
+        fn lookup<P: StateProvider>(
+            state: &P,
+            targets: &[(Address, Vec<StorageKey>)],
+        ) -> ProviderResult<Vec<(Option<Account>, Vec<Option<StorageValue>>)>> {
+            targets
+                .iter()
+                .map(|(address, slots)| {
+                    let storage = slots
+                        .iter()
+                        .map(|slot| state.storage(*address, *slot))
+                        .collect::<ProviderResult<_>>()?;
+                    Ok((state.basic_account(address)?, storage))
+                })
+                .collect()
+        }
+
+        let Some(parallelism) = self.parallelism().filter(|_| targets.len() > 1) else {
+            return lookup(self, targets)
+        };
+
+        // The workers read through the transaction of this provider, but don't split their
+        // lookups any further.
+        let (provider, block_number, lowest_available_blocks) =
+            (self.provider, self.block_number, self.lowest_available_blocks);
+        let chunks = targets
+            .par_chunks(targets.len().div_ceil(parallelism))
+            .map(|targets| {
+                lookup(
+                    &HistoricalStateProviderRef::new_with_lowest_available_blocks(
+                        provider,
+                        block_number,
+                        lowest_available_blocks,
+                    ),
+                    targets,
+                )
+            })
+            .collect::<ProviderResult<Vec<_>>>()?;
+
+        Ok(chunks.into_iter().flatten().collect())
+    }
 }
 
 impl<Provider: StateCommitmentProvider> StateCommitmentProvider
@@ -456,6 +633,8 @@ pub struct HistoricalStateProvider<Provider> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// Number of workers that changeset walks and state lookups are split across.
+    parallelism: usize,
 }
 
 impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
@@ -463,7 +642,15 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
 {
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: Provider, block_number: BlockNumber) -> Self {
-        Self { provider, block_number, lowest_available_blocks: Default::default() }
+        Self { provider, block_number, lowest_available_blocks: Default::default(), parallelism: 1 }
+    }
+
+    /// Set the number of workers that changeset walks and state lookups are split across.
+    ///
+    /// See [`HistoricalStateProviderRef::with_changeset_walk_parallelism`].
+    pub const fn with_changeset_walk_parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = parallelism;
+        self
     }
 
     /// Set the lowest block number at which the account history is available.
@@ -492,6 +679,7 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
             self.block_number,
             self.lowest_available_blocks,
         )
+        .with_changeset_walk_parallelism(self.parallelism)
     }
 }
 
@@ -504,6 +692,24 @@ impl<Provider: StateCommitmentProvider> StateCommitmentProvider
 // Delegates all provider impls to [HistoricalStateProviderRef]
 delegate_provider_impls!(HistoricalStateProvider<Provider> where [Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentProvider]);
 
+/// Splits the changeset block range `from..=to` into at most `parallelism` contiguous ranges of
+/// roughly equal size, in ascending order.
+fn changeset_walk_ranges(
+    from: BlockNumber,
+    to: BlockNumber,
+    parallelism: usize,
+) -> Vec<RangeInclusive<BlockNumber>> {
+    if from > to {
+        return Vec::new()
+    }
+
+    let chunk_size = (to - from + 1).div_ceil(parallelism.max(1) as u64);
+    (from..=to)
+        .step_by(chunk_size as usize)
+        .map(|start| start..=start.saturating_add(chunk_size - 1).min(to))
+        .collect()
+}
+
 /// Lowest blocks at which different parts of the state are available.
 /// They may be [Some] if pruning is enabled.
 #[derive(Clone, Copy, Debug, Default)]
@@ -535,7 +741,7 @@ impl LowestAvailableBlocks {
 #[cfg(test)]
 mod tests {
     use crate::{
-        providers::state::historical::{HistoryInfo, LowestAvailableBlocks},
+        providers::state::historical::{changeset_walk_ranges, HistoryInfo, LowestAvailableBlocks},
         test_utils::create_test_provider_factory,
         AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, StateProvider,
     };
@@ -836,4 +1042,58 @@ mod tests {
             Ok(HistoryInfo::MaybeInPlainState)
         ));
     }
+
+    #[test]
+    fn history_provider_parallel_revert_state() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+
+        for block in 1..=20u64 {
+            let info = Some(Account { nonce: block, ..Default::default() });
+            tx.put::<tables::AccountChangeSets>(block, AccountBeforeTx { address: ADDRESS, info })
+                .unwrap();
+            tx.put::<tables::StorageChangeSets>(
+                (block, ADDRESS).into(),
+                StorageEntry { key: STORAGE, value: U256::from(block) },
+            )
+            .unwrap();
+        }
+        tx.put::<tables::AccountChangeSets>(
+            17,
+            AccountBeforeTx { address: HIGHER_ADDRESS, info: None },
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        let db = factory.provider().unwrap();
+        for block_number in [1, 5, 11, 17, 20, 21] {
+            let sequential =
+                HistoricalStateProviderRef::new(&db, block_number).revert_state().unwrap();
+            for workers in [2, 3, 8, 64] {
+                let factory = factory.clone();
+                let parallel_providers =
+                    ParallelProviders::new(workers, move || factory.provider());
+                let state = HistoricalStateProviderRef::new(&db, block_number)
+                    .with_parallel_providers(Some(&parallel_providers));
+                assert_eq!(state.revert_state().unwrap(), sequential);
+
+                let targets =
+                    [(ADDRESS, vec![STORAGE]), (HIGHER_ADDRESS, vec![]), (ADDRESS, vec![])];
+                assert_eq!(
+                    state.accounts_with_storage(&targets).unwrap(),
+                    HistoricalStateProviderRef::new(&db, block_number)
+                        .accounts_with_storage(&targets)
+                        .unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn changeset_walk_ranges_cover_range() {
+        assert!(changeset_walk_ranges(10, 9, 4).is_empty());
+        assert_eq!(changeset_walk_ranges(1, 10, 1), vec![1..=10]);
+        assert_eq!(changeset_walk_ranges(1, 10, 3), vec![1..=4, 5..=8, 9..=10]);
+        assert_eq!(changeset_walk_ranges(5, 6, 8), vec![5..=5, 6..=6]);
+    }
 }
@@ -13,12 +13,12 @@ use reth_trie::{
     proof::{Proof, StorageProof},
     updates::TrieUpdates,
     witness::TrieWitness,
-    AccountProof, HashedPostState, HashedStorage, MultiProof, MultiProofTargets, StateRoot,
-    StorageMultiProof, StorageRoot, TrieInput,
+    AccountProof, HashedPostState, HashedStorage, HashedStorageRange, MultiProof,
+    MultiProofTargets, StateRoot, StorageMultiProof, StorageRoot, TrieInput,
 };
 use reth_trie_db::{
-    DatabaseProof, DatabaseStateRoot, DatabaseStorageProof, DatabaseStorageRoot,
-    DatabaseTrieWitness, StateCommitment,
+    DatabaseHashedStorage, DatabaseProof, DatabaseStateRoot, DatabaseStorageProof,
+    DatabaseStorageRoot, DatabaseTrieWitness, StateCommitment,
 };
 
 /// State provider over latest state that takes tx reference.
@@ -121,6 +121,18 @@ impl<Provider: DBProvider + StateCommitmentProvider> StorageRootProvider
         StorageProof::overlay_storage_multiproof(self.tx(), address, slots, hashed_storage)
             .map_err(ProviderError::from)
     }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStorageRange> {
+        hashed_storage
+            .overlay_range(self.tx(), address, start, limit)
+            .map_err(|err| ProviderError::Database(err.into()))
+    }
 }
 
 impl<Provider: DBProvider + StateCommitmentProvider> StateProofProvider
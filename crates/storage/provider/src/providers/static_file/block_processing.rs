@@ -0,0 +1,197 @@
+use alloy_primitives::{BlockNumber, B256};
+use parking_lot::Mutex;
+use reth_storage_api::BlockProcessingStats;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufWriter, Read, Seek, SeekFrom, Write},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// The name of the file of the block processing history, in the static files directory.
+const BLOCK_PROCESSING_FILE_NAME: &str = "block_processing";
+
+/// The size of a record: block number, hash, gas used, and execution and state root time in
+/// microseconds.
+const RECORD_SIZE: u64 = 8 + 32 + 8 + 8 + 8;
+
+/// Append-only segment of the processing timings of the blocks, ordered by block number.
+///
+/// Every block is stored as a fixed size record, so a range of blocks is looked up by a binary
+/// search over the records. A partially written record at the end of the file, e.g. after a crash,
+/// is ignored and overwritten by the next append.
+#[derive(Debug)]
+pub struct BlockProcessingHistory {
+    /// The path of the file.
+    path: PathBuf,
+    /// Serializes the writes to the file.
+    write_lock: Mutex<()>,
+}
+
+impl BlockProcessingHistory {
+    /// Creates the history stored in the given static files directory.
+    pub(crate) fn new(directory: &Path) -> Self {
+        Self { path: directory.join(BLOCK_PROCESSING_FILE_NAME), write_lock: Mutex::new(()) }
+    }
+
+    /// Returns the path of the file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the recorded blocks in the given range.
+    pub fn read(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockProcessingStats>> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(ProviderError::other(err)),
+        };
+        let records = records(&file)?;
+
+        let mut index = find_record(&mut file, records, *range.start())?;
+        file.seek(SeekFrom::Start(index * RECORD_SIZE)).map_err(ProviderError::other)?;
+
+        let mut stats = Vec::new();
+        while index < records {
+            let record = read_record(&mut file)?;
+            if record.number > *range.end() {
+                break
+            }
+            stats.push(record);
+            index += 1;
+        }
+        Ok(stats)
+    }
+
+    /// Appends the given blocks, replacing all recorded blocks at or above the first one.
+    pub fn append(&self, stats: &[BlockProcessingStats]) -> ProviderResult<()> {
+        let Some(first) = stats.first() else { return Ok(()) };
+
+        let _lock = self.write_lock.lock();
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.path)
+            .map_err(ProviderError::other)?;
+
+        let index = find_record(&mut file, records(&file)?, first.number)?;
+        file.set_len(index * RECORD_SIZE).map_err(ProviderError::other)?;
+        file.seek(SeekFrom::End(0)).map_err(ProviderError::other)?;
+
+        let mut writer = BufWriter::new(file);
+        for record in stats {
+            writer.write_all(&encode_record(record)).map_err(ProviderError::other)?;
+        }
+        writer.flush().map_err(ProviderError::other)
+    }
+
+    /// Removes all recorded blocks above the given block.
+    pub fn unwind(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        let _lock = self.write_lock.lock();
+        let mut file = match OpenOptions::new().read(true).write(true).open(&self.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(ProviderError::other(err)),
+        };
+
+        let index = find_record(&mut file, records(&file)?, block_number + 1)?;
+        file.set_len(index * RECORD_SIZE).map_err(ProviderError::other)
+    }
+}
+
+/// Returns the number of complete records in the file.
+fn records(file: &File) -> ProviderResult<u64> {
+    Ok(file.metadata().map_err(ProviderError::other)?.len() / RECORD_SIZE)
+}
+
+/// Returns the index of the first record at or above the given block number.
+fn find_record(file: &mut File, records: u64, block_number: BlockNumber) -> ProviderResult<u64> {
+    let (mut low, mut high) = (0, records);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        file.seek(SeekFrom::Start(mid * RECORD_SIZE)).map_err(ProviderError::other)?;
+        let mut number = [0; 8];
+        file.read_exact(&mut number).map_err(ProviderError::other)?;
+        if BlockNumber::from_be_bytes(number) < block_number {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    Ok(low)
+}
+
+fn read_record(file: &mut File) -> ProviderResult<BlockProcessingStats> {
+    let mut buf = [0; RECORD_SIZE as usize];
+    file.read_exact(&mut buf).map_err(ProviderError::other)?;
+
+    let u64_at = |offset: usize| u64::from_be_bytes(buf[offset..offset + 8].try_into().unwrap());
+    Ok(BlockProcessingStats {
+        number: u64_at(0),
+        hash: B256::from_slice(&buf[8..40]),
+        gas_used: u64_at(40),
+        execution_time: Duration::from_micros(u64_at(48)),
+        state_root_time: Duration::from_micros(u64_at(56)),
+    })
+}
+
+fn encode_record(stats: &BlockProcessingStats) -> [u8; RECORD_SIZE as usize] {
+    let micros = |duration: Duration| u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+
+    let mut buf = [0; RECORD_SIZE as usize];
+    buf[..8].copy_from_slice(&stats.number.to_be_bytes());
+    buf[8..40].copy_from_slice(stats.hash.as_slice());
+    buf[40..48].copy_from_slice(&stats.gas_used.to_be_bytes());
+    buf[48..56].copy_from_slice(&micros(stats.execution_time).to_be_bytes());
+    buf[56..].copy_from_slice(&micros(stats.state_root_time).to_be_bytes());
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(number: BlockNumber) -> BlockProcessingStats {
+        BlockProcessingStats {
+            number,
+            hash: B256::with_last_byte(number as u8),
+            gas_used: number * 1_000,
+            execution_time: Duration::from_millis(number),
+            state_root_time: Duration::from_micros(number),
+        }
+    }
+
+    #[test]
+    fn append_read_and_unwind() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = BlockProcessingHistory::new(dir.path());
+        assert!(history.read(0..=10).unwrap().is_empty());
+
+        history.append(&[stats(1), stats(2), stats(4), stats(5)]).unwrap();
+        assert_eq!(history.read(2..=4).unwrap(), vec![stats(2), stats(4)]);
+        assert_eq!(history.read(3..=10).unwrap(), vec![stats(4), stats(5)]);
+
+        // blocks at or above the first appended block are replaced
+        let mut reorged = stats(4);
+        reorged.hash = B256::repeat_byte(0xff);
+        history.append(&[reorged]).unwrap();
+        assert_eq!(history.read(0..=10).unwrap(), vec![stats(1), stats(2), reorged]);
+
+        history.unwind(1).unwrap();
+        assert_eq!(history.read(0..=10).unwrap(), vec![stats(1)]);
+
+        // a partially written record is ignored
+        let mut file = OpenOptions::new().append(true).open(history.path()).unwrap();
+        file.write_all(&[1, 2, 3]).unwrap();
+        assert_eq!(history.read(0..=10).unwrap(), vec![stats(1)]);
+        history.append(&[stats(2)]).unwrap();
+        assert_eq!(history.read(0..=10).unwrap(), vec![stats(1), stats(2)]);
+    }
+}
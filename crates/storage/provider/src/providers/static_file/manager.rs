@@ -1,6 +1,7 @@
 use super::{
-    metrics::StaticFileProviderMetrics, writer::StaticFileWriters, LoadedJar,
-    StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
+    metrics::StaticFileProviderMetrics, writer::StaticFileWriters, BlockProcessingHistory,
+    LoadedJar, ManifestMismatch, StaticFileJarProvider, StaticFileManifest, StaticFileProviderRW,
+    StaticFileProviderRWRefMut, VerificationMode,
 };
 use crate::{
     to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, HeaderProvider,
@@ -14,7 +15,7 @@ use alloy_primitives::{
 };
 use dashmap::DashMap;
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, MutexGuard, RwLock};
 use reth_chainspec::{ChainInfo, ChainSpecProvider, EthChainSpec};
 use reth_db::{
     lockfile::StorageLock,
@@ -51,7 +52,7 @@ use std::{
     path::{Path, PathBuf},
     sync::{mpsc, Arc},
 };
-use tracing::{info, trace, warn};
+use tracing::{debug, info, trace, warn};
 
 /// Alias type for a map that can be queried for block ranges from a transaction
 /// segment respectively. It uses `TxNumber` to represent the transaction end of a static file
@@ -231,10 +232,16 @@ pub struct StaticFileProviderInner<N> {
     map: DashMap<(BlockNumber, StaticFileSegment), LoadedJar>,
     /// Max static file block for each segment
     static_files_max_block: RwLock<HashMap<StaticFileSegment, u64>>,
+    /// Min static file block and transaction for each segment. Anything below has been expired.
+    static_files_min: RwLock<HashMap<StaticFileSegment, (BlockNumber, Option<TxNumber>)>>,
     /// Available static file block ranges on disk indexed by max transactions.
     static_files_tx_index: RwLock<SegmentRanges>,
     /// Directory where `static_files` are located
     path: PathBuf,
+    /// The processing timings of the blocks, stored next to the static files.
+    block_processing_history: BlockProcessingHistory,
+    /// Serializes the updates of the [`StaticFileManifest`].
+    manifest_lock: Mutex<()>,
     /// Maintains a writer set of [`StaticFileSegment`].
     writers: StaticFileWriters<N>,
     /// Metrics for the static files.
@@ -262,8 +269,11 @@ impl<N: NodePrimitives> StaticFileProviderInner<N> {
             map: Default::default(),
             writers: Default::default(),
             static_files_max_block: Default::default(),
+            static_files_min: Default::default(),
             static_files_tx_index: Default::default(),
             path: path.as_ref().to_path_buf(),
+            block_processing_history: BlockProcessingHistory::new(path.as_ref()),
+            manifest_lock: Mutex::new(()),
             metrics: None,
             access,
             blocks_per_file: DEFAULT_BLOCKS_PER_STATIC_FILE,
@@ -426,6 +436,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     ///
     /// CAUTION: destructive. Deletes files on disk.
     pub fn delete_jar(&self, segment: StaticFileSegment, block: BlockNumber) -> ProviderResult<()> {
+        let manifest_lock = self.manifest_lock.lock();
         let fixed_block_range = self.find_fixed_range(block);
         let key = (fixed_block_range.end(), segment);
         let jar = if let Some((_, jar)) = self.map.remove(&key) {
@@ -436,6 +447,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         };
 
         jar.delete().map_err(ProviderError::other)?;
+        self.remove_from_manifest(&manifest_lock, [segment.filename(&fixed_block_range)]);
+        drop(manifest_lock);
 
         let mut segment_max_block = None;
         if fixed_block_range.start() > 0 {
@@ -446,6 +459,247 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(())
     }
 
+    /// Given a segment and block, it deletes all jars that only contain blocks below it and
+    /// returns their fixed block ranges. The highest jar of the segment is never deleted.
+    ///
+    /// This is used to expire history, e.g. according to EIP-4444. Any data of the deleted jars is
+    /// reported as missing afterwards, see [`Self::is_block_expired`].
+    ///
+    /// CAUTION: destructive. Deletes files on disk.
+    pub fn delete_segment_below_block(
+        &self,
+        segment: StaticFileSegment,
+        block: BlockNumber,
+    ) -> ProviderResult<Vec<SegmentRangeInclusive>> {
+        let ranges = iter_static_files(&self.path)
+            .map_err(ProviderError::other)?
+            .remove(&segment)
+            .unwrap_or_default();
+
+        let manifest_lock = self.manifest_lock.lock();
+        let mut deleted = Vec::new();
+        for (block_range, _) in ranges.iter().take(ranges.len().saturating_sub(1)) {
+            if block_range.end() >= block {
+                break
+            }
+
+            let fixed_block_range = self.find_fixed_range(block_range.start());
+            let key = (fixed_block_range.end(), segment);
+            let jar = if let Some((_, jar)) = self.map.remove(&key) {
+                jar.jar
+            } else {
+                NippyJar::<SegmentHeader>::load(
+                    &self.path.join(segment.filename(&fixed_block_range)),
+                )
+                .map_err(ProviderError::other)?
+            };
+
+            jar.delete().map_err(ProviderError::other)?;
+            deleted.push(fixed_block_range);
+        }
+
+        if !deleted.is_empty() {
+            info!(target: "provider::static_file", ?segment, ?deleted, "Deleted expired static files");
+            self.remove_from_manifest(
+                &manifest_lock,
+                deleted.iter().map(|range| segment.filename(range)),
+            );
+            drop(manifest_lock);
+            self.initialize_index()?;
+        }
+
+        Ok(deleted)
+    }
+
+    /// Removes the given jars from the [`StaticFileManifest`], after they were deleted.
+    ///
+    /// The manifest lock must be held while the jars are deleted, so that a concurrent
+    /// verification doesn't report the deleted files as missing, see [`Self::check_manifest`].
+    /// The manifest isn't required for the deletion, so a failure to update it is only logged.
+    fn remove_from_manifest(
+        &self,
+        _manifest_lock: &MutexGuard<'_, ()>,
+        jars: impl IntoIterator<Item = String>,
+    ) {
+        let result = StaticFileManifest::load(&self.path).and_then(|mut manifest| {
+            let mut removed = false;
+            for jar in jars {
+                removed |= manifest.remove_jar(&jar);
+            }
+            if removed {
+                manifest.save()?;
+            }
+            Ok(())
+        });
+        if let Err(err) = result {
+            warn!(
+                target: "provider::static_file",
+                %err,
+                "Failed to remove deleted static files from the manifest"
+            );
+        }
+    }
+
+    /// Removes the jar of the given fixed block range from the [`StaticFileManifest`], since it's
+    /// written to again after an unwind.
+    pub(crate) fn unrecord_jar(
+        &self,
+        segment: StaticFileSegment,
+        fixed_block_range: &SegmentRangeInclusive,
+    ) {
+        let manifest_lock = self.manifest_lock.lock();
+        self.remove_from_manifest(&manifest_lock, [segment.filename(fixed_block_range)]);
+    }
+
+    /// Records the checksums of the jar of the given fixed block range in the
+    /// [`StaticFileManifest`], once the writer finalized it.
+    ///
+    /// The jar is hashed on its own thread, so that finalizing it doesn't stall the writer. A
+    /// failure to record it is only logged, since finalized jars are recorded again on startup,
+    /// see [`Self::update_manifest`].
+    pub(crate) fn record_finalized_jar(
+        &self,
+        segment: StaticFileSegment,
+        fixed_block_range: &SegmentRangeInclusive,
+    ) {
+        let provider = self.clone();
+        let jar = segment.filename(fixed_block_range);
+        let result = std::thread::Builder::new()
+            .name("static-file-manifest".to_string())
+            .spawn(move || {
+                if let Err(err) = provider.record_jar(&jar) {
+                    warn!(target: "provider::static_file", %err, %jar, "Failed to record static file checksums");
+                }
+            });
+        if let Err(err) = result {
+            warn!(target: "provider::static_file", %err, "Failed to spawn static file manifest thread");
+        }
+    }
+
+    /// Hashes the files of the given jar that aren't recorded yet, and merges them into the
+    /// [`StaticFileManifest`] unless the jar was deleted or truncated in the meantime.
+    fn record_jar(&self, jar: &str) -> ProviderResult<()> {
+        let mut hashed = StaticFileManifest::load(&self.path)?;
+        if hashed.record_jar(jar)? == 0 {
+            return Ok(())
+        }
+
+        let _lock = self.manifest_lock.lock();
+        if !hashed.has_jar_sizes(jar) {
+            return Ok(())
+        }
+        let mut manifest = StaticFileManifest::load(&self.path)?;
+        let recorded = manifest.merge_jar(&hashed, jar);
+        if recorded > 0 {
+            manifest.save()?;
+            debug!(target: "provider::static_file", %jar, recorded, "Recorded static file checksums");
+        }
+        Ok(())
+    }
+
+    /// Returns the data file names of the finalized jars, and the fixed block range start of the
+    /// highest jar of each segment.
+    ///
+    /// Only the highest jar of a segment is appended to or truncated by an unwind, so all jars
+    /// below it are finalized.
+    fn finalized_jars(
+        &self,
+    ) -> ProviderResult<(Vec<String>, HashMap<StaticFileSegment, BlockNumber>)> {
+        let mut finalized = Vec::new();
+        let mut highest = HashMap::default();
+        for (segment, ranges) in iter_static_files(&self.path).map_err(ProviderError::other)? {
+            let Some(((last_block_range, _), ranges)) = ranges.split_last() else { continue };
+            for (block_range, _) in ranges {
+                finalized.push(segment.filename(&self.find_fixed_range(block_range.start())));
+            }
+            highest.insert(segment, self.find_fixed_range(last_block_range.start()).start());
+        }
+        Ok((finalized, highest))
+    }
+
+    /// Returns the [`StaticFileManifest`] of the finalized jars.
+    ///
+    /// Jars that were finalized when they were recorded, but are written to again after an
+    /// unwind, are left out.
+    pub fn manifest(&self) -> ProviderResult<StaticFileManifest> {
+        let (_, highest) = self.finalized_jars()?;
+        let mut manifest = StaticFileManifest::load(&self.path)?;
+        manifest.retain_jars(|segment, block_range| is_finalized(&highest, segment, block_range));
+        Ok(manifest)
+    }
+
+    /// Records the checksums of the finalized jars that aren't in the [`StaticFileManifest`] yet,
+    /// and removes the jars that are written to again after an unwind.
+    ///
+    /// The writer records jars as it finalizes them, see [`Self::record_finalized_jar`], so this
+    /// only hashes the jars that weren't recorded, e.g. all existing static files on the first
+    /// update. The files are hashed without holding the manifest lock, which is only taken to merge
+    /// the checksums into the manifest, so jars that are deleted or truncated in the meantime are
+    /// not recorded.
+    pub fn update_manifest(&self) -> ProviderResult<StaticFileManifest> {
+        let (finalized, _) = self.finalized_jars()?;
+        let mut hashed = StaticFileManifest::load(&self.path)?;
+        for jar in &finalized {
+            hashed.record_jar(jar)?;
+        }
+
+        let _lock = self.manifest_lock.lock();
+        let (finalized, highest) = self.finalized_jars()?;
+        let mut manifest = StaticFileManifest::load(&self.path)?;
+        let mut changed = manifest
+            .retain_jars(|segment, block_range| is_finalized(&highest, segment, block_range));
+        let mut recorded = 0;
+        for jar in finalized {
+            if hashed.has_jar_sizes(&jar) {
+                recorded += manifest.merge_jar(&hashed, &jar);
+            }
+        }
+        if recorded > 0 {
+            info!(target: "provider::static_file", recorded, "Recorded static file checksums");
+            changed = true;
+        }
+        if changed {
+            manifest.save()?;
+        }
+
+        Ok(manifest)
+    }
+
+    /// Verifies the finalized jars against the [`StaticFileManifest`] and logs any mismatch. The
+    /// manifest is updated first if the provider has read-write access.
+    ///
+    /// Returns the mismatching files.
+    pub fn check_manifest(&self, mode: VerificationMode) -> ProviderResult<Vec<ManifestMismatch>> {
+        let manifest =
+            if self.access.is_read_write() { self.update_manifest()? } else { self.manifest()? };
+
+        info!(target: "reth::cli", files = manifest.len(), ?mode, "Verifying static files.");
+        let mut mismatches = manifest.verify(mode)?;
+
+        // Jars are removed from the manifest while they're deleted, so missing files that aren't
+        // recorded anymore were deleted concurrently, e.g. by history expiry.
+        if mismatches.iter().any(|mismatch| matches!(mismatch, ManifestMismatch::Missing { .. })) {
+            let _lock = self.manifest_lock.lock();
+            let recorded = StaticFileManifest::load(&self.path)?;
+            mismatches.retain(|mismatch| match mismatch {
+                ManifestMismatch::Missing { file } => recorded.contains(file),
+                _ => true,
+            });
+        }
+        for mismatch in &mismatches {
+            warn!(target: "reth::cli", %mismatch, "Static file doesn't match the manifest");
+        }
+        if !mismatches.is_empty() {
+            warn!(
+                target: "reth::cli",
+                mismatches = mismatches.len(),
+                "Static files are corrupted, run `reth db verify-static-files --full` to list them"
+            );
+        }
+
+        Ok(mismatches)
+    }
+
     /// Given a segment and block range it returns a cached
     /// [`StaticFileJarProvider`]. TODO(joshie): we should check the size and pop N if there's too
     /// many.
@@ -481,6 +735,10 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         segment: StaticFileSegment,
         block: u64,
     ) -> Option<SegmentRangeInclusive> {
+        if self.is_block_expired(segment, block) {
+            return None
+        }
+
         self.static_files_max_block
             .read()
             .get(&segment)
@@ -495,6 +753,10 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         segment: StaticFileSegment,
         tx: u64,
     ) -> Option<SegmentRangeInclusive> {
+        if self.get_lowest_static_file_tx(segment).is_some_and(|lowest| tx < lowest) {
+            return None
+        }
+
         let static_files = self.static_files_tx_index.read();
         let segment_static_files = static_files.get(&segment)?;
 
@@ -528,6 +790,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     ) -> ProviderResult<()> {
         let mut max_block = self.static_files_max_block.write();
         let mut tx_index = self.static_files_tx_index.write();
+        let mut min = self.static_files_min.write();
 
         match segment_max_block {
             Some(segment_max_block) => {
@@ -540,6 +803,11 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 )
                 .map_err(ProviderError::other)?;
 
+                // The first static file of the segment is being written.
+                min.entry(segment).or_insert_with(|| {
+                    (fixed_range.start(), jar.user_header().tx_range().map(|range| range.start()))
+                });
+
                 // Updates the tx index by first removing all entries which have a higher
                 // block_start than our current static file.
                 if let Some(tx_range) = jar.user_header().tx_range() {
@@ -589,6 +857,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             None => {
                 tx_index.remove(&segment);
                 max_block.remove(&segment);
+                min.remove(&segment);
             }
         };
 
@@ -599,9 +868,11 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     pub fn initialize_index(&self) -> ProviderResult<()> {
         let mut max_block = self.static_files_max_block.write();
         let mut tx_index = self.static_files_tx_index.write();
+        let mut min = self.static_files_min.write();
 
         max_block.clear();
         tx_index.clear();
+        min.clear();
 
         for (segment, ranges) in iter_static_files(&self.path).map_err(ProviderError::other)? {
             // Update last block for each segment
@@ -609,6 +880,11 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 max_block.insert(segment, block_range.end());
             }
 
+            // Update first block and transaction for each segment
+            if let Some((block_range, tx_range)) = ranges.first() {
+                min.insert(segment, (block_range.start(), tx_range.map(|range| range.start())));
+            }
+
             // Update tx -> block_range index
             for (block_range, tx_range) in ranges {
                 if let Some(tx_range) = tx_range {
@@ -946,6 +1222,25 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         self.static_files_max_block.read().get(&segment).copied()
     }
 
+    /// Gets the lowest static file block if it exists for a static file segment.
+    ///
+    /// This is only greater than zero if the blocks below it have been expired, see
+    /// [`Self::delete_segment_below_block`].
+    pub fn get_lowest_static_file_block(&self, segment: StaticFileSegment) -> Option<BlockNumber> {
+        self.static_files_min.read().get(&segment).map(|(block, _)| *block)
+    }
+
+    /// Gets the lowest static file transaction if it exists for a static file segment.
+    pub fn get_lowest_static_file_tx(&self, segment: StaticFileSegment) -> Option<TxNumber> {
+        self.static_files_min.read().get(&segment).and_then(|(_, tx)| *tx)
+    }
+
+    /// Returns `true` if the data of the given block has been expired from the static files of the
+    /// given segment.
+    pub fn is_block_expired(&self, segment: StaticFileSegment, block: BlockNumber) -> bool {
+        self.get_lowest_static_file_block(segment).is_some_and(|lowest| block < lowest)
+    }
+
     /// Gets the highest static file transaction.
     ///
     /// If there is nothing on disk for the given segment, this will return [`None`].
@@ -975,7 +1270,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     ) -> ProviderResult<Option<T>> {
         if let Some(highest_block) = self.get_highest_static_file_block(segment) {
             let mut range = self.find_fixed_range(highest_block);
-            while range.end() > 0 {
+            while range.end() > 0 && !self.is_block_expired(segment, range.start()) {
                 if let Some(res) = func(self.get_or_create_jar_provider(segment, &range)?)? {
                     return Ok(Some(res))
                 }
@@ -1110,6 +1405,11 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         &self.path
     }
 
+    /// Returns the history of the block processing timings.
+    pub fn block_processing_history(&self) -> &BlockProcessingHistory {
+        &self.block_processing_history
+    }
+
     /// Retrieves data from the database or static file, wherever it's available.
     ///
     /// # Arguments
@@ -1130,6 +1430,16 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         FS: Fn(&Self) -> ProviderResult<Option<T>>,
         FD: Fn() -> ProviderResult<Option<T>>,
     {
+        // Data below the lowest static file has been expired.
+        let static_file_lower_bound = if segment.is_block_based() {
+            self.get_lowest_static_file_block(segment)
+        } else {
+            self.get_lowest_static_file_tx(segment)
+        };
+        if static_file_lower_bound.is_some_and(|lower_bound| number < lower_bound) {
+            return Ok(None)
+        }
+
         // If there is, check the maximum block or transaction number of the segment.
         let static_file_upper_bound = if segment.is_block_based() {
             self.get_highest_static_file_block(segment)
@@ -1613,6 +1923,11 @@ impl<N: NodePrimitives> BlockNumReader for StaticFileProvider<N> {
         Err(ProviderError::UnsupportedProvider)
     }
 
+    fn earliest_block_number(&self) -> ProviderResult<BlockNumber> {
+        // block bodies are expired by deleting the transactions below the earliest block
+        Ok(self.get_lowest_static_file_block(StaticFileSegment::Transactions).unwrap_or_default())
+    }
+
     fn block_number(&self, _hash: B256) -> ProviderResult<Option<BlockNumber>> {
         // Required data not present in static_files
         Err(ProviderError::UnsupportedProvider)
@@ -1798,3 +2113,15 @@ where
     tx.encode_2718(rlp_buf);
     Ok((keccak256(rlp_buf), tx_id))
 }
+
+/// Returns `true` if the jar is below the highest jar of its segment, given the fixed block range
+/// start of the highest jar of each segment.
+///
+/// Jars of segments without any jar are kept, so that they are reported as missing.
+fn is_finalized(
+    highest: &HashMap<StaticFileSegment, BlockNumber>,
+    segment: StaticFileSegment,
+    block_range: SegmentRangeInclusive,
+) -> bool {
+    highest.get(&segment).is_none_or(|start| block_range.end() < *start)
+}
@@ -0,0 +1,430 @@
+use alloy_primitives::B256;
+use reth_primitives::{static_file::SegmentRangeInclusive, StaticFileSegment};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs::File,
+    hash::{BuildHasher, RandomState},
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// The name of the file of the manifest, in the static files directory.
+const MANIFEST_FILE_NAME: &str = "manifest";
+
+/// The version of the manifest format, written as its first line.
+const MANIFEST_VERSION: &str = "v1";
+
+/// The size of the chunks that the files are hashed in.
+const CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+
+/// The number of chunks that are hashed by [`VerificationMode::Sample`].
+const SAMPLED_CHUNKS: usize = 16;
+
+/// The extensions of the files of a jar, with the empty extension for the data file.
+const JAR_FILE_EXTENSIONS: [&str; 4] = ["", "off", "conf", "idx"];
+
+/// How thoroughly the static files are verified against the manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationMode {
+    /// Checks that every file exists with its recorded size, and hashes a few random chunks.
+    Sample,
+    /// Checks that every file exists with its recorded size, and hashes all chunks.
+    Full,
+}
+
+/// A static file that doesn't match the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifestMismatch {
+    /// The file doesn't exist.
+    Missing {
+        /// The name of the file.
+        file: String,
+    },
+    /// The file doesn't have the recorded size, e.g. because it was partially copied.
+    Size {
+        /// The name of the file.
+        file: String,
+        /// The recorded size in bytes.
+        expected: u64,
+        /// The size of the file on disk in bytes.
+        actual: u64,
+    },
+    /// The content of a chunk of the file doesn't have the recorded checksum.
+    Checksum {
+        /// The name of the file.
+        file: String,
+        /// The index of the chunk.
+        chunk: usize,
+    },
+}
+
+impl fmt::Display for ManifestMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { file } => write!(f, "{file}: missing"),
+            Self::Size { file, expected, actual } => {
+                write!(f, "{file}: expected {expected} bytes, found {actual} bytes")
+            }
+            Self::Checksum { file, chunk } => write!(
+                f,
+                "{file}: checksum mismatch at bytes {}..{}",
+                *chunk as u64 * CHUNK_SIZE,
+                (*chunk as u64 + 1) * CHUNK_SIZE
+            ),
+        }
+    }
+}
+
+/// The recorded size and chunk checksums of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FileChecksums {
+    /// The size of the file in bytes.
+    size: u64,
+    /// The SHA-256 checksum of every [`CHUNK_SIZE`] chunk of the file.
+    chunks: Vec<B256>,
+}
+
+/// Manifest of the checksums of the finalized static files, to detect bit rot and partially
+/// copied files.
+///
+/// The files are hashed in chunks of [`CHUNK_SIZE`], so a sample of the chunks can be verified
+/// quickly on startup and a mismatch can be located within a file. The manifest is stored as a
+/// text file next to the static files, with a line of the name, size and chunk checksums per file.
+#[derive(Debug)]
+pub struct StaticFileManifest {
+    /// The static files directory.
+    directory: PathBuf,
+    /// The recorded files by name.
+    files: BTreeMap<String, FileChecksums>,
+}
+
+impl StaticFileManifest {
+    /// Loads the manifest of the given static files directory, or returns an empty manifest if
+    /// there is none.
+    pub fn load(directory: &Path) -> ProviderResult<Self> {
+        let mut manifest = Self { directory: directory.to_path_buf(), files: BTreeMap::new() };
+
+        let content = match std::fs::read_to_string(manifest.path()) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(manifest),
+            Err(err) => return Err(ProviderError::other(err)),
+        };
+
+        let mut lines = content.lines();
+        if lines.next() != Some(MANIFEST_VERSION) {
+            return Err(invalid_manifest("unsupported version"))
+        }
+        for line in lines {
+            let mut parts = line.split(' ');
+            let (Some(name), Some(size), chunks) = (parts.next(), parts.next(), parts.next())
+            else {
+                return Err(invalid_manifest(line))
+            };
+            let checksums = FileChecksums {
+                size: size.parse().map_err(|_| invalid_manifest(line))?,
+                chunks: chunks
+                    .unwrap_or_default()
+                    .split(',')
+                    .filter(|chunk| !chunk.is_empty())
+                    .map(B256::from_str)
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| invalid_manifest(line))?,
+            };
+            manifest.files.insert(name.to_string(), checksums);
+        }
+
+        Ok(manifest)
+    }
+
+    /// Writes the manifest to the static files directory.
+    pub fn save(&self) -> ProviderResult<()> {
+        reth_fs_util::atomic_write_file(&self.path(), |file| {
+            let mut writer = io::BufWriter::new(file);
+            writeln!(writer, "{MANIFEST_VERSION}")?;
+            for (name, checksums) in &self.files {
+                write!(writer, "{name} {} ", checksums.size)?;
+                for (index, chunk) in checksums.chunks.iter().enumerate() {
+                    let separator = if index == 0 { "" } else { "," };
+                    write!(writer, "{separator}{chunk}")?;
+                }
+                writeln!(writer)?;
+            }
+            writer.flush()
+        })
+        .map_err(ProviderError::other)
+    }
+
+    /// Returns the path of the manifest file.
+    pub fn path(&self) -> PathBuf {
+        self.directory.join(MANIFEST_FILE_NAME)
+    }
+
+    /// Returns the number of recorded files.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns `true` if no file is recorded.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Returns `true` if the file with the given name is recorded.
+    pub fn contains(&self, file: &str) -> bool {
+        self.files.contains_key(file)
+    }
+
+    /// Returns the total size of the recorded files in bytes.
+    pub fn total_size(&self) -> u64 {
+        self.files.values().map(|checksums| checksums.size).sum()
+    }
+
+    /// Records the checksums of the files of the jar with the given data file name, that aren't
+    /// recorded yet. Returns the number of newly recorded files.
+    pub(crate) fn record_jar(&mut self, jar: &str) -> ProviderResult<usize> {
+        let mut recorded = 0;
+        for extension in JAR_FILE_EXTENSIONS {
+            let name =
+                if extension.is_empty() { jar.to_string() } else { format!("{jar}.{extension}") };
+            if self.files.contains_key(&name) {
+                continue
+            }
+
+            let mut file = match File::open(self.directory.join(&name)) {
+                Ok(file) => file,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(ProviderError::other(err)),
+            };
+            let mut checksums = FileChecksums { size: 0, chunks: Vec::new() };
+            while let Some((size, checksum)) =
+                hash_chunk(&mut file).map_err(ProviderError::other)?
+            {
+                checksums.size += size;
+                checksums.chunks.push(checksum);
+            }
+            self.files.insert(name, checksums);
+            recorded += 1;
+        }
+        Ok(recorded)
+    }
+
+    /// Returns `true` if the recorded files of the jar with the given data file name exist with
+    /// their recorded size, i.e. the jar wasn't deleted or truncated since it was recorded.
+    pub(crate) fn has_jar_sizes(&self, jar: &str) -> bool {
+        self.files.iter().filter(|(name, _)| jar_name(name) == jar).all(|(name, checksums)| {
+            std::fs::metadata(self.directory.join(name))
+                .is_ok_and(|metadata| metadata.len() == checksums.size)
+        })
+    }
+
+    /// Copies the files of the jar with the given data file name that are recorded in `other`, but
+    /// not in this manifest. Returns the number of copied files.
+    pub(crate) fn merge_jar(&mut self, other: &Self, jar: &str) -> usize {
+        let mut merged = 0;
+        for (name, checksums) in other.files.iter().filter(|(name, _)| jar_name(name) == jar) {
+            if !self.files.contains_key(name) {
+                self.files.insert(name.clone(), checksums.clone());
+                merged += 1;
+            }
+        }
+        merged
+    }
+
+    /// Removes the files of the jar with the given data file name. Returns `true` if any file was
+    /// recorded.
+    pub(crate) fn remove_jar(&mut self, jar: &str) -> bool {
+        let len = self.files.len();
+        self.files.retain(|name, _| jar_name(name) != jar);
+        self.files.len() != len
+    }
+
+    /// Retains only the files of the jars for which the predicate returns `true`, given the segment
+    /// and fixed block range of the jar. Returns `true` if any file was removed.
+    pub(crate) fn retain_jars(
+        &mut self,
+        mut f: impl FnMut(StaticFileSegment, SegmentRangeInclusive) -> bool,
+    ) -> bool {
+        let len = self.files.len();
+        self.files.retain(|name, _| {
+            StaticFileSegment::parse_filename(jar_name(name))
+                .is_none_or(|(segment, block_range)| f(segment, block_range))
+        });
+        self.files.len() != len
+    }
+
+    /// Verifies the static files against the manifest, and returns the mismatching files.
+    pub fn verify(&self, mode: VerificationMode) -> ProviderResult<Vec<ManifestMismatch>> {
+        let mut mismatches = Vec::new();
+        let mut sampled = Vec::new();
+        for (name, checksums) in &self.files {
+            let path = self.directory.join(name);
+            let size = match std::fs::metadata(&path) {
+                Ok(metadata) => metadata.len(),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                    mismatches.push(ManifestMismatch::Missing { file: name.clone() });
+                    continue
+                }
+                Err(err) => return Err(ProviderError::other(err)),
+            };
+            if size != checksums.size {
+                mismatches.push(ManifestMismatch::Size {
+                    file: name.clone(),
+                    expected: checksums.size,
+                    actual: size,
+                });
+                continue
+            }
+
+            match mode {
+                VerificationMode::Sample => {
+                    sampled.extend((0..checksums.chunks.len()).map(|chunk| (name, chunk)))
+                }
+                VerificationMode::Full => {
+                    let Some(mut file) = open_recorded(&path)? else {
+                        mismatches.push(ManifestMismatch::Missing { file: name.clone() });
+                        continue
+                    };
+                    for (chunk, expected) in checksums.chunks.iter().enumerate() {
+                        let checksum =
+                            hash_chunk(&mut file).map_err(ProviderError::other)?.map(|(_, c)| c);
+                        if checksum.as_ref() != Some(expected) {
+                            mismatches
+                                .push(ManifestMismatch::Checksum { file: name.clone(), chunk });
+                        }
+                    }
+                }
+            }
+        }
+
+        // Chunks are picked uniformly over all chunks, so that large files are sampled more often.
+        if sampled.len() > SAMPLED_CHUNKS {
+            let random = RandomState::new();
+            sampled = (0..SAMPLED_CHUNKS)
+                .map(|index| sampled[random.hash_one(index) as usize % sampled.len()])
+                .collect();
+        }
+        for (name, chunk) in sampled {
+            let missing = ManifestMismatch::Missing { file: name.clone() };
+            let Some(mut file) = open_recorded(&self.directory.join(name))? else {
+                if !mismatches.contains(&missing) {
+                    mismatches.push(missing);
+                }
+                continue
+            };
+            file.seek(SeekFrom::Start(chunk as u64 * CHUNK_SIZE)).map_err(ProviderError::other)?;
+            let checksum = hash_chunk(&mut file).map_err(ProviderError::other)?.map(|(_, c)| c);
+            let mismatch = ManifestMismatch::Checksum { file: name.clone(), chunk };
+            if checksum.as_ref() != Some(&self.files[name].chunks[chunk]) &&
+                !mismatches.contains(&mismatch)
+            {
+                mismatches.push(mismatch);
+            }
+        }
+
+        Ok(mismatches)
+    }
+}
+
+/// Returns the data file name of the jar that the file with the given name belongs to.
+fn jar_name(file: &str) -> &str {
+    file.split('.').next().unwrap_or(file)
+}
+
+/// Opens a recorded file, or returns [`None`] if it was deleted since its size was checked.
+fn open_recorded(path: &Path) -> ProviderResult<Option<File>> {
+    match File::open(path) {
+        Ok(file) => Ok(Some(file)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(ProviderError::other(err)),
+    }
+}
+
+/// Hashes the next chunk of the reader, and returns its size and checksum, or [`None`] if the
+/// reader is at its end.
+fn hash_chunk(reader: &mut impl Read) -> io::Result<Option<(u64, B256)>> {
+    let mut chunk = reader.by_ref().take(CHUNK_SIZE);
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0; 64 * 1024];
+    let mut size = 0;
+    loop {
+        let read = match chunk.read(&mut buf) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        hasher.update(&buf[..read]);
+        size += read as u64;
+    }
+    Ok((size > 0).then(|| (size, B256::from_slice(&hasher.finalize()))))
+}
+
+fn invalid_manifest(line: &str) -> ProviderError {
+    ProviderError::other(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid static file manifest: {line}"),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_and_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        let jar = StaticFileSegment::Headers.filename(&SegmentRangeInclusive::new(0, 499_999));
+        std::fs::write(dir.path().join(&jar), vec![1; 1024]).unwrap();
+        std::fs::write(dir.path().join(format!("{jar}.conf")), [2; 16]).unwrap();
+        std::fs::write(dir.path().join(format!("{jar}.off")), []).unwrap();
+
+        let mut manifest = StaticFileManifest::load(dir.path()).unwrap();
+        assert!(manifest.is_empty());
+        assert_eq!(manifest.record_jar(&jar).unwrap(), 3);
+        assert_eq!(manifest.record_jar(&jar).unwrap(), 0);
+        assert_eq!(manifest.total_size(), 1024 + 16);
+        manifest.save().unwrap();
+
+        let manifest = StaticFileManifest::load(dir.path()).unwrap();
+        assert_eq!(manifest.len(), 3);
+        assert!(manifest.verify(VerificationMode::Full).unwrap().is_empty());
+        assert!(manifest.verify(VerificationMode::Sample).unwrap().is_empty());
+        assert!(manifest.has_jar_sizes(&jar));
+        assert!(manifest.contains(&format!("{jar}.off")));
+
+        // a flipped bit is detected by both modes, since the sample covers the only chunk
+        let mut data = vec![1; 1024];
+        data[512] ^= 1;
+        std::fs::write(dir.path().join(&jar), data).unwrap();
+        let mismatch = ManifestMismatch::Checksum { file: jar.clone(), chunk: 0 };
+        assert_eq!(manifest.verify(VerificationMode::Full).unwrap(), vec![mismatch.clone()]);
+        assert_eq!(manifest.verify(VerificationMode::Sample).unwrap(), vec![mismatch]);
+
+        // partially copied and missing files
+        std::fs::write(dir.path().join(&jar), [1; 100]).unwrap();
+        std::fs::remove_file(dir.path().join(format!("{jar}.conf"))).unwrap();
+        assert!(!manifest.has_jar_sizes(&jar));
+        assert_eq!(
+            manifest.verify(VerificationMode::Sample).unwrap(),
+            vec![
+                ManifestMismatch::Size { file: jar.clone(), expected: 1024, actual: 100 },
+                ManifestMismatch::Missing { file: format!("{jar}.conf") },
+            ]
+        );
+
+        let mut merged = StaticFileManifest::load(dir.path()).unwrap();
+        merged.files.clear();
+        assert_eq!(merged.merge_jar(&manifest, &jar), 3);
+        assert_eq!(merged.merge_jar(&manifest, &jar), 0);
+        assert_eq!(merged.files, manifest.files);
+
+        let mut manifest = manifest;
+        assert!(!manifest.retain_jars(|_, block_range| block_range.end() < 500_000));
+        assert!(manifest.retain_jars(|_, block_range| block_range.end() < 499_999));
+        assert!(manifest.is_empty());
+    }
+}
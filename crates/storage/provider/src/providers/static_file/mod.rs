@@ -9,6 +9,12 @@ pub use writer::{StaticFileProviderRW, StaticFileProviderRWRefMut};
 
 mod metrics;
 
+mod block_processing;
+pub use block_processing::BlockProcessingHistory;
+
+mod manifest;
+pub use manifest::{ManifestMismatch, StaticFileManifest, VerificationMode};
+
 use reth_nippy_jar::NippyJar;
 use reth_primitives::{static_file::SegmentHeader, StaticFileSegment};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
@@ -549,6 +555,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_tx_based_expiry() {
+        let segments = [StaticFileSegment::Transactions, StaticFileSegment::Receipts];
+        let blocks_per_file = 10;
+        let files_per_range = 3;
+
+        for segment in segments {
+            let (static_dir, _) = create_test_static_files_dir();
+
+            let sf_rw = StaticFileProvider::read_write(&static_dir)
+                .expect("Failed to create static file provider")
+                .with_custom_blocks_per_file(blocks_per_file);
+
+            setup_tx_based_scenario(&sf_rw, segment, blocks_per_file);
+
+            // The highest static file is never deleted.
+            let deleted = sf_rw.delete_segment_below_block(segment, blocks_per_file * 2).unwrap();
+            assert_eq!(
+                deleted,
+                vec![SegmentRangeInclusive::new(0, 9), SegmentRangeInclusive::new(10, 19)]
+            );
+            assert!(sf_rw.delete_segment_below_block(segment, u64::MAX).unwrap().is_empty());
+            assert_eq!(count_files_without_lockfile(&static_dir).unwrap(), files_per_range);
+
+            // The index survives a restart.
+            let sf_rw = StaticFileProvider::<EthPrimitives>::read_write(&static_dir)
+                .expect("Failed to create static file provider")
+                .with_custom_blocks_per_file(blocks_per_file);
+
+            assert_eq!(sf_rw.get_lowest_static_file_block(segment), Some(20));
+            assert_eq!(sf_rw.get_lowest_static_file_tx(segment), Some(9));
+            assert_eq!(sf_rw.get_highest_static_file_tx(segment), Some(9));
+            assert!(sf_rw.is_block_expired(segment, 19));
+            assert!(!sf_rw.is_block_expired(segment, 20));
+
+            // Expired data is reported as missing, the remaining data is still available.
+            if segment.is_receipts() {
+                assert_eq!(sf_rw.receipt(0).unwrap(), None);
+                assert_eq!(sf_rw.receipt(9).unwrap().map(|r| r.cumulative_gas_used), Some(9));
+            } else {
+                assert_eq!(sf_rw.transaction_by_id(0).unwrap(), None);
+                assert_eq!(sf_rw.transaction_by_id(9).unwrap().map(|t| t.nonce()), Some(9));
+            }
+        }
+    }
+
     /// Returns the number of files in the provided path, excluding ".lock" files.
     fn count_files_without_lockfile(path: impl AsRef<Path>) -> eyre::Result<usize> {
         let is_lockfile = |entry: &fs::DirEntry| {
@@ -68,14 +68,32 @@ impl<N: NodePrimitives> StaticFileWriters<N> {
         Ok(StaticFileProviderRWRefMut(write_guard))
     }
 
+    /// Commits the writers of all segments.
+    ///
+    /// Each segment is committed on its own thread, so that a segment which finalizes its file
+    /// doesn't stall the others.
     pub(crate) fn commit(&self) -> ProviderResult<()> {
-        for writer_lock in [&self.headers, &self.transactions, &self.receipts] {
-            let mut writer = writer_lock.write();
-            if let Some(writer) = writer.as_mut() {
-                writer.commit()?;
-            }
-        }
-        Ok(())
+        std::thread::scope(|scope| {
+            let handles = [&self.headers, &self.transactions, &self.receipts]
+                .into_iter()
+                .filter(|writer_lock| writer_lock.read().is_some())
+                .map(|writer_lock| {
+                    std::thread::Builder::new()
+                        .name("static-file-commit".to_string())
+                        .spawn_scoped(scope, move || -> ProviderResult<()> {
+                            if let Some(writer) = writer_lock.write().as_mut() {
+                                writer.commit()?;
+                            }
+                            Ok(())
+                        })
+                        .map_err(ProviderError::other)
+                })
+                .collect::<ProviderResult<Vec<_>>>()?;
+
+            handles.into_iter().try_for_each(|handle| {
+                handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+        })
     }
 }
 
@@ -334,6 +352,8 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
             if last_block == self.writer.user_header().expected_block_end() {
                 // Commits offsets and new user_header to disk
                 self.commit()?;
+                let reader = self.reader();
+                reader.record_finalized_jar(segment, &reader.find_fixed_range(last_block));
 
                 // Opens the new static file
                 let (writer, data_path) =
@@ -469,6 +489,12 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
         self.writer = previous_writer;
         self.writer.set_dirty();
         self.data_path = data_path;
+        // the previous static file isn't finalized anymore
+        let reader = self.reader();
+        reader.unrecord_jar(
+            self.user_header().segment(),
+            &reader.find_fixed_range(self.user_header().expected_block_start()),
+        );
         NippyJar::<SegmentHeader>::load(&current_path)
             .map_err(ProviderError::other)?
             .delete()
@@ -1,8 +1,10 @@
 use crate::{
     traits::{BlockSource, ReceiptProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    ChainSpecProvider, ChangeSetReader, EthStorage, HeaderProvider, ReceiptProviderIdExt,
-    StateProvider, StateProviderBox, StateProviderFactory, StateReader, StateRootProvider,
+    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockProcessingHistoryReader,
+    BlockProcessingStats, BlockReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader,
+    DepositIndexReader, EthStorage, HeaderProvider, ReceiptProviderIdExt, StateProvider,
+    StateProviderBox, StateProviderFactory, StateReader, StateRootProvider,
+    TransactionLookupIndexReader, TransactionLookupIndexWriter, TransactionLookupStatus,
     TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use alloy_consensus::{
@@ -36,8 +38,8 @@ use reth_storage_api::{
 };
 use reth_storage_errors::provider::{ConsistentViewError, ProviderError, ProviderResult};
 use reth_trie::{
-    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
-    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
+    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, HashedStorageRange,
+    MultiProof, MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
 };
 use reth_trie_db::MerklePatriciaTrie;
 use std::{
@@ -741,6 +743,16 @@ impl<T: Transaction, ChainSpec: EthChainSpec> StorageRootProvider
     ) -> ProviderResult<StorageMultiProof> {
         Ok(StorageMultiProof::empty())
     }
+
+    fn storage_range(
+        &self,
+        _address: Address,
+        _hashed_storage: HashedStorage,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<HashedStorageRange> {
+        Ok(HashedStorageRange::default())
+    }
 }
 
 impl<T: Transaction, ChainSpec: EthChainSpec> StateProofProvider for MockEthProvider<T, ChainSpec> {
@@ -894,6 +906,43 @@ impl<T: Transaction, ChainSpec: EthChainSpec> ChangeSetReader for MockEthProvide
     }
 }
 
+impl<T: Transaction, ChainSpec: EthChainSpec> DepositIndexReader for MockEthProvider<T, ChainSpec> {
+    fn deposit_count(&self, _block_number: BlockNumber) -> ProviderResult<Option<u64>> {
+        Ok(None)
+    }
+
+    fn deposit_root(&self, _block_number: BlockNumber) -> ProviderResult<Option<B256>> {
+        Ok(None)
+    }
+}
+
+impl<T: Transaction, ChainSpec: EthChainSpec> BlockProcessingHistoryReader
+    for MockEthProvider<T, ChainSpec>
+{
+    fn block_processing_history(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockProcessingStats>> {
+        Ok(Vec::new())
+    }
+}
+
+impl<T: Transaction, ChainSpec: EthChainSpec> TransactionLookupIndexReader
+    for MockEthProvider<T, ChainSpec>
+{
+    fn transaction_lookup_status(&self) -> ProviderResult<TransactionLookupStatus> {
+        Ok(TransactionLookupStatus::default())
+    }
+}
+
+impl<T: Transaction, ChainSpec: EthChainSpec> TransactionLookupIndexWriter
+    for MockEthProvider<T, ChainSpec>
+{
+    fn index_transaction_hashes(&self, _range: RangeInclusive<BlockNumber>) -> ProviderResult<u64> {
+        Ok(0)
+    }
+}
+
 impl<T: Transaction, ChainSpec: EthChainSpec> StateReader for MockEthProvider<T, ChainSpec> {
     type Receipt = Receipt;
 
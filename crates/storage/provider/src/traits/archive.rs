@@ -0,0 +1,59 @@
+use alloy_eips::{BlockHashOrNumber, BlockId};
+use reth_primitives::{
+    archive::{archive_receipts, archive_recovered_block, archive_sealed_block, AlignedVec},
+    Block, Receipt, SealedBlock,
+};
+use reth_storage_api::{BlockReader, ReceiptProviderIdExt, TransactionVariant};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+
+/// Provider extension serving blocks and receipts in the zero-copy archival format of
+/// [`reth_primitives::archive`].
+///
+/// The returned bytes can be handed out as is, e.g. to RPC endpoints serving many blocks, and
+/// accessed with [`access_block`](reth_primitives::archive::access_block) and
+/// [`access_receipts`](reth_primitives::archive::access_receipts) without copying.
+pub trait ArchivedBlockReader:
+    BlockReader<Block = Block> + ReceiptProviderIdExt<Receipt = Receipt>
+{
+    /// Returns the archived sealed block with matching number or hash.
+    ///
+    /// Returns `None` if block is not found.
+    ///
+    /// Unlike [`Self::archived_recovered_block`], this doesn't read or recover the senders.
+    fn archived_sealed_block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<AlignedVec>> {
+        self.block(id)?
+            .map(|block| {
+                archive_sealed_block(&SealedBlock::seal_slow(block)).map_err(ProviderError::other)
+            })
+            .transpose()
+    }
+
+    /// Returns the archived block with senders with matching number or hash.
+    ///
+    /// Returns the block's transactions in the requested variant.
+    ///
+    /// Returns `None` if block is not found.
+    fn archived_recovered_block(
+        &self,
+        id: BlockHashOrNumber,
+        transaction_kind: TransactionVariant,
+    ) -> ProviderResult<Option<AlignedVec>> {
+        self.sealed_block_with_senders(id, transaction_kind)?
+            .map(|block| archive_recovered_block(&block).map_err(ProviderError::other))
+            .transpose()
+    }
+
+    /// Returns the archived receipts of the given block.
+    ///
+    /// Returns `None` if the block is not found.
+    fn archived_receipts(&self, block: BlockId) -> ProviderResult<Option<AlignedVec>> {
+        self.receipts_by_block_id(block)?
+            .map(|receipts| archive_receipts(&receipts).map_err(ProviderError::other))
+            .transpose()
+    }
+}
+
+impl<T> ArchivedBlockReader for T where
+    T: BlockReader<Block = Block> + ReceiptProviderIdExt<Receipt = Receipt>
+{
+}
@@ -19,3 +19,8 @@ pub use static_file_provider::StaticFileProviderFactory;
 
 mod full;
 pub use full::{FullProvider, FullRpcProvider};
+
+#[cfg(feature = "rkyv")]
+mod archive;
+#[cfg(feature = "rkyv")]
+pub use archive::ArchivedBlockReader;
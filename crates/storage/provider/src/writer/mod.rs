@@ -4,17 +4,78 @@ use crate::{
     StorageLocation, TrieWriter,
 };
 use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumHash;
 use reth_chain_state::{ExecutedBlock, ExecutedBlockWithTrieUpdates};
 use reth_db_api::transaction::{DbTx, DbTxMut};
 use reth_errors::ProviderResult;
+use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{NodePrimitives, StaticFileSegment};
-use reth_primitives_traits::SignedTransaction;
-use reth_storage_api::{DBProvider, StageCheckpointWriter, TransactionsProviderExt};
+use reth_primitives_traits::{RecoveredBlock, SignedTransaction};
+use reth_stages_types::{StageCheckpoint, StageId};
+use reth_storage_api::{
+    DBProvider, DepositIndexWriter, StageCheckpointReader, StageCheckpointWriter,
+    TransactionsProviderExt,
+};
 use reth_storage_errors::writer::UnifiedStorageWriterError;
+use reth_trie::{updates::TrieUpdates, HashedPostStateSorted};
 use revm_database::OriginalValuesKnown;
 use std::sync::Arc;
 use tracing::debug;
 
+/// Executed blocks that are prepared to be written to storage.
+///
+/// Preparing the blocks doesn't access storage, so the blocks can be prepared while previously
+/// written blocks are being committed.
+#[derive(Debug)]
+pub struct PreparedBlocks<N: NodePrimitives> {
+    blocks: Vec<PreparedBlock<N>>,
+}
+
+/// An executed block that is prepared to be written to storage.
+#[derive(Debug)]
+struct PreparedBlock<N: NodePrimitives> {
+    block: RecoveredBlock<N::Block>,
+    execution_output: Arc<ExecutionOutcome<N::Receipt>>,
+    hashed_state: HashedPostStateSorted,
+    trie: Arc<TrieUpdates>,
+}
+
+impl<N: NodePrimitives> PreparedBlocks<N> {
+    /// Prepares the given executed blocks, ordered by block number, to be written to storage.
+    pub fn new(blocks: Vec<ExecutedBlockWithTrieUpdates<N>>) -> Self {
+        let blocks = blocks
+            .into_iter()
+            .map(
+                |ExecutedBlockWithTrieUpdates {
+                     block: ExecutedBlock { recovered_block, execution_output, hashed_state },
+                     trie,
+                 }| PreparedBlock {
+                    block: Arc::unwrap_or_clone(recovered_block),
+                    execution_output,
+                    hashed_state: Arc::unwrap_or_clone(hashed_state).into_sorted(),
+                    trie,
+                },
+            )
+            .collect();
+        Self { blocks }
+    }
+
+    /// Returns the number of prepared blocks.
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns `true` if there are no prepared blocks.
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Returns the number and hash of the highest prepared block.
+    pub fn last_num_hash(&self) -> Option<BlockNumHash> {
+        self.blocks.last().map(|prepared| prepared.block.num_hash())
+    }
+}
+
 /// [`UnifiedStorageWriter`] is responsible for managing the writing to storage with both database
 /// and static file providers.
 #[derive(Debug)]
@@ -135,22 +196,46 @@ where
     pub fn save_blocks<N>(&self, blocks: Vec<ExecutedBlockWithTrieUpdates<N>>) -> ProviderResult<()>
     where
         N: NodePrimitives<SignedTx: SignedTransaction>,
-        ProviderDB: BlockWriter<Block = N::Block> + StateWriter<Receipt = N::Receipt>,
+        ProviderDB: BlockWriter<Block = N::Block>
+            + StateWriter<Receipt = N::Receipt>
+            + StageCheckpointReader
+            + DepositIndexWriter,
     {
+        self.save_prepared_blocks(PreparedBlocks::new(blocks))
+    }
+
+    /// Writes executed blocks and receipts that were prepared with [`PreparedBlocks::new`] to
+    /// storage.
+    pub fn save_prepared_blocks<N>(&self, blocks: PreparedBlocks<N>) -> ProviderResult<()>
+    where
+        N: NodePrimitives<SignedTx: SignedTransaction>,
+        ProviderDB: BlockWriter<Block = N::Block>
+            + StateWriter<Receipt = N::Receipt>
+            + StageCheckpointReader
+            + DepositIndexWriter,
+    {
+        let PreparedBlocks { blocks } = blocks;
         if blocks.is_empty() {
             debug!(target: "provider::storage_writer", "Attempted to write empty block range");
             return Ok(())
         }
 
         // NOTE: checked non-empty above
-        let first_block = blocks.first().unwrap().recovered_block();
+        let first_block = &blocks.first().unwrap().block;
 
-        let last_block = blocks.last().unwrap().recovered_block();
+        let last_block = &blocks.last().unwrap().block;
         let first_number = first_block.number();
         let last_block_number = last_block.number();
 
         debug!(target: "provider::storage_writer", block_count = %blocks.len(), "Writing blocks and execution data to storage");
 
+        // The optional deposit index is only extended if it's up to date with the written blocks,
+        // otherwise it's caught up by its stage.
+        let index_deposits = self
+            .database()
+            .get_stage_checkpoint(StageId::DepositIndex)?
+            .is_some_and(|checkpoint| checkpoint.block_number + 1 == first_number);
+
         // TODO: Do performant / batched writes for each type of object
         // instead of a loop over all blocks,
         // meaning:
@@ -160,13 +245,9 @@ where
         //  * trie updates (cannot naively extend, need helper)
         //  * indices (already done basically)
         // Insert the blocks
-        for ExecutedBlockWithTrieUpdates {
-            block: ExecutedBlock { recovered_block, execution_output, hashed_state },
-            trie,
-        } in blocks
-        {
-            self.database()
-                .insert_block(Arc::unwrap_or_clone(recovered_block), StorageLocation::Both)?;
+        for PreparedBlock { block, execution_output, hashed_state, trie } in blocks {
+            let block_number = block.number();
+            self.database().insert_block(block, StorageLocation::Both)?;
 
             // Write state and changesets to the database.
             // Must be written after blocks because of the receipt lookup.
@@ -177,9 +258,13 @@ where
             )?;
 
             // insert hashes and intermediate merkle nodes
-            self.database()
-                .write_hashed_state(&Arc::unwrap_or_clone(hashed_state).into_sorted())?;
+            self.database().write_hashed_state(&hashed_state)?;
             self.database().write_trie_updates(&trie)?;
+
+            if index_deposits {
+                self.database()
+                    .append_deposits(block_number, execution_output.receipts.iter().flatten())?;
+            }
         }
 
         // update history indices
@@ -187,6 +272,12 @@ where
 
         // Update pipeline progress
         self.database().update_pipeline_stages(last_block_number, false)?;
+        if index_deposits {
+            self.database().save_stage_checkpoint(
+                StageId::DepositIndex,
+                StageCheckpoint::new(last_block_number),
+            )?;
+        }
 
         debug!(target: "provider::storage_writer", range = ?first_number..=last_block_number, "Appended block data");
 
@@ -1,5 +1,5 @@
 use crate::BlockHashReader;
-use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
+use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag};
 use alloy_primitives::{BlockNumber, B256};
 use reth_chainspec::ChainInfo;
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
@@ -19,6 +19,13 @@ pub trait BlockNumReader: BlockHashReader + Send + Sync {
     /// Returns the last block number associated with the last canonical header in the database.
     fn last_block_number(&self) -> ProviderResult<BlockNumber>;
 
+    /// Returns the earliest block number whose block body is available.
+    ///
+    /// This is greater than zero if the history below it has been expired.
+    fn earliest_block_number(&self) -> ProviderResult<BlockNumber> {
+        Ok(0)
+    }
+
     /// Gets the `BlockNumber` for the given hash. Returns `None` if no block with this hash exists.
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>>;
 
@@ -64,10 +71,10 @@ pub trait BlockIdReader: BlockNumReader + Send + Sync {
             }
             BlockNumberOrTag::Number(num) => num,
             BlockNumberOrTag::Finalized => {
-                self.finalized_block_number()?.ok_or(ProviderError::FinalizedBlockNotFound)?
+                self.finalized_tag_num_hash()?.ok_or(ProviderError::FinalizedBlockNotFound)?.number
             }
             BlockNumberOrTag::Safe => {
-                self.safe_block_number()?.ok_or(ProviderError::SafeBlockNotFound)?
+                self.safe_tag_num_hash()?.ok_or(ProviderError::SafeBlockNotFound)?.number
             }
         };
         Ok(Some(num))
@@ -82,8 +89,12 @@ pub trait BlockIdReader: BlockNumReader + Send + Sync {
                 BlockNumberOrTag::Pending => self
                     .pending_block_num_hash()
                     .map(|res_opt| res_opt.map(|num_hash| num_hash.hash)),
-                BlockNumberOrTag::Finalized => self.finalized_block_hash(),
-                BlockNumberOrTag::Safe => self.safe_block_hash(),
+                BlockNumberOrTag::Finalized => self
+                    .finalized_tag_num_hash()
+                    .map(|res_opt| res_opt.map(|num_hash| num_hash.hash)),
+                BlockNumberOrTag::Safe => {
+                    self.safe_tag_num_hash().map(|res_opt| res_opt.map(|num_hash| num_hash.hash))
+                }
                 BlockNumberOrTag::Earliest => self.block_hash(0),
                 BlockNumberOrTag::Number(num) => self.block_hash(num),
             },
@@ -107,6 +118,22 @@ pub trait BlockIdReader: BlockNumReader + Send + Sync {
     /// Get the current finalized block number and hash.
     fn finalized_block_num_hash(&self) -> ProviderResult<Option<alloy_eips::BlockNumHash>>;
 
+    /// Get the block the `safe` tag resolves to.
+    ///
+    /// This is the safe block, unless the implementation falls back to another block as long as
+    /// the safe block isn't set, see [`BlockTagFallbacks`].
+    fn safe_tag_num_hash(&self) -> ProviderResult<Option<alloy_eips::BlockNumHash>> {
+        self.safe_block_num_hash()
+    }
+
+    /// Get the block the `finalized` tag resolves to.
+    ///
+    /// This is the finalized block, unless the implementation falls back to another block as long
+    /// as the finalized block isn't set, see [`BlockTagFallbacks`].
+    fn finalized_tag_num_hash(&self) -> ProviderResult<Option<alloy_eips::BlockNumHash>> {
+        self.finalized_block_num_hash()
+    }
+
     /// Get the safe block number.
     fn safe_block_number(&self) -> ProviderResult<Option<BlockNumber>> {
         self.safe_block_num_hash().map(|res_opt| res_opt.map(|num_hash| num_hash.number))
@@ -128,5 +155,51 @@ pub trait BlockIdReader: BlockNumReader + Send + Sync {
     }
 }
 
+/// Fallbacks for the `safe` and `finalized` block tags, used as long as the consensus layer hasn't
+/// set these blocks, e.g. on dev chains without consensus layer updates.
+///
+/// A fallback is the number of blocks below the latest block the tag resolves to. Without a
+/// fallback, resolving the tag fails with [`ProviderError::SafeBlockNotFound`] or
+/// [`ProviderError::FinalizedBlockNotFound`].
+///
+/// The fallbacks only apply to resolving the tags, e.g. of RPC requests, see
+/// [`BlockIdReader::safe_tag_num_hash`]. The safe and finalized blocks themselves, which the rest
+/// of the node acts on, stay unset.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlockTagFallbacks {
+    /// The number of blocks below the latest block the `safe` tag resolves to.
+    pub safe: Option<u64>,
+    /// The number of blocks below the latest block the `finalized` tag resolves to.
+    pub finalized: Option<u64>,
+}
+
+impl BlockTagFallbacks {
+    /// Returns the fallback of the `safe` tag, if configured.
+    pub fn safe_num_hash<P: BlockNumReader + ?Sized>(
+        &self,
+        provider: &P,
+    ) -> ProviderResult<Option<BlockNumHash>> {
+        self.safe.map_or(Ok(None), |depth| Self::below_latest(provider, depth))
+    }
+
+    /// Returns the fallback of the `finalized` tag, if configured.
+    pub fn finalized_num_hash<P: BlockNumReader + ?Sized>(
+        &self,
+        provider: &P,
+    ) -> ProviderResult<Option<BlockNumHash>> {
+        self.finalized.map_or(Ok(None), |depth| Self::below_latest(provider, depth))
+    }
+
+    /// Returns the block `depth` blocks below the latest block, or the genesis block if the chain
+    /// is shorter.
+    fn below_latest<P: BlockNumReader + ?Sized>(
+        provider: &P,
+        depth: u64,
+    ) -> ProviderResult<Option<BlockNumHash>> {
+        let number = provider.best_block_number()?.saturating_sub(depth);
+        Ok(provider.block_hash(number)?.map(|hash| BlockNumHash::new(number, hash)))
+    }
+}
+
 #[cfg(test)]
 fn _object_safe(_: Box<dyn BlockIdReader>) {}
@@ -0,0 +1,48 @@
+use alloc::vec::Vec;
+use alloy_primitives::{BlockNumber, B256};
+use auto_impl::auto_impl;
+use core::{ops::RangeInclusive, time::Duration};
+use reth_storage_errors::provider::ProviderResult;
+
+/// Timings of the processing of a block by the engine.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlockProcessingStats {
+    /// The number of the block.
+    pub number: BlockNumber,
+    /// The hash of the block.
+    pub hash: B256,
+    /// The gas used by the block.
+    pub gas_used: u64,
+    /// The time it took to execute the block.
+    pub execution_time: Duration,
+    /// The time it took to compute the state root of the block.
+    pub state_root_time: Duration,
+}
+
+/// Reader of the history of block processing timings.
+#[auto_impl(&, Arc, Box)]
+pub trait BlockProcessingHistoryReader: Send + Sync {
+    /// Returns the recorded processing timings of the blocks in the given range, ordered by block
+    /// number.
+    ///
+    /// Blocks that weren't processed by the engine, e.g. because they were synced by the pipeline,
+    /// are missing. The blocks aren't guaranteed to be canonical anymore if the chain was unwound
+    /// by the pipeline.
+    fn block_processing_history(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockProcessingStats>>;
+}
+
+/// Writer of the history of block processing timings.
+#[auto_impl(&, Arc, Box)]
+pub trait BlockProcessingHistoryWriter: Send + Sync {
+    /// Appends the processing timings of the given blocks to the history.
+    ///
+    /// The blocks must be ordered by block number. Recorded blocks at or above the first given
+    /// block are replaced.
+    fn append_block_processing_stats(&self, stats: &[BlockProcessingStats]) -> ProviderResult<()>;
+
+    /// Removes the processing timings of all blocks above the given block from the history.
+    fn unwind_block_processing_history(&self, block_number: BlockNumber) -> ProviderResult<()>;
+}
@@ -0,0 +1,44 @@
+use alloc::boxed::Box;
+use alloy_primitives::B256;
+use auto_impl::auto_impl;
+use reth_primitives_traits::Bytecode;
+use reth_storage_errors::provider::ProviderResult;
+
+/// An iterator over stored contract bytecode and its code hash.
+pub type BytecodeIter<'a> = Box<dyn Iterator<Item = ProviderResult<(B256, Bytecode)>> + 'a>;
+
+/// Filter for contract bytecode by the byte size of its code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BytecodeSizeFilter {
+    /// The minimum size of the code, inclusive.
+    pub min_size: Option<usize>,
+    /// The maximum size of the code, inclusive.
+    pub max_size: Option<usize>,
+}
+
+impl BytecodeSizeFilter {
+    /// Returns `true` if code of the given size passes the filter.
+    pub const fn contains(&self, size: usize) -> bool {
+        if let Some(min_size) = self.min_size {
+            if size < min_size {
+                return false
+            }
+        }
+        if let Some(max_size) = self.max_size {
+            if size > max_size {
+                return false
+            }
+        }
+        true
+    }
+}
+
+/// Provider of all stored contract bytecode, e.g. for chain-wide contract analysis.
+#[auto_impl(&, Arc, Box)]
+pub trait BytecodeProvider: Send + Sync {
+    /// Returns an iterator over the stored bytecode whose size passes the filter, ordered by code
+    /// hash.
+    ///
+    /// The bytecode is read lazily, so the whole table is never held in memory.
+    fn iter_bytecodes(&self, filter: BytecodeSizeFilter) -> ProviderResult<BytecodeIter<'_>>;
+}
@@ -0,0 +1,39 @@
+use alloy_consensus::TxReceipt;
+use alloy_primitives::{BlockNumber, Log, B256};
+use auto_impl::auto_impl;
+use reth_storage_errors::provider::ProviderResult;
+
+/// Reader of the index of the beacon deposit contract's deposits.
+#[auto_impl(&, Arc, Box)]
+pub trait DepositIndexReader: Send + Sync {
+    /// Returns the number of deposits made until the end of the given block, as returned by
+    /// `get_deposit_count` of the deposit contract.
+    ///
+    /// Returns `None` if the block is not indexed, e.g. because the index is disabled.
+    fn deposit_count(&self, block_number: BlockNumber) -> ProviderResult<Option<u64>>;
+
+    /// Returns the root of the deposit contract's merkle tree at the end of the given block, as
+    /// returned by `get_deposit_root` of the deposit contract.
+    ///
+    /// Returns `None` if the block is not indexed, e.g. because the index is disabled.
+    fn deposit_root(&self, block_number: BlockNumber) -> ProviderResult<Option<B256>>;
+}
+
+/// Writer of the index of the beacon deposit contract's deposits.
+#[auto_impl(&, Arc, Box)]
+pub trait DepositIndexWriter: Send + Sync {
+    /// Extracts the deposits from the receipts of the given block and appends them to the index.
+    ///
+    /// The block must follow the last indexed block. Returns an error if a deposit event can't be
+    /// decoded or its index doesn't follow the previous deposit.
+    fn append_deposits<'a, R>(
+        &self,
+        block_number: BlockNumber,
+        receipts: impl IntoIterator<Item = &'a R>,
+    ) -> ProviderResult<()>
+    where
+        R: TxReceipt<Log = Log> + 'a;
+
+    /// Removes the deposits of all blocks above the given block from the index.
+    fn unwind_deposit_index(&self, block_number: BlockNumber) -> ProviderResult<()>;
+}
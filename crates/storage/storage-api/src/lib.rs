@@ -22,14 +22,23 @@ pub use block::*;
 mod block_id;
 pub use block_id::*;
 
+mod block_processing;
+pub use block_processing::*;
+
 mod block_hash;
 pub use block_hash::*;
 
+mod bytecode;
+pub use bytecode::*;
+
 #[cfg(feature = "db-api")]
 mod chain;
 #[cfg(feature = "db-api")]
 pub use chain::*;
 
+mod deposits;
+pub use deposits::*;
+
 mod header;
 pub use header::*;
 
@@ -54,6 +63,9 @@ pub use transactions::*;
 mod trie;
 pub use trie::*;
 
+mod tx_lookup;
+pub use tx_lookup::*;
+
 mod chain_info;
 pub use chain_info::*;
 
@@ -2,11 +2,13 @@
 
 use crate::{
     AccountReader, BlockBodyIndicesProvider, BlockHashReader, BlockIdReader, BlockNumReader,
-    BlockReader, BlockReaderIdExt, BlockSource, ChangeSetReader, HashedPostStateProvider,
-    HeaderProvider, NodePrimitivesProvider, OmmersProvider, PruneCheckpointReader, ReceiptProvider,
-    ReceiptProviderIdExt, StageCheckpointReader, StateProofProvider, StateProvider,
-    StateProviderBox, StateProviderFactory, StateRootProvider, StorageRootProvider,
-    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    BlockProcessingHistoryReader, BlockProcessingStats, BlockReader, BlockReaderIdExt, BlockSource,
+    BytecodeIter, BytecodeProvider, BytecodeSizeFilter, ChangeSetReader, DepositIndexReader,
+    HashedPostStateProvider, HeaderProvider, NodePrimitivesProvider, OmmersProvider,
+    PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader,
+    StateProofProvider, StateProvider, StateProviderBox, StateProviderFactory, StateRootProvider,
+    StorageRootProvider, TransactionLookupIndexReader, TransactionLookupIndexWriter,
+    TransactionLookupStatus, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
 use alloy_consensus::transaction::TransactionMeta;
@@ -28,8 +30,8 @@ use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie_common::{
-    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
-    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
+    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, HashedStorageRange,
+    MultiProof, MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
 };
 
 /// Supports various api interfaces for testing purposes.
@@ -366,6 +368,43 @@ impl<C: Send + Sync, N: NodePrimitives> ChangeSetReader for NoopProvider<C, N> {
     }
 }
 
+impl<C: Send + Sync, N: NodePrimitives> DepositIndexReader for NoopProvider<C, N> {
+    fn deposit_count(&self, _block_number: BlockNumber) -> ProviderResult<Option<u64>> {
+        Ok(None)
+    }
+
+    fn deposit_root(&self, _block_number: BlockNumber) -> ProviderResult<Option<B256>> {
+        Ok(None)
+    }
+}
+
+impl<C: Send + Sync, N: NodePrimitives> BlockProcessingHistoryReader for NoopProvider<C, N> {
+    fn block_processing_history(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockProcessingStats>> {
+        Ok(Vec::new())
+    }
+}
+
+impl<C: Send + Sync, N: NodePrimitives> BytecodeProvider for NoopProvider<C, N> {
+    fn iter_bytecodes(&self, _filter: BytecodeSizeFilter) -> ProviderResult<BytecodeIter<'_>> {
+        Ok(Box::new(core::iter::empty()))
+    }
+}
+
+impl<C: Send + Sync, N: NodePrimitives> TransactionLookupIndexReader for NoopProvider<C, N> {
+    fn transaction_lookup_status(&self) -> ProviderResult<TransactionLookupStatus> {
+        Ok(TransactionLookupStatus::default())
+    }
+}
+
+impl<C: Send + Sync, N: NodePrimitives> TransactionLookupIndexWriter for NoopProvider<C, N> {
+    fn index_transaction_hashes(&self, _range: RangeInclusive<BlockNumber>) -> ProviderResult<u64> {
+        Ok(0)
+    }
+}
+
 impl<C: Send + Sync, N: NodePrimitives> StateRootProvider for NoopProvider<C, N> {
     fn state_root(&self, _state: HashedPostState) -> ProviderResult<B256> {
         Ok(B256::default())
@@ -416,6 +455,16 @@ impl<C: Send + Sync, N: NodePrimitives> StorageRootProvider for NoopProvider<C,
     ) -> ProviderResult<StorageMultiProof> {
         Ok(StorageMultiProof::empty())
     }
+
+    fn storage_range(
+        &self,
+        _address: Address,
+        _hashed_storage: HashedStorage,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<HashedStorageRange> {
+        Ok(HashedStorageRange::default())
+    }
 }
 
 impl<C: Send + Sync, N: NodePrimitives> StateProofProvider for NoopProvider<C, N> {
@@ -1,8 +1,9 @@
 use crate::BlockIdReader;
 use alloc::vec::Vec;
+use alloy_consensus::TxReceipt;
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
-use alloy_primitives::{TxHash, TxNumber};
-use core::ops::RangeBounds;
+use alloy_primitives::{BlockNumber, Log, TxHash, TxNumber};
+use core::ops::{RangeBounds, RangeInclusive};
 use reth_primitives_traits::Receipt;
 use reth_storage_errors::provider::ProviderResult;
 
@@ -38,6 +39,73 @@ pub trait ReceiptProvider: Send + Sync {
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Self::Receipt>>;
+
+    /// Get the logs of the receipts in the block range that match the filter, which is called with
+    /// the block number and the log.
+    ///
+    /// Unlike [`ReceiptProvider::receipts_by_block`], only the matching logs are kept, so the
+    /// receipts of the range are never collected. Blocks without receipts are skipped.
+    fn logs_by_block(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        filter: impl Fn(BlockNumber, &Log) -> bool,
+    ) -> ProviderResult<Vec<ReceiptLog>> {
+        let mut logs = Vec::new();
+        for block_number in range {
+            if let Some(receipts) = self.receipts_by_block(block_number.into())? {
+                ReceiptLog::extend_matching(
+                    &mut logs,
+                    block_number,
+                    receipts.iter().map(TxReceipt::logs),
+                    &filter,
+                );
+            }
+        }
+        Ok(logs)
+    }
+}
+
+/// A log of a stored receipt with its position in the block, returned by
+/// [`ReceiptProvider::logs_by_block`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceiptLog {
+    /// The number of the block.
+    pub block_number: BlockNumber,
+    /// The index of the transaction in the block.
+    pub transaction_index: u64,
+    /// The index of the log in the block.
+    pub log_index: u64,
+    /// The log.
+    pub log: Log,
+}
+
+impl ReceiptLog {
+    /// Appends the logs of the receipts of a block that match the filter.
+    ///
+    /// The receipts are given by their logs, in the order of the transactions of the block.
+    pub fn extend_matching<L>(
+        logs: &mut Vec<Self>,
+        block_number: BlockNumber,
+        receipts: impl IntoIterator<Item = L>,
+        filter: impl Fn(BlockNumber, &Log) -> bool,
+    ) where
+        L: AsRef<[Log]>,
+    {
+        let mut log_index = 0;
+        for (transaction_index, receipt_logs) in receipts.into_iter().enumerate() {
+            for log in receipt_logs.as_ref() {
+                if filter(block_number, log) {
+                    logs.push(Self {
+                        block_number,
+                        transaction_index: transaction_index as u64,
+                        log_index,
+                        log: log.clone(),
+                    });
+                }
+                log_index += 1;
+            }
+        }
+    }
 }
 
 /// Trait extension for `ReceiptProvider`, for types that implement `BlockId` conversion.
@@ -2,12 +2,12 @@ use super::{
     AccountReader, BlockHashReader, BlockIdReader, StateProofProvider, StateRootProvider,
     StorageRootProvider,
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use alloy_consensus::constants::KECCAK_EMPTY;
 use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_primitives::{Address, BlockHash, BlockNumber, StorageKey, StorageValue, B256, U256};
 use auto_impl::auto_impl;
-use reth_primitives_traits::Bytecode;
+use reth_primitives_traits::{Account, Bytecode};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie_common::HashedPostState;
 use revm_database::BundleState;
@@ -37,6 +37,27 @@ pub trait StateProvider:
     /// Get account code by its hash
     fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>>;
 
+    /// Get the accounts and the values of the given storage slots of multiple accounts, in the
+    /// order of the targets.
+    ///
+    /// By default the targets are looked up one by one, historical state providers may split them
+    /// across parallel workers.
+    fn accounts_with_storage(
+        &self,
+        targets: &[(Address, Vec<StorageKey>)],
+    ) -> ProviderResult<Vec<(Option<Account>, Vec<Option<StorageValue>>)>> {
+        targets
+            .iter()
+            .map(|(address, slots)| {
+                let storage = slots
+                    .iter()
+                    .map(|slot| self.storage(*address, *slot))
+                    .collect::<ProviderResult<_>>()?;
+                Ok((self.basic_account(address)?, storage))
+            })
+            .collect()
+    }
+
     /// Get account code by its address.
     ///
     /// Returns `None` if the account doesn't exist or account is not a contract
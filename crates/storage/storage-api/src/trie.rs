@@ -3,8 +3,8 @@ use alloy_primitives::{map::B256Map, Address, Bytes, B256};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie_common::{
     updates::{StorageTrieUpdates, TrieUpdates},
-    AccountProof, HashedPostState, HashedStorage, MultiProof, MultiProofTargets, StorageMultiProof,
-    StorageProof, TrieInput,
+    AccountProof, HashedPostState, HashedStorage, HashedStorageRange, MultiProof,
+    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
 };
 
 /// A type that can compute the state root of a given post state.
@@ -63,6 +63,16 @@ pub trait StorageRootProvider: Send + Sync {
         slots: &[B256],
         hashed_storage: HashedStorage,
     ) -> ProviderResult<StorageMultiProof>;
+
+    /// Returns up to `limit` storage slots of the `HashedStorage` for target address on top of the
+    /// current state, starting at the given hashed slot.
+    fn storage_range(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStorageRange>;
 }
 
 /// A type that can generate state proof on top of a given post state.
@@ -77,6 +87,21 @@ pub trait StateProofProvider: Send + Sync {
         slots: &[B256],
     ) -> ProviderResult<AccountProof>;
 
+    /// Get account and storage proofs for multiple target accounts in the `HashedPostState`
+    /// on top of the current state.
+    ///
+    /// Proofs are generated from a single [`MultiProof`] and returned in the order of the
+    /// targets.
+    fn proofs(
+        &self,
+        input: TrieInput,
+        targets: &[(Address, Vec<B256>)],
+    ) -> ProviderResult<Vec<AccountProof>> {
+        let multiproof =
+            self.multiproof(input, MultiProofTargets::from_addresses_with_slots(targets))?;
+        Ok(multiproof.account_proofs(targets)?)
+    }
+
     /// Generate [`MultiProof`] for target hashed account and corresponding
     /// hashed storage slot keys.
     fn multiproof(
@@ -0,0 +1,54 @@
+use alloy_primitives::BlockNumber;
+use auto_impl::auto_impl;
+use core::ops::RangeInclusive;
+use reth_storage_errors::provider::ProviderResult;
+
+/// Progress of the index of transaction hashes to transaction numbers.
+///
+/// The stored blocks are split into three consecutive segments: the blocks whose transaction
+/// hashes were pruned from the index, the indexed blocks, and the blocks that weren't indexed yet,
+/// e.g. because the transaction lookup stage is still catching up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransactionLookupStatus {
+    /// The highest block whose transaction hashes were pruned from the index, if any.
+    pub pruned_block: Option<BlockNumber>,
+    /// The highest block whose transaction hashes are indexed.
+    pub indexed_block: BlockNumber,
+    /// The highest block whose transactions are stored.
+    pub stored_block: BlockNumber,
+}
+
+impl TransactionLookupStatus {
+    /// Returns the number of stored blocks whose transaction hashes aren't indexed yet.
+    pub const fn lag(&self) -> u64 {
+        self.stored_block.saturating_sub(self.indexed_block)
+    }
+
+    /// Returns the blocks whose transaction hashes aren't indexed yet, if any.
+    pub const fn unindexed_blocks(&self) -> Option<RangeInclusive<BlockNumber>> {
+        if self.lag() == 0 {
+            return None
+        }
+        Some(self.indexed_block + 1..=self.stored_block)
+    }
+}
+
+/// Reader of the progress of the transaction hash index.
+#[auto_impl(&, Arc, Box)]
+pub trait TransactionLookupIndexReader: Send + Sync {
+    /// Returns the progress of the transaction hash index.
+    fn transaction_lookup_status(&self) -> ProviderResult<TransactionLookupStatus>;
+}
+
+/// Writer of the transaction hash index.
+#[auto_impl(&, Arc, Box)]
+pub trait TransactionLookupIndexWriter: Send + Sync {
+    /// Indexes the transaction hashes of the given blocks, ahead of the transaction lookup stage.
+    ///
+    /// The range must continue the index, i.e. start above the pruned blocks and at most one block
+    /// above the last indexed block, and end at or below the last stored block. The progress of
+    /// the index is advanced to the end of the range.
+    ///
+    /// Returns the number of indexed transactions.
+    fn index_transaction_hashes(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<u64>;
+}
@@ -23,7 +23,7 @@ use futures_util::{
 };
 use std::{
     any::Any,
-    fmt::{Display, Formatter},
+    fmt::{self, Display, Formatter},
     pin::{pin, Pin},
     sync::{
         atomic::{AtomicUsize, Ordering},
@@ -209,6 +209,7 @@ impl TaskManager {
             panicked_tasks_tx: self.panicked_tasks_tx.clone(),
             metrics: Default::default(),
             graceful_tasks: Arc::clone(&self.graceful_tasks),
+            task_hook: None,
         }
     }
 
@@ -299,6 +300,18 @@ pub struct TaskExecutor {
     metrics: TaskExecutorMetrics,
     /// How many [`GracefulShutdown`] tasks are currently active
     graceful_tasks: Arc<AtomicUsize>,
+    /// Wraps every spawned task, see [`TaskExecutor::with_task_hook`].
+    task_hook: Option<TaskHook>,
+}
+
+/// Wraps the futures of the tasks spawned by a [`TaskExecutor`].
+#[derive(Clone)]
+struct TaskHook(Arc<dyn Fn(BoxFuture<'static, ()>) -> BoxFuture<'static, ()> + Send + Sync>);
+
+impl fmt::Debug for TaskHook {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskHook").finish_non_exhaustive()
+    }
 }
 
 // === impl TaskExecutor ===
@@ -309,6 +322,30 @@ impl TaskExecutor {
         &self.handle
     }
 
+    /// Sets a hook that wraps the future of every task spawned by this executor and its clones,
+    /// replacing the previous hook.
+    ///
+    /// This can be used to run all tasks of a component in the same context, e.g. a
+    /// [`tokio::task_local`].
+    pub fn with_task_hook(
+        mut self,
+        hook: impl Fn(BoxFuture<'static, ()>) -> BoxFuture<'static, ()> + Send + Sync + 'static,
+    ) -> Self {
+        self.task_hook = Some(TaskHook(Arc::new(hook)));
+        self
+    }
+
+    /// Spawns the future onto the runtime, wrapped by the task hook if it's set.
+    fn spawn_on_handle<F>(&self, fut: F) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        match &self.task_hook {
+            Some(hook) => self.handle.spawn((hook.0)(Box::pin(fut))),
+            None => self.handle.spawn(fut),
+        }
+    }
+
     /// Returns the receiver of the shutdown signal.
     pub const fn on_shutdown_signal(&self) -> &Shutdown {
         &self.on_shutdown
@@ -320,9 +357,13 @@ impl TaskExecutor {
         F: Future<Output = ()> + Send + 'static,
     {
         match task_kind {
-            TaskKind::Default => self.handle.spawn(fut),
+            TaskKind::Default => self.spawn_on_handle(fut),
             TaskKind::Blocking => {
                 let handle = self.handle.clone();
+                let fut = match &self.task_hook {
+                    Some(hook) => (hook.0)(Box::pin(fut)),
+                    None => Box::pin(fut),
+                };
                 self.handle.spawn_blocking(move || handle.block_on(fut))
             }
         }
@@ -388,7 +429,7 @@ impl TaskExecutor {
 
         let task = fut.in_current_span();
 
-        self.handle.spawn(task)
+        self.spawn_on_handle(task)
     }
 
     /// Spawns a critical task depending on the given [`TaskKind`]
@@ -475,7 +516,7 @@ impl TaskExecutor {
             .map(drop)
             .in_current_span();
 
-        self.handle.spawn(task)
+        self.spawn_on_handle(task)
     }
 
     /// This spawns a critical task onto the runtime.
@@ -524,7 +565,7 @@ impl TaskExecutor {
             .map(drop)
             .in_current_span();
 
-        self.handle.spawn(task)
+        self.spawn_on_handle(task)
     }
 
     /// This spawns a regular task onto the runtime.
@@ -559,7 +600,7 @@ impl TaskExecutor {
         );
         let fut = f(on_shutdown);
 
-        self.handle.spawn(fut)
+        self.spawn_on_handle(fut)
     }
 }
 
@@ -679,6 +720,36 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_task_hook() {
+        tokio::task_local! {
+            static LABEL: &'static str;
+        }
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let manager = TaskManager::new(runtime.handle().clone());
+        let executor =
+            manager.executor().with_task_hook(|fut| Box::pin(LABEL.scope("hooked", fut)));
+
+        let (tx, mut rx) = unbounded_channel();
+        let task = move || {
+            let tx = tx.clone();
+            async move {
+                let _ = tx.send(LABEL.try_with(|label| *label).ok());
+            }
+        };
+        executor.spawn(task());
+        executor.spawn_blocking(task());
+        executor.spawn_critical("critical", task());
+        executor.clone().spawn_with_graceful_shutdown_signal(|_| task());
+
+        runtime.block_on(async move {
+            for _ in 0..4 {
+                assert_eq!(rx.recv().await.unwrap(), Some("hooked"));
+            }
+        });
+    }
+
     // Tests that spawned tasks are terminated if the `TaskManager` drops
     #[test]
     fn test_manager_shutdown_critical() {
@@ -1,8 +1,9 @@
 use crate::layers::BoxedLayer;
 use clap::ValueEnum;
 use std::{fmt, fmt::Display};
+use tracing::level_filters::LevelFilter;
 use tracing_appender::non_blocking::NonBlocking;
-use tracing_subscriber::{EnvFilter, Layer, Registry};
+use tracing_subscriber::{layer::Filter, EnvFilter, Layer, Registry};
 
 /// Represents the logging format.
 ///
@@ -43,6 +44,24 @@ impl LogFormat {
         color: Option<String>,
         file_writer: Option<NonBlocking>,
     ) -> BoxedLayer<Registry> {
+        let max_level_hint = filter.max_level_hint();
+        self.apply_with_filter(filter, max_level_hint, color, file_writer)
+    }
+
+    /// Applies the specified logging format to create a new layer with an arbitrary filter.
+    ///
+    /// See [`LogFormat::apply`] for more details. The `max_level_hint` of the filter determines
+    /// whether the target is shown in the logs.
+    pub(crate) fn apply_with_filter<F>(
+        &self,
+        filter: F,
+        max_level_hint: Option<LevelFilter>,
+        color: Option<String>,
+        file_writer: Option<NonBlocking>,
+    ) -> BoxedLayer<Registry>
+    where
+        F: Filter<Registry> + Send + Sync + 'static,
+    {
         let ansi = if let Some(color) = color {
             std::env::var("RUST_LOG_STYLE").map(|val| val != "never").unwrap_or(color != "never")
         } else {
@@ -54,7 +73,7 @@ impl LogFormat {
             .unwrap_or_else(|_|
                 // If `RUST_LOG_TARGET` is not set, show target in logs only if the max enabled
                 // level is higher than INFO (DEBUG, TRACE)
-                filter.max_level_hint().is_none_or(|max_level| max_level > tracing::Level::INFO));
+                max_level_hint.is_none_or(|max_level| max_level > tracing::Level::INFO));
 
         match self {
             Self::Json => {
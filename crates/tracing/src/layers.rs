@@ -1,8 +1,11 @@
-use std::path::{Path, PathBuf};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use rolling_file::{RollingConditionBasic, RollingFileAppender};
 use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::{filter::Directive, EnvFilter, Layer, Registry};
+use tracing_subscriber::{filter::Directive, reload, EnvFilter, Layer, Registry};
 
 use crate::formatter::LogFormat;
 
@@ -33,17 +36,31 @@ const DEFAULT_ENV_FILTER_DIRECTIVES: [&str; 5] = [
 /// Each layer can be configured separately and then combined into a tracing subscriber.
 pub(crate) struct Layers {
     inner: Vec<BoxedLayer<Registry>>,
+    filters: Vec<ReloadableFilter>,
 }
 
 impl Layers {
     /// Creates a new `Layers` instance.
     pub(crate) fn new() -> Self {
-        Self { inner: vec![] }
+        Self { inner: vec![], filters: vec![] }
     }
 
-    /// Consumes the `Layers` instance, returning the inner vector of layers.
-    pub(crate) fn into_inner(self) -> Vec<BoxedLayer<Registry>> {
-        self.inner
+    /// Consumes the `Layers` instance, returning the inner vector of layers and the handle to
+    /// change their filters.
+    pub(crate) fn into_inner(self) -> (Vec<BoxedLayer<Registry>>, LogFilterHandle) {
+        (self.inner, LogFilterHandle { filters: Arc::new(self.filters) })
+    }
+
+    /// Wraps the filter of a layer, so it can be changed at runtime through the
+    /// [`LogFilterHandle`].
+    fn reloadable(
+        &mut self,
+        default_directive: Option<Directive>,
+        filter: EnvFilter,
+    ) -> reload::Layer<EnvFilter, Registry> {
+        let (filter, handle) = reload::Layer::new(filter);
+        self.filters.push(ReloadableFilter { default_directive, handle });
+        filter
     }
 
     /// Adds a journald layer to the layers collection.
@@ -54,12 +71,27 @@ impl Layers {
     /// # Returns
     /// An `eyre::Result<()>` indicating the success or failure of the operation.
     pub(crate) fn journald(&mut self, filter: &str) -> eyre::Result<()> {
-        let journald_filter = build_env_filter(None, filter)?;
+        let journald_filter = self.reloadable(None, build_env_filter(None, filter)?);
         let layer = tracing_journald::layer()?.with_filter(journald_filter).boxed();
         self.inner.push(layer);
         Ok(())
     }
 
+    /// Adds a layer exporting the spans to an OTLP collector.
+    ///
+    /// # Arguments
+    /// * `endpoint` - The OTLP/HTTP endpoint the spans are exported to.
+    /// * `filter` - A string containing additional filter directives for this layer.
+    ///
+    /// # Returns
+    /// An `eyre::Result<()>` indicating the success or failure of the operation.
+    pub(crate) fn otlp(&mut self, endpoint: &str, filter: &str) -> eyre::Result<()> {
+        let otlp_filter = self.reloadable(None, build_env_filter(None, filter)?);
+        let layer = crate::otlp::span_layer(endpoint)?.with_filter(otlp_filter).boxed();
+        self.inner.push(layer);
+        Ok(())
+    }
+
     /// Adds a stdout layer with specified formatting and filtering.
     ///
     /// # Type Parameters
@@ -80,8 +112,10 @@ impl Layers {
         filters: &str,
         color: Option<String>,
     ) -> eyre::Result<()> {
-        let filter = build_env_filter(Some(default_directive), filters)?;
-        let layer = format.apply(filter, color, None);
+        let filter = build_env_filter(Some(default_directive.clone()), filters)?;
+        let max_level_hint = filter.max_level_hint();
+        let filter = self.reloadable(Some(default_directive), filter);
+        let layer = format.apply_with_filter(filter, max_level_hint, color, None);
         self.inner.push(layer.boxed());
         Ok(())
     }
@@ -103,12 +137,54 @@ impl Layers {
     ) -> eyre::Result<FileWorkerGuard> {
         let (writer, guard) = file_info.create_log_writer();
         let file_filter = build_env_filter(None, filter)?;
-        let layer = format.apply(file_filter, None, Some(writer));
+        let max_level_hint = file_filter.max_level_hint();
+        let file_filter = self.reloadable(None, file_filter);
+        let layer = format.apply_with_filter(file_filter, max_level_hint, None, Some(writer));
         self.inner.push(layer);
         Ok(guard)
     }
 }
 
+/// The filter of a layer that can be changed at runtime.
+#[derive(Debug)]
+struct ReloadableFilter {
+    /// The default directive the layer was configured with.
+    default_directive: Option<Directive>,
+    handle: reload::Handle<EnvFilter, Registry>,
+}
+
+/// A handle to change the filters of the installed logging layers at runtime.
+///
+/// The handle is returned by [`log_filter_handle`](crate::log_filter_handle) once the
+/// [`RethTracer`](crate::RethTracer) is initialized.
+#[derive(Debug, Clone)]
+pub struct LogFilterHandle {
+    filters: Arc<Vec<ReloadableFilter>>,
+}
+
+impl LogFilterHandle {
+    /// Replaces the filter directives of all logging layers, e.g. `engine::tree=trace`.
+    ///
+    /// This has the same effect as setting the `--log.<layer>.filter` arguments on startup: the
+    /// default verbosity of the layers is kept and the directives are applied on top of it.
+    /// Passing an empty string removes all previously set directives.
+    pub fn set_filter(&self, directives: &str) -> eyre::Result<()> {
+        // Build all filters first, so an invalid directive doesn't leave the layers in a partially
+        // updated state.
+        let filters = self
+            .filters
+            .iter()
+            .map(|filter| build_env_filter(filter.default_directive.clone(), directives))
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        for (filter, new_filter) in self.filters.iter().zip(filters) {
+            filter.handle.reload(new_filter)?;
+        }
+
+        Ok(())
+    }
+}
+
 /// Holds configuration information for file logging.
 ///
 /// Contains details about the log file's path, name, size, and rotation strategy.
@@ -1,9 +1,9 @@
 //!  The `tracing` module provides functionalities for setting up and configuring logging.
 //!
 //!  It includes structures and functions to create and manage various logging layers: stdout,
-//!  file, or journald. The module's primary entry point is the `Tracer` struct, which can be
-//!  configured to use different logging formats and destinations. If no layer is specified, it will
-//!  default to stdout.
+//!  file, journald, or an OTLP span exporter. The module's primary entry point is the `Tracer`
+//!  struct, which can be configured to use different logging formats and destinations. If no layer
+//!  is specified, it will default to stdout.
 //!
 //!  # Examples
 //!
@@ -50,18 +50,31 @@ pub use tracing_subscriber;
 
 // Re-export our types
 pub use formatter::LogFormat;
-pub use layers::{FileInfo, FileWorkerGuard};
+pub use layers::{FileInfo, FileWorkerGuard, LogFilterHandle};
+pub use otlp::{current_trace_id, set_remote_parent};
 pub use test_tracer::TestTracer;
 
 mod formatter;
 mod layers;
+mod otlp;
 mod test_tracer;
 
 use crate::layers::Layers;
+use std::sync::OnceLock;
 use tracing::level_filters::LevelFilter;
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+/// The handle to change the log filters of the subscriber installed by [`RethTracer`].
+static LOG_FILTER_HANDLE: OnceLock<LogFilterHandle> = OnceLock::new();
+
+/// Returns the handle to change the log filters at runtime.
+///
+/// Returns `None` if the global subscriber wasn't installed by [`RethTracer`].
+pub fn log_filter_handle() -> Option<LogFilterHandle> {
+    LOG_FILTER_HANDLE.get().cloned()
+}
+
 ///  Tracer for application logging.
 ///
 ///  Manages the configuration and initialization of logging layers,
@@ -71,15 +84,16 @@ pub struct RethTracer {
     stdout: LayerInfo,
     journald: Option<String>,
     file: Option<(LayerInfo, FileInfo)>,
+    otlp: Option<(String, String)>,
 }
 
 impl RethTracer {
     ///  Constructs a new `Tracer` with default settings.
     ///
     ///  Initializes with default stdout layer configuration.
-    ///  Journald and file layers are not set by default.
+    ///  Journald, file and OTLP layers are not set by default.
     pub fn new() -> Self {
-        Self { stdout: LayerInfo::default(), journald: None, file: None }
+        Self { stdout: LayerInfo::default(), journald: None, file: None, otlp: None }
     }
 
     ///  Sets a custom configuration for the stdout layer.
@@ -109,6 +123,16 @@ impl RethTracer {
         self.file = Some((config, file_info));
         self
     }
+
+    ///  Sets the OTLP span exporter configuration.
+    ///
+    ///  # Arguments
+    ///  * `endpoint` - The OTLP/HTTP endpoint the spans are exported to.
+    ///  * `filter` - The `filter` to use for the exported spans.
+    pub fn with_otlp(mut self, endpoint: String, filter: String) -> Self {
+        self.otlp = Some((endpoint, filter));
+        self
+    }
 }
 
 impl Default for RethTracer {
@@ -183,7 +207,7 @@ impl Tracer for RethTracer {
     ///  Initializes the logging system based on the configured layers.
     ///
     ///  This method sets up the global tracing subscriber with the specified
-    ///  stdout, journald, and file layers.
+    ///  stdout, journald, file, and OTLP layers.
     ///
     ///  The default layer is stdout.
     ///
@@ -204,6 +228,10 @@ impl Tracer for RethTracer {
             layers.journald(&config)?;
         }
 
+        if let Some((endpoint, filter)) = self.otlp {
+            layers.otlp(&endpoint, &filter)?;
+        }
+
         let file_guard = if let Some((config, file_info)) = self.file {
             Some(layers.file(config.format, &config.filters, file_info)?)
         } else {
@@ -212,7 +240,10 @@ impl Tracer for RethTracer {
 
         // The error is returned if the global default subscriber is already set,
         // so it's safe to ignore it
-        let _ = tracing_subscriber::registry().with(layers.into_inner()).try_init();
+        let (layers, filter_handle) = layers.into_inner();
+        if tracing_subscriber::registry().with(layers).try_init().is_ok() {
+            let _ = LOG_FILTER_HANDLE.set(filter_handle);
+        }
         Ok(file_guard)
     }
 }
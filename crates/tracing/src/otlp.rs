@@ -0,0 +1,60 @@
+//! Export of spans to an OpenTelemetry collector.
+
+use opentelemetry::{
+    global,
+    propagation::TextMapPropagator,
+    trace::{TraceContextExt, TracerProvider},
+};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{propagation::TraceContextPropagator, trace::SdkTracerProvider, Resource};
+use std::collections::HashMap;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::{Layer, Registry};
+
+/// The service name the spans are exported under.
+const SERVICE_NAME: &str = "reth";
+
+/// Builds a layer that exports the spans to the OTLP/HTTP collector at the given endpoint, e.g.
+/// `http://localhost:4318/v1/traces`.
+///
+/// This also installs the W3C trace context propagator, so remote trace contexts can be continued
+/// with [`set_remote_parent`].
+pub(crate) fn span_layer(endpoint: &str) -> eyre::Result<impl Layer<Registry> + Send + Sync> {
+    let exporter = SpanExporter::builder().with_http().with_endpoint(endpoint).build()?;
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(SERVICE_NAME).build())
+        .build();
+
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    let tracer = provider.tracer(SERVICE_NAME);
+    global::set_tracer_provider(provider);
+
+    Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Continues the remote trace context propagated in the given headers, e.g. the W3C `traceparent`
+/// header of a request, by making it the parent of the span.
+///
+/// This is a no-op if the span isn't exported or the headers don't carry a valid trace context.
+pub fn set_remote_parent<'a>(span: &Span, headers: impl IntoIterator<Item = (&'a str, &'a str)>) {
+    let headers = headers
+        .into_iter()
+        .map(|(key, value)| (key.to_ascii_lowercase(), value.to_string()))
+        .collect::<HashMap<_, _>>();
+    let cx = global::get_text_map_propagator(|propagator| propagator.extract(&headers));
+    if cx.span().span_context().is_valid() {
+        span.set_parent(cx);
+    }
+}
+
+/// Returns the id of the trace the current span belongs to, if spans are exported.
+///
+/// This can be attached to logs and metrics to find the trace of e.g. a slow operation.
+pub fn current_trace_id() -> Option<String> {
+    let cx = Span::current().context();
+    let span = cx.span();
+    let span_context = span.span_context();
+    span_context.is_valid().then(|| span_context.trace_id().to_string())
+}
@@ -44,6 +44,12 @@ pub struct PoolConfig {
     pub blob_limit: SubPoolLimit,
     /// Max number of executable transaction slots guaranteed per account
     pub max_account_slots: usize,
+    /// Max number of pending transactions per account, if limited.
+    ///
+    /// Further executable transactions of the account are parked in the queued sub-pool instead
+    /// of being rejected, and are promoted once the pending transactions are mined. Local
+    /// transactions are exempt unless configured otherwise.
+    pub max_pending_account_slots: Option<usize>,
     /// Price bump (in %) for the transaction pool underpriced check.
     pub price_bumps: PriceBumpConfig,
     /// Minimum base fee required by the protocol.
@@ -59,8 +65,15 @@ pub struct PoolConfig {
     pub new_tx_listener_buffer_size: usize,
     /// How many new pending transactions to buffer and send iterators in progress.
     pub max_new_pending_txs_notifications: usize,
-    /// Maximum lifetime for transactions in the pool
+    /// Maximum lifetime of external transactions in the queued sub-pool.
+    ///
+    /// Transactions that were not promoted within this time are removed from the pool.
     pub max_queued_lifetime: Duration,
+    /// Maximum lifetime of external transactions in the basefee sub-pool, disabled by default.
+    ///
+    /// Expired transactions are removed together with their descendants, so no nonce gaps are
+    /// left behind.
+    pub max_basefee_lifetime: Option<Duration>,
 }
 
 impl PoolConfig {
@@ -72,6 +85,14 @@ impl PoolConfig {
             self.basefee_limit.is_exceeded(pool_size.basefee, pool_size.basefee_size) ||
             self.queued_limit.is_exceeded(pool_size.queued, pool_size.queued_size)
     }
+
+    /// Returns the shortest lifetime of all sub-pools, which is how often expired transactions
+    /// should be removed.
+    #[inline]
+    pub fn min_lifetime(&self) -> Duration {
+        self.max_basefee_lifetime
+            .map_or(self.max_queued_lifetime, |lifetime| lifetime.min(self.max_queued_lifetime))
+    }
 }
 
 impl Default for PoolConfig {
@@ -82,6 +103,7 @@ impl Default for PoolConfig {
             queued_limit: Default::default(),
             blob_limit: Default::default(),
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+            max_pending_account_slots: None,
             price_bumps: Default::default(),
             minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE,
             gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
@@ -90,6 +112,7 @@ impl Default for PoolConfig {
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
+            max_basefee_lifetime: None,
         }
     }
 }
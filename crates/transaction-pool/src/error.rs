@@ -277,7 +277,8 @@ impl InvalidPoolTransactionError {
                     InvalidTransactionError::ChainIdMismatch |
                     InvalidTransactionError::GasUintOverflow |
                     InvalidTransactionError::TxTypeNotSupported |
-                    InvalidTransactionError::SignerAccountHasBytecode => true,
+                    InvalidTransactionError::SignerAccountHasBytecode |
+                    InvalidTransactionError::ExceedsMaxInitCodeSize { .. } => true,
                 }
             }
             Self::ExceedsGasLimit(_, _) => true,
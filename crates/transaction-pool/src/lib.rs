@@ -476,6 +476,10 @@ where
         self.pool.remove_transactions(hashes)
     }
 
+    fn remove_expired_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        self.pool.remove_expired_transactions()
+    }
+
     fn remove_transactions_and_descendants(
         &self,
         hashes: Vec<TxHash>,
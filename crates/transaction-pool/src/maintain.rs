@@ -5,10 +5,10 @@ use crate::{
     error::PoolError,
     metrics::MaintainPoolMetrics,
     traits::{CanonicalStateUpdate, EthPoolTransaction, TransactionPool, TransactionPoolExt},
-    BlockInfo, PoolTransaction, PoolUpdateKind,
+    BlockInfo, PoolTransaction, PoolUpdateKind, TransactionOrigin,
 };
 use alloy_consensus::{BlockHeader, Typed2718};
-use alloy_eips::BlockNumberOrTag;
+use alloy_eips::{eip7840::BlobParams, BlockNumberOrTag};
 use alloy_primitives::{Address, BlockHash, BlockNumber};
 use alloy_rlp::Encodable;
 use futures_util::{
@@ -53,7 +53,9 @@ pub struct MaintainPoolConfig {
     /// Default: 100
     pub max_reload_accounts: usize,
 
-    /// Maximum amount of time non-executable, non local transactions are queued.
+    /// How often transactions that exceeded the lifetime of their sub-pool are removed, see
+    /// [`PoolConfig::min_lifetime`](crate::PoolConfig::min_lifetime).
+    ///
     /// Default: 3 hours
     pub max_tx_lifetime: Duration,
 }
@@ -121,6 +123,10 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
 {
     let metrics = MaintainPoolMetrics::default();
     let MaintainPoolConfig { max_update_depth, max_reload_accounts, .. } = config;
+
+    // keeps track of the blob params to detect forks that change them
+    let mut blob_params_tracker = BlobParamsTracker::default();
+
     // ensure the pool points to latest state
     if let Ok(Some(latest)) = client.header_by_number_or_tag(BlockNumberOrTag::Latest) {
         let latest = SealedHeader::seal_slow(latest);
@@ -136,6 +142,7 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
                 .maybe_next_block_blob_fee(chain_spec.blob_params_at_timestamp(latest.timestamp())),
         };
         pool.set_block_info(info);
+        blob_params_tracker.update(chain_spec.blob_params_at_timestamp(latest.timestamp()));
     }
 
     // keeps track of mined blob transaction so we can clean finalized transactions
@@ -253,17 +260,8 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
                 }
             }
             _ = stale_eviction_interval.tick() => {
-                let stale_txs: Vec<_> = pool
-                    .queued_transactions()
-                    .into_iter()
-                    .filter(|tx| {
-                        // filter stale external txs
-                        tx.origin.is_external() && tx.timestamp.elapsed() > config.max_tx_lifetime
-                    })
-                    .map(|tx| *tx.hash())
-                    .collect();
-                debug!(target: "txpool", count=%stale_txs.len(), "removing stale transactions");
-                pool.remove_transactions(stale_txs);
+                let expired = pool.remove_expired_transactions();
+                debug!(target: "txpool", count=%expired.len(), "removed expired transactions");
             }
         }
         // handle the result of the account reload
@@ -406,6 +404,14 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
                 metrics.inc_reinserted_transactions(pruned_old_transactions.len());
                 let _ = pool.add_external_transactions(pruned_old_transactions).await;
 
+                // the pool's validator was updated to the new tip, re-validate the blob
+                // transactions if the tip crossed a fork that changed the blob params
+                if blob_params_tracker
+                    .update(chain_spec.blob_params_at_timestamp(new_tip.timestamp()))
+                {
+                    revalidate_blob_transactions(&pool, &metrics).await;
+                }
+
                 // keep track of new mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&new_blocks);
             }
@@ -481,6 +487,13 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
                 };
                 pool.on_canonical_state_change(update);
 
+                // the pool's validator was updated to the new tip, re-validate the blob
+                // transactions if the tip crossed a fork that changed the blob params
+                if blob_params_tracker.update(chain_spec.blob_params_at_timestamp(tip.timestamp()))
+                {
+                    revalidate_blob_transactions(&pool, &metrics).await;
+                }
+
                 // keep track of mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&blocks);
             }
@@ -488,6 +501,68 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
     }
 }
 
+/// Re-validates all blob transactions in the pool, after a fork changed the blob params.
+///
+/// The transactions are removed and re-added with their sidecars, so that they're validated
+/// against the new blob params. Transactions that are no longer valid, e.g. because they have more
+/// blobs than permitted per block, are discarded.
+async fn revalidate_blob_transactions<P>(pool: &P, metrics: &MaintainPoolMetrics)
+where
+    P: TransactionPool,
+{
+    let blob_transactions =
+        pool.pooled_transactions().into_iter().filter(|tx| tx.is_eip4844()).collect::<Vec<_>>();
+    if blob_transactions.is_empty() {
+        return
+    }
+
+    let mut discarded = Vec::new();
+    let mut revalidated = Vec::with_capacity(blob_transactions.len());
+    for tx in &blob_transactions {
+        let transaction = pool
+            .get_blob(*tx.hash())
+            .ok()
+            .flatten()
+            .map(Arc::unwrap_or_clone)
+            .and_then(|sidecar| P::Transaction::try_from_eip4844(tx.to_consensus(), sidecar));
+        match transaction {
+            Some(transaction) => revalidated.push((tx.origin, transaction)),
+            None => discarded.push(*tx.hash()),
+        }
+    }
+
+    pool.remove_transactions(blob_transactions.iter().map(|tx| *tx.hash()).collect());
+
+    for origin in
+        [TransactionOrigin::Local, TransactionOrigin::Private, TransactionOrigin::External]
+    {
+        let transactions = revalidated
+            .iter()
+            .filter(|(tx_origin, _)| *tx_origin == origin)
+            .map(|(_, tx)| tx.clone())
+            .collect::<Vec<_>>();
+        if transactions.is_empty() {
+            continue
+        }
+
+        for res in pool.add_transactions(origin, transactions).await {
+            if let Err(err) = res {
+                trace!(target: "txpool", %err, "discarded blob transaction after re-validation");
+                discarded.push(err.hash);
+            }
+        }
+    }
+
+    info!(
+        target: "txpool",
+        revalidated = blob_transactions.len(),
+        discarded = discarded.len(),
+        "re-validated blob transactions after blob params changed"
+    );
+    metrics.inc_discarded_revalidated_blob_transactions(discarded.len());
+    pool.delete_blobs(discarded);
+}
+
 struct FinalizedBlockTracker {
     last_finalized_block: Option<BlockNumber>,
 }
@@ -507,6 +582,22 @@ impl FinalizedBlockTracker {
     }
 }
 
+/// Keeps track of the blob params of the pool's block, to detect forks that change them.
+#[derive(Debug, Default)]
+struct BlobParamsTracker {
+    /// The target and max blob count per block of the last block.
+    last_blob_counts: Option<(u64, u64)>,
+}
+
+impl BlobParamsTracker {
+    /// Updates the tracked blob params and returns `true` if the blob counts changed.
+    fn update(&mut self, blob_params: Option<BlobParams>) -> bool {
+        let blob_counts =
+            blob_params.map(|params| (params.target_blob_count, params.max_blob_count));
+        std::mem::replace(&mut self.last_blob_counts, blob_counts) != blob_counts
+    }
+}
+
 /// Keeps track of the pool's state, whether the accounts in the pool are in sync with the actual
 /// state.
 #[derive(Debug, PartialEq, Eq)]
@@ -705,7 +796,9 @@ pub async fn backup_local_transactions_task<P>(
 mod tests {
     use super::*;
     use crate::{
-        blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
+        blobstore::InMemoryBlobStore,
+        test_utils::{testing_pool, MockTransaction},
+        validate::EthTransactionValidatorBuilder,
         CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionOrigin,
     };
     use alloy_consensus::transaction::PooledTransaction;
@@ -815,4 +908,45 @@ mod tests {
         assert_eq!(tracker.update(None), None);
         assert_eq!(tracker.last_finalized_block, None);
     }
+
+    #[test]
+    fn test_blob_params_tracker() {
+        let mut tracker = BlobParamsTracker::default();
+        assert!(!tracker.update(None));
+        assert!(tracker.update(Some(BlobParams::cancun())));
+        assert!(!tracker.update(Some(BlobParams::cancun())));
+        assert!(tracker.update(Some(BlobParams::prague())));
+        assert_eq!(
+            tracker.last_blob_counts,
+            Some((BlobParams::prague().target_blob_count, BlobParams::prague().max_blob_count))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_revalidate_blob_transactions() {
+        let pool = testing_pool();
+        let metrics = MaintainPoolMetrics::default();
+
+        let non_blob = MockTransaction::eip1559();
+        let local_blob = MockTransaction::eip4844_with_sidecar(Default::default());
+        let external_blob = MockTransaction::eip4844_with_sidecar(Default::default());
+        let missing_sidecar = MockTransaction::eip4844_with_sidecar(Default::default());
+        pool.add_transaction(TransactionOrigin::External, non_blob.clone()).await.unwrap();
+        pool.add_transaction(TransactionOrigin::Local, local_blob.clone()).await.unwrap();
+        pool.add_transaction(TransactionOrigin::External, external_blob.clone()).await.unwrap();
+        pool.add_transaction(TransactionOrigin::External, missing_sidecar.clone()).await.unwrap();
+        pool.delete_blob(*missing_sidecar.hash());
+
+        revalidate_blob_transactions(&pool, &metrics).await;
+
+        // other transactions aren't touched
+        assert!(pool.contains(non_blob.hash()));
+        // blob transactions are re-added with their origin and sidecar
+        assert_eq!(pool.get(local_blob.hash()).unwrap().origin, TransactionOrigin::Local);
+        assert_eq!(pool.get(external_blob.hash()).unwrap().origin, TransactionOrigin::External);
+        assert!(pool.get_blob(*external_blob.hash()).unwrap().is_some());
+        // blob transactions without a sidecar can't be re-validated and are discarded
+        assert!(!pool.contains(missing_sidecar.hash()));
+        assert_eq!(pool.pooled_transactions().iter().filter(|tx| tx.is_eip4844()).count(), 2);
+    }
 }
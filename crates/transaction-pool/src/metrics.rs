@@ -60,6 +60,11 @@ pub struct TxPoolMetrics {
     pub(crate) blob_transactions_evicted: Counter,
     /// Counter for the number of queued transactions evicted
     pub(crate) queued_transactions_evicted: Counter,
+
+    /// Counter for the number of basefee transactions that expired
+    pub(crate) basefee_transactions_expired: Counter,
+    /// Counter for the number of queued transactions that expired
+    pub(crate) queued_transactions_expired: Counter,
 }
 
 /// Transaction pool blobstore metrics
@@ -91,6 +96,9 @@ pub struct MaintainPoolMetrics {
     pub(crate) reinserted_transactions: Counter,
     /// Counter for the number of finalized blob transactions that have been removed from tracking.
     pub(crate) deleted_tracked_finalized_blobs: Counter,
+    /// Counter for the number of blob transactions that were discarded when they were re-validated
+    /// after a fork changed the blob params.
+    pub(crate) discarded_revalidated_blob_transactions: Counter,
 }
 
 impl MaintainPoolMetrics {
@@ -109,6 +117,11 @@ impl MaintainPoolMetrics {
         self.deleted_tracked_finalized_blobs.increment(count as u64);
     }
 
+    #[inline]
+    pub(crate) fn inc_discarded_revalidated_blob_transactions(&self, count: usize) {
+        self.discarded_revalidated_blob_transactions.increment(count as u64);
+    }
+
     #[inline]
     pub(crate) fn inc_drift(&self) {
         self.drift_count.increment(1);
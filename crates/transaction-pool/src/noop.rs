@@ -184,6 +184,10 @@ impl TransactionPool for NoopTransactionPool {
         vec![]
     }
 
+    fn remove_expired_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        vec![]
+    }
+
     fn remove_transactions_and_descendants(
         &self,
         _hashes: Vec<TxHash>,
@@ -88,13 +88,18 @@ use crate::{
     TransactionValidator,
 };
 
+use alloy_consensus::Transaction as _;
 use alloy_primitives::{Address, TxHash, B256};
 use best::BestTransactions;
 use parking_lot::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_execution_types::ChangedAccount;
 
-use alloy_eips::{eip4844::BlobTransactionSidecar, Typed2718};
+use alloy_eips::{
+    eip2718::Encodable2718,
+    eip4844::{BlobTransactionSidecar, BYTES_PER_BLOB},
+    Typed2718,
+};
 use reth_primitives_traits::Recovered;
 use rustc_hash::FxHashMap;
 use std::{collections::HashSet, fmt, sync::Arc, time::Instant};
@@ -337,6 +342,9 @@ where
     }
 
     /// Returns pooled transactions for the given transaction hashes.
+    ///
+    /// The transactions are returned until adding the next one would exceed the size limit, but
+    /// at least one transaction is returned.
     pub fn get_pooled_transaction_elements(
         &self,
         tx_hashes: Vec<TxHash>,
@@ -349,17 +357,23 @@ where
         let mut elements = Vec::with_capacity(transactions.len());
         let mut size = 0;
         for transaction in transactions {
-            let encoded_len = transaction.encoded_length();
+            // avoid loading the sidecar of a blob transaction that doesn't fit into the response
+            if !elements.is_empty() && limit.exceeds(size + min_pooled_encoded_length(&transaction))
+            {
+                break
+            }
+
             let Some(pooled) = self.to_pooled_transaction(transaction) else {
                 continue;
             };
 
-            size += encoded_len;
-            elements.push(pooled.into_inner());
-
-            if limit.exceeds(size) {
+            let encoded_len = pooled.encode_2718_len();
+            if !elements.is_empty() && limit.exceeds(size + encoded_len) {
                 break
             }
+
+            size += encoded_len;
+            elements.push(pooled.into_inner());
         }
 
         elements
@@ -742,6 +756,18 @@ where
         removed
     }
 
+    /// Removes and returns all transactions that exceeded the configured lifetime of their
+    /// sub-pool.
+    pub fn remove_expired_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let removed = self.pool.write().remove_expired_transactions();
+
+        let mut listener = self.event_listener.write();
+
+        removed.iter().for_each(|tx| listener.expired(tx.hash()));
+
+        removed
+    }
+
     /// Removes and returns all matching transactions and their dependent transactions from the
     /// pool.
     pub fn remove_transactions_and_descendants(
@@ -966,6 +992,17 @@ impl<V, T: TransactionOrdering, S> fmt::Debug for PoolInner<V, T, S> {
     }
 }
 
+/// Returns a lower bound of the encoded length of the transaction in a `PooledTransactions`
+/// response.
+///
+/// Blob transactions are served with their sidecar, which isn't included in the encoded length of
+/// blob transactions that were reinjected from a block.
+fn min_pooled_encoded_length<T: PoolTransaction>(transaction: &ValidPoolTransaction<T>) -> usize {
+    let blob_count =
+        transaction.transaction.blob_versioned_hashes().map_or(0, |hashes| hashes.len());
+    transaction.encoded_length().max(blob_count * BYTES_PER_BLOB)
+}
+
 /// Tracks an added transaction and all graph changes caused by adding it.
 #[derive(Debug, Clone)]
 pub struct AddedPendingTransaction<T: PoolTransaction> {
@@ -1182,9 +1219,10 @@ mod tests {
         blobstore::{BlobStore, InMemoryBlobStore},
         test_utils::{MockTransaction, TestPoolBuilder},
         validate::ValidTransaction,
-        BlockInfo, PoolConfig, SubPoolLimit, TransactionOrigin, TransactionValidationOutcome, U256,
+        BlockInfo, GetPooledTransactionLimit, PoolConfig, SubPoolLimit, TransactionOrigin,
+        TransactionValidationOutcome, U256,
     };
-    use alloy_eips::eip4844::BlobTransactionSidecar;
+    use alloy_eips::eip4844::{Blob, BlobTransactionSidecar, Bytes48, BYTES_PER_BLOB};
     use std::{fs, path::PathBuf};
 
     #[test]
@@ -1267,4 +1305,51 @@ mod tests {
         // Assert that the pool's blob store matches the expected blob store.
         assert_eq!(*test_pool.blob_store(), blob_store);
     }
+
+    #[test]
+    fn test_pooled_transaction_elements_size_limit() {
+        let test_pool = &TestPoolBuilder::default().pool;
+        let sidecar = BlobTransactionSidecar::new(
+            vec![Blob::default()],
+            vec![Bytes48::default()],
+            vec![Bytes48::default()],
+        );
+
+        let mut hashes = Vec::new();
+        for _ in 0..2 {
+            let tx = MockTransaction::eip4844_with_sidecar(sidecar.clone());
+            hashes.push(*tx.get_hash());
+            test_pool.add_transactions(
+                TransactionOrigin::External,
+                [TransactionValidationOutcome::Valid {
+                    balance: U256::from(1_000),
+                    state_nonce: 0,
+                    transaction: ValidTransaction::ValidWithSidecar {
+                        transaction: tx,
+                        sidecar: sidecar.clone(),
+                    },
+                    propagate: true,
+                }],
+            );
+        }
+
+        let all = test_pool
+            .get_pooled_transaction_elements(hashes.clone(), GetPooledTransactionLimit::None);
+        assert_eq!(all.len(), 2);
+
+        // the size of the blobs is accounted for, even if it exceeds the encoded length of the
+        // pool transactions
+        let limited = test_pool.get_pooled_transaction_elements(
+            hashes.clone(),
+            GetPooledTransactionLimit::ResponseSizeSoftLimit(BYTES_PER_BLOB * 3 / 2),
+        );
+        assert_eq!(limited.len(), 1);
+
+        // a transaction is returned even if it exceeds the limit on its own
+        let limited = test_pool.get_pooled_transaction_elements(
+            hashes,
+            GetPooledTransactionLimit::ResponseSizeSoftLimit(1),
+        );
+        assert_eq!(limited.len(), 1);
+    }
 }
@@ -36,6 +36,7 @@ use std::{
     fmt,
     ops::Bound::{Excluded, Unbounded},
     sync::Arc,
+    time::Duration,
 };
 use tracing::trace;
 
@@ -776,6 +777,45 @@ impl<T: TransactionOrdering> TxPool<T> {
         txs
     }
 
+    /// Removes and returns all external transactions of the parked sub-pools that exceeded the
+    /// configured lifetime of their sub-pool.
+    ///
+    /// Expired transactions of the basefee sub-pool are removed with their descendants, which
+    /// would otherwise be left behind a nonce gap.
+    ///
+    /// Note: like [`Self::remove_transactions`] this does not apply any additional updates.
+    pub(crate) fn remove_expired_transactions(
+        &mut self,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let is_expired = |tx: &Arc<ValidPoolTransaction<T::Transaction>>, lifetime: Duration| {
+            tx.origin.is_external() && tx.timestamp.elapsed() > lifetime
+        };
+
+        let queued_lifetime = self.config.max_queued_lifetime;
+        let queued: Vec<_> = self
+            .queued_pool
+            .all()
+            .filter(|tx| is_expired(tx, queued_lifetime))
+            .map(|tx| *tx.hash())
+            .collect();
+        let basefee: Vec<_> = match self.config.max_basefee_lifetime {
+            Some(lifetime) => self
+                .basefee_pool
+                .all()
+                .filter(|tx| is_expired(tx, lifetime))
+                .map(|tx| *tx.hash())
+                .collect(),
+            None => Vec::new(),
+        };
+
+        self.metrics.queued_transactions_expired.increment(queued.len() as u64);
+        self.metrics.basefee_transactions_expired.increment(basefee.len() as u64);
+
+        let mut removed = self.remove_transactions(queued);
+        removed.extend(self.remove_transactions_and_descendants(basefee));
+        removed
+    }
+
     /// Removes and returns all matching transactions and their descendants from the pool.
     pub(crate) fn remove_transactions_and_descendants(
         &mut self,
@@ -1102,6 +1142,8 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     block_gas_limit: u64,
     /// Max number of executable transaction slots guaranteed per account
     max_account_slots: usize,
+    /// Max number of pending transactions per account, if limited.
+    max_pending_account_slots: Option<usize>,
     /// _All_ transactions identified by their hash.
     by_hash: HashMap<TxHash, Arc<ValidPoolTransaction<T>>>,
     /// _All_ transaction in the pool sorted by their sender and nonce pair.
@@ -1127,6 +1169,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
     fn new(config: &PoolConfig) -> Self {
         Self {
             max_account_slots: config.max_account_slots,
+            max_pending_account_slots: config.max_pending_account_slots,
             price_bumps: config.price_bumps,
             local_transactions_config: config.local_transactions_config.clone(),
             minimal_protocol_basefee: config.minimal_protocol_basefee,
@@ -1300,8 +1343,17 @@ impl<T: PoolTransaction> AllTransactions<T> {
             // Track if the transaction's sub-pool changed.
             Self::record_subpool_update(&mut updates, tx);
 
+            // Only a limited number of the sender's transactions can be pending, the remaining
+            // ones are parked.
+            let pending_slots = Self::pending_account_slots(
+                self.max_pending_account_slots,
+                &self.local_transactions_config,
+                &tx.transaction,
+            );
+            let mut pending = usize::from(tx.state.is_pending());
+
             // Track blocking transactions.
-            let mut has_parked_ancestor = !tx.state.is_pending();
+            let mut has_parked_ancestor = !tx.state.is_pending() || pending >= pending_slots;
 
             let mut cumulative_cost = tx.next_cumulative_cost();
 
@@ -1348,7 +1400,8 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 } else {
                     tx.state.insert(TxState::NO_PARKED_ANCESTORS);
                 }
-                has_parked_ancestor = !tx.state.is_pending();
+                pending += usize::from(tx.state.is_pending());
+                has_parked_ancestor = !tx.state.is_pending() || pending >= pending_slots;
 
                 // Update and record sub-pool changes.
                 Self::update_tx_base_fee(self.pending_fees.base_fee, tx);
@@ -1379,6 +1432,26 @@ impl<T: PoolTransaction> AllTransactions<T> {
         }
     }
 
+    /// Returns the number of transactions of the transaction's sender that can be pending at the
+    /// same time.
+    ///
+    /// Local transactions are exempt from the limit, unless configured otherwise.
+    fn pending_account_slots(
+        max_pending_account_slots: Option<usize>,
+        local_transactions_config: &LocalTransactionConfig,
+        transaction: &ValidPoolTransaction<T>,
+    ) -> usize {
+        match max_pending_account_slots {
+            Some(slots)
+                if !local_transactions_config
+                    .is_local(transaction.origin, transaction.sender_ref()) =>
+            {
+                slots
+            }
+            _ => usize::MAX,
+        }
+    }
+
     /// Rechecks the transaction's dynamic fee condition.
     fn update_tx_base_fee(pending_block_base_fee: u64, tx: &mut PoolInternalTransaction<T>) {
         // Recheck dynamic fee condition.
@@ -1737,6 +1810,15 @@ impl<T: PoolTransaction> AllTransactions<T> {
             // pending, so we can set this to `false`
             let mut has_parked_ancestor = false;
 
+            // Only a limited number of the sender's transactions can be pending, the remaining
+            // ones are parked.
+            let pending_slots = Self::pending_account_slots(
+                self.max_pending_account_slots,
+                &self.local_transactions_config,
+                &transaction,
+            );
+            let mut pending = 0;
+
             // Traverse all future transactions of the sender starting with the on chain nonce, and
             // update existing transactions: `[on_chain_nonce,..]`
             for (id, tx) in self.descendant_txs_mut(&on_chain_id) {
@@ -1769,7 +1851,8 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 } else {
                     tx.state.insert(TxState::NO_PARKED_ANCESTORS);
                 }
-                has_parked_ancestor = !tx.state.is_pending();
+                pending += usize::from(tx.state.is_pending());
+                has_parked_ancestor = !tx.state.is_pending() || pending >= pending_slots;
 
                 // update the pool based on the state
                 tx.subpool = tx.state.into();
@@ -1835,6 +1918,7 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
     fn default() -> Self {
         Self {
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+            max_pending_account_slots: None,
             minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE,
             block_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
             by_hash: Default::default(),
@@ -1970,6 +2054,7 @@ mod tests {
     };
     use alloy_consensus::{Transaction, TxType};
     use alloy_primitives::address;
+    use std::time::Instant;
 
     #[test]
     fn test_insert_blob() {
@@ -2696,6 +2781,64 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn spills_pending_transactions_over_sender_limit() {
+        let on_chain_balance = U256::MAX;
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = AllTransactions::new(&PoolConfig {
+            max_pending_account_slots: Some(2),
+            ..Default::default()
+        });
+
+        let tx = MockTransaction::eip1559().inc_price().inc_limit();
+        let txs = [tx.clone(), tx.next(), tx.next().next(), tx.next().next().next()]
+            .map(|tx| f.validated(tx));
+        for tx in &txs {
+            pool.insert_tx(tx.clone(), on_chain_balance, on_chain_nonce).unwrap();
+        }
+
+        let subpool = |pool: &AllTransactions<_>, tx: &ValidPoolTransaction<_>| {
+            pool.txs.get(tx.id()).unwrap().subpool
+        };
+        assert_eq!(subpool(&pool, &txs[0]), SubPool::Pending);
+        assert_eq!(subpool(&pool, &txs[1]), SubPool::Pending);
+        assert_eq!(subpool(&pool, &txs[2]), SubPool::Queued);
+        assert_eq!(subpool(&pool, &txs[3]), SubPool::Queued);
+
+        // once the first transaction is mined, the next one is promoted
+        let mut changed_senders = FxHashMap::default();
+        changed_senders
+            .insert(txs[0].sender_id(), SenderInfo { state_nonce: 1, balance: on_chain_balance });
+        let updates = pool.update(&changed_senders);
+        assert!(updates.iter().any(|update| update.id == *txs[2].id() &&
+            matches!(update.destination, Destination::Pool(SubPool::Pending))));
+        assert_eq!(subpool(&pool, &txs[3]), SubPool::Queued);
+    }
+
+    #[test]
+    fn local_transactions_exempt_from_pending_sender_limit() {
+        let on_chain_balance = U256::MAX;
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = AllTransactions::new(&PoolConfig {
+            max_pending_account_slots: Some(1),
+            ..Default::default()
+        });
+
+        let tx = MockTransaction::eip1559().inc_price().inc_limit();
+        for tx in [tx.clone(), tx.next()] {
+            let InsertOk { move_to, .. } = pool
+                .insert_tx(
+                    f.validated_with_origin(TransactionOrigin::Local, tx),
+                    on_chain_balance,
+                    on_chain_nonce,
+                )
+                .unwrap();
+            assert_eq!(move_to, SubPool::Pending);
+        }
+    }
+
     #[test]
     fn reject_tx_over_gas_limit() {
         let on_chain_balance = U256::from(1_000);
@@ -2947,6 +3090,67 @@ mod tests {
         assert_eq!(removed.len(), 1);
     }
 
+    #[test]
+    fn remove_expired_transactions() {
+        let mut f = MockTransactionFactory::default();
+        let config = PoolConfig {
+            max_queued_lifetime: Duration::from_secs(10),
+            max_basefee_lifetime: Some(Duration::from_secs(100)),
+            ..Default::default()
+        };
+        let mut pool = TxPool::new(MockOrdering::default(), config);
+        pool.update_basefee(100);
+
+        let mut add = |tx: MockTransaction, origin, age| {
+            let mut tx = f.validated_with_origin(origin, tx);
+            tx.timestamp = Instant::now() - Duration::from_secs(age);
+            let hash = *tx.hash();
+            pool.add_transaction(tx, U256::from(1_000), 0).unwrap();
+            hash
+        };
+
+        // nonce gapped transactions are queued
+        let queued = MockTransaction::eip1559().with_max_fee(200).with_nonce(1);
+        let expired_queued = add(queued.clone(), TransactionOrigin::External, 20);
+        add(queued.clone().rng_hash().with_sender(Address::random()), TransactionOrigin::Local, 20);
+        add(queued.rng_hash().with_sender(Address::random()), TransactionOrigin::External, 5);
+
+        // transactions below the base fee are parked in the basefee pool
+        let basefee = MockTransaction::eip1559();
+        let expired_basefee = add(basefee.clone(), TransactionOrigin::External, 200);
+        // the descendant of an expired transaction is removed as well
+        let descendant = add(basefee.next(), TransactionOrigin::External, 0);
+        add(basefee.rng_hash().with_sender(Address::random()), TransactionOrigin::External, 20);
+
+        assert_eq!(pool.queued_pool.len(), 3);
+        assert_eq!(pool.basefee_pool.len(), 3);
+
+        let removed = pool.remove_expired_transactions();
+        let removed = removed.iter().map(|tx| *tx.hash()).collect::<HashSet<_>>();
+        assert_eq!(removed, HashSet::from([expired_queued, expired_basefee, descendant]));
+        assert_eq!(pool.queued_pool.len(), 2);
+        assert_eq!(pool.basefee_pool.len(), 1);
+    }
+
+    #[test]
+    fn basefee_transactions_dont_expire_by_default() {
+        let mut f = MockTransactionFactory::default();
+        let config =
+            PoolConfig { max_queued_lifetime: Duration::from_secs(10), ..Default::default() };
+        assert_eq!(config.min_lifetime(), Duration::from_secs(10));
+        let mut pool = TxPool::new(MockOrdering::default(), config);
+        pool.update_basefee(100);
+
+        let mut tx =
+            f.validated_with_origin(TransactionOrigin::External, MockTransaction::eip1559());
+        tx.timestamp = Instant::now() - Duration::from_secs(200);
+        pool.add_transaction(tx, U256::from(1_000), 0).unwrap();
+        assert_eq!(pool.basefee_pool.len(), 1);
+
+        assert!(pool.remove_expired_transactions().is_empty());
+        assert_eq!(pool.basefee_pool.len(), 1);
+    }
+
     #[test]
     fn discard_at_capacity() {
         let mut f = MockTransactionFactory::default();
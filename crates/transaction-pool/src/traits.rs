@@ -238,6 +238,10 @@ pub trait TransactionPool: Send + Sync + Clone {
     ///
     /// If the transaction is a blob transaction, the sidecar will be included.
     ///
+    /// Transactions are returned until the next one would exceed the size limit, measured by the
+    /// encoded length of the returned transactions including blob sidecars. At least one
+    /// transaction is returned, even if it exceeds the limit on its own.
+    ///
     /// Consumer: P2P
     fn get_pooled_transaction_elements(
         &self,
@@ -321,6 +325,12 @@ pub trait TransactionPool: Send + Sync + Clone {
         hashes: Vec<TxHash>,
     ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
 
+    /// Removes all transactions that exceeded the lifetime of their sub-pool, see
+    /// [`PoolConfig::max_queued_lifetime`](crate::PoolConfig::max_queued_lifetime).
+    ///
+    /// Consumer: Utility
+    fn remove_expired_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
+
     /// Removes all transactions corresponding to the given hashes.
     ///
     /// Also removes all _dependent_ transactions.
@@ -11,8 +11,4 @@ pub const TX_SLOT_BYTE_SIZE: usize = 32 * 1024;
 /// which defaults to 32 KiB, so 128 KiB.
 pub const DEFAULT_MAX_TX_INPUT_BYTES: usize = 4 * TX_SLOT_BYTE_SIZE; // 128KB
 
-/// Maximum bytecode to permit for a contract.
-pub const MAX_CODE_BYTE_SIZE: usize = 24576;
-
-/// Maximum initcode to permit in a creation transaction and create instructions.
-pub const MAX_INIT_CODE_BYTE_SIZE: usize = 2 * MAX_CODE_BYTE_SIZE;
+pub use reth_primitives_traits::constants::{MAX_CODE_BYTE_SIZE, MAX_INIT_CODE_BYTE_SIZE};
@@ -0,0 +1,267 @@
+//! Denylist of transaction targets.
+
+use crate::error::PoolTransactionError;
+use alloy_primitives::{map::HashMap, Address, B256};
+use parking_lot::{Mutex, RwLock};
+use reth_fs_util::FsPathError;
+use std::{
+    any::Any,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::SystemTime,
+};
+use tracing::{debug, warn};
+
+/// What to do with a transaction that targets a denied contract.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DenylistAction {
+    /// Reject the transaction.
+    #[default]
+    Reject,
+    /// Accept the transaction, but don't propagate it to peers.
+    Deprioritize,
+}
+
+impl FromStr for DenylistAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "reject" => Ok(Self::Reject),
+            "deprioritize" => Ok(Self::Deprioritize),
+            _ => Err(format!("unknown denylist action: {s}")),
+        }
+    }
+}
+
+/// The denied contract addresses and code hashes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DenylistRules {
+    /// Denied contract addresses.
+    pub addresses: HashMap<Address, DenylistAction>,
+    /// Denied code hashes, matching every contract deployed with that code.
+    pub code_hashes: HashMap<B256, DenylistAction>,
+}
+
+impl DenylistRules {
+    /// Parses the rules from the content of a denylist file.
+    ///
+    /// Every line holds an address or a code hash, optionally followed by the action to apply,
+    /// `reject` by default. Empty lines and everything after a `#` are ignored:
+    ///
+    /// ```text
+    /// # griefing contract
+    /// 0x00000000000000000000000000000000000000aa
+    /// 0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470 deprioritize
+    /// ```
+    pub fn parse(content: &str) -> Result<Self, DenylistError> {
+        let mut rules = Self::default();
+        for (idx, line) in content.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default();
+            let mut parts = line.split_whitespace();
+            let Some(target) = parts.next() else { continue };
+            let invalid = |reason: String| DenylistError::InvalidLine { line: idx + 1, reason };
+
+            let action = parts.next().map(str::parse).transpose().map_err(invalid)?;
+            if let Some(extra) = parts.next() {
+                return Err(invalid(format!("unexpected token: {extra}")))
+            }
+            let action = action.unwrap_or_default();
+
+            if let Ok(address) = target.parse::<Address>() {
+                rules.addresses.insert(address, action);
+            } else if let Ok(code_hash) = target.parse::<B256>() {
+                rules.code_hashes.insert(code_hash, action);
+            } else {
+                return Err(invalid(format!("not an address or code hash: {target}")))
+            }
+        }
+        Ok(rules)
+    }
+
+    /// Returns `true` if there are no rules.
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty() && self.code_hashes.is_empty()
+    }
+}
+
+/// Errors when loading a [`TargetDenylist`].
+#[derive(Debug, thiserror::Error)]
+pub enum DenylistError {
+    /// Failed to read the denylist file.
+    #[error(transparent)]
+    Io(#[from] FsPathError),
+    /// A line of the denylist file is invalid.
+    #[error("invalid denylist entry on line {line}: {reason}")]
+    InvalidLine {
+        /// The line number, starting at 1.
+        line: usize,
+        /// Why the line is invalid.
+        reason: String,
+    },
+}
+
+/// A denylist of contracts that pool transactions must not target, e.g. known griefing contracts.
+///
+/// Contracts are matched by their address, or by the hash of their deployed code. The denylist
+/// can be backed by a file, which is reloaded by [`TargetDenylist::reload_if_modified`] whenever
+/// it changed.
+#[derive(Debug, Default)]
+pub struct TargetDenylist {
+    /// The file the rules are loaded from, if any.
+    path: Option<PathBuf>,
+    /// The modification time of the file when the rules were loaded.
+    modified: Mutex<Option<SystemTime>>,
+    /// The current rules.
+    rules: RwLock<DenylistRules>,
+}
+
+impl TargetDenylist {
+    /// Creates a denylist with fixed rules.
+    pub fn new(rules: DenylistRules) -> Self {
+        Self { rules: RwLock::new(rules), ..Default::default() }
+    }
+
+    /// Loads the denylist from the given file.
+    pub fn from_file(path: impl Into<PathBuf>) -> Result<Self, DenylistError> {
+        let denylist = Self { path: Some(path.into()), ..Default::default() };
+        denylist.reload()?;
+        Ok(denylist)
+    }
+
+    /// Returns the file the denylist is loaded from, if any.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Returns a copy of the current rules.
+    pub fn rules(&self) -> DenylistRules {
+        self.rules.read().clone()
+    }
+
+    /// Replaces the current rules.
+    pub fn set_rules(&self, rules: DenylistRules) {
+        *self.rules.write() = rules;
+    }
+
+    /// Reloads the rules from the file, if the denylist is backed by a file.
+    pub fn reload(&self) -> Result<(), DenylistError> {
+        let Some(path) = &self.path else { return Ok(()) };
+        let modified = reth_fs_util::metadata(path)?.modified().ok();
+        let rules = DenylistRules::parse(&reth_fs_util::read_to_string(path)?)?;
+        debug!(
+            target: "txpool",
+            path = %path.display(),
+            addresses = rules.addresses.len(),
+            code_hashes = rules.code_hashes.len(),
+            "Loaded transaction target denylist"
+        );
+        self.set_rules(rules);
+        *self.modified.lock() = modified;
+        Ok(())
+    }
+
+    /// Reloads the rules if the file was modified since they were last loaded.
+    ///
+    /// If the file can't be loaded, the previous rules are kept.
+    pub fn reload_if_modified(&self) {
+        let Some(path) = &self.path else { return };
+        let modified = std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+        if modified.is_some() && modified == *self.modified.lock() {
+            return
+        }
+        if let Err(err) = self.reload() {
+            warn!(target: "txpool", %err, "Failed to reload transaction target denylist");
+        }
+    }
+
+    /// Returns the action for a transaction to the given address, which has the given code hash.
+    pub fn check(&self, to: &Address, code_hash: Option<&B256>) -> Option<DenylistAction> {
+        let rules = self.rules.read();
+        rules
+            .addresses
+            .get(to)
+            .or_else(|| code_hash.and_then(|code_hash| rules.code_hashes.get(code_hash)))
+            .copied()
+    }
+
+    /// Returns `true` if code hashes need to be looked up to check transactions.
+    pub fn has_code_hashes(&self) -> bool {
+        !self.rules.read().code_hashes.is_empty()
+    }
+}
+
+/// A transaction was rejected because it targets a denied contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("transaction target {0} is denied")]
+pub struct DeniedTargetError(pub Address);
+
+impl PoolTransactionError for DeniedTargetError {
+    fn is_bad_transaction(&self) -> bool {
+        // the denylist is local policy, the transaction itself is valid
+        false
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256};
+
+    #[test]
+    fn parse_rules() {
+        let rules = DenylistRules::parse(
+            "# griefing contracts\n\
+             0x00000000000000000000000000000000000000aa\n\
+             \n\
+             0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470 deprioritize # x\n",
+        )
+        .unwrap();
+        assert_eq!(
+            rules.addresses.get(&address!("0x00000000000000000000000000000000000000aa")),
+            Some(&DenylistAction::Reject)
+        );
+        assert_eq!(
+            rules
+                .code_hashes
+                .get(&b256!("0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")),
+            Some(&DenylistAction::Deprioritize)
+        );
+
+        assert!(matches!(
+            DenylistRules::parse("0xaa\n"),
+            Err(DenylistError::InvalidLine { line: 1, .. })
+        ));
+        assert!(matches!(
+            DenylistRules::parse("\n0x00000000000000000000000000000000000000aa drop"),
+            Err(DenylistError::InvalidLine { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn reload_modified_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("denylist.txt");
+        let target = address!("0x00000000000000000000000000000000000000aa");
+
+        reth_fs_util::write(&path, "").unwrap();
+        let denylist = TargetDenylist::from_file(&path).unwrap();
+        assert_eq!(denylist.check(&target, None), None);
+
+        reth_fs_util::write(&path, format!("{target} deprioritize")).unwrap();
+        // force a different modification time, file systems may have a coarse resolution
+        *denylist.modified.lock() = Some(SystemTime::UNIX_EPOCH);
+        denylist.reload_if_modified();
+        assert_eq!(denylist.check(&target, None), Some(DenylistAction::Deprioritize));
+
+        // invalid files keep the previous rules
+        reth_fs_util::write(&path, "invalid").unwrap();
+        *denylist.modified.lock() = Some(SystemTime::UNIX_EPOCH);
+        denylist.reload_if_modified();
+        assert_eq!(denylist.check(&target, None), Some(DenylistAction::Deprioritize));
+    }
+}
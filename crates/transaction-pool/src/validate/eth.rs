@@ -7,7 +7,10 @@ use crate::{
         Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
     },
     traits::TransactionOrigin,
-    validate::{ValidTransaction, ValidationTask, MAX_INIT_CODE_BYTE_SIZE},
+    validate::{
+        DeniedTargetError, DenylistAction, TargetDenylist, ValidTransaction, ValidationTask,
+        MAX_INIT_CODE_BYTE_SIZE,
+    },
     EthBlobTransactionSidecar, EthPoolTransaction, LocalTransactionConfig,
     TransactionValidationOutcome, TransactionValidationTaskExecutor, TransactionValidator,
 };
@@ -22,7 +25,9 @@ use alloy_eips::{
     eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M, eip4844::env_settings::EnvKzgSettings,
     eip7840::BlobParams,
 };
-use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
+use reth_chainspec::{
+    ChainSpecProvider, EthChainSpec, EthereumHardforks, BLOCK_RLP_LENGTH_RESERVE,
+};
 use reth_primitives_traits::{
     transaction::error::InvalidTransactionError, Block, GotExpected, SealedBlock,
 };
@@ -159,6 +164,8 @@ pub(crate) struct EthTransactionValidatorInner<Client, T> {
     local_transactions_config: LocalTransactionConfig,
     /// Maximum size in bytes a single transaction can have in order to be accepted into the pool.
     max_tx_input_bytes: usize,
+    /// Denylist of contracts transactions must not target.
+    target_denylist: Option<Arc<TargetDenylist>>,
     /// Marker for the transaction type
     _marker: PhantomData<T>,
 }
@@ -250,6 +257,17 @@ where
             )
         }
 
+        // Reject transactions that don't fit into a block of the chain's maximum block size
+        if let Some(max_rlp_length) = self.chain_spec().block_limits().max_rlp_length {
+            let tx_len = transaction.encoded_length();
+            if BLOCK_RLP_LENGTH_RESERVE + tx_len > max_rlp_length {
+                return TransactionValidationOutcome::Invalid(
+                    transaction,
+                    InvalidPoolTransactionError::OversizedData(tx_len, max_rlp_length),
+                )
+            }
+        }
+
         // Check whether the init code size has been exceeded.
         if self.fork_tracker.is_shanghai_activated() {
             if let Err(err) = transaction.ensure_max_init_code_size(MAX_INIT_CODE_BYTE_SIZE) {
@@ -433,6 +451,35 @@ where
             )
         }
 
+        // Checks the target against the denylist
+        let mut denied = false;
+        if let (Some(denylist), Some(to)) = (&self.target_denylist, transaction.to()) {
+            let code_hash = if denylist.has_code_hashes() {
+                match state.basic_account(&to) {
+                    Ok(account) => account.and_then(|account| account.bytecode_hash),
+                    Err(err) => {
+                        return TransactionValidationOutcome::Error(
+                            *transaction.hash(),
+                            Box::new(err),
+                        )
+                    }
+                }
+            } else {
+                None
+            };
+
+            match denylist.check(&to, code_hash.as_ref()) {
+                Some(DenylistAction::Reject) => {
+                    return TransactionValidationOutcome::Invalid(
+                        transaction,
+                        InvalidPoolTransactionError::Other(Box::new(DeniedTargetError(to))),
+                    )
+                }
+                Some(DenylistAction::Deprioritize) => denied = true,
+                None => {}
+            }
+        }
+
         let mut maybe_blob_sidecar = None;
 
         // heavy blob tx validation
@@ -483,8 +530,10 @@ where
             balance: account.balance,
             state_nonce: account.nonce,
             transaction: ValidTransaction::new(transaction, maybe_blob_sidecar),
-            // by this point assume all external transactions should be propagated
+            // by this point assume all external transactions should be propagated, unless their
+            // target is deprioritized
             propagate: match origin {
+                _ if denied => false,
                 TransactionOrigin::External => true,
                 TransactionOrigin::Local => {
                     self.local_transactions_config.propagate_local_transactions
@@ -539,6 +588,10 @@ where
         }
 
         self.block_gas_limit.store(new_tip_block.gas_limit(), std::sync::atomic::Ordering::Relaxed);
+
+        if let Some(denylist) = &self.target_denylist {
+            denylist.reload_if_modified();
+        }
     }
 
     fn max_gas_limit(&self) -> u64 {
@@ -581,6 +634,8 @@ pub struct EthTransactionValidatorBuilder<Client> {
     local_transactions_config: LocalTransactionConfig,
     /// Max size in bytes of a single transaction allowed
     max_tx_input_bytes: usize,
+    /// Denylist of contracts transactions must not target.
+    target_denylist: Option<Arc<TargetDenylist>>,
 }
 
 impl<Client> EthTransactionValidatorBuilder<Client> {
@@ -601,6 +656,7 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
             kzg_settings: EnvKzgSettings::Default,
             local_transactions_config: Default::default(),
             max_tx_input_bytes: DEFAULT_MAX_TX_INPUT_BYTES,
+            target_denylist: None,
 
             // by default all transaction types are allowed
             eip2718: true,
@@ -740,6 +796,14 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
         self
     }
 
+    /// Sets the denylist of contracts transactions must not target.
+    ///
+    /// File backed denylists are reloaded on new head blocks if the file was modified.
+    pub fn with_target_denylist(mut self, target_denylist: Arc<TargetDenylist>) -> Self {
+        self.target_denylist = Some(target_denylist);
+        self
+    }
+
     /// Sets the block gas limit
     ///
     /// Transactions with a gas limit greater than this will be rejected.
@@ -767,6 +831,7 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
             kzg_settings,
             local_transactions_config,
             max_tx_input_bytes,
+            target_denylist,
             ..
         } = self;
 
@@ -796,6 +861,7 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
             kzg_settings,
             local_transactions_config,
             max_tx_input_bytes,
+            target_denylist,
             _marker: Default::default(),
         };
 
@@ -896,24 +962,8 @@ pub fn ensure_intrinsic_gas<T: EthPoolTransaction>(
         SpecId::MERGE
     };
 
-    let gas = revm_interpreter::gas::calculate_initial_tx_gas(
-        spec_id,
-        transaction.input(),
-        transaction.is_create(),
-        transaction.access_list().map(|l| l.len()).unwrap_or_default() as u64,
-        transaction
-            .access_list()
-            .map(|l| l.iter().map(|i| i.storage_keys.len()).sum::<usize>())
-            .unwrap_or_default() as u64,
-        transaction.authorization_list().map(|l| l.len()).unwrap_or_default() as u64,
-    );
-
-    let gas_limit = transaction.gas_limit();
-    if gas_limit < gas.initial_gas || gas_limit < gas.floor_gas {
-        Err(InvalidPoolTransactionError::IntrinsicGasTooLow)
-    } else {
-        Ok(())
-    }
+    reth_primitives_traits::transaction::validation::ensure_intrinsic_gas(transaction, spec_id)
+        .map_err(|_| InvalidPoolTransactionError::IntrinsicGasTooLow)
 }
 
 #[cfg(test)]
@@ -926,6 +976,7 @@ mod tests {
     use alloy_consensus::Transaction;
     use alloy_eips::eip2718::Decodable2718;
     use alloy_primitives::{hex, U256};
+    use reth_chainspec::{BlockLimits, ChainSpec, ChainSpecBuilder};
     use reth_ethereum_primitives::PooledTransaction;
     use reth_primitives_traits::SignedTransaction;
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
@@ -1012,4 +1063,35 @@ mod tests {
         let tx = pool.get(transaction.hash());
         assert!(tx.is_none());
     }
+
+    #[tokio::test]
+    async fn invalid_on_exceeding_max_block_size() {
+        let transaction = get_transaction();
+
+        let max_rlp_length = BLOCK_RLP_LENGTH_RESERVE + 1_000;
+        let chain_spec = ChainSpec {
+            block_limits: BlockLimits {
+                max_rlp_length: Some(max_rlp_length),
+                ..Default::default()
+            },
+            ..ChainSpecBuilder::mainnet().build()
+        };
+        let provider = MockEthProvider::default().with_chain_spec(chain_spec);
+        provider.add_account(
+            transaction.sender(),
+            ExtendedAccount::new(transaction.nonce(), U256::MAX),
+        );
+
+        let validator =
+            EthTransactionValidatorBuilder::new(provider).build(InMemoryBlobStore::default());
+
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction.clone());
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::OversizedData(len, max)
+            ) if len == transaction.encoded_length() && max == max_rlp_length
+        ));
+    }
 }
@@ -13,9 +13,13 @@ use reth_primitives_traits::{Recovered, SealedBlock};
 use std::{fmt, future::Future, time::Instant};
 
 mod constants;
+mod denylist;
 mod eth;
 mod task;
 
+pub use denylist::{
+    DeniedTargetError, DenylistAction, DenylistError, DenylistRules, TargetDenylist,
+};
 pub use eth::*;
 
 pub use task::{TransactionValidationTaskExecutor, ValidationTask};
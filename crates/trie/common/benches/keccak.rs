@@ -0,0 +1,58 @@
+#![allow(missing_docs, unreachable_pub)]
+use alloy_primitives::keccak256;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use reth_trie_common::{
+    hash_builder::BatchedHashBuilder, keccak::keccak256_batch, HashBuilder, Nibbles,
+};
+use std::hint::black_box;
+
+pub fn keccak_batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Keccak Batch");
+
+    // Leaf nodes of the storage and account tries are around 70 and 110 bytes long
+    for len in [70, 110] {
+        let inputs = (0..16u8).map(|i| vec![i; len]).collect::<Vec<_>>();
+
+        group.bench_function(BenchmarkId::new("keccak256", len), |b| {
+            b.iter(|| inputs.iter().map(keccak256).collect::<Vec<_>>())
+        });
+
+        group.bench_function(BenchmarkId::new("keccak256_batch", len), |b| {
+            b.iter(|| keccak256_batch(black_box(&inputs)))
+        });
+    }
+}
+
+pub fn hash_builder_root(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Hash Builder Root");
+    group.sample_size(20);
+
+    for size in [1_000, 10_000, 100_000] {
+        let mut keys = (0..size as u64).map(|i| keccak256(i.to_be_bytes())).collect::<Vec<_>>();
+        keys.sort_unstable();
+        let leaves = keys.into_iter().map(|key| (Nibbles::unpack(key), key)).collect::<Vec<_>>();
+
+        group.bench_function(BenchmarkId::new("hash builder", size), |b| {
+            b.iter(|| {
+                let mut hash_builder = HashBuilder::default();
+                for (key, value) in &leaves {
+                    hash_builder.add_leaf(key.clone(), value.as_slice());
+                }
+                hash_builder.root()
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("batched hash builder", size), |b| {
+            b.iter(|| {
+                let mut hash_builder = BatchedHashBuilder::new(HashBuilder::default());
+                for (key, value) in &leaves {
+                    hash_builder.add_leaf(key.clone(), value.as_slice());
+                }
+                hash_builder.root()
+            })
+        });
+    }
+}
+
+criterion_group!(keccak, keccak_batch, hash_builder_root);
+criterion_main!(keccak);
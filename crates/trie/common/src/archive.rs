@@ -0,0 +1,455 @@
+//! Zero-copy archival format for hashed state and trie updates, based on [`rkyv`].
+//!
+//! Map and set entries are archived in ascending key order, which makes the archival format
+//! canonical: equal values are always archived to equal bytes. Archived bytes are validated when
+//! accessed and must be aligned, e.g. stored in an [`AlignedVec`].
+
+use crate::{
+    updates::{StorageTrieUpdates, TrieUpdates},
+    BranchNodeCompact, HashedPostState, HashedStorage, Nibbles, TrieMask,
+};
+use alloc::vec::Vec;
+use alloy_primitives::{
+    map::{HashMap, HashSet},
+    B256, U256,
+};
+use reth_primitives_traits::Account;
+use rkyv::{rancor, Archive, Deserialize, Serialize};
+
+pub use rkyv::util::AlignedVec;
+
+/// Errors that can occur while archiving or reading archived hashed state and trie updates.
+#[derive(Debug, derive_more::Display, derive_more::From)]
+pub enum ArchiveError {
+    /// Failed to serialize or validate the archived bytes.
+    #[display("invalid archive: {_0}")]
+    Rkyv(rancor::Error),
+    /// An archived trie node path contains a value that isn't a nibble.
+    #[display("invalid archived trie node path")]
+    InvalidPath,
+    /// The masks of an archived branch node don't match its children.
+    #[display("invalid archived branch node")]
+    InvalidBranchNode,
+}
+
+impl core::error::Error for ArchiveError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Rkyv(err) => Some(err),
+            Self::InvalidPath | Self::InvalidBranchNode => None,
+        }
+    }
+}
+
+/// Archival representation of a [`HashedPostState`].
+#[derive(Archive, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[rkyv(derive(Debug))]
+pub struct HashedPostStateArchive {
+    /// Changed accounts, in ascending order of their hashed addresses.
+    pub accounts: Vec<HashedAccountArchive>,
+    /// Changed storages, in ascending order of their hashed addresses.
+    pub storages: Vec<HashedStorageArchive>,
+}
+
+/// Archival representation of a changed account of a [`HashedPostState`].
+#[derive(Archive, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[rkyv(derive(Debug))]
+pub struct HashedAccountArchive {
+    /// Hashed address of the account.
+    pub hashed_address: [u8; 32],
+    /// The account, `None` if it was destroyed.
+    pub account: Option<AccountArchive>,
+}
+
+/// Archival representation of an [`Account`].
+#[derive(Archive, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[rkyv(derive(Debug))]
+pub struct AccountArchive {
+    /// Account nonce.
+    pub nonce: u64,
+    /// Big-endian account balance.
+    pub balance: [u8; 32],
+    /// Hash of the account's bytecode.
+    pub bytecode_hash: Option<[u8; 32]>,
+}
+
+/// Archival representation of a [`HashedStorage`].
+#[derive(Archive, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[rkyv(derive(Debug))]
+pub struct HashedStorageArchive {
+    /// Hashed address of the account the storage belongs to.
+    pub hashed_address: [u8; 32],
+    /// Whether the storage was wiped.
+    pub wiped: bool,
+    /// Changed storage slots, in ascending order of their hashed keys.
+    pub slots: Vec<StorageSlotArchive>,
+}
+
+/// Archival representation of a changed storage slot of a [`HashedStorage`].
+#[derive(Archive, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[rkyv(derive(Debug))]
+pub struct StorageSlotArchive {
+    /// Hashed key of the slot.
+    pub hashed_slot: [u8; 32],
+    /// Big-endian value of the slot.
+    pub value: [u8; 32],
+}
+
+/// Archival representation of [`TrieUpdates`].
+#[derive(Archive, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[rkyv(derive(Debug))]
+pub struct TrieUpdatesArchive {
+    /// Updated account trie nodes, in ascending order of their paths.
+    pub account_nodes: Vec<TrieNodeArchive>,
+    /// Paths of the removed account trie nodes, in ascending order.
+    pub removed_nodes: Vec<Vec<u8>>,
+    /// Updated storage tries, in ascending order of their hashed addresses.
+    pub storage_tries: Vec<StorageTrieUpdatesArchive>,
+}
+
+/// Archival representation of [`StorageTrieUpdates`].
+#[derive(Archive, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[rkyv(derive(Debug))]
+pub struct StorageTrieUpdatesArchive {
+    /// Hashed address of the account the storage trie belongs to.
+    pub hashed_address: [u8; 32],
+    /// Whether the storage trie was deleted.
+    pub is_deleted: bool,
+    /// Updated storage trie nodes, in ascending order of their paths.
+    pub storage_nodes: Vec<TrieNodeArchive>,
+    /// Paths of the removed storage trie nodes, in ascending order.
+    pub removed_nodes: Vec<Vec<u8>>,
+}
+
+/// Archival representation of an updated trie node, a [`BranchNodeCompact`] at its path.
+#[derive(Archive, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[rkyv(derive(Debug))]
+pub struct TrieNodeArchive {
+    /// Nibbles of the node path.
+    pub path: Vec<u8>,
+    /// The bitmask indicating the presence of children at the respective nibble positions.
+    pub state_mask: u16,
+    /// The bitmask of children that are stored in the database.
+    pub tree_mask: u16,
+    /// The bitmask of children whose hashes are stored.
+    pub hash_mask: u16,
+    /// Hashes of the children in the hash mask.
+    pub hashes: Vec<[u8; 32]>,
+    /// Hash of the node, if it's the root of a trie.
+    pub root_hash: Option<[u8; 32]>,
+}
+
+impl From<&HashedPostState> for HashedPostStateArchive {
+    fn from(state: &HashedPostState) -> Self {
+        let mut accounts = state
+            .accounts
+            .iter()
+            .map(|(hashed_address, account)| HashedAccountArchive {
+                hashed_address: hashed_address.0,
+                account: account.as_ref().map(AccountArchive::from),
+            })
+            .collect::<Vec<_>>();
+        accounts.sort_unstable_by(|a, b| a.hashed_address.cmp(&b.hashed_address));
+
+        let mut storages = state
+            .storages
+            .iter()
+            .map(|(hashed_address, storage)| HashedStorageArchive::new(*hashed_address, storage))
+            .collect::<Vec<_>>();
+        storages.sort_unstable_by(|a, b| a.hashed_address.cmp(&b.hashed_address));
+
+        Self { accounts, storages }
+    }
+}
+
+impl From<&Account> for AccountArchive {
+    fn from(account: &Account) -> Self {
+        Self {
+            nonce: account.nonce,
+            balance: account.balance.to_be_bytes(),
+            bytecode_hash: account.bytecode_hash.map(|hash| hash.0),
+        }
+    }
+}
+
+impl HashedStorageArchive {
+    fn new(hashed_address: B256, storage: &HashedStorage) -> Self {
+        let mut slots = storage
+            .storage
+            .iter()
+            .map(|(hashed_slot, value)| StorageSlotArchive {
+                hashed_slot: hashed_slot.0,
+                value: value.to_be_bytes(),
+            })
+            .collect::<Vec<_>>();
+        slots.sort_unstable_by(|a, b| a.hashed_slot.cmp(&b.hashed_slot));
+        Self { hashed_address: hashed_address.0, wiped: storage.wiped, slots }
+    }
+}
+
+impl From<&TrieUpdates> for TrieUpdatesArchive {
+    fn from(updates: &TrieUpdates) -> Self {
+        let mut storage_tries = updates
+            .storage_tries
+            .iter()
+            .map(|(hashed_address, updates)| {
+                StorageTrieUpdatesArchive::new(*hashed_address, updates)
+            })
+            .collect::<Vec<_>>();
+        storage_tries.sort_unstable_by(|a, b| a.hashed_address.cmp(&b.hashed_address));
+
+        Self {
+            account_nodes: archive_nodes(&updates.account_nodes),
+            removed_nodes: archive_paths(&updates.removed_nodes),
+            storage_tries,
+        }
+    }
+}
+
+impl StorageTrieUpdatesArchive {
+    fn new(hashed_address: B256, updates: &StorageTrieUpdates) -> Self {
+        Self {
+            hashed_address: hashed_address.0,
+            is_deleted: updates.is_deleted,
+            storage_nodes: archive_nodes(&updates.storage_nodes),
+            removed_nodes: archive_paths(&updates.removed_nodes),
+        }
+    }
+}
+
+/// Returns the archival representation of the nodes, in ascending order of their paths.
+fn archive_nodes(nodes: &HashMap<Nibbles, BranchNodeCompact>) -> Vec<TrieNodeArchive> {
+    let mut nodes = nodes
+        .iter()
+        .map(|(path, node)| TrieNodeArchive {
+            path: path.to_vec(),
+            state_mask: node.state_mask.get(),
+            tree_mask: node.tree_mask.get(),
+            hash_mask: node.hash_mask.get(),
+            hashes: node.hashes.iter().map(|hash| hash.0).collect(),
+            root_hash: node.root_hash.map(|hash| hash.0),
+        })
+        .collect::<Vec<_>>();
+    nodes.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+    nodes
+}
+
+/// Returns the paths in ascending order.
+fn archive_paths(paths: &HashSet<Nibbles>) -> Vec<Vec<u8>> {
+    let mut paths = paths.iter().map(|path| path.to_vec()).collect::<Vec<_>>();
+    paths.sort_unstable();
+    paths
+}
+
+impl ArchivedHashedPostStateArchive {
+    /// Decodes the archived state into a [`HashedPostState`].
+    pub fn to_hashed_post_state(&self) -> HashedPostState {
+        let accounts = self
+            .accounts
+            .iter()
+            .map(|entry| {
+                let account = entry.account.as_ref().map(|account| Account {
+                    nonce: account.nonce.to_native(),
+                    balance: U256::from_be_bytes(account.balance),
+                    bytecode_hash: account.bytecode_hash.as_ref().map(|hash| B256::new(*hash)),
+                });
+                (B256::new(entry.hashed_address), account)
+            })
+            .collect();
+        let storages = self
+            .storages
+            .iter()
+            .map(|entry| (B256::new(entry.hashed_address), entry.to_hashed_storage()))
+            .collect();
+        HashedPostState { accounts, storages }
+    }
+}
+
+impl ArchivedHashedStorageArchive {
+    /// Decodes the archived storage into a [`HashedStorage`].
+    pub fn to_hashed_storage(&self) -> HashedStorage {
+        let slots = self
+            .slots
+            .iter()
+            .map(|slot| (B256::new(slot.hashed_slot), U256::from_be_bytes(slot.value)));
+        HashedStorage::from_iter(self.wiped, slots)
+    }
+}
+
+impl ArchivedTrieUpdatesArchive {
+    /// Decodes the archived updates into [`TrieUpdates`].
+    pub fn to_trie_updates(&self) -> Result<TrieUpdates, ArchiveError> {
+        let storage_tries = self
+            .storage_tries
+            .iter()
+            .map(|entry| {
+                let updates = StorageTrieUpdates {
+                    is_deleted: entry.is_deleted,
+                    storage_nodes: decode_nodes(&entry.storage_nodes)?,
+                    removed_nodes: decode_paths(&entry.removed_nodes)?,
+                };
+                Ok((B256::new(entry.hashed_address), updates))
+            })
+            .collect::<Result<_, ArchiveError>>()?;
+
+        Ok(TrieUpdates {
+            account_nodes: decode_nodes(&self.account_nodes)?,
+            removed_nodes: decode_paths(&self.removed_nodes)?,
+            storage_tries,
+        })
+    }
+}
+
+impl ArchivedTrieNodeArchive {
+    /// Decodes the archived node into its path and [`BranchNodeCompact`].
+    pub fn to_node(&self) -> Result<(Nibbles, BranchNodeCompact), ArchiveError> {
+        let state_mask = TrieMask::new(self.state_mask.to_native());
+        let tree_mask = TrieMask::new(self.tree_mask.to_native());
+        let hash_mask = TrieMask::new(self.hash_mask.to_native());
+        if !tree_mask.is_subset_of(state_mask) ||
+            !hash_mask.is_subset_of(state_mask) ||
+            hash_mask.count_ones() as usize != self.hashes.len()
+        {
+            return Err(ArchiveError::InvalidBranchNode)
+        }
+
+        let node = BranchNodeCompact::new(
+            state_mask,
+            tree_mask,
+            hash_mask,
+            self.hashes.iter().map(|hash| B256::new(*hash)).collect(),
+            self.root_hash.as_ref().map(|hash| B256::new(*hash)),
+        );
+        Ok((decode_path(&self.path)?, node))
+    }
+}
+
+fn decode_nodes(
+    nodes: &[ArchivedTrieNodeArchive],
+) -> Result<HashMap<Nibbles, BranchNodeCompact>, ArchiveError> {
+    nodes.iter().map(ArchivedTrieNodeArchive::to_node).collect()
+}
+
+fn decode_paths(paths: &[rkyv::vec::ArchivedVec<u8>]) -> Result<HashSet<Nibbles>, ArchiveError> {
+    paths.iter().map(|path| decode_path(path)).collect()
+}
+
+fn decode_path(path: &[u8]) -> Result<Nibbles, ArchiveError> {
+    if path.iter().any(|nibble| *nibble > 0x0f) {
+        return Err(ArchiveError::InvalidPath)
+    }
+    Ok(Nibbles::from_nibbles_unchecked(path))
+}
+
+/// Archives a [`HashedPostState`].
+pub fn archive_hashed_state(state: &HashedPostState) -> Result<AlignedVec, ArchiveError> {
+    Ok(rkyv::to_bytes::<rancor::Error>(&HashedPostStateArchive::from(state))?)
+}
+
+/// Archives [`TrieUpdates`].
+pub fn archive_trie_updates(updates: &TrieUpdates) -> Result<AlignedVec, ArchiveError> {
+    Ok(rkyv::to_bytes::<rancor::Error>(&TrieUpdatesArchive::from(updates))?)
+}
+
+/// Validates the given bytes and returns the archived hashed state without copying it.
+pub fn access_hashed_state(bytes: &[u8]) -> Result<&ArchivedHashedPostStateArchive, ArchiveError> {
+    Ok(rkyv::access::<ArchivedHashedPostStateArchive, rancor::Error>(bytes)?)
+}
+
+/// Validates the given bytes and returns the archived trie updates without copying them.
+pub fn access_trie_updates(bytes: &[u8]) -> Result<&ArchivedTrieUpdatesArchive, ArchiveError> {
+    Ok(rkyv::access::<ArchivedTrieUpdatesArchive, rancor::Error>(bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hashed_state() -> HashedPostState {
+        let mut state = HashedPostState::default();
+        state.accounts.insert(B256::with_last_byte(1), None);
+        state.accounts.insert(
+            B256::with_last_byte(2),
+            Some(Account {
+                nonce: 1,
+                balance: U256::from(2),
+                bytecode_hash: Some(B256::with_last_byte(3)),
+            }),
+        );
+        state.storages.insert(
+            B256::with_last_byte(2),
+            HashedStorage::from_iter(true, [(B256::with_last_byte(4), U256::from(5))]),
+        );
+        state
+    }
+
+    fn trie_updates() -> TrieUpdates {
+        let node = BranchNodeCompact::new(
+            TrieMask::new(0b11),
+            TrieMask::new(0b01),
+            TrieMask::new(0b10),
+            vec![B256::with_last_byte(6)],
+            Some(B256::with_last_byte(7)),
+        );
+        let mut updates = TrieUpdates::default();
+        updates.account_nodes.insert(Nibbles::from_nibbles([0x0a, 0x0b]), node.clone());
+        updates.removed_nodes.insert(Nibbles::from_nibbles([0x0c]));
+        updates.storage_tries.insert(
+            B256::with_last_byte(2),
+            StorageTrieUpdates {
+                is_deleted: false,
+                storage_nodes: HashMap::from_iter([(Nibbles::from_nibbles([0x01]), node)]),
+                removed_nodes: HashSet::from_iter([Nibbles::from_nibbles([0x02])]),
+            },
+        );
+        updates.storage_tries.insert(B256::with_last_byte(8), StorageTrieUpdates::deleted());
+        updates
+    }
+
+    #[test]
+    fn hashed_state_roundtrip() {
+        let state = hashed_state();
+        let bytes = archive_hashed_state(&state).unwrap();
+        assert_eq!(access_hashed_state(&bytes).unwrap().to_hashed_post_state(), state);
+    }
+
+    #[test]
+    fn trie_updates_roundtrip() {
+        let updates = trie_updates();
+        let bytes = archive_trie_updates(&updates).unwrap();
+        assert_eq!(access_trie_updates(&bytes).unwrap().to_trie_updates().unwrap(), updates);
+    }
+
+    #[test]
+    fn archive_is_canonical() {
+        let nodes = (0..16u8).map(|nibble| Nibbles::from_vec(vec![nibble, 0x0f - nibble]));
+        let mut updates = TrieUpdates::default();
+        updates.removed_nodes.extend(nodes.clone());
+        let mut reversed = TrieUpdates::default();
+        reversed.removed_nodes.extend(nodes.rev());
+
+        assert_eq!(
+            archive_trie_updates(&updates).unwrap().as_slice(),
+            archive_trie_updates(&reversed).unwrap().as_slice()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_nodes() {
+        let mut archive = TrieUpdatesArchive::from(&trie_updates());
+        archive.account_nodes[0].hash_mask = 0b100;
+        let bytes = rkyv::to_bytes::<rancor::Error>(&archive).unwrap();
+        assert!(matches!(
+            access_trie_updates(&bytes).unwrap().to_trie_updates(),
+            Err(ArchiveError::InvalidBranchNode)
+        ));
+
+        let mut archive = TrieUpdatesArchive::from(&trie_updates());
+        archive.removed_nodes[0] = vec![0x10];
+        let bytes = rkyv::to_bytes::<rancor::Error>(&archive).unwrap();
+        assert!(matches!(
+            access_trie_updates(&bytes).unwrap().to_trie_updates(),
+            Err(ArchiveError::InvalidPath)
+        ));
+    }
+}
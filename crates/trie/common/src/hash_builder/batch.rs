@@ -0,0 +1,253 @@
+use crate::keccak::{keccak256_batch, KECCAK_LANES};
+use alloc::vec::Vec;
+use alloy_primitives::B256;
+use alloy_rlp::Encodable;
+use alloy_trie::{nodes::LeafNodeRef, HashBuilder};
+use core::ops::Range;
+use nybbles::Nibbles;
+
+/// The number of buffered elements after which the buffered leaves are hashed.
+const BATCH_SIZE: usize = KECCAK_LANES * 16;
+
+/// An element added to the [`BatchedHashBuilder`].
+#[derive(Debug, Clone)]
+enum Element {
+    /// A leaf with its RLP encoded value.
+    Leaf(Vec<u8>),
+    /// A branch node with its hash.
+    Branch { hash: B256, children_are_in_trie: bool },
+}
+
+/// A [`HashBuilder`] that hashes leaf nodes in batches with [`keccak256_batch`].
+///
+/// The hash builder hashes each leaf node once the key of the next element is known. Instead, the
+/// leaves are buffered, and the position of each leaf is derived from its neighbours: a leaf is
+/// the child of the branch node at the longest common prefix with the previous and the next key.
+/// The leaf nodes of a batch are then encoded and hashed together, and added to the hash builder
+/// as hashed children of their branch nodes. Leaf nodes shorter than 32 bytes are inlined into
+/// their branch nodes, so they're added as leaves.
+///
+/// Hashed leaves would be recorded in the hash masks of the updated branch nodes and be missing
+/// from the retained proofs, so if the hash builder retains updates or proofs, all elements are
+/// added to it directly.
+#[derive(Debug)]
+pub struct BatchedHashBuilder {
+    inner: HashBuilder,
+    /// Whether leaves are hashed in batches.
+    batched: bool,
+    /// The elements that weren't added to the hash builder yet.
+    pending: Vec<(Nibbles, Element)>,
+    /// The key of the last element added to the hash builder.
+    last_key: Option<Nibbles>,
+    /// The RLP encoded leaf nodes of the batch.
+    rlp_buf: Vec<u8>,
+}
+
+impl BatchedHashBuilder {
+    /// Creates a new batched hash builder on top of the given hash builder.
+    pub fn new(inner: HashBuilder) -> Self {
+        let batched = inner.updated_branch_nodes.is_none() && inner.proof_retainer.is_none();
+        Self { inner, batched, pending: Vec::new(), last_key: None, rlp_buf: Vec::new() }
+    }
+
+    /// Adds a new leaf element and its value to the trie.
+    pub fn add_leaf(&mut self, key: Nibbles, value: &[u8]) {
+        if self.batched {
+            self.push(key, Element::Leaf(value.to_vec()));
+        } else {
+            self.inner.add_leaf(key, value);
+        }
+    }
+
+    /// Adds a new branch element and its hash to the trie.
+    pub fn add_branch(&mut self, key: Nibbles, hash: B256, children_are_in_trie: bool) {
+        if self.batched {
+            self.push(key, Element::Branch { hash, children_are_in_trie });
+        } else {
+            self.inner.add_branch(key, hash, children_are_in_trie);
+        }
+    }
+
+    /// Returns the number of updated branch nodes of the hash builder.
+    pub fn updates_len(&self) -> usize {
+        self.inner.updates_len()
+    }
+
+    /// Returns the root of the trie.
+    pub fn root(&mut self) -> B256 {
+        self.flush(true);
+        self.inner.root()
+    }
+
+    /// Adds the buffered elements and returns the hash builder.
+    pub fn into_inner(mut self) -> HashBuilder {
+        self.flush(true);
+        self.inner
+    }
+
+    fn push(&mut self, key: Nibbles, element: Element) {
+        self.pending.push((key, element));
+        if self.pending.len() > BATCH_SIZE {
+            self.flush(false);
+        }
+    }
+
+    /// Hashes the buffered leaves and adds the buffered elements to the hash builder.
+    ///
+    /// Unless the trie is `finished`, the last element is kept, because the position of a leaf
+    /// depends on the next key.
+    fn flush(&mut self, finished: bool) {
+        let count =
+            if finished { self.pending.len() } else { self.pending.len().saturating_sub(1) };
+
+        self.rlp_buf.clear();
+        let mut unhashed = Vec::<(usize, usize, Range<usize>)>::new();
+        for index in 0..count {
+            let (key, Element::Leaf(value)) = &self.pending[index] else { continue };
+            let previous =
+                if index == 0 { self.last_key.as_ref() } else { Some(&self.pending[index - 1].0) };
+            let next = self.pending.get(index + 1).map(|(key, _)| key);
+            if previous.is_none() && next.is_none() {
+                // The only leaf of the trie is the root node
+                continue
+            }
+
+            let depth = previous
+                .map_or(0, |previous| key.common_prefix_length(previous))
+                .max(next.map_or(0, |next| key.common_prefix_length(next)));
+            if depth >= key.len() {
+                continue
+            }
+
+            let offset = self.rlp_buf.len();
+            LeafNodeRef::new(&key.slice(depth + 1..), value).encode(&mut self.rlp_buf);
+            if self.rlp_buf.len() - offset < 32 {
+                self.rlp_buf.truncate(offset);
+                continue
+            }
+            unhashed.push((index, depth, offset..self.rlp_buf.len()));
+        }
+
+        let encoded = unhashed.iter().map(|(_, _, range)| &self.rlp_buf[range.clone()]);
+        let hashes = keccak256_batch(&encoded.collect::<Vec<_>>());
+        let mut hashed = unhashed.into_iter().zip(hashes).peekable();
+        for (index, (key, element)) in self.pending.drain(..count).enumerate() {
+            if let Some(((_, depth, _), hash)) = hashed.next_if(|((i, _, _), _)| *i == index) {
+                self.inner.add_branch(key.slice(..=depth), hash, false);
+            } else {
+                match element {
+                    Element::Leaf(value) => self.inner.add_leaf(key.clone(), &value),
+                    Element::Branch { hash, children_are_in_trie } => {
+                        self.inner.add_branch(key.clone(), hash, children_are_in_trie)
+                    }
+                }
+            }
+            self.last_key = Some(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::keccak256;
+
+    /// Returns the roots computed by the hash builder and the batched hash builder.
+    fn roots(elements: &[(Nibbles, Element)]) -> (B256, B256) {
+        let mut hash_builder = HashBuilder::default();
+        let mut batched = BatchedHashBuilder::new(HashBuilder::default());
+        for (key, element) in elements {
+            match element {
+                Element::Leaf(value) => {
+                    hash_builder.add_leaf(key.clone(), value);
+                    batched.add_leaf(key.clone(), value);
+                }
+                Element::Branch { hash, children_are_in_trie } => {
+                    hash_builder.add_branch(key.clone(), *hash, *children_are_in_trie);
+                    batched.add_branch(key.clone(), *hash, *children_are_in_trie);
+                }
+            }
+        }
+        (hash_builder.root(), batched.root())
+    }
+
+    fn leaf(key: B256, value_len: usize) -> (Nibbles, Element) {
+        (Nibbles::unpack(key), Element::Leaf(keccak256(key)[..value_len].to_vec()))
+    }
+
+    #[test]
+    fn matches_hash_builder() {
+        for len in [0, 1, 2, 3, BATCH_SIZE, BATCH_SIZE + 1, BATCH_SIZE * 3 + 7] {
+            let mut keys = (0..len as u64).map(|i| keccak256(i.to_be_bytes())).collect::<Vec<_>>();
+            keys.sort_unstable();
+            let leaves = keys
+                .into_iter()
+                .enumerate()
+                .map(|(i, key)| leaf(key, 1 + i % 32))
+                .collect::<Vec<_>>();
+
+            let (expected, root) = roots(&leaves);
+            assert_eq!(root, expected, "{len} leaves");
+        }
+    }
+
+    #[test]
+    fn inlines_short_leaf_nodes() {
+        // Keys that only differ in the last nibbles, so their leaf nodes are shorter than 32 bytes
+        let leaves = (0..4u8)
+            .map(|i| leaf(B256::with_last_byte(i), 1))
+            .chain((0..4u8).map(|i| {
+                let mut key = B256::repeat_byte(0x11);
+                key[31] = i;
+                leaf(key, 20)
+            }))
+            .collect::<Vec<_>>();
+
+        let (expected, root) = roots(&leaves);
+        assert_eq!(root, expected);
+    }
+
+    #[test]
+    fn hashes_leaves_around_branch_nodes() {
+        let key = |first: u8, third: u8| {
+            let mut key = B256::with_last_byte(third);
+            key[0] = first;
+            key[1] = third << 4;
+            key
+        };
+        // The leaves below 0x12 with different third nibbles, whose branch node is added by hash
+        let branch_leaves = [0, 5, 9].map(|third| leaf(key(0x12, third), 8));
+        let mut subtrie = HashBuilder::default();
+        for (key, element) in &branch_leaves {
+            let Element::Leaf(value) = element else { unreachable!() };
+            subtrie.add_leaf(key.slice(2..), value);
+        }
+        let branch = (
+            Nibbles::from_nibbles([1, 2]),
+            Element::Branch { hash: subtrie.root(), children_are_in_trie: false },
+        );
+
+        let before = [0x01, 0x10, 0x11].map(|first| leaf(key(first, 0), 8));
+        let after = [0x13, 0x20, 0xf0].map(|first| leaf(key(first, 0), 8));
+        let leaves = before.iter().chain(&branch_leaves).chain(&after).cloned().collect::<Vec<_>>();
+        let (expected, _) = roots(&leaves);
+        let (_, root) = roots(&before.into_iter().chain([branch]).chain(after).collect::<Vec<_>>());
+        assert_eq!(root, expected);
+    }
+
+    #[test]
+    fn passes_through_when_retaining_updates() {
+        let mut keys = (0..100u64).map(|i| keccak256(i.to_be_bytes())).collect::<Vec<_>>();
+        keys.sort_unstable();
+
+        let mut hash_builder = HashBuilder::default().with_updates(true);
+        let mut batched = BatchedHashBuilder::new(HashBuilder::default().with_updates(true));
+        for key in keys {
+            hash_builder.add_leaf(Nibbles::unpack(key), key.as_slice());
+            batched.add_leaf(Nibbles::unpack(key), key.as_slice());
+        }
+
+        assert_eq!(batched.root(), hash_builder.root());
+        assert_eq!(batched.into_inner().split().1, hash_builder.split().1);
+    }
+}
@@ -3,4 +3,7 @@
 mod state;
 pub use state::HashBuilderState;
 
+mod batch;
+pub use batch::BatchedHashBuilder;
+
 pub use alloy_trie::hash_builder::*;
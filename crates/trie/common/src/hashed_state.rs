@@ -22,6 +22,7 @@ use revm_database::{AccountStatus, BundleAccount};
 
 /// Representation of in-memory hashed state.
 #[derive(PartialEq, Eq, Clone, Default, Debug)]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize, serde::Deserialize))]
 pub struct HashedPostState {
     /// Mapping of hashed address to account info, `None` if destroyed.
     pub accounts: B256Map<Option<Account>>,
@@ -337,6 +338,7 @@ impl HashedPostState {
 
 /// Representation of in-memory hashed storage.
 #[derive(PartialEq, Eq, Clone, Debug, Default)]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize, serde::Deserialize))]
 pub struct HashedStorage {
     /// Flag indicating whether the storage was wiped or not.
     pub wiped: bool,
@@ -574,6 +576,216 @@ impl Iterator for ChunkedHashedPostState {
     }
 }
 
+/// Bincode-compatible hashed state type serde implementations.
+#[cfg(feature = "serde-bincode-compat")]
+pub mod serde_bincode_compat {
+    use crate::serde_bincode_compat::SortedMap;
+    use alloc::borrow::Cow;
+    use alloy_primitives::{map::B256Map, U256};
+    use reth_primitives_traits::Account;
+    use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+
+    /// Bincode-compatible [`super::HashedPostState`] serde implementation.
+    ///
+    /// Intended to use with the [`serde_with::serde_as`] macro in the following way:
+    /// ```rust
+    /// use reth_trie_common::{serde_bincode_compat, HashedPostState};
+    /// use serde::{Deserialize, Serialize};
+    /// use serde_with::serde_as;
+    ///
+    /// #[serde_as]
+    /// #[derive(Serialize, Deserialize)]
+    /// struct Data {
+    ///     #[serde_as(as = "serde_bincode_compat::hashed_state::HashedPostState")]
+    ///     hashed_state: HashedPostState,
+    /// }
+    /// ```
+    #[derive(Debug, Deserialize)]
+    pub struct HashedPostState<'a> {
+        accounts: Cow<'a, B256Map<Option<Account>>>,
+        storages: B256Map<HashedStorage<'a>>,
+    }
+
+    impl Serialize for HashedPostState<'_> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let mut state = serializer.serialize_struct("HashedPostState", 2)?;
+            state.serialize_field("accounts", &SortedMap(&*self.accounts))?;
+            state.serialize_field("storages", &SortedMap(&self.storages))?;
+            state.end()
+        }
+    }
+
+    impl<'a> From<&'a super::HashedPostState> for HashedPostState<'a> {
+        fn from(value: &'a super::HashedPostState) -> Self {
+            Self {
+                accounts: Cow::Borrowed(&value.accounts),
+                storages: value.storages.iter().map(|(k, v)| (*k, v.into())).collect(),
+            }
+        }
+    }
+
+    impl<'a> From<HashedPostState<'a>> for super::HashedPostState {
+        fn from(value: HashedPostState<'a>) -> Self {
+            Self {
+                accounts: value.accounts.into_owned(),
+                storages: value.storages.into_iter().map(|(k, v)| (k, v.into())).collect(),
+            }
+        }
+    }
+
+    impl SerializeAs<super::HashedPostState> for HashedPostState<'_> {
+        fn serialize_as<S>(
+            source: &super::HashedPostState,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            HashedPostState::from(source).serialize(serializer)
+        }
+    }
+
+    impl<'de> DeserializeAs<'de, super::HashedPostState> for HashedPostState<'de> {
+        fn deserialize_as<D>(deserializer: D) -> Result<super::HashedPostState, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            HashedPostState::deserialize(deserializer).map(Into::into)
+        }
+    }
+
+    /// Bincode-compatible [`super::HashedStorage`] serde implementation.
+    ///
+    /// Intended to use with the [`serde_with::serde_as`] macro in the following way:
+    /// ```rust
+    /// use reth_trie_common::{serde_bincode_compat, HashedStorage};
+    /// use serde::{Deserialize, Serialize};
+    /// use serde_with::serde_as;
+    ///
+    /// #[serde_as]
+    /// #[derive(Serialize, Deserialize)]
+    /// struct Data {
+    ///     #[serde_as(as = "serde_bincode_compat::hashed_state::HashedStorage")]
+    ///     hashed_storage: HashedStorage,
+    /// }
+    /// ```
+    #[derive(Debug, Deserialize)]
+    pub struct HashedStorage<'a> {
+        wiped: bool,
+        storage: Cow<'a, B256Map<U256>>,
+    }
+
+    impl Serialize for HashedStorage<'_> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let mut state = serializer.serialize_struct("HashedStorage", 2)?;
+            state.serialize_field("wiped", &self.wiped)?;
+            state.serialize_field("storage", &SortedMap(&*self.storage))?;
+            state.end()
+        }
+    }
+
+    impl<'a> From<&'a super::HashedStorage> for HashedStorage<'a> {
+        fn from(value: &'a super::HashedStorage) -> Self {
+            Self { wiped: value.wiped, storage: Cow::Borrowed(&value.storage) }
+        }
+    }
+
+    impl<'a> From<HashedStorage<'a>> for super::HashedStorage {
+        fn from(value: HashedStorage<'a>) -> Self {
+            Self { wiped: value.wiped, storage: value.storage.into_owned() }
+        }
+    }
+
+    impl SerializeAs<super::HashedStorage> for HashedStorage<'_> {
+        fn serialize_as<S>(source: &super::HashedStorage, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            HashedStorage::from(source).serialize(serializer)
+        }
+    }
+
+    impl<'de> DeserializeAs<'de, super::HashedStorage> for HashedStorage<'de> {
+        fn deserialize_as<D>(deserializer: D) -> Result<super::HashedStorage, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            HashedStorage::deserialize(deserializer).map(Into::into)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::{serde_bincode_compat, HashedPostState, HashedStorage};
+        use alloc::collections::BTreeMap;
+        use alloy_primitives::{B256, U256};
+        use reth_primitives_traits::Account;
+        use serde::{Deserialize, Serialize};
+        use serde_with::serde_as;
+
+        #[serde_as]
+        #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+        struct Data {
+            #[serde_as(as = "serde_bincode_compat::hashed_state::HashedPostState")]
+            hashed_state: HashedPostState,
+        }
+
+        #[test]
+        fn test_hashed_post_state_bincode_roundtrip() {
+            let mut data = Data { hashed_state: HashedPostState::default() };
+            let encoded = bincode::serialize(&data).unwrap();
+            let decoded: Data = bincode::deserialize(&encoded).unwrap();
+            assert_eq!(decoded, data);
+
+            data.hashed_state.accounts.insert(B256::with_last_byte(1), Some(Account::default()));
+            data.hashed_state.accounts.insert(B256::with_last_byte(2), None);
+            let encoded = bincode::serialize(&data).unwrap();
+            let decoded: Data = bincode::deserialize(&encoded).unwrap();
+            assert_eq!(decoded, data);
+
+            data.hashed_state.storages.insert(
+                B256::with_last_byte(1),
+                HashedStorage::from_iter(true, [(B256::with_last_byte(3), U256::from(4))]),
+            );
+            let encoded = bincode::serialize(&data).unwrap();
+            let decoded: Data = bincode::deserialize(&encoded).unwrap();
+            assert_eq!(decoded, data);
+        }
+
+        #[test]
+        fn test_hashed_post_state_bincode_canonical() {
+            let accounts = (0..32u8).map(|byte| (B256::with_last_byte(byte), None::<Account>));
+            let data = Data {
+                hashed_state: HashedPostState {
+                    accounts: accounts.clone().collect(),
+                    ..Default::default()
+                },
+            };
+            let reversed = Data {
+                hashed_state: HashedPostState {
+                    accounts: accounts.clone().rev().collect(),
+                    ..Default::default()
+                },
+            };
+
+            let encoded = bincode::serialize(&data).unwrap();
+            assert_eq!(encoded, bincode::serialize(&reversed).unwrap());
+            assert_eq!(
+                encoded,
+                bincode::serialize(&(accounts.collect::<BTreeMap<_, _>>(), Vec::<()>::new()))
+                    .unwrap()
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -59,6 +59,19 @@ impl TrieInput {
         self.state.extend_ref(state);
     }
 
+    /// Append state to the input by reference and extend the prefix sets with the given ones.
+    ///
+    /// The prefix sets must cover all changes of the state, e.g. when they were collected during
+    /// execution. This avoids constructing them from the state.
+    pub fn append_ref_with_prefix_sets(
+        &mut self,
+        state: &HashedPostState,
+        prefix_sets: TriePrefixSetsMut,
+    ) {
+        self.prefix_sets.extend(prefix_sets);
+        self.state.extend_ref(state);
+    }
+
     /// Append intermediate nodes and state to the input.
     /// Prefix sets for incoming state will be ignored.
     pub fn append_cached(&mut self, nodes: TrieUpdates, state: HashedPostState) {
@@ -0,0 +1,312 @@
+//! Multi-buffer keccak256 hashing.
+//!
+//! Trie nodes are small, so hashing them one by one is dominated by the latency of the
+//! permutation. [`keccak256_batch`] interleaves the states of [`KECCAK_LANES`] inputs lane by lane
+//! and runs the Keccak-f\[1600\] permutation on all of them at once. On `x86_64` CPUs with AVX2,
+//! the permutation runs on the four lanes in 256-bit registers, otherwise it falls back to a
+//! portable implementation that the compiler may vectorize.
+
+use alloc::{vec, vec::Vec};
+use alloy_primitives::{keccak256, B256};
+
+/// The number of inputs that are hashed at once.
+pub const KECCAK_LANES: usize = 4;
+
+/// The rate of keccak256 in bytes, i.e. the size of an absorbed block.
+const RATE: usize = 136;
+
+/// The round constants of Keccak-f\[1600\].
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// The rotation offsets of the rho step, in the order of [`PI`].
+const RHO: [u32; 24] =
+    [1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44];
+
+/// The lane permutation of the pi step.
+const PI: [usize; 24] =
+    [10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1];
+
+/// A lane of the Keccak state for each of the interleaved inputs.
+type Lanes = [u64; KECCAK_LANES];
+
+/// Applies `f` to each of the interleaved lanes.
+#[inline(always)]
+fn map(f: impl Fn(usize) -> u64) -> Lanes {
+    core::array::from_fn(f)
+}
+
+/// Runs Keccak-f\[1600\] on the interleaved states, with AVX2 if the CPU supports it.
+#[inline]
+fn keccak_f1600(state: &mut [Lanes; 25]) {
+    #[cfg(all(target_arch = "x86_64", any(target_feature = "avx2", feature = "std")))]
+    if avx2::is_supported() {
+        // SAFETY: the CPU supports AVX2
+        return unsafe { avx2::keccak_f1600(state) }
+    }
+
+    keccak_f1600_portable(state)
+}
+
+/// Runs Keccak-f\[1600\] on the interleaved states, lane by lane.
+#[inline]
+fn keccak_f1600_portable(state: &mut [Lanes; 25]) {
+    for round_constant in ROUND_CONSTANTS {
+        // theta
+        let parity: [Lanes; 5] = core::array::from_fn(|x| {
+            map(|i| {
+                state[x][i] ^
+                    state[x + 5][i] ^
+                    state[x + 10][i] ^
+                    state[x + 15][i] ^
+                    state[x + 20][i]
+            })
+        });
+        for x in 0..5 {
+            let d = map(|i| parity[(x + 4) % 5][i] ^ parity[(x + 1) % 5][i].rotate_left(1));
+            for y in (0..25).step_by(5) {
+                state[y + x] = map(|i| state[y + x][i] ^ d[i]);
+            }
+        }
+
+        // rho and pi
+        let mut last = state[1];
+        for (pi, rho) in PI.into_iter().zip(RHO) {
+            let current = state[pi];
+            state[pi] = map(|i| last[i].rotate_left(rho));
+            last = current;
+        }
+
+        // chi
+        for y in (0..25).step_by(5) {
+            let row: [Lanes; 5] = core::array::from_fn(|x| state[y + x]);
+            for x in 0..5 {
+                state[y + x] = map(|i| row[x][i] ^ (!row[(x + 1) % 5][i] & row[(x + 2) % 5][i]));
+            }
+        }
+
+        // iota
+        state[0] = map(|i| state[0][i] ^ round_constant);
+    }
+}
+
+/// Keccak-f\[1600\] on the four interleaved states in 256-bit AVX2 registers.
+#[cfg(all(target_arch = "x86_64", any(target_feature = "avx2", feature = "std")))]
+mod avx2 {
+    use super::{Lanes, PI, RHO, ROUND_CONSTANTS};
+    use core::arch::x86_64::*;
+
+    const _: () = assert!(super::KECCAK_LANES == 4, "a 256-bit register holds four lanes");
+
+    /// Returns `true` if the CPU supports AVX2.
+    #[cfg(target_feature = "avx2")]
+    pub(super) const fn is_supported() -> bool {
+        true
+    }
+
+    /// Returns `true` if the CPU supports AVX2.
+    #[cfg(not(target_feature = "avx2"))]
+    pub(super) fn is_supported() -> bool {
+        std::is_x86_feature_detected!("avx2")
+    }
+
+    /// Rotates the 64-bit lanes left by `n` bits.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn rotate_left(x: __m256i, n: u32) -> __m256i {
+        _mm256_or_si256(
+            _mm256_sll_epi64(x, _mm_cvtsi32_si128(n as i32)),
+            _mm256_srl_epi64(x, _mm_cvtsi32_si128(64 - n as i32)),
+        )
+    }
+
+    /// Runs Keccak-f\[1600\] on the interleaved states.
+    ///
+    /// # Safety
+    ///
+    /// The CPU must support AVX2.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn keccak_f1600(state: &mut [Lanes; 25]) {
+        let mut a = [_mm256_setzero_si256(); 25];
+        for (lanes, words) in a.iter_mut().zip(state.iter()) {
+            *lanes = _mm256_loadu_si256(words.as_ptr().cast());
+        }
+
+        for round_constant in ROUND_CONSTANTS {
+            // theta
+            let mut parity = [_mm256_setzero_si256(); 5];
+            for (x, column) in parity.iter_mut().enumerate() {
+                *column = _mm256_xor_si256(
+                    _mm256_xor_si256(_mm256_xor_si256(a[x], a[x + 5]), a[x + 10]),
+                    _mm256_xor_si256(a[x + 15], a[x + 20]),
+                );
+            }
+            for x in 0..5 {
+                let d = _mm256_xor_si256(parity[(x + 4) % 5], rotate_left(parity[(x + 1) % 5], 1));
+                for y in (0..25).step_by(5) {
+                    a[y + x] = _mm256_xor_si256(a[y + x], d);
+                }
+            }
+
+            // rho and pi
+            let mut last = a[1];
+            for (pi, rho) in PI.into_iter().zip(RHO) {
+                let current = a[pi];
+                a[pi] = rotate_left(last, rho);
+                last = current;
+            }
+
+            // chi
+            for y in (0..25).step_by(5) {
+                let row = [a[y], a[y + 1], a[y + 2], a[y + 3], a[y + 4]];
+                for (x, lanes) in row.iter().enumerate() {
+                    a[y + x] = _mm256_xor_si256(
+                        *lanes,
+                        _mm256_andnot_si256(row[(x + 1) % 5], row[(x + 2) % 5]),
+                    );
+                }
+            }
+
+            // iota
+            a[0] = _mm256_xor_si256(a[0], _mm256_set1_epi64x(round_constant as i64));
+        }
+
+        for (words, lanes) in state.iter_mut().zip(a) {
+            _mm256_storeu_si256(words.as_mut_ptr().cast(), lanes);
+        }
+    }
+}
+
+/// Returns the number of blocks that an input of the given length is absorbed in, including the
+/// padding.
+const fn blocks(len: usize) -> usize {
+    len / RATE + 1
+}
+
+/// Hashes up to [`KECCAK_LANES`] inputs at once.
+fn keccak256_lanes(inputs: &[&[u8]], out: &mut [B256]) {
+    debug_assert!(inputs.len() <= KECCAK_LANES);
+    debug_assert_eq!(inputs.len(), out.len());
+
+    let mut state = [[0u64; KECCAK_LANES]; 25];
+    let max_blocks = inputs.iter().map(|input| blocks(input.len())).max().unwrap_or_default();
+    let mut block = [0u8; RATE];
+    for block_index in 0..max_blocks {
+        for (lane, input) in inputs.iter().enumerate() {
+            if block_index >= blocks(input.len()) {
+                // the input is already hashed, its lane is permuted for nothing
+                continue
+            }
+
+            let start = block_index * RATE;
+            let chunk = &input[start..input.len().min(start + RATE)];
+            block.fill(0);
+            block[..chunk.len()].copy_from_slice(chunk);
+            if block_index + 1 == blocks(input.len()) {
+                // keccak padding
+                block[chunk.len()] ^= 0x01;
+                block[RATE - 1] ^= 0x80;
+            }
+
+            for (word, bytes) in state.iter_mut().zip(block.chunks_exact(8)) {
+                word[lane] ^= u64::from_le_bytes(bytes.try_into().unwrap());
+            }
+        }
+
+        keccak_f1600(&mut state);
+
+        for (lane, input) in inputs.iter().enumerate() {
+            if block_index + 1 == blocks(input.len()) {
+                for (word, bytes) in state.iter().zip(out[lane].0.chunks_exact_mut(8)) {
+                    bytes.copy_from_slice(&word[lane].to_le_bytes());
+                }
+            }
+        }
+    }
+}
+
+/// Computes the keccak256 hashes of the inputs.
+///
+/// Inputs are hashed [`KECCAK_LANES`] at a time, grouped by the number of blocks they're absorbed
+/// in, so that the interleaved permutations are not wasted on inputs that are already hashed. A
+/// single input is hashed with [`keccak256`].
+pub fn keccak256_batch<T: AsRef<[u8]>>(inputs: &[T]) -> Vec<B256> {
+    let mut hashes = vec![B256::ZERO; inputs.len()];
+    if let [input] = inputs {
+        hashes[0] = keccak256(input);
+        return hashes
+    }
+
+    let mut order = (0..inputs.len()).collect::<Vec<_>>();
+    order.sort_unstable_by_key(|index| blocks(inputs[*index].as_ref().len()));
+
+    let mut lane_inputs: Vec<&[u8]> = Vec::with_capacity(KECCAK_LANES);
+    let mut lane_hashes = [B256::ZERO; KECCAK_LANES];
+    for group in order.chunks(KECCAK_LANES) {
+        lane_inputs.clear();
+        lane_inputs.extend(group.iter().map(|index| inputs[*index].as_ref()));
+        keccak256_lanes(&lane_inputs, &mut lane_hashes[..group.len()]);
+        for (index, hash) in group.iter().zip(lane_hashes) {
+            hashes[*index] = hash;
+        }
+    }
+
+    hashes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_keccak256() {
+        // lengths around the block boundaries, where the padding is split or shared
+        let inputs = [0, 1, 31, 32, 33, 134, 135, 136, 137, 271, 272, 532, 1000]
+            .into_iter()
+            .map(|len| (0..len).map(|i| (i * 7 + len) as u8).collect::<Vec<u8>>())
+            .collect::<Vec<_>>();
+
+        let expected = inputs.iter().map(keccak256).collect::<Vec<_>>();
+        assert_eq!(keccak256_batch(&inputs), expected);
+
+        for len in 0..=inputs.len() {
+            assert_eq!(keccak256_batch(&inputs[..len]), expected[..len]);
+        }
+    }
+
+    #[test]
+    fn portable_matches_dispatched_permutation() {
+        let mut state: [Lanes; 25] = core::array::from_fn(|x| {
+            map(|i| (x as u64 + 1).wrapping_mul(0x9e3779b97f4a7c15) ^ i as u64)
+        });
+        let mut portable = state;
+
+        keccak_f1600(&mut state);
+        keccak_f1600_portable(&mut portable);
+        assert_eq!(state, portable);
+    }
+}
@@ -32,11 +32,14 @@ pub use account::TrieAccount;
 mod key;
 pub use key::{KeccakKeyHasher, KeyHasher};
 
+/// Multi-buffer keccak256 hashing of trie nodes.
+pub mod keccak;
+
 mod nibbles;
 pub use nibbles::{Nibbles, StoredNibbles, StoredNibblesSubKey};
 
 mod storage;
-pub use storage::StorageTrieEntry;
+pub use storage::{HashedStorageRange, StorageTrieEntry};
 
 mod subnode;
 pub use subnode::StoredSubNode;
@@ -55,15 +58,52 @@ pub mod root;
 /// Buffer for trie updates.
 pub mod updates;
 
+#[cfg(feature = "rkyv")]
+pub mod archive;
+
 /// Bincode-compatible serde implementations for trie types.
 ///
 /// `bincode` crate allows for more efficient serialization of trie types, because it allows
 /// non-string map keys.
 ///
+/// Map and set entries are serialized in ascending key order, which makes the serialization
+/// canonical: equal values are always serialized to equal bytes.
+///
 /// Read more: <https://github.com/paradigmxyz/reth/issues/11370>
 #[cfg(all(feature = "serde", feature = "serde-bincode-compat"))]
 pub mod serde_bincode_compat {
-    pub use super::updates::serde_bincode_compat as updates;
+    use alloc::vec::Vec;
+    use alloy_primitives::map::{HashMap, HashSet};
+    use serde::{Serialize, Serializer};
+
+    pub use super::{
+        hashed_state::serde_bincode_compat as hashed_state,
+        updates::serde_bincode_compat as updates,
+    };
+
+    /// Serializes a map with its entries in ascending key order, so that equal maps are serialized
+    /// to equal bytes.
+    pub(crate) struct SortedMap<'a, K, V, S>(pub(crate) &'a HashMap<K, V, S>);
+
+    impl<K: Ord + Serialize, V: Serialize, S> Serialize for SortedMap<'_, K, V, S> {
+        fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+            let mut entries = self.0.iter().collect::<Vec<_>>();
+            entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+            serializer.collect_map(entries)
+        }
+    }
+
+    /// Serializes a set with its entries in ascending order, so that equal sets are serialized to
+    /// equal bytes.
+    pub(crate) struct SortedSet<'a, T, S>(pub(crate) &'a HashSet<T, S>);
+
+    impl<T: Ord + Serialize, S> Serialize for SortedSet<'_, T, S> {
+        fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+            let mut entries = self.0.iter().collect::<Vec<_>>();
+            entries.sort_unstable();
+            serializer.collect_seq(entries)
+        }
+    }
 }
 
 /// Re-export
@@ -52,6 +52,18 @@ impl MultiProofTargets {
         Self(iter.into_iter().map(|hashed_address| (hashed_address, Default::default())).collect())
     }
 
+    /// Create `MultiProofTargets` from plain addresses and their plain storage slots.
+    pub fn from_addresses_with_slots<'a, I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a (Address, Vec<B256>)>,
+    {
+        let mut targets = Self::default();
+        for (address, slots) in iter {
+            targets.entry(keccak256(address)).or_default().extend(slots.iter().map(keccak256));
+        }
+        targets
+    }
+
     /// Retains the targets representing the difference,
     /// i.e., the values that are in `self` but not in `other`.
     pub fn retain_difference(&mut self, other: &Self) {
@@ -260,6 +272,15 @@ impl MultiProof {
         Ok(AccountProof { address, info, proof, storage_root, storage_proofs })
     }
 
+    /// Construct the account proofs for multiple accounts from the multiproof, in the order of
+    /// the targets.
+    pub fn account_proofs(
+        &self,
+        targets: &[(Address, Vec<B256>)],
+    ) -> Result<Vec<AccountProof>, alloy_rlp::Error> {
+        targets.iter().map(|(address, slots)| self.account_proof(*address, slots)).collect()
+    }
+
     /// Extends this multiproof with another one, merging both account and storage
     /// proofs.
     pub fn extend(&mut self, other: Self) {
@@ -985,6 +1006,27 @@ mod tests {
         assert_eq!(retained.get(&addr2), Some(&B256Set::from_iter([slot2])));
     }
 
+    #[test]
+    fn test_multiproof_targets_from_addresses_with_slots() {
+        let addr1 = Address::random();
+        let addr2 = Address::random();
+        let slot1 = B256::random();
+        let slot2 = B256::random();
+
+        let targets = MultiProofTargets::from_addresses_with_slots(&[
+            (addr1, vec![slot1]),
+            (addr2, vec![]),
+            (addr1, vec![slot2]),
+        ]);
+
+        assert_eq!(targets.len(), 2);
+        assert_eq!(
+            targets.get(&keccak256(addr1)),
+            Some(&B256Set::from_iter([keccak256(slot1), keccak256(slot2)]))
+        );
+        assert_eq!(targets.get(&keccak256(addr2)), Some(&B256Set::default()));
+    }
+
     #[test]
     #[cfg(feature = "eip1186")]
     fn eip_1186_roundtrip() {
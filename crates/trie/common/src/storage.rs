@@ -1,4 +1,6 @@
 use super::{BranchNodeCompact, StoredNibblesSubKey};
+use alloc::vec::Vec;
+use alloy_primitives::{B256, U256};
 
 /// Account storage trie node.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -10,6 +12,15 @@ pub struct StorageTrieEntry {
     pub node: BranchNodeCompact,
 }
 
+/// A range of the hashed storage of an account, sorted by hashed slot.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HashedStorageRange {
+    /// The hashed slots of the range and their values.
+    pub slots: Vec<(B256, U256)>,
+    /// The hashed slot following the range, `None` if the range ends with the last slot.
+    pub next_key: Option<B256>,
+}
+
 // NOTE: Removing reth_codec and manually encode subkey
 // and compress second part of the value. If we have compression
 // over whole value (Even SubKey) that would mess up fetching of values with seek_by_key_subkey
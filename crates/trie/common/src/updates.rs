@@ -421,10 +421,13 @@ fn exclude_empty_from_pair<V>(
 /// Bincode-compatible trie updates type serde implementations.
 #[cfg(feature = "serde-bincode-compat")]
 pub mod serde_bincode_compat {
-    use crate::{BranchNodeCompact, Nibbles};
+    use crate::{
+        serde_bincode_compat::{SortedMap, SortedSet},
+        BranchNodeCompact, Nibbles,
+    };
     use alloc::borrow::Cow;
     use alloy_primitives::map::{B256Map, HashMap, HashSet};
-    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
     use serde_with::{DeserializeAs, SerializeAs};
 
     /// Bincode-compatible [`super::TrieUpdates`] serde implementation.
@@ -442,13 +445,26 @@ pub mod serde_bincode_compat {
     ///     trie_updates: TrieUpdates,
     /// }
     /// ```
-    #[derive(Debug, Serialize, Deserialize)]
+    #[derive(Debug, Deserialize)]
     pub struct TrieUpdates<'a> {
         account_nodes: Cow<'a, HashMap<Nibbles, BranchNodeCompact>>,
         removed_nodes: Cow<'a, HashSet<Nibbles>>,
         storage_tries: B256Map<StorageTrieUpdates<'a>>,
     }
 
+    impl Serialize for TrieUpdates<'_> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let mut state = serializer.serialize_struct("TrieUpdates", 3)?;
+            state.serialize_field("account_nodes", &SortedMap(&*self.account_nodes))?;
+            state.serialize_field("removed_nodes", &SortedSet(&*self.removed_nodes))?;
+            state.serialize_field("storage_tries", &SortedMap(&self.storage_tries))?;
+            state.end()
+        }
+    }
+
     impl<'a> From<&'a super::TrieUpdates> for TrieUpdates<'a> {
         fn from(value: &'a super::TrieUpdates) -> Self {
             Self {
@@ -506,13 +522,26 @@ pub mod serde_bincode_compat {
     ///     trie_updates: StorageTrieUpdates,
     /// }
     /// ```
-    #[derive(Debug, Serialize, Deserialize)]
+    #[derive(Debug, Deserialize)]
     pub struct StorageTrieUpdates<'a> {
         is_deleted: bool,
         storage_nodes: Cow<'a, HashMap<Nibbles, BranchNodeCompact>>,
         removed_nodes: Cow<'a, HashSet<Nibbles>>,
     }
 
+    impl Serialize for StorageTrieUpdates<'_> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let mut state = serializer.serialize_struct("StorageTrieUpdates", 3)?;
+            state.serialize_field("is_deleted", &self.is_deleted)?;
+            state.serialize_field("storage_nodes", &SortedMap(&*self.storage_nodes))?;
+            state.serialize_field("removed_nodes", &SortedSet(&*self.removed_nodes))?;
+            state.end()
+        }
+    }
+
     impl<'a> From<&'a super::StorageTrieUpdates> for StorageTrieUpdates<'a> {
         fn from(value: &'a super::StorageTrieUpdates) -> Self {
             Self {
@@ -561,6 +590,7 @@ pub mod serde_bincode_compat {
             updates::{StorageTrieUpdates, TrieUpdates},
             BranchNodeCompact, Nibbles,
         };
+        use alloc::collections::BTreeSet;
         use alloy_primitives::B256;
         use serde::{Deserialize, Serialize};
         use serde_with::serde_as;
@@ -598,6 +628,34 @@ pub mod serde_bincode_compat {
             assert_eq!(decoded, data);
         }
 
+        #[test]
+        fn test_trie_updates_bincode_canonical() {
+            #[serde_as]
+            #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+            struct Data {
+                #[serde_as(as = "serde_bincode_compat::updates::TrieUpdates")]
+                trie_updates: TrieUpdates,
+            }
+
+            let nodes = (0..16u8).map(|nibble| Nibbles::from_vec(vec![nibble, 0x0f - nibble]));
+            let mut data = Data { trie_updates: TrieUpdates::default() };
+            data.trie_updates.removed_nodes.extend(nodes.clone());
+            let mut reversed = Data { trie_updates: TrieUpdates::default() };
+            reversed.trie_updates.removed_nodes.extend(nodes.clone().rev());
+
+            let encoded = bincode::serialize(&data).unwrap();
+            assert_eq!(encoded, bincode::serialize(&reversed).unwrap());
+            assert_eq!(
+                encoded,
+                bincode::serialize(&(
+                    Vec::<()>::new(),
+                    nodes.collect::<BTreeSet<_>>(),
+                    Vec::<()>::new()
+                ))
+                .unwrap()
+            );
+        }
+
         #[test]
         fn test_storage_trie_updates_bincode_roundtrip() {
             #[serde_as]
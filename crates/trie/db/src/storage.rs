@@ -5,7 +5,8 @@ use reth_db_api::{
 };
 use reth_execution_errors::StorageRootError;
 use reth_trie::{
-    hashed_cursor::HashedPostStateCursorFactory, HashedPostState, HashedStorage, StorageRoot,
+    hashed_cursor::{hashed_storage_range, HashedPostStateCursorFactory},
+    HashedPostState, HashedStorage, HashedStorageRange, StorageRoot,
 };
 
 #[cfg(feature = "metrics")]
@@ -32,6 +33,16 @@ pub trait DatabaseHashedStorage<TX>: Sized {
     /// Initializes [`HashedStorage`] from reverts. Iterates over storage reverts from the specified
     /// block up to the current tip and aggregates them into hashed storage in reverse.
     fn from_reverts(tx: &TX, address: Address, from: BlockNumber) -> Result<Self, DatabaseError>;
+
+    /// Returns up to `limit` storage slots of the account with this [`HashedStorage`] on top of
+    /// the database state, starting at the given hashed slot.
+    fn overlay_range(
+        self,
+        tx: &TX,
+        address: Address,
+        start: B256,
+        limit: usize,
+    ) -> Result<HashedStorageRange, DatabaseError>;
 }
 
 impl<'a, TX: DbTx> DatabaseStorageRoot<'a, TX>
@@ -94,4 +105,21 @@ impl<TX: DbTx> DatabaseHashedStorage<TX> for HashedStorage {
         }
         Ok(storage)
     }
+
+    fn overlay_range(
+        self,
+        tx: &TX,
+        address: Address,
+        start: B256,
+        limit: usize,
+    ) -> Result<HashedStorageRange, DatabaseError> {
+        let hashed_address = keccak256(address);
+        let state_sorted = HashedPostState::from_hashed_storage(hashed_address, self).into_sorted();
+        hashed_storage_range(
+            &HashedPostStateCursorFactory::new(DatabaseHashedCursorFactory::new(tx), &state_sorted),
+            hashed_address,
+            start,
+            limit,
+        )
+    }
 }
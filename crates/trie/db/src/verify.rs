@@ -0,0 +1,16 @@
+use crate::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory};
+use reth_db_api::transaction::DbTx;
+use reth_execution_errors::StateRootError;
+use reth_trie::verify::{TrieVerifier, TrieVerifierStep};
+
+/// Extends [`TrieVerifier`] with operations specific for working with a database transaction.
+pub trait DatabaseTrieVerifier<TX> {
+    /// Verifies the next subtrie against the hashed state of the given transaction.
+    fn verify_next_from_tx(&mut self, tx: &TX) -> Result<TrieVerifierStep, StateRootError>;
+}
+
+impl<TX: DbTx> DatabaseTrieVerifier<TX> for TrieVerifier {
+    fn verify_next_from_tx(&mut self, tx: &TX) -> Result<TrieVerifierStep, StateRootError> {
+        self.verify_next(DatabaseTrieCursorFactory::new(tx), DatabaseHashedCursorFactory::new(tx))
+    }
+}
@@ -121,6 +121,28 @@ fn testspec_empty_storage_proof() {
     assert_eq!(account_proof.verify(root), Ok(()));
 }
 
+#[test]
+fn testspec_multiple_account_proofs() {
+    // Create test database and insert genesis accounts.
+    let factory = create_test_provider_factory();
+    let root = insert_genesis(&factory, TEST_SPEC.clone()).unwrap();
+
+    let targets = Vec::from([
+        (address!("0x2031f89b3ea8014eb51a78c316e42af3e0d7695f"), vec![]),
+        (address!("0x1ed9b1dd266b607ee278726d324b855a093394a6"), vec![B256::with_last_byte(1)]),
+        (Address::random(), vec![]),
+    ]);
+
+    let provider = factory.provider().unwrap();
+    let account_proofs = Proof::from_tx(provider.tx_ref()).account_proofs(&targets).unwrap();
+    assert_eq!(account_proofs.len(), targets.len());
+    for ((target, slots), account_proof) in targets.iter().zip(account_proofs) {
+        let expected = Proof::from_tx(provider.tx_ref()).account_proof(*target, slots).unwrap();
+        similar_asserts::assert_eq!(account_proof, expected);
+        assert_eq!(account_proof.verify(root), Ok(()));
+    }
+}
+
 #[test]
 fn mainnet_genesis_account_proof() {
     // Create test database and insert genesis accounts.
@@ -1,7 +1,7 @@
 #[cfg(feature = "metrics")]
 use crate::metrics::ParallelStateRootMetrics;
 use crate::{stats::ParallelTrieTracker, storage_root_targets::StorageRootTargets};
-use alloy_primitives::B256;
+use alloy_primitives::{map::B256Set, B256};
 use alloy_rlp::{BufMut, Encodable};
 use itertools::Itertools;
 use reth_execution_errors::StorageRootError;
@@ -10,16 +10,22 @@ use reth_provider::{
     StateCommitmentProvider,
 };
 use reth_storage_errors::db::DatabaseError;
+#[cfg(feature = "metrics")]
+use reth_trie::metrics::TrieRootMetrics;
 use reth_trie::{
     hashed_cursor::{HashedCursorFactory, HashedPostStateCursorFactory},
     node_iter::{TrieElement, TrieNodeIter},
+    prefix_set::{PrefixSet, PrefixSetMut},
     trie_cursor::{InMemoryTrieCursorFactory, TrieCursorFactory},
-    updates::TrieUpdates,
+    updates::{StorageTrieUpdates, TrieUpdates, TrieUpdatesSorted},
     walker::TrieWalker,
-    HashBuilder, Nibbles, StorageRoot, TrieInput, TRIE_ACCOUNT_RLP_MAX_SIZE,
+    HashBuilder, HashedPostStateSorted, Nibbles, StorageRoot, TrieInput, TRIE_ACCOUNT_RLP_MAX_SIZE,
 };
 use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory};
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{mpsc, Arc},
+};
 use thiserror::Error;
 use tracing::*;
 
@@ -30,6 +36,10 @@ use tracing::*;
 /// nodes in the process. Upon encountering a leaf node, it will poll the storage root
 /// task for the corresponding hashed address.
 ///
+/// If the account trie is split into subtries, see [`Self::with_subtrie_split`], the walk is split
+/// into a task per changed subtrie below the root, and the root is computed from the updated
+/// subtries afterwards.
+///
 /// Internally, the calculator uses [`ConsistentDbView`] since
 /// it needs to rely on database state saying the same until
 /// the last transaction is open.
@@ -40,6 +50,8 @@ pub struct ParallelStateRoot<Factory> {
     view: ConsistentDbView<Factory>,
     /// Trie input.
     input: TrieInput,
+    /// Whether to walk the subtries below the root of the account trie in parallel.
+    subtrie_split: bool,
     /// Parallel state root metrics.
     #[cfg(feature = "metrics")]
     metrics: ParallelStateRootMetrics,
@@ -51,10 +63,21 @@ impl<Factory> ParallelStateRoot<Factory> {
         Self {
             view,
             input,
+            subtrie_split: false,
             #[cfg(feature = "metrics")]
             metrics: ParallelStateRootMetrics::default(),
         }
     }
+
+    /// Set whether to split the account trie into the 16 subtries below its root, which are
+    /// walked in parallel.
+    ///
+    /// This speeds up the calculation for blocks that change accounts across the whole trie, at
+    /// the cost of a final walk over the changed subtrie roots.
+    pub const fn with_subtrie_split(mut self, subtrie_split: bool) -> Self {
+        self.subtrie_split = subtrie_split;
+        self
+    }
 }
 
 impl<Factory> ParallelStateRoot<Factory>
@@ -133,76 +156,41 @@ where
         }
 
         trace!(target: "trie::parallel_state_root", "calculating state root");
-        let mut trie_updates = TrieUpdates::default();
-
-        let provider_ro = self.view.provider_ro()?;
-        let trie_cursor_factory = InMemoryTrieCursorFactory::new(
-            DatabaseTrieCursorFactory::new(provider_ro.tx_ref()),
-            &trie_nodes_sorted,
-        );
-        let hashed_cursor_factory = HashedPostStateCursorFactory::new(
-            DatabaseHashedCursorFactory::new(provider_ro.tx_ref()),
-            &hashed_state_sorted,
-        );
-
-        let walker = TrieWalker::new(
-            trie_cursor_factory.account_trie_cursor().map_err(ProviderError::Database)?,
-            prefix_sets.account_prefix_set,
-        )
-        .with_deletions_retained(retain_updates);
-        let mut account_node_iter = TrieNodeIter::new(
-            walker,
-            hashed_cursor_factory.hashed_account_cursor().map_err(ProviderError::Database)?,
-        );
-
-        let mut hash_builder = HashBuilder::default().with_updates(retain_updates);
-        let mut account_rlp = Vec::with_capacity(TRIE_ACCOUNT_RLP_MAX_SIZE);
-        while let Some(node) = account_node_iter.try_next().map_err(ProviderError::Database)? {
-            match node {
-                TrieElement::Branch(node) => {
-                    hash_builder.add_branch(node.key, node.value, node.children_are_in_trie);
-                }
-                TrieElement::Leaf(hashed_address, account) => {
-                    let (storage_root, _, updates) = match storage_roots.remove(&hashed_address) {
-                        Some(rx) => rx.recv().map_err(|_| {
-                            ParallelStateRootError::StorageRoot(StorageRootError::Database(
-                                DatabaseError::Other(format!(
-                                    "channel closed for {hashed_address}"
-                                )),
-                            ))
-                        })??,
-                        // Since we do not store all intermediate nodes in the database, there might
-                        // be a possibility of re-adding a non-modified leaf to the hash builder.
-                        None => {
-                            tracker.inc_missed_leaves();
-                            StorageRoot::new_hashed(
-                                trie_cursor_factory.clone(),
-                                hashed_cursor_factory.clone(),
-                                hashed_address,
-                                Default::default(),
-                                #[cfg(feature = "metrics")]
-                                self.metrics.storage_trie.clone(),
-                            )
-                            .calculate(retain_updates)?
-                        }
-                    };
-
-                    if retain_updates {
-                        trie_updates.insert_storage_updates(hashed_address, updates);
-                    }
-
-                    account_rlp.clear();
-                    let account = account.into_trie_account(storage_root);
-                    account.encode(&mut account_rlp as &mut dyn BufMut);
-                    hash_builder.add_leaf(Nibbles::unpack(hashed_address), &account_rlp);
-                }
-            }
-        }
-
-        let root = hash_builder.root();
-
-        let removed_keys = account_node_iter.walker.take_removed_keys();
-        trie_updates.finalize(hash_builder, removed_keys, prefix_sets.destroyed_accounts);
+        // An empty prefix set either has no changes or marks the whole trie as changed, neither
+        // of which can be split.
+        let (root, trie_updates) =
+            if self.subtrie_split && !prefix_sets.account_prefix_set.is_empty() {
+                self.calculate_subtries(
+                    trie_nodes_sorted,
+                    hashed_state_sorted,
+                    prefix_sets.account_prefix_set,
+                    prefix_sets.destroyed_accounts,
+                    storage_roots,
+                    retain_updates,
+                    &mut tracker,
+                )?
+            } else {
+                let provider_ro = self.view.provider_ro()?;
+                let trie_cursor_factory = InMemoryTrieCursorFactory::new(
+                    DatabaseTrieCursorFactory::new(provider_ro.tx_ref()),
+                    &trie_nodes_sorted,
+                );
+                let hashed_cursor_factory = HashedPostStateCursorFactory::new(
+                    DatabaseHashedCursorFactory::new(provider_ro.tx_ref()),
+                    &hashed_state_sorted,
+                );
+                calculate_account_trie(
+                    trie_cursor_factory,
+                    hashed_cursor_factory,
+                    prefix_sets.account_prefix_set,
+                    prefix_sets.destroyed_accounts,
+                    &mut storage_roots,
+                    retain_updates,
+                    &mut tracker,
+                    #[cfg(feature = "metrics")]
+                    &self.metrics.storage_trie,
+                )?
+            };
 
         let stats = tracker.finish();
 
@@ -222,6 +210,231 @@ where
 
         Ok((root, trie_updates))
     }
+
+    /// Walks the changed subtries below the root of the account trie in parallel, and computes
+    /// the root from the updated subtries.
+    #[expect(clippy::too_many_arguments)]
+    fn calculate_subtries(
+        &self,
+        trie_nodes_sorted: Arc<TrieUpdatesSorted>,
+        hashed_state_sorted: Arc<HashedPostStateSorted>,
+        account_prefix_set: PrefixSet,
+        destroyed_accounts: B256Set,
+        storage_roots: HashMap<B256, StorageRootReceiver>,
+        retain_updates: bool,
+        tracker: &mut ParallelTrieTracker,
+    ) -> Result<(B256, TrieUpdates), ParallelStateRootError> {
+        // Partition the changes by the first nibble of the hashed addresses.
+        let mut subtries = BTreeMap::<u8, Subtrie>::new();
+        for key in account_prefix_set.iter() {
+            let Some(&nibble) = key.first() else { continue };
+            subtries.entry(nibble).or_default().prefix_set.insert(key.clone());
+        }
+        for hashed_address in destroyed_accounts {
+            subtries
+                .entry(hashed_address[0] >> 4)
+                .or_default()
+                .destroyed_accounts
+                .insert(hashed_address);
+        }
+        for (hashed_address, rx) in storage_roots {
+            subtries
+                .entry(hashed_address[0] >> 4)
+                .or_default()
+                .storage_roots
+                .insert(hashed_address, rx);
+        }
+        debug!(target: "trie::parallel_state_root", subtries = subtries.len(), "calculating subtries");
+
+        let mut subtrie_results = Vec::with_capacity(subtries.len());
+        for (nibble, subtrie) in subtries {
+            let view = self.view.clone();
+            let hashed_state_sorted = hashed_state_sorted.clone();
+            let trie_nodes_sorted = trie_nodes_sorted.clone();
+            #[cfg(feature = "metrics")]
+            let metrics = self.metrics.storage_trie.clone();
+
+            let (tx, rx) = std::sync::mpsc::sync_channel(1);
+
+            rayon::spawn_fifo(move || {
+                let result = (|| -> Result<_, ParallelStateRootError> {
+                    let provider_ro = view.provider_ro()?;
+                    let trie_cursor_factory = InMemoryTrieCursorFactory::new(
+                        DatabaseTrieCursorFactory::new(provider_ro.tx_ref()),
+                        &trie_nodes_sorted,
+                    );
+                    let hashed_cursor_factory = HashedPostStateCursorFactory::new(
+                        DatabaseHashedCursorFactory::new(provider_ro.tx_ref()),
+                        &hashed_state_sorted,
+                    );
+                    let Subtrie { prefix_set, destroyed_accounts, mut storage_roots } = subtrie;
+                    let mut tracker = ParallelTrieTracker::default();
+                    // The updates are always retained, the subtries are merged from them.
+                    let (_, mut updates) = calculate_account_trie(
+                        trie_cursor_factory,
+                        hashed_cursor_factory,
+                        prefix_set.freeze(),
+                        destroyed_accounts,
+                        &mut storage_roots,
+                        true,
+                        &mut tracker,
+                        #[cfg(feature = "metrics")]
+                        &metrics,
+                    )?;
+
+                    // Only the nodes of the subtrie are up to date, the nodes above it and the
+                    // storage tries of the accounts in other subtries are discarded.
+                    updates.account_nodes.retain(|path, _| path.first() == Some(&nibble));
+                    updates.removed_nodes.retain(|path| path.first() == Some(&nibble));
+                    updates
+                        .storage_tries
+                        .retain(|hashed_address, _| hashed_address[0] >> 4 == nibble);
+                    Ok((updates, tracker.finish().missed_leaves()))
+                })();
+                let _ = tx.send(result);
+            });
+            subtrie_results.push((nibble, rx));
+        }
+
+        let mut subtrie_updates = TrieUpdates::default();
+        let mut root_prefix_set = PrefixSetMut::with_capacity(subtrie_results.len());
+        for (nibble, rx) in subtrie_results {
+            let (updates, missed_leaves) = rx.recv().map_err(|_| {
+                ParallelStateRootError::Other(format!("channel closed for subtrie {nibble:x}"))
+            })??;
+            subtrie_updates.extend(updates);
+            tracker.add_missed_leaves(missed_leaves);
+            root_prefix_set.insert(Nibbles::from_nibbles_unchecked([nibble]));
+        }
+
+        // Compute the root from the updated subtries, only the nodes above them are walked.
+        let subtrie_updates_sorted = subtrie_updates.clone().into_sorted();
+        let provider_ro = self.view.provider_ro()?;
+        let trie_cursor_factory = InMemoryTrieCursorFactory::new(
+            InMemoryTrieCursorFactory::new(
+                DatabaseTrieCursorFactory::new(provider_ro.tx_ref()),
+                &trie_nodes_sorted,
+            ),
+            &subtrie_updates_sorted,
+        );
+        let hashed_cursor_factory = HashedPostStateCursorFactory::new(
+            DatabaseHashedCursorFactory::new(provider_ro.tx_ref()),
+            &hashed_state_sorted,
+        );
+        let (root, root_updates) = calculate_account_trie(
+            trie_cursor_factory,
+            hashed_cursor_factory,
+            root_prefix_set.freeze(),
+            B256Set::default(),
+            &mut HashMap::default(),
+            retain_updates,
+            tracker,
+            #[cfg(feature = "metrics")]
+            &self.metrics.storage_trie,
+        )?;
+
+        if !retain_updates {
+            return Ok((root, TrieUpdates::default()))
+        }
+        subtrie_updates.extend(root_updates);
+        Ok((root, subtrie_updates))
+    }
+}
+
+/// The receiver of a storage root calculated in parallel.
+type StorageRootReceiver =
+    mpsc::Receiver<Result<(B256, usize, StorageTrieUpdates), ParallelStateRootError>>;
+
+/// The changes of a subtrie below the root of the account trie.
+#[derive(Default)]
+struct Subtrie {
+    /// The changed keys of the subtrie.
+    prefix_set: PrefixSetMut,
+    /// The destroyed accounts of the subtrie.
+    destroyed_accounts: B256Set,
+    /// The storage roots of the changed accounts of the subtrie.
+    storage_roots: HashMap<B256, StorageRootReceiver>,
+}
+
+/// Walks the account trie, updating the nodes along the paths in the prefix set.
+///
+/// The storage roots of the changed accounts are received from `storage_roots`, the storage roots
+/// of other accounts added to the hash builder are calculated in place.
+#[cfg_attr(feature = "metrics", expect(clippy::too_many_arguments))]
+fn calculate_account_trie<T, H>(
+    trie_cursor_factory: T,
+    hashed_cursor_factory: H,
+    prefix_set: PrefixSet,
+    destroyed_accounts: B256Set,
+    storage_roots: &mut HashMap<B256, StorageRootReceiver>,
+    retain_updates: bool,
+    tracker: &mut ParallelTrieTracker,
+    #[cfg(feature = "metrics")] storage_trie_metrics: &TrieRootMetrics,
+) -> Result<(B256, TrieUpdates), ParallelStateRootError>
+where
+    T: TrieCursorFactory + Clone,
+    H: HashedCursorFactory + Clone,
+{
+    let mut trie_updates = TrieUpdates::default();
+
+    let walker = TrieWalker::new(
+        trie_cursor_factory.account_trie_cursor().map_err(ProviderError::Database)?,
+        prefix_set,
+    )
+    .with_deletions_retained(retain_updates);
+    let mut account_node_iter = TrieNodeIter::new(
+        walker,
+        hashed_cursor_factory.hashed_account_cursor().map_err(ProviderError::Database)?,
+    );
+
+    let mut hash_builder = HashBuilder::default().with_updates(retain_updates);
+    let mut account_rlp = Vec::with_capacity(TRIE_ACCOUNT_RLP_MAX_SIZE);
+    while let Some(node) = account_node_iter.try_next().map_err(ProviderError::Database)? {
+        match node {
+            TrieElement::Branch(node) => {
+                hash_builder.add_branch(node.key, node.value, node.children_are_in_trie);
+            }
+            TrieElement::Leaf(hashed_address, account) => {
+                let (storage_root, _, updates) = match storage_roots.remove(&hashed_address) {
+                    Some(rx) => rx.recv().map_err(|_| {
+                        ParallelStateRootError::StorageRoot(StorageRootError::Database(
+                            DatabaseError::Other(format!("channel closed for {hashed_address}")),
+                        ))
+                    })??,
+                    // Since we do not store all intermediate nodes in the database, there might
+                    // be a possibility of re-adding a non-modified leaf to the hash builder.
+                    None => {
+                        tracker.inc_missed_leaves();
+                        StorageRoot::new_hashed(
+                            trie_cursor_factory.clone(),
+                            hashed_cursor_factory.clone(),
+                            hashed_address,
+                            Default::default(),
+                            #[cfg(feature = "metrics")]
+                            storage_trie_metrics.clone(),
+                        )
+                        .calculate(retain_updates)?
+                    }
+                };
+
+                if retain_updates {
+                    trie_updates.insert_storage_updates(hashed_address, updates);
+                }
+
+                account_rlp.clear();
+                let account = account.into_trie_account(storage_root);
+                account.encode(&mut account_rlp as &mut dyn BufMut);
+                hash_builder.add_leaf(Nibbles::unpack(hashed_address), &account_rlp);
+            }
+        }
+    }
+
+    let root = hash_builder.root();
+
+    let removed_keys = account_node_iter.walker.take_removed_keys();
+    trie_updates.finalize(hash_builder, removed_keys, destroyed_accounts);
+
+    Ok((root, trie_updates))
 }
 
 /// Error during parallel state root calculation.
@@ -256,7 +469,7 @@ mod tests {
     use alloy_primitives::{keccak256, Address, U256};
     use rand::Rng;
     use reth_primitives_traits::{Account, StorageEntry};
-    use reth_provider::{test_utils::create_test_provider_factory, HashingWriter};
+    use reth_provider::{test_utils::create_test_provider_factory, HashingWriter, TrieWriter};
     use reth_trie::{test_utils, HashedPostState, HashedStorage};
 
     #[test]
@@ -343,4 +556,112 @@ mod tests {
             test_utils::state_root(state)
         );
     }
+
+    #[test]
+    fn split_parallel_root() {
+        let factory = create_test_provider_factory();
+        let consistent_view = ConsistentDbView::new(factory.clone(), None);
+
+        let mut rng = rand::thread_rng();
+        let mut state = (0..1000)
+            .map(|_| {
+                let address = Address::random();
+                let account =
+                    Account { balance: U256::from(rng.gen::<u64>()), ..Default::default() };
+                let storage = (0..rng.gen_range(0..10))
+                    .map(|_| {
+                        (B256::from(U256::from(rng.gen::<u64>())), U256::from(rng.gen::<u64>()))
+                    })
+                    .collect::<HashMap<_, _>>();
+                (address, (account, storage))
+            })
+            .collect::<HashMap<_, _>>();
+
+        {
+            let provider_rw = factory.provider_rw().unwrap();
+            provider_rw
+                .insert_account_for_hashing(
+                    state.iter().map(|(address, (account, _))| (*address, Some(*account))),
+                )
+                .unwrap();
+            provider_rw
+                .insert_storage_for_hashing(state.iter().map(|(address, (_, storage))| {
+                    (
+                        *address,
+                        storage
+                            .iter()
+                            .map(|(slot, value)| StorageEntry { key: *slot, value: *value }),
+                    )
+                }))
+                .unwrap();
+            provider_rw.commit().unwrap();
+        }
+
+        // Store the trie, so the subtries are walked from the stored branch nodes.
+        let (root, updates) = ParallelStateRoot::new(consistent_view.clone(), Default::default())
+            .with_subtrie_split(true)
+            .incremental_root_with_updates()
+            .unwrap();
+        assert_eq!(root, test_utils::state_root(state.clone()));
+        {
+            let provider_rw = factory.provider_rw().unwrap();
+            provider_rw.write_trie_updates(&updates).unwrap();
+            provider_rw.commit().unwrap();
+        }
+
+        let mut hashed_state = HashedPostState::default();
+        for (address, (account, storage)) in &mut state {
+            let hashed_address = keccak256(address);
+
+            if rng.gen_bool(0.1) {
+                *account = Account { balance: U256::from(rng.gen::<u64>()), ..*account };
+                hashed_state.accounts.insert(hashed_address, Some(*account));
+            }
+
+            if rng.gen_bool(0.05) {
+                for (slot, value) in storage.iter_mut() {
+                    *value = U256::from(rng.gen::<u64>());
+                    hashed_state
+                        .storages
+                        .entry(hashed_address)
+                        .or_insert_with(HashedStorage::default)
+                        .storage
+                        .insert(keccak256(slot), *value);
+                }
+            }
+        }
+
+        let (root, updates) =
+            ParallelStateRoot::new(consistent_view.clone(), TrieInput::from_state(hashed_state))
+                .with_subtrie_split(true)
+                .incremental_root_with_updates()
+                .unwrap();
+        assert_eq!(root, test_utils::state_root(state.clone()));
+
+        // The stored trie must be consistent with the state after applying the updates.
+        {
+            let provider_rw = factory.provider_rw().unwrap();
+            provider_rw
+                .insert_account_for_hashing(
+                    state.iter().map(|(address, (account, _))| (*address, Some(*account))),
+                )
+                .unwrap();
+            provider_rw
+                .insert_storage_for_hashing(state.iter().map(|(address, (_, storage))| {
+                    (
+                        *address,
+                        storage
+                            .iter()
+                            .map(|(slot, value)| StorageEntry { key: *slot, value: *value }),
+                    )
+                }))
+                .unwrap();
+            provider_rw.write_trie_updates(&updates).unwrap();
+            provider_rw.commit().unwrap();
+        }
+        assert_eq!(
+            ParallelStateRoot::new(consistent_view, Default::default()).incremental_root().unwrap(),
+            root
+        );
+    }
 }
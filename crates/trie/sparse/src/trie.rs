@@ -4,10 +4,11 @@ use alloy_primitives::{
     map::{Entry, HashMap, HashSet},
     B256,
 };
-use alloy_rlp::Decodable;
+use alloy_rlp::{Decodable, Encodable};
 use reth_execution_errors::{SparseTrieErrorKind, SparseTrieResult};
 use reth_tracing::tracing::trace;
 use reth_trie_common::{
+    keccak::keccak256_batch,
     prefix_set::{PrefixSet, PrefixSetMut},
     BranchNodeCompact, BranchNodeRef, ExtensionNodeRef, LeafNodeRef, Nibbles, RlpNode, TrieMask,
     TrieNode, CHILD_INDEX_RANGE, EMPTY_ROOT_HASH,
@@ -961,6 +962,7 @@ impl RevealedSparseTrie {
                                 path,
                                 is_in_prefix_set,
                             });
+                            let children = buffers.branch_child_buf.len();
                             buffers.path_stack.extend(buffers.branch_child_buf.drain(..).map(
                                 |path| RlpNodePathStackItem {
                                     level: level + 1,
@@ -968,6 +970,7 @@ impl RevealedSparseTrie {
                                     is_in_prefix_set: None,
                                 },
                             ));
+                            self.rlp_leaf_children(prefix_set, buffers, rlp_buf, children);
                             continue 'main
                         }
                     }
@@ -1060,6 +1063,82 @@ impl RevealedSparseTrie {
         debug_assert_eq!(buffers.rlp_node_stack.len(), 1);
         buffers.rlp_node_stack.pop().unwrap().rlp_node
     }
+
+    /// Computes the RLP nodes of the `children` branch node children on top of the path stack at
+    /// once, if they're all leaves or blinded nodes, and moves them to the RLP node stack.
+    ///
+    /// The leaves that need to be hashed are hashed together with [`keccak256_batch`], instead of
+    /// one by one when popped from the path stack. If any of the children is a branch or an
+    /// extension node, the stacks are left unchanged and the children are handled one by one.
+    fn rlp_leaf_children(
+        &mut self,
+        prefix_set: &mut PrefixSet,
+        buffers: &mut RlpNodeBuffers,
+        rlp_buf: &mut Vec<u8>,
+        children: usize,
+    ) {
+        let start = buffers.path_stack.len() - children;
+        let all_leaves_or_hashes = buffers.path_stack[start..].iter().all(|item| {
+            matches!(
+                self.nodes.get(&item.path),
+                Some(SparseNode::Leaf { .. } | SparseNode::Hash(_))
+            )
+        });
+        if children < 2 || !all_leaves_or_hashes {
+            return
+        }
+
+        // The top of the path stack is the child with the lowest nibble, which is processed first.
+        let mut rlp_nodes = SmallVec::<[(RlpNode, SparseNodeType); 16]>::new_const();
+        let mut unhashed = SmallVec::<[(usize, std::ops::Range<usize>); 16]>::new_const();
+        rlp_buf.clear();
+        for item in buffers.path_stack[start..].iter().rev() {
+            let (rlp_node, node_type) = match self.nodes.get(&item.path).unwrap() {
+                SparseNode::Hash(hash) => (RlpNode::word_rlp(hash), SparseNodeType::Hash),
+                SparseNode::Leaf { key, hash } => {
+                    let mut path = item.path.clone();
+                    path.extend_from_slice_unchecked(key);
+                    if let Some(hash) = hash.filter(|_| !prefix_set.contains(&path)) {
+                        (RlpNode::word_rlp(&hash), SparseNodeType::Leaf)
+                    } else {
+                        let value = self.values.get(&path).unwrap();
+                        let offset = rlp_buf.len();
+                        LeafNodeRef { key, value }.encode(rlp_buf);
+                        if rlp_buf.len() - offset < 32 {
+                            // Short nodes are inlined into the parent instead of being hashed
+                            let rlp_node = RlpNode::from_raw(&rlp_buf[offset..]).unwrap();
+                            rlp_buf.truncate(offset);
+                            (rlp_node, SparseNodeType::Leaf)
+                        } else {
+                            unhashed.push((rlp_nodes.len(), offset..rlp_buf.len()));
+                            (RlpNode::default(), SparseNodeType::Leaf)
+                        }
+                    }
+                }
+                _ => unreachable!("all children are leaves or blinded nodes"),
+            };
+            rlp_nodes.push((rlp_node, node_type));
+        }
+
+        let encoded = unhashed.iter().map(|(_, range)| &rlp_buf[range.clone()]).collect::<Vec<_>>();
+        for ((index, _), hash) in unhashed.iter().zip(keccak256_batch(&encoded)) {
+            rlp_nodes[*index].0 = RlpNode::word_rlp(&hash);
+        }
+
+        let paths = buffers.path_stack.drain(start..).rev().map(|item| item.path);
+        for (path, (rlp_node, node_type)) in paths.zip(rlp_nodes) {
+            if let Some(SparseNode::Leaf { hash, .. }) = self.nodes.get_mut(&path) {
+                *hash = rlp_node.as_hash();
+            }
+            trace!(
+                target: "trie::sparse",
+                ?path,
+                ?node_type,
+                "Added batched node to rlp node stack"
+            );
+            buffers.rlp_node_stack.push(RlpNodeStackItem { path, rlp_node, node_type });
+        }
+    }
 }
 
 impl RevealedSparseTrie {
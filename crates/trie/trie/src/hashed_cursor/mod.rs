@@ -1,6 +1,7 @@
 use alloy_primitives::{B256, U256};
 use reth_primitives_traits::Account;
 use reth_storage_errors::db::DatabaseError;
+use reth_trie_common::HashedStorageRange;
 
 /// Implementation of hashed state cursor traits for the post state.
 mod post_state;
@@ -48,3 +49,59 @@ pub trait HashedStorageCursor: HashedCursor {
     /// Returns `true` if there are no entries for a given key.
     fn is_storage_empty(&mut self) -> Result<bool, DatabaseError>;
 }
+
+/// Returns up to `limit` storage slots of the account, starting at the given hashed slot.
+pub fn hashed_storage_range<F: HashedCursorFactory>(
+    factory: &F,
+    hashed_address: B256,
+    start: B256,
+    limit: usize,
+) -> Result<HashedStorageRange, DatabaseError> {
+    let mut cursor = factory.hashed_storage_cursor(hashed_address)?;
+    let mut range = HashedStorageRange::default();
+    let mut entry = cursor.seek(start)?;
+    while let Some((hashed_slot, value)) = entry {
+        if range.slots.len() == limit {
+            range.next_key = Some(hashed_slot);
+            break
+        }
+        range.slots.push((hashed_slot, value));
+        entry = cursor.next()?;
+    }
+    Ok(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HashedPostState, HashedStorage};
+    use alloy_primitives::map::B256Map;
+    use mock::MockHashedCursorFactory;
+
+    #[test]
+    fn storage_range_with_overlay() {
+        let hashed_address = B256::with_last_byte(1);
+        let slot = B256::with_last_byte;
+        let storage = (1..=4).map(|i| (slot(i), U256::from(i))).collect();
+        let factory = MockHashedCursorFactory::new(
+            Default::default(),
+            B256Map::from_iter([(hashed_address, storage)]),
+        );
+
+        // the overlay removes slot 2 and adds slot 5
+        let overlay = HashedPostState::from_hashed_storage(
+            hashed_address,
+            HashedStorage::from_iter(false, [(slot(2), U256::ZERO), (slot(5), U256::from(5))]),
+        )
+        .into_sorted();
+        let factory = HashedPostStateCursorFactory::new(factory, &overlay);
+
+        let range = hashed_storage_range(&factory, hashed_address, slot(1), 2).unwrap();
+        assert_eq!(range.slots, vec![(slot(1), U256::from(1)), (slot(3), U256::from(3))]);
+        assert_eq!(range.next_key, Some(slot(4)));
+
+        let range = hashed_storage_range(&factory, hashed_address, slot(4), 2).unwrap();
+        assert_eq!(range.slots, vec![(slot(4), U256::from(4)), (slot(5), U256::from(5))]);
+        assert_eq!(range.next_key, None);
+    }
+}
@@ -35,6 +35,9 @@ pub mod proof;
 /// Trie witness generation.
 pub mod witness;
 
+/// Verification of the stored trie against the hashed state.
+pub mod verify;
+
 /// The implementation of the Merkle Patricia Trie.
 mod trie;
 pub use trie::{StateRoot, StorageRoot};
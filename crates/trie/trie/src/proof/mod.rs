@@ -100,6 +100,19 @@ where
             .account_proof(address, slots)?)
     }
 
+    /// Generate account proofs for multiple accounts and their storage slots.
+    ///
+    /// The trie is walked only once for all targets. Proofs are returned in the order of the
+    /// targets.
+    pub fn account_proofs(
+        self,
+        targets: &[(Address, Vec<B256>)],
+    ) -> Result<Vec<AccountProof>, StateProofError> {
+        Ok(self
+            .multiproof(MultiProofTargets::from_addresses_with_slots(targets))?
+            .account_proofs(targets)?)
+    }
+
     /// Generate a state multiproof according to specified targets.
     pub fn multiproof(
         mut self,
@@ -1,4 +1,5 @@
 use crate::{
+    hash_builder::BatchedHashBuilder,
     hashed_cursor::{HashedCursorFactory, HashedStorageCursor},
     node_iter::{TrieElement, TrieNodeIter},
     prefix_set::{PrefixSet, TriePrefixSets},
@@ -158,7 +159,8 @@ where
         let hashed_account_cursor = self.hashed_cursor_factory.hashed_account_cursor()?;
         let (mut hash_builder, mut account_node_iter) = match self.previous_state {
             Some(state) => {
-                let hash_builder = state.hash_builder.with_updates(retain_updates);
+                let hash_builder =
+                    BatchedHashBuilder::new(state.hash_builder.with_updates(retain_updates));
                 let walker = TrieWalker::from_stack(
                     trie_cursor,
                     state.walker_stack,
@@ -170,7 +172,8 @@ where
                 (hash_builder, node_iter)
             }
             None => {
-                let hash_builder = HashBuilder::default().with_updates(retain_updates);
+                let hash_builder =
+                    BatchedHashBuilder::new(HashBuilder::default().with_updates(retain_updates));
                 let walker = TrieWalker::new(trie_cursor, self.prefix_sets.account_prefix_set)
                     .with_deletions_retained(retain_updates);
                 let node_iter = TrieNodeIter::new(walker, hashed_account_cursor);
@@ -235,7 +238,8 @@ where
                     if retain_updates && total_updates_len as u64 >= self.threshold {
                         let (walker_stack, walker_deleted_keys) = account_node_iter.walker.split();
                         trie_updates.removed_nodes.extend(walker_deleted_keys);
-                        let (hash_builder, hash_builder_updates) = hash_builder.split();
+                        let (hash_builder, hash_builder_updates) =
+                            hash_builder.into_inner().split();
                         trie_updates.account_nodes.extend(hash_builder_updates);
 
                         let state = IntermediateStateRootState {
@@ -257,7 +261,11 @@ where
         let root = hash_builder.root();
 
         let removed_keys = account_node_iter.walker.take_removed_keys();
-        trie_updates.finalize(hash_builder, removed_keys, self.prefix_sets.destroyed_accounts);
+        trie_updates.finalize(
+            hash_builder.into_inner(),
+            removed_keys,
+            self.prefix_sets.destroyed_accounts,
+        );
 
         let stats = tracker.finish();
 
@@ -410,7 +418,8 @@ where
         let walker =
             TrieWalker::new(trie_cursor, self.prefix_set).with_deletions_retained(retain_updates);
 
-        let mut hash_builder = HashBuilder::default().with_updates(retain_updates);
+        let mut hash_builder =
+            BatchedHashBuilder::new(HashBuilder::default().with_updates(retain_updates));
 
         let mut storage_node_iter = TrieNodeIter::new(walker, hashed_storage_cursor);
         while let Some(node) = storage_node_iter.try_next()? {
@@ -433,7 +442,7 @@ where
 
         let mut trie_updates = StorageTrieUpdates::default();
         let removed_keys = storage_node_iter.walker.take_removed_keys();
-        trie_updates.finalize(hash_builder, removed_keys);
+        trie_updates.finalize(hash_builder.into_inner(), removed_keys);
 
         let stats = tracker.finish();
 
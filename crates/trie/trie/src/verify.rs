@@ -0,0 +1,782 @@
+use crate::{
+    hashed_cursor::{HashedCursor, HashedCursorFactory},
+    prefix_set::{PrefixSet, PrefixSetMut, TriePrefixSets},
+    trie_cursor::{noop::NoopTrieCursorFactory, TrieCursor, TrieCursorFactory},
+    BranchNodeCompact, Nibbles, StateRoot, StorageRoot,
+};
+use alloy_primitives::{map::HashSet, B256};
+use reth_execution_errors::{StateRootError, StorageRootError};
+use reth_storage_errors::db::DatabaseError;
+use std::{collections::BTreeMap, fmt};
+
+#[cfg(feature = "metrics")]
+use crate::metrics::{TrieRootMetrics, TrieType};
+
+/// The depth of the account subtries verified by the [`TrieVerifier`], one after another.
+///
+/// A full pass over the trie takes at least `16^3` steps.
+pub const VERIFIER_SUBTRIE_DEPTH: usize = 3;
+
+/// The depth of the storage subtries a storage trie is verified in if it has more slots than a
+/// single [`TrieVerifier::verify_next`] step recomputes storage tries from.
+pub const VERIFIER_STORAGE_SUBTRIE_DEPTH: usize = 2;
+
+/// The default number of storage slots a single [`TrieVerifier::verify_next`] step recomputes
+/// storage tries from.
+pub const DEFAULT_VERIFIER_MAX_STORAGE_SLOTS: usize = 100_000;
+
+/// The trie a [`TrieDivergence`] was found in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DivergentTrie {
+    /// The account trie.
+    Account,
+    /// The storage trie of the account with the given hashed address.
+    Storage(B256),
+}
+
+impl fmt::Display for DivergentTrie {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Account => f.write_str("account trie"),
+            Self::Storage(hashed_address) => write!(f, "storage trie of {hashed_address}"),
+        }
+    }
+}
+
+/// How a stored trie node diverges from the node computed from the hashed state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrieDivergenceKind {
+    /// The node is missing from the stored trie.
+    Missing {
+        /// The node computed from the hashed state.
+        expected: BranchNodeCompact,
+    },
+    /// The stored node doesn't match the node computed from the hashed state.
+    Mismatch {
+        /// The node computed from the hashed state.
+        expected: BranchNodeCompact,
+        /// The stored node.
+        stored: BranchNodeCompact,
+    },
+    /// The stored node doesn't exist in the trie computed from the hashed state.
+    Extra {
+        /// The stored node.
+        stored: BranchNodeCompact,
+    },
+}
+
+/// A stored trie node that diverges from the trie computed from the hashed state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieDivergence {
+    /// The trie the node belongs to.
+    pub trie: DivergentTrie,
+    /// The path of the node.
+    pub path: Nibbles,
+    /// How the node diverges.
+    pub kind: TrieDivergenceKind,
+}
+
+impl fmt::Display for TrieDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} node at path 0x", self.trie)?;
+        for nibble in self.path.iter() {
+            write!(f, "{nibble:x}")?;
+        }
+        match self.kind {
+            TrieDivergenceKind::Missing { .. } => f.write_str(" is missing"),
+            TrieDivergenceKind::Mismatch { .. } => f.write_str(" doesn't match the hashed state"),
+            TrieDivergenceKind::Extra { .. } => f.write_str(" is not part of the hashed state"),
+        }
+    }
+}
+
+/// The result of a single [`TrieVerifier::verify_next`] step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieVerifierStep {
+    /// The prefix of the verified account subtrie.
+    pub prefix: Nibbles,
+    /// The divergent nodes found in the account subtrie, its ancestors and the storage tries of
+    /// its accounts.
+    pub divergences: Vec<TrieDivergence>,
+    /// Whether the step finished the verification of the account subtrie and the storage tries of
+    /// its accounts.
+    pub subtrie_finished: bool,
+    /// Whether the step finished a full pass over the trie.
+    pub pass_finished: bool,
+}
+
+/// The storage tries left to verify in an account subtrie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageTriesProgress {
+    /// The hashed address of the account whose storage trie is verified next.
+    pub hashed_address: B256,
+    /// The next storage subtrie to verify, out of [`TrieVerifier::STORAGE_SUBTRIES`], if the
+    /// storage trie of the account is too large to be verified in a single step.
+    pub next_subtrie: Option<usize>,
+}
+
+/// Incrementally verifies the stored account and storage tries against the hashed state tables.
+///
+/// The verifier recomputes the account subtrie under the next prefix of
+/// [`VERIFIER_SUBTRIE_DEPTH`] nibbles from the hashed accounts, followed by the storage tries of
+/// its accounts, and compares the computed nodes with the stored ones. A step recomputes storage
+/// tries from at most [`DEFAULT_VERIFIER_MAX_STORAGE_SLOTS`] slots, and the storage tries of an
+/// account subtrie are spread over as many steps as needed. Storage tries with more slots are
+/// verified one storage subtrie of [`VERIFIER_STORAGE_SUBTRIE_DEPTH`] nibbles per step.
+///
+/// Since a step only touches a small part of the state, each step can run in its own database
+/// transaction, which detects silent trie corruption before it causes a state root mismatch at
+/// the tip.
+#[derive(Debug)]
+pub struct TrieVerifier {
+    /// The index of the next account subtrie to verify.
+    next_subtrie: usize,
+    /// The storage tries left to verify in the current account subtrie, or [`None`] if the next
+    /// account subtrie is verified next.
+    storage: Option<StorageTriesProgress>,
+    /// The number of storage slots a step recomputes storage tries from.
+    max_storage_slots: usize,
+    /// The divergent ancestor nodes reported in the current pass.
+    ///
+    /// Ancestors are recomputed by every step below them, so they're only reported once per pass.
+    reported_ancestors: HashSet<(DivergentTrie, Nibbles)>,
+}
+
+impl Default for TrieVerifier {
+    fn default() -> Self {
+        Self {
+            next_subtrie: 0,
+            storage: None,
+            max_storage_slots: DEFAULT_VERIFIER_MAX_STORAGE_SLOTS,
+            reported_ancestors: HashSet::default(),
+        }
+    }
+}
+
+impl TrieVerifier {
+    /// The number of account subtries of a full pass over the trie.
+    pub const SUBTRIES: usize = 1 << (4 * VERIFIER_SUBTRIE_DEPTH);
+
+    /// The number of storage subtries a large storage trie is verified in.
+    pub const STORAGE_SUBTRIES: usize = 1 << (4 * VERIFIER_STORAGE_SUBTRIE_DEPTH);
+
+    /// Creates a new verifier, starting at the beginning of the trie.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of storage slots a step recomputes storage tries from.
+    pub const fn with_max_storage_slots(mut self, max_storage_slots: usize) -> Self {
+        self.max_storage_slots = max_storage_slots;
+        self
+    }
+
+    /// Returns the index of the next account subtrie to verify, out of [`Self::SUBTRIES`].
+    pub const fn next_subtrie(&self) -> usize {
+        self.next_subtrie
+    }
+
+    /// Verifies the next account subtrie, or the next storage tries of its accounts if the account
+    /// subtrie was verified by a previous step.
+    ///
+    /// The cursor factories must be backed by the same consistent view of the database.
+    pub fn verify_next<T, H>(
+        &mut self,
+        trie_cursor_factory: T,
+        hashed_cursor_factory: H,
+    ) -> Result<TrieVerifierStep, StateRootError>
+    where
+        T: TrieCursorFactory + Clone,
+        H: HashedCursorFactory + Clone,
+    {
+        let prefix = subtrie_prefix(self.next_subtrie, VERIFIER_SUBTRIE_DEPTH);
+
+        let mut divergences = Vec::new();
+        let progress = match self.storage.take() {
+            Some(progress) => progress,
+            None => {
+                divergences = verify_account_subtrie(
+                    trie_cursor_factory.clone(),
+                    hashed_cursor_factory.clone(),
+                    prefix.clone(),
+                )?;
+                self.retain_unreported(&mut divergences, prefix.len());
+                StorageTriesProgress {
+                    hashed_address: B256::right_padding_from(&prefix.pack()),
+                    next_subtrie: None,
+                }
+            }
+        };
+        divergences.extend(self.verify_storage_tries(
+            trie_cursor_factory,
+            hashed_cursor_factory,
+            &prefix,
+            progress,
+        )?);
+
+        let subtrie_finished = self.storage.is_none();
+        if subtrie_finished {
+            self.next_subtrie = (self.next_subtrie + 1) % Self::SUBTRIES;
+        }
+        let pass_finished = subtrie_finished && self.next_subtrie == 0;
+        if pass_finished {
+            self.reported_ancestors.clear();
+        }
+
+        Ok(TrieVerifierStep { prefix, divergences, subtrie_finished, pass_finished })
+    }
+
+    /// Verifies the storage tries of the accounts under the given prefix, starting at the given
+    /// progress, until the storage tries of [`Self::with_max_storage_slots`] slots were
+    /// recomputed.
+    ///
+    /// A storage trie with more slots is verified one storage subtrie per step. If storage tries
+    /// are left to verify, the progress is kept for the next step.
+    fn verify_storage_tries<T, H>(
+        &mut self,
+        trie_cursor_factory: T,
+        hashed_cursor_factory: H,
+        prefix: &Nibbles,
+        mut progress: StorageTriesProgress,
+    ) -> Result<Vec<TrieDivergence>, StorageRootError>
+    where
+        T: TrieCursorFactory,
+        H: HashedCursorFactory + Clone,
+    {
+        let mut divergences = Vec::new();
+        let mut remaining_slots = self.max_storage_slots;
+        let mut hashed_account_cursor = hashed_cursor_factory.hashed_account_cursor()?;
+        let mut entry = hashed_account_cursor.seek(progress.hashed_address)?;
+        while let Some((hashed_address, _)) = entry {
+            if !Nibbles::unpack(hashed_address).starts_with(prefix) {
+                break
+            }
+
+            // the account may have been removed since the previous step
+            let next_subtrie =
+                progress.next_subtrie.filter(|_| progress.hashed_address == hashed_address);
+            if let Some(subtrie) = next_subtrie {
+                let storage_prefix = subtrie_prefix(subtrie, VERIFIER_STORAGE_SUBTRIE_DEPTH);
+                let mut subtrie_divergences = verify_storage_subtrie(
+                    &trie_cursor_factory,
+                    hashed_cursor_factory.clone(),
+                    hashed_address,
+                    storage_prefix.clone(),
+                )?;
+                self.retain_unreported(&mut subtrie_divergences, storage_prefix.len());
+                divergences.extend(subtrie_divergences);
+
+                if subtrie + 1 < Self::STORAGE_SUBTRIES {
+                    self.storage = Some(StorageTriesProgress {
+                        hashed_address,
+                        next_subtrie: Some(subtrie + 1),
+                    });
+                    return Ok(divergences)
+                }
+
+                // the storage trie is verified, the next ones are verified by the next step
+                remaining_slots = 0;
+                progress.next_subtrie = None;
+                entry = hashed_account_cursor.next()?;
+                continue
+            }
+
+            let slots = count_storage_slots(
+                &hashed_cursor_factory,
+                hashed_address,
+                self.max_storage_slots + 1,
+            )?;
+            if slots > remaining_slots {
+                if remaining_slots < self.max_storage_slots {
+                    self.storage =
+                        Some(StorageTriesProgress { hashed_address, next_subtrie: None });
+                    return Ok(divergences)
+                }
+
+                // the storage trie is too large for a single step
+                progress = StorageTriesProgress { hashed_address, next_subtrie: Some(0) };
+                continue
+            }
+
+            remaining_slots -= slots;
+            divergences.extend(verify_storage_trie(
+                &trie_cursor_factory,
+                hashed_cursor_factory.clone(),
+                hashed_address,
+            )?);
+            entry = hashed_account_cursor.next()?;
+        }
+        Ok(divergences)
+    }
+
+    /// Removes the divergent ancestors of a subtrie of the given depth that were already reported
+    /// in the current pass.
+    fn retain_unreported(&mut self, divergences: &mut Vec<TrieDivergence>, subtrie_depth: usize) {
+        divergences.retain(|divergence| {
+            divergence.path.len() >= subtrie_depth ||
+                self.reported_ancestors.insert((divergence.trie, divergence.path.clone()))
+        });
+    }
+}
+
+/// Recomputes the account subtrie under the given prefix from the hashed accounts and compares it,
+/// together with the ancestors of the subtrie, to the stored account trie.
+///
+/// The storage roots of the accounts are taken from the stored storage tries, see
+/// [`verify_storage_trie`].
+pub fn verify_account_subtrie<T, H>(
+    trie_cursor_factory: T,
+    hashed_cursor_factory: H,
+    prefix: Nibbles,
+) -> Result<Vec<TrieDivergence>, StateRootError>
+where
+    T: TrieCursorFactory + Clone,
+    H: HashedCursorFactory + Clone,
+{
+    // Hide the stored subtrie, so it's recomputed from the hashed accounts, while the stored
+    // hashes of all other subtries are reused to compute its ancestors.
+    let mut account_prefix_set = PrefixSetMut::default();
+    account_prefix_set.insert(prefix.clone());
+    let (_, updates) = StateRoot::new(
+        SubtrieHidingCursorFactory {
+            inner: trie_cursor_factory.clone(),
+            trie: DivergentTrie::Account,
+            prefix: prefix.clone(),
+        },
+        hashed_cursor_factory,
+    )
+    .with_prefix_sets(TriePrefixSets {
+        account_prefix_set: account_prefix_set.freeze(),
+        ..Default::default()
+    })
+    .root_with_updates()?;
+
+    let expected = updates
+        .account_nodes
+        .into_iter()
+        .filter(|(path, _)| path.starts_with(&prefix) || prefix.starts_with(path))
+        .collect();
+
+    let stored =
+        stored_subtrie_with_ancestors(&mut trie_cursor_factory.account_trie_cursor()?, &prefix)?;
+
+    Ok(compare_nodes(DivergentTrie::Account, expected, stored))
+}
+
+/// Recomputes the storage subtrie of the given account under the given prefix from the hashed
+/// storage and compares it, together with the ancestors of the subtrie, to the stored storage
+/// trie.
+pub fn verify_storage_subtrie<T, H>(
+    trie_cursor_factory: T,
+    hashed_cursor_factory: H,
+    hashed_address: B256,
+    prefix: Nibbles,
+) -> Result<Vec<TrieDivergence>, StorageRootError>
+where
+    T: TrieCursorFactory,
+    H: HashedCursorFactory,
+{
+    // Same as for the account trie, only the stored subtrie is recomputed.
+    let mut prefix_set = PrefixSetMut::default();
+    prefix_set.insert(prefix.clone());
+    let (_, _, updates) = StorageRoot::new_hashed(
+        SubtrieHidingCursorFactory {
+            inner: &trie_cursor_factory,
+            trie: DivergentTrie::Storage(hashed_address),
+            prefix: prefix.clone(),
+        },
+        hashed_cursor_factory,
+        hashed_address,
+        prefix_set.freeze(),
+        #[cfg(feature = "metrics")]
+        TrieRootMetrics::new(TrieType::Storage),
+    )
+    .root_with_updates()?;
+
+    let expected = updates
+        .storage_nodes
+        .into_iter()
+        .filter(|(path, _)| path.starts_with(&prefix) || prefix.starts_with(path))
+        .collect();
+    let stored = stored_subtrie_with_ancestors(
+        &mut trie_cursor_factory.storage_trie_cursor(hashed_address)?,
+        &prefix,
+    )?;
+
+    Ok(compare_nodes(DivergentTrie::Storage(hashed_address), expected, stored))
+}
+
+/// Recomputes the storage trie of the given account from the hashed storage and compares it to the
+/// stored storage trie.
+pub fn verify_storage_trie<T, H>(
+    trie_cursor_factory: T,
+    hashed_cursor_factory: H,
+    hashed_address: B256,
+) -> Result<Vec<TrieDivergence>, StorageRootError>
+where
+    T: TrieCursorFactory,
+    H: HashedCursorFactory,
+{
+    let (_, _, updates) = StorageRoot::new_hashed(
+        NoopTrieCursorFactory::default(),
+        hashed_cursor_factory,
+        hashed_address,
+        PrefixSet::default(),
+        #[cfg(feature = "metrics")]
+        TrieRootMetrics::new(TrieType::Storage),
+    )
+    .root_with_updates()?;
+
+    let stored = stored_subtrie(
+        &mut trie_cursor_factory.storage_trie_cursor(hashed_address)?,
+        &Nibbles::default(),
+    )?;
+
+    Ok(compare_nodes(
+        DivergentTrie::Storage(hashed_address),
+        updates.storage_nodes.into_iter().collect(),
+        stored,
+    ))
+}
+
+/// Returns the prefix of the subtrie of the given depth with the given index.
+fn subtrie_prefix(index: usize, depth: usize) -> Nibbles {
+    Nibbles::from_nibbles_unchecked(
+        (0..depth).rev().map(|i| ((index >> (4 * i)) & 0xf) as u8).collect::<Vec<_>>(),
+    )
+}
+
+/// Returns the stored nodes under the given prefix.
+fn stored_subtrie(
+    cursor: &mut impl TrieCursor,
+    prefix: &Nibbles,
+) -> Result<BTreeMap<Nibbles, BranchNodeCompact>, DatabaseError> {
+    let mut nodes = BTreeMap::new();
+    let mut entry = cursor.seek(prefix.clone())?;
+    while let Some((path, node)) = entry {
+        if !path.starts_with(prefix) {
+            break
+        }
+        nodes.insert(path, node);
+        entry = cursor.next()?;
+    }
+    Ok(nodes)
+}
+
+/// Returns the stored nodes under the given prefix and the stored ancestors of the prefix.
+fn stored_subtrie_with_ancestors(
+    cursor: &mut impl TrieCursor,
+    prefix: &Nibbles,
+) -> Result<BTreeMap<Nibbles, BranchNodeCompact>, DatabaseError> {
+    let mut nodes = stored_subtrie(cursor, prefix)?;
+    for len in 1..prefix.len() {
+        if let Some((path, node)) = cursor.seek_exact(prefix.slice(..len))? {
+            nodes.insert(path, node);
+        }
+    }
+    Ok(nodes)
+}
+
+/// Returns the number of storage slots of the given account, counting at most `limit` slots.
+fn count_storage_slots(
+    hashed_cursor_factory: &impl HashedCursorFactory,
+    hashed_address: B256,
+    limit: usize,
+) -> Result<usize, DatabaseError> {
+    let mut cursor = hashed_cursor_factory.hashed_storage_cursor(hashed_address)?;
+    let mut slots = 0;
+    let mut entry = cursor.seek(B256::ZERO)?;
+    while entry.is_some() && slots < limit {
+        slots += 1;
+        entry = cursor.next()?;
+    }
+    Ok(slots)
+}
+
+fn compare_nodes(
+    trie: DivergentTrie,
+    expected: BTreeMap<Nibbles, BranchNodeCompact>,
+    mut stored: BTreeMap<Nibbles, BranchNodeCompact>,
+) -> Vec<TrieDivergence> {
+    let mut divergences = Vec::new();
+    for (path, expected) in expected {
+        let kind = match stored.remove(&path) {
+            None => TrieDivergenceKind::Missing { expected },
+            Some(stored) if stored != expected => TrieDivergenceKind::Mismatch { expected, stored },
+            Some(_) => continue,
+        };
+        divergences.push(TrieDivergence { trie, path, kind });
+    }
+    divergences.extend(stored.into_iter().map(|(path, stored)| TrieDivergence {
+        trie,
+        path,
+        kind: TrieDivergenceKind::Extra { stored },
+    }));
+    divergences.sort_by(|a, b| a.path.cmp(&b.path));
+    divergences
+}
+
+/// A trie cursor factory that hides the nodes under a prefix of the account trie or of a storage
+/// trie.
+#[derive(Debug, Clone)]
+struct SubtrieHidingCursorFactory<T> {
+    inner: T,
+    trie: DivergentTrie,
+    prefix: Nibbles,
+}
+
+impl<T: TrieCursorFactory> TrieCursorFactory for SubtrieHidingCursorFactory<T> {
+    type AccountTrieCursor = SubtrieHidingCursor<T::AccountTrieCursor>;
+    type StorageTrieCursor = SubtrieHidingCursor<T::StorageTrieCursor>;
+
+    fn account_trie_cursor(&self) -> Result<Self::AccountTrieCursor, DatabaseError> {
+        Ok(SubtrieHidingCursor {
+            inner: self.inner.account_trie_cursor()?,
+            prefix: (self.trie == DivergentTrie::Account).then(|| self.prefix.clone()),
+        })
+    }
+
+    fn storage_trie_cursor(
+        &self,
+        hashed_address: B256,
+    ) -> Result<Self::StorageTrieCursor, DatabaseError> {
+        Ok(SubtrieHidingCursor {
+            inner: self.inner.storage_trie_cursor(hashed_address)?,
+            prefix: (self.trie == DivergentTrie::Storage(hashed_address))
+                .then(|| self.prefix.clone()),
+        })
+    }
+}
+
+/// A trie cursor that skips the nodes under a prefix, if any.
+#[derive(Debug)]
+struct SubtrieHidingCursor<C> {
+    inner: C,
+    prefix: Option<Nibbles>,
+}
+
+impl<C: TrieCursor> SubtrieHidingCursor<C> {
+    fn is_hidden(&self, path: &Nibbles) -> bool {
+        self.prefix.as_ref().is_some_and(|prefix| path.starts_with(prefix))
+    }
+
+    fn skip_hidden(
+        &mut self,
+        entry: Option<(Nibbles, BranchNodeCompact)>,
+    ) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        match (entry, &self.prefix) {
+            (Some((path, _)), Some(prefix)) if path.starts_with(prefix) => {
+                match prefix.increment() {
+                    Some(next) => self.inner.seek(next),
+                    None => Ok(None),
+                }
+            }
+            (entry, _) => Ok(entry),
+        }
+    }
+}
+
+impl<C: TrieCursor> TrieCursor for SubtrieHidingCursor<C> {
+    fn seek_exact(
+        &mut self,
+        key: Nibbles,
+    ) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        if self.is_hidden(&key) {
+            return Ok(None)
+        }
+        self.inner.seek_exact(key)
+    }
+
+    fn seek(
+        &mut self,
+        key: Nibbles,
+    ) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        let entry = self.inner.seek(key)?;
+        self.skip_hidden(entry)
+    }
+
+    fn next(&mut self) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        let entry = self.inner.next()?;
+        self.skip_hidden(entry)
+    }
+
+    fn current(&mut self) -> Result<Option<Nibbles>, DatabaseError> {
+        self.inner.current()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        hashed_cursor::mock::MockHashedCursorFactory, trie_cursor::mock::MockTrieCursorFactory,
+    };
+    use alloy_primitives::{keccak256, map::B256Map, U256};
+    use reth_primitives_traits::Account;
+
+    /// Builds the hashed state and the matching stored trie of a few hundred accounts, every
+    /// tenth of them with storage.
+    fn state() -> (
+        MockHashedCursorFactory,
+        BTreeMap<Nibbles, BranchNodeCompact>,
+        B256Map<BTreeMap<Nibbles, BranchNodeCompact>>,
+    ) {
+        let mut accounts = BTreeMap::new();
+        let mut storages = B256Map::default();
+        for i in 0..512u64 {
+            let hashed_address = keccak256(i.to_be_bytes());
+            accounts.insert(hashed_address, Account { nonce: i, ..Default::default() });
+            let storage = if i % 10 == 0 {
+                (0..64u64)
+                    .map(|slot| (keccak256(slot.to_be_bytes()), U256::from(slot + 1)))
+                    .collect()
+            } else {
+                BTreeMap::new()
+            };
+            storages.insert(hashed_address, storage);
+        }
+        let hashed_cursor_factory = MockHashedCursorFactory::new(accounts, storages.clone());
+
+        let (_, updates) =
+            StateRoot::new(NoopTrieCursorFactory::default(), hashed_cursor_factory.clone())
+                .root_with_updates()
+                .unwrap();
+        let storage_tries = storages
+            .keys()
+            .map(|hashed_address| {
+                let nodes = updates
+                    .storage_tries
+                    .get(hashed_address)
+                    .map(|trie| trie.storage_nodes.clone().into_iter().collect())
+                    .unwrap_or_default();
+                (*hashed_address, nodes)
+            })
+            .collect();
+
+        (hashed_cursor_factory, updates.account_nodes.into_iter().collect(), storage_tries)
+    }
+
+    fn verify_pass(
+        mut verifier: TrieVerifier,
+        trie_cursor_factory: MockTrieCursorFactory,
+        hashed_cursor_factory: MockHashedCursorFactory,
+    ) -> Vec<TrieDivergence> {
+        let mut divergences = Vec::new();
+        loop {
+            let step = verifier
+                .verify_next(trie_cursor_factory.clone(), hashed_cursor_factory.clone())
+                .unwrap();
+            divergences.extend(step.divergences);
+            if step.pass_finished {
+                return divergences
+            }
+        }
+    }
+
+    #[test]
+    fn consistent_trie() {
+        let (hashed_cursor_factory, account_nodes, storage_tries) = state();
+        assert!(!account_nodes.is_empty());
+        let trie_cursor_factory = MockTrieCursorFactory::new(account_nodes, storage_tries);
+        assert_eq!(
+            verify_pass(
+                TrieVerifier::new(),
+                trie_cursor_factory.clone(),
+                hashed_cursor_factory.clone()
+            ),
+            Vec::new()
+        );
+
+        // every storage trie is verified in storage subtries
+        assert_eq!(
+            verify_pass(
+                TrieVerifier::new().with_max_storage_slots(32),
+                trie_cursor_factory,
+                hashed_cursor_factory
+            ),
+            Vec::new()
+        );
+    }
+
+    #[test]
+    fn bounded_storage_verification() {
+        let (hashed_cursor_factory, account_nodes, storage_tries) = state();
+        let trie_cursor_factory = MockTrieCursorFactory::new(account_nodes, storage_tries);
+
+        // the storage trie of the first account with storage
+        let hashed_address = keccak256(0u64.to_be_bytes());
+        let subtrie = Nibbles::unpack(hashed_address).slice(..VERIFIER_SUBTRIE_DEPTH);
+        let index = subtrie.iter().fold(0, |index, nibble| (index << 4) | *nibble as usize);
+
+        let mut verifier = TrieVerifier::new().with_max_storage_slots(32);
+        while verifier.next_subtrie() != index {
+            verifier
+                .verify_next(trie_cursor_factory.clone(), hashed_cursor_factory.clone())
+                .unwrap();
+        }
+
+        // the accounts with storage in the subtrie, whose storage tries are too large for a step
+        let large_storage_tries = (0..512u64)
+            .filter(|i| i % 10 == 0)
+            .filter(|i| Nibbles::unpack(keccak256(i.to_be_bytes())).starts_with(&subtrie))
+            .count();
+
+        // the account subtrie and the first storage subtrie are verified by the first step, and
+        // every other storage subtrie by its own step
+        let mut steps = 0;
+        loop {
+            let step = verifier
+                .verify_next(trie_cursor_factory.clone(), hashed_cursor_factory.clone())
+                .unwrap();
+            assert_eq!(step.prefix, subtrie);
+            steps += 1;
+            if step.subtrie_finished {
+                break
+            }
+        }
+        assert_eq!(steps, TrieVerifier::STORAGE_SUBTRIES * large_storage_tries);
+        assert_eq!(verifier.next_subtrie(), index + 1);
+    }
+
+    #[test]
+    fn detects_divergent_nodes() {
+        let (hashed_cursor_factory, mut account_nodes, mut storage_tries) = state();
+
+        // corrupt a hash of an account trie node
+        let (account_path, mut node) =
+            account_nodes.iter().next().map(|(path, node)| (path.clone(), node.clone())).unwrap();
+        node.hashes = node.hashes.iter().map(|_| B256::ZERO).collect::<Vec<_>>().into();
+        account_nodes.insert(account_path.clone(), node);
+
+        // drop a storage trie node
+        let (hashed_address, storage_path) = storage_tries
+            .iter()
+            .find_map(|(address, nodes)| Some((*address, nodes.keys().next()?.clone())))
+            .unwrap();
+        storage_tries.get_mut(&hashed_address).unwrap().remove(&storage_path);
+
+        let trie_cursor_factory = MockTrieCursorFactory::new(account_nodes, storage_tries);
+        for verifier in [TrieVerifier::new(), TrieVerifier::new().with_max_storage_slots(32)] {
+            let divergences =
+                verify_pass(verifier, trie_cursor_factory.clone(), hashed_cursor_factory.clone());
+
+            let find = |trie, path: &Nibbles| {
+                let mut found = divergences
+                    .iter()
+                    .filter(|divergence| divergence.trie == trie && divergence.path == *path);
+                let kind = found.next().map(|divergence| &divergence.kind);
+                // ancestors are only reported once
+                assert!(found.next().is_none());
+                kind
+            };
+            assert!(matches!(
+                find(DivergentTrie::Account, &account_path),
+                Some(TrieDivergenceKind::Mismatch { .. })
+            ));
+            assert!(matches!(
+                find(DivergentTrie::Storage(hashed_address), &storage_path),
+                Some(TrieDivergenceKind::Missing { .. })
+            ));
+        }
+    }
+}
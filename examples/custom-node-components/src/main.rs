@@ -94,7 +94,7 @@ where
                     chain_events,
                     ctx.task_executor().clone(),
                     reth_transaction_pool::maintain::MaintainPoolConfig {
-                        max_tx_lifetime: transaction_pool.config().max_queued_lifetime,
+                        max_tx_lifetime: transaction_pool.config().min_lifetime(),
                         ..Default::default()
                     },
                 ),
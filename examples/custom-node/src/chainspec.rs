@@ -84,6 +84,10 @@ impl EthChainSpec for CustomChainSpec {
     fn final_paris_total_difficulty(&self) -> Option<revm_primitives::U256> {
         self.inner.get_final_paris_total_difficulty()
     }
+
+    fn paris_block(&self) -> Option<u64> {
+        self.inner.paris_block()
+    }
 }
 
 impl EthereumHardforks for CustomChainSpec {
@@ -0,0 +1,193 @@
+//! Host of a WASM module implementing the notification ABI.
+
+use eyre::{eyre, WrapErr};
+use reth_tracing::tracing::info;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use wasmi::{Caller, Config, Engine, Extern, Linker, Memory, Module, Store, TypedFunc};
+
+/// The fuel available to a single call into the module, so that a module stuck in a loop can't
+/// stall the node.
+const FUEL_PER_CALL: u64 = 1_000_000_000;
+
+/// A loaded WASM module that is reloaded once its file changes.
+#[derive(Debug)]
+pub(crate) struct WasmHost {
+    /// The path of the module file.
+    path: PathBuf,
+    /// The modification time of the loaded module file.
+    modified: Option<SystemTime>,
+    /// The engine the modules are compiled with.
+    engine: Engine,
+    /// The instance of the loaded module.
+    instance: WasmInstance,
+}
+
+impl WasmHost {
+    /// Loads the module at the given path.
+    pub(crate) fn load(path: impl Into<PathBuf>) -> eyre::Result<Self> {
+        let path = path.into();
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+
+        let modified = modified(&path);
+        let instance = WasmInstance::load(&engine, &path)?;
+        Ok(Self { path, modified, engine, instance })
+    }
+
+    /// Returns the path of the module file.
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reloads the module if its file was modified since it was loaded, and returns whether it
+    /// was reloaded.
+    ///
+    /// If the new module fails to load, the loaded module is kept and the error is returned.
+    pub(crate) fn reload_if_modified(&mut self) -> eyre::Result<bool> {
+        let modified = modified(&self.path);
+        if modified.is_none() || modified == self.modified {
+            return Ok(false)
+        }
+
+        // the file is considered handled even if it's invalid, the next write reloads it again
+        self.modified = modified;
+        self.instance = WasmInstance::load(&self.engine, &self.path)?;
+        Ok(true)
+    }
+
+    /// Passes a committed block to the module.
+    pub(crate) fn on_block_committed(&mut self, payload: &[u8]) -> eyre::Result<()> {
+        let on_block_committed = self.instance.on_block_committed;
+        self.instance.call(on_block_committed, payload)
+    }
+
+    /// Passes a reverted block to the module.
+    pub(crate) fn on_block_reverted(&mut self, payload: &[u8]) -> eyre::Result<()> {
+        let on_block_reverted = self.instance.on_block_reverted;
+        self.instance.call(on_block_reverted, payload)
+    }
+}
+
+/// An instance of a module and its exports.
+#[derive(Debug)]
+struct WasmInstance {
+    store: Store<()>,
+    memory: Memory,
+    /// `alloc(len: u32) -> u32`
+    alloc: TypedFunc<u32, u32>,
+    /// `on_block_committed(ptr: u32, len: u32) -> u32`
+    on_block_committed: TypedFunc<(u32, u32), u32>,
+    /// `on_block_reverted(ptr: u32, len: u32) -> u32`
+    on_block_reverted: TypedFunc<(u32, u32), u32>,
+}
+
+impl WasmInstance {
+    /// Compiles and instantiates the module at the given path.
+    fn load(engine: &Engine, path: &Path) -> eyre::Result<Self> {
+        let wasm = fs::read(path)
+            .wrap_err_with(|| format!("failed to read WASM module {}", path.display()))?;
+        let module = Module::new(engine, &wasm)?;
+
+        let mut store = Store::new(engine, ());
+        let mut linker = Linker::new(engine);
+        linker.func_wrap("reth", "log", log)?;
+
+        store.set_fuel(FUEL_PER_CALL)?;
+        let instance = linker.instantiate(&mut store, &module)?.start(&mut store)?;
+        let memory =
+            instance.get_memory(&store, "memory").ok_or_else(|| eyre!("missing memory export"))?;
+        Ok(Self {
+            memory,
+            alloc: instance.get_typed_func(&store, "alloc")?,
+            on_block_committed: instance.get_typed_func(&store, "on_block_committed")?,
+            on_block_reverted: instance.get_typed_func(&store, "on_block_reverted")?,
+            store,
+        })
+    }
+
+    /// Copies the payload into a buffer allocated by the module, and calls the function with it.
+    fn call(&mut self, func: TypedFunc<(u32, u32), u32>, payload: &[u8]) -> eyre::Result<()> {
+        let len = u32::try_from(payload.len())?;
+
+        self.store.set_fuel(FUEL_PER_CALL)?;
+        let ptr = self.alloc.call(&mut self.store, len)?;
+        self.memory.write(&mut self.store, ptr as usize, payload)?;
+
+        match func.call(&mut self.store, (ptr, len))? {
+            0 => Ok(()),
+            code => Err(eyre!("module returned error code {code}")),
+        }
+    }
+}
+
+/// The `reth.log(ptr: u32, len: u32)` import, which logs a UTF-8 message of the module.
+fn log(caller: Caller<'_, ()>, ptr: u32, len: u32) {
+    let Some(memory) = caller.get_export("memory").and_then(Extern::into_memory) else { return };
+
+    let mut message = vec![0; len as usize];
+    if memory.read(&caller, ptr as usize, &mut message).is_ok() {
+        info!(target: "exex::wasm", message = %String::from_utf8_lossy(&message), "Module log");
+    }
+}
+
+/// Returns the modification time of the file, if it exists.
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs::File, time::Duration};
+
+    /// Returns a module whose `on_block_reverted` returns the given code.
+    fn module(reverted_code: u32) -> Vec<u8> {
+        wat::parse_str(format!(
+            r#"
+            (module
+                (import "reth" "log" (func $log (param i32 i32)))
+                (memory (export "memory") 1)
+                (func (export "alloc") (param i32) (result i32) (i32.const 1024))
+                (func (export "on_block_committed") (param i32 i32) (result i32)
+                    (call $log (local.get 0) (local.get 1))
+                    (i32.const 0))
+                (func (export "on_block_reverted") (param i32 i32) (result i32)
+                    (i32.const {reverted_code})))
+            "#
+        ))
+        .unwrap()
+    }
+
+    /// Writes the module and moves its modification time forward.
+    fn write_module(path: &Path, wasm: &[u8], modified: SystemTime) {
+        fs::write(path, wasm).unwrap();
+        File::options().write(true).open(path).unwrap().set_modified(modified).unwrap();
+    }
+
+    #[test]
+    fn hot_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("module.wasm");
+        let now = SystemTime::now();
+        write_module(&path, &module(1), now);
+
+        let mut host = WasmHost::load(&path).unwrap();
+        host.on_block_committed(br#"{"number":1}"#).unwrap();
+        assert!(host.on_block_reverted(br#"{"number":1}"#).is_err());
+        assert!(!host.reload_if_modified().unwrap());
+
+        write_module(&path, &module(0), now + Duration::from_secs(1));
+        assert!(host.reload_if_modified().unwrap());
+        host.on_block_reverted(br#"{"number":1}"#).unwrap();
+
+        // an invalid module is rejected and the loaded one is kept
+        write_module(&path, b"invalid", now + Duration::from_secs(2));
+        assert!(host.reload_if_modified().is_err());
+        host.on_block_reverted(br#"{"number":1}"#).unwrap();
+    }
+}
@@ -0,0 +1,170 @@
+//! Example of an Execution Extension that runs user logic compiled to WASM.
+//!
+//! The ExEx loads a WASM module and passes every committed and reverted block to it. The module is
+//! reloaded as soon as its file changes, so the logic can be updated without restarting the node.
+//! The state of the module instance is dropped on reload.
+//!
+//! A module implements the following ABI:
+//!
+//! - `memory`: the exported memory of the module.
+//! - `alloc(len: u32) -> u32`: returns a buffer of the given length that the host writes the
+//!   payload to. The buffer is owned by the module after the call.
+//! - `on_block_committed(ptr: u32, len: u32) -> u32`: called for every committed block, in
+//!   ascending order.
+//! - `on_block_reverted(ptr: u32, len: u32) -> u32`: called for every reverted block, in descending
+//!   order, before the blocks of the new chain are committed.
+//!
+//! The payload is a JSON object with the `number`, `hash`, `parentHash` and `timestamp` of the
+//! block, and the `logs` of its transactions. The callbacks return `0` on success, any other value
+//! is logged as an error. The module may import `reth.log(ptr: u32, len: u32)` to log a UTF-8
+//! message.
+//!
+//! Run with
+//!
+//! ```sh
+//! cargo run -p example-exex-wasm -- node --dev --dev.block-time 5s --wasm.module <PATH>
+//! ```
+
+#![warn(unused_crate_dependencies)]
+
+mod host;
+
+use alloy_consensus::{BlockHeader, TxReceipt};
+use alloy_primitives::{Log, B256};
+use clap::Parser;
+use futures::TryStreamExt;
+use host::WasmHost;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use reth::{chainspec::EthereumChainSpecParser, cli::Cli};
+use reth_ethereum::{
+    exex::{ExExContext, ExExEvent},
+    node::{
+        api::{FullNodeComponents, NodeTypes},
+        EthereumNode,
+    },
+    primitives::RecoveredBlock,
+    Block, EthPrimitives, Receipt,
+};
+use reth_tracing::tracing::{error, info};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
+
+/// Arguments of the WASM ExEx.
+#[derive(Debug, Clone, clap::Args)]
+struct WasmArgs {
+    /// Path of the WASM module, the module is reloaded when the file changes.
+    #[arg(long = "wasm.module", value_name = "PATH")]
+    module: PathBuf,
+}
+
+/// The payload of a block passed to the module.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockPayload<'a> {
+    number: u64,
+    hash: B256,
+    parent_hash: B256,
+    timestamp: u64,
+    logs: Vec<&'a Log>,
+}
+
+impl<'a> BlockPayload<'a> {
+    /// Returns the JSON payload of the block with the given receipts.
+    fn encode(block: &RecoveredBlock<Block>, receipts: &'a [Receipt]) -> Vec<u8> {
+        let payload = BlockPayload {
+            number: block.number(),
+            hash: block.hash(),
+            parent_hash: block.parent_hash(),
+            timestamp: block.timestamp(),
+            logs: receipts.iter().flat_map(|receipt| receipt.logs()).collect(),
+        };
+        serde_json::to_vec(&payload).expect("payload is serializable")
+    }
+}
+
+/// Returns a watcher that sends a message every time the given file may have changed.
+///
+/// The parent directory is watched, so that the file is also picked up if it's replaced instead
+/// of modified in place.
+fn watch_module(
+    path: &Path,
+    changes: mpsc::UnboundedSender<()>,
+) -> eyre::Result<RecommendedWatcher> {
+    let file_name = path.file_name().map(ToOwned::to_owned);
+    let mut watcher = RecommendedWatcher::new(
+        move |res: notify::Result<notify::Event>| {
+            if res.is_ok_and(|event| {
+                event.paths.iter().any(|path| path.file_name() == file_name.as_deref())
+            }) {
+                let _ = changes.send(());
+            }
+        },
+        notify::Config::default(),
+    )?;
+
+    let directory = path.parent().filter(|parent| !parent.as_os_str().is_empty());
+    watcher.watch(directory.unwrap_or(Path::new(".")), RecursiveMode::NonRecursive)?;
+    Ok(watcher)
+}
+
+async fn wasm_exex<Node: FullNodeComponents<Types: NodeTypes<Primitives = EthPrimitives>>>(
+    mut ctx: ExExContext<Node>,
+    mut host: WasmHost,
+) -> eyre::Result<()> {
+    let (changes_tx, mut changes) = mpsc::unbounded_channel();
+    let _watcher = watch_module(host.path(), changes_tx)?;
+
+    loop {
+        tokio::select! {
+            notification = ctx.notifications.try_next() => {
+                let Some(notification) = notification? else { break };
+
+                if let Some(reverted_chain) = notification.reverted_chain() {
+                    let blocks = reverted_chain.blocks_and_receipts().collect::<Vec<_>>();
+                    for (block, receipts) in blocks.into_iter().rev() {
+                        let payload = BlockPayload::encode(block, receipts);
+                        if let Err(err) = host.on_block_reverted(&payload) {
+                            error!(block = block.number(), %err, "Module failed to revert block");
+                        }
+                    }
+                }
+
+                if let Some(committed_chain) = notification.committed_chain() {
+                    for (block, receipts) in committed_chain.blocks_and_receipts() {
+                        let payload = BlockPayload::encode(block, receipts);
+                        if let Err(err) = host.on_block_committed(&payload) {
+                            error!(block = block.number(), %err, "Module failed to commit block");
+                        }
+                    }
+                    let tip = committed_chain.tip().num_hash();
+                    ctx.events.send(ExExEvent::FinishedHeight(tip))?;
+                }
+            }
+            Some(()) = changes.recv() => {
+                match host.reload_if_modified() {
+                    Ok(true) => info!(path = %host.path().display(), "Reloaded WASM module"),
+                    Ok(false) => {}
+                    Err(err) => error!(%err, "Failed to reload WASM module, keeping the old one"),
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> eyre::Result<()> {
+    Cli::<EthereumChainSpecParser, WasmArgs>::parse().run(async move |builder, args| {
+        let host = WasmHost::load(&args.module)?;
+        info!(path = %args.module.display(), "Loaded WASM module");
+
+        let handle = builder
+            .node(EthereumNode::default())
+            .install_exex("wasm", async move |ctx| Ok(wasm_exex(ctx, host)))
+            .launch()
+            .await?;
+
+        handle.wait_for_node_exit().await
+    })
+}
@@ -0,0 +1,233 @@
+//! Template for an `op-reth` Execution Extension that validates interop messages.
+//!
+//! Executing messages are emitted as logs by the `CrossL2Inbox` predeploy. For every executing
+//! message in a committed block, the ExEx checks that its initiating message is from a chain of
+//! the configured dependency set, and asks the `op-supervisor` whether the initiating message
+//! exists with the required safety level. Invalid messages are logged.
+//!
+//! Run with
+//!
+//! ```sh
+//! cargo run -p example-op-interop-exex -- node --chain <CHAIN> \
+//!     --interop.dependency-set 901,902 \
+//!     --interop.supervisor http://localhost:8545
+//! ```
+
+#![warn(unused_crate_dependencies)]
+
+mod supervisor;
+
+use alloy_consensus::{BlockHeader, TxReceipt};
+use alloy_primitives::{address, Address, Log};
+use alloy_sol_types::{sol, SolEvent};
+use clap::Parser;
+use futures::TryStreamExt;
+use reth_exex::{ExExContext, ExExEvent};
+use reth_node_api::{FullNodeComponents, NodeTypes};
+use reth_optimism_cli::{chainspec::OpChainSpecParser, Cli};
+use reth_optimism_node::{args::RollupArgs, OpNode};
+use reth_optimism_primitives::OpPrimitives;
+use reth_tracing::tracing::{info, warn};
+use std::collections::HashSet;
+use supervisor::{Identifier, Message, SafetyLevel, SupervisorClient};
+
+/// Address of the `CrossL2Inbox` predeploy.
+const CROSS_L2_INBOX: Address = address!("0x4200000000000000000000000000000000000022");
+
+sol! {
+    /// Identifier of an initiating message, as emitted by the `CrossL2Inbox`.
+    struct MessageIdentifier {
+        address origin;
+        uint256 blockNumber;
+        uint256 logIndex;
+        uint256 timestamp;
+        uint256 chainId;
+    }
+
+    /// Emitted by the `CrossL2Inbox` for every executing message.
+    event ExecutingMessage(bytes32 indexed msgHash, MessageIdentifier id);
+}
+
+/// Arguments of the interop validation.
+#[derive(Debug, Clone, clap::Args)]
+struct InteropArgs {
+    #[command(flatten)]
+    rollup: RollupArgs,
+
+    /// Chain IDs of the dependency set, initiating messages from other chains are invalid.
+    #[arg(long = "interop.dependency-set", value_name = "CHAIN_IDS", value_delimiter = ',')]
+    dependency_set: Vec<u64>,
+
+    /// HTTP endpoint of the `op-supervisor` RPC.
+    #[arg(
+        long = "interop.supervisor",
+        value_name = "HTTP_URL",
+        default_value = "http://localhost:8545"
+    )]
+    supervisor: String,
+
+    /// Minimum safety level of initiating messages.
+    #[arg(long = "interop.min-safety", value_enum, default_value_t = SafetyLevel::CrossUnsafe)]
+    min_safety: SafetyLevel,
+}
+
+/// Validates the executing messages of blocks.
+#[derive(Debug)]
+struct InteropValidator {
+    /// Chain IDs of the dependency set.
+    dependency_set: HashSet<u64>,
+    /// Client of the `op-supervisor` RPC.
+    supervisor: SupervisorClient,
+    /// Minimum safety level of initiating messages.
+    min_safety: SafetyLevel,
+}
+
+impl InteropValidator {
+    /// Validates the executing messages of the block with the given receipts.
+    async fn validate_block<R: TxReceipt<Log = Log>>(
+        &self,
+        block_number: u64,
+        timestamp: u64,
+        receipts: &[R],
+    ) {
+        let mut messages = Vec::new();
+        for (log_index, message) in executing_messages(receipts) {
+            let chain_id = message.identifier.chain_id;
+            if !u64::try_from(chain_id).is_ok_and(|id| self.dependency_set.contains(&id)) {
+                warn!(block_number, log_index, %chain_id, "Executing message from a chain outside of the dependency set");
+                continue
+            }
+            if message.identifier.timestamp > timestamp {
+                warn!(
+                    block_number,
+                    log_index,
+                    ?message,
+                    "Executing message references a future initiating message"
+                );
+                continue
+            }
+            messages.push(message);
+        }
+
+        if messages.is_empty() {
+            return
+        }
+
+        match self.supervisor.check_messages(&messages, self.min_safety).await {
+            Ok(()) => {
+                info!(block_number, count = messages.len(), "Validated executing messages")
+            }
+            Err(err) => {
+                warn!(block_number, %err, ?messages, "Supervisor rejected executing messages")
+            }
+        }
+    }
+}
+
+/// Returns the executing messages in the given receipts of a block, with their log index.
+fn executing_messages<R: TxReceipt<Log = Log>>(
+    receipts: &[R],
+) -> impl Iterator<Item = (usize, Message)> + '_ {
+    receipts.iter().flat_map(|receipt| receipt.logs()).enumerate().filter_map(|(index, log)| {
+        if log.address != CROSS_L2_INBOX {
+            return None
+        }
+        let event = ExecutingMessage::decode_log_data(&log.data, true).ok()?;
+        let identifier = Identifier {
+            origin: event.id.origin,
+            block_number: event.id.blockNumber.saturating_to(),
+            log_index: event.id.logIndex.saturating_to(),
+            timestamp: event.id.timestamp.saturating_to(),
+            chain_id: event.id.chainId,
+        };
+        Some((index, Message { identifier, payload_hash: event.msgHash }))
+    })
+}
+
+async fn interop_exex<Node>(
+    mut ctx: ExExContext<Node>,
+    validator: InteropValidator,
+) -> eyre::Result<()>
+where
+    Node: FullNodeComponents<Types: NodeTypes<Primitives = OpPrimitives>>,
+{
+    while let Some(notification) = ctx.notifications.try_next().await? {
+        if let Some(committed_chain) = notification.committed_chain() {
+            for (block, receipts) in committed_chain.blocks_and_receipts() {
+                validator.validate_block(block.number(), block.timestamp(), receipts).await;
+            }
+            ctx.events.send(ExExEvent::FinishedHeight(committed_chain.tip().num_hash()))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> eyre::Result<()> {
+    Cli::<OpChainSpecParser, InteropArgs>::parse().run(async move |builder, args| {
+        let validator = InteropValidator {
+            dependency_set: args.dependency_set.into_iter().collect(),
+            supervisor: SupervisorClient::new(&args.supervisor)?,
+            min_safety: args.min_safety,
+        };
+
+        let handle = builder
+            .node(OpNode::new(args.rollup))
+            .install_exex("interop", async move |ctx| Ok(interop_exex(ctx, validator)))
+            .launch()
+            .await?;
+
+        handle.wait_for_node_exit().await
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{b256, U256};
+    use reth_optimism_primitives::OpReceipt;
+
+    #[test]
+    fn decodes_executing_messages() {
+        let id = MessageIdentifier {
+            origin: Address::with_last_byte(1),
+            blockNumber: U256::from(10),
+            logIndex: U256::from(2),
+            timestamp: U256::from(100),
+            chainId: U256::from(901),
+        };
+        let msg_hash = b256!("0x0000000000000000000000000000000000000000000000000000000000000001");
+        let event = ExecutingMessage { msgHash: msg_hash, id };
+
+        let other_log = Log { address: Address::with_last_byte(2), data: event.encode_log_data() };
+        let inbox_log = Log { address: CROSS_L2_INBOX, data: event.encode_log_data() };
+        let receipts = vec![
+            OpReceipt::Eip1559(alloy_consensus::Receipt {
+                logs: vec![other_log],
+                ..Default::default()
+            }),
+            OpReceipt::Eip1559(alloy_consensus::Receipt {
+                logs: vec![inbox_log],
+                ..Default::default()
+            }),
+        ];
+
+        let messages = executing_messages(&receipts).collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![(
+                1,
+                Message {
+                    identifier: Identifier {
+                        origin: Address::with_last_byte(1),
+                        block_number: 10,
+                        log_index: 2,
+                        timestamp: 100,
+                        chain_id: U256::from(901),
+                    },
+                    payload_hash: msg_hash,
+                }
+            )]
+        );
+    }
+}
@@ -0,0 +1,80 @@
+//! Minimal client for the `supervisor` RPC namespace of `op-supervisor`.
+
+use alloy_primitives::{Address, B256, U256};
+use jsonrpsee::{
+    core::{client::ClientT, ClientError},
+    http_client::{HttpClient, HttpClientBuilder},
+    rpc_params,
+};
+use serde::{Deserialize, Serialize};
+
+/// Identifier of an initiating message on a chain of the dependency set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Identifier {
+    /// Account that emitted the initiating message.
+    pub(crate) origin: Address,
+    /// Block the initiating message was emitted in.
+    #[serde(with = "alloy_serde::quantity")]
+    pub(crate) block_number: u64,
+    /// Index of the initiating message in the logs of its block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub(crate) log_index: u64,
+    /// Timestamp of the block the initiating message was emitted in.
+    #[serde(with = "alloy_serde::quantity")]
+    pub(crate) timestamp: u64,
+    /// Chain the initiating message was emitted on.
+    #[serde(rename = "chainID")]
+    pub(crate) chain_id: U256,
+}
+
+/// An executing message, referencing its initiating message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Message {
+    /// Identifier of the initiating message.
+    pub(crate) identifier: Identifier,
+    /// Hash of the payload of the initiating message.
+    pub(crate) payload_hash: B256,
+}
+
+/// Minimum safety level of the initiating messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum SafetyLevel {
+    /// The initiating message is finalized.
+    Finalized,
+    /// The initiating message is safe.
+    Safe,
+    /// The initiating message is safe on its own chain.
+    LocalSafe,
+    /// The initiating message is unsafe, but its dependencies are valid.
+    CrossUnsafe,
+    /// The initiating message is unsafe.
+    Unsafe,
+}
+
+/// Client of the `op-supervisor` RPC.
+#[derive(Debug, Clone)]
+pub(crate) struct SupervisorClient {
+    client: HttpClient,
+}
+
+impl SupervisorClient {
+    /// Creates a new client connected to the given `op-supervisor` HTTP endpoint.
+    pub(crate) fn new(url: &str) -> eyre::Result<Self> {
+        Ok(Self { client: HttpClientBuilder::default().build(url)? })
+    }
+
+    /// Checks that the initiating messages of the given executing messages exist and have at least
+    /// the given safety level.
+    ///
+    /// Returns an error if any of the messages is invalid.
+    pub(crate) async fn check_messages(
+        &self,
+        messages: &[Message],
+        min_safety: SafetyLevel,
+    ) -> Result<(), ClientError> {
+        self.client.request("supervisor_checkMessages", rpc_params![messages, min_safety]).await
+    }
+}
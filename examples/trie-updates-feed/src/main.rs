@@ -0,0 +1,272 @@
+//! Example of streaming the hashed state and trie updates of every canonical block to subscribers
+//! over TCP.
+//!
+//! Downstream stateless verifiers or replica nodes can apply the changes to their copy of the state
+//! and the trie, without executing the blocks or recomputing the trie. Each [`FeedMessage`] is sent
+//! as a frame: the length of the bincode encoded message as big-endian `u32`, followed by the
+//! message. The encoding is canonical, equal messages are always encoded to equal bytes.
+//!
+//! Reorgs are announced with a [`FeedMessage::Reorg`], after which subscribers must revert the
+//! changes of the reverted blocks before applying the blocks of the new chain. Subscribers that
+//! can't keep up with the feed are disconnected, since a replica can't skip blocks.
+//!
+//! Run with
+//!
+//! ```sh
+//! cargo run -p example-trie-updates-feed -- node --trie-feed.addr 127.0.0.1:9988
+//! ```
+
+#![warn(unused_crate_dependencies)]
+
+use alloy_primitives::{BlockNumber, B256};
+use clap::Parser;
+use futures::StreamExt;
+use reth::{
+    chainspec::EthereumChainSpecParser,
+    cli::Cli,
+    primitives::{Block, EthPrimitives, RecoveredBlock},
+    providers::{
+        providers::{BlockchainProvider, ProviderNodeTypes},
+        CanonStateNotification, CanonStateSubscriptions, Chain,
+    },
+};
+use reth_node_ethereum::EthereumNode;
+use reth_tracing::tracing::{debug, info, warn};
+use reth_trie_common::{serde_bincode_compat, updates::TrieUpdates, HashedPostState};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use std::{net::SocketAddr, sync::Arc};
+use tokio::{
+    io::AsyncWriteExt,
+    net::TcpListener,
+    sync::broadcast::{self, error::RecvError},
+};
+
+/// Number of frames buffered for each subscriber before it is disconnected.
+const FEED_CAPACITY: usize = 64;
+
+/// Arguments of the trie updates feed.
+#[derive(Debug, Clone, Copy, clap::Args)]
+struct TrieFeedArgs {
+    /// Address to accept feed subscribers on.
+    #[arg(long = "trie-feed.addr", default_value = "127.0.0.1:9988")]
+    addr: SocketAddr,
+}
+
+/// Changes of the hashed state and the trie of a canonical block.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct BlockTrieDiff {
+    /// Number of the block.
+    number: BlockNumber,
+    /// Hash of the block.
+    hash: B256,
+    /// Hash of the parent block.
+    parent_hash: B256,
+    /// State root after applying the block.
+    state_root: B256,
+    /// Hashed accounts and storage slots changed by the block.
+    #[serde_as(as = "serde_bincode_compat::hashed_state::HashedPostState")]
+    hashed_state: HashedPostState,
+    /// Trie nodes changed by the block.
+    #[serde_as(as = "serde_bincode_compat::updates::TrieUpdates")]
+    trie_updates: TrieUpdates,
+}
+
+impl BlockTrieDiff {
+    /// Creates the diff of the given block.
+    fn new(
+        block: &RecoveredBlock<Block>,
+        hashed_state: &HashedPostState,
+        trie_updates: &TrieUpdates,
+    ) -> Self {
+        Self {
+            number: block.header().number,
+            hash: block.hash(),
+            parent_hash: block.header().parent_hash,
+            state_root: block.header().state_root,
+            hashed_state: hashed_state.clone(),
+            trie_updates: trie_updates.clone(),
+        }
+    }
+}
+
+/// A message of the feed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+enum FeedMessage {
+    /// A block was appended to the canonical chain.
+    Block(BlockTrieDiff),
+    /// The canonical chain was reorged or reverted to the given block.
+    ///
+    /// The changes of the reverted blocks must be reverted, the blocks of the new chain follow as
+    /// [`FeedMessage::Block`]s.
+    Reorg {
+        /// Number of the last block that remains canonical.
+        number: BlockNumber,
+        /// Hash of the last block that remains canonical.
+        hash: B256,
+        /// Hashes of the reverted blocks, in ascending order.
+        reverted: Vec<B256>,
+    },
+}
+
+impl FeedMessage {
+    /// Encodes the message into a length-prefixed frame.
+    fn encode_frame(&self) -> bincode::Result<Vec<u8>> {
+        let payload = bincode::serialize(self)?;
+        let mut frame = Vec::with_capacity(4 + payload.len());
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(&payload);
+        Ok(frame)
+    }
+}
+
+/// Broadcasts the reorgs and the diffs of all new canonical blocks to the subscribers.
+///
+/// The diffs are read from the in-memory state of the canonical chain, which holds the hashed
+/// state and trie updates of the blocks that aren't persisted yet.
+async fn publish_diffs<N: ProviderNodeTypes<Primitives = EthPrimitives>>(
+    provider: BlockchainProvider<N>,
+    frames: broadcast::Sender<Arc<Vec<u8>>>,
+) {
+    let mut notifications = provider.canonical_state_stream();
+    let in_memory_state = provider.canonical_in_memory_state();
+    while let Some(notification) = notifications.next().await {
+        if frames.receiver_count() == 0 {
+            continue
+        }
+
+        let mut messages = Vec::new();
+        let new = match notification {
+            CanonStateNotification::Commit { new } => new,
+            CanonStateNotification::Reorg { old, new } => {
+                messages.push(reorg_message(&old));
+                new
+            }
+        };
+        for block in new.blocks_iter() {
+            let Some(state) = in_memory_state.state_by_hash(block.hash()) else {
+                warn!(hash = %block.hash(), "Trie diff of canonical block is unavailable");
+                continue
+            };
+            let executed = state.block();
+            messages.push(FeedMessage::Block(BlockTrieDiff::new(
+                block,
+                executed.hashed_state(),
+                executed.trie_updates(),
+            )));
+        }
+
+        for message in messages {
+            match message.encode_frame() {
+                Ok(frame) => {
+                    debug!(size = frame.len(), "Publishing feed message");
+                    let _ = frames.send(Arc::new(frame));
+                }
+                Err(err) => warn!(%err, "Failed to encode feed message"),
+            }
+        }
+    }
+}
+
+/// Returns the message announcing the revert of the given chain.
+fn reorg_message(reverted: &Chain) -> FeedMessage {
+    let fork_block = reverted.fork_block();
+    FeedMessage::Reorg {
+        number: fork_block.number,
+        hash: fork_block.hash,
+        reverted: reverted.blocks_iter().map(|block| block.hash()).collect(),
+    }
+}
+
+/// Accepts subscribers and streams the broadcast frames to them.
+async fn accept_subscribers(listener: TcpListener, frames: broadcast::Sender<Arc<Vec<u8>>>) {
+    loop {
+        let (mut stream, peer) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(err) => {
+                warn!(%err, "Failed to accept feed subscriber");
+                continue
+            }
+        };
+        info!(%peer, "Feed subscriber connected");
+
+        let mut rx = frames.subscribe();
+        tokio::spawn(async move {
+            loop {
+                match rx.recv().await {
+                    Ok(frame) => {
+                        if let Err(err) = stream.write_all(&frame).await {
+                            info!(%peer, %err, "Feed subscriber disconnected");
+                            break
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(%peer, skipped, "Feed subscriber lagged behind, disconnecting");
+                        break
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+}
+
+fn main() -> eyre::Result<()> {
+    Cli::<EthereumChainSpecParser, TrieFeedArgs>::parse().run(|builder, args| async move {
+        let handle = builder.node(EthereumNode::default()).launch().await?;
+
+        let listener = TcpListener::bind(args.addr).await?;
+        info!(addr = %args.addr, "Trie updates feed started");
+
+        let (frames, _) = broadcast::channel(FEED_CAPACITY);
+        handle.node.task_executor.spawn(accept_subscribers(listener, frames.clone()));
+        handle.node.task_executor.spawn(publish_diffs(handle.node.provider.clone(), frames));
+
+        handle.wait_for_node_exit().await
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+    use reth_trie_common::{
+        updates::StorageTrieUpdates, BranchNodeCompact, HashedStorage, Nibbles,
+    };
+
+    #[test]
+    fn frame_roundtrip() {
+        let mut diff = BlockTrieDiff {
+            number: 1,
+            hash: B256::with_last_byte(1),
+            parent_hash: B256::with_last_byte(2),
+            state_root: B256::with_last_byte(3),
+            hashed_state: HashedPostState::default(),
+            trie_updates: TrieUpdates::default(),
+        };
+        diff.hashed_state.accounts.insert(B256::with_last_byte(4), None);
+        diff.hashed_state.storages.insert(
+            B256::with_last_byte(5),
+            HashedStorage::from_iter(false, [(B256::with_last_byte(6), U256::from(7))]),
+        );
+        diff.trie_updates
+            .account_nodes
+            .insert(Nibbles::from_nibbles([0x0a, 0x0b]), BranchNodeCompact::default());
+        diff.trie_updates
+            .storage_tries
+            .insert(B256::with_last_byte(5), StorageTrieUpdates::deleted());
+
+        let reorg = FeedMessage::Reorg {
+            number: 1,
+            hash: B256::with_last_byte(1),
+            reverted: vec![B256::with_last_byte(8), B256::with_last_byte(9)],
+        };
+        for message in [FeedMessage::Block(diff), reorg] {
+            let frame = message.encode_frame().unwrap();
+            let (len, payload) = frame.split_at(4);
+            assert_eq!(u32::from_be_bytes(len.try_into().unwrap()) as usize, payload.len());
+            assert_eq!(bincode::deserialize::<FeedMessage>(payload).unwrap(), message);
+        }
+    }
+}